@@ -35,6 +35,15 @@ fi
 
 chmod +x "$ROOT_DIR/src-tauri/wowid3-launcher.sh"
 
+# Self-updates and manifests are only verified when the server's public keys are built in
+# (print them with `wowid3-modpack-server generate-signing-key`)
+if [[ -z "${WOWID3_LAUNCHER_PUBLIC_KEY:-}" ]]; then
+  echo "Warning: WOWID3_LAUNCHER_PUBLIC_KEY not set; this build will install unsigned launcher updates." >&2
+fi
+if [[ -z "${WOWID3_MANIFEST_PUBLIC_KEY:-}" ]]; then
+  echo "Warning: WOWID3_MANIFEST_PUBLIC_KEY not set; this build will accept unsigned modpack manifests." >&2
+fi
+
 # Check if .env.wayland exists and source it for Wayland compatibility
 if [[ -f "$ROOT_DIR/.env.wayland" ]]; then
   echo "Sourcing .env.wayland for Wayland compatibility..."
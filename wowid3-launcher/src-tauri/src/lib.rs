@@ -1,63 +1,113 @@
 mod modules;
 
-use modules::auth::{authenticate_from_official_launcher, get_current_user, logout, refresh_token, get_device_code, complete_device_code_auth, MinecraftProfile, DeviceCodeInfo};
+use modules::auth::{authenticate_from_official_launcher, get_current_user, logout, get_device_code, complete_device_code_auth, demo_accounts_enabled, sign_in_demo, MinecraftProfile, DeviceCodeInfo};
 use modules::avatar_proxy::{fetch_avatar, AvatarData, is_avatar_cached, read_cached_avatar, write_cached_avatar, clear_avatar_cache};
+use modules::skin::{get_player_skin, PlayerSkin};
 use modules::discord::{DiscordClient, GamePresence};
-use modules::minecraft::{launch_game, launch_game_with_metadata, analyze_crash, LaunchConfig, stop_game, kill_game, is_game_running};
+use modules::discord_presence::PresenceService;
+use modules::minecraft::{launch_game, launch_game_with_metadata, analyze_crash, LaunchConfig, stop_game, kill_game, is_game_running, DEFAULT_STOP_GRACE_SECS};
 use modules::minecraft_version::{list_versions, get_latest_release, get_latest_snapshot, VersionInfo};
 use modules::fabric_installer::{get_fabric_loaders, get_latest_fabric_loader, FabricLoader};
 use modules::game_installer::{install_minecraft, is_version_installed, InstallConfig};
-use modules::server::{ping_server, ping_server_with_vpn, resolve_player_name, fetch_tracker_status, ServerStatus, TrackerState};
+use modules::mod_loader::{LoaderVersion, ModLoader};
+use modules::server::{ping_server, ping_server_with_vpn, resolve_player_name, fetch_tracker_status, send_chat_message, ServerStatus, TrackerState};
 use modules::minecraft::verify_server_reachable;
-use modules::stats::{get_player_stats, PlayerStats};
-use modules::updater::{check_for_updates, get_installed_version, install_modpack, verify_and_repair_modpack, has_manifest_changed, update_version_file, Manifest};
-use modules::audio::{get_cached_audio, download_and_cache_audio, read_cached_audio_bytes, clear_audio_cache};
+use modules::stats::{
+    get_player_advancements, get_player_sessions, get_player_stats, get_stat_leaderboard, Leaderboard,
+    PlayerAdvancements, PlayerSessions, PlayerStats,
+};
+use modules::instance_export::{export_instance, ExportSummary, InstanceFormat};
+use modules::instance_import::{import_installation, ImportReport};
+use modules::updater::{check_for_updates, get_installed_version, install_modpack, rollback_modpack, verify_and_repair_modpack, verify_installation, has_manifest_changed, update_version_file, Manifest, VerificationReport};
+use modules::audio::{get_cached_audio, download_and_cache_audio, cancel_audio_downloads, read_cached_audio_bytes, clear_audio_cache, load_playlist, next_track, previous_track, set_shuffle, AudioTrack};
 use modules::java_runtime::{get_cached_java, download_and_cache_java};
+use modules::jvm_settings::{delete_profile, list_profiles, load_profile, save_profile, JvmProfile, JvmSettings};
+use modules::java_detection::{detect_java_installations, probe_java, JavaInstallation};
 use modules::logger::initialize_logger;
-use modules::log_reader::{read_latest_log, get_log_path, get_new_log_lines, read_log_tail, read_log_from_offset, read_log_before_offset, LogResult};
+use modules::log_reader::{read_latest_log, get_log_path, read_log_tail, read_log_from_offset, read_log_before_offset, LogResult, LogWatcher};
 use modules::paths::{get_default_game_directory, resolve_game_directory, validate_game_directory};
-use modules::launcher_updater::{check_launcher_update, install_launcher_update, LauncherUpdateInfo};
+use modules::launcher_updater::{check_launcher_update, ensure_no_mandatory_update, install_launcher_update, LauncherUpdateInfo};
 use modules::map_viewer::{check_bluemap_available, open_map_viewer, close_map_viewer, get_bluemap_url, BlueMapStatus};
+use modules::overlay::{OverlayWriter, OverlaySettings, OverlayState};
+use modules::settings_bundle::{build_bundle, export_to_file, import_from_file, ImportSummary};
+use modules::crash_report::{collect_crash_report, upload_crash_report, CrashUploadResult};
+use modules::cms::{get_cms_config, CmsConfig};
+use modules::mod_conflicts::{check_mod_conflicts, PreLaunchWarning};
+use modules::storage::{clean_crash_reports, clean_logs, clean_unused_assets, get_disk_usage, CleanupResult, DiskUsage};
+use modules::system_report::{collect_system_report, upload_system_report, SupportReportResult, SystemReport};
+use modules::backup::{backup_all_worlds, backup_world, list_backups, list_worlds, restore_backup, BackupInfo, BackupOptions};
+use modules::screenshots::{delete_screenshot, list_screenshots, open_screenshot_folder, thumbnail_cache_dir, upload_screenshot, Screenshot, ScreenshotUploadResult};
+use modules::instances::{clone_instance, create_instance, delete_instance, get_instance, load_instances, set_active_instance, set_installed_version as set_instance_version, update_instance, Instance, InstancesConfig, NewInstance};
+use modules::download_manager::{
+    cancel_installation, is_cancelled, set_download_limit, BatchProgress, DownloadControlState,
+    BANDWIDTH,
+};
+use modules::telemetry::{report_in_background as report_update_in_background, UpdateTracker};
+use modules::optional_mods::{group_states, set_selection as set_optional_group, OptionalGroupState};
+use modules::offline::{is_network_error, save_last_known_good, validate_offline_install, UpdatesSkippedEvent};
+use modules::server_events::ServerEvents;
+use modules::session_refresh::SessionRefresher;
+use modules::error::{ErrorKind, LauncherError};
 use modules::network_test::{test_game_server_reachability, test_latency_and_jitter, test_download_speed, test_upload_speed, test_packet_loss, run_full_network_analysis};
 use modules::VpnManager;
+use modules::vpn::{VpnRegistration, VpnStatus};
+use anyhow::Context;
 use serde::Serialize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Emitter, Manager, State};
 
 // Authentication Commands
 #[tauri::command]
-async fn cmd_authenticate_official_launcher() -> Result<MinecraftProfile, String> {
+async fn cmd_authenticate_official_launcher() -> Result<MinecraftProfile, LauncherError> {
     authenticate_from_official_launcher()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-fn cmd_get_current_user() -> Result<Option<MinecraftProfile>, String> {
-    get_current_user().map_err(|e| e.to_string())
+fn cmd_get_current_user() -> Result<Option<MinecraftProfile>, LauncherError> {
+    get_current_user().map_err(LauncherError::from)
 }
 
+/// Refresh the session; during an auth service outage the stored session is
+/// returned unrefreshed and `auth-session` events report the background retries
 #[tauri::command]
-async fn cmd_refresh_token() -> Result<MinecraftProfile, String> {
-    refresh_token()
+async fn cmd_refresh_token(
+    app: AppHandle,
+    refresher: State<'_, SessionRefresher>,
+) -> Result<MinecraftProfile, LauncherError> {
+    refresher
+        .refresh(&app)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_logout(refresher: State<'_, SessionRefresher>) -> Result<(), LauncherError> {
+    refresher.stop().await;
+    logout().map_err(LauncherError::from)
 }
 
+/// Whether username-only demo accounts are enabled (`WOWID3_DEMO_ACCOUNTS=1`)
 #[tauri::command]
-fn cmd_logout() -> Result<(), String> {
-    logout().map_err(|e| e.to_string())
+fn cmd_demo_accounts_enabled() -> bool {
+    demo_accounts_enabled()
 }
 
 #[tauri::command]
-async fn cmd_get_device_code() -> Result<DeviceCodeInfo, String> {
+fn cmd_sign_in_demo(username: String) -> Result<MinecraftProfile, LauncherError> {
+    sign_in_demo(&username).map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_get_device_code() -> Result<DeviceCodeInfo, LauncherError> {
     get_device_code()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_complete_device_code_auth(device_code: String, interval: u64) -> Result<MinecraftProfile, String> {
+async fn cmd_complete_device_code_auth(device_code: String, interval: u64) -> Result<MinecraftProfile, LauncherError> {
     eprintln!("[Tauri Command] cmd_complete_device_code_auth called with device_code length: {}, interval: {}", device_code.len(), interval);
 
     let result = complete_device_code_auth(device_code, interval).await;
@@ -72,47 +122,58 @@ async fn cmd_complete_device_code_auth(device_code: String, interval: u64) -> Re
         }
     }
 
-    result.map_err(|e| e.to_string())
+    result.map_err(LauncherError::from)
 }
 
 // Avatar Proxy Commands
 #[tauri::command]
-async fn cmd_fetch_avatar(username: String) -> Result<AvatarData, String> {
-    fetch_avatar(&username).await.map_err(|e| e.to_string())
+async fn cmd_fetch_avatar(username: String) -> Result<AvatarData, LauncherError> {
+    fetch_avatar(&username).await.map_err(LauncherError::from)
 }
 
 #[tauri::command]
-fn cmd_is_avatar_cached(app: AppHandle, identifier: String) -> Result<bool, String> {
-    is_avatar_cached(&app, &identifier).map_err(|e| e.to_string())
+async fn cmd_get_player_skin(uuid: String) -> Result<PlayerSkin, LauncherError> {
+    get_player_skin(&uuid).await.map_err(LauncherError::from)
 }
 
 #[tauri::command]
-fn cmd_read_cached_avatar(app: AppHandle, identifier: String) -> Result<String, String> {
-    read_cached_avatar(&app, &identifier).map_err(|e| e.to_string())
+fn cmd_is_avatar_cached(app: AppHandle, identifier: String) -> Result<bool, LauncherError> {
+    is_avatar_cached(&app, &identifier).map_err(LauncherError::from)
 }
 
 #[tauri::command]
-fn cmd_write_cached_avatar(app: AppHandle, identifier: String, data_uri: String) -> Result<(), String> {
-    write_cached_avatar(&app, &identifier, &data_uri).map_err(|e| e.to_string())
+fn cmd_read_cached_avatar(app: AppHandle, identifier: String) -> Result<String, LauncherError> {
+    read_cached_avatar(&app, &identifier).map_err(LauncherError::from)
 }
 
 #[tauri::command]
-fn cmd_clear_avatar_cache(app: AppHandle) -> Result<(), String> {
-    clear_avatar_cache(&app).map_err(|e| e.to_string())
+fn cmd_write_cached_avatar(app: AppHandle, identifier: String, data_uri: String) -> Result<(), LauncherError> {
+    write_cached_avatar(&app, &identifier, &data_uri).map_err(LauncherError::from)
+}
+
+#[tauri::command]
+fn cmd_clear_avatar_cache(app: AppHandle) -> Result<(), LauncherError> {
+    clear_avatar_cache(&app).map_err(LauncherError::from)
 }
 
 // Launcher Update Commands
 #[tauri::command]
-async fn cmd_check_launcher_update(app: AppHandle) -> Result<LauncherUpdateInfo, String> {
-    check_launcher_update(&app).await.map_err(|e| e.to_string())
+async fn cmd_check_launcher_update(app: AppHandle) -> Result<LauncherUpdateInfo, LauncherError> {
+    check_launcher_update(&app).await.map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_install_launcher_update(app: AppHandle, url: String, sha256: String) -> Result<(), String> {
+async fn cmd_install_launcher_update(
+    app: AppHandle,
+    url: String,
+    sha256: String,
+    file_type: Option<String>,
+    signature: Option<String>,
+) -> Result<(), LauncherError> {
     // Clone app handle for the callback
     let app_handle = app.clone();
     
-    install_launcher_update(url, sha256, move |current, total| {
+    install_launcher_update(url, sha256, file_type, signature, move |current, total| {
         let _ = app_handle.emit("launcher-update-progress", serde_json::json!({
             "current": current,
             "total": total,
@@ -120,22 +181,22 @@ async fn cmd_install_launcher_update(app: AppHandle, url: String, sha256: String
         }));
     })
     .await
-    .map_err(|e| e.to_string())
+    .map_err(LauncherError::from)
 }
 
 // BlueMap Commands
 #[tauri::command]
-async fn cmd_check_bluemap_available() -> Result<BlueMapStatus, String> {
+async fn cmd_check_bluemap_available() -> Result<BlueMapStatus, LauncherError> {
     check_bluemap_available().await
 }
 
 #[tauri::command]
-async fn cmd_open_map_viewer(app: AppHandle) -> Result<(), String> {
+async fn cmd_open_map_viewer(app: AppHandle) -> Result<(), LauncherError> {
     open_map_viewer(app).await
 }
 
 #[tauri::command]
-async fn cmd_close_map_viewer(app: AppHandle) -> Result<(), String> {
+async fn cmd_close_map_viewer(app: AppHandle) -> Result<(), LauncherError> {
     close_map_viewer(app).await
 }
 
@@ -144,9 +205,75 @@ fn cmd_get_bluemap_url() -> String {
     get_bluemap_url()
 }
 
+/// Game directory a command works on: the instance's directory when `instance_id` is set,
+/// otherwise the `game_dir` passed by the frontend
+async fn resolve_game_dir(instance_id: Option<&str>, game_dir: Option<PathBuf>) -> Result<PathBuf, LauncherError> {
+    match (instance_id, game_dir) {
+        (Some(id), _) => get_instance(id)
+            .await
+            .map(|instance| instance.game_dir)
+            .map_err(LauncherError::from),
+        (None, Some(game_dir)) => Ok(game_dir),
+        (None, None) => Err("No instance or game directory given".into()),
+    }
+}
+
+/// Point a launch at the instance's game directory and memory settings
+async fn apply_instance(config: &mut LaunchConfig) -> Result<(), LauncherError> {
+    if let Some(id) = &config.instance_id {
+        let instance = get_instance(id).await?;
+        eprintln!("[Launcher] Launching instance '{}'", instance.name);
+        config.game_dir = instance.game_dir;
+        config.ram_mb = instance.jvm.max_heap_mb;
+        config.jvm = Some(instance.jvm);
+    }
+    Ok(())
+}
+
+/// Cached Java runtime, downloading it from the release server on first use
+async fn resolve_java_path(app: &AppHandle) -> Result<PathBuf, LauncherError> {
+    match get_cached_java(app).await {
+        Ok(Some(java_path)) => Ok(java_path),
+        Ok(None) => {
+            eprintln!("[Launcher] Java not cached, downloading from release server...");
+            let java_url = "https://wowid-launcher.frostdev.io/api/java";
+            Ok(download_and_cache_java(app, java_url.to_string())
+                .await
+                .context("Failed to download Java runtime")?)
+        }
+        Err(e) => Err(e.context("Failed to check for cached Java").into()),
+    }
+}
+
+/// Remember which modpack version an instance has installed
+async fn record_instance_version(instance_id: Option<&str>, version: &str) {
+    if let Some(id) = instance_id {
+        if let Err(e) = set_instance_version(id, Some(version.to_string())).await {
+            eprintln!("[Instances] Failed to record installed version for '{}': {}", id, e);
+        }
+    }
+}
+
+/// Warn about conflicting mods the player added; never blocks the launch
+async fn emit_pre_launch_warnings(app: &AppHandle, game_dir: &Path) {
+    match check_mod_conflicts(game_dir).await {
+        Ok(warnings) if !warnings.is_empty() => {
+            for warning in &warnings {
+                eprintln!("[ModConflicts] {}", warning.message);
+            }
+            let _ = app.emit("pre-launch-warnings", serde_json::json!({ "warnings": warnings }));
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("[ModConflicts] Pre-launch check failed: {}", e),
+    }
+}
+
 // Minecraft Launch Commands
 #[tauri::command]
-async fn cmd_launch_game(app: AppHandle, mut config: LaunchConfig) -> Result<String, String> {
+async fn cmd_launch_game(app: AppHandle, mut config: LaunchConfig) -> Result<String, LauncherError> {
+    ensure_no_mandatory_update()?;
+    apply_instance(&mut config).await?;
+
     // Resolve game directory if it's relative and doesn't exist in current dir
     if config.game_dir.is_relative() {
         // Check if it exists relative to current directory first
@@ -159,7 +286,7 @@ async fn cmd_launch_game(app: AppHandle, mut config: LaunchConfig) -> Result<Str
                 }
                 Err(e) => {
                     eprintln!("[Launcher] Failed to resolve game directory: {}", e);
-                    return Err(format!("Failed to resolve game directory: {}", e));
+                    return Err(e.context("Failed to resolve game directory").into());
                 }
             }
         } else {
@@ -169,37 +296,17 @@ async fn cmd_launch_game(app: AppHandle, mut config: LaunchConfig) -> Result<Str
 
     // Store game_dir for crash analysis
     let game_dir = config.game_dir.clone();
+    emit_pre_launch_warnings(&app, &game_dir).await;
 
     // Resolve Java path if not set - use downloaded runtime
     if config.java_path.is_none() {
-        // Try to get cached Java first
-        match get_cached_java(&app).await {
-            Ok(Some(java_path)) => {
-                config.java_path = Some(java_path);
-            }
-            Ok(None) => {
-                // Download Java from release server
-                eprintln!("[Launcher] Java not cached, downloading from release server...");
-                let java_url = "https://wowid-launcher.frostdev.io/api/java";
-                match download_and_cache_java(&app, java_url.to_string()).await {
-                    Ok(java_path) => {
-                        config.java_path = Some(java_path);
-                    }
-                    Err(e) => {
-                        return Err(format!("Failed to download Java runtime: {}", e));
-                    }
-                }
-            }
-            Err(e) => {
-                return Err(format!("Failed to check for cached Java: {}", e));
-            }
-        }
+        config.java_path = Some(resolve_java_path(&app).await?);
     }
 
     // Launch the game process
+    let (player_uuid, player_name) = (config.uuid.clone(), config.username.clone());
     let mut process = launch_game(config)
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
     // Take stdout and stderr for streaming
     let stdout = process.stdout.take();
@@ -245,9 +352,19 @@ async fn cmd_launch_game(app: AppHandle, mut config: LaunchConfig) -> Result<Str
 
     // Spawn task to monitor process exit
     let app_monitor = app.clone();
+    let overlay = app.state::<OverlayWriter>().inner().clone();
+    if let Err(e) = overlay.start_session().await {
+        eprintln!("[Overlay] Failed to start session: {}", e);
+    }
+    let presence = app.state::<PresenceService>().inner().clone();
+    presence.start(player_uuid, player_name).await;
     tokio::spawn(async move {
         match process.wait().await {
             Ok(status) => {
+                if let Err(e) = overlay.end_session().await {
+                    eprintln!("[Overlay] Failed to end session: {}", e);
+                }
+                presence.stop().await;
                 let exit_code = status.code().unwrap_or(-1);
                 let crashed = exit_code != 0;
 
@@ -260,7 +377,8 @@ async fn cmd_launch_game(app: AppHandle, mut config: LaunchConfig) -> Result<Str
                 if crashed {
                     if let Ok(crash_msg) = analyze_crash(&game_dir).await {
                         let _ = app_monitor.emit("minecraft-crash", serde_json::json!({
-                            "message": crash_msg
+                            "message": crash_msg,
+                            "exit_code": exit_code
                         }));
                     }
                 }
@@ -274,67 +392,14 @@ async fn cmd_launch_game(app: AppHandle, mut config: LaunchConfig) -> Result<Str
     Ok("Game launched successfully".to_string())
 }
 
-#[tauri::command]
-async fn cmd_launch_game_with_metadata(
-    app: AppHandle,
-    mut config: LaunchConfig,
-    version_id: String,
-) -> Result<String, String> {
-    // Resolve game directory if it's relative and doesn't exist in current dir
-    if config.game_dir.is_relative() {
-        // Check if it exists relative to current directory first
-        if !config.game_dir.exists() {
-            // Resolve using persistent data directory (fixes AppImage temp path issue)
-            match resolve_game_directory(&app, &config.game_dir) {
-                Ok(resolved_path) => {
-                    config.game_dir = resolved_path;
-                    eprintln!("[Launcher] Resolved game directory to: {:?}", config.game_dir);
-                }
-                Err(e) => {
-                    eprintln!("[Launcher] Failed to resolve game directory: {}", e);
-                    return Err(format!("Failed to resolve game directory: {}", e));
-                }
-            }
-        } else {
-            eprintln!("[Launcher] Using existing game directory: {:?}", config.game_dir);
-        }
-    }
-
-    // Store game_dir for crash analysis
-    let game_dir = config.game_dir.clone();
-
-    // Resolve Java path if not set - use downloaded runtime
-    if config.java_path.is_none() {
-        // Try to get cached Java first
-        match get_cached_java(&app).await {
-            Ok(Some(java_path)) => {
-                config.java_path = Some(java_path);
-            }
-            Ok(None) => {
-                // Download Java from release server
-                eprintln!("[Launcher] Java not cached, downloading from release server...");
-                let java_url = "https://wowid-launcher.frostdev.io/api/java";
-                match download_and_cache_java(&app, java_url.to_string()).await {
-                    Ok(java_path) => {
-                        config.java_path = Some(java_path);
-                    }
-                    Err(e) => {
-                        return Err(format!("Failed to download Java runtime: {}", e));
-                    }
-                }
-            }
-            Err(e) => {
-                return Err(format!("Failed to check for cached Java: {}", e));
-            }
-        }
-    }
-
-    // Launch the game process
-    let mut process = launch_game_with_metadata(config, &version_id)
-        .await
-        .map_err(|e| e.to_string())?;
-
-    // Take stdout and stderr for streaming (same as cmd_launch_game)
+/// Stream game output as `minecraft-log` events and report exit/crash when it stops
+async fn monitor_game_process(
+    app: &AppHandle,
+    mut process: tokio::process::Child,
+    game_dir: PathBuf,
+    (player_uuid, player_name): (String, String),
+) {
+    // Take stdout and stderr for streaming
     let stdout = process.stdout.take();
     let stderr = process.stderr.take();
 
@@ -375,9 +440,19 @@ async fn cmd_launch_game_with_metadata(
     }
 
     let app_monitor = app.clone();
+    let overlay = app.state::<OverlayWriter>().inner().clone();
+    if let Err(e) = overlay.start_session().await {
+        eprintln!("[Overlay] Failed to start session: {}", e);
+    }
+    let presence = app.state::<PresenceService>().inner().clone();
+    presence.start(player_uuid, player_name).await;
     tokio::spawn(async move {
         match process.wait().await {
             Ok(status) => {
+                if let Err(e) = overlay.end_session().await {
+                    eprintln!("[Overlay] Failed to end session: {}", e);
+                }
+                presence.stop().await;
                 let exit_code = status.code().unwrap_or(-1);
                 let crashed = exit_code != 0;
 
@@ -389,7 +464,8 @@ async fn cmd_launch_game_with_metadata(
                 if crashed {
                     if let Ok(crash_msg) = analyze_crash(&game_dir).await {
                         let _ = app_monitor.emit("minecraft-crash", serde_json::json!({
-                            "message": crash_msg
+                            "message": crash_msg,
+                            "exit_code": exit_code
                         }));
                     }
                 }
@@ -399,75 +475,155 @@ async fn cmd_launch_game_with_metadata(
             }
         }
     });
+}
+
+#[tauri::command]
+async fn cmd_launch_game_with_metadata(
+    app: AppHandle,
+    mut config: LaunchConfig,
+    version_id: String,
+) -> Result<String, LauncherError> {
+    ensure_no_mandatory_update()?;
+    apply_instance(&mut config).await?;
+
+    // Resolve game directory if it's relative and doesn't exist in current dir
+    if config.game_dir.is_relative() {
+        // Check if it exists relative to current directory first
+        if !config.game_dir.exists() {
+            // Resolve using persistent data directory (fixes AppImage temp path issue)
+            match resolve_game_directory(&app, &config.game_dir) {
+                Ok(resolved_path) => {
+                    config.game_dir = resolved_path;
+                    eprintln!("[Launcher] Resolved game directory to: {:?}", config.game_dir);
+                }
+                Err(e) => {
+                    eprintln!("[Launcher] Failed to resolve game directory: {}", e);
+                    return Err(e.context("Failed to resolve game directory").into());
+                }
+            }
+        } else {
+            eprintln!("[Launcher] Using existing game directory: {:?}", config.game_dir);
+        }
+    }
+
+    // Store game_dir for crash analysis
+    let game_dir = config.game_dir.clone();
+    emit_pre_launch_warnings(&app, &game_dir).await;
+
+    // Resolve Java path if not set - use downloaded runtime
+    if config.java_path.is_none() {
+        config.java_path = Some(resolve_java_path(&app).await?);
+    }
+
+    // Launch the game process
+    let java_path = config.java_path.clone();
+    let player = (config.uuid.clone(), config.username.clone());
+    let process = launch_game_with_metadata(config, &version_id)
+        .await?;
+
+    // Remember this configuration for offline launches
+    if let Err(e) = save_last_known_good(&game_dir, &version_id, java_path).await {
+        eprintln!("[Launcher] Failed to save last-known-good configuration: {}", e);
+    }
+
+    monitor_game_process(&app, process, game_dir, player).await;
 
     Ok("Game launched successfully".to_string())
 }
 
 // Minecraft Version Commands
 #[tauri::command]
-async fn cmd_list_minecraft_versions(version_type: Option<String>) -> Result<Vec<VersionInfo>, String> {
+async fn cmd_list_minecraft_versions(version_type: Option<String>) -> Result<Vec<VersionInfo>, LauncherError> {
     list_versions(version_type.as_deref())
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_get_latest_release() -> Result<String, String> {
+async fn cmd_get_latest_release() -> Result<String, LauncherError> {
     get_latest_release()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_get_latest_snapshot() -> Result<String, String> {
+async fn cmd_get_latest_snapshot() -> Result<String, LauncherError> {
     get_latest_snapshot()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 // Fabric Commands
 #[tauri::command]
-async fn cmd_get_fabric_loaders(game_version: String) -> Result<Vec<FabricLoader>, String> {
+async fn cmd_get_fabric_loaders(game_version: String) -> Result<Vec<FabricLoader>, LauncherError> {
     get_fabric_loaders(&game_version)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_get_latest_fabric_loader(game_version: String) -> Result<FabricLoader, String> {
+async fn cmd_get_latest_fabric_loader(game_version: String) -> Result<FabricLoader, LauncherError> {
     get_latest_fabric_loader(&game_version)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
+}
+
+// Mod Loader Commands
+#[tauri::command]
+async fn cmd_get_loader_versions(loader: ModLoader, game_version: String) -> Result<Vec<LoaderVersion>, LauncherError> {
+    loader
+        .list_versions(&game_version)
+        .await
+        .map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_get_latest_loader_version(loader: ModLoader, game_version: String) -> Result<LoaderVersion, LauncherError> {
+    loader
+        .latest_version(&game_version)
+        .await
+        .map_err(LauncherError::from)
 }
 
 // Minecraft Installation Commands
 #[tauri::command]
 async fn cmd_install_minecraft(
     app: AppHandle,
-    config: InstallConfig,
-) -> Result<String, String> {
+    mut config: InstallConfig,
+) -> Result<String, LauncherError> {
+    if let Some(id) = config.instance_id.as_deref() {
+        config.game_dir = resolve_game_dir(Some(id), None).await?;
+    }
+
+    // NeoForge's installer is a Java program
+    if config.loader == ModLoader::NeoForge && config.loader_version.is_some() && config.java_path.is_none() {
+        config.java_path = Some(resolve_java_path(&app).await?);
+    }
+
     install_minecraft(config, move |progress| {
         let _ = app.emit("minecraft-install-progress", progress);
     })
     .await
     .map(|_| "Installation complete".to_string())
-    .map_err(|e| e.to_string())
+    .map_err(LauncherError::from)
 }
 
 #[tauri::command]
 async fn cmd_is_version_installed(
-    game_dir: PathBuf,
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
     version_id: String,
-) -> Result<bool, String> {
+) -> Result<bool, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
     is_version_installed(&game_dir, &version_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 // Discord Rich Presence Commands
 #[tauri::command]
-async fn cmd_discord_connect(discord: State<'_, DiscordClient>) -> Result<(), String> {
-    discord.connect().await.map_err(|e| e.to_string())
+async fn cmd_discord_connect(discord: State<'_, DiscordClient>) -> Result<(), LauncherError> {
+    discord.connect().await.map_err(LauncherError::from)
 }
 
 #[tauri::command]
@@ -476,7 +632,7 @@ async fn cmd_discord_set_presence(
     details: String,
     state: String,
     large_image: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), LauncherError> {
     let presence = GamePresence {
         state,
         details: Some(details),
@@ -493,7 +649,7 @@ async fn cmd_discord_set_presence(
         party_max: None,
         player_count: None,
     };
-    discord.set_presence(&presence).await.map_err(|e| e.to_string())
+    discord.set_presence(&presence).await.map_err(LauncherError::from)
 }
 
 #[tauri::command]
@@ -506,7 +662,7 @@ async fn cmd_discord_update_presence(
     party_size: Option<u32>,
     party_max: Option<u32>,
     start_time: Option<i64>,
-) -> Result<(), String> {
+) -> Result<(), LauncherError> {
     let presence = GamePresence {
         state,
         details: Some(details),
@@ -520,48 +676,66 @@ async fn cmd_discord_update_presence(
         party_max,
         player_count: None,
     };
-    discord.update_presence(&presence).await.map_err(|e| e.to_string())
+    discord.update_presence(&presence).await.map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_discord_clear_presence(discord: State<'_, DiscordClient>) -> Result<(), String> {
-    discord.clear_presence().await.map_err(|e| e.to_string())
+async fn cmd_discord_clear_presence(discord: State<'_, DiscordClient>) -> Result<(), LauncherError> {
+    discord.clear_presence().await.map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_discord_disconnect(discord: State<'_, DiscordClient>) -> Result<(), String> {
-    discord.disconnect().await.map_err(|e| e.to_string())
+async fn cmd_discord_disconnect(discord: State<'_, DiscordClient>) -> Result<(), LauncherError> {
+    discord.disconnect().await.map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_discord_is_connected(discord: State<'_, DiscordClient>) -> Result<bool, String> {
+async fn cmd_discord_is_connected(discord: State<'_, DiscordClient>) -> Result<bool, LauncherError> {
     Ok(discord.is_connected().await)
 }
 
 // Server Status Commands
 #[tauri::command]
-async fn cmd_ping_server(address: String) -> Result<ServerStatus, String> {
-    ping_server(&address).await.map_err(|e| e.to_string())
+async fn cmd_ping_server(overlay: State<'_, OverlayWriter>, address: String) -> Result<ServerStatus, LauncherError> {
+    let status = ping_server(&address).await?;
+    if let Err(e) = overlay.set_player_count(status.player_count, status.max_players).await {
+        eprintln!("[Overlay] Failed to update player count: {}", e);
+    }
+    Ok(status)
 }
 
 #[tauri::command]
-async fn cmd_resolve_player_name(uuid: String) -> Result<String, String> {
-    resolve_player_name(&uuid).await.map_err(|e| e.to_string())
+async fn cmd_resolve_player_name(uuid: String) -> Result<String, LauncherError> {
+    resolve_player_name(&uuid).await.map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_get_detailed_server_status(base_url: String) -> Result<TrackerState, String> {
-    fetch_tracker_status(&base_url).await.map_err(|e| e.to_string())
+async fn cmd_get_detailed_server_status(base_url: String) -> Result<TrackerState, LauncherError> {
+    fetch_tracker_status(&base_url).await.map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_ping_server_with_vpn(vpn_enabled: bool) -> Result<ServerStatus, String> {
-    ping_server_with_vpn(vpn_enabled).await.map_err(|e| e.to_string())
+async fn cmd_send_chat_message(base_url: String, content: String) -> Result<(), LauncherError> {
+    use modules::auth::{get_access_token_by_session_id, get_current_user};
+
+    let profile = get_current_user()
+        .context("Authentication error")?
+        .ok_or_else(|| LauncherError::new(ErrorKind::NotSignedIn, "Not logged in. Please log in with Microsoft first."))?;
+    let access_token = get_access_token_by_session_id(&profile.session_id)?;
+
+    send_chat_message(&base_url, &profile.uuid, &access_token, &content)
+        .await
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_verify_server_reachable(address: String) -> Result<bool, String> {
-    verify_server_reachable(&address).await.map_err(|e| e.to_string())
+async fn cmd_ping_server_with_vpn(vpn_enabled: bool) -> Result<ServerStatus, LauncherError> {
+    ping_server_with_vpn(vpn_enabled).await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_verify_server_reachable(address: String) -> Result<bool, LauncherError> {
+    verify_server_reachable(&address).await.map_err(LauncherError::from)
 }
 
 #[tauri::command]
@@ -569,8 +743,27 @@ async fn cmd_get_player_stats(
     app: AppHandle,
     uuid: String,
     server_url: String
-) -> Result<PlayerStats, String> {
-    get_player_stats(&app, &uuid, &server_url).await.map_err(|e| e.to_string())
+) -> Result<PlayerStats, LauncherError> {
+    get_player_stats(&app, &uuid, &server_url).await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_get_player_sessions(uuid: String, server_url: String) -> Result<PlayerSessions, LauncherError> {
+    get_player_sessions(&uuid, &server_url).await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_get_stat_leaderboard(
+    metric: String,
+    window: String,
+    server_url: String,
+) -> Result<Leaderboard, LauncherError> {
+    get_stat_leaderboard(&metric, &window, &server_url).await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_get_player_advancements(uuid: String, server_url: String) -> Result<PlayerAdvancements, LauncherError> {
+    get_player_advancements(&uuid, &server_url).await.map_err(LauncherError::from)
 }
 
 // Download progress event payload
@@ -583,120 +776,868 @@ struct DownloadProgressEvent {
     total_bytes: u64,    // Total bytes to download
 }
 
-// Modpack Update Commands
+/// Emit `download-progress-v2` for every report, and `download-progress` when a file finishes
+fn emit_download_progress(app: &AppHandle, progress: BatchProgress) {
+    if let Some(filename) = &progress.completed_file {
+        let _ = app.emit(
+            "download-progress",
+            DownloadProgressEvent {
+                current: progress.files_completed,
+                total: progress.total_files,
+                filename: filename.clone(),
+                current_bytes: progress.bytes_downloaded,
+                total_bytes: progress.total_bytes,
+            },
+        );
+    }
+    let _ = app.emit("download-progress-v2", progress);
+}
+
+// Stream Overlay Commands
+#[tauri::command]
+async fn cmd_overlay_configure(
+    overlay: State<'_, OverlayWriter>,
+    settings: OverlaySettings,
+) -> Result<String, LauncherError> {
+    overlay
+        .configure(settings)
+        .await
+        .map(|dir| dir.to_string_lossy().to_string())
+        .map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_overlay_get_settings(overlay: State<'_, OverlayWriter>) -> Result<OverlaySettings, LauncherError> {
+    Ok(overlay.settings().await)
+}
+
+#[tauri::command]
+async fn cmd_overlay_get_state(overlay: State<'_, OverlayWriter>) -> Result<OverlayState, LauncherError> {
+    Ok(overlay.state().await)
+}
+
+#[tauri::command]
+async fn cmd_overlay_set_pack_version(
+    overlay: State<'_, OverlayWriter>,
+    version: Option<String>,
+) -> Result<(), LauncherError> {
+    overlay.set_pack_version(version).await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_overlay_set_player_count(
+    overlay: State<'_, OverlayWriter>,
+    online: Option<u32>,
+    max: Option<u32>,
+) -> Result<(), LauncherError> {
+    overlay.set_player_count(online, max).await.map_err(LauncherError::from)
+}
+
+// Settings Export/Import Commands
+#[tauri::command]
+async fn cmd_export_settings(
+    overlay: State<'_, OverlayWriter>,
+    path: PathBuf,
+    passphrase: String,
+    settings: serde_json::Value,
+    game_dirs: Vec<PathBuf>,
+    trusted_mods: Option<Vec<String>>,
+) -> Result<String, LauncherError> {
+    let bundle = build_bundle(
+        settings,
+        Some(overlay.settings().await),
+        &game_dirs,
+        trusted_mods.unwrap_or_default(),
+    )
+    .await?;
+
+    export_to_file(&bundle, &passphrase, &path)
+        .await
+        .map(|_| path.to_string_lossy().to_string())
+        .map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_import_settings(
+    overlay: State<'_, OverlayWriter>,
+    path: PathBuf,
+    passphrase: String,
+) -> Result<ImportSummary, LauncherError> {
+    let summary = import_from_file(&path, &passphrase)
+        .await?;
+
+    // Backend-owned state is applied here; the frontend applies summary.settings
+    if let Some(settings) = summary.overlay.clone() {
+        if let Err(e) = overlay.configure(settings).await {
+            eprintln!("[Settings] Failed to apply imported overlay settings: {}", e);
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Launch the installed modpack without contacting the release server
+///
+/// Uses the version id and Java runtime of the last successful launch; never downloads anything.
+#[tauri::command]
+async fn cmd_launch_offline(app: AppHandle, mut config: LaunchConfig) -> Result<String, LauncherError> {
+    apply_instance(&mut config).await?;
+    if config.game_dir.is_relative() && !config.game_dir.exists() {
+        config.game_dir = resolve_game_directory(&app, &config.game_dir)
+            .context("Failed to resolve game directory")?;
+    }
+    let game_dir = config.game_dir.clone();
+
+    let last_good = validate_offline_install(&game_dir)
+        .await
+        .context("Cannot launch offline")?;
+    emit_pre_launch_warnings(&app, &game_dir).await;
+
+    if config.java_path.is_none() {
+        config.java_path = match last_good.java_path.filter(|p| p.exists()) {
+            Some(java_path) => Some(java_path),
+            None => Some(
+                get_cached_java(&app)
+                    .await
+                    .context("Failed to check for cached Java")?
+                    .ok_or_else(|| "Cannot launch offline: no cached Java runtime".to_string())?,
+            ),
+        };
+    }
+
+    eprintln!(
+        "[Launcher] Launching offline: {} (modpack {:?})",
+        last_good.version_id, last_good.modpack_version
+    );
+
+    let player = (config.uuid.clone(), config.username.clone());
+    let process = launch_game_with_metadata(config, &last_good.version_id)
+        .await?;
+
+    monitor_game_process(&app, process, game_dir, player).await;
+
+    Ok("Game launched in offline mode".to_string())
+}
+
+/// Find existing Java runtimes so users can skip the bundled runtime download
+#[tauri::command]
+async fn cmd_detect_java_installations(app: AppHandle) -> Result<Vec<JavaInstallation>, LauncherError> {
+    let mut installations = detect_java_installations().await;
+
+    // The launcher's own runtime, if already downloaded, is always a safe choice
+    if let Ok(Some(bundled)) = get_cached_java(&app).await {
+        match probe_java(&bundled, "bundled").await {
+            Ok(installation) => installations.insert(0, installation),
+            Err(e) => eprintln!("[Java] Cached runtime at {} is unusable: {}", bundled.display(), e),
+        }
+    }
+
+    Ok(installations)
+}
+
+/// Check a user-selected Java executable
+#[tauri::command]
+async fn cmd_validate_java(path: PathBuf) -> Result<JavaInstallation, LauncherError> {
+    probe_java(&path, "custom")
+        .await
+        .map_err(LauncherError::from)
+}
+
+/// Upload the latest crash report to the release server (only called when the user opted in)
+#[tauri::command]
+async fn cmd_upload_crash_report(
+    app: AppHandle,
+    server_url: String,
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    exit_code: Option<i32>,
+    java_path: Option<PathBuf>,
+) -> Result<CrashUploadResult, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let game_dir = if game_dir.is_relative() && !game_dir.exists() {
+        resolve_game_directory(&app, &game_dir)?
+    } else {
+        game_dir
+    };
+
+    // No custom Java means the bundled runtime was used
+    let java_path = match java_path {
+        Some(path) => Some(path),
+        None => get_cached_java(&app).await.ok().flatten(),
+    };
+
+    let upload = collect_crash_report(&game_dir, exit_code, java_path.as_deref())
+        .await?;
+
+    let result = upload_crash_report(&server_url, &upload)
+        .await?;
+
+    eprintln!("[CrashReport] Uploaded crash report {} (signature {})", result.id, result.signature);
+    Ok(result)
+}
+
+/// Gather OS, hardware, Java and modpack details for a support request
+#[tauri::command]
+async fn cmd_get_system_report(
+    app: AppHandle,
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    java_path: Option<PathBuf>,
+) -> Result<SystemReport, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let game_dir = if game_dir.is_relative() && !game_dir.exists() {
+        resolve_game_directory(&app, &game_dir)?
+    } else {
+        game_dir
+    };
+
+    // No custom Java means the bundled runtime is used
+    let java_path = match java_path {
+        Some(path) => Some(path),
+        None => get_cached_java(&app).await.ok().flatten(),
+    };
+
+    collect_system_report(&game_dir, java_path.as_deref())
+        .await
+        .map_err(LauncherError::from)
+}
+
+/// Upload a system report so support can look it up by id
 #[tauri::command]
-async fn cmd_check_updates(manifest_url: String) -> Result<Manifest, String> {
-    check_for_updates(&manifest_url)
+async fn cmd_upload_system_report(
+    server_url: String,
+    report: SystemReport,
+    note: Option<String>,
+) -> Result<SupportReportResult, LauncherError> {
+    let result = upload_system_report(&server_url, &report, note.as_deref())
+        .await?;
+
+    eprintln!("[SystemReport] Uploaded system report {}", result.id);
+    Ok(result)
+}
+
+/// Launcher settings from the server, falling back to the cached copy when offline
+#[tauri::command]
+async fn cmd_get_cms_config(server_url: String) -> Result<CmsConfig, LauncherError> {
+    Ok(get_cms_config(&server_url).await)
+}
+
+// World Backup Commands
+#[tauri::command]
+async fn cmd_list_worlds(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<Vec<String>, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    list_worlds(&game_dir).map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_backup_world(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    world_name: String,
+    options: Option<BackupOptions>,
+) -> Result<BackupInfo, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    backup_world(&game_dir, &world_name, &options.unwrap_or_default())
+        .await
+        .map_err(LauncherError::from)
+}
+
+#[tauri::command]
+fn cmd_list_backups(
+    world_name: Option<String>,
+    options: Option<BackupOptions>,
+) -> Result<Vec<BackupInfo>, LauncherError> {
+    let backup_root = options.unwrap_or_default().backup_root()?;
+    list_backups(&backup_root, world_name.as_deref()).map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_restore_backup(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    world_name: String,
+    filename: String,
+    options: Option<BackupOptions>,
+) -> Result<BackupInfo, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    if is_game_running().await {
+        return Err("Close Minecraft before restoring a world backup".into());
+    }
+    restore_backup(&game_dir, &world_name, &filename, &options.unwrap_or_default())
+        .await
+        .map_err(LauncherError::from)
+}
+
+// Storage Commands
+/// What the game directory is using, by category
+#[tauri::command]
+async fn cmd_get_disk_usage(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<DiskUsage, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    get_disk_usage(&game_dir).await.map_err(LauncherError::from)
+}
+
+/// Delete rotated game logs
+#[tauri::command]
+async fn cmd_clean_logs(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<CleanupResult, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let result = clean_logs(&game_dir).await?;
+    eprintln!("[Storage] Removed {} log files ({} bytes)", result.files_removed, result.bytes_freed);
+    Ok(result)
+}
+
+/// Delete crash reports and JVM crash dumps
+#[tauri::command]
+async fn cmd_clean_crash_reports(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<CleanupResult, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let result = clean_crash_reports(&game_dir).await?;
+    eprintln!("[Storage] Removed {} crash reports ({} bytes)", result.files_removed, result.bytes_freed);
+    Ok(result)
+}
+
+/// Delete assets left behind by Minecraft versions that are no longer installed
+#[tauri::command]
+async fn cmd_clean_unused_assets(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<CleanupResult, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    if is_game_running().await {
+        return Err("Close Minecraft before cleaning up assets".into());
+    }
+    let result = clean_unused_assets(&game_dir).await?;
+    eprintln!("[Storage] Removed {} unused assets ({} bytes)", result.files_removed, result.bytes_freed);
+    Ok(result)
+}
+
+/// Same check the launcher runs before starting the game
+#[tauri::command]
+async fn cmd_check_mod_conflicts(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<Vec<PreLaunchWarning>, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    check_mod_conflicts(&game_dir).await.map_err(LauncherError::from)
+}
+
+// Screenshot Commands
+#[tauri::command]
+async fn cmd_list_screenshots(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<Vec<Screenshot>, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let cache_dir = thumbnail_cache_dir()?;
+    list_screenshots(&game_dir, &cache_dir)
+        .await
+        .map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_delete_screenshot(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    filename: String,
+) -> Result<(), LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let cache_dir = thumbnail_cache_dir()?;
+    delete_screenshot(&game_dir, &filename, &cache_dir)
+        .await
+        .map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_open_screenshot_folder(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<(), LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    open_screenshot_folder(&game_dir)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_upload_screenshot(
+    server_url: String,
+    uuid: String,
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    filename: String,
+) -> Result<ScreenshotUploadResult, LauncherError> {
+    use modules::auth::{get_access_token_by_session_id, get_current_user};
+
+    let profile = get_current_user()
+        .context("Authentication error")?
+        .ok_or_else(|| LauncherError::new(ErrorKind::NotSignedIn, "Not logged in. Please log in with Microsoft first."))?;
+    let access_token = get_access_token_by_session_id(&profile.session_id)?;
+
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    upload_screenshot(&server_url, &uuid, &access_token, &game_dir, &filename)
+        .await
+        .map_err(LauncherError::from)
+}
+
+// JVM Profile Commands
+#[tauri::command]
+async fn cmd_list_jvm_profiles() -> Result<Vec<JvmProfile>, LauncherError> {
+    list_profiles().await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_load_jvm_profile(name: String) -> Result<JvmProfile, LauncherError> {
+    load_profile(&name).await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_save_jvm_profile(profile: JvmProfile) -> Result<JvmProfile, LauncherError> {
+    save_profile(profile).await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_delete_jvm_profile(name: String) -> Result<(), LauncherError> {
+    delete_profile(&name).await.map_err(LauncherError::from)
+}
+
+/// Check JVM settings without saving them; returns the flags they produce
+#[tauri::command]
+fn cmd_validate_jvm_settings(settings: JvmSettings) -> Result<Vec<String>, LauncherError> {
+    settings.validate()?;
+    Ok(settings.jvm_args())
+}
+
+// Instance Commands
+/// All instances and the active one; `legacy_game_dir` seeds the default instance on first run
+#[tauri::command]
+async fn cmd_list_instances(legacy_game_dir: Option<PathBuf>) -> Result<InstancesConfig, LauncherError> {
+    load_instances(legacy_game_dir).await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_create_instance(instance: NewInstance) -> Result<Instance, LauncherError> {
+    create_instance(instance).await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_clone_instance(instance_id: String, name: String) -> Result<Instance, LauncherError> {
+    clone_instance(&instance_id, &name).await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_update_instance(instance: Instance) -> Result<Instance, LauncherError> {
+    update_instance(instance).await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_delete_instance(instance_id: String, delete_files: bool) -> Result<(), LauncherError> {
+    if delete_files && is_game_running().await {
+        return Err("Close Minecraft before deleting an instance's files".into());
+    }
+    delete_instance(&instance_id, delete_files).await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_set_active_instance(instance_id: String) -> Result<Instance, LauncherError> {
+    set_active_instance(&instance_id).await.map_err(LauncherError::from)
+}
+
+// Modpack Update Commands
+#[tauri::command]
+async fn cmd_check_updates(
+    app: AppHandle,
+    manifest_url: Option<String>,
+    channel: Option<String>,
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<Manifest, LauncherError> {
+    // An instance brings its own modpack source unless the caller overrides it
+    let (manifest_url, game_dir) = match instance_id.as_deref() {
+        Some(id) => {
+            let instance = get_instance(id).await?;
+            (manifest_url.unwrap_or(instance.manifest_url), Some(instance.game_dir))
+        }
+        None => (
+            manifest_url.ok_or_else(|| "No instance or manifest URL given".to_string())?,
+            game_dir,
+        ),
+    };
+
+    match check_for_updates(&manifest_url, channel.as_deref()).await {
+        Ok(manifest) => Ok(manifest),
+        Err(e) => {
+            // Tell the UI the server is unreachable so it can offer offline mode
+            if is_network_error(&e) {
+                let (installed_version, offline_available) = match &game_dir {
+                    Some(dir) => (
+                        get_installed_version(dir).await.ok().flatten(),
+                        validate_offline_install(dir).await.is_ok(),
+                    ),
+                    None => (None, false),
+                };
+                eprintln!("[Updater] Release server unreachable, update check skipped: {}", e);
+                let _ = app.emit("updates-skipped", UpdatesSkippedEvent {
+                    reason: e.to_string(),
+                    installed_version,
+                    offline_available,
+                });
+            }
+            Err(e.into())
+        }
+    }
 }
 
 #[tauri::command]
-async fn cmd_get_installed_version(game_dir: PathBuf) -> Result<Option<String>, String> {
+async fn cmd_get_installed_version(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<Option<String>, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
     get_installed_version(&game_dir)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_set_installed_version(game_dir: PathBuf, version: String) -> Result<String, String> {
+async fn cmd_set_installed_version(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    version: String,
+) -> Result<String, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
     update_version_file(&game_dir, &version)
-        .await
-        .map(|_| format!("Version file updated to {}", version))
-        .map_err(|e| e.to_string())
+        .await?;
+    record_instance_version(instance_id.as_deref(), &version).await;
+
+    Ok(format!("Version file updated to {}", version))
 }
 
 #[tauri::command]
 async fn cmd_install_modpack(
     app: AppHandle,
+    overlay: State<'_, OverlayWriter>,
     manifest: Manifest,
-    game_dir: PathBuf,
-) -> Result<String, String> {
-    install_modpack(&manifest, &game_dir, move |current, total, filename, current_bytes, total_bytes| {
-        let progress = DownloadProgressEvent {
-            current,
-            total,
-            filename,
-            current_bytes,
-            total_bytes,
-        };
-        let _ = app.emit("download-progress", progress);
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    backup: Option<BackupOptions>,
+    telemetry_url: Option<String>,
+) -> Result<String, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let installed = match get_installed_version(&game_dir).await {
+        Ok(installed) => installed,
+        // Without a readable version we can't tell whether worlds need a backup
+        Err(e) if backup.is_some() => return Err(e.into()),
+        Err(_) => None,
+    };
+    let is_update = installed.as_deref() != Some(manifest.version.as_str());
+
+    // Mod updates can corrupt single-player worlds; back them up first when enabled
+    if let Some(options) = backup {
+        if installed.is_some() && is_update {
+            let backups = backup_all_worlds(&game_dir, &options)
+                .await
+                .context("Failed to back up worlds before updating")?;
+            eprintln!("[Backup] Backed up {} world(s) before updating to {}", backups.len(), manifest.version);
+        }
+    }
+
+    let tracker = UpdateTracker::start(installed);
+    let progress_tracker = tracker.clone();
+    let result = install_modpack(&manifest, &game_dir, move |progress| {
+        progress_tracker.progress(progress.files_completed, progress.bytes_downloaded);
+        emit_download_progress(&app, progress);
     })
-    .await
-    .map(|_| "Modpack installed successfully".to_string())
-    .map_err(|e| e.to_string())
+    .await;
+
+    // Opt-in: the frontend only passes a server URL when the player allowed update reports
+    if let Some(server_url) = telemetry_url {
+        // A cancel isn't a failed update
+        let cancelled = matches!(&result, Err(e) if is_cancelled(e));
+        if (is_update || result.is_err()) && !cancelled {
+            report_update_in_background(server_url, tracker.finish(&manifest.version, result.as_ref().err()));
+        }
+    }
+    result?;
+
+    if let Err(e) = overlay.set_pack_version(Some(manifest.version.clone())).await {
+        eprintln!("[Overlay] Failed to update pack version: {}", e);
+    }
+    record_instance_version(instance_id.as_deref(), &manifest.version).await;
+
+    Ok("Modpack installed successfully".to_string())
+}
+
+#[tauri::command]
+async fn cmd_rollback_modpack(
+    app: AppHandle,
+    overlay: State<'_, OverlayWriter>,
+    manifest_url: Option<String>,
+    version: String,
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<Manifest, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let manifest_url = match (manifest_url, instance_id.as_deref()) {
+        (Some(url), _) => url,
+        (None, Some(id)) => get_instance(id).await?.manifest_url,
+        (None, None) => return Err("No instance or manifest URL given".into()),
+    };
+    let manifest = rollback_modpack(&manifest_url, &version, &game_dir, move |progress| emit_download_progress(&app, progress))
+    .await?;
+
+    if let Err(e) = overlay.set_pack_version(Some(manifest.version.clone())).await {
+        eprintln!("[Overlay] Failed to update pack version: {}", e);
+    }
+    record_instance_version(instance_id.as_deref(), &manifest.version).await;
+
+    Ok(manifest)
 }
 
 #[tauri::command]
 async fn cmd_verify_and_repair_modpack(
     app: AppHandle,
     manifest: Manifest,
-    game_dir: PathBuf,
-) -> Result<String, String> {
-    verify_and_repair_modpack(&manifest, &game_dir, move |current, total, filename, current_bytes, total_bytes| {
-        let progress = DownloadProgressEvent {
-            current,
-            total,
-            filename,
-            current_bytes,
-            total_bytes,
-        };
-        let _ = app.emit("download-progress", progress);
-    })
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<String, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    verify_and_repair_modpack(&manifest, &game_dir, move |progress| emit_download_progress(&app, progress))
     .await
     .map(|_| "Modpack verification and repair complete".to_string())
-    .map_err(|e| e.to_string())
+    .map_err(LauncherError::from)
+}
+
+/// Copy modpack files from a Prism, MultiMC or CurseForge instance instead of downloading them
+///
+/// Files are matched to the manifest by hash; the report lists what still needs downloading,
+/// which the normal install then fetches.
+#[tauri::command]
+async fn cmd_import_installation(
+    source: PathBuf,
+    manifest: Manifest,
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<ImportReport, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    if is_game_running().await {
+        return Err("Close Minecraft before importing an installation".into());
+    }
+    import_installation(&source, &manifest, &game_dir)
+        .await
+        .map_err(LauncherError::from)
+}
+
+/// Zip the installed modpack as a Prism Launcher or MultiMC instance at `path`
+///
+/// The instance doesn't update itself; players re-export after each release.
+#[tauri::command]
+async fn cmd_export_instance(
+    target_format: InstanceFormat,
+    path: PathBuf,
+    manifest: Manifest,
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<ExportSummary, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    export_instance(&manifest, &game_dir, target_format, &path)
+        .await
+        .map_err(LauncherError::from)
 }
 
+/// Check the installation against the manifest and report missing, corrupted and extra files
+///
+/// Nothing is downloaded or deleted unless `repair` is set; the report then describes
+/// what was fixed. `deep` rehashes every file instead of trusting unchanged ones.
 #[tauri::command]
-async fn cmd_has_manifest_changed(manifest: Manifest, game_dir: PathBuf) -> Result<bool, String> {
+async fn cmd_verify_installation(
+    app: AppHandle,
+    manifest: Manifest,
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    repair: Option<bool>,
+    deep: Option<bool>,
+) -> Result<VerificationReport, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let mut report = verify_installation(&manifest, &game_dir, deep.unwrap_or(false))
+        .await?;
+
+    if !repair.unwrap_or(false) || report.is_clean() {
+        return Ok(report);
+    }
+    if is_game_running().await {
+        return Err("Close Minecraft before repairing the installation".into());
+    }
+
+    verify_and_repair_modpack(&manifest, &game_dir, move |progress| emit_download_progress(&app, progress))
+    .await?;
+
+    report.repaired = true;
+    Ok(report)
+}
+
+#[tauri::command]
+async fn cmd_has_manifest_changed(
+    manifest: Manifest,
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<bool, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
     has_manifest_changed(&manifest, &game_dir)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
+}
+
+// Download Control Commands
+#[tauri::command]
+async fn cmd_get_download_state() -> Result<DownloadControlState, LauncherError> {
+    Ok(BANDWIDTH.state())
+}
+
+#[tauri::command]
+async fn cmd_set_download_limit(max_bytes_per_sec: u64) -> Result<DownloadControlState, LauncherError> {
+    set_download_limit(max_bytes_per_sec)
+        .await
+        .map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_pause_downloads() -> Result<DownloadControlState, LauncherError> {
+    BANDWIDTH.pause();
+    Ok(BANDWIDTH.state())
+}
+
+#[tauri::command]
+async fn cmd_resume_downloads() -> Result<DownloadControlState, LauncherError> {
+    BANDWIDTH.resume();
+    Ok(BANDWIDTH.state())
+}
+
+/// Stop the running Minecraft install and modpack update; partial downloads are kept for resume
+#[tauri::command]
+async fn cmd_cancel_installation() -> Result<(), LauncherError> {
+    cancel_installation();
+    Ok(())
+}
+
+// Optional Mod Commands
+#[tauri::command]
+async fn cmd_get_optional_groups(
+    manifest: Manifest,
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<Vec<OptionalGroupState>, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    group_states(&manifest, &game_dir)
+        .await
+        .map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_set_optional_group(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    group_id: String,
+    enabled: bool,
+) -> Result<(), LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    set_optional_group(&game_dir, &group_id, enabled)
+        .await
+        .map_err(LauncherError::from)
 }
 
 // Audio Commands
 #[tauri::command]
-async fn cmd_get_cached_audio(app: AppHandle) -> Result<Option<String>, String> {
+async fn cmd_get_cached_audio(app: AppHandle) -> Result<Option<String>, LauncherError> {
     get_cached_audio(&app)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_download_and_cache_audio(app: AppHandle, url: String) -> Result<String, String> {
-    download_and_cache_audio(&app, url)
-        .await
-        .map_err(|e| e.to_string())
+async fn cmd_download_and_cache_audio(app: AppHandle, url: String) -> Result<String, LauncherError> {
+    let app_handle = app.clone();
+    download_and_cache_audio(&app, url, move |downloaded, total| {
+        let _ = app_handle.emit("audio-download-progress", serde_json::json!({
+            "downloaded": downloaded,
+            "total": total,
+        }));
+    })
+    .await
+    .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_read_cached_audio_bytes(app: AppHandle) -> Result<Option<Vec<u8>>, String> {
+async fn cmd_cancel_audio_download() -> Result<(), LauncherError> {
+    cancel_audio_downloads();
+    Ok(())
+}
+
+#[tauri::command]
+async fn cmd_read_cached_audio_bytes(app: AppHandle) -> Result<Option<Vec<u8>>, LauncherError> {
     read_cached_audio_bytes(&app)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_clear_audio_cache(app: AppHandle) -> Result<(), String> {
+async fn cmd_clear_audio_cache(app: AppHandle) -> Result<(), LauncherError> {
     clear_audio_cache(&app)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
-// Game Control Commands
 #[tauri::command]
-async fn cmd_stop_game() -> Result<(), String> {
-    stop_game()
+async fn cmd_load_audio_playlist(server_url: String, shuffle: Option<bool>) -> Result<Option<AudioTrack>, LauncherError> {
+    load_playlist(&server_url, shuffle.unwrap_or(false))
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_next_audio_track() -> Result<Option<AudioTrack>, LauncherError> {
+    next_track().await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_previous_audio_track() -> Result<Option<AudioTrack>, LauncherError> {
+    previous_track().await.map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_set_audio_shuffle(enabled: bool) -> Result<(), LauncherError> {
+    set_shuffle(enabled).await;
+    Ok(())
+}
+
+// Game Control Commands
+#[tauri::command]
+async fn cmd_stop_game(app: AppHandle, grace_secs: Option<u64>) -> Result<bool, LauncherError> {
+    let grace_secs = grace_secs.unwrap_or(DEFAULT_STOP_GRACE_SECS);
+    stop_game(std::time::Duration::from_secs(grace_secs), move |phase| {
+        let _ = app.emit("game-stop-progress", serde_json::json!({
+            "phase": phase,
+            "grace_secs": grace_secs
+        }));
+    })
+    .await
+    .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn cmd_kill_game() -> Result<(), String> {
+async fn cmd_kill_game() -> Result<(), LauncherError> {
     kill_game()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
@@ -706,83 +1647,149 @@ async fn cmd_is_game_running() -> bool {
 
 // Log Reading Commands
 #[tauri::command]
-fn cmd_read_latest_log(game_dir: String, lines: usize) -> Result<Vec<String>, String> {
+async fn cmd_read_latest_log(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    lines: usize,
+) -> Result<Vec<String>, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let game_dir = game_dir.to_string_lossy();
     read_latest_log(&game_dir, lines)
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-fn cmd_get_log_path(game_dir: String) -> String {
-    get_log_path(&game_dir)
+async fn cmd_get_log_path(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+) -> Result<String, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let game_dir = game_dir.to_string_lossy();
+    Ok(get_log_path(&game_dir)
         .to_string_lossy()
-        .to_string()
+        .to_string())
 }
 
+/// Stream latest.log as `minecraft-log-structured` events, starting at `start_offset`
+/// (end of file when omitted)
 #[tauri::command]
-fn cmd_get_new_log_lines(game_dir: String, known_line_count: usize) -> Result<Vec<String>, String> {
-    get_new_log_lines(&game_dir, known_line_count)
-        .map_err(|e| e.to_string())
+async fn cmd_start_log_watcher(
+    app: AppHandle,
+    watcher: State<'_, LogWatcher>,
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    start_offset: Option<u64>,
+) -> Result<(), LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let game_dir = game_dir.to_string_lossy();
+    watcher
+        .start(app, &game_dir, start_offset)
+        .await
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-fn cmd_read_log_tail(game_dir: String, lines: usize) -> Result<LogResult, String> {
-    read_log_tail(&game_dir, lines).map_err(|e| e.to_string())
+async fn cmd_stop_log_watcher(watcher: State<'_, LogWatcher>) -> Result<(), LauncherError> {
+    watcher.stop().await;
+    Ok(())
 }
 
+/// Subscribe to the modpack server's release and launcher notifications,
+/// forwarded as `server-event` events
 #[tauri::command]
-fn cmd_read_log_from_offset(game_dir: String, start_offset: u64) -> Result<LogResult, String> {
-    read_log_from_offset(&game_dir, start_offset).map_err(|e| e.to_string())
+async fn cmd_start_server_events(
+    app: AppHandle,
+    events: State<'_, ServerEvents>,
+    server_url: String,
+) -> Result<(), LauncherError> {
+    events.start(app, server_url).await;
+    Ok(())
+}
+
+#[tauri::command]
+async fn cmd_stop_server_events(events: State<'_, ServerEvents>) -> Result<(), LauncherError> {
+    events.stop().await;
+    Ok(())
 }
 
 #[tauri::command]
-fn cmd_read_log_before_offset(game_dir: String, end_offset: u64, lines: usize) -> Result<LogResult, String> {
-    read_log_before_offset(&game_dir, end_offset, lines).map_err(|e| e.to_string())
+async fn cmd_read_log_tail(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    lines: usize,
+) -> Result<LogResult, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let game_dir = game_dir.to_string_lossy();
+    read_log_tail(&game_dir, lines).map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_read_log_from_offset(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    start_offset: u64,
+) -> Result<LogResult, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let game_dir = game_dir.to_string_lossy();
+    read_log_from_offset(&game_dir, start_offset).map_err(LauncherError::from)
+}
+
+#[tauri::command]
+async fn cmd_read_log_before_offset(
+    game_dir: Option<PathBuf>,
+    instance_id: Option<String>,
+    end_offset: u64,
+    lines: usize,
+) -> Result<LogResult, LauncherError> {
+    let game_dir = resolve_game_dir(instance_id.as_deref(), game_dir).await?;
+    let game_dir = game_dir.to_string_lossy();
+    read_log_before_offset(&game_dir, end_offset, lines).map_err(LauncherError::from)
 }
 
 // Path Management Commands
 #[tauri::command]
-fn cmd_get_default_game_directory(app: AppHandle) -> Result<String, String> {
+fn cmd_get_default_game_directory(app: AppHandle) -> Result<String, LauncherError> {
     get_default_game_directory(&app)
         .map(|p| p.to_string_lossy().to_string())
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-fn cmd_resolve_game_directory(app: AppHandle, path: String) -> Result<String, String> {
+fn cmd_resolve_game_directory(app: AppHandle, path: String) -> Result<String, LauncherError> {
     let path_buf = PathBuf::from(path);
     resolve_game_directory(&app, &path_buf)
         .map(|p| p.to_string_lossy().to_string())
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-fn cmd_validate_game_directory(path: String) -> Result<(), String> {
+fn cmd_validate_game_directory(path: String) -> Result<(), LauncherError> {
     let path_buf = PathBuf::from(path);
     validate_game_directory(&path_buf)
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 // VPN Commands (Cross-platform)
 #[tauri::command]
-async fn vpn_generate_keypair() -> Result<(String, String), String> {
+async fn vpn_generate_keypair() -> Result<(String, String), LauncherError> {
     VpnManager::generate_keypair()
-        .map_err(|e| e.to_string())
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn vpn_has_keypair() -> Result<bool, String> {
-    let manager = VpnManager::new().map_err(|e| e.to_string())?;
+async fn vpn_has_keypair() -> Result<bool, LauncherError> {
+    let manager = VpnManager::new()?;
     Ok(manager.has_keypair())
 }
 
 #[tauri::command]
-async fn vpn_check_wireguard_installed() -> Result<bool, String> {
+async fn vpn_check_wireguard_installed() -> Result<bool, LauncherError> {
     Ok(VpnManager::is_wireguard_installed())
 }
 
 #[cfg(target_os = "windows")]
 #[tauri::command]
-async fn vpn_install_wireguard_windows(app: tauri::AppHandle) -> Result<(), String> {
+async fn vpn_install_wireguard_windows(app: tauri::AppHandle) -> Result<(), LauncherError> {
     use std::process::Command;
     use std::path::PathBuf;
 
@@ -880,132 +1887,92 @@ async fn vpn_install_wireguard_windows(app: tauri::AppHandle) -> Result<(), Stri
             &format!("Start-Process -FilePath '{}' -Verb RunAs -Wait", installer_path)
         ])
         .spawn()
-        .map_err(|e| format!("Failed to launch installer: {}", e))?
+        .context("Failed to launch installer")?
         .wait()
-        .map_err(|e| format!("Installer process error: {}", e))?;
+        .context("Installer process error")?;
 
     if status.success() {
         eprintln!("[VPN Installer] Installer completed successfully");
         Ok(())
     } else {
-        Err(format!("Installer exited with code: {:?}", status.code()))
+        Err(format!("Installer exited with code: {:?}", status.code()).into())
     }
 }
 
 #[cfg(not(target_os = "windows"))]
 #[tauri::command]
-async fn vpn_install_wireguard_windows(_app: tauri::AppHandle) -> Result<(), String> {
-    Err("This command is only available on Windows".to_string())
+async fn vpn_install_wireguard_windows(_app: tauri::AppHandle) -> Result<(), LauncherError> {
+    Err("This command is only available on Windows".into())
 }
 
 #[tauri::command]
-async fn vpn_tunnel_status() -> Result<String, String> {
-    let manager = VpnManager::new().map_err(|e| e.to_string())?;
-
-    if manager.tunnel_exists() {
-        if manager.is_tunnel_running() {
-            Ok("running".to_string())
-        } else {
-            Ok("stopped".to_string())
-        }
-    } else {
-        Ok("not_installed".to_string())
-    }
+async fn vpn_tunnel_status() -> Result<String, LauncherError> {
+    let manager = VpnManager::new()?;
+    Ok(manager.tunnel_state().to_string())
 }
 
 #[tauri::command]
-async fn vpn_start_tunnel() -> Result<(), String> {
+async fn vpn_start_tunnel() -> Result<(), LauncherError> {
     // Check if WireGuard is installed first
     if !VpnManager::is_wireguard_installed() {
-        #[cfg(target_os = "windows")]
-        return Err("WireGuard is not installed. Please install WireGuard from the bundled installer.".to_string());
-
-        #[cfg(target_os = "linux")]
-        return Err("WireGuard is not installed. Please install it using your package manager:\n\nArch/Manjaro: sudo pacman -S wireguard-tools\nUbuntu/Debian: sudo apt install wireguard-tools\nFedora: sudo dnf install wireguard-tools".to_string());
-
-        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
-        return Err("WireGuard is not installed or unsupported platform.".to_string());
+        return Err(VpnManager::install_hint().into());
     }
 
-    let manager = VpnManager::new().map_err(|e| e.to_string())?;
-    manager.start_tunnel().map_err(|e| e.to_string())
+    let manager = VpnManager::new()?;
+    manager.start_tunnel().map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn vpn_stop_tunnel() -> Result<(), String> {
+async fn vpn_stop_tunnel() -> Result<(), LauncherError> {
     // Check if WireGuard is installed first
     if !VpnManager::is_wireguard_installed() {
         // Don't error on stop if WireGuard isn't installed - just succeed silently
         return Ok(());
     }
 
-    let manager = VpnManager::new().map_err(|e| e.to_string())?;
-    manager.stop_tunnel().map_err(|e| e.to_string())
+    let manager = VpnManager::new()?;
+    manager.stop_tunnel().map_err(LauncherError::from)
 }
 
+/// Register this device with the VPN server as the logged-in player and
+/// write the tunnel config
 #[tauri::command]
-async fn vpn_register_with_server(public_key: String, manifest_url: String) -> Result<serde_json::Value, String> {
+async fn cmd_vpn_register(manifest_url: String) -> Result<VpnRegistration, LauncherError> {
     use modules::auth::get_current_user;
 
-    // Get current Minecraft profile
-    let profile_option = get_current_user()
-        .map_err(|e| format!("Authentication error: {}", e))?;
-
-    let profile = profile_option
-        .ok_or_else(|| "Not logged in. Please log in with Microsoft first.".to_string())?;
+    let profile = get_current_user()
+        .context("Authentication error")?
+        .ok_or_else(|| LauncherError::new(ErrorKind::NotSignedIn, "Not logged in. Please log in with Microsoft first."))?;
 
-    // Derive base URL from manifest URL
     let base_url = manifest_url.trim_end_matches("/api/manifest/latest");
-
-    // Call server registration API
-    let client = reqwest::Client::new();
-    let response = client
-        .post(format!("{}/api/vpn/register", base_url))
-        .json(&serde_json::json!({
-            "minecraft_uuid": profile.uuid,
-            "minecraft_username": profile.username,
-            "public_key": public_key,
-            "auth_token": profile.session_id
-        }))
-        .send()
+    let manager = VpnManager::new()?;
+    manager
+        .register(base_url, &profile)
         .await
-        .map_err(|e| format!("Failed to connect to VPN server: {}", e))?;
+        .map_err(LauncherError::from)
+}
 
-    if !response.status().is_success() {
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("Server returned error: {}", error_text));
+#[tauri::command]
+async fn cmd_vpn_connect() -> Result<(), LauncherError> {
+    if !VpnManager::is_wireguard_installed() {
+        return Err(VpnManager::install_hint().into());
     }
 
-    let result: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse server response: {}", e))?;
-
-    Ok(result)
+    let manager = VpnManager::new()?;
+    tokio::task::spawn_blocking(move || manager.connect())
+        .await?
+        .map_err(LauncherError::from)
 }
 
 #[tauri::command]
-async fn vpn_write_config(
-    private_key: String,
-    assigned_ip: String,
-    server_public_key: String,
-    endpoint: String,
-) -> Result<(), String> {
-    let manager = VpnManager::new().map_err(|e| e.to_string())?;
-
-    // Store keypair
-    manager.store_keypair(&private_key, &server_public_key).map_err(|e| e.to_string())?;
-
-    // Generate WireGuard config
-    let config_content = format!(
-        "[Interface]\nPrivateKey = {}\nAddress = {}/24\nDNS = 1.1.1.1\n\n[Peer]\nPublicKey = {}\nEndpoint = {}\nAllowedIPs = 10.8.0.0/24\nPersistentKeepalive = 25",
-        private_key, assigned_ip, server_public_key, endpoint
-    );
-
-    // Write config file
-    manager.write_config(&config_content).map_err(|e| e.to_string())?;
+async fn cmd_vpn_disconnect() -> Result<(), LauncherError> {
+    vpn_stop_tunnel().await
+}
 
-    Ok(())
+#[tauri::command]
+async fn cmd_vpn_status() -> Result<VpnStatus, LauncherError> {
+    let manager = VpnManager::new()?;
+    manager.status().map_err(LauncherError::from)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -1013,12 +1980,22 @@ pub fn run() {
     // Initialize logger on startup
     initialize_logger();
 
+    let discord = DiscordClient::new();
     let mut builder = tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_os::init())
-        .manage(DiscordClient::new());
+        .manage(PresenceService::new(discord.clone()))
+        .manage(discord)
+        .manage(OverlayWriter::new())
+        .manage(LogWatcher::new())
+        .manage(ServerEvents::new())
+        .manage(SessionRefresher::new())
+        .setup(|app| {
+            app.state::<SessionRefresher>().start_maintenance(app.handle().clone());
+            Ok(())
+        });
 
     #[cfg(target_os = "windows")]
     {
@@ -1029,6 +2006,8 @@ pub fn run() {
             cmd_logout,
             cmd_get_device_code,
             cmd_complete_device_code_auth,
+            cmd_demo_accounts_enabled,
+            cmd_sign_in_demo,
             cmd_fetch_avatar,
             cmd_is_avatar_cached,
             cmd_read_cached_avatar,
@@ -1041,6 +2020,8 @@ pub fn run() {
             cmd_get_latest_snapshot,
             cmd_get_fabric_loaders,
             cmd_get_latest_fabric_loader,
+            cmd_get_loader_versions,
+            cmd_get_latest_loader_version,
             cmd_install_minecraft,
             cmd_is_version_installed,
             cmd_ping_server,
@@ -1054,6 +2035,9 @@ pub fn run() {
             cmd_set_installed_version,
             cmd_install_modpack,
             cmd_verify_and_repair_modpack,
+            cmd_verify_installation,
+            cmd_import_installation,
+            cmd_export_instance,
             cmd_has_manifest_changed,
             cmd_discord_connect,
             cmd_discord_set_presence,
@@ -1063,14 +2047,20 @@ pub fn run() {
             cmd_discord_is_connected,
             cmd_get_cached_audio,
             cmd_download_and_cache_audio,
+            cmd_cancel_audio_download,
             cmd_read_cached_audio_bytes,
             cmd_clear_audio_cache,
+            cmd_load_audio_playlist,
+            cmd_next_audio_track,
+            cmd_previous_audio_track,
+            cmd_set_audio_shuffle,
             cmd_stop_game,
             cmd_kill_game,
             cmd_is_game_running,
             cmd_read_latest_log,
             cmd_get_log_path,
-            cmd_get_new_log_lines,
+            cmd_start_log_watcher,
+            cmd_stop_log_watcher,
             cmd_read_log_tail,
             cmd_read_log_from_offset,
             cmd_read_log_before_offset,
@@ -1090,8 +2080,63 @@ pub fn run() {
             vpn_tunnel_status,
             vpn_start_tunnel,
             vpn_stop_tunnel,
-            vpn_register_with_server,
-            vpn_write_config
+            cmd_overlay_configure,
+            cmd_overlay_get_settings,
+            cmd_overlay_get_state,
+            cmd_overlay_set_pack_version,
+            cmd_overlay_set_player_count,
+            cmd_export_settings,
+            cmd_import_settings,
+            cmd_rollback_modpack,
+            cmd_launch_offline,
+            cmd_detect_java_installations,
+            cmd_validate_java,
+            cmd_list_jvm_profiles,
+            cmd_load_jvm_profile,
+            cmd_save_jvm_profile,
+            cmd_delete_jvm_profile,
+            cmd_validate_jvm_settings,
+            cmd_upload_crash_report,
+            cmd_get_system_report,
+            cmd_upload_system_report,
+            cmd_list_screenshots,
+            cmd_delete_screenshot,
+            cmd_open_screenshot_folder,
+            cmd_upload_screenshot,
+            cmd_list_worlds,
+            cmd_backup_world,
+            cmd_list_backups,
+            cmd_restore_backup,
+            cmd_get_disk_usage,
+            cmd_clean_logs,
+            cmd_clean_crash_reports,
+            cmd_clean_unused_assets,
+            cmd_check_mod_conflicts,
+            cmd_get_cms_config,
+            cmd_list_instances,
+            cmd_create_instance,
+            cmd_clone_instance,
+            cmd_update_instance,
+            cmd_delete_instance,
+            cmd_set_active_instance,
+            cmd_get_optional_groups,
+            cmd_set_optional_group,
+            cmd_get_download_state,
+            cmd_set_download_limit,
+            cmd_pause_downloads,
+            cmd_resume_downloads,
+            cmd_cancel_installation,
+            cmd_start_server_events,
+            cmd_stop_server_events,
+            cmd_vpn_register,
+            cmd_vpn_connect,
+            cmd_vpn_disconnect,
+            cmd_vpn_status,
+            cmd_get_player_sessions,
+            cmd_get_stat_leaderboard,
+            cmd_get_player_advancements,
+            cmd_send_chat_message,
+            cmd_get_player_skin
         ]);
     }
 
@@ -1104,6 +2149,8 @@ pub fn run() {
             cmd_logout,
             cmd_get_device_code,
             cmd_complete_device_code_auth,
+            cmd_demo_accounts_enabled,
+            cmd_sign_in_demo,
             cmd_fetch_avatar,
             cmd_is_avatar_cached,
             cmd_read_cached_avatar,
@@ -1116,6 +2163,8 @@ pub fn run() {
             cmd_get_latest_snapshot,
             cmd_get_fabric_loaders,
             cmd_get_latest_fabric_loader,
+            cmd_get_loader_versions,
+            cmd_get_latest_loader_version,
             cmd_install_minecraft,
             cmd_is_version_installed,
             cmd_ping_server,
@@ -1129,6 +2178,9 @@ pub fn run() {
             cmd_set_installed_version,
             cmd_install_modpack,
             cmd_verify_and_repair_modpack,
+            cmd_verify_installation,
+            cmd_import_installation,
+            cmd_export_instance,
             cmd_has_manifest_changed,
             cmd_discord_connect,
             cmd_discord_set_presence,
@@ -1138,14 +2190,20 @@ pub fn run() {
             cmd_discord_is_connected,
             cmd_get_cached_audio,
             cmd_download_and_cache_audio,
+            cmd_cancel_audio_download,
             cmd_read_cached_audio_bytes,
             cmd_clear_audio_cache,
+            cmd_load_audio_playlist,
+            cmd_next_audio_track,
+            cmd_previous_audio_track,
+            cmd_set_audio_shuffle,
             cmd_stop_game,
             cmd_kill_game,
             cmd_is_game_running,
             cmd_read_latest_log,
             cmd_get_log_path,
-            cmd_get_new_log_lines,
+            cmd_start_log_watcher,
+            cmd_stop_log_watcher,
             cmd_read_log_tail,
             cmd_read_log_from_offset,
             cmd_read_log_before_offset,
@@ -1171,8 +2229,63 @@ pub fn run() {
             vpn_tunnel_status,
             vpn_start_tunnel,
             vpn_stop_tunnel,
-            vpn_register_with_server,
-            vpn_write_config
+            cmd_overlay_configure,
+            cmd_overlay_get_settings,
+            cmd_overlay_get_state,
+            cmd_overlay_set_pack_version,
+            cmd_overlay_set_player_count,
+            cmd_export_settings,
+            cmd_import_settings,
+            cmd_rollback_modpack,
+            cmd_launch_offline,
+            cmd_detect_java_installations,
+            cmd_validate_java,
+            cmd_list_jvm_profiles,
+            cmd_load_jvm_profile,
+            cmd_save_jvm_profile,
+            cmd_delete_jvm_profile,
+            cmd_validate_jvm_settings,
+            cmd_upload_crash_report,
+            cmd_get_system_report,
+            cmd_upload_system_report,
+            cmd_list_screenshots,
+            cmd_delete_screenshot,
+            cmd_open_screenshot_folder,
+            cmd_upload_screenshot,
+            cmd_list_worlds,
+            cmd_backup_world,
+            cmd_list_backups,
+            cmd_restore_backup,
+            cmd_get_disk_usage,
+            cmd_clean_logs,
+            cmd_clean_crash_reports,
+            cmd_clean_unused_assets,
+            cmd_check_mod_conflicts,
+            cmd_get_cms_config,
+            cmd_list_instances,
+            cmd_create_instance,
+            cmd_clone_instance,
+            cmd_update_instance,
+            cmd_delete_instance,
+            cmd_set_active_instance,
+            cmd_get_optional_groups,
+            cmd_set_optional_group,
+            cmd_get_download_state,
+            cmd_set_download_limit,
+            cmd_pause_downloads,
+            cmd_resume_downloads,
+            cmd_cancel_installation,
+            cmd_start_server_events,
+            cmd_stop_server_events,
+            cmd_vpn_register,
+            cmd_vpn_connect,
+            cmd_vpn_disconnect,
+            cmd_vpn_status,
+            cmd_get_player_sessions,
+            cmd_get_stat_leaderboard,
+            cmd_get_player_advancements,
+            cmd_send_chat_message,
+            cmd_get_player_skin
         ]);
     }
 
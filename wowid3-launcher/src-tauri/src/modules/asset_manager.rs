@@ -1,12 +1,10 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use sha1::{Digest, Sha1};
-use std::collections::HashMap;
-use std::path::Path;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
-use super::download_manager::{DownloadManager, DownloadPriority, DownloadTask, HashType};
+use super::download_manager::{DownloadPriority, DownloadTask, HashType};
 use super::minecraft_version::AssetIndex as AssetIndexMeta;
 
 const ASSETS_BASE_URL: &str = "https://resources.download.minecraft.net";
@@ -15,6 +13,12 @@ const ASSETS_BASE_URL: &str = "https://resources.download.minecraft.net";
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetIndex {
     pub objects: HashMap<String, AssetObject>,
+    /// 1.6-1.7.2 (`legacy` index): the game reads assets by name from `assets/virtual/<id>`
+    #[serde(default, rename = "virtual", skip_serializing_if = "std::ops::Not::not")]
+    pub is_virtual: bool,
+    /// Before 1.6 (`pre-1.6` index): assets are read by name from `<game_dir>/resources`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub map_to_resources: bool,
 }
 
 /// Individual asset object
@@ -82,116 +86,105 @@ fn verify_sha1_string(content: &str, expected: &str) -> bool {
     hash == expected
 }
 
-
-/// Verify SHA1 hash of bytes
-fn verify_sha1_bytes(bytes: &[u8], expected: &str) -> bool {
-    let mut hasher = Sha1::new();
-    hasher.update(bytes);
-    let hash = format!("{:x}", hasher.finalize());
-    hash == expected
-}
-
-/// Download all assets with progress reporting using DownloadManager
-pub async fn download_all_assets<F>(
-    asset_index: &AssetIndex,
-    assets_dir: &Path,
-    progress_callback: F,
-) -> Result<()>
-where
-    F: FnMut(usize, usize, u64, u64, String) + Send + 'static,
-{
-    tokio::fs::create_dir_all(assets_dir.join("objects")).await?;
-
-    let total = asset_index.objects.len();
-    let total_bytes: u64 = asset_index.objects.values().map(|obj| obj.size).sum();
-
-    // Collect all download tasks, filtering out already-downloaded assets
-    let mut download_tasks = Vec::new();
-    let assets_dir_path = assets_dir.to_path_buf();
+/// Download tasks for every object in the asset index
+///
+/// Nothing is checked on disk; see `retain_missing`.
+pub fn asset_download_tasks(asset_index: &AssetIndex, assets_dir: &Path) -> Vec<DownloadTask> {
+    let mut tasks: Vec<DownloadTask> = Vec::with_capacity(asset_index.objects.len());
+    let mut seen: HashSet<&str> = HashSet::new();
 
     for asset_object in asset_index.objects.values() {
         let hash = &asset_object.hash;
-        let subdir = &hash[0..2];
-        let object_dir = assets_dir_path.join("objects").join(subdir);
-        let dest = object_dir.join(hash);
-
-        // Skip if file exists and hash matches
-        if dest.exists() {
-            if let Ok(bytes) = tokio::fs::read(&dest).await {
-                if verify_sha1_bytes(&bytes, hash) {
-                    continue;
-                }
-            }
+        // Several asset names can share one object
+        if !seen.insert(hash) {
+            continue;
         }
 
-        // Create subdirectory
-        tokio::fs::create_dir_all(&object_dir).await?;
-
-        let url = format!("{}/{}/{}", ASSETS_BASE_URL, subdir, hash);
-        download_tasks.push(DownloadTask {
-            url,
-            dest,
+        let subdir = &hash[0..2];
+        tasks.push(DownloadTask {
+            url: format!("{}/{}/{}", ASSETS_BASE_URL, subdir, hash),
+            dest: assets_dir.join("objects").join(subdir).join(hash),
             expected_hash: HashType::Sha1(hash.clone()),
             priority: DownloadPriority::Medium,
             size: asset_object.size,
+            mirrors: Vec::new(),
         });
     }
 
-    if download_tasks.is_empty() {
+    tasks
+}
+
+/// Lay out assets by name for versions that predate the hashed object store
+///
+/// Call after the objects are downloaded. Copies whose size already matches are kept.
+pub async fn reconstruct_legacy_assets(
+    asset_index: &AssetIndex,
+    index_id: &str,
+    assets_dir: &Path,
+    game_dir: &Path,
+) -> Result<()> {
+    let mut targets = Vec::new();
+    if asset_index.is_virtual {
+        targets.push(assets_dir.join("virtual").join(index_id));
+    }
+    if asset_index.map_to_resources {
+        targets.push(game_dir.join("resources"));
+    }
+    if targets.is_empty() {
         return Ok(());
     }
 
-    // Track progress across all parallel downloads
-    let (progress_tx, mut progress_rx) =
-        tokio::sync::mpsc::channel::<super::download_manager::DownloadProgress>(1000);
-    let completed = Arc::new(Mutex::new(0usize));
-    let completed_bytes = Arc::new(Mutex::new(0u64));
-
-    // Wrap callback in Arc<Mutex<>> to make it thread-safe
-    let callback_mutex = Arc::new(Mutex::new(progress_callback));
-
-    // Spawn progress tracking task
-    let completed_clone = completed.clone();
-    let completed_bytes_clone = completed_bytes.clone();
-    let callback_clone = callback_mutex.clone();
-    let progress_task = tokio::spawn(async move {
-        while let Some(progress) = progress_rx.recv().await {
-            if progress.completed {
-                let mut comp = completed_clone.lock().await;
-                *comp += 1;
-                let mut bytes = completed_bytes_clone.lock().await;
-                *bytes += progress.total_bytes;
-                let current_completed = *comp;
-                let current_bytes = *bytes;
-                drop(comp);
-                drop(bytes);
-
-                let mut callback = callback_clone.lock().await;
-                callback(
-                    current_completed,
-                    total,
-                    current_bytes,
-                    total_bytes,
-                    "Downloading assets".to_string(),
-                );
+    let mut copied = 0;
+    for (name, asset_object) in &asset_index.objects {
+        let hash = &asset_object.hash;
+        let source = assets_dir.join("objects").join(&hash[0..2]).join(hash);
+
+        for target in &targets {
+            let dest = target.join(name);
+            let intact = tokio::fs::metadata(&dest)
+                .await
+                .is_ok_and(|m| m.len() == asset_object.size);
+            if intact {
+                continue;
             }
-        }
-    });
 
-    // Download all files in parallel using DownloadManager with higher concurrency
-    let concurrency = super::download_manager::calculate_optimal_concurrency();
-    let manager = DownloadManager::new(concurrency, 3)?;
-    manager
-        .download_files(download_tasks, Some(progress_tx))
-        .await
-        .context("Failed to download assets")?;
+            if let Some(parent) = dest.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::copy(&source, &dest)
+                .await
+                .with_context(|| format!("Failed to copy asset {}", name))?;
+            copied += 1;
+        }
+    }
 
-    // Wait for progress tracking to complete
-    progress_task.await?;
+    if copied > 0 {
+        eprintln!("[Assets] Copied {} legacy assets for index {}", copied, index_id);
+    }
 
     Ok(())
 }
 
+/// Directory the game reads named assets from (`${game_assets}`), relative to the game directory
+///
+/// Falls back to `assets` when the index hasn't been downloaded.
+pub async fn game_assets_dir(game_dir: &Path, index_id: &str) -> PathBuf {
+    let index_file = game_dir
+        .join("assets")
+        .join("indexes")
+        .join(format!("{}.json", index_id));
+
+    let index = tokio::fs::read_to_string(&index_file)
+        .await
+        .ok()
+        .and_then(|content| serde_json::from_str::<AssetIndex>(&content).ok());
+
+    match index {
+        Some(index) if index.map_to_resources => PathBuf::from("resources"),
+        Some(index) if index.is_virtual => PathBuf::from("assets").join("virtual").join(index_id),
+        _ => PathBuf::from("assets"),
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -204,13 +197,6 @@ mod tests {
         assert!(verify_sha1_string(content, hash));
     }
 
-    #[test]
-    fn test_verify_sha1_bytes() {
-        let content = b"Hello, World!";
-        let hash = "0a0a9f2a6772942557ab5355d76af442f8f65e01";
-        assert!(verify_sha1_bytes(content, hash));
-    }
-
     #[tokio::test]
     async fn test_asset_index_parse() {
         let json = r#"{
@@ -227,5 +213,38 @@ mod tests {
 
         let obj = index.objects.get("minecraft/sounds/ambient/cave/cave1.ogg").unwrap();
         assert_eq!(obj.size, 123456);
+        assert!(!index.is_virtual);
+        assert!(!index.map_to_resources);
+    }
+
+    #[tokio::test]
+    async fn test_reconstruct_legacy_assets() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let game_dir = temp_dir.path();
+        let assets_dir = game_dir.join("assets");
+
+        let hash = "0a0a9f2a6772942557ab5355d76af442f8f65e01";
+        let object_dir = assets_dir.join("objects").join(&hash[0..2]);
+        tokio::fs::create_dir_all(&object_dir).await.unwrap();
+        tokio::fs::write(object_dir.join(hash), "Hello, World!").await.unwrap();
+
+        let json = format!(
+            r#"{{"virtual": true, "objects": {{"sounds/step/grass1.ogg": {{"hash": "{}", "size": 13}}}}}}"#,
+            hash
+        );
+        let index: AssetIndex = serde_json::from_str(&json).unwrap();
+        tokio::fs::create_dir_all(assets_dir.join("indexes")).await.unwrap();
+        tokio::fs::write(assets_dir.join("indexes").join("legacy.json"), &json).await.unwrap();
+
+        reconstruct_legacy_assets(&index, "legacy", &assets_dir, game_dir).await.unwrap();
+
+        let copy = assets_dir.join("virtual").join("legacy").join("sounds/step/grass1.ogg");
+        assert_eq!(tokio::fs::read_to_string(copy).await.unwrap(), "Hello, World!");
+        assert!(!game_dir.join("resources").exists());
+        assert_eq!(
+            game_assets_dir(game_dir, "legacy").await,
+            PathBuf::from("assets").join("virtual").join("legacy")
+        );
+        assert_eq!(game_assets_dir(game_dir, "1.12").await, PathBuf::from("assets"));
     }
 }
@@ -1,13 +1,46 @@
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use futures_util::StreamExt;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
+use tokio::sync::{Mutex, Notify};
+
+use super::http::{self, RetryPolicy, SendRetrying};
 
 const MAX_DOWNLOAD_RETRIES: u32 = 3;
 const RETRY_DELAY_MS: u64 = 1000;
 const MAX_AUDIO_SIZE_BYTES: u64 = 50 * 1024 * 1024; // 50 MB limit
 const AUDIO_CACHE_DIR: &str = "cache/audio";
+const PLAYLIST_FILE: &str = "playlist.json";
+/// Tracks are stored by hash, so a re-uploaded track never reuses a stale file
+const TRACKS_DIR: &str = "tracks";
+const PLAYLIST_FETCH_TIMEOUT_SECS: u64 = 10;
+
+lazy_static::lazy_static! {
+    static ref PLAYER: Mutex<Option<PlaylistPlayer>> = Mutex::new(None);
+    /// Wakes every in-flight audio download so it stops
+    static ref CANCEL_DOWNLOADS: Notify = Notify::new();
+}
+
+/// Returned (inside anyhow) when a download is stopped by `cancel_audio_downloads`
+#[derive(Debug)]
+pub struct DownloadCancelled;
+
+impl std::fmt::Display for DownloadCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Audio download cancelled")
+    }
+}
+
+impl std::error::Error for DownloadCancelled {}
+
+fn is_cancelled(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<DownloadCancelled>().is_some()
+}
 
 /// Get the cache directory for audio files
 fn get_cache_dir(_app_handle: &tauri::AppHandle) -> Result<PathBuf> {
@@ -47,10 +80,17 @@ pub async fn get_cached_audio(app_handle: &tauri::AppHandle) -> Result<Option<St
 }
 
 /// Download and cache audio file with retry logic
-pub async fn download_and_cache_audio(
+///
+/// `on_progress` is called with (bytes downloaded, total bytes) as the file streams to disk;
+/// total is 0 when the server doesn't send a length.
+pub async fn download_and_cache_audio<F>(
     app_handle: &tauri::AppHandle,
     url: String,
-) -> Result<String> {
+    on_progress: F,
+) -> Result<String>
+where
+    F: Fn(u64, u64) + Send + Sync,
+{
     eprintln!("[Audio] Starting download from: {}", url);
 
     let cache_dir = get_cache_dir(app_handle)?;
@@ -64,12 +104,12 @@ pub async fn download_and_cache_audio(
     // Try downloading with retries
     let mut retries = 0;
     loop {
-        match download_audio_file(&url, &temp_file).await {
+        match download_audio_file(&url, &temp_file, &on_progress).await {
             Ok(file_size) => {
                 eprintln!("[Audio] Download successful: {} bytes", file_size);
 
                 // Verify file size
-                if file_size < 1024 * 1024 || file_size > MAX_AUDIO_SIZE_BYTES {
+                if file_size < 1024 * 1024 {
                     eprintln!(
                         "[Audio] Downloaded file has invalid size: {} bytes",
                         file_size
@@ -86,6 +126,11 @@ pub async fn download_and_cache_audio(
                 eprintln!("[Audio] Cached audio at: {}", audio_file.display());
                 return Ok(audio_file.to_string_lossy().to_string());
             }
+            Err(e) if is_cancelled(&e) => {
+                let _ = fs::remove_file(&temp_file).await;
+                eprintln!("[Audio] Download cancelled");
+                return Err(e);
+            }
             Err(e) => {
                 retries += 1;
                 if retries >= MAX_DOWNLOAD_RETRIES {
@@ -109,18 +154,27 @@ pub async fn download_and_cache_audio(
     }
 }
 
-/// Download audio file from URL
-async fn download_audio_file(url: &str, output_path: &PathBuf) -> Result<u64> {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(60))
-        .build()
-        .context("Failed to create HTTP client")?;
+/// Stop every audio download in progress; each fails with `DownloadCancelled`
+pub fn cancel_audio_downloads() {
+    eprintln!("[Audio] Cancelling audio downloads");
+    CANCEL_DOWNLOADS.notify_waiters();
+}
 
-    let response = client
-        .get(url)
-        .send()
-        .await
-        .context(format!("Failed to download audio from {}", url))?;
+/// Stream audio file from URL to disk
+async fn download_audio_file(url: &str, output_path: &Path, on_progress: &(dyn Fn(u64, u64) + Send + Sync)) -> Result<u64> {
+    // Registered before the request so a cancel during connect isn't missed
+    let cancelled = CANCEL_DOWNLOADS.notified();
+    tokio::pin!(cancelled);
+    cancelled.as_mut().enable();
+
+    // No whole-request timeout: a 30 MB track on a slow connection takes minutes, and
+    // the shared client's read timeout catches stalls
+    let response = tokio::select! {
+        response = http::client().get(url).send() => {
+            response.context(format!("Failed to download audio from {}", url))?
+        }
+        _ = &mut cancelled => return Err(DownloadCancelled.into()),
+    };
 
     if !response.status().is_success() {
         anyhow::bail!(
@@ -130,12 +184,10 @@ async fn download_audio_file(url: &str, output_path: &PathBuf) -> Result<u64> {
         );
     }
 
-    let bytes = response
-        .bytes()
-        .await
-        .context("Failed to read response bytes")?;
-
-    let file_size = bytes.len() as u64;
+    let total = response.content_length().unwrap_or(0);
+    if total > MAX_AUDIO_SIZE_BYTES {
+        anyhow::bail!("Audio file is too large: {} bytes", total);
+    }
 
     // Create parent directories if they don't exist
     if let Some(parent) = output_path.parent() {
@@ -149,9 +201,28 @@ async fn download_audio_file(url: &str, output_path: &PathBuf) -> Result<u64> {
         .await
         .context("Failed to create audio file")?;
 
-    f.write_all(&bytes)
-        .await
-        .context("Failed to write audio file contents")?;
+    let mut stream = response.bytes_stream();
+    let mut downloaded: u64 = 0;
+    on_progress(0, total);
+
+    loop {
+        let chunk = tokio::select! {
+            chunk = stream.next() => chunk,
+            _ = &mut cancelled => return Err(DownloadCancelled.into()),
+        };
+        let Some(chunk) = chunk else { break };
+        let chunk = chunk.context("Failed to read audio download")?;
+
+        downloaded += chunk.len() as u64;
+        if downloaded > MAX_AUDIO_SIZE_BYTES {
+            anyhow::bail!("Audio file exceeds {} bytes", MAX_AUDIO_SIZE_BYTES);
+        }
+
+        f.write_all(&chunk)
+            .await
+            .context("Failed to write audio file contents")?;
+        on_progress(downloaded, total);
+    }
 
     f.flush()
         .await
@@ -161,7 +232,7 @@ async fn download_audio_file(url: &str, output_path: &PathBuf) -> Result<u64> {
         .await
         .context("Failed to sync audio file to disk")?;
 
-    Ok(file_size)
+    Ok(downloaded)
 }
 
 /// Read cached audio file as bytes for Blob URL creation
@@ -211,3 +282,421 @@ pub async fn clear_audio_cache(app_handle: &tauri::AppHandle) -> Result<()> {
 
     Ok(())
 }
+
+/// A track as listed in the server's /api/assets/playlist.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaylistTrack {
+    pub filename: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Absolute, or relative to the server URL
+    pub url: String,
+    pub sha256: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AudioPlaylist {
+    #[serde(default)]
+    pub tracks: Vec<PlaylistTrack>,
+}
+
+/// A playlist track as returned to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioTrack {
+    /// Position in the server's playlist order
+    pub index: usize,
+    pub title: String,
+    /// Local file to play; `None` if it couldn't be downloaded
+    pub path: Option<String>,
+}
+
+/// Play order over the loaded playlist
+#[derive(Debug, Clone)]
+struct PlaylistPlayer {
+    server_url: String,
+    tracks: Vec<PlaylistTrack>,
+    /// Indexes into `tracks` in the order they play
+    order: Vec<usize>,
+    position: usize,
+    shuffle: bool,
+}
+
+impl PlaylistPlayer {
+    fn new(server_url: String, tracks: Vec<PlaylistTrack>, shuffle: bool) -> Self {
+        let mut player = Self {
+            server_url,
+            order: (0..tracks.len()).collect(),
+            tracks,
+            position: 0,
+            shuffle: false,
+        };
+        player.set_shuffle(shuffle);
+        player
+    }
+
+    fn current(&self) -> Option<usize> {
+        self.order.get(self.position).copied()
+    }
+
+    /// Move `step` tracks forward (or back), wrapping around; a new shuffled lap starts on wrap
+    fn advance(&mut self, step: isize) -> Option<usize> {
+        let len = self.order.len() as isize;
+        if len == 0 {
+            return None;
+        }
+
+        let next = self.position as isize + step;
+        if self.shuffle && next >= len {
+            let last = self.current();
+            self.order.shuffle(&mut rand::thread_rng());
+            // Don't play the same track twice in a row across laps
+            if len > 1 && self.order.first().copied() == last {
+                self.order.swap(0, 1);
+            }
+            self.position = 0;
+        } else {
+            self.position = next.rem_euclid(len) as usize;
+        }
+        self.current()
+    }
+
+    /// Shuffle the remaining tracks (keeping the current one playing) or restore playlist order
+    fn set_shuffle(&mut self, shuffle: bool) {
+        let current = self.current();
+        self.shuffle = shuffle;
+
+        if shuffle {
+            self.order.shuffle(&mut rand::thread_rng());
+            if let Some(current) = current {
+                let at = self.order.iter().position(|&i| i == current).unwrap_or(0);
+                self.order.swap(0, at);
+            }
+        } else {
+            self.order = (0..self.tracks.len()).collect();
+        }
+        self.position = current
+            .and_then(|c| self.order.iter().position(|&i| i == c))
+            .unwrap_or(0);
+    }
+
+    fn track(&self, index: usize, path: Option<String>) -> AudioTrack {
+        let track = &self.tracks[index];
+        AudioTrack {
+            index,
+            title: track.title.clone().unwrap_or_else(|| track.filename.clone()),
+            path,
+        }
+    }
+}
+
+fn playlist_cache_dir() -> Result<PathBuf> {
+    Ok(super::paths::get_persistent_data_dir()?.join(AUDIO_CACHE_DIR))
+}
+
+fn tracks_dir() -> Result<PathBuf> {
+    Ok(playlist_cache_dir()?.join(TRACKS_DIR))
+}
+
+/// Cache file for a track: `<sha256>.<ext>`
+fn track_path(dir: &Path, track: &PlaylistTrack) -> PathBuf {
+    let ext = track
+        .filename
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .filter(|ext| ext.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or_else(|| "mp3".to_string());
+    dir.join(format!("{}.{}", track.sha256, ext))
+}
+
+async fn file_sha256(path: &Path) -> Result<String> {
+    let data = fs::read(path).await.context("Failed to read audio track")?;
+    Ok(format!("{:x}", Sha256::digest(&data)))
+}
+
+/// Make sure a track is cached and intact, downloading it if needed
+async fn ensure_track(server_url: &str, track: &PlaylistTrack) -> Result<PathBuf> {
+    if track.size > MAX_AUDIO_SIZE_BYTES {
+        anyhow::bail!("Track {} exceeds the audio size limit", track.filename);
+    }
+
+    let dir = tracks_dir()?;
+    let path = track_path(&dir, track);
+    if path.exists() {
+        if file_sha256(&path).await? == track.sha256 {
+            return Ok(path);
+        }
+        eprintln!("[Audio] Cached track {} is corrupt, re-downloading", track.filename);
+        let _ = fs::remove_file(&path).await;
+    }
+
+    let url = if track.url.starts_with("http://") || track.url.starts_with("https://") {
+        track.url.clone()
+    } else {
+        format!("{}/{}", server_url.trim_end_matches('/'), track.url.trim_start_matches('/'))
+    };
+    // Unique temp name: a prefetch and a skip can download the same track at once
+    let temp = dir.join(format!("{}.{}.tmp", track.sha256, uuid::Uuid::new_v4()));
+
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let result = async {
+            download_audio_file(&url, &temp, &|_, _| {}).await?;
+            let sha256 = file_sha256(&temp).await?;
+            if sha256 != track.sha256 {
+                anyhow::bail!("Checksum mismatch for {} (expected {}, got {})", track.filename, track.sha256, sha256);
+            }
+            Ok(())
+        }
+        .await;
+
+        match result {
+            Ok(()) => break,
+            Err(e) if attempt < MAX_DOWNLOAD_RETRIES && !is_cancelled(&e) => {
+                eprintln!("[Audio] Track download failed (attempt {}/{}): {}", attempt, MAX_DOWNLOAD_RETRIES, e);
+                tokio::time::sleep(Duration::from_millis(RETRY_DELAY_MS * attempt as u64)).await;
+            }
+            Err(e) => {
+                let _ = fs::remove_file(&temp).await;
+                return Err(e);
+            }
+        }
+    }
+
+    fs::rename(&temp, &path)
+        .await
+        .context("Failed to move audio track into cache")?;
+    eprintln!("[Audio] Cached track {} at {}", track.filename, path.display());
+    Ok(path)
+}
+
+/// Fetch the playlist, falling back to the last copy fetched when offline
+async fn fetch_playlist(server_url: &str) -> Result<AudioPlaylist> {
+    let cache_file = playlist_cache_dir()?.join(PLAYLIST_FILE);
+
+    let url = format!("{}/api/assets/playlist.json", server_url.trim_end_matches('/'));
+
+    let fetched: Result<AudioPlaylist> = async {
+        let playlist = http::client()
+            .get(&url)
+            .timeout(Duration::from_secs(PLAYLIST_FETCH_TIMEOUT_SECS))
+            .send_retrying(RetryPolicy::default())
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(playlist)
+    }
+    .await;
+
+    match fetched {
+        Ok(playlist) => {
+            if let Some(parent) = cache_file.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            fs::write(&cache_file, serde_json::to_string(&playlist)?)
+                .await
+                .context("Failed to cache audio playlist")?;
+            Ok(playlist)
+        }
+        Err(e) => {
+            eprintln!("[Audio] Using cached playlist: {}", e);
+            if !cache_file.exists() {
+                return Ok(AudioPlaylist::default());
+            }
+            let json = fs::read_to_string(&cache_file).await?;
+            Ok(serde_json::from_str(&json).context("Failed to parse cached playlist")?)
+        }
+    }
+}
+
+/// Delete cached tracks that are no longer in the playlist
+async fn prune_tracks(playlist: &AudioPlaylist) -> Result<()> {
+    let dir = tracks_dir()?;
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    let keep: Vec<PathBuf> = playlist.tracks.iter().map(|t| track_path(&dir, t)).collect();
+    let mut entries = fs::read_dir(&dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if !keep.contains(&path) {
+            let _ = fs::remove_file(&path).await;
+        }
+    }
+    Ok(())
+}
+
+/// Download a track in the background so it's ready when the current one ends
+fn prefetch(server_url: String, track: PlaylistTrack) {
+    tokio::spawn(async move {
+        if let Err(e) = ensure_track(&server_url, &track).await {
+            eprintln!("[Audio] Failed to prefetch {}: {}", track.filename, e);
+        }
+    });
+}
+
+/// Resolve the player's current track, skipping tracks that can't be downloaded
+async fn play_current(mut player: tokio::sync::MutexGuard<'_, Option<PlaylistPlayer>>) -> Result<Option<AudioTrack>> {
+    let Some(state) = player.as_mut() else {
+        return Ok(None);
+    };
+
+    for _ in 0..state.order.len() {
+        let Some(index) = state.current() else {
+            return Ok(None);
+        };
+        let track = state.tracks[index].clone();
+        let server_url = state.server_url.clone();
+
+        match ensure_track(&server_url, &track).await {
+            Ok(path) => {
+                let result = state.track(index, Some(path.to_string_lossy().to_string()));
+                let upcoming = state.order[(state.position + 1) % state.order.len()];
+                if upcoming != index {
+                    prefetch(server_url, state.tracks[upcoming].clone());
+                }
+                return Ok(Some(result));
+            }
+            Err(e) => {
+                eprintln!("[Audio] Skipping {}: {}", track.filename, e);
+                state.advance(1);
+            }
+        }
+    }
+
+    anyhow::bail!("No playlist track could be downloaded")
+}
+
+/// Load the server playlist and return the track to start with (`None` if the playlist is empty)
+pub async fn load_playlist(server_url: &str, shuffle: bool) -> Result<Option<AudioTrack>> {
+    let playlist = fetch_playlist(server_url).await?;
+    if let Err(e) = prune_tracks(&playlist).await {
+        eprintln!("[Audio] Failed to prune cached tracks: {}", e);
+    }
+    eprintln!("[Audio] Playlist has {} tracks", playlist.tracks.len());
+
+    let mut player = PLAYER.lock().await;
+    *player = Some(PlaylistPlayer::new(server_url.to_string(), playlist.tracks, shuffle));
+    play_current(player).await
+}
+
+/// Skip to the next track (wraps around; reshuffles after each lap when shuffling)
+pub async fn next_track() -> Result<Option<AudioTrack>> {
+    let mut player = PLAYER.lock().await;
+    if let Some(state) = player.as_mut() {
+        state.advance(1);
+    }
+    play_current(player).await
+}
+
+/// Go back to the previous track
+pub async fn previous_track() -> Result<Option<AudioTrack>> {
+    let mut player = PLAYER.lock().await;
+    if let Some(state) = player.as_mut() {
+        state.advance(-1);
+    }
+    play_current(player).await
+}
+
+/// Turn shuffle on or off without interrupting the current track
+pub async fn set_shuffle(shuffle: bool) {
+    if let Some(state) = PLAYER.lock().await.as_mut() {
+        state.set_shuffle(shuffle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracks(count: usize) -> Vec<PlaylistTrack> {
+        (0..count)
+            .map(|i| PlaylistTrack {
+                filename: format!("track{}.ogg", i),
+                title: None,
+                url: format!("/api/assets-cms/track{}.ogg", i),
+                sha256: format!("{:064}", i),
+                size: 1024,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_playlist_order_wraps() {
+        let mut player = PlaylistPlayer::new(String::new(), tracks(3), false);
+        assert_eq!(player.current(), Some(0));
+        assert_eq!(player.advance(1), Some(1));
+        assert_eq!(player.advance(1), Some(2));
+        assert_eq!(player.advance(1), Some(0));
+        assert_eq!(player.advance(-1), Some(2));
+        assert_eq!(player.track(2, None).title, "track2.ogg");
+    }
+
+    #[test]
+    fn test_shuffle_keeps_current_track_and_covers_all() {
+        let mut player = PlaylistPlayer::new(String::new(), tracks(5), false);
+        player.advance(1);
+        player.set_shuffle(true);
+        assert_eq!(player.current(), Some(1));
+
+        // The current track plus the rest of the lap plays every track once
+        let mut lap: Vec<usize> = (0..4).map(|_| player.advance(1).unwrap()).collect();
+        lap.push(1);
+        lap.sort();
+        assert_eq!(lap, vec![0, 1, 2, 3, 4]);
+
+        player.set_shuffle(false);
+        let current = player.current().unwrap();
+        assert_eq!(player.advance(1), Some((current + 1) % 5));
+    }
+
+    #[tokio::test]
+    async fn test_download_streams_progress_and_cancels() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/track.mp3"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![7u8; 64 * 1024]))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/slow.mp3"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(30)))
+            .mount(&server)
+            .await;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let last = std::sync::Mutex::new((0, 0));
+        let size = download_audio_file(
+            &format!("{}/track.mp3", server.uri()),
+            &temp.path().join("track.mp3"),
+            &|downloaded, total| *last.lock().unwrap() = (downloaded, total),
+        )
+        .await
+        .unwrap();
+        assert_eq!(size, 64 * 1024);
+        assert_eq!(*last.lock().unwrap(), (size, size));
+
+        let url = format!("{}/slow.mp3", server.uri());
+        let output = temp.path().join("slow.mp3");
+        let download = download_audio_file(&url, &output, &|_, _| {});
+        let cancel = async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            cancel_audio_downloads();
+        };
+        let (result, _) = tokio::join!(download, cancel);
+        assert!(is_cancelled(&result.unwrap_err()));
+    }
+
+    #[test]
+    fn test_track_path_uses_hash() {
+        let track = &tracks(1)[0];
+        let path = track_path(Path::new("/cache"), track);
+        assert_eq!(path, Path::new("/cache").join(format!("{}.ogg", track.sha256)));
+    }
+}
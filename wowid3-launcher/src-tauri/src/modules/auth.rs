@@ -32,6 +32,7 @@ use std::path::PathBuf;
 use std::time::Duration as StdDuration;
 use uuid::Uuid;
 
+use super::http::{self, RetryPolicy, SendRetrying};
 use super::logger::{log_auth, log_storage};
 use super::encrypted_storage::{
     save_encrypted_profile, load_encrypted_profile, delete_encrypted_profile,
@@ -57,6 +58,59 @@ const MINECRAFT_AUTH_URL: &str = "https://api.minecraftservices.com/launcher/log
 const MINECRAFT_PROFILE_URL: &str = "https://api.minecraftservices.com/minecraft/profile";
 const MINECRAFT_ENTITLEMENTS_URL: &str = "https://api.minecraftservices.com/entitlements/mcstore";
 
+const AUTH_REQUEST_TIMEOUT: StdDuration = StdDuration::from_secs(30);
+
+/// Set to `1` to allow demo accounts, for pack developers testing without Microsoft sign-in
+const DEMO_ACCOUNTS_ENV: &str = "WOWID3_DEMO_ACCOUNTS";
+/// Access token stored for demo accounts; accepted by singleplayer and offline-mode servers
+const DEMO_ACCESS_TOKEN: &str = "demo";
+
+/// Returned (inside anyhow) when a Microsoft, Xbox Live or Minecraft service answers
+/// with a server error, as opposed to rejecting the account or its tokens
+#[derive(Debug)]
+pub struct AuthServiceUnavailable(pub String);
+
+impl std::fmt::Display for AuthServiceUnavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for AuthServiceUnavailable {}
+
+/// Returned (inside anyhow) when a command needs a signed-in player and there is none
+#[derive(Debug)]
+pub struct NotSignedIn;
+
+impl std::fmt::Display for NotSignedIn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "No user logged in")
+    }
+}
+
+impl std::error::Error for NotSignedIn {}
+
+/// Error for a failed auth response; 5xx marks the service as unavailable
+fn response_error(status: reqwest::StatusCode, message: String) -> anyhow::Error {
+    if status.is_server_error() {
+        AuthServiceUnavailable(message).into()
+    } else {
+        anyhow!(message)
+    }
+}
+
+/// Whether an auth failure is an outage (unreachable or 5xx) rather than a rejection
+///
+/// A refresh that fails this way leaves the stored session usable, just not refreshed.
+pub fn is_service_outage(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause.is::<AuthServiceUnavailable>()
+            || cause
+                .downcast_ref::<reqwest::Error>()
+                .is_some_and(|e| e.is_connect() || e.is_timeout() || e.is_request())
+    })
+}
+
 // Public profile (exposed to frontend - no tokens)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MinecraftProfile {
@@ -65,6 +119,9 @@ pub struct MinecraftProfile {
     pub session_id: String, // Session ID for token lookup
     pub skin_url: Option<String>,
     pub expires_at: Option<DateTime<Utc>>,
+    /// Username-only demo account; its session is never refreshed
+    #[serde(default)]
+    pub demo: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -219,10 +276,6 @@ struct ProfileInfo {
 
 /// Authenticate with Xbox Live using Microsoft token
 async fn authenticate_with_xbox_live(ms_access_token: &str) -> Result<(String, String)> {
-    let client = reqwest::Client::builder()
-        .timeout(StdDuration::from_secs(30))
-        .build()?;
-
     let request_body = XboxLiveAuthRequest {
         properties: XboxLiveProperties {
             auth_method: "RPS".to_string(),
@@ -233,22 +286,26 @@ async fn authenticate_with_xbox_live(ms_access_token: &str) -> Result<(String, S
         token_type: "JWT".to_string(),
     };
 
-    let response = client
+    let response = http::client()
         .post(XBOX_LIVE_AUTH_URL)
+        .timeout(AUTH_REQUEST_TIMEOUT)
         .json(&request_body)
         .header("Content-Type", "application/json")
         .header("Accept", "application/json")
-        .send()
+        .send_retrying(RetryPolicy::default())
         .await
         .context("Failed to send Xbox Live authentication request")?;
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(anyhow!(
-            "Xbox Live authentication failed with status {}: {}",
+        return Err(response_error(
             status,
-            text
+            format!(
+                "Xbox Live authentication failed with status {}: {}",
+                status,
+                text
+            ),
         ));
     }
 
@@ -270,10 +327,6 @@ async fn authenticate_with_xbox_live(ms_access_token: &str) -> Result<(String, S
 
 /// Get XSTS token using Xbox Live token
 async fn get_xsts_token(xbox_token: &str) -> Result<(String, String)> {
-    let client = reqwest::Client::builder()
-        .timeout(StdDuration::from_secs(30))
-        .build()?;
-
     let request_body = XSTSAuthRequest {
         properties: XSTSProperties {
             sandbox_id: "RETAIL".to_string(),
@@ -283,22 +336,26 @@ async fn get_xsts_token(xbox_token: &str) -> Result<(String, String)> {
         token_type: "JWT".to_string(),
     };
 
-    let response = client
+    let response = http::client()
         .post(XSTS_AUTH_URL)
+        .timeout(AUTH_REQUEST_TIMEOUT)
         .json(&request_body)
         .header("Content-Type", "application/json")
         .header("Accept", "application/json")
-        .send()
+        .send_retrying(RetryPolicy::default())
         .await
         .context("Failed to send XSTS authentication request")?;
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(anyhow!(
-            "XSTS authentication failed with status {}: {}. This may mean you don't have an Xbox account or need to accept Xbox terms.",
+        return Err(response_error(
             status,
-            text
+            format!(
+                "XSTS authentication failed with status {}: {}. This may mean you don't have an Xbox account or need to accept Xbox terms.",
+                status,
+                text
+            ),
         ));
     }
 
@@ -320,31 +377,31 @@ async fn get_xsts_token(xbox_token: &str) -> Result<(String, String)> {
 
 /// Authenticate with Minecraft using XSTS token
 async fn authenticate_minecraft_token(xsts_token: &str, user_hash: &str) -> Result<String> {
-    let client = reqwest::Client::builder()
-        .timeout(StdDuration::from_secs(30))
-        .build()?;
-
     let xtoken = format!("XBL3.0 x={};{}", user_hash, xsts_token);
     let request_body = MinecraftAuthRequest {
         xtoken,
         platform: "PC_LAUNCHER".to_string(),
     };
 
-    let response = client
+    let response = http::client()
         .post(MINECRAFT_AUTH_URL)
+        .timeout(AUTH_REQUEST_TIMEOUT)
         .json(&request_body)
         .header("Content-Type", "application/json")
-        .send()
+        .send_retrying(RetryPolicy::default())
         .await
         .context("Failed to send Minecraft authentication request")?;
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(anyhow!(
-            "Minecraft authentication failed with status {}: {}",
+        return Err(response_error(
             status,
-            text
+            format!(
+                "Minecraft authentication failed with status {}: {}",
+                status,
+                text
+            ),
         ));
     }
 
@@ -358,14 +415,11 @@ async fn authenticate_minecraft_token(xsts_token: &str, user_hash: &str) -> Resu
 
 /// Check if user owns Minecraft
 async fn check_minecraft_ownership(mc_access_token: &str) -> Result<bool> {
-    let client = reqwest::Client::builder()
-        .timeout(StdDuration::from_secs(30))
-        .build()?;
-
-    let response = client
+    let response = http::client()
         .get(MINECRAFT_ENTITLEMENTS_URL)
+        .timeout(AUTH_REQUEST_TIMEOUT)
         .bearer_auth(mc_access_token)
-        .send()
+        .send_retrying(RetryPolicy::default())
         .await
         .context("Failed to check Minecraft ownership")?;
 
@@ -387,24 +441,24 @@ async fn check_minecraft_ownership(mc_access_token: &str) -> Result<bool> {
 
 /// Fetch Minecraft player profile
 async fn get_minecraft_profile(mc_access_token: &str) -> Result<MinecraftProfileResponse> {
-    let client = reqwest::Client::builder()
-        .timeout(StdDuration::from_secs(30))
-        .build()?;
-
-    let response = client
+    let response = http::client()
         .get(MINECRAFT_PROFILE_URL)
+        .timeout(AUTH_REQUEST_TIMEOUT)
         .bearer_auth(mc_access_token)
-        .send()
+        .send_retrying(RetryPolicy::default())
         .await
         .context("Failed to fetch Minecraft profile")?;
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(anyhow!(
-            "Failed to fetch Minecraft profile with status {}: {}. User may not own Minecraft.",
+        return Err(response_error(
             status,
-            text
+            format!(
+                "Failed to fetch Minecraft profile with status {}: {}. User may not own Minecraft.",
+                status,
+                text
+            ),
         ));
     }
 
@@ -416,30 +470,29 @@ async fn get_minecraft_profile(mc_access_token: &str) -> Result<MinecraftProfile
 
 /// Request a device code from Microsoft
 async fn request_device_code() -> Result<DeviceCodeResponse> {
-    let http_client = reqwest::Client::builder()
-        .timeout(StdDuration::from_secs(30))
-        .build()
-        .context("Failed to create HTTP client")?;
-
     let params = [
         ("client_id", MICROSOFT_CLIENT_ID),
         ("scope", "XboxLive.signin offline_access"),
     ];
 
-    let response = http_client
+    let response = http::client()
         .post(MICROSOFT_DEVICE_CODE_URL)
+        .timeout(AUTH_REQUEST_TIMEOUT)
         .form(&params)
-        .send()
+        .send_retrying(RetryPolicy::default())
         .await
         .context("Failed to request device code from Microsoft")?;
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(anyhow!(
-            "Device code request failed with status {}: {}",
+        return Err(response_error(
             status,
-            text
+            format!(
+                "Device code request failed with status {}: {}",
+                status,
+                text
+            ),
         ));
     }
 
@@ -451,11 +504,6 @@ async fn request_device_code() -> Result<DeviceCodeResponse> {
 
 /// Poll Microsoft for token after user completes device code authentication
 async fn poll_for_token(device_code: String, interval: u64) -> Result<MicrosoftTokenResponse> {
-    let http_client = reqwest::Client::builder()
-        .timeout(StdDuration::from_secs(30))
-        .build()
-        .context("Failed to create HTTP client")?;
-
     let params = [
         ("client_id", MICROSOFT_CLIENT_ID),
         ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
@@ -466,10 +514,11 @@ async fn poll_for_token(device_code: String, interval: u64) -> Result<MicrosoftT
     loop {
         tokio::time::sleep(StdDuration::from_secs(interval)).await;
 
-        let response = http_client
+        let response = http::client()
             .post(MICROSOFT_TOKEN_URL)
+            .timeout(AUTH_REQUEST_TIMEOUT)
             .form(&params)
-            .send()
+            .send_retrying(RetryPolicy::default())
             .await
             .context("Failed to poll for token")?;
 
@@ -816,6 +865,7 @@ pub async fn complete_device_code_auth(device_code: String, interval: u64) -> Re
             .skins
             .and_then(|skins| skins.first().map(|s| s.url.clone())),
         expires_at: Some(expires_at),
+        demo: false,
     };
 
     eprintln!("[AUTH] Saving profile to secure storage: {}", profile.username);
@@ -989,21 +1039,29 @@ pub fn logout() -> Result<()> {
 
 /// Check if token is expired or will expire soon
 fn is_token_expired(expires_at: &Option<DateTime<Utc>>) -> bool {
+    expires_within(expires_at, Duration::minutes(5))
+}
+
+/// Whether a token expires within `margin` from now
+pub fn expires_within(expires_at: &Option<DateTime<Utc>>, margin: Duration) -> bool {
     match expires_at {
-        Some(expiry) => {
-            let now = Utc::now();
-            let buffer = Duration::minutes(5);
-            *expiry <= now + buffer
-        }
+        Some(expiry) => *expiry <= Utc::now() + margin,
         None => true, // If no expiry info, assume expired
     }
 }
 
 /// Refresh expired OAuth token
 pub async fn refresh_token() -> Result<MinecraftProfile> {
+    refresh_token_within(Duration::zero()).await
+}
+
+/// Refresh the OAuth token if it is expired or expires within `margin`
+pub async fn refresh_token_within(margin: Duration) -> Result<MinecraftProfile> {
     // Get current profile
-    let current_profile = get_current_user()?
-        .ok_or_else(|| anyhow!("No user logged in"))?;
+    let current_profile = get_current_user()?.ok_or(NotSignedIn)?;
+    if current_profile.demo {
+        return Ok(current_profile);
+    }
 
     // Get tokens by session_id
     let tokens = get_tokens(&current_profile.session_id)?
@@ -1013,38 +1071,37 @@ pub async fn refresh_token() -> Result<MinecraftProfile> {
         .ok_or_else(|| anyhow!("No refresh token available"))?;
 
     // Check if token actually needs refresh
-    if !is_token_expired(&tokens.expires_at) {
+    if !is_token_expired(&tokens.expires_at) && !expires_within(&tokens.expires_at, margin) {
         return Ok(current_profile);
     }
 
     println!("Refreshing expired access token...");
 
     // Request new token using refresh token with timeout
-    let http_client = reqwest::Client::builder()
-        .timeout(StdDuration::from_secs(30))
-        .build()
-        .context("Failed to create HTTP client")?;
-
     let params = [
         ("client_id", MICROSOFT_CLIENT_ID),
         ("refresh_token", &refresh_token),
         ("grant_type", "refresh_token"),
     ];
 
-    let response = http_client
+    let response = http::client()
         .post(MICROSOFT_TOKEN_URL)
+        .timeout(AUTH_REQUEST_TIMEOUT)
         .form(&params)
-        .send()
+        .send_retrying(RetryPolicy::default())
         .await
         .context("Failed to send refresh token request to Microsoft")?;
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(anyhow!(
-            "Token refresh failed with status {}: {}",
+        return Err(response_error(
             status,
-            text
+            format!(
+                "Token refresh failed with status {}: {}",
+                status,
+                text
+            ),
         ));
     }
 
@@ -1084,6 +1141,78 @@ pub async fn refresh_token() -> Result<MinecraftProfile> {
     Ok(updated_profile)
 }
 
+/// Whether the session can be renewed without the player signing in again
+///
+/// Demo accounts and sessions imported from the official launcher have no refresh token.
+pub fn can_refresh(profile: &MinecraftProfile) -> bool {
+    !profile.demo
+        && get_tokens(&profile.session_id)
+            .ok()
+            .flatten()
+            .is_some_and(|tokens| tokens.refresh_token.is_some())
+}
+
+/// Whether demo accounts are enabled (`WOWID3_DEMO_ACCOUNTS=1`)
+pub fn demo_accounts_enabled() -> bool {
+    std::env::var(DEMO_ACCOUNTS_ENV).is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+}
+
+/// Deterministic UUID for an offline player, the same on every install
+///
+/// Name-based (SHA-1 of `OfflinePlayer:<name>`), so worlds and player data follow the
+/// username across test instances.
+pub fn offline_uuid(username: &str) -> String {
+    use sha1::{Digest, Sha1};
+
+    let hash = Sha1::digest(format!("OfflinePlayer:{}", username).as_bytes());
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&hash[..16]);
+    uuid::Builder::from_sha1_bytes(bytes).into_uuid().simple().to_string()
+}
+
+fn is_valid_username(username: &str) -> bool {
+    (3..=16).contains(&username.len())
+        && username.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Sign in with a username-only demo account
+///
+/// The profile and its placeholder token are stored like a Microsoft session, so
+/// launching needs no special handling; online-mode servers will reject it.
+pub fn sign_in_demo(username: &str) -> Result<MinecraftProfile> {
+    if !demo_accounts_enabled() {
+        return Err(anyhow!("Demo accounts are disabled; set {}=1 to enable them", DEMO_ACCOUNTS_ENV));
+    }
+    let username = username.trim();
+    if !is_valid_username(username) {
+        return Err(anyhow!(
+            "Invalid username '{}': use 3-16 letters, digits or underscores",
+            username
+        ));
+    }
+
+    let session_id = Uuid::new_v4().to_string();
+    let tokens = TokenData {
+        access_token: DEMO_ACCESS_TOKEN.to_string(),
+        refresh_token: None,
+        expires_at: None,
+    };
+    store_tokens(&session_id, &tokens)?;
+
+    let profile = MinecraftProfile {
+        uuid: offline_uuid(username),
+        username: username.to_string(),
+        session_id,
+        skin_url: None,
+        expires_at: None,
+        demo: true,
+    };
+    save_user_profile(&profile)?;
+    log_auth("DEMO_SIGN_IN", &format!("Signed in demo account {}", profile.username));
+
+    Ok(profile)
+}
+
 /// Get the Minecraft directory path based on the current OS
 fn get_minecraft_dir() -> Result<PathBuf> {
     let minecraft_dir = if cfg!(target_os = "windows") {
@@ -1174,6 +1303,7 @@ pub async fn authenticate_from_official_launcher() -> Result<MinecraftProfile> {
         session_id,
         skin_url: None,
         expires_at: None,
+        demo: false,
     };
 
     // Store in keyring for persistence
@@ -1218,6 +1348,7 @@ mod tests {
             session_id: "test-session-id".to_string(),
             skin_url: Some("https://example.com/skin.png".to_string()),
             expires_at: Some(Utc::now()),
+            demo: false,
         };
 
         // Test serialization
@@ -1241,6 +1372,7 @@ mod tests {
             session_id: "session-abc".to_string(),
             skin_url: None,
             expires_at: None,
+            demo: false,
         };
 
         // Should serialize and deserialize even without optional fields
@@ -1251,6 +1383,7 @@ mod tests {
         assert_eq!(deserialized.session_id, "session-abc");
         assert_eq!(deserialized.skin_url, None);
         assert_eq!(deserialized.expires_at, None);
+        assert!(!deserialized.demo);
     }
 
     #[test]
@@ -1342,4 +1475,43 @@ mod tests {
         assert!(MINECRAFT_AUTH_URL.starts_with("https://"));
         assert!(MINECRAFT_PROFILE_URL.starts_with("https://"));
     }
+
+    #[test]
+    fn test_offline_uuid_is_deterministic() {
+        let uuid = offline_uuid("PackDev");
+        assert_eq!(uuid, offline_uuid("PackDev"));
+        assert_ne!(uuid, offline_uuid("packdev"));
+        let parsed = Uuid::parse_str(&uuid).unwrap();
+        assert_eq!(parsed.get_version_num(), 5);
+
+        assert!(is_valid_username("Pack_Dev1"));
+        assert!(!is_valid_username("ab"));
+        assert!(!is_valid_username("has space"));
+        assert!(!is_valid_username("seventeen_chars__"));
+    }
+
+    #[test]
+    fn test_expires_within_margin() {
+        let in_twenty = Some(Utc::now() + Duration::minutes(20));
+        assert!(expires_within(&in_twenty, Duration::minutes(30)));
+        assert!(!expires_within(&in_twenty, Duration::minutes(10)));
+        assert!(expires_within(&None, Duration::zero()));
+    }
+
+    #[tokio::test]
+    async fn test_service_outage_classification() {
+        let outage = response_error(reqwest::StatusCode::SERVICE_UNAVAILABLE, "Xbox Live down".to_string());
+        assert!(is_service_outage(&outage.context("Failed to refresh")));
+
+        let rejected = response_error(reqwest::StatusCode::BAD_REQUEST, "invalid_grant".to_string());
+        assert!(!is_service_outage(&rejected));
+        assert!(!is_service_outage(&anyhow!("No user logged in")));
+
+        let unreachable = reqwest::Client::new()
+            .post("http://localhost:1/token")
+            .send()
+            .await
+            .unwrap_err();
+        assert!(is_service_outage(&anyhow::Error::from(unreachable).context("Failed to send refresh token request")));
+    }
 }
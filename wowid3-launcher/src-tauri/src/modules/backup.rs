@@ -0,0 +1,402 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+
+/// Single-player worlds live in `<game_dir>/saves/<world>`
+pub const SAVES_DIR: &str = "saves";
+
+/// Backups kept per world when no limit is configured
+pub const DEFAULT_BACKUP_KEEP: usize = 5;
+
+/// Held open by Minecraft while the world is loaded; not needed to restore
+const SESSION_LOCK: &str = "session.lock";
+
+/// Where backups go and how many to keep (mirrors the settings screen)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupOptions {
+    /// Backup root; defaults to `<data dir>/backups` when unset
+    #[serde(default)]
+    pub directory: Option<PathBuf>,
+    /// Backups kept per world; older ones are deleted after each new backup
+    #[serde(default = "default_keep")]
+    pub keep: usize,
+}
+
+fn default_keep() -> usize {
+    DEFAULT_BACKUP_KEEP
+}
+
+impl Default for BackupOptions {
+    fn default() -> Self {
+        BackupOptions {
+            directory: None,
+            keep: DEFAULT_BACKUP_KEEP,
+        }
+    }
+}
+
+impl BackupOptions {
+    pub fn backup_root(&self) -> Result<PathBuf> {
+        match &self.directory {
+            Some(dir) if !dir.as_os_str().is_empty() => Ok(dir.clone()),
+            _ => Ok(super::paths::get_persistent_data_dir()?.join("backups")),
+        }
+    }
+}
+
+/// A world backup archive (`<backup root>/<world>/<world>_<timestamp>.zip`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub world_name: String,
+    pub filename: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    /// RFC 3339 creation time
+    pub created_at: String,
+}
+
+/// Reject world/backup names that could escape their directory
+fn validate_name(kind: &str, name: &str) -> Result<()> {
+    if name.is_empty()
+        || name.contains(['/', '\\'])
+        || name == "."
+        || name.contains("..")
+    {
+        anyhow::bail!("Invalid {} name: '{}'", kind, name);
+    }
+    Ok(())
+}
+
+/// World folders in `saves/` (directories containing a `level.dat`)
+pub fn list_worlds(game_dir: &Path) -> Result<Vec<String>> {
+    let saves = game_dir.join(SAVES_DIR);
+    if !saves.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut worlds: Vec<String> = std::fs::read_dir(&saves)
+        .context("Failed to read saves directory")?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().join("level.dat").is_file())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    worlds.sort();
+    Ok(worlds)
+}
+
+fn backup_info(world_name: &str, path: PathBuf) -> Option<BackupInfo> {
+    let metadata = std::fs::metadata(&path).ok()?;
+    let created = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    Some(BackupInfo {
+        world_name: world_name.to_string(),
+        filename: path.file_name()?.to_string_lossy().to_string(),
+        size_bytes: metadata.len(),
+        created_at: chrono::DateTime::<chrono::Utc>::from(created).to_rfc3339(),
+        path,
+    })
+}
+
+/// Backups for one world, or all worlds, newest first
+pub fn list_backups(backup_root: &Path, world_name: Option<&str>) -> Result<Vec<BackupInfo>> {
+    if !backup_root.exists() {
+        return Ok(Vec::new());
+    }
+
+    let worlds: Vec<String> = match world_name {
+        Some(world) => {
+            validate_name("world", world)?;
+            vec![world.to_string()]
+        }
+        None => std::fs::read_dir(backup_root)
+            .context("Failed to read backup directory")?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect(),
+    };
+
+    let mut backups = Vec::new();
+    for world in worlds {
+        let dir = backup_root.join(&world);
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        backups.extend(
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().and_then(|s| s.to_str()) == Some("zip"))
+                .filter_map(|p| backup_info(&world, p)),
+        );
+    }
+
+    // Timestamped names sort chronologically; mtime can be changed by copying
+    backups.sort_by(|a, b| b.filename.cmp(&a.filename));
+    Ok(backups)
+}
+
+/// Delete all but the newest `keep` backups of a world
+fn rotate_backups(backup_root: &Path, world_name: &str, keep: usize) -> Result<()> {
+    for old in list_backups(backup_root, Some(world_name))?.into_iter().skip(keep.max(1)) {
+        eprintln!("[Backup] Removing old backup {}", old.path.display());
+        std::fs::remove_file(&old.path)
+            .with_context(|| format!("Failed to remove old backup {}", old.path.display()))?;
+    }
+    Ok(())
+}
+
+fn zip_directory(source: &Path, dest: &Path) -> Result<()> {
+    let file = File::create(dest).with_context(|| format!("Failed to create {}", dest.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+
+    let mut buffer = Vec::new();
+    for entry in WalkDir::new(source).min_depth(1) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(source)?;
+        if relative == Path::new(SESSION_LOCK) {
+            continue;
+        }
+        // Zip paths always use forward slashes
+        let name = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if entry.file_type().is_dir() {
+            zip.add_directory(name, options)?;
+        } else if entry.file_type().is_file() {
+            zip.start_file(name, options)?;
+            buffer.clear();
+            File::open(entry.path())
+                .with_context(|| format!("Failed to read {}", entry.path().display()))?
+                .read_to_end(&mut buffer)?;
+            zip.write_all(&buffer)?;
+        }
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+fn unzip_to(archive_path: &Path, dest: &Path) -> Result<()> {
+    let file = File::open(archive_path)
+        .with_context(|| format!("Failed to open {}", archive_path.display()))?;
+    let mut archive = zip::ZipArchive::new(file).context("Failed to read backup archive")?;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        // enclosed_name rejects absolute paths and `..` (zip slip)
+        let relative = entry
+            .enclosed_name()
+            .ok_or_else(|| anyhow::anyhow!("Backup contains an unsafe path: {}", entry.name()))?;
+        let out_path = dest.join(relative);
+
+        if entry.is_dir() {
+            std::fs::create_dir_all(&out_path)?;
+        } else {
+            if let Some(parent) = out_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut out = File::create(&out_path)?;
+            std::io::copy(&mut entry, &mut out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Zip `saves/<world>` into the backup directory and rotate old backups
+pub async fn backup_world(game_dir: &Path, world_name: &str, options: &BackupOptions) -> Result<BackupInfo> {
+    validate_name("world", world_name)?;
+
+    let world_dir = game_dir.join(SAVES_DIR).join(world_name);
+    if !world_dir.is_dir() {
+        anyhow::bail!("World '{}' not found", world_name);
+    }
+
+    let backup_root = options.backup_root()?;
+    let world_backups = backup_root.join(world_name);
+    let keep = options.keep;
+    let world_name = world_name.to_string();
+
+    tokio::task::spawn_blocking(move || {
+        std::fs::create_dir_all(&world_backups).context("Failed to create backup directory")?;
+
+        let stamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+        let mut dest = world_backups.join(format!("{}_{}.zip", world_name, stamp));
+        let mut n = 1;
+        while dest.exists() {
+            // `_n` sorts after the plain name, keeping name order chronological
+            dest = world_backups.join(format!("{}_{}_{}.zip", world_name, stamp, n));
+            n += 1;
+        }
+
+        // Write to a temp name so a failed backup never counts as one
+        let partial = dest.with_extension("zip.partial");
+        if let Err(e) = zip_directory(&world_dir, &partial) {
+            let _ = std::fs::remove_file(&partial);
+            return Err(e);
+        }
+        std::fs::rename(&partial, &dest)?;
+        eprintln!("[Backup] Backed up world '{}' to {}", world_name, dest.display());
+
+        rotate_backups(&backup_root, &world_name, keep)?;
+
+        backup_info(&world_name, dest).ok_or_else(|| anyhow::anyhow!("Backup was written but cannot be read"))
+    })
+    .await
+    .context("Backup task panicked")?
+}
+
+/// Back up every world (used before modpack updates); one failing world doesn't stop the rest
+pub async fn backup_all_worlds(game_dir: &Path, options: &BackupOptions) -> Result<Vec<BackupInfo>> {
+    let mut backups = Vec::new();
+    for world in list_worlds(game_dir)? {
+        match backup_world(game_dir, &world, options).await {
+            Ok(info) => backups.push(info),
+            Err(e) => eprintln!("[Backup] Failed to back up world '{}': {}", world, e),
+        }
+    }
+    Ok(backups)
+}
+
+/// Replace `saves/<world>` with the contents of a backup
+///
+/// The current world is backed up first so a restore can be undone. The archive
+/// is extracted next to the world and swapped in only once extraction succeeded.
+pub async fn restore_backup(
+    game_dir: &Path,
+    world_name: &str,
+    filename: &str,
+    options: &BackupOptions,
+) -> Result<BackupInfo> {
+    validate_name("world", world_name)?;
+    validate_name("backup", filename)?;
+
+    let archive = options.backup_root()?.join(world_name).join(filename);
+    let restored = backup_info(world_name, archive.clone())
+        .ok_or_else(|| anyhow::anyhow!("Backup '{}' not found", filename))?;
+
+    let saves = game_dir.join(SAVES_DIR);
+    let world_dir = saves.join(world_name);
+    if world_dir.is_dir() {
+        backup_world(game_dir, world_name, options)
+            .await
+            .context("Failed to back up the current world before restoring")?;
+    }
+
+    let world_name = world_name.to_string();
+    tokio::task::spawn_blocking(move || {
+        std::fs::create_dir_all(&saves)?;
+        let staging = saves.join(format!(".{}.restoring", world_name));
+        let previous = saves.join(format!(".{}.previous", world_name));
+        for leftover in [&staging, &previous] {
+            if leftover.exists() {
+                std::fs::remove_dir_all(leftover)?;
+            }
+        }
+
+        if let Err(e) = unzip_to(&archive, &staging) {
+            let _ = std::fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+
+        if world_dir.exists() {
+            std::fs::rename(&world_dir, &previous).context("Failed to move the current world aside")?;
+        }
+        if let Err(e) = std::fs::rename(&staging, &world_dir) {
+            // Put the original back
+            if previous.exists() {
+                let _ = std::fs::rename(&previous, &world_dir);
+            }
+            return Err(e).context("Failed to move the restored world into place");
+        }
+        if previous.exists() {
+            std::fs::remove_dir_all(&previous)?;
+        }
+
+        eprintln!("[Backup] Restored world '{}' from {}", world_name, archive.display());
+        Ok(restored)
+    })
+    .await
+    .context("Restore task panicked")?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_world(game_dir: &Path, name: &str, marker: &str) {
+        let world = game_dir.join(SAVES_DIR).join(name);
+        std::fs::create_dir_all(world.join("region")).unwrap();
+        std::fs::write(world.join("level.dat"), marker).unwrap();
+        std::fs::write(world.join("region").join("r.0.0.mca"), "chunks").unwrap();
+        std::fs::write(world.join(SESSION_LOCK), "lock").unwrap();
+    }
+
+    fn options(temp: &TempDir, keep: usize) -> BackupOptions {
+        BackupOptions {
+            directory: Some(temp.path().join("backups")),
+            keep,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backup_and_rotation() {
+        let temp = TempDir::new().unwrap();
+        let game_dir = temp.path().join("game");
+        make_world(&game_dir, "Survival", "v1");
+        std::fs::create_dir_all(game_dir.join(SAVES_DIR).join("not-a-world")).unwrap();
+
+        assert_eq!(list_worlds(&game_dir).unwrap(), vec!["Survival"]);
+
+        let opts = options(&temp, 2);
+        for _ in 0..3 {
+            backup_world(&game_dir, "Survival", &opts).await.unwrap();
+        }
+
+        let backups = list_backups(&opts.backup_root().unwrap(), Some("Survival")).unwrap();
+        assert_eq!(backups.len(), 2);
+        assert!(backups[0].filename > backups[1].filename); // newest first
+
+        assert!(backup_world(&game_dir, "Missing", &opts).await.is_err());
+        assert!(backup_world(&game_dir, "../game", &opts).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_restore_backup() {
+        let temp = TempDir::new().unwrap();
+        let game_dir = temp.path().join("game");
+        make_world(&game_dir, "Survival", "original");
+        let opts = options(&temp, 10);
+
+        let backup = backup_world(&game_dir, "Survival", &opts).await.unwrap();
+
+        // A mod update corrupts the world
+        let world = game_dir.join(SAVES_DIR).join("Survival");
+        std::fs::write(world.join("level.dat"), "corrupted").unwrap();
+        std::fs::write(world.join("junk.dat"), "junk").unwrap();
+
+        restore_backup(&game_dir, "Survival", &backup.filename, &opts).await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(world.join("level.dat")).unwrap(), "original");
+        assert!(world.join("region").join("r.0.0.mca").exists());
+        assert!(!world.join("junk.dat").exists());
+        assert!(!world.join(SESSION_LOCK).exists());
+
+        // The corrupted state was saved before restoring
+        let backups = list_backups(&opts.backup_root().unwrap(), Some("Survival")).unwrap();
+        assert_eq!(backups.len(), 2);
+
+        assert!(restore_backup(&game_dir, "Survival", "missing.zip", &opts).await.is_err());
+    }
+}
@@ -0,0 +1,249 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::fs;
+
+const CMS_CONFIG_FILE: &str = "cms-config.json";
+/// ETag of the cached config, sent as If-None-Match so unchanged configs cost a 304
+const CMS_ETAG_FILE: &str = "cms-config.etag";
+const CMS_FETCH_TIMEOUT_SECS: u64 = 5;
+/// Floor for server-provided intervals, in case a bad config slips through
+const MIN_POLL_INTERVAL_MS: u64 = 1_000;
+
+/// Names shown in the launcher
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Branding {
+    pub server_name: String,
+    pub tagline: Option<String>,
+    pub discord_url: Option<String>,
+    pub website_url: Option<String>,
+}
+
+impl Default for Branding {
+    fn default() -> Self {
+        Self {
+            server_name: "WOWID3".to_string(),
+            tagline: None,
+            discord_url: None,
+            website_url: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// Theme for players who haven't picked one
+    pub default_theme: String,
+    pub accent_color: Option<String>,
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            default_theme: "christmas".to_string(),
+            accent_color: None,
+        }
+    }
+}
+
+/// How often the launcher polls the server (milliseconds)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PollingIntervals {
+    pub server_status_ms: u64,
+    pub tracker_status_ms: u64,
+    /// Modpack update checks while the event stream is disconnected
+    pub update_check_ms: u64,
+}
+
+impl Default for PollingIntervals {
+    fn default() -> Self {
+        Self {
+            server_status_ms: 30_000,
+            tracker_status_ms: 5_000,
+            update_check_ms: 300_000,
+        }
+    }
+}
+
+/// Backoff used when a poll fails
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryConfig {
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay_ms: 1_000,
+            max_delay_ms: 30_000,
+        }
+    }
+}
+
+/// Launcher settings served by the modpack server (`/api/cms-config`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CmsConfig {
+    pub branding: Branding,
+    pub theme: ThemeConfig,
+    pub polling: PollingIntervals,
+    pub retry: RetryConfig,
+    pub features: BTreeMap<String, bool>,
+}
+
+impl CmsConfig {
+    /// Clamp values the launcher applies directly so a bad config can't make it spin
+    fn sanitized(mut self) -> Self {
+        let polling = &mut self.polling;
+        polling.server_status_ms = polling.server_status_ms.max(MIN_POLL_INTERVAL_MS);
+        polling.tracker_status_ms = polling.tracker_status_ms.max(MIN_POLL_INTERVAL_MS);
+        polling.update_check_ms = polling.update_check_ms.max(MIN_POLL_INTERVAL_MS);
+
+        let retry = &mut self.retry;
+        retry.base_delay_ms = retry.base_delay_ms.max(1);
+        retry.max_delay_ms = retry.max_delay_ms.max(retry.base_delay_ms);
+        self
+    }
+}
+
+fn cache_path() -> Result<PathBuf> {
+    Ok(super::paths::get_persistent_data_dir()?.join(CMS_CONFIG_FILE))
+}
+
+fn etag_path() -> Result<PathBuf> {
+    Ok(super::paths::get_persistent_data_dir()?.join(CMS_ETAG_FILE))
+}
+
+/// Fetch the config; `None` means the server says the cached copy is current
+async fn fetch_cms_config(server_url: &str, etag: Option<&str>) -> Result<Option<(CmsConfig, Option<String>)>> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(CMS_FETCH_TIMEOUT_SECS))
+        .build()?;
+
+    let url = format!("{}/api/cms-config", server_url.trim_end_matches('/'));
+    let mut request = client.get(&url);
+    if let Some(etag) = etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+
+    let response = request.send().await.context("Failed to fetch CMS config")?;
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
+
+    let response = response.error_for_status()?;
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let config = response.json().await.context("Failed to parse CMS config")?;
+
+    Ok(Some((config, etag)))
+}
+
+async fn load_cached_config() -> Result<Option<CmsConfig>> {
+    let path = cache_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json = fs::read_to_string(&path)
+        .await
+        .context("Failed to read cached CMS config")?;
+    Ok(Some(serde_json::from_str(&json).context("Failed to parse cached CMS config")?))
+}
+
+async fn save_cached_config(config: &CmsConfig, etag: Option<&str>) -> Result<()> {
+    let path = cache_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(config)?)
+        .await
+        .context("Failed to cache CMS config")?;
+
+    // Without a matching cache the ETag is useless, so it's written second and removed when absent
+    let etag_path = etag_path()?;
+    match etag {
+        Some(etag) => fs::write(&etag_path, etag).await.context("Failed to save CMS config ETag")?,
+        None => {
+            let _ = fs::remove_file(&etag_path).await;
+        }
+    }
+    Ok(())
+}
+
+fn cached_or_default(cached: Result<Option<CmsConfig>>) -> CmsConfig {
+    match cached {
+        Ok(cached) => cached.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("[CMS] {}", e);
+            CmsConfig::default()
+        }
+    }
+}
+
+/// Current CMS config: fetched from the server, else the last cached copy, else built-in defaults
+pub async fn get_cms_config(server_url: &str) -> CmsConfig {
+    let cached = load_cached_config().await;
+
+    // Only offer the ETag when there is a usable cached copy to fall back on
+    let etag = match (&cached, etag_path()) {
+        (Ok(Some(_)), Ok(path)) => fs::read_to_string(path).await.ok(),
+        _ => None,
+    };
+
+    let config = match fetch_cms_config(server_url, etag.as_deref().map(str::trim)).await {
+        Ok(Some((config, etag))) => {
+            if let Err(e) = save_cached_config(&config, etag.as_deref()).await {
+                eprintln!("[CMS] {}", e);
+            }
+            config
+        }
+        Ok(None) => cached_or_default(cached),
+        Err(e) => {
+            eprintln!("[CMS] Using cached config: {}", e);
+            cached_or_default(cached)
+        }
+    };
+
+    config.sanitized()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_config_uses_defaults() {
+        let config: CmsConfig = serde_json::from_str(
+            r#"{"polling": {"update_check_ms": 60000}, "features": {"map_viewer": false}, "unknown": 1}"#,
+        )
+        .unwrap();
+        assert_eq!(config.polling.update_check_ms, 60_000);
+        assert_eq!(config.polling.server_status_ms, 30_000);
+        assert_eq!(config.retry, RetryConfig::default());
+        assert_eq!(config.features.get("map_viewer"), Some(&false));
+    }
+
+    #[test]
+    fn test_sanitized_clamps_intervals() {
+        let mut config = CmsConfig::default();
+        config.polling.tracker_status_ms = 0;
+        config.retry.base_delay_ms = 5_000;
+        config.retry.max_delay_ms = 1_000;
+
+        let config = config.sanitized();
+        assert_eq!(config.polling.tracker_status_ms, MIN_POLL_INTERVAL_MS);
+        assert_eq!(config.retry.max_delay_ms, 5_000);
+    }
+}
@@ -0,0 +1,435 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+use super::download_manager::sha256_hex;
+use super::updater::ManifestFile;
+
+/// Copies of the config files as last shipped, relative to the game directory
+pub const CONFIG_BASE_DIR: &str = ".wowid3-config-base";
+
+/// Suffix of the shipped version written next to a config file that couldn't be merged
+pub const CONFLICT_SUFFIX: &str = ".new";
+
+const MERGEABLE_EXTENSIONS: &[&str] = &["txt", "toml", "properties", "cfg", "ini"];
+
+/// Line diffs above this size (base lines × other lines) are treated as conflicts
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Config files under `config/` that are merged line by line instead of overwritten
+pub fn is_mergeable(relative_path: &str) -> bool {
+    let path = relative_path.replace('\\', "/");
+    path.starts_with("config/")
+        && Path::new(&path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| MERGEABLE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+            .unwrap_or(false)
+}
+
+fn base_path(game_dir: &Path, relative_path: &str) -> PathBuf {
+    game_dir.join(CONFIG_BASE_DIR).join(relative_path)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeResult {
+    Merged(String),
+    Conflict,
+}
+
+/// Map each `base` line to its matching line in `other` (longest common subsequence)
+fn match_lines(base: &[&str], other: &[&str]) -> Option<Vec<Option<usize>>> {
+    let mut map = vec![None; base.len()];
+
+    let prefix = base.iter().zip(other).take_while(|(a, b)| a == b).count();
+    let suffix = base[prefix..]
+        .iter()
+        .rev()
+        .zip(other[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    for (i, slot) in map.iter_mut().enumerate().take(prefix) {
+        *slot = Some(i);
+    }
+    for k in 0..suffix {
+        map[base.len() - 1 - k] = Some(other.len() - 1 - k);
+    }
+
+    let a = &base[prefix..base.len() - suffix];
+    let b = &other[prefix..other.len() - suffix];
+    if a.is_empty() || b.is_empty() {
+        return Some(map);
+    }
+    if a.len() * b.len() > MAX_DIFF_CELLS {
+        return None;
+    }
+
+    // lengths[i][j] = LCS length of a[i..] and b[j..]
+    let width = b.len() + 1;
+    let mut lengths = vec![0u32; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i * width + j] = if a[i] == b[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            map[prefix + i] = Some(prefix + j);
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    Some(map)
+}
+
+/// Line-based three-way merge of the user's copy with a new shipped version
+///
+/// Changes made on only one side are kept; regions changed differently on both sides conflict.
+pub fn merge3(base: &str, user: &str, new: &str) -> MergeResult {
+    if user == base || user == new {
+        return MergeResult::Merged(new.to_string());
+    }
+    if new == base {
+        return MergeResult::Merged(user.to_string());
+    }
+
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let user_lines: Vec<&str> = user.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+
+    let (Some(to_user), Some(to_new)) = (
+        match_lines(&base_lines, &user_lines),
+        match_lines(&base_lines, &new_lines),
+    ) else {
+        return MergeResult::Conflict;
+    };
+
+    let mut merged = String::with_capacity(new.len().max(user.len()));
+    let (mut i, mut u, mut n) = (0, 0, 0);
+    loop {
+        // Lines unchanged on both sides
+        while i < base_lines.len() && to_user[i] == Some(u) && to_new[i] == Some(n) {
+            merged.push_str(base_lines[i]);
+            i += 1;
+            u += 1;
+            n += 1;
+        }
+        if i == base_lines.len() && u == user_lines.len() && n == new_lines.len() {
+            break;
+        }
+
+        // Next base line both sides still share, or the end of all three
+        let (next_i, next_u, next_n) = (i..base_lines.len())
+            .find_map(|k| Some((k, to_user[k]?, to_new[k]?)))
+            .unwrap_or((base_lines.len(), user_lines.len(), new_lines.len()));
+
+        let base_chunk = &base_lines[i..next_i];
+        let user_chunk = &user_lines[u..next_u];
+        let new_chunk = &new_lines[n..next_n];
+
+        if user_chunk == base_chunk || user_chunk == new_chunk {
+            merged.extend(new_chunk.iter().copied());
+        } else if new_chunk == base_chunk {
+            merged.extend(user_chunk.iter().copied());
+        } else {
+            return MergeResult::Conflict;
+        }
+
+        i = next_i;
+        u = next_u;
+        n = next_n;
+    }
+
+    MergeResult::Merged(merged)
+}
+
+/// True when the user edited a config file after the launcher installed the manifest's version
+///
+/// Such files are left alone by delta checks and repairs instead of being re-downloaded.
+pub async fn is_user_modified(game_dir: &Path, file: &ManifestFile) -> bool {
+    if !is_mergeable(&file.path) {
+        return false;
+    }
+    match fs::read(base_path(game_dir, &file.path)).await {
+        Ok(base) => sha256_hex(&base).eq_ignore_ascii_case(&file.sha256),
+        Err(_) => false,
+    }
+}
+
+/// The user's copy of a config file, captured before the update overwrites it
+#[derive(Debug, Clone)]
+pub struct PendingMerge {
+    pub path: String,
+    user: String,
+    base: Option<String>,
+}
+
+/// Read the current contents of config files that are about to be replaced
+pub async fn snapshot_user_configs(game_dir: &Path, files: &[ManifestFile]) -> Vec<PendingMerge> {
+    let mut pending = Vec::new();
+    for file in files.iter().filter(|f| is_mergeable(&f.path)) {
+        // Binary or unreadable files just get overwritten
+        let Ok(user) = fs::read_to_string(game_dir.join(&file.path)).await else {
+            continue;
+        };
+        let base = fs::read_to_string(base_path(game_dir, &file.path)).await.ok();
+        pending.push(PendingMerge {
+            path: file.path.clone(),
+            user,
+            base,
+        });
+    }
+    pending
+}
+
+/// Keep only snapshots of files that have since been replaced
+///
+/// For installs that stopped partway: merging a file that still holds the user's copy
+/// would record that copy as the shipped version.
+pub async fn retain_replaced(game_dir: &Path, pending: Vec<PendingMerge>) -> Vec<PendingMerge> {
+    let mut replaced = Vec::new();
+    for entry in pending {
+        let current = fs::read_to_string(game_dir.join(&entry.path)).await;
+        if current.is_ok_and(|current| current != entry.user) {
+            replaced.push(entry);
+        }
+    }
+    replaced
+}
+
+/// Outcome of merging config files after an update
+#[derive(Debug, Clone, Default)]
+pub struct MergeSummary {
+    pub merged: Vec<String>,
+    /// Files where the user's copy was kept and the shipped one written to `<file>.new`
+    pub conflicts: Vec<String>,
+}
+
+/// Merge the user's snapshots into the freshly installed config files
+pub async fn merge_user_configs(game_dir: &Path, pending: Vec<PendingMerge>) -> Result<MergeSummary> {
+    let mut summary = MergeSummary::default();
+
+    for entry in pending {
+        let target = game_dir.join(&entry.path);
+        let new = fs::read_to_string(&target)
+            .await
+            .with_context(|| format!("Failed to read updated {}", entry.path))?;
+
+        // Without a record of what was shipped before we can't tell user edits from
+        // the old release, so the shipped file wins (the pre-merge behaviour)
+        let result = match &entry.base {
+            Some(base) => merge3(base, &entry.user, &new),
+            None => MergeResult::Merged(new.clone()),
+        };
+
+        match result {
+            MergeResult::Merged(merged) => {
+                if merged != new {
+                    fs::write(&target, &merged)
+                        .await
+                        .with_context(|| format!("Failed to write merged {}", entry.path))?;
+                    eprintln!("[ConfigMerge] Kept local changes in {}", entry.path);
+                    summary.merged.push(entry.path.clone());
+                }
+            }
+            MergeResult::Conflict => {
+                let conflict_path = game_dir.join(format!("{}{}", entry.path, CONFLICT_SUFFIX));
+                fs::write(&conflict_path, &new)
+                    .await
+                    .with_context(|| format!("Failed to write {}", conflict_path.display()))?;
+                fs::write(&target, &entry.user)
+                    .await
+                    .with_context(|| format!("Failed to restore {}", entry.path))?;
+                eprintln!(
+                    "[ConfigMerge] Conflict in {}; kept local copy, shipped version saved as {}{}",
+                    entry.path, entry.path, CONFLICT_SUFFIX
+                );
+                summary.conflicts.push(entry.path.clone());
+            }
+        }
+
+        write_base(game_dir, &entry.path, new.as_bytes()).await?;
+    }
+
+    Ok(summary)
+}
+
+async fn write_base(game_dir: &Path, relative_path: &str, content: &[u8]) -> Result<()> {
+    let path = base_path(game_dir, relative_path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    fs::write(&path, content)
+        .await
+        .with_context(|| format!("Failed to record shipped {}", relative_path))
+}
+
+/// Remember the shipped contents of every config file that is installed unmodified
+///
+/// Files the user has edited keep their existing base so the next update can merge them.
+pub async fn record_shipped_configs(game_dir: &Path, files: &[ManifestFile]) -> Result<()> {
+    for file in files.iter().filter(|f| is_mergeable(&f.path)) {
+        let Ok(current) = fs::read(game_dir.join(&file.path)).await else {
+            continue;
+        };
+        if !sha256_hex(&current).eq_ignore_ascii_case(&file.sha256) {
+            continue;
+        }
+        let base = base_path(game_dir, &file.path);
+        if fs::read(&base).await.ok().as_deref() == Some(current.as_slice()) {
+            continue;
+        }
+        write_base(game_dir, &file.path, &current).await?;
+    }
+    Ok(())
+}
+
+/// True for launcher-managed merge files that cleanup must never delete
+pub fn is_merge_artifact(relative_path: &str, manifest_paths: &std::collections::HashSet<String>) -> bool {
+    relative_path.starts_with(&format!("{}/", CONFIG_BASE_DIR))
+        || relative_path
+            .strip_suffix(CONFLICT_SUFFIX)
+            .is_some_and(|original| manifest_paths.contains(original))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn manifest_file(path: &str, content: &str) -> ManifestFile {
+        ManifestFile {
+            path: path.to_string(),
+            url: String::new(),
+            sha256: sha256_hex(content.as_bytes()),
+            size: content.len() as u64,
+            mirrors: vec![],
+        }
+    }
+
+    #[test]
+    fn test_is_mergeable() {
+        assert!(is_mergeable("config/sodium-options.properties"));
+        assert!(is_mergeable("config/create/client.TOML"));
+        assert!(is_mergeable("config\\keybinds.txt"));
+        assert!(!is_mergeable("config/pack-icon.png"));
+        assert!(!is_mergeable("mods/config.toml"));
+        assert!(!is_mergeable("options.txt"));
+    }
+
+    #[test]
+    fn test_merge3_combines_independent_changes() {
+        let base = "fov=70\nrenderDistance=12\nvsync=true\n";
+        let user = "fov=90\nrenderDistance=12\nvsync=true\n";
+        let new = "fov=70\nrenderDistance=12\nvsync=true\nshaders=false\n";
+        assert_eq!(
+            merge3(base, user, new),
+            MergeResult::Merged("fov=90\nrenderDistance=12\nvsync=true\nshaders=false\n".to_string())
+        );
+
+        // Shipped change to a line the user didn't touch
+        let new = "fov=70\nrenderDistance=8\nvsync=true\n";
+        assert_eq!(
+            merge3(base, user, new),
+            MergeResult::Merged("fov=90\nrenderDistance=8\nvsync=true\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_merge3_trivial_cases() {
+        let base = "a=1\n";
+        assert_eq!(merge3(base, base, "a=2\n"), MergeResult::Merged("a=2\n".to_string()));
+        assert_eq!(merge3(base, "a=3\n", base), MergeResult::Merged("a=3\n".to_string()));
+        assert_eq!(merge3(base, "a=2\n", "a=2\n"), MergeResult::Merged("a=2\n".to_string()));
+    }
+
+    #[test]
+    fn test_merge3_conflict() {
+        let base = "fov=70\nvsync=true\n";
+        assert_eq!(merge3(base, "fov=90\nvsync=true\n", "fov=80\nvsync=true\n"), MergeResult::Conflict);
+    }
+
+    #[tokio::test]
+    async fn test_merge_user_configs() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path();
+        fs::create_dir_all(game_dir.join("config")).await.unwrap();
+
+        let old = "fov=70\nvsync=true\n";
+        fs::write(game_dir.join("config/a.properties"), old).await.unwrap();
+        fs::write(game_dir.join("config/b.properties"), old).await.unwrap();
+        record_shipped_configs(
+            game_dir,
+            &[manifest_file("config/a.properties", old), manifest_file("config/b.properties", old)],
+        )
+        .await
+        .unwrap();
+
+        // User edits both, then an update ships new versions
+        fs::write(game_dir.join("config/a.properties"), "fov=90\nvsync=true\n").await.unwrap();
+        fs::write(game_dir.join("config/b.properties"), "fov=90\nvsync=true\n").await.unwrap();
+        let files = [
+            manifest_file("config/a.properties", "fov=70\nvsync=false\n"),
+            manifest_file("config/b.properties", "fov=80\nvsync=true\n"),
+        ];
+        let pending = snapshot_user_configs(game_dir, &files).await;
+        fs::write(game_dir.join("config/a.properties"), "fov=70\nvsync=false\n").await.unwrap();
+        fs::write(game_dir.join("config/b.properties"), "fov=80\nvsync=true\n").await.unwrap();
+
+        let summary = merge_user_configs(game_dir, pending).await.unwrap();
+        assert_eq!(summary.merged, vec!["config/a.properties"]);
+        assert_eq!(summary.conflicts, vec!["config/b.properties"]);
+
+        let a = fs::read_to_string(game_dir.join("config/a.properties")).await.unwrap();
+        assert_eq!(a, "fov=90\nvsync=false\n");
+        let b = fs::read_to_string(game_dir.join("config/b.properties")).await.unwrap();
+        assert_eq!(b, "fov=90\nvsync=true\n");
+        let b_new = fs::read_to_string(game_dir.join("config/b.properties.new")).await.unwrap();
+        assert_eq!(b_new, "fov=80\nvsync=true\n");
+
+        // Both are now recorded as the shipped version, so the edits are respected
+        assert!(is_user_modified(game_dir, &files[0]).await);
+        assert!(is_user_modified(game_dir, &files[1]).await);
+    }
+
+    #[tokio::test]
+    async fn test_retain_replaced_skips_untouched_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path();
+        fs::create_dir_all(game_dir.join("config")).await.unwrap();
+        fs::write(game_dir.join("config/a.properties"), "fov=90\n").await.unwrap();
+        fs::write(game_dir.join("config/b.properties"), "fov=90\n").await.unwrap();
+
+        let files = [
+            manifest_file("config/a.properties", "fov=70\n"),
+            manifest_file("config/b.properties", "fov=70\n"),
+        ];
+        let pending = snapshot_user_configs(game_dir, &files).await;
+        // The install stopped after replacing only a.properties
+        fs::write(game_dir.join("config/a.properties"), "fov=70\n").await.unwrap();
+
+        let replaced = retain_replaced(game_dir, pending).await;
+        let paths: Vec<&str> = replaced.iter().map(|entry| entry.path.as_str()).collect();
+        assert_eq!(paths, vec!["config/a.properties"]);
+    }
+
+    #[test]
+    fn test_is_merge_artifact() {
+        let manifest: std::collections::HashSet<String> = ["config/a.toml".to_string()].into();
+        assert!(is_merge_artifact(".wowid3-config-base/config/a.toml", &manifest));
+        assert!(is_merge_artifact("config/a.toml.new", &manifest));
+        assert!(!is_merge_artifact("config/b.toml.new", &manifest));
+        assert!(!is_merge_artifact("config/a.toml", &manifest));
+    }
+}
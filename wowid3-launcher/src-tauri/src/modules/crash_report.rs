@@ -0,0 +1,270 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tokio::fs;
+
+use super::java_detection::probe_java;
+use super::offline::load_last_known_good;
+use super::updater::get_installed_version;
+
+/// Crash reports older than this were not produced by the run that just exited
+const CRASH_REPORT_MAX_AGE: Duration = Duration::from_secs(10 * 60);
+
+/// Lines of latest.log sent when the game died without writing a crash report
+const LOG_TAIL_LINES: usize = 400;
+
+/// Mirrors the server limit; larger reports are truncated there anyway
+const MAX_REPORT_BYTES: usize = 512 * 1024;
+
+/// Body of `POST /api/crashes`
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashReportUpload {
+    pub modpack_version: String,
+    pub minecraft_version: Option<String>,
+    pub os: String,
+    pub java_version: Option<String>,
+    pub launcher_version: Option<String>,
+    pub exit_code: Option<i32>,
+    pub report: String,
+}
+
+/// Server response for an uploaded report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashUploadResult {
+    pub id: i64,
+    /// Signature the server grouped the crash under
+    pub signature: String,
+}
+
+/// Newest `crash-reports/*.txt` modified after `since`
+pub async fn find_latest_crash_report(game_dir: &Path, since: SystemTime) -> Result<Option<PathBuf>> {
+    let crash_reports_dir = game_dir.join("crash-reports");
+    if !crash_reports_dir.exists() {
+        return Ok(None);
+    }
+
+    let mut entries = fs::read_dir(&crash_reports_dir)
+        .await
+        .context("Failed to read crash-reports directory")?;
+
+    let mut latest: Option<(PathBuf, SystemTime)> = None;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("txt") {
+            continue;
+        }
+        let Ok(modified) = entry.metadata().await.and_then(|m| m.modified()) else {
+            continue;
+        };
+        if modified < since {
+            continue;
+        }
+        if latest.as_ref().map_or(true, |(_, newest)| modified > *newest) {
+            latest = Some((path, modified));
+        }
+    }
+
+    Ok(latest.map(|(path, _)| path))
+}
+
+/// Last `lines` lines of a text file
+async fn read_tail(path: &Path, lines: usize) -> Result<String> {
+    let content = fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let all: Vec<&str> = content.lines().collect();
+    Ok(all[all.len().saturating_sub(lines)..].join("\n"))
+}
+
+/// Cut a string to at most `max` bytes on a char boundary
+fn truncate_bytes(mut s: String, max: usize) -> String {
+    if s.len() > max {
+        let mut end = max;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        s.truncate(end);
+    }
+    s
+}
+
+/// `fabric-loader-0.17.3-1.20.1` -> `1.20.1`, `1.20.1` -> `1.20.1`
+pub(super) fn minecraft_version_from_id(version_id: &str) -> Option<String> {
+    let candidate = version_id.rsplit('-').next()?;
+    candidate
+        .chars()
+        .next()
+        .filter(|c| c.is_ascii_digit())
+        .map(|_| candidate.to_string())
+}
+
+/// Gather the crash report and environment details for the run that just crashed
+///
+/// Uses the newest recent crash report, falling back to the tail of `logs/latest.log`
+/// for crashes that happen before Minecraft can write one (e.g. mod loading errors).
+pub async fn collect_crash_report(
+    game_dir: &Path,
+    exit_code: Option<i32>,
+    java_path: Option<&Path>,
+) -> Result<CrashReportUpload> {
+    let since = SystemTime::now()
+        .checked_sub(CRASH_REPORT_MAX_AGE)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let report = match find_latest_crash_report(game_dir, since).await? {
+        Some(path) => fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to read crash report {}", path.display()))?,
+        None => {
+            let latest_log = game_dir.join("logs").join("latest.log");
+            if !latest_log.exists() {
+                anyhow::bail!("No crash report or game log found to upload");
+            }
+            read_tail(&latest_log, LOG_TAIL_LINES).await?
+        }
+    };
+
+    let modpack_version = get_installed_version(&game_dir.to_path_buf())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No modpack is installed (missing .wowid3-version)"))?;
+
+    let last_good = load_last_known_good(game_dir).await.ok().flatten();
+    let minecraft_version = last_good
+        .as_ref()
+        .and_then(|lg| minecraft_version_from_id(&lg.version_id));
+
+    let java_path = java_path
+        .map(Path::to_path_buf)
+        .or_else(|| last_good.and_then(|lg| lg.java_path));
+    let java_version = match java_path {
+        Some(path) => match probe_java(&path, "launch").await {
+            Ok(java) => Some(java.version),
+            Err(e) => {
+                eprintln!("[CrashReport] Could not determine Java version: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    Ok(CrashReportUpload {
+        modpack_version,
+        minecraft_version,
+        os: std::env::consts::OS.to_string(),
+        java_version,
+        launcher_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        exit_code,
+        report: truncate_bytes(report, MAX_REPORT_BYTES),
+    })
+}
+
+/// Send a crash report to the release server
+pub async fn upload_crash_report(server_url: &str, upload: &CrashReportUpload) -> Result<CrashUploadResult> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()?;
+
+    let url = format!("{}/api/crashes", server_url.trim_end_matches('/'));
+    let response = client
+        .post(&url)
+        .json(upload)
+        .send()
+        .await
+        .context("Failed to upload crash report")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("Crash report upload failed with HTTP status {}: {}", status, body);
+    }
+
+    response
+        .json::<CrashUploadResult>()
+        .await
+        .context("Failed to parse crash upload response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::updater::update_version_file;
+    use tempfile::TempDir;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_minecraft_version_from_id() {
+        assert_eq!(minecraft_version_from_id("fabric-loader-0.17.3-1.20.1").as_deref(), Some("1.20.1"));
+        assert_eq!(minecraft_version_from_id("1.20.1").as_deref(), Some("1.20.1"));
+        assert_eq!(minecraft_version_from_id("custom-profile"), None);
+    }
+
+    #[test]
+    fn test_truncate_bytes() {
+        assert_eq!(truncate_bytes("héllo".to_string(), 2), "h");
+        assert_eq!(truncate_bytes("hello".to_string(), 10), "hello");
+    }
+
+    #[tokio::test]
+    async fn test_collect_prefers_crash_report_over_log() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path();
+        update_version_file(&game_dir.to_path_buf(), "1.2.0").await.unwrap();
+
+        fs::create_dir_all(game_dir.join("logs")).await.unwrap();
+        fs::write(game_dir.join("logs/latest.log"), "line 1\nline 2\n").await.unwrap();
+
+        // Only the log exists yet
+        let upload = collect_crash_report(game_dir, Some(1), None).await.unwrap();
+        assert_eq!(upload.modpack_version, "1.2.0");
+        assert!(upload.report.contains("line 2"));
+
+        fs::create_dir_all(game_dir.join("crash-reports")).await.unwrap();
+        fs::write(
+            game_dir.join("crash-reports/crash-2026-01-10_20.14.55-client.txt"),
+            "---- Minecraft Crash Report ----",
+        )
+        .await
+        .unwrap();
+
+        let upload = collect_crash_report(game_dir, Some(1), None).await.unwrap();
+        assert!(upload.report.contains("Minecraft Crash Report"));
+        assert_eq!(upload.exit_code, Some(1));
+        assert_eq!(upload.os, std::env::consts::OS);
+    }
+
+    #[tokio::test]
+    async fn test_collect_without_logs_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        update_version_file(&temp_dir.path().to_path_buf(), "1.2.0").await.unwrap();
+        assert!(collect_crash_report(temp_dir.path(), None, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_upload_crash_report() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/crashes"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": 7,
+                "signature": "0123456789abcdef"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let upload = CrashReportUpload {
+            modpack_version: "1.2.0".to_string(),
+            minecraft_version: Some("1.20.1".to_string()),
+            os: "linux".to_string(),
+            java_version: None,
+            launcher_version: None,
+            exit_code: Some(1),
+            report: "boom".to_string(),
+        };
+
+        let result = upload_crash_report(&mock_server.uri(), &upload).await.unwrap();
+        assert_eq!(result.id, 7);
+        assert_eq!(result.signature, "0123456789abcdef");
+    }
+}
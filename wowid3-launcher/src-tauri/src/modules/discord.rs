@@ -196,7 +196,7 @@ impl DiscordClient {
 }
 
 /// Game presence information for Discord
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GamePresence {
     /// Current game state (e.g., "Playing WOW Is Dead 3!")
     pub state: String,
@@ -0,0 +1,235 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use super::discord::{DiscordClient, GamePresence};
+use super::server::{fetch_tracker_status, TrackerState};
+
+/// Server whose tracker reports player dimension and online count
+const TRACKER_BASE_URL: &str = "https://wowid-launcher.frostdev.io";
+
+/// How often the tracker is polled while the game runs
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Discord allows 5 activity updates per 20 seconds; stay well below that
+const MIN_UPDATE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// The tracker doesn't report capacity
+const DEFAULT_MAX_PLAYERS: u32 = 20;
+
+/// Keeps Discord presence in sync with the running game and tracker state
+///
+/// Started when the game process launches and stopped when it exits; updates
+/// are only sent when the presence changes, at most every `MIN_UPDATE_INTERVAL`.
+#[derive(Clone)]
+pub struct PresenceService {
+    discord: DiscordClient,
+    task: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl PresenceService {
+    pub fn new(discord: DiscordClient) -> Self {
+        PresenceService {
+            discord,
+            task: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Begin a game session for `uuid`, replacing any previous one
+    pub async fn start(&self, uuid: String, username: String) {
+        let mut task = self.task.lock().await;
+        if let Some(previous) = task.take() {
+            previous.abort();
+        }
+
+        let discord = self.discord.clone();
+        *task = Some(tokio::spawn(async move {
+            if !discord.is_connected().await {
+                if let Err(e) = discord.connect().await {
+                    eprintln!("[Discord] Presence disabled for this session: {}", e);
+                    return;
+                }
+            }
+
+            let start_time = chrono::Utc::now().timestamp();
+            let mut last_sent: Option<(GamePresence, Instant)> = None;
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+            loop {
+                interval.tick().await;
+
+                let tracker = match fetch_tracker_status(TRACKER_BASE_URL).await {
+                    Ok(tracker) => Some(tracker),
+                    Err(e) => {
+                        eprintln!("[Discord] Tracker unavailable, using basic presence: {}", e);
+                        None
+                    }
+                };
+                let presence = build_presence(tracker.as_ref(), &uuid, &username, start_time);
+
+                let due = match &last_sent {
+                    None => true,
+                    Some((sent, at)) => *sent != presence && at.elapsed() >= MIN_UPDATE_INTERVAL,
+                };
+                if !due {
+                    continue;
+                }
+
+                match discord.set_presence(&presence).await {
+                    Ok(()) => last_sent = Some((presence, Instant::now())),
+                    Err(e) => eprintln!("[Discord] Failed to update presence: {}", e),
+                }
+            }
+        }));
+    }
+
+    /// End the game session and clear presence
+    pub async fn stop(&self) {
+        if let Some(task) = self.task.lock().await.take() {
+            task.abort();
+        }
+        if let Err(e) = self.discord.clear_presence().await {
+            eprintln!("[Discord] Failed to clear presence: {}", e);
+        }
+    }
+}
+
+/// Presence for a player, from the tracker state when available
+pub fn build_presence(
+    tracker: Option<&TrackerState>,
+    uuid: &str,
+    username: &str,
+    start_time: i64,
+) -> GamePresence {
+    let mut presence = GamePresence {
+        start_time: Some(start_time),
+        ..GamePresence::default()
+    };
+
+    let Some(tracker) = tracker else {
+        presence.details = Some("Playing on WOW Is Dead 3!".to_string());
+        presence.state = "Multiplayer Game".to_string();
+        return presence;
+    };
+
+    let online = tracker.online_players.len() as u32;
+    presence.party_size = Some(online);
+    presence.party_max = Some(DEFAULT_MAX_PLAYERS.max(online));
+
+    // Tracker UUIDs are dashed; the launcher's may not be
+    let undashed = uuid.replace('-', "");
+    let player = tracker
+        .online_players
+        .iter()
+        .find(|p| p.uuid.replace('-', "") == undashed || p.name == username);
+
+    let Some(player) = player else {
+        presence.details = Some("Connecting to server...".to_string());
+        return presence;
+    };
+
+    presence.details = Some("In Game".to_string());
+    if let Some(dimension) = &player.dimension {
+        let (state, icon) = dimension_label(dimension);
+        presence.state = state;
+        presence.small_image = icon.map(str::to_string);
+        presence.small_image_text = icon.map(|_| presence.state.clone());
+    }
+    if let Some(biome) = &player.biome {
+        presence.details = Some(format!("Biome: {}", title_case(biome)));
+    }
+
+    presence
+}
+
+/// State line and small icon for a dimension id like `minecraft:the_nether`
+fn dimension_label(dimension: &str) -> (String, Option<&'static str>) {
+    match dimension.trim_start_matches("minecraft:") {
+        "overworld" => ("In the Overworld".to_string(), Some("grass_block")),
+        "the_nether" => ("In the Nether".to_string(), Some("netherrack")),
+        "the_end" => ("In the End".to_string(), Some("end_stone")),
+        other => (format!("Exploring {}", title_case(other)), None),
+    }
+}
+
+/// `minecraft:dark_forest` -> `Dark Forest`
+fn title_case(id: &str) -> String {
+    let name = id.rsplit(':').next().unwrap_or(id);
+    name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::server::PlayerExt;
+
+    fn tracker(players: Vec<PlayerExt>) -> TrackerState {
+        TrackerState {
+            online_players: players,
+            recent_chat: Vec::new(),
+            tps: None,
+            mspt: None,
+            last_updated: 0,
+        }
+    }
+
+    fn player(name: &str, uuid: &str, dimension: Option<&str>, biome: Option<&str>) -> PlayerExt {
+        PlayerExt {
+            name: name.to_string(),
+            uuid: uuid.to_string(),
+            position: None,
+            dimension: dimension.map(str::to_string),
+            biome: biome.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_presence_from_tracker() {
+        let state = tracker(vec![
+            player("Notch", "069a79f4-44e9-4726-a5be-fca90e38aaf5", Some("minecraft:the_nether"), Some("minecraft:soul_sand_valley")),
+            player("jeb_", "853c80ef-3c37-49fd-aa49-938b674adae6", None, None),
+        ]);
+
+        let presence = build_presence(Some(&state), "069a79f444e94726a5befca90e38aaf5", "Notch", 1_700_000_000);
+        assert_eq!(presence.state, "In the Nether");
+        assert_eq!(presence.details.as_deref(), Some("Biome: Soul Sand Valley"));
+        assert_eq!(presence.small_image.as_deref(), Some("netherrack"));
+        assert_eq!(presence.party_size, Some(2));
+        assert_eq!(presence.party_max, Some(DEFAULT_MAX_PLAYERS));
+        assert_eq!(presence.start_time, Some(1_700_000_000));
+    }
+
+    #[test]
+    fn test_presence_before_player_appears() {
+        let state = tracker(vec![player("jeb_", "853c80ef-3c37-49fd-aa49-938b674adae6", None, None)]);
+        let presence = build_presence(Some(&state), "069a79f4-44e9-4726-a5be-fca90e38aaf5", "Notch", 0);
+        assert_eq!(presence.details.as_deref(), Some("Connecting to server..."));
+        assert_eq!(presence.party_size, Some(1));
+    }
+
+    #[test]
+    fn test_presence_without_tracker() {
+        let presence = build_presence(None, "uuid", "Notch", 0);
+        assert_eq!(presence.state, "Multiplayer Game");
+        assert_eq!(presence.party_size, None);
+    }
+
+    #[test]
+    fn test_dimension_label_modded() {
+        assert_eq!(
+            dimension_label("twilightforest:twilight_forest"),
+            ("Exploring Twilight Forest".to_string(), None)
+        );
+    }
+}
@@ -1,14 +1,122 @@
 use anyhow::{Context, Result};
 use futures::stream::StreamExt;
-use reqwest::Client;
+use reqwest::header::{CONTENT_RANGE, RANGE};
+use reqwest::{Client, Response, StatusCode};
+use serde::{Deserialize, Serialize};
 use sha1::{Digest, Sha1};
 use sha2::Sha256;
+use std::collections::{HashMap, VecDeque};
+use std::io::SeekFrom;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::fs::{self, File};
-use tokio::io::AsyncWriteExt;
-use tokio::sync::{mpsc, Semaphore};
+use std::time::{Duration, Instant};
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::{mpsc, watch, Semaphore};
+
+/// Suffix for in-progress downloads (`mod.jar` -> `mod.jar.part`)
+const PART_SUFFIX: &str = "part";
+/// Suffix for the resume record stored next to the partial file
+const RECORD_SUFFIX: &str = "part.json";
+/// How often the resume record is refreshed while streaming
+const RECORD_INTERVAL_BYTES: u64 = 1024 * 1024;
+/// Persisted speed cap, in the launcher's data directory
+const DOWNLOAD_SETTINGS_FILE: &str = "download_settings.json";
+/// Latency probes slower than this rank a source last
+const LATENCY_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Window the current transfer speed is measured over
+const SPEED_WINDOW: Duration = Duration::from_secs(3);
+/// Minimum time between batch progress reports while files are streaming
+const BATCH_REPORT_INTERVAL: Duration = Duration::from_millis(250);
+
+lazy_static::lazy_static! {
+    /// Limiter shared by every download manager, so the cap covers all concurrent downloads
+    pub static ref BANDWIDTH: Arc<BandwidthLimiter> = Arc::new(BandwidthLimiter::new(
+        download_settings_path()
+            .map(|path| read_download_settings(&path).max_bytes_per_sec)
+            .unwrap_or(0),
+    ));
+
+    /// Token for the installs and updates currently running
+    static ref INSTALL_CANCEL: std::sync::Mutex<CancelToken> = std::sync::Mutex::new(CancelToken::new());
+}
+
+/// Returned (inside anyhow) when an install is stopped by `cancel_installation`
+#[derive(Debug)]
+pub struct InstallCancelled;
+
+impl std::fmt::Display for InstallCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Installation cancelled")
+    }
+}
+
+impl std::error::Error for InstallCancelled {}
+
+/// Whether an install failed because it was cancelled, however deep in the context chain
+pub fn is_cancelled(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| cause.is::<InstallCancelled>())
+}
+
+/// Cancellation flag shared by everything one install runs
+#[derive(Debug, Clone)]
+pub struct CancelToken(Arc<watch::Sender<bool>>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self(Arc::new(watch::channel(false).0))
+    }
+
+    pub fn cancel(&self) {
+        self.0.send_replace(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    /// Fail with `InstallCancelled` once cancelled; installers call this between steps
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(InstallCancelled.into());
+        }
+        Ok(())
+    }
+
+    /// Resolves when the token is cancelled
+    pub async fn cancelled(&self) {
+        let mut rx = self.0.subscribe();
+        let _ = rx.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+impl Default for CancelToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Token for a new install or update
+///
+/// Installs running at the same time share a token, so one cancel stops them all.
+/// A token that was already cancelled is replaced.
+pub fn begin_install() -> CancelToken {
+    let mut current = INSTALL_CANCEL.lock().unwrap_or_else(|e| e.into_inner());
+    if current.is_cancelled() {
+        *current = CancelToken::new();
+    }
+    current.clone()
+}
+
+/// Cancel every install and update in progress
+///
+/// Partial downloads are kept and resumed by the next attempt; finished files are
+/// only ever moved into place whole, so nothing is left corrupt.
+pub fn cancel_installation() {
+    eprintln!("[Download] Cancelling installation");
+    INSTALL_CANCEL.lock().unwrap_or_else(|e| e.into_inner()).cancel();
+}
 
 /// Download priority levels for task scheduling
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -25,10 +133,21 @@ pub enum HashType {
     Sha256(String),
 }
 
+impl HashType {
+    fn value(&self) -> &str {
+        match self {
+            HashType::Sha1(h) => h,
+            HashType::Sha256(h) => h,
+        }
+    }
+}
+
 /// Individual download task
 #[derive(Debug, Clone)]
 pub struct DownloadTask {
     pub url: String,
+    /// Alternative URLs serving the same bytes, tried when `url` fails
+    pub mirrors: Vec<String>,
     pub dest: PathBuf,
     pub expected_hash: HashType,
     pub priority: DownloadPriority,
@@ -39,17 +158,348 @@ pub struct DownloadTask {
 #[derive(Debug, Clone)]
 pub struct DownloadProgress {
     pub url: String,
-    #[allow(dead_code)]
     pub bytes_downloaded: u64,
     pub total_bytes: u64,
     pub completed: bool,
 }
 
+/// Byte progress of one file in a batch
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileProgress {
+    pub url: String,
+    pub bytes_downloaded: u64,
+    pub total_bytes: u64,
+}
+
+/// Progress of a whole batch of downloads, with speed and ETA
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BatchProgress {
+    pub files_completed: usize,
+    pub total_files: usize,
+    /// Bytes on disk across the batch, including resumed partial files
+    pub bytes_downloaded: u64,
+    pub total_bytes: u64,
+    /// Files currently transferring
+    pub active_files: Vec<FileProgress>,
+    /// Transfer rate over the last few seconds
+    pub speed_bytes_per_sec: u64,
+    /// Transfer rate since the batch started
+    pub average_bytes_per_sec: u64,
+    /// `None` until anything has been transferred
+    pub eta_secs: Option<u64>,
+    /// Set when this report was triggered by a file finishing
+    pub completed_file: Option<String>,
+}
+
+/// Turns per-file `DownloadProgress` updates into `BatchProgress` reports
+///
+/// Bytes a file already had on disk when it resumed count toward progress but not
+/// toward speed, so resuming a large partial file doesn't show a burst.
+pub struct ProgressTracker {
+    total_files: usize,
+    total_bytes: u64,
+    files_completed: usize,
+    completed_bytes: u64,
+    /// Bytes transferred by finished files during this batch
+    completed_transferred: u64,
+    /// url -> (bytes on disk when first seen, bytes now, file size)
+    in_flight: HashMap<String, (u64, u64, u64)>,
+    started: Instant,
+    /// (time, bytes transferred) samples covering `SPEED_WINDOW`
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl ProgressTracker {
+    pub fn new(total_files: usize, total_bytes: u64) -> Self {
+        Self::started_at(total_files, total_bytes, Instant::now())
+    }
+
+    fn started_at(total_files: usize, total_bytes: u64, started: Instant) -> Self {
+        Self {
+            total_files,
+            total_bytes,
+            files_completed: 0,
+            completed_bytes: 0,
+            completed_transferred: 0,
+            in_flight: HashMap::new(),
+            started,
+            samples: VecDeque::from([(started, 0)]),
+        }
+    }
+
+    /// Record an update and return the batch progress as of `now`
+    pub fn update(&mut self, progress: &DownloadProgress, now: Instant) -> BatchProgress {
+        let completed_file = if progress.completed {
+            let baseline = self.in_flight.remove(&progress.url).map_or(0, |(baseline, _, _)| baseline);
+            self.files_completed += 1;
+            self.completed_bytes += progress.total_bytes;
+            self.completed_transferred += progress.total_bytes.saturating_sub(baseline);
+            Some(progress.url.clone())
+        } else {
+            let entry = self
+                .in_flight
+                .entry(progress.url.clone())
+                .or_insert((progress.bytes_downloaded, 0, 0));
+            // A retry restarting from zero resets the baseline too
+            entry.0 = entry.0.min(progress.bytes_downloaded);
+            entry.1 = progress.bytes_downloaded;
+            entry.2 = progress.total_bytes;
+            None
+        };
+
+        let transferred = self.transferred();
+        self.samples.push_back((now, transferred));
+        // Keep one sample at or before the window start to measure from
+        while self.samples.len() > 2 && now.duration_since(self.samples[1].0) >= SPEED_WINDOW {
+            self.samples.pop_front();
+        }
+
+        let mut report = self.snapshot(now);
+        report.completed_file = completed_file;
+        report
+    }
+
+    fn transferred(&self) -> u64 {
+        self.completed_transferred
+            + self
+                .in_flight
+                .values()
+                .map(|(baseline, current, _)| current.saturating_sub(*baseline))
+                .sum::<u64>()
+    }
+
+    fn snapshot(&self, now: Instant) -> BatchProgress {
+        let transferred = self.transferred();
+        let (window_start, window_bytes) = self.samples.front().copied().unwrap_or((self.started, 0));
+        let speed = bytes_per_sec(transferred.saturating_sub(window_bytes), now.duration_since(window_start));
+        let average = bytes_per_sec(transferred, now.duration_since(self.started));
+
+        let bytes_downloaded = self.completed_bytes
+            + self.in_flight.values().map(|(_, current, _)| current).sum::<u64>();
+        let remaining = self.total_bytes.saturating_sub(bytes_downloaded);
+        let rate = if speed > 0 { speed } else { average };
+        let eta_secs = (rate > 0).then(|| remaining.div_ceil(rate));
+
+        let mut active_files: Vec<FileProgress> = self
+            .in_flight
+            .iter()
+            .map(|(url, (_, current, total))| FileProgress {
+                url: url.clone(),
+                bytes_downloaded: *current,
+                total_bytes: *total,
+            })
+            .collect();
+        active_files.sort_by(|a, b| a.url.cmp(&b.url));
+
+        BatchProgress {
+            files_completed: self.files_completed,
+            total_files: self.total_files,
+            bytes_downloaded,
+            total_bytes: self.total_bytes,
+            active_files,
+            speed_bytes_per_sec: speed,
+            average_bytes_per_sec: average,
+            eta_secs,
+            completed_file: None,
+        }
+    }
+}
+
+fn bytes_per_sec(bytes: u64, elapsed: Duration) -> u64 {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return 0;
+    }
+    (bytes as f64 / secs) as u64
+}
+
+/// Report batch progress for a download's progress channel until it closes
+///
+/// Completions are always reported; streaming updates at most every `BATCH_REPORT_INTERVAL`.
+pub async fn track_batch_progress(
+    mut progress_rx: mpsc::Receiver<DownloadProgress>,
+    total_files: usize,
+    total_bytes: u64,
+    on_progress: impl Fn(BatchProgress),
+) {
+    let mut tracker = ProgressTracker::new(total_files, total_bytes);
+    let mut last_report: Option<Instant> = None;
+
+    while let Some(progress) = progress_rx.recv().await {
+        let now = Instant::now();
+        let report = tracker.update(&progress, now);
+        if !progress.completed && last_report.is_some_and(|at| now.duration_since(at) < BATCH_REPORT_INTERVAL) {
+            continue;
+        }
+        last_report = Some(now);
+        on_progress(report);
+    }
+}
+
+/// Resume record persisted alongside a `.part` file
+///
+/// A partial download is only resumed when the URL and expected hash still match,
+/// so a changed manifest entry never gets glued onto stale bytes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PartialRecord {
+    url: String,
+    expected_hash: String,
+    bytes_downloaded: u64,
+}
+
+/// Persisted download preferences
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DownloadSettings {
+    /// Speed cap across all downloads in bytes per second; 0 means unlimited
+    #[serde(default)]
+    pub max_bytes_per_sec: u64,
+}
+
+/// Current limiter state reported to the frontend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DownloadControlState {
+    pub max_bytes_per_sec: u64,
+    pub paused: bool,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket speed cap plus a global pause switch
+///
+/// The bucket holds at most one second worth of bytes. Chunks larger than the
+/// balance are let through and paid back by sleeping, so the average rate holds
+/// without splitting chunks.
+#[derive(Debug)]
+pub struct BandwidthLimiter {
+    max_bytes_per_sec: AtomicU64,
+    bucket: std::sync::Mutex<TokenBucket>,
+    paused: watch::Sender<bool>,
+    /// Bumped on every pause so downloads can tell a failure was caused by pausing
+    pause_epoch: AtomicU64,
+}
+
+impl BandwidthLimiter {
+    pub fn new(max_bytes_per_sec: u64) -> Self {
+        Self {
+            max_bytes_per_sec: AtomicU64::new(max_bytes_per_sec),
+            bucket: std::sync::Mutex::new(TokenBucket {
+                tokens: max_bytes_per_sec as f64,
+                last_refill: Instant::now(),
+            }),
+            paused: watch::channel(false).0,
+            pause_epoch: AtomicU64::new(0),
+        }
+    }
+
+    pub fn state(&self) -> DownloadControlState {
+        DownloadControlState {
+            max_bytes_per_sec: self.max_bytes_per_sec.load(Ordering::Relaxed),
+            paused: self.is_paused(),
+        }
+    }
+
+    pub fn set_limit(&self, max_bytes_per_sec: u64) {
+        self.max_bytes_per_sec.store(max_bytes_per_sec, Ordering::Relaxed);
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        bucket.tokens = bucket.tokens.min(max_bytes_per_sec as f64);
+    }
+
+    pub fn pause(&self) {
+        self.pause_epoch.fetch_add(1, Ordering::Relaxed);
+        self.paused.send_replace(true);
+    }
+
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    fn pause_epoch(&self) -> u64 {
+        self.pause_epoch.load(Ordering::Relaxed)
+    }
+
+    /// Block until downloads are resumed (returns immediately when not paused)
+    pub async fn wait_while_paused(&self) {
+        let mut rx = self.paused.subscribe();
+        let _ = rx.wait_for(|paused| !*paused).await;
+    }
+
+    /// Wait until `bytes` may be written under the current cap
+    pub async fn acquire(&self, bytes: usize) {
+        self.wait_while_paused().await;
+
+        let limit = self.max_bytes_per_sec.load(Ordering::Relaxed);
+        if limit == 0 {
+            return;
+        }
+
+        let delay = {
+            let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * limit as f64).min(limit as f64);
+            bucket.last_refill = now;
+            bucket.tokens -= bytes as f64;
+            if bucket.tokens < 0.0 {
+                Duration::from_secs_f64(-bucket.tokens / limit as f64)
+            } else {
+                Duration::ZERO
+            }
+        };
+
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+fn download_settings_path() -> Result<PathBuf> {
+    Ok(super::paths::get_persistent_data_dir()?.join(DOWNLOAD_SETTINGS_FILE))
+}
+
+/// Missing or unreadable settings mean "no cap"
+fn read_download_settings(path: &Path) -> DownloadSettings {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub async fn save_download_settings_at(path: &Path, settings: &DownloadSettings) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .context("Failed to create data directory")?;
+    }
+    let json = serde_json::to_string_pretty(settings)?;
+    fs::write(path, json)
+        .await
+        .context("Failed to write download settings")
+}
+
+/// Apply and persist a new speed cap (0 = unlimited)
+pub async fn set_download_limit(max_bytes_per_sec: u64) -> Result<DownloadControlState> {
+    save_download_settings_at(&download_settings_path()?, &DownloadSettings { max_bytes_per_sec }).await?;
+    BANDWIDTH.set_limit(max_bytes_per_sec);
+    Ok(BANDWIDTH.state())
+}
+
 /// Shared download manager for coordinating concurrent downloads
 pub struct DownloadManager {
     client: Client,
     semaphore: Arc<Semaphore>,
     max_retries: u32,
+    limiter: Arc<BandwidthLimiter>,
+    cancel: CancelToken,
+    /// Measured round trip per origin (`None` = unreachable), probed once per manager
+    latencies: tokio::sync::Mutex<std::collections::HashMap<String, Option<Duration>>>,
 }
 
 impl DownloadManager {
@@ -59,7 +509,9 @@ impl DownloadManager {
             .pool_max_idle_per_host(max_concurrent)
             .pool_idle_timeout(Duration::from_secs(90))
             .connect_timeout(Duration::from_secs(30))
-            .timeout(Duration::from_secs(300))
+            // Per-read rather than whole-request timeout: capped or paused downloads can
+            // legitimately take longer than any fixed total
+            .read_timeout(Duration::from_secs(60))
             .build()
             .context("Failed to create HTTP client")?;
 
@@ -67,24 +519,92 @@ impl DownloadManager {
             client,
             semaphore: Arc::new(Semaphore::new(max_concurrent)),
             max_retries,
+            limiter: BANDWIDTH.clone(),
+            cancel: CancelToken::new(),
+            latencies: tokio::sync::Mutex::new(std::collections::HashMap::new()),
         })
     }
 
-    /// Get the shared HTTP client
+    /// Use a separate limiter instead of the global one
+    #[cfg(test)]
+    pub fn with_limiter(mut self, limiter: Arc<BandwidthLimiter>) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Stop downloading when `cancel` is cancelled
+    pub fn with_cancel(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Order a task's sources fastest first
+    ///
+    /// Each origin is probed once with a HEAD request and the result reused for every
+    /// later task. Tasks without mirrors skip probing entirely.
+    async fn rank_sources(&self, task: &DownloadTask) -> Vec<String> {
+        let mut sources = task.sources();
+        if sources.len() < 2 {
+            return sources;
+        }
+
+        let mut latencies = self.latencies.lock().await;
+        for url in &sources {
+            let origin = url_origin(url);
+            if !latencies.contains_key(&origin) {
+                let latency = self.probe_latency(url).await;
+                eprintln!("[Download] Source {} latency: {:?}", origin, latency);
+                latencies.insert(origin, latency);
+            }
+        }
+
+        // Stable sort keeps the manifest order between equally fast sources
+        sources.sort_by_key(|url| latencies.get(&url_origin(url)).copied().flatten().unwrap_or(Duration::MAX));
+        sources
+    }
+
+    async fn probe_latency(&self, url: &str) -> Option<Duration> {
+        let start = Instant::now();
+        let response = self
+            .client
+            .head(url)
+            .timeout(LATENCY_PROBE_TIMEOUT)
+            .send()
+            .await
+            .ok()?;
+        (response.status().is_success() || response.status().is_redirection()).then(|| start.elapsed())
+    }
 
     /// Download a single file with retry logic and progress tracking
+    ///
+    /// Attempts rotate through the task's sources (fastest first), so every mirror is
+    /// tried at least once before giving up.
     pub async fn download_file(
         &self,
         task: DownloadTask,
         progress_tx: Option<mpsc::Sender<DownloadProgress>>,
     ) -> Result<()> {
         // Acquire semaphore permit for concurrency control
-        let _permit = self.semaphore.acquire().await?;
+        let _permit = tokio::select! {
+            permit = self.semaphore.acquire() => permit?,
+            _ = self.cancel.cancelled() => return Err(InstallCancelled.into()),
+        };
+
+        let sources = self.rank_sources(&task).await;
+        let max_attempts = (self.max_retries + 1).max(sources.len() as u32);
 
         // Attempt download with retries
         let mut attempt = 0;
         loop {
-            match self.download_attempt(&task, progress_tx.clone()).await {
+            tokio::select! {
+                _ = self.limiter.wait_while_paused() => {}
+                _ = self.cancel.cancelled() => return Err(InstallCancelled.into()),
+            }
+            self.cancel.check()?;
+            let pause_epoch = self.limiter.pause_epoch();
+            let source = &sources[attempt as usize % sources.len()];
+
+            match self.download_attempt(&task, source, progress_tx.clone()).await {
                 Ok(_) => {
                     // Notify completion
                     if let Some(tx) = &progress_tx {
@@ -99,23 +619,37 @@ impl DownloadManager {
                     }
                     return Ok(());
                 }
-                Err(e) if attempt >= self.max_retries => {
+                Err(e) if is_cancelled(&e) => return Err(e),
+                // Connections dropped while paused don't count as failed attempts
+                Err(e) if self.limiter.pause_epoch() != pause_epoch => {
+                    eprintln!("[Download] {} interrupted by pause: {}", task.url, e);
+                }
+                Err(e) if attempt + 1 >= max_attempts => {
                     return Err(e).context(format!(
                         "Failed to download {} after {} attempts",
                         task.url,
                         attempt + 1
                     ));
                 }
+                Err(e) if (attempt as usize + 1) % sources.len() != 0 => {
+                    let next = &sources[(attempt as usize + 1) % sources.len()];
+                    eprintln!("[Download] {} failed: {}. Trying {}", source, e, next);
+                    attempt += 1;
+                }
                 Err(e) => {
-                    let backoff = Duration::from_secs(2_u64.pow(attempt));
+                    let round = attempt / sources.len() as u32;
+                    let backoff = Duration::from_secs(2_u64.pow(round));
                     eprintln!(
                         "Download failed (attempt {}/{}): {}. Retrying in {:?}",
                         attempt + 1,
-                        self.max_retries + 1,
+                        max_attempts,
                         e,
                         backoff
                     );
-                    tokio::time::sleep(backoff).await;
+                    tokio::select! {
+                        _ = tokio::time::sleep(backoff) => {}
+                        _ = self.cancel.cancelled() => return Err(InstallCancelled.into()),
+                    }
                     attempt += 1;
                 }
             }
@@ -123,9 +657,14 @@ impl DownloadManager {
     }
 
     /// Single download attempt with streaming and hash verification
+    ///
+    /// Data is streamed into `<dest>.part`; if a previous attempt left a partial file
+    /// behind, the download resumes from its offset with a Range request. Servers that
+    /// ignore the Range header (plain 200 response) get a full restart instead.
     async fn download_attempt(
         &self,
         task: &DownloadTask,
+        source: &str,
         progress_tx: Option<mpsc::Sender<DownloadProgress>>,
     ) -> Result<()> {
         // Create parent directory if needed
@@ -135,28 +674,112 @@ impl DownloadManager {
                 .context("Failed to create parent directory")?;
         }
 
-        // Start download
-        let response = self
-            .client
-            .get(&task.url)
-            .send()
-            .await
-            .context("Failed to send request")?
+        let part_path = partial_path(&task.dest, PART_SUFFIX);
+        let record_path = partial_path(&task.dest, RECORD_SUFFIX);
+        let mut offset = resume_offset(task, &part_path, &record_path).await;
+
+        // Start download (ranged if we have bytes to resume from)
+        let mut response = self.send_request(source, offset).await?;
+        if offset > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            eprintln!(
+                "[Download] Resume range rejected for {}, restarting from zero",
+                source
+            );
+            discard_partial(&part_path, &record_path).await;
+            offset = 0;
+            response = self.send_request(source, 0).await?;
+        }
+
+        let response = response
             .error_for_status()
             .context("HTTP error response")?;
 
-        let total_size = response.content_length().unwrap_or(task.size);
+        let resumed = offset > 0
+            && response.status() == StatusCode::PARTIAL_CONTENT
+            && content_range_start(&response) == Some(offset);
+        if offset > 0 && !resumed {
+            eprintln!(
+                "[Download] Server did not honor range for {}, restarting from zero",
+                source
+            );
+            offset = 0;
+        }
+
+        let total_size = response
+            .content_length()
+            .map(|len| len + offset)
+            .unwrap_or(task.size);
+
+        // Open the partial file, re-hashing any bytes we are keeping
+        let mut hasher = create_hasher(&task.expected_hash);
+        let mut file = if resumed {
+            hash_existing(&part_path, offset, hasher.as_mut()).await?;
+            let mut file = OpenOptions::new()
+                .write(true)
+                .open(&part_path)
+                .await
+                .context("Failed to open partial file")?;
+            file.set_len(offset)
+                .await
+                .context("Failed to truncate partial file")?;
+            file.seek(SeekFrom::End(0))
+                .await
+                .context("Failed to seek partial file")?;
+            eprintln!("[Download] Resuming {} at byte {} from {}", task.url, offset, source);
+            file
+        } else {
+            File::create(&part_path)
+                .await
+                .context("Failed to create file")?
+        };
+
+        let mut record = PartialRecord {
+            url: task.url.clone(),
+            expected_hash: task.expected_hash.value().to_string(),
+            bytes_downloaded: offset,
+        };
+        save_record(&record_path, &record).await?;
 
         // Stream download to file with progress tracking
-        let mut file = File::create(&task.dest)
-            .await
-            .context("Failed to create file")?;
         let mut stream = response.bytes_stream();
-        let mut bytes_downloaded = 0u64;
-        let mut hasher = create_hasher(&task.expected_hash);
+        let mut bytes_downloaded = offset;
+
+        loop {
+            let chunk = tokio::select! {
+                chunk = stream.next() => chunk,
+                _ = self.cancel.cancelled() => None,
+            };
+            let Some(chunk) = chunk else {
+                if self.cancel.is_cancelled() {
+                    // Keep the partial file so the next install resumes it
+                    file.flush().await.context("Failed to flush file")?;
+                    record.bytes_downloaded = bytes_downloaded;
+                    save_record(&record_path, &record).await?;
+                    return Err(InstallCancelled.into());
+                }
+                break;
+            };
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    // Keep what we have so the next attempt can pick up from here
+                    if file.flush().await.is_ok() {
+                        record.bytes_downloaded = bytes_downloaded;
+                        let _ = save_record(&record_path, &record).await;
+                    }
+                    return Err(e).context("Failed to read chunk");
+                }
+            };
 
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk.context("Failed to read chunk")?;
+            tokio::select! {
+                _ = self.limiter.acquire(chunk.len()) => {}
+                _ = self.cancel.cancelled() => {
+                    file.flush().await.context("Failed to flush file")?;
+                    record.bytes_downloaded = bytes_downloaded;
+                    save_record(&record_path, &record).await?;
+                    return Err(InstallCancelled.into());
+                }
+            }
 
             // Update hash
             hasher.update(&chunk);
@@ -168,6 +791,13 @@ impl DownloadManager {
 
             bytes_downloaded += chunk.len() as u64;
 
+            // Periodically persist the offset in case the launcher is closed mid-download
+            if bytes_downloaded - record.bytes_downloaded >= RECORD_INTERVAL_BYTES {
+                file.flush().await.context("Failed to flush file")?;
+                record.bytes_downloaded = bytes_downloaded;
+                save_record(&record_path, &record).await?;
+            }
+
             // Send progress update
             if let Some(tx) = &progress_tx {
                 let _ = tx
@@ -184,12 +814,30 @@ impl DownloadManager {
         file.flush().await.context("Failed to flush file")?;
         drop(file);
 
-        // Verify hash
-        verify_hash(hasher, &task.expected_hash, &task.dest)?;
+        // Verify hash; a corrupt partial must not be resumed again
+        if let Err(e) = verify_hash(hasher, &task.expected_hash, &task.dest) {
+            discard_partial(&part_path, &record_path).await;
+            return Err(e);
+        }
+
+        fs::rename(&part_path, &task.dest)
+            .await
+            .context("Failed to move completed download into place")?;
+        let _ = fs::remove_file(&record_path).await;
 
         Ok(())
     }
 
+    /// Send a GET request, asking for `bytes=offset-` when resuming
+    async fn send_request(&self, url: &str, offset: u64) -> Result<Response> {
+        let mut request = super::install_id::apply(self.client.get(url), url);
+        if offset > 0 {
+            request = request.header(RANGE, format!("bytes={}-", offset));
+        }
+
+        request.send().await.context("Failed to send request")
+    }
+
     /// Download multiple files concurrently
     pub async fn download_files(
         &self,
@@ -214,15 +862,127 @@ impl DownloadManager {
             .await;
 
         // Check for any errors
-        let errors: Vec<_> = results.into_iter().filter_map(|r| r.err()).collect();
+        let mut errors: Vec<_> = results.into_iter().filter_map(|r| r.err()).collect();
+        // Report a cancel as such, not as the failures it caused
+        if let Some(index) = errors.iter().position(is_cancelled) {
+            return Err(errors.swap_remove(index));
+        }
         if !errors.is_empty() {
-            anyhow::bail!("Download failures: {} files failed", errors.len());
+            // Keep the first failure as the cause so callers can tell what went wrong
+            let count = errors.len();
+            return Err(errors.swap_remove(0).context(format!("Download failures: {} files failed", count)));
         }
 
         Ok(())
     }
 }
 
+impl DownloadTask {
+    /// Primary URL followed by the mirrors, without duplicates
+    pub fn sources(&self) -> Vec<String> {
+        let mut sources = vec![self.url.clone()];
+        for mirror in &self.mirrors {
+            if !sources.contains(mirror) {
+                sources.push(mirror.clone());
+            }
+        }
+        sources
+    }
+}
+
+/// `scheme://host[:port]` part of a URL, used to share latency probes between files
+fn url_origin(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) => parsed.origin().ascii_serialization(),
+        Err(_) => url.to_string(),
+    }
+}
+
+/// Build a sibling path with an extra suffix (`mod.jar` -> `mod.jar.part`)
+fn partial_path(dest: &Path, suffix: &str) -> PathBuf {
+    let mut name = dest.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    name.push(".");
+    name.push(suffix);
+    dest.with_file_name(name)
+}
+
+/// Determine how many bytes of an existing partial download can be reused
+///
+/// Returns 0 (and removes leftovers) when there is no usable record.
+async fn resume_offset(task: &DownloadTask, part_path: &Path, record_path: &Path) -> u64 {
+    let record = match fs::read_to_string(record_path).await {
+        Ok(content) => serde_json::from_str::<PartialRecord>(&content).ok(),
+        Err(_) => None,
+    };
+
+    let record = match record {
+        Some(record)
+            if record.url == task.url
+                && record
+                    .expected_hash
+                    .eq_ignore_ascii_case(task.expected_hash.value()) =>
+        {
+            record
+        }
+        _ => {
+            discard_partial(part_path, record_path).await;
+            return 0;
+        }
+    };
+
+    let on_disk = fs::metadata(part_path).await.map(|m| m.len()).unwrap_or(0);
+    record.bytes_downloaded.min(on_disk)
+}
+
+/// Remove a partial download and its resume record (missing files are fine)
+async fn discard_partial(part_path: &Path, record_path: &Path) {
+    let _ = fs::remove_file(part_path).await;
+    let _ = fs::remove_file(record_path).await;
+}
+
+/// Persist the resume record
+async fn save_record(record_path: &Path, record: &PartialRecord) -> Result<()> {
+    let json = serde_json::to_string(record).context("Failed to serialize resume record")?;
+    fs::write(record_path, json)
+        .await
+        .context("Failed to write resume record")
+}
+
+/// Feed the first `len` bytes of a (partial) file into the hasher
+async fn hash_existing(part_path: &Path, len: u64, hasher: &mut dyn Hasher) -> Result<()> {
+    let mut file = File::open(part_path)
+        .await
+        .context("Failed to open partial file")?;
+    let mut remaining = len;
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    while remaining > 0 {
+        let want = remaining.min(buffer.len() as u64) as usize;
+        let read = file
+            .read(&mut buffer[..want])
+            .await
+            .context("Failed to read partial file")?;
+        if read == 0 {
+            anyhow::bail!("Partial file {} is shorter than expected", part_path.display());
+        }
+        hasher.update(&buffer[..read]);
+        remaining -= read as u64;
+    }
+
+    Ok(())
+}
+
+/// Parse the start offset from a `Content-Range: bytes <start>-<end>/<total>` header
+fn content_range_start(response: &Response) -> Option<u64> {
+    parse_content_range_start(response.headers().get(CONTENT_RANGE)?.to_str().ok()?)
+}
+
+fn parse_content_range_start(value: &str) -> Option<u64> {
+    let range = value.trim().strip_prefix("bytes ")?;
+    let (start, _) = range.split_once('-')?;
+    start.trim().parse().ok()
+}
+
 /// Create appropriate hasher based on hash type
 fn create_hasher(hash_type: &HashType) -> Box<dyn Hasher> {
     match hash_type {
@@ -234,10 +994,7 @@ fn create_hasher(hash_type: &HashType) -> Box<dyn Hasher> {
 /// Verify hash matches expected value
 fn verify_hash(hasher: Box<dyn Hasher>, expected: &HashType, path: &Path) -> Result<()> {
     let actual = hasher.finalize();
-    let expected_str = match expected {
-        HashType::Sha1(h) => h,
-        HashType::Sha256(h) => h,
-    };
+    let expected_str = expected.value();
 
     if actual.to_lowercase() != expected_str.to_lowercase() {
         anyhow::bail!(
@@ -277,6 +1034,46 @@ impl Hasher for Sha256Hasher {
     }
 }
 
+/// Lowercase hex SHA-256 of data already in memory
+pub fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    Digest::update(&mut hasher, data);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Whether `task.dest` already holds the expected file
+async fn is_already_downloaded(task: &DownloadTask) -> bool {
+    let len = match fs::metadata(&task.dest).await {
+        Ok(metadata) if metadata.is_file() => metadata.len(),
+        _ => return false,
+    };
+    // A size mismatch settles it without reading the file
+    if task.size > 0 && len != task.size {
+        return false;
+    }
+
+    let mut hasher = create_hasher(&task.expected_hash);
+    if hash_existing(&task.dest, len, hasher.as_mut()).await.is_err() {
+        return false;
+    }
+    verify_hash(hasher, &task.expected_hash, &task.dest).is_ok()
+}
+
+/// Drop tasks whose destination already matches the expected hash
+///
+/// Files are checked concurrently, so a reinstall over intact files costs one
+/// read of each file instead of a download.
+pub async fn retain_missing(tasks: Vec<DownloadTask>) -> Vec<DownloadTask> {
+    use futures::stream;
+
+    stream::iter(tasks)
+        .map(|task| async move { (!is_already_downloaded(&task).await).then_some(task) })
+        .buffer_unordered(calculate_optimal_concurrency())
+        .filter_map(|task| async move { task })
+        .collect()
+        .await
+}
+
 /// Calculate optimal concurrency based on system resources
 pub fn calculate_optimal_concurrency() -> usize {
     let cores = num_cpus::get();
@@ -290,3 +1087,365 @@ pub fn calculate_optimal_concurrency() -> usize {
         _ => 50,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const CONTENT: &[u8] = b"0123456789abcdefghij";
+
+    fn task_for(server: &MockServer, dest: PathBuf) -> DownloadTask {
+        DownloadTask {
+            url: format!("{}/mod.jar", server.uri()),
+            dest,
+            expected_hash: HashType::Sha256(sha256_hex(CONTENT)),
+            priority: DownloadPriority::Low,
+            size: CONTENT.len() as u64,
+            mirrors: Vec::new(),
+        }
+    }
+
+    fn update(url: &str, bytes_downloaded: u64, total_bytes: u64, completed: bool) -> DownloadProgress {
+        DownloadProgress { url: url.to_string(), bytes_downloaded, total_bytes, completed }
+    }
+
+    #[test]
+    fn test_progress_tracker_speed_and_eta() {
+        let start = Instant::now();
+        let mut tracker = ProgressTracker::started_at(2, 1000, start);
+
+        tracker.update(&update("a", 100, 800, false), start);
+        let report = tracker.update(&update("a", 300, 800, false), start + Duration::from_secs(1));
+        assert_eq!(report.bytes_downloaded, 300);
+        assert_eq!(report.active_files, vec![FileProgress { url: "a".to_string(), bytes_downloaded: 300, total_bytes: 800 }]);
+        // The 100 bytes the file resumed from don't count toward speed
+        assert_eq!(report.speed_bytes_per_sec, 200);
+        assert_eq!(report.average_bytes_per_sec, 200);
+        assert_eq!(report.eta_secs, Some(4));
+
+        let report = tracker.update(&update("a", 800, 800, true), start + Duration::from_secs(2));
+        assert_eq!(report.files_completed, 1);
+        assert_eq!(report.completed_file.as_deref(), Some("a"));
+        assert!(report.active_files.is_empty());
+        assert_eq!(report.bytes_downloaded, 800);
+        assert_eq!(report.average_bytes_per_sec, 350);
+    }
+
+    #[test]
+    fn test_progress_tracker_speed_window_drops_old_samples() {
+        let start = Instant::now();
+        let mut tracker = ProgressTracker::started_at(1, 10_000, start);
+
+        tracker.update(&update("a", 0, 10_000, false), start);
+        tracker.update(&update("a", 6_000, 10_000, false), start + Duration::from_secs(1));
+        // Stalled since: the windowed speed falls while the average stays up
+        let report = tracker.update(&update("a", 6_000, 10_000, false), start + Duration::from_secs(10));
+        assert_eq!(report.speed_bytes_per_sec, 0);
+        assert_eq!(report.average_bytes_per_sec, 600);
+        assert_eq!(report.eta_secs, Some(7));
+    }
+
+    fn write_partial(task: &DownloadTask, bytes: &[u8]) {
+        std::fs::write(partial_path(&task.dest, PART_SUFFIX), bytes).unwrap();
+        let record = PartialRecord {
+            url: task.url.clone(),
+            expected_hash: task.expected_hash.value().to_string(),
+            bytes_downloaded: bytes.len() as u64,
+        };
+        std::fs::write(
+            partial_path(&task.dest, RECORD_SUFFIX),
+            serde_json::to_string(&record).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_retain_missing_skips_intact_files() {
+        let server = MockServer::start().await;
+        let temp = TempDir::new().unwrap();
+
+        let intact = task_for(&server, temp.path().join("intact.jar"));
+        std::fs::write(&intact.dest, CONTENT).unwrap();
+        let corrupt = task_for(&server, temp.path().join("corrupt.jar"));
+        std::fs::write(&corrupt.dest, b"01234567890123456789").unwrap();
+        let truncated = task_for(&server, temp.path().join("truncated.jar"));
+        std::fs::write(&truncated.dest, &CONTENT[..5]).unwrap();
+        let missing = task_for(&server, temp.path().join("missing.jar"));
+
+        let mut remaining: Vec<PathBuf> = retain_missing(vec![intact, corrupt, truncated, missing])
+            .await
+            .into_iter()
+            .map(|task| task.dest)
+            .collect();
+        remaining.sort();
+        assert_eq!(
+            remaining,
+            vec![
+                temp.path().join("corrupt.jar"),
+                temp.path().join("missing.jar"),
+                temp.path().join("truncated.jar"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bandwidth_limiter_caps_rate() {
+        let limiter = BandwidthLimiter::new(10_000);
+
+        // The bucket starts full, so the first second worth of bytes is free
+        let start = Instant::now();
+        limiter.acquire(10_000).await;
+        assert!(start.elapsed() < Duration::from_millis(100));
+
+        limiter.acquire(5_000).await;
+        assert!(start.elapsed() >= Duration::from_millis(450));
+    }
+
+    #[tokio::test]
+    async fn test_bandwidth_limiter_pause_blocks_until_resumed() {
+        let limiter = Arc::new(BandwidthLimiter::new(0));
+        limiter.pause();
+
+        let waiter = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire(1).await })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+
+        limiter.resume();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("acquire should finish after resume")
+            .unwrap();
+        assert!(!limiter.state().paused);
+    }
+
+    #[tokio::test]
+    async fn test_download_waits_for_resume() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/mod.jar"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(CONTENT))
+            .mount(&server)
+            .await;
+
+        let temp_dir = TempDir::new().unwrap();
+        let task = task_for(&server, temp_dir.path().join("mod.jar"));
+        let limiter = Arc::new(BandwidthLimiter::new(0));
+        limiter.pause();
+        let manager = DownloadManager::new(1, 0).unwrap().with_limiter(limiter.clone());
+
+        let download = tokio::spawn(async move { manager.download_file(task, None).await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!temp_dir.path().join("mod.jar").exists());
+
+        limiter.resume();
+        download.await.unwrap().unwrap();
+        assert_eq!(std::fs::read(temp_dir.path().join("mod.jar")).unwrap(), CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_keeps_partial_for_resume() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/mod.jar"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(CONTENT))
+            .mount(&server)
+            .await;
+
+        let temp_dir = TempDir::new().unwrap();
+        let task = task_for(&server, temp_dir.path().join("mod.jar"));
+        // Slow enough that the download is still streaming when cancelled
+        let limiter = Arc::new(BandwidthLimiter::new(1));
+        let cancel = CancelToken::new();
+        let manager = DownloadManager::new(1, 3)
+            .unwrap()
+            .with_limiter(limiter)
+            .with_cancel(cancel.clone());
+
+        let download = tokio::spawn(async move { manager.download_file(task, None).await });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        cancel.cancel();
+
+        let result = tokio::time::timeout(Duration::from_secs(2), download).await.unwrap().unwrap();
+        assert!(is_cancelled(&result.unwrap_err()));
+        assert!(!temp_dir.path().join("mod.jar").exists());
+        assert!(partial_path(&temp_dir.path().join("mod.jar"), RECORD_SUFFIX).exists());
+    }
+
+    #[tokio::test]
+    async fn test_download_settings_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(DOWNLOAD_SETTINGS_FILE);
+        assert_eq!(read_download_settings(&path), DownloadSettings::default());
+
+        let settings = DownloadSettings { max_bytes_per_sec: 2 * 1024 * 1024 };
+        save_download_settings_at(&path, &settings).await.unwrap();
+        assert_eq!(read_download_settings(&path), settings);
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_to_mirror() {
+        let origin = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/mod.jar"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&origin)
+            .await;
+        let mirror = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/mod.jar"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(CONTENT))
+            .mount(&mirror)
+            .await;
+
+        let temp_dir = TempDir::new().unwrap();
+        let mut task = task_for(&origin, temp_dir.path().join("mod.jar"));
+        task.mirrors = vec![format!("{}/mod.jar", mirror.uri())];
+
+        // No retries configured, but every source still gets one attempt
+        let manager = DownloadManager::new(1, 0).unwrap();
+        manager.download_file(task, None).await.unwrap();
+        assert_eq!(std::fs::read(temp_dir.path().join("mod.jar")).unwrap(), CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_rank_sources_puts_unreachable_source_last() {
+        let mirror = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mirror)
+            .await;
+
+        let temp_dir = TempDir::new().unwrap();
+        let mut task = task_for(&mirror, temp_dir.path().join("mod.jar"));
+        task.url = "http://127.0.0.1:9/mod.jar".to_string();
+        task.mirrors = vec![format!("{}/mod.jar", mirror.uri())];
+
+        let manager = DownloadManager::new(1, 0).unwrap();
+        let sources = manager.rank_sources(&task).await;
+        assert_eq!(sources, vec![format!("{}/mod.jar", mirror.uri()), task.url.clone()]);
+    }
+
+    #[test]
+    fn test_task_sources_skip_duplicates() {
+        let task = DownloadTask {
+            url: "https://a.example/mod.jar".to_string(),
+            mirrors: vec![
+                "https://b.example/mod.jar".to_string(),
+                "https://a.example/mod.jar".to_string(),
+            ],
+            dest: PathBuf::from("mod.jar"),
+            expected_hash: HashType::Sha256(String::new()),
+            priority: DownloadPriority::Low,
+            size: 0,
+        };
+        assert_eq!(task.sources(), vec!["https://a.example/mod.jar", "https://b.example/mod.jar"]);
+        assert_eq!(url_origin("https://b.example:8443/files/mod.jar"), "https://b.example:8443");
+    }
+
+    #[test]
+    fn test_parse_content_range_start() {
+        assert_eq!(parse_content_range_start("bytes 10-19/20"), Some(10));
+        assert_eq!(parse_content_range_start("bytes */20"), None);
+        assert_eq!(parse_content_range_start("items 0-1/2"), None);
+    }
+
+    #[tokio::test]
+    async fn test_fresh_download_cleans_up_partial_files() {
+        let server = MockServer::start().await;
+        let temp_dir = TempDir::new().unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/mod.jar"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(CONTENT))
+            .mount(&server)
+            .await;
+
+        let task = task_for(&server, temp_dir.path().join("mod.jar"));
+        let manager = DownloadManager::new(1, 0).unwrap();
+        manager.download_file(task.clone(), None).await.unwrap();
+
+        assert_eq!(std::fs::read(&task.dest).unwrap(), CONTENT);
+        assert!(!partial_path(&task.dest, PART_SUFFIX).exists());
+        assert!(!partial_path(&task.dest, RECORD_SUFFIX).exists());
+    }
+
+    #[tokio::test]
+    async fn test_resumes_partial_download_with_range() {
+        let server = MockServer::start().await;
+        let temp_dir = TempDir::new().unwrap();
+
+        // Only a ranged request is answered; a full GET would 404
+        Mock::given(method("GET"))
+            .and(path("/mod.jar"))
+            .and(header("Range", "bytes=8-"))
+            .respond_with(
+                ResponseTemplate::new(206)
+                    .insert_header("Content-Range", "bytes 8-19/20")
+                    .set_body_bytes(&CONTENT[8..]),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let task = task_for(&server, temp_dir.path().join("mod.jar"));
+        write_partial(&task, &CONTENT[..8]);
+
+        let manager = DownloadManager::new(1, 0).unwrap();
+        manager.download_file(task.clone(), None).await.unwrap();
+
+        assert_eq!(std::fs::read(&task.dest).unwrap(), CONTENT);
+        assert!(!partial_path(&task.dest, PART_SUFFIX).exists());
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_full_download_without_range_support() {
+        let server = MockServer::start().await;
+        let temp_dir = TempDir::new().unwrap();
+
+        // Server ignores Range and always sends the whole file
+        Mock::given(method("GET"))
+            .and(path("/mod.jar"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(CONTENT))
+            .mount(&server)
+            .await;
+
+        let task = task_for(&server, temp_dir.path().join("mod.jar"));
+        write_partial(&task, &CONTENT[..8]);
+
+        let manager = DownloadManager::new(1, 0).unwrap();
+        manager.download_file(task.clone(), None).await.unwrap();
+
+        assert_eq!(std::fs::read(&task.dest).unwrap(), CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_stale_record_is_discarded() {
+        let temp_dir = TempDir::new().unwrap();
+        let task = DownloadTask {
+            url: "http://example.com/mod.jar".to_string(),
+            dest: temp_dir.path().join("mod.jar"),
+            expected_hash: HashType::Sha256("newhash".to_string()),
+            priority: DownloadPriority::Low,
+            size: 0,
+            mirrors: Vec::new(),
+        };
+
+        let stale = DownloadTask {
+            expected_hash: HashType::Sha256("oldhash".to_string()),
+            ..task.clone()
+        };
+        write_partial(&stale, b"old bytes");
+
+        let part_path = partial_path(&task.dest, PART_SUFFIX);
+        let record_path = partial_path(&task.dest, RECORD_SUFFIX);
+        assert_eq!(resume_offset(&task, &part_path, &record_path).await, 0);
+        assert!(!part_path.exists());
+        assert!(!record_path.exists());
+    }
+}
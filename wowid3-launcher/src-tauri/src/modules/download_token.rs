@@ -0,0 +1,134 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::install_id::url_origin;
+use super::{auth, http};
+
+/// Fetch a new token when the current one has less than this left
+const REFRESH_MARGIN_SECS: i64 = 300;
+const TOKEN_REQUEST_TIMEOUT_SECS: u64 = 15;
+
+/// Download token for the modpack server, tied to the signed-in player
+#[derive(Debug, Clone)]
+struct CachedToken {
+    origin: String,
+    uuid: String,
+    token: String,
+    expires_at: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    token: String,
+    expires_at: i64,
+}
+
+lazy_static::lazy_static! {
+    static ref TOKEN: RwLock<Option<CachedToken>> = RwLock::new(None);
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// The token to present to the modpack server, if one is still valid
+pub fn current() -> Option<String> {
+    let cached = TOKEN.read().unwrap_or_else(|e| e.into_inner());
+    cached
+        .as_ref()
+        .filter(|cached| cached.expires_at > now_secs())
+        .map(|cached| cached.token.clone())
+}
+
+fn is_fresh(cached: &CachedToken, origin: &str, uuid: &str) -> bool {
+    cached.origin == origin && cached.uuid == uuid && cached.expires_at - now_secs() > REFRESH_MARGIN_SECS
+}
+
+/// Make sure a download token for the server at `server_url` (any URL on it) is on hand
+///
+/// Servers without DOWNLOAD_AUTH serve everything without one, so failures (signed
+/// out, not whitelisted, older server) are logged rather than returned; the download
+/// itself reports the 401 if the server does insist on a token.
+pub async fn ensure(server_url: &str) {
+    if let Err(e) = refresh(server_url).await {
+        eprintln!("[DownloadToken] No download token: {:#}", e);
+    }
+}
+
+async fn refresh(server_url: &str) -> Result<()> {
+    let origin = url_origin(server_url).context("Invalid server URL")?;
+    let Some(profile) = auth::get_current_user()? else {
+        anyhow::bail!("not signed in");
+    };
+
+    let fresh = TOKEN
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .is_some_and(|cached| is_fresh(cached, &origin, &profile.uuid));
+    if fresh {
+        return Ok(());
+    }
+
+    let access_token = auth::get_access_token_by_session_id(&profile.session_id)?;
+    let url = format!("{}/api/auth/token", origin);
+    let response = http::client()
+        .post(&url)
+        .timeout(Duration::from_secs(TOKEN_REQUEST_TIMEOUT_SECS))
+        .json(&serde_json::json!({
+            "uuid": profile.uuid,
+            "access_token": access_token,
+        }))
+        .send()
+        .await
+        .context("Failed to request a download token")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        // Don't keep presenting a token the server no longer issues to us
+        *TOKEN.write().unwrap_or_else(|e| e.into_inner()) = None;
+        anyhow::bail!("server refused ({}): {}", status, body);
+    }
+
+    let issued: TokenResponse = response
+        .json()
+        .await
+        .context("Invalid download token response")?;
+    *TOKEN.write().unwrap_or_else(|e| e.into_inner()) = Some(CachedToken {
+        origin,
+        uuid: profile.uuid,
+        token: issued.token,
+        expires_at: issued.expires_at,
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_refreshed_near_expiry_or_on_change() {
+        let cached = CachedToken {
+            origin: "https://packs.example.com".to_string(),
+            uuid: "069a79f4-44e9-4726-a5be-fca90e38aaf5".to_string(),
+            token: "t".to_string(),
+            expires_at: now_secs() + 3600,
+        };
+        assert!(is_fresh(&cached, &cached.origin, &cached.uuid));
+        assert!(!is_fresh(&cached, "https://other.example.com", &cached.uuid));
+        assert!(!is_fresh(&cached, &cached.origin, "853c80ef-3c37-49fd-aa49-938b674adae6"));
+
+        let expiring = CachedToken {
+            expires_at: now_secs() + REFRESH_MARGIN_SECS - 10,
+            ..cached
+        };
+        assert!(!is_fresh(&expiring, &expiring.origin, &expiring.uuid));
+    }
+}
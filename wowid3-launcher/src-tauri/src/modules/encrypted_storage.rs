@@ -274,6 +274,7 @@ mod tests {
             session_id: "test-session-id".to_string(),
             skin_url: Some("https://example.com/skin.png".to_string()),
             expires_at: None,
+            demo: false,
         };
 
         // Save
@@ -0,0 +1,179 @@
+use serde::Serialize;
+use std::io;
+
+use super::auth::{AuthServiceUnavailable, NotSignedIn};
+use super::download_manager::InstallCancelled;
+use super::offline;
+
+/// What went wrong, for the frontend to pick its message and recovery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// A server couldn't be reached, timed out or answered with a server error
+    Network,
+    /// No one is signed in, or the session can't be used any more
+    NotSignedIn,
+    /// Microsoft, Xbox Live or Minecraft auth services are down
+    AuthUnavailable,
+    DiskFull,
+    PermissionDenied,
+    NotFound,
+    /// Stopped by `cmd_cancel_installation`
+    Cancelled,
+    Other,
+}
+
+impl ErrorKind {
+    /// Whether running the same command again may succeed without the user changing anything
+    pub fn retryable(self) -> bool {
+        matches!(self, ErrorKind::Network | ErrorKind::AuthUnavailable)
+    }
+}
+
+/// Error every command returns; serialized as `{ kind, message, retryable, context }`
+///
+/// `message` is the whole error as the commands used to report it; `context` lists the
+/// causes beneath the outermost one, outermost first.
+#[derive(Debug, Clone, Serialize)]
+pub struct LauncherError {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub retryable: bool,
+    pub context: Vec<String>,
+}
+
+impl LauncherError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            retryable: kind.retryable(),
+            context: Vec::new(),
+        }
+    }
+
+    pub fn network(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Network, message)
+    }
+}
+
+impl std::fmt::Display for LauncherError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+fn io_kind(error: &io::Error) -> Option<ErrorKind> {
+    // ENOSPC on Unix; ERROR_HANDLE_DISK_FULL and ERROR_DISK_FULL on Windows
+    if error.kind() == io::ErrorKind::StorageFull || matches!(error.raw_os_error(), Some(28 | 39 | 112)) {
+        return Some(ErrorKind::DiskFull);
+    }
+    match error.kind() {
+        io::ErrorKind::PermissionDenied | io::ErrorKind::ReadOnlyFilesystem => Some(ErrorKind::PermissionDenied),
+        io::ErrorKind::NotFound => Some(ErrorKind::NotFound),
+        io::ErrorKind::TimedOut | io::ErrorKind::ConnectionRefused | io::ErrorKind::ConnectionReset => {
+            Some(ErrorKind::Network)
+        }
+        _ => None,
+    }
+}
+
+/// Kind of a module error, from the first cause in its chain that says
+fn classify(error: &anyhow::Error) -> ErrorKind {
+    for cause in error.chain() {
+        if cause.is::<InstallCancelled>() {
+            return ErrorKind::Cancelled;
+        }
+        if cause.is::<NotSignedIn>() {
+            return ErrorKind::NotSignedIn;
+        }
+        if cause.is::<AuthServiceUnavailable>() {
+            return ErrorKind::AuthUnavailable;
+        }
+        if let Some(kind) = cause.downcast_ref::<io::Error>().and_then(io_kind) {
+            return kind;
+        }
+    }
+    if offline::is_network_error(error) {
+        ErrorKind::Network
+    } else {
+        ErrorKind::Other
+    }
+}
+
+impl From<anyhow::Error> for LauncherError {
+    fn from(error: anyhow::Error) -> Self {
+        let kind = classify(&error);
+        // A cancel reads the same whichever step it stopped
+        let message = if kind == ErrorKind::Cancelled {
+            InstallCancelled.to_string()
+        } else {
+            format!("{:#}", error)
+        };
+        Self {
+            context: error.chain().skip(1).map(|cause| cause.to_string()).collect(),
+            ..Self::new(kind, message)
+        }
+    }
+}
+
+impl From<String> for LauncherError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorKind::Other, message)
+    }
+}
+
+impl From<&str> for LauncherError {
+    fn from(message: &str) -> Self {
+        Self::new(ErrorKind::Other, message)
+    }
+}
+
+impl From<tokio::task::JoinError> for LauncherError {
+    fn from(error: tokio::task::JoinError) -> Self {
+        anyhow::Error::new(error).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_errors_classified_from_cause_chain() {
+        let disk_full: anyhow::Result<()> = Err(io::Error::from_raw_os_error(28).into());
+        let error = LauncherError::from(disk_full.context("Failed to write mods/sodium.jar").unwrap_err());
+        assert_eq!(error.kind, ErrorKind::DiskFull);
+        assert!(error.message.starts_with("Failed to write mods/sodium.jar: "));
+        assert_eq!(error.context.len(), 1);
+        assert!(!error.retryable);
+
+        let cancelled = anyhow::Error::new(InstallCancelled).context("Failed to download assets");
+        let error = LauncherError::from(cancelled);
+        assert_eq!(error.kind, ErrorKind::Cancelled);
+        assert_eq!(error.message, "Installation cancelled");
+
+        let outage = anyhow::Error::new(AuthServiceUnavailable("Xbox Live returned 503".to_string()));
+        let error = LauncherError::from(outage);
+        assert_eq!(error.kind, ErrorKind::AuthUnavailable);
+        assert!(error.retryable);
+
+        assert_eq!(LauncherError::from(anyhow::Error::new(NotSignedIn)).kind, ErrorKind::NotSignedIn);
+        assert_eq!(LauncherError::from(anyhow::anyhow!("Invalid manifest")).kind, ErrorKind::Other);
+    }
+
+    #[test]
+    fn test_serialized_shape() {
+        let error = LauncherError::network("Connection timeout");
+        assert_eq!(
+            serde_json::to_value(error).unwrap(),
+            serde_json::json!({
+                "kind": "network",
+                "message": "Connection timeout",
+                "retryable": true,
+                "context": [],
+            })
+        );
+    }
+}
@@ -1,21 +1,37 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Mutex};
 
 use super::asset_manager;
-use super::fabric_installer;
+use super::download_manager::{
+    self, begin_install, CancelToken, DownloadManager, DownloadPriority, DownloadProgress, DownloadTask, HashType,
+    InstallCancelled,
+};
 use super::library_manager;
-use super::minecraft_version::{get_version_meta, VersionMeta};
+use super::minecraft_version::{get_version_meta, VersionMeta, LOG_CONFIGS_DIR};
+use super::mod_loader::ModLoader;
 
 /// Installation configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InstallConfig {
     pub game_version: String,
-    pub fabric_version: Option<String>,
+    #[serde(default)]
+    pub loader: ModLoader,
+    /// Vanilla install when `None`
+    #[serde(alias = "fabric_version")]
+    pub loader_version: Option<String>,
+    /// Ignored when `instance_id` is set
+    #[serde(default)]
     pub game_dir: PathBuf,
+    #[serde(default)]
+    pub instance_id: Option<String>,
+    /// Java used to run loader installers (NeoForge)
+    #[serde(default)]
+    pub java_path: Option<PathBuf>,
 }
 
 /// Installation progress
@@ -29,7 +45,14 @@ pub struct InstallProgress {
     pub message: String,
 }
 
-/// Install Minecraft (vanilla or Fabric)
+/// Minimum time between byte-level progress reports (file completions always report)
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Install Minecraft (vanilla or with a mod loader)
+///
+/// The client jar, libraries and assets are downloaded as one prioritized batch
+/// (libraries first). Files already on disk with the right hash are kept, so a
+/// reinstall only fetches what is missing or corrupt.
 pub async fn install_minecraft<F>(
     config: InstallConfig,
     progress_callback: F,
@@ -39,11 +62,12 @@ where
 {
     let game_dir = &config.game_dir;
     let cache_dir = game_dir.join(".cache");
+    let cancel = begin_install();
 
     // Wrap callback in Arc<Mutex<>> for thread-safe sharing
     let progress_callback = Arc::new(Mutex::new(progress_callback));
 
-    // Step 0: Clean existing installation to force fresh install
+    // Step 0: Clear extracted natives; libraries and assets are verified in place instead
     {
         let mut callback = progress_callback.lock().await;
         callback(InstallProgress {
@@ -52,36 +76,10 @@ where
             total: 6,
             current_bytes: 0,
             total_bytes: 0,
-            message: "Cleaning existing installation...".to_string(),
+            message: "Preparing installation...".to_string(),
         });
     }
 
-    // Determine version ID for cleanup
-    let temp_version_id = if let Some(fabric_version) = &config.fabric_version {
-        format!("fabric-loader-{}-{}", fabric_version, config.game_version)
-    } else {
-        config.game_version.clone()
-    };
-
-    // Delete existing version directory, libraries, assets, and natives for fresh install
-    let version_dir = game_dir.join("versions").join(&temp_version_id);
-    if version_dir.exists() {
-        tokio::fs::remove_dir_all(&version_dir).await.ok();
-    }
-
-    // Delete libraries (they'll be re-downloaded)
-    let libraries_dir = game_dir.join("libraries");
-    if libraries_dir.exists() {
-        tokio::fs::remove_dir_all(&libraries_dir).await.ok();
-    }
-
-    // Delete assets (they'll be re-downloaded)
-    let assets_dir = game_dir.join("assets");
-    if assets_dir.exists() {
-        tokio::fs::remove_dir_all(&assets_dir).await.ok();
-    }
-
-    // Delete natives
     let natives_dir = game_dir.join("natives");
     if natives_dir.exists() {
         tokio::fs::remove_dir_all(&natives_dir).await.ok();
@@ -101,121 +99,124 @@ where
     }
 
     let mut version_meta = get_version_meta(&config.game_version, &cache_dir).await?;
+    let libraries_dir = game_dir.join("libraries");
 
-    // Step 2: Handle Fabric if requested
-    if let Some(fabric_version) = &config.fabric_version {
+    // Step 2: Install the mod loader if requested
+    if let Some(loader_version) = &config.loader_version {
         {
             let mut callback = progress_callback.lock().await;
             callback(InstallProgress {
-                step: "fabric".to_string(),
+                step: "loader".to_string(),
                 current: 2,
                 total: 6,
                 current_bytes: 0,
                 total_bytes: 0,
-                message: format!("Installing Fabric loader {}", fabric_version),
+                message: format!("Installing {} loader {}", config.loader.name(), loader_version),
             });
         }
 
-        let fabric_profile = fabric_installer::get_fabric_profile(
-            &config.game_version,
-            fabric_version,
-            &cache_dir,
-        )
-        .await?;
-
-        // Merge Fabric with vanilla
-        version_meta = fabric_installer::merge_fabric_with_vanilla(&version_meta, &fabric_profile, fabric_version);
-
-        // Download Fabric libraries
-        let libraries_dir = game_dir.join("libraries");
-        fabric_installer::download_fabric_libraries(&fabric_profile.libraries, &libraries_dir)
-            .await?;
+        // Its checksummed libraries download with the rest. Dropping the install on
+        // cancel also kills the NeoForge installer process.
+        version_meta = tokio::select! {
+            result = config.loader.install(&version_meta, loader_version, game_dir, config.java_path.as_deref()) => result?,
+            _ = cancel.cancelled() => return Err(InstallCancelled.into()),
+        };
     }
 
-    // Step 3: Download client JAR
+    // Step 3: Collect client, library and asset downloads and skip intact files
     {
         let mut callback = progress_callback.lock().await;
         callback(InstallProgress {
-            step: "client".to_string(),
+            step: "verifying".to_string(),
             current: 3,
             total: 6,
             current_bytes: 0,
             total_bytes: 0,
-            message: "Downloading Minecraft client".to_string(),
+            message: "Checking installed files".to_string(),
         });
     }
 
     let versions_dir = game_dir.join("versions").join(&version_meta.id);
     tokio::fs::create_dir_all(&versions_dir).await?;
 
-    let client_jar = versions_dir.join(format!("{}.jar", version_meta.id));
-    library_manager::download_file_verified(
-        &version_meta.downloads.client.url,
-        &client_jar,
-        Some(&version_meta.downloads.client.sha1),
-    )
-    .await?;
-
-    // Step 4: Download libraries
-    {
-        let mut callback = progress_callback.lock().await;
-        callback(InstallProgress {
-            step: "libraries".to_string(),
-            current: 4,
-            total: 6,
-            current_bytes: 0,
-            total_bytes: 0,
-            message: format!("Downloading {} libraries", version_meta.libraries.len()),
-        });
-    }
+    let client = &version_meta.downloads.client;
+    let mut tasks = vec![DownloadTask {
+        url: client.url.clone(),
+        mirrors: Vec::new(),
+        dest: versions_dir.join(format!("{}.jar", version_meta.id)),
+        expected_hash: HashType::Sha1(client.sha1.clone()),
+        priority: DownloadPriority::High,
+        size: client.size,
+    }];
 
-    let libraries_dir = game_dir.join("libraries");
     let features = HashMap::new(); // Default features (can be extended later)
-    library_manager::download_all_libraries(&version_meta.libraries, &libraries_dir, &features)
-        .await?;
-
-    // Extract natives
-    let natives_dir = game_dir.join("natives");
-    library_manager::extract_natives(
+    tasks.extend(library_manager::library_download_tasks(
         &version_meta.libraries,
         &libraries_dir,
-        &natives_dir,
         &features,
+    ));
+
+    let assets_dir = game_dir.join("assets");
+    let asset_index = asset_manager::download_asset_index(&version_meta.asset_index, &assets_dir)
+        .await?;
+    tasks.extend(asset_manager::asset_download_tasks(&asset_index, &assets_dir));
+
+    // Versions bundling a log4j vulnerable to Log4Shell launch with Mojang's patched config
+    if let Some(log_config) = version_meta.log4j_config() {
+        tasks.push(DownloadTask {
+            url: log_config.file.url.clone(),
+            mirrors: Vec::new(),
+            dest: game_dir.join(LOG_CONFIGS_DIR).join(&log_config.file.id),
+            expected_hash: HashType::Sha1(log_config.file.sha1.clone()),
+            priority: DownloadPriority::High,
+            size: log_config.file.size,
+        });
+    }
+
+    cancel.check()?;
+    let checked = tasks.len();
+    let tasks = download_manager::retain_missing(tasks).await;
+    eprintln!(
+        "[Game Installer] {} of {} files already installed, {} to download",
+        checked - tasks.len(),
+        checked,
+        tasks.len()
+    );
+
+    // Step 4: Download everything missing in one batch
+    if !tasks.is_empty() {
+        download_game_files(tasks, progress_callback.clone(), cancel.clone()).await?;
+    }
+
+    asset_manager::reconstruct_legacy_assets(
+        &asset_index,
+        &version_meta.asset_index.id,
+        &assets_dir,
+        game_dir,
     )
     .await?;
 
-    // Step 5: Download assets
+    cancel.check()?;
+
+    // Step 5: Extract natives
     {
         let mut callback = progress_callback.lock().await;
         callback(InstallProgress {
-            step: "assets".to_string(),
+            step: "natives".to_string(),
             current: 5,
             total: 6,
             current_bytes: 0,
             total_bytes: 0,
-            message: "Downloading assets".to_string(),
+            message: "Extracting native libraries".to_string(),
         });
     }
 
-    let assets_dir = game_dir.join("assets");
-    let asset_index = asset_manager::download_asset_index(&version_meta.asset_index, &assets_dir)
-        .await?;
-
-    let progress_callback_clone = progress_callback.clone();
-    asset_manager::download_all_assets(&asset_index, &assets_dir, move |current, total, current_bytes, total_bytes, msg| {
-        let callback = progress_callback_clone.clone();
-        tokio::spawn(async move {
-            let mut cb = callback.lock().await;
-            cb(InstallProgress {
-                step: "assets".to_string(),
-                current: current as u64,
-                total: total as u64,
-                current_bytes,
-                total_bytes,
-                message: msg,
-            });
-        });
-    })
+    library_manager::extract_natives(
+        &version_meta.libraries,
+        &libraries_dir,
+        &natives_dir,
+        &features,
+    )
     .await?;
 
     // Step 6: Save version metadata
@@ -239,12 +240,93 @@ where
     Ok(version_meta)
 }
 
+/// Download the client, libraries and assets together, reporting combined progress
+///
+/// Progress is reported as step `libraries` until every high-priority file is done,
+/// then as `assets`; `current`/`total` count files across the whole batch.
+async fn download_game_files<F>(
+    tasks: Vec<DownloadTask>,
+    progress_callback: Arc<Mutex<F>>,
+    cancel: CancelToken,
+) -> Result<()>
+where
+    F: FnMut(InstallProgress) + Send + 'static,
+{
+    let total = tasks.len() as u64;
+    let total_bytes: u64 = tasks.iter().map(|task| task.size).sum();
+    let library_urls: HashSet<String> = tasks
+        .iter()
+        .filter(|task| task.priority == DownloadPriority::High)
+        .map(|task| task.url.clone())
+        .collect();
+
+    let (progress_tx, mut progress_rx) = mpsc::channel::<DownloadProgress>(1000);
+    let reporter = tokio::spawn(async move {
+        let mut in_flight: HashMap<String, u64> = HashMap::new();
+        let mut completed: u64 = 0;
+        let mut completed_bytes: u64 = 0;
+        let mut libraries_left = library_urls.len();
+        let mut last_report: Option<Instant> = None;
+
+        while let Some(progress) = progress_rx.recv().await {
+            if progress.completed {
+                in_flight.remove(&progress.url);
+                completed += 1;
+                completed_bytes += progress.total_bytes;
+                if library_urls.contains(&progress.url) {
+                    libraries_left = libraries_left.saturating_sub(1);
+                }
+            } else {
+                in_flight.insert(progress.url, progress.bytes_downloaded);
+                if last_report.is_some_and(|at| at.elapsed() < PROGRESS_INTERVAL) {
+                    continue;
+                }
+            }
+            last_report = Some(Instant::now());
+
+            let current_bytes = completed_bytes + in_flight.values().sum::<u64>();
+            let step = if libraries_left > 0 { "libraries" } else { "assets" };
+            let mut callback = progress_callback.lock().await;
+            callback(InstallProgress {
+                step: step.to_string(),
+                current: completed,
+                total,
+                current_bytes: current_bytes.min(total_bytes),
+                total_bytes,
+                message: format!("Downloading {} ({}/{} files)", step, completed, total),
+            });
+        }
+    });
+
+    let manager =
+        DownloadManager::new(download_manager::calculate_optimal_concurrency(), 3)?.with_cancel(cancel);
+    let result = manager
+        .download_files(tasks, Some(progress_tx))
+        .await
+        .context("Failed to download game files");
+
+    // All senders are gone once the batch finishes, which ends the reporter
+    reporter.await?;
+    result
+}
+
 /// Check if a version is installed
 pub async fn is_version_installed(game_dir: &Path, version_id: &str) -> Result<bool> {
     let version_dir = game_dir.join("versions").join(version_id);
     let version_json = version_dir.join(format!("{}.json", version_id));
     let version_jar = version_dir.join(format!("{}.jar", version_id));
 
+    // A custom version without its own jar runs its parent's
+    if version_json.exists() && !version_jar.exists() {
+        return match get_installed_version(game_dir, version_id).await {
+            Ok(meta) => Ok(game_dir.join(client_jar_path(game_dir, &meta)).exists()),
+            Err(e) => {
+                eprintln!("[Game Installer] Cannot resolve version {}: {:#}", version_id, e);
+                Ok(false)
+            }
+        };
+    }
+
     eprintln!("[Game Installer] is_version_installed() checking for version: {}", version_id);
     eprintln!("[Game Installer]   game_dir: {:?}", game_dir);
     eprintln!("[Game Installer]   version_dir: {:?}", version_dir);
@@ -257,21 +339,150 @@ pub async fn is_version_installed(game_dir: &Path, version_id: &str) -> Result<b
     Ok(installed)
 }
 
+/// Longest `inheritsFrom` chain followed before assuming a loop
+const MAX_INHERITANCE_DEPTH: usize = 8;
+
 /// Get installed version metadata
+///
+/// Custom version JSONs (OptiFine, tweaked loader profiles) may only list what they
+/// change and name the rest with `inheritsFrom`; the parent chain is merged the way the
+/// vanilla launcher does it. Parents that aren't installed come from Mojang's metadata.
 pub async fn get_installed_version(game_dir: &Path, version_id: &str) -> Result<VersionMeta> {
+    let mut chain: Vec<serde_json::Value> = Vec::new();
+    let mut next = Some(version_id.to_string());
+
+    while let Some(id) = next {
+        if chain.len() >= MAX_INHERITANCE_DEPTH {
+            anyhow::bail!("Version {} inherits through more than {} versions", version_id, MAX_INHERITANCE_DEPTH);
+        }
+
+        let json = match read_version_json(game_dir, &id).await? {
+            Some(json) => json,
+            None if !chain.is_empty() => {
+                let meta = get_version_meta(&id, &game_dir.join(".cache"))
+                    .await
+                    .with_context(|| format!("Parent version {} is not installed", id))?;
+                serde_json::to_value(meta)?
+            }
+            None => anyhow::bail!("Failed to read version metadata: {} is not installed", id),
+        };
+
+        next = json.get("inheritsFrom").and_then(|v| v.as_str()).map(str::to_string);
+        chain.push(json);
+    }
+
+    // Apply each child over its parent, starting from the root
+    let mut merged = chain.pop().context("Empty version chain")?;
+    while let Some(child) = chain.pop() {
+        merged = merge_version_json(merged, child)?;
+    }
+
+    serde_json::from_value(merged).context("Failed to parse version metadata")
+}
+
+async fn read_version_json(game_dir: &Path, version_id: &str) -> Result<Option<serde_json::Value>> {
     let version_json = game_dir
         .join("versions")
         .join(version_id)
         .join(format!("{}.json", version_id));
 
+    if !version_json.exists() {
+        return Ok(None);
+    }
+
     let content = tokio::fs::read_to_string(&version_json)
         .await
         .context("Failed to read version metadata")?;
 
-    let meta: VersionMeta = serde_json::from_str(&content)
-        .context("Failed to parse version metadata")?;
+    let json = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse version metadata for {}", version_id))?;
+
+    Ok(Some(json))
+}
 
-    Ok(meta)
+/// Maven coordinates without the version, so a child's library replaces the parent's copy
+fn library_key(library: &serde_json::Value) -> Option<String> {
+    let name = library.get("name")?.as_str()?;
+    let mut parts: Vec<&str> = name.split(':').collect();
+    if parts.len() >= 3 {
+        parts.remove(2);
+    }
+    Some(parts.join(":"))
+}
+
+/// Apply a child version JSON over its `inheritsFrom` parent
+///
+/// Child fields replace the parent's, child libraries take precedence over the same
+/// artifact in the parent, and argument lists are appended to the parent's.
+fn merge_version_json(parent: serde_json::Value, child: serde_json::Value) -> Result<serde_json::Value> {
+    use serde_json::Value;
+
+    let (Value::Object(mut merged), Value::Object(child)) = (parent, child) else {
+        anyhow::bail!("Version metadata is not a JSON object");
+    };
+
+    if !child.contains_key("jar") {
+        if let Some(jar) = merged.get("jar").or_else(|| merged.get("id")).cloned() {
+            merged.insert("jar".to_string(), jar);
+        }
+    }
+
+    for (key, value) in child {
+        match key.as_str() {
+            "inheritsFrom" => {}
+            "libraries" => {
+                let child_libraries = match value {
+                    Value::Array(libraries) => libraries,
+                    _ => Vec::new(),
+                };
+                let overridden: HashSet<String> = child_libraries.iter().filter_map(library_key).collect();
+
+                let mut libraries = child_libraries;
+                if let Some(Value::Array(parent_libraries)) = merged.remove("libraries") {
+                    libraries.extend(
+                        parent_libraries
+                            .into_iter()
+                            .filter(|library| !library_key(library).is_some_and(|k| overridden.contains(&k))),
+                    );
+                }
+                merged.insert(key, Value::Array(libraries));
+            }
+            "arguments" => {
+                let mut arguments = match merged.remove("arguments") {
+                    Some(Value::Object(arguments)) => arguments,
+                    _ => serde_json::Map::new(),
+                };
+                for kind in ["game", "jvm"] {
+                    let list = arguments.entry(kind).or_insert_with(|| Value::Array(Vec::new()));
+                    if let (Value::Array(list), Some(Value::Array(extra))) = (list, value.get(kind)) {
+                        list.extend(extra.iter().cloned());
+                    }
+                }
+                merged.insert(key, Value::Object(arguments));
+            }
+            _ => {
+                merged.insert(key, value);
+            }
+        }
+    }
+
+    Ok(Value::Object(merged))
+}
+
+/// Client jar to launch a version with, relative to the game directory
+///
+/// A custom version without a jar of its own runs the one named by `jar` (its parent's).
+pub fn client_jar_path(game_dir: &Path, meta: &VersionMeta) -> PathBuf {
+    let own = PathBuf::from("versions")
+        .join(&meta.id)
+        .join(format!("{}.jar", meta.id));
+
+    match &meta.jar {
+        Some(jar) if !game_dir.join(&own).exists() => {
+            PathBuf::from("versions").join(jar).join(format!("{}.jar", jar))
+        }
+        _ => own,
+    }
 }
 
 #[cfg(test)]
@@ -308,6 +519,72 @@ mod tests {
         assert!(installed);
     }
 
+    async fn write_version(game_dir: &Path, id: &str, json: serde_json::Value) {
+        let dir = game_dir.join("versions").join(id);
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        tokio::fs::write(dir.join(format!("{}.json", id)), json.to_string())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_installed_version_resolves_inheritance() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path();
+
+        write_version(game_dir, "1.20.1", serde_json::json!({
+            "id": "1.20.1",
+            "type": "release",
+            "mainClass": "net.minecraft.client.main.Main",
+            "arguments": {"game": ["--username", "${auth_player_name}"], "jvm": ["-cp", "${classpath}"]},
+            "libraries": [
+                {"name": "org.ow2.asm:asm:9.3"},
+                {"name": "com.mojang:brigadier:1.1.8"}
+            ],
+            "downloads": {"client": {"sha1": "abc", "size": 1, "url": "https://example.com/client.jar"}},
+            "assetIndex": {"id": "5", "sha1": "def", "size": 1, "totalSize": 1, "url": "https://example.com/5.json"},
+            "assets": "5"
+        }))
+        .await;
+        tokio::fs::write(game_dir.join("versions/1.20.1/1.20.1.jar"), b"mock").await.unwrap();
+
+        write_version(game_dir, "tweaked", serde_json::json!({
+            "id": "tweaked",
+            "inheritsFrom": "1.20.1",
+            "mainClass": "net.fabricmc.loader.impl.launch.knot.KnotClient",
+            "arguments": {"jvm": ["-Dtweaked=true"]},
+            "libraries": [{"name": "org.ow2.asm:asm:9.6"}]
+        }))
+        .await;
+
+        let meta = get_installed_version(game_dir, "tweaked").await.unwrap();
+        assert_eq!(meta.id, "tweaked");
+        assert_eq!(meta.main_class, "net.fabricmc.loader.impl.launch.knot.KnotClient");
+        assert_eq!(meta.asset_index.id, "5");
+
+        let names: Vec<&str> = meta.libraries.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, vec!["org.ow2.asm:asm:9.6", "com.mojang:brigadier:1.1.8"]);
+
+        let arguments = meta.arguments.as_ref().unwrap();
+        assert_eq!(arguments.game.len(), 2);
+        assert_eq!(arguments.jvm.len(), 3);
+
+        // No jar of its own, so it runs the parent's
+        assert_eq!(client_jar_path(game_dir, &meta), PathBuf::from("versions/1.20.1/1.20.1.jar"));
+        assert!(is_version_installed(game_dir, "tweaked").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_get_installed_version_rejects_inheritance_loop() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path();
+
+        write_version(game_dir, "a", serde_json::json!({"id": "a", "inheritsFrom": "b"})).await;
+        write_version(game_dir, "b", serde_json::json!({"id": "b", "inheritsFrom": "a"})).await;
+
+        assert!(get_installed_version(game_dir, "a").await.is_err());
+    }
+
     #[tokio::test]
     #[ignore] // Ignored by default as it downloads real files
     async fn test_install_vanilla_minecraft() {
@@ -316,8 +593,11 @@ mod tests {
 
         let config = InstallConfig {
             game_version: "1.20.1".to_string(),
-            fabric_version: None,
+            loader: ModLoader::Fabric,
+            loader_version: None,
             game_dir,
+            instance_id: None,
+            java_path: None,
         };
 
         let result = install_minecraft(config, |progress| {
@@ -340,14 +620,15 @@ mod tests {
         let game_dir = temp_dir.path().to_path_buf();
 
         // Get latest Fabric version
-        let fabric_loader = fabric_installer::get_latest_fabric_loader("1.20.1")
-            .await
-            .unwrap();
+        let fabric_loader = ModLoader::Fabric.latest_version("1.20.1").await.unwrap();
 
         let config = InstallConfig {
             game_version: "1.20.1".to_string(),
-            fabric_version: Some(fabric_loader.version.clone()),
+            loader: ModLoader::Fabric,
+            loader_version: Some(fabric_loader.version.clone()),
             game_dir,
+            instance_id: None,
+            java_path: None,
         };
 
         let result = install_minecraft(config, |progress| {
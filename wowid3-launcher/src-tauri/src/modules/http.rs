@@ -0,0 +1,241 @@
+use chrono::{DateTime, Utc};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use std::future::Future;
+use std::time::Duration;
+
+/// Sent with every request, so server logs can tell launcher versions apart
+pub const USER_AGENT: &str = concat!("WOWID3-Launcher/", env!("CARGO_PKG_VERSION"));
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+/// Per read rather than per request: large downloads on slow connections take minutes
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+const POOL_MAX_IDLE_PER_HOST: usize = 8;
+
+lazy_static::lazy_static! {
+    static ref CLIENT: Client = Client::builder()
+        .user_agent(USER_AGENT)
+        .connect_timeout(CONNECT_TIMEOUT)
+        .read_timeout(READ_TIMEOUT)
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
+        .build()
+        .expect("Failed to create HTTP client");
+}
+
+/// Client shared by the network modules, keeping connections to each host alive between requests
+///
+/// Set a whole-request limit with `RequestBuilder::timeout` where one is wanted.
+pub fn client() -> &'static Client {
+    &CLIENT
+}
+
+/// How [`SendRetrying::send_retrying`] retries a request that failed in a way that may pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    /// Upper bound for the backoff; a longer `Retry-After` ends the retries
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A single attempt, for requests that must not be repeated
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_attempts(self, max_attempts: u32) -> Self {
+        Self { max_attempts, ..self }
+    }
+
+    /// Backoff before attempt `attempt + 1`, doubling from `base_delay`
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay)
+    }
+}
+
+/// Statuses meaning "not now" rather than "no"
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+fn is_retryable_error(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout()
+}
+
+/// `Retry-After` as a delay; either seconds or an HTTP date
+fn retry_after(response: &Response, now: DateTime<Utc>) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some((at - now).to_std().unwrap_or(Duration::ZERO))
+}
+
+/// `RequestBuilder::send` with retries
+pub trait SendRetrying {
+    /// Send the request, retrying connection failures, timeouts, 429 and 502-504 per `policy`
+    ///
+    /// The last response is returned whatever its status, so callers check it as they
+    /// would after `send`. Requests with streaming bodies can't be repeated and get a
+    /// single attempt.
+    fn send_retrying(self, policy: RetryPolicy) -> impl Future<Output = reqwest::Result<Response>> + Send;
+}
+
+impl SendRetrying for RequestBuilder {
+    fn send_retrying(self, policy: RetryPolicy) -> impl Future<Output = reqwest::Result<Response>> + Send {
+        send(self, policy)
+    }
+}
+
+async fn send(mut request: RequestBuilder, policy: RetryPolicy) -> reqwest::Result<Response> {
+    let mut attempt = 1;
+    loop {
+        let retry = (attempt < policy.max_attempts)
+            .then(|| request.try_clone())
+            .flatten();
+        let Some(next) = retry else {
+            return request.send().await;
+        };
+
+        let delay = match request.send().await {
+            Ok(response) if is_retryable_status(response.status()) => {
+                let delay = retry_after(&response, Utc::now()).unwrap_or_else(|| policy.backoff(attempt));
+                if delay > policy.max_delay {
+                    return Ok(response);
+                }
+                eprintln!(
+                    "[HTTP] {} answered {}, retrying in {}ms (attempt {}/{})",
+                    response.url(),
+                    response.status(),
+                    delay.as_millis(),
+                    attempt + 1,
+                    policy.max_attempts
+                );
+                delay
+            }
+            Err(e) if is_retryable_error(&e) => {
+                let delay = policy.backoff(attempt);
+                eprintln!(
+                    "[HTTP] Request failed, retrying in {}ms (attempt {}/{}): {}",
+                    delay.as_millis(),
+                    attempt + 1,
+                    policy.max_attempts,
+                    e
+                );
+                delay
+            }
+            result => return result,
+        };
+
+        tokio::time::sleep(delay).await;
+        request = next;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn fast() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(50),
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy::default();
+        let delays: Vec<u128> = (1..=6).map(|attempt| policy.backoff(attempt).as_millis()).collect();
+        assert_eq!(delays, vec![500, 1000, 2000, 4000, 8000, 10000]);
+    }
+
+    #[tokio::test]
+    async fn test_retries_unavailable_then_succeeds() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/manifest"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/manifest"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/manifest", server.uri());
+        let response = client().get(&url).send_retrying(fast()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Out of attempts: the last response comes back as-is
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let response = client().get(server.uri()).send_retrying(fast().with_attempts(2)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_respected() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "120"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // Longer than the policy allows: give up rather than hammer the server
+        let response = client().get(server.uri()).send_retrying(fast()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(retry_after(&response, Utc::now()), Some(Duration::from_secs(120)));
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("user-agent", USER_AGENT))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let response = client().get(server.uri()).send_retrying(fast()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,112 @@
+use std::path::Path;
+use std::sync::RwLock;
+
+use super::{download_token, paths};
+
+/// Anonymous id identifying this launcher install, sent only to the modpack server
+pub const INSTALL_ID_HEADER: &str = "X-Launcher-Id";
+pub const LAUNCHER_VERSION_HEADER: &str = "X-Launcher-Version";
+
+const INSTALL_ID_FILE: &str = "install_id";
+
+lazy_static::lazy_static! {
+    /// Random UUID created on first use; carries no account or machine information
+    static ref INSTALL_ID: Option<String> = paths::get_persistent_data_dir()
+        .ok()
+        .and_then(|dir| load_or_create_at(&dir.join(INSTALL_ID_FILE)));
+
+    /// Origin of the modpack server, learned from the manifest URL
+    static ref SERVER_ORIGIN: RwLock<Option<String>> = RwLock::new(None);
+}
+
+fn load_or_create_at(path: &Path) -> Option<String> {
+    if let Ok(existing) = std::fs::read_to_string(path) {
+        let existing = existing.trim();
+        if uuid::Uuid::parse_str(existing).is_ok() {
+            return Some(existing.to_string());
+        }
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Err(e) = std::fs::write(path, &id) {
+        // Still usable for this session; the server just sees a new install next launch
+        eprintln!("[InstallId] Failed to save install id: {}", e);
+    }
+    Some(id)
+}
+
+pub(crate) fn url_origin(url: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .ok()
+        .map(|parsed| parsed.origin().ascii_serialization())
+}
+
+/// Remember which server the modpack manifest comes from
+pub fn set_server_url(manifest_url: &str) {
+    if let Some(origin) = url_origin(manifest_url) {
+        *SERVER_ORIGIN.write().unwrap_or_else(|e| e.into_inner()) = Some(origin);
+    }
+}
+
+fn is_server_url(url: &str) -> bool {
+    let server = SERVER_ORIGIN.read().unwrap_or_else(|e| e.into_inner());
+    server.is_some() && *server == url_origin(url)
+}
+
+/// Add the install id headers, and the download token if we hold one, to requests
+/// for the modpack server
+///
+/// Requests to anything else (Mojang, mirrors, CDNs) are left untouched.
+pub fn apply(request: reqwest::RequestBuilder, url: &str) -> reqwest::RequestBuilder {
+    if !is_server_url(url) {
+        return request;
+    }
+    let request = match INSTALL_ID.as_deref() {
+        Some(id) => request
+            .header(INSTALL_ID_HEADER, id)
+            .header(LAUNCHER_VERSION_HEADER, env!("CARGO_PKG_VERSION")),
+        None => request,
+    };
+    match download_token::current() {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_install_id_is_persisted() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(INSTALL_ID_FILE);
+
+        let first = load_or_create_at(&path).unwrap();
+        assert!(uuid::Uuid::parse_str(&first).is_ok());
+        assert_eq!(load_or_create_at(&path).unwrap(), first);
+    }
+
+    #[test]
+    fn test_invalid_install_id_is_replaced() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(INSTALL_ID_FILE);
+        std::fs::write(&path, "not-a-uuid").unwrap();
+
+        let id = load_or_create_at(&path).unwrap();
+        assert_ne!(id, "not-a-uuid");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), id);
+    }
+
+    #[test]
+    fn test_headers_only_sent_to_modpack_server() {
+        set_server_url("https://packs.example.com/api/manifest/latest");
+        assert!(is_server_url("https://packs.example.com/files/1.0.0/mods/a.jar"));
+        assert!(!is_server_url("https://libraries.minecraft.net/a.jar"));
+        assert!(!is_server_url("http://packs.example.com/files/1.0.0/mods/a.jar"));
+    }
+}
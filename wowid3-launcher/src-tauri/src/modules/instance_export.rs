@@ -0,0 +1,227 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+use super::mod_loader::ModLoader;
+use super::optional_mods;
+use super::updater::Manifest;
+
+/// Instance folder name inside the zip; Prism and MultiMC import `<name>/instance.cfg`
+const INSTANCE_NAME: &str = "WOWID3";
+
+/// Launcher an exported instance is meant for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstanceFormat {
+    Prism,
+    /// Same layout as Prism, but MultiMC has no NeoForge component
+    MultiMc,
+}
+
+/// Result of `export_instance`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportSummary {
+    pub path: PathBuf,
+    pub modpack_version: String,
+    pub files: usize,
+    pub bytes: u64,
+    /// Manifest files that weren't installed and so aren't in the zip
+    pub missing: Vec<String>,
+}
+
+/// `mmc-pack.json`: the Minecraft version and loader the instance runs
+fn mmc_pack(manifest: &Manifest, format: InstanceFormat) -> Result<serde_json::Value> {
+    let game = &manifest.minecraft_version;
+    let loader = &manifest.fabric_loader;
+    let mut components = vec![serde_json::json!({ "uid": "net.minecraft", "version": game, "important": true })];
+
+    match manifest.loader {
+        ModLoader::Fabric => {
+            components.push(serde_json::json!({ "uid": "net.fabricmc.intermediary", "version": game, "dependencyOnly": true }));
+            components.push(serde_json::json!({ "uid": "net.fabricmc.fabric-loader", "version": loader }));
+        }
+        ModLoader::Quilt => {
+            components.push(serde_json::json!({ "uid": "net.fabricmc.intermediary", "version": game, "dependencyOnly": true }));
+            components.push(serde_json::json!({ "uid": "org.quiltmc.quilt-loader", "version": loader }));
+        }
+        ModLoader::NeoForge if format == InstanceFormat::MultiMc => {
+            anyhow::bail!("MultiMC can't run NeoForge packs; export for Prism Launcher instead");
+        }
+        ModLoader::NeoForge => {
+            components.push(serde_json::json!({ "uid": "net.neoforged", "version": loader }));
+        }
+    }
+
+    Ok(serde_json::json!({ "components": components, "formatVersion": 1 }))
+}
+
+/// `instance.cfg`: display name and a note on where the pack came from
+fn instance_cfg(manifest: &Manifest) -> String {
+    format!(
+        "InstanceType=OneSix\nname={name} {version}\niconKey=default\nnotes=Exported from the {name} launcher (modpack {version}). It won't update itself; check the launcher for new releases.\n",
+        name = INSTANCE_NAME,
+        version = manifest.version
+    )
+}
+
+/// Zip the installed modpack as a Prism/MultiMC instance (`mmc-pack.json`, `instance.cfg`, `.minecraft/`)
+///
+/// Only manifest files are included, so worlds, logs and settings stay behind. Files of
+/// optional groups the player switched off are left out, just like in the launcher.
+pub async fn export_instance(
+    manifest: &Manifest,
+    game_dir: &Path,
+    format: InstanceFormat,
+    dest: &Path,
+) -> Result<ExportSummary> {
+    let pack = serde_json::to_string_pretty(&mmc_pack(manifest, format)?)?;
+    let cfg = instance_cfg(manifest);
+    let disabled = optional_mods::disabled_files_in(manifest, game_dir).await;
+
+    let mut summary = ExportSummary {
+        path: dest.to_path_buf(),
+        modpack_version: manifest.version.clone(),
+        files: 0,
+        bytes: 0,
+        missing: Vec::new(),
+    };
+    let mut included = Vec::new();
+    for file in &manifest.files {
+        let relative = file.path.replace('\\', "/");
+        if disabled.contains(&relative) {
+            continue;
+        }
+        if game_dir.join(&file.path).is_file() {
+            included.push(relative);
+        } else {
+            summary.missing.push(relative);
+        }
+    }
+
+    let game_dir = game_dir.to_path_buf();
+    let dest = dest.to_path_buf();
+    let (files, bytes) = tokio::task::spawn_blocking(move || write_instance_zip(&dest, &pack, &cfg, &game_dir, &included))
+        .await
+        .context("Export task panicked")??;
+    summary.files = files;
+    summary.bytes = bytes;
+
+    eprintln!(
+        "[Export] Wrote {} ({} files, {} MB, {} missing)",
+        summary.path.display(),
+        summary.files,
+        summary.bytes / 1024 / 1024,
+        summary.missing.len()
+    );
+    Ok(summary)
+}
+
+/// Write the zip through a temp file; returns the number of game files and their total size
+fn write_instance_zip(dest: &Path, pack: &str, cfg: &str, game_dir: &Path, files: &[String]) -> Result<(usize, u64)> {
+    let temp_path = dest.with_extension("zip.partial");
+    let file = File::create(&temp_path).with_context(|| format!("Failed to create {}", temp_path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+    // Jars are already compressed; deflating them again only costs time
+    let stored = options.compression_method(zip::CompressionMethod::Stored);
+
+    zip.start_file(format!("{}/mmc-pack.json", INSTANCE_NAME), options)?;
+    zip.write_all(pack.as_bytes())?;
+    zip.start_file(format!("{}/instance.cfg", INSTANCE_NAME), options)?;
+    zip.write_all(cfg.as_bytes())?;
+
+    let mut bytes = 0;
+    for relative in files {
+        let path = game_dir.join(relative);
+        let mut source = File::open(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let file_options = if relative.ends_with(".jar") || relative.ends_with(".zip") { stored } else { options };
+        zip.start_file(format!("{}/.minecraft/{}", INSTANCE_NAME, relative), file_options)?;
+        bytes += std::io::copy(&mut source, &mut zip)?;
+    }
+
+    zip.finish()?;
+    std::fs::rename(&temp_path, dest).with_context(|| format!("Failed to write {}", dest.display()))?;
+    Ok((files.len(), bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    fn manifest(loader: &str) -> Manifest {
+        serde_json::from_value(serde_json::json!({
+            "version": "2.0.0",
+            "minecraft_version": "1.21.1",
+            "loader": loader,
+            "fabric_loader": "21.1.77",
+            "files": [
+                { "path": "mods/sodium.jar", "url": "https://example.com/a", "sha256": "a", "size": 6 },
+                { "path": "config/sodium.json", "url": "https://example.com/b", "sha256": "b", "size": 2 },
+                { "path": "mods/missing.jar", "url": "https://example.com/c", "sha256": "c", "size": 1 }
+            ],
+            "changelog": ""
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mmc_pack_components() {
+        let pack = mmc_pack(&manifest("fabric"), InstanceFormat::MultiMc).unwrap();
+        let uids: Vec<&str> = pack["components"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["uid"].as_str().unwrap())
+            .collect();
+        assert_eq!(uids, vec!["net.minecraft", "net.fabricmc.intermediary", "net.fabricmc.fabric-loader"]);
+
+        assert!(mmc_pack(&manifest("neoforge"), InstanceFormat::MultiMc).is_err());
+        let pack = mmc_pack(&manifest("neoforge"), InstanceFormat::Prism).unwrap();
+        assert_eq!(pack["components"][1]["uid"], "net.neoforged");
+        assert_eq!(pack["components"][1]["version"], "21.1.77");
+    }
+
+    #[tokio::test]
+    async fn test_export_instance_zip_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path().join("game");
+        std::fs::create_dir_all(game_dir.join("mods")).unwrap();
+        std::fs::create_dir_all(game_dir.join("config")).unwrap();
+        std::fs::create_dir_all(game_dir.join("saves/World")).unwrap();
+        std::fs::write(game_dir.join("mods/sodium.jar"), b"sodium").unwrap();
+        std::fs::write(game_dir.join("config/sodium.json"), b"{}").unwrap();
+        std::fs::write(game_dir.join("saves/World/level.dat"), b"world").unwrap();
+
+        let dest = temp_dir.path().join("wowid3.zip");
+        let summary = export_instance(&manifest("fabric"), &game_dir, InstanceFormat::Prism, &dest)
+            .await
+            .unwrap();
+        assert_eq!(summary.files, 2);
+        assert_eq!(summary.bytes, 8);
+        assert_eq!(summary.missing, vec!["mods/missing.jar"]);
+
+        let mut archive = zip::ZipArchive::new(File::open(&dest).unwrap()).unwrap();
+        let mut names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "WOWID3/.minecraft/config/sodium.json",
+                "WOWID3/.minecraft/mods/sodium.jar",
+                "WOWID3/instance.cfg",
+                "WOWID3/mmc-pack.json",
+            ]
+        );
+
+        let mut cfg = String::new();
+        archive.by_name("WOWID3/instance.cfg").unwrap().read_to_string(&mut cfg).unwrap();
+        assert!(cfg.contains("name=WOWID3 2.0.0"));
+    }
+}
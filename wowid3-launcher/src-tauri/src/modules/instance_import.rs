@@ -0,0 +1,296 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use walkdir::WalkDir;
+
+use super::manifest_signature;
+use super::updater::{get_files_to_download, FileIssue, Manifest, ManifestFile};
+use super::verify_cache::{self, sha256_file, VerifyCache};
+
+/// Launcher an imported instance folder belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceLauncher {
+    /// Prism Launcher or MultiMC (Prism kept MultiMC's instance layout)
+    Prism,
+    CurseForge,
+    /// A bare `.minecraft`-style folder
+    Minecraft,
+}
+
+/// Result of `import_installation`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub source_launcher: SourceLauncher,
+    /// Folder the files were copied from (the instance's game directory)
+    pub source_dir: PathBuf,
+    /// Manifest files that were already installed and needed nothing
+    pub already_installed: usize,
+    /// Manifest paths copied from the other launcher
+    pub imported: Vec<String>,
+    pub imported_bytes: u64,
+    /// Files that still have to be downloaded
+    pub remaining: Vec<FileIssue>,
+    pub remaining_bytes: u64,
+}
+
+/// Work out which launcher `source` belongs to and where its game files live
+///
+/// Accepts the instance folder itself (Prism/MultiMC, CurseForge) or its game directory.
+pub fn detect_source(source: &Path) -> Result<(SourceLauncher, PathBuf)> {
+    if !source.is_dir() {
+        anyhow::bail!("{} is not a folder", source.display());
+    }
+
+    if source.join("minecraftinstance.json").is_file() {
+        return Ok((SourceLauncher::CurseForge, source.to_path_buf()));
+    }
+
+    if source.join("instance.cfg").is_file() || source.join("mmc-pack.json").is_file() {
+        let game_dir = [".minecraft", "minecraft"]
+            .iter()
+            .map(|name| source.join(name))
+            .find(|dir| dir.is_dir())
+            .context("Instance folder has no .minecraft directory")?;
+        return Ok((SourceLauncher::Prism, game_dir));
+    }
+
+    if source.join("mods").is_dir() {
+        return Ok((SourceLauncher::Minecraft, source.to_path_buf()));
+    }
+
+    anyhow::bail!(
+        "{} doesn't look like a Prism, MultiMC or CurseForge instance",
+        source.display()
+    )
+}
+
+/// Files under `root` whose size one of `sizes` has, grouped by size
+fn files_by_size(root: &Path, sizes: &std::collections::HashSet<u64>) -> HashMap<u64, Vec<PathBuf>> {
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    for entry in WalkDir::new(root).follow_links(false).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if sizes.contains(&metadata.len()) {
+            by_size.entry(metadata.len()).or_default().push(entry.into_path());
+        }
+    }
+    by_size
+}
+
+/// Source file with the manifest file's hash: the same path first, then any file of the same size
+///
+/// Other launchers often rename mods (e.g. CurseForge's `.jar.disabled`), so a path miss
+/// falls back to comparing content.
+async fn find_match(
+    file: &ManifestFile,
+    source_dir: &Path,
+    by_size: &HashMap<u64, Vec<PathBuf>>,
+) -> Option<PathBuf> {
+    let same_path = source_dir.join(&file.path);
+    let candidates = std::iter::once(&same_path)
+        .chain(by_size.get(&file.size).into_iter().flatten().filter(|path| **path != same_path));
+
+    for candidate in candidates {
+        let size_matches = fs::metadata(candidate)
+            .await
+            .is_ok_and(|metadata| metadata.len() == file.size);
+        if !size_matches {
+            continue;
+        }
+        match sha256_file(candidate).await {
+            Ok(hash) if hash.eq_ignore_ascii_case(&file.sha256) => return Some(candidate.clone()),
+            Ok(_) => {}
+            Err(e) => eprintln!("[Import] Failed to hash {}: {}", candidate.display(), e),
+        }
+    }
+    None
+}
+
+/// Copy via a temp file so an interrupted import never leaves a truncated file behind
+async fn copy_into_place(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).await.context("Failed to create directory")?;
+    }
+    let temp_path = to.with_file_name(format!(
+        ".{}.import",
+        to.file_name().map(|name| name.to_string_lossy()).unwrap_or_default()
+    ));
+    fs::copy(from, &temp_path)
+        .await
+        .with_context(|| format!("Failed to copy {}", from.display()))?;
+    fs::rename(&temp_path, to).await.context("Failed to move imported file into place")
+}
+
+/// Copy modpack files from another launcher's instance instead of downloading them
+///
+/// Only files whose hash matches `manifest` are copied; everything else is reported as
+/// remaining for the normal install to fetch. The source instance is never modified.
+pub async fn import_installation(source: &Path, manifest: &Manifest, game_dir: &PathBuf) -> Result<ImportReport> {
+    use futures::stream::{self, StreamExt};
+
+    manifest_signature::ensure_verified(manifest)?;
+    let (source_launcher, source_dir) = detect_source(source)?;
+
+    let same_dir = match (source_dir.canonicalize(), game_dir.canonicalize()) {
+        (Ok(source), Ok(game)) => source == game,
+        _ => false,
+    };
+    if same_dir {
+        anyhow::bail!("Choose another launcher's instance, not this launcher's game directory");
+    }
+
+    fs::create_dir_all(game_dir)
+        .await
+        .context("Failed to create game directory")?;
+
+    let needed = get_files_to_download(manifest, game_dir).await?;
+    let already_installed = manifest.files.len().saturating_sub(needed.len());
+    eprintln!(
+        "[Import] Looking for {} of {} files in {:?} instance at {}",
+        needed.len(),
+        manifest.files.len(),
+        source_launcher,
+        source_dir.display()
+    );
+
+    let sizes = needed.iter().map(|file| file.size).collect();
+    let walk_dir = source_dir.clone();
+    let by_size = tokio::task::spawn_blocking(move || files_by_size(&walk_dir, &sizes))
+        .await
+        .context("Import scan panicked")?;
+
+    let matches: Vec<Option<PathBuf>> = stream::iter(&needed)
+        .map(|file| find_match(file, &source_dir, &by_size))
+        .buffered(verify_cache::hash_concurrency())
+        .collect()
+        .await;
+
+    let cache = VerifyCache::load(game_dir).await;
+    let mut report = ImportReport {
+        source_launcher,
+        source_dir: source_dir.clone(),
+        already_installed,
+        imported: Vec::new(),
+        imported_bytes: 0,
+        remaining: Vec::new(),
+        remaining_bytes: 0,
+    };
+
+    for (file, found) in needed.iter().zip(matches) {
+        let destination = game_dir.join(&file.path);
+        let copied = match found {
+            Some(found) => match copy_into_place(&found, &destination).await {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("[Import] {}: {}", file.path, e);
+                    false
+                }
+            },
+            None => false,
+        };
+
+        if copied {
+            cache
+                .record(&file.path.replace('\\', "/"), &destination, &file.sha256)
+                .await;
+            report.imported.push(file.path.clone());
+            report.imported_bytes += file.size;
+        } else {
+            report.remaining.push(FileIssue {
+                path: file.path.clone(),
+                size: file.size,
+            });
+            report.remaining_bytes += file.size;
+        }
+    }
+
+    if let Err(e) = cache.save(game_dir).await {
+        eprintln!("[Import] {}", e);
+    }
+
+    eprintln!(
+        "[Import] Imported {} files ({} MB); {} still need downloading ({} MB)",
+        report.imported.len(),
+        report.imported_bytes / 1024 / 1024,
+        report.remaining.len(),
+        report.remaining_bytes / 1024 / 1024
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+    use tempfile::TempDir;
+
+    fn manifest_file(path: &str, content: &[u8]) -> ManifestFile {
+        ManifestFile {
+            path: path.to_string(),
+            url: format!("https://example.com/{}", path),
+            sha256: format!("{:x}", Sha256::digest(content)),
+            size: content.len() as u64,
+            mirrors: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_from_prism_instance() {
+        let temp_dir = TempDir::new().unwrap();
+        let instance = temp_dir.path().join("prism/instances/WOWID3");
+        let source_dir = instance.join(".minecraft");
+        std::fs::create_dir_all(source_dir.join("mods")).unwrap();
+        std::fs::create_dir_all(source_dir.join("config")).unwrap();
+        std::fs::write(instance.join("instance.cfg"), "name=WOWID3\n").unwrap();
+        std::fs::write(source_dir.join("mods/sodium.jar"), b"sodium").unwrap();
+        // Renamed by the other launcher, matched by content
+        std::fs::write(source_dir.join("mods/iris-1.6.jar.disabled"), b"iris").unwrap();
+        // Different version of the config: not imported
+        std::fs::write(source_dir.join("config/sodium.json"), b"{\"old\":1}").unwrap();
+
+        let manifest: Manifest = serde_json::from_value(serde_json::json!({
+            "version": "1.0.0",
+            "minecraft_version": "1.20.1",
+            "fabric_loader": "0.15.0",
+            "files": [
+                manifest_file("mods/sodium.jar", b"sodium"),
+                manifest_file("mods/iris.jar", b"iris"),
+                manifest_file("config/sodium.json", b"{\"new\":1}"),
+            ],
+            "changelog": ""
+        }))
+        .unwrap();
+
+        let game_dir = temp_dir.path().join("wowid3");
+        let report = import_installation(&instance, &manifest, &game_dir).await.unwrap();
+
+        assert_eq!(report.source_launcher, SourceLauncher::Prism);
+        assert_eq!(report.imported, vec!["mods/sodium.jar", "mods/iris.jar"]);
+        assert_eq!(report.remaining.len(), 1);
+        assert_eq!(report.remaining[0].path, "config/sodium.json");
+        assert_eq!(std::fs::read(game_dir.join("mods/iris.jar")).unwrap(), b"iris");
+        // The source instance is left as it was
+        assert!(source_dir.join("mods/iris-1.6.jar.disabled").exists());
+
+        let remaining = get_files_to_download(&manifest, &game_dir).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+    }
+
+    #[test]
+    fn test_detect_source_rejects_unknown_folders() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(detect_source(temp_dir.path()).is_err());
+
+        std::fs::write(temp_dir.path().join("minecraftinstance.json"), "{}").unwrap();
+        let (launcher, dir) = detect_source(temp_dir.path()).unwrap();
+        assert_eq!(launcher, SourceLauncher::CurseForge);
+        assert_eq!(dir, temp_dir.path());
+    }
+}
@@ -0,0 +1,480 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use walkdir::WalkDir;
+
+use super::jvm_settings::JvmSettings;
+
+const INSTANCES_FILE: &str = "instances.json";
+
+/// Id of the instance created from the pre-instances game directory
+pub const DEFAULT_INSTANCE_ID: &str = "default";
+
+const DEFAULT_MANIFEST_URL: &str = "https://wowid-launcher.frostdev.io/api/manifest/latest";
+const DEFAULT_RAM_MB: u32 = 4096;
+
+/// Per-instance runtime output that isn't worth copying when cloning
+const CLONE_SKIP_DIRS: &[&str] = &["logs", "crash-reports", "screenshots"];
+
+/// A named game directory with its own modpack source and memory settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Instance {
+    pub id: String,
+    pub name: String,
+    pub game_dir: PathBuf,
+    pub manifest_url: String,
+    pub jvm: JvmSettings,
+    /// Last modpack version installed into this instance
+    #[serde(default)]
+    pub installed_version: Option<String>,
+    /// RFC 3339
+    pub created_at: String,
+}
+
+/// Fields supplied when creating an instance
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewInstance {
+    pub name: String,
+    /// Defaults to `<data dir>/instances/<id>`
+    #[serde(default)]
+    pub game_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub manifest_url: Option<String>,
+    #[serde(default)]
+    pub jvm: Option<JvmSettings>,
+}
+
+/// Contents of `instances.json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstancesConfig {
+    #[serde(default)]
+    pub active: Option<String>,
+    #[serde(default)]
+    pub instances: Vec<Instance>,
+}
+
+impl InstancesConfig {
+    pub fn get(&self, id: &str) -> Option<&Instance> {
+        self.instances.iter().find(|i| i.id == id)
+    }
+
+    fn get_mut(&mut self, id: &str) -> Result<&mut Instance> {
+        self.instances
+            .iter_mut()
+            .find(|i| i.id == id)
+            .ok_or_else(|| anyhow::anyhow!("No instance with id '{}'", id))
+    }
+
+    /// Turn a display name into a unique, filesystem-safe id
+    fn unique_id(&self, name: &str) -> String {
+        let mut slug = String::new();
+        for c in name.trim().chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c.to_ascii_lowercase());
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        let slug = slug.trim_end_matches('-');
+        let base = if slug.is_empty() { "instance" } else { slug };
+
+        let mut id = base.to_string();
+        let mut n = 2;
+        while self.get(&id).is_some() {
+            id = format!("{}-{}", base, n);
+            n += 1;
+        }
+        id
+    }
+
+    fn check_name_available(&self, name: &str, except_id: Option<&str>) -> Result<()> {
+        let taken = self
+            .instances
+            .iter()
+            .any(|i| i.name.eq_ignore_ascii_case(name) && Some(i.id.as_str()) != except_id);
+        if taken {
+            anyhow::bail!("An instance named '{}' already exists", name);
+        }
+        Ok(())
+    }
+
+    fn check_game_dir_available(&self, game_dir: &Path, except_id: Option<&str>) -> Result<()> {
+        if let Some(other) = self
+            .instances
+            .iter()
+            .find(|i| i.game_dir == game_dir && Some(i.id.as_str()) != except_id)
+        {
+            anyhow::bail!("'{}' already uses {}", other.name, game_dir.display());
+        }
+        Ok(())
+    }
+}
+
+fn instances_path() -> Result<PathBuf> {
+    Ok(super::paths::get_persistent_data_dir()?.join(INSTANCES_FILE))
+}
+
+fn instances_root() -> Result<PathBuf> {
+    Ok(super::paths::get_persistent_data_dir()?.join("instances"))
+}
+
+/// The single game directory used before instances existed
+fn legacy_instance(game_dir: Option<PathBuf>) -> Result<Instance> {
+    let game_dir = match game_dir {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => super::paths::get_persistent_data_dir()?.join("game"),
+    };
+    Ok(Instance {
+        id: DEFAULT_INSTANCE_ID.to_string(),
+        name: "WOWID3".to_string(),
+        game_dir,
+        manifest_url: DEFAULT_MANIFEST_URL.to_string(),
+        jvm: JvmSettings::from_ram(DEFAULT_RAM_MB),
+        installed_version: None,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+async fn read_config(path: &Path) -> Result<Option<InstancesConfig>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)
+        .await
+        .context("Failed to read instances")?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .context("Failed to parse instances")
+}
+
+async fn write_config(path: &Path, config: &InstancesConfig) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    // Write through a temp file so a crash can't leave a half-written config
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_string_pretty(config)?)
+        .await
+        .context("Failed to write instances")?;
+    fs::rename(&tmp, path)
+        .await
+        .context("Failed to write instances")
+}
+
+/// Load the instance list, seeding it with `initial` the first time
+pub async fn load_config_at(path: &Path, initial: impl FnOnce() -> Result<Instance>) -> Result<InstancesConfig> {
+    if let Some(config) = read_config(path).await? {
+        return Ok(config);
+    }
+
+    let instance = initial()?;
+    let config = InstancesConfig {
+        active: Some(instance.id.clone()),
+        instances: vec![instance],
+    };
+    write_config(path, &config).await?;
+    Ok(config)
+}
+
+pub async fn create_instance_at(path: &Path, instances_root: &Path, new: NewInstance) -> Result<Instance> {
+    let mut config = load_config_at(path, || legacy_instance(None)).await?;
+
+    let name = new.name.trim().to_string();
+    if name.is_empty() {
+        anyhow::bail!("Instance name is required");
+    }
+    config.check_name_available(&name, None)?;
+
+    let id = config.unique_id(&name);
+    let game_dir = match new.game_dir {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => instances_root.join(&id),
+    };
+    config.check_game_dir_available(&game_dir, None)?;
+
+    let jvm = new.jvm.unwrap_or_else(|| JvmSettings::from_ram(DEFAULT_RAM_MB));
+    jvm.validate()?;
+
+    let instance = Instance {
+        id,
+        name,
+        game_dir,
+        manifest_url: new
+            .manifest_url
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| DEFAULT_MANIFEST_URL.to_string()),
+        jvm,
+        installed_version: None,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    fs::create_dir_all(&instance.game_dir)
+        .await
+        .with_context(|| format!("Failed to create {}", instance.game_dir.display()))?;
+
+    config.instances.push(instance.clone());
+    write_config(path, &config).await?;
+    Ok(instance)
+}
+
+/// Copy a game directory, skipping logs and other per-run output
+fn copy_game_dir(source: &Path, dest: &Path) -> Result<()> {
+    let walker = WalkDir::new(source).min_depth(1).into_iter().filter_entry(|entry| {
+        !(entry.depth() == 1
+            && entry.file_type().is_dir()
+            && CLONE_SKIP_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()))
+    });
+
+    for entry in walker {
+        let entry = entry?;
+        let target = dest.join(entry.path().strip_prefix(source)?);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else if entry.file_type().is_file() {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Duplicate an instance's settings and game directory under a new name
+pub async fn clone_instance_at(path: &Path, instances_root: &Path, id: &str, name: &str) -> Result<Instance> {
+    let config = load_config_at(path, || legacy_instance(None)).await?;
+    let source = config
+        .get(id)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("No instance with id '{}'", id))?;
+
+    let instance = create_instance_at(
+        path,
+        instances_root,
+        NewInstance {
+            name: name.to_string(),
+            game_dir: None,
+            manifest_url: Some(source.manifest_url.clone()),
+            jvm: Some(source.jvm.clone()),
+        },
+    )
+    .await?;
+
+    if source.game_dir.exists() {
+        let (from, to) = (source.game_dir.clone(), instance.game_dir.clone());
+        let copied = tokio::task::spawn_blocking(move || copy_game_dir(&from, &to)).await?;
+        if let Err(e) = copied {
+            // Don't leave a half-copied instance behind
+            let _ = delete_instance_at(path, &instance.id, true).await;
+            return Err(e.context(format!("Failed to clone '{}'", source.name)));
+        }
+    }
+
+    set_installed_version_at(path, &instance.id, source.installed_version).await
+}
+
+/// Rename or reconfigure an instance; the id and creation time never change
+pub async fn update_instance_at(path: &Path, instance: Instance) -> Result<Instance> {
+    let mut config = load_config_at(path, || legacy_instance(None)).await?;
+
+    let name = instance.name.trim().to_string();
+    if name.is_empty() {
+        anyhow::bail!("Instance name is required");
+    }
+    if instance.manifest_url.trim().is_empty() {
+        anyhow::bail!("Manifest URL is required");
+    }
+    config.check_name_available(&name, Some(&instance.id))?;
+    config.check_game_dir_available(&instance.game_dir, Some(&instance.id))?;
+    instance.jvm.validate()?;
+
+    let existing = config.get_mut(&instance.id)?;
+    existing.name = name;
+    existing.game_dir = instance.game_dir;
+    existing.manifest_url = instance.manifest_url.trim().to_string();
+    existing.jvm = instance.jvm;
+    let updated = existing.clone();
+
+    write_config(path, &config).await?;
+    Ok(updated)
+}
+
+/// Remove an instance, optionally deleting its game directory
+pub async fn delete_instance_at(path: &Path, id: &str, delete_files: bool) -> Result<()> {
+    let mut config = load_config_at(path, || legacy_instance(None)).await?;
+    if config.instances.len() == 1 && config.get(id).is_some() {
+        anyhow::bail!("Can't delete the last instance");
+    }
+
+    let removed = config.get_mut(id)?.clone();
+    config.instances.retain(|i| i.id != id);
+    if config.active.as_deref() == Some(id) {
+        config.active = config.instances.first().map(|i| i.id.clone());
+    }
+    write_config(path, &config).await?;
+
+    if delete_files && removed.game_dir.exists() {
+        fs::remove_dir_all(&removed.game_dir)
+            .await
+            .with_context(|| format!("Failed to delete {}", removed.game_dir.display()))?;
+    }
+    Ok(())
+}
+
+pub async fn set_active_instance_at(path: &Path, id: &str) -> Result<Instance> {
+    let mut config = load_config_at(path, || legacy_instance(None)).await?;
+    let instance = config.get_mut(id)?.clone();
+    config.active = Some(instance.id.clone());
+    write_config(path, &config).await?;
+    Ok(instance)
+}
+
+pub async fn set_installed_version_at(path: &Path, id: &str, version: Option<String>) -> Result<Instance> {
+    let mut config = load_config_at(path, || legacy_instance(None)).await?;
+    let instance = config.get_mut(id)?;
+    instance.installed_version = version;
+    let updated = instance.clone();
+    write_config(path, &config).await?;
+    Ok(updated)
+}
+
+/// Load all instances; on first run the existing game directory becomes the default instance
+pub async fn load_instances(legacy_game_dir: Option<PathBuf>) -> Result<InstancesConfig> {
+    load_config_at(&instances_path()?, || legacy_instance(legacy_game_dir)).await
+}
+
+pub async fn get_instance(id: &str) -> Result<Instance> {
+    load_instances(None)
+        .await?
+        .get(id)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("No instance with id '{}'", id))
+}
+
+pub async fn create_instance(new: NewInstance) -> Result<Instance> {
+    create_instance_at(&instances_path()?, &instances_root()?, new).await
+}
+
+pub async fn clone_instance(id: &str, name: &str) -> Result<Instance> {
+    clone_instance_at(&instances_path()?, &instances_root()?, id, name).await
+}
+
+pub async fn update_instance(instance: Instance) -> Result<Instance> {
+    update_instance_at(&instances_path()?, instance).await
+}
+
+pub async fn delete_instance(id: &str, delete_files: bool) -> Result<()> {
+    delete_instance_at(&instances_path()?, id, delete_files).await
+}
+
+pub async fn set_active_instance(id: &str) -> Result<Instance> {
+    set_active_instance_at(&instances_path()?, id).await
+}
+
+pub async fn set_installed_version(id: &str, version: Option<String>) -> Result<Instance> {
+    set_installed_version_at(&instances_path()?, id, version).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn seed(root: &Path) -> impl FnOnce() -> Result<Instance> {
+        let game_dir = root.join("game");
+        move || {
+            Ok(Instance {
+                id: DEFAULT_INSTANCE_ID.to_string(),
+                name: "WOWID3".to_string(),
+                game_dir,
+                manifest_url: DEFAULT_MANIFEST_URL.to_string(),
+                jvm: JvmSettings::from_ram(DEFAULT_RAM_MB),
+                installed_version: Some("1.0.0".to_string()),
+                created_at: chrono::Utc::now().to_rfc3339(),
+            })
+        }
+    }
+
+    fn new_instance(name: &str) -> NewInstance {
+        NewInstance {
+            name: name.to_string(),
+            game_dir: None,
+            manifest_url: None,
+            jvm: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_and_delete_instance() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(INSTANCES_FILE);
+        let root = temp_dir.path().join("instances");
+        load_config_at(&path, seed(temp_dir.path())).await.unwrap();
+
+        let created = create_instance_at(&path, &root, new_instance("Creative Test!")).await.unwrap();
+        assert_eq!(created.id, "creative-test");
+        assert_eq!(created.game_dir, root.join("creative-test"));
+        assert!(created.game_dir.is_dir());
+
+        // Names are unique regardless of case
+        assert!(create_instance_at(&path, &root, new_instance("creative test!")).await.is_err());
+
+        let config = read_config(&path).await.unwrap().unwrap();
+        assert_eq!(config.instances.len(), 2);
+        assert_eq!(config.active.as_deref(), Some(DEFAULT_INSTANCE_ID));
+
+        set_active_instance_at(&path, "creative-test").await.unwrap();
+        delete_instance_at(&path, "creative-test", true).await.unwrap();
+        assert!(!created.game_dir.exists());
+
+        let config = read_config(&path).await.unwrap().unwrap();
+        assert_eq!(config.active.as_deref(), Some(DEFAULT_INSTANCE_ID));
+        assert!(delete_instance_at(&path, DEFAULT_INSTANCE_ID, false).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_clone_instance_copies_game_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(INSTANCES_FILE);
+        let root = temp_dir.path().join("instances");
+        let game_dir = temp_dir.path().join("game");
+        load_config_at(&path, seed(temp_dir.path())).await.unwrap();
+
+        fs::create_dir_all(game_dir.join("mods")).await.unwrap();
+        fs::create_dir_all(game_dir.join("logs")).await.unwrap();
+        fs::write(game_dir.join("mods/sodium.jar"), b"jar").await.unwrap();
+        fs::write(game_dir.join("options.txt"), b"fov:90").await.unwrap();
+        fs::write(game_dir.join("logs/latest.log"), b"log").await.unwrap();
+
+        let clone = clone_instance_at(&path, &root, DEFAULT_INSTANCE_ID, "Copy").await.unwrap();
+        assert_eq!(clone.id, "copy");
+        assert_eq!(clone.installed_version.as_deref(), Some("1.0.0"));
+        assert!(clone.game_dir.join("mods/sodium.jar").exists());
+        assert!(clone.game_dir.join("options.txt").exists());
+        assert!(!clone.game_dir.join("logs").exists());
+
+        // The source is untouched
+        assert!(game_dir.join("logs/latest.log").exists());
+    }
+
+    #[tokio::test]
+    async fn test_update_instance_rejects_shared_game_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(INSTANCES_FILE);
+        let root = temp_dir.path().join("instances");
+        load_config_at(&path, seed(temp_dir.path())).await.unwrap();
+
+        let mut created = create_instance_at(&path, &root, new_instance("Test")).await.unwrap();
+        created.name = "Renamed".to_string();
+        created.jvm.max_heap_mb = 6144;
+        let updated = update_instance_at(&path, created.clone()).await.unwrap();
+        assert_eq!(updated.name, "Renamed");
+        assert_eq!(updated.jvm.max_heap_mb, 6144);
+
+        created.game_dir = temp_dir.path().join("game");
+        assert!(update_instance_at(&path, created).await.is_err());
+    }
+}
@@ -0,0 +1,318 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::process::Command;
+
+/// Minecraft 1.20.x requires Java 17 or newer
+pub const MIN_JAVA_MAJOR: u32 = 17;
+
+const JAVA_PROBE_TIMEOUT_SECS: u64 = 10;
+
+#[cfg(windows)]
+const JAVA_EXECUTABLE: &str = "java.exe";
+#[cfg(not(windows))]
+const JAVA_EXECUTABLE: &str = "java";
+
+/// A Java runtime found on this machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaInstallation {
+    pub path: PathBuf,
+    /// Full version string, e.g. `21.0.9` or `1.8.0_392`
+    pub version: String,
+    pub major_version: u32,
+    pub vendor: Option<String>,
+    /// `os.arch` reported by the JVM (`amd64`, `aarch64`, ...)
+    pub arch: Option<String>,
+    pub is_64bit: bool,
+    /// Where it was found: `JAVA_HOME`, `PATH` or `system`
+    pub source: String,
+    /// Whether it can run this modpack (version and architecture)
+    pub compatible: bool,
+}
+
+/// Details parsed from `java -XshowSettings:properties -version`
+#[derive(Debug, Clone, PartialEq)]
+struct JavaVersionInfo {
+    version: String,
+    major_version: u32,
+    vendor: Option<String>,
+    arch: Option<String>,
+    is_64bit: bool,
+}
+
+/// Major version from a `java.version` string (`1.8.0_392` -> 8, `21.0.9` -> 21, `17` -> 17)
+fn parse_major_version(version: &str) -> Option<u32> {
+    let mut parts = version.split(|c: char| !c.is_ascii_digit());
+    let first: u32 = parts.next()?.parse().ok()?;
+    if first == 1 {
+        parts.next()?.parse().ok()
+    } else {
+        Some(first)
+    }
+}
+
+/// Parse the combined output of `java -XshowSettings:properties -version`
+fn parse_java_output(output: &str) -> Option<JavaVersionInfo> {
+    let mut version = None;
+    let mut vendor = None;
+    let mut arch = None;
+    let mut data_model = None;
+
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some((key, value)) = line.split_once(" = ") {
+            let value = value.trim().to_string();
+            match key.trim() {
+                "java.version" => version = Some(value),
+                "java.vendor" => vendor = Some(value),
+                "os.arch" => arch = Some(value),
+                "sun.arch.data.model" => data_model = Some(value),
+                _ => {}
+            }
+        } else if version.is_none() && line.contains(" version \"") {
+            // `openjdk version "21.0.9" 2025-10-21 LTS`
+            version = line.split('"').nth(1).map(|v| v.to_string());
+        }
+    }
+
+    let version = version?;
+    let major_version = parse_major_version(&version)?;
+    let is_64bit = match (&data_model, &arch) {
+        (Some(model), _) => model == "64",
+        (None, Some(arch)) => arch.contains("64"),
+        (None, None) => output.contains("64-Bit"),
+    };
+
+    Some(JavaVersionInfo {
+        version,
+        major_version,
+        vendor,
+        arch,
+        is_64bit,
+    })
+}
+
+/// Whether a JVM's `os.arch` can run on this machine without emulation
+fn arch_matches_host(arch: Option<&str>) -> bool {
+    let Some(arch) = arch else {
+        return true;
+    };
+    match std::env::consts::ARCH {
+        "x86_64" => arch == "amd64" || arch == "x86_64",
+        "aarch64" => arch == "aarch64" || arch == "arm64",
+        host => arch == host,
+    }
+}
+
+/// Run `java -version` on a candidate and describe it
+pub async fn probe_java(path: &Path, source: &str) -> Result<JavaInstallation> {
+    let mut command = Command::new(path);
+    command
+        .args(["-XshowSettings:properties", "-version"])
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true);
+
+    #[cfg(windows)]
+    {
+        // CREATE_NO_WINDOW - don't flash a console for each probe
+        command.creation_flags(0x0800_0000);
+    }
+
+    let output = tokio::time::timeout(Duration::from_secs(JAVA_PROBE_TIMEOUT_SECS), command.output())
+        .await
+        .context("Timed out running java -version")?
+        .with_context(|| format!("Failed to run {}", path.display()))?;
+
+    // The JVM prints version and settings to stderr
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stderr),
+        String::from_utf8_lossy(&output.stdout)
+    );
+    let info = parse_java_output(&text)
+        .ok_or_else(|| anyhow::anyhow!("Unrecognized java -version output from {}", path.display()))?;
+
+    let compatible = info.major_version >= MIN_JAVA_MAJOR
+        && info.is_64bit
+        && arch_matches_host(info.arch.as_deref());
+
+    Ok(JavaInstallation {
+        path: path.to_path_buf(),
+        version: info.version,
+        major_version: info.major_version,
+        vendor: info.vendor,
+        arch: info.arch,
+        is_64bit: info.is_64bit,
+        source: source.to_string(),
+        compatible,
+    })
+}
+
+/// Add `<dir>/*/<suffix>` for every subdirectory of `dir`
+fn push_children(candidates: &mut Vec<(PathBuf, &'static str)>, dir: &Path, suffix: &[&str]) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let mut path = entry.path();
+        for part in suffix {
+            path.push(part);
+        }
+        candidates.push((path, "system"));
+    }
+}
+
+/// Common Java install locations for this platform
+fn candidate_paths() -> Vec<(PathBuf, &'static str)> {
+    let mut candidates = Vec::new();
+
+    if let Some(java_home) = std::env::var_os("JAVA_HOME") {
+        candidates.push((PathBuf::from(java_home).join("bin").join(JAVA_EXECUTABLE), "JAVA_HOME"));
+    }
+
+    if let Ok(paths) = which::which_all(JAVA_EXECUTABLE) {
+        candidates.extend(paths.map(|p| (p, "PATH")));
+    }
+
+    let home = dirs::home_dir();
+    let bin_java = ["bin", JAVA_EXECUTABLE];
+
+    #[cfg(target_os = "windows")]
+    {
+        for root in ["C:\\Program Files", "C:\\Program Files (x86)"] {
+            for vendor in [
+                "Java",
+                "Eclipse Adoptium",
+                "Eclipse Foundation",
+                "Zulu",
+                "Microsoft",
+                "Amazon Corretto",
+                "BellSoft",
+                "Semeru",
+            ] {
+                push_children(&mut candidates, &Path::new(root).join(vendor), &bin_java);
+            }
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let jvm_home = ["Contents", "Home", "bin", JAVA_EXECUTABLE];
+        push_children(&mut candidates, Path::new("/Library/Java/JavaVirtualMachines"), &jvm_home);
+        if let Some(home) = &home {
+            push_children(&mut candidates, &home.join("Library/Java/JavaVirtualMachines"), &jvm_home);
+        }
+        for prefix in ["/opt/homebrew/opt", "/usr/local/opt"] {
+            push_children(&mut candidates, Path::new(prefix), &bin_java);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        for dir in ["/usr/lib/jvm", "/usr/java", "/opt/java", "/opt/jdk"] {
+            push_children(&mut candidates, Path::new(dir), &bin_java);
+        }
+    }
+
+    if let Some(home) = &home {
+        // IntelliJ and SDKMAN! downloads
+        push_children(&mut candidates, &home.join(".jdks"), &bin_java);
+        push_children(&mut candidates, &home.join(".sdkman/candidates/java"), &bin_java);
+    }
+
+    candidates
+}
+
+/// Find Java installations on this machine, best candidates first
+///
+/// Checks JAVA_HOME, PATH and the usual vendor install directories, runs each
+/// `java` found and returns the ones that respond. Compatible runtimes sort
+/// before incompatible ones, newest version first.
+pub async fn detect_java_installations() -> Vec<JavaInstallation> {
+    let mut seen = HashSet::new();
+    let mut installations = Vec::new();
+
+    for (path, source) in candidate_paths() {
+        if !path.is_file() {
+            continue;
+        }
+        // Symlinks like /usr/bin/java usually point into /usr/lib/jvm
+        let canonical = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+        if !seen.insert(canonical) {
+            continue;
+        }
+
+        match probe_java(&path, source).await {
+            Ok(installation) => {
+                eprintln!(
+                    "[Java] Found Java {} at {} ({})",
+                    installation.version,
+                    path.display(),
+                    source
+                );
+                installations.push(installation);
+            }
+            Err(e) => eprintln!("[Java] Skipping {}: {}", path.display(), e),
+        }
+    }
+
+    installations.sort_by(|a, b| {
+        b.compatible
+            .cmp(&a.compatible)
+            .then(b.major_version.cmp(&a.major_version))
+    });
+    installations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_major_version() {
+        assert_eq!(parse_major_version("21.0.9"), Some(21));
+        assert_eq!(parse_major_version("17"), Some(17));
+        assert_eq!(parse_major_version("1.8.0_392"), Some(8));
+        assert_eq!(parse_major_version("22-ea"), Some(22));
+        assert_eq!(parse_major_version("abc"), None);
+    }
+
+    #[test]
+    fn test_parse_java_output_with_properties() {
+        let output = r#"Property settings:
+    java.vendor = Azul Systems, Inc.
+    java.version = 21.0.9
+    os.arch = amd64
+    sun.arch.data.model = 64
+
+openjdk version "21.0.9" 2025-10-21 LTS
+OpenJDK Runtime Environment Zulu21.46+19-CA (build 21.0.9+10-LTS)
+OpenJDK 64-Bit Server VM Zulu21.46+19-CA (build 21.0.9+10-LTS, mixed mode, sharing)"#;
+
+        let info = parse_java_output(output).unwrap();
+        assert_eq!(info.version, "21.0.9");
+        assert_eq!(info.major_version, 21);
+        assert_eq!(info.vendor.as_deref(), Some("Azul Systems, Inc."));
+        assert_eq!(info.arch.as_deref(), Some("amd64"));
+        assert!(info.is_64bit);
+    }
+
+    #[test]
+    fn test_parse_java_output_version_only() {
+        let output = r#"java version "1.8.0_392"
+Java(TM) SE Runtime Environment (build 1.8.0_392-b08)
+Java HotSpot(TM) Client VM (build 25.392-b08, mixed mode)"#;
+
+        let info = parse_java_output(output).unwrap();
+        assert_eq!(info.version, "1.8.0_392");
+        assert_eq!(info.major_version, 8);
+        assert!(!info.is_64bit);
+    }
+
+    #[test]
+    fn test_parse_java_output_garbage() {
+        assert!(parse_java_output("command not found").is_none());
+    }
+}
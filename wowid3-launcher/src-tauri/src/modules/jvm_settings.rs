@@ -0,0 +1,416 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+const PROFILES_FILE: &str = "jvm_profiles.json";
+
+const MIN_HEAP_MB: u32 = 1024;
+const MIN_INITIAL_HEAP_MB: u32 = 256;
+
+/// Garbage collector configuration
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GcPreset {
+    /// G1 with Aikar-style tuning for modded Minecraft
+    #[default]
+    G1Tuned,
+    /// G1 with JVM defaults
+    G1,
+    /// Generational ZGC (Java 21+); low pauses, needs more memory headroom
+    Zgc,
+}
+
+impl GcPreset {
+    fn args(self) -> Vec<String> {
+        let args: &[&str] = match self {
+            GcPreset::G1Tuned => &[
+                "-XX:+UseG1GC",
+                "-XX:+ParallelRefProcEnabled",
+                "-XX:MaxGCPauseMillis=200",
+                "-XX:+UnlockExperimentalVMOptions",
+                "-XX:+DisableExplicitGC",
+                "-XX:G1NewSizePercent=30",
+                "-XX:G1MaxNewSizePercent=40",
+                "-XX:G1HeapRegionSize=8M",
+                "-XX:G1ReservePercent=20",
+                "-XX:G1HeapWastePercent=5",
+                "-XX:G1MixedGCCountTarget=4",
+                "-XX:InitiatingHeapOccupancyPercent=15",
+                "-XX:G1MixedGCLiveThresholdPercent=90",
+                "-XX:G1RSetUpdatingPauseTimePercent=5",
+                "-XX:SurvivorRatio=32",
+                "-XX:+PerfDisableSharedMem",
+                "-XX:MaxTenuringThreshold=1",
+            ],
+            GcPreset::G1 => &["-XX:+UseG1GC"],
+            GcPreset::Zgc => &["-XX:+UseZGC", "-XX:+ZGenerational", "-XX:+DisableExplicitGC"],
+        };
+        args.iter().map(|a| a.to_string()).collect()
+    }
+}
+
+/// Structured JVM settings; turned into command-line flags by `jvm_args`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JvmSettings {
+    pub max_heap_mb: u32,
+    /// Initial heap (`-Xms`); defaults to `max_heap_mb`
+    #[serde(default)]
+    pub min_heap_mb: Option<u32>,
+    #[serde(default)]
+    pub gc_preset: GcPreset,
+    /// Extra flags appended after the generated ones, one argument per entry
+    #[serde(default)]
+    pub custom_args: Vec<String>,
+}
+
+impl JvmSettings {
+    pub fn from_ram(ram_mb: u32) -> Self {
+        Self {
+            max_heap_mb: ram_mb,
+            min_heap_mb: None,
+            gc_preset: GcPreset::default(),
+            custom_args: Vec::new(),
+        }
+    }
+
+    /// Reject settings that would stop the JVM from starting
+    pub fn validate(&self) -> Result<()> {
+        if self.max_heap_mb < MIN_HEAP_MB {
+            anyhow::bail!("Maximum heap must be at least {} MB", MIN_HEAP_MB);
+        }
+        if let Some(min) = self.min_heap_mb {
+            if min < MIN_INITIAL_HEAP_MB {
+                anyhow::bail!("Initial heap must be at least {} MB", MIN_INITIAL_HEAP_MB);
+            }
+            if min > self.max_heap_mb {
+                anyhow::bail!(
+                    "Initial heap ({} MB) cannot exceed maximum heap ({} MB)",
+                    min,
+                    self.max_heap_mb
+                );
+            }
+        }
+        for arg in &self.custom_args {
+            validate_custom_arg(arg)?;
+        }
+        Ok(())
+    }
+
+    pub fn jvm_args(&self) -> Vec<String> {
+        let mut args = vec![
+            format!("-Xmx{}M", self.max_heap_mb),
+            format!("-Xms{}M", self.min_heap_mb.unwrap_or(self.max_heap_mb)),
+        ];
+        args.extend(self.gc_preset.args());
+        args.extend(self.custom_args.iter().map(|a| a.trim().to_string()));
+        args
+    }
+}
+
+/// Check a single user-supplied JVM flag
+///
+/// Heap size, GC selection and classpath are controlled by the launcher and
+/// can't be overridden here; anything that isn't a recognizable JVM option is rejected.
+fn validate_custom_arg(arg: &str) -> Result<()> {
+    let arg = arg.trim();
+
+    if arg.is_empty() {
+        anyhow::bail!("Empty JVM argument");
+    }
+    if arg.contains(char::is_whitespace) && !arg.starts_with("-D") {
+        anyhow::bail!("JVM argument '{}' contains spaces; put each flag on its own line", arg);
+    }
+    if arg.starts_with("-Xmx") || arg.starts_with("-Xms") {
+        anyhow::bail!("'{}' conflicts with the heap settings; use the memory fields instead", arg);
+    }
+    if arg.starts_with("-XX:+Use") && arg.ends_with("GC") {
+        anyhow::bail!("'{}' conflicts with the GC preset; pick a preset instead", arg);
+    }
+    if matches!(arg, "-cp" | "-classpath" | "--class-path" | "-jar") || arg.starts_with("--module-path") {
+        anyhow::bail!("'{}' is managed by the launcher", arg);
+    }
+
+    if arg.starts_with("-XX") && !arg.starts_with("-XX:") {
+        anyhow::bail!("Malformed -XX option '{}' (missing ':')", arg);
+    }
+
+    if let Some(option) = arg.strip_prefix("-XX:") {
+        let valid = match option.chars().next() {
+            Some('+') | Some('-') => option.len() > 1 && option[1..].chars().all(|c| c.is_ascii_alphanumeric()),
+            Some(_) => match option.split_once('=') {
+                Some((name, value)) => {
+                    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric()) && !value.is_empty()
+                }
+                None => false,
+            },
+            None => false,
+        };
+        if !valid {
+            anyhow::bail!("Malformed -XX option '{}' (expected -XX:+Flag, -XX:-Flag or -XX:Name=value)", arg);
+        }
+        return Ok(());
+    }
+
+    if let Some(property) = arg.strip_prefix("-D") {
+        if property.is_empty() || property.starts_with('=') {
+            anyhow::bail!("Malformed system property '{}' (expected -Dname=value)", arg);
+        }
+        return Ok(());
+    }
+
+    const ALLOWED_PREFIXES: &[&str] = &[
+        "-X",
+        "-javaagent:",
+        "--add-opens=",
+        "--add-exports=",
+        "--enable-native-access=",
+        "-ea",
+        "-da",
+        "-verbose",
+    ];
+    if ALLOWED_PREFIXES.iter().any(|p| arg.starts_with(p)) {
+        return Ok(());
+    }
+
+    anyhow::bail!("Unrecognized JVM argument '{}'", arg)
+}
+
+/// A named, reusable set of JVM settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JvmProfile {
+    pub name: String,
+    pub settings: JvmSettings,
+    /// Shipped with the launcher; can't be overwritten or deleted
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+fn builtin_profiles() -> Vec<JvmProfile> {
+    vec![
+        JvmProfile {
+            name: "Default".to_string(),
+            settings: JvmSettings::from_ram(8192),
+            builtin: true,
+        },
+        JvmProfile {
+            name: "Low RAM".to_string(),
+            settings: JvmSettings {
+                max_heap_mb: 4096,
+                min_heap_mb: Some(1024),
+                gc_preset: GcPreset::G1,
+                custom_args: Vec::new(),
+            },
+            builtin: true,
+        },
+        JvmProfile {
+            name: "Shaders".to_string(),
+            settings: JvmSettings {
+                max_heap_mb: 12288,
+                min_heap_mb: None,
+                gc_preset: GcPreset::Zgc,
+                custom_args: Vec::new(),
+            },
+            builtin: true,
+        },
+    ]
+}
+
+fn profiles_path() -> Result<PathBuf> {
+    Ok(super::paths::get_persistent_data_dir()?.join(PROFILES_FILE))
+}
+
+async fn read_user_profiles(path: &Path) -> Result<Vec<JvmProfile>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path)
+        .await
+        .context("Failed to read JVM profiles")?;
+    serde_json::from_str(&content).context("Failed to parse JVM profiles")
+}
+
+async fn write_user_profiles(path: &Path, profiles: &[JvmProfile]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let content = serde_json::to_string_pretty(profiles)?;
+    fs::write(path, content)
+        .await
+        .context("Failed to write JVM profiles")
+}
+
+/// Built-in profiles followed by the user's saved profiles
+pub async fn list_profiles_at(path: &Path) -> Result<Vec<JvmProfile>> {
+    let mut profiles = builtin_profiles();
+    profiles.extend(read_user_profiles(path).await?);
+    Ok(profiles)
+}
+
+/// Create or replace a user profile
+pub async fn save_profile_at(path: &Path, mut profile: JvmProfile) -> Result<JvmProfile> {
+    profile.name = profile.name.trim().to_string();
+    if profile.name.is_empty() {
+        anyhow::bail!("Profile name is required");
+    }
+    if builtin_profiles().iter().any(|p| p.name.eq_ignore_ascii_case(&profile.name)) {
+        anyhow::bail!("'{}' is a built-in profile; save under a different name", profile.name);
+    }
+    profile.settings.validate()?;
+    profile.builtin = false;
+
+    let mut profiles = read_user_profiles(path).await?;
+    match profiles.iter_mut().find(|p| p.name.eq_ignore_ascii_case(&profile.name)) {
+        Some(existing) => *existing = profile.clone(),
+        None => profiles.push(profile.clone()),
+    }
+    write_user_profiles(path, &profiles).await?;
+    Ok(profile)
+}
+
+pub async fn delete_profile_at(path: &Path, name: &str) -> Result<()> {
+    let mut profiles = read_user_profiles(path).await?;
+    let before = profiles.len();
+    profiles.retain(|p| !p.name.eq_ignore_ascii_case(name));
+    if profiles.len() == before {
+        anyhow::bail!("No saved profile named '{}'", name);
+    }
+    write_user_profiles(path, &profiles).await
+}
+
+pub async fn list_profiles() -> Result<Vec<JvmProfile>> {
+    list_profiles_at(&profiles_path()?).await
+}
+
+pub async fn load_profile(name: &str) -> Result<JvmProfile> {
+    list_profiles()
+        .await?
+        .into_iter()
+        .find(|p| p.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| anyhow::anyhow!("No JVM profile named '{}'", name))
+}
+
+pub async fn save_profile(profile: JvmProfile) -> Result<JvmProfile> {
+    save_profile_at(&profiles_path()?, profile).await
+}
+
+pub async fn delete_profile(name: &str) -> Result<()> {
+    delete_profile_at(&profiles_path()?, name).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_jvm_args() {
+        let settings = JvmSettings {
+            max_heap_mb: 8192,
+            min_heap_mb: Some(2048),
+            gc_preset: GcPreset::Zgc,
+            custom_args: vec!["-Dfoo=bar".to_string()],
+        };
+
+        let args = settings.jvm_args();
+        assert_eq!(args[0], "-Xmx8192M");
+        assert_eq!(args[1], "-Xms2048M");
+        assert!(args.contains(&"-XX:+UseZGC".to_string()));
+        assert!(!args.contains(&"-XX:+UseG1GC".to_string()));
+        assert_eq!(args.last().unwrap(), "-Dfoo=bar");
+
+        let args = JvmSettings::from_ram(4096).jvm_args();
+        assert_eq!(args[1], "-Xms4096M");
+        assert!(args.contains(&"-XX:G1HeapRegionSize=8M".to_string()));
+    }
+
+    #[test]
+    fn test_validate_heap() {
+        assert!(JvmSettings::from_ram(512).validate().is_err());
+        let mut settings = JvmSettings::from_ram(4096);
+        settings.min_heap_mb = Some(8192);
+        assert!(settings.validate().is_err());
+        settings.min_heap_mb = Some(1024);
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_custom_args() {
+        for ok in [
+            "-XX:+AlwaysPreTouch",
+            "-XX:-UseAdaptiveSizePolicy",
+            "-XX:MaxGCPauseMillis=50",
+            "-Dfile.encoding=UTF-8",
+            "-Dsome.path=C:\\Program Files\\x",
+            "-Xss4M",
+            "--add-opens=java.base/java.lang=ALL-UNNAMED",
+        ] {
+            assert!(validate_custom_arg(ok).is_ok(), "{}", ok);
+        }
+
+        for bad in [
+            "-Xmx4G",
+            "-XX:+UseZGC",
+            "-XX+AlwaysPreTouch",
+            "-XX:MaxGCPauseMillis",
+            "-XX:+AlwaysPreTouch -XX:+UseNUMA",
+            "-cp",
+            "-D",
+            "AlwaysPreTouch",
+            "",
+        ] {
+            assert!(validate_custom_arg(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_gc_preset_serialization() {
+        let settings: JvmSettings = serde_json::from_str(r#"{"max_heap_mb": 4096}"#).unwrap();
+        assert_eq!(settings.gc_preset, GcPreset::G1Tuned);
+
+        let settings: JvmSettings =
+            serde_json::from_str(r#"{"max_heap_mb": 4096, "gc_preset": "zgc"}"#).unwrap();
+        assert_eq!(settings.gc_preset, GcPreset::Zgc);
+    }
+
+    #[tokio::test]
+    async fn test_profile_storage() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(PROFILES_FILE);
+
+        let profiles = list_profiles_at(&path).await.unwrap();
+        assert!(profiles.iter().any(|p| p.name == "Low RAM" && p.builtin));
+
+        let profile = JvmProfile {
+            name: " Mine ".to_string(),
+            settings: JvmSettings::from_ram(6144),
+            builtin: true,
+        };
+        let saved = save_profile_at(&path, profile).await.unwrap();
+        assert_eq!(saved.name, "Mine");
+        assert!(!saved.builtin);
+
+        // Overwrite by name
+        let mut updated = saved.clone();
+        updated.settings.max_heap_mb = 7168;
+        save_profile_at(&path, updated).await.unwrap();
+        let profiles = list_profiles_at(&path).await.unwrap();
+        let mine: Vec<_> = profiles.iter().filter(|p| p.name == "Mine").collect();
+        assert_eq!(mine.len(), 1);
+        assert_eq!(mine[0].settings.max_heap_mb, 7168);
+
+        // Built-ins and invalid settings are rejected
+        let builtin = JvmProfile {
+            name: "shaders".to_string(),
+            settings: JvmSettings::from_ram(4096),
+            builtin: false,
+        };
+        assert!(save_profile_at(&path, builtin).await.is_err());
+        let mut invalid = saved.clone();
+        invalid.settings.custom_args = vec!["-Xmx2G".to_string()];
+        assert!(save_profile_at(&path, invalid).await.is_err());
+
+        delete_profile_at(&path, "mine").await.unwrap();
+        assert!(delete_profile_at(&path, "mine").await.is_err());
+    }
+}
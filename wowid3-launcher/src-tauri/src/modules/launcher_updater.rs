@@ -1,16 +1,30 @@
 use anyhow::{Result, Context};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::signature::{UnparsedPublicKey, ED25519};
 use serde::{Serialize, Deserialize};
 use tauri::AppHandle;
 use std::time::Duration;
 use sha2::{Digest, Sha256};
 use std::env;
 use std::process::Command;
+use std::sync::RwLock;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use futures_util::StreamExt;
 
+const LAUNCHER_API_URL: &str = "https://wowid-launcher.frostdev.io/api/launcher";
+/// Servers without the versions index only publish the latest executable here
 const LAUNCHER_MANIFEST_URL: &str = "https://wowid-launcher.frostdev.io/api/launcher/latest/executable";
 
+/// Base64 Ed25519 key the server signs launcher uploads with, set when building
+/// releases; builds without it (local development) skip signature checks
+const SIGNING_PUBLIC_KEY: Option<&str> = option_env!("WOWID3_LAUNCHER_PUBLIC_KEY");
+
+lazy_static::lazy_static! {
+    /// Version of a pending mandatory update; launching the game is refused until it's applied
+    static ref MANDATORY_UPDATE: RwLock<Option<String>> = RwLock::new(None);
+}
+
 // Old single-file manifest format (for backward compatibility)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LauncherManifest {
@@ -20,6 +34,8 @@ pub struct LauncherManifest {
     pub size: u64,
     pub changelog: String,
     pub mandatory: bool,
+    #[serde(default)]
+    pub signature: Option<String>,
 }
 
 // New multi-platform manifest format
@@ -41,6 +57,16 @@ pub struct LauncherFile {
     pub url: String,
     pub sha256: String,
     pub size: u64,
+    /// Base64 Ed25519 signature over the SHA-256 digest
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+/// GET /api/launcher/versions
+#[derive(Debug, Clone, Deserialize)]
+pub struct LauncherVersionsIndex {
+    pub versions: Vec<String>,
+    pub latest: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -48,25 +74,189 @@ pub struct LauncherUpdateInfo {
     pub available: bool,
     pub version: String,
     pub changelog: String,
+    /// Set when the latest version, or any version between it and this one, is mandatory
     pub mandatory: bool,
     pub download_url: String,
     pub sha256: String,
+    /// "executable" (swapped in place) or "installer" (run, then the launcher exits)
+    pub file_type: String,
+    pub signature: Option<String>,
+}
+
+impl LauncherUpdateInfo {
+    fn up_to_date(current_version: &str) -> Self {
+        Self {
+            available: false,
+            version: current_version.to_string(),
+            changelog: String::new(),
+            mandatory: false,
+            download_url: String::new(),
+            sha256: String::new(),
+            file_type: String::new(),
+            signature: None,
+        }
+    }
+}
+
+fn current_platform() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "windows"
+    } else if cfg!(target_os = "linux") {
+        "linux"
+    } else if cfg!(target_os = "macos") {
+        "macos"
+    } else {
+        "unknown"
+    }
+}
+
+/// The file to update from: the platform's executable, or its installer if there is none
+fn select_platform_file<'a>(files: &'a [LauncherFile], platform: &str) -> Option<&'a LauncherFile> {
+    let for_platform = || files.iter().filter(move |f| f.platform == platform);
+    for_platform()
+        .find(|f| f.file_type.as_deref() == Some("executable") || f.file_type.is_none())
+        .or_else(|| for_platform().find(|f| f.file_type.as_deref() == Some("installer")))
+}
+
+/// Published versions newer than `current`, newest first
+fn newer_versions<'a>(index: &'a LauncherVersionsIndex, current: &str) -> Vec<&'a str> {
+    let mut newer: Vec<&str> = index
+        .versions
+        .iter()
+        .map(String::as_str)
+        .filter(|v| is_newer_version(v, current))
+        .collect();
+    if is_newer_version(&index.latest, current) && !newer.contains(&index.latest.as_str()) {
+        newer.push(&index.latest);
+    }
+    newer.sort_by(|a, b| {
+        if is_newer_version(a, b) {
+            std::cmp::Ordering::Less
+        } else if is_newer_version(b, a) {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    });
+    newer
+}
+
+fn set_mandatory_update(version: Option<String>) {
+    *MANDATORY_UPDATE.write().unwrap_or_else(|e| e.into_inner()) = version;
+}
+
+/// Refuse to continue while a mandatory launcher update is pending
+pub fn ensure_no_mandatory_update() -> Result<()> {
+    if let Some(version) = MANDATORY_UPDATE.read().unwrap_or_else(|e| e.into_inner()).as_deref() {
+        anyhow::bail!("Launcher update {} is mandatory; install it before playing", version);
+    }
+    Ok(())
 }
 
 /// Check for launcher updates
+///
+/// Compares against every published version, so skipping past a mandatory
+/// release still makes the update mandatory.
 pub async fn check_launcher_update(app: &AppHandle) -> Result<LauncherUpdateInfo> {
-    // Get current version
-    let package_info = app.package_info();
-    let current_version = &package_info.version;
-
+    let current_version = app.package_info().version.to_string();
     eprintln!("[Launcher Updater] Current launcher version: {}", current_version);
 
-    // Fetch manifest
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(10))
         .build()
         .context("Failed to create HTTP client")?;
 
+    let info = match fetch_versions_index(&client).await {
+        Ok(Some(index)) => check_versions_index(&client, &index, &current_version).await?,
+        Ok(None) => check_latest_manifest(&client, &current_version).await?,
+        Err(e) => {
+            eprintln!("[Launcher Updater] Versions index unavailable, using latest manifest: {}", e);
+            check_latest_manifest(&client, &current_version).await?
+        }
+    };
+
+    set_mandatory_update((info.available && info.mandatory).then(|| info.version.clone()));
+    Ok(info)
+}
+
+/// The versions index, or `None` if the server doesn't publish one
+async fn fetch_versions_index(client: &reqwest::Client) -> Result<Option<LauncherVersionsIndex>> {
+    let response = client
+        .get(format!("{}/versions", LAUNCHER_API_URL))
+        .send()
+        .await
+        .context("Failed to fetch launcher versions")?;
+    if response.status() == 404 {
+        return Ok(None);
+    }
+    let index = response
+        .error_for_status()?
+        .json::<LauncherVersionsIndex>()
+        .await
+        .context("Failed to parse launcher versions")?;
+    Ok(Some(index))
+}
+
+async fn fetch_version(client: &reqwest::Client, version: &str) -> Result<LauncherVersion> {
+    client
+        .get(format!("{}/{}", LAUNCHER_API_URL, version))
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<LauncherVersion>()
+        .await
+        .with_context(|| format!("Failed to parse launcher version {}", version))
+}
+
+async fn check_versions_index(
+    client: &reqwest::Client,
+    index: &LauncherVersionsIndex,
+    current_version: &str,
+) -> Result<LauncherUpdateInfo> {
+    let newer = newer_versions(index, current_version);
+    let Some(latest) = newer.first() else {
+        eprintln!("[Launcher Updater] Up to date (latest: {})", index.latest);
+        return Ok(LauncherUpdateInfo::up_to_date(current_version));
+    };
+
+    let latest = fetch_version(client, latest).await?;
+    let platform = current_platform();
+    let file = select_platform_file(&latest.files, platform)
+        .with_context(|| format!("Launcher {} has no file for platform: {}", latest.version, platform))?;
+
+    let mut mandatory = latest.mandatory;
+    for version in newer.iter().skip(1) {
+        if mandatory {
+            break;
+        }
+        match fetch_version(client, version).await {
+            Ok(skipped) => mandatory = skipped.mandatory,
+            Err(e) => eprintln!("[Launcher Updater] Failed to read launcher {}: {}", version, e),
+        }
+    }
+
+    eprintln!(
+        "[Launcher Updater] Update available: {} -> {} ({} newer, mandatory: {})",
+        current_version,
+        latest.version,
+        newer.len(),
+        mandatory
+    );
+
+    Ok(LauncherUpdateInfo {
+        available: true,
+        version: latest.version.clone(),
+        changelog: latest.changelog.clone(),
+        mandatory,
+        download_url: file.url.clone(),
+        sha256: file.sha256.clone(),
+        file_type: file.file_type.clone().unwrap_or_else(|| "executable".to_string()),
+        signature: file.signature.clone(),
+    })
+}
+
+/// Check the latest executable only (servers without a versions index)
+async fn check_latest_manifest(client: &reqwest::Client, current_version: &str) -> Result<LauncherUpdateInfo> {
     let response = client
         .get(LAUNCHER_MANIFEST_URL)
         .send()
@@ -76,14 +266,7 @@ pub async fn check_launcher_update(app: &AppHandle) -> Result<LauncherUpdateInfo
     if response.status() == 404 {
         // No update available (or configured)
         eprintln!("[Launcher Updater] No launcher manifest found on server (404)");
-        return Ok(LauncherUpdateInfo {
-            available: false,
-            version: current_version.to_string(),
-            changelog: String::new(),
-            mandatory: false,
-            download_url: String::new(),
-            sha256: String::new(),
-        });
+        return Ok(LauncherUpdateInfo::up_to_date(current_version));
     }
 
     let response_text = response.text().await
@@ -94,29 +277,14 @@ pub async fn check_launcher_update(app: &AppHandle) -> Result<LauncherUpdateInfo
         eprintln!("[Launcher Updater] Using new multi-platform format");
         eprintln!("[Launcher Updater] Remote launcher version: {}", launcher_version.version);
 
-        // Determine current platform
-        let current_platform = if cfg!(target_os = "windows") {
-            "windows"
-        } else if cfg!(target_os = "linux") {
-            "linux"
-        } else if cfg!(target_os = "macos") {
-            "macos"
-        } else {
-            "unknown"
-        };
+        let platform = current_platform();
+        let platform_file = select_platform_file(&launcher_version.files, platform)
+            .context(format!("No executable found for platform: {}", platform))?;
 
-        // Find the file for the current platform with file_type = "executable"
-        let platform_file = launcher_version.files.iter()
-            .find(|f| {
-                f.platform == current_platform &&
-                (f.file_type.as_deref() == Some("executable") || f.file_type.is_none())
-            })
-            .context(format!("No executable found for platform: {}", current_platform))?;
-
-        eprintln!("[Launcher Updater] Found file for platform {}: {}", current_platform, platform_file.filename);
+        eprintln!("[Launcher Updater] Found file for platform {}: {}", platform, platform_file.filename);
 
         // Compare versions
-        let update_available = is_newer_version(&launcher_version.version, &current_version.to_string());
+        let update_available = is_newer_version(&launcher_version.version, current_version);
 
         eprintln!("[Launcher Updater] Update available: {} (remote: {}, local: {})",
             update_available, launcher_version.version, current_version);
@@ -128,6 +296,8 @@ pub async fn check_launcher_update(app: &AppHandle) -> Result<LauncherUpdateInfo
             mandatory: launcher_version.mandatory,
             download_url: platform_file.url.clone(),
             sha256: platform_file.sha256.clone(),
+            file_type: platform_file.file_type.clone().unwrap_or_else(|| "executable".to_string()),
+            signature: platform_file.signature.clone(),
         });
     }
 
@@ -137,7 +307,7 @@ pub async fn check_launcher_update(app: &AppHandle) -> Result<LauncherUpdateInfo
         eprintln!("[Launcher Updater] Remote launcher version: {}", manifest.version);
 
         // Compare versions
-        let update_available = is_newer_version(&manifest.version, &current_version.to_string());
+        let update_available = is_newer_version(&manifest.version, current_version);
 
         eprintln!("[Launcher Updater] Update available: {} (remote: {}, local: {})",
             update_available, manifest.version, current_version);
@@ -149,6 +319,8 @@ pub async fn check_launcher_update(app: &AppHandle) -> Result<LauncherUpdateInfo
             mandatory: manifest.mandatory,
             download_url: manifest.url,
             sha256: manifest.sha256,
+            file_type: "executable".to_string(),
+            signature: manifest.signature,
         });
     }
 
@@ -185,29 +357,48 @@ fn is_newer_version(remote: &str, local: &str) -> bool {
 }
 
 /// Install launcher update - platform-specific implementation
+///
+/// `file_type` is the update's `file_type`: executables replace the running
+/// launcher, installers are run and the launcher exits so they can replace it.
+/// Nothing is applied unless the download matches `sha256` and, in release
+/// builds, `signature` verifies against the embedded public key.
 pub async fn install_launcher_update<F>(
     url: String,
     sha256: String,
+    file_type: Option<String>,
+    signature: Option<String>,
     progress_callback: F
 ) -> Result<()>
 where
     F: Fn(u64, u64) + Send + Sync + 'static
 {
+    let installer = file_type.as_deref() == Some("installer");
+
     // Common: Download and verify file
     eprintln!("[Updater] Downloading update from {}", url);
-    let response = reqwest::get(&url).await.context("Failed to download update")?;
+    let response = reqwest::get(&url)
+        .await
+        .context("Failed to download update")?
+        .error_for_status()
+        .context("Failed to download update")?;
     let total_size = response.content_length().unwrap_or(0);
 
     let temp_dir = env::temp_dir();
-    let temp_file = temp_dir.join(format!("launcher_update_{}", uuid::Uuid::new_v4()));
+    let mut temp_file = temp_dir.join(format!("launcher_update_{}", uuid::Uuid::new_v4()));
+    if installer && cfg!(target_os = "windows") {
+        // Windows only runs it with an executable extension
+        temp_file.set_extension("exe");
+    }
 
     let mut file = fs::File::create(&temp_file).await.context("Failed to create temp file")?;
     let mut stream = response.bytes_stream();
+    let mut hasher = Sha256::new();
     let mut downloaded: u64 = 0;
 
     while let Some(chunk) = stream.next().await {
         let chunk = chunk.context("Error downloading chunk")?;
         file.write_all(&chunk).await.context("Error writing to file")?;
+        hasher.update(&chunk);
         downloaded += chunk.len() as u64;
         progress_callback(downloaded, total_size);
     }
@@ -216,37 +407,79 @@ where
     drop(file); // Close file
 
     // Verify checksum
-    let bytes = fs::read(&temp_file).await.context("Failed to read downloaded file")?;
-    let mut hasher = Sha256::new();
-    hasher.update(&bytes);
-    let calculated_hash = format!("{:x}", hasher.finalize());
-
-    if calculated_hash != sha256 {
+    let digest = hasher.finalize();
+    let calculated_hash = format!("{:x}", digest);
+    if !calculated_hash.eq_ignore_ascii_case(&sha256) {
         fs::remove_file(&temp_file).await.ok();
         anyhow::bail!("Checksum mismatch. Expected {}, got {}", sha256, calculated_hash);
     }
 
+    if let Err(e) = verify_signature(&digest, signature.as_deref(), SIGNING_PUBLIC_KEY) {
+        fs::remove_file(&temp_file).await.ok();
+        return Err(e);
+    }
+
     eprintln!("[Updater] Checksum verified. Applying update...");
 
     // Platform-specific update logic
     #[cfg(target_os = "windows")]
     {
-        install_windows_update(temp_file).await?;
+        if installer {
+            run_windows_installer(temp_file).await?;
+        } else {
+            install_windows_update(temp_file).await?;
+        }
     }
 
     #[cfg(target_os = "linux")]
     {
+        if installer {
+            fs::remove_file(&temp_file).await.ok();
+            anyhow::bail!("Linux updates must be an AppImage executable");
+        }
         install_linux_appimage(temp_file).await?;
     }
 
     #[cfg(target_os = "macos")]
     {
+        let _ = installer;
         anyhow::bail!("macOS self-update not yet implemented");
     }
 
     Ok(())
 }
 
+/// Check the server's signature over a download's SHA-256 digest
+///
+/// With a public key, unsigned or mis-signed updates are rejected; without one
+/// (development builds) the check is skipped.
+fn verify_signature(digest: &[u8], signature: Option<&str>, public_key: Option<&str>) -> Result<()> {
+    let Some(public_key) = public_key else {
+        eprintln!("[Updater] No signing key built in; skipping signature check");
+        return Ok(());
+    };
+    let signature = signature.context("Update is not signed; refusing to install it")?;
+
+    let public_key = STANDARD.decode(public_key).context("Invalid embedded signing key")?;
+    let signature = STANDARD.decode(signature.trim()).context("Invalid update signature encoding")?;
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(digest, &signature)
+        .map_err(|_| anyhow::anyhow!("Update signature is invalid; refusing to install it"))?;
+
+    eprintln!("[Updater] Signature verified");
+    Ok(())
+}
+
+/// Hand over to the installer; it replaces the launcher once this process is gone
+#[cfg(target_os = "windows")]
+async fn run_windows_installer(installer: std::path::PathBuf) -> Result<()> {
+    eprintln!("[Updater] Running installer {:?}", installer);
+    Command::new(&installer)
+        .spawn()
+        .context("Failed to start installer")?;
+    std::process::exit(0);
+}
+
 #[cfg(target_os = "windows")]
 async fn install_windows_update(temp_file: std::path::PathBuf) -> Result<()> {
     let current_exe = env::current_exe().context("Failed to get current executable path")?;
@@ -393,6 +626,72 @@ mod tests {
         std::fs::remove_file(&dst).ok();
     }
 
+    fn file(platform: &str, file_type: Option<&str>) -> LauncherFile {
+        LauncherFile {
+            platform: platform.to_string(),
+            file_type: file_type.map(str::to_string),
+            filename: format!("{}-{}", platform, file_type.unwrap_or("none")),
+            url: String::new(),
+            sha256: String::new(),
+            size: 0,
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn test_select_platform_file_prefers_executable() {
+        let files = vec![
+            file("windows", Some("installer")),
+            file("windows", Some("executable")),
+            file("linux", None),
+        ];
+        assert_eq!(select_platform_file(&files, "windows").unwrap().filename, "windows-executable");
+        assert_eq!(select_platform_file(&files, "linux").unwrap().filename, "linux-none");
+        assert!(select_platform_file(&files, "macos").is_none());
+
+        let installer_only = vec![file("windows", Some("installer"))];
+        assert_eq!(select_platform_file(&installer_only, "windows").unwrap().filename, "windows-installer");
+    }
+
+    #[test]
+    fn test_newer_versions_newest_first() {
+        let index = LauncherVersionsIndex {
+            versions: vec!["1.2.0".to_string(), "1.10.0".to_string(), "1.0.0".to_string(), "1.1.1".to_string()],
+            latest: "1.10.0".to_string(),
+        };
+        assert_eq!(newer_versions(&index, "1.1.0"), vec!["1.10.0", "1.2.0", "1.1.1"]);
+        assert!(newer_versions(&index, "1.10.0").is_empty());
+    }
+
+    #[test]
+    fn test_mandatory_update_blocks_until_cleared() {
+        set_mandatory_update(Some("2.0.0".to_string()));
+        assert!(ensure_no_mandatory_update().unwrap_err().to_string().contains("2.0.0"));
+        set_mandatory_update(None);
+        assert!(ensure_no_mandatory_update().is_ok());
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        use ring::rand::SystemRandom;
+        use ring::signature::{Ed25519KeyPair, KeyPair};
+
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let public_key = STANDARD.encode(key.public_key().as_ref());
+
+        let digest = Sha256::digest(b"launcher binary");
+        let signature = STANDARD.encode(key.sign(&digest).as_ref());
+        assert!(verify_signature(&digest, Some(&signature), Some(&public_key)).is_ok());
+
+        let tampered = Sha256::digest(b"tampered binary");
+        assert!(verify_signature(&tampered, Some(&signature), Some(&public_key)).is_err());
+        assert!(verify_signature(&digest, None, Some(&public_key)).is_err());
+
+        // Development builds have no key to check against
+        assert!(verify_signature(&digest, None, None).is_ok());
+    }
+
     #[test]
     fn test_version_comparison() {
         assert!(is_newer_version("1.0.1", "1.0.0"));
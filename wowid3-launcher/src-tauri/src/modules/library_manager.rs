@@ -1,11 +1,11 @@
 use anyhow::{Context, Result};
 use sha1::{Digest, Sha1};
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use tokio::io::AsyncWriteExt;
 use zip::ZipArchive;
 
-use super::download_manager::{DownloadManager, DownloadPriority, DownloadTask, HashType};
+use super::download_manager::{DownloadPriority, DownloadTask, HashType};
 use super::minecraft_version::{Library, Rule};
 
 /// Current OS name for rule evaluation
@@ -40,6 +40,13 @@ fn get_arch() -> &'static str {
     }
 }
 
+/// Classifier of the library's natives for this OS (old versions use `${arch}` for 32/64-bit)
+fn native_classifier(library: &Library) -> Option<String> {
+    let key = library.natives.as_ref()?.get(get_os_name())?;
+    let bits = if cfg!(target_pointer_width = "64") { "64" } else { "32" };
+    Some(key.replace("${arch}", bits))
+}
+
 /// Evaluate if a rule applies to the current system
 fn evaluate_rule(rule: &Rule, features: &HashMap<String, bool>) -> bool {
     let action_allow = rule.action == "allow";
@@ -182,88 +189,60 @@ pub async fn verify_sha1(path: &Path, expected: &str) -> Result<bool> {
 }
 
 
-/// Download all libraries for a version using DownloadManager for parallel downloads
-pub async fn download_all_libraries(
+/// Download tasks for every library (and native) this OS needs
+///
+/// Libraries without `downloads` are fetched from their Maven repository when the
+/// profile gives a checksum. Nothing is checked on disk; see `retain_missing`.
+pub fn library_download_tasks(
     libraries: &[Library],
     libraries_dir: &Path,
     features: &HashMap<String, bool>,
-) -> Result<Vec<PathBuf>> {
-    tokio::fs::create_dir_all(libraries_dir).await?;
-
-    // Collect all download tasks upfront
-    let mut download_tasks = Vec::new();
-    let mut expected_paths = Vec::new();
+) -> Vec<DownloadTask> {
+    let mut tasks: Vec<DownloadTask> = Vec::new();
+    let mut push = |url: String, path: &str, sha1: &str, size: u64| {
+        let dest = libraries_dir.join(path);
+        // Fabric and vanilla can list the same artifact
+        if tasks.iter().any(|task| task.dest == dest) {
+            return;
+        }
+        tasks.push(DownloadTask {
+            url,
+            dest,
+            expected_hash: HashType::Sha1(sha1.to_string()),
+            priority: DownloadPriority::High,
+            size,
+            mirrors: Vec::new(),
+        });
+    };
 
     for library in libraries {
         if !should_download_library(library, features) {
             continue;
         }
 
-        // Main artifact
-        if let Some(downloads) = &library.downloads {
-            if let Some(artifact) = &downloads.artifact {
-                let dest = libraries_dir.join(&artifact.path);
-
-                // Skip if already exists and hash matches
-                if dest.exists() {
-                    if let Ok(true) = verify_sha1(&dest, &artifact.sha1).await {
-                        expected_paths.push(dest);
-                        continue;
-                    }
-                }
-
-                download_tasks.push(DownloadTask {
-                    url: artifact.url.clone(),
-                    dest: dest.clone(),
-                    expected_hash: HashType::Sha1(artifact.sha1.clone()),
-                    priority: DownloadPriority::High,
-                    size: artifact.size,
-                });
-                expected_paths.push(dest);
+        let Some(downloads) = &library.downloads else {
+            if let (Some(base), Some(sha1)) = (&library.url, &library.sha1) {
+                let path = maven_to_path(&library.name);
+                let url = format!("{}/{}", base.trim_end_matches('/'), path);
+                push(url, &path, sha1, library.size.unwrap_or(0));
             }
+            continue;
+        };
 
-            // Native libraries
-            if let Some(natives) = &library.natives {
-                let os_name = get_os_name();
-                if let Some(native_key) = natives.get(os_name) {
-                    if let Some(classifiers) = &downloads.classifiers {
-                        if let Some(native_artifact) = classifiers.get(native_key) {
-                            let dest = libraries_dir.join(&native_artifact.path);
-
-                            // Skip if already exists and hash matches
-                            if dest.exists() {
-                                if let Ok(true) = verify_sha1(&dest, &native_artifact.sha1).await {
-                                    expected_paths.push(dest);
-                                    continue;
-                                }
-                            }
-
-                            download_tasks.push(DownloadTask {
-                                url: native_artifact.url.clone(),
-                                dest: dest.clone(),
-                                expected_hash: HashType::Sha1(native_artifact.sha1.clone()),
-                                priority: DownloadPriority::High,
-                                size: native_artifact.size,
-                            });
-                            expected_paths.push(dest);
-                        }
-                    }
-                }
-            }
+        // Main artifact; an empty url marks a file generated by the loader installer (NeoForge)
+        if let Some(artifact) = downloads.artifact.as_ref().filter(|a| !a.url.is_empty()) {
+            push(artifact.url.clone(), &artifact.path, &artifact.sha1, artifact.size);
         }
-    }
 
-    // Download all files in parallel using DownloadManager
-    if !download_tasks.is_empty() {
-        let concurrency = super::download_manager::calculate_optimal_concurrency();
-        let manager = DownloadManager::new(concurrency, 3)?;
-        manager
-            .download_files(download_tasks, None)
-            .await
-            .context("Failed to download libraries")?;
+        // Native libraries
+        let native_artifact = native_classifier(library)
+            .and_then(|key| downloads.classifiers.as_ref()?.get(&key));
+        if let Some(native) = native_artifact {
+            push(native.url.clone(), &native.path, &native.sha1, native.size);
+        }
     }
 
-    Ok(expected_paths)
+    tasks
 }
 
 /// Extract native libraries from JAR files
@@ -283,10 +262,9 @@ pub async fn extract_natives(
         // Check if this is a native library
         if library.natives.is_some() {
             if let Some(downloads) = &library.downloads {
-                let os_name = get_os_name();
-                if let Some(native_key) = library.natives.as_ref().and_then(|n| n.get(os_name)) {
+                if let Some(native_key) = native_classifier(library) {
                     if let Some(classifiers) = &downloads.classifiers {
-                        if let Some(native_artifact) = classifiers.get(native_key) {
+                        if let Some(native_artifact) = classifiers.get(&native_key) {
                             let native_jar = libraries_dir.join(&native_artifact.path);
 
                             if native_jar.exists() {
@@ -437,6 +415,23 @@ mod tests {
         assert!(os == "windows" || os == "linux" || os == "osx");
     }
 
+    #[test]
+    fn test_native_classifier_substitutes_arch() {
+        let library = Library {
+            name: "tv.twitch:twitch-platform:5.16".to_string(),
+            downloads: None,
+            rules: None,
+            natives: Some(HashMap::from([(get_os_name().to_string(), "natives-${arch}".to_string())])),
+            extract: None,
+            url: None,
+            sha1: None,
+            size: None,
+        };
+
+        let expected = if cfg!(target_pointer_width = "64") { "natives-64" } else { "natives-32" };
+        assert_eq!(native_classifier(&library).as_deref(), Some(expected));
+    }
+
     #[test]
     fn test_should_download_library_no_rules() {
         let library = Library {
@@ -445,9 +440,39 @@ mod tests {
             rules: None,
             natives: None,
             extract: None,
+            url: None,
+            sha1: None,
+            size: None,
         };
 
         let features = HashMap::new();
         assert!(should_download_library(&library, &features));
     }
+
+    #[test]
+    fn test_library_download_tasks_includes_maven_libraries() {
+        let maven = Library {
+            name: "net.fabricmc:fabric-loader:0.16.9".to_string(),
+            downloads: None,
+            rules: None,
+            natives: None,
+            extract: None,
+            url: Some("https://maven.fabricmc.net/".to_string()),
+            sha1: Some("abc123".to_string()),
+            size: Some(1024),
+        };
+        let unverified = Library {
+            sha1: None,
+            ..maven.clone()
+        };
+
+        let tasks = library_download_tasks(&[maven.clone(), maven, unverified], Path::new("/libs"), &HashMap::new());
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(
+            tasks[0].url,
+            "https://maven.fabricmc.net/net/fabricmc/fabric-loader/0.16.9/fabric-loader-0.16.9.jar"
+        );
+        assert_eq!(tasks[0].size, 1024);
+        assert_eq!(tasks[0].priority, DownloadPriority::High);
+    }
 }
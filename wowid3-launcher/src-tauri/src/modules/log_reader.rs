@@ -1,7 +1,25 @@
 use std::fs::File;
-use std::io::{BufRead, BufReader, Read, Result as IoResult, Seek, SeekFrom};
-use std::path::PathBuf;
+use std::io::{Read, Result as IoResult, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use notify::{RecursiveMode, Watcher};
 use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+
+/// Event carrying parsed log lines from the watcher
+pub const STRUCTURED_LOG_EVENT: &str = "minecraft-log-structured";
+
+/// Fallback poll interval in case the platform drops file events (network drives, some VMs)
+const WATCH_FALLBACK_POLL: Duration = Duration::from_secs(2);
+
+/// Maximum bytes read per poll; the rest is picked up on the next one
+const MAX_READ_PER_POLL: u64 = 1024 * 1024;
+
+/// Maximum lines per emitted event so a burst doesn't freeze the UI
+const MAX_LINES_PER_EVENT: usize = 500;
 
 #[derive(Debug, Serialize, Clone)]
 pub struct LogResult {
@@ -279,32 +297,295 @@ pub fn read_log_before_offset(game_dir: &str, end_offset: u64, lines: usize) ->
     })
 }
 
-// Deprecated functions kept for compatibility if needed, but we'll remove them from lib.rs
-pub fn read_latest_log(game_dir: &str, lines: usize) -> IoResult<Vec<String>> {
-    let result = read_log_tail(game_dir, lines)?;
-    Ok(result.lines)
+
+/// One parsed log4j line
+///
+/// Lines without a log4j prefix (stack traces, multi-line messages) inherit the
+/// level of the line before them and have no timestamp, thread or logger.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct StructuredLogLine {
+    pub timestamp: Option<String>,
+    pub level: String,
+    pub thread: Option<String>,
+    pub logger: Option<String>,
+    pub message: String,
+    pub raw: String,
 }
 
-pub fn get_new_log_lines(
-    game_dir: &str,
-    known_line_count: usize,
-) -> IoResult<Vec<String>> {
-    // This old function is hard to map to offsets without reading everything.
-    // We'll implement a fallback that reads everything, which is what it did before.
-    let log_path = get_log_path(game_dir);
-    if !log_path.exists() {
-        return Ok(Vec::new());
+/// Payload of the `minecraft-log-structured` event
+#[derive(Debug, Serialize, Clone)]
+pub struct StructuredLogBatch {
+    pub lines: Vec<StructuredLogLine>,
+    /// Byte offset just past the last line in this batch
+    pub end_offset: u64,
+    /// latest.log was rotated or truncated; earlier lines belong to the previous session
+    pub rotated: bool,
+}
+
+/// Parse a log4j line as written by Minecraft and Fabric
+///
+/// Handles `[12:34:56] [Render thread/INFO]: msg`, Fabric's
+/// `[12:34:56] [main/INFO] (FabricLoader) msg` and Forge-style
+/// `[12Jan2026 12:34:56.789] [main/INFO] [net.minecraft.Foo/]: msg`.
+pub fn parse_log_line(line: &str, previous_level: Option<&str>) -> StructuredLogLine {
+    let continuation = || StructuredLogLine {
+        timestamp: None,
+        level: previous_level.unwrap_or("INFO").to_string(),
+        thread: None,
+        logger: None,
+        message: line.to_string(),
+        raw: line.to_string(),
+    };
+
+    let Some(rest) = line.strip_prefix('[') else {
+        return continuation();
+    };
+    let Some((timestamp, rest)) = rest.split_once("] [") else {
+        return continuation();
+    };
+    let Some((thread_level, rest)) = rest.split_once(']') else {
+        return continuation();
+    };
+    // Thread names may contain '/', the level never does
+    let Some((thread, level)) = thread_level.rsplit_once('/') else {
+        return continuation();
+    };
+    if level.is_empty() || !level.chars().all(|c| c.is_ascii_uppercase()) {
+        return continuation();
     }
-    
-    let file = File::open(&log_path)?;
-    let reader = BufReader::new(file);
-    let all_lines: Vec<String> = reader.lines().filter_map(|l| l.ok()).collect();
-    
-    if all_lines.len() > known_line_count {
-        Ok(all_lines[known_line_count..].to_vec())
+
+    let (logger, message) = if let Some(msg) = rest.strip_prefix(": ") {
+        (None, msg)
+    } else if let Some((logger, msg)) = rest.strip_prefix(" (").and_then(|r| r.split_once(')')) {
+        (Some(logger), msg.strip_prefix(' ').unwrap_or(msg))
+    } else if let Some((logger, msg)) = rest.strip_prefix(" [").and_then(|r| r.split_once(']')) {
+        let msg = msg.strip_prefix(':').unwrap_or(msg);
+        (Some(logger.trim_end_matches('/')), msg.strip_prefix(' ').unwrap_or(msg))
     } else {
-        Ok(Vec::new())
+        (None, rest.trim_start_matches(':').trim_start())
+    };
+
+    StructuredLogLine {
+        timestamp: Some(timestamp.to_string()),
+        level: level.to_string(),
+        thread: Some(thread.to_string()),
+        logger: logger.map(|l| l.to_string()),
+        message: message.to_string(),
+        raw: line.to_string(),
+    }
+}
+
+/// Identity of the file behind a path, to notice when latest.log is replaced
+#[cfg(unix)]
+fn file_identity(metadata: &std::fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn file_identity(metadata: &std::fs::Metadata) -> Option<u64> {
+    metadata
+        .created()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as u64)
+}
+
+/// Incremental reader for latest.log that survives rotation and truncation
+///
+/// Only complete lines are returned; a partially written last line is kept
+/// until its newline arrives.
+pub struct LogTailer {
+    path: PathBuf,
+    offset: u64,
+    identity: Option<u64>,
+    pending: Vec<u8>,
+    last_level: Option<String>,
+}
+
+impl LogTailer {
+    /// Start reading `path` at `start_offset` (end of file when `None`)
+    pub fn new(path: PathBuf, start_offset: Option<u64>) -> Self {
+        let metadata = std::fs::metadata(&path).ok();
+        let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+        LogTailer {
+            offset: start_offset.unwrap_or(size).min(size),
+            identity: metadata.as_ref().and_then(file_identity),
+            path,
+            pending: Vec::new(),
+            last_level: None,
+        }
+    }
+
+    /// Read lines appended since the last poll
+    pub fn poll(&mut self) -> IoResult<StructuredLogBatch> {
+        let mut batch = StructuredLogBatch {
+            lines: Vec::new(),
+            end_offset: self.offset,
+            rotated: false,
+        };
+
+        let metadata = match std::fs::metadata(&self.path) {
+            Ok(metadata) => metadata,
+            // Between rotation and Minecraft creating the new file
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(batch),
+            Err(e) => return Err(e),
+        };
+
+        let identity = file_identity(&metadata);
+        if identity != self.identity || metadata.len() < self.offset {
+            self.identity = identity;
+            self.offset = 0;
+            self.pending.clear();
+            self.last_level = None;
+            batch.rotated = true;
+        }
+
+        if metadata.len() > self.offset {
+            let mut file = File::open(&self.path)?;
+            file.seek(SeekFrom::Start(self.offset))?;
+            let mut buf = Vec::new();
+            file.take(MAX_READ_PER_POLL).read_to_end(&mut buf)?;
+            self.offset += buf.len() as u64;
+            self.pending.extend_from_slice(&buf);
+        }
+
+        if let Some(last_newline) = self.pending.iter().rposition(|b| *b == b'\n') {
+            let complete: Vec<u8> = self.pending.drain(..=last_newline).collect();
+            for line in String::from_utf8_lossy(&complete).lines() {
+                let line = line.trim_end_matches('\r');
+                if line.is_empty() {
+                    continue;
+                }
+                let parsed = parse_log_line(line, self.last_level.as_deref());
+                self.last_level = Some(parsed.level.clone());
+                batch.lines.push(parsed);
+            }
+        }
+
+        batch.end_offset = self.offset - self.pending.len() as u64;
+        Ok(batch)
+    }
+}
+
+struct WatchSession {
+    task: JoinHandle<()>,
+    // Dropping the watcher stops file notifications
+    _watcher: notify::RecommendedWatcher,
+}
+
+/// Streams latest.log to the frontend as `minecraft-log-structured` events
+///
+/// Watches the logs directory rather than the file itself so rotation
+/// (latest.log renamed and recreated at game start) is picked up.
+#[derive(Clone, Default)]
+pub struct LogWatcher {
+    session: Arc<Mutex<Option<WatchSession>>>,
+}
+
+impl LogWatcher {
+    pub fn new() -> Self {
+        Self::default()
     }
+
+    /// Start watching `game_dir`, replacing any previous watch
+    pub async fn start(&self, app: AppHandle, game_dir: &str, start_offset: Option<u64>) -> anyhow::Result<()> {
+        self.stop().await;
+
+        let log_path = get_log_path(game_dir);
+        let log_dir = log_path
+            .parent()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| anyhow::anyhow!("Invalid log path {}", log_path.display()))?;
+        std::fs::create_dir_all(&log_dir)?;
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<()>();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if res.is_ok() {
+                let _ = tx.send(());
+            }
+        })?;
+        watcher.watch(&log_dir, RecursiveMode::NonRecursive)?;
+
+        let mut tailer = LogTailer::new(log_path, start_offset);
+        let task = tokio::spawn(async move {
+            let mut fallback = tokio::time::interval(WATCH_FALLBACK_POLL);
+            loop {
+                tokio::select! {
+                    event = rx.recv() => {
+                        if event.is_none() {
+                            break;
+                        }
+                        // Coalesce bursts of write events into one read
+                        while rx.try_recv().is_ok() {}
+                    }
+                    _ = fallback.tick() => {}
+                }
+
+                let polled = tokio::task::spawn_blocking(move || {
+                    let result = tailer.poll();
+                    (tailer, result)
+                })
+                .await;
+                let result = match polled {
+                    Ok((returned, result)) => {
+                        tailer = returned;
+                        result
+                    }
+                    Err(e) => {
+                        eprintln!("[LogWatcher] Tail task failed: {}", e);
+                        break;
+                    }
+                };
+
+                match result {
+                    Ok(batch) => emit_batch(&app, batch),
+                    Err(e) => eprintln!("[LogWatcher] Failed to read latest.log: {}", e),
+                }
+            }
+        });
+
+        *self.session.lock().await = Some(WatchSession {
+            task,
+            _watcher: watcher,
+        });
+        eprintln!("[LogWatcher] Watching {}", log_dir.display());
+        Ok(())
+    }
+
+    pub async fn stop(&self) {
+        if let Some(session) = self.session.lock().await.take() {
+            session.task.abort();
+            eprintln!("[LogWatcher] Stopped");
+        }
+    }
+}
+
+/// Emit a batch, split into chunks of `MAX_LINES_PER_EVENT`
+fn emit_batch(app: &AppHandle, batch: StructuredLogBatch) {
+    if batch.lines.is_empty() && !batch.rotated {
+        return;
+    }
+
+    let chunk_count = batch.lines.len().div_ceil(MAX_LINES_PER_EVENT).max(1);
+    let mut lines = batch.lines.into_iter();
+    for index in 0..chunk_count {
+        let chunk: Vec<_> = lines.by_ref().take(MAX_LINES_PER_EVENT).collect();
+        let _ = app.emit(
+            STRUCTURED_LOG_EVENT,
+            StructuredLogBatch {
+                lines: chunk,
+                end_offset: batch.end_offset,
+                rotated: batch.rotated && index == 0,
+            },
+        );
+    }
+}
+
+// Deprecated functions kept for compatibility if needed, but we'll remove them from lib.rs
+pub fn read_latest_log(game_dir: &str, lines: usize) -> IoResult<Vec<String>> {
+    let result = read_log_tail(game_dir, lines)?;
+    Ok(result.lines)
 }
 
 #[cfg(test)]
@@ -354,4 +635,73 @@ mod tests {
         assert_eq!(result2.lines.len(), 1);
         assert_eq!(result2.lines[0], "Line 3");
     }
+
+    #[test]
+    fn test_parse_log_line_formats() {
+        let vanilla = parse_log_line("[12:34:56] [Render thread/INFO]: Loaded 7 recipes", None);
+        assert_eq!(vanilla.timestamp.as_deref(), Some("12:34:56"));
+        assert_eq!(vanilla.thread.as_deref(), Some("Render thread"));
+        assert_eq!(vanilla.level, "INFO");
+        assert_eq!(vanilla.logger, None);
+        assert_eq!(vanilla.message, "Loaded 7 recipes");
+
+        let fabric = parse_log_line("[12:34:56] [main/WARN] (FabricLoader/Mixin) Reference map not found", None);
+        assert_eq!(fabric.level, "WARN");
+        assert_eq!(fabric.logger.as_deref(), Some("FabricLoader/Mixin"));
+        assert_eq!(fabric.message, "Reference map not found");
+
+        let forge = parse_log_line("[12Jan2026 12:34:56.789] [Worker-Main-2/ERROR] [net.minecraft.Util/]: Oops", None);
+        assert_eq!(forge.thread.as_deref(), Some("Worker-Main-2"));
+        assert_eq!(forge.level, "ERROR");
+        assert_eq!(forge.logger.as_deref(), Some("net.minecraft.Util"));
+        assert_eq!(forge.message, "Oops");
+    }
+
+    #[test]
+    fn test_parse_log_line_continuation() {
+        let line = parse_log_line("\tat net.minecraft.class_310.method_1514(class_310.java:881)", Some("ERROR"));
+        assert_eq!(line.level, "ERROR");
+        assert_eq!(line.timestamp, None);
+        assert!(line.message.contains("class_310"));
+
+        let line = parse_log_line("[not a timestamp", None);
+        assert_eq!(line.level, "INFO");
+        assert_eq!(line.message, "[not a timestamp");
+    }
+
+    #[test]
+    fn test_tailer_partial_lines_and_rotation() {
+        use std::io::Write;
+
+        let temp_dir = TempDir::new().unwrap();
+        let log_dir = temp_dir.path().join("logs");
+        fs::create_dir_all(&log_dir).unwrap();
+        let log_file = log_dir.join("latest.log");
+        fs::write(&log_file, "[10:00:00] [main/INFO]: old\n").unwrap();
+
+        // Starts at the end of the existing file
+        let mut tailer = LogTailer::new(log_file.clone(), None);
+        assert!(tailer.poll().unwrap().lines.is_empty());
+
+        let mut file = fs::OpenOptions::new().append(true).open(&log_file).unwrap();
+        write!(file, "[10:00:01] [main/ERROR]: boom\n\tat a.b.C.d(C.java:1)\n[10:00:02] [ma").unwrap();
+        let batch = tailer.poll().unwrap();
+        assert_eq!(batch.lines.len(), 2);
+        assert_eq!(batch.lines[1].level, "ERROR"); // stack frame inherits the level
+
+        writeln!(file, "in/INFO]: done").unwrap();
+        let batch = tailer.poll().unwrap();
+        assert_eq!(batch.lines.len(), 1);
+        assert_eq!(batch.lines[0].message, "done");
+        assert_eq!(batch.end_offset, fs::metadata(&log_file).unwrap().len());
+
+        // Minecraft moves latest.log away and starts a new one
+        drop(file);
+        fs::rename(&log_file, log_dir.join("2026-01-10-1.log")).unwrap();
+        fs::write(&log_file, "[11:00:00] [main/INFO]: new session\n").unwrap();
+        let batch = tailer.poll().unwrap();
+        assert!(batch.rotated);
+        assert_eq!(batch.lines.len(), 1);
+        assert_eq!(batch.lines[0].message, "new session");
+    }
 }
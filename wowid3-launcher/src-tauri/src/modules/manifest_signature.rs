@@ -0,0 +1,126 @@
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::signature::{UnparsedPublicKey, ED25519};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use super::updater::Manifest;
+
+/// Base64 Ed25519 key the server signs manifests with (MANIFEST_SIGNING_KEY on the server),
+/// set when building releases; builds without it (local development) accept unsigned manifests
+const MANIFEST_PUBLIC_KEY: Option<&str> = option_env!("WOWID3_MANIFEST_PUBLIC_KEY");
+
+/// Response header carrying the signature over the manifest body's SHA-256 digest
+pub const SIGNATURE_HEADER: &str = "x-manifest-signature";
+
+lazy_static::lazy_static! {
+    /// Digests of manifests whose signature checked out this session
+    ///
+    /// Manifests reach the install commands through the UI, so installs look their
+    /// manifest up here instead of trusting whatever JSON they were handed.
+    static ref VERIFIED_MANIFESTS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+/// Check a manifest response body against its signature header
+///
+/// With a public key built in, unsigned or mis-signed manifests are rejected so a
+/// compromised CDN or proxy can't hand out its own file URLs and hashes.
+pub fn verify_manifest_body(body: &[u8], signature: Option<&str>) -> Result<()> {
+    verify_body(body, signature, MANIFEST_PUBLIC_KEY)
+}
+
+fn verify_body(body: &[u8], signature: Option<&str>, public_key: Option<&str>) -> Result<()> {
+    let Some(public_key) = public_key else {
+        return Ok(());
+    };
+    let signature = signature.context("Manifest is not signed; refusing to use it")?;
+
+    let public_key = STANDARD.decode(public_key).context("Invalid embedded manifest key")?;
+    let signature = STANDARD.decode(signature.trim()).context("Invalid manifest signature encoding")?;
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(&Sha256::digest(body), &signature)
+        .map_err(|_| anyhow::anyhow!("Manifest signature is invalid; refusing to use it"))
+}
+
+fn manifest_digest(manifest: &Manifest) -> String {
+    let json = serde_json::to_vec(manifest).unwrap_or_default();
+    format!("{:x}", Sha256::digest(json))
+}
+
+/// Remember a manifest parsed from a verified body
+pub fn mark_verified(manifest: &Manifest) {
+    VERIFIED_MANIFESTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(manifest_digest(manifest));
+}
+
+/// Refuse to install from a manifest that didn't come from a verified response
+pub fn ensure_verified(manifest: &Manifest) -> Result<()> {
+    ensure_verified_with(manifest, MANIFEST_PUBLIC_KEY)
+}
+
+fn ensure_verified_with(manifest: &Manifest, public_key: Option<&str>) -> Result<()> {
+    if public_key.is_none() {
+        return Ok(());
+    }
+    let verified = VERIFIED_MANIFESTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains(&manifest_digest(manifest));
+    if !verified {
+        anyhow::bail!(
+            "Manifest for version {} was not verified against the server's signature",
+            manifest.version
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    #[test]
+    fn test_verify_manifest_body() {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let public_key = STANDARD.encode(key.public_key().as_ref());
+
+        let body = br#"{"version":"1.0.0","files":[]}"#;
+        let signature = STANDARD.encode(key.sign(&Sha256::digest(body)).as_ref());
+        assert!(verify_body(body, Some(&signature), Some(&public_key)).is_ok());
+
+        let tampered = br#"{"version":"1.0.0","files":[{"url":"https://evil.example/a.jar"}]}"#;
+        assert!(verify_body(tampered, Some(&signature), Some(&public_key)).is_err());
+        assert!(verify_body(body, None, Some(&public_key)).is_err());
+
+        // Development builds have no key to check against
+        assert!(verify_body(body, None, None).is_ok());
+    }
+
+    #[test]
+    fn test_only_verified_manifests_install() {
+        let mut manifest: Manifest = serde_json::from_value(serde_json::json!({
+            "version": "9.9.9-signature-test",
+            "minecraft_version": "1.20.1",
+            "fabric_loader": "0.15.0",
+            "files": [{ "path": "mods/a.jar", "url": "https://example.com/a.jar", "sha256": "abc", "size": 1 }],
+            "changelog": ""
+        }))
+        .unwrap();
+        let key = Some("key");
+
+        assert!(ensure_verified_with(&manifest, key).is_err());
+        mark_verified(&manifest);
+        assert!(ensure_verified_with(&manifest, key).is_ok());
+
+        // Any change after verification, such as a swapped URL, is caught
+        manifest.files[0].url = "https://evil.example/a.jar".to_string();
+        assert!(ensure_verified_with(&manifest, key).is_err());
+        assert!(ensure_verified_with(&manifest, None).is_ok());
+    }
+}
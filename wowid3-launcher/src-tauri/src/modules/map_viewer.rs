@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
 
+use super::error::LauncherError;
+
 /// BlueMap availability status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlueMapStatus {
@@ -23,7 +25,7 @@ const BLUEMAP_URL: &str = "https://wowid-launcher.frostdev.io/api/bluemap/webapp
 /// Note: BlueMap is now served via the release server API, which streams
 /// map tiles and data from the Minecraft server's mounted filesystem.
 #[tauri::command]
-pub async fn check_bluemap_available() -> Result<BlueMapStatus, String> {
+pub async fn check_bluemap_available() -> Result<BlueMapStatus, LauncherError> {
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(2))
         .build()
@@ -67,14 +69,14 @@ pub async fn check_bluemap_available() -> Result<BlueMapStatus, String> {
 /// The window displays the interactive 3D map with live player tracking,
 /// custom markers, and full BlueMap functionality.
 #[tauri::command]
-pub async fn open_map_viewer(app: AppHandle) -> Result<(), String> {
+pub async fn open_map_viewer(app: AppHandle) -> Result<(), LauncherError> {
     // First check if BlueMap is available
     let status = check_bluemap_available().await?;
 
     if !status.available {
-        return Err(status.error.unwrap_or_else(|| {
+        return Err(LauncherError::network(status.error.unwrap_or_else(|| {
             "BlueMap is not available. Make sure the Minecraft server with BlueMap is running.".to_string()
-        }));
+        })));
     }
 
     // Parse the URL for Tauri - need to append index.html
@@ -104,14 +106,14 @@ pub async fn open_map_viewer(app: AppHandle) -> Result<(), String> {
 /// Closes the BlueMap webview window. This is useful for cleanup
 /// or when the user wants to manually close the map.
 #[tauri::command]
-pub async fn close_map_viewer(app: AppHandle) -> Result<(), String> {
+pub async fn close_map_viewer(app: AppHandle) -> Result<(), LauncherError> {
     if let Some(window) = app.get_webview_window("bluemap") {
         window
             .close()
             .map_err(|e| format!("Failed to close BlueMap window: {}", e))?;
         Ok(())
     } else {
-        Err("BlueMap window is not open".to_string())
+        Err("BlueMap window is not open".into())
     }
 }
 
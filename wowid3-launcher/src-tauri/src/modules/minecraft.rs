@@ -9,10 +9,12 @@ use tokio::process::{Child, Command};
 use tokio::sync::Mutex;
 use sysinfo::{System, Pid, ProcessesToUpdate};
 
-use super::game_installer::get_installed_version;
+use super::asset_manager;
+use super::game_installer::{client_jar_path, get_installed_version};
 use super::library_manager;
-use super::minecraft_version::{Argument, ArgumentValue};
+use super::minecraft_version::{Argument, ArgumentValue, LOG_CONFIGS_DIR};
 use super::auth::get_access_token_by_session_id;
+use super::jvm_settings::JvmSettings;
 use super::updater::get_installed_version as get_modpack_version;
 
 #[cfg(target_os = "windows")]
@@ -27,10 +29,30 @@ lazy_static::lazy_static! {
 pub struct LaunchConfig {
     pub ram_mb: u32,
     pub java_path: Option<PathBuf>,
+    /// Ignored when `instance_id` is set
+    #[serde(default)]
     pub game_dir: PathBuf,
+    /// Launch an instance with its own game directory and JVM settings
+    #[serde(default)]
+    pub instance_id: Option<String>,
     pub username: String,
     pub uuid: String,
     pub session_id: String, // Session ID for token lookup
+    /// Heap, GC and extra flags; `None` uses `ram_mb` with the default GC preset
+    #[serde(default)]
+    pub jvm: Option<JvmSettings>,
+}
+
+impl LaunchConfig {
+    /// Effective JVM settings, validated
+    pub fn jvm_settings(&self) -> Result<JvmSettings> {
+        let settings = self
+            .jvm
+            .clone()
+            .unwrap_or_else(|| JvmSettings::from_ram(self.ram_mb));
+        settings.validate().context("Invalid JVM settings")?;
+        Ok(settings)
+    }
 }
 
 /// Launch Minecraft with version metadata (new system)
@@ -45,6 +67,9 @@ pub async fn launch_game_with_metadata(
         .await
         .context("Failed to load version metadata")?;
 
+    // Validate JVM settings before doing any work
+    let jvm_settings = config.jvm_settings()?;
+
     let java_path = config
         .java_path
         .unwrap_or_else(|| get_bundled_java_path());
@@ -59,9 +84,7 @@ pub async fn launch_game_with_metadata(
 
     // Build classpath with relative paths (since working directory will be game_dir)
     let libraries_dir = PathBuf::from("libraries");
-    let client_jar = PathBuf::from("versions")
-        .join(&version_meta.id)
-        .join(format!("{}.jar", version_meta.id));
+    let client_jar = client_jar_path(game_dir, &version_meta);
 
     let features = HashMap::new();
     let classpath = library_manager::build_classpath(
@@ -84,41 +107,29 @@ pub async fn launch_game_with_metadata(
     arg_map.insert("game_directory".to_string(), ".".to_string()); // Current directory since cwd = game_dir
     arg_map.insert("assets_root".to_string(), "assets".to_string()); // Relative to game_dir
     arg_map.insert("assets_index_name".to_string(), version_meta.asset_index.id.clone());
+    // Pre-1.7.3 versions read assets by name from a virtual or resources directory
+    let game_assets = asset_manager::game_assets_dir(game_dir, &version_meta.asset_index.id).await;
+    arg_map.insert("game_assets".to_string(), game_assets.to_string_lossy().into_owned());
     arg_map.insert("auth_uuid".to_string(), config.uuid.clone());
+    // Pre-1.7 versions take the session as one argument
+    arg_map.insert("auth_session".to_string(), format!("token:{}:{}", access_token, config.uuid));
     arg_map.insert("auth_access_token".to_string(), access_token);
+    arg_map.insert("user_properties".to_string(), "{}".to_string());
     arg_map.insert("user_type".to_string(), "msa".to_string());
     arg_map.insert("version_type".to_string(), version_meta.version_type.clone());
     arg_map.insert("natives_directory".to_string(), "natives".to_string()); // Relative to game_dir
     arg_map.insert("launcher_name".to_string(), "wowid3-launcher".to_string());
     arg_map.insert("launcher_version".to_string(), "1.0.0".to_string());
     arg_map.insert("classpath".to_string(), classpath.clone());
+    // Used by NeoForge's module path arguments
+    arg_map.insert("library_directory".to_string(), "libraries".to_string());
+    arg_map.insert("classpath_separator".to_string(), get_classpath_separator().to_string());
 
-    // Build JVM arguments with optimized GC settings
-    let mut jvm_args = vec![
-        format!("-Xmx{}M", config.ram_mb),
-        format!("-Xms{}M", config.ram_mb),
-        // G1GC optimizations
-        "-XX:+UseG1GC".to_string(),
-        "-XX:+ParallelRefProcEnabled".to_string(),
-        "-XX:MaxGCPauseMillis=200".to_string(),
-        "-XX:+UnlockExperimentalVMOptions".to_string(),
-        "-XX:+DisableExplicitGC".to_string(),
-        "-XX:G1NewSizePercent=30".to_string(),
-        "-XX:G1MaxNewSizePercent=40".to_string(),
-        "-XX:G1HeapRegionSize=8M".to_string(),
-        "-XX:G1ReservePercent=20".to_string(),
-        "-XX:G1HeapWastePercent=5".to_string(),
-        "-XX:G1MixedGCCountTarget=4".to_string(),
-        "-XX:InitiatingHeapOccupancyPercent=15".to_string(),
-        "-XX:G1MixedGCLiveThresholdPercent=90".to_string(),
-        "-XX:G1RSetUpdatingPauseTimePercent=5".to_string(),
-        "-XX:SurvivorRatio=32".to_string(),
-        "-XX:+PerfDisableSharedMem".to_string(),
-        "-XX:MaxTenuringThreshold=1".to_string(),
-        // Minecraft-specific optimizations
-        "-Dorg.lwjgl.opengl.Display.allowSoftwareOpenGL=true".to_string(),
-        "-Dfml.earlyprogresswindow=false".to_string(),
-    ];
+    // Build JVM arguments: heap, GC preset and user flags from the JVM settings
+    let mut jvm_args = jvm_settings.jvm_args();
+    // Minecraft-specific optimizations
+    jvm_args.push("-Dorg.lwjgl.opengl.Display.allowSoftwareOpenGL=true".to_string());
+    jvm_args.push("-Dfml.earlyprogresswindow=false".to_string());
 
     // Platform-specific optimizations
     #[cfg(target_os = "linux")]
@@ -149,6 +160,10 @@ pub async fn launch_game_with_metadata(
         let game_jar_path = client_jar.to_string_lossy().replace("\\", "/");
         jvm_args.push(format!("-Dfabric.gameJar={}", game_jar_path));
         eprintln!("[Fabric] Added gameJar argument: {}", game_jar_path);
+    } else if version_meta.main_class.contains("quiltmc") {
+        let game_jar_path = client_jar.to_string_lossy().replace("\\", "/");
+        jvm_args.push(format!("-Dloader.gameJarPath={}", game_jar_path));
+        eprintln!("[Quilt] Added gameJarPath argument: {}", game_jar_path);
     }
 
     // Add JVM arguments from version metadata
@@ -163,6 +178,17 @@ pub async fn launch_game_with_metadata(
         jvm_args.push(classpath.clone());
     }
 
+    // Log4Shell mitigation for versions bundling a vulnerable log4j (see game_installer)
+    if let Some(log_config) = version_meta.log4j_config() {
+        let config_path = PathBuf::from(LOG_CONFIGS_DIR).join(&log_config.file.id);
+        if game_dir.join(&config_path).exists() {
+            let path = config_path.to_string_lossy().replace("\\", "/");
+            jvm_args.push(log_config.argument.replace("${path}", &path));
+        } else {
+            eprintln!("[Minecraft] Log4j config {} missing, launching without it", log_config.file.id);
+        }
+    }
+
     // Build game arguments
     let mut game_args = Vec::new();
 
@@ -247,37 +273,111 @@ pub async fn launch_game_with_metadata(
     Ok(child)
 }
 
-/// Stop the Minecraft game gracefully (on Unix) or forcefully (on Windows)
-/// Note: Graceful shutdown via stdin is not possible with this approach
-/// Consider implementing an RPC/IPC mechanism if graceful shutdown is critical
-pub async fn stop_game() -> Result<()> {
+/// Time the game gets to save and exit after a close request, unless configured
+pub const DEFAULT_STOP_GRACE_SECS: u64 = 30;
+
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Phase of a graceful stop, emitted to the UI as `game-stop-progress`
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StopPhase {
+    /// The game was asked to close and is saving its world
+    CloseRequested,
+    /// It didn't exit within the grace period and is being killed
+    Killing,
+    Stopped,
+}
+
+/// Ask the game to close the way a player would
+///
+/// Windows gets a window close (taskkill without /F), which Minecraft handles
+/// like clicking Quit. On Unix SIGTERM runs the client's shutdown hook, which
+/// stops the singleplayer server and saves the world before exiting.
+fn request_close(pid: u32) {
+    #[cfg(unix)]
+    {
+        use std::process::Command;
+        let _ = Command::new("kill")
+            .arg("-15") // SIGTERM
+            .arg(pid.to_string())
+            .output();
+    }
+
+    #[cfg(windows)]
+    {
+        use std::process::Command;
+        let _ = Command::new("taskkill")
+            .args(["/PID", &pid.to_string()])
+            .output();
+    }
+}
+
+fn force_kill(pid: u32) {
+    #[cfg(unix)]
+    {
+        use std::process::Command;
+        let _ = Command::new("kill")
+            .arg("-9") // SIGKILL
+            .arg(pid.to_string())
+            .output();
+    }
+
+    #[cfg(windows)]
+    {
+        use std::process::Command;
+        let _ = Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/F"])
+            .output();
+    }
+}
+
+/// Forget the tracked PID, unless a new game was launched in the meantime
+async fn clear_game_pid(pid: u32) {
     let mut game_pid = GAME_PROCESS_ID.lock().await;
+    if *game_pid == Some(pid) {
+        *game_pid = None;
+    }
+}
 
-    if let Some(pid) = *game_pid {
-        #[cfg(unix)]
-        {
-            // On Unix, send SIGTERM for graceful shutdown
-            use std::process::Command;
-            let _ = Command::new("kill")
-                .arg("-15") // SIGTERM
-                .arg(pid.to_string())
-                .output();
+/// Stop the Minecraft game, giving it time to save first
+///
+/// Requests a normal close, waits up to `grace` for the process to exit and
+/// only then kills it. `on_phase` is called as each phase starts. Returns
+/// whether the game had to be killed.
+pub async fn stop_game<F>(grace: Duration, on_phase: F) -> Result<bool>
+where
+    F: Fn(StopPhase),
+{
+    let Some(pid) = *GAME_PROCESS_ID.lock().await else {
+        return Ok(false);
+    };
+
+    on_phase(StopPhase::CloseRequested);
+    request_close(pid);
+    eprintln!("[Minecraft] Close requested for PID {}; waiting up to {}s", pid, grace.as_secs());
+
+    // The lock isn't held while waiting, so kill_game still works meanwhile
+    let deadline = tokio::time::Instant::now() + grace;
+    loop {
+        if !is_pid_running(pid) {
+            eprintln!("[Minecraft] PID {} exited after close request", pid);
+            clear_game_pid(pid).await;
+            on_phase(StopPhase::Stopped);
+            return Ok(false);
         }
-
-        #[cfg(windows)]
-        {
-            // On Windows, forcefully terminate
-            use std::process::Command;
-            let _ = Command::new("taskkill")
-                .args(&["/PID", &pid.to_string(), "/F"])
-                .output();
+        if tokio::time::Instant::now() >= deadline {
+            break;
         }
-
-        eprintln!("[Minecraft] Stop signal sent to PID: {}", pid);
+        tokio::time::sleep(STOP_POLL_INTERVAL).await;
     }
 
-    *game_pid = None;
-    Ok(())
+    on_phase(StopPhase::Killing);
+    eprintln!("[Minecraft] PID {} still running after {}s; killing it", pid, grace.as_secs());
+    force_kill(pid);
+    clear_game_pid(pid).await;
+    on_phase(StopPhase::Stopped);
+    Ok(true)
 }
 
 /// Kill the Minecraft game forcefully
@@ -285,23 +385,7 @@ pub async fn kill_game() -> Result<()> {
     let mut game_pid = GAME_PROCESS_ID.lock().await;
 
     if let Some(pid) = *game_pid {
-        #[cfg(unix)]
-        {
-            use std::process::Command;
-            let _ = Command::new("kill")
-                .arg("-9") // SIGKILL
-                .arg(pid.to_string())
-                .output();
-        }
-
-        #[cfg(windows)]
-        {
-            use std::process::Command;
-            let _ = Command::new("taskkill")
-                .args(&["/PID", &pid.to_string(), "/F"])
-                .output();
-        }
-
+        force_kill(pid);
         eprintln!("[Minecraft] Kill signal sent to PID: {}", pid);
         *game_pid = None;
     }
@@ -309,21 +393,24 @@ pub async fn kill_game() -> Result<()> {
     Ok(())
 }
 
+fn is_pid_running(pid: u32) -> bool {
+    // Use sysinfo to check if process with this PID still exists
+    // This works on both Windows and Unix, and avoids spawning terminal windows
+    let mut system = System::new();
+    let sysinfo_pid = Pid::from(pid as usize);
+    // sysinfo 0.33+ uses refresh_processes instead of refresh_process
+    // Refresh all processes to check if the specific PID exists
+    system.refresh_processes(ProcessesToUpdate::All, true);
+    system.process(sysinfo_pid).is_some()
+}
+
 /// Check if the game is currently running
 pub async fn is_game_running() -> bool {
     let game_pid = GAME_PROCESS_ID.lock().await;
 
-    if let Some(pid) = *game_pid {
-        // Use sysinfo to check if process with this PID still exists
-        // This works on both Windows and Unix, and avoids spawning terminal windows
-        let mut system = System::new();
-        let sysinfo_pid = Pid::from(pid as usize);
-        // sysinfo 0.33+ uses refresh_processes instead of refresh_process
-        // Refresh all processes to check if the specific PID exists
-        system.refresh_processes(ProcessesToUpdate::All, true);
-        system.process(sysinfo_pid).is_some()
-    } else {
-        false
+    match *game_pid {
+        Some(pid) => is_pid_running(pid),
+        None => false,
     }
 }
 
@@ -367,6 +454,9 @@ fn evaluate_argument_rules(
                 rules: Some(vec![rule.clone()]),
                 natives: None,
                 extract: None,
+                url: None,
+                sha1: None,
+                size: None,
             },
             features,
         ) {
@@ -395,6 +485,8 @@ pub async fn launch_game(config: LaunchConfig) -> Result<Child> {
     let access_token = get_access_token_by_session_id(&config.session_id)
         .context("Failed to retrieve access token from session_id")?;
 
+    let jvm_settings = config.jvm_settings()?;
+
     let java_path = config
         .java_path
         .unwrap_or_else(|| get_bundled_java_path());
@@ -425,14 +517,7 @@ pub async fn launch_game(config: LaunchConfig) -> Result<Child> {
     let mut cmd = Command::new(&java_path);
 
     // JVM arguments
-    cmd.arg(format!("-Xmx{}M", config.ram_mb))
-        .arg(format!("-Xms{}M", config.ram_mb))
-        .arg("-XX:+UseG1GC") // Use G1 garbage collector for better performance
-        .arg("-XX:+UnlockExperimentalVMOptions")
-        .arg("-XX:G1NewSizePercent=20")
-        .arg("-XX:G1ReservePercent=20")
-        .arg("-XX:MaxGCPauseMillis=50")
-        .arg("-XX:G1HeapRegionSize=32M")
+    cmd.args(jvm_settings.jvm_args())
         .arg(format!(
             "-Djava.library.path={}/natives",
             game_dir.display()
@@ -636,9 +721,11 @@ mod tests {
             ram_mb: 4096,
             java_path: Some(PathBuf::from("/usr/bin/java")),
             game_dir: PathBuf::from("/home/user/.minecraft"),
+            instance_id: None,
             username: "TestUser".to_string(),
             uuid: "550e8400-e29b-41d4-a716-446655440000".to_string(),
             session_id: "test_session".to_string(),
+            jvm: None,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -673,4 +760,45 @@ mod tests {
 
         std::fs::remove_dir_all(&temp_dir).ok();
     }
+
+    /// Track `script` as the running game, reaping it like the launcher's exit monitor does
+    #[cfg(unix)]
+    async fn spawn_fake_game(script: &str) -> u32 {
+        let mut child = Command::new("sh").arg("-c").arg(script).spawn().unwrap();
+        let pid = child.id().unwrap();
+        tokio::spawn(async move { child.wait().await });
+        *GAME_PROCESS_ID.lock().await = Some(pid);
+        // Let the script install its signal handling before it's signalled
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        pid
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stop_game_escalates_only_after_grace_period() {
+        let phases = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let record = |phases: &Arc<std::sync::Mutex<Vec<StopPhase>>>| {
+            let phases = phases.clone();
+            move |phase| phases.lock().unwrap().push(phase)
+        };
+
+        // Exits on the close request
+        spawn_fake_game("exec sleep 30").await;
+        let killed = stop_game(Duration::from_secs(5), record(&phases)).await.unwrap();
+        assert!(!killed);
+        assert_eq!(*phases.lock().unwrap(), vec![StopPhase::CloseRequested, StopPhase::Stopped]);
+
+        // Ignores it, like a JVM stuck mid-save
+        phases.lock().unwrap().clear();
+        let pid = spawn_fake_game("trap '' TERM; exec sleep 30").await;
+        let killed = stop_game(Duration::from_secs(1), record(&phases)).await.unwrap();
+        assert!(killed);
+        assert_eq!(
+            *phases.lock().unwrap(),
+            vec![StopPhase::CloseRequested, StopPhase::Killing, StopPhase::Stopped]
+        );
+        assert!(GAME_PROCESS_ID.lock().await.is_none());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!is_pid_running(pid));
+    }
 }
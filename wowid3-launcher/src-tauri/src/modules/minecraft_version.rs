@@ -45,6 +45,65 @@ pub struct VersionMeta {
     pub asset_index: AssetIndex,
     pub assets: String,
     pub java_version: Option<JavaVersion>,
+    /// Version whose client jar this one runs (set for versions resolved through `inheritsFrom`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jar: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logging: Option<Logging>,
+}
+
+/// Log4j configuration Mojang publishes per version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Logging {
+    pub client: Option<LoggingConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggingConfig {
+    /// JVM argument with a `${path}` placeholder for the config file
+    pub argument: String,
+    pub file: LoggingFile,
+    #[serde(rename = "type")]
+    pub config_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggingFile {
+    pub id: String,
+    pub sha1: String,
+    pub size: u64,
+    pub url: String,
+}
+
+/// Where log4j configs are stored, relative to the game directory
+pub const LOG_CONFIGS_DIR: &str = "assets/log_configs";
+
+/// First log4j release without the Log4Shell lookups (CVE-2021-44228 and follow-ups)
+const LOG4J_FIXED_VERSION: [u32; 3] = [2, 17, 0];
+
+impl VersionMeta {
+    /// Mojang's hardened log4j config, if this version bundles a log4j vulnerable to Log4Shell
+    ///
+    /// Versions from 1.18.1 ship a fixed log4j and keep their normal console output.
+    pub fn log4j_config(&self) -> Option<&LoggingConfig> {
+        let client = self.logging.as_ref()?.client.as_ref()?;
+        let vulnerable = self.libraries.iter().any(|library| {
+            library
+                .name
+                .strip_prefix("org.apache.logging.log4j:log4j-core:")
+                .is_some_and(is_vulnerable_log4j)
+        });
+        vulnerable.then_some(client)
+    }
+}
+
+fn is_vulnerable_log4j(version: &str) -> bool {
+    // "2.0-beta9" -> [2, 0], "2.8.1" -> [2, 8, 1]
+    let parts: Vec<u32> = version
+        .split(['.', '-'])
+        .map_while(|part| part.parse().ok())
+        .collect();
+    parts.as_slice() < LOG4J_FIXED_VERSION.as_slice()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,6 +151,13 @@ pub struct Library {
     pub rules: Option<Vec<Rule>>,
     pub natives: Option<HashMap<String, String>>,
     pub extract: Option<Extract>,
+    /// Maven repository base for libraries without `downloads` (Fabric profiles)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -314,4 +380,13 @@ mod tests {
         let meta2 = get_version_meta("1.20.1", cache_dir).await;
         assert!(meta2.is_ok());
     }
+
+    #[test]
+    fn test_is_vulnerable_log4j() {
+        assert!(is_vulnerable_log4j("2.0-beta9"));
+        assert!(is_vulnerable_log4j("2.8.1"));
+        assert!(is_vulnerable_log4j("2.16.0"));
+        assert!(!is_vulnerable_log4j("2.17.0"));
+        assert!(!is_vulnerable_log4j("2.19.0"));
+    }
 }
@@ -1,17 +1,27 @@
 pub mod auth;
 pub mod avatar_proxy;
 pub mod discord;
+pub mod discord_presence;
 pub mod download_manager;
 pub mod minecraft;
 pub mod minecraft_version;
 pub mod library_manager;
 pub mod asset_manager;
 pub mod fabric_installer;
+pub mod quilt_installer;
+pub mod neoforge_installer;
+pub mod mod_loader;
 pub mod game_installer;
 pub mod server;
+pub mod skin;
 pub mod updater;
+pub mod verify_cache;
+pub mod manifest_signature;
 pub mod audio;
+pub mod backup;
 pub mod java_runtime;
+pub mod java_detection;
+pub mod jvm_settings;
 pub mod logger;
 pub mod encrypted_storage;
 pub mod log_reader;
@@ -20,6 +30,27 @@ pub mod launcher_updater;
 pub mod stats;
 pub mod map_viewer;
 pub mod network_test;
+pub mod offline;
+pub mod crash_report;
 pub mod vpn;
+pub mod overlay;
+pub mod settings_bundle;
+pub mod screenshots;
+pub mod instances;
+pub mod instance_import;
+pub mod instance_export;
+pub mod config_merge;
+pub mod optional_mods;
+pub mod install_id;
+pub mod download_token;
+pub mod telemetry;
+pub mod server_events;
+pub mod session_refresh;
+pub mod error;
+pub mod http;
 
 pub use vpn::VpnManager;
+pub mod system_report;
+pub mod storage;
+pub mod mod_conflicts;
+pub mod cms;
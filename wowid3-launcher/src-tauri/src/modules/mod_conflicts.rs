@@ -0,0 +1,341 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+use tokio::fs;
+
+use super::mod_loader::ModLoader;
+use super::updater::load_installed_manifest;
+
+const COMPATIBILITY_URL: &str = "https://wowid-launcher.frostdev.io/api/mods/compatibility";
+/// Launching shouldn't wait on a slow server; the cached list is used instead
+const COMPATIBILITY_FETCH_TIMEOUT_SECS: u64 = 3;
+const COMPATIBILITY_CACHE_FILE: &str = ".cache/mod-compatibility.json";
+/// fabric.mod.json is a few KB; don't inflate anything much bigger
+const MAX_MOD_JSON_BYTES: u64 = 1024 * 1024;
+
+/// A mod known to break the pack when a player adds it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncompatibleMod {
+    pub mod_id: String,
+    pub reason: String,
+}
+
+/// Compatibility list served by the modpack server
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModCompatibilityList {
+    #[serde(default)]
+    pub incompatible: Vec<IncompatibleMod>,
+}
+
+/// The parts of fabric.mod.json the check needs
+#[derive(Debug, Clone, Deserialize)]
+struct FabricModJson {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    provides: Vec<String>,
+}
+
+/// A jar in mods/ and the mod it contains (`None` if it isn't a Fabric mod)
+#[derive(Debug, Clone)]
+struct ModJar {
+    /// Path relative to the game directory, e.g. `mods/sodium.jar`
+    path: String,
+    user_added: bool,
+    info: Option<FabricModJson>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    /// Two jars contain the same mod id; Fabric refuses to start
+    Duplicate,
+    /// A user-added mod is on the server's compatibility list
+    Incompatible,
+    /// A user-added jar has no fabric.mod.json (Forge mod, corrupt download, ...)
+    NotFabric,
+}
+
+/// Problem found in mods/ before launching, shown to the player without blocking the launch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreLaunchWarning {
+    pub kind: WarningKind,
+    pub mod_id: Option<String>,
+    /// Jars involved, relative to the game directory
+    pub files: Vec<String>,
+    pub message: String,
+}
+
+/// Read fabric.mod.json from a mod jar
+fn read_fabric_mod_json(jar: &Path) -> Result<Option<FabricModJson>> {
+    let file = std::fs::File::open(jar).context("Failed to open jar")?;
+    let mut archive = zip::ZipArchive::new(file).context("Failed to read jar")?;
+
+    let entry = match archive.by_name("fabric.mod.json") {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e).context("Failed to read fabric.mod.json"),
+    };
+
+    let mut json = String::new();
+    entry
+        .take(MAX_MOD_JSON_BYTES)
+        .read_to_string(&mut json)
+        .context("Failed to read fabric.mod.json")?;
+
+    parse_fabric_mod_json(&json).map(Some)
+}
+
+/// Parse fabric.mod.json, tolerating the raw newlines/tabs in strings that Fabric accepts
+fn parse_fabric_mod_json(json: &str) -> Result<FabricModJson> {
+    let json: String = json
+        .trim_start_matches('\u{feff}')
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    let mut info: FabricModJson =
+        serde_json::from_str(&json).context("Invalid fabric.mod.json")?;
+    info.id = info.id.to_lowercase();
+    Ok(info)
+}
+
+/// Every jar in mods/, marking the ones that aren't part of the installed modpack
+fn scan_mods(game_dir: &Path, installed: Option<&HashSet<String>>) -> Result<Vec<ModJar>> {
+    let mods_dir = game_dir.join("mods");
+    if !mods_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut jars = Vec::new();
+    for entry in std::fs::read_dir(&mods_dir).context("Failed to read mods directory")? {
+        let path = entry?.path();
+        let is_jar = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("jar"))
+            .unwrap_or(false);
+        if !path.is_file() || !is_jar {
+            continue;
+        }
+
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let relative = format!("mods/{}", name);
+        // Without an installed manifest every jar is checked as if the player added it
+        let user_added = !installed.is_some_and(|paths| paths.contains(&relative));
+
+        let info = match read_fabric_mod_json(&path) {
+            Ok(info) => info,
+            Err(e) => {
+                eprintln!("[ModConflicts] Skipping {}: {}", relative, e);
+                None
+            }
+        };
+
+        jars.push(ModJar {
+            path: relative,
+            user_added,
+            info,
+        });
+    }
+
+    jars.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(jars)
+}
+
+/// Compare the scanned jars against each other and the compatibility list
+fn find_conflicts(jars: &[ModJar], list: &ModCompatibilityList) -> Vec<PreLaunchWarning> {
+    let mut warnings = Vec::new();
+
+    let mut by_id: BTreeMap<&str, Vec<&ModJar>> = BTreeMap::new();
+    for jar in jars {
+        if let Some(info) = &jar.info {
+            by_id.entry(info.id.as_str()).or_default().push(jar);
+        }
+    }
+    for (mod_id, jars) in &by_id {
+        if jars.len() > 1 {
+            let files: Vec<String> = jars.iter().map(|jar| jar.path.clone()).collect();
+            warnings.push(PreLaunchWarning {
+                kind: WarningKind::Duplicate,
+                mod_id: Some(mod_id.to_string()),
+                message: format!(
+                    "'{}' is installed more than once ({}). Remove all but one copy or the game won't start.",
+                    mod_id,
+                    files.join(", ")
+                ),
+                files,
+            });
+        }
+    }
+
+    for jar in jars.iter().filter(|jar| jar.user_added) {
+        let Some(info) = &jar.info else {
+            warnings.push(PreLaunchWarning {
+                kind: WarningKind::NotFabric,
+                mod_id: None,
+                files: vec![jar.path.clone()],
+                message: format!("{} is not a Fabric mod and will not load.", jar.path),
+            });
+            continue;
+        };
+
+        let known = list.incompatible.iter().find(|entry| {
+            let mod_id = entry.mod_id.to_lowercase();
+            info.id == mod_id || info.provides.iter().any(|p| p.to_lowercase() == mod_id)
+        });
+        if let Some(entry) = known {
+            warnings.push(PreLaunchWarning {
+                kind: WarningKind::Incompatible,
+                mod_id: Some(info.id.clone()),
+                files: vec![jar.path.clone()],
+                message: format!(
+                    "{} ({}) is known to break the modpack: {}",
+                    info.name.as_deref().unwrap_or(&info.id),
+                    jar.path,
+                    entry.reason
+                ),
+            });
+        }
+    }
+
+    warnings
+}
+
+/// Fetch the compatibility list, falling back to the last copy fetched when offline
+async fn load_compatibility_list(game_dir: &Path) -> ModCompatibilityList {
+    let cache_path = game_dir.join(COMPATIBILITY_CACHE_FILE);
+
+    match fetch_compatibility_list().await {
+        Ok(list) => {
+            if let Err(e) = save_cached_list(&cache_path, &list).await {
+                eprintln!("[ModConflicts] Failed to cache compatibility list: {}", e);
+            }
+            list
+        }
+        Err(e) => {
+            eprintln!("[ModConflicts] Using cached compatibility list: {}", e);
+            match fs::read_to_string(&cache_path).await {
+                Ok(json) => serde_json::from_str(&json).unwrap_or_default(),
+                Err(_) => ModCompatibilityList::default(),
+            }
+        }
+    }
+}
+
+async fn fetch_compatibility_list() -> Result<ModCompatibilityList> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(COMPATIBILITY_FETCH_TIMEOUT_SECS))
+        .build()?;
+
+    let list = client
+        .get(COMPATIBILITY_URL)
+        .send()
+        .await
+        .context("Failed to fetch compatibility list")?
+        .error_for_status()?
+        .json()
+        .await
+        .context("Failed to parse compatibility list")?;
+
+    Ok(list)
+}
+
+async fn save_cached_list(path: &Path, list: &ModCompatibilityList) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    fs::write(path, serde_json::to_string(list)?).await?;
+    Ok(())
+}
+
+/// Look for duplicate mod ids and known-incompatible mods the player added to mods/
+pub async fn check_mod_conflicts(game_dir: &Path) -> Result<Vec<PreLaunchWarning>> {
+    let manifest = load_installed_manifest(game_dir).await.unwrap_or_else(|e| {
+        eprintln!("[ModConflicts] Ignoring installed manifest: {}", e);
+        None
+    });
+
+    // Only fabric.mod.json is understood; every NeoForge mod would look foreign
+    if manifest.as_ref().is_some_and(|m| m.loader == ModLoader::NeoForge) {
+        return Ok(Vec::new());
+    }
+
+    let installed: Option<HashSet<String>> =
+        manifest.map(|manifest| manifest.files.into_iter().map(|file| file.path).collect());
+
+    let dir = game_dir.to_path_buf();
+    let jars = tokio::task::spawn_blocking(move || scan_mods(&dir, installed.as_ref()))
+        .await
+        .context("Mod scan task failed")??;
+
+    // Nothing the player added means nothing the list could flag
+    let list = if jars.iter().any(|jar| jar.user_added) {
+        load_compatibility_list(game_dir).await
+    } else {
+        ModCompatibilityList::default()
+    };
+
+    Ok(find_conflicts(&jars, &list))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn write_jar(dir: &Path, name: &str, mod_json: Option<&str>) {
+        let file = std::fs::File::create(dir.join("mods").join(name)).unwrap();
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default();
+        if let Some(json) = mod_json {
+            zip.start_file("fabric.mod.json", options).unwrap();
+            zip.write_all(json.as_bytes()).unwrap();
+        }
+        zip.start_file("META-INF/MANIFEST.MF", options).unwrap();
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_parse_fabric_mod_json_with_raw_newlines() {
+        let info = parse_fabric_mod_json(
+            "{\"id\": \"OptiFabric\", \"name\": \"OptiFabric\", \"description\": \"line one\nline two\"}",
+        )
+        .unwrap();
+        assert_eq!(info.id, "optifabric");
+        assert!(info.provides.is_empty());
+    }
+
+    #[test]
+    fn test_find_conflicts_in_user_added_mods() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("mods")).unwrap();
+        write_jar(temp.path(), "sodium-0.5.jar", Some(r#"{"id": "sodium", "name": "Sodium"}"#));
+        write_jar(temp.path(), "sodium-0.6.jar", Some(r#"{"id": "sodium", "name": "Sodium"}"#));
+        write_jar(temp.path(), "optifabric.jar", Some(r#"{"id": "optifabric", "name": "OptiFabric"}"#));
+        write_jar(temp.path(), "forge-mod.jar", None);
+
+        let installed: HashSet<String> = ["mods/sodium-0.5.jar".to_string()].into();
+        let jars = scan_mods(temp.path(), Some(&installed)).unwrap();
+        assert_eq!(jars.len(), 4);
+        assert!(!jars.iter().find(|j| j.path == "mods/sodium-0.5.jar").unwrap().user_added);
+
+        let list = ModCompatibilityList {
+            incompatible: vec![IncompatibleMod {
+                mod_id: "optifabric".to_string(),
+                reason: "Conflicts with Sodium".to_string(),
+            }],
+        };
+        let warnings = find_conflicts(&jars, &list);
+        let kinds: Vec<_> = warnings.iter().map(|w| w.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![WarningKind::Duplicate, WarningKind::NotFabric, WarningKind::Incompatible]
+        );
+        assert_eq!(warnings[0].files, vec!["mods/sodium-0.5.jar", "mods/sodium-0.6.jar"]);
+        assert!(warnings[2].message.contains("Conflicts with Sodium"));
+    }
+}
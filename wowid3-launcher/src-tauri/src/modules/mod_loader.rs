@@ -0,0 +1,263 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::fabric_installer;
+use super::library_manager::{download_file_verified, maven_to_path};
+use super::minecraft_version::{Arguments, Library, VersionMeta};
+use super::neoforge_installer;
+use super::quilt_installer;
+
+/// Mod loader a modpack runs on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModLoader {
+    #[default]
+    Fabric,
+    Quilt,
+    NeoForge,
+}
+
+/// A loader release for one Minecraft version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoaderVersion {
+    pub loader: ModLoader,
+    pub version: String,
+    pub stable: bool,
+}
+
+/// Loader version JSON: a Fabric/Quilt meta profile, or the one the NeoForge installer writes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoaderProfile {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub version_type: String,
+    pub inherits_from: String,
+    pub main_class: String,
+    pub arguments: Option<Arguments>,
+    pub libraries: Vec<Library>,
+}
+
+impl ModLoader {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ModLoader::Fabric => "Fabric",
+            ModLoader::Quilt => "Quilt",
+            ModLoader::NeoForge => "NeoForge",
+        }
+    }
+
+    /// Version id an installation is saved under (`versions/<id>/<id>.json`)
+    ///
+    /// Matches the ids the official installers use, e.g. `fabric-loader-0.17.3-1.20.1`
+    /// or `neoforge-21.1.77`.
+    pub fn version_id(&self, loader_version: &str, game_version: &str) -> String {
+        match self {
+            ModLoader::Fabric => format!("fabric-loader-{}-{}", loader_version, game_version),
+            ModLoader::Quilt => format!("quilt-loader-{}-{}", loader_version, game_version),
+            ModLoader::NeoForge => format!("neoforge-{}", loader_version),
+        }
+    }
+
+    /// Loader releases for a game version, newest first
+    pub async fn list_versions(&self, game_version: &str) -> Result<Vec<LoaderVersion>> {
+        let loader = *self;
+        let versions = match self {
+            ModLoader::Fabric => fabric_installer::get_fabric_loaders(game_version)
+                .await?
+                .into_iter()
+                .map(|l| LoaderVersion { loader, version: l.version, stable: l.stable })
+                .collect(),
+            ModLoader::Quilt => quilt_installer::get_quilt_loaders(game_version)
+                .await?
+                .into_iter()
+                .map(|l| LoaderVersion { loader, stable: quilt_installer::is_stable(&l.version), version: l.version })
+                .collect(),
+            ModLoader::NeoForge => neoforge_installer::get_neoforge_versions(game_version)
+                .await?
+                .into_iter()
+                .map(|version| LoaderVersion { loader, stable: neoforge_installer::is_stable(&version), version })
+                .collect(),
+        };
+        Ok(versions)
+    }
+
+    /// Newest stable loader release for a game version
+    pub async fn latest_version(&self, game_version: &str) -> Result<LoaderVersion> {
+        self.list_versions(game_version)
+            .await?
+            .into_iter()
+            .find(|v| v.stable)
+            .ok_or_else(|| anyhow::anyhow!("No stable {} loader found for {}", self.name(), game_version))
+    }
+
+    /// Install the loader over `vanilla` and return the merged version metadata
+    ///
+    /// Checksummed libraries in the result still need downloading through
+    /// `library_manager::library_download_tasks`; only what that can't handle is fetched here.
+    /// `java_path` is needed for NeoForge, whose installer has to run to patch the game.
+    pub async fn install(
+        &self,
+        vanilla: &VersionMeta,
+        loader_version: &str,
+        game_dir: &Path,
+        java_path: Option<&Path>,
+    ) -> Result<VersionMeta> {
+        let cache_dir = game_dir.join(".cache");
+        let libraries_dir = game_dir.join("libraries");
+
+        let profile = match self {
+            ModLoader::Fabric => {
+                fabric_installer::get_fabric_profile(&vanilla.id, loader_version, &cache_dir).await?
+            }
+            ModLoader::Quilt => {
+                quilt_installer::get_quilt_profile(&vanilla.id, loader_version, &cache_dir).await?
+            }
+            ModLoader::NeoForge => {
+                let java_path = java_path.context("NeoForge needs a Java runtime to run its installer")?;
+                neoforge_installer::install_neoforge(&vanilla.id, loader_version, game_dir, java_path).await?
+            }
+        };
+
+        if profile.inherits_from != vanilla.id {
+            anyhow::bail!(
+                "{} {} is for Minecraft {}, not {}",
+                self.name(),
+                loader_version,
+                profile.inherits_from,
+                vanilla.id
+            );
+        }
+
+        download_unverified_libraries(&profile.libraries, &libraries_dir).await?;
+
+        Ok(merge_with_vanilla(vanilla, &profile))
+    }
+}
+
+/// Fetch a loader profile from a meta API, caching it at `cache_file`
+pub(crate) async fn fetch_profile(url: &str, cache_file: &Path, loader: ModLoader) -> Result<LoaderProfile> {
+    // Try cache first
+    if cache_file.exists() {
+        if let Ok(content) = tokio::fs::read_to_string(cache_file).await {
+            if let Ok(profile) = serde_json::from_str::<LoaderProfile>(&content) {
+                return Ok(profile);
+            }
+        }
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?;
+
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch {} profile", loader.name()))?;
+
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "Failed to fetch {} profile: HTTP {}",
+            loader.name(),
+            response.status()
+        ));
+    }
+
+    let profile: LoaderProfile = response
+        .json()
+        .await
+        .with_context(|| format!("Failed to parse {} profile JSON", loader.name()))?;
+
+    // Cache it
+    tokio::fs::create_dir_all(cache_file.parent().unwrap()).await?;
+    let json = serde_json::to_string_pretty(&profile)?;
+    tokio::fs::write(cache_file, json).await?;
+
+    Ok(profile)
+}
+
+/// Merge a loader profile with vanilla version metadata
+pub fn merge_with_vanilla(vanilla_meta: &VersionMeta, profile: &LoaderProfile) -> VersionMeta {
+    let mut merged = vanilla_meta.clone();
+
+    // Override main class with the loader's
+    merged.main_class = profile.main_class.clone();
+
+    // Add loader libraries (prepend so they take precedence)
+    let mut all_libraries = profile.libraries.clone();
+    all_libraries.extend(vanilla_meta.libraries.clone());
+    merged.libraries = all_libraries;
+
+    // Merge arguments if present
+    if let Some(loader_args) = &profile.arguments {
+        if let Some(vanilla_args) = &mut merged.arguments {
+            // Loader game arguments go after vanilla's (e.g. NeoForge's --fml.* options)
+            vanilla_args.game.extend(loader_args.game.clone());
+
+            // Prepend the loader's JVM arguments
+            let mut all_jvm_args = loader_args.jvm.clone();
+            all_jvm_args.extend(vanilla_args.jvm.clone());
+            vanilla_args.jvm = all_jvm_args;
+        } else {
+            merged.arguments = Some(loader_args.clone());
+        }
+    }
+
+    merged.id = profile.id.clone();
+
+    merged
+}
+
+/// Download Maven libraries the profile lists without a checksum
+///
+/// Checksummed libraries go through `library_manager::library_download_tasks` with
+/// everything else; these can't be verified, so a file already on disk is kept.
+async fn download_unverified_libraries(libraries: &[Library], libraries_dir: &Path) -> Result<()> {
+    let downloads = libraries
+        .iter()
+        .filter(|library| library.downloads.is_none() && (library.sha1.is_none() || library.url.is_none()))
+        .map(|library| async move {
+            let path = maven_to_path(&library.name);
+            let dest = libraries_dir.join(&path);
+            if dest.exists() {
+                return;
+            }
+
+            let base = library.url.as_deref().unwrap_or(fabric_installer::FABRIC_MAVEN_URL);
+            let url = format!("{}/{}", base.trim_end_matches('/'), path);
+            if let Err(e) = download_file_verified(&url, &dest, None).await {
+                eprintln!("[ModLoader] Failed to download library {}: {}", library.name, e);
+            }
+        });
+    futures::future::join_all(downloads).await;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_id() {
+        assert_eq!(ModLoader::Fabric.version_id("0.17.3", "1.20.1"), "fabric-loader-0.17.3-1.20.1");
+        assert_eq!(ModLoader::Quilt.version_id("0.26.4", "1.20.1"), "quilt-loader-0.26.4-1.20.1");
+        assert_eq!(ModLoader::NeoForge.version_id("21.1.77", "1.21.1"), "neoforge-21.1.77");
+    }
+
+    #[test]
+    fn test_loader_defaults_to_fabric() {
+        #[derive(Deserialize)]
+        struct Config {
+            #[serde(default)]
+            loader: ModLoader,
+        }
+
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.loader, ModLoader::Fabric);
+        let config: Config = serde_json::from_str(r#"{"loader": "neoforge"}"#).unwrap();
+        assert_eq!(config.loader, ModLoader::NeoForge);
+    }
+}
@@ -0,0 +1,224 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::process::Command;
+
+use super::library_manager::download_file_verified;
+use super::mod_loader::LoaderProfile;
+
+const NEOFORGE_MAVEN_URL: &str = "https://maven.neoforged.net/releases";
+const NEOFORGE_VERSIONS_URL: &str =
+    "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged/neoforge";
+/// The installer downloads vanilla files and runs the patching processors
+const INSTALLER_TIMEOUT_SECS: u64 = 15 * 60;
+/// Installer output lines kept in the error when it fails
+const INSTALLER_ERROR_LINES: usize = 20;
+
+/// Version list from the NeoForged Maven API
+#[derive(Debug, Deserialize)]
+struct MavenVersions {
+    versions: Vec<String>,
+}
+
+/// NeoForge versions drop the leading `1.` of the Minecraft version: 1.20.4 -> `20.4.x`, 1.21 -> `21.0.x`
+fn version_prefix(game_version: &str) -> Option<String> {
+    let mut parts = game_version.strip_prefix("1.")?.split('.');
+    let minor = parts.next().filter(|p| p.parse::<u32>().is_ok())?;
+    let patch = parts.next().unwrap_or("0");
+    if patch.parse::<u32>().is_err() || parts.next().is_some() {
+        return None;
+    }
+    Some(format!("{}.{}.", minor, patch))
+}
+
+/// Numeric parts of a version for ordering, ignoring any `-beta` suffix
+fn version_key(version: &str) -> Vec<u32> {
+    version
+        .split('-')
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+/// Early builds for a new Minecraft version are published as `-beta`
+pub fn is_stable(version: &str) -> bool {
+    !version.contains("-beta")
+}
+
+/// Get all NeoForge versions for a game version, newest first
+pub async fn get_neoforge_versions(game_version: &str) -> Result<Vec<String>> {
+    let prefix = version_prefix(game_version)
+        .ok_or_else(|| anyhow::anyhow!("NeoForge is not available for Minecraft {}", game_version))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()?;
+
+    let response = client
+        .get(NEOFORGE_VERSIONS_URL)
+        .send()
+        .await
+        .context("Failed to fetch NeoForge versions")?;
+
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "Failed to fetch NeoForge versions: HTTP {}",
+            response.status()
+        ));
+    }
+
+    let all: MavenVersions = response
+        .json()
+        .await
+        .context("Failed to parse NeoForge versions JSON")?;
+
+    Ok(filter_versions(all.versions, &prefix))
+}
+
+fn filter_versions(versions: Vec<String>, prefix: &str) -> Vec<String> {
+    let mut matching: Vec<String> = versions.into_iter().filter(|v| v.starts_with(prefix)).collect();
+    matching.sort_by_key(|v| std::cmp::Reverse(version_key(v)));
+    matching
+}
+
+/// Install NeoForge with its official installer and return the version JSON it generates
+///
+/// The installer patches the client into `libraries/`, so it only runs again when the
+/// cached profile is missing or one of the files it generated has gone.
+pub async fn install_neoforge(
+    game_version: &str,
+    version: &str,
+    game_dir: &Path,
+    java_path: &Path,
+) -> Result<LoaderProfile> {
+    let id = format!("neoforge-{}", version);
+    let cache_dir = game_dir.join(".cache").join("neoforge");
+    let cached_profile = cache_dir.join(format!("{}.json", id));
+
+    if let Some(profile) = load_installed_profile(&cached_profile, game_dir).await {
+        return Ok(profile);
+    }
+
+    eprintln!("[NeoForge] Installing {} for Minecraft {}", version, game_version);
+    let installer = download_installer(version, &cache_dir).await?;
+
+    // The installer refuses to run without a launcher profile file in the target directory
+    let launcher_profiles = game_dir.join("launcher_profiles.json");
+    if !launcher_profiles.exists() {
+        tokio::fs::write(&launcher_profiles, r#"{"profiles":{}}"#)
+            .await
+            .context("Failed to create launcher_profiles.json")?;
+    }
+
+    run_installer(java_path, &installer, game_dir).await?;
+
+    // Keep the installer's JSON: the game installer overwrites versions/<id>/<id>.json with the merged one
+    let generated = game_dir.join("versions").join(&id).join(format!("{}.json", id));
+    let content = tokio::fs::read_to_string(&generated)
+        .await
+        .with_context(|| format!("NeoForge installer did not create {}", generated.display()))?;
+    let profile: LoaderProfile =
+        serde_json::from_str(&content).context("Failed to parse NeoForge version JSON")?;
+    tokio::fs::write(&cached_profile, content).await?;
+
+    Ok(profile)
+}
+
+/// Cached profile, if every file the installer generated is still on disk
+async fn load_installed_profile(cached_profile: &Path, game_dir: &Path) -> Option<LoaderProfile> {
+    let content = tokio::fs::read_to_string(cached_profile).await.ok()?;
+    let profile: LoaderProfile = serde_json::from_str(&content).ok()?;
+
+    let libraries_dir = game_dir.join("libraries");
+    let complete = generated_files(&profile).iter().all(|path| libraries_dir.join(path).exists());
+    complete.then_some(profile)
+}
+
+/// Library paths with no download url, i.e. produced by the installer's processors
+fn generated_files(profile: &LoaderProfile) -> Vec<PathBuf> {
+    profile
+        .libraries
+        .iter()
+        .filter_map(|library| library.downloads.as_ref()?.artifact.as_ref())
+        .filter(|artifact| artifact.url.is_empty())
+        .map(|artifact| PathBuf::from(&artifact.path))
+        .collect()
+}
+
+async fn download_installer(version: &str, cache_dir: &Path) -> Result<PathBuf> {
+    let url = format!(
+        "{}/net/neoforged/neoforge/{}/neoforge-{}-installer.jar",
+        NEOFORGE_MAVEN_URL, version, version
+    );
+
+    let sha1 = reqwest::get(format!("{}.sha1", url))
+        .await
+        .and_then(|response| response.error_for_status())
+        .context("Failed to fetch NeoForge installer checksum")?
+        .text()
+        .await?;
+
+    let dest = cache_dir.join(format!("neoforge-{}-installer.jar", version));
+    download_file_verified(&url, &dest, Some(sha1.trim()))
+        .await
+        .context("Failed to download NeoForge installer")?;
+
+    Ok(dest)
+}
+
+async fn run_installer(java_path: &Path, installer: &Path, game_dir: &Path) -> Result<()> {
+    let mut command = Command::new(java_path);
+    command
+        .arg("-jar")
+        .arg(installer)
+        .arg("--install-client")
+        .arg(game_dir)
+        .current_dir(game_dir)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true);
+
+    #[cfg(windows)]
+    {
+        // CREATE_NO_WINDOW - the installer runs headless
+        command.creation_flags(0x0800_0000);
+    }
+
+    let output = tokio::time::timeout(Duration::from_secs(INSTALLER_TIMEOUT_SECS), command.output())
+        .await
+        .context("NeoForge installer timed out")?
+        .context("Failed to run NeoForge installer")?;
+
+    if !output.status.success() {
+        let log = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = log.lines().collect();
+        let tail = lines[lines.len().saturating_sub(INSTALLER_ERROR_LINES)..].join("\n");
+        anyhow::bail!("NeoForge installer exited with {}:\n{}", output.status, tail);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_prefix() {
+        assert_eq!(version_prefix("1.20.4").as_deref(), Some("20.4."));
+        assert_eq!(version_prefix("1.21").as_deref(), Some("21.0."));
+        assert_eq!(version_prefix("1.21.1").as_deref(), Some("21.1."));
+        assert_eq!(version_prefix("24w14a"), None);
+    }
+
+    #[test]
+    fn test_filter_versions_newest_first() {
+        let versions = ["21.1.9", "20.4.237", "21.1.10", "21.1.1-beta", "21.10.2"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(filter_versions(versions, "21.1."), vec!["21.1.10", "21.1.9", "21.1.1-beta"]);
+        assert!(!is_stable("21.1.1-beta"));
+    }
+}
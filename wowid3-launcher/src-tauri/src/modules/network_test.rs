@@ -4,6 +4,8 @@ use tauri::{AppHandle, Emitter};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
 
+use super::error::LauncherError;
+
 const TEST_SERVER_HOST: &str = "mc.frostdev.io";
 const TEST_SERVER_PORT: u16 = 25567;
 const GAME_SERVER_PORT: u16 = 25565;
@@ -64,7 +66,7 @@ pub struct NetworkTestProgress {
 
 /// Test game server reachability (quick TCP connection test)
 #[tauri::command]
-pub async fn test_game_server_reachability() -> Result<(bool, Option<f64>), String> {
+pub async fn test_game_server_reachability() -> Result<(bool, Option<f64>), LauncherError> {
     let addr = format!("{}:{}", TEST_SERVER_HOST, GAME_SERVER_PORT);
 
     let start = Instant::now();
@@ -86,7 +88,7 @@ pub async fn test_game_server_reachability() -> Result<(bool, Option<f64>), Stri
 pub async fn test_latency_and_jitter(
     app: AppHandle,
     packet_count: u32,
-) -> Result<LatencyTestResult, String> {
+) -> Result<LatencyTestResult, LauncherError> {
     emit_progress(&app, "latency", 0, "Connecting to test server...");
 
     let packet_count = packet_count.min(100); // Max 100 packets
@@ -95,32 +97,32 @@ pub async fn test_latency_and_jitter(
     // Connect to test server
     let mut stream = tokio::time::timeout(DEFAULT_TIMEOUT, TcpStream::connect(&addr))
         .await
-        .map_err(|_| "Connection timeout".to_string())?
-        .map_err(|e| format!("Failed to connect: {}", e))?;
+        .map_err(|_| LauncherError::network("Connection timeout"))?
+        .map_err(|e| LauncherError::network(format!("Failed to connect: {}", e)))?;
 
     // Disable Nagle's algorithm for lower latency
     stream.set_nodelay(true)
-        .map_err(|e| format!("Failed to set TCP_NODELAY: {}", e))?;
+        .map_err(|e| LauncherError::network(format!("Failed to set TCP_NODELAY: {}", e)))?;
 
     emit_progress(&app, "latency", 10, "Starting echo test...");
 
     // Send test type
     stream.write_all(b"ECHO").await
-        .map_err(|e| format!("Failed to send test type: {}", e))?;
+        .map_err(|e| LauncherError::network(format!("Failed to send test type: {}", e)))?;
 
     // Send packet count
     stream.write_all(&packet_count.to_be_bytes()).await
-        .map_err(|e| format!("Failed to send packet count: {}", e))?;
+        .map_err(|e| LauncherError::network(format!("Failed to send packet count: {}", e)))?;
 
     // Wait for ACK
     let mut ack = [0u8; 2];
     tokio::time::timeout(Duration::from_secs(3), stream.read_exact(&mut ack))
         .await
-        .map_err(|_| "ACK timeout".to_string())?
-        .map_err(|e| format!("Failed to read ACK: {}", e))?;
+        .map_err(|_| LauncherError::network("ACK timeout"))?
+        .map_err(|e| LauncherError::network(format!("Failed to read ACK: {}", e)))?;
 
     if &ack != b"OK" {
-        return Err("Invalid ACK from server".to_string());
+        return Err(LauncherError::network("Invalid ACK from server"));
     }
 
     emit_progress(&app, "latency", 20, "Measuring round-trip times...");
@@ -144,28 +146,28 @@ pub async fn test_latency_and_jitter(
         // Send packet size
         let packet_size = test_data.len() as u16;
         stream.write_all(&packet_size.to_be_bytes()).await
-            .map_err(|e| format!("Failed to send packet size: {}", e))?;
+            .map_err(|e| LauncherError::network(format!("Failed to send packet size: {}", e)))?;
 
         // Send packet data
         stream.write_all(test_data).await
-            .map_err(|e| format!("Failed to send packet: {}", e))?;
+            .map_err(|e| LauncherError::network(format!("Failed to send packet: {}", e)))?;
 
         // Flush to ensure immediate send (disable buffering)
         stream.flush().await
-            .map_err(|e| format!("Failed to flush: {}", e))?;
+            .map_err(|e| LauncherError::network(format!("Failed to flush: {}", e)))?;
 
         // Read echoed packet size
         let mut size_bytes = [0u8; 2];
         tokio::time::timeout(Duration::from_secs(3), stream.read_exact(&mut size_bytes))
             .await
-            .map_err(|_| format!("Timeout receiving packet {} echo", i + 1))?
-            .map_err(|e| format!("Failed to read echo size: {}", e))?;
+            .map_err(|_| LauncherError::network(format!("Timeout receiving packet {} echo", i + 1)))?
+            .map_err(|e| LauncherError::network(format!("Failed to read echo size: {}", e)))?;
 
         // Read echoed packet data
         let echo_size = u16::from_be_bytes(size_bytes) as usize;
         let mut echo_data = vec![0u8; echo_size];
         stream.read_exact(&mut echo_data).await
-            .map_err(|e| format!("Failed to read echo data: {}", e))?;
+            .map_err(|e| LauncherError::network(format!("Failed to read echo data: {}", e)))?;
 
         let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
         latencies.push(latency_ms);
@@ -205,7 +207,7 @@ pub async fn test_latency_and_jitter(
 pub async fn test_download_speed(
     app: AppHandle,
     duration_secs: u32,
-) -> Result<SpeedTestResult, String> {
+) -> Result<SpeedTestResult, LauncherError> {
     emit_progress(&app, "download", 0, "Connecting to test server...");
 
     let duration_secs = duration_secs.min(30); // Max 30 seconds
@@ -214,28 +216,28 @@ pub async fn test_download_speed(
     // Connect to test server
     let mut stream = tokio::time::timeout(DEFAULT_TIMEOUT, TcpStream::connect(&addr))
         .await
-        .map_err(|_| "Connection timeout".to_string())?
-        .map_err(|e| format!("Failed to connect: {}", e))?;
+        .map_err(|_| LauncherError::network("Connection timeout"))?
+        .map_err(|e| LauncherError::network(format!("Failed to connect: {}", e)))?;
 
     emit_progress(&app, "download", 10, "Starting download test...");
 
     // Send test type
     stream.write_all(b"DOWN").await
-        .map_err(|e| format!("Failed to send test type: {}", e))?;
+        .map_err(|e| LauncherError::network(format!("Failed to send test type: {}", e)))?;
 
     // Send duration
     stream.write_all(&duration_secs.to_be_bytes()).await
-        .map_err(|e| format!("Failed to send duration: {}", e))?;
+        .map_err(|e| LauncherError::network(format!("Failed to send duration: {}", e)))?;
 
     // Wait for ACK
     let mut ack = [0u8; 2];
     tokio::time::timeout(Duration::from_secs(3), stream.read_exact(&mut ack))
         .await
-        .map_err(|_| "ACK timeout".to_string())?
-        .map_err(|e| format!("Failed to read ACK: {}", e))?;
+        .map_err(|_| LauncherError::network("ACK timeout"))?
+        .map_err(|e| LauncherError::network(format!("Failed to read ACK: {}", e)))?;
 
     if &ack != b"OK" {
-        return Err("Invalid ACK from server".to_string());
+        return Err(LauncherError::network("Invalid ACK from server"));
     }
 
     emit_progress(&app, "download", 20, "Downloading data...");
@@ -262,10 +264,10 @@ pub async fn test_download_speed(
                 }
             }
             Ok(Err(e)) => {
-                return Err(format!("Read error: {}", e));
+                return Err(LauncherError::network(format!("Read error: {}", e)));
             }
             Err(_) => {
-                return Err("Read timeout".to_string());
+                return Err(LauncherError::network("Read timeout"));
             }
         }
     }
@@ -287,7 +289,7 @@ pub async fn test_download_speed(
 pub async fn test_upload_speed(
     app: AppHandle,
     duration_secs: u32,
-) -> Result<SpeedTestResult, String> {
+) -> Result<SpeedTestResult, LauncherError> {
     emit_progress(&app, "upload", 0, "Connecting to test server...");
 
     let duration_secs = duration_secs.min(30); // Max 30 seconds
@@ -296,31 +298,31 @@ pub async fn test_upload_speed(
     // Connect to test server
     let mut stream = tokio::time::timeout(DEFAULT_TIMEOUT, TcpStream::connect(&addr))
         .await
-        .map_err(|_| "Upload: Connection timeout".to_string())?
-        .map_err(|e| format!("Upload: Failed to connect: {}", e))?;
+        .map_err(|_| LauncherError::network("Upload: Connection timeout"))?
+        .map_err(|e| LauncherError::network(format!("Upload: Failed to connect: {}", e)))?;
 
     emit_progress(&app, "upload", 10, "Starting upload test...");
 
     // Send test type
     stream.write_all(b"UPLD").await
-        .map_err(|e| format!("Upload: Failed to send test type: {}", e))?;
+        .map_err(|e| LauncherError::network(format!("Upload: Failed to send test type: {}", e)))?;
 
     // Send duration
     stream.write_all(&duration_secs.to_be_bytes()).await
-        .map_err(|e| format!("Upload: Failed to send duration: {}", e))?;
+        .map_err(|e| LauncherError::network(format!("Upload: Failed to send duration: {}", e)))?;
 
     stream.flush().await
-        .map_err(|e| format!("Upload: Failed to flush: {}", e))?;
+        .map_err(|e| LauncherError::network(format!("Upload: Failed to flush: {}", e)))?;
 
     // Wait for ACK
     let mut ack = [0u8; 2];
     tokio::time::timeout(Duration::from_secs(5), stream.read_exact(&mut ack))
         .await
-        .map_err(|_| "Upload: ACK timeout (server may not be responding)".to_string())?
-        .map_err(|e| format!("Upload: Failed to read ACK: {}", e))?;
+        .map_err(|_| LauncherError::network("Upload: ACK timeout (server may not be responding)"))?
+        .map_err(|e| LauncherError::network(format!("Upload: Failed to read ACK: {}", e)))?;
 
     if &ack != b"OK" {
-        return Err(format!("Upload: Invalid ACK from server: {:?}", ack));
+        return Err(LauncherError::network(format!("Upload: Invalid ACK from server: {:?}", ack)));
     }
 
     emit_progress(&app, "upload", 20, "Uploading data...");
@@ -348,17 +350,17 @@ pub async fn test_upload_speed(
                 }
             }
             Ok(Err(e)) => {
-                return Err(format!("Write error: {}", e));
+                return Err(LauncherError::network(format!("Write error: {}", e)));
             }
             Err(_) => {
-                return Err("Write timeout".to_string());
+                return Err(LauncherError::network("Write timeout"));
             }
         }
     }
 
     // Signal end of upload by shutting down the write side
     stream.shutdown().await
-        .map_err(|e| format!("Upload: Failed to shutdown write: {}", e))?;
+        .map_err(|e| LauncherError::network(format!("Upload: Failed to shutdown write: {}", e)))?;
 
     emit_progress(&app, "upload", 90, "Waiting for server confirmation...");
 
@@ -369,10 +371,10 @@ pub async fn test_upload_speed(
             // Successfully read byte count
         }
         Ok(Err(e)) => {
-            return Err(format!("Upload: Failed to read final count: {}", e));
+            return Err(LauncherError::network(format!("Upload: Failed to read final count: {}", e)));
         }
         Err(_) => {
-            return Err(format!("Upload: Timeout reading final count. Sent {} bytes in {:.1}s", bytes_sent, start.elapsed().as_secs_f64()));
+            return Err(LauncherError::network(format!("Upload: Timeout reading final count. Sent {} bytes in {:.1}s", bytes_sent, start.elapsed().as_secs_f64())));
         }
     }
 
@@ -395,7 +397,7 @@ pub async fn test_upload_speed(
 pub async fn test_packet_loss(
     app: AppHandle,
     packet_count: u32,
-) -> Result<PacketLossResult, String> {
+) -> Result<PacketLossResult, LauncherError> {
     emit_progress(&app, "packet_loss", 0, "Starting packet loss test...");
 
     let packet_count = packet_count.min(100); // Max 100 packets
@@ -405,28 +407,28 @@ pub async fn test_packet_loss(
 
     let mut stream = tokio::time::timeout(DEFAULT_TIMEOUT, TcpStream::connect(&addr))
         .await
-        .map_err(|_| "Connection timeout".to_string())?
-        .map_err(|e| format!("Failed to connect: {}", e))?;
+        .map_err(|_| LauncherError::network("Connection timeout"))?
+        .map_err(|e| LauncherError::network(format!("Failed to connect: {}", e)))?;
 
     emit_progress(&app, "packet_loss", 10, "Connected");
 
     // Send test type
     stream.write_all(b"ECHO").await
-        .map_err(|e| format!("Failed to send test type: {}", e))?;
+        .map_err(|e| LauncherError::network(format!("Failed to send test type: {}", e)))?;
 
     // Send packet count
     stream.write_all(&packet_count.to_be_bytes()).await
-        .map_err(|e| format!("Failed to send packet count: {}", e))?;
+        .map_err(|e| LauncherError::network(format!("Failed to send packet count: {}", e)))?;
 
     // Wait for ACK
     let mut ack = [0u8; 2];
     tokio::time::timeout(Duration::from_secs(3), stream.read_exact(&mut ack))
         .await
-        .map_err(|_| "ACK timeout".to_string())?
-        .map_err(|e| format!("Failed to read ACK: {}", e))?;
+        .map_err(|_| LauncherError::network("ACK timeout"))?
+        .map_err(|e| LauncherError::network(format!("Failed to read ACK: {}", e)))?;
 
     if &ack != b"OK" {
-        return Err("Invalid ACK from server".to_string());
+        return Err(LauncherError::network("Invalid ACK from server"));
     }
 
     emit_progress(&app, "packet_loss", 20, "Sending packets...");
@@ -488,7 +490,7 @@ pub async fn test_packet_loss(
 
 /// Run a full network analysis (all tests)
 #[tauri::command]
-pub async fn run_full_network_analysis(app: AppHandle) -> Result<NetworkTestResult, String> {
+pub async fn run_full_network_analysis(app: AppHandle) -> Result<NetworkTestResult, LauncherError> {
     let timestamp = chrono::Local::now().to_rfc3339();
 
     emit_progress(&app, "full_analysis", 0, "Testing game server reachability...");
@@ -0,0 +1,171 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+use super::game_installer::is_version_installed;
+use super::updater::get_installed_version;
+
+/// Written to the game directory after every successful launch
+pub const LAST_KNOWN_GOOD_FILE: &str = ".wowid3-last-good.json";
+
+/// Configuration of the last launch that started successfully, used for offline launches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LastKnownGood {
+    /// Minecraft/Fabric version id passed to the launcher (e.g. `fabric-loader-0.17.3-1.20.1`)
+    pub version_id: String,
+    /// Modpack version from `.wowid3-version` at launch time
+    pub modpack_version: Option<String>,
+    pub java_path: Option<PathBuf>,
+    pub launched_at: String,
+}
+
+/// Payload of the `updates-skipped` event
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdatesSkippedEvent {
+    /// Human readable reason (the underlying network error)
+    pub reason: String,
+    pub installed_version: Option<String>,
+    /// Whether a last-known-good launch configuration exists for this game directory
+    pub offline_available: bool,
+}
+
+/// Whether an update check failed because the release server is unreachable
+///
+/// Connection failures, timeouts and gateway errors qualify; bad manifests and
+/// client errors do not, since launching offline would hide a real problem.
+pub fn is_network_error(err: &anyhow::Error) -> bool {
+    for cause in err.chain() {
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            if e.is_connect() || e.is_timeout() || e.is_request() {
+                return true;
+            }
+            if let Some(status) = e.status() {
+                return status.is_server_error();
+            }
+        }
+    }
+
+    // check_for_updates reports HTTP failures as plain messages
+    let message = err.to_string();
+    ["HTTP status 502", "HTTP status 503", "HTTP status 504"]
+        .iter()
+        .any(|marker| message.contains(marker))
+}
+
+pub async fn load_last_known_good(game_dir: &Path) -> Result<Option<LastKnownGood>> {
+    let path = game_dir.join(LAST_KNOWN_GOOD_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path)
+        .await
+        .context("Failed to read last-known-good launch configuration")?;
+    let last_good = serde_json::from_str(&content)
+        .context("Failed to parse last-known-good launch configuration")?;
+    Ok(Some(last_good))
+}
+
+pub async fn save_last_known_good(
+    game_dir: &Path,
+    version_id: &str,
+    java_path: Option<PathBuf>,
+) -> Result<()> {
+    let last_good = LastKnownGood {
+        version_id: version_id.to_string(),
+        modpack_version: get_installed_version(&game_dir.to_path_buf()).await?,
+        java_path,
+        launched_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let content = serde_json::to_string_pretty(&last_good)?;
+    fs::write(game_dir.join(LAST_KNOWN_GOOD_FILE), content)
+        .await
+        .context("Failed to save last-known-good launch configuration")?;
+    Ok(())
+}
+
+/// Check that the local install can be launched without the release server
+///
+/// Requires a previous successful launch, a `.wowid3-version` matching that launch,
+/// and the Minecraft version files still on disk.
+pub async fn validate_offline_install(game_dir: &Path) -> Result<LastKnownGood> {
+    let last_good = load_last_known_good(game_dir).await?.ok_or_else(|| {
+        anyhow::anyhow!("The game has never been launched from this directory, so it cannot be started offline")
+    })?;
+
+    let installed = get_installed_version(&game_dir.to_path_buf())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No modpack is installed (missing .wowid3-version)"))?;
+
+    if let Some(expected) = &last_good.modpack_version {
+        if &installed != expected {
+            anyhow::bail!(
+                "Installed modpack version {} does not match the last working version {}",
+                installed,
+                expected
+            );
+        }
+    }
+
+    if !is_version_installed(game_dir, &last_good.version_id).await? {
+        anyhow::bail!("Minecraft version {} is not installed", last_good.version_id);
+    }
+
+    Ok(last_good)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::updater::update_version_file;
+    use tempfile::TempDir;
+
+    async fn install_version_files(game_dir: &Path, version_id: &str) {
+        let version_dir = game_dir.join("versions").join(version_id);
+        fs::create_dir_all(&version_dir).await.unwrap();
+        fs::write(version_dir.join(format!("{}.json", version_id)), "{}").await.unwrap();
+        fs::write(version_dir.join(format!("{}.jar", version_id)), "").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_validate_offline_install() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path();
+
+        // Never launched
+        assert!(validate_offline_install(game_dir).await.is_err());
+
+        update_version_file(&game_dir.to_path_buf(), "1.0.0").await.unwrap();
+        install_version_files(game_dir, "fabric-loader-0.17.3-1.20.1").await;
+        save_last_known_good(game_dir, "fabric-loader-0.17.3-1.20.1", None).await.unwrap();
+
+        let last_good = validate_offline_install(game_dir).await.unwrap();
+        assert_eq!(last_good.version_id, "fabric-loader-0.17.3-1.20.1");
+        assert_eq!(last_good.modpack_version.as_deref(), Some("1.0.0"));
+
+        // A half-finished update leaves a different version on disk
+        update_version_file(&game_dir.to_path_buf(), "1.1.0").await.unwrap();
+        assert!(validate_offline_install(game_dir).await.is_err());
+    }
+
+    #[test]
+    fn test_is_network_error() {
+        assert!(is_network_error(&anyhow::anyhow!(
+            "Manifest request failed with HTTP status 503: Service Unavailable (URL: x)"
+        )));
+        assert!(!is_network_error(&anyhow::anyhow!(
+            "Manifest request failed with HTTP status 404: Not Found (URL: x)"
+        )));
+        assert!(!is_network_error(&anyhow::anyhow!("Failed to parse manifest JSON")));
+    }
+
+    #[tokio::test]
+    async fn test_is_network_error_connection_refused() {
+        let err = crate::modules::updater::check_for_updates("http://localhost:1/api/manifest/latest", None)
+            .await
+            .unwrap_err();
+        assert!(is_network_error(&err));
+    }
+}
@@ -0,0 +1,187 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use tokio::fs;
+
+use super::updater::{Manifest, OptionalGroup};
+
+/// Player's optional group choices, stored per game directory (group id -> enabled)
+pub const SELECTIONS_FILE: &str = ".wowid3-optional-mods.json";
+
+pub type Selections = BTreeMap<String, bool>;
+
+/// Optional group as shown in the launcher, with the player's current choice
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionalGroupState {
+    #[serde(flatten)]
+    pub group: OptionalGroup,
+    pub enabled: bool,
+}
+
+pub async fn load_selections(game_dir: &Path) -> Result<Selections> {
+    let path = game_dir.join(SELECTIONS_FILE);
+    if !path.exists() {
+        return Ok(Selections::new());
+    }
+    let content = fs::read_to_string(&path)
+        .await
+        .context("Failed to read optional mod selections")?;
+    serde_json::from_str(&content).context("Failed to parse optional mod selections")
+}
+
+async fn save_selections(game_dir: &Path, selections: &Selections) -> Result<()> {
+    fs::create_dir_all(game_dir)
+        .await
+        .context("Failed to create game directory")?;
+    let content = serde_json::to_string_pretty(selections)?;
+    fs::write(game_dir.join(SELECTIONS_FILE), content)
+        .await
+        .context("Failed to write optional mod selections")
+}
+
+/// Groups the player hasn't chosen for fall back to the manifest default
+pub fn is_enabled(group: &OptionalGroup, selections: &Selections) -> bool {
+    selections.get(&group.id).copied().unwrap_or(group.default_enabled)
+}
+
+/// List the manifest's optional groups with the player's choices applied
+pub async fn group_states(manifest: &Manifest, game_dir: &Path) -> Result<Vec<OptionalGroupState>> {
+    let selections = load_selections(game_dir).await?;
+    Ok(manifest
+        .optional_groups
+        .iter()
+        .map(|group| OptionalGroupState {
+            enabled: is_enabled(group, &selections),
+            group: group.clone(),
+        })
+        .collect())
+}
+
+/// Enable or disable a group; takes effect on the next install or repair
+pub async fn set_selection(game_dir: &Path, group_id: &str, enabled: bool) -> Result<()> {
+    let mut selections = load_selections(game_dir).await?;
+    selections.insert(group_id.to_string(), enabled);
+    save_selections(game_dir, &selections).await
+}
+
+/// Manifest paths that belong only to disabled groups
+///
+/// A file listed in both an enabled and a disabled group stays installed.
+pub fn disabled_files(manifest: &Manifest, selections: &Selections) -> HashSet<String> {
+    let (enabled, disabled): (Vec<&OptionalGroup>, Vec<&OptionalGroup>) = manifest
+        .optional_groups
+        .iter()
+        .partition(|group| is_enabled(group, selections));
+
+    let keep: HashSet<String> = enabled
+        .iter()
+        .flat_map(|group| group.files.iter().map(|f| f.replace('\\', "/")))
+        .collect();
+
+    disabled
+        .iter()
+        .flat_map(|group| group.files.iter().map(|f| f.replace('\\', "/")))
+        .filter(|path| !keep.contains(path))
+        .collect()
+}
+
+/// Disabled files for the selections saved in `game_dir`
+///
+/// Unreadable selections are logged and treated as "use the defaults" so a corrupt
+/// file never blocks an update.
+pub async fn disabled_files_in(manifest: &Manifest, game_dir: &Path) -> HashSet<String> {
+    if manifest.optional_groups.is_empty() {
+        return HashSet::new();
+    }
+    let selections = load_selections(game_dir).await.unwrap_or_else(|e| {
+        eprintln!("[OptionalMods] {}. Using defaults", e);
+        Selections::new()
+    });
+    disabled_files(manifest, &selections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::mod_loader::ModLoader;
+    use tempfile::TempDir;
+
+    fn group(id: &str, default_enabled: bool, files: &[&str]) -> OptionalGroup {
+        OptionalGroup {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            default_enabled,
+            files: files.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    fn manifest(groups: Vec<OptionalGroup>) -> Manifest {
+        Manifest {
+            version: "1.0.0".to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
+            fabric_loader: "0.15.0".to_string(),
+            files: vec![],
+            changelog: String::new(),
+            ignore_patterns: vec![],
+            optional_groups: groups,
+            ignore_overrides: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_disabled_files_uses_defaults() {
+        let manifest = manifest(vec![
+            group("shaders", false, &["mods/iris.jar", "mods/sodium.jar"]),
+            group("minimap", true, &["mods/xaero.jar"]),
+        ]);
+
+        let disabled = disabled_files(&manifest, &Selections::new());
+        assert_eq!(disabled.len(), 2);
+        assert!(disabled.contains("mods/iris.jar"));
+        assert!(!disabled.contains("mods/xaero.jar"));
+    }
+
+    #[test]
+    fn test_disabled_files_keeps_files_shared_with_enabled_group() {
+        let manifest = manifest(vec![
+            group("shaders", false, &["mods/iris.jar", "mods/sodium.jar"]),
+            group("performance", true, &["mods\\sodium.jar"]),
+        ]);
+
+        let disabled = disabled_files(&manifest, &Selections::new());
+        assert!(disabled.contains("mods/iris.jar"));
+        assert!(!disabled.contains("mods/sodium.jar"));
+    }
+
+    #[tokio::test]
+    async fn test_selections_override_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = manifest(vec![
+            group("shaders", false, &["mods/iris.jar"]),
+            group("minimap", true, &["mods/xaero.jar"]),
+        ]);
+
+        set_selection(temp_dir.path(), "shaders", true).await.unwrap();
+        set_selection(temp_dir.path(), "minimap", false).await.unwrap();
+
+        let disabled = disabled_files_in(&manifest, temp_dir.path()).await;
+        assert_eq!(disabled, HashSet::from(["mods/xaero.jar".to_string()]));
+
+        let states = group_states(&manifest, temp_dir.path()).await.unwrap();
+        assert!(states.iter().find(|s| s.group.id == "shaders").unwrap().enabled);
+        assert!(!states.iter().find(|s| s.group.id == "minimap").unwrap().enabled);
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_selections_fall_back_to_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(SELECTIONS_FILE), "not json").unwrap();
+        let manifest = manifest(vec![group("shaders", false, &["mods/iris.jar"])]);
+
+        let disabled = disabled_files_in(&manifest, temp_dir.path()).await;
+        assert!(disabled.contains("mods/iris.jar"));
+    }
+}
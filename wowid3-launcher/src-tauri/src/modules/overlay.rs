@@ -0,0 +1,255 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::fs;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+const OVERLAY_DIR: &str = "overlay";
+const SESSION_TICK_SECS: u64 = 1;
+
+/// Overlay writer settings (mirrors the toggle in the settings screen)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OverlaySettings {
+    pub enabled: bool,
+    /// Output directory; defaults to <data dir>/overlay when not set
+    #[serde(default)]
+    pub directory: Option<PathBuf>,
+}
+
+/// Current values exposed to OBS text sources
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OverlayState {
+    pub pack_version: Option<String>,
+    pub players_online: Option<u32>,
+    pub players_max: Option<u32>,
+    /// Unix timestamp (seconds) when the current game session started
+    pub session_started_at: Option<i64>,
+    pub session_seconds: u64,
+}
+
+struct OverlayInner {
+    settings: OverlaySettings,
+    state: OverlayState,
+    ticker: Option<JoinHandle<()>>,
+}
+
+/// Maintains small text/JSON files that streaming software can read directly
+///
+/// Files written to the overlay directory:
+/// - `pack_version.txt`: installed modpack version
+/// - `players.txt`: online/max player count (e.g. "3/20")
+/// - `session_time.txt`: current session duration (HH:MM:SS)
+/// - `overlay.json`: all of the above as JSON
+#[derive(Clone)]
+pub struct OverlayWriter {
+    inner: Arc<Mutex<OverlayInner>>,
+}
+
+impl OverlayWriter {
+    pub fn new() -> Self {
+        OverlayWriter {
+            inner: Arc::new(Mutex::new(OverlayInner {
+                settings: OverlaySettings::default(),
+                state: OverlayState::default(),
+                ticker: None,
+            })),
+        }
+    }
+
+    /// Apply new settings and rewrite all files if enabled
+    pub async fn configure(&self, settings: OverlaySettings) -> Result<PathBuf> {
+        let mut inner = self.inner.lock().await;
+        inner.settings = settings;
+
+        let dir = resolve_directory(&inner.settings)?;
+
+        if inner.settings.enabled {
+            write_files(&dir, &inner.state).await?;
+            if inner.state.session_started_at.is_some() && inner.ticker.is_none() {
+                inner.ticker = Some(self.spawn_ticker());
+            }
+        } else if let Some(ticker) = inner.ticker.take() {
+            ticker.abort();
+        }
+
+        Ok(dir)
+    }
+
+    pub async fn settings(&self) -> OverlaySettings {
+        self.inner.lock().await.settings.clone()
+    }
+
+    pub async fn state(&self) -> OverlayState {
+        self.inner.lock().await.state.clone()
+    }
+
+    /// Update the modpack version shown in the overlay
+    pub async fn set_pack_version(&self, version: Option<String>) -> Result<()> {
+        let mut inner = self.inner.lock().await;
+        inner.state.pack_version = version;
+        flush(&inner).await
+    }
+
+    /// Update the server player count shown in the overlay
+    pub async fn set_player_count(&self, online: Option<u32>, max: Option<u32>) -> Result<()> {
+        let mut inner = self.inner.lock().await;
+        inner.state.players_online = online;
+        inner.state.players_max = max;
+        flush(&inner).await
+    }
+
+    /// Mark the start of a game session and begin ticking the session timer
+    pub async fn start_session(&self) -> Result<()> {
+        let mut inner = self.inner.lock().await;
+        inner.state.session_started_at = Some(chrono::Utc::now().timestamp());
+        inner.state.session_seconds = 0;
+
+        if inner.settings.enabled && inner.ticker.is_none() {
+            inner.ticker = Some(self.spawn_ticker());
+        }
+
+        flush(&inner).await
+    }
+
+    /// Mark the end of a game session; the last duration stays visible
+    pub async fn end_session(&self) -> Result<()> {
+        let mut inner = self.inner.lock().await;
+        refresh_session_seconds(&mut inner.state);
+        inner.state.session_started_at = None;
+
+        if let Some(ticker) = inner.ticker.take() {
+            ticker.abort();
+        }
+
+        flush(&inner).await
+    }
+
+    fn spawn_ticker(&self) -> JoinHandle<()> {
+        let inner = self.inner.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(SESSION_TICK_SECS));
+            loop {
+                interval.tick().await;
+                let mut guard = inner.lock().await;
+                if guard.state.session_started_at.is_none() || !guard.settings.enabled {
+                    guard.ticker = None;
+                    break;
+                }
+                refresh_session_seconds(&mut guard.state);
+                if let Err(e) = flush_session_time(&guard).await {
+                    eprintln!("[Overlay] Failed to update session time: {}", e);
+                }
+            }
+        })
+    }
+}
+
+impl Default for OverlayWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn resolve_directory(settings: &OverlaySettings) -> Result<PathBuf> {
+    match &settings.directory {
+        Some(dir) if !dir.as_os_str().is_empty() => Ok(dir.clone()),
+        _ => Ok(super::paths::get_persistent_data_dir()?.join(OVERLAY_DIR)),
+    }
+}
+
+fn refresh_session_seconds(state: &mut OverlayState) {
+    if let Some(started) = state.session_started_at {
+        let now = chrono::Utc::now().timestamp();
+        state.session_seconds = (now - started).max(0) as u64;
+    }
+}
+
+/// Format seconds as HH:MM:SS
+pub fn format_duration(total_seconds: u64) -> String {
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+    format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+}
+
+fn format_players(state: &OverlayState) -> String {
+    match (state.players_online, state.players_max) {
+        (Some(online), Some(max)) => format!("{}/{}", online, max),
+        (Some(online), None) => online.to_string(),
+        _ => String::new(),
+    }
+}
+
+async fn flush(inner: &OverlayInner) -> Result<()> {
+    if !inner.settings.enabled {
+        return Ok(());
+    }
+    let dir = resolve_directory(&inner.settings)?;
+    write_files(&dir, &inner.state).await
+}
+
+async fn flush_session_time(inner: &OverlayInner) -> Result<()> {
+    let dir = resolve_directory(&inner.settings)?;
+    write_text(&dir.join("session_time.txt"), &format_duration(inner.state.session_seconds)).await?;
+    write_text(&dir.join("overlay.json"), &serde_json::to_string_pretty(&inner.state)?).await
+}
+
+async fn write_files(dir: &PathBuf, state: &OverlayState) -> Result<()> {
+    fs::create_dir_all(dir)
+        .await
+        .context("Failed to create overlay directory")?;
+
+    write_text(&dir.join("pack_version.txt"), state.pack_version.as_deref().unwrap_or_default()).await?;
+    write_text(&dir.join("players.txt"), &format_players(state)).await?;
+    write_text(&dir.join("session_time.txt"), &format_duration(state.session_seconds)).await?;
+    write_text(&dir.join("overlay.json"), &serde_json::to_string_pretty(state)?).await?;
+
+    Ok(())
+}
+
+/// Write via temp file + rename so OBS never reads a half-written file
+async fn write_text(path: &PathBuf, content: &str) -> Result<()> {
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, content)
+        .await
+        .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+    fs::rename(&temp_path, path)
+        .await
+        .with_context(|| format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "00:00:00");
+        assert_eq!(format_duration(3725), "01:02:05");
+    }
+
+    #[tokio::test]
+    async fn test_writes_files_when_enabled() {
+        let temp = tempfile::tempdir().unwrap();
+        let writer = OverlayWriter::new();
+        writer
+            .configure(OverlaySettings {
+                enabled: true,
+                directory: Some(temp.path().to_path_buf()),
+            })
+            .await
+            .unwrap();
+
+        writer.set_pack_version(Some("1.2.3".to_string())).await.unwrap();
+        writer.set_player_count(Some(3), Some(20)).await.unwrap();
+
+        let version = std::fs::read_to_string(temp.path().join("pack_version.txt")).unwrap();
+        let players = std::fs::read_to_string(temp.path().join("players.txt")).unwrap();
+        assert_eq!(version, "1.2.3");
+        assert_eq!(players, "3/20");
+    }
+}
@@ -0,0 +1,86 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::mod_loader::{fetch_profile, LoaderProfile, ModLoader};
+
+const QUILT_META_URL: &str = "https://meta.quiltmc.org";
+
+/// Quilt loader version information (top-level response from API)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuiltLoaderResponse {
+    pub loader: QuiltLoader,
+}
+
+/// Quilt loader details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuiltLoader {
+    #[serde(default)]
+    pub separator: Option<String>,
+    pub build: i32,
+    pub maven: String,
+    pub version: String,
+}
+
+/// Quilt meta has no stable flag; pre-releases carry a suffix like `-beta.3`
+pub fn is_stable(version: &str) -> bool {
+    !version.contains('-')
+}
+
+/// Get all available Quilt loader versions for a game version, newest first
+pub async fn get_quilt_loaders(game_version: &str) -> Result<Vec<QuiltLoader>> {
+    let url = format!("{}/v3/versions/loader/{}", QUILT_META_URL, game_version);
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?;
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to fetch Quilt loader versions")?;
+
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "Failed to fetch Quilt loaders: HTTP {}",
+            response.status()
+        ));
+    }
+
+    let loader_responses: Vec<QuiltLoaderResponse> = response
+        .json()
+        .await
+        .context("Failed to parse Quilt loader JSON")?;
+
+    Ok(loader_responses.into_iter().map(|r| r.loader).collect())
+}
+
+/// Get Quilt profile (same format as Fabric's)
+pub async fn get_quilt_profile(
+    game_version: &str,
+    loader_version: &str,
+    cache_dir: &Path,
+) -> Result<LoaderProfile> {
+    let cache_file = cache_dir
+        .join("quilt")
+        .join(format!("{}-{}.json", game_version, loader_version));
+
+    let url = format!(
+        "{}/v3/versions/loader/{}/{}/profile/json",
+        QUILT_META_URL, game_version, loader_version
+    );
+
+    fetch_profile(&url, &cache_file, ModLoader::Quilt).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_stable() {
+        assert!(is_stable("0.26.4"));
+        assert!(!is_stable("0.27.0-beta.1"));
+    }
+}
@@ -0,0 +1,318 @@
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tokio::fs;
+
+/// Minecraft writes screenshots (F2) here; it is blacklisted from modpack sync
+pub const SCREENSHOTS_DIR: &str = "screenshots";
+
+/// Thumbnails fit in a box of this size, keeping the aspect ratio
+const THUMBNAIL_SIZE: u32 = 320;
+
+/// A screenshot in the game directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Screenshot {
+    pub filename: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    /// RFC 3339 modification time (when Minecraft saved it)
+    pub taken_at: String,
+    /// `data:image/jpeg;base64,...` thumbnail, if one could be generated
+    pub thumbnail: Option<String>,
+}
+
+/// Server response for an uploaded screenshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenshotUploadResult {
+    pub id: i64,
+    pub filename: String,
+    /// Path on the server, e.g. `/api/screenshots/<uuid>/<file>`
+    pub url: String,
+}
+
+pub fn screenshots_dir(game_dir: &Path) -> PathBuf {
+    game_dir.join(SCREENSHOTS_DIR)
+}
+
+/// Default thumbnail cache (`<data dir>/cache/screenshot_thumbnails`)
+pub fn thumbnail_cache_dir() -> Result<PathBuf> {
+    Ok(super::paths::get_persistent_data_dir()?
+        .join("cache")
+        .join("screenshot_thumbnails"))
+}
+
+fn is_image_file(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    lower.ends_with(".png") || lower.ends_with(".jpg") || lower.ends_with(".jpeg")
+}
+
+/// Reject anything that isn't a plain image file name inside the screenshots folder
+fn validate_filename(filename: &str) -> Result<()> {
+    if filename.is_empty()
+        || filename.contains(['/', '\\'])
+        || filename.contains("..")
+        || !is_image_file(filename)
+    {
+        anyhow::bail!("Invalid screenshot name: '{}'", filename);
+    }
+    Ok(())
+}
+
+/// Cache key for a screenshot's thumbnail; changes when the file is replaced
+fn thumbnail_key(filename: &str, size: u64, modified: SystemTime) -> String {
+    let modified = modified
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_millis();
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{}:{}:{}", filename, size, modified).as_bytes());
+    format!("{:x}.jpg", hasher.finalize())
+}
+
+fn generate_thumbnail(source: &Path, dest: &Path) -> Result<()> {
+    let image = image::open(source)
+        .with_context(|| format!("Failed to decode {}", source.display()))?;
+    // JPEG has no alpha channel
+    let thumbnail = image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgb8();
+    thumbnail
+        .save_with_format(dest, image::ImageFormat::Jpeg)
+        .with_context(|| format!("Failed to write thumbnail {}", dest.display()))?;
+    Ok(())
+}
+
+/// Load the cached thumbnail, generating it on first use
+async fn load_thumbnail(source: &Path, cache_dir: &Path, key: &str) -> Result<String> {
+    let cached = cache_dir.join(key);
+    if !cached.exists() {
+        fs::create_dir_all(cache_dir).await?;
+        let source = source.to_path_buf();
+        let dest = cached.clone();
+        // Decoding a 4K PNG takes a while; keep it off the async workers
+        tokio::task::spawn_blocking(move || generate_thumbnail(&source, &dest)).await??;
+    }
+
+    let bytes = fs::read(&cached).await?;
+    Ok(format!("data:image/jpeg;base64,{}", STANDARD.encode(bytes)))
+}
+
+/// List screenshots in the game directory, newest first, with thumbnails
+pub async fn list_screenshots(game_dir: &Path, cache_dir: &Path) -> Result<Vec<Screenshot>> {
+    let dir = screenshots_dir(game_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = fs::read_dir(&dir)
+        .await
+        .context("Failed to read screenshots directory")?;
+
+    let mut found = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let filename = entry.file_name().to_string_lossy().to_string();
+        if !is_image_file(&filename) {
+            continue;
+        }
+        let metadata = entry.metadata().await?;
+        if !metadata.is_file() {
+            continue;
+        }
+        let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        found.push((filename, entry.path(), metadata.len(), modified));
+    }
+    found.sort_by(|a, b| b.3.cmp(&a.3));
+
+    let mut screenshots = Vec::with_capacity(found.len());
+    for (filename, path, size_bytes, modified) in found {
+        let key = thumbnail_key(&filename, size_bytes, modified);
+        let thumbnail = match load_thumbnail(&path, cache_dir, &key).await {
+            Ok(thumbnail) => Some(thumbnail),
+            Err(e) => {
+                eprintln!("[Screenshots] No thumbnail for {}: {}", filename, e);
+                None
+            }
+        };
+
+        screenshots.push(Screenshot {
+            filename,
+            path,
+            size_bytes,
+            taken_at: chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339(),
+            thumbnail,
+        });
+    }
+
+    Ok(screenshots)
+}
+
+/// Delete a screenshot and its cached thumbnail
+pub async fn delete_screenshot(game_dir: &Path, filename: &str, cache_dir: &Path) -> Result<()> {
+    validate_filename(filename)?;
+
+    let path = screenshots_dir(game_dir).join(filename);
+    let metadata = fs::metadata(&path)
+        .await
+        .with_context(|| format!("Screenshot {} not found", filename))?;
+
+    if let Ok(modified) = metadata.modified() {
+        let thumbnail = cache_dir.join(thumbnail_key(filename, metadata.len(), modified));
+        let _ = fs::remove_file(thumbnail).await;
+    }
+
+    fs::remove_file(&path)
+        .await
+        .with_context(|| format!("Failed to delete {}", filename))?;
+    Ok(())
+}
+
+/// Open the screenshots folder in the system file manager, creating it if needed
+pub async fn open_screenshot_folder(game_dir: &Path) -> Result<()> {
+    let dir = screenshots_dir(game_dir);
+    fs::create_dir_all(&dir).await?;
+    open::that(&dir).with_context(|| format!("Failed to open {}", dir.display()))?;
+    Ok(())
+}
+
+/// Upload a screenshot to the release server under the player's UUID, proving who
+/// they are with their Minecraft access token
+pub async fn upload_screenshot(
+    server_url: &str,
+    uuid: &str,
+    access_token: &str,
+    game_dir: &Path,
+    filename: &str,
+) -> Result<ScreenshotUploadResult> {
+    validate_filename(filename)?;
+
+    let data = fs::read(screenshots_dir(game_dir).join(filename))
+        .await
+        .with_context(|| format!("Failed to read screenshot {}", filename))?;
+
+    let content_type = if filename.to_ascii_lowercase().ends_with(".png") {
+        "image/png"
+    } else {
+        "image/jpeg"
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(120))
+        .build()?;
+
+    let url = format!("{}/api/screenshots/{}", server_url.trim_end_matches('/'), uuid);
+    let response = client
+        .post(&url)
+        .query(&[("filename", filename)])
+        .bearer_auth(access_token)
+        .header(reqwest::header::CONTENT_TYPE, content_type)
+        .body(data)
+        .send()
+        .await
+        .context("Failed to upload screenshot")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("Screenshot upload failed with HTTP status {}: {}", status, body);
+    }
+
+    response
+        .json::<ScreenshotUploadResult>()
+        .await
+        .context("Failed to parse screenshot upload response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn write_png(path: &Path, width: u32, height: u32) {
+        let image = image::RgbImage::from_pixel(width, height, image::Rgb([40, 120, 200]));
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || image.save(path).unwrap()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_screenshots_with_thumbnails() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path().join("game");
+        let cache_dir = temp_dir.path().join("thumbs");
+
+        // No folder yet
+        assert!(list_screenshots(&game_dir, &cache_dir).await.unwrap().is_empty());
+
+        let dir = screenshots_dir(&game_dir);
+        fs::create_dir_all(&dir).await.unwrap();
+        write_png(&dir.join("2026-01-10_20.14.55.png"), 1280, 720).await;
+        fs::write(dir.join("notes.txt"), "ignored").await.unwrap();
+        fs::write(dir.join("broken.png"), "not a png").await.unwrap();
+
+        let screenshots = list_screenshots(&game_dir, &cache_dir).await.unwrap();
+        assert_eq!(screenshots.len(), 2);
+
+        let good = screenshots.iter().find(|s| s.filename == "2026-01-10_20.14.55.png").unwrap();
+        assert!(good.thumbnail.as_deref().unwrap().starts_with("data:image/jpeg;base64,"));
+
+        let broken = screenshots.iter().find(|s| s.filename == "broken.png").unwrap();
+        assert!(broken.thumbnail.is_none());
+
+        let thumb = image::open(std::fs::read_dir(&cache_dir).unwrap().next().unwrap().unwrap().path()).unwrap();
+        assert_eq!((thumb.width(), thumb.height()), (320, 180));
+    }
+
+    #[tokio::test]
+    async fn test_delete_screenshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path();
+        let cache_dir = temp_dir.path().join("thumbs");
+        let dir = screenshots_dir(game_dir);
+        fs::create_dir_all(&dir).await.unwrap();
+        write_png(&dir.join("shot.png"), 64, 64).await;
+
+        list_screenshots(game_dir, &cache_dir).await.unwrap();
+        delete_screenshot(game_dir, "shot.png", &cache_dir).await.unwrap();
+
+        assert!(!dir.join("shot.png").exists());
+        assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 0);
+
+        assert!(delete_screenshot(game_dir, "../options.txt", &cache_dir).await.is_err());
+        assert!(delete_screenshot(game_dir, "missing.png", &cache_dir).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_upload_screenshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = screenshots_dir(temp_dir.path());
+        fs::create_dir_all(&dir).await.unwrap();
+        write_png(&dir.join("shot.png"), 8, 8).await;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/screenshots/adca5752c67a4f0aae7444d9f369f6f8"))
+            .and(query_param("filename", "shot.png"))
+            .and(header("authorization", "Bearer minecraft-token"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": 3,
+                "filename": "0123456789abcdef.png",
+                "url": "/api/screenshots/adca5752-c67a-4f0a-ae74-44d9f369f6f8/0123456789abcdef.png"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let result = upload_screenshot(
+            &mock_server.uri(),
+            "adca5752c67a4f0aae7444d9f369f6f8",
+            "minecraft-token",
+            temp_dir.path(),
+            "shot.png",
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.id, 3);
+        assert_eq!(result.filename, "0123456789abcdef.png");
+    }
+}
@@ -1,12 +1,42 @@
 use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io::{Read, Write};
-use std::net::{TcpStream, ToSocketAddrs};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
 use std::time::Duration;
 
-#[cfg(target_os = "windows")]
+use super::http::{self, RetryPolicy, SendRetrying};
 use super::vpn::VpnManager;
 
+/// Limit for each connection attempt, so one dead address can't hold up the others
+const CONNECT_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Head start each address gets before the next is tried alongside it (RFC 8305's default)
+const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AddressFamily {
+    V4,
+    V6,
+}
+
+impl AddressFamily {
+    fn of(address: &SocketAddr) -> Self {
+        if address.is_ipv6() {
+            AddressFamily::V6
+        } else {
+            AddressFamily::V4
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Family each host was last reached over, tried first on its next ping
+    static ref PREFERRED_FAMILY: Mutex<HashMap<String, AddressFamily>> = Mutex::new(HashMap::new());
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerInfo {
     pub name: String,
@@ -52,11 +82,11 @@ pub struct TrackerState {
 /// Fetch detailed server status from the tracker API
 pub async fn fetch_tracker_status(base_url: &str) -> Result<TrackerState> {
     let url = format!("{}/api/tracker/status", base_url.trim_end_matches('/'));
-    let client = reqwest::Client::builder()
+    let response = http::client()
+        .get(&url)
         .timeout(Duration::from_secs(5))
-        .build()?;
-
-    let response = client.get(&url).send().await?;
+        .send_retrying(RetryPolicy::default())
+        .await?;
     
     if !response.status().is_success() {
         anyhow::bail!("Failed to fetch tracker status: {}", response.status());
@@ -66,6 +96,33 @@ pub async fn fetch_tracker_status(base_url: &str) -> Result<TrackerState> {
     Ok(state)
 }
 
+/// Send a chat message through the tracker, to be broadcast in-game
+///
+/// The server checks `access_token` against the Minecraft profile API, so the
+/// message can only be sent as the signed-in player.
+pub async fn send_chat_message(base_url: &str, uuid: &str, access_token: &str, content: &str) -> Result<()> {
+    let url = format!("{}/api/tracker/chat/send", base_url.trim_end_matches('/'));
+    // Not retried: a message the server got but didn't answer would be broadcast twice
+    let response = http::client()
+        .post(&url)
+        .timeout(Duration::from_secs(15))
+        .json(&serde_json::json!({
+            "uuid": uuid,
+            "access_token": access_token,
+            "content": content,
+        }))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("Failed to send chat message ({}): {}", status, body);
+    }
+
+    Ok(())
+}
+
 /// Minecraft server status response structure (from JSON response)
 #[derive(Debug, Deserialize)]
 struct MinecraftStatusResponse {
@@ -101,10 +158,9 @@ struct MojangProfile {
 
 /// Resolve player name from UUID using Mojang API
 pub async fn resolve_player_name(uuid: &str) -> Result<String> {
-    let client = reqwest::Client::new();
     let url = format!("https://sessionserver.mojang.com/session/minecraft/profile/{}", uuid);
     
-    let response = client.get(&url).send().await?;
+    let response = http::client().get(&url).send_retrying(RetryPolicy::default()).await?;
     
     if response.status().is_success() {
         let profile: MojangProfile = response.json().await?;
@@ -222,40 +278,32 @@ fn extract_motd_text(description: &serde_json::Value) -> String {
 }
 
 /// Determine which server address to use based on VPN settings
-/// Returns VPN address (10.8.0.1:25565) if VPN is enabled and running,
-/// otherwise returns direct address (mc.frostdev.io:25565)
-pub fn get_server_address(vpn_enabled: bool) -> &'static str {
-    #[cfg(target_os = "windows")]
-    {
-        if vpn_enabled {
-            // Check if VPN tunnel is running
-            if let Ok(manager) = VpnManager::new() {
-                if manager.is_tunnel_running() {
-                    eprintln!("[Server] Using VPN address: 10.8.0.1:25565");
-                    return "10.8.0.1:25565";
-                } else {
-                    eprintln!("[Server] VPN enabled but tunnel not running, using direct connection");
-                }
+/// Returns the game server's tunnel address from the VPN registration if VPN is
+/// enabled and running, otherwise returns direct address (mc.frostdev.io:25565)
+pub fn get_server_address(vpn_enabled: bool) -> String {
+    if vpn_enabled {
+        // Check if VPN tunnel is running
+        if let Ok(manager) = VpnManager::new() {
+            if let Some(address) = manager.active_server_address() {
+                eprintln!("[Server] Using VPN address: {}", address);
+                return address;
             } else {
-                eprintln!("[Server] VPN enabled but manager failed to initialize, using direct connection");
+                eprintln!("[Server] VPN enabled but tunnel not running or not registered, using direct connection");
             }
+        } else {
+            eprintln!("[Server] VPN enabled but manager failed to initialize, using direct connection");
         }
     }
 
-    #[cfg(not(target_os = "windows"))]
-    {
-        let _ = vpn_enabled; // Suppress unused variable warning on non-Windows
-    }
-
     eprintln!("[Server] Using direct address: mc.frostdev.io:25565");
-    "mc.frostdev.io:25565"
+    "mc.frostdev.io:25565".to_string()
 }
 
 /// Ping Minecraft server with VPN-aware address selection
 /// Automatically selects VPN or direct address based on VPN settings
 pub async fn ping_server_with_vpn(vpn_enabled: bool) -> Result<ServerStatus> {
     let address = get_server_address(vpn_enabled);
-    ping_server(address).await
+    ping_server(&address).await
 }
 
 /// Ping Minecraft server and get status
@@ -310,20 +358,20 @@ pub async fn ping_server(address: &str) -> Result<ServerStatus> {
             return Err(anyhow::anyhow!("No addresses resolved for '{}'", addr_str));
         }
 
-        // Try to connect to the first resolved address
-        let socket_addr = addresses[0];
-        eprintln!("[Server Ping] Attempting connection to {}", socket_addr);
+        let preferred = PREFERRED_FAMILY.lock().ok().and_then(|families| families.get(&host).copied());
+        let addresses = order_addresses(addresses, preferred);
 
-        let stream = match TcpStream::connect_timeout(&socket_addr, Duration::from_secs(5)) {
-            Ok(s) => {
-                eprintln!("[Server Ping] TCP connection successful");
-                s
-            },
+        let (socket_addr, stream) = match connect_happy_eyeballs(&addresses) {
+            Ok(connected) => connected,
             Err(e) => {
                 eprintln!("[Server Ping] TCP connection failed: {}", e);
-                return Err(anyhow::anyhow!("Failed to connect to {}: {}", socket_addr, e));
+                return Err(e.context(format!("Failed to connect to {}", addr_str)));
             }
         };
+        eprintln!("[Server Ping] TCP connection successful to {}", socket_addr);
+        if let Ok(mut families) = PREFERRED_FAMILY.lock() {
+            families.insert(host.clone(), AddressFamily::of(&socket_addr));
+        }
 
         // Set read/write timeouts
         stream.set_read_timeout(Some(Duration::from_secs(5)))
@@ -373,6 +421,68 @@ pub async fn ping_server(address: &str) -> Result<ServerStatus> {
     }
 }
 
+/// Order resolved addresses for connecting, alternating between IPv6 and IPv4
+///
+/// Starts with `preferred`, or else the family the resolver listed first; the
+/// resolver's order is kept within each family.
+fn order_addresses(addresses: Vec<SocketAddr>, preferred: Option<AddressFamily>) -> Vec<SocketAddr> {
+    let Some(first) = preferred.or_else(|| addresses.first().map(AddressFamily::of)) else {
+        return addresses;
+    };
+    let (primary, secondary): (Vec<_>, Vec<_>) =
+        addresses.into_iter().partition(|address| AddressFamily::of(address) == first);
+
+    let mut ordered = Vec::with_capacity(primary.len() + secondary.len());
+    let mut primary = primary.into_iter();
+    let mut secondary = secondary.into_iter();
+    loop {
+        match (primary.next(), secondary.next()) {
+            (None, None) => break,
+            (a, b) => ordered.extend(a.into_iter().chain(b)),
+        }
+    }
+    ordered
+}
+
+/// Connect to whichever of `addresses` answers first, happy-eyeballs style (RFC 8305)
+///
+/// Attempts start `CONNECTION_ATTEMPT_DELAY` apart, or straight away when the one
+/// before fails, and run alongside each other. Connections made after the first are
+/// dropped.
+fn connect_happy_eyeballs(addresses: &[SocketAddr]) -> Result<(SocketAddr, TcpStream)> {
+    let (tx, rx) = mpsc::channel();
+    let mut pending = addresses.iter().copied();
+    let mut in_flight = 0;
+    let mut errors = Vec::new();
+
+    loop {
+        if let Some(address) = pending.next() {
+            eprintln!("[Server Ping] Attempting connection to {}", address);
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let _ = tx.send((address, TcpStream::connect_timeout(&address, CONNECT_ATTEMPT_TIMEOUT)));
+            });
+            in_flight += 1;
+        }
+        if in_flight == 0 {
+            break;
+        }
+
+        match rx.recv_timeout(CONNECTION_ATTEMPT_DELAY) {
+            Ok((address, Ok(stream))) => return Ok((address, stream)),
+            Ok((address, Err(e))) => {
+                eprintln!("[Server Ping] Connection to {} failed: {}", address, e);
+                errors.push(format!("{}: {}", address, e));
+                in_flight -= 1;
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => unreachable!("sender is held until return"),
+        }
+    }
+
+    Err(anyhow!("No address answered ({})", errors.join("; ")))
+}
+
 /// Send a packet with VarInt length prefix
 fn send_packet(stream: &mut TcpStream, packet_id: i32, data: &[u8]) -> Result<()> {
     let mut packet = Vec::new();
@@ -840,6 +950,37 @@ mod tests {
         }).await;
     }
 
+    #[test]
+    fn test_order_addresses_alternates_families() {
+        let v6a: SocketAddr = "[2001:db8::1]:25565".parse().unwrap();
+        let v6b: SocketAddr = "[2001:db8::2]:25565".parse().unwrap();
+        let v4a: SocketAddr = "192.0.2.1:25565".parse().unwrap();
+        let v4b: SocketAddr = "192.0.2.2:25565".parse().unwrap();
+        let resolved = vec![v6a, v6b, v4a, v4b];
+
+        assert_eq!(order_addresses(resolved.clone(), None), vec![v6a, v4a, v6b, v4b]);
+        assert_eq!(
+            order_addresses(resolved, Some(AddressFamily::V4)),
+            vec![v4a, v6a, v4b, v6b]
+        );
+        assert_eq!(order_addresses(vec![v4a], Some(AddressFamily::V6)), vec![v4a]);
+    }
+
+    #[test]
+    fn test_connect_falls_through_to_reachable_address() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let reachable = listener.local_addr().unwrap();
+        // Closed ports: refused (or unroutable where IPv6 is off) straight away
+        let closed = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let closed_v6: SocketAddr = format!("[::1]:{}", closed.port()).parse().unwrap();
+
+        let (address, _stream) = connect_happy_eyeballs(&[closed_v6, closed, reachable]).unwrap();
+        assert_eq!(address, reachable);
+
+        let error = connect_happy_eyeballs(&[closed_v6, closed]).unwrap_err();
+        assert!(error.to_string().contains(&closed.to_string()));
+    }
+
     #[tokio::test]
     async fn test_varint_encoding() {
         // Test VarInt encoding/decoding
@@ -0,0 +1,236 @@
+use anyhow::{Context, Result};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use super::install_id;
+
+/// Emitted for every `release` or `launcher` notification from the server
+pub const SERVER_EVENT: &str = "server-event";
+/// Emitted when the event stream connects or drops, so the UI can fall back to polling
+pub const SERVER_EVENTS_STATUS: &str = "server-events-status";
+
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(300);
+
+/// The server sends a keep-alive every 25s; silence for longer means a dead connection
+const IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// One message from the server's `/api/events` stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerEvent {
+    /// `release` (modpack manifest may have changed) or `launcher` (new launcher build)
+    pub kind: String,
+    /// Missing when the server asks for a full re-check
+    pub version: Option<String>,
+    #[serde(default)]
+    pub deleted: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct StatusPayload {
+    connected: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct EventData {
+    version: Option<String>,
+    #[serde(default)]
+    deleted: bool,
+}
+
+/// Incremental parser for a `text/event-stream` body
+#[derive(Default)]
+struct SseParser {
+    buffer: String,
+}
+
+impl SseParser {
+    /// Feed a chunk, returning the (event name, data) pairs it completed
+    fn push(&mut self, chunk: &str) -> Vec<(String, String)> {
+        self.buffer.push_str(&chunk.replace("\r\n", "\n"));
+
+        let mut messages = Vec::new();
+        while let Some(end) = self.buffer.find("\n\n") {
+            let block: String = self.buffer.drain(..end + 2).collect();
+            let mut event = String::from("message");
+            let mut data = Vec::new();
+            for line in block.lines() {
+                if let Some(value) = line.strip_prefix("event:") {
+                    event = value.trim().to_string();
+                } else if let Some(value) = line.strip_prefix("data:") {
+                    data.push(value.strip_prefix(' ').unwrap_or(value));
+                }
+                // Comments (keep-alives) and `retry:` need no handling
+            }
+            if !data.is_empty() {
+                messages.push((event, data.join("\n")));
+            }
+        }
+        messages
+    }
+}
+
+fn to_server_event(name: &str, data: &str) -> Option<ServerEvent> {
+    if name != "release" && name != "launcher" {
+        return None;
+    }
+    let data: EventData = serde_json::from_str(data).unwrap_or_default();
+    Some(ServerEvent {
+        kind: name.to_string(),
+        version: data.version,
+        deleted: data.deleted,
+    })
+}
+
+/// Read the event stream until it ends, calling `on_event` for each notification
+///
+/// `on_connected` runs once the server has accepted the subscription.
+async fn stream_events(
+    server_url: &str,
+    mut on_connected: impl FnMut(),
+    mut on_event: impl FnMut(ServerEvent),
+) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(10))
+        .build()?;
+
+    let url = format!("{}/api/events", server_url.trim_end_matches('/'));
+    let response = install_id::apply(client.get(&url), &url)
+        .header(reqwest::header::ACCEPT, "text/event-stream")
+        .send()
+        .await
+        .context("Failed to connect to server events")?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Server events rejected with HTTP status {}", response.status());
+    }
+    on_connected();
+
+    let mut parser = SseParser::default();
+    let mut body = response.bytes_stream();
+    loop {
+        let chunk = match tokio::time::timeout(IDLE_TIMEOUT, body.next()).await {
+            Ok(Some(chunk)) => chunk.context("Server events stream failed")?,
+            Ok(None) => return Ok(()),
+            Err(_) => anyhow::bail!("Server events stream went silent"),
+        };
+        for (name, data) in parser.push(&String::from_utf8_lossy(&chunk)) {
+            if let Some(event) = to_server_event(&name, &data) {
+                on_event(event);
+            }
+        }
+    }
+}
+
+/// Subscription to the modpack server's push notifications
+///
+/// Forwards them to the frontend as `server-event`, which triggers update
+/// checks instead of polling the manifest. Reconnects with backoff.
+#[derive(Clone, Default)]
+pub struct ServerEvents {
+    task: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl ServerEvents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribe to `server_url`, replacing any previous subscription
+    pub async fn start(&self, app: AppHandle, server_url: String) {
+        self.stop().await;
+
+        let task = tokio::spawn(async move {
+            let mut delay = MIN_RECONNECT_DELAY;
+            loop {
+                let mut connected = false;
+                let result = stream_events(
+                    &server_url,
+                    || {
+                        connected = true;
+                        let _ = app.emit(SERVER_EVENTS_STATUS, StatusPayload { connected: true });
+                    },
+                    |event| {
+                        let _ = app.emit(SERVER_EVENT, event);
+                    },
+                )
+                .await;
+
+                if connected {
+                    delay = MIN_RECONNECT_DELAY;
+                    let _ = app.emit(SERVER_EVENTS_STATUS, StatusPayload { connected: false });
+                }
+                if let Err(e) = result {
+                    eprintln!("[ServerEvents] {:#}", e);
+                }
+
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+            }
+        });
+
+        *self.task.lock().await = Some(task);
+    }
+
+    pub async fn stop(&self) {
+        if let Some(task) = self.task.lock().await.take() {
+            task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_parser_handles_split_chunks() {
+        let mut parser = SseParser::default();
+        let messages = parser.push("retry: 10000\nevent: connected\ndata: {}\n\n: keep-alive\n\nevent: rel");
+        assert_eq!(messages, vec![("connected".to_string(), "{}".to_string())]);
+
+        let messages = parser.push("ease\r\ndata: {\"version\":\"1.2.0\"}\r\n\r\n");
+        assert_eq!(messages, vec![("release".to_string(), "{\"version\":\"1.2.0\"}".to_string())]);
+    }
+
+    #[test]
+    fn test_only_update_events_are_forwarded() {
+        assert_eq!(to_server_event("connected", "{}"), None);
+        assert_eq!(
+            to_server_event("release", "{}"),
+            Some(ServerEvent { kind: "release".to_string(), version: None, deleted: false })
+        );
+        let launcher = to_server_event("launcher", "{\"version\":\"2.0.0\"}").unwrap();
+        assert_eq!(launcher.version.as_deref(), Some("2.0.0"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_events() {
+        let server = MockServer::start().await;
+        let body = "event: connected\ndata: {}\n\n\
+                    event: release\ndata: {\"version\":\"1.2.0\"}\n\n\
+                    event: launcher\ndata: {\"version\":\"2.0.0\"}\n\n";
+        Mock::given(method("GET"))
+            .and(path("/api/events"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+            .mount(&server)
+            .await;
+
+        let mut connected = false;
+        let mut events = Vec::new();
+        stream_events(&server.uri(), || connected = true, |event| events.push(event))
+            .await
+            .unwrap();
+
+        assert!(connected);
+        let kinds: Vec<_> = events.iter().map(|e| e.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["release", "launcher"]);
+    }
+}
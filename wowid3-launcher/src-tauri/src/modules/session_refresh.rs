@@ -0,0 +1,229 @@
+use anyhow::Result;
+use chrono::Duration as ChronoDuration;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use super::auth::{self, MinecraftProfile};
+
+/// Emitted when the signed-in session is renewed, or can't be
+pub const AUTH_SESSION_EVENT: &str = "auth-session";
+
+const MIN_RETRY_DELAY: Duration = Duration::from_secs(30);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30 * 60);
+
+/// How often the background task looks at the session's expiry
+const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// Renew this long before the tokens expire, so a launcher left open never holds an expired session
+const RENEW_BEFORE_MINUTES: i64 = 30;
+
+/// Payload of `auth-session`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum SessionStatus {
+    /// The tokens were renewed
+    Refreshed { username: String },
+    /// Auth services are down; the stored session is used until a retry gets through
+    NotRefreshed {
+        username: String,
+        reason: String,
+        retry_in_secs: u64,
+    },
+    /// A refresh was rejected (e.g. the refresh token was revoked); sign in again
+    LoginRequired { reason: String },
+}
+
+fn next_delay(delay: Duration) -> Duration {
+    (delay * 2).min(MAX_RETRY_DELAY)
+}
+
+/// The signed-in profile, if its tokens are still stored
+fn stored_session() -> Result<Option<MinecraftProfile>> {
+    Ok(auth::get_current_user()?
+        .filter(|profile| auth::get_access_token_by_session_id(&profile.session_id).is_ok()))
+}
+
+/// Token refresh that survives Microsoft, Xbox Live and Minecraft service outages
+#[derive(Clone, Default)]
+pub struct SessionRefresher {
+    retry: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl SessionRefresher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Refresh the signed-in player's tokens
+    ///
+    /// When the auth services are unreachable or answer with server errors and a
+    /// session is stored, that session is returned unrefreshed so the game can still
+    /// be launched; `auth-session` reports `not_refreshed` and the refresh is retried
+    /// in the background with exponential backoff. Rejections are returned as errors.
+    pub async fn refresh(&self, app: &AppHandle) -> Result<MinecraftProfile> {
+        self.refresh_within(app, ChronoDuration::zero()).await
+    }
+
+    /// [`Self::refresh`], also renewing tokens that expire within `margin`
+    async fn refresh_within(&self, app: &AppHandle, margin: ChronoDuration) -> Result<MinecraftProfile> {
+        let previous_expiry = auth::get_current_user().ok().flatten().and_then(|p| p.expires_at);
+        match auth::refresh_token_within(margin).await {
+            Ok(profile) => {
+                let renewed = profile.expires_at != previous_expiry;
+                if self.stop().await || renewed {
+                    let _ = app.emit(
+                        AUTH_SESSION_EVENT,
+                        SessionStatus::Refreshed { username: profile.username.clone() },
+                    );
+                }
+                Ok(profile)
+            }
+            Err(e) if auth::is_service_outage(&e) => {
+                let Some(profile) = stored_session()? else {
+                    return Err(e);
+                };
+                eprintln!("[Auth] Session not refreshed, auth services unavailable: {:#}", e);
+                self.start_retry(app.clone(), profile.username.clone(), e.to_string(), margin).await;
+                Ok(profile)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn start_retry(&self, app: AppHandle, username: String, reason: String, margin: ChronoDuration) {
+        self.stop().await;
+
+        let task = tokio::spawn(async move {
+            let mut delay = MIN_RETRY_DELAY;
+            let mut status = SessionStatus::NotRefreshed {
+                username: username.clone(),
+                reason,
+                retry_in_secs: delay.as_secs(),
+            };
+            loop {
+                let _ = app.emit(AUTH_SESSION_EVENT, status);
+                tokio::time::sleep(delay).await;
+
+                status = match auth::refresh_token_within(margin).await {
+                    Ok(profile) => {
+                        eprintln!("[Auth] Session refreshed after auth service outage");
+                        let _ = app.emit(
+                            AUTH_SESSION_EVENT,
+                            SessionStatus::Refreshed { username: profile.username },
+                        );
+                        return;
+                    }
+                    Err(e) if auth::is_service_outage(&e) => {
+                        delay = next_delay(delay);
+                        eprintln!("[Auth] Refresh retry failed, next in {}s: {:#}", delay.as_secs(), e);
+                        SessionStatus::NotRefreshed {
+                            username: username.clone(),
+                            reason: e.to_string(),
+                            retry_in_secs: delay.as_secs(),
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("[Auth] Refresh retry rejected: {:#}", e);
+                        let _ = app.emit(
+                            AUTH_SESSION_EVENT,
+                            SessionStatus::LoginRequired { reason: e.to_string() },
+                        );
+                        return;
+                    }
+                };
+            }
+        });
+
+        *self.retry.lock().await = Some(task);
+    }
+
+    /// Renew the session in the background ahead of its expiry for as long as the app runs
+    ///
+    /// Outcomes are reported as `auth-session` events; outages hand over to the
+    /// backoff retry.
+    pub fn start_maintenance(&self, app: AppHandle) {
+        let refresher = self.clone();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(MAINTENANCE_INTERVAL).await;
+                refresher.maintain(&app).await;
+            }
+        });
+    }
+
+    async fn maintain(&self, app: &AppHandle) {
+        if self.retrying().await {
+            return;
+        }
+        let margin = ChronoDuration::minutes(RENEW_BEFORE_MINUTES);
+        let Ok(Some(profile)) = auth::get_current_user() else {
+            return;
+        };
+        if !auth::can_refresh(&profile) || !auth::expires_within(&profile.expires_at, margin) {
+            return;
+        }
+
+        eprintln!("[Auth] Session expires soon, renewing it");
+        if let Err(e) = self.refresh_within(app, margin).await {
+            eprintln!("[Auth] Session renewal failed: {:#}", e);
+            let _ = app.emit(AUTH_SESSION_EVENT, SessionStatus::LoginRequired { reason: e.to_string() });
+        }
+    }
+
+    async fn retrying(&self) -> bool {
+        self.retry
+            .lock()
+            .await
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+    }
+
+    /// Cancel a pending retry; returns whether one was still running
+    pub async fn stop(&self) -> bool {
+        match self.retry.lock().await.take() {
+            Some(task) => {
+                let running = !task.is_finished();
+                task.abort();
+                running
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_backoff_is_capped() {
+        let mut delay = MIN_RETRY_DELAY;
+        let mut delays = Vec::new();
+        for _ in 0..8 {
+            delays.push(delay.as_secs());
+            delay = next_delay(delay);
+        }
+        assert_eq!(delays, vec![30, 60, 120, 240, 480, 960, 1800, 1800]);
+    }
+
+    #[test]
+    fn test_status_payload() {
+        let status = SessionStatus::NotRefreshed {
+            username: "Notch".to_string(),
+            reason: "Xbox Live authentication failed with status 503".to_string(),
+            retry_in_secs: 30,
+        };
+        assert_eq!(
+            serde_json::to_value(status).unwrap(),
+            serde_json::json!({
+                "state": "not_refreshed",
+                "username": "Notch",
+                "reason": "Xbox Live authentication failed with status 503",
+                "retry_in_secs": 30,
+            })
+        );
+    }
+}
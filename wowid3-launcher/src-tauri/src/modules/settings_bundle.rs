@@ -0,0 +1,325 @@
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::Sha256;
+use std::path::{Path, PathBuf};
+
+use super::overlay::OverlaySettings;
+
+const BUNDLE_FORMAT_VERSION: u32 = 1;
+const ENVELOPE_VERSION: u32 = 1;
+const KDF_ITERATIONS: u32 = 200_000;
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// Settings keys that must never leave the machine (matched case-insensitively as substrings)
+const SENSITIVE_KEY_MARKERS: &[&str] = &["token", "secret", "password", "session", "credential"];
+
+/// Per-instance metadata (game directory + pinned modpack version)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceInfo {
+    pub game_dir: PathBuf,
+    pub installed_version: Option<String>,
+}
+
+/// Account-agnostic launcher state; never contains auth tokens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub format_version: u32,
+    pub exported_at: String,
+    pub launcher_version: String,
+    /// Persisted frontend settings (theme, RAM, manifest URL, pinned Minecraft/Fabric versions, ...)
+    pub settings: Map<String, Value>,
+    #[serde(default)]
+    pub overlay: Option<OverlaySettings>,
+    #[serde(default)]
+    pub instances: Vec<InstanceInfo>,
+    #[serde(default)]
+    pub trusted_mods: Vec<String>,
+}
+
+/// Summary returned to the frontend after an import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub exported_at: String,
+    pub launcher_version: String,
+    /// Sanitized settings for the frontend store to apply
+    pub settings: Map<String, Value>,
+    pub overlay: Option<OverlaySettings>,
+    pub instances: Vec<InstanceInfo>,
+    pub trusted_mods: Vec<String>,
+    /// Human-readable list of what was restored
+    pub restored: Vec<String>,
+    /// Keys dropped during validation
+    pub skipped: Vec<String>,
+}
+
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    SENSITIVE_KEY_MARKERS.iter().any(|marker| key.contains(marker))
+}
+
+/// Drop internal (`_`-prefixed) and sensitive keys; returns the removed key names
+pub fn sanitize_settings(settings: &mut Map<String, Value>) -> Vec<String> {
+    let removed: Vec<String> = settings
+        .keys()
+        .filter(|key| key.starts_with('_') || is_sensitive_key(key))
+        .cloned()
+        .collect();
+
+    for key in &removed {
+        settings.remove(key);
+    }
+
+    removed
+}
+
+/// Build a bundle from the current launcher state
+pub async fn build_bundle(
+    settings: Value,
+    overlay: Option<OverlaySettings>,
+    game_dirs: &[PathBuf],
+    trusted_mods: Vec<String>,
+) -> Result<SettingsBundle> {
+    let mut settings = match settings {
+        Value::Object(map) => map,
+        _ => anyhow::bail!("Settings must be a JSON object"),
+    };
+    sanitize_settings(&mut settings);
+
+    let mut instances = Vec::new();
+    for game_dir in game_dirs {
+        let installed_version = super::updater::get_installed_version(game_dir).await?;
+        instances.push(InstanceInfo {
+            game_dir: game_dir.clone(),
+            installed_version,
+        });
+    }
+
+    Ok(SettingsBundle {
+        format_version: BUNDLE_FORMAT_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        launcher_version: env!("CARGO_PKG_VERSION").to_string(),
+        settings,
+        overlay,
+        instances,
+        trusted_mods,
+    })
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, KDF_ITERATIONS, &mut key);
+    key
+}
+
+/// Encrypt a bundle with a passphrase-derived key
+///
+/// The machine key used for session storage can't be used here: the whole point
+/// is restoring on a fresh OS install, so the key comes from the user's passphrase.
+pub fn encrypt_bundle(bundle: &SettingsBundle, passphrase: &str) -> Result<String> {
+    if passphrase.len() < MIN_PASSPHRASE_LEN {
+        anyhow::bail!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_LEN
+        );
+    }
+
+    let mut rng = rand::thread_rng();
+    let salt: [u8; 16] = rng.gen();
+    let nonce_bytes: [u8; 12] = rng.gen();
+
+    let key = derive_key(passphrase, &salt);
+    let cipher = Aes256Gcm::new(&key.into());
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let plaintext = serde_json::to_string(bundle)?;
+    let ciphertext = cipher
+        .encrypt(nonce, Payload::from(plaintext.as_bytes()))
+        .map_err(|e| anyhow!("Encryption failed: {}", e))?;
+
+    let envelope = serde_json::json!({
+        "v": ENVELOPE_VERSION,
+        "kind": "wowid3-settings",
+        "salt": STANDARD.encode(salt),
+        "nonce": STANDARD.encode(nonce_bytes),
+        "ciphertext": STANDARD.encode(&ciphertext),
+    });
+
+    Ok(serde_json::to_string_pretty(&envelope)?)
+}
+
+/// Decrypt and validate a bundle
+pub fn decrypt_bundle(envelope_json: &str, passphrase: &str) -> Result<SettingsBundle> {
+    let envelope: Value =
+        serde_json::from_str(envelope_json).context("Not a launcher settings export")?;
+
+    if envelope["kind"].as_str() != Some("wowid3-settings") {
+        anyhow::bail!("Not a launcher settings export");
+    }
+    if envelope["v"].as_u64() != Some(ENVELOPE_VERSION as u64) {
+        anyhow::bail!("Unsupported export format");
+    }
+
+    let decode = |field: &str| -> Result<Vec<u8>> {
+        let value = envelope[field]
+            .as_str()
+            .ok_or_else(|| anyhow!("Invalid export: missing {}", field))?;
+        STANDARD
+            .decode(value)
+            .map_err(|e| anyhow!("Invalid {} encoding: {}", field, e))
+    };
+
+    let salt = decode("salt")?;
+    let nonce_bytes = decode("nonce")?;
+    let ciphertext = decode("ciphertext")?;
+
+    if nonce_bytes.len() != 12 {
+        anyhow::bail!("Invalid nonce length");
+    }
+
+    let key = derive_key(passphrase, &salt);
+    let cipher = Aes256Gcm::new(&key.into());
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce_bytes), Payload::from(ciphertext.as_slice()))
+        .map_err(|_| anyhow!("Wrong passphrase or corrupted export"))?;
+
+    let bundle: SettingsBundle =
+        serde_json::from_slice(&plaintext).context("Export contents are invalid")?;
+
+    if bundle.format_version > BUNDLE_FORMAT_VERSION {
+        anyhow::bail!(
+            "Export was created by a newer launcher (format {})",
+            bundle.format_version
+        );
+    }
+
+    Ok(bundle)
+}
+
+/// Write an encrypted bundle to disk
+pub async fn export_to_file(bundle: &SettingsBundle, passphrase: &str, path: &Path) -> Result<()> {
+    let envelope = encrypt_bundle(bundle, passphrase)?;
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .context("Failed to create export directory")?;
+    }
+
+    tokio::fs::write(path, envelope)
+        .await
+        .context("Failed to write settings export")?;
+
+    eprintln!("[Settings] Exported settings to {:?}", path);
+    Ok(())
+}
+
+/// Read, decrypt and validate a bundle, returning a summary for the frontend to apply
+pub async fn import_from_file(path: &Path, passphrase: &str) -> Result<ImportSummary> {
+    let envelope = tokio::fs::read_to_string(path)
+        .await
+        .context("Failed to read settings export")?;
+
+    let mut bundle = decrypt_bundle(&envelope, passphrase)?;
+
+    // Re-sanitize in case the bundle was produced by a tampered or older build
+    let skipped = sanitize_settings(&mut bundle.settings);
+
+    let mut restored: Vec<String> = bundle
+        .settings
+        .keys()
+        .map(|key| format!("setting: {}", key))
+        .collect();
+    if bundle.overlay.is_some() {
+        restored.push("overlay settings".to_string());
+    }
+    for instance in &bundle.instances {
+        restored.push(format!(
+            "instance: {} ({})",
+            instance.game_dir.display(),
+            instance.installed_version.as_deref().unwrap_or("not installed")
+        ));
+    }
+    if !bundle.trusted_mods.is_empty() {
+        restored.push(format!("trusted mods: {}", bundle.trusted_mods.len()));
+    }
+
+    eprintln!(
+        "[Settings] Imported settings from {:?} ({} items, {} skipped)",
+        path,
+        restored.len(),
+        skipped.len()
+    );
+
+    Ok(ImportSummary {
+        exported_at: bundle.exported_at,
+        launcher_version: bundle.launcher_version,
+        settings: bundle.settings,
+        overlay: bundle.overlay,
+        instances: bundle.instances,
+        trusted_mods: bundle.trusted_mods,
+        restored,
+        skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sanitize_strips_tokens_and_internal_keys() {
+        let mut settings = json!({
+            "theme": "dark",
+            "accessToken": "abc",
+            "refresh_token": "def",
+            "_defaultGameDirectoryFetched": true,
+            "ramAllocation": 8192
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+
+        let removed = sanitize_settings(&mut settings);
+
+        assert_eq!(removed.len(), 3);
+        assert!(settings.contains_key("theme"));
+        assert!(settings.contains_key("ramAllocation"));
+        assert!(!settings.contains_key("accessToken"));
+    }
+
+    #[tokio::test]
+    async fn test_export_import_roundtrip() {
+        let temp = tempfile::tempdir().unwrap();
+        let game_dir = temp.path().join("game");
+        std::fs::create_dir_all(&game_dir).unwrap();
+        std::fs::write(game_dir.join(".wowid3-version"), "1.4.0").unwrap();
+
+        let bundle = build_bundle(
+            json!({ "theme": "light", "sessionId": "nope" }),
+            Some(OverlaySettings { enabled: true, directory: None }),
+            &[game_dir.clone()],
+            vec!["sodium".to_string()],
+        )
+        .await
+        .unwrap();
+
+        let path = temp.path().join("export.wowid3");
+        export_to_file(&bundle, "correct horse", &path).await.unwrap();
+
+        let summary = import_from_file(&path, "correct horse").await.unwrap();
+        assert_eq!(summary.settings.get("theme"), Some(&json!("light")));
+        assert!(!summary.settings.contains_key("sessionId"));
+        assert_eq!(summary.instances[0].installed_version.as_deref(), Some("1.4.0"));
+        assert_eq!(summary.trusted_mods, vec!["sodium".to_string()]);
+
+        assert!(import_from_file(&path, "wrong passphrase").await.is_err());
+    }
+}
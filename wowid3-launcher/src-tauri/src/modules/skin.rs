@@ -0,0 +1,352 @@
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use image::{imageops, GenericImageView, ImageFormat, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::fs;
+
+/// Re-download textures after this long; older caches are still used offline
+const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The front view is 16x32 skin pixels; scaled up so the frontend needn't
+const RENDER_SCALE: u32 = 8;
+
+/// A player's skin for the frontend, everything as `data:image/png` URLs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSkin {
+    pub uuid: String,
+    /// Front-facing 2D render with the overlay layers applied
+    pub render: String,
+    /// Raw skin texture (for the 3D viewer)
+    pub skin: String,
+    /// Raw cape texture, if the player has one equipped
+    pub cape: Option<String>,
+    /// Alex-style 3 pixel arms
+    pub slim: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SkinMetadata {
+    slim: bool,
+    has_cape: bool,
+    fetched_at: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileResponse {
+    properties: Vec<ProfileProperty>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileProperty {
+    name: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TexturesPayload {
+    textures: Textures,
+}
+
+#[derive(Debug, Deserialize)]
+struct Textures {
+    #[serde(rename = "SKIN")]
+    skin: Option<Texture>,
+    #[serde(rename = "CAPE")]
+    cape: Option<Texture>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Texture {
+    url: String,
+    #[serde(default)]
+    metadata: Option<TextureMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TextureMetadata {
+    model: Option<String>,
+}
+
+fn cache_dir(uuid: &str) -> Result<PathBuf> {
+    Ok(super::paths::get_persistent_data_dir()?
+        .join("cache")
+        .join("skins")
+        .join(uuid))
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn data_url(png: &[u8]) -> String {
+    format!("data:image/png;base64,{}", STANDARD.encode(png))
+}
+
+/// Skin, render and cape for `uuid`, from the disk cache when it's fresh
+/// (or when Mojang can't be reached)
+pub async fn get_player_skin(uuid: &str) -> Result<PlayerSkin> {
+    let uuid = uuid::Uuid::parse_str(uuid.trim())
+        .context("Invalid player UUID")?
+        .simple()
+        .to_string();
+    let dir = cache_dir(&uuid)?;
+
+    let cached: Option<SkinMetadata> = match fs::read_to_string(dir.join("meta.json")).await {
+        Ok(content) => serde_json::from_str(&content).ok(),
+        Err(_) => None,
+    };
+
+    let fresh = cached
+        .as_ref()
+        .is_some_and(|meta| now_secs().saturating_sub(meta.fetched_at) < CACHE_TTL.as_secs());
+    if !fresh {
+        match download_textures(&uuid, &dir).await {
+            Ok(()) => {}
+            Err(e) if cached.is_some() => {
+                eprintln!("[Skin] Using cached skin for {}: {}", uuid, e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    read_cached(&uuid, &dir).await
+}
+
+async fn read_cached(uuid: &str, dir: &PathBuf) -> Result<PlayerSkin> {
+    let meta: SkinMetadata = serde_json::from_str(&fs::read_to_string(dir.join("meta.json")).await?)?;
+    let skin = fs::read(dir.join("skin.png")).await?;
+    let render = fs::read(dir.join("render.png")).await?;
+    let cape = if meta.has_cape {
+        fs::read(dir.join("cape.png")).await.ok()
+    } else {
+        None
+    };
+
+    Ok(PlayerSkin {
+        uuid: uuid.to_string(),
+        render: data_url(&render),
+        skin: data_url(&skin),
+        cape: cape.as_deref().map(data_url),
+        slim: meta.slim,
+    })
+}
+
+/// Fetch the textures from Mojang, render the front view and write it all to `dir`
+async fn download_textures(uuid: &str, dir: &PathBuf) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let url = format!("https://sessionserver.mojang.com/session/minecraft/profile/{}", uuid);
+    let response = client.get(&url).send().await?;
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to fetch profile: {}", response.status());
+    }
+    let profile: ProfileResponse = response.json().await?;
+
+    let property = profile
+        .properties
+        .iter()
+        .find(|p| p.name == "textures")
+        .context("Profile has no textures")?;
+    let payload: TexturesPayload = serde_json::from_slice(&STANDARD.decode(&property.value)?)?;
+    let skin_texture = payload.textures.skin.context("Player has no custom skin")?;
+    let slim = skin_texture
+        .metadata
+        .as_ref()
+        .and_then(|m| m.model.as_deref())
+        == Some("slim");
+
+    let skin_png = client.get(&skin_texture.url).send().await?.error_for_status()?.bytes().await?;
+    let skin = image::load_from_memory_with_format(&skin_png, ImageFormat::Png)
+        .context("Invalid skin texture")?
+        .to_rgba8();
+    let render = encode_png(&render_front(&skin, slim)?)?;
+
+    let cape_png = match &payload.textures.cape {
+        Some(cape) => Some(client.get(&cape.url).send().await?.error_for_status()?.bytes().await?),
+        None => None,
+    };
+
+    fs::create_dir_all(dir).await?;
+    fs::write(dir.join("skin.png"), &skin_png).await?;
+    fs::write(dir.join("render.png"), &render).await?;
+    match &cape_png {
+        Some(cape) => fs::write(dir.join("cape.png"), cape).await?,
+        None => {
+            let _ = fs::remove_file(dir.join("cape.png")).await;
+        }
+    }
+    let meta = SkinMetadata {
+        slim,
+        has_cape: cape_png.is_some(),
+        fetched_at: now_secs(),
+    };
+    fs::write(dir.join("meta.json"), serde_json::to_string(&meta)?).await?;
+
+    Ok(())
+}
+
+fn encode_png(image: &RgbaImage) -> Result<Vec<u8>> {
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    Ok(png)
+}
+
+/// A rectangle of the skin texture
+#[derive(Clone, Copy)]
+struct Region {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+const fn region(x: u32, y: u32, width: u32, height: u32) -> Region {
+    Region { x, y, width, height }
+}
+
+fn crop(skin: &RgbaImage, r: Region) -> RgbaImage {
+    skin.view(r.x, r.y, r.width, r.height).to_image()
+}
+
+/// Legacy skins store hats as opaque filler; the game ignores a hat with no
+/// transparent pixels, so do the same
+fn fully_opaque(image: &RgbaImage) -> bool {
+    image.pixels().all(|p| p.0[3] == 255)
+}
+
+/// Compose the front view of a skin: base layers with the overlay (hat,
+/// jacket, sleeves, pants) on top, scaled by `RENDER_SCALE`
+///
+/// Handles both 64x64 skins and legacy 64x32 ones, whose left limbs mirror
+/// the right ones and which have no body overlays.
+pub fn render_front(skin: &RgbaImage, slim: bool) -> Result<RgbaImage> {
+    let (width, height) = skin.dimensions();
+    if width != 64 || (height != 64 && height != 32) {
+        anyhow::bail!("Unsupported skin size {}x{}", width, height);
+    }
+    let legacy = height == 32;
+    let arm = if slim && !legacy { 3 } else { 4 };
+
+    let mut canvas = RgbaImage::new(16, 32);
+
+    // Viewer's left is the player's right
+    let right_arm = crop(skin, region(44, 20, arm, 12));
+    let right_leg = crop(skin, region(4, 20, 4, 12));
+    let (left_arm, left_leg) = if legacy {
+        (imageops::flip_horizontal(&right_arm), imageops::flip_horizontal(&right_leg))
+    } else {
+        (crop(skin, region(36, 52, arm, 12)), crop(skin, region(20, 52, 4, 12)))
+    };
+
+    let parts: [(RgbaImage, i64, i64); 6] = [
+        (crop(skin, region(8, 8, 8, 8)), 4, 0),
+        (crop(skin, region(20, 20, 8, 12)), 4, 8),
+        (right_arm, 4 - arm as i64, 8),
+        (left_arm, 12, 8),
+        (right_leg, 4, 20),
+        (left_leg, 8, 20),
+    ];
+    for (part, x, y) in &parts {
+        imageops::replace(&mut canvas, part, *x, *y);
+    }
+
+    let hat = crop(skin, region(40, 8, 8, 8));
+    if !(legacy && fully_opaque(&hat)) {
+        imageops::overlay(&mut canvas, &hat, 4, 0);
+    }
+    if !legacy {
+        let overlays = [
+            (region(20, 36, 8, 12), 4, 8),
+            (region(44, 36, arm, 12), 4 - arm as i64, 8),
+            (region(52, 52, arm, 12), 12, 8),
+            (region(4, 36, 4, 12), 4, 20),
+            (region(4, 52, 4, 12), 8, 20),
+        ];
+        for (r, x, y) in overlays {
+            imageops::overlay(&mut canvas, &crop(skin, r), x, y);
+        }
+    }
+
+    Ok(imageops::resize(
+        &canvas,
+        16 * RENDER_SCALE,
+        32 * RENDER_SCALE,
+        imageops::FilterType::Nearest,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+    const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+    const CLEAR: Rgba<u8> = Rgba([0, 0, 0, 0]);
+
+    fn fill(skin: &mut RgbaImage, r: Region, color: Rgba<u8>) {
+        for y in r.y..r.y + r.height {
+            for x in r.x..r.x + r.width {
+                skin.put_pixel(x, y, color);
+            }
+        }
+    }
+
+    /// Render pixel at skin-pixel coordinates of the 16x32 front view
+    fn at(render: &RgbaImage, x: u32, y: u32) -> Rgba<u8> {
+        *render.get_pixel(x * RENDER_SCALE, y * RENDER_SCALE)
+    }
+
+    #[test]
+    fn test_render_front_layers() {
+        let mut skin = RgbaImage::from_pixel(64, 64, CLEAR);
+        fill(&mut skin, region(8, 8, 8, 8), RED); // head
+        fill(&mut skin, region(40, 8, 4, 8), BLUE); // left half of the hat
+        fill(&mut skin, region(36, 52, 4, 12), RED); // left arm
+
+        let render = render_front(&skin, false).unwrap();
+        assert_eq!(render.dimensions(), (16 * RENDER_SCALE, 32 * RENDER_SCALE));
+        assert_eq!(at(&render, 4, 0), BLUE);
+        assert_eq!(at(&render, 11, 0), RED);
+        assert_eq!(at(&render, 12, 8), RED);
+        assert_eq!(at(&render, 0, 8), CLEAR);
+    }
+
+    #[test]
+    fn test_render_front_slim_arms() {
+        let mut skin = RgbaImage::from_pixel(64, 64, CLEAR);
+        fill(&mut skin, region(44, 20, 3, 12), RED);
+
+        let render = render_front(&skin, true).unwrap();
+        assert_eq!(at(&render, 0, 8), CLEAR);
+        assert_eq!(at(&render, 1, 8), RED);
+        assert_eq!(at(&render, 3, 8), RED);
+    }
+
+    #[test]
+    fn test_render_front_legacy() {
+        let mut skin = RgbaImage::from_pixel(64, 32, CLEAR);
+        fill(&mut skin, region(8, 8, 8, 8), RED);
+        fill(&mut skin, region(40, 8, 8, 8), BLUE); // opaque filler, ignored
+        fill(&mut skin, region(44, 20, 1, 12), RED); // outer edge of the right arm
+
+        let render = render_front(&skin, false).unwrap();
+        assert_eq!(at(&render, 4, 0), RED);
+        assert_eq!(at(&render, 0, 8), RED);
+        // Mirrored onto the left arm's outer edge
+        assert_eq!(at(&render, 15, 8), RED);
+    }
+
+    #[test]
+    fn test_render_front_rejects_odd_sizes() {
+        assert!(render_front(&RgbaImage::new(32, 32), false).is_err());
+    }
+}
@@ -36,6 +36,10 @@ pub struct PlayerStats {
     
     #[serde(default)]
     pub playtime_seconds: u64,
+    #[serde(default)]
+    pub distance_traveled: f64,
+    #[serde(default)]
+    pub advancements_completed: u64,
     
     // Aggregate totals for quick access
     #[serde(default)]
@@ -138,3 +142,99 @@ pub async fn get_player_stats(
     Ok(stats)
 }
 
+
+/// One stretch of a player being online, as recorded by the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSession {
+    pub joined_at: i64,
+    pub left_at: Option<i64>,
+    pub duration_secs: i64,
+}
+
+/// Response of `/api/stats/:uuid/sessions`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSessions {
+    pub uuid: String,
+    #[serde(default)]
+    pub online: bool,
+    pub total_playtime_secs: i64,
+    pub playtime_last_7_days_secs: i64,
+    #[serde(default)]
+    pub sessions: Vec<PlayerSession>,
+}
+
+/// Recent sessions and playtime; not cached since it changes while playing
+pub async fn get_player_sessions(uuid: &str, server_url: &str) -> Result<PlayerSessions> {
+    let url = format!("{}/api/stats/{}/sessions", server_url.trim_end_matches('/'), uuid);
+    let response = reqwest::Client::new().get(&url).send().await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to fetch sessions: {}", response.status());
+    }
+
+    Ok(response.json().await?)
+}
+
+/// One ranked player on a server leaderboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub rank: u32,
+    pub uuid: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub value: f64,
+}
+
+/// Response of `/api/stats/leaderboards/:metric`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Leaderboard {
+    /// `deaths`, `mob_kills`, `blocks_mined` or `distance_traveled`
+    pub metric: String,
+    /// `7d`, `30d` or `all`
+    pub window: String,
+    #[serde(default)]
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+/// Top players for a stat; not cached since it changes while people play
+pub async fn get_stat_leaderboard(metric: &str, window: &str, server_url: &str) -> Result<Leaderboard> {
+    let url = format!("{}/api/stats/leaderboards/{}", server_url.trim_end_matches('/'), metric);
+    let response = reqwest::Client::new()
+        .get(&url)
+        .query(&[("window", window)])
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to fetch leaderboard: {}", response.status());
+    }
+
+    Ok(response.json().await?)
+}
+
+/// An advancement the player completed on the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerAdvancement {
+    pub advancement_id: String,
+    pub title: Option<String>,
+    pub completed_at: i64,
+}
+
+/// Response of `/api/stats/:uuid/advancements`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerAdvancements {
+    pub uuid: String,
+    pub advancements: Vec<PlayerAdvancement>,
+}
+
+/// Fetch a player's completed advancements from the server, oldest first
+pub async fn get_player_advancements(uuid: &str, server_url: &str) -> Result<PlayerAdvancements> {
+    let url = format!("{}/api/stats/{}/advancements", server_url.trim_end_matches('/'), uuid);
+    let response = reqwest::Client::new().get(&url).send().await?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to fetch advancements: {}", response.status());
+    }
+
+    Ok(response.json().await?)
+}
@@ -0,0 +1,378 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use sysinfo::Disks;
+use walkdir::WalkDir;
+
+use super::asset_manager::AssetIndex;
+
+/// Top-level folders of the game directory, grouped the way players think about them
+///
+/// Anything not listed counts as `other`.
+const CATEGORIES: &[(&str, &[&str])] = &[
+    ("mods", &["mods"]),
+    ("config", &["config", "defaultconfigs"]),
+    ("saves", &["saves"]),
+    ("logs", &["logs"]),
+    ("crash_reports", &["crash-reports"]),
+    ("cache", &[".cache"]),
+    ("assets", &["assets"]),
+    ("libraries", &["libraries"]),
+    ("versions", &["versions"]),
+    ("resourcepacks", &["resourcepacks", "shaderpacks"]),
+    ("screenshots", &["screenshots"]),
+];
+
+/// Logs Minecraft is (or was last) writing to; the rotated `*.log.gz` files are safe to delete
+const CURRENT_LOGS: &[&str] = &["latest.log", "debug.log"];
+
+/// Space used by one category of files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryUsage {
+    /// `mods`, `config`, `saves`, `logs`, `crash_reports`, `cache`, ..., `other`
+    pub category: String,
+    pub bytes: u64,
+    pub files: u64,
+}
+
+/// Breakdown of what the game directory is using
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskUsage {
+    pub game_dir: PathBuf,
+    pub total_bytes: u64,
+    /// Largest first
+    pub categories: Vec<CategoryUsage>,
+    /// Free space on the disk holding the game directory, when it could be determined
+    pub available_bytes: Option<u64>,
+}
+
+/// What a cleanup removed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CleanupResult {
+    pub files_removed: u64,
+    pub bytes_freed: u64,
+}
+
+impl CleanupResult {
+    fn remove(&mut self, path: &Path) {
+        let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        match std::fs::remove_file(path) {
+            Ok(()) => {
+                self.files_removed += 1;
+                self.bytes_freed += size;
+            }
+            Err(e) => eprintln!("[Storage] Failed to remove {}: {}", path.display(), e),
+        }
+    }
+}
+
+fn category_of(top_level: &str) -> &'static str {
+    CATEGORIES
+        .iter()
+        .find(|(_, dirs)| dirs.contains(&top_level))
+        .map_or("other", |(category, _)| category)
+}
+
+/// Measure the game directory by category
+pub async fn get_disk_usage(game_dir: &Path) -> Result<DiskUsage> {
+    let game_dir = game_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || disk_usage_blocking(&game_dir))
+        .await
+        .context("Disk usage scan panicked")?
+}
+
+fn disk_usage_blocking(game_dir: &Path) -> Result<DiskUsage> {
+    if !game_dir.is_dir() {
+        anyhow::bail!("Game directory does not exist: {}", game_dir.display());
+    }
+
+    let mut categories: Vec<CategoryUsage> = CATEGORIES
+        .iter()
+        .map(|(category, _)| *category)
+        .chain(std::iter::once("other"))
+        .map(|category| CategoryUsage {
+            category: category.to_string(),
+            bytes: 0,
+            files: 0,
+        })
+        .collect();
+
+    // Symlinks aren't followed, so linked folders (e.g. shared saves) aren't counted twice
+    for entry in WalkDir::new(game_dir).min_depth(1).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else { continue };
+        let top_level = entry
+            .path()
+            .strip_prefix(game_dir)
+            .ok()
+            .and_then(|relative| relative.components().next())
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .unwrap_or_default();
+        // A file at the top level is its own "folder", so it lands in `other`
+        let category = if entry.depth() == 1 { "other" } else { category_of(&top_level) };
+
+        if let Some(usage) = categories.iter_mut().find(|u| u.category == category) {
+            usage.bytes += metadata.len();
+            usage.files += 1;
+        }
+    }
+
+    categories.sort_by_key(|u| std::cmp::Reverse(u.bytes));
+    let total_bytes = categories.iter().map(|u| u.bytes).sum();
+
+    Ok(DiskUsage {
+        game_dir: game_dir.to_path_buf(),
+        total_bytes,
+        categories,
+        available_bytes: available_space(game_dir),
+    })
+}
+
+/// Free space on the disk with the longest mount point containing `path`
+fn available_space(path: &Path) -> Option<u64> {
+    let path = std::fs::canonicalize(path).ok()?;
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+/// Delete rotated logs, keeping the ones Minecraft writes to
+pub async fn clean_logs(game_dir: &Path) -> Result<CleanupResult> {
+    let logs_dir = game_dir.join("logs");
+    tokio::task::spawn_blocking(move || {
+        let mut result = CleanupResult::default();
+        if !logs_dir.is_dir() {
+            return result;
+        }
+        for entry in WalkDir::new(&logs_dir).min_depth(1).into_iter().filter_map(|e| e.ok()) {
+            let current = entry.depth() == 1
+                && CURRENT_LOGS.contains(&entry.file_name().to_string_lossy().as_ref());
+            if entry.file_type().is_file() && !current {
+                result.remove(entry.path());
+            }
+        }
+        result
+    })
+    .await
+    .context("Log cleanup panicked")
+}
+
+/// Delete Minecraft crash reports and JVM crash dumps (`hs_err_pid*.log`)
+pub async fn clean_crash_reports(game_dir: &Path) -> Result<CleanupResult> {
+    let game_dir = game_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut result = CleanupResult::default();
+
+        let crash_reports_dir = game_dir.join("crash-reports");
+        if crash_reports_dir.is_dir() {
+            for entry in WalkDir::new(&crash_reports_dir).min_depth(1).into_iter().filter_map(|e| e.ok()) {
+                if entry.file_type().is_file() {
+                    result.remove(entry.path());
+                }
+            }
+        }
+
+        if let Ok(entries) = std::fs::read_dir(&game_dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if name.starts_with("hs_err_pid") && name.ends_with(".log") {
+                    result.remove(&entry.path());
+                }
+            }
+        }
+
+        result
+    })
+    .await
+    .context("Crash report cleanup panicked")
+}
+
+/// Asset index ids used by the installed Minecraft versions (`assetIndex.id` in `versions/*/*.json`)
+fn asset_indexes_in_use(game_dir: &Path) -> HashSet<String> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct VersionJson {
+        asset_index: Option<IndexRef>,
+        assets: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct IndexRef {
+        id: String,
+    }
+
+    let mut in_use = HashSet::new();
+    let Ok(versions) = std::fs::read_dir(game_dir.join("versions")) else {
+        return in_use;
+    };
+    for version_dir in versions.filter_map(|e| e.ok()) {
+        let Ok(files) = std::fs::read_dir(version_dir.path()) else { continue };
+        for file in files.filter_map(|e| e.ok()) {
+            let path = file.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(version) = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|json| serde_json::from_str::<VersionJson>(&json).ok())
+            else {
+                continue;
+            };
+            in_use.extend(version.asset_index.map(|index| index.id));
+            in_use.extend(version.assets);
+        }
+    }
+    in_use
+}
+
+/// Delete asset objects and indexes no installed Minecraft version uses
+///
+/// Old versions leave their assets behind after the modpack moves to a newer one.
+pub async fn clean_unused_assets(game_dir: &Path) -> Result<CleanupResult> {
+    let game_dir = game_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || clean_unused_assets_blocking(&game_dir))
+        .await
+        .context("Asset cleanup panicked")?
+}
+
+fn clean_unused_assets_blocking(game_dir: &Path) -> Result<CleanupResult> {
+    let assets_dir = game_dir.join("assets");
+    let indexes_dir = assets_dir.join("indexes");
+    let objects_dir = assets_dir.join("objects");
+    let mut result = CleanupResult::default();
+    if !objects_dir.is_dir() {
+        return Ok(result);
+    }
+
+    let in_use = asset_indexes_in_use(game_dir);
+    if in_use.is_empty() {
+        // Without a version to go by, every asset would look unused
+        anyhow::bail!("No installed Minecraft version found; not removing any assets");
+    }
+
+    let mut keep = HashSet::new();
+    for id in &in_use {
+        let index_file = indexes_dir.join(format!("{}.json", id));
+        let index: AssetIndex = std::fs::read_to_string(&index_file)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .with_context(|| {
+                format!("Asset index {} is missing or unreadable; not removing any assets", id)
+            })?;
+        keep.extend(index.objects.into_values().map(|object| object.hash));
+    }
+
+    // objects/<first two hash chars>/<hash>
+    for entry in WalkDir::new(&objects_dir).min_depth(2).max_depth(2).into_iter().filter_map(|e| e.ok()) {
+        let name = entry.file_name().to_string_lossy();
+        if entry.file_type().is_file() && !keep.contains(name.as_ref()) {
+            result.remove(entry.path());
+        }
+    }
+    // Drop the hash prefix folders that are now empty
+    if let Ok(subdirs) = std::fs::read_dir(&objects_dir) {
+        for subdir in subdirs.filter_map(|e| e.ok()) {
+            let _ = std::fs::remove_dir(subdir.path());
+        }
+    }
+
+    if let Ok(indexes) = std::fs::read_dir(&indexes_dir) {
+        for index in indexes.filter_map(|e| e.ok()) {
+            let path = index.path();
+            let id = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+            if path.is_file() && !in_use.contains(&id) {
+                result.remove(&path);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_disk_usage_by_category() {
+        let temp = TempDir::new().unwrap();
+        let game_dir = temp.path();
+        write(&game_dir.join("mods/sodium.jar"), &"x".repeat(1000));
+        write(&game_dir.join("mods/lithium.jar"), &"x".repeat(500));
+        write(&game_dir.join("config/sodium.json"), "{}");
+        write(&game_dir.join("saves/World/level.dat"), &"x".repeat(300));
+        write(&game_dir.join("options.txt"), "fov:90");
+        write(&game_dir.join("custom/readme.txt"), "hi");
+
+        let usage = get_disk_usage(game_dir).await.unwrap();
+        assert_eq!(usage.total_bytes, 1000 + 500 + 2 + 300 + 6 + 2);
+        assert_eq!(usage.categories[0].category, "mods");
+        assert_eq!(usage.categories[0].files, 2);
+
+        let bytes = |category: &str| usage.categories.iter().find(|u| u.category == category).unwrap().bytes;
+        assert_eq!(bytes("saves"), 300);
+        assert_eq!(bytes("config"), 2);
+        assert_eq!(bytes("other"), 8);
+        assert_eq!(bytes("logs"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_clean_logs_and_crash_reports() {
+        let temp = TempDir::new().unwrap();
+        let game_dir = temp.path();
+        write(&game_dir.join("logs/latest.log"), "current");
+        write(&game_dir.join("logs/2024-01-01-1.log.gz"), "old");
+        write(&game_dir.join("logs/telemetry/events.txt"), "old");
+        write(&game_dir.join("crash-reports/crash-2024-01-01.txt"), "boom");
+        write(&game_dir.join("hs_err_pid1234.log"), "jvm");
+        write(&game_dir.join("options.txt"), "fov:90");
+
+        let logs = clean_logs(game_dir).await.unwrap();
+        assert_eq!(logs.files_removed, 2);
+        assert_eq!(logs.bytes_freed, 6);
+        assert!(game_dir.join("logs/latest.log").exists());
+
+        let crashes = clean_crash_reports(game_dir).await.unwrap();
+        assert_eq!(crashes.files_removed, 2);
+        assert!(!game_dir.join("hs_err_pid1234.log").exists());
+        assert!(game_dir.join("options.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_clean_unused_assets() {
+        let temp = TempDir::new().unwrap();
+        let game_dir = temp.path();
+
+        // Nothing to go by: refuse rather than delete everything
+        write(&game_dir.join("assets/objects/aa/aa11"), "used");
+        assert!(clean_unused_assets(game_dir).await.is_err());
+
+        write(&game_dir.join("versions/1.20.1/1.20.1.json"), r#"{"id":"1.20.1","assetIndex":{"id":"5"}}"#);
+        write(
+            &game_dir.join("versions/fabric-loader-0.17.3-1.20.1/fabric-loader-0.17.3-1.20.1.json"),
+            r#"{"id":"fabric-loader-0.17.3-1.20.1","inheritsFrom":"1.20.1"}"#,
+        );
+        write(&game_dir.join("assets/indexes/5.json"), r#"{"objects":{"a.ogg":{"hash":"aa11","size":4}}}"#);
+        write(&game_dir.join("assets/indexes/3.json"), r#"{"objects":{"b.ogg":{"hash":"bb22","size":6}}}"#);
+        write(&game_dir.join("assets/objects/bb/bb22"), "unused");
+
+        let result = clean_unused_assets(game_dir).await.unwrap();
+        assert_eq!(result.files_removed, 2); // bb22 and index 3
+        assert!(game_dir.join("assets/objects/aa/aa11").exists());
+        assert!(!game_dir.join("assets/objects/bb").exists());
+        assert!(game_dir.join("assets/indexes/5.json").exists());
+        assert!(!game_dir.join("assets/indexes/3.json").exists());
+    }
+}
@@ -0,0 +1,434 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
+use tokio::process::Command;
+
+use super::crash_report::minecraft_version_from_id;
+use super::java_detection::probe_java;
+use super::offline::load_last_known_good;
+use super::updater::get_installed_version;
+
+/// GPU queries shell out to OS tools, which can hang on broken drivers
+const GPU_PROBE_TIMEOUT_SECS: u64 = 10;
+
+/// Environment details support asks for at the start of every incident
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemReport {
+    /// RFC 3339 time the report was gathered
+    pub generated_at: String,
+    pub launcher_version: String,
+    pub os: OsInfo,
+    pub cpu: CpuInfo,
+    pub memory: MemoryInfo,
+    /// Empty when the GPU couldn't be queried
+    pub gpus: Vec<GpuInfo>,
+    pub java: Option<JavaInfo>,
+    pub modpack_version: Option<String>,
+    pub minecraft_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OsInfo {
+    /// `windows`, `macos` or `linux`
+    pub family: String,
+    /// e.g. `Windows 11 Pro` or `Linux 24.04 Ubuntu`
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub kernel: Option<String>,
+    pub arch: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpuInfo {
+    pub model: Option<String>,
+    pub physical_cores: Option<usize>,
+    pub logical_cores: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryInfo {
+    pub total_mb: u64,
+    pub available_mb: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GpuInfo {
+    pub name: String,
+    /// Kernel driver (Linux) or vendor, when known
+    pub driver: Option<String>,
+    pub driver_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JavaInfo {
+    pub path: PathBuf,
+    pub version: String,
+    pub vendor: Option<String>,
+    pub arch: Option<String>,
+}
+
+/// Server response for an uploaded report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupportReportResult {
+    /// Reference to paste in the support channel
+    pub id: i64,
+}
+
+/// Body of `POST /api/support/reports`
+#[derive(Debug, Serialize)]
+struct SupportReportUpload<'a> {
+    report: &'a SystemReport,
+    note: Option<&'a str>,
+}
+
+/// Gather the system report for a game directory
+///
+/// Every part is best effort: a report with gaps is still more than support
+/// would otherwise get.
+pub async fn collect_system_report(game_dir: &Path, java_path: Option<&Path>) -> Result<SystemReport> {
+    let (os, cpu, memory) = tokio::task::spawn_blocking(read_host_info)
+        .await
+        .context("Failed to read system information")?;
+
+    let gpus = match detect_gpus().await {
+        Ok(gpus) => gpus,
+        Err(e) => {
+            eprintln!("[SystemReport] Could not detect GPUs: {}", e);
+            Vec::new()
+        }
+    };
+
+    let modpack_version = get_installed_version(&game_dir.to_path_buf()).await.ok().flatten();
+
+    let last_good = load_last_known_good(game_dir).await.ok().flatten();
+    let minecraft_version = last_good
+        .as_ref()
+        .and_then(|lg| minecraft_version_from_id(&lg.version_id));
+
+    let java_path = java_path
+        .map(Path::to_path_buf)
+        .or_else(|| last_good.and_then(|lg| lg.java_path));
+    let java = match java_path {
+        Some(path) => match probe_java(&path, "launch").await {
+            Ok(java) => Some(JavaInfo {
+                path: java.path,
+                version: java.version,
+                vendor: java.vendor,
+                arch: java.arch,
+            }),
+            Err(e) => {
+                eprintln!("[SystemReport] Could not determine Java version: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    Ok(SystemReport {
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        launcher_version: env!("CARGO_PKG_VERSION").to_string(),
+        os,
+        cpu,
+        memory,
+        gpus,
+        java,
+        modpack_version,
+        minecraft_version,
+    })
+}
+
+fn read_host_info() -> (OsInfo, CpuInfo, MemoryInfo) {
+    let system = System::new_with_specifics(
+        RefreshKind::nothing()
+            .with_cpu(CpuRefreshKind::nothing())
+            .with_memory(MemoryRefreshKind::nothing().with_ram()),
+    );
+
+    let os = OsInfo {
+        family: std::env::consts::OS.to_string(),
+        name: System::long_os_version(),
+        version: System::os_version(),
+        kernel: System::kernel_version(),
+        arch: System::cpu_arch(),
+    };
+
+    let cpu = CpuInfo {
+        model: system
+            .cpus()
+            .first()
+            .map(|cpu| cpu.brand().trim().to_string())
+            .filter(|brand| !brand.is_empty()),
+        physical_cores: system.physical_core_count(),
+        logical_cores: system.cpus().len(),
+    };
+
+    let memory = MemoryInfo {
+        total_mb: system.total_memory() / 1024 / 1024,
+        available_mb: system.available_memory() / 1024 / 1024,
+    };
+
+    (os, cpu, memory)
+}
+
+/// Run a query tool and return its stdout
+async fn run_probe(program: &str, args: &[&str]) -> Result<String> {
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true);
+
+    #[cfg(windows)]
+    {
+        // CREATE_NO_WINDOW - don't flash a console while gathering the report
+        command.creation_flags(0x0800_0000);
+    }
+
+    let output = tokio::time::timeout(Duration::from_secs(GPU_PROBE_TIMEOUT_SECS), command.output())
+        .await
+        .with_context(|| format!("Timed out running {}", program))?
+        .with_context(|| format!("Failed to run {}", program))?;
+
+    if !output.status.success() {
+        anyhow::bail!("{} exited with {}", program, output.status);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(windows)]
+async fn detect_gpus() -> Result<Vec<GpuInfo>> {
+    let output = run_probe(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Get-CimInstance Win32_VideoController | Select-Object Name, DriverVersion, AdapterCompatibility | ConvertTo-Json",
+        ],
+    )
+    .await?;
+    parse_video_controllers(&output)
+}
+
+#[cfg(target_os = "macos")]
+async fn detect_gpus() -> Result<Vec<GpuInfo>> {
+    let output = run_probe("system_profiler", &["SPDisplaysDataType", "-json"]).await?;
+    parse_system_profiler(&output)
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+async fn detect_gpus() -> Result<Vec<GpuInfo>> {
+    let output = run_probe("lspci", &["-k"]).await?;
+    let mut gpus = parse_lspci(&output);
+
+    // lspci only knows the driver's name; NVIDIA's proprietary driver reports its version here
+    if let Ok(version) = tokio::fs::read_to_string("/proc/driver/nvidia/version").await {
+        let version = parse_nvidia_driver_version(&version);
+        for gpu in gpus.iter_mut().filter(|gpu| gpu.driver.as_deref() == Some("nvidia")) {
+            gpu.driver_version = version.clone();
+        }
+    }
+    Ok(gpus)
+}
+
+/// `ConvertTo-Json` output for `Win32_VideoController` (an object for one adapter, an array for several)
+#[cfg_attr(not(windows), allow(dead_code))]
+fn parse_video_controllers(json: &str) -> Result<Vec<GpuInfo>> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct VideoController {
+        name: Option<String>,
+        driver_version: Option<String>,
+        adapter_compatibility: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(VideoController),
+        Many(Vec<VideoController>),
+    }
+
+    if json.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let controllers = match serde_json::from_str(json).context("Unrecognized video controller list")? {
+        OneOrMany::One(controller) => vec![controller],
+        OneOrMany::Many(controllers) => controllers,
+    };
+
+    Ok(controllers
+        .into_iter()
+        .filter_map(|c| {
+            Some(GpuInfo {
+                name: c.name?,
+                driver: c.adapter_compatibility,
+                driver_version: c.driver_version,
+            })
+        })
+        .collect())
+}
+
+/// `system_profiler SPDisplaysDataType -json` output
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_system_profiler(json: &str) -> Result<Vec<GpuInfo>> {
+    let value: serde_json::Value = serde_json::from_str(json).context("Unrecognized system_profiler output")?;
+    let displays = value
+        .get("SPDisplaysDataType")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(displays
+        .iter()
+        .filter_map(|display| {
+            let name = display
+                .get("sppci_model")
+                .or_else(|| display.get("_name"))
+                .and_then(|v| v.as_str())?;
+            Some(GpuInfo {
+                name: name.to_string(),
+                driver: display
+                    .get("spdisplays_vendor")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string),
+                driver_version: None,
+            })
+        })
+        .collect())
+}
+
+/// Display devices from `lspci -k`, with the kernel driver bound to each
+#[cfg_attr(any(windows, target_os = "macos"), allow(dead_code))]
+fn parse_lspci(output: &str) -> Vec<GpuInfo> {
+    const DISPLAY_CLASSES: [&str; 3] = ["VGA compatible controller", "3D controller", "Display controller"];
+
+    let mut gpus = Vec::new();
+    let mut current: Option<GpuInfo> = None;
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            gpus.extend(current.take());
+            // `01:00.0 VGA compatible controller: NVIDIA Corporation AD104 [GeForce RTX 4070] (rev a1)`
+            let Some((_, rest)) = line.split_once(' ') else { continue };
+            let Some((class, name)) = rest.split_once(": ") else { continue };
+            if DISPLAY_CLASSES.contains(&class) {
+                current = Some(GpuInfo {
+                    name: name.trim().to_string(),
+                    driver: None,
+                    driver_version: None,
+                });
+            }
+        } else if let Some(gpu) = current.as_mut() {
+            if let Some(driver) = line.trim().strip_prefix("Kernel driver in use:") {
+                gpu.driver = Some(driver.trim().to_string());
+            }
+        }
+    }
+    gpus.extend(current);
+    gpus
+}
+
+/// `NVRM version: NVIDIA UNIX x86_64 Kernel Module  550.120  Fri Sep 13 ...` -> `550.120`
+#[cfg_attr(any(windows, target_os = "macos"), allow(dead_code))]
+fn parse_nvidia_driver_version(contents: &str) -> Option<String> {
+    let line = contents.lines().find(|line| line.starts_with("NVRM version:"))?;
+    line.split_whitespace()
+        .find(|word| word.contains('.') && word.chars().all(|c| c.is_ascii_digit() || c == '.'))
+        .map(str::to_string)
+}
+
+/// Send a system report to the release server so support can look it up by id
+pub async fn upload_system_report(
+    server_url: &str,
+    report: &SystemReport,
+    note: Option<&str>,
+) -> Result<SupportReportResult> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()?;
+
+    let url = format!("{}/api/support/reports", server_url.trim_end_matches('/'));
+    let response = client
+        .post(&url)
+        .json(&SupportReportUpload { report, note })
+        .send()
+        .await
+        .context("Failed to upload system report")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("System report upload failed with HTTP status {}: {}", status, body);
+    }
+
+    response
+        .json::<SupportReportResult>()
+        .await
+        .context("Failed to parse system report upload response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lspci() {
+        let output = "\
+00:02.0 VGA compatible controller: Intel Corporation Raptor Lake-S GT1 [UHD Graphics 770] (rev 04)
+\tSubsystem: ASUSTeK Computer Inc. Device 8882
+\tKernel driver in use: i915
+\tKernel modules: i915, xe
+00:14.0 USB controller: Intel Corporation Raptor Lake USB 3.2 Gen 2x2 XHCI Host Controller (rev 11)
+\tKernel driver in use: xhci_hcd
+01:00.0 VGA compatible controller: NVIDIA Corporation AD104 [GeForce RTX 4070] (rev a1)
+\tKernel driver in use: nvidia
+\tKernel modules: nouveau, nvidia_drm, nvidia
+";
+        let gpus = parse_lspci(output);
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[0].name, "Intel Corporation Raptor Lake-S GT1 [UHD Graphics 770] (rev 04)");
+        assert_eq!(gpus[0].driver.as_deref(), Some("i915"));
+        assert_eq!(gpus[1].name, "NVIDIA Corporation AD104 [GeForce RTX 4070] (rev a1)");
+        assert_eq!(gpus[1].driver.as_deref(), Some("nvidia"));
+    }
+
+    #[test]
+    fn test_parse_nvidia_driver_version() {
+        let contents = "NVRM version: NVIDIA UNIX x86_64 Kernel Module  550.120  Fri Sep 13 10:10:01 UTC 2024\n\
+                        GCC version:  gcc version 13.2.0";
+        assert_eq!(parse_nvidia_driver_version(contents).as_deref(), Some("550.120"));
+        assert_eq!(parse_nvidia_driver_version("garbage"), None);
+    }
+
+    #[test]
+    fn test_parse_video_controllers() {
+        let one = r#"{"Name":"NVIDIA GeForce RTX 3060","DriverVersion":"32.0.15.6094","AdapterCompatibility":"NVIDIA"}"#;
+        let gpus = parse_video_controllers(one).unwrap();
+        assert_eq!(gpus.len(), 1);
+        assert_eq!(gpus[0].driver_version.as_deref(), Some("32.0.15.6094"));
+
+        let many = r#"[
+            {"Name":"Intel(R) UHD Graphics","DriverVersion":"31.0.101.2125","AdapterCompatibility":"Intel Corporation"},
+            {"Name":"NVIDIA GeForce RTX 3060","DriverVersion":"32.0.15.6094","AdapterCompatibility":"NVIDIA"}
+        ]"#;
+        let gpus = parse_video_controllers(many).unwrap();
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[1].name, "NVIDIA GeForce RTX 3060");
+        assert_eq!(gpus[1].driver.as_deref(), Some("NVIDIA"));
+
+        assert!(parse_video_controllers("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_system_profiler() {
+        let json = r#"{"SPDisplaysDataType":[{"_name":"kHW_AppleM2Item","sppci_model":"Apple M2","spdisplays_vendor":"sppci_vendor_Apple"}]}"#;
+        let gpus = parse_system_profiler(json).unwrap();
+        assert_eq!(gpus, vec![GpuInfo {
+            name: "Apple M2".to_string(),
+            driver: Some("sppci_vendor_Apple".to_string()),
+            driver_version: None,
+        }]);
+    }
+}
@@ -0,0 +1,189 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::install_id;
+
+/// Coarse failure cause, so reports never contain paths or other personal data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    Network,
+    Timeout,
+    Server,
+    Checksum,
+    DiskSpace,
+    Filesystem,
+    Other,
+}
+
+/// Classify an update error by walking its cause chain
+pub fn categorize(error: &anyhow::Error) -> ErrorCategory {
+    for cause in error.chain() {
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            return if e.is_timeout() {
+                ErrorCategory::Timeout
+            } else if e.is_status() {
+                ErrorCategory::Server
+            } else {
+                ErrorCategory::Network
+            };
+        }
+        let message = cause.to_string();
+        if message.starts_with("Hash mismatch") {
+            return ErrorCategory::Checksum;
+        }
+        if message.starts_with("Insufficient disk space") {
+            return ErrorCategory::DiskSpace;
+        }
+        if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+            return if e.kind() == std::io::ErrorKind::TimedOut {
+                ErrorCategory::Timeout
+            } else {
+                ErrorCategory::Filesystem
+            };
+        }
+    }
+    ErrorCategory::Other
+}
+
+/// Body of POST /api/telemetry/update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateReport {
+    pub version: String,
+    pub from_version: Option<String>,
+    pub success: bool,
+    pub duration_ms: u64,
+    pub bytes: u64,
+    pub files: usize,
+    pub error_category: Option<ErrorCategory>,
+    pub launcher_version: String,
+    pub os: String,
+}
+
+/// Measures one install or update from the download progress callback
+#[derive(Clone)]
+pub struct UpdateTracker {
+    started: Instant,
+    from_version: Option<String>,
+    bytes: Arc<AtomicU64>,
+    files: Arc<AtomicUsize>,
+}
+
+impl UpdateTracker {
+    pub fn start(from_version: Option<String>) -> Self {
+        Self {
+            started: Instant::now(),
+            from_version,
+            bytes: Arc::new(AtomicU64::new(0)),
+            files: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Record cumulative progress as reported by the installer
+    pub fn progress(&self, files: usize, bytes: u64) {
+        self.files.fetch_max(files, Ordering::Relaxed);
+        self.bytes.fetch_max(bytes, Ordering::Relaxed);
+    }
+
+    pub fn finish(&self, version: &str, error: Option<&anyhow::Error>) -> UpdateReport {
+        UpdateReport {
+            version: version.to_string(),
+            from_version: self.from_version.clone(),
+            success: error.is_none(),
+            duration_ms: self.started.elapsed().as_millis() as u64,
+            bytes: self.bytes.load(Ordering::Relaxed),
+            files: self.files.load(Ordering::Relaxed),
+            error_category: error.map(categorize),
+            launcher_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+        }
+    }
+}
+
+/// Send an update report to the release server
+pub async fn send_update_report(server_url: &str, report: &UpdateReport) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let url = format!("{}/api/telemetry/update", server_url.trim_end_matches('/'));
+    let response = install_id::apply(client.post(&url), &url)
+        .json(report)
+        .send()
+        .await
+        .context("Failed to send update report")?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Update report rejected with HTTP status {}", response.status());
+    }
+    Ok(())
+}
+
+/// Send a report without delaying the install; failures are only logged
+pub fn report_in_background(server_url: String, report: UpdateReport) {
+    tokio::spawn(async move {
+        if let Err(e) = send_update_report(&server_url, &report).await {
+            eprintln!("[Telemetry] {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_categorize_errors() {
+        let checksum = anyhow::anyhow!("Hash mismatch for mods/a.jar: expected aa, got bb")
+            .context("Download failures: 1 files failed");
+        assert_eq!(categorize(&checksum), ErrorCategory::Checksum);
+
+        let disk = anyhow::anyhow!("Insufficient disk space: 10 MB available, 200 MB required");
+        assert_eq!(categorize(&disk), ErrorCategory::DiskSpace);
+
+        let io = anyhow::Error::new(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied"))
+            .context("Failed to write chunk");
+        assert_eq!(categorize(&io), ErrorCategory::Filesystem);
+
+        assert_eq!(categorize(&anyhow::anyhow!("something else")), ErrorCategory::Other);
+    }
+
+    #[test]
+    fn test_tracker_keeps_highest_progress() {
+        let tracker = UpdateTracker::start(Some("1.0.0".to_string()));
+        tracker.progress(3, 3000);
+        tracker.progress(2, 2000);
+
+        let report = tracker.finish("1.1.0", None);
+        assert!(report.success);
+        assert_eq!(report.files, 3);
+        assert_eq!(report.bytes, 3000);
+        assert_eq!(report.from_version.as_deref(), Some("1.0.0"));
+        assert_eq!(report.error_category, None);
+    }
+
+    #[tokio::test]
+    async fn test_send_update_report() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/telemetry/update"))
+            .and(body_partial_json(serde_json::json!({
+                "version": "1.1.0",
+                "success": false,
+                "error_category": "disk_space",
+            })))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let error = anyhow::anyhow!("Insufficient disk space: 10 MB available, 200 MB required");
+        let report = UpdateTracker::start(None).finish("1.1.0", Some(&error));
+        send_update_report(&format!("{}/", server.uri()), &report).await.unwrap();
+    }
+}
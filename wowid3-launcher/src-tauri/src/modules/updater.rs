@@ -2,23 +2,34 @@ use anyhow::{Context, Result};
 use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use sysinfo::Disks;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
-use tokio::sync::Mutex;
 
 use walkdir::WalkDir;
 
+use super::config_merge;
+use super::manifest_signature;
+use super::mod_loader::ModLoader;
+use super::optional_mods;
+use super::verify_cache::{self, VerifyCache};
+use super::install_id;
+use super::http::{self, RetryPolicy, SendRetrying};
+use super::download_token;
 use super::download_manager::{
-    calculate_optimal_concurrency, DownloadManager, DownloadPriority, DownloadTask, HashType,
+    begin_install, calculate_optimal_concurrency, track_batch_progress, BatchProgress, DownloadManager,
+    DownloadPriority, DownloadTask, HashType,
 };
 
 const MAX_DOWNLOAD_RETRIES: u32 = 3;
 const MANIFEST_FETCH_TIMEOUT_SECS: u64 = 10;
 const MANIFEST_HASH_FILE: &str = ".wowid3-manifest-hash";
+/// Copy of the manifest the game directory was last installed or repaired from
+pub const INSTALLED_MANIFEST_FILE: &str = ".wowid3-manifest.json";
+const PATCH_FETCH_TIMEOUT_SECS: u64 = 60;
+const MAX_CONCURRENT_PATCHES: usize = 8;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ManifestFile {
@@ -26,31 +37,96 @@ pub struct ManifestFile {
     pub url: String,
     pub sha256: String,
     pub size: u64,
+    /// Same file on mirror servers; the downloader picks the fastest and fails over
+    #[serde(default)]
+    pub mirrors: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Manifest {
     pub version: String,
     pub minecraft_version: String,
+    /// Loader the pack runs on; packs published before this field existed are Fabric
+    #[serde(default)]
+    pub loader: ModLoader,
+    /// Version of `loader`
     pub fabric_loader: String,
     pub files: Vec<ManifestFile>,
     pub changelog: String,
     #[serde(default)]
     pub ignore_patterns: Vec<String>,
+    #[serde(default)]
+    pub optional_groups: Vec<OptionalGroup>,
+    /// This release's exceptions to the server blacklist (already applied to `ignore_patterns`)
+    #[serde(default)]
+    pub ignore_overrides: IgnorePatternOverrides,
 }
 
-/// Check for modpack updates by fetching the manifest
-pub async fn check_for_updates(manifest_url: &str) -> Result<Manifest> {
-    eprintln!("[Updater] Fetching manifest from: {}", manifest_url);
+/// Blacklist exceptions a release ships with
+///
+/// Files matching a `remove` pattern are force-reset: they're overwritten even when the
+/// player edited them, but cleanup still never deletes them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IgnorePatternOverrides {
+    #[serde(default)]
+    pub add: Vec<String>,
+    #[serde(default)]
+    pub remove: Vec<String>,
+}
+
+/// Set of manifest files players can switch on or off (e.g. shaders, minimap)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionalGroup {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub default_enabled: bool,
+    /// Paths of the manifest files that belong to this group
+    pub files: Vec<String>,
+}
 
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(MANIFEST_FETCH_TIMEOUT_SECS))
-        .build()
-        .context("Failed to create HTTP client")?;
+/// Binary patch for a single file between two releases
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilePatch {
+    pub path: String,
+    pub from_sha256: String,
+    pub to_sha256: String,
+    /// URL of the bsdiff patch blob
+    pub url: String,
+    pub size: u64,
+}
 
-    let response = client
+/// Response of `/api/patch/:installed/:latest`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchSet {
+    pub from_version: String,
+    pub to_version: String,
+    #[serde(default)]
+    pub patches: Vec<FilePatch>,
+}
+
+/// Check for modpack updates by fetching the manifest
+///
+/// `channel` selects a release channel (e.g. `beta`); `None` or `stable` uses
+/// the manifest URL as-is. The body must carry a valid server signature when the
+/// build has a manifest key.
+pub async fn check_for_updates(manifest_url: &str, channel: Option<&str>) -> Result<Manifest> {
+    eprintln!("[Updater] Fetching manifest from: {} (channel: {})", manifest_url, channel.unwrap_or("stable"));
+
+    install_id::set_server_url(manifest_url);
+    download_token::ensure(manifest_url).await;
+    let request = http::client()
         .get(manifest_url)
-        .send()
+        .timeout(Duration::from_secs(MANIFEST_FETCH_TIMEOUT_SECS));
+    let mut request = install_id::apply(request, manifest_url);
+    if let Some(channel) = channel.filter(|c| !c.is_empty() && *c != "stable") {
+        request = request.query(&[("channel", channel)]);
+    }
+
+    let response = request
+        .send_retrying(RetryPolicy::default())
         .await
         .context(format!(
             "Failed to fetch manifest from URL '{}'. Check your network connection and verify the server is reachable.",
@@ -67,12 +143,22 @@ pub async fn check_for_updates(manifest_url: &str) -> Result<Manifest> {
         );
     }
 
+    let signature = response
+        .headers()
+        .get(manifest_signature::SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = response
+        .bytes()
+        .await
+        .context("Failed to read manifest response")?;
+    manifest_signature::verify_manifest_body(&body, signature.as_deref())?;
+
     eprintln!("[Updater] Manifest fetched successfully, parsing JSON...");
 
-    let manifest: Manifest = response
-        .json()
-        .await
+    let manifest: Manifest = serde_json::from_slice(&body)
         .context("Failed to parse manifest JSON - server returned invalid JSON")?;
+    manifest_signature::mark_verified(&manifest);
 
     eprintln!("[Updater] Manifest parsed successfully: version {}", manifest.version);
     eprintln!("[Updater] Manifest contains {} files", manifest.files.len());
@@ -128,6 +214,28 @@ async fn save_manifest_hash(game_dir: &PathBuf, hash: &str) -> Result<()> {
     Ok(())
 }
 
+/// Keep the manifest next to the files it describes, for checks that run offline
+async fn save_installed_manifest(game_dir: &Path, manifest: &Manifest) -> Result<()> {
+    let json = serde_json::to_string(manifest).context("Failed to serialize manifest")?;
+    fs::write(game_dir.join(INSTALLED_MANIFEST_FILE), json)
+        .await
+        .context("Failed to write installed manifest")?;
+    Ok(())
+}
+
+/// The manifest saved at the last install or repair (`None` for installs older than this file)
+pub async fn load_installed_manifest(game_dir: &Path) -> Result<Option<Manifest>> {
+    let path = game_dir.join(INSTALLED_MANIFEST_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json = fs::read_to_string(&path)
+        .await
+        .context("Failed to read installed manifest")?;
+    let manifest = serde_json::from_str(&json).context("Failed to parse installed manifest")?;
+    Ok(Some(manifest))
+}
+
 /// Check if manifest has changed
 pub async fn has_manifest_changed(manifest: &Manifest, game_dir: &PathBuf) -> Result<bool> {
     let current_hash = calculate_manifest_hash(manifest);
@@ -142,14 +250,7 @@ async fn verify_file_checksum(file_path: &PathBuf, expected_sha256: &str) -> Res
         return Ok(false);
     }
 
-    let bytes = fs::read(file_path)
-        .await
-        .context("Failed to read file for checksum verification")?;
-
-    let mut hasher = Sha256::new();
-    hasher.update(&bytes);
-    let hash = format!("{:x}", hasher.finalize());
-
+    let hash = verify_cache::sha256_file(file_path).await?;
     Ok(hash == expected_sha256)
 }
 
@@ -202,7 +303,9 @@ pub async fn download_file(file: &ManifestFile, base_dir: &PathBuf) -> Result<()
     }
 
     // Download file
-    let response = reqwest::get(&file.url)
+    let response = http::client()
+        .get(&file.url)
+        .send()
         .await
         .context(format!("Failed to download file from {}", file.url))?;
 
@@ -269,25 +372,31 @@ pub async fn update_version_file(game_dir: &PathBuf, version: &str) -> Result<()
     Ok(())
 }
 
-/// Clean up extra files not in the manifest, respecting ignore patterns from server
-async fn cleanup_extra_files(manifest: &Manifest, game_dir: &PathBuf) -> Result<()> {
+/// Files in the game directory the manifest doesn't account for (relative, `/`-separated)
+///
+/// Respects ignore patterns from the server and never lists launcher meta files or
+/// the base Minecraft installation. Files of optional groups the player disabled
+/// count as extra.
+async fn find_extra_files(manifest: &Manifest, game_dir: &PathBuf) -> Result<Vec<String>> {
+    let disabled_files = optional_mods::disabled_files_in(manifest, game_dir).await;
     let game_dir = game_dir.clone();
     let manifest_files: std::collections::HashSet<String> = manifest
         .files
         .iter()
         .map(|f| f.path.replace('\\', "/"))
+        .filter(|path| !disabled_files.contains(path))
         .collect();
 
-    let ignore_patterns = manifest.ignore_patterns.clone();
-
-    println!("[Cleanup] Starting cleanup of extra files...");
+    // Files a release lifted from the blacklist are reset, never deleted
+    let mut ignore_patterns = manifest.ignore_patterns.clone();
+    ignore_patterns.extend(manifest.ignore_overrides.remove.iter().cloned());
     println!("[Cleanup] Using {} ignore patterns from server", ignore_patterns.len());
 
     // Compile ignore patterns into GlobSet BEFORE entering spawn_blocking
     let glob_set = compile_ignore_patterns(&ignore_patterns)?;
 
     tokio::task::spawn_blocking(move || {
-        let mut removed_count = 0;
+        let mut extra = Vec::new();
         let mut kept_count = 0;
 
         let walker = WalkDir::new(&game_dir).follow_links(false);
@@ -318,10 +427,14 @@ async fn cleanup_extra_files(manifest: &Manifest, game_dir: &PathBuf) -> Result<
             // CRITICAL: Never delete launcher meta files and base Minecraft installation
             if relative_path == ".wowid3-version"
                 || relative_path == ".wowid3-manifest-hash"
+                || relative_path == INSTALLED_MANIFEST_FILE
+                || relative_path == super::offline::LAST_KNOWN_GOOD_FILE
+                || relative_path == optional_mods::SELECTIONS_FILE
                 || relative_path.starts_with("versions/")  // Protect Minecraft base installation
                 || relative_path.starts_with(".cache/")    // Protect caches
                 || relative_path.starts_with("assets/")    // Protect Minecraft assets
                 || relative_path.starts_with("libraries/") // Protect Minecraft libraries
+                || config_merge::is_merge_artifact(&relative_path, &manifest_files)
             {
                 kept_count += 1;
                 continue;
@@ -334,23 +447,31 @@ async fn cleanup_extra_files(manifest: &Manifest, game_dir: &PathBuf) -> Result<
                 continue;
             }
 
-            // If we got here, delete it
-            println!("[Cleanup] DELETING: {}", relative_path);
-            if let Err(e) = std::fs::remove_file(path) {
-                eprintln!("[Cleanup] Failed to delete {}: {}", relative_path, e);
-            } else {
-                removed_count += 1;
-            }
+            extra.push(relative_path);
         }
 
-        println!(
-            "[Cleanup] Finished. Removed {} files, kept {} ignored files.",
-            removed_count, kept_count
-        );
-        Ok(())
+        println!("[Cleanup] Found {} extra files, kept {} ignored files.", extra.len(), kept_count);
+        extra.sort();
+        extra
     })
     .await
-    .context("Cleanup task panicked")?
+    .context("Cleanup task panicked")
+}
+
+/// Clean up extra files not in the manifest (see `find_extra_files`)
+async fn cleanup_extra_files(manifest: &Manifest, game_dir: &PathBuf) -> Result<()> {
+    println!("[Cleanup] Starting cleanup of extra files...");
+    let mut removed_count = 0;
+    for relative_path in find_extra_files(manifest, game_dir).await? {
+        println!("[Cleanup] DELETING: {}", relative_path);
+        if let Err(e) = fs::remove_file(game_dir.join(&relative_path)).await {
+            eprintln!("[Cleanup] Failed to delete {}: {}", relative_path, e);
+        } else {
+            removed_count += 1;
+        }
+    }
+    println!("[Cleanup] Finished. Removed {} files.", removed_count);
+    Ok(())
 }
 
 /// Check if there's enough disk space for the download
@@ -465,76 +586,331 @@ fn matches_ignore_pattern(file_path: &str, glob_set: &GlobSet) -> bool {
     matched
 }
 
+/// How a manifest file on disk compares to the manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileStatus {
+    Ok,
+    Missing,
+    Corrupted,
+    /// A shipped config the player has edited since; left alone
+    LocallyModified,
+    /// Blacklisted file that exists, or an optional mod the player disabled
+    Skipped,
+}
+
+/// Check one manifest file against the game directory
+/// RESPECTS BLACKLIST: existing blacklisted files are never reported as corrupted
+async fn file_status(
+    file: &ManifestFile,
+    game_dir: &PathBuf,
+    glob_set: &GlobSet,
+    forced_set: &GlobSet,
+    disabled_files: &std::collections::HashSet<String>,
+    cache: &VerifyCache,
+) -> FileStatus {
+    let file_path = game_dir.join(&file.path);
+    let relative_path = file.path.replace('\\', "/");
+
+    if disabled_files.contains(&relative_path) {
+        eprintln!("[Delta] Optional mod disabled, skipping: {}", relative_path);
+        return FileStatus::Skipped;
+    }
+
+    // CRITICAL: Check if file is blacklisted
+    if matches_ignore_pattern(&relative_path, glob_set) {
+        // If file doesn't exist, download it (first install)
+        // If file exists, NEVER touch it (user may have modified it)
+        if !file_path.exists() {
+            eprintln!("[Delta] Blacklisted but missing, downloading: {}", relative_path);
+            return FileStatus::Missing;
+        }
+        eprintln!("[Delta] Blacklisted and exists, skipping: {}", relative_path);
+        return FileStatus::Skipped;
+    }
+
+    // Normal file handling (not blacklisted)
+    // Download if file doesn't exist or checksum doesn't match
+    if !file_path.exists() {
+        eprintln!("[Delta] Missing: {}", file.path);
+        return FileStatus::Missing;
+    }
+    match cache.verify(&relative_path, &file_path, &file.sha256).await {
+        // File exists and checksum matches, skip
+        Ok(true) => FileStatus::Ok,
+        Ok(false) => {
+            // Configs the user tweaked after we shipped this version stay as they are,
+            // unless the release force-resets them
+            if !forced_set.is_match(&relative_path) && config_merge::is_user_modified(game_dir, file).await {
+                eprintln!("[Delta] Locally edited config, keeping: {}", file.path);
+                return FileStatus::LocallyModified;
+            }
+            // Checksum mismatch, need to re-download
+            eprintln!("[Delta] Checksum mismatch: {} (expected: {}, size in manifest: {})", file.path, file.sha256, file.size);
+            FileStatus::Corrupted
+        }
+        Err(e) => {
+            // Error verifying checksum, re-download to be safe
+            eprintln!("[Delta] Error verifying {}: {}. Will re-download.", file.path, e);
+            FileStatus::Corrupted
+        }
+    }
+}
+
+/// Check every manifest file, hashing several at once off the async runtime
+///
+/// Results are in manifest order. Stamps of files that verified are saved for the next check.
+async fn manifest_file_statuses(
+    manifest: &Manifest,
+    game_dir: &PathBuf,
+    cache: VerifyCache,
+) -> Result<Vec<FileStatus>> {
+    use futures::stream::{self, StreamExt};
+
+    let disabled_files = optional_mods::disabled_files_in(manifest, game_dir).await;
+
+    // Compile ignore patterns into GlobSet for efficient matching
+    let glob_set = compile_ignore_patterns(&manifest.ignore_patterns)?;
+    let forced_set = compile_ignore_patterns(&manifest.ignore_overrides.remove)?;
+
+    let statuses = stream::iter(&manifest.files)
+        .map(|file| file_status(file, game_dir, &glob_set, &forced_set, &disabled_files, &cache))
+        .buffered(verify_cache::hash_concurrency())
+        .collect()
+        .await;
+
+    if let Err(e) = cache.save(game_dir).await {
+        eprintln!("[Delta] {}", e);
+    }
+    Ok(statuses)
+}
+
 /// Determine which files need to be downloaded (delta update)
 /// RESPECTS BLACKLIST: Will NOT download blacklisted files if they already exist
+/// Files of optional groups the player disabled are never downloaded
+///
+/// Files unchanged since they last verified (same size and mtime) aren't rehashed.
 pub async fn get_files_to_download(
     manifest: &Manifest,
     game_dir: &PathBuf,
 ) -> Result<Vec<ManifestFile>> {
-    let mut files_to_download = Vec::new();
-    let ignore_patterns = &manifest.ignore_patterns;
+    eprintln!(
+        "[Delta] Checking {} files against {} ignore patterns",
+        manifest.files.len(),
+        manifest.ignore_patterns.len()
+    );
 
-    // Compile ignore patterns into GlobSet for efficient matching
-    let glob_set = compile_ignore_patterns(ignore_patterns)?;
+    let cache = VerifyCache::load(game_dir).await;
+    let statuses = manifest_file_statuses(manifest, game_dir, cache).await?;
 
-    eprintln!("[Delta] Checking {} files against {} ignore patterns", manifest.files.len(), ignore_patterns.len());
+    Ok(manifest
+        .files
+        .iter()
+        .zip(statuses)
+        .filter(|(_, status)| matches!(status, FileStatus::Missing | FileStatus::Corrupted))
+        .map(|(file, _)| file.clone())
+        .collect())
+}
 
-    for file in &manifest.files {
-        let file_path = game_dir.join(&file.path);
-        let relative_path = file.path.replace('\\', "/");
+/// Stamp files the download manager just fetched and hash-checked, so the next check skips them
+async fn record_downloaded_files(game_dir: &PathBuf, files: &[ManifestFile]) {
+    let cache = VerifyCache::load(game_dir).await;
+    for file in files {
+        cache
+            .record(&file.path.replace('\\', "/"), &game_dir.join(&file.path), &file.sha256)
+            .await;
+    }
+    if let Err(e) = cache.save(game_dir).await {
+        eprintln!("[Delta] {}", e);
+    }
+}
 
-        // CRITICAL: Check if file is blacklisted
-        if matches_ignore_pattern(&relative_path, &glob_set) {
-            // If file doesn't exist, download it (first install)
-            // If file exists, NEVER touch it (user may have modified it)
-            if !file_path.exists() {
-                eprintln!("[Delta] Blacklisted but missing, downloading: {}", relative_path);
-                files_to_download.push(file.clone());
-            } else {
-                eprintln!("[Delta] Blacklisted and exists, skipping: {}", relative_path);
-            }
-            continue;
+/// Calculate total size of files to download
+pub fn calculate_total_size(files: &[ManifestFile]) -> u64 {
+    files.iter().map(|f| f.size).sum()
+}
+
+/// Derive the server base URL from manifest file URLs (`{base}/files/{version}/{path}`)
+fn derive_server_base_url(manifest: &Manifest) -> Option<String> {
+    manifest
+        .files
+        .iter()
+        .find_map(|f| f.url.split_once("/files/").map(|(base, _)| base.to_string()))
+}
+
+/// Fetch the patch set between two versions; `None` when the server has no patches
+async fn fetch_patch_set(
+    base_url: &str,
+    installed: &str,
+    latest: &str,
+) -> Result<Option<PatchSet>> {
+    let url = format!("{}/api/patch/{}/{}", base_url, installed, latest);
+    eprintln!("[Patch] Fetching patch set from: {}", url);
+
+    let response = http::client()
+        .get(&url)
+        .timeout(Duration::from_secs(PATCH_FETCH_TIMEOUT_SECS))
+        .send_retrying(RetryPolicy::default())
+        .await
+        .context("Failed to request patch set")?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    let patch_set: PatchSet = response
+        .error_for_status()
+        .context("Patch set request failed")?
+        .json()
+        .await
+        .context("Failed to parse patch set JSON")?;
+
+    Ok(Some(patch_set))
+}
+
+/// Download and apply a single binary patch, writing the result only if it verifies
+async fn apply_file_patch(
+    patch: &FilePatch,
+    game_dir: &PathBuf,
+) -> Result<()> {
+    let file_path = game_dir.join(&patch.path);
+
+    if !verify_file_checksum(&file_path, &patch.from_sha256).await? {
+        anyhow::bail!("Local file does not match patch base");
+    }
+
+    let old = fs::read(&file_path)
+        .await
+        .context("Failed to read file to patch")?;
+
+    let patch_bytes = http::client()
+        .get(&patch.url)
+        .timeout(Duration::from_secs(PATCH_FETCH_TIMEOUT_SECS))
+        .send_retrying(RetryPolicy::default())
+        .await
+        .context("Failed to download patch")?
+        .error_for_status()
+        .context("Patch download failed")?
+        .bytes()
+        .await
+        .context("Failed to read patch bytes")?;
+
+    let new = tokio::task::spawn_blocking(move || {
+        let mut new = Vec::new();
+        bsdiff::patch(&old, &mut patch_bytes.as_ref(), &mut new).map(|_| new)
+    })
+    .await
+    .context("Patch task panicked")?
+    .context("Failed to apply binary patch")?;
+
+    // Verify SHA256 of the patched output before touching the real file
+    let mut hasher = Sha256::new();
+    hasher.update(&new);
+    let hash = format!("{:x}", hasher.finalize());
+    if !hash.eq_ignore_ascii_case(&patch.to_sha256) {
+        anyhow::bail!(
+            "Checksum mismatch after patching: expected {}, got {}",
+            patch.to_sha256,
+            hash
+        );
+    }
+
+    let temp_path = file_path.with_file_name(format!(
+        ".{}.patched",
+        file_path.file_name().and_then(|n| n.to_str()).unwrap_or("file")
+    ));
+    fs::write(&temp_path, &new)
+        .await
+        .context("Failed to write patched file")?;
+    fs::rename(&temp_path, &file_path)
+        .await
+        .context("Failed to replace file with patched version")?;
+
+    Ok(())
+}
+
+/// Try to bring files up to date with binary patches instead of full downloads
+///
+/// Returns the files that still need a full download: anything without a patch,
+/// any patch that fails to apply, and any patched output that fails SHA256 verification.
+pub async fn apply_patches(
+    manifest: &Manifest,
+    game_dir: &PathBuf,
+    installed_version: &str,
+    files_to_download: Vec<ManifestFile>,
+) -> Vec<ManifestFile> {
+    use futures::stream::{self, StreamExt};
+
+    let Some(base_url) = derive_server_base_url(manifest) else {
+        return files_to_download;
+    };
+
+    let patch_set = match fetch_patch_set(&base_url, installed_version, &manifest.version).await {
+        Ok(Some(patch_set)) => patch_set,
+        Ok(None) => {
+            eprintln!("[Patch] No patches available, using full downloads");
+            return files_to_download;
         }
+        Err(e) => {
+            eprintln!("[Patch] Failed to fetch patch set: {}. Using full downloads", e);
+            return files_to_download;
+        }
+    };
 
-        // Normal file handling (not blacklisted)
-        // Download if file doesn't exist or checksum doesn't match
-        if !file_path.exists() {
-            eprintln!("[Delta] Missing: {}", file.path);
-            files_to_download.push(file.clone());
-        } else {
-            match verify_file_checksum(&file_path, &file.sha256).await {
-                Ok(true) => {
-                    // File exists and checksum matches, skip
-                    continue;
-                }
-                Ok(false) => {
-                    // Checksum mismatch, need to re-download
-                    eprintln!("[Delta] Checksum mismatch: {} (expected: {}, size in manifest: {})", file.path, file.sha256, file.size);
-                    files_to_download.push(file.clone());
-                }
-                Err(e) => {
-                    // Error verifying checksum, re-download to be safe
-                    eprintln!("[Delta] Error verifying {}: {}. Will re-download.", file.path, e);
-                    files_to_download.push(file.clone());
+    let patches: std::collections::HashMap<String, FilePatch> = patch_set
+        .patches
+        .into_iter()
+        .map(|p| (p.path.replace('\\', "/"), p))
+        .collect();
+
+    let results: Vec<(ManifestFile, bool)> = stream::iter(files_to_download)
+        .map(|file| {
+            let patch = patches
+                .get(&file.path.replace('\\', "/"))
+                .filter(|p| p.to_sha256.eq_ignore_ascii_case(&file.sha256))
+                .cloned();
+            async move {
+                let Some(patch) = patch else {
+                    return (file, false);
+                };
+                match apply_file_patch(&patch, game_dir).await {
+                    Ok(()) => {
+                        eprintln!("[Patch] Patched: {}", file.path);
+                        (file, true)
+                    }
+                    Err(e) => {
+                        eprintln!("[Patch] Falling back to full download for {}: {}", file.path, e);
+                        (file, false)
+                    }
                 }
             }
-        }
-    }
+        })
+        .buffer_unordered(MAX_CONCURRENT_PATCHES)
+        .collect()
+        .await;
 
-    Ok(files_to_download)
-}
+    let patched = results.iter().filter(|(_, ok)| *ok).count();
+    eprintln!("[Patch] Applied {} of {} patches", patched, patches.len());
 
-/// Calculate total size of files to download
-pub fn calculate_total_size(files: &[ManifestFile]) -> u64 {
-    files.iter().map(|f| f.size).sum()
+    results
+        .into_iter()
+        .filter(|(_, ok)| !ok)
+        .map(|(file, _)| file)
+        .collect()
 }
 
 /// Install or update modpack with delta updates
 pub async fn install_modpack(
     manifest: &Manifest,
     game_dir: &PathBuf,
-    progress_callback: impl Fn(usize, usize, String, u64, u64) + Send + Sync + 'static,
+    progress_callback: impl Fn(BatchProgress) + Send + Sync + 'static,
 ) -> Result<()> {
+    manifest_signature::ensure_verified(manifest)?;
+    // Long installs may outlive the token fetched with the manifest
+    if let Some(base_url) = derive_server_base_url(manifest) {
+        download_token::ensure(&base_url).await;
+    }
+    let cancel = begin_install();
+
     // Ensure game directory exists
     if !game_dir.exists() {
         fs::create_dir_all(game_dir)
@@ -543,7 +919,28 @@ pub async fn install_modpack(
     }
 
     // Determine which files need downloading (delta update)
-    let files_to_download = get_files_to_download(manifest, game_dir).await?;
+    let mut files_to_download = get_files_to_download(manifest, game_dir).await?;
+
+    // Capture the user's config files before they're overwritten so edits can be merged back;
+    // files the release force-resets are replaced outright
+    let forced_set = compile_ignore_patterns(&manifest.ignore_overrides.remove)?;
+    let mergeable: Vec<ManifestFile> = files_to_download
+        .iter()
+        .filter(|file| !forced_set.is_match(file.path.replace('\\', "/")))
+        .cloned()
+        .collect();
+    let pending_merges = config_merge::snapshot_user_configs(game_dir, &mergeable).await;
+
+    // Prefer binary patches when upgrading from an older installed version
+    if !files_to_download.is_empty() {
+        if let Some(installed) = get_installed_version(game_dir).await? {
+            if installed != manifest.version {
+                files_to_download =
+                    apply_patches(manifest, game_dir, &installed, files_to_download).await;
+            }
+        }
+    }
+    cancel.check()?;
 
     if !files_to_download.is_empty() {
         // Check disk space
@@ -559,7 +956,8 @@ pub async fn install_modpack(
         // Create download manager with optimal concurrency
         let concurrency = calculate_optimal_concurrency();
         let download_manager = DownloadManager::new(concurrency, MAX_DOWNLOAD_RETRIES)
-            .context("Failed to create download manager")?;
+            .context("Failed to create download manager")?
+            .with_cancel(cancel.clone());
 
         // Convert manifest files to download tasks
         let tasks: Vec<DownloadTask> = files_to_download
@@ -570,54 +968,47 @@ pub async fn install_modpack(
                 expected_hash: HashType::Sha256(file.sha256.clone()),
                 priority: DownloadPriority::Low, // Modpack files are lower priority than game files
                 size: file.size,
+                mirrors: file.mirrors.clone(),
             })
             .collect();
 
         // Track progress across all parallel downloads
-        let (progress_tx, mut progress_rx) =
+        let (progress_tx, progress_rx) =
             tokio::sync::mpsc::channel::<super::download_manager::DownloadProgress>(100);
-        let total_files = files_to_download.len();
-        let bytes_downloaded = Arc::new(Mutex::new(0u64));
-        let files_completed = Arc::new(Mutex::new(0usize));
-
-        // Spawn progress tracking task
-        let bytes_downloaded_clone = bytes_downloaded.clone();
-        let files_completed_clone = files_completed.clone();
-        let progress_task = tokio::spawn(async move {
-            while let Some(progress) = progress_rx.recv().await {
-                if progress.completed {
-                    let mut completed = files_completed_clone.lock().await;
-                    *completed += 1;
-                    let mut bytes = bytes_downloaded_clone.lock().await;
-                    *bytes += progress.total_bytes;
-                    let current_completed = *completed;
-                    let current_bytes = *bytes;
-                    drop(completed);
-                    drop(bytes);
-
-                    progress_callback(
-                        current_completed,
-                        total_files,
-                        progress.url.clone(),
-                        current_bytes,
-                        total_bytes,
-                    );
-                }
-            }
-        });
+        let progress_task = tokio::spawn(track_batch_progress(
+            progress_rx,
+            files_to_download.len(),
+            total_bytes,
+            progress_callback,
+        ));
 
         // Download all files in parallel
-        download_manager
-            .download_files(tasks, Some(progress_tx))
-            .await
-            .context("Failed to download modpack files")?;
+        let downloaded = download_manager.download_files(tasks, Some(progress_tx)).await;
 
         // Wait for progress tracking to complete
         progress_task.await?;
+
+        if let Err(e) = downloaded {
+            // Files finish whole or not at all; configs already replaced keep the user's edits
+            let replaced = config_merge::retain_replaced(game_dir, pending_merges).await;
+            config_merge::merge_user_configs(game_dir, replaced).await?;
+            return Err(e).context("Failed to download modpack files");
+        }
+        record_downloaded_files(game_dir, &files_to_download).await;
     } else {
         println!("All files up to date, no downloads needed");
     }
 
+    let merge_summary = config_merge::merge_user_configs(game_dir, pending_merges).await?;
+    if !merge_summary.conflicts.is_empty() {
+        eprintln!(
+            "[ConfigMerge] {} config file(s) had conflicting changes; shipped versions saved with a {} suffix",
+            merge_summary.conflicts.len(),
+            config_merge::CONFLICT_SUFFIX
+        );
+    }
+    config_merge::record_shipped_configs(game_dir, &manifest.files).await?;
+
     // Clean up extra files not in the manifest
     cleanup_extra_files(manifest, game_dir).await?;
 
@@ -627,18 +1018,78 @@ pub async fn install_modpack(
     // Save manifest hash to detect future changes
     let manifest_hash = calculate_manifest_hash(manifest);
     save_manifest_hash(game_dir, &manifest_hash).await?;
+    save_installed_manifest(game_dir, manifest).await?;
 
     println!("Modpack installation complete: version {}", manifest.version);
     Ok(())
 }
 
+/// Build the URL of a specific release's manifest from the configured manifest URL
+///
+/// `https://host/api/manifest/latest?channel=beta` becomes `https://host/api/manifest/{version}`.
+pub fn versioned_manifest_url(manifest_url: &str, version: &str) -> Result<String> {
+    if version.is_empty()
+        || version.contains(['/', '\\', '?', '#'])
+        || version.contains("..")
+    {
+        anyhow::bail!("Invalid modpack version: '{}'", version);
+    }
+
+    let (base, _) = manifest_url.split_once("/api/manifest").ok_or_else(|| {
+        anyhow::anyhow!(
+            "Cannot derive a versioned manifest URL from '{}' (expected .../api/manifest/latest)",
+            manifest_url
+        )
+    })?;
+
+    Ok(format!("{}/api/manifest/{}", base, version))
+}
+
+/// Roll back to an earlier modpack release
+///
+/// Fetches that release's manifest and installs it like an update: changed files are
+/// re-downloaded and files not in the older manifest are removed. Returns the manifest installed.
+pub async fn rollback_modpack(
+    manifest_url: &str,
+    version: &str,
+    game_dir: &PathBuf,
+    progress_callback: impl Fn(BatchProgress) + Send + Sync + 'static,
+) -> Result<Manifest> {
+    let url = versioned_manifest_url(manifest_url, version)?;
+    let manifest = check_for_updates(&url, None)
+        .await
+        .with_context(|| format!("Failed to fetch manifest for version {}", version))?;
+
+    if manifest.version != version {
+        anyhow::bail!(
+            "Server returned manifest for version {} instead of {}",
+            manifest.version,
+            version
+        );
+    }
+
+    eprintln!(
+        "[Updater] Rolling back to version {} (installed: {:?})",
+        version,
+        get_installed_version(game_dir).await.ok().flatten()
+    );
+
+    install_modpack(&manifest, game_dir, progress_callback).await?;
+    Ok(manifest)
+}
+
 /// Verify and repair modpack - checks all files against manifest checksums
 /// and re-downloads any corrupted files, even if version matches
 pub async fn verify_and_repair_modpack(
     manifest: &Manifest,
     game_dir: &PathBuf,
-    progress_callback: impl Fn(usize, usize, String, u64, u64) + Send + Sync + 'static,
+    progress_callback: impl Fn(BatchProgress) + Send + Sync + 'static,
 ) -> Result<()> {
+    manifest_signature::ensure_verified(manifest)?;
+    if let Some(base_url) = derive_server_base_url(manifest) {
+        download_token::ensure(&base_url).await;
+    }
+
     // Ensure game directory exists
     if !game_dir.exists() {
         fs::create_dir_all(game_dir)
@@ -656,6 +1107,7 @@ pub async fn verify_and_repair_modpack(
         // Still run cleanup even if no repairs needed
         println!("[Repair] Running cleanup to remove extra files...");
         cleanup_extra_files(manifest, game_dir).await?;
+        save_installed_manifest(game_dir, manifest).await?;
         return Ok(());
     }
 
@@ -677,7 +1129,8 @@ pub async fn verify_and_repair_modpack(
     // Create download manager with optimal concurrency
     let concurrency = calculate_optimal_concurrency();
     let download_manager = DownloadManager::new(concurrency, MAX_DOWNLOAD_RETRIES)
-        .context("Failed to create download manager")?;
+        .context("Failed to create download manager")?
+        .with_cancel(begin_install());
 
     // Convert manifest files to download tasks
     let tasks: Vec<DownloadTask> = files_to_repair
@@ -688,41 +1141,19 @@ pub async fn verify_and_repair_modpack(
             expected_hash: HashType::Sha256(file.sha256.clone()),
             priority: DownloadPriority::Low,
             size: file.size,
+            mirrors: file.mirrors.clone(),
         })
         .collect();
 
     // Track progress across all parallel downloads
-    let (progress_tx, mut progress_rx) =
+    let (progress_tx, progress_rx) =
         tokio::sync::mpsc::channel::<super::download_manager::DownloadProgress>(100);
-    let total_files = files_to_repair.len();
-    let bytes_downloaded = Arc::new(Mutex::new(0u64));
-    let files_completed = Arc::new(Mutex::new(0usize));
-
-    // Spawn progress tracking task
-    let bytes_downloaded_clone = bytes_downloaded.clone();
-    let files_completed_clone = files_completed.clone();
-    let progress_task = tokio::spawn(async move {
-        while let Some(progress) = progress_rx.recv().await {
-            if progress.completed {
-                let mut completed = files_completed_clone.lock().await;
-                *completed += 1;
-                let mut bytes = bytes_downloaded_clone.lock().await;
-                *bytes += progress.total_bytes;
-                let current_completed = *completed;
-                let current_bytes = *bytes;
-                drop(completed);
-                drop(bytes);
-
-                progress_callback(
-                    current_completed,
-                    total_files,
-                    progress.url.clone(),
-                    current_bytes,
-                    total_bytes,
-                );
-            }
-        }
-    });
+    let progress_task = tokio::spawn(track_batch_progress(
+        progress_rx,
+        files_to_repair.len(),
+        total_bytes,
+        progress_callback,
+    ));
 
     // Download all corrupted files in parallel
     download_manager
@@ -732,14 +1163,18 @@ pub async fn verify_and_repair_modpack(
 
     // Wait for progress tracking to complete
     progress_task.await?;
+    record_downloaded_files(game_dir, &files_to_repair).await;
 
     // Clean up extra files not in the manifest
     println!("[Repair] Running cleanup to remove extra files...");
     cleanup_extra_files(manifest, game_dir).await?;
 
+    config_merge::record_shipped_configs(game_dir, &manifest.files).await?;
+
     // Save manifest hash to prevent re-detection of these files on next repair
     let manifest_hash = calculate_manifest_hash(manifest);
     save_manifest_hash(game_dir, &manifest_hash).await?;
+    save_installed_manifest(game_dir, manifest).await?;
 
     println!("[Repair] ✓ Modpack repair complete!");
     println!("[Repair] Repaired {} files", files_to_repair.len());
@@ -747,6 +1182,93 @@ pub async fn verify_and_repair_modpack(
     Ok(())
 }
 
+/// A manifest file that is missing or doesn't match its checksum
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileIssue {
+    pub path: String,
+    pub size: u64,
+}
+
+/// Result of checking an installation against its manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationReport {
+    pub manifest_version: String,
+    pub files_checked: usize,
+    pub missing: Vec<FileIssue>,
+    pub corrupted: Vec<FileIssue>,
+    /// Files the manifest doesn't account for (removed by a repair)
+    pub extra: Vec<String>,
+    /// Shipped configs the player edited; a repair keeps them
+    pub locally_modified: Vec<String>,
+    /// Bytes a repair would download
+    pub repair_bytes: u64,
+    /// Whether the issues above were fixed
+    pub repaired: bool,
+}
+
+impl VerificationReport {
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.corrupted.is_empty() && self.extra.is_empty()
+    }
+}
+
+/// Check every manifest file and look for extra files, without changing anything
+///
+/// Only files whose size or mtime changed since they last verified are hashed, unless
+/// `deep` asks for every file to be rehashed.
+pub async fn verify_installation(
+    manifest: &Manifest,
+    game_dir: &PathBuf,
+    deep: bool,
+) -> Result<VerificationReport> {
+    let cache = if deep {
+        VerifyCache::untrusted()
+    } else {
+        VerifyCache::load(game_dir).await
+    };
+    let statuses = manifest_file_statuses(manifest, game_dir, cache).await?;
+
+    let mut report = VerificationReport {
+        manifest_version: manifest.version.clone(),
+        files_checked: 0,
+        missing: Vec::new(),
+        corrupted: Vec::new(),
+        extra: Vec::new(),
+        locally_modified: Vec::new(),
+        repair_bytes: 0,
+        repaired: false,
+    };
+
+    for (file, status) in manifest.files.iter().zip(statuses) {
+        if status != FileStatus::Skipped {
+            report.files_checked += 1;
+        }
+        let issue = || FileIssue {
+            path: file.path.replace('\\', "/"),
+            size: file.size,
+        };
+        match status {
+            FileStatus::Missing => report.missing.push(issue()),
+            FileStatus::Corrupted => report.corrupted.push(issue()),
+            FileStatus::LocallyModified => report.locally_modified.push(issue().path),
+            FileStatus::Ok | FileStatus::Skipped => {}
+        }
+    }
+
+    report.repair_bytes = report.missing.iter().chain(&report.corrupted).map(|f| f.size).sum();
+    report.extra = find_extra_files(manifest, game_dir).await?;
+
+    println!(
+        "[Verify] {} files checked: {} missing, {} corrupted, {} extra",
+        report.files_checked,
+        report.missing.len(),
+        report.corrupted.len(),
+        report.extra.len()
+    );
+
+    Ok(report)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -832,12 +1354,14 @@ mod tests {
                 url: "http://example.com/file1.txt".to_string(),
                 sha256: "abc123".to_string(),
                 size: 1024,
+                mirrors: vec![],
             },
             ManifestFile {
                 path: "file2.txt".to_string(),
                 url: "http://example.com/file2.txt".to_string(),
                 sha256: "def456".to_string(),
                 size: 2048,
+                mirrors: vec![],
             },
         ];
 
@@ -852,6 +1376,7 @@ mod tests {
         let manifest = Manifest {
             version: "1.0.0".to_string(),
             minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
             fabric_loader: "0.15.0".to_string(),
             changelog: "Initial release".to_string(),
             ignore_patterns: vec![],
@@ -861,8 +1386,11 @@ mod tests {
                     url: "http://example.com/mod1.jar".to_string(),
                     sha256: "abc123".to_string(),
                     size: 1024,
+                    mirrors: vec![],
                 },
             ],
+            optional_groups: vec![],
+            ignore_overrides: Default::default(),
         };
 
         let files_to_download = get_files_to_download(&manifest, &temp_dir.path().to_path_buf())
@@ -890,6 +1418,7 @@ mod tests {
         let manifest = Manifest {
             version: "1.0.0".to_string(),
             minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
             fabric_loader: "0.15.0".to_string(),
             changelog: "Update".to_string(),
             ignore_patterns: vec![],
@@ -899,14 +1428,18 @@ mod tests {
                     url: "http://example.com/mod1.jar".to_string(),
                     sha256: checksum.clone(),
                     size: 12,
+                    mirrors: vec![],
                 },
                 ManifestFile {
                     path: "mods/mod2.jar".to_string(),
                     url: "http://example.com/mod2.jar".to_string(),
                     sha256: "newfile".to_string(),
                     size: 2048,
+                    mirrors: vec![],
                 },
             ],
+            optional_groups: vec![],
+            ignore_overrides: Default::default(),
         };
 
         let files_to_download = get_files_to_download(&manifest, &temp_dir.path().to_path_buf())
@@ -931,6 +1464,7 @@ mod tests {
         let manifest = Manifest {
             version: "1.0.0".to_string(),
             minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
             fabric_loader: "0.15.0".to_string(),
             changelog: "Update".to_string(),
             ignore_patterns: vec![],
@@ -940,8 +1474,11 @@ mod tests {
                     url: "http://example.com/mod1.jar".to_string(),
                     sha256: "wrongchecksum".to_string(),
                     size: 1024,
+                    mirrors: vec![],
                 },
             ],
+            optional_groups: vec![],
+            ignore_overrides: Default::default(),
         };
 
         let files_to_download = get_files_to_download(&manifest, &temp_dir.path().to_path_buf())
@@ -958,9 +1495,10 @@ mod tests {
 #[cfg(test)]
 mod integration_tests {
     use super::*;
+    use crate::modules::download_manager::sha256_hex;
     use sha2::{Digest, Sha256};
     use tempfile::TempDir;
-    use wiremock::matchers::{method, path};
+    use wiremock::matchers::{method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[tokio::test]
@@ -982,7 +1520,7 @@ mod integration_tests {
             .await;
 
         let url = format!("{}/manifest.json", &mock_server.uri());
-        let result = check_for_updates(&url).await;
+        let result = check_for_updates(&url, None).await;
 
         assert!(result.is_ok());
         let manifest = result.unwrap();
@@ -993,7 +1531,7 @@ mod integration_tests {
     #[tokio::test]
     async fn test_check_for_updates_network_error() {
         // Use an invalid URL to trigger network error
-        let result = check_for_updates("http://localhost:1/nonexistent").await;
+        let result = check_for_updates("http://localhost:1/nonexistent", None).await;
         assert!(result.is_err());
     }
 
@@ -1008,11 +1546,192 @@ mod integration_tests {
             .await;
 
         let url = format!("{}/manifest.json", &mock_server.uri());
-        let result = check_for_updates(&url).await;
+        let result = check_for_updates(&url, None).await;
 
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_versioned_manifest_url() {
+        assert_eq!(
+            versioned_manifest_url("https://example.com/api/manifest/latest", "1.0.0").unwrap(),
+            "https://example.com/api/manifest/1.0.0"
+        );
+        assert_eq!(
+            versioned_manifest_url("https://example.com/api/manifest/latest?channel=beta", "1.1.0-beta.1").unwrap(),
+            "https://example.com/api/manifest/1.1.0-beta.1"
+        );
+        assert!(versioned_manifest_url("https://example.com/manifest.json", "1.0.0").is_err());
+        assert!(versioned_manifest_url("https://example.com/api/manifest/latest", "../admin").is_err());
+        assert!(versioned_manifest_url("https://example.com/api/manifest/latest", "").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rollback_modpack_removes_newer_files() {
+        let mock_server = MockServer::start().await;
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path().to_path_buf();
+
+        let content = b"old mod";
+        let mut hasher = Sha256::new();
+        hasher.update(content);
+        let sha256 = format!("{:x}", hasher.finalize());
+
+        let manifest_json = format!(
+            r#"{{
+                "version": "1.0.0",
+                "minecraft_version": "1.20.1",
+                "fabric_loader": "0.15.0",
+                "changelog": "Old release",
+                "files": [{{
+                    "path": "mods/old.jar",
+                    "url": "{}/files/1.0.0/mods/old.jar",
+                    "sha256": "{}",
+                    "size": {}
+                }}]
+            }}"#,
+            mock_server.uri(),
+            sha256,
+            content.len()
+        );
+
+        Mock::given(method("GET"))
+            .and(path("/api/manifest/1.0.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(manifest_json))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/files/1.0.0/mods/old.jar"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(content.to_vec()))
+            .mount(&mock_server)
+            .await;
+
+        // Files from the bad release
+        fs::create_dir_all(game_dir.join("mods")).await.unwrap();
+        fs::write(game_dir.join("mods/new.jar"), b"broken mod").await.unwrap();
+        update_version_file(&game_dir, "1.1.0").await.unwrap();
+
+        let url = format!("{}/api/manifest/latest", mock_server.uri());
+        let manifest = rollback_modpack(&url, "1.0.0", &game_dir, |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(manifest.version, "1.0.0");
+        assert!(game_dir.join("mods/old.jar").exists());
+        assert!(!game_dir.join("mods/new.jar").exists());
+        assert_eq!(get_installed_version(&game_dir).await.unwrap().as_deref(), Some("1.0.0"));
+    }
+
+    fn config_manifest(server: &str, version: &str, content: &[u8]) -> Manifest {
+        Manifest {
+            version: version.to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
+            fabric_loader: "0.15.0".to_string(),
+            files: vec![ManifestFile {
+                path: "config/sodium-options.properties".to_string(),
+                url: format!("{}/files/{}/config/sodium-options.properties", server, version),
+                sha256: format!("{:x}", Sha256::digest(content)),
+                size: content.len() as u64,
+                mirrors: vec![],
+            }],
+            changelog: String::new(),
+            ignore_patterns: Vec::new(),
+            optional_groups: vec![],
+            ignore_overrides: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_install_modpack_keeps_user_config_edits() {
+        let mock_server = MockServer::start().await;
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path().to_path_buf();
+        let config_path = game_dir.join("config/sodium-options.properties");
+
+        let v1 = b"quality=high\nvsync=true\n";
+        let v2 = b"quality=high\nvsync=true\nfog=false\n";
+        for (version, content) in [("1.0.0", &v1[..]), ("1.1.0", &v2[..])] {
+            Mock::given(method("GET"))
+                .and(path(format!("/files/{}/config/sodium-options.properties", version)))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(content.to_vec()))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let v1_manifest = config_manifest(&mock_server.uri(), "1.0.0", v1);
+        install_modpack(&v1_manifest, &game_dir, |_| {}).await.unwrap();
+
+        // A local tweak survives repairs of the same version...
+        fs::write(&config_path, b"quality=low\nvsync=true\n").await.unwrap();
+        assert!(get_files_to_download(&v1_manifest, &game_dir).await.unwrap().is_empty());
+
+        // ...and is merged with the shipped change on update
+        let v2_manifest = config_manifest(&mock_server.uri(), "1.1.0", v2);
+        install_modpack(&v2_manifest, &game_dir, |_| {}).await.unwrap();
+
+        let merged = fs::read_to_string(&config_path).await.unwrap();
+        assert_eq!(merged, "quality=low\nvsync=true\nfog=false\n");
+        assert!(game_dir.join(config_merge::CONFIG_BASE_DIR).join("config/sodium-options.properties").exists());
+    }
+
+    #[tokio::test]
+    async fn test_ignore_override_forces_config_reset() {
+        let mock_server = MockServer::start().await;
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path().to_path_buf();
+        let config_path = game_dir.join("config/sodium-options.properties");
+
+        let v1 = b"quality=high\nvsync=true\n";
+        let v2 = b"quality=high\nvsync=false\n";
+        for (version, content) in [("1.0.0", &v1[..]), ("1.1.0", &v2[..])] {
+            Mock::given(method("GET"))
+                .and(path(format!("/files/{}/config/sodium-options.properties", version)))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(content.to_vec()))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let v1_manifest = config_manifest(&mock_server.uri(), "1.0.0", v1);
+        install_modpack(&v1_manifest, &game_dir, |_| {}).await.unwrap();
+        fs::write(&config_path, b"quality=low\nvsync=true\n").await.unwrap();
+        fs::write(game_dir.join("config/sodium-extra.properties"), b"user file").await.unwrap();
+
+        // The release lifts the config from the blacklist: the local edit is replaced, not merged
+        let mut v2_manifest = config_manifest(&mock_server.uri(), "1.1.0", v2);
+        v2_manifest.ignore_overrides.remove = vec!["config/sodium-*.properties".to_string()];
+        install_modpack(&v2_manifest, &game_dir, |_| {}).await.unwrap();
+
+        assert_eq!(fs::read(&config_path).await.unwrap(), v2);
+        // Unlisted files matching the lifted pattern are still never deleted
+        assert!(game_dir.join("config/sodium-extra.properties").exists());
+    }
+
+    #[tokio::test]
+    async fn test_check_for_updates_beta_channel() {
+        let mock_server = MockServer::start().await;
+
+        let manifest_json = r#"{
+            "version": "1.1.0-beta.1",
+            "minecraft_version": "1.20.1",
+            "fabric_loader": "0.15.0",
+            "changelog": "Beta release",
+            "files": []
+        }"#;
+
+        Mock::given(method("GET"))
+            .and(path("/manifest.json"))
+            .and(query_param("channel", "beta"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(manifest_json))
+            .mount(&mock_server)
+            .await;
+
+        let url = format!("{}/manifest.json", &mock_server.uri());
+        let manifest = check_for_updates(&url, Some("beta")).await.unwrap();
+
+        assert_eq!(manifest.version, "1.1.0-beta.1");
+    }
+
     #[tokio::test]
     async fn test_download_file_success() {
         let mock_server = MockServer::start().await;
@@ -1034,6 +1753,7 @@ mod integration_tests {
             url: format!("{}/test.txt", &mock_server.uri()),
             sha256: checksum.clone(),
             size: file_content.len() as u64,
+            mirrors: vec![],
         };
 
         let result = download_file(&file, &temp_dir.path().to_path_buf()).await;
@@ -1062,6 +1782,7 @@ mod integration_tests {
             url: format!("{}/test.txt", &mock_server.uri()),
             sha256: "wrongchecksum".to_string(),
             size: file_content.len() as u64,
+            mirrors: vec![],
         };
 
         let result = download_file(&file, &temp_dir.path().to_path_buf()).await;
@@ -1098,6 +1819,7 @@ mod integration_tests {
             url: format!("{}/test.txt", &mock_server.uri()),
             sha256: checksum.clone(),
             size: file_content.len() as u64,
+            mirrors: vec![],
         };
 
         let result = download_file_with_retry(&file, &temp_dir.path().to_path_buf(), 3).await;
@@ -1121,6 +1843,7 @@ mod integration_tests {
             url: format!("{}/test.txt", &mock_server.uri()),
             sha256: "somechecksum".to_string(),
             size: 100,
+            mirrors: vec![],
         };
 
         let result = download_file_with_retry(&file, &temp_dir.path().to_path_buf(), 2).await;
@@ -1162,6 +1885,7 @@ mod integration_tests {
         let manifest = Manifest {
             version: "1.0.0".to_string(),
             minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
             fabric_loader: "0.15.0".to_string(),
             changelog: "Initial release".to_string(),
             ignore_patterns: vec![],
@@ -1171,21 +1895,25 @@ mod integration_tests {
                     url: format!("{}/mod1.jar", &mock_server.uri()),
                     sha256: checksum1,
                     size: file1_content.len() as u64,
+                    mirrors: vec![],
                 },
                 ManifestFile {
                     path: "mods/mod2.jar".to_string(),
                     url: format!("{}/mod2.jar", &mock_server.uri()),
                     sha256: checksum2,
                     size: file2_content.len() as u64,
+                    mirrors: vec![],
                 },
             ],
+            optional_groups: vec![],
+            ignore_overrides: Default::default(),
         };
 
-        let result = install_modpack(&manifest, &temp_dir.path().to_path_buf(), |current, total, filename, _current_bytes, _total_bytes| {
+        let result = install_modpack(&manifest, &temp_dir.path().to_path_buf(), |progress| {
             // Verify progress callback is called with reasonable values
-            assert!(current <= total);
-            assert!(total == 2); // We have 2 files
-            assert!(!filename.is_empty());
+            assert!(progress.files_completed <= progress.total_files);
+            assert!(progress.total_files == 2); // We have 2 files
+            assert!(progress.bytes_downloaded <= progress.total_bytes);
         })
         .await;
 
@@ -1233,6 +1961,7 @@ mod integration_tests {
         let manifest = Manifest {
             version: "1.1.0".to_string(),
             minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
             fabric_loader: "0.15.0".to_string(),
             changelog: "Update".to_string(),
             ignore_patterns: vec![],
@@ -1242,21 +1971,27 @@ mod integration_tests {
                     url: format!("{}/mod1.jar", &mock_server.uri()),
                     sha256: checksum1,
                     size: file1_content.len() as u64,
+                    mirrors: vec![],
                 },
                 ManifestFile {
                     path: "mods/mod2.jar".to_string(),
                     url: format!("{}/mod2.jar", &mock_server.uri()),
                     sha256: checksum2,
                     size: file2_content.len() as u64,
+                    mirrors: vec![],
                 },
             ],
+            optional_groups: vec![],
+            ignore_overrides: Default::default(),
         };
 
-        let result = install_modpack(&manifest, &temp_dir.path().to_path_buf(), |current, total, filename, _current_bytes, _total_bytes| {
+        let result = install_modpack(&manifest, &temp_dir.path().to_path_buf(), |progress| {
             // Only mod2.jar needs downloading, so total should be 1
-            assert_eq!(total, 1);
-            assert_eq!(current, 1);
-            assert!(!filename.is_empty());
+            assert_eq!(progress.total_files, 1);
+            if let Some(filename) = &progress.completed_file {
+                assert_eq!(progress.files_completed, 1);
+                assert!(filename.ends_with("/mod2.jar"));
+            }
         })
         .await;
 
@@ -1291,6 +2026,7 @@ mod integration_tests {
         let manifest = Manifest {
             version: "1.0.0".to_string(),
             minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
             fabric_loader: "0.15.0".to_string(),
             changelog: "No changes".to_string(),
             ignore_patterns: vec![],
@@ -1299,10 +2035,13 @@ mod integration_tests {
                 url: "http://example.com/mod1.jar".to_string(),
                 sha256: checksum,
                 size: file_content.len() as u64,
+                mirrors: vec![],
             }],
+            optional_groups: vec![],
+            ignore_overrides: Default::default(),
         };
 
-        let result = install_modpack(&manifest, &temp_dir.path().to_path_buf(), |_current, _total, _filename, _current_bytes, _total_bytes| {
+        let result = install_modpack(&manifest, &temp_dir.path().to_path_buf(), |_progress| {
             // Should never be called since no downloads needed
             panic!("Progress callback should not be called when no files need downloading");
         })
@@ -1317,6 +2056,136 @@ mod integration_tests {
         assert_eq!(version, Some("1.0.0".to_string()));
     }
 
+    async fn mount_patch_set(
+        mock_server: &MockServer,
+        old: &[u8],
+        new: &[u8],
+        patch_bytes: Vec<u8>,
+        to_sha256: &str,
+    ) {
+        let patch_set = PatchSet {
+            from_version: "1.0.0".to_string(),
+            to_version: "1.1.0".to_string(),
+            patches: vec![FilePatch {
+                path: "mods/mod1.jar".to_string(),
+                from_sha256: sha256_hex(old),
+                to_sha256: to_sha256.to_string(),
+                url: format!("{}/patches/mod1.jar.bsdiff", mock_server.uri()),
+                size: new.len() as u64,
+            }],
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/api/patch/1.0.0/1.1.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&patch_set))
+            .mount(mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/patches/mod1.jar.bsdiff"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(patch_bytes))
+            .mount(mock_server)
+            .await;
+    }
+
+    fn patch_manifest(mock_server: &MockServer, new: &[u8]) -> Manifest {
+        Manifest {
+            version: "1.1.0".to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
+            fabric_loader: "0.15.0".to_string(),
+            changelog: "Update".to_string(),
+            ignore_patterns: vec![],
+            files: vec![ManifestFile {
+                path: "mods/mod1.jar".to_string(),
+                url: format!("{}/files/1.1.0/mods/mod1.jar", mock_server.uri()),
+                sha256: sha256_hex(new),
+                size: new.len() as u64,
+                mirrors: vec![],
+            }],
+            optional_groups: vec![],
+            ignore_overrides: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_patches_updates_file_in_place() {
+        let mock_server = MockServer::start().await;
+        let temp_dir = TempDir::new().unwrap();
+
+        let old = b"mod1 content version one";
+        let new = b"mod1 content version two, now with more";
+        let mut patch_bytes = Vec::new();
+        bsdiff::diff(old, new, &mut patch_bytes).unwrap();
+
+        mount_patch_set(&mock_server, old, new, patch_bytes, &sha256_hex(new)).await;
+
+        let mods_dir = temp_dir.path().join("mods");
+        std::fs::create_dir_all(&mods_dir).unwrap();
+        std::fs::write(mods_dir.join("mod1.jar"), old).unwrap();
+
+        let manifest = patch_manifest(&mock_server, new);
+        let remaining = apply_patches(
+            &manifest,
+            &temp_dir.path().to_path_buf(),
+            "1.0.0",
+            manifest.files.clone(),
+        )
+        .await;
+
+        assert!(remaining.is_empty());
+        assert_eq!(std::fs::read(mods_dir.join("mod1.jar")).unwrap(), new);
+    }
+
+    #[tokio::test]
+    async fn test_apply_patches_falls_back_on_checksum_mismatch() {
+        let mock_server = MockServer::start().await;
+        let temp_dir = TempDir::new().unwrap();
+
+        let old = b"mod1 content version one";
+        let new = b"mod1 content version two, now with more";
+        let mut patch_bytes = Vec::new();
+        // Patch produces different output than the manifest expects
+        bsdiff::diff(old, b"something else entirely", &mut patch_bytes).unwrap();
+
+        mount_patch_set(&mock_server, old, new, patch_bytes, &sha256_hex(new)).await;
+
+        let mods_dir = temp_dir.path().join("mods");
+        std::fs::create_dir_all(&mods_dir).unwrap();
+        std::fs::write(mods_dir.join("mod1.jar"), old).unwrap();
+
+        let manifest = patch_manifest(&mock_server, new);
+        let remaining = apply_patches(
+            &manifest,
+            &temp_dir.path().to_path_buf(),
+            "1.0.0",
+            manifest.files.clone(),
+        )
+        .await;
+
+        // File is queued for full download and left untouched
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(std::fs::read(mods_dir.join("mod1.jar")).unwrap(), old);
+    }
+
+    #[tokio::test]
+    async fn test_apply_patches_without_patch_endpoint() {
+        let mock_server = MockServer::start().await;
+        let temp_dir = TempDir::new().unwrap();
+
+        let new = b"mod1 content";
+        let manifest = patch_manifest(&mock_server, new);
+        let remaining = apply_patches(
+            &manifest,
+            &temp_dir.path().to_path_buf(),
+            "1.0.0",
+            manifest.files.clone(),
+        )
+        .await;
+
+        assert_eq!(remaining.len(), 1);
+    }
+
     #[test]
     fn test_blacklist_pattern_matching() {
         // Test patterns that were previously failing
@@ -1379,10 +2248,13 @@ mod integration_tests {
         let manifest = Manifest {
             version: "1.0.0".to_string(),
             minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
             fabric_loader: "0.15.0".to_string(),
             changelog: "Test".to_string(),
             files: vec![],
             ignore_patterns: vec!["options.txt".to_string()],
+            optional_groups: vec![],
+            ignore_overrides: Default::default(),
         };
 
         let files_to_download = get_files_to_download(&manifest, &temp_dir.path().to_path_buf()).await.unwrap();
@@ -1395,6 +2267,57 @@ mod integration_tests {
         assert_eq!(content, "user customized options");
     }
 
+    #[tokio::test]
+    async fn test_disabled_optional_group_is_skipped_and_removed() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path().to_path_buf();
+        std::fs::create_dir_all(game_dir.join("mods")).unwrap();
+        std::fs::write(game_dir.join("mods/iris.jar"), "shader mod").unwrap();
+
+        let manifest = Manifest {
+            version: "1.0.0".to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
+            fabric_loader: "0.15.0".to_string(),
+            changelog: "Test".to_string(),
+            files: vec![
+                ManifestFile {
+                    path: "mods/iris.jar".to_string(),
+                    url: "http://example.com/iris.jar".to_string(),
+                    sha256: "different_hash".to_string(),
+                    size: 100,
+                    mirrors: vec![],
+                },
+                ManifestFile {
+                    path: "mods/xaero.jar".to_string(),
+                    url: "http://example.com/xaero.jar".to_string(),
+                    sha256: "different_hash".to_string(),
+                    size: 100,
+                    mirrors: vec![],
+                },
+            ],
+            ignore_patterns: vec![],
+            optional_groups: vec![OptionalGroup {
+                id: "shaders".to_string(),
+                name: "Shaders".to_string(),
+                description: String::new(),
+                default_enabled: true,
+                files: vec!["mods/iris.jar".to_string()],
+            }],
+            ignore_overrides: Default::default(),
+        };
+
+        optional_mods::set_selection(&game_dir, "shaders", false).await.unwrap();
+
+        let files_to_download = get_files_to_download(&manifest, &game_dir).await.unwrap();
+        assert_eq!(files_to_download.len(), 1);
+        assert_eq!(files_to_download[0].path, "mods/xaero.jar");
+
+        cleanup_extra_files(&manifest, &game_dir).await.unwrap();
+        assert!(!game_dir.join("mods/iris.jar").exists());
+        assert!(game_dir.join(optional_mods::SELECTIONS_FILE).exists());
+    }
+
     #[tokio::test]
     async fn test_config_files_protected_by_new_patterns() {
         let temp_dir = TempDir::new().unwrap();
@@ -1412,6 +2335,7 @@ mod integration_tests {
         let manifest = Manifest {
             version: "1.0.0".to_string(),
             minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
             fabric_loader: "0.15.0".to_string(),
             changelog: "Test".to_string(),
             files: vec![
@@ -1420,18 +2344,21 @@ mod integration_tests {
                     url: "http://example.com/xaerominimap.txt".to_string(),
                     sha256: "different_hash".to_string(),
                     size: 100,
+                    mirrors: vec![],
                 },
                 ManifestFile {
                     path: "config/xaeroworldmap.txt".to_string(),
                     url: "http://example.com/xaeroworldmap.txt".to_string(),
                     sha256: "different_hash".to_string(),
                     size: 100,
+                    mirrors: vec![],
                 },
                 ManifestFile {
                     path: "config/sodium-options.json".to_string(),
                     url: "http://example.com/sodium-options.json".to_string(),
                     sha256: "different_hash".to_string(),
                     size: 100,
+                    mirrors: vec![],
                 },
             ],
             ignore_patterns: vec![
@@ -1439,6 +2366,8 @@ mod integration_tests {
                 "config/xaeroworldmap*.txt".to_string(),
                 "config/sodium-options.json".to_string(),
             ],
+            optional_groups: vec![],
+            ignore_overrides: Default::default(),
         };
 
         let files_to_download = get_files_to_download(&manifest, &temp_dir.path().to_path_buf()).await.unwrap();
@@ -1451,6 +2380,54 @@ mod integration_tests {
         assert_eq!(std::fs::read_to_string(config_dir.join("xaeroworldmap.txt")).unwrap(), "user world map settings");
         assert_eq!(std::fs::read_to_string(config_dir.join("sodium-options.json")).unwrap(), "user sodium settings");
     }
+
+    #[tokio::test]
+    async fn test_verify_installation_reports_without_changing_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path().to_path_buf();
+        let file = |path: &str, contents: &str| ManifestFile {
+            path: path.to_string(),
+            url: format!("http://example.com/{}", path),
+            sha256: format!("{:x}", Sha256::digest(contents.as_bytes())),
+            size: contents.len() as u64,
+            mirrors: vec![],
+        };
+
+        std::fs::create_dir_all(game_dir.join("mods")).unwrap();
+        std::fs::write(game_dir.join("mods/good.jar"), "good").unwrap();
+        std::fs::write(game_dir.join("mods/broken.jar"), "truncat").unwrap();
+        std::fs::write(game_dir.join("mods/stray.jar"), "stray").unwrap();
+        std::fs::write(game_dir.join("options.txt"), "fov:90").unwrap();
+
+        let manifest = Manifest {
+            version: "1.2.0".to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
+            fabric_loader: "0.15.0".to_string(),
+            changelog: "Test".to_string(),
+            files: vec![
+                file("mods/good.jar", "good"),
+                file("mods/broken.jar", "truncated"),
+                file("mods/missing.jar", "missing"),
+            ],
+            ignore_patterns: vec!["options.txt".to_string()],
+            optional_groups: vec![],
+            ignore_overrides: Default::default(),
+        };
+
+        let report = verify_installation(&manifest, &game_dir, true).await.unwrap();
+        assert_eq!(report.manifest_version, "1.2.0");
+        assert_eq!(report.files_checked, 3);
+        assert_eq!(report.missing.iter().map(|f| f.path.as_str()).collect::<Vec<_>>(), vec!["mods/missing.jar"]);
+        assert_eq!(report.corrupted.iter().map(|f| f.path.as_str()).collect::<Vec<_>>(), vec!["mods/broken.jar"]);
+        assert_eq!(report.extra, vec!["mods/stray.jar"]);
+        assert_eq!(report.repair_bytes, "missing".len() as u64 + "truncated".len() as u64);
+        assert!(!report.repaired && !report.is_clean());
+
+        // Dry run: nothing was downloaded or deleted
+        assert!(game_dir.join("mods/stray.jar").exists());
+        assert_eq!(std::fs::read_to_string(game_dir.join("mods/broken.jar")).unwrap(), "truncat");
+    }
 }
 #[cfg(test)]
 mod test_glob_patterns {
@@ -0,0 +1,221 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Stamps of verified files, relative to the game directory (`.cache/` survives cleanup)
+pub const VERIFY_CACHE_FILE: &str = ".cache/verified-files.json";
+/// Files are hashed through a fixed buffer instead of being read whole
+const HASH_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// A file as it was when it last matched its manifest hash
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Stamp {
+    sha256: String,
+    size: u64,
+    modified_ns: u64,
+    /// Unix seconds of the hash check or verified download
+    #[serde(default)]
+    verified_at: u64,
+}
+
+impl Stamp {
+    fn of(metadata: &std::fs::Metadata, sha256: &str) -> Self {
+        Self {
+            sha256: sha256.to_ascii_lowercase(),
+            size: metadata.len(),
+            modified_ns: modified_ns(metadata),
+            verified_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+        }
+    }
+
+    /// Same content as far as size and mtime can tell
+    fn matches(&self, other: &Stamp) -> bool {
+        self.sha256 == other.sha256 && self.size == other.size && self.modified_ns == other.modified_ns
+    }
+}
+
+/// Persisted state of installed files: path -> size, mtime, sha256 and when it was verified
+///
+/// Stamps are written after hash checks and verified downloads. A file whose size and
+/// modification time still match its stamp is trusted without rehashing, so a routine
+/// check over an unchanged install only stats each file.
+#[derive(Debug, Default)]
+pub struct VerifyCache {
+    trusted: bool,
+    stamps: Mutex<HashMap<String, Stamp>>,
+}
+
+impl VerifyCache {
+    /// Stamps from earlier checks; a missing or unreadable cache starts empty
+    pub async fn load(game_dir: &Path) -> Self {
+        let stamps = tokio::fs::read_to_string(game_dir.join(VERIFY_CACHE_FILE))
+            .await
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            trusted: true,
+            stamps: Mutex::new(stamps),
+        }
+    }
+
+    /// Hash every file ("deep verify"), recording fresh stamps for the next check
+    pub fn untrusted() -> Self {
+        Self::default()
+    }
+
+    pub async fn save(&self, game_dir: &Path) -> Result<()> {
+        let json = {
+            let stamps = self.stamps.lock().unwrap_or_else(|e| e.into_inner());
+            serde_json::to_string(&*stamps)?
+        };
+        let path = game_dir.join(VERIFY_CACHE_FILE);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, json)
+            .await
+            .context("Failed to save verification cache")
+    }
+
+    /// Whether the file at `path` matches `expected_sha256` (false when it doesn't exist)
+    pub async fn verify(&self, relative_path: &str, path: &Path, expected_sha256: &str) -> Result<bool> {
+        let metadata = match tokio::fs::metadata(path).await {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e).context("Failed to read file metadata"),
+        };
+        let stamp = Stamp::of(&metadata, expected_sha256);
+
+        let trusted = self.trusted
+            && self
+                .stamps
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(relative_path)
+                .is_some_and(|known| known.matches(&stamp));
+        if trusted {
+            return Ok(true);
+        }
+
+        let matches = sha256_file(path).await?.eq_ignore_ascii_case(expected_sha256);
+        let mut stamps = self.stamps.lock().unwrap_or_else(|e| e.into_inner());
+        if matches {
+            stamps.insert(relative_path.to_string(), stamp);
+        } else {
+            stamps.remove(relative_path);
+        }
+        Ok(matches)
+    }
+
+    /// Stamp a file whose hash was already checked, e.g. by the download manager
+    pub async fn record(&self, relative_path: &str, path: &Path, sha256: &str) {
+        let Ok(metadata) = tokio::fs::metadata(path).await else {
+            return;
+        };
+        self.stamps
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(relative_path.to_string(), Stamp::of(&metadata, sha256));
+    }
+}
+
+fn modified_ns(metadata: &std::fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |since| since.as_nanos() as u64)
+}
+
+/// SHA256 of a file, hashed in chunks on the blocking pool
+pub async fn sha256_file(path: &Path) -> Result<String> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut file = std::fs::File::open(&path).context("Failed to open file for checksum verification")?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; HASH_CHUNK_SIZE];
+        loop {
+            let read = file.read(&mut buffer).context("Failed to read file for checksum verification")?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    })
+    .await
+    .context("Checksum task panicked")?
+}
+
+/// Files hashed at once: enough to keep a fast SSD busy without starving the game of cores
+pub fn hash_concurrency() -> usize {
+    num_cpus::get().clamp(2, 8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::download_manager::sha256_hex;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_sha256_file_spans_chunks() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("big.bin");
+        let content: Vec<u8> = (0..HASH_CHUNK_SIZE * 2 + 17).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&path, &content).unwrap();
+
+        assert_eq!(sha256_file(&path).await.unwrap(), sha256_hex(&content));
+    }
+
+    #[tokio::test]
+    async fn test_trusted_stamp_skips_rehash() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path();
+        let path = game_dir.join("mod.jar");
+        std::fs::write(&path, b"mod content").unwrap();
+        let expected = sha256_hex(b"mod content");
+
+        let cache = VerifyCache::untrusted();
+        assert!(cache.verify("mod.jar", &path, &expected).await.unwrap());
+        cache.save(game_dir).await.unwrap();
+
+        // Same size and mtime: the stamp is trusted even though the bytes changed
+        let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+        std::fs::write(&path, b"mod CONTENT").unwrap();
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(modified).unwrap();
+
+        let cache = VerifyCache::load(game_dir).await;
+        assert!(cache.verify("mod.jar", &path, &expected).await.unwrap());
+        assert!(!VerifyCache::untrusted().verify("mod.jar", &path, &expected).await.unwrap());
+
+        // A changed size invalidates the stamp
+        std::fs::write(&path, b"modified mod").unwrap();
+        assert!(!cache.verify("mod.jar", &path, &expected).await.unwrap());
+        assert!(!cache.verify("missing.jar", &game_dir.join("missing.jar"), &expected).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_recorded_download_is_trusted() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path();
+        let path = game_dir.join("mod.jar");
+        std::fs::write(&path, b"mod content").unwrap();
+
+        let cache = VerifyCache::load(game_dir).await;
+        // Recorded stamps are trusted as-is, so the wrong hash proves no rehash happened
+        cache.record("mod.jar", &path, "ABC123").await;
+        cache.save(game_dir).await.unwrap();
+
+        let cache = VerifyCache::load(game_dir).await;
+        assert!(cache.verify("mod.jar", &path, "abc123").await.unwrap());
+    }
+}
@@ -0,0 +1,193 @@
+// Linux backend: kernel WireGuard driven by wg-quick, elevated through pkexec
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::TunnelBackend;
+
+pub struct LinuxBackend;
+
+fn wg_quick_path() -> PathBuf {
+    which::which("wg-quick").unwrap_or_else(|_| PathBuf::from("/usr/bin/wg-quick"))
+}
+
+impl TunnelBackend for LinuxBackend {
+    fn is_wireguard_installed(&self) -> bool {
+        which::which("wg-quick").is_ok()
+    }
+
+    fn config_dir(&self) -> Result<PathBuf> {
+        let home = std::env::var("HOME")
+            .map_err(|_| anyhow::anyhow!("HOME environment variable not set"))?;
+        Ok(Path::new(&home).join(".config").join("wowid3-launcher").join("vpn"))
+    }
+
+    fn tunnel_exists(&self, config_path: &Path) -> bool {
+        self.is_wireguard_installed() && config_path.exists()
+    }
+
+    fn is_running(&self) -> bool {
+        // The interface is named after the config file
+        let output = Command::new("wg")
+            .args(&["show", "wowid3"])
+            .output();
+
+        output.map(|o| o.status.success()).unwrap_or(false)
+    }
+
+    /// wg-quick brings the interface up straight from the config file
+    fn install(&self, config_path: &Path) -> Result<()> {
+        if !config_path.exists() {
+            anyhow::bail!("VPN config not found; register this device first");
+        }
+        Ok(())
+    }
+
+    fn start(&self, config_path: &Path) -> Result<()> {
+        // Verify config exists
+        if !config_path.exists() {
+            return Err(anyhow::anyhow!(
+                "VPN config not found at: {}\nPlease complete VPN setup first.",
+                config_path.display()
+            ));
+        }
+
+        // Try to load WireGuard kernel module if not already loaded
+        eprintln!("[VPN] Checking WireGuard kernel module...");
+        let modprobe_result = Command::new("sudo")
+            .args(&["modprobe", "wireguard"])
+            .output();
+
+        match modprobe_result {
+            Ok(out) if !out.status.success() => {
+                let stderr = String::from_utf8_lossy(&out.stderr);
+                eprintln!("[VPN] Warning: Could not load wireguard module: {}", stderr);
+                eprintln!("[VPN] Continuing anyway (module might already be built-in)...");
+            }
+            Ok(_) => eprintln!("[VPN] WireGuard module loaded successfully"),
+            Err(e) => eprintln!("[VPN] Warning: Could not run modprobe: {}", e),
+        }
+
+        // Use full path to wg-quick
+        let wg_quick_path = wg_quick_path();
+
+        eprintln!("[VPN] Starting tunnel with config: {}", config_path.display());
+        eprintln!("[VPN] Using wg-quick at: {}", wg_quick_path.display());
+
+        // Try pkexec first (graphical sudo prompt)
+        let output = Command::new("pkexec")
+            .arg(&wg_quick_path)
+            .arg("up")
+            .arg(config_path)
+            .output();
+
+        match output {
+            Ok(out) => {
+                let stdout = String::from_utf8_lossy(&out.stdout);
+                let stderr = String::from_utf8_lossy(&out.stderr);
+
+                eprintln!("[VPN] Exit code: {:?}", out.status.code());
+                eprintln!("[VPN] Stdout: {}", stdout);
+                eprintln!("[VPN] Stderr: {}", stderr);
+
+                if out.status.success() {
+                    Ok(())
+                } else {
+                    // Check if pkexec was denied or failed
+                    if stderr.contains("dismissed") || stderr.contains("Not authorized") || out.status.code() == Some(127) {
+                        Err(anyhow::anyhow!(
+                            "PolicyKit authorization required. To fix this, configure passwordless sudo for wg-quick:\n\n\
+                            1. Run this command:\n   sudo visudo -f /etc/sudoers.d/wowid3-vpn\n\n\
+                            2. Add this line:\n   {} ALL=(ALL) NOPASSWD: {}\n\n\
+                            3. Save and exit (Ctrl+X, then Y, then Enter)\n\n\
+                            4. Try enabling VPN again\n\n\
+                            This allows the launcher to manage the VPN without password prompts.",
+                            whoami::username(),
+                            wg_quick_path.display()
+                        ))
+                    } else if stderr.contains("already exists") || stdout.contains("already exists") {
+                        // Interface already up, that's fine
+                        Ok(())
+                    } else if stderr.contains("Protocol not supported") || stderr.contains("Unknown device type") {
+                        // WireGuard kernel module not available
+                        Err(anyhow::anyhow!(
+                            "WireGuard kernel module not available.\n\n\
+                            On Arch Linux, try:\n\
+                            1. sudo modprobe wireguard\n\
+                            2. If that fails, install the module:\n   sudo pacman -S wireguard-dkms\n\n\
+                            On other distros:\n\
+                            - Ubuntu/Debian: sudo apt install wireguard-dkms\n\
+                            - Fedora: sudo dnf install wireguard-tools\n\n\
+                            Alternatively, update your kernel to 5.6+ (WireGuard is built-in).\n\n\
+                            Original error: {}",
+                            stderr
+                        ))
+                    } else {
+                        Err(anyhow::anyhow!(
+                            "Failed to start VPN tunnel (exit code: {:?})\nStdout: {}\nStderr: {}",
+                            out.status.code(),
+                            stdout,
+                            stderr
+                        ))
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                // pkexec not found, show instructions for sudo setup
+                Err(anyhow::anyhow!(
+                    "pkexec not found. Please configure passwordless sudo for wg-quick:\n\n\
+                    sudo visudo -f /etc/sudoers.d/wowid3-vpn\n\
+                    Add: {} ALL=(ALL) NOPASSWD: {}\n\n\
+                    Then try again.",
+                    whoami::username(),
+                    wg_quick_path.display()
+                ))
+            }
+            Err(e) => Err(anyhow::anyhow!("Failed to execute pkexec: {}", e))
+        }
+    }
+
+    fn stop(&self, config_path: &Path) -> Result<()> {
+        // Pass the config path: a bare interface name makes wg-quick look in /etc/wireguard
+        let output = Command::new("pkexec")
+            .arg(wg_quick_path())
+            .arg("down")
+            .arg(config_path)
+            .output();
+
+        match output {
+            Ok(out) if out.status.success() => Ok(()),
+            Ok(out) => {
+                let stderr = String::from_utf8_lossy(&out.stderr);
+
+                // If interface doesn't exist, that's fine (already stopped)
+                if stderr.contains("is not a WireGuard interface")
+                    || stderr.contains("does not exist")
+                    || stderr.contains("Cannot find device")
+                {
+                    return Ok(());
+                }
+
+                // Check if pkexec was denied
+                if stderr.contains("dismissed") || stderr.contains("Not authorized") {
+                    Err(anyhow::anyhow!(
+                        "Authorization required to stop VPN tunnel. \
+                        Please grant permission when prompted."
+                    ))
+                } else {
+                    Err(anyhow::anyhow!("Failed to stop VPN tunnel: {}", stderr))
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                // pkexec not found, try without sudo (might already be down)
+                Ok(())
+            }
+            Err(e) => Err(anyhow::anyhow!("Failed to execute pkexec: {}", e))
+        }
+    }
+
+    fn install_hint(&self) -> &'static str {
+        "WireGuard is not installed. Please install it using your package manager:\n\nArch/Manjaro: sudo pacman -S wireguard-tools\nUbuntu/Debian: sudo apt install wireguard-tools\nFedora: sudo dnf install wireguard-tools"
+    }
+}
@@ -0,0 +1,144 @@
+// macOS backend: wg-quick from Homebrew's wireguard-tools, which runs the
+// userspace wireguard-go on a utun interface. Elevated through an osascript
+// administrator prompt.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::TunnelBackend;
+
+/// Homebrew prefixes (Apple Silicon, Intel); `do shell script` runs with a minimal PATH
+const BREW_BIN_DIRS: &[&str] = &["/opt/homebrew/bin", "/usr/local/bin"];
+
+/// wg-quick records the utun interface it picked for `wowid3` here
+const INTERFACE_NAME_FILE: &str = "/var/run/wireguard/wowid3.name";
+
+pub struct MacosBackend;
+
+fn find_tool(name: &str) -> Option<PathBuf> {
+    which::which(name).ok().or_else(|| {
+        BREW_BIN_DIRS
+            .iter()
+            .map(|dir| Path::new(dir).join(name))
+            .find(|path| path.exists())
+    })
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Run a shell command as root behind the standard macOS password prompt
+fn run_as_admin(command: &str) -> Result<std::process::Output> {
+    let command = format!("PATH={}:$PATH {}", BREW_BIN_DIRS.join(":"), command);
+    let script = format!(
+        "do shell script \"{}\" with administrator privileges",
+        command.replace('\\', "\\\\").replace('"', "\\\"")
+    );
+    Command::new("osascript")
+        .args(["-e", &script])
+        .output()
+        .context("Failed to execute osascript")
+}
+
+fn wg_quick(action: &str, config_path: &Path) -> Result<std::process::Output> {
+    let wg_quick = find_tool("wg-quick").context("wg-quick not found")?;
+    run_as_admin(&format!(
+        "{} {} {}",
+        shell_quote(&wg_quick.to_string_lossy()),
+        action,
+        shell_quote(&config_path.to_string_lossy())
+    ))
+}
+
+impl TunnelBackend for MacosBackend {
+    fn is_wireguard_installed(&self) -> bool {
+        find_tool("wg-quick").is_some() && find_tool("wireguard-go").is_some()
+    }
+
+    fn config_dir(&self) -> Result<PathBuf> {
+        let home = std::env::var("HOME")
+            .map_err(|_| anyhow::anyhow!("HOME environment variable not set"))?;
+        Ok(Path::new(&home)
+            .join("Library")
+            .join("Application Support")
+            .join("wowid3-launcher")
+            .join("vpn"))
+    }
+
+    fn tunnel_exists(&self, config_path: &Path) -> bool {
+        self.is_wireguard_installed() && config_path.exists()
+    }
+
+    fn is_running(&self) -> bool {
+        // The name file is world readable; `wg show` would need root
+        let Ok(interface) = std::fs::read_to_string(INTERFACE_NAME_FILE) else {
+            return false;
+        };
+        Command::new("ifconfig")
+            .arg(interface.trim())
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    fn install(&self, config_path: &Path) -> Result<()> {
+        if !config_path.exists() {
+            anyhow::bail!("VPN config not found; register this device first");
+        }
+        Ok(())
+    }
+
+    fn start(&self, config_path: &Path) -> Result<()> {
+        eprintln!("[VPN] Starting tunnel with config: {}", config_path.display());
+        let out = wg_quick("up", config_path)?;
+        let stderr = String::from_utf8_lossy(&out.stderr);
+
+        if out.status.success() || stderr.contains("already exists") {
+            Ok(())
+        } else if stderr.contains("User canceled") || stderr.contains("(-128)") {
+            Err(anyhow::anyhow!(
+                "Administrator permission is required to start the VPN tunnel."
+            ))
+        } else if stderr.contains("Version mismatch") || stderr.contains("bash") {
+            Err(anyhow::anyhow!(
+                "wg-quick needs a newer bash than macOS ships. Install it with:\n\nbrew install bash wireguard-tools\n\nOriginal error: {}",
+                stderr
+            ))
+        } else {
+            Err(anyhow::anyhow!("Failed to start VPN tunnel: {}", stderr))
+        }
+    }
+
+    fn stop(&self, config_path: &Path) -> Result<()> {
+        if !self.is_running() {
+            return Ok(());
+        }
+
+        let out = wg_quick("down", config_path)?;
+        if out.status.success() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "Failed to stop VPN tunnel: {}",
+                String::from_utf8_lossy(&out.stderr)
+            ))
+        }
+    }
+
+    fn install_hint(&self) -> &'static str {
+        "WireGuard tools are not installed. Install them with Homebrew:\n\nbrew install wireguard-tools wireguard-go bash"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_quote_escapes_single_quotes() {
+        assert_eq!(shell_quote("/Users/a/Application Support"), "'/Users/a/Application Support'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+}
@@ -0,0 +1,553 @@
+// Cross-platform VPN management module
+// - Windows: Uses sc.exe for service control, PROGRAMDATA for storage
+// - Linux: Uses wg-quick for tunnel control, ~/.config for storage
+// - macOS: Uses wg-quick + wireguard-go (utun), ~/Library/Application Support for storage
+//
+// All platforms use WireGuard with identical config format; only tunnel
+// control differs, behind `TunnelBackend`
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use super::auth::MinecraftProfile;
+use super::install_id;
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "windows")]
+mod windows;
+
+/// Servers that predate `server_ip` in the registration response all use this
+const DEFAULT_SERVER_IP: &str = "10.8.0.1";
+const MINECRAFT_PORT: u16 = 25565;
+
+fn default_server_ip() -> String {
+    DEFAULT_SERVER_IP.to_string()
+}
+
+/// Peer registration returned by the server's `/api/vpn/register`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VpnRegistration {
+    /// Our address inside the tunnel
+    pub assigned_ip: String,
+    /// The game server's address inside the tunnel
+    #[serde(default = "default_server_ip")]
+    pub server_ip: String,
+    pub server_public_key: String,
+    pub endpoint: String,
+}
+
+impl VpnRegistration {
+    /// Minecraft address to use while the tunnel is up
+    pub fn server_address(&self) -> String {
+        format!("{}:{}", self.server_ip, MINECRAFT_PORT)
+    }
+
+    fn wireguard_config(&self, private_key: &str) -> String {
+        format!(
+            "[Interface]\nPrivateKey = {}\nAddress = {}/24\nDNS = 1.1.1.1\n\n[Peer]\nPublicKey = {}\nEndpoint = {}\nAllowedIPs = 10.8.0.0/24\nPersistentKeepalive = 25",
+            private_key, self.assigned_ip, self.server_public_key, self.endpoint
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RegisterResponse {
+    success: bool,
+    #[serde(flatten)]
+    registration: VpnRegistration,
+}
+
+/// Snapshot of the VPN setup for the settings screen
+#[derive(Debug, Clone, Serialize)]
+pub struct VpnStatus {
+    pub wireguard_installed: bool,
+    pub registered: bool,
+    pub assigned_ip: Option<String>,
+    pub server_ip: Option<String>,
+    /// `running`, `stopped` or `not_installed`
+    pub tunnel: String,
+}
+
+/// Platform specific tunnel control
+pub trait TunnelBackend: Send + Sync {
+    fn is_wireguard_installed(&self) -> bool;
+    /// Where keys, `wowid3.conf` and the registration are stored
+    fn config_dir(&self) -> Result<PathBuf>;
+    /// Whether the tunnel can be started without further setup
+    fn tunnel_exists(&self, config_path: &Path) -> bool;
+    fn is_running(&self) -> bool;
+    /// One-time setup after the config is written (e.g. a system service)
+    fn install(&self, config_path: &Path) -> Result<()>;
+    fn start(&self, config_path: &Path) -> Result<()>;
+    fn stop(&self, config_path: &Path) -> Result<()>;
+    /// Shown when WireGuard is missing
+    fn install_hint(&self) -> &'static str;
+}
+
+/// Fallback for platforms without a WireGuard integration
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+struct UnsupportedBackend;
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+impl TunnelBackend for UnsupportedBackend {
+    fn is_wireguard_installed(&self) -> bool {
+        false
+    }
+
+    fn config_dir(&self) -> Result<PathBuf> {
+        Err(anyhow::anyhow!("Unsupported platform"))
+    }
+
+    fn tunnel_exists(&self, _config_path: &Path) -> bool {
+        false
+    }
+
+    fn is_running(&self) -> bool {
+        false
+    }
+
+    fn install(&self, _config_path: &Path) -> Result<()> {
+        Err(anyhow::anyhow!("Unsupported platform"))
+    }
+
+    fn start(&self, _config_path: &Path) -> Result<()> {
+        Err(anyhow::anyhow!("Unsupported platform"))
+    }
+
+    fn stop(&self, _config_path: &Path) -> Result<()> {
+        Err(anyhow::anyhow!("Unsupported platform"))
+    }
+
+    fn install_hint(&self) -> &'static str {
+        "WireGuard is not installed or unsupported platform."
+    }
+}
+
+/// The tunnel backend for the platform we were built for
+pub fn platform_backend() -> Box<dyn TunnelBackend> {
+    #[cfg(target_os = "windows")]
+    return Box::new(windows::WindowsBackend);
+
+    #[cfg(target_os = "linux")]
+    return Box::new(linux::LinuxBackend);
+
+    #[cfg(target_os = "macos")]
+    return Box::new(macos::MacosBackend);
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+    return Box::new(UnsupportedBackend);
+}
+
+pub struct VpnManager {
+    config_dir: PathBuf,
+    backend: Box<dyn TunnelBackend>,
+}
+
+impl VpnManager {
+    pub fn new() -> Result<Self> {
+        let backend = platform_backend();
+        let config_dir = backend.config_dir()?;
+        std::fs::create_dir_all(&config_dir)?;
+        Ok(Self { config_dir, backend })
+    }
+
+    /// Check if WireGuard is installed on the system
+    pub fn is_wireguard_installed() -> bool {
+        platform_backend().is_wireguard_installed()
+    }
+
+    /// How to install WireGuard on this platform
+    pub fn install_hint() -> &'static str {
+        platform_backend().install_hint()
+    }
+
+    pub fn generate_keypair() -> Result<(String, String)> {
+        let private_key = StaticSecret::random_from_rng(OsRng);
+        let public_key = PublicKey::from(&private_key);
+
+        let private_b64 = general_purpose::STANDARD.encode(private_key.to_bytes());
+        let public_b64 = general_purpose::STANDARD.encode(public_key.as_bytes());
+
+        Ok((private_b64, public_b64))
+    }
+
+    /// Public key belonging to a base64 private key
+    pub fn public_key_for(private_key: &str) -> Result<String> {
+        let bytes: [u8; 32] = general_purpose::STANDARD
+            .decode(private_key.trim())
+            .context("Invalid private key encoding")?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Private key must be 32 bytes"))?;
+        let public_key = PublicKey::from(&StaticSecret::from(bytes));
+        Ok(general_purpose::STANDARD.encode(public_key.as_bytes()))
+    }
+
+    pub fn has_keypair(&self) -> bool {
+        self.config_dir.join("private.key").exists()
+    }
+
+    pub fn store_keypair(&self, private_key: &str, public_key: &str) -> Result<()> {
+        std::fs::write(self.config_dir.join("private.key"), private_key)?;
+        std::fs::write(self.config_dir.join("public.key"), public_key)?;
+        Ok(())
+    }
+
+    pub fn load_keypair(&self) -> Result<(String, String)> {
+        let private = std::fs::read_to_string(self.config_dir.join("private.key"))?;
+        let public = std::fs::read_to_string(self.config_dir.join("public.key"))?;
+        Ok((private, public))
+    }
+
+    pub fn write_config(&self, config_content: &str) -> Result<()> {
+        let config_path = self.config_dir.join("wowid3.conf");
+        std::fs::write(&config_path, config_content)?;
+
+        // Set secure permissions (600 - owner read/write only)
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let permissions = std::fs::Permissions::from_mode(0o600);
+            std::fs::set_permissions(&config_path, permissions)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_config_path(&self) -> Result<PathBuf> {
+        Ok(self.config_dir.join("wowid3.conf"))
+    }
+
+    pub fn save_registration(&self, registration: &VpnRegistration) -> Result<()> {
+        let json = serde_json::to_string_pretty(registration)?;
+        std::fs::write(self.config_dir.join("registration.json"), json)?;
+        Ok(())
+    }
+
+    /// The last successful registration, if this device has one
+    pub fn load_registration(&self) -> Result<Option<VpnRegistration>> {
+        let path = self.config_dir.join("registration.json");
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = std::fs::read_to_string(path)?;
+        Ok(Some(serde_json::from_str(&json)?))
+    }
+
+    /// Register this device as a peer for `profile` and write the tunnel config
+    ///
+    /// Reuses the stored private key so re-registering keeps the same peer;
+    /// a key is generated on first use.
+    pub async fn register(&self, server_url: &str, profile: &MinecraftProfile) -> Result<VpnRegistration> {
+        let private_key = if self.has_keypair() {
+            self.load_keypair()?.0.trim().to_string()
+        } else {
+            Self::generate_keypair()?.0
+        };
+        let public_key = Self::public_key_for(&private_key)?;
+
+        let url = format!("{}/api/vpn/register", server_url.trim_end_matches('/'));
+        let response = install_id::apply(reqwest::Client::new().post(&url), &url)
+            .json(&serde_json::json!({
+                "minecraft_uuid": profile.uuid,
+                "minecraft_username": profile.username,
+                "public_key": public_key,
+                "auth_token": profile.session_id
+            }))
+            .send()
+            .await
+            .context("Failed to connect to VPN server")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!("VPN registration failed ({}): {}", status, error_text);
+        }
+
+        let response: RegisterResponse = response
+            .json()
+            .await
+            .context("Failed to parse VPN registration response")?;
+        if !response.success {
+            anyhow::bail!("VPN server rejected the registration");
+        }
+
+        let registration = response.registration;
+        self.store_keypair(&private_key, &public_key)?;
+        self.write_config(&registration.wireguard_config(&private_key))?;
+        self.save_registration(&registration)?;
+        Ok(registration)
+    }
+
+    /// `running`, `stopped` or `not_installed`
+    pub fn tunnel_state(&self) -> &'static str {
+        if !self.tunnel_exists() {
+            "not_installed"
+        } else if self.is_tunnel_running() {
+            "running"
+        } else {
+            "stopped"
+        }
+    }
+
+    pub fn status(&self) -> Result<VpnStatus> {
+        let registration = self.load_registration()?;
+        Ok(VpnStatus {
+            wireguard_installed: Self::is_wireguard_installed(),
+            registered: registration.is_some(),
+            assigned_ip: registration.as_ref().map(|r| r.assigned_ip.clone()),
+            server_ip: registration.map(|r| r.server_ip),
+            tunnel: self.tunnel_state().to_string(),
+        })
+    }
+
+    /// Bring the tunnel up, installing the WireGuard tunnel service first if needed
+    pub fn connect(&self) -> Result<()> {
+        if self.load_registration()?.is_none() {
+            anyhow::bail!("This device is not registered for the VPN yet");
+        }
+        if !self.tunnel_exists() {
+            self.install_tunnel()?;
+        }
+        if self.is_tunnel_running() {
+            return Ok(());
+        }
+        self.start_tunnel()
+    }
+
+    /// Game server address to use: the tunnel address while connected, otherwise `None`
+    pub fn active_server_address(&self) -> Option<String> {
+        if !self.is_tunnel_running() {
+            return None;
+        }
+        self.load_registration().ok().flatten().map(|r| r.server_address())
+    }
+
+    fn install_tunnel(&self) -> Result<()> {
+        self.backend.install(&self.get_config_path()?)
+    }
+
+    pub fn tunnel_exists(&self) -> bool {
+        self.get_config_path()
+            .map(|path| self.backend.tunnel_exists(&path))
+            .unwrap_or(false)
+    }
+
+    pub fn is_tunnel_running(&self) -> bool {
+        self.backend.is_running()
+    }
+
+    pub fn start_tunnel(&self) -> Result<()> {
+        self.backend.start(&self.get_config_path()?)
+    }
+
+    pub fn stop_tunnel(&self) -> Result<()> {
+        self.backend.stop(&self.get_config_path()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    // Helper to create a test VpnManager with a temporary directory
+    fn create_test_manager() -> (VpnManager, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = VpnManager {
+            config_dir: temp_dir.path().to_path_buf(),
+            backend: platform_backend(),
+        };
+        std::fs::create_dir_all(&manager.config_dir).unwrap();
+        (manager, temp_dir)
+    }
+
+    #[test]
+    fn test_generate_keypair_creates_valid_base64() {
+        let (private, public) = VpnManager::generate_keypair().unwrap();
+
+        // Verify base64 encoding
+        assert!(general_purpose::STANDARD.decode(&private).is_ok());
+        assert!(general_purpose::STANDARD.decode(&public).is_ok());
+
+        // Verify lengths (32 bytes each = 44 chars in base64)
+        assert_eq!(
+            general_purpose::STANDARD.decode(&private).unwrap().len(),
+            32
+        );
+        assert_eq!(general_purpose::STANDARD.decode(&public).unwrap().len(), 32);
+    }
+
+    #[test]
+    fn test_keypair_is_different_each_time() {
+        let (private1, public1) = VpnManager::generate_keypair().unwrap();
+        let (private2, public2) = VpnManager::generate_keypair().unwrap();
+
+        assert_ne!(private1, private2);
+        assert_ne!(public1, public2);
+    }
+
+    #[test]
+    fn test_vpn_manager_creation_succeeds() {
+        let (manager, _temp_dir) = create_test_manager();
+
+        // Verify config directory exists
+        assert!(manager.config_dir.exists());
+        assert!(manager.config_dir.is_dir());
+    }
+
+    #[test]
+    fn test_manager_initialization_is_idempotent() {
+        let (manager, _temp_dir) = create_test_manager();
+
+        // Create directory structure multiple times
+        std::fs::create_dir_all(&manager.config_dir).unwrap();
+        std::fs::create_dir_all(&manager.config_dir).unwrap();
+
+        // Should still be valid
+        assert!(manager.config_dir.exists());
+    }
+
+    #[test]
+    fn test_keypair_generation_length_is_correct() {
+        let (private, public) = VpnManager::generate_keypair().unwrap();
+
+        // Decode and verify exact length (x25519 keys are 32 bytes)
+        let private_bytes = general_purpose::STANDARD.decode(&private).unwrap();
+        let public_bytes = general_purpose::STANDARD.decode(&public).unwrap();
+
+        assert_eq!(
+            private_bytes.len(),
+            32,
+            "Private key should be exactly 32 bytes"
+        );
+        assert_eq!(
+            public_bytes.len(),
+            32,
+            "Public key should be exactly 32 bytes"
+        );
+    }
+
+    #[test]
+    fn test_keypair_storage_and_retrieval() {
+        let (manager, _temp_dir) = create_test_manager();
+
+        // Generate and store keypair
+        let (private, public) = VpnManager::generate_keypair().unwrap();
+        manager.store_keypair(&private, &public).unwrap();
+
+        // Verify has_keypair returns true
+        assert!(manager.has_keypair());
+
+        // Load and verify
+        let (loaded_private, loaded_public) = manager.load_keypair().unwrap();
+        assert_eq!(loaded_private, private);
+        assert_eq!(loaded_public, public);
+    }
+
+    #[test]
+    fn test_has_keypair_returns_false_when_no_keypair() {
+        let (manager, _temp_dir) = create_test_manager();
+
+        // Should return false when no keypair is stored
+        assert!(!manager.has_keypair());
+    }
+
+    #[test]
+    fn test_keypair_overwrite_works() {
+        let (manager, _temp_dir) = create_test_manager();
+
+        // Store first keypair
+        let (private1, public1) = VpnManager::generate_keypair().unwrap();
+        manager.store_keypair(&private1, &public1).unwrap();
+
+        // Store second keypair (overwrite)
+        let (private2, public2) = VpnManager::generate_keypair().unwrap();
+        manager.store_keypair(&private2, &public2).unwrap();
+
+        // Load and verify it's the second keypair
+        let (loaded_private, loaded_public) = manager.load_keypair().unwrap();
+        assert_eq!(loaded_private, private2);
+        assert_eq!(loaded_public, public2);
+        assert_ne!(loaded_private, private1);
+        assert_ne!(loaded_public, public1);
+    }
+
+    #[test]
+    fn test_config_file_writing() {
+        let (manager, _temp_dir) = create_test_manager();
+
+        let config_content = "[Interface]\nPrivateKey = test_key\nAddress = 10.8.0.2/24\n\n[Peer]\nPublicKey = server_key\nEndpoint = example.com:51820";
+
+        // Write config
+        manager.write_config(config_content).unwrap();
+
+        // Verify file exists
+        let config_path = manager.get_config_path().unwrap();
+        assert!(config_path.exists());
+
+        // Verify content matches
+        let written_content = std::fs::read_to_string(config_path).unwrap();
+        assert_eq!(written_content, config_content);
+    }
+
+    #[test]
+    fn test_config_overwrite_works() {
+        let (manager, _temp_dir) = create_test_manager();
+
+        // Write first config
+        let config1 = "[Interface]\nAddress = 10.8.0.2/24";
+        manager.write_config(config1).unwrap();
+
+        // Write second config (overwrite)
+        let config2 = "[Interface]\nAddress = 10.8.0.3/24";
+        manager.write_config(config2).unwrap();
+
+        // Verify it's the second config
+        let config_path = manager.get_config_path().unwrap();
+        let written_content = std::fs::read_to_string(config_path).unwrap();
+        assert_eq!(written_content, config2);
+    }
+
+    #[test]
+    fn test_public_key_for_matches_generated_pair() {
+        let (private, public) = VpnManager::generate_keypair().unwrap();
+        assert_eq!(VpnManager::public_key_for(&private).unwrap(), public);
+        assert!(VpnManager::public_key_for("not a key").is_err());
+    }
+
+    #[test]
+    fn test_registration_round_trip() {
+        let (manager, _temp_dir) = create_test_manager();
+        assert_eq!(manager.load_registration().unwrap(), None);
+
+        // Older servers omit server_ip
+        let registration: VpnRegistration = serde_json::from_str(
+            r#"{"assigned_ip":"10.8.0.7","server_public_key":"key","endpoint":"example.com:51820"}"#,
+        )
+        .unwrap();
+        assert_eq!(registration.server_address(), "10.8.0.1:25565");
+
+        manager.save_registration(&registration).unwrap();
+        assert_eq!(manager.load_registration().unwrap(), Some(registration.clone()));
+
+        let config = registration.wireguard_config("private");
+        assert!(config.contains("Address = 10.8.0.7/24"));
+        assert!(config.contains("Endpoint = example.com:51820"));
+    }
+
+    #[test]
+    fn test_get_config_path_returns_correct_path() {
+        let (manager, _temp_dir) = create_test_manager();
+
+        let config_path = manager.get_config_path().unwrap();
+        assert_eq!(config_path.file_name().unwrap(), "wowid3.conf");
+        assert_eq!(config_path.parent().unwrap(), manager.config_dir);
+    }
+}
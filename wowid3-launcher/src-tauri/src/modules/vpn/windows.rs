@@ -0,0 +1,121 @@
+// Windows backend: the WireGuard app's tunnel service (`WireGuardTunnel$wowid3`),
+// controlled through net.exe / sc.exe
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::TunnelBackend;
+
+const SERVICE_NAME: &str = "WireGuardTunnel$wowid3";
+
+pub struct WindowsBackend;
+
+impl TunnelBackend for WindowsBackend {
+    fn is_wireguard_installed(&self) -> bool {
+        // Strategy 1: Check if wg.exe is in PATH
+        let in_path = Command::new("where")
+            .arg("wg.exe")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+
+        if in_path {
+            eprintln!("[VPN] WireGuard found in PATH");
+            return true;
+        }
+
+        // Strategy 2: Check common installation directories
+        let common_paths = vec![
+            r"C:\Program Files\WireGuard\wg.exe",
+            r"C:\Program Files (x86)\WireGuard\wg.exe",
+        ];
+
+        for path in common_paths {
+            if std::path::Path::new(path).exists() {
+                eprintln!("[VPN] WireGuard found at: {}", path);
+                return true;
+            }
+        }
+
+        eprintln!("[VPN] WireGuard not found in PATH or common install locations");
+        false
+    }
+
+    fn config_dir(&self) -> Result<PathBuf> {
+        let program_data =
+            std::env::var("PROGRAMDATA").unwrap_or_else(|_| "C:\\ProgramData".to_string());
+        Ok(Path::new(&program_data).join("wowid3-launcher").join("vpn"))
+    }
+
+    fn tunnel_exists(&self, _config_path: &Path) -> bool {
+        // Check if WireGuard service exists
+        let output = Command::new("sc")
+            .args(&["query", SERVICE_NAME])
+            .output();
+
+        output.map(|o| o.status.success()).unwrap_or(false)
+    }
+
+    fn is_running(&self) -> bool {
+        let output = Command::new("sc")
+            .args(&["query", SERVICE_NAME])
+            .output();
+
+        if let Ok(output) = output {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            stdout.contains("RUNNING")
+        } else {
+            false
+        }
+    }
+
+    /// Register `wowid3.conf` as the tunnel service (also starts it)
+    fn install(&self, config_path: &Path) -> Result<()> {
+        let wireguard = which::which("wireguard.exe").unwrap_or_else(|_| {
+            PathBuf::from(r"C:\Program Files\WireGuard\wireguard.exe")
+        });
+        let output = Command::new(&wireguard)
+            .arg("/installtunnelservice")
+            .arg(config_path)
+            .output()
+            .context("Failed to run wireguard.exe")?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "Failed to install VPN tunnel service: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ))
+        }
+    }
+
+    fn start(&self, _config_path: &Path) -> Result<()> {
+        let output = Command::new("net")
+            .args(&["start", SERVICE_NAME])
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Failed to start VPN tunnel"))
+        }
+    }
+
+    fn stop(&self, _config_path: &Path) -> Result<()> {
+        let output = Command::new("net")
+            .args(&["stop", SERVICE_NAME])
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Failed to stop VPN tunnel"))
+        }
+    }
+
+    fn install_hint(&self) -> &'static str {
+        "WireGuard is not installed. Please install WireGuard from the bundled installer."
+    }
+}
@@ -1,16 +1,19 @@
 import { useEffect, useState } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from './utils/invoke';
 import { getVersion } from '@tauri-apps/api/app';
 import { logger, LogCategory } from './utils/logger';
-import { useModpack, useServer, useTheme, useAudio, useDiscord, useVpnInitialization } from './hooks';
+import { useModpack, useServer, useTheme, useAudio, useDiscord, useVpnInitialization, useServerEvents } from './hooks';
 import { usePolling } from './hooks/usePolling';
 import { useSettingsStore } from './stores/settingsStore';
 import { useUIStore } from './stores/uiStore';
 import { useUpdateStore } from './stores/updateStore';
-import { checkLauncherUpdate } from './hooks/useTauriCommands';
+import { useCmsStore } from './stores/cmsStore';
+import { checkLauncherUpdate, getCmsConfig } from './hooks/useTauriCommands';
+import { extractBaseUrl } from './utils/url';
 import LauncherHome from './components/LauncherHome';
 import { SettingsScreen } from './components/SettingsScreen';
 import { StatsScreen } from './components/StatsScreen';
+import { ScreenshotsScreen } from './components/ScreenshotsScreen';
 import LogViewerModal from './components/LogViewerModal';
 import LauncherUpdateModal from './components/LauncherUpdateModal';
 import ChristmasBackground from './components/theme/ChristmasBackground';
@@ -20,16 +23,17 @@ import { ChangelogViewer } from './components/ChangelogViewer';
 import './App.css';
 
 function AppContent() {
-  const [activeTab, setActiveTab] = useState<'home' | 'settings' | 'stats'>('home');
+  const [activeTab, setActiveTab] = useState<'home' | 'settings' | 'stats' | 'screenshots'>('home');
   const [showChangelog, setShowChangelog] = useState(false);
   const [showChangelogModal, setShowChangelogModal] = useState(false);
   const [appVersion, setAppVersion] = useState<string>('');
   const { checkUpdates, latestManifest } = useModpack();
   const { ping } = useServer();
-  const { initializeGameDirectory } = useSettingsStore();
+  const { initializeGameDirectory, manifestUrl } = useSettingsStore();
+  const { config: cmsConfig, setConfig: setCmsConfig } = useCmsStore();
   const { showLogViewer, setShowLogViewer } = useUIStore();
   const { launcherUpdate, setLauncherUpdate, showLauncherUpdateModal } = useUpdateStore();
-  const { isMuted, toggleMute, fallbackRef, mainRef, fallbackUrl } = useAudio();
+  const { isMuted, toggleMute, fallbackRef, mainRef, fallbackUrl, nextTrack, currentTrack, hasPlaylist } = useAudio();
   useTheme(); // Apply theme on mount
   useVpnInitialization(); // Initialize VPN tunnel if enabled
 
@@ -47,6 +51,13 @@ function AppContent() {
     initializeGameDirectory();
   }, [initializeGameDirectory]);
 
+  // Polling intervals and retry backoff come from the server so they can be retuned without a release
+  useEffect(() => {
+    getCmsConfig(extractBaseUrl(manifestUrl))
+      .then(setCmsConfig)
+      .catch(err => logger.warn(LogCategory.NETWORK, `Failed to load CMS config: ${err}`));
+  }, [manifestUrl, setCmsConfig]);
+
   // Note: Installed version is now loaded by useModpack hook on mount
   // This legacy effect is no longer needed as useModpack handles version persistence
 
@@ -66,21 +77,44 @@ function AppContent() {
     ping(); // Initial ping
   }, [checkUpdates, ping, setLauncherUpdate]);
 
+  // Release and launcher publishes are pushed by the server
+  const { connected: serverEventsConnected } = useServerEvents({
+    onRelease: () => {
+      checkUpdates().catch(e => logger.error(LogCategory.MODPACK, 'checkUpdates after server event failed:', e instanceof Error ? e : new Error(String(e))));
+    },
+    onLauncher: () => {
+      checkLauncherUpdate().then(info => {
+        if (info.available) {
+          setLauncherUpdate(info);
+        }
+      }).catch(err => {
+        logger.error(LogCategory.UPDATER, "Failed to check for launcher updates:", err instanceof Error ? err : new Error(String(err)));
+      });
+    },
+  });
+
   // Unified Polling
+  const { polling, retry } = cmsConfig;
   usePolling({
     name: 'ServerStatus',
-    interval: 30000,
+    interval: polling.server_status_ms,
     fn: async () => { await ping(); },
     enabled: true,
-    exponentialBackoff: true
+    exponentialBackoff: true,
+    maxRetries: retry.max_attempts,
+    retryBaseDelay: retry.base_delay_ms,
+    retryMaxDelay: retry.max_delay_ms,
   });
 
   usePolling({
     name: 'ModpackUpdate',
-    interval: 300000, // 5 minutes
+    interval: polling.update_check_ms,
     fn: async () => { await checkUpdates(); },
-    enabled: true,
-    exponentialBackoff: true
+    enabled: !serverEventsConnected, // Fallback while the event stream is down
+    exponentialBackoff: true,
+    maxRetries: retry.max_attempts,
+    retryBaseDelay: retry.base_delay_ms,
+    retryMaxDelay: retry.max_delay_ms,
   });
 
   return (
@@ -94,10 +128,9 @@ function AppContent() {
         loop
       />
 
-      {/* Main Audio (full track from server) */}
+      {/* Main Audio (server playlist, or the bundled full track; looping is set by useAudio) */}
       <audio
         ref={mainRef}
-        loop
       />
 
       <div className="relative z-10 w-full h-full flex flex-col">
@@ -145,6 +178,25 @@ function AppContent() {
               <line x1="6" y1="20" x2="6" y2="16" />
             </svg>
           </button>
+          <button
+            onClick={() => setActiveTab('screenshots')}
+            className={`p-5 transition-all ${
+              activeTab === 'screenshots'
+                ? 'bg-christmas-gold bg-opacity-90 text-black font-bold'
+                : 'bg-black bg-opacity-40 text-white hover:bg-opacity-60'
+            }`}
+            style={{
+              backdropFilter: 'blur(12px)',
+              border: activeTab === 'screenshots' ? '2px solid rgba(255, 215, 0, 0.8)' : '2px solid rgba(255, 255, 255, 0.3)',
+              borderRadius: '0',
+            }}
+            title="Screenshots"
+          >
+            <svg width="32" height="32" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round">
+              <path d="M14.5 4h-5L7 7H4a2 2 0 0 0-2 2v9a2 2 0 0 0 2 2h16a2 2 0 0 0 2-2V9a2 2 0 0 0-2-2h-3l-2.5-3z" />
+              <circle cx="12" cy="13" r="3" />
+            </svg>
+          </button>
           <button
             onClick={() => setActiveTab('settings')}
             className={`p-5 transition-all ${
@@ -298,6 +350,25 @@ function AppContent() {
             )}
           </button>
 
+          {/* Next Track Button (only when the server has a playlist) */}
+          {hasPlaylist && (
+            <button
+              onClick={nextTrack}
+              className="p-5 transition-all bg-black bg-opacity-40 text-white hover:bg-opacity-60"
+              style={{
+                backdropFilter: 'blur(12px)',
+                border: '2px solid rgba(255, 255, 255, 0.3)',
+                borderRadius: '0',
+              }}
+              title={currentTrack ? `Now playing: ${currentTrack.title} (click for next track)` : 'Next Track'}
+            >
+              <svg width="32" height="32" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round">
+                <polygon points="5 4 15 12 5 20 5 4" />
+                <line x1="19" y1="5" x2="19" y2="19" />
+              </svg>
+            </button>
+          )}
+
           {/* Map Viewer Button */}
           <button
             onClick={() => {
@@ -324,6 +395,7 @@ function AppContent() {
         <div className="flex-1 overflow-auto">
           {activeTab === 'home' && <LauncherHome />}
           {activeTab === 'stats' && <StatsScreen />}
+          {activeTab === 'screenshots' && <ScreenshotsScreen />}
           {activeTab === 'settings' && <SettingsScreen />}
         </div>
       </div>
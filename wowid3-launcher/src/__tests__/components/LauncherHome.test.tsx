@@ -39,8 +39,11 @@ vi.mock('../../hooks/useDiscord', () => ({
   useDiscord: () => ({
     isConnected: false,
     connect: vi.fn()
-  }),
-  useDiscordPresence: vi.fn()
+  })
+}));
+
+vi.mock('../../hooks/usePlayerSkin', () => ({
+  usePlayerSkin: () => null
 }));
 
 vi.mock('../../hooks/useMinecraftInstaller', () => ({
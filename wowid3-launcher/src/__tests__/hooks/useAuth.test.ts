@@ -3,6 +3,7 @@ import { renderHook, act } from '@testing-library/react';
 import { useAuth } from '../../hooks/useAuth';
 import { useAuthStore } from '../../stores/authStore';
 import * as tauriCommands from '../../hooks/useTauriCommands';
+import { CommandError, LauncherErrorCode } from '../../utils/errors';
 
 // Mock the tauri commands
 vi.mock('../../hooks/useTauriCommands', () => ({
@@ -11,6 +12,7 @@ vi.mock('../../hooks/useTauriCommands', () => ({
   refreshToken: vi.fn(),
   getDeviceCode: vi.fn(),
   completeDeviceCodeAuth: vi.fn(),
+  signInDemo: vi.fn(),
 }));
 
 describe('useAuth', () => {
@@ -89,6 +91,27 @@ describe('useAuth', () => {
     expect(useAuthStore.getState().isAuthenticated).toBe(true);
   });
 
+  it('should sign in with a demo account', async () => {
+    const mockProfile = {
+      uuid: 'a1b2c3d4e5f65a1b8c9d0e1f2a3b4c5d',
+      username: 'PackDev',
+      session_id: 'demo-session',
+      demo: true,
+    };
+
+    vi.mocked(tauriCommands.signInDemo).mockResolvedValue(mockProfile);
+
+    const { result } = renderHook(() => useAuth());
+
+    await act(async () => {
+      await result.current.signInDemo('PackDev');
+    });
+
+    expect(tauriCommands.signInDemo).toHaveBeenCalledWith('PackDev');
+    expect(result.current.user).toEqual(mockProfile);
+    expect(result.current.isAuthenticated).toBe(true);
+  });
+
   it('should handle auth errors gracefully', async () => {
     const errorMessage = 'Auth failed';
     vi.mocked(tauriCommands.completeDeviceCodeAuth).mockRejectedValue(new Error(errorMessage));
@@ -107,4 +130,30 @@ describe('useAuth', () => {
     expect(result.current.isLoading).toBe(false);
     expect(result.current.isAuthenticated).toBe(false);
   });
+
+  it('should keep the kind of a command error', async () => {
+    vi.mocked(tauriCommands.completeDeviceCodeAuth).mockRejectedValue(
+      new CommandError({
+        kind: 'auth_unavailable',
+        message: 'Xbox Live authentication failed with status 503',
+        retryable: true,
+        context: [],
+      })
+    );
+
+    const { result } = renderHook(() => useAuth());
+
+    await act(async () => {
+      try {
+        await result.current.finishDeviceCodeAuth('test-code', 5);
+      } catch (e) {
+        // Expected error
+      }
+    });
+
+    const error = useAuthStore.getState().error;
+    expect(error?.code).toBe(LauncherErrorCode.AUTH_NETWORK_ERROR);
+    expect(error?.retryable).toBe(true);
+    expect(error?.message).toBe('Xbox Live authentication failed with status 503');
+  });
 });
@@ -10,7 +10,9 @@ vi.mock('../../hooks/useTauriCommands', () => ({
   getInstalledVersion: vi.fn(),
   installModpack: vi.fn(),
   verifyAndRepairModpack: vi.fn(),
+  rollbackModpack: vi.fn(),
   hasManifestChanged: vi.fn(),
+  isInstallCancelled: vi.fn(() => false),
 }));
 
 // Mock Listen
@@ -78,7 +80,7 @@ describe('useModpack', () => {
       await result.current.install();
     });
 
-    expect(tauriCommands.installModpack).toHaveBeenCalledWith(mockManifest, expect.anything());
+    expect(tauriCommands.installModpack).toHaveBeenCalledWith(mockManifest, expect.anything(), { directory: null, keep: 5 }, null, null);
     expect(result.current.isDownloading).toBe(false);
     expect(result.current.installedVersion).toBe('1.0.0');
   });
@@ -0,0 +1,97 @@
+import { useState } from 'react';
+import { createPortal } from 'react-dom';
+import { FocusTrap } from './ui/FocusTrap';
+
+interface DemoAccountModalProps {
+  onSignIn: (username: string) => Promise<void>;
+  onUseMicrosoft: () => void;
+  onCancel: () => void;
+}
+
+const USERNAME_PATTERN = /^[A-Za-z0-9_]{3,16}$/;
+
+/** Username-only sign-in for pack testing, shown when WOWID3_DEMO_ACCOUNTS is set */
+export default function DemoAccountModal({ onSignIn, onUseMicrosoft, onCancel }: DemoAccountModalProps) {
+  const [username, setUsername] = useState('');
+  const [submitting, setSubmitting] = useState(false);
+  const valid = USERNAME_PATTERN.test(username.trim());
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!valid || submitting) return;
+    setSubmitting(true);
+    try {
+      await onSignIn(username.trim());
+    } finally {
+      setSubmitting(false);
+    }
+  };
+
+  return createPortal(
+    <div
+      className="fixed inset-0 flex items-center justify-center"
+      style={{ zIndex: 2147483647, backgroundColor: 'rgba(0, 0, 0, 0.95)' }}
+      role="dialog"
+      aria-modal="true"
+      aria-labelledby="demo-account-title"
+    >
+      <FocusTrap isActive={true} onEscape={onCancel}>
+        <form
+          onSubmit={handleSubmit}
+          className="p-8 max-w-md w-full"
+          style={{
+            backgroundColor: 'rgba(0, 0, 0, 0.85)',
+            backdropFilter: 'blur(12px)',
+            border: '2px solid rgba(255, 255, 255, 0.3)',
+            borderRadius: '0',
+            fontFamily: "'Trebuchet MS', sans-serif",
+          }}
+        >
+          <h2 id="demo-account-title" className="text-2xl font-bold text-white mb-2">Demo Account</h2>
+          <p className="text-gray-300 text-sm mb-4">
+            Play offline as any username. Online-mode servers will not accept demo accounts.
+          </p>
+
+          <input
+            autoFocus
+            value={username}
+            onChange={(e) => setUsername(e.target.value)}
+            placeholder="Username"
+            maxLength={16}
+            aria-label="Demo account username"
+            className="w-full px-3 py-2 mb-4 text-white focus:outline-none"
+            style={{
+              backgroundColor: 'rgba(0, 0, 0, 0.8)',
+              border: '1px solid rgba(255, 255, 255, 0.3)',
+              borderRadius: '0',
+            }}
+          />
+
+          <button
+            type="submit"
+            disabled={!valid || submitting}
+            className="w-full font-semibold py-2 px-4 mb-3 transition-colors disabled:opacity-50"
+            style={{
+              backgroundColor: 'rgba(34, 197, 94, 0.3)',
+              border: '2px solid rgba(34, 197, 94, 0.6)',
+              color: '#22c55e',
+              borderRadius: '0',
+            }}
+          >
+            {submitting ? 'Signing in...' : 'Play as Demo'}
+          </button>
+
+          <div className="flex items-center justify-between">
+            <button type="button" onClick={onUseMicrosoft} className="text-sm text-blue-400 hover:underline">
+              Sign in with Microsoft instead
+            </button>
+            <button type="button" onClick={onCancel} className="text-sm text-gray-400 hover:underline">
+              Cancel
+            </button>
+          </div>
+        </form>
+      </FocusTrap>
+    </div>,
+    document.body
+  );
+}
@@ -0,0 +1,122 @@
+import { FC, useEffect, useState } from 'react';
+import {
+  cancelInstallation,
+  getDownloadState,
+  pauseDownloads,
+  resumeDownloads,
+  setDownloadLimit,
+} from '../hooks/useTauriCommands';
+import type { DownloadControlState } from '../types/minecraft';
+import { Button } from './ui/Button';
+import { Input } from './ui/Input';
+import { useToast } from './ui/ToastContainer';
+
+const BYTES_PER_MB = 1024 * 1024;
+
+const useDownloadState = () => {
+  const [state, setState] = useState<DownloadControlState | null>(null);
+
+  useEffect(() => {
+    getDownloadState().then(setState).catch(() => setState(null));
+  }, []);
+
+  return [state, setState] as const;
+};
+
+/**
+ * Pause or resume every running download (modpack, libraries and assets)
+ */
+export const PauseDownloadsButton: FC = () => {
+  const [state, setState] = useDownloadState();
+  const { addToast } = useToast();
+
+  const handleClick = async () => {
+    try {
+      setState(await (state?.paused ? resumeDownloads() : pauseDownloads()));
+    } catch (err) {
+      addToast(`Failed to ${state?.paused ? 'resume' : 'pause'} downloads: ${err}`, 'error');
+    }
+  };
+
+  return (
+    <Button size="sm" variant="outline" onClick={handleClick} disabled={!state}>
+      {state?.paused ? 'Resume' : 'Pause'}
+    </Button>
+  );
+};
+
+/**
+ * Cancel the running install or update; finished files stay and partial ones resume later
+ */
+export const CancelInstallButton: FC = () => {
+  const [cancelling, setCancelling] = useState(false);
+  const { addToast } = useToast();
+
+  const handleClick = async () => {
+    setCancelling(true);
+    try {
+      await cancelInstallation();
+    } catch (err) {
+      addToast(`Failed to cancel installation: ${err}`, 'error');
+      setCancelling(false);
+    }
+  };
+
+  return (
+    <Button size="sm" variant="outline" onClick={handleClick} disabled={cancelling}>
+      {cancelling ? 'Cancelling...' : 'Cancel'}
+    </Button>
+  );
+};
+
+/**
+ * Download speed cap for metered connections, shared across all concurrent downloads
+ */
+export const DownloadSettings: FC = () => {
+  const [state, setState] = useDownloadState();
+  const [limitText, setLimitText] = useState('');
+  const { addToast } = useToast();
+
+  useEffect(() => {
+    if (state) {
+      setLimitText(state.max_bytes_per_sec ? String(state.max_bytes_per_sec / BYTES_PER_MB) : '');
+    }
+  }, [state?.max_bytes_per_sec]);
+
+  const limitMb = limitText.trim() === '' ? 0 : Number(limitText);
+  const limitValid = Number.isFinite(limitMb) && limitMb >= 0;
+
+  const handleSave = async () => {
+    try {
+      setState(await setDownloadLimit(Math.round(limitMb * BYTES_PER_MB)));
+      addToast(limitMb ? `Downloads capped at ${limitMb} MB/s` : 'Download speed unlimited', 'success');
+    } catch (err) {
+      addToast(`Failed to save download limit: ${err}`, 'error');
+    }
+  };
+
+  return (
+    <div>
+      <h2 className="text-xl font-semibold mb-4 text-white">Downloads</h2>
+
+      <div className="flex items-end gap-3">
+        <div className="w-48">
+          <Input
+            label="Speed limit (MB/s)"
+            type="number"
+            min={0}
+            step={0.5}
+            value={limitText}
+            onChange={(e) => setLimitText(e.target.value)}
+            error={limitValid ? undefined : 'Enter a positive number'}
+            helperText="Leave empty for unlimited"
+          />
+        </div>
+        <Button size="sm" onClick={handleSave} disabled={!state || !limitValid}>
+          Save
+        </Button>
+        <PauseDownloadsButton />
+      </div>
+    </div>
+  );
+};
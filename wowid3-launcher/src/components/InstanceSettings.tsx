@@ -0,0 +1,179 @@
+import { FC, useCallback, useEffect, useState } from 'react';
+import { useSettingsStore } from '../stores/settingsStore';
+import {
+  cloneInstance,
+  createInstance,
+  deleteInstance,
+  listInstances,
+  setActiveInstance,
+  updateInstance,
+} from '../hooks/useTauriCommands';
+import type { Instance } from '../types/minecraft';
+import { Button } from './ui/Button';
+import { Input } from './ui/Input';
+
+/**
+ * Separate game directories (e.g. the main pack and a creative test pack), each with
+ * its own modpack source and memory settings
+ */
+export const InstanceSettings: FC = () => {
+  const activeInstanceId = useSettingsStore((state) => state.activeInstanceId);
+  const applyInstance = useSettingsStore((state) => state.applyInstance);
+
+  const [instances, setInstances] = useState<Instance[]>([]);
+  const [name, setName] = useState('');
+  const [manifestUrlText, setManifestUrlText] = useState('');
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      // The first load turns the current game directory into the default instance
+      const { gameDirectory } = useSettingsStore.getState();
+      const config = await listInstances(gameDirectory || null);
+      setInstances(config.instances);
+
+      const active = config.instances.find((i) => i.id === config.active);
+      if (active && useSettingsStore.getState().activeInstanceId === null) {
+        applyInstance(active);
+      }
+    } catch (err) {
+      setError(String(err));
+    }
+  }, [applyInstance]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  // Save the settings edited on this screen back into the instance being left
+  const saveActiveInstance = useCallback(async () => {
+    const state = useSettingsStore.getState();
+    const current = instances.find((i) => i.id === state.activeInstanceId);
+    if (!current) return;
+    await updateInstance({
+      ...current,
+      game_dir: state.gameDirectory,
+      manifest_url: state.manifestUrl,
+      jvm: state.getJvmSettings(),
+    });
+  }, [instances]);
+
+  const run = useCallback(
+    async (action: () => Promise<void>) => {
+      setBusy(true);
+      setError(null);
+      try {
+        await action();
+        await refresh();
+      } catch (err) {
+        setError(String(err));
+      } finally {
+        setBusy(false);
+      }
+    },
+    [refresh]
+  );
+
+  const handleSwitch = (instance: Instance) =>
+    run(async () => {
+      await saveActiveInstance();
+      applyInstance(await setActiveInstance(instance.id));
+    });
+
+  const handleCreate = () =>
+    run(async () => {
+      await createInstance({ name: name.trim(), manifest_url: manifestUrlText.trim() || null });
+      setName('');
+      setManifestUrlText('');
+    });
+
+  const handleClone = (instance: Instance) => {
+    const cloneName = prompt(`Name for the copy of ${instance.name}:`, `${instance.name} (copy)`);
+    if (!cloneName?.trim()) return;
+    run(async () => {
+      if (instance.id === activeInstanceId) {
+        await saveActiveInstance();
+      }
+      await cloneInstance(instance.id, cloneName.trim());
+    });
+  };
+
+  const handleDelete = (instance: Instance) => {
+    if (!confirm(`Delete ${instance.name}?`)) return;
+    const deleteFiles = confirm(`Also delete its game directory?\n${instance.game_dir}`);
+    run(async () => {
+      await deleteInstance(instance.id, deleteFiles);
+      if (instance.id === activeInstanceId) {
+        const config = await listInstances();
+        const next = config.instances.find((i) => i.id === config.active);
+        if (next) applyInstance(next);
+      }
+    });
+  };
+
+  return (
+    <div>
+      <h2 className="text-xl font-semibold mb-4 text-white">Instances</h2>
+
+      <ul className="space-y-2 mb-6">
+        {instances.map((instance) => (
+          <li
+            key={instance.id}
+            className="flex items-center justify-between gap-3 px-3 py-2 rounded border border-gray-600 text-sm"
+          >
+            <div className="min-w-0">
+              <div className="text-gray-200">
+                {instance.name}
+                {instance.installed_version && (
+                  <span className="text-gray-500"> · v{instance.installed_version}</span>
+                )}
+              </div>
+              <div className="text-xs text-gray-500 truncate">{instance.game_dir}</div>
+            </div>
+            <div className="flex gap-2">
+              <Button
+                size="sm"
+                variant={instance.id === activeInstanceId ? 'secondary' : 'primary'}
+                disabled={busy || instance.id === activeInstanceId}
+                onClick={() => handleSwitch(instance)}
+              >
+                {instance.id === activeInstanceId ? 'Active' : 'Use'}
+              </Button>
+              <Button size="sm" variant="outline" disabled={busy} onClick={() => handleClone(instance)}>
+                Clone
+              </Button>
+              <Button
+                size="sm"
+                variant="outline"
+                disabled={busy || instances.length === 1}
+                onClick={() => handleDelete(instance)}
+              >
+                Delete
+              </Button>
+            </div>
+          </li>
+        ))}
+      </ul>
+
+      <div className="flex items-end gap-3">
+        <div className="flex-1">
+          <Input label="New instance" value={name} onChange={(e) => setName(e.target.value)} />
+        </div>
+        <div className="flex-1">
+          <Input
+            label="Manifest URL"
+            value={manifestUrlText}
+            onChange={(e) => setManifestUrlText(e.target.value)}
+            helperText="Leave empty for the main modpack"
+          />
+        </div>
+        <Button size="sm" onClick={handleCreate} disabled={busy || !name.trim()}>
+          Create
+        </Button>
+      </div>
+
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+};
@@ -0,0 +1,119 @@
+import { FC, useCallback, useState } from 'react';
+import { useSettingsStore } from '../stores/settingsStore';
+import { detectJavaInstallations, validateJava } from '../hooks/useTauriCommands';
+import type { JavaInstallation } from '../types/minecraft';
+import { Button } from './ui/Button';
+import { Input } from './ui/Input';
+
+const describe = (java: JavaInstallation) =>
+  `Java ${java.version}${java.vendor ? ` (${java.vendor})` : ''}${java.arch ? ` · ${java.arch}` : ''}`;
+
+/**
+ * Pick an existing Java runtime instead of downloading the bundled one
+ */
+export const JavaSettings: FC = () => {
+  const javaPath = useSettingsStore((state) => state.javaPath);
+  const setJavaPath = useSettingsStore((state) => state.setJavaPath);
+
+  const [installations, setInstallations] = useState<JavaInstallation[] | null>(null);
+  const [isDetecting, setIsDetecting] = useState(false);
+  const [customPath, setCustomPath] = useState('');
+  const [error, setError] = useState<string | null>(null);
+
+  const handleDetect = useCallback(async () => {
+    setIsDetecting(true);
+    setError(null);
+    try {
+      setInstallations(await detectJavaInstallations());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsDetecting(false);
+    }
+  }, []);
+
+  const handleUseCustom = useCallback(async () => {
+    setError(null);
+    try {
+      const java = await validateJava(customPath.trim());
+      if (!java.compatible) {
+        setError(`${describe(java)} can't run the modpack (needs 64-bit Java 17 or newer)`);
+        return;
+      }
+      setJavaPath(java.path);
+      setCustomPath('');
+    } catch (err) {
+      setError(String(err));
+    }
+  }, [customPath, setJavaPath]);
+
+  return (
+    <div>
+      <h2 className="text-xl font-semibold mb-2 text-white">Java Runtime</h2>
+      <p className="text-xs text-gray-400 mb-4">
+        Current: {javaPath ?? 'Bundled runtime (downloaded automatically)'}
+      </p>
+
+      <div className="flex gap-3 mb-4">
+        <Button size="sm" onClick={handleDetect} disabled={isDetecting}>
+          {isDetecting ? 'Scanning...' : 'Detect installed Java'}
+        </Button>
+        {javaPath && (
+          <Button size="sm" variant="outline" onClick={() => setJavaPath(null)}>
+            Use bundled runtime
+          </Button>
+        )}
+      </div>
+
+      {installations && installations.length === 0 && (
+        <p className="text-sm text-gray-400 mb-4">No Java installations found.</p>
+      )}
+
+      {installations && installations.length > 0 && (
+        <ul className="space-y-2 mb-4">
+          {installations.map((java) => (
+            <li
+              key={java.path}
+              className="flex items-center justify-between gap-3 px-3 py-2 rounded border border-gray-600 text-sm"
+            >
+              <div className="min-w-0">
+                <div className="text-gray-200">
+                  {describe(java)} <span className="text-gray-500">[{java.source}]</span>
+                </div>
+                <div className="text-xs text-gray-500 truncate">{java.path}</div>
+              </div>
+              {java.compatible ? (
+                <Button
+                  size="sm"
+                  variant={javaPath === java.path ? 'secondary' : 'primary'}
+                  disabled={javaPath === java.path}
+                  onClick={() => setJavaPath(java.path)}
+                >
+                  {javaPath === java.path ? 'In use' : 'Use'}
+                </Button>
+              ) : (
+                <span className="text-xs text-yellow-400 whitespace-nowrap">Incompatible</span>
+              )}
+            </li>
+          ))}
+        </ul>
+      )}
+
+      <div className="flex items-end gap-3">
+        <div className="flex-1">
+          <Input
+            label="Custom Java executable"
+            value={customPath}
+            onChange={(e) => setCustomPath(e.target.value)}
+            helperText="Full path to java (or javaw.exe on Windows)"
+          />
+        </div>
+        <Button size="sm" onClick={handleUseCustom} disabled={!customPath.trim()}>
+          Validate
+        </Button>
+      </div>
+
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+};
@@ -0,0 +1,214 @@
+import { FC, useCallback, useEffect, useState } from 'react';
+import { useSettingsStore } from '../stores/settingsStore';
+import {
+  deleteJvmProfile,
+  listJvmProfiles,
+  saveJvmProfile,
+  validateJvmSettings,
+} from '../hooks/useTauriCommands';
+import type { GcPreset, JvmProfile } from '../types/minecraft';
+import { Button } from './ui/Button';
+import { Input } from './ui/Input';
+
+const GC_PRESETS: { value: GcPreset; label: string }[] = [
+  { value: 'g1_tuned', label: 'G1 (tuned for modpacks)' },
+  { value: 'g1', label: 'G1 (JVM defaults)' },
+  { value: 'zgc', label: 'ZGC (low pauses, Java 21+)' },
+];
+
+/**
+ * GC preset, initial heap and extra JVM flags, with named profiles
+ */
+export const JvmProfileSettings: FC = () => {
+  const {
+    minHeapMb,
+    gcPreset,
+    customJvmArgs,
+    jvmProfile,
+    setJvmSettings,
+    getJvmSettings,
+  } = useSettingsStore();
+
+  const [profiles, setProfiles] = useState<JvmProfile[]>([]);
+  const [argsText, setArgsText] = useState(customJvmArgs.join('\n'));
+  const [minHeapText, setMinHeapText] = useState(minHeapMb?.toString() ?? '');
+  const [profileName, setProfileName] = useState('');
+  const [error, setError] = useState<string | null>(null);
+
+  const refreshProfiles = useCallback(async () => {
+    try {
+      setProfiles(await listJvmProfiles());
+    } catch (err) {
+      setError(String(err));
+    }
+  }, []);
+
+  useEffect(() => {
+    refreshProfiles();
+  }, [refreshProfiles]);
+
+  useEffect(() => {
+    setArgsText(customJvmArgs.join('\n'));
+  }, [customJvmArgs]);
+
+  useEffect(() => {
+    setMinHeapText(minHeapMb?.toString() ?? '');
+  }, [minHeapMb]);
+
+  // Validate in the backend so users see bad flags here instead of in a crash log
+  const applySettings = useCallback(
+    async (changes: Partial<ReturnType<typeof getJvmSettings>>) => {
+      const next = { ...getJvmSettings(), ...changes };
+      try {
+        await validateJvmSettings(next);
+        setError(null);
+        setJvmSettings(next, null);
+      } catch (err) {
+        setError(String(err));
+      }
+    },
+    [getJvmSettings, setJvmSettings]
+  );
+
+  const handleArgsBlur = useCallback(() => {
+    const args = argsText
+      .split('\n')
+      .map((line) => line.trim())
+      .filter((line) => line.length > 0);
+    applySettings({ custom_args: args });
+  }, [argsText, applySettings]);
+
+  const handleProfileSelect = useCallback(
+    (name: string) => {
+      const profile = profiles.find((p) => p.name === name);
+      if (profile) {
+        setError(null);
+        setJvmSettings(profile.settings, profile.name);
+      }
+    },
+    [profiles, setJvmSettings]
+  );
+
+  const handleSaveProfile = useCallback(async () => {
+    try {
+      const saved = await saveJvmProfile({
+        name: profileName,
+        settings: getJvmSettings(),
+        builtin: false,
+      });
+      setProfileName('');
+      setError(null);
+      setJvmSettings(saved.settings, saved.name);
+      await refreshProfiles();
+    } catch (err) {
+      setError(String(err));
+    }
+  }, [profileName, getJvmSettings, setJvmSettings, refreshProfiles]);
+
+  const handleDeleteProfile = useCallback(async () => {
+    if (!jvmProfile) return;
+    try {
+      await deleteJvmProfile(jvmProfile);
+      setJvmSettings(getJvmSettings(), null);
+      await refreshProfiles();
+    } catch (err) {
+      setError(String(err));
+    }
+  }, [jvmProfile, getJvmSettings, setJvmSettings, refreshProfiles]);
+
+  const selectedProfile = profiles.find((p) => p.name === jvmProfile);
+
+  return (
+    <div>
+      <h2 className="text-xl font-semibold mb-6 text-white">Memory &amp; JVM</h2>
+
+      <div className="space-y-6">
+        <div className="flex items-end gap-3">
+          <div className="flex-1">
+            <label htmlFor="jvmProfile" className="block text-sm font-medium text-gray-200 mb-2">
+              Profile
+            </label>
+            <select
+              id="jvmProfile"
+              value={jvmProfile ?? ''}
+              onChange={(e) => handleProfileSelect(e.target.value)}
+              className="w-full px-3 py-2 rounded bg-gray-700 border border-gray-600 text-white"
+            >
+              <option value="">Custom</option>
+              {profiles.map((profile) => (
+                <option key={profile.name} value={profile.name}>
+                  {profile.name}
+                  {profile.builtin ? '' : ' (saved)'}
+                </option>
+              ))}
+            </select>
+          </div>
+          {selectedProfile && !selectedProfile.builtin && (
+            <Button size="sm" variant="danger" onClick={handleDeleteProfile}>
+              Delete
+            </Button>
+          )}
+        </div>
+
+        <div>
+          <label htmlFor="gcPreset" className="block text-sm font-medium text-gray-200 mb-2">
+            Garbage collector
+          </label>
+          <select
+            id="gcPreset"
+            value={gcPreset}
+            onChange={(e) => applySettings({ gc_preset: e.target.value as GcPreset })}
+            className="w-full px-3 py-2 rounded bg-gray-700 border border-gray-600 text-white"
+          >
+            {GC_PRESETS.map((preset) => (
+              <option key={preset.value} value={preset.value}>
+                {preset.label}
+              </option>
+            ))}
+          </select>
+        </div>
+
+        <Input
+          label="Initial heap (MB)"
+          type="number"
+          value={minHeapText}
+          onChange={(e) => setMinHeapText(e.target.value)}
+          onBlur={() => applySettings({ min_heap_mb: minHeapText.trim() === '' ? null : Number(minHeapText) })}
+          helperText="Leave empty to reserve the full RAM allocation at startup"
+        />
+
+        <div>
+          <label htmlFor="customJvmArgs" className="block text-sm font-medium text-gray-200 mb-2">
+            Extra JVM arguments
+          </label>
+          <textarea
+            id="customJvmArgs"
+            value={argsText}
+            onChange={(e) => setArgsText(e.target.value)}
+            onBlur={handleArgsBlur}
+            rows={3}
+            placeholder="-XX:+AlwaysPreTouch"
+            className="w-full px-3 py-2 rounded bg-gray-700 border border-gray-600 text-white font-mono text-sm"
+          />
+          <p className="text-xs text-gray-400 mt-1">One flag per line. Heap size and GC are set above.</p>
+        </div>
+
+        <div className="flex items-end gap-3">
+          <div className="flex-1">
+            <Input
+              label="Save as profile"
+              value={profileName}
+              onChange={(e) => setProfileName(e.target.value)}
+              placeholder="e.g. Shaders + Distant Horizons"
+            />
+          </div>
+          <Button size="sm" onClick={handleSaveProfile} disabled={!profileName.trim()}>
+            Save
+          </Button>
+        </div>
+
+        {error && <p className="text-sm text-red-400">{error}</p>}
+      </div>
+    </div>
+  );
+};
@@ -1,6 +1,6 @@
 import { useState, useEffect, useRef, useCallback, useMemo } from 'react';
 import { motion, AnimatePresence } from 'framer-motion';
-import { useAuth, useModpack, useServer, useDiscord, useMinecraftInstaller, useDiscordPresence } from '../hooks';
+import { useAuth, useModpack, useServer, useDiscord, useMinecraftInstaller, usePlayerSkin } from '../hooks';
 import { useServerTracker } from '../hooks/useServerTracker';
 import { extractBaseUrl } from '../utils/url';
 import {
@@ -9,9 +9,11 @@ import {
 } from '../stores/selectors';
 import { useToast } from './ui/ToastContainer';
 import { ProgressBar } from './ui/ProgressBar';
+import { CancelInstallButton, PauseDownloadsButton } from './DownloadControls';
 import { ChangelogViewer } from './ChangelogViewer';
 import { PlayerList } from './PlayerList';
 import DeviceCodeModal from './DeviceCodeModal';
+import DemoAccountModal from './DemoAccountModal';
 import ModpackUpdateDialog from './ModpackUpdateDialog';
 import { SkinViewerWithSuspense, CatModelWithSuspense } from './LazyComponents';
 import { MinecraftSetup } from './MinecraftSetup';
@@ -23,6 +25,8 @@ import { PlayButton, usePlayButtonState } from './features/PlayButton';
 import { useGameLauncher } from '../hooks/useGameLauncher';
 import { useModpackLifecycle } from '../hooks/useModpackLifecycle';
 import { useUpdateStore } from '../stores/updateStore';
+import { useCmsStore } from '../stores/cmsStore';
+import { demoAccountsEnabled } from '../hooks/useTauriCommands';
 import type { DeviceCodeInfo } from '../hooks/useTauriCommands';
 
 export default function LauncherHome() {
@@ -30,12 +34,14 @@ export default function LauncherHome() {
   const justInstalledRef = useRef(false);
 
   // Global State
-  const { user, isAuthenticated, login, finishDeviceCodeAuth, isLoading: authLoading, error: authError } = useAuth();
+  const { user, isAuthenticated, login, finishDeviceCodeAuth, signInDemo, isLoading: authLoading, error: authError } = useAuth();
+  const playerSkin = usePlayerSkin(user?.uuid);
   const { status } = useServer();
   const ramAllocation = useRamAllocation();
   const manifestUrl = useManifestUrl();
   
-  const { state: trackerState } = useServerTracker(extractBaseUrl(manifestUrl));
+  const trackerInterval = useCmsStore((state) => state.config.polling.tracker_status_ms);
+  const { state: trackerState } = useServerTracker(extractBaseUrl(manifestUrl), trackerInterval);
   const { addToast } = useToast();
   const { isConnected: discordConnected, isConnecting: discordConnecting, error: discordError, connect: connectDiscord } = useDiscord();
   const { versionId, isInstalled: minecraftInstalled } = useMinecraftInstaller();
@@ -48,7 +54,8 @@ export default function LauncherHome() {
     isDownloading,
     isBlockedForInstall,
     downloadProgress,
-    checkUpdates
+    checkUpdates,
+    updatesSkipped
   } = useModpack();
 
   // Update Store
@@ -63,6 +70,8 @@ export default function LauncherHome() {
   // Local State
   const [showChangelog, setShowChangelog] = useState(false);
   const [deviceCodeInfo, setDeviceCodeInfo] = useState<DeviceCodeInfo | null>(null);
+  const [demoEnabled, setDemoEnabled] = useState(false);
+  const [showDemoModal, setShowDemoModal] = useState(false);
   const [isCheckingLauncherUpdates, setIsCheckingLauncherUpdates] = useState(false);
   const [isCheckingModpackUpdates, setIsCheckingModpackUpdates] = useState(false);
   const lastAuthError = useRef<string | null>(null);
@@ -115,10 +124,12 @@ export default function LauncherHome() {
 
   // Effects
 
-  // 1. Discord Presence
-  useDiscordPresence(isPlaying, user?.username, user?.uuid);
+  // Demo accounts are offered only when the launcher was started with WOWID3_DEMO_ACCOUNTS=1
+  useEffect(() => {
+    demoAccountsEnabled().then(setDemoEnabled).catch(() => setDemoEnabled(false));
+  }, []);
 
-  // 2. Auth Error Toast
+  // 1. Auth Error Toast
   useEffect(() => {
     const currentErrorMsg = authError?.message || null;
     if (authError && currentErrorMsg !== lastAuthError.current) {
@@ -129,7 +140,7 @@ export default function LauncherHome() {
     }
   }, [authError, addToast]);
 
-  // 3. Launch Error Toast
+  // 2. Launch Error Toast
   useEffect(() => {
     if (launchError) {
       addToast(launchError.message, 'error');
@@ -137,7 +148,7 @@ export default function LauncherHome() {
     }
   }, [launchError, addToast, clearLaunchError]);
 
-  // 4. Check Launcher Updates (after authentication)
+  // 3. Check Launcher Updates (after authentication)
   useEffect(() => {
     if (!isAuthenticated || authLoading) {
       return;
@@ -166,7 +177,7 @@ export default function LauncherHome() {
     checkLauncherUpdates();
   }, [isAuthenticated, authLoading, setLauncherUpdate]);
 
-  // 5. Check Modpack Updates (after authentication)
+  // 4. Check Modpack Updates (after authentication)
   useEffect(() => {
     if (!isAuthenticated || authLoading) {
       return;
@@ -209,7 +220,20 @@ export default function LauncherHome() {
     // checkUpdates() already fetches the current version internally
   }, [isAuthenticated, authLoading, checkUpdates, setModpackUpdate]);
 
-  // 6. Modpack Error Toast
+  // 6b. Release server outage - offer offline mode
+  useEffect(() => {
+    if (!updatesSkipped) return;
+    if (updatesSkipped.offline_available) {
+      addToast(
+        `Release server unreachable. Playing offline with modpack ${updatesSkipped.installed_version ?? 'unknown'}.`,
+        'warning'
+      );
+    } else {
+      addToast('Release server unreachable and no working install found to launch offline.', 'error');
+    }
+  }, [updatesSkipped, addToast]);
+
+  // 5. Modpack Error Toast
   useEffect(() => {
     if (modpackState.error) {
       addToast(modpackState.error.message, 'error');
@@ -227,20 +251,54 @@ export default function LauncherHome() {
   }, [isAuthenticated, authLoading]); // Intentionally limited dependencies to prevent loop
 
   // Handlers
+  const startMicrosoftLogin = useCallback(async () => {
+    try {
+      const deviceCode = await login();
+      if (deviceCode) {
+        setDeviceCodeInfo(deviceCode);
+        await finishDeviceCodeAuth(deviceCode.device_code, deviceCode.interval);
+        setDeviceCodeInfo(null);
+        addToast('Authentication successful!', 'success');
+      }
+    } catch (err) {
+      setDeviceCodeInfo(null);
+      addToast(`Authentication failed: ${err}`, 'error');
+    }
+  }, [login, finishDeviceCodeAuth, addToast]);
+
+  const handleDemoSignIn = useCallback(async (username: string) => {
+    try {
+      await signInDemo(username);
+      setShowDemoModal(false);
+      addToast(`Playing as demo account ${username}`, 'success');
+    } catch (err) {
+      addToast(`Demo sign-in failed: ${err}`, 'error');
+    }
+  }, [signInDemo, addToast]);
+
   const handlePlayClick = useCallback(async () => {
     // Handle authentication
     if (!isAuthenticated || !user) {
+      if (demoEnabled) {
+        setShowDemoModal(true);
+      } else {
+        await startMicrosoftLogin();
+      }
+      return;
+    }
+
+    // Release server is down: skip update checks and launch the last working install
+    if (updatesSkipped?.offline_available && user.session_id) {
       try {
-        const deviceCode = await login();
-        if (deviceCode) {
-          setDeviceCodeInfo(deviceCode);
-          await finishDeviceCodeAuth(deviceCode.device_code, deviceCode.interval);
-          setDeviceCodeInfo(null);
-          addToast('Authentication successful!', 'success');
-        }
+        await launchGame({
+          username: user.username,
+          uuid: user.uuid,
+          accessToken: user.session_id,
+          versionId: versionId ?? '',
+          offline: true
+        });
       } catch (err) {
-        setDeviceCodeInfo(null);
-        addToast(`Authentication failed: ${err}`, 'error');
+        // Error handled by hook state
       }
       return;
     }
@@ -275,15 +333,15 @@ export default function LauncherHome() {
   }, [
     isAuthenticated,
     user,
-    login,
-    finishDeviceCodeAuth,
-    addToast,
+    demoEnabled,
+    startMicrosoftLogin,
     launcherUpdate,
     modpackUpdate,
     setShowLauncherUpdateModal,
     minecraftInstalled,
     versionId,
-    launchGame
+    launchGame,
+    updatesSkipped
   ]);
 
   // Handler for modpack update confirmation
@@ -409,10 +467,18 @@ export default function LauncherHome() {
                       borderRadius: '0',
                     }}
                   >
-                    <p className="text-white font-semibold mb-3" style={{ fontFamily: "'Trebuchet MS', sans-serif" }}>Installing Update...</p>
+                    <div className="flex items-center justify-between mb-3">
+                      <p className="text-white font-semibold" style={{ fontFamily: "'Trebuchet MS', sans-serif" }}>Installing Update...</p>
+                      <div className="flex gap-2">
+                        <PauseDownloadsButton />
+                        <CancelInstallButton />
+                      </div>
+                    </div>
                     <ProgressBar
                       current={downloadProgress.current}
                       total={downloadProgress.total}
+                      speed={downloadProgress.speed}
+                      etaSeconds={downloadProgress.etaSeconds}
                       showLabel={true}
                       showPercentage={true}
                     />
@@ -467,6 +533,18 @@ export default function LauncherHome() {
                   />
                 )}
 
+                {/* Demo Account Modal */}
+                {showDemoModal && (
+                  <DemoAccountModal
+                    onSignIn={handleDemoSignIn}
+                    onUseMicrosoft={() => {
+                      setShowDemoModal(false);
+                      startMicrosoftLogin();
+                    }}
+                    onCancel={() => setShowDemoModal(false)}
+                  />
+                )}
+
                 {/* Device Code Modal */}
                 {deviceCodeInfo && (
                   <DeviceCodeModal
@@ -505,7 +583,7 @@ export default function LauncherHome() {
             <SkinViewerWithSuspense
               username={user.username}
               uuid={user.uuid}
-              skinUrl={user.skin_url}
+              skinUrl={playerSkin?.skin ?? user.skin_url}
             />
           </div>
         )}
@@ -48,7 +48,7 @@ const LauncherUpdateModal: React.FC<LauncherUpdateModalProps> = ({ updateInfo })
     setError(null);
     
     try {
-      await installLauncherUpdate(updateInfo.download_url, updateInfo.sha256);
+      await installLauncherUpdate(updateInfo.download_url, updateInfo.sha256, updateInfo.file_type, updateInfo.signature);
       // The app should restart automatically, but if not:
       setTimeout(() => {
           window.location.reload();
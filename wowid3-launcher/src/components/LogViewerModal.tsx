@@ -1,8 +1,10 @@
 import React, { useState, useEffect, useRef, useCallback, useMemo } from 'react';
 import { motion, AnimatePresence } from 'framer-motion';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../utils/invoke';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import { useSettingsStore } from '../stores/settingsStore';
 import { useToast } from './ui/ToastContainer';
+import { POLLING_INTERVALS } from '../config/constants';
 import { ChevronDown, X, Square, Zap, AlertCircle, Filter } from 'lucide-react';
 
 interface LogLine {
@@ -13,6 +15,12 @@ interface LogLine {
   message?: string;
 }
 
+/** Payload of the `game-stop-progress` event */
+interface GameStopProgress {
+  phase: 'close_requested' | 'killing' | 'stopped';
+  grace_secs: number;
+}
+
 interface LogResult {
   lines: string[];
   start_offset: number;
@@ -20,6 +28,23 @@ interface LogResult {
   total_size: number;
 }
 
+/** Line parsed by the Rust log watcher */
+interface StructuredLogLine {
+  timestamp: string | null;
+  level: string;
+  thread: string | null;
+  logger: string | null;
+  message: string;
+  raw: string;
+}
+
+/** Payload of the `minecraft-log-structured` event */
+interface StructuredLogBatch {
+  lines: StructuredLogLine[];
+  end_offset: number;
+  rotated: boolean; // latest.log was replaced; previous lines are from an older session
+}
+
 interface LogViewerModalProps {
   isOpen: boolean;
   onClose: () => void;
@@ -37,10 +62,11 @@ const LogViewerModal: React.FC<LogViewerModalProps> = ({ isOpen, onClose }) => {
   const [selectedLevels, setSelectedLevels] = useState<Set<string>>(new Set(['INFO', 'WARN', 'ERROR', 'DEBUG', 'FATAL']));
   const [showFilters, setShowFilters] = useState(false);
   const logsContainerRef = useRef<HTMLDivElement>(null);
-  const logPollingRef = useRef<number | null>(null);
+  const unlistenLogsRef = useRef<UnlistenFn | null>(null);
   const logsRef = useRef<LogLine[]>([]);
   const searchInputRef = useRef<HTMLInputElement>(null);
   const gameDir = useSettingsStore((state) => state.gameDirectory);
+  const stopGraceSecs = useSettingsStore((state) => state.gameStopGraceSecs);
   const { addToast } = useToast();
 
   // Track file offsets for efficient loading
@@ -291,59 +317,53 @@ const LogViewerModal: React.FC<LogViewerModalProps> = ({ isOpen, onClose }) => {
     }
   }, []);
 
-  // Poll for new logs
-  const startLogPolling = useCallback(() => {
-    if (logPollingRef.current) {
-      clearInterval(logPollingRef.current);
-    }
+  // Convert a watcher line into the viewer's display format
+  const fromStructured = (line: StructuredLogLine): LogLine => ({
+    raw: line.raw,
+    timestamp: line.timestamp ?? undefined,
+    level: line.level,
+    source: line.logger ?? line.thread ?? undefined,
+    message: line.message,
+  });
+
+  // Stream new lines from the Rust file watcher
+  const startLogWatcher = useCallback(async () => {
+    if (!gameDir) return;
+
+    unlistenLogsRef.current?.();
+    unlistenLogsRef.current = await listen<StructuredLogBatch>('minecraft-log-structured', (event) => {
+      const { lines, end_offset, rotated } = event.payload;
+      const newLines = lines.map(fromStructured);
+
+      if (rotated) {
+        // New game session: older lines belong to the previous log file
+        startOffsetRef.current = 0;
+        logsRef.current = newLines;
+      } else {
+        logsRef.current = [...logsRef.current, ...newLines];
+      }
 
-    console.log('[LogViewer] Starting log polling with gameDir:', gameDir);
-    let pollCount = 0;
-
-    logPollingRef.current = setInterval(async () => {
-      pollCount++;
-      try {
-        if (!gameDir) return;
-
-        // Use efficient offset-based polling
-        const result = await invoke<LogResult>('cmd_read_log_from_offset', {
-          gameDir: gameDir,
-          startOffset: endOffsetRef.current,
-        });
-
-        if (result.lines.length > 0) {
-          console.log('[LogViewer] Poll #' + pollCount + ': Received', result.lines.length, 'new lines');
-          
-          // Update end offset
-          endOffsetRef.current = result.end_offset;
-          totalSizeRef.current = result.total_size;
-          
-          const parsedNewLines = result.lines.map(parseLogLine);
-          const updatedLogs = [...logsRef.current, ...parsedNewLines];
-          
-          // Limit total logs in memory if needed, but since we paginate upwards, 
-          // we might want to keep them or implement virtual scrolling.
-          // For now, let's keep them as user might want to see history they just scrolled through.
-          // But if it gets too large (> 5000 lines), we might want to trim from top if user is at bottom.
-          
-          if (updatedLogs.length > 5000 && isScrolledToBottom) {
-             // If we have too many logs and are at bottom, trim top to save memory
-             // But this breaks "scroll up" continuity if we trim what we just loaded.
-             // Let's just keep it simple for now. The main issue was loading 100MB at once.
-             // Appending small chunks is fine.
-          }
-          
-          logsRef.current = updatedLogs;
-          setLogs(updatedLogs);
-        }
+      endOffsetRef.current = end_offset;
+      setLogs(logsRef.current);
+    });
 
-        // Check if game is still running
-        await checkGameStatus();
-      } catch (error) {
-        console.error('[LogViewer] Poll #' + pollCount + ': Error polling logs:', error);
-      }
-    }, 1000) as any;
-  }, [gameDir, checkGameStatus, isScrolledToBottom]);
+    try {
+      await invoke('cmd_start_log_watcher', {
+        gameDir: gameDir,
+        startOffset: endOffsetRef.current,
+      });
+    } catch (error) {
+      console.error('[LogViewer] Failed to start log watcher:', error);
+    }
+  }, [gameDir]);
+
+  const stopLogWatcher = useCallback(() => {
+    unlistenLogsRef.current?.();
+    unlistenLogsRef.current = null;
+    invoke('cmd_stop_log_watcher').catch((error) => {
+      console.error('[LogViewer] Failed to stop log watcher:', error);
+    });
+  }, []);
 
   // Initialize on mount
   useEffect(() => {
@@ -358,10 +378,6 @@ const LogViewerModal: React.FC<LogViewerModalProps> = ({ isOpen, onClose }) => {
       setSearchTerm('');
       setSelectedLevels(new Set(LOG_LEVELS));
       setShowFilters(false);
-      if (logPollingRef.current) {
-        clearInterval(logPollingRef.current);
-        logPollingRef.current = null;
-      }
       return;
     }
 
@@ -374,20 +390,16 @@ const LogViewerModal: React.FC<LogViewerModalProps> = ({ isOpen, onClose }) => {
     endOffsetRef.current = 0;
     setLoading(true);
 
-    // Load logs
-    loadLogs();
+    // Load the tail, then stream everything written after it
+    loadLogs().then(startLogWatcher);
 
     // Check game status
     checkGameStatus();
-
-    // Start polling
-    startLogPolling();
+    const statusInterval = setInterval(checkGameStatus, POLLING_INTERVALS.HEALTH_CHECK);
 
     return () => {
-      if (logPollingRef.current) {
-        clearInterval(logPollingRef.current);
-        logPollingRef.current = null;
-      }
+      clearInterval(statusInterval);
+      stopLogWatcher();
     };
     // eslint-disable-next-line react-hooks/exhaustive-deps
   }, [isOpen]); // Only depend on isOpen to prevent infinite loops
@@ -435,12 +447,20 @@ const LogViewerModal: React.FC<LogViewerModalProps> = ({ isOpen, onClose }) => {
     }
   };
 
-  // Stop game gracefully
+  // Stop game gracefully: close request, grace period for the world save, then kill
   const handleStopGame = async () => {
+    let unlisten: UnlistenFn | null = null;
     try {
       setIsStoppingGame(true);
-      await invoke('cmd_stop_game');
-      addToast('Game stopped successfully', 'success');
+      unlisten = await listen<GameStopProgress>('game-stop-progress', (event) => {
+        if (event.payload.phase === 'close_requested') {
+          addToast(`Saving and closing the game (up to ${event.payload.grace_secs}s)...`, 'info');
+        } else if (event.payload.phase === 'killing') {
+          addToast('Game did not close in time; force-closing it', 'warning');
+        }
+      });
+      const killed = await invoke<boolean>('cmd_stop_game', { graceSecs: stopGraceSecs });
+      addToast(killed ? 'Game force-closed' : 'Game stopped successfully', killed ? 'warning' : 'success');
 
       // Wait a moment for process to stop, then refresh status
       setTimeout(async () => {
@@ -450,6 +470,7 @@ const LogViewerModal: React.FC<LogViewerModalProps> = ({ isOpen, onClose }) => {
       console.error('Failed to stop game:', error);
       addToast(`Failed to stop game: ${error}`, 'error');
     } finally {
+      unlisten?.();
       setIsStoppingGame(false);
     }
   };
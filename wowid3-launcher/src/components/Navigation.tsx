@@ -1,15 +1,16 @@
 import { MapViewerButton } from './MapViewerButton';
-import { Home, BarChart3, Settings } from 'lucide-react';
+import { Home, BarChart3, Camera, Settings } from 'lucide-react';
 
 interface NavigationProps {
-  activeTab: 'home' | 'settings' | 'stats';
-  onTabChange: (tab: 'home' | 'settings' | 'stats') => void;
+  activeTab: 'home' | 'settings' | 'stats' | 'screenshots';
+  onTabChange: (tab: 'home' | 'settings' | 'stats' | 'screenshots') => void;
 }
 
 export const Navigation = ({ activeTab, onTabChange }: NavigationProps) => {
   const tabs = [
     { id: 'home', label: 'Home', icon: Home },
     { id: 'stats', label: 'Stats', icon: BarChart3 },
+    { id: 'screenshots', label: 'Screenshots', icon: Camera },
     { id: 'settings', label: 'Settings', icon: Settings },
   ] as const;
 
@@ -0,0 +1,92 @@
+import { FC, useCallback, useEffect, useState } from 'react';
+import { useSettingsStore } from '../stores/settingsStore';
+import { useModpack } from '../hooks/useModpack';
+import { getOptionalGroups, setOptionalGroup } from '../hooks/useTauriCommands';
+import type { OptionalGroupState } from '../types/minecraft';
+import { Button } from './ui/Button';
+import { useToast } from './ui/ToastContainer';
+
+/**
+ * Switch optional mod groups (shaders, minimaps, ...) on or off; changes are applied
+ * by verifying the installation, which downloads enabled and removes disabled files
+ */
+export const OptionalModsSettings: FC = () => {
+  const gameDirectory = useSettingsStore((state) => state.gameDirectory);
+  const { latestManifest, verifyAndRepair, isDownloading } = useModpack();
+  const { addToast } = useToast();
+
+  const [groups, setGroups] = useState<OptionalGroupState[]>([]);
+  const [pendingChanges, setPendingChanges] = useState(false);
+
+  const refresh = useCallback(async () => {
+    if (!gameDirectory || !latestManifest?.optional_groups?.length) {
+      setGroups([]);
+      return;
+    }
+    try {
+      setGroups(await getOptionalGroups(latestManifest, gameDirectory));
+    } catch (err) {
+      addToast(`Failed to load optional mods: ${err}`, 'error');
+    }
+  }, [gameDirectory, latestManifest, addToast]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  const handleToggle = async (group: OptionalGroupState, enabled: boolean) => {
+    try {
+      await setOptionalGroup(gameDirectory, group.id, enabled);
+      setGroups((current) => current.map((g) => (g.id === group.id ? { ...g, enabled } : g)));
+      setPendingChanges(true);
+    } catch (err) {
+      addToast(`Failed to update ${group.name}: ${err}`, 'error');
+    }
+  };
+
+  const handleApply = async () => {
+    try {
+      await verifyAndRepair();
+      setPendingChanges(false);
+      addToast('Optional mods applied', 'success');
+    } catch (err) {
+      addToast(`Failed to apply optional mods: ${err}`, 'error');
+    }
+  };
+
+  return (
+    <div>
+      <h2 className="text-xl font-semibold mb-4 text-white">Optional Mods</h2>
+
+      {groups.length === 0 ? (
+        <p className="text-sm text-gray-400">This modpack has no optional mods.</p>
+      ) : (
+        <>
+          <ul className="space-y-3 mb-6">
+            {groups.map((group) => (
+              <li key={group.id}>
+                <label className="flex items-start gap-3 cursor-pointer">
+                  <input
+                    type="checkbox"
+                    checked={group.enabled}
+                    onChange={(e) => handleToggle(group, e.target.checked)}
+                    disabled={isDownloading}
+                    className="w-5 h-5 rounded mt-0.5"
+                  />
+                  <div>
+                    <div className="text-white">{group.name}</div>
+                    {group.description && <div className="text-xs text-gray-400">{group.description}</div>}
+                  </div>
+                </label>
+              </li>
+            ))}
+          </ul>
+
+          <Button size="sm" onClick={handleApply} disabled={!pendingChanges || isDownloading}>
+            {isDownloading ? 'Applying...' : 'Apply'}
+          </Button>
+        </>
+      )}
+    </div>
+  );
+};
@@ -1,5 +1,5 @@
 import { useState, useEffect, memo } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../utils/invoke';
 import { ServerStatus, PlayerInfo } from '../stores/serverStore';
 import { Card } from './ui/Card';
 import { resolvePlayerName, isAvatarCached, readCachedAvatar, writeCachedAvatar } from '../hooks/useTauriCommands';
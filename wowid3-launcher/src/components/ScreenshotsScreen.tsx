@@ -0,0 +1,156 @@
+import { useCallback, useEffect, useState } from 'react';
+import { Camera, FolderOpen, RefreshCw, Trash2, Upload } from 'lucide-react';
+import { useAuthStore } from '../stores/authStore';
+import { useSettingsStore } from '../stores/settingsStore';
+import {
+  deleteScreenshot,
+  listScreenshots,
+  openScreenshotFolder,
+  uploadScreenshot,
+} from '../hooks/useTauriCommands';
+import type { Screenshot } from '../types/minecraft';
+import { LoadingSpinner } from './ui/LoadingSpinner';
+import { useToast } from './ui/ToastContainer';
+import { extractBaseUrl } from '../utils/url';
+import { logger, LogCategory } from '../utils/logger';
+
+function formatSize(bytes: number): string {
+  if (bytes >= 1024 * 1024) return `${(bytes / 1024 / 1024).toFixed(1)} MB`;
+  return `${Math.round(bytes / 1024)} KB`;
+}
+
+export function ScreenshotsScreen() {
+  const { user } = useAuthStore();
+  const { gameDirectory, manifestUrl } = useSettingsStore();
+  const { addToast } = useToast();
+  const [screenshots, setScreenshots] = useState<Screenshot[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [busy, setBusy] = useState<string | null>(null); // filename being deleted/uploaded
+
+  const refresh = useCallback(async () => {
+    if (!gameDirectory) return;
+    setLoading(true);
+    try {
+      setScreenshots(await listScreenshots(gameDirectory));
+    } catch (err) {
+      logger.error(LogCategory.UI, 'Failed to list screenshots:', err instanceof Error ? err : new Error(String(err)));
+      addToast(`Failed to load screenshots: ${err}`, 'error');
+    } finally {
+      setLoading(false);
+    }
+  }, [gameDirectory, addToast]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  const handleDelete = async (filename: string) => {
+    if (!confirm(`Delete ${filename}? This cannot be undone.`)) return;
+    setBusy(filename);
+    try {
+      await deleteScreenshot(gameDirectory, filename);
+      setScreenshots((prev) => prev.filter((s) => s.filename !== filename));
+    } catch (err) {
+      addToast(`Failed to delete screenshot: ${err}`, 'error');
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleUpload = async (filename: string) => {
+    if (!user) return;
+    setBusy(filename);
+    try {
+      await uploadScreenshot(extractBaseUrl(manifestUrl), user.uuid, gameDirectory, filename);
+      addToast('Screenshot shared with the server', 'success');
+    } catch (err) {
+      addToast(`Failed to upload screenshot: ${err}`, 'error');
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleOpenFolder = () => {
+    openScreenshotFolder(gameDirectory).catch((err) => {
+      addToast(`Failed to open screenshots folder: ${err}`, 'error');
+    });
+  };
+
+  return (
+    <div className="container mx-auto p-6 space-y-6 max-w-7xl flex flex-col items-center">
+      <div className="text-center w-full">
+        <h1 className="text-4xl font-bold text-white mb-2">📸 Screenshots</h1>
+        <p className="text-gray-400">Press F2 in game to take a screenshot</p>
+      </div>
+
+      <div className="flex gap-3">
+        <button
+          onClick={refresh}
+          disabled={loading}
+          className="flex items-center gap-2 px-4 py-2 bg-black bg-opacity-40 text-white border border-white border-opacity-20 rounded-lg hover:bg-opacity-60 disabled:opacity-50 transition-all"
+        >
+          <RefreshCw size={18} className={loading ? 'animate-spin' : ''} />
+          Refresh
+        </button>
+        <button
+          onClick={handleOpenFolder}
+          className="flex items-center gap-2 px-4 py-2 bg-black bg-opacity-40 text-white border border-white border-opacity-20 rounded-lg hover:bg-opacity-60 transition-all"
+        >
+          <FolderOpen size={18} />
+          Open Folder
+        </button>
+      </div>
+
+      {loading && screenshots.length === 0 ? (
+        <LoadingSpinner size="lg" message="Loading screenshots..." />
+      ) : screenshots.length === 0 ? (
+        <div className="flex flex-col items-center justify-center py-16 text-slate-300">
+          <Camera size={48} className="mb-4 text-slate-400" />
+          <p className="text-lg font-semibold">No screenshots yet</p>
+        </div>
+      ) : (
+        <div className="grid grid-cols-2 md:grid-cols-3 lg:grid-cols-4 gap-4 w-full">
+          {screenshots.map((shot) => (
+            <div
+              key={shot.filename}
+              className="bg-black bg-opacity-40 rounded-lg overflow-hidden border border-white border-opacity-10 backdrop-blur-sm"
+            >
+              {shot.thumbnail ? (
+                <img src={shot.thumbnail} alt={shot.filename} className="w-full aspect-video object-cover" />
+              ) : (
+                <div className="w-full aspect-video flex items-center justify-center text-slate-500 text-sm">
+                  No preview
+                </div>
+              )}
+              <div className="p-3 space-y-2">
+                <div className="text-sm text-white truncate" title={shot.filename}>{shot.filename}</div>
+                <div className="text-xs text-slate-400">
+                  {new Date(shot.taken_at).toLocaleString()} • {formatSize(shot.size_bytes)}
+                </div>
+                <div className="flex gap-2">
+                  <button
+                    onClick={() => handleUpload(shot.filename)}
+                    disabled={!user || busy === shot.filename}
+                    title={user ? 'Share with the server' : 'Log in to share screenshots'}
+                    className="flex-1 flex items-center justify-center gap-1 px-2 py-1 rounded bg-blue-600 hover:bg-blue-700 text-white text-xs disabled:opacity-50 disabled:cursor-not-allowed"
+                  >
+                    <Upload size={14} />
+                    Share
+                  </button>
+                  <button
+                    onClick={() => handleDelete(shot.filename)}
+                    disabled={busy === shot.filename}
+                    title="Delete"
+                    className="flex items-center justify-center px-2 py-1 rounded bg-red-600 hover:bg-red-700 text-white text-xs disabled:opacity-50"
+                  >
+                    <Trash2 size={14} />
+                  </button>
+                </div>
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
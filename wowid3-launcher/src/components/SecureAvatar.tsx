@@ -1,5 +1,5 @@
 import { FC, useState, useEffect } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../utils/invoke';
 
 interface SecureAvatarProps {
   username: string;
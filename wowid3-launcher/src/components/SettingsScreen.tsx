@@ -4,6 +4,14 @@ import { useVpnStore } from '../stores/vpnStore';
 import { useAudio } from '../hooks';
 import { Input } from './ui/Input';
 import { NetworkTest } from './NetworkTest';
+import { DownloadSettings } from './DownloadControls';
+import { InstanceSettings } from './InstanceSettings';
+import { JavaSettings } from './JavaSettings';
+import { JvmProfileSettings } from './JvmProfileSettings';
+import { OptionalModsSettings } from './OptionalModsSettings';
+import { WorldBackups } from './WorldBackups';
+import { StorageSettings } from './StorageSettings';
+import { SupportReport } from './SupportReport';
 import { VpnSetupModal } from './VpnSetupModal';
 // Logger import for future use
 // import { logger, LogCategory } from '../utils/logger';
@@ -44,6 +52,14 @@ export const SettingsScreen: FC = () => {
     setRamAllocation,
     keepLauncherOpen,
     setKeepLauncherOpen,
+    gameStopGraceSecs,
+    setGameStopGraceSecs,
+    releaseChannel,
+    setReleaseChannel,
+    uploadCrashReports,
+    setUploadCrashReports,
+    shareUpdateTelemetry,
+    setShareUpdateTelemetry,
   } = useSettingsStore();
 
   const vpnEnabled = useVpnStore((state) => state.enabled);
@@ -114,9 +130,9 @@ export const SettingsScreen: FC = () => {
       // Check if VPN is already configured
       const { invoke } = await import('@tauri-apps/api/core');
       try {
-        const hasKeypair = await invoke<boolean>('vpn_has_keypair');
+        const setup = await invoke<{ registered: boolean }>('cmd_vpn_status');
 
-        if (!hasKeypair) {
+        if (!setup.registered) {
           // Show setup modal for first-time setup
           setShowVpnSetupModal(true);
         } else {
@@ -125,7 +141,7 @@ export const SettingsScreen: FC = () => {
 
           // Try to start the tunnel
           try {
-            await invoke('vpn_start_tunnel');
+            await invoke('cmd_vpn_connect');
             useVpnStore.getState().setStatus('connected');
           } catch (err) {
             console.error('Failed to start VPN tunnel:', err);
@@ -144,7 +160,7 @@ export const SettingsScreen: FC = () => {
       // Try to stop the tunnel
       const { invoke } = await import('@tauri-apps/api/core');
       try {
-        await invoke('vpn_stop_tunnel');
+        await invoke('cmd_vpn_disconnect');
       } catch (err) {
         console.error('Failed to stop VPN tunnel:', err);
       }
@@ -211,6 +227,16 @@ export const SettingsScreen: FC = () => {
             helperText={`Recommended: ${getRecommendedRam()}MB. Allocated to Minecraft Java process.`}
           />
 
+          <Input
+            label="Stop Grace Period (seconds)"
+            type="number"
+            min={5}
+            max={300}
+            value={gameStopGraceSecs}
+            onChange={(e) => setGameStopGraceSecs(Number(e.target.value))}
+            helperText="How long Stop Game waits for the world to save before force-closing Minecraft"
+          />
+
           <div className="space-y-4 pt-2">
             <div>
               <label htmlFor="volume" className="block text-sm font-medium text-gray-200 mb-2">
@@ -247,10 +273,93 @@ export const SettingsScreen: FC = () => {
                 Keep launcher open while game is running
               </label>
             </div>
+
+            <div className="flex items-center space-x-3">
+              <input
+                id="betaChannel"
+                type="checkbox"
+                checked={releaseChannel === 'beta'}
+                onChange={(e) => setReleaseChannel(e.target.checked ? 'beta' : 'stable')}
+                className="w-5 h-5 rounded border-gray-300 text-blue-600 focus:ring-blue-500 bg-gray-700 border-gray-600"
+              />
+              <label
+                htmlFor="betaChannel"
+                className="text-sm font-medium text-gray-200 cursor-pointer"
+              >
+                Receive beta modpack updates
+              </label>
+            </div>
+
+            <div className="flex items-center space-x-3">
+              <input
+                id="uploadCrashReports"
+                type="checkbox"
+                checked={uploadCrashReports}
+                onChange={(e) => setUploadCrashReports(e.target.checked)}
+                className="w-5 h-5 rounded border-gray-300 text-blue-600 focus:ring-blue-500 bg-gray-700 border-gray-600"
+              />
+              <label
+                htmlFor="uploadCrashReports"
+                className="text-sm font-medium text-gray-200 cursor-pointer"
+              >
+                Send crash reports to the modpack team
+              </label>
+            </div>
+
+            <div className="flex items-center space-x-3">
+              <input
+                id="shareUpdateTelemetry"
+                type="checkbox"
+                checked={shareUpdateTelemetry}
+                onChange={(e) => setShareUpdateTelemetry(e.target.checked)}
+                className="w-5 h-5 rounded border-gray-300 text-blue-600 focus:ring-blue-500 bg-gray-700 border-gray-600"
+              />
+              <label
+                htmlFor="shareUpdateTelemetry"
+                className="text-sm font-medium text-gray-200 cursor-pointer"
+              >
+                Share anonymous update statistics (speed and failures) with the modpack team
+              </label>
+            </div>
           </div>
         </div>
       </div>
 
+      {/* Instances */}
+      <div className="bg-black bg-opacity-40 p-6 rounded-lg backdrop-blur-sm border border-white border-opacity-10 mb-8">
+        <InstanceSettings />
+      </div>
+
+      {/* Optional Mods */}
+      <div className="bg-black bg-opacity-40 p-6 rounded-lg backdrop-blur-sm border border-white border-opacity-10 mb-8">
+        <OptionalModsSettings />
+      </div>
+
+      {/* Memory & JVM */}
+      <div className="bg-black bg-opacity-40 p-6 rounded-lg backdrop-blur-sm border border-white border-opacity-10 mb-8">
+        <JvmProfileSettings />
+      </div>
+
+      {/* Java Runtime */}
+      <div className="bg-black bg-opacity-40 p-6 rounded-lg backdrop-blur-sm border border-white border-opacity-10 mb-8">
+        <JavaSettings />
+      </div>
+
+      {/* Downloads */}
+      <div className="bg-black bg-opacity-40 p-6 rounded-lg backdrop-blur-sm border border-white border-opacity-10 mb-8">
+        <DownloadSettings />
+      </div>
+
+      {/* World Backups */}
+      <div className="bg-black bg-opacity-40 p-6 rounded-lg backdrop-blur-sm border border-white border-opacity-10 mb-8">
+        <WorldBackups />
+      </div>
+
+      {/* Storage */}
+      <div className="bg-black bg-opacity-40 p-6 rounded-lg backdrop-blur-sm border border-white border-opacity-10 mb-8">
+        <StorageSettings />
+      </div>
+
       {/* Performance Section - VPN */}
       <div className="bg-black bg-opacity-40 p-6 rounded-lg backdrop-blur-sm border border-white border-opacity-10 mb-8">
         <h2 className="text-xl font-semibold mb-6 text-white">Performance</h2>
@@ -294,10 +403,15 @@ export const SettingsScreen: FC = () => {
       </div>
 
       {/* Network Test */}
-      <div className="bg-black bg-opacity-40 p-6 rounded-lg backdrop-blur-sm border border-white border-opacity-10">
+      <div className="bg-black bg-opacity-40 p-6 rounded-lg backdrop-blur-sm border border-white border-opacity-10 mb-8">
         <NetworkTest />
       </div>
 
+      {/* Support */}
+      <div className="bg-black bg-opacity-40 p-6 rounded-lg backdrop-blur-sm border border-white border-opacity-10">
+        <SupportReport />
+      </div>
+
       <div className="mt-8 text-center text-xs text-gray-500">
         <p>WOWID3 Launcher v{import.meta.env.PACKAGE_VERSION || '0.1.0'}</p>
       </div>
@@ -181,7 +181,7 @@ const SkinViewerComponentBase = ({ username, uuid, skinUrl }: SkinViewerComponen
         logger.error(LogCategory.MINECRAFT, `Error initializing viewer: ${String(err)}`);
       }
     }
-  }, [username, uuid]);
+  }, [username, uuid, skinUrl]);
 
   return (
     <div
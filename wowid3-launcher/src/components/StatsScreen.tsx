@@ -104,7 +104,7 @@ export function StatsScreen() {
   const { user } = useAuthStore();
   const { manifestUrl } = useSettingsStore();
   const serverUrl = extractBaseUrl(manifestUrl);
-  const { stats, loading, error, refresh } = usePlayerStats(user?.uuid || null, serverUrl);
+  const { stats, sessions, loading, error, refresh } = usePlayerStats(user?.uuid || null, serverUrl);
 
   if (loading && !stats) {
     return (
@@ -194,7 +194,11 @@ export function StatsScreen() {
           value={formatPlaytime(stats.playtime_seconds)}
           icon="⏱️"
           color="gold"
-          subtitle={`${(stats.playtime_seconds / 3600).toFixed(1)} hours`}
+          subtitle={
+            sessions
+              ? `${(sessions.playtime_last_7_days_secs / 3600).toFixed(1)}h this week`
+              : `${(stats.playtime_seconds / 3600).toFixed(1)} hours`
+          }
         />
         <StatCard
           label="Mobs Killed"
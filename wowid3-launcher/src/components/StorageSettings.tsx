@@ -0,0 +1,130 @@
+import { FC, useCallback, useEffect, useState } from 'react';
+import { useSettingsStore } from '../stores/settingsStore';
+import { cleanCrashReports, cleanLogs, cleanUnusedAssets, getDiskUsage } from '../hooks/useTauriCommands';
+import type { CleanupResult, DiskUsage } from '../types/minecraft';
+import { Button } from './ui/Button';
+import { useToast } from './ui/ToastContainer';
+
+const CATEGORY_LABELS: Record<string, string> = {
+  mods: 'Mods',
+  config: 'Config',
+  saves: 'Worlds',
+  logs: 'Logs',
+  crash_reports: 'Crash reports',
+  cache: 'Cache',
+  assets: 'Game assets',
+  libraries: 'Libraries',
+  versions: 'Minecraft versions',
+  resourcepacks: 'Resource & shader packs',
+  screenshots: 'Screenshots',
+  other: 'Other',
+};
+
+const formatSize = (bytes: number) => {
+  if (bytes >= 1024 * 1024 * 1024) return `${(bytes / 1024 / 1024 / 1024).toFixed(1)} GB`;
+  if (bytes >= 1024 * 1024) return `${(bytes / 1024 / 1024).toFixed(1)} MB`;
+  return `${Math.round(bytes / 1024)} KB`;
+};
+
+type Cleanup = 'logs' | 'crash_reports' | 'assets';
+
+/**
+ * Show what the game directory is using and clean up files that are safe to delete
+ */
+export const StorageSettings: FC = () => {
+  const gameDirectory = useSettingsStore((state) => state.gameDirectory);
+  const { addToast } = useToast();
+
+  const [usage, setUsage] = useState<DiskUsage | null>(null);
+  const [busy, setBusy] = useState<Cleanup | 'scan' | null>(null);
+
+  const refresh = useCallback(async () => {
+    if (!gameDirectory) return;
+    setBusy('scan');
+    try {
+      setUsage(await getDiskUsage(gameDirectory));
+    } catch (err) {
+      addToast(`Failed to measure disk usage: ${err}`, 'error');
+    } finally {
+      setBusy(null);
+    }
+  }, [gameDirectory, addToast]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  const runCleanup = async (kind: Cleanup, label: string, clean: (gameDir: string) => Promise<CleanupResult>) => {
+    setBusy(kind);
+    try {
+      const result = await clean(gameDirectory);
+      addToast(`Removed ${result.files_removed} ${label} (${formatSize(result.bytes_freed)} freed)`, 'success');
+    } catch (err) {
+      addToast(`Failed to clean up ${label}: ${err}`, 'error');
+    } finally {
+      setBusy(null);
+    }
+    await refresh();
+  };
+
+  const largest = usage?.categories[0]?.bytes || 1;
+
+  return (
+    <div>
+      <div className="flex items-center justify-between mb-4">
+        <h2 className="text-xl font-semibold text-white">Storage</h2>
+        <Button size="sm" variant="outline" onClick={refresh} disabled={busy !== null}>
+          {busy === 'scan' ? 'Scanning...' : 'Refresh'}
+        </Button>
+      </div>
+
+      {usage && (
+        <>
+          <p className="text-sm text-gray-400 mb-4">
+            The game is using {formatSize(usage.total_bytes)}
+            {usage.available_bytes !== null && ` • ${formatSize(usage.available_bytes)} free on this disk`}
+          </p>
+
+          <ul className="space-y-2 mb-6">
+            {usage.categories
+              .filter((category) => category.bytes > 0)
+              .map((category) => (
+                <li key={category.category} className="text-sm">
+                  <div className="flex justify-between text-gray-200">
+                    <span>{CATEGORY_LABELS[category.category] ?? category.category}</span>
+                    <span className="text-gray-400">{formatSize(category.bytes)}</span>
+                  </div>
+                  <div className="h-1.5 mt-1 rounded bg-gray-700">
+                    <div
+                      className="h-1.5 rounded bg-blue-500"
+                      style={{ width: `${Math.max(1, (category.bytes / largest) * 100)}%` }}
+                    />
+                  </div>
+                </li>
+              ))}
+          </ul>
+        </>
+      )}
+
+      <div className="flex flex-wrap gap-3">
+        <Button variant="outline" onClick={() => runCleanup('logs', 'old logs', cleanLogs)} disabled={busy !== null}>
+          {busy === 'logs' ? 'Cleaning...' : 'Delete old logs'}
+        </Button>
+        <Button
+          variant="outline"
+          onClick={() => runCleanup('crash_reports', 'crash reports', cleanCrashReports)}
+          disabled={busy !== null}
+        >
+          {busy === 'crash_reports' ? 'Cleaning...' : 'Delete crash reports'}
+        </Button>
+        <Button
+          variant="outline"
+          onClick={() => runCleanup('assets', 'unused assets', cleanUnusedAssets)}
+          disabled={busy !== null}
+        >
+          {busy === 'assets' ? 'Cleaning...' : 'Delete unused assets'}
+        </Button>
+      </div>
+    </div>
+  );
+};
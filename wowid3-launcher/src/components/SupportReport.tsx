@@ -0,0 +1,81 @@
+import { FC, useState } from 'react';
+import { useSettingsStore } from '../stores/settingsStore';
+import { getSystemReport, uploadSystemReport } from '../hooks/useTauriCommands';
+import { extractBaseUrl } from '../utils/url';
+import { Button } from './ui/Button';
+import { Input } from './ui/Input';
+import { useToast } from './ui/ToastContainer';
+
+/**
+ * Gather OS, hardware, Java and modpack details to copy into a support request or send to the team
+ */
+export const SupportReport: FC = () => {
+  const gameDirectory = useSettingsStore((state) => state.gameDirectory);
+  const javaPath = useSettingsStore((state) => state.javaPath);
+  const manifestUrl = useSettingsStore((state) => state.manifestUrl);
+  const { addToast } = useToast();
+
+  const [note, setNote] = useState('');
+  const [busy, setBusy] = useState<'copy' | 'send' | null>(null);
+  const [reportId, setReportId] = useState<number | null>(null);
+
+  const handleCopy = async () => {
+    setBusy('copy');
+    try {
+      const report = await getSystemReport(gameDirectory, javaPath);
+      await navigator.clipboard.writeText(JSON.stringify(report, null, 2));
+      addToast('System report copied to clipboard', 'success');
+    } catch (err) {
+      addToast(`Failed to gather system report: ${err}`, 'error');
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleSend = async () => {
+    setBusy('send');
+    try {
+      const report = await getSystemReport(gameDirectory, javaPath);
+      const result = await uploadSystemReport(extractBaseUrl(manifestUrl), report, note.trim() || null);
+      setReportId(result.id);
+      setNote('');
+    } catch (err) {
+      addToast(`Failed to send system report: ${err}`, 'error');
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  return (
+    <div>
+      <h2 className="text-xl font-semibold mb-2 text-white">Support</h2>
+      <p className="text-sm text-gray-400 mb-4">
+        Share your OS, hardware, Java and modpack versions when asking for help.
+      </p>
+
+      <div className="mb-4">
+        <Input
+          label="What went wrong? (optional)"
+          value={note}
+          maxLength={1000}
+          onChange={(e) => setNote(e.target.value)}
+        />
+      </div>
+
+      <div className="flex gap-3">
+        <Button variant="outline" onClick={handleCopy} disabled={busy !== null}>
+          {busy === 'copy' ? 'Gathering...' : 'Copy system report'}
+        </Button>
+        <Button onClick={handleSend} disabled={busy !== null}>
+          {busy === 'send' ? 'Sending...' : 'Send to support'}
+        </Button>
+      </div>
+
+      {reportId !== null && (
+        <p className="mt-4 text-sm text-green-400">
+          Report sent. Give support this reference: <span className="font-mono font-semibold">#{reportId}</span>
+        </p>
+      )}
+    </div>
+  );
+};
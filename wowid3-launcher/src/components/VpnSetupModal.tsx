@@ -11,7 +11,7 @@ interface VpnSetupModalProps {
   onError: (error: string) => void;
 }
 
-type SetupStep = 'intro' | 'generating' | 'registering' | 'configuring' | 'success' | 'error';
+type SetupStep = 'intro' | 'registering' | 'configuring' | 'success' | 'error';
 
 export const VpnSetupModal: FC<VpnSetupModalProps> = ({ isOpen, onClose, onSuccess, onError }) => {
   const [step, setStep] = useState<SetupStep>('intro');
@@ -19,6 +19,7 @@ export const VpnSetupModal: FC<VpnSetupModalProps> = ({ isOpen, onClose, onSucce
   const [errorMessage, setErrorMessage] = useState<string>('');
   const [isWindows, setIsWindows] = useState<boolean>(false);
   const [isLinux, setIsLinux] = useState<boolean>(false);
+  const [isMacos, setIsMacos] = useState<boolean>(false);
   const [isInstallingWg, setIsInstallingWg] = useState<boolean>(false);
 
   // Detect OS on mount
@@ -28,6 +29,7 @@ export const VpnSetupModal: FC<VpnSetupModalProps> = ({ isOpen, onClose, onSucce
       const osType = platform.type();
       setIsWindows(osType === 'windows');
       setIsLinux(osType === 'linux');
+      setIsMacos(osType === 'macos');
     };
     detectOS();
   }, []);
@@ -77,6 +79,10 @@ export const VpnSetupModal: FC<VpnSetupModalProps> = ({ isOpen, onClose, onSucce
                       'Ubuntu/Debian: sudo apt install wireguard-tools\n' +
                       'Fedora: sudo dnf install wireguard-tools\n\n' +
                       'After installation, restart the launcher and try again.';
+        } else if (osType === 'macos') {
+          errorMsg += '\n\nPlease install the WireGuard tools with Homebrew:\n\n' +
+                      'brew install wireguard-tools wireguard-go bash\n\n' +
+                      'After installation, restart the launcher and try again.';
         } else if (isWin) {
           errorMsg += '\n\nClick "Install WireGuard" below to run the bundled installer.';
         }
@@ -88,41 +94,25 @@ export const VpnSetupModal: FC<VpnSetupModalProps> = ({ isOpen, onClose, onSucce
         return;
       }
 
-      // Step 1: Generate keypair
-      setStep('generating');
-      console.log('[VPN Setup] Starting keypair generation...');
-      const [privateKey, publicKey] = await invoke<[string, string]>('vpn_generate_keypair');
-      console.log('[VPN Setup] Keypair generated successfully');
-
-      // Step 2: Register with server
+      // Step 1: Register with server (generates this device's key on first use)
       setStep('registering');
       const manifestUrl = useSettingsStore.getState().manifestUrl;
       console.log('[VPN Setup] Registering with server:', manifestUrl);
-      const registrationResult = await invoke<{ success: boolean; assigned_ip: string; server_public_key: string; endpoint: string }>('vpn_register_with_server', {
-        publicKey,
+      const registration = await invoke<{ assigned_ip: string; server_ip: string; server_public_key: string; endpoint: string }>('cmd_vpn_register', {
         manifestUrl,
       });
-      console.log('[VPN Setup] Registration result:', registrationResult);
-
-      if (!registrationResult.success) {
-        throw new Error('Failed to register with VPN server');
-      }
+      console.log('[VPN Setup] Registered with assigned IP:', registration.assigned_ip);
 
-      // Step 3: Write WireGuard config
+      // Step 2: Bring the tunnel up
       setStep('configuring');
-      console.log('[VPN Setup] Writing WireGuard config...');
-      await invoke('vpn_write_config', {
-        privateKey,
-        assignedIp: registrationResult.assigned_ip,
-        serverPublicKey: registrationResult.server_public_key,
-        endpoint: registrationResult.endpoint,
-      });
-      console.log('[VPN Setup] Config written successfully');
+      console.log('[VPN Setup] Connecting WireGuard tunnel...');
+      await invoke('cmd_vpn_connect');
+      console.log('[VPN Setup] Tunnel connected');
 
-      // Step 4: Success
-      setAssignedIp(registrationResult.assigned_ip);
+      // Step 3: Success
+      setAssignedIp(registration.assigned_ip);
       setStep('success');
-      onSuccess(registrationResult.assigned_ip);
+      onSuccess(registration.assigned_ip);
     } catch (err) {
       console.error('[VPN Setup] Error occurred:', err);
       console.error('[VPN Setup] Error type:', typeof err);
@@ -197,6 +187,20 @@ export const VpnSetupModal: FC<VpnSetupModalProps> = ({ isOpen, onClose, onSucce
                       </p>
                     </div>
                   )}
+
+                  {isMacos && (
+                    <div className="mt-2">
+                      <p className="text-gray-300 text-sm">
+                        <strong>macOS:</strong> You must install the WireGuard tools first:
+                      </p>
+                      <code className="text-xs bg-gray-800 px-2 py-1 rounded mt-2 block">
+                        brew install wireguard-tools wireguard-go bash
+                      </code>
+                      <p className="text-gray-400 text-xs mt-2">
+                        <strong>Note:</strong> Starting/stopping the VPN will ask for your administrator password. This is normal and required for network operations.
+                      </p>
+                    </div>
+                  )}
                 </div>
               </div>
             </Modal.Body>
@@ -211,7 +215,6 @@ export const VpnSetupModal: FC<VpnSetupModalProps> = ({ isOpen, onClose, onSucce
           </>
         );
 
-      case 'generating':
       case 'registering':
       case 'configuring':
         return (
@@ -219,9 +222,8 @@ export const VpnSetupModal: FC<VpnSetupModalProps> = ({ isOpen, onClose, onSucce
             <div className="flex flex-col items-center justify-center py-8 space-y-4">
               <LoadingSpinner size="lg" />
               <p className="text-gray-300 text-lg">
-                {step === 'generating' && 'Generating encryption keys...'}
                 {step === 'registering' && 'Registering with VPN server...'}
-                {step === 'configuring' && 'Configuring WireGuard tunnel...'}
+                {step === 'configuring' && 'Connecting WireGuard tunnel...'}
               </p>
               <p className="text-gray-500 text-sm">Please wait, this should only take a moment.</p>
             </div>
@@ -304,7 +306,7 @@ export const VpnSetupModal: FC<VpnSetupModalProps> = ({ isOpen, onClose, onSucce
   };
 
   return (
-    <Modal isOpen={isOpen} onClose={step === 'generating' || step === 'registering' || step === 'configuring' ? () => {} : onClose} size="md">
+    <Modal isOpen={isOpen} onClose={step === 'registering' || step === 'configuring' ? () => {} : onClose} size="md">
       <Modal.Header>
         <Modal.Title>VPN Setup</Modal.Title>
       </Modal.Header>
@@ -0,0 +1,162 @@
+import { FC, useCallback, useEffect, useState } from 'react';
+import { useSettingsStore } from '../stores/settingsStore';
+import { backupWorld, listBackups, listWorlds, restoreBackup } from '../hooks/useTauriCommands';
+import type { BackupInfo } from '../types/minecraft';
+import { Button } from './ui/Button';
+import { Input } from './ui/Input';
+import { useToast } from './ui/ToastContainer';
+
+const formatSize = (bytes: number) =>
+  bytes >= 1024 * 1024 ? `${(bytes / 1024 / 1024).toFixed(1)} MB` : `${Math.round(bytes / 1024)} KB`;
+
+/**
+ * Back up and restore singleplayer worlds, and configure automatic backups before updates
+ */
+export const WorldBackups: FC = () => {
+  const gameDirectory = useSettingsStore((state) => state.gameDirectory);
+  const autoBackupBeforeUpdate = useSettingsStore((state) => state.autoBackupBeforeUpdate);
+  const backupDirectory = useSettingsStore((state) => state.backupDirectory);
+  const backupKeep = useSettingsStore((state) => state.backupKeep);
+  const setAutoBackupBeforeUpdate = useSettingsStore((state) => state.setAutoBackupBeforeUpdate);
+  const setBackupDirectory = useSettingsStore((state) => state.setBackupDirectory);
+  const setBackupKeep = useSettingsStore((state) => state.setBackupKeep);
+  const getBackupOptions = useSettingsStore((state) => state.getBackupOptions);
+  const { addToast } = useToast();
+
+  const [worlds, setWorlds] = useState<string[]>([]);
+  const [backups, setBackups] = useState<BackupInfo[]>([]);
+  const [busy, setBusy] = useState<string | null>(null); // world or backup file being processed
+
+  const refresh = useCallback(async () => {
+    if (!gameDirectory) return;
+    try {
+      const [foundWorlds, foundBackups] = await Promise.all([
+        listWorlds(gameDirectory),
+        listBackups(null, getBackupOptions()),
+      ]);
+      setWorlds(foundWorlds);
+      setBackups(foundBackups);
+    } catch (err) {
+      addToast(`Failed to load world backups: ${err}`, 'error');
+    }
+  }, [gameDirectory, backupDirectory, getBackupOptions, addToast]);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  const handleBackup = async (world: string) => {
+    setBusy(world);
+    try {
+      const backup = await backupWorld(gameDirectory, world, getBackupOptions());
+      addToast(`Backed up ${world} (${formatSize(backup.size_bytes)})`, 'success');
+      await refresh();
+    } catch (err) {
+      addToast(`Failed to back up ${world}: ${err}`, 'error');
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleRestore = async (backup: BackupInfo) => {
+    if (!confirm(`Restore ${backup.world_name} from ${backup.filename}? The current world is backed up first.`)) {
+      return;
+    }
+    setBusy(backup.filename);
+    try {
+      await restoreBackup(gameDirectory, backup.world_name, backup.filename, getBackupOptions());
+      addToast(`Restored ${backup.world_name}`, 'success');
+      await refresh();
+    } catch (err) {
+      addToast(`Failed to restore ${backup.world_name}: ${err}`, 'error');
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  return (
+    <div>
+      <h2 className="text-xl font-semibold mb-4 text-white">World Backups</h2>
+
+      <div className="space-y-4 mb-6">
+        <label className="flex items-center gap-3 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={autoBackupBeforeUpdate}
+            onChange={(e) => setAutoBackupBeforeUpdate(e.target.checked)}
+            className="w-5 h-5 rounded"
+          />
+          <span className="text-white">Back up worlds before installing modpack updates</span>
+        </label>
+
+        <div className="flex gap-3">
+          <div className="flex-1">
+            <Input
+              label="Backup folder"
+              value={backupDirectory ?? ''}
+              onChange={(e) => setBackupDirectory(e.target.value.trim() || null)}
+              helperText="Leave empty to use the launcher's data folder"
+            />
+          </div>
+          <div className="w-32">
+            <Input
+              label="Keep per world"
+              type="number"
+              min={1}
+              max={100}
+              value={backupKeep}
+              onChange={(e) => setBackupKeep(Number(e.target.value))}
+            />
+          </div>
+        </div>
+      </div>
+
+      {worlds.length === 0 ? (
+        <p className="text-sm text-gray-400 mb-4">No singleplayer worlds found.</p>
+      ) : (
+        <ul className="space-y-2 mb-6">
+          {worlds.map((world) => (
+            <li
+              key={world}
+              className="flex items-center justify-between gap-3 px-3 py-2 rounded border border-gray-600 text-sm"
+            >
+              <span className="text-gray-200 truncate">{world}</span>
+              <Button size="sm" onClick={() => handleBackup(world)} disabled={busy !== null}>
+                {busy === world ? 'Backing up...' : 'Back up now'}
+              </Button>
+            </li>
+          ))}
+        </ul>
+      )}
+
+      {backups.length > 0 && (
+        <>
+          <h3 className="text-sm font-semibold text-gray-300 mb-2">Backups</h3>
+          <ul className="space-y-2">
+            {backups.map((backup) => (
+              <li
+                key={backup.path}
+                className="flex items-center justify-between gap-3 px-3 py-2 rounded border border-gray-600 text-sm"
+              >
+                <div className="min-w-0">
+                  <div className="text-gray-200 truncate">{backup.world_name}</div>
+                  <div className="text-xs text-gray-500">
+                    {new Date(backup.created_at).toLocaleString()} • {formatSize(backup.size_bytes)}
+                  </div>
+                </div>
+                <Button
+                  size="sm"
+                  variant="outline"
+                  onClick={() => handleRestore(backup)}
+                  disabled={busy !== null}
+                >
+                  {busy === backup.filename ? 'Restoring...' : 'Restore'}
+                </Button>
+              </li>
+            ))}
+          </ul>
+        </>
+      )}
+    </div>
+  );
+};
@@ -8,6 +8,9 @@ interface ProgressBarProps {
   showPercentage?: boolean;
   showSpeed?: boolean;
   showETA?: boolean;
+  /** Bytes per second measured by the backend; estimated from `current` when omitted */
+  speed?: number;
+  etaSeconds?: number | null;
   className?: string;
   label?: string;
   progressColor?: 'primary' | 'success' | 'warning' | 'error' | 'info' | 'adaptive';
@@ -28,47 +31,54 @@ export const ProgressBar = ({
   showPercentage = true,
   showSpeed = true,
   showETA = true,
+  speed,
+  etaSeconds,
   className = '',
   label = 'Downloading',
   progressColor = 'adaptive',
 }: ProgressBarProps) => {
   const percentage = total > 0 ? Math.min(100, Math.round((current / total) * 100)) : 0;
-  const [downloadSpeed, setDownloadSpeed] = useState(0);
-  const [eta, setEta] = useState<string>('Calculating...');
+  const [estimatedSpeed, setEstimatedSpeed] = useState(0);
+  const [estimatedEta, setEstimatedEta] = useState<number | null>(null);
   const lastCurrentRef = useRef(current);
   const lastTimeRef = useRef(Date.now());
 
   useEffect(() => {
-    // Calculate download speed and ETA
+    // Estimate download speed and ETA from successive updates
     const now = Date.now();
     const timeDiff = (now - lastTimeRef.current) / 1000; // seconds
     const bytesDiff = current - lastCurrentRef.current;
 
     if (timeDiff > 0 && bytesDiff > 0) {
       const speedBytesPerSecond = bytesDiff / timeDiff;
-      setDownloadSpeed(speedBytesPerSecond);
-
-      // Calculate ETA
-      const bytesRemaining = total - current;
-      const secondsRemaining = bytesRemaining / speedBytesPerSecond;
-
-      if (secondsRemaining < 60) {
-        setEta(`${Math.ceil(secondsRemaining)}s`);
-      } else if (secondsRemaining < 3600) {
-        const minutes = Math.floor(secondsRemaining / 60);
-        const seconds = Math.ceil(secondsRemaining % 60);
-        setEta(`${minutes}m ${seconds}s`);
-      } else {
-        const hours = Math.floor(secondsRemaining / 3600);
-        const minutes = Math.floor((secondsRemaining % 3600) / 60);
-        setEta(`${hours}h ${minutes}m`);
-      }
+      setEstimatedSpeed(speedBytesPerSecond);
+      setEstimatedEta((total - current) / speedBytesPerSecond);
     }
 
     lastCurrentRef.current = current;
     lastTimeRef.current = now;
   }, [current, total]);
 
+  const downloadSpeed = speed ?? estimatedSpeed;
+  const secondsRemaining = speed !== undefined ? etaSeconds ?? null : estimatedEta;
+
+  const formatEta = (seconds: number | null): string => {
+    if (seconds === null) {
+      return 'Calculating...';
+    }
+    if (seconds < 60) {
+      return `${Math.ceil(seconds)}s`;
+    }
+    if (seconds < 3600) {
+      const minutes = Math.floor(seconds / 60);
+      const remainder = Math.ceil(seconds % 60);
+      return `${minutes}m ${remainder}s`;
+    }
+    const hours = Math.floor(seconds / 3600);
+    const minutes = Math.floor((seconds % 3600) / 60);
+    return `${hours}h ${minutes}m`;
+  };
+
   const formatSpeed = (bytesPerSecond: number): string => {
     const mbPerSecond = bytesPerSecond / 1024 / 1024;
     if (mbPerSecond < 1) {
@@ -123,7 +133,7 @@ export const ProgressBar = ({
           </div>
           {showETA && downloadSpeed > 0 && current < total && (
             <span className="text-slate-300">
-              ETA: {eta}
+              ETA: {formatEta(secondsRemaining)}
             </span>
           )}
         </div>
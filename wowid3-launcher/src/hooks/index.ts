@@ -3,11 +3,12 @@ export { useModpack } from './useModpack';
 export { useServer } from './useServer';
 export { useTheme } from './useTheme';
 export { useDiscord } from './useDiscord';
-export { useDiscordPresence } from './useDiscordPresence';
 export { useMinecraftInstaller } from './useMinecraftInstaller';
 export { useAccessibility, useFocusTrap } from './useAccessibility';
 export { useRetry, retry } from './useRetry';
 export { useAudio } from './useAudio';
 export { useNetworkTest } from './useNetworkTest';
 export { useVpnInitialization } from './useVpnInitialization';
+export { useServerEvents } from './useServerEvents';
+export { usePlayerSkin } from './usePlayerSkin';
 export * from './useTauriCommands';
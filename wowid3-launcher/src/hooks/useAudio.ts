@@ -1,8 +1,13 @@
 import { useEffect, useRef, useCallback } from 'react';
+import { convertFileSrc } from '@tauri-apps/api/core';
 import { logger, LogCategory } from '../utils/logger';
 import { useAudioStore } from '../stores/audioStore';
+import { useSettingsStore } from '../stores/settingsStore';
+import { loadAudioPlaylist, nextAudioTrack, previousAudioTrack, setAudioShuffle } from './useTauriCommands';
+import { extractBaseUrl } from '../utils/url';
+import type { AudioTrack } from '../types/minecraft';
 
-// Bundled audio files (no download needed)
+// Bundled audio files (no download needed); the main track is replaced by the server playlist when it has one
 const MAIN_AUDIO_URL = '/8-bit-christmas.mp3';
 const FALLBACK_AUDIO_URL = '/wid3menu-fallback.mp3';
 const CROSSFADE_DURATION_MS = 2000;
@@ -23,7 +28,12 @@ export const useAudio = () => {
     setVolume: setStoreVolume,
     pauseForGame,
     resumeFromGame,
+    currentTrack,
+    setCurrentTrack,
+    shuffle,
+    setShuffle,
   } = useAudioStore();
+  const { manifestUrl } = useSettingsStore();
 
   // Cleanup audio elements on unmount
   useEffect(() => {
@@ -134,11 +144,25 @@ export const useAudio = () => {
     }
   }, [audioState, setAudioState, volume]);
 
-  // Load main audio from bundled file
-  const loadMainAudio = useCallback(() => {
-    if (!mainRef.current) return;
+  // Point the main element at a playlist track; keeps playing if the previous track was
+  const playTrack = useCallback((track: AudioTrack | null) => {
+    const main = mainRef.current;
+    if (!main || !track?.path) return;
+
+    const wasPlaying = !main.paused;
+    logger.info(LogCategory.AUDIO, `Playing playlist track: ${track.title}`);
+    setCurrentTrack(track);
+    main.loop = false;
+    main.src = convertFileSrc(track.path);
+    main.load();
+    if (wasPlaying) {
+      main.play().catch(err => logger.error(LogCategory.AUDIO, 'Failed to play track:', err instanceof Error ? err : new Error(String(err))));
+    }
+  }, [setCurrentTrack]);
 
-    logger.info(LogCategory.AUDIO, 'Loading bundled main audio');
+  // Load the server playlist, or the bundled main track if it is empty or unreachable
+  const loadMainAudio = useCallback(async () => {
+    if (!mainRef.current) return;
 
     mainRef.current.onloadeddata = () => {
       logger.info(LogCategory.AUDIO, 'Main audio successfully loaded');
@@ -152,9 +176,64 @@ export const useAudio = () => {
       logger.info(LogCategory.AUDIO, 'Will continue with fallback audio');
     };
 
+    try {
+      const track = await loadAudioPlaylist(extractBaseUrl(manifestUrl), shuffle);
+      if (track?.path) {
+        playTrack(track);
+        return;
+      }
+    } catch (err) {
+      logger.warn(LogCategory.AUDIO, `Audio playlist unavailable, using bundled track: ${err}`);
+    }
+
+    if (!mainRef.current) return;
+    logger.info(LogCategory.AUDIO, 'Loading bundled main audio');
+    setCurrentTrack(null);
+    mainRef.current.loop = true;
     mainRef.current.src = MAIN_AUDIO_URL;
     mainRef.current.load();
-  }, [setMainAudioReady]);
+  }, [manifestUrl, shuffle, playTrack, setCurrentTrack, setMainAudioReady]);
+
+  const nextTrack = useCallback(async () => {
+    if (!currentTrack) return;
+    try {
+      playTrack(await nextAudioTrack());
+    } catch (err) {
+      logger.error(LogCategory.AUDIO, 'Failed to skip track:', err instanceof Error ? err : new Error(String(err)));
+    }
+  }, [currentTrack, playTrack]);
+
+  const previousTrack = useCallback(async () => {
+    if (!currentTrack) return;
+    try {
+      playTrack(await previousAudioTrack());
+    } catch (err) {
+      logger.error(LogCategory.AUDIO, 'Failed to go back a track:', err instanceof Error ? err : new Error(String(err)));
+    }
+  }, [currentTrack, playTrack]);
+
+  const toggleShuffle = useCallback(() => {
+    setShuffle(!shuffle);
+    setAudioShuffle(!shuffle).catch(err => logger.error(LogCategory.AUDIO, 'Failed to set shuffle:', err instanceof Error ? err : new Error(String(err))));
+  }, [shuffle, setShuffle]);
+
+  // Playlist tracks don't loop; move on to the next one when a track ends
+  useEffect(() => {
+    const main = mainRef.current;
+    if (!main || !currentTrack) return;
+
+    const onEnded = () => {
+      nextAudioTrack()
+        .then(track => {
+          playTrack(track);
+          main.play().catch(err => logger.error(LogCategory.AUDIO, 'Failed to play next track:', err instanceof Error ? err : new Error(String(err))));
+        })
+        .catch(err => logger.error(LogCategory.AUDIO, 'Failed to load next track:', err instanceof Error ? err : new Error(String(err))));
+    };
+
+    main.addEventListener('ended', onEnded);
+    return () => main.removeEventListener('ended', onEnded);
+  }, [currentTrack, playTrack]);
 
   // Initialize audio on mount
   useEffect(() => {
@@ -255,6 +334,14 @@ export const useAudio = () => {
     pause,
     stop,
 
+    // Playlist control (no-ops while the bundled track is playing)
+    nextTrack,
+    previousTrack,
+    toggleShuffle,
+    currentTrack,
+    shuffle,
+    hasPlaylist: currentTrack !== null,
+
     // Volume control
     setVolume,
     setMuted,
@@ -1,9 +1,10 @@
-import { useEffect } from 'react';
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
 import { logger, LogCategory } from '../utils/logger';
 import { LauncherError, LauncherErrorCode } from '../utils/errors';
 import { useAuthUser, useIsAuthenticated, useAuthLoading, useAuthError, useAuthActions } from '../stores/selectors';
-import { getCurrentUser, logout as logoutCommand, refreshToken, getDeviceCode, completeDeviceCodeAuth } from './useTauriCommands';
-import type { DeviceCodeInfo } from './useTauriCommands';
+import { getCurrentUser, logout as logoutCommand, refreshToken, getDeviceCode, completeDeviceCodeAuth, signInDemo as signInDemoCommand } from './useTauriCommands';
+import type { AuthSessionEvent, DeviceCodeInfo } from './useTauriCommands';
 
 export const useAuth = () => {
   const user = useAuthUser();
@@ -12,6 +13,38 @@ export const useAuth = () => {
   const error = useAuthError();
   const { setUser, setLoading, setError, logout: logoutStore } = useAuthActions();
 
+  // Set while Microsoft/Xbox services are down and the stored session is used unrefreshed
+  const [sessionNotRefreshed, setSessionNotRefreshed] = useState<AuthSessionEvent | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<AuthSessionEvent>('auth-session', (event) => {
+      const status = event.payload;
+      if (status.state === 'not_refreshed') {
+        logger.warn(LogCategory.AUTH, 'Session not refreshed, auth services unavailable', {
+          metadata: { reason: status.reason, retryInSecs: status.retry_in_secs },
+        });
+        setSessionNotRefreshed(status);
+        return;
+      }
+
+      setSessionNotRefreshed(null);
+      if (status.state === 'refreshed') {
+        // Renewed in the background; pick up the new expiry
+        getCurrentUser()
+          .then(current => current && setUser(current))
+          .catch(err => logger.warn(LogCategory.AUTH, 'Failed to reload renewed session', { metadata: { error: String(err) } }));
+      } else if (status.state === 'login_required') {
+        logger.error(LogCategory.AUTH, 'Session refresh rejected:', new Error(status.reason));
+        setError(new LauncherError(LauncherErrorCode.AUTH_TOKEN_EXPIRED, status.reason));
+      }
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
+
   // Check for existing user and refresh token on mount ONCE
   useEffect(() => {
     let mounted = true;
@@ -126,6 +159,24 @@ export const useAuth = () => {
     }
   };
 
+  const signInDemo = async (username: string) => {
+    try {
+      setLoading(true);
+      setError(null);
+
+      const profile = await signInDemoCommand(username);
+      logger.info(LogCategory.AUTH, 'Signed in with demo account');
+      setUser(profile);
+    } catch (err) {
+      const error = LauncherError.from(err, LauncherErrorCode.AUTH_FAILED);
+      logger.error(LogCategory.AUTH, 'Demo sign-in failed:', error);
+      setError(error);
+      throw err;
+    } finally {
+      setLoading(false);
+    }
+  };
+
   const logout = async () => {
     try {
       await logoutCommand();
@@ -141,9 +192,11 @@ export const useAuth = () => {
     isAuthenticated,
     isLoading,
     error,
+    sessionNotRefreshed,
     login,
     logout,
     startDeviceCodeAuth,
     finishDeviceCodeAuth,
+    signInDemo,
   };
 };
@@ -1,12 +1,15 @@
 import { useState, useEffect, useCallback } from 'react';
 import { listen, UnlistenFn } from '@tauri-apps/api/event';
-import { launchGameWithMetadata, isGameRunning } from './useTauriCommands';
+import { launchGameWithMetadata, launchOffline, isGameRunning, uploadCrashReport } from './useTauriCommands';
 import { useSettingsStore } from '../stores/settingsStore';
 import { useAudioStore } from '../stores/audioStore';
 import { useUIStore } from '../stores/uiStore';
 import { LauncherError, LauncherErrorCode } from '../utils/errors';
 import { logger, LogCategory } from '../utils/logger';
+import { extractBaseUrl } from '../utils/url';
 import { POLLING_INTERVALS } from '../config/constants';
+import { useToast } from '../components/ui/ToastContainer';
+import type { PreLaunchWarning } from '../types/minecraft';
 
 export interface GameLauncherState {
   isLaunching: boolean;
@@ -27,6 +30,8 @@ export interface GameLaunchParams {
   uuid: string;
   accessToken: string;
   versionId: string;
+  /** Skip the release server and launch the last-known-good configuration */
+  offline?: boolean;
 }
 
 export interface MinecraftLogEvent {
@@ -41,6 +46,11 @@ export interface MinecraftExitEvent {
 
 export interface MinecraftCrashEvent {
   message: string;
+  exit_code: number;
+}
+
+export interface PreLaunchWarningsEvent {
+  warnings: PreLaunchWarning[];
 }
 
 /**
@@ -53,9 +63,18 @@ export function useGameLauncher(): UseGameLauncherReturn {
   const [crashed, setCrashed] = useState(false);
   const [error, setError] = useState<LauncherError | null>(null);
 
-  const { ramAllocation, gameDirectory, keepLauncherOpen } = useSettingsStore();
+  const {
+    ramAllocation,
+    gameDirectory,
+    keepLauncherOpen,
+    javaPath,
+    getJvmSettings,
+    uploadCrashReports,
+    manifestUrl,
+  } = useSettingsStore();
   const { pauseForGame } = useAudioStore();
   const { setShowLogViewer } = useUIStore();
+  const { addToast } = useToast();
 
   /**
    * Launch the game with the provided parameters
@@ -68,6 +87,7 @@ export function useGameLauncher(): UseGameLauncherReturn {
           username: params.username,
           versionId: params.versionId,
           ram: ramAllocation,
+          offline: params.offline ?? false,
         },
       });
 
@@ -105,16 +125,20 @@ export function useGameLauncher(): UseGameLauncherReturn {
         }
 
         // Launch the game
-        await launchGameWithMetadata(
-          {
-            ram_mb: ramAllocation,
-            game_dir: gameDirectory,
-            username: params.username,
-            uuid: params.uuid,
-            session_id: params.accessToken, // Backend expects session_id
-          },
-          params.versionId
-        );
+        const config = {
+          ram_mb: ramAllocation,
+          game_dir: gameDirectory,
+          username: params.username,
+          uuid: params.uuid,
+          session_id: params.accessToken, // Backend expects session_id
+          java_path: javaPath ?? undefined, // Unset = bundled runtime
+          jvm: getJvmSettings(),
+        };
+        if (params.offline) {
+          await launchOffline(config);
+        } else {
+          await launchGameWithMetadata(config, params.versionId);
+        }
 
         setIsLaunching(false);
         setIsPlaying(true);
@@ -127,7 +151,7 @@ export function useGameLauncher(): UseGameLauncherReturn {
         throw launcherError;
       }
     },
-    [ramAllocation, gameDirectory, keepLauncherOpen, pauseForGame, setShowLogViewer]
+    [ramAllocation, gameDirectory, keepLauncherOpen, javaPath, getJvmSettings, pauseForGame, setShowLogViewer]
   );
 
   /**
@@ -222,9 +246,27 @@ export function useGameLauncher(): UseGameLauncherReturn {
         setError(
           new LauncherError(LauncherErrorCode.MC_LAUNCH_FAILED, event.payload.message)
         );
+
+        if (uploadCrashReports) {
+          uploadCrashReport(extractBaseUrl(manifestUrl), gameDirectory, event.payload.exit_code, javaPath)
+            .then((result) => {
+              logger.info(LogCategory.MINECRAFT, `Crash report uploaded (signature ${result.signature})`);
+            })
+            .catch((err) => {
+              logger.warn(LogCategory.MINECRAFT, `Failed to upload crash report: ${err}`);
+            });
+        }
+      });
+
+      // Listen for mod conflicts found before launch (the game still starts)
+        const unlistenWarnings = await listen<PreLaunchWarningsEvent>('pre-launch-warnings', (event) => {
+        for (const warning of event.payload.warnings) {
+          logger.warn(LogCategory.MINECRAFT, warning.message, { metadata: { files: warning.files } });
+          addToast(warning.message, 'warning', 10000);
+        }
       });
 
-      unlisteners = [unlistenLog, unlistenExit, unlistenCrash];
+      unlisteners = [unlistenLog, unlistenExit, unlistenCrash, unlistenWarnings];
       } catch (err) {
         logger.error(LogCategory.MINECRAFT, 'Failed to setup event listeners:', err instanceof Error ? err : new Error(String(err)));
       }
@@ -242,7 +284,7 @@ export function useGameLauncher(): UseGameLauncherReturn {
         }
       });
     };
-  }, [handleGameExit]);
+  }, [handleGameExit, uploadCrashReports, manifestUrl, gameDirectory, javaPath, addToast]);
 
   // Check if game is already running on mount
   useEffect(() => {
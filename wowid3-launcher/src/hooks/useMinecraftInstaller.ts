@@ -7,7 +7,8 @@ import {
   isVersionInstalled,
 } from './useTauriCommands';
 import { useSettingsStore } from '../stores/settingsStore';
-import { InstallProgress } from '../types/minecraft';
+import { useModpackStore } from '../stores/modpackStore';
+import { InstallProgress, ModLoader } from '../types/minecraft';
 import { type VersionInfo, type FabricLoader } from '../types/schemas';
 import { logger, LogCategory } from '../utils/logger';
 import { LauncherError, LauncherErrorCode } from '../utils/errors';
@@ -41,9 +42,22 @@ export interface UseMinecraftInstallerReturn {
   clearError: () => void;
 
   // Computed values
+  loader: ModLoader;         // Loader the modpack runs on
   versionId: string | null;  // The full version ID for launching (e.g., "fabric-loader-0.18.0-1.20.1")
 }
 
+/** Version ID the backend saves an installation under (matches ModLoader::version_id) */
+const loaderVersionId = (loader: ModLoader, loaderVersion: string, gameVersion: string): string => {
+  switch (loader) {
+    case 'quilt':
+      return `quilt-loader-${loaderVersion}-${gameVersion}`;
+    case 'neoforge':
+      return `neoforge-${loaderVersion}`;
+    default:
+      return `fabric-loader-${loaderVersion}-${gameVersion}`;
+  }
+};
+
 export function useMinecraftInstaller(): UseMinecraftInstallerReturn {
   const {
     gameDirectory,
@@ -57,6 +71,7 @@ export function useMinecraftInstaller(): UseMinecraftInstallerReturn {
     setFabricVersion,
     setIsMinecraftInstalled,
   } = useSettingsStore();
+  const latestManifest = useModpackStore((state) => state.latestManifest);
 
   // Version state
   const [versions, setVersions] = useState<VersionInfo[]>([]);
@@ -71,10 +86,16 @@ export function useMinecraftInstaller(): UseMinecraftInstallerReturn {
   const [installProgress, setInstallProgress] = useState<InstallProgress | null>(null);
   const [error, setError] = useState<string | null>(null);
 
+  // The modpack picks the loader; Fabric versions can still be chosen in settings
+  const loader: ModLoader = latestManifest?.loader ?? 'fabric';
+  const loaderVersion = loader === 'fabric'
+    ? (fabricEnabled ? fabricVersion : null)
+    : latestManifest?.fabric_loader ?? null;
+
   // Computed version ID
   const versionId = minecraftVersion
-    ? fabricEnabled && fabricVersion
-      ? `fabric-loader-${fabricVersion}-${minecraftVersion}`
+    ? loaderVersion
+      ? loaderVersionId(loader, loaderVersion, minecraftVersion)
       : minecraftVersion
     : null;
 
@@ -145,7 +166,8 @@ export function useMinecraftInstaller(): UseMinecraftInstallerReturn {
 
       const config = {
         game_version: minecraftVersion,
-        fabric_version: fabricEnabled ? fabricVersion || undefined : undefined,
+        loader,
+        loader_version: loaderVersion || undefined,
         game_dir: gameDirectory,
       };
 
@@ -177,7 +199,7 @@ export function useMinecraftInstaller(): UseMinecraftInstallerReturn {
       setIsInstalling(false);
       setInstallProgress(null);
     }
-  }, [minecraftVersion, fabricEnabled, fabricVersion, gameDirectory, versionId, checkInstalled]);
+  }, [minecraftVersion, loader, loaderVersion, gameDirectory, versionId, checkInstalled]);
 
   // Actions (Pure setters now)
   const setSelectedVersion = useCallback(
@@ -274,6 +296,7 @@ export function useMinecraftInstaller(): UseMinecraftInstallerReturn {
     clearError,
 
     // Computed values
+    loader,
     versionId,
   };
 }
@@ -1,4 +1,4 @@
-import { useEffect, useCallback, useMemo } from 'react';
+import { useEffect, useCallback, useMemo, useState } from 'react';
 import { listen } from '@tauri-apps/api/event';
 import { logger, LogCategory } from '../utils/logger';
 import { LauncherError, LauncherErrorCode } from '../utils/errors';
@@ -14,9 +14,19 @@ import {
   useModpackError,
   useModpackActions
 } from '../stores/selectors';
-import { checkForUpdates, getInstalledVersion, installModpack, verifyAndRepairModpack } from './useTauriCommands';
+import {
+  checkForUpdates,
+  getInstalledVersion,
+  installModpack,
+  isInstallCancelled,
+  rollbackModpack,
+  verifyAndRepairModpack,
+  type UpdatesSkippedEvent,
+} from './useTauriCommands';
 import { createRateLimiter } from '../utils/rateLimit';
+import { extractBaseUrl } from '../utils/url';
 import { POLLING_CONFIG } from '../config/polling';
+import type { DownloadProgressV2Event } from '../types/tauri';
 
 export const useModpack = () => {
   const installedVersion = useInstalledVersion();
@@ -42,6 +52,27 @@ export const useModpack = () => {
 
   const gameDirectory = useSettingsStore(state => state.gameDirectory);
   const manifestUrl = useSettingsStore(state => state.manifestUrl);
+  const releaseChannel = useSettingsStore(state => state.releaseChannel);
+  const activeInstanceId = useSettingsStore(state => state.activeInstanceId);
+  const autoBackupBeforeUpdate = useSettingsStore(state => state.autoBackupBeforeUpdate);
+  const shareUpdateTelemetry = useSettingsStore(state => state.shareUpdateTelemetry);
+  const getBackupOptions = useSettingsStore(state => state.getBackupOptions);
+
+  // Set when the release server is unreachable; cleared by the next successful check
+  const [updatesSkipped, setUpdatesSkipped] = useState<UpdatesSkippedEvent | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<UpdatesSkippedEvent>('updates-skipped', (event) => {
+      logger.warn(LogCategory.MODPACK, 'Update check skipped, release server unreachable', {
+        metadata: { reason: event.payload.reason, offlineAvailable: event.payload.offline_available },
+      });
+      setUpdatesSkipped(event.payload);
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
 
   // Create rate-limited update checker
   const rateLimitedCheck = useMemo(() => 
@@ -110,8 +141,9 @@ export const useModpack = () => {
         setInstalledVersion(currentVersion);
       }
       
-      const manifest = await rateLimitedCheck(manifestUrl);
+      const manifest = await rateLimitedCheck(manifestUrl, releaseChannel, gameDirectory);
       setLatestManifest(manifest);
+      setUpdatesSkipped(null);
 
       // Check if update is available using the freshly loaded version
       const versionToCheck = currentVersion || installedVersion;
@@ -137,7 +169,7 @@ export const useModpack = () => {
       setError(error);
       throw err;
     }
-  }, [manifestUrl, gameDirectory, setError, setLatestManifest, setUpdateAvailable, setInstalledVersion, rateLimitedCheck]);
+  }, [manifestUrl, releaseChannel, gameDirectory, setError, setLatestManifest, setUpdateAvailable, setInstalledVersion, rateLimitedCheck]);
   // Note: installedVersion removed from dependencies to prevent infinite loop
   // checkUpdates() fetches current version from disk (line 105), so installedVersion is just a fallback
 
@@ -162,21 +194,20 @@ export const useModpack = () => {
       // We keep updateAvailable true until finished to prevent UI flickering if using that to hide buttons
       
       // Listen for download progress events
-      const unlisten = await listen<{
-        current: number;
-        total: number;
-        filename: string;
-        current_bytes: number;
-        total_bytes: number;
-      }>(
-        'download-progress',
-        (event) => {
-          setDownloadProgress(event.payload.current_bytes, event.payload.total_bytes);
-        }
-      );
+      const unlisten = await listen<DownloadProgressV2Event>('download-progress-v2', (event) => {
+        const { bytes_downloaded, total_bytes, speed_bytes_per_sec, eta_secs } = event.payload;
+        setDownloadProgress(bytes_downloaded, total_bytes, speed_bytes_per_sec, eta_secs);
+      });
 
       try {
-        await installModpack(latestManifest, gameDirectory);
+        // Worlds are backed up by the installer only when the version actually changes
+        await installModpack(
+          latestManifest,
+          gameDirectory,
+          autoBackupBeforeUpdate ? getBackupOptions() : null,
+          activeInstanceId,
+          shareUpdateTelemetry ? extractBaseUrl(manifestUrl) : null
+        );
 
         // Validate installation before updating state
         try {
@@ -219,6 +250,11 @@ export const useModpack = () => {
         unlisten();
       }
     } catch (err) {
+      // The player stopped it; finished files stay and the update is still offered
+      if (isInstallCancelled(err)) {
+        logger.info(LogCategory.MODPACK, 'Modpack update cancelled');
+        return;
+      }
       const error = LauncherError.from(err, LauncherErrorCode.MODPACK_DOWNLOAD_FAILED);
       setError(error);
       throw err;
@@ -229,7 +265,7 @@ export const useModpack = () => {
         setDownloading(false);
       }
     }
-  }, [latestManifest, gameDirectory, setBlockedForInstall, setDownloading, setError, setDownloadProgress, setInstalledVersion, setUpdateAvailable, reset]);
+  }, [latestManifest, manifestUrl, gameDirectory, activeInstanceId, autoBackupBeforeUpdate, shareUpdateTelemetry, getBackupOptions, setBlockedForInstall, setDownloading, setError, setDownloadProgress, setInstalledVersion, setUpdateAvailable, reset]);
   // Note: installedVersion removed from dependencies
   // The function captures installedVersion at execution time (line 148), not dependency time
 
@@ -249,7 +285,7 @@ export const useModpack = () => {
       }
 
       // First fetch the latest manifest
-      const manifest = await rateLimitedCheck(manifestUrl);
+      const manifest = await rateLimitedCheck(manifestUrl, releaseChannel, gameDirectory);
 
       if (!silent) {
         setLatestManifest(manifest);
@@ -259,18 +295,10 @@ export const useModpack = () => {
       let unlisten: (() => void) | null = null;
 
       if (!silent) {
-        unlisten = await listen<{
-          current: number;
-          total: number;
-          filename: string;
-          current_bytes: number;
-          total_bytes: number;
-        }>(
-          'download-progress',
-          (event) => {
-            setDownloadProgress(event.payload.current_bytes, event.payload.total_bytes);
-          }
-        );
+        unlisten = await listen<DownloadProgressV2Event>('download-progress-v2', (event) => {
+          const { bytes_downloaded, total_bytes, speed_bytes_per_sec, eta_secs } = event.payload;
+          setDownloadProgress(bytes_downloaded, total_bytes, speed_bytes_per_sec, eta_secs);
+        });
       }
 
       try {
@@ -297,7 +325,39 @@ export const useModpack = () => {
         setDownloading(false);
       }
     }
-  }, [manifestUrl, gameDirectory, setDownloading, setVerifying, setError, setLatestManifest, setDownloadProgress, rateLimitedCheck]);
+  }, [manifestUrl, releaseChannel, gameDirectory, setDownloading, setVerifying, setError, setLatestManifest, setDownloadProgress, rateLimitedCheck]);
+
+  const rollback = useCallback(async (version: string) => {
+    try {
+      setDownloading(true);
+      setError(null);
+
+      const unlisten = await listen<DownloadProgressV2Event>('download-progress-v2', (event) => {
+        const { bytes_downloaded, total_bytes, speed_bytes_per_sec, eta_secs } = event.payload;
+        setDownloadProgress(bytes_downloaded, total_bytes, speed_bytes_per_sec, eta_secs);
+      });
+
+      try {
+        const manifest = await rollbackModpack(manifestUrl, version, gameDirectory, activeInstanceId);
+        setInstalledVersion(manifest.version);
+        // Keep offering the latest release as an update
+        setUpdateAvailable(latestManifest !== null && latestManifest.version !== manifest.version);
+        logger.info(LogCategory.MODPACK, `Rolled back modpack to ${manifest.version}`);
+      } finally {
+        unlisten();
+      }
+    } catch (err) {
+      if (isInstallCancelled(err)) {
+        logger.info(LogCategory.MODPACK, 'Modpack rollback cancelled');
+        return;
+      }
+      const error = LauncherError.from(err, LauncherErrorCode.MODPACK_DOWNLOAD_FAILED);
+      setError(error);
+      throw err;
+    } finally {
+      setDownloading(false);
+    }
+  }, [manifestUrl, gameDirectory, activeInstanceId, latestManifest, setDownloading, setError, setDownloadProgress, setInstalledVersion, setUpdateAvailable]);
 
   return {
     installedVersion,
@@ -311,5 +371,7 @@ export const useModpack = () => {
     checkUpdates,
     install,
     verifyAndRepair,
+    rollback,
+    updatesSkipped,
   };
 };
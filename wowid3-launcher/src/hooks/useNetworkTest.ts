@@ -1,5 +1,5 @@
 import { useCallback, useEffect } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../utils/invoke';
 import { listen } from '@tauri-apps/api/event';
 import {
   useNetworkTestStore,
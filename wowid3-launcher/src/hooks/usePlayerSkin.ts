@@ -0,0 +1,31 @@
+import { useEffect, useState } from 'react';
+import { getPlayerSkin, PlayerSkin } from './useTauriCommands';
+import { logger, LogCategory } from '../utils/logger';
+
+/**
+ * Skin textures and 2D render for a player, served from the launcher's disk cache
+ * so they work offline. Null until loaded, or if the player has no custom skin.
+ */
+export function usePlayerSkin(uuid: string | null | undefined): PlayerSkin | null {
+  const [skin, setSkin] = useState<PlayerSkin | null>(null);
+
+  useEffect(() => {
+    setSkin(null);
+    if (!uuid) return;
+
+    let cancelled = false;
+    getPlayerSkin(uuid)
+      .then((result) => {
+        if (!cancelled) setSkin(result);
+      })
+      .catch((err) => {
+        logger.warn(LogCategory.MINECRAFT, `Failed to load skin: ${String(err)}`);
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [uuid]);
+
+  return skin;
+}
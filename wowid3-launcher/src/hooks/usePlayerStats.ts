@@ -1,9 +1,11 @@
 import { useState, useEffect } from 'react';
-import { invoke } from '@tauri-apps/api/core';
-import { PlayerStats } from '../types/stats';
+import { invoke } from '../utils/invoke';
+import { PlayerSessions, PlayerStats } from '../types/stats';
 
 export interface UsePlayerStatsResult {
   stats: PlayerStats | null;
+  /** Session history from the server's tracker; null if unavailable */
+  sessions: PlayerSessions | null;
   loading: boolean;
   error: string | null;
   refresh: () => Promise<void>;
@@ -11,6 +13,7 @@ export interface UsePlayerStatsResult {
 
 export function usePlayerStats(uuid: string | null, serverUrl: string, autoRefresh: boolean = true): UsePlayerStatsResult {
   const [stats, setStats] = useState<PlayerStats | null>(null);
+  const [sessions, setSessions] = useState<PlayerSessions | null>(null);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
 
@@ -22,6 +25,11 @@ export function usePlayerStats(uuid: string | null, serverUrl: string, autoRefre
       setError(null);
       const result = await invoke<PlayerStats>('cmd_get_player_stats', { uuid, serverUrl });
       setStats(result);
+
+      // Best effort: older servers don't track sessions
+      invoke<PlayerSessions>('cmd_get_player_sessions', { uuid, serverUrl })
+        .then(setSessions)
+        .catch((err) => console.warn('Failed to fetch player sessions:', err));
     } catch (err) {
       setError(String(err));
       console.error('Failed to fetch player stats:', err);
@@ -39,6 +47,6 @@ export function usePlayerStats(uuid: string | null, serverUrl: string, autoRefre
     }
   }, [uuid, serverUrl, autoRefresh]);
 
-  return { stats, loading, error, refresh: fetchStats };
+  return { stats, sessions, loading, error, refresh: fetchStats };
 }
 
@@ -8,10 +8,21 @@ export interface PollConfig {
   enabled?: boolean;
   exponentialBackoff?: boolean;
   maxRetries?: number;
+  retryBaseDelay?: number; // milliseconds, doubled per retry
+  retryMaxDelay?: number; // milliseconds
 }
 
 export function usePolling(config: PollConfig) {
-  const { name, interval, fn, enabled = true, exponentialBackoff = false, maxRetries = 3 } = config;
+  const {
+    name,
+    interval,
+    fn,
+    enabled = true,
+    exponentialBackoff = false,
+    maxRetries = 3,
+    retryBaseDelay = 1000,
+    retryMaxDelay = 30000,
+  } = config;
   const [isRunning, setIsRunning] = useState(false);
   const [lastError, setLastError] = useState<Error | null>(null);
   const retryCount = useRef(0);
@@ -41,7 +52,7 @@ export function usePolling(config: PollConfig) {
         logger.warn(LogCategory.NETWORK, `Polling failed: ${name}`);
 
         if (exponentialBackoff && retryCount.current < maxRetries) {
-          const delay = Math.min(1000 * Math.pow(2, retryCount.current), 30000);
+          const delay = Math.min(retryBaseDelay * Math.pow(2, retryCount.current), retryMaxDelay);
           retryCount.current++;
           logger.debug(LogCategory.NETWORK, `Polling ${name} retrying in ${delay}ms`);
           timeoutId = setTimeout(() => isMounted && poll(), delay);
@@ -64,7 +75,7 @@ export function usePolling(config: PollConfig) {
       isMounted = false;
       clearTimeout(timeoutId);
     };
-  }, [enabled, interval, name, exponentialBackoff, maxRetries]);
+  }, [enabled, interval, name, exponentialBackoff, maxRetries, retryBaseDelay, retryMaxDelay]);
 
   return { isRunning, lastError };
 }
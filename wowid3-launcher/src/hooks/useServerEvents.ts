@@ -0,0 +1,61 @@
+import { useEffect, useRef, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { logger, LogCategory } from '../utils/logger';
+import { useSettingsStore } from '../stores/settingsStore';
+import { extractBaseUrl } from '../utils/url';
+import { startServerEvents, stopServerEvents } from './useTauriCommands';
+import type { ServerEventPayload, ServerEventsStatusPayload } from '../types/tauri-events';
+
+interface ServerEventHandlers {
+  /** A modpack release was published or removed */
+  onRelease: (event: ServerEventPayload) => void;
+  /** A new launcher build was published */
+  onLauncher: (event: ServerEventPayload) => void;
+}
+
+/**
+ * Subscribe to the modpack server's push notifications.
+ *
+ * Returns whether the stream is currently connected, so callers can fall
+ * back to polling while it isn't.
+ */
+export function useServerEvents(handlers: ServerEventHandlers) {
+  const manifestUrl = useSettingsStore(state => state.manifestUrl);
+  const [connected, setConnected] = useState(false);
+  const handlersRef = useRef(handlers);
+  handlersRef.current = handlers;
+
+  useEffect(() => {
+    const unlistenEvent = listen<ServerEventPayload>('server-event', (event) => {
+      logger.debug(LogCategory.MODPACK, 'Server event received', { metadata: { ...event.payload } });
+      if (event.payload.kind === 'launcher') {
+        handlersRef.current.onLauncher(event.payload);
+      } else {
+        handlersRef.current.onRelease(event.payload);
+      }
+    });
+    const unlistenStatus = listen<ServerEventsStatusPayload>('server-events-status', (event) => {
+      setConnected(event.payload.connected);
+    });
+
+    return () => {
+      unlistenEvent.then(fn => fn());
+      unlistenStatus.then(fn => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    if (!manifestUrl) return;
+
+    startServerEvents(extractBaseUrl(manifestUrl)).catch(err => {
+      logger.error(LogCategory.MODPACK, 'Failed to subscribe to server events:', err instanceof Error ? err : new Error(String(err)));
+    });
+
+    return () => {
+      setConnected(false);
+      stopServerEvents().catch(() => {});
+    };
+  }, [manifestUrl]);
+
+  return { connected };
+}
@@ -1,5 +1,5 @@
 import { useState, useEffect, useRef } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../utils/invoke';
 import { TrackerState } from '../types/tracker';
 
 export function useServerTracker(baseUrl: string, intervalMs: number = 5000) {
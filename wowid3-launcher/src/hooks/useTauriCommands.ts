@@ -1,10 +1,35 @@
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../utils/invoke';
 import { z } from 'zod';
-import type { MinecraftProfile, Manifest, ServerStatus } from '../stores';
+import type { MinecraftProfile, Manifest, ReleaseChannel, ServerStatus } from '../stores';
 import { deduplicator } from '../utils/deduplication';
+import { CommandError } from '../utils/errors';
 import {
+  AudioTrack,
+  BackupInfo,
+  BackupOptions,
+  CleanupResult,
+  CmsConfig,
+  CrashUploadResult,
+  DiskUsage,
+  DownloadControlState,
   InstallConfig,
-  LaunchConfig
+  Instance,
+  InstancesConfig,
+  JavaInstallation,
+  JvmProfile,
+  JvmSettings,
+  LaunchConfig,
+  ExportSummary,
+  ImportReport,
+  ModLoader,
+  NewInstance,
+  OptionalGroupState,
+  PreLaunchWarning,
+  Screenshot,
+  ScreenshotUploadResult,
+  SupportReportResult,
+  SystemReport,
+  VerificationReport
 } from '../types/minecraft';
 import {
   MinecraftProfileSchema,
@@ -14,8 +39,10 @@ import {
   DeviceCodeInfoSchema,
   VersionInfoSchema,
   FabricLoaderSchema,
+  LoaderVersionSchema,
   type VersionInfo,
   type FabricLoader,
+  type LoaderVersion,
 } from '../types/schemas';
 
 // Re-export LaunchConfig for backward compatibility
@@ -36,6 +63,10 @@ export interface LauncherUpdateInfo {
     mandatory: boolean;
     download_url: string;
     sha256: string;
+    /** "executable" or "installer" */
+    file_type: string;
+    /** Base64 Ed25519 signature, checked before the update is applied */
+    signature: string | null;
 }
 
 export interface AvatarData {
@@ -43,6 +74,14 @@ export interface AvatarData {
     content_type: string;
 }
 
+export interface PlayerSkin {
+    uuid: string;
+    render: string;  // data URL of the front-facing 2D render
+    skin: string;    // data URL of the raw skin texture
+    cape: string | null;
+    slim: boolean;
+}
+
 // Authentication commands with Zod validation
 export const authenticateMinecraft = async (): Promise<MinecraftProfile> => {
   try {
@@ -108,10 +147,24 @@ export const completeDeviceCodeAuth = async (deviceCode: string, interval: numbe
   return MinecraftProfileSchema.parse(result);
 };
 
+/** Whether username-only demo accounts are enabled (`WOWID3_DEMO_ACCOUNTS=1`) */
+export const demoAccountsEnabled = async (): Promise<boolean> => {
+  return await invoke<boolean>('cmd_demo_accounts_enabled');
+};
+
+export const signInDemo = async (username: string): Promise<MinecraftProfile> => {
+  const result = await invoke('cmd_sign_in_demo', { username });
+  return MinecraftProfileSchema.parse(result);
+};
+
 export const fetchAvatar = async (username: string): Promise<AvatarData> => {
   return await invoke<AvatarData>('cmd_fetch_avatar', { username });
 };
 
+export const getPlayerSkin = async (uuid: string): Promise<PlayerSkin> => {
+  return await invoke<PlayerSkin>('cmd_get_player_skin', { uuid });
+};
+
 export const isAvatarCached = async (identifier: string): Promise<boolean> => {
   return await invoke<boolean>('cmd_is_avatar_cached', { identifier });
 };
@@ -150,10 +203,19 @@ export const getDetailedServerStatus = async (baseUrl: string): Promise<any> =>
   return TrackerStateSchema.parse(result);
 };
 
+/** Send a chat message as the signed-in player; the tracker mod broadcasts it in-game */
+export const sendChatMessage = async (baseUrl: string, content: string): Promise<void> => {
+  await invoke('cmd_send_chat_message', { baseUrl, content });
+};
+
 // Modpack update commands
-export const checkForUpdates = async (manifestUrl: string): Promise<Manifest> => {
-  const result = await deduplicator.execute(`checkForUpdates:${manifestUrl}`, () =>
-    invoke('cmd_check_updates', { manifestUrl })
+export const checkForUpdates = async (
+  manifestUrl: string,
+  channel: ReleaseChannel = 'stable',
+  gameDir?: string
+): Promise<Manifest> => {
+  const result = await deduplicator.execute(`checkForUpdates:${manifestUrl}:${channel}`, () =>
+    invoke('cmd_check_updates', { manifestUrl, channel, gameDir: gameDir || null })
   );
   return ManifestSchema.parse(result);
 };
@@ -164,9 +226,22 @@ export const getInstalledVersion = async (gameDir: string): Promise<string | nul
 
 export const installModpack = async (
   manifest: Manifest,
-  gameDir: string
+  gameDir: string,
+  backup: BackupOptions | null = null,
+  instanceId: string | null = null,
+  telemetryUrl: string | null = null
 ): Promise<string> => {
-  return await invoke<string>('cmd_install_modpack', { manifest, gameDir });
+  return await invoke<string>('cmd_install_modpack', { manifest, gameDir, backup, instanceId, telemetryUrl });
+};
+
+export const rollbackModpack = async (
+  manifestUrl: string,
+  version: string,
+  gameDir: string,
+  instanceId: string | null = null
+): Promise<Manifest> => {
+  const result = await invoke('cmd_rollback_modpack', { manifestUrl, version, gameDir, instanceId });
+  return ManifestSchema.parse(result);
 };
 
 export const verifyAndRepairModpack = async (
@@ -176,6 +251,37 @@ export const verifyAndRepairModpack = async (
   return await invoke<string>('cmd_verify_and_repair_modpack', { manifest, gameDir });
 };
 
+/** Check files against the manifest; only downloads or deletes anything when `repair` is set.
+ *  `deep` rehashes every file instead of only those changed since they last verified. */
+export const verifyInstallation = async (
+  manifest: Manifest,
+  gameDir: string,
+  repair: boolean = false,
+  deep: boolean = false
+): Promise<VerificationReport> => {
+  return await invoke<VerificationReport>('cmd_verify_installation', { manifest, gameDir, repair, deep });
+};
+
+/** Copy matching modpack files from a Prism, MultiMC or CurseForge instance folder.
+ *  Run the normal install afterwards to download what's listed in `remaining`. */
+export const importInstallation = async (
+  source: string,
+  manifest: Manifest,
+  gameDir: string
+): Promise<ImportReport> => {
+  return await invoke<ImportReport>('cmd_import_installation', { source, manifest, gameDir });
+};
+
+/** Zip the installed modpack as a Prism Launcher or MultiMC instance at `path` */
+export const exportInstance = async (
+  targetFormat: 'prism' | 'multimc',
+  path: string,
+  manifest: Manifest,
+  gameDir: string
+): Promise<ExportSummary> => {
+  return await invoke<ExportSummary>('cmd_export_instance', { targetFormat, path, manifest, gameDir });
+};
+
 export const hasManifestChanged = async (
   manifest: Manifest,
   gameDir: string
@@ -183,6 +289,41 @@ export const hasManifestChanged = async (
   return await invoke<boolean>('cmd_has_manifest_changed', { manifest, gameDir });
 };
 
+// Settings export/import commands
+export interface SettingsImportSummary {
+  exported_at: string;
+  launcher_version: string;
+  settings: Record<string, unknown>;
+  overlay: { enabled: boolean; directory: string | null } | null;
+  instances: { game_dir: string; installed_version: string | null }[];
+  trusted_mods: string[];
+  restored: string[];
+  skipped: string[];
+}
+
+export const exportSettings = async (
+  path: string,
+  passphrase: string,
+  settings: Record<string, unknown>,
+  gameDirs: string[],
+  trustedMods?: string[]
+): Promise<string> => {
+  return await invoke<string>('cmd_export_settings', {
+    path,
+    passphrase,
+    settings,
+    gameDirs,
+    trustedMods,
+  });
+};
+
+export const importSettings = async (
+  path: string,
+  passphrase: string
+): Promise<SettingsImportSummary> => {
+  return await invoke<SettingsImportSummary>('cmd_import_settings', { path, passphrase });
+};
+
 // Discord Rich Presence commands
 export const discordConnect = async (): Promise<void> => {
   return await invoke<void>('cmd_discord_connect');
@@ -251,6 +392,17 @@ export const getLatestFabricLoader = async (gameVersion: string): Promise<Fabric
   return FabricLoaderSchema.parse(result);
 };
 
+// Mod Loader Management (Fabric, Quilt, NeoForge)
+export const getLoaderVersions = async (loader: ModLoader, gameVersion: string): Promise<LoaderVersion[]> => {
+  const result = await invoke('cmd_get_loader_versions', { loader, gameVersion });
+  return z.array(LoaderVersionSchema).parse(result);
+};
+
+export const getLatestLoaderVersion = async (loader: ModLoader, gameVersion: string): Promise<LoaderVersion> => {
+  const result = await invoke('cmd_get_latest_loader_version', { loader, gameVersion });
+  return LoaderVersionSchema.parse(result);
+};
+
 // Installation Commands
 export const installMinecraft = async (config: InstallConfig): Promise<void> => {
   return await invoke<void>('cmd_install_minecraft', { config });
@@ -265,6 +417,234 @@ export const launchGameWithMetadata = async (config: LaunchConfig, versionId: st
   return await invoke<string>('cmd_launch_game_with_metadata', { config, versionId });
 };
 
+// Java detection commands
+export const detectJavaInstallations = async (): Promise<JavaInstallation[]> => {
+  return await invoke<JavaInstallation[]>('cmd_detect_java_installations');
+};
+
+export const validateJava = async (path: string): Promise<JavaInstallation> => {
+  return await invoke<JavaInstallation>('cmd_validate_java', { path });
+};
+
+// Screenshot commands
+export const listScreenshots = async (gameDir: string): Promise<Screenshot[]> => {
+  return await invoke<Screenshot[]>('cmd_list_screenshots', { gameDir });
+};
+
+export const deleteScreenshot = async (gameDir: string, filename: string): Promise<void> => {
+  return await invoke<void>('cmd_delete_screenshot', { gameDir, filename });
+};
+
+export const openScreenshotFolder = async (gameDir: string): Promise<void> => {
+  return await invoke<void>('cmd_open_screenshot_folder', { gameDir });
+};
+
+export const uploadScreenshot = async (
+  serverUrl: string,
+  uuid: string,
+  gameDir: string,
+  filename: string
+): Promise<ScreenshotUploadResult> => {
+  return await invoke<ScreenshotUploadResult>('cmd_upload_screenshot', {
+    serverUrl,
+    uuid,
+    gameDir,
+    filename,
+  });
+};
+
+// Instance commands
+export const listInstances = async (legacyGameDir: string | null = null): Promise<InstancesConfig> => {
+  return await invoke<InstancesConfig>('cmd_list_instances', { legacyGameDir });
+};
+
+export const createInstance = async (instance: NewInstance): Promise<Instance> => {
+  return await invoke<Instance>('cmd_create_instance', { instance });
+};
+
+export const cloneInstance = async (instanceId: string, name: string): Promise<Instance> => {
+  return await invoke<Instance>('cmd_clone_instance', { instanceId, name });
+};
+
+export const updateInstance = async (instance: Instance): Promise<Instance> => {
+  return await invoke<Instance>('cmd_update_instance', { instance });
+};
+
+export const deleteInstance = async (instanceId: string, deleteFiles: boolean): Promise<void> => {
+  return await invoke<void>('cmd_delete_instance', { instanceId, deleteFiles });
+};
+
+export const setActiveInstance = async (instanceId: string): Promise<Instance> => {
+  return await invoke<Instance>('cmd_set_active_instance', { instanceId });
+};
+
+// Download control commands
+export const getDownloadState = async (): Promise<DownloadControlState> => {
+  return await invoke<DownloadControlState>('cmd_get_download_state');
+};
+
+export const setDownloadLimit = async (maxBytesPerSec: number): Promise<DownloadControlState> => {
+  return await invoke<DownloadControlState>('cmd_set_download_limit', { maxBytesPerSec });
+};
+
+export const pauseDownloads = async (): Promise<DownloadControlState> => {
+  return await invoke<DownloadControlState>('cmd_pause_downloads');
+};
+
+export const resumeDownloads = async (): Promise<DownloadControlState> => {
+  return await invoke<DownloadControlState>('cmd_resume_downloads');
+};
+
+/** Error the install and update commands fail with after `cancelInstallation` */
+export const INSTALL_CANCELLED = 'Installation cancelled';
+
+export const isInstallCancelled = (err: unknown): boolean =>
+  err instanceof CommandError ? err.kind === 'cancelled' : String(err).includes(INSTALL_CANCELLED);
+
+// Stops the running install or update; partial downloads resume next time
+export const cancelInstallation = async (): Promise<void> => {
+  return await invoke<void>('cmd_cancel_installation');
+};
+
+// Optional mod commands
+export const getOptionalGroups = async (
+  manifest: Manifest,
+  gameDir: string
+): Promise<OptionalGroupState[]> => {
+  return await invoke<OptionalGroupState[]>('cmd_get_optional_groups', { manifest, gameDir });
+};
+
+export const setOptionalGroup = async (
+  gameDir: string,
+  groupId: string,
+  enabled: boolean
+): Promise<void> => {
+  return await invoke<void>('cmd_set_optional_group', { gameDir, groupId, enabled });
+};
+
+// World backup commands
+export const listWorlds = async (gameDir: string): Promise<string[]> => {
+  return await invoke<string[]>('cmd_list_worlds', { gameDir });
+};
+
+export const backupWorld = async (
+  gameDir: string,
+  worldName: string,
+  options: BackupOptions
+): Promise<BackupInfo> => {
+  return await invoke<BackupInfo>('cmd_backup_world', { gameDir, worldName, options });
+};
+
+export const listBackups = async (
+  worldName: string | null,
+  options: BackupOptions
+): Promise<BackupInfo[]> => {
+  return await invoke<BackupInfo[]>('cmd_list_backups', { worldName, options });
+};
+
+export const restoreBackup = async (
+  gameDir: string,
+  worldName: string,
+  filename: string,
+  options: BackupOptions
+): Promise<BackupInfo> => {
+  return await invoke<BackupInfo>('cmd_restore_backup', { gameDir, worldName, filename, options });
+};
+
+// Storage commands
+export const getDiskUsage = async (gameDir: string): Promise<DiskUsage> => {
+  return await invoke<DiskUsage>('cmd_get_disk_usage', { gameDir });
+};
+
+export const cleanLogs = async (gameDir: string): Promise<CleanupResult> => {
+  return await invoke<CleanupResult>('cmd_clean_logs', { gameDir });
+};
+
+export const cleanCrashReports = async (gameDir: string): Promise<CleanupResult> => {
+  return await invoke<CleanupResult>('cmd_clean_crash_reports', { gameDir });
+};
+
+export const cleanUnusedAssets = async (gameDir: string): Promise<CleanupResult> => {
+  return await invoke<CleanupResult>('cmd_clean_unused_assets', { gameDir });
+};
+
+export const checkModConflicts = async (gameDir: string): Promise<PreLaunchWarning[]> => {
+  return await invoke<PreLaunchWarning[]>('cmd_check_mod_conflicts', { gameDir });
+};
+
+// JVM profile commands
+export const listJvmProfiles = async (): Promise<JvmProfile[]> => {
+  return await invoke<JvmProfile[]>('cmd_list_jvm_profiles');
+};
+
+export const loadJvmProfile = async (name: string): Promise<JvmProfile> => {
+  return await invoke<JvmProfile>('cmd_load_jvm_profile', { name });
+};
+
+export const saveJvmProfile = async (profile: JvmProfile): Promise<JvmProfile> => {
+  return await invoke<JvmProfile>('cmd_save_jvm_profile', { profile });
+};
+
+export const deleteJvmProfile = async (name: string): Promise<void> => {
+  return await invoke<void>('cmd_delete_jvm_profile', { name });
+};
+
+/** Resolves to the generated JVM flags, rejects with the validation error */
+export const validateJvmSettings = async (settings: JvmSettings): Promise<string[]> => {
+  return await invoke<string[]>('cmd_validate_jvm_settings', { settings });
+};
+
+// Launch with the last-known-good configuration when the release server is unreachable
+export const launchOffline = async (config: LaunchConfig): Promise<string> => {
+  return await invoke<string>('cmd_launch_offline', { config });
+};
+
+export const uploadCrashReport = async (
+  serverUrl: string,
+  gameDir: string,
+  exitCode: number | null,
+  javaPath: string | null
+): Promise<CrashUploadResult> => {
+  return await invoke<CrashUploadResult>('cmd_upload_crash_report', {
+    serverUrl,
+    gameDir,
+    exitCode,
+    javaPath,
+  });
+};
+
+export const getSystemReport = async (
+  gameDir: string,
+  javaPath: string | null
+): Promise<SystemReport> => {
+  return await invoke<SystemReport>('cmd_get_system_report', { gameDir, javaPath });
+};
+
+export const uploadSystemReport = async (
+  serverUrl: string,
+  report: SystemReport,
+  note: string | null
+): Promise<SupportReportResult> => {
+  return await invoke<SupportReportResult>('cmd_upload_system_report', { serverUrl, report, note });
+};
+
+export const getCmsConfig = async (serverUrl: string): Promise<CmsConfig> => {
+  return await invoke<CmsConfig>('cmd_get_cms_config', { serverUrl });
+};
+
+/** Payload of the `updates-skipped` event emitted when the release server can't be reached */
+export interface UpdatesSkippedEvent {
+  reason: string;
+  installed_version: string | null;
+  offline_available: boolean;
+}
+
+/** Payload of the `auth-session` event: the session was renewed, is running unrefreshed during an auth outage, or needs a new sign-in */
+export type AuthSessionEvent =
+  | { state: 'refreshed'; username: string }
+  | { state: 'not_refreshed'; username: string; reason: string; retry_in_secs: number }
+  | { state: 'login_required'; reason: string };
+
 export const isGameRunning = async (): Promise<boolean> => {
   return await invoke<boolean>('cmd_is_game_running');
 };
@@ -274,21 +654,58 @@ export const getCachedAudio = async (): Promise<string | null> => {
   return await invoke<string | null>('cmd_get_cached_audio');
 };
 
+// Streams to disk; progress arrives as `audio-download-progress` events
 export const downloadAndCacheAudio = async (url: string): Promise<string> => {
   return await invoke<string>('cmd_download_and_cache_audio', { url });
 };
 
+// The pending download rejects with "Audio download cancelled"
+export const cancelAudioDownload = async (): Promise<void> => {
+  return await invoke<void>('cmd_cancel_audio_download');
+};
+
 export const clearAudioCache = async (): Promise<void> => {
   return await invoke<void>('cmd_clear_audio_cache');
 };
 
+// Fetch and cache the server playlist; resolves to the first track, or null if the playlist is empty
+export const loadAudioPlaylist = async (serverUrl: string, shuffle: boolean): Promise<AudioTrack | null> => {
+  return await invoke<AudioTrack | null>('cmd_load_audio_playlist', { serverUrl, shuffle });
+};
+
+export const nextAudioTrack = async (): Promise<AudioTrack | null> => {
+  return await invoke<AudioTrack | null>('cmd_next_audio_track');
+};
+
+export const previousAudioTrack = async (): Promise<AudioTrack | null> => {
+  return await invoke<AudioTrack | null>('cmd_previous_audio_track');
+};
+
+export const setAudioShuffle = async (enabled: boolean): Promise<void> => {
+  return await invoke<void>('cmd_set_audio_shuffle', { enabled });
+};
+
 // Launcher update commands
 export const checkLauncherUpdate = async (): Promise<LauncherUpdateInfo> => {
     return await invoke<LauncherUpdateInfo>('cmd_check_launcher_update');
 };
 
-export const installLauncherUpdate = async (url: string, sha256: string): Promise<void> => {
-    return await invoke<void>('cmd_install_launcher_update', { url, sha256 });
+export const installLauncherUpdate = async (
+    url: string,
+    sha256: string,
+    fileType?: string,
+    signature?: string | null,
+): Promise<void> => {
+    return await invoke<void>('cmd_install_launcher_update', { url, sha256, fileType, signature });
+};
+
+// Server push notifications (`server-event` / `server-events-status`)
+export const startServerEvents = async (serverUrl: string): Promise<void> => {
+    return await invoke<void>('cmd_start_server_events', { serverUrl });
+};
+
+export const stopServerEvents = async (): Promise<void> => {
+    return await invoke<void>('cmd_stop_server_events');
 };
 
 // BlueMap commands
@@ -1,7 +1,15 @@
 import { useEffect, useRef } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../utils/invoke';
 import { useVpnStore } from '../stores/vpnStore';
 
+interface VpnStatus {
+  wireguard_installed: boolean;
+  registered: boolean;
+  assigned_ip: string | null;
+  server_ip: string | null;
+  tunnel: 'running' | 'stopped' | 'not_installed';
+}
+
 /**
  * Hook to initialize VPN tunnel on app startup if enabled
  * This runs once when the app loads and checks if VPN should be auto-started
@@ -10,6 +18,7 @@ export const useVpnInitialization = () => {
   const enabled = useVpnStore((state) => state.enabled);
   const setStatus = useVpnStore((state) => state.setStatus);
   const setError = useVpnStore((state) => state.setError);
+  const setAssignedIp = useVpnStore((state) => state.setAssignedIp);
   const hasInitialized = useRef(false);
 
   useEffect(() => {
@@ -28,46 +37,36 @@ export const useVpnInitialization = () => {
       setStatus('connecting');
 
       try {
-        // Check if VPN is configured (has keypair)
-        const hasKeypair = await invoke<boolean>('vpn_has_keypair');
+        const vpnStatus = await invoke<VpnStatus>('cmd_vpn_status');
+        console.log('[VPN] Tunnel status:', vpnStatus.tunnel);
 
-        if (!hasKeypair) {
-          console.warn('[VPN] VPN enabled but no keypair found - user needs to complete setup');
+        if (!vpnStatus.registered) {
+          console.warn('[VPN] VPN enabled but device is not registered - user needs to complete setup');
           setStatus('error');
           setError('VPN configuration missing. Please disable and re-enable VPN in settings to complete setup.');
           return;
         }
 
-        // Check tunnel status
-        const tunnelStatus = await invoke<string>('vpn_tunnel_status');
-        console.log('[VPN] Tunnel status:', tunnelStatus);
-
-        if (tunnelStatus === 'not_installed') {
+        if (!vpnStatus.wireguard_installed) {
           console.warn('[VPN] WireGuard not installed');
           setStatus('error');
           setError('WireGuard is not installed. Please install WireGuard from wireguard.com');
           return;
         }
 
-        if (tunnelStatus === 'running') {
-          console.log('[VPN] Tunnel already running');
-          setStatus('connected');
-          return;
-        }
+        setAssignedIp(vpnStatus.assigned_ip);
 
-        // Tunnel exists but not running, start it
-        if (tunnelStatus === 'stopped') {
-          console.log('[VPN] Starting VPN tunnel...');
-          await invoke('vpn_start_tunnel');
-          console.log('[VPN] VPN tunnel started successfully');
+        if (vpnStatus.tunnel === 'running') {
+          console.log('[VPN] Tunnel already running');
           setStatus('connected');
           return;
         }
 
-        // Unknown status
-        console.warn('[VPN] Unknown tunnel status:', tunnelStatus);
-        setStatus('error');
-        setError(`Unknown VPN status: ${tunnelStatus}`);
+        // Installs the tunnel service if needed, then starts it
+        console.log('[VPN] Starting VPN tunnel...');
+        await invoke('cmd_vpn_connect');
+        console.log('[VPN] VPN tunnel started successfully');
+        setStatus('connected');
       } catch (error) {
         console.error('[VPN] Failed to initialize VPN:', error);
         setStatus('error');
@@ -76,5 +75,5 @@ export const useVpnInitialization = () => {
     };
 
     initializeVpn();
-  }, [enabled, setStatus, setError]);
+  }, [enabled, setStatus, setError, setAssignedIp]);
 };
@@ -1,5 +1,6 @@
 import { create } from 'zustand';
 import { persist } from 'zustand/middleware';
+import type { AudioTrack } from '../types/minecraft';
 
 export type AudioState = 'loading' | 'fallback' | 'transitioning' | 'main';
 
@@ -9,9 +10,11 @@ interface AudioStoreState {
   wasPaused: boolean; // Track if audio was paused before game launch
   audioState: AudioState;
   mainAudioReady: boolean;
+  currentTrack: AudioTrack | null; // Playlist track on the main element; null for the bundled track
 
-  // Volume (persisted)
+  // Volume and shuffle (persisted)
   volume: number; // 0-1 range
+  shuffle: boolean;
 
   // Actions
   setMuted: (muted: boolean) => void;
@@ -19,6 +22,8 @@ interface AudioStoreState {
   setAudioState: (state: AudioState) => void;
   setMainAudioReady: (ready: boolean) => void;
   setVolume: (volume: number) => void;
+  setCurrentTrack: (track: AudioTrack | null) => void;
+  setShuffle: (shuffle: boolean) => void;
   reset: () => void; // Reset to initial state
 
   // Game launch integration
@@ -31,7 +36,9 @@ const INITIAL_STATE = {
   wasPaused: false,
   audioState: 'loading' as AudioState,
   mainAudioReady: false,
+  currentTrack: null,
   volume: 0.3, // Default volume 30%
+  shuffle: false,
 };
 
 export const useAudioStore = create<AudioStoreState>()(
@@ -45,6 +52,8 @@ export const useAudioStore = create<AudioStoreState>()(
       setAudioState: (state) => set({ audioState: state }),
       setMainAudioReady: (ready) => set({ mainAudioReady: ready }),
       setVolume: (volume: number) => set({ volume: Math.max(0, Math.min(1, volume)) }),
+      setCurrentTrack: (track) => set({ currentTrack: track }),
+      setShuffle: (shuffle) => set({ shuffle }),
 
       reset: () => set(INITIAL_STATE),
 
@@ -62,9 +71,10 @@ export const useAudioStore = create<AudioStoreState>()(
     {
       name: 'wowid3-audio-storage', // localStorage key
       partialize: (state) => ({
-        // Only persist volume and muted state
+        // Only persist volume, muted and shuffle state
         volume: state.volume,
         isMuted: state.isMuted,
+        shuffle: state.shuffle,
       }),
     }
   )
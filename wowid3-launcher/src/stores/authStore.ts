@@ -8,6 +8,7 @@ export interface MinecraftProfile {
   session_id: string; // Session ID for backend token lookup
   skin_url?: string;
   expires_at?: string; // ISO 8601 date string from Rust's DateTime<Utc>
+  demo?: boolean; // Username-only demo account (WOWID3_DEMO_ACCOUNTS)
 }
 
 interface AuthState {
@@ -0,0 +1,42 @@
+import { create } from 'zustand';
+import type { CmsConfig } from '../types/minecraft';
+
+/** Used until the server config (or its cached copy) has loaded; matches the server defaults */
+export const DEFAULT_CMS_CONFIG: CmsConfig = {
+  branding: {
+    server_name: 'WOWID3',
+    tagline: null,
+    discord_url: null,
+    website_url: null,
+  },
+  theme: {
+    default_theme: 'christmas',
+    accent_color: null,
+  },
+  polling: {
+    server_status_ms: 30000,
+    tracker_status_ms: 5000,
+    update_check_ms: 300000,
+  },
+  retry: {
+    max_attempts: 3,
+    base_delay_ms: 1000,
+    max_delay_ms: 30000,
+  },
+  features: {},
+};
+
+interface CmsState {
+  config: CmsConfig;
+  loaded: boolean;
+
+  // Actions
+  setConfig: (config: CmsConfig) => void;
+}
+
+export const useCmsStore = create<CmsState>((set) => ({
+  config: DEFAULT_CMS_CONFIG,
+  loaded: false,
+
+  setConfig: (config) => set({ config, loaded: true }),
+}));
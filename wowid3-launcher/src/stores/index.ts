@@ -5,10 +5,12 @@ export { useSettingsStore } from './settingsStore';
 export { useUpdateStore } from './updateStore';
 export { useNetworkTestStore } from './networkTestStore';
 export { useVpnStore } from './vpnStore';
+export { useCmsStore } from './cmsStore';
 
 export type { MinecraftProfile } from './authStore';
 export type { Manifest, ModpackFile } from './modpackStore';
 export type { ServerStatus } from './serverStore';
+export type { ReleaseChannel } from './settingsStore';
 export type { LauncherUpdateInfo, ModpackUpdateInfo } from './updateStore';
 export type {
   NetworkTestResult,
@@ -1,20 +1,24 @@
 import { create } from 'zustand';
 import { persist } from 'zustand/middleware';
 import { LauncherError } from '../types';
+import type { ModLoader, OptionalGroup } from '../types/minecraft';
 
 export interface ModpackFile {
   path: string;
   url: string;
   sha256: string;
   size: number;
+  mirrors?: string[];
 }
 
 export interface Manifest {
   version: string;
   minecraft_version: string;
+  loader?: ModLoader;
   fabric_loader: string;
   files: ModpackFile[];
   changelog: string;
+  optional_groups?: OptionalGroup[];
 }
 
 interface ModpackStoreState {
@@ -28,6 +32,9 @@ interface ModpackStoreState {
   downloadProgress: {
     current: number;
     total: number;
+    /** Bytes per second, when the backend reports it */
+    speed?: number;
+    etaSeconds?: number | null;
   } | null;
   error: LauncherError | null;
 
@@ -45,7 +52,7 @@ interface ModpackStoreState {
   setValidating: (validating: boolean) => void;
   setVerifying: (verifying: boolean) => void;
   setBlockedForInstall: (blocked: boolean) => void;
-  setDownloadProgress: (current: number, total: number) => void;
+  setDownloadProgress: (current: number, total: number, speed?: number, etaSeconds?: number | null) => void;
   setError: (error: LauncherError | null) => void;
   setHasCheckedForModpack: (checked: boolean) => void;
   incrementCheckRetries: () => void;
@@ -92,8 +99,8 @@ export const useModpackStore = create<ModpackStoreState>()(
 
       setBlockedForInstall: (blocked) => set({ isBlockedForInstall: blocked }),
 
-      setDownloadProgress: (current, total) =>
-        set({ downloadProgress: { current, total } }),
+      setDownloadProgress: (current, total, speed, etaSeconds) =>
+        set({ downloadProgress: { current, total, speed, etaSeconds } }),
 
       setError: (error) => set({ error }),
 
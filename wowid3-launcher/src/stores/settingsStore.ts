@@ -1,6 +1,6 @@
 import { create } from 'zustand';
 import { persist, createJSONStorage } from 'zustand/middleware';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../utils/invoke';
 import { platform } from '@tauri-apps/plugin-os';
 import { homeDir } from '@tauri-apps/api/path';
 import {
@@ -9,13 +9,20 @@ import {
   validateRamAllocation,
   validateServerAddress,
 } from '../utils/security';
+import type { BackupOptions, GcPreset, Instance, JvmSettings } from '../types/minecraft';
 // Secure storage disabled for now due to Zustand compatibility issues
 // import { setSecureItem, getSecureItem } from '../utils/secureStorage';
 
+export type ReleaseChannel = 'stable' | 'beta';
+
 interface SettingsState {
   // Java settings
   javaPath: string | null;
-  ramAllocation: number; // in MB
+  ramAllocation: number; // in MB (max heap)
+  minHeapMb: number | null; // null = same as ramAllocation
+  gcPreset: GcPreset;
+  customJvmArgs: string[];
+  jvmProfile: string | null; // Name of the last applied JVM profile
 
   // Game settings
   gameDirectory: string;
@@ -35,12 +42,30 @@ interface SettingsState {
   // Launcher settings
   theme: 'christmas' | 'dark' | 'light';
   manifestUrl: string;
+  releaseChannel: ReleaseChannel; // 'beta' also receives pre-release modpack versions
   keepLauncherOpen: boolean; // Show log viewer instead of minimizing
+  gameStopGraceSecs: number; // Time the game gets to save and exit before Stop kills it
+  uploadCrashReports: boolean; // Opt-in: send crash reports to the release server
+  shareUpdateTelemetry: boolean; // Opt-in: report update duration and failures to the release server
   musicWasPaused: boolean; // Track if music was paused before game launch
 
+  // Stream overlay settings (text/JSON files for OBS)
+  overlayEnabled: boolean;
+  overlayDirectory: string | null; // null = default <data dir>/overlay
+
+  // Instances
+  activeInstanceId: string | null; // null until instances have been loaded once
+
+  // World backups
+  autoBackupBeforeUpdate: boolean; // Back up all worlds before installing a new modpack version
+  backupDirectory: string | null; // null = default <data dir>/backups
+  backupKeep: number; // Backups kept per world
+
   // Actions
   setJavaPath: (path: string | null) => void;
   setRamAllocation: (ram: number) => void;
+  setJvmSettings: (settings: JvmSettings, profileName?: string | null) => void;
+  getJvmSettings: () => JvmSettings;
   setGameDirectory: (dir: string) => void;
   setServerAddress: (address: string) => void;
   setMinecraftVersion: (version: string | null) => void;
@@ -51,11 +76,31 @@ interface SettingsState {
   setIsMinecraftInstalled: (installed: boolean) => void;
   setTheme: (theme: 'christmas' | 'dark' | 'light') => void;
   setManifestUrl: (url: string) => void;
+  setReleaseChannel: (channel: ReleaseChannel) => void;
   setKeepLauncherOpen: (keep: boolean) => void;
+  setGameStopGraceSecs: (secs: number) => void;
+  setUploadCrashReports: (enabled: boolean) => void;
+  setShareUpdateTelemetry: (enabled: boolean) => void;
   setMusicWasPaused: (paused: boolean) => void;
+  setOverlayEnabled: (enabled: boolean) => void;
+  setOverlayDirectory: (dir: string | null) => void;
+  applyInstance: (instance: Instance) => void;
+  setAutoBackupBeforeUpdate: (enabled: boolean) => void;
+  setBackupDirectory: (dir: string | null) => void;
+  setBackupKeep: (keep: number) => void;
+  getBackupOptions: () => BackupOptions;
   initializeGameDirectory: () => Promise<void>;
 }
 
+// Push overlay settings to the Rust overlay writer
+const syncOverlaySettings = (enabled: boolean, directory: string | null) => {
+  invoke<string>('cmd_overlay_configure', { settings: { enabled, directory } })
+    .then((dir) => {
+      if (enabled) console.log('[Settings] Overlay files written to:', dir);
+    })
+    .catch((error) => console.error('[Settings] Failed to configure overlay:', error));
+};
+
 // Note: SecureStorage with HMAC verification is disabled for now due to Zustand compatibility
 // The persist storage expects synchronous operations, but HMAC verification is async
 // For production, consider implementing a custom persist middleware or using sessionStorage
@@ -66,6 +111,10 @@ export const useSettingsStore = create<SettingsState>()(
       // Defaults
       javaPath: null, // Will use bundled Java
       ramAllocation: 16384, // 16GB default
+      minHeapMb: null,
+      gcPreset: 'g1_tuned',
+      customJvmArgs: [],
+      jvmProfile: null,
       gameDirectory: '', // Will be set by initializeGameDirectory()
       serverAddress: 'mc.frostdev.io:25565',
       _defaultGameDirectoryFetched: false,
@@ -77,8 +126,18 @@ export const useSettingsStore = create<SettingsState>()(
       isMinecraftInstalled: false, // Will be checked on startup
       theme: 'christmas',
       manifestUrl: 'https://wowid-launcher.frostdev.io/api/manifest/latest',
+      releaseChannel: 'stable',
       keepLauncherOpen: false, // Default to minimize launcher
+      gameStopGraceSecs: 30,
+      uploadCrashReports: false,
+      shareUpdateTelemetry: false,
       musicWasPaused: false, // Track music state
+      overlayEnabled: false,
+      overlayDirectory: null,
+      activeInstanceId: null,
+      autoBackupBeforeUpdate: true,
+      backupDirectory: null,
+      backupKeep: 5,
 
       setJavaPath: (path) => set({ javaPath: path }),
       setRamAllocation: (ram) => {
@@ -88,6 +147,28 @@ export const useSettingsStore = create<SettingsState>()(
         }
         set({ ramAllocation: ram });
       },
+      setJvmSettings: (settings, profileName = null) => {
+        if (!validateRamAllocation(settings.max_heap_mb)) {
+          console.error('[Settings] Invalid RAM allocation:', settings.max_heap_mb);
+          return;
+        }
+        set({
+          ramAllocation: settings.max_heap_mb,
+          minHeapMb: settings.min_heap_mb ?? null,
+          gcPreset: settings.gc_preset ?? 'g1_tuned',
+          customJvmArgs: settings.custom_args ?? [],
+          jvmProfile: profileName,
+        });
+      },
+      getJvmSettings: () => {
+        const { ramAllocation, minHeapMb, gcPreset, customJvmArgs } = get();
+        return {
+          max_heap_mb: ramAllocation,
+          min_heap_mb: minHeapMb,
+          gc_preset: gcPreset,
+          custom_args: customJvmArgs,
+        };
+      },
       setGameDirectory: (dir) => {
         if (!validateGameDirectory(dir)) {
           console.error('[Settings] Invalid game directory:', dir);
@@ -116,8 +197,51 @@ export const useSettingsStore = create<SettingsState>()(
         }
         set({ manifestUrl: url });
       },
+      setReleaseChannel: (channel) => set({ releaseChannel: channel }),
       setKeepLauncherOpen: (keep) => set({ keepLauncherOpen: keep }),
+      setGameStopGraceSecs: (secs) => {
+        if (!Number.isInteger(secs) || secs < 5 || secs > 300) {
+          console.error('[Settings] Invalid stop grace period:', secs);
+          return;
+        }
+        set({ gameStopGraceSecs: secs });
+      },
+      setUploadCrashReports: (enabled) => set({ uploadCrashReports: enabled }),
+      setShareUpdateTelemetry: (enabled) => set({ shareUpdateTelemetry: enabled }),
       setMusicWasPaused: (paused) => set({ musicWasPaused: paused }),
+      setOverlayEnabled: (enabled) => {
+        set({ overlayEnabled: enabled });
+        syncOverlaySettings(enabled, get().overlayDirectory);
+      },
+      setOverlayDirectory: (dir) => {
+        set({ overlayDirectory: dir });
+        syncOverlaySettings(get().overlayEnabled, dir);
+      },
+      // Switch the game directory, modpack source and memory settings to an instance
+      applyInstance: (instance) =>
+        set({
+          activeInstanceId: instance.id,
+          gameDirectory: instance.game_dir,
+          manifestUrl: instance.manifest_url,
+          ramAllocation: instance.jvm.max_heap_mb,
+          minHeapMb: instance.jvm.min_heap_mb ?? null,
+          gcPreset: instance.jvm.gc_preset ?? 'g1_tuned',
+          customJvmArgs: instance.jvm.custom_args ?? [],
+          jvmProfile: null,
+        }),
+      setAutoBackupBeforeUpdate: (enabled) => set({ autoBackupBeforeUpdate: enabled }),
+      setBackupDirectory: (dir) => set({ backupDirectory: dir }),
+      setBackupKeep: (keep) => {
+        if (!Number.isInteger(keep) || keep < 1 || keep > 100) {
+          console.error('[Settings] Invalid backup count:', keep);
+          return;
+        }
+        set({ backupKeep: keep });
+      },
+      getBackupOptions: () => {
+        const { backupDirectory, backupKeep } = get();
+        return { directory: backupDirectory, keep: backupKeep };
+      },
 
       // Initialize game directory with OS-specific default
       initializeGameDirectory: async () => {
@@ -213,6 +337,11 @@ export const useSettingsStore = create<SettingsState>()(
             console.warn('[Settings] Invalid server address in storage, resetting to default');
             state.serverAddress = 'mc.frostdev.io:25565';
           }
+
+          // Restore overlay writer configuration
+          if (state.overlayEnabled) {
+            syncOverlaySettings(true, state.overlayDirectory ?? null);
+          }
         }
       },
     }
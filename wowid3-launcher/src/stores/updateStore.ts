@@ -7,6 +7,10 @@ export interface LauncherUpdateInfo {
   changelog: string;
   download_url: string;
   sha256: string;
+  /** "executable" or "installer" */
+  file_type: string;
+  /** Base64 Ed25519 signature, checked before the update is applied */
+  signature: string | null;
 }
 
 export interface ModpackUpdateInfo {
@@ -80,6 +80,8 @@ export {
   TrackerStateSchema,
   VersionInfoSchema,
   FabricLoaderSchema,
+  ModLoaderSchema,
+  LoaderVersionSchema,
   InstallProgressSchema,
   DeviceCodeInfoSchema,
   LauncherUpdateInfoSchema,
@@ -107,6 +109,7 @@ export {
   type TrackerState,
   type VersionInfo,
   type FabricLoader,
+  type LoaderVersion,
   type InstallProgress,
   type DeviceCodeInfo,
   type LauncherUpdateInfo,
@@ -119,6 +122,8 @@ export type {
   LaunchConfig,
   InstallConfig,
   DownloadProgressEvent,
+  DownloadProgressV2Event,
+  FileProgress,
   MinecraftProfileRaw,
   ManifestRaw,
   ServerStatusRaw,
@@ -16,12 +16,254 @@ export interface FabricLoader {
   stable: boolean;     // Is this a stable release?
 }
 
+export type ModLoader = 'fabric' | 'quilt' | 'neoforge';
+
+export interface JavaInstallation {
+  path: string;
+  version: string;          // "21.0.9" or "1.8.0_392"
+  major_version: number;
+  vendor: string | null;
+  arch: string | null;      // JVM os.arch, e.g. "amd64"
+  is_64bit: boolean;
+  source: 'bundled' | 'JAVA_HOME' | 'PATH' | 'system' | 'custom';
+  compatible: boolean;      // Java 17+, 64-bit, matches host architecture
+}
+
+export interface CrashUploadResult {
+  id: number;
+  signature: string; // Server-side grouping key for this crash
+}
+
+/** Environment details gathered for a support request (`cmd_get_system_report`) */
+export interface SystemReport {
+  generated_at: string;      // RFC 3339
+  launcher_version: string;
+  os: {
+    family: string;          // "windows" | "macos" | "linux"
+    name: string | null;     // e.g. "Windows 11 Pro"
+    version: string | null;
+    kernel: string | null;
+    arch: string;
+  };
+  cpu: {
+    model: string | null;
+    physical_cores: number | null;
+    logical_cores: number;
+  };
+  memory: {
+    total_mb: number;
+    available_mb: number;
+  };
+  gpus: {
+    name: string;
+    driver: string | null;   // Kernel driver (Linux) or vendor
+    driver_version: string | null;
+  }[];
+  java: {
+    path: string;
+    version: string;
+    vendor: string | null;
+    arch: string | null;
+  } | null;
+  modpack_version: string | null;
+  minecraft_version: string | null;
+}
+
+export interface SupportReportResult {
+  id: number; // Reference to give to support
+}
+
+export interface Screenshot {
+  filename: string;
+  path: string;
+  size_bytes: number;
+  taken_at: string;          // RFC 3339
+  thumbnail: string | null;  // data:image/jpeg;base64,... (null if the image couldn't be decoded)
+}
+
+export interface ScreenshotUploadResult {
+  id: number;
+  filename: string;
+  url: string;               // Server path, e.g. /api/screenshots/<uuid>/<file>
+}
+
+export interface Instance {
+  id: string;
+  name: string;
+  game_dir: string;
+  manifest_url: string;
+  jvm: JvmSettings;
+  installed_version: string | null;
+  created_at: string;        // RFC 3339
+}
+
+export interface InstancesConfig {
+  active: string | null;
+  instances: Instance[];
+}
+
+export interface NewInstance {
+  name: string;
+  game_dir?: string | null;     // Default: <data dir>/instances/<id>
+  manifest_url?: string | null; // Default: main modpack manifest
+  jvm?: JvmSettings | null;
+}
+
+export interface OptionalGroup {
+  id: string;
+  name: string;
+  description: string;
+  default_enabled: boolean;
+  files: string[];           // Manifest paths in this group
+}
+
+export interface OptionalGroupState extends OptionalGroup {
+  enabled: boolean;          // Player's choice, or default_enabled if never changed
+}
+
+export interface DownloadControlState {
+  max_bytes_per_sec: number; // Shared speed cap, 0 = unlimited
+  paused: boolean;
+}
+
+export interface BackupOptions {
+  directory: string | null;  // null = default <data dir>/backups
+  keep: number;              // Backups kept per world
+}
+
+export interface BackupInfo {
+  world_name: string;
+  filename: string;
+  path: string;
+  size_bytes: number;
+  created_at: string;        // RFC 3339
+}
+
+export interface CategoryUsage {
+  category: string;          // "mods" | "config" | "saves" | "logs" | "crash_reports" | "cache" | ... | "other"
+  bytes: number;
+  files: number;
+}
+
+export interface DiskUsage {
+  game_dir: string;
+  total_bytes: number;
+  categories: CategoryUsage[]; // Largest first
+  available_bytes: number | null;
+}
+
+export interface CleanupResult {
+  files_removed: number;
+  bytes_freed: number;
+}
+
+/** Problem with the mods folder found before launch (`pre-launch-warnings` event) */
+export interface PreLaunchWarning {
+  kind: 'duplicate' | 'incompatible' | 'not_fabric';
+  mod_id: string | null;
+  /** Jars involved, relative to the game directory */
+  files: string[];
+  message: string;
+}
+
+/** How often the launcher polls the server, in milliseconds */
+export interface PollingIntervals {
+  server_status_ms: number;
+  tracker_status_ms: number;
+  update_check_ms: number;   // Only while the server event stream is down
+}
+
+/** Backoff used when a poll fails */
+export interface RetryConfig {
+  max_attempts: number;
+  base_delay_ms: number;
+  max_delay_ms: number;
+}
+
+/** Launcher settings served by the modpack server (`cmd_get_cms_config`) */
+export interface CmsConfig {
+  branding: {
+    server_name: string;
+    tagline: string | null;
+    discord_url: string | null;
+    website_url: string | null;
+  };
+  theme: {
+    default_theme: 'christmas' | 'dark' | 'light';
+    accent_color: string | null;
+  };
+  polling: PollingIntervals;
+  retry: RetryConfig;
+  features: Record<string, boolean>;
+}
+
+/** Payload of the `audio-download-progress` event */
+export interface AudioDownloadProgress {
+  downloaded: number;
+  total: number;  // 0 when the server doesn't send a length
+}
+
+/** Menu music track from the server playlist (`cmd_load_audio_playlist`, `cmd_next_audio_track`) */
+export interface AudioTrack {
+  index: number;        // Position in the server's playlist order
+  title: string;
+  path: string | null;  // Cached local file; null if it couldn't be downloaded
+}
+
+/** Result of `cmd_verify_installation` */
+export interface VerificationReport {
+  manifest_version: string;
+  files_checked: number;
+  missing: { path: string; size: number }[];
+  corrupted: { path: string; size: number }[];
+  extra: string[];             // Not in the manifest; removed by a repair
+  locally_modified: string[];  // Edited configs a repair keeps
+  repair_bytes: number;        // Bytes a repair downloads
+  repaired: boolean;
+}
+
+/** Result of `cmd_import_installation` */
+export interface ImportReport {
+  source_launcher: 'prism' | 'curseforge' | 'minecraft';
+  source_dir: string;
+  already_installed: number;           // Manifest files that were already in place
+  imported: string[];                  // Paths copied from the other launcher
+  imported_bytes: number;
+  remaining: { path: string; size: number }[];  // Still to download
+  remaining_bytes: number;
+}
+
+/** Result of `cmd_export_instance` */
+export interface ExportSummary {
+  path: string;
+  modpack_version: string;
+  files: number;
+  bytes: number;
+  missing: string[];  // Manifest files that weren't installed
+}
+
 export interface InstallConfig {
   game_version: string;      // "1.20.1"
-  fabric_version?: string;   // Optional: "0.18.0" (omit for vanilla)
+  loader?: ModLoader;        // Defaults to 'fabric'
+  loader_version?: string;   // Optional: "0.18.0" (omit for vanilla)
   game_dir: string;          // Absolute path to game directory
 }
 
+export type GcPreset = 'g1_tuned' | 'g1' | 'zgc';
+
+export interface JvmSettings {
+  max_heap_mb: number;
+  min_heap_mb?: number | null; // Defaults to max_heap_mb
+  gc_preset?: GcPreset;         // Defaults to 'g1_tuned'
+  custom_args?: string[];       // One JVM flag per entry
+}
+
+export interface JvmProfile {
+  name: string;
+  settings: JvmSettings;
+  builtin: boolean;
+}
+
 export interface LaunchConfig {
   ram_mb: number;           // RAM allocation in MB (e.g., 4096)
   java_path?: string;       // Optional: custom Java path
@@ -29,10 +271,12 @@ export interface LaunchConfig {
   username: string;         // Minecraft username
   uuid: string;             // Player UUID
   session_id: string;       // Session ID for backend token lookup
+  jvm?: JvmSettings;        // Optional: heap/GC/custom flags (defaults from ram_mb)
+  instance_id?: string | null; // Optional: launch this instance (overrides game_dir and jvm)
 }
 
 export interface InstallProgress {
-  step: string;      // "version_meta" | "fabric" | "client" | "libraries" | "assets" | "complete"
+  step: string;      // "clean" | "version_meta" | "fabric" | "verifying" | "libraries" | "assets" | "natives" | "complete"
   current: number;   // Current files downloaded
   total: number;     // Total files to download
   current_bytes: number;  // Current bytes downloaded
@@ -42,17 +286,21 @@ export interface InstallProgress {
 
 export type InstallStep =
   | 'version_meta'
-  | 'fabric'
+  | 'loader'
   | 'client'
+  | 'verifying'
   | 'libraries'
   | 'assets'
+  | 'natives'
   | 'complete';
 
 export const INSTALL_STEP_LABELS: Record<InstallStep, string> = {
   version_meta: 'Fetching version metadata...',
-  fabric: 'Installing Fabric loader...',
+  loader: 'Installing mod loader...',
   client: 'Downloading Minecraft client...',
+  verifying: 'Checking installed files...',
   libraries: 'Downloading libraries...',
   assets: 'Downloading assets...',
+  natives: 'Extracting native libraries...',
   complete: 'Installation complete!',
 };
@@ -27,6 +27,7 @@ export const MinecraftProfileSchema = z.object({
   skin_url: z.string().url().optional(),
   refresh_token: z.string().optional(),
   expires_at: z.string().optional(), // Relaxed datetime validation
+  demo: z.boolean().optional(),
 });
 
 export type MinecraftProfile = z.infer<typeof MinecraftProfileSchema>;
@@ -39,20 +40,39 @@ export const ModpackFileSchema = z.object({
   url: z.string().url(),
   sha256: z.string().regex(SHA256_REGEX, 'Invalid SHA256 hash'),
   size: z.number().int().min(0), // Size 0 is valid for empty files
+  mirrors: z.array(z.string().url()).optional(),
 });
 
 export type ModpackFile = z.infer<typeof ModpackFileSchema>;
 
+/**
+ * Mod Loader Schema
+ */
+export const ModLoaderSchema = z.enum(['fabric', 'quilt', 'neoforge']);
+
+/**
+ * Optional Group Schema (mods players can switch on or off)
+ */
+export const OptionalGroupSchema = z.object({
+  id: z.string().min(1),
+  name: z.string().min(1),
+  description: z.string().default(''),
+  default_enabled: z.boolean().default(false),
+  files: z.array(z.string()),
+});
+
 /**
  * Manifest Schema
  */
 export const ManifestSchema = z.object({
   version: z.string().min(1),
   minecraft_version: z.string().min(1),
+  loader: ModLoaderSchema.default('fabric'),
   fabric_loader: z.string().min(1),
   files: z.array(ModpackFileSchema),
   changelog: z.string(),
   ignore_patterns: z.array(z.string()).default([]),
+  optional_groups: z.array(OptionalGroupSchema).default([]),
 });
 
 export type Manifest = z.infer<typeof ManifestSchema>;
@@ -148,11 +168,22 @@ export const FabricLoaderSchema = z.object({
 
 export type FabricLoader = z.infer<typeof FabricLoaderSchema>;
 
+/**
+ * Loader Version Schema (any mod loader)
+ */
+export const LoaderVersionSchema = z.object({
+  loader: ModLoaderSchema,
+  version: z.string().min(1),
+  stable: z.boolean(),
+});
+
+export type LoaderVersion = z.infer<typeof LoaderVersionSchema>;
+
 /**
  * Install Progress Schema
  */
 export const InstallProgressSchema = z.object({
-  step: z.enum(['version_meta', 'fabric', 'client', 'libraries', 'assets', 'complete']),
+  step: z.enum(['version_meta', 'loader', 'client', 'verifying', 'libraries', 'assets', 'natives', 'complete']),
   current: z.number().int().min(0),
   total: z.number().int().min(0),
   current_bytes: z.number().int().min(0),
@@ -185,6 +216,8 @@ export const LauncherUpdateInfoSchema = z.object({
   mandatory: z.boolean(),
   download_url: z.string().url(),
   sha256: z.string().regex(SHA256_REGEX, 'Invalid SHA256 hash'),
+  file_type: z.enum(['executable', 'installer']).optional(),
+  signature: z.string().nullable().optional(),
 });
 
 export type LauncherUpdateInfo = z.infer<typeof LauncherUpdateInfoSchema>;
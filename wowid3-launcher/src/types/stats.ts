@@ -14,6 +14,8 @@ export interface PlayerStats {
   dimensions_visited: string[];
   biomes_visited: string[];
   playtime_seconds: number;
+  distance_traveled: number;
+  advancements_completed: number;
   total_blocks_broken: number;
   total_blocks_placed: number;
   total_mobs_killed: number;
@@ -24,3 +26,45 @@ export interface PlayerStats {
   last_updated: number;
 }
 
+
+export interface PlayerSession {
+  joined_at: number;
+  left_at: number | null;
+  duration_secs: number;
+}
+
+export interface PlayerSessions {
+  uuid: string;
+  online: boolean;
+  total_playtime_secs: number;
+  playtime_last_7_days_secs: number;
+  sessions: PlayerSession[];
+}
+
+export type LeaderboardMetric = 'deaths' | 'mob_kills' | 'blocks_mined' | 'distance_traveled';
+
+export type LeaderboardWindow = '7d' | '30d' | 'all';
+
+export interface LeaderboardEntry {
+  rank: number;
+  uuid: string;
+  name: string | null;
+  value: number;
+}
+
+export interface Leaderboard {
+  metric: LeaderboardMetric;
+  window: LeaderboardWindow;
+  entries: LeaderboardEntry[];
+}
+
+export interface PlayerAdvancement {
+  advancement_id: string;
+  title: string | null;
+  completed_at: number;
+}
+
+export interface PlayerAdvancements {
+  uuid: string;
+  advancements: PlayerAdvancement[];
+}
@@ -3,6 +3,8 @@
  * Provides type safety for event listeners and emitters
  */
 
+import type { DownloadProgressV2Event } from './tauri';
+
 /**
  * Download progress event payload
  */
@@ -128,11 +130,29 @@ export interface ToastPayload {
   duration?: number;
 }
 
+/**
+ * Release or launcher publish pushed by the modpack server
+ */
+export interface ServerEventPayload {
+  kind: 'release' | 'launcher';
+  /** Missing when the launcher should re-check everything */
+  version: string | null;
+  deleted: boolean;
+}
+
+/**
+ * Server event stream connection state
+ */
+export interface ServerEventsStatusPayload {
+  connected: boolean;
+}
+
 /**
  * Map of event names to their payload types
  */
 export interface TauriEventMap {
   'download-progress': DownloadProgressPayload;
+  'download-progress-v2': DownloadProgressV2Event;
   'install-progress': InstallProgressPayload;
   'verification-progress': VerificationProgressPayload;
   'log-line': LogLinePayload;
@@ -144,6 +164,8 @@ export interface TauriEventMap {
   'file-verification': FileVerificationPayload;
   'error': ErrorPayload;
   'toast': ToastPayload;
+  'server-event': ServerEventPayload;
+  'server-events-status': ServerEventsStatusPayload;
 }
 
 /**
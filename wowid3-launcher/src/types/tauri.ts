@@ -23,7 +23,8 @@ export interface LaunchConfig {
  */
 export interface InstallConfig {
   game_version: string;
-  fabric_version?: string;
+  loader?: 'fabric' | 'quilt' | 'neoforge';
+  loader_version?: string;
   game_dir: string;
 }
 
@@ -38,6 +39,33 @@ export interface DownloadProgressEvent {
   total_bytes: Bytes;
 }
 
+/**
+ * Byte progress of one file in a download batch
+ */
+export interface FileProgress {
+  url: string;
+  bytes_downloaded: Bytes;
+  total_bytes: Bytes;
+}
+
+/**
+ * `download-progress-v2` payload: sent while files stream, not only when they finish
+ */
+export interface DownloadProgressV2Event {
+  files_completed: number;
+  total_files: number;
+  bytes_downloaded: Bytes;
+  total_bytes: Bytes;
+  active_files: FileProgress[];
+  /** Transfer rate over the last few seconds */
+  speed_bytes_per_sec: number;
+  average_bytes_per_sec: number;
+  /** Null until anything has been transferred */
+  eta_secs: number | null;
+  /** Set when a file just finished */
+  completed_file: string | null;
+}
+
 /**
  * Minecraft Profile from Rust
  */
@@ -56,15 +84,24 @@ export interface MinecraftProfileRaw {
 export interface ManifestRaw {
   version: string;
   minecraft_version: string;
+  loader?: 'fabric' | 'quilt' | 'neoforge';
   fabric_loader: string;
   files: Array<{
     path: string;
     url: string;
     sha256: string;
     size: number;
+    mirrors?: string[];
   }>;
   changelog: string;
   ignore_patterns?: string[];
+  optional_groups?: Array<{
+    id: string;
+    name: string;
+    description?: string;
+    default_enabled?: boolean;
+    files: string[];
+  }>;
 }
 
 /**
@@ -108,7 +145,7 @@ export interface TrackerStateRaw {
  * Install Progress Event from Rust
  */
 export interface InstallProgressEvent {
-  step: 'version_meta' | 'fabric' | 'client' | 'libraries' | 'assets' | 'complete';
+  step: 'version_meta' | 'loader' | 'client' | 'verifying' | 'libraries' | 'assets' | 'natives' | 'complete';
   current: number;
   total: number;
   current_bytes: Bytes;
@@ -137,6 +174,10 @@ export interface LauncherUpdateInfoRaw {
   mandatory: boolean;
   download_url: string;
   sha256: string;
+  /** "executable" or "installer" */
+  file_type: string;
+  /** Base64 Ed25519 signature, checked before the update is applied */
+  signature: string | null;
 }
 
 /**
@@ -50,6 +50,60 @@ export enum LauncherErrorCode {
   UNKNOWN = 9999,
 }
 
+/** `kind` of the error a Tauri command fails with (src-tauri/src/modules/error.rs) */
+export type CommandErrorKind =
+  | 'network'
+  | 'not_signed_in'
+  | 'auth_unavailable'
+  | 'disk_full'
+  | 'permission_denied'
+  | 'not_found'
+  | 'cancelled'
+  | 'other';
+
+const COMMAND_ERROR_CODES: Partial<Record<CommandErrorKind, LauncherErrorCode>> = {
+  network: LauncherErrorCode.NETWORK_OFFLINE,
+  not_signed_in: LauncherErrorCode.AUTH_TOKEN_EXPIRED,
+  auth_unavailable: LauncherErrorCode.AUTH_NETWORK_ERROR,
+  disk_full: LauncherErrorCode.FS_DISK_FULL,
+  permission_denied: LauncherErrorCode.FS_PERMISSION_DENIED,
+  not_found: LauncherErrorCode.FS_FILE_NOT_FOUND,
+};
+
+/**
+ * Rejection of a Tauri command, as thrown by `invoke` from utils/invoke
+ *
+ * Converts to a string as its message, so `${err}` reads as it did when
+ * commands failed with plain strings.
+ */
+export class CommandError extends Error {
+  public readonly kind: CommandErrorKind;
+  public readonly retryable: boolean;
+  public readonly context: string[];
+
+  constructor(payload: { kind: CommandErrorKind; message: string; retryable: boolean; context?: string[] }) {
+    super(payload.message);
+    this.name = 'CommandError';
+    this.kind = payload.kind;
+    this.retryable = payload.retryable;
+    this.context = payload.context ?? [];
+  }
+
+  public toString(): string {
+    return this.message;
+  }
+
+  /** Whether `value` is the `{ kind, message, retryable, context }` object a command rejects with */
+  public static isPayload(value: unknown): value is ConstructorParameters<typeof CommandError>[0] {
+    return (
+      typeof value === 'object' &&
+      value !== null &&
+      typeof (value as { kind?: unknown }).kind === 'string' &&
+      typeof (value as { message?: unknown }).message === 'string'
+    );
+  }
+}
+
 export class LauncherError extends Error {
   public readonly code: LauncherErrorCode;
   public readonly recoverable: boolean;
@@ -173,6 +227,16 @@ export class LauncherError extends Error {
       return error;
     }
 
+    // The command says what went wrong; keep the caller's code when it doesn't know better
+    if (error instanceof CommandError) {
+      return new LauncherError(COMMAND_ERROR_CODES[error.kind] ?? defaultCode, error.message, {
+        retryable: error.retryable,
+        technicalDetails: error.context.join('\n') || undefined,
+        context: { kind: error.kind },
+        cause: error,
+      });
+    }
+
     if (error instanceof Error) {
       return new LauncherError(defaultCode, error.message, {
         cause: error,
@@ -0,0 +1,14 @@
+import { invoke as tauriInvoke } from '@tauri-apps/api/core';
+import { CommandError } from './errors';
+
+/**
+ * `invoke` from @tauri-apps/api/core, rejecting with a CommandError when the
+ * command fails, so callers can tell a network failure from a full disk
+ */
+export async function invoke<T>(...args: Parameters<typeof tauriInvoke>): Promise<T> {
+  try {
+    return await tauriInvoke<T>(...args);
+  } catch (error) {
+    throw CommandError.isPayload(error) ? new CommandError(error) : error;
+  }
+}
@@ -187,7 +187,7 @@ sleep 2
 section "Verifying Deployment"
 
 log_info "Checking backend health..."
-if ssh "$REMOTE_HOST" "curl -s http://127.0.0.1:5566/health | jq -e '.status == \"ok\"' >/dev/null 2>&1"; then
+if ssh "$REMOTE_HOST" "curl -s http://127.0.0.1:5566/health/ready | jq -e '.status == \"ok\"' >/dev/null 2>&1"; then
     log_success "Backend health check passed"
 else
     log_error "Backend health check failed"
@@ -1,24 +1,42 @@
-use crate::config::Config;
+use crate::cache::CacheEvent;
+use crate::config::{Config, LiveSettings};
+use crate::database::{self, Database};
 use crate::middleware::AdminToken;
 use crate::models::{
-    AdminError, BlacklistResponse, CreateReleaseRequest, DeleteReleaseResponse, DraftFile,
-    DraftRelease, LoginRequest, LoginResponse, Manifest, ManifestFile, ReleaseInfo,
-    UpdateBlacklistRequest, UploadResponse,
+    AdminError, AdminRole, AssetUploadResponse, BlacklistPreviewRequest, BlacklistResponse, CreateReleaseRequest, DeleteReleaseResponse, DraftFile,
+    DraftRelease, LoginRequest, LoginResponse, LogoutRequest, Manifest, ManifestFile, OptionalGroup,
+    ModInfo, RefreshTokenRequest, ReleaseChannel, ReleaseInfo, ReleaseTagsResponse, RetentionQuery, SearchQuery, UpdateBlacklistRequest,
+    UpdateReleaseTagsRequest, UploadResponse, CreateUploadSessionRequest, UploadSession,
     manifest::{LauncherFile, LauncherVersion},
 };
+use crate::services::jwt::{JwtManager, TokenType};
+use crate::services::jobs::JobQueue;
+use crate::services::notifications::Notifier;
+use crate::services::retention::{self, RetentionPlan};
+use crate::services::search::{self, SearchHit, SearchResults};
+use crate::services::signing::{self, Signer};
+use crate::services::tasks::{TaskHandle, TaskRegistry};
 use crate::storage;
+use crate::storage::blacklist::{BlacklistPreview, BlacklistRevision};
+use crate::storage::cms::{CmsAsset, CmsConfig};
+use crate::storage::uploads::UploadsOverview;
+use crate::storage::compatibility::ModCompatibilityList;
+use crate::storage::playlist::{AudioPlaylist, PlaylistTrack};
 use crate::utils;
 use axum::{
+    body::Body,
     extract::{multipart::Multipart, Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::IntoResponse,
     Extension, Json,
 };
 use chrono::Utc;
+use futures_util::StreamExt;
 use serde_json::json;
 use sha2::Digest;
+use std::collections::HashSet;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock};
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use uuid::Uuid;
@@ -27,8 +45,30 @@ use zip::ZipArchive;
 #[derive(Clone)]
 pub struct AdminState {
     pub config: Arc<Config>,
-    pub admin_password: Arc<String>,
+    /// Settings that change on reload: base URL, admin password, CORS origin, cache limits
+    pub settings: LiveSettings,
     pub cache: crate::cache::CacheManager,
+    pub jwt: Arc<JwtManager>,
+    pub db: Database,
+    pub tasks: TaskRegistry,
+    pub jobs: JobQueue,
+    pub storage: Arc<dyn storage::backend::StorageBackend>,
+    pub tracker: Arc<tokio::sync::RwLock<crate::models::TrackerState>>,
+    pub notifier: Notifier,
+    /// Signs uploaded launcher binaries, when a signing key is configured
+    pub signer: Option<Arc<Signer>>,
+}
+
+/// Reject the request unless the token's role grants `required`
+pub fn require_role(token: &AdminToken, required: AdminRole) -> Result<(), AppError> {
+    if token.role().allows(required) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(format!(
+            "This action requires the {} role",
+            required.as_str()
+        )))
+    }
 }
 
 /// Extract a zip file to the specified output directory
@@ -89,30 +129,181 @@ async fn extract_zip(zip_path: &PathBuf, output_dir: &PathBuf) -> Result<Vec<(St
 }
 
 /// POST /api/admin/login - Authenticate and get token
+///
+/// With a username, checks the `admin_users` table. Without one, the shared
+/// ADMIN_PASSWORD grants superadmin only until the first admin account is created.
 pub async fn login(
     State(state): State<AdminState>,
     Json(request): Json<LoginRequest>,
 ) -> Result<Json<LoginResponse>, AppError> {
-    if request.password == *state.admin_password {
-        // Simple token is the password hash (in production, use proper JWT)
-        let mut hasher = sha2::Sha256::new();
-        hasher.update(request.password.as_bytes());
-        let token = format!("{:x}", hasher.finalize());
-        Ok(Json(LoginResponse {
-            token,
-            message: "Login successful".to_string(),
-        }))
-    } else {
-        Err(AppError::Unauthorized("Invalid password".to_string()))
+    let (subject, role) = match request.username.as_deref() {
+        Some(username) => {
+            let (user, hash) = database::admin_users::get_user_with_hash(&state.db.conn, username)
+                .await?
+                .ok_or_else(|| AppError::Unauthorized("Invalid username or password".to_string()))?;
+
+            if !crate::services::password::verify_password(&request.password, &hash) {
+                return Err(AppError::Unauthorized("Invalid username or password".to_string()));
+            }
+            if user.disabled {
+                return Err(AppError::Unauthorized("Account is disabled".to_string()));
+            }
+
+            (user.username, user.role)
+        }
+        None => {
+            if database::admin_users::count_users(&state.db.conn).await? > 0 {
+                return Err(AppError::Unauthorized("Username is required".to_string()));
+            }
+            if request.password != state.settings.get().admin_password {
+                return Err(AppError::Unauthorized("Invalid password".to_string()));
+            }
+
+            (database::admin_users::ADMIN_SUBJECT.to_string(), AdminRole::Superadmin)
+        }
+    };
+
+    let pair = state.jwt.issue_pair(&subject, role)?;
+    tracing::info!("Admin login successful: {} ({})", subject, role.as_str());
+
+    Ok(Json(LoginResponse {
+        token: pair.access_token,
+        role,
+        refresh_token: pair.refresh_token,
+        expires_in: pair.expires_in,
+        message: "Login successful".to_string(),
+    }))
+}
+
+/// POST /api/admin/refresh - Exchange a refresh token for a new token pair
+///
+/// The presented refresh token is revoked (rotation), so each one works only once.
+/// The role is re-read so demotions and disabled accounts take effect on refresh.
+pub async fn refresh_token(
+    State(state): State<AdminState>,
+    Json(request): Json<RefreshTokenRequest>,
+) -> Result<Json<LoginResponse>, AppError> {
+    let claims = state
+        .jwt
+        .verify(&request.refresh_token, TokenType::Refresh)
+        .map_err(|_| AppError::Unauthorized("Invalid or expired refresh token".to_string()))?;
+
+    // Revoking is the check: of two concurrent refreshes only one inserts the jti
+    if !database::auth::revoke_token(&state.db.conn, &claims.jti, claims.exp).await? {
+        return Err(AppError::Unauthorized("Refresh token has been revoked".to_string()));
+    }
+
+    let role = database::admin_users::current_role(&state.db.conn, &claims.sub).await?;
+    let pair = state.jwt.issue_pair(&claims.sub, role)?;
+
+    Ok(Json(LoginResponse {
+        token: pair.access_token,
+        role,
+        refresh_token: pair.refresh_token,
+        expires_in: pair.expires_in,
+        message: "Token refreshed".to_string(),
+    }))
+}
+
+/// POST /api/admin/logout - Revoke the current access token (and optional refresh token)
+pub async fn logout(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    request: Option<Json<LogoutRequest>>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    database::auth::revoke_token(&state.db.conn, &token.claims.jti, token.claims.exp).await?;
+
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    if let Some(refresh) = request.refresh_token {
+        // Only revoke refresh tokens we actually issued; ignore garbage
+        if let Ok(claims) = state.jwt.verify(&refresh, TokenType::Refresh) {
+            database::auth::revoke_token(&state.db.conn, &claims.jti, claims.exp).await?;
+        }
     }
+
+    tracing::info!("Admin {} logged out", token.claims.sub);
+
+    Ok(Json(json!({
+        "message": "Logged out successfully"
+    })))
+}
+
+/// Turn a fully received file into upload responses
+///
+/// Zips are extracted into `upload_dir` and removed; other files are moved to
+/// `upload_dir/<file_name>` if they were received elsewhere.
+async fn finish_upload(
+    upload_dir: &PathBuf,
+    upload_id: &str,
+    file_name: &str,
+    received: &PathBuf,
+    file_size: u64,
+    sha256: String,
+) -> Result<Vec<UploadResponse>, AppError> {
+    if !file_name.to_lowercase().ends_with(".zip") {
+        let file_path = upload_dir.join(file_name);
+        if &file_path != received {
+            if let Some(parent) = file_path.parent() {
+                fs::create_dir_all(parent)
+                    .await
+                    .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create subdirectory: {}", e)))?;
+            }
+            fs::rename(received, &file_path)
+                .await
+                .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to move uploaded file: {}", e)))?;
+        }
+
+        return Ok(vec![UploadResponse {
+            upload_id: upload_id.to_string(),
+            file_name: file_name.to_string(),
+            file_size,
+            sha256,
+            message: "File uploaded successfully".to_string(),
+        }]);
+    }
+
+    // Extract zip file
+    tracing::info!("Extracting zip file: {}", file_name);
+    let extracted_files = extract_zip(received, upload_dir).await?;
+
+    // Delete temp zip file
+    fs::remove_file(received)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to remove temp zip: {}", e)))?;
+
+    // Calculate hashes for extracted files and add to responses
+    let mut responses = Vec::new();
+    for (relative_path, file_size) in extracted_files {
+        let file_path = upload_dir.join(&relative_path);
+        let data = fs::read(&file_path)
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read extracted file: {}", e)))?;
+
+        let mut file_hasher = sha2::Sha256::new();
+        file_hasher.update(&data);
+        let file_sha256 = format!("{:x}", file_hasher.finalize());
+
+        responses.push(UploadResponse {
+            upload_id: upload_id.to_string(),
+            file_name: relative_path,
+            file_size,
+            sha256: file_sha256,
+            message: format!("Extracted from {}", file_name),
+        });
+    }
+
+    tracing::info!("Extracted {} files from {}", responses.len(), file_name);
+    Ok(responses)
 }
 
 /// POST /api/admin/upload - Upload modpack files (with automatic zip extraction)
 pub async fn upload_files(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     mut multipart: Multipart,
 ) -> Result<Json<Vec<UploadResponse>>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     let start = std::time::Instant::now();
     let upload_id = Uuid::new_v4().to_string();
     let upload_dir = state.config.uploads_path().join(&upload_id);
@@ -178,62 +369,341 @@ pub async fn upload_files(
 
         tracing::info!("Uploaded: {} ({} bytes, sha256: {})", file_name, total_bytes, &sha256[..12]);
 
-        if is_zip {
-            // Extract zip file
-            tracing::info!("Extracting zip file: {}", file_name);
-            let extracted_files = extract_zip(&temp_path, &upload_dir).await?;
+        responses.extend(finish_upload(&upload_dir, &upload_id, &file_name, &temp_path, total_bytes, sha256).await?);
+    }
 
-            // Delete temp zip file
-            fs::remove_file(&temp_path)
-                .await
-                .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to remove temp zip: {}", e)))?;
+    let duration = start.elapsed();
+    tracing::info!("upload_files completed in {:?} ({} files, upload_id: {})", duration, responses.len(), upload_id);
 
-            // Calculate hashes for extracted files and add to responses
-            for (relative_path, file_size) in extracted_files {
-                let file_path = upload_dir.join(&relative_path);
-                let data = fs::read(&file_path)
-                    .await
-                    .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read extracted file: {}", e)))?;
-
-                let mut file_hasher = sha2::Sha256::new();
-                file_hasher.update(&data);
-                let file_sha256 = format!("{:x}", file_hasher.finalize());
-
-                responses.push(UploadResponse {
-                    upload_id: upload_id.clone(),
-                    file_name: relative_path,
-                    file_size,
-                    sha256: file_sha256,
-                    message: format!("Extracted from {}", file_name),
-                });
+    Ok(Json(responses))
+}
+
+/// Header carrying the byte offset a chunk starts at
+const UPLOAD_OFFSET_HEADER: &str = "upload-offset";
+
+/// Unfinished chunked uploads are removed after this long
+const UPLOAD_SESSION_TTL_HOURS: i64 = 72;
+
+/// Sessions with a PATCH in flight; a second concurrent PATCH would interleave writes
+fn active_upload_sessions() -> &'static Mutex<HashSet<Uuid>> {
+    static ACTIVE: OnceLock<Mutex<HashSet<Uuid>>> = OnceLock::new();
+    ACTIVE.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Marks a session busy until dropped
+struct SessionLock(Uuid);
+
+impl SessionLock {
+    fn acquire(session_id: Uuid) -> Result<Self, AppError> {
+        let mut active = active_upload_sessions().lock().unwrap_or_else(|e| e.into_inner());
+        if !active.insert(session_id) {
+            return Err(AppError::Conflict("Another chunk is being written to this upload".to_string()));
+        }
+        Ok(Self(session_id))
+    }
+}
+
+impl Drop for SessionLock {
+    fn drop(&mut self) {
+        active_upload_sessions()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.0);
+    }
+}
+
+/// Relative path without traversal, as accepted for uploaded file names
+fn validate_upload_file_name(file_name: &str) -> Result<(), AppError> {
+    if file_name.is_empty()
+        || file_name.starts_with('/')
+        || file_name.contains('\\')
+        || file_name.split('/').any(|part| part == ".." || part.is_empty())
+    {
+        return Err(AppError::BadRequest(format!("Invalid file name: {}", file_name)));
+    }
+    Ok(())
+}
+
+/// Load a session with its offset taken from the bytes actually on disk
+async fn load_upload_session(config: &Config, session_id: Uuid) -> Result<(PathBuf, UploadSession), AppError> {
+    let session_dir = config.upload_sessions_path().join(session_id.to_string());
+    let raw = fs::read(session_dir.join("session.json"))
+        .await
+        .map_err(|_| AppError::NotFound(format!("Upload session {} not found", session_id)))?;
+    let mut session: UploadSession = serde_json::from_slice(&raw)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Corrupt upload session {}: {}", session_id, e)))?;
+    session.offset = fs::metadata(session_dir.join("data"))
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
+    Ok((session_dir, session))
+}
+
+/// Remove sessions that were abandoned before finalize
+async fn sweep_stale_upload_sessions(config: &Config) {
+    let Ok(mut entries) = fs::read_dir(config.upload_sessions_path()).await else {
+        return;
+    };
+    let cutoff = Utc::now() - chrono::Duration::hours(UPLOAD_SESSION_TTL_HOURS);
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let Ok(session_id) = entry.file_name().to_string_lossy().parse::<Uuid>() else {
+            continue;
+        };
+        let stale = match load_upload_session(config, session_id).await {
+            Ok((_, session)) => session.created_at < cutoff,
+            // No readable session.json: possibly one being created right now, so only
+            // give up on the directory once it has sat untouched for the whole TTL
+            Err(_) => match entry.metadata().await.and_then(|m| m.modified()) {
+                Ok(modified) => chrono::DateTime::<Utc>::from(modified) < cutoff,
+                Err(_) => false,
+            },
+        };
+        if stale {
+            tracing::info!("Removing stale upload session {}", session_id);
+            let _ = fs::remove_dir_all(entry.path()).await;
+        }
+    }
+}
+
+/// GET /api/admin/uploads - Upload directories and unfinished chunked uploads, with sizes
+///
+/// Directories untouched for UPLOAD_RETENTION_HOURS are deleted by the upload janitor.
+pub async fn list_uploads(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+) -> Result<Json<UploadsOverview>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let overview = storage::uploads::overview(&state.config)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to list uploads: {}", e)))?;
+    Ok(Json(overview))
+}
+
+/// POST /api/admin/uploads - Start a chunked upload for a large file
+///
+/// Send the bytes with `PATCH /api/admin/uploads/:id` (`Upload-Offset` header),
+/// then `POST /api/admin/uploads/:id/finalize`. An interrupted upload resumes
+/// from the offset reported by `GET /api/admin/uploads/:id`.
+pub async fn create_upload_session(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Json(request): Json<CreateUploadSessionRequest>,
+) -> Result<(StatusCode, Json<UploadSession>), AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+    validate_upload_file_name(&request.file_name)?;
+    if let Some(sha256) = &request.sha256 {
+        if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(AppError::BadRequest("sha256 must be 64 hex characters".to_string()));
+        }
+    }
+
+    sweep_stale_upload_sessions(&state.config).await;
+
+    let session = UploadSession {
+        session_id: Uuid::new_v4(),
+        upload_id: request.upload_id.unwrap_or_else(Uuid::new_v4),
+        file_name: request.file_name,
+        size: request.size,
+        sha256: request.sha256.map(|s| s.to_lowercase()),
+        created_at: Utc::now(),
+        offset: 0,
+    };
+
+    let session_dir = state.config.upload_sessions_path().join(session.session_id.to_string());
+    fs::create_dir_all(&session_dir)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create upload session: {}", e)))?;
+    fs::File::create(session_dir.join("data"))
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create upload session: {}", e)))?;
+    let raw = serde_json::to_vec_pretty(&session)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to serialize upload session: {}", e)))?;
+    fs::write(session_dir.join("session.json"), raw)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to write upload session: {}", e)))?;
+
+    tracing::info!(
+        "Started upload session {} for {} ({} bytes, upload_id: {})",
+        session.session_id, session.file_name, session.size, session.upload_id
+    );
+
+    Ok((StatusCode::CREATED, Json(session)))
+}
+
+/// GET /api/admin/uploads/:id - Upload session status, including the offset to resume from
+pub async fn get_upload_session(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(session_id): Path<Uuid>,
+) -> Result<Json<UploadSession>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+    let (_, session) = load_upload_session(&state.config, session_id).await?;
+    Ok(Json(session))
+}
+
+/// PATCH /api/admin/uploads/:id - Append a chunk starting at the `Upload-Offset` header
+///
+/// Returns the session with its new offset. If the connection drops mid-chunk,
+/// the bytes already written are kept and the next chunk resumes after them.
+pub async fn upload_session_chunk(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(session_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Json<UploadSession>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let offset: u64 = headers
+        .get(UPLOAD_OFFSET_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| AppError::BadRequest("Missing or invalid Upload-Offset header".to_string()))?;
+
+    let _lock = SessionLock::acquire(session_id)?;
+    let (session_dir, mut session) = load_upload_session(&state.config, session_id).await?;
+    if offset != session.offset {
+        return Err(AppError::Conflict(format!(
+            "Upload-Offset {} does not match the received {} bytes",
+            offset, session.offset
+        )));
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .append(true)
+        .open(session_dir.join("data"))
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to open upload data: {}", e)))?;
+
+    let mut stream = body.into_data_stream();
+    let mut result = Ok(());
+    while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                // Client went away; keep what arrived so it can resume
+                result = Err(AppError::BadRequest(format!("Upload interrupted: {}", e)));
+                break;
             }
+        };
+        if session.offset + chunk.len() as u64 > session.size {
+            result = Err(AppError::BadRequest(format!(
+                "Chunk exceeds the declared size of {} bytes",
+                session.size
+            )));
+            break;
+        }
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to write chunk: {}", e)))?;
+        session.offset += chunk.len() as u64;
+    }
 
-            tracing::info!("Extracted {} files from {}", responses.len(), file_name);
-        } else {
-            // Regular file (not a zip)
-            responses.push(UploadResponse {
-                upload_id: upload_id.clone(),
-                file_name,
-                file_size: total_bytes,
-                sha256,
-                message: "File uploaded successfully".to_string(),
-            });
+    file.flush()
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to flush upload data: {}", e)))?;
+    result?;
+
+    Ok(Json(session))
+}
+
+/// POST /api/admin/uploads/:id/finalize - Verify a completed chunked upload and add it to its upload
+///
+/// Returns the same entries as `/api/admin/upload`; zips are extracted.
+pub async fn finalize_upload_session(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(session_id): Path<Uuid>,
+) -> Result<Json<Vec<UploadResponse>>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let _lock = SessionLock::acquire(session_id)?;
+    let (session_dir, session) = load_upload_session(&state.config, session_id).await?;
+    if session.offset != session.size {
+        return Err(AppError::Conflict(format!(
+            "Upload incomplete: received {} of {} bytes",
+            session.offset, session.size
+        )));
+    }
+
+    let data_path = session_dir.join("data");
+    let mut data = fs::File::open(&data_path)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to open upload data: {}", e)))?;
+    let mut hasher = sha2::Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = tokio::io::AsyncReadExt::read(&mut data, &mut buf)
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read upload data: {}", e)))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    drop(data);
+    let sha256 = format!("{:x}", hasher.finalize());
+
+    if let Some(expected) = &session.sha256 {
+        if *expected != sha256 {
+            let _ = fs::remove_dir_all(&session_dir).await;
+            return Err(AppError::BadRequest(format!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                session.file_name, expected, sha256
+            )));
         }
     }
 
-    let duration = start.elapsed();
-    tracing::info!("upload_files completed in {:?} ({} files, upload_id: {})", duration, responses.len(), upload_id);
+    let upload_id = session.upload_id.to_string();
+    let upload_dir = state.config.uploads_path().join(&upload_id);
+    fs::create_dir_all(&upload_dir)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create upload directory: {}", e)))?;
+
+    let responses = finish_upload(&upload_dir, &upload_id, &session.file_name, &data_path, session.size, sha256).await?;
+    let _ = fs::remove_dir_all(&session_dir).await;
+
+    tracing::info!(
+        "Finalized upload session {} ({} files, upload_id: {})",
+        session_id, responses.len(), upload_id
+    );
 
     Ok(Json(responses))
 }
 
+/// DELETE /api/admin/uploads/:id - Abandon a chunked upload
+pub async fn delete_upload_session(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(session_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let _lock = SessionLock::acquire(session_id)?;
+    let (session_dir, _) = load_upload_session(&state.config, session_id).await?;
+    fs::remove_dir_all(&session_dir)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to remove upload session: {}", e)))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// POST /api/admin/releases - Create a new release from uploaded files
+///
+/// Validates the request, then queues the build as a job. Returns `202 Accepted`
+/// with a job id; see `/api/admin/jobs/:id` for the result and
+/// `/ws/admin/tasks/:id` (same id) for live progress.
 pub async fn create_release(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
-    Json(request): Json<CreateReleaseRequest>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let start = std::time::Instant::now();
+    Extension(token): Extension<AdminToken>,
+    Json(mut request): Json<CreateReleaseRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+    OptionalGroup::validate(&request.optional_groups).map_err(AppError::BadRequest)?;
+    request.ignore_overrides = std::mem::take(&mut request.ignore_overrides)
+        .validate()
+        .map_err(AppError::BadRequest)?;
 
     // Get upload directory
     let upload_dir = state.config.uploads_path().join(&request.upload_id);
@@ -246,7 +716,7 @@ pub async fn create_release(
         )));
     }
 
-    // Create release directory
+    // Refuse to overwrite an existing release
     let release_dir = state.config.release_path(&request.version);
     if release_dir.exists() {
         return Err(AppError::BadRequest(format!(
@@ -255,27 +725,71 @@ pub async fn create_release(
         )));
     }
 
-    fs::create_dir_all(&release_dir)
+    let version = request.version.clone();
+    let job_state = state.clone();
+    let job_id = state
+        .jobs
+        .enqueue("create_release", token.subject(), move |task| {
+            build_release(job_state, request, upload_dir, release_dir, task)
+        })
         .await
-        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create release directory: {}", e)))?;
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to queue release creation: {}", e)))?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "message": "Release creation queued",
+            "version": version,
+            "job_id": job_id,
+            "task_id": job_id
+        })),
+    ))
+}
+
+/// Background part of `create_release`: copy, checksum and publish the uploaded files
+async fn build_release(
+    state: AdminState,
+    request: CreateReleaseRequest,
+    upload_dir: PathBuf,
+    release_dir: PathBuf,
+    task: TaskHandle,
+) -> Result<serde_json::Value, AppError> {
+    let start = std::time::Instant::now();
+
+    // Another queued job may have created this version since the request was validated
+    if release_dir.exists() {
+        return Err(AppError::BadRequest(format!(
+            "Release version {} already exists",
+            request.version
+        )));
+    }
+
+    // Built in staging and moved into place once complete, so a crash leaves no half-copied release
+    let staging_dir = storage::publish::prepare_staging(&state.config, &request.version).await?;
 
     // Load blacklist patterns
     let blacklist_patterns = utils::load_blacklist_patterns(&state.config)
         .await
+        .map(|patterns| request.ignore_overrides.apply(&patterns))
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to load blacklist: {}", e)))?;
 
     let glob_set = utils::compile_patterns(&blacklist_patterns)
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to compile blacklist patterns: {}", e)))?;
 
     // Walk uploaded files and create manifest
-    let mut files = Vec::new();
-    let mut total_size = 0u64;
-
-    for entry in walkdir::WalkDir::new(&upload_dir)
+    let entries: Vec<_> = walkdir::WalkDir::new(&upload_dir)
         .into_iter()
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
-    {
+        .collect();
+
+    task.stage("Copying files", Some(entries.len() as u64));
+
+    let settings = state.settings.get();
+    let mut files = Vec::new();
+    let mut total_size = 0u64;
+
+    for (index, entry) in entries.iter().enumerate() {
         let file_path = entry.path();
         let relative_path = file_path
             .strip_prefix(&upload_dir)
@@ -285,21 +799,23 @@ pub async fn create_release(
             .to_string_lossy()
             .replace("\\", "/");
 
+        task.progress(index as u64 + 1, relative_str.as_str());
+
         // Check if file matches blacklist pattern
         if utils::is_blacklisted(&relative_str, &glob_set) {
             tracing::debug!("Skipping blacklisted file: {}", relative_str);
             continue;
         }
 
-        // Copy file to release directory
-        let target_path = release_dir.join(relative_path);
+        // Copy file to the staged release
+        let target_path = staging_dir.join(relative_path);
         if let Some(parent) = target_path.parent() {
             fs::create_dir_all(parent)
                 .await
                 .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create directory: {}", e)))?;
         }
 
-        fs::copy(file_path, &target_path)
+        storage::files::copy_detached(file_path, &target_path)
             .await
             .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to copy file: {}", e)))?;
 
@@ -319,28 +835,57 @@ pub async fn create_release(
             path: relative_str.clone(),
             url: format!(
                 "{}/files/{}/{}",
-                state.config.base_url, request.version, relative_str
+                settings.base_url, request.version, relative_str
             ),
             sha256,
             size: file_size,
+            mirrors: Vec::new(),
         });
     }
 
+    task.stage("Writing manifest", None);
+
     // Create manifest
     let changelog_preview = request.changelog.chars().take(100).collect::<String>();
-    let manifest = Manifest {
+    let mut manifest = Manifest {
         version: request.version.clone(),
         minecraft_version: request.minecraft_version,
+        loader: request.loader,
         fabric_loader: request.fabric_loader,
         files,
         changelog: request.changelog,
         ignore_patterns: blacklist_patterns,
+        channel: request.channel,
+        optional_groups: request.optional_groups,
+        ignore_overrides: request.ignore_overrides,
     };
+    manifest.prune_optional_groups();
 
     // Write manifest
-    storage::manifest::write_manifest(&state.config, &manifest)
+    storage::manifest::write_manifest_to(&staging_dir, &manifest)
         .await
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to write manifest: {}", e)))?;
+    storage::publish::commit_staging(&state.config, &manifest.version).await?;
+
+    // Share unchanged files with earlier releases
+    task.stage("Deduplicating files", None);
+    if let Err(e) = storage::files::deduplicate_release(&state.config, &manifest).await {
+        tracing::warn!("Failed to deduplicate release {}: {}", manifest.version, e);
+    }
+
+    // Players are only pointed at the release once its files are downloadable
+    task.stage("Uploading files", None);
+    state
+        .storage
+        .publish_release(&state.config, &manifest)
+        .await
+        .map_err(|e| {
+            AppError::Internal(anyhow::anyhow!(
+                "Failed to publish release files to {} storage: {}",
+                state.storage.name(),
+                e
+            ))
+        })?;
 
     // Update latest manifest
     storage::manifest::set_latest_manifest(&state.config, &request.version)
@@ -348,8 +893,11 @@ pub async fn create_release(
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to update latest manifest: {}", e)))?;
 
     // Invalidate cache after creating release
-    state.cache.invalidate_manifest("latest").await;
-    state.cache.invalidate_manifest(&format!("version:{}", request.version)).await;
+    state
+        .cache
+        .publish(CacheEvent::ReleaseWritten { version: request.version.clone() })
+        .await;
+    state.notifier.release_published(&manifest);
 
     // Clean up upload directory
     fs::remove_dir_all(&upload_dir)
@@ -360,13 +908,13 @@ pub async fn create_release(
     tracing::info!("create_release completed in {:?} (version: {}, {} files, {} bytes)",
         duration, request.version, manifest.files.len(), total_size);
 
-    Ok(Json(json!({
+    Ok(json!({
         "message": "Release created successfully",
         "version": request.version,
         "file_count": manifest.files.len(),
         "size_bytes": total_size,
         "changelog_preview": changelog_preview
-    })))
+    }))
 }
 
 /// Query parameters for pagination
@@ -376,6 +924,9 @@ pub struct PaginationQuery {
     pub page: usize,
     #[serde(default = "default_limit")]
     pub limit: usize,
+    /// Only include releases carrying this tag
+    #[serde(default)]
+    pub tag: Option<String>,
 }
 
 fn default_page() -> usize { 1 }
@@ -394,15 +945,33 @@ pub struct PaginatedReleaseResponse {
 /// GET /api/admin/releases - List all releases with pagination
 pub async fn list_releases(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     axum::extract::Query(pagination): axum::extract::Query<PaginationQuery>,
 ) -> Result<Json<PaginatedReleaseResponse>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
     let start = std::time::Instant::now();
 
     let mut versions = storage::manifest::list_versions(&state.config)
         .await
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to list versions: {}", e)))?;
 
+    let release_tags = storage::tags::load_release_tags(&state.config)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to load release tags: {}", e)))?;
+
+    // Apply tag filter before pagination
+    if let Some(tag) = pagination.tag.as_deref().filter(|t| !t.trim().is_empty()) {
+        let tag = storage::tags::normalize_tag(tag)
+            .map_err(|e| AppError::BadRequest(e.to_string()))?;
+        versions.retain(|v| {
+            release_tags
+                .get(v)
+                .map(|tags| tags.contains(&tag))
+                .unwrap_or(false)
+        });
+    }
+
     // Sort versions before pagination (newest first)
     versions.sort_by(|a, b| {
         // Try semantic version comparison first
@@ -443,8 +1012,10 @@ pub async fn list_releases(
                 }
 
                 releases.push(ReleaseInfo {
+                    tags: release_tags.get(version).cloned().unwrap_or_default(),
                     version: manifest.version,
                     minecraft_version: manifest.minecraft_version,
+                    channel: manifest.channel,
                     created_at: Utc::now().to_rfc3339(),
                     file_count,
                     size_bytes: total_size,
@@ -471,9 +1042,11 @@ pub async fn list_releases(
 /// DELETE /api/admin/releases/:version - Delete a release
 pub async fn delete_release(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(version): Path<String>,
 ) -> Result<Json<DeleteReleaseResponse>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     let release_dir = state.config.release_path(&version);
 
     // Verify release exists
@@ -484,19 +1057,24 @@ pub async fn delete_release(
         )));
     }
 
-    // Prevent deletion if it's the latest version
-    if let Ok(latest) = storage::manifest::read_latest_manifest(&state.config).await {
-        if latest.version == version {
-            return Err(AppError::BadRequest(
-                "Cannot delete the latest release. Promote another version first.".to_string(),
-            ));
+    // Prevent deletion if it's the latest version of any channel
+    for channel in [ReleaseChannel::Stable, ReleaseChannel::Beta] {
+        if let Ok(latest) = storage::manifest::read_latest_channel_manifest(&state.config, channel).await {
+            if latest.version == version {
+                return Err(AppError::BadRequest(format!(
+                    "Cannot delete the latest {} release. Promote another version first.",
+                    channel.as_str()
+                )));
+            }
         }
     }
 
-    // Delete release directory
-    fs::remove_dir_all(&release_dir)
+    retention::delete_release(&state.config, &state.cache, &version)
         .await
-        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to delete release: {}", e)))?;
+        .map_err(AppError::Internal)?;
+
+    // Free objects only this release used
+    retention::collect_garbage(&state.config, state.storage.as_ref()).await;
 
     Ok(Json(DeleteReleaseResponse {
         message: format!("Release {} deleted successfully", version),
@@ -504,12 +1082,134 @@ pub async fn delete_release(
     }))
 }
 
+/// GET /api/admin/retention - Dry run of the release retention policy
+///
+/// `?keep=N` previews a different number of kept releases than RELEASE_RETENTION_KEEP.
+pub async fn preview_retention(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    axum::extract::Query(query): axum::extract::Query<RetentionQuery>,
+) -> Result<Json<RetentionPlan>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let keep = query.keep.unwrap_or(state.config.release_retention_keep);
+    let plan = retention::build_plan(&state.config, keep)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to plan release retention: {}", e)))?;
+
+    Ok(Json(plan))
+}
+
+/// POST /api/admin/releases/:version/promote - Promote a beta release to the stable channel
+pub async fn promote_release(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(version): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    if !state.config.release_path(&version).exists() {
+        return Err(AppError::NotFound(format!("Release {} not found", version)));
+    }
+
+    let manifest = storage::manifest::promote_release(&state.config, &version)
+        .await
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    state
+        .cache
+        .publish(CacheEvent::ReleaseWritten { version: version.clone() })
+        .await;
+
+    tracing::info!("Admin {} promoted release {} to stable", token.subject(), version);
+
+    Ok(Json(json!({
+        "message": format!("Release {} promoted to stable", version),
+        "version": manifest.version,
+        "channel": manifest.channel
+    })))
+}
+
+/// GET /api/admin/releases/:version/tags - Get tags for a release
+pub async fn get_release_tags(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(version): Path<String>,
+) -> Result<Json<ReleaseTagsResponse>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    if !state.config.release_path(&version).exists() {
+        return Err(AppError::NotFound(format!("Release {} not found", version)));
+    }
+
+    let tags = storage::tags::get_release_tags(&state.config, &version)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to load release tags: {}", e)))?;
+
+    Ok(Json(ReleaseTagsResponse { version, tags }))
+}
+
+/// POST /api/admin/releases/:version/tags - Add tags to a release
+pub async fn add_release_tags(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(version): Path<String>,
+    Json(request): Json<UpdateReleaseTagsRequest>,
+) -> Result<Json<ReleaseTagsResponse>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    if !state.config.release_path(&version).exists() {
+        return Err(AppError::NotFound(format!("Release {} not found", version)));
+    }
+
+    if request.tags.is_empty() {
+        return Err(AppError::BadRequest("At least one tag is required".to_string()));
+    }
+
+    for tag in &request.tags {
+        storage::tags::normalize_tag(tag).map_err(|e| AppError::BadRequest(e.to_string()))?;
+    }
+
+    let tags = storage::tags::add_release_tags(&state.config, &version, &request.tags)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to save release tags: {}", e)))?;
+
+    tracing::info!("Tagged release {} with {:?}", version, request.tags);
+
+    Ok(Json(ReleaseTagsResponse { version, tags }))
+}
+
+/// DELETE /api/admin/releases/:version/tags/:tag - Remove a tag from a release
+pub async fn remove_release_tag(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path((version, tag)): Path<(String, String)>,
+) -> Result<Json<ReleaseTagsResponse>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    if !state.config.release_path(&version).exists() {
+        return Err(AppError::NotFound(format!("Release {} not found", version)));
+    }
+
+    storage::tags::normalize_tag(&tag).map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    let tags = storage::tags::remove_release_tag(&state.config, &version, &tag)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to save release tags: {}", e)))?;
+
+    tracing::info!("Removed tag {} from release {}", tag, version);
+
+    Ok(Json(ReleaseTagsResponse { version, tags }))
+}
+
 /// POST /api/admin/releases/:version/copy-to-draft - Copy a release to a new draft
 pub async fn copy_release_to_draft(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(version): Path<String>,
 ) -> Result<Json<DraftRelease>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     // Read the published release manifest
     let manifest = storage::manifest::read_manifest(&state.config, &version)
         .await
@@ -527,7 +1227,23 @@ pub async fn copy_release_to_draft(
         Some(manifest.minecraft_version.clone()),
         Some(manifest.fabric_loader.clone()),
         Some(manifest.changelog.clone()),
+        Some(manifest.channel),
     ).await?;
+    storage::set_draft_loader(state.config.storage_path(), new_draft.id, manifest.loader).await?;
+    if !manifest.optional_groups.is_empty() {
+        storage::set_draft_optional_groups(
+            state.config.storage_path(),
+            new_draft.id,
+            manifest.optional_groups.clone(),
+        ).await?;
+    }
+    if !manifest.ignore_overrides.is_empty() {
+        storage::set_draft_ignore_overrides(
+            state.config.storage_path(),
+            new_draft.id,
+            manifest.ignore_overrides.clone(),
+        ).await?;
+    }
 
     // Copy files from release to draft
     let release_dir = state.config.release_path(&version);
@@ -568,7 +1284,8 @@ async fn copy_dir_all_recursive(src: &PathBuf, dst: &PathBuf) -> Result<(), AppE
                     .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create parent directory: {}", e)))?;
             }
 
-            fs::copy(path, &dest_path).await
+            // Release files may be links into the object store; never write through them
+            storage::files::copy_detached(path, &dest_path).await
                 .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to copy file: {}", e)))?;
         }
     }
@@ -620,8 +1337,10 @@ async fn scan_directory_files(dir: &PathBuf) -> Result<Vec<DraftFile>, AppError>
 /// GET /api/admin/blacklist - Get current blacklist
 pub async fn get_blacklist(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
 ) -> Result<Json<BlacklistResponse>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
     let blacklist_path = state.config.blacklist_path();
 
     let patterns = if blacklist_path.exists() {
@@ -682,6 +1401,7 @@ pub async fn get_blacklist(
             
             // Launcher internal files
             ".wowid3-manifest-hash".to_string(),
+            ".wowid3-manifest.json".to_string(),
             ".wowid3-version".to_string(),
         ]
     };
@@ -690,45 +1410,396 @@ pub async fn get_blacklist(
 }
 
 /// PUT /api/admin/blacklist - Update blacklist
+///
+/// Every pattern must compile; the saved list is recorded as a new history revision.
 pub async fn update_blacklist(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Json(request): Json<UpdateBlacklistRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
-    let blacklist_path = state.config.blacklist_path();
+    require_role(&token, AdminRole::ReleaseManager)?;
 
-    // Create parent directory if needed
-    if let Some(parent) = blacklist_path.parent() {
-        fs::create_dir_all(parent)
-            .await
-            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create directory: {}", e)))?;
-    }
-
-    // Format patterns with comments
-    let content = format!(
-        "# Blacklist patterns - files matching these patterns are not synced to clients\n# Glob patterns are supported (e.g., journeymap/**, *.txt)\n{}",
-        request
-            .patterns
-            .iter()
-            .map(|p| format!("{}\n", p))
-            .collect::<String>()
-    );
+    let patterns = storage::blacklist::validate_patterns(request.patterns)
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
 
-    fs::write(&blacklist_path, content)
+    storage::blacklist::save_blacklist(&state.config, &patterns)
         .await
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to write blacklist: {}", e)))?;
 
+    state.cache.publish(CacheEvent::BlacklistChanged).await;
+
+    let revision = storage::blacklist::record_revision(&state.config, &patterns, token.subject())
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to record blacklist history: {}", e)))?;
+
+    tracing::info!(
+        "Blacklist revision {} saved by {} ({} patterns)",
+        revision.revision,
+        revision.changed_by,
+        patterns.len()
+    );
+
     Ok(Json(json!({
         "message": "Blacklist updated successfully",
-        "pattern_count": request.patterns.len()
+        "pattern_count": patterns.len(),
+        "revision": revision.revision
     })))
 }
 
+/// POST /api/admin/blacklist/preview - Show which files proposed patterns would exclude
+///
+/// Checks the latest release, or the draft given by `draft_id`. Nothing is saved.
+pub async fn preview_blacklist(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Json(request): Json<BlacklistPreviewRequest>,
+) -> Result<Json<BlacklistPreview>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let patterns = storage::blacklist::validate_patterns(request.patterns)
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    let (source, files) = match request.draft_id {
+        Some(id) => {
+            let draft = storage::read_draft(state.config.storage_path(), id)
+                .await
+                .map_err(|_| AppError::NotFound(format!("Draft {} not found", id)))?;
+            let files = draft.files.into_iter().map(|f| (f.path, f.size)).collect::<Vec<_>>();
+            (format!("draft {}", id), files)
+        }
+        None => {
+            let manifest = storage::read_latest_manifest(&state.config)
+                .await
+                .map_err(|_| AppError::NotFound("No release has been published yet".to_string()))?;
+            let files = manifest.files.into_iter().map(|f| (f.path, f.size)).collect::<Vec<_>>();
+            (format!("release {}", manifest.version), files)
+        }
+    };
+
+    let preview = storage::blacklist::preview(&patterns, &files, source)
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    Ok(Json(preview))
+}
+
+/// GET /api/admin/blacklist/history - Saved blacklist revisions, newest first
+pub async fn get_blacklist_history(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+) -> Result<Json<Vec<BlacklistRevision>>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let mut history = storage::blacklist::load_history(&state.config)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to load blacklist history: {}", e)))?;
+    history.reverse();
+
+    Ok(Json(history))
+}
+
+/// Mod metadata of the jars among `files` (path, sha256) under `dir`
+///
+/// Metadata is cached by hash, so jars shared between releases are only opened once.
+async fn jar_mods(state: &AdminState, dir: PathBuf, files: Vec<(String, String)>) -> Vec<ModInfo> {
+    let mut mods = Vec::new();
+    let mut unread = Vec::new();
+    for (path, sha256) in files.into_iter().filter(|(path, _)| path.ends_with(".jar")) {
+        match state.cache.get_jar_metadata(&sha256) {
+            Some(mod_info) => mods.push(ModInfo { file: Some(path), ..mod_info }),
+            None => unread.push((path, sha256)),
+        }
+    }
+    if unread.is_empty() {
+        return mods;
+    }
+
+    let read = tokio::task::spawn_blocking(move || {
+        unread
+            .into_iter()
+            .filter_map(|(path, sha256)| {
+                let mod_info = crate::services::read_jar_metadata(&dir.join(&path)).ok()?;
+                Some((path, sha256, mod_info))
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .unwrap_or_default();
+
+    for (path, sha256, mod_info) in read {
+        state.cache.put_jar_metadata(sha256, mod_info.clone());
+        mods.push(ModInfo { file: Some(path), ..mod_info });
+    }
+    mods
+}
+
+/// GET /api/admin/search?q= - Find releases and drafts containing a file, mod id or changelog text
+pub async fn search_releases(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    axum::extract::Query(query): axum::extract::Query<SearchQuery>,
+) -> Result<Json<SearchResults>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let q = query.q.trim().to_string();
+    if q.chars().count() < search::MIN_QUERY_LEN {
+        return Err(AppError::BadRequest(format!(
+            "Search terms must be at least {} characters",
+            search::MIN_QUERY_LEN
+        )));
+    }
+
+    let versions = storage::manifest::list_versions(&state.config)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to list versions: {}", e)))?;
+
+    let mut releases = Vec::new();
+    for version in versions {
+        let manifest = match storage::manifest::read_manifest(&state.config, &version).await {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                tracing::warn!("Skipping release {} in search: {}", version, e);
+                continue;
+            }
+        };
+        let files = manifest.files.iter().map(|f| (f.path.clone(), f.sha256.clone())).collect();
+        let mods = jar_mods(&state, state.config.release_path(&version), files).await;
+        let matches = search::find_matches(&q, manifest.files.iter().map(|f| f.path.as_str()), &mods, &manifest.changelog);
+        if !matches.is_empty() {
+            releases.push(SearchHit { version, draft_id: None, matches });
+        }
+    }
+
+    let mut drafts = Vec::new();
+    for draft in storage::list_drafts(state.config.storage_path()).await? {
+        let files = draft.files.iter().map(|f| (f.path.clone(), f.sha256.clone())).collect();
+        let files_dir = storage::get_draft_files_dir(state.config.storage_path(), draft.id);
+        let mods = jar_mods(&state, files_dir, files).await;
+        let matches = search::find_matches(&q, draft.files.iter().map(|f| f.path.as_str()), &mods, &draft.changelog);
+        if !matches.is_empty() {
+            drafts.push(SearchHit { version: draft.version, draft_id: Some(draft.id), matches });
+        }
+    }
+
+    Ok(Json(search::collect_results(q, releases, drafts)))
+}
+
+/// GET /api/admin/mods/compatibility - Get the mod compatibility list
+pub async fn get_compatibility_list(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+) -> Result<Json<ModCompatibilityList>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let list = storage::compatibility::load_compatibility_list(&state.config)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to load mod compatibility list: {}", e)))?;
+
+    Ok(Json(list))
+}
+
+/// PUT /api/admin/mods/compatibility - Replace the mod compatibility list
+pub async fn update_compatibility_list(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Json(request): Json<ModCompatibilityList>,
+) -> Result<Json<ModCompatibilityList>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let list = storage::compatibility::validate_list(request)
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    storage::compatibility::save_compatibility_list(&state.config, &list)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to save mod compatibility list: {}", e)))?;
+
+    tracing::info!("Updated mod compatibility list ({} incompatible mods)", list.incompatible.len());
+
+    Ok(Json(list))
+}
+
+/// GET /api/admin/cms-config - Get the launcher CMS config
+pub async fn get_cms_config_admin(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+) -> Result<Json<CmsConfig>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let cms = storage::cms::load_cms_config(&state.config)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to load CMS config: {}", e)))?;
+
+    Ok(Json(cms))
+}
+
+/// PUT /api/admin/cms-config - Replace the launcher CMS config
+pub async fn update_cms_config(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Json(request): Json<serde_json::Value>,
+) -> Result<Json<CmsConfig>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let cms = storage::cms::parse_update(request)
+        .map_err(|e| AppError::BadRequest(format!("{:#}", e)))?;
+
+    storage::cms::save_cms_config(&state.config, &cms)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to save CMS config: {}", e)))?;
+
+    tracing::info!("Updated launcher CMS config");
+
+    Ok(Json(cms))
+}
+
+/// POST /api/admin/assets - Upload branding images or audio for the launcher
+pub async fn upload_cms_assets(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    mut multipart: Multipart,
+) -> Result<Json<Vec<AssetUploadResponse>>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let mut responses = Vec::new();
+
+    while let Some(mut field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Multipart error: {}", e)))?
+    {
+        let filename = field
+            .file_name()
+            .ok_or_else(|| AppError::BadRequest("Missing file name".to_string()))?
+            .to_string();
+        storage::cms::validate_asset_filename(&filename)
+            .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+        let mut data = Vec::new();
+        while let Some(chunk) = field
+            .chunk()
+            .await
+            .map_err(|e| AppError::BadRequest(format!("Failed to read chunk: {}", e)))?
+        {
+            if data.len() + chunk.len() > storage::cms::MAX_ASSET_BYTES {
+                return Err(AppError::BadRequest(format!("{} is too large", filename)));
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        let asset = storage::cms::save_asset(&state.config, &filename, &data)
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to save asset: {}", e)))?;
+
+        tracing::info!("Uploaded CMS asset: {} ({} bytes)", asset.filename, asset.size);
+
+        storage::playlist::sync_asset(&state.config, &asset.filename)
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to update audio playlist: {}", e)))?;
+
+        responses.push(AssetUploadResponse {
+            url: format!("/api/assets-cms/{}", asset.filename),
+            filename: asset.filename,
+            size: asset.size,
+            content_type: asset.content_type,
+        });
+    }
+
+    if responses.is_empty() {
+        return Err(AppError::BadRequest("No files uploaded".to_string()));
+    }
+
+    Ok(Json(responses))
+}
+
+/// GET /api/admin/assets - List CMS assets
+pub async fn list_cms_assets(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+) -> Result<Json<Vec<CmsAsset>>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let assets = storage::cms::list_assets(&state.config)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to list assets: {}", e)))?;
+
+    Ok(Json(assets))
+}
+
+/// DELETE /api/admin/assets/:filename - Delete a CMS asset
+pub async fn delete_cms_asset(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(filename): Path<String>,
+) -> Result<StatusCode, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    storage::cms::validate_asset_filename(&filename)
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    let deleted = storage::cms::delete_asset(&state.config, &filename)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to delete asset: {}", e)))?;
+    if !deleted {
+        return Err(AppError::NotFound(format!("Asset {} not found", filename)));
+    }
+
+    tracing::info!("Deleted CMS asset: {}", filename);
+
+    storage::playlist::sync_asset(&state.config, &filename)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to update audio playlist: {}", e)))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/admin/assets/playlist - Get the launcher's menu music playlist
+pub async fn get_audio_playlist_admin(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+) -> Result<Json<AudioPlaylist>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let playlist = storage::playlist::load_playlist(&state.config)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to load audio playlist: {}", e)))?;
+
+    Ok(Json(playlist))
+}
+
+/// Body of PUT /api/admin/assets/playlist
+#[derive(Debug, serde::Deserialize)]
+pub struct UpdatePlaylistRequest {
+    /// Tracks in play order; only `filename` and `title` are read
+    pub tracks: Vec<PlaylistTrack>,
+}
+
+/// PUT /api/admin/assets/playlist - Set which uploaded audio assets play, in order
+pub async fn update_audio_playlist(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Json(request): Json<UpdatePlaylistRequest>,
+) -> Result<Json<AudioPlaylist>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let playlist = storage::playlist::build_playlist(&state.config, request.tracks)
+        .await
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    storage::playlist::save_playlist(&state.config, &playlist)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to save audio playlist: {}", e)))?;
+
+    tracing::info!("Updated audio playlist ({} tracks)", playlist.tracks.len());
+
+    Ok(Json(playlist))
+}
+
 /// GET /api/admin/cache/stats - Get cache statistics
 pub async fn get_cache_stats(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
 ) -> Result<Json<crate::cache::CacheStats>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
     let stats = state.cache.get_stats().await;
     Ok(Json(stats))
 }
@@ -736,8 +1807,10 @@ pub async fn get_cache_stats(
 /// POST /api/admin/cache/clear - Clear all caches
 pub async fn clear_cache(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     state.cache.clear_all().await;
     Ok(Json(json!({
         "message": "All caches cleared successfully"
@@ -747,8 +1820,10 @@ pub async fn clear_cache(
 /// POST /api/admin/cache/clear/manifests - Clear manifest cache only
 pub async fn clear_manifest_cache(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     state.cache.clear_manifests().await;
     Ok(Json(json!({
         "message": "Manifest cache cleared successfully"
@@ -758,8 +1833,10 @@ pub async fn clear_manifest_cache(
 /// POST /api/admin/cache/clear/jar - Clear JAR metadata cache only
 pub async fn clear_jar_cache(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     state.cache.clear_jar_metadata().await;
     Ok(Json(json!({
         "message": "JAR metadata cache cleared successfully"
@@ -769,9 +1846,11 @@ pub async fn clear_jar_cache(
 /// POST /api/admin/resources - Upload resource pack files
 pub async fn upload_resource(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     mut multipart: Multipart,
 ) -> Result<Json<Vec<UploadResponse>>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     let start = std::time::Instant::now();
     let resources_dir = state.config.resources_path();
 
@@ -848,9 +1927,11 @@ pub async fn upload_resource(
 /// DELETE /api/admin/resources/:filename - Delete a resource pack
 pub async fn delete_resource(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(filename): Path<String>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     // Prevent directory traversal
     if filename.contains('/') || filename.contains('\\') || filename.contains("..") {
         return Err(AppError::BadRequest("Invalid file name".to_string()));
@@ -884,12 +1965,36 @@ pub async fn delete_resource(
     })))
 }
 
+/// Sign an uploaded launcher file and write its detached signature next to it
+///
+/// Returns `None` without a configured signing key.
+async fn sign_launcher_file(
+    state: &AdminState,
+    path: &std::path::Path,
+    digest: &[u8],
+) -> Result<Option<String>, AppError> {
+    let Some(signer) = &state.signer else {
+        tracing::warn!("No LAUNCHER_SIGNING_KEY configured; {} is published unsigned", path.display());
+        return Ok(None);
+    };
+
+    let signature = signer.sign_digest(digest);
+    let file_name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let sig_path = path.with_file_name(signing::signature_file_name(&file_name));
+    fs::write(&sig_path, &signature)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to write launcher signature: {}", e)))?;
+    Ok(Some(signature))
+}
+
 /// POST /api/admin/launcher - Upload new launcher version
 pub async fn upload_launcher_release(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     mut multipart: Multipart,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     let start = std::time::Instant::now();
     
     let mut version = String::new();
@@ -897,6 +2002,7 @@ pub async fn upload_launcher_release(
     let mut mandatory = true; // Default to mandatory
     let mut file_saved = false;
     let mut file_sha256 = String::new();
+    let mut file_digest = Vec::new();
     let mut file_size = 0u64;
     let file_name = String::from("WOWID3Launcher.exe");
 
@@ -943,7 +2049,9 @@ pub async fn upload_launcher_release(
             }
             
             file.flush().await.map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to flush file: {}", e)))?;
-            file_sha256 = format!("{:x}", hasher.finalize());
+            let digest = hasher.finalize();
+            file_sha256 = format!("{:x}", digest);
+            file_digest = digest.to_vec();
             file_saved = true;
 
             tracing::info!("Uploaded launcher binary: {} ({} bytes, sha256: {})", original_name, file_size, &file_sha256[..12]);
@@ -957,14 +2065,17 @@ pub async fn upload_launcher_release(
         return Err(AppError::BadRequest("Version is required".to_string()));
     }
 
+    let signature = sign_launcher_file(&state, &launcher_dir.join(&file_name), &file_digest).await?;
+
     // Create manifest
     let manifest = crate::models::manifest::LauncherManifest {
         version: version.clone(),
-        url: format!("{}/files/launcher/{}", state.config.base_url, file_name),
+        url: format!("{}/files/launcher/{}", state.settings.get().base_url, file_name),
         sha256: file_sha256,
         size: file_size,
         changelog,
         mandatory,
+        signature: signature.clone(),
     };
 
     // Save manifest (old format for backward compatibility)
@@ -980,9 +2091,10 @@ pub async fn upload_launcher_release(
                 platform: "windows".to_string(),
                 file_type: None,
                 filename: file_name.clone(),
-                url: format!("{}/files/launcher/{}", state.config.base_url, file_name),
+                url: format!("{}/files/launcher/{}", state.settings.get().base_url, file_name),
                 sha256: manifest.sha256.clone(),
                 size: manifest.size,
+                signature: signature.clone(),
             }
         ],
         changelog: manifest.changelog.clone(),
@@ -1013,13 +2125,20 @@ pub async fn upload_launcher_release(
         .await
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to save versions index: {}", e)))?;
 
+    state
+        .cache
+        .publish(CacheEvent::LauncherReleased { version: version.clone() })
+        .await;
+    state.notifier.launcher_published(&launcher_version);
+
     let duration = start.elapsed();
     tracing::info!("upload_launcher_release completed in {:?} (version: {})", duration, version);
 
     Ok(Json(json!({
         "message": "Launcher release uploaded successfully",
         "version": version,
-        "mandatory": mandatory
+        "mandatory": mandatory,
+        "signature": signature
     })))
 }
 
@@ -1027,9 +2146,11 @@ pub async fn upload_launcher_release(
 /// Allows uploading individual platform files to a version (create version if it doesn't exist)
 pub async fn upload_launcher_version_file(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     mut multipart: Multipart,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     let start = std::time::Instant::now();
 
     let mut version = String::new();
@@ -1038,6 +2159,7 @@ pub async fn upload_launcher_version_file(
     let mut platform = String::new();
     let mut file_saved = false;
     let mut file_sha256 = String::new();
+    let mut file_digest = Vec::new();
     let mut file_size = 0u64;
     let mut original_filename = String::new();
 
@@ -1090,7 +2212,9 @@ pub async fn upload_launcher_version_file(
             }
 
             file.flush().await.map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to flush file: {}", e)))?;
-            file_sha256 = format!("{:x}", hasher.finalize());
+            let digest = hasher.finalize();
+            file_sha256 = format!("{:x}", digest);
+            file_digest = digest.to_vec();
             file_saved = true;
 
             tracing::info!("Uploaded launcher file: {} for platform {} ({} bytes, sha256: {})", original_filename, platform, file_size, &file_sha256[..12]);
@@ -1124,14 +2248,18 @@ pub async fn upload_launcher_version_file(
     }
     launcher_version.mandatory = mandatory;
 
+    let file_path = state.config.launcher_version_file_path(&version, &original_filename);
+    let signature = sign_launcher_file(&state, &file_path, &file_digest).await?;
+
     // Add or update file for this platform
     let launcher_file = LauncherFile {
         platform: platform.clone(),
         file_type: None,
         filename: original_filename.clone(),
-        url: format!("{}/files/launcher/versions/{}/{}", state.config.base_url, version, original_filename),
+        url: format!("{}/files/launcher/versions/{}/{}", state.settings.get().base_url, version, original_filename),
         sha256: file_sha256,
         size: file_size,
+        signature: signature.clone(),
     };
 
     // Remove existing file for this platform if present
@@ -1143,6 +2271,11 @@ pub async fn upload_launcher_version_file(
         .await
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to save launcher version: {}", e)))?;
 
+    state
+        .cache
+        .publish(CacheEvent::LauncherReleased { version: version.clone() })
+        .await;
+
     let duration = start.elapsed();
     tracing::info!("upload_launcher_version_file completed in {:?} (version: {}, platform: {})", duration, version, platform);
 
@@ -1151,6 +2284,7 @@ pub async fn upload_launcher_version_file(
         "version": version,
         "platform": platform,
         "filename": original_filename,
+        "signature": signature,
         "platforms": launcher_version.platforms()
     })))
 }
@@ -1158,9 +2292,11 @@ pub async fn upload_launcher_version_file(
 /// DELETE /api/admin/launcher/:version - Delete a launcher version
 pub async fn delete_launcher_version(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(version): Path<String>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     storage::launcher::delete_launcher_version(&state.config, &version)
         .await
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to delete launcher version: {}", e)))?;
@@ -1176,9 +2312,11 @@ pub async fn delete_launcher_version(
 /// POST /api/admin/launcher/releases - Upload new launcher release
 pub async fn create_launcher_release(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     mut multipart: Multipart,
 ) -> Result<Json<LauncherVersion>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     let mut version = String::new();
     let mut changelog = String::new();
     let mut mandatory = false;
@@ -1256,20 +2394,20 @@ pub async fn create_launcher_release(
 
     for (platform, file_type, filename, bytes) in files {
         // Calculate SHA256
-        let mut hasher = sha2::Sha256::new();
-        hasher.update(&bytes);
-        let sha256 = format!("{:x}", hasher.finalize());
+        let digest = sha2::Sha256::digest(&bytes);
+        let sha256 = format!("{:x}", digest);
 
         // Save file
         let file_path = version_dir.join(&filename);
         fs::write(&file_path, &bytes).await.map_err(|e| {
             AppError::Internal(anyhow::anyhow!("Failed to write file: {}", e))
         })?;
+        let signature = sign_launcher_file(&state, &file_path, &digest).await?;
 
         // Generate URL
         let url = format!(
             "{}/files/launcher/{}/{}",
-            state.config.base_url, version, filename
+            state.settings.get().base_url, version, filename
         );
 
         launcher_files.push(LauncherFile {
@@ -1279,6 +2417,7 @@ pub async fn create_launcher_release(
             url,
             sha256,
             size: bytes.len() as u64,
+            signature,
         });
     }
 
@@ -1298,14 +2437,22 @@ pub async fn create_launcher_release(
             AppError::Internal(anyhow::anyhow!("Failed to save launcher version: {}", e))
         })?;
 
+    state
+        .cache
+        .publish(CacheEvent::LauncherReleased { version: version.clone() })
+        .await;
+    state.notifier.launcher_published(&launcher_version);
+
     Ok(Json(launcher_version))
 }
 
 /// GET /api/admin/launcher/releases - List all launcher releases
 pub async fn list_launcher_releases(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
 ) -> Result<Json<Vec<LauncherVersion>>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
     // Load versions index
     let index = storage::launcher::load_launcher_versions_index(&state.config)
         .await
@@ -1324,17 +2471,46 @@ pub async fn list_launcher_releases(
 
 
 // Error handling
+#[derive(Debug)]
 pub enum AppError {
     Internal(anyhow::Error),
     NotFound(String),
     BadRequest(String),
     Unauthorized(String),
     Forbidden(String),
+    Conflict(String),
 }
 
 impl From<anyhow::Error> for AppError {
     fn from(err: anyhow::Error) -> Self {
-        AppError::Internal(err)
+        let err = match err.downcast::<storage::drafts::DraftConflict>() {
+            Ok(conflict) => return AppError::Conflict(conflict.to_string()),
+            Err(err) => err,
+        };
+        match err.downcast::<database::admin_users::InactiveAccount>() {
+            Ok(inactive) => AppError::Unauthorized(inactive.to_string()),
+            Err(err) => AppError::Internal(err),
+        }
+    }
+}
+
+impl From<crate::utils::player::InvalidPlayerUuid> for AppError {
+    fn from(err: crate::utils::player::InvalidPlayerUuid) -> Self {
+        AppError::BadRequest(err.to_string())
+    }
+}
+
+/// Used when reporting background task failures to admins
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppError::Internal(err) => write!(f, "{}", err),
+            AppError::NotFound(msg)
+            | AppError::BadRequest(msg)
+            | AppError::Unauthorized(msg)
+            | AppError::Forbidden(msg)
+            | AppError::Conflict(msg) => write!(f, "{}", msg),
+        }
     }
 }
 
@@ -1349,8 +2525,142 @@ impl IntoResponse for AppError {
             AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
             AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
             AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, msg),
         };
 
         (status, Json(AdminError { error: message })).into_response()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AdminRole, CreateUploadSessionRequest};
+    use crate::services::jwt::Claims;
+
+    async fn test_state(dir: &std::path::Path) -> AdminState {
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "storage_path": dir.join("storage"),
+            "jwt_secret": "test-secret"
+        }))
+        .unwrap();
+        let db = Database::new(dir.join("test.db")).await.unwrap();
+        db.init_schema().await.unwrap();
+        let settings = LiveSettings::new(&config);
+        let tasks = TaskRegistry::new();
+        AdminState {
+            settings: settings.clone(),
+            cache: crate::cache::CacheManager::new(),
+            jwt: Arc::new(JwtManager::from_config(&config)),
+            jobs: JobQueue::start(db.clone(), tasks.clone()).await.unwrap(),
+            db,
+            tasks,
+            storage: storage::backend::from_config(&config).unwrap(),
+            tracker: Default::default(),
+            notifier: Notifier::from_config(&config, settings),
+            signer: None,
+            config: Arc::new(config),
+        }
+    }
+
+    fn release_manager() -> AdminToken {
+        AdminToken {
+            token: String::new(),
+            claims: Claims {
+                sub: "admin".to_string(),
+                jti: Uuid::new_v4().to_string(),
+                iat: 0,
+                exp: i64::MAX,
+                typ: TokenType::Access,
+                role: AdminRole::ReleaseManager,
+            },
+        }
+    }
+
+    async fn start_session(state: &AdminState, size: u64, sha256: Option<String>) -> Uuid {
+        let request = CreateUploadSessionRequest {
+            file_name: "mods/big.jar".to_string(),
+            size,
+            sha256,
+            upload_id: None,
+        };
+        let (status, Json(session)) =
+            create_upload_session(State(state.clone()), Extension(release_manager()), Json(request))
+                .await
+                .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        session.session_id
+    }
+
+    async fn send_chunk(state: &AdminState, session_id: Uuid, offset: u64, data: &'static [u8]) -> Result<u64, AppError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(UPLOAD_OFFSET_HEADER, offset.to_string().parse().unwrap());
+        let Json(session) = upload_session_chunk(
+            State(state.clone()),
+            Extension(release_manager()),
+            Path(session_id),
+            headers,
+            Body::from(data),
+        )
+        .await?;
+        Ok(session.offset)
+    }
+
+    #[tokio::test]
+    async fn test_upload_session_chunks_and_finalize() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = test_state(dir.path()).await;
+        let sha256 = format!("{:x}", sha2::Sha256::digest(b"hello world"));
+        let session_id = start_session(&state, 11, Some(sha256.clone())).await;
+
+        assert_eq!(send_chunk(&state, session_id, 0, b"hello").await.unwrap(), 5);
+        // Out of order, or the same chunk sent twice, doesn't match the received offset
+        assert!(matches!(send_chunk(&state, session_id, 8, b"rld").await, Err(AppError::Conflict(_))));
+        assert!(matches!(send_chunk(&state, session_id, 0, b"hello").await, Err(AppError::Conflict(_))));
+
+        // Finalizing before every byte arrived is refused and keeps the session
+        let incomplete = finalize_upload_session(State(state.clone()), Extension(release_manager()), Path(session_id)).await;
+        assert!(matches!(incomplete, Err(AppError::Conflict(_))));
+
+        // A chunk running past the declared size is rejected
+        assert!(matches!(
+            send_chunk(&state, session_id, 5, b" world and more").await,
+            Err(AppError::BadRequest(_))
+        ));
+        assert_eq!(send_chunk(&state, session_id, 5, b" world").await.unwrap(), 11);
+
+        let Json(responses) = finalize_upload_session(State(state.clone()), Extension(release_manager()), Path(session_id))
+            .await
+            .unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].sha256, sha256);
+        assert!(!state.config.upload_sessions_path().join(session_id.to_string()).exists());
+    }
+
+    #[tokio::test]
+    async fn test_sweep_spares_sessions_being_created() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = test_state(dir.path()).await;
+        let sessions = state.config.upload_sessions_path();
+
+        let expired = start_session(&state, 4, None).await;
+        let session_json = sessions.join(expired.to_string()).join("session.json");
+        let mut session: UploadSession = serde_json::from_slice(&std::fs::read(&session_json).unwrap()).unwrap();
+        session.created_at = Utc::now() - chrono::Duration::hours(UPLOAD_SESSION_TTL_HOURS + 1);
+        std::fs::write(&session_json, serde_json::to_vec(&session).unwrap()).unwrap();
+
+        // No session.json yet: one still being created, and one abandoned long ago
+        let creating = sessions.join(Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&creating).unwrap();
+        let abandoned = sessions.join(Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&abandoned).unwrap();
+        let old = std::time::SystemTime::now() - std::time::Duration::from_secs(UPLOAD_SESSION_TTL_HOURS as u64 * 3600 + 60);
+        std::fs::File::open(&abandoned).unwrap().set_modified(old).unwrap();
+
+        sweep_stale_upload_sessions(&state.config).await;
+
+        assert!(!sessions.join(expired.to_string()).exists());
+        assert!(creating.exists());
+        assert!(!abandoned.exists());
+    }
+}
@@ -0,0 +1,159 @@
+use crate::api::admin::{require_role, AdminState, AppError};
+use crate::database;
+use crate::middleware::AdminToken;
+use crate::models::{AdminRole, AdminUser, CreateAdminUserRequest, UpdateAdminUserRequest};
+use crate::services::password::hash_password;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+
+const MAX_USERNAME_LEN: usize = 32;
+
+fn validate_username(username: &str) -> Result<(), AppError> {
+    let valid = !username.is_empty()
+        && username.len() <= MAX_USERNAME_LEN
+        && username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Username must be 1-{} characters of letters, digits, '-', '_' or '.'",
+            MAX_USERNAME_LEN
+        )))
+    }
+}
+
+/// Refuse changes that would leave no enabled superadmin
+async fn ensure_superadmin_remains(state: &AdminState, target: &AdminUser) -> Result<(), AppError> {
+    if target.role == AdminRole::Superadmin && !target.disabled {
+        let remaining = database::admin_users::count_active_superadmins(&state.db.conn).await?;
+        if remaining <= 1 {
+            return Err(AppError::BadRequest(
+                "Cannot remove the last active superadmin".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// GET /api/admin/users - List admin accounts
+pub async fn list_admin_users(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+) -> Result<Json<Vec<AdminUser>>, AppError> {
+    require_role(&token, AdminRole::Superadmin)?;
+
+    let users = database::admin_users::list_users(&state.db.conn).await?;
+    Ok(Json(users))
+}
+
+/// POST /api/admin/users - Create an admin account
+pub async fn create_admin_user(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Json(request): Json<CreateAdminUserRequest>,
+) -> Result<(StatusCode, Json<AdminUser>), AppError> {
+    require_role(&token, AdminRole::Superadmin)?;
+    validate_username(&request.username)?;
+
+    // The first account ends the shared-password login, so it has to be able to manage the rest
+    if request.role != AdminRole::Superadmin
+        && database::admin_users::count_users(&state.db.conn).await? == 0
+    {
+        return Err(AppError::BadRequest(
+            "The first admin account must be a superadmin".to_string(),
+        ));
+    }
+
+    if database::admin_users::get_user_with_hash(&state.db.conn, &request.username)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::BadRequest(format!(
+            "Admin user {} already exists",
+            request.username
+        )));
+    }
+
+    let hash = hash_password(&request.password).map_err(|e| AppError::BadRequest(e.to_string()))?;
+    let user = database::admin_users::create_user(&state.db.conn, &request.username, &hash, request.role)
+        .await?;
+
+    tracing::info!(
+        "Admin {} created admin user {} ({})",
+        token.subject(),
+        user.username,
+        user.role.as_str()
+    );
+
+    Ok((StatusCode::CREATED, Json(user)))
+}
+
+/// PUT /api/admin/users/:id - Update role, password or disabled flag
+pub async fn update_admin_user(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(id): Path<i64>,
+    Json(request): Json<UpdateAdminUserRequest>,
+) -> Result<Json<AdminUser>, AppError> {
+    require_role(&token, AdminRole::Superadmin)?;
+
+    let existing = database::admin_users::get_user(&state.db.conn, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Admin user {} not found", id)))?;
+
+    let demoting = request.role.is_some_and(|role| role != AdminRole::Superadmin);
+    let disabling = request.disabled == Some(true);
+    if demoting || disabling {
+        ensure_superadmin_remains(&state, &existing).await?;
+    }
+
+    let password_hash = match request.password.as_deref() {
+        Some(password) => {
+            Some(hash_password(password).map_err(|e| AppError::BadRequest(e.to_string()))?)
+        }
+        None => None,
+    };
+
+    let user = database::admin_users::update_user(
+        &state.db.conn,
+        id,
+        password_hash,
+        request.role,
+        request.disabled,
+    )
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Admin user {} not found", id)))?;
+
+    tracing::info!("Admin {} updated admin user {}", token.subject(), user.username);
+
+    Ok(Json(user))
+}
+
+/// DELETE /api/admin/users/:id - Delete an admin account
+pub async fn delete_admin_user(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, AppError> {
+    require_role(&token, AdminRole::Superadmin)?;
+
+    let existing = database::admin_users::get_user(&state.db.conn, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Admin user {} not found", id)))?;
+
+    if existing.username == token.subject() {
+        return Err(AppError::BadRequest("Cannot delete your own account".to_string()));
+    }
+    ensure_superadmin_remains(&state, &existing).await?;
+
+    database::admin_users::delete_user(&state.db.conn, id).await?;
+    tracing::info!("Admin {} deleted admin user {}", token.subject(), existing.username);
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,84 @@
+use crate::api::admin::{require_role, AdminState, AppError};
+use crate::database::{self, audit::{AuditEntry, AuditFilter}};
+use crate::middleware::AdminToken;
+use crate::models::AdminRole;
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+/// Query parameters for GET /api/admin/audit
+#[derive(Debug, Deserialize)]
+pub struct AuditQuery {
+    #[serde(default = "default_page")]
+    pub page: usize,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    /// Only entries by this admin
+    #[serde(default)]
+    pub actor: Option<String>,
+    /// Only entries with this HTTP method (POST, PUT, DELETE, ...)
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Only entries whose route or path starts with this prefix
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Unix timestamp lower bound (inclusive)
+    #[serde(default)]
+    pub since: Option<i64>,
+    /// Unix timestamp upper bound (inclusive)
+    #[serde(default)]
+    pub until: Option<i64>,
+    /// Only entries whose response was an error (status >= 400)
+    #[serde(default)]
+    pub failed: bool,
+}
+
+fn default_page() -> usize { 1 }
+fn default_limit() -> usize { 50 }
+
+/// Paginated audit log response
+#[derive(Serialize)]
+pub struct PaginatedAuditResponse {
+    pub entries: Vec<AuditEntry>,
+    pub page: usize,
+    pub limit: usize,
+    pub total: usize,
+    pub total_pages: usize,
+}
+
+/// GET /api/admin/audit - List recorded admin mutations, newest first
+pub async fn list_audit_log(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Query(query): Query<AuditQuery>,
+) -> Result<Json<PaginatedAuditResponse>, AppError> {
+    require_role(&token, AdminRole::Superadmin)?;
+
+    let limit = query.limit.clamp(1, 200);
+    let page = query.page.max(1); // Min page 1
+
+    let filter = AuditFilter {
+        actor: query.actor.filter(|s| !s.is_empty()),
+        method: query.method.filter(|s| !s.is_empty()),
+        path_prefix: query.path.filter(|s| !s.is_empty()),
+        since: query.since,
+        until: query.until,
+        failed_only: query.failed,
+    };
+
+    let (entries, total) = database::audit::list(&state.db.conn, filter, limit, (page - 1) * limit)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read audit log: {}", e)))?;
+
+    let total_pages = total.div_ceil(limit);
+
+    Ok(Json(PaginatedAuditResponse {
+        entries,
+        page,
+        limit,
+        total,
+        total_pages,
+    }))
+}
@@ -1,7 +1,15 @@
+use crate::api::admin::{require_role, AppError};
+use crate::cache::tiles::{TileCache, TileCacheStats};
+use crate::middleware::AdminToken;
+use crate::models::AdminRole;
+use crate::utils::compression::{self, Encoding};
+use crate::utils::http::etag_matches;
+use crate::utils::safe_path;
 use axum::{
     extract::{Path, State},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Response},
+    Extension, Json,
 };
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -9,17 +17,20 @@ use tokio::fs;
 use tracing::error;
 
 /// BlueMap base path - mounted Minecraft server filesystem
-const BLUEMAP_BASE_PATH: &str = "/mnt/wowid3/bluemap/web";
+pub const BLUEMAP_BASE_PATH: &str = "/mnt/wowid3/bluemap/web";
 
 #[derive(Clone)]
 pub struct BlueMapState {
     pub base_path: PathBuf,
+    /// Local tile cache; tiles are read from `base_path` on every request when unset
+    pub tiles: Option<Arc<TileCache>>,
 }
 
 impl BlueMapState {
-    pub fn new() -> Self {
+    pub fn new(tiles: Option<Arc<TileCache>>) -> Self {
         Self {
             base_path: PathBuf::from(BLUEMAP_BASE_PATH),
+            tiles,
         }
     }
 }
@@ -36,6 +47,7 @@ pub async fn get_global_settings(
 pub async fn serve_webapp_file(
     State(state): State<Arc<BlueMapState>>,
     Path(path): Path<String>,
+    headers: HeaderMap,
 ) -> Result<Response, StatusCode> {
     // Handle map data textures.json specially - serve the .gz file with proper encoding
     if path.ends_with("textures.json") && path.starts_with("maps/") {
@@ -43,24 +55,18 @@ pub async fn serve_webapp_file(
         if parts.len() >= 3 && parts[0] == "maps" {
             let map_id = parts[1].to_string();
             // Call the textures handler directly
-            return get_map_textures(State(state), Path(map_id)).await;
+            return get_map_textures(State(state), Path(map_id), headers).await;
         }
     }
 
     // Security: prevent path traversal
-    if path.contains("..") {
+    if safe_path::relative_path(&path).is_err() {
         error!("Path traversal attempt detected: {}", path);
         return Err(StatusCode::FORBIDDEN);
     }
 
     let file_path = state.base_path.join(&path);
 
-    // Ensure the path is within the base path
-    if !file_path.starts_with(&state.base_path) {
-        error!("Path outside base directory: {:?}", file_path);
-        return Err(StatusCode::FORBIDDEN);
-    }
-
     // Determine content type from extension
     let content_type = match file_path.extension().and_then(|e| e.to_str()) {
         Some("html") => "text/html",
@@ -79,13 +85,27 @@ pub async fn serve_webapp_file(
     serve_file_internal(file_path, content_type).await
 }
 
+/// GET /api/admin/bluemap/cache - Tile cache statistics
+pub async fn get_tile_cache_stats(
+    State(state): State<Arc<BlueMapState>>,
+    Extension(token): Extension<AdminToken>,
+) -> Result<Json<TileCacheStats>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let tiles = state
+        .tiles
+        .as_ref()
+        .ok_or_else(|| AppError::NotFound("BlueMap tile cache is disabled".to_string()))?;
+    Ok(Json(tiles.stats()))
+}
+
 /// Serve map-specific settings
 pub async fn get_map_settings(
     State(state): State<Arc<BlueMapState>>,
     Path(map_id): Path<String>,
 ) -> Result<Response, StatusCode> {
     // Security: validate map_id
-    if map_id.contains("..") || map_id.contains('/') {
+    if safe_path::segment(&map_id).is_err() {
         error!("Invalid map_id: {}", map_id);
         return Err(StatusCode::FORBIDDEN);
     }
@@ -95,18 +115,31 @@ pub async fn get_map_settings(
 }
 
 /// Serve map textures (handles both .json and .json.gz requests)
+///
+/// BlueMap writes `textures.json.gz`; a `textures.json.zst` next to it is
+/// preferred for clients that accept zstd. Clients accepting neither get the
+/// decompressed JSON.
 pub async fn get_map_textures(
     State(state): State<Arc<BlueMapState>>,
     Path(map_id): Path<String>,
+    headers: HeaderMap,
 ) -> Result<Response, StatusCode> {
     // Security: validate map_id
-    if map_id.contains("..") || map_id.contains('/') {
+    if safe_path::segment(&map_id).is_err() {
         error!("Invalid map_id: {}", map_id);
         return Err(StatusCode::FORBIDDEN);
     }
 
-    // The actual file is textures.json.gz
-    let file_path = state.base_path.join("maps").join(&map_id).join("textures.json.gz");
+    let map_path = state.base_path.join("maps").join(&map_id);
+    let gz_path = map_path.join("textures.json.gz");
+    let zst_path = map_path.join("textures.json.zst");
+
+    let mut available = vec![Encoding::Gzip];
+    if zst_path.exists() {
+        available.push(Encoding::Zstd);
+    }
+    let encoding = compression::negotiate(&headers, &available);
+    let file_path = if encoding == Encoding::Zstd { zst_path } else { gz_path };
 
     // Check if file exists
     if !file_path.exists() {
@@ -120,28 +153,30 @@ pub async fn get_map_textures(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    // Serve the gzipped file with proper headers
-    // BlueMap can handle gzip-encoded responses
-    Ok((
-        StatusCode::OK,
-        [
-            (header::CONTENT_TYPE, "application/json"),
-            (header::CONTENT_ENCODING, "gzip"),
-            (header::CACHE_CONTROL, "public, max-age=300"),
-            (header::ACCESS_CONTROL_ALLOW_ORIGIN, "*"),
-        ],
-        contents,
-    )
-        .into_response())
+    let contents = if encoding == Encoding::Identity {
+        compression::gunzip(&contents).map_err(|e| {
+            error!("Failed to decompress {:?}: {}", file_path, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+    } else {
+        contents
+    };
+
+    let mut response = compression::encoded_response(contents.into(), encoding, "application/json");
+    let response_headers = response.headers_mut();
+    response_headers.insert(header::CACHE_CONTROL, "public, max-age=300".parse().unwrap());
+    response_headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*".parse().unwrap());
+    Ok(response)
 }
 
 /// Serve map textures with .gz extension (legacy support)
 pub async fn get_map_textures_gz(
     State(state): State<Arc<BlueMapState>>,
     Path(map_id): Path<String>,
+    headers: HeaderMap,
 ) -> Result<Response, StatusCode> {
     // Just call the main textures handler
-    get_map_textures(State(state), Path(map_id)).await
+    get_map_textures(State(state), Path(map_id), headers).await
 }
 
 /// Serve live markers
@@ -150,7 +185,7 @@ pub async fn get_live_markers(
     Path(map_id): Path<String>,
 ) -> Result<Response, StatusCode> {
     // Security: validate map_id
-    if map_id.contains("..") || map_id.contains('/') {
+    if safe_path::segment(&map_id).is_err() {
         error!("Invalid map_id: {}", map_id);
         return Err(StatusCode::FORBIDDEN);
     }
@@ -165,7 +200,7 @@ pub async fn get_live_players(
     Path(map_id): Path<String>,
 ) -> Result<Response, StatusCode> {
     // Security: validate map_id
-    if map_id.contains("..") || map_id.contains('/') {
+    if safe_path::segment(&map_id).is_err() {
         error!("Invalid map_id: {}", map_id);
         return Err(StatusCode::FORBIDDEN);
     }
@@ -178,26 +213,20 @@ pub async fn get_live_players(
 pub async fn get_map_tile(
     State(state): State<Arc<BlueMapState>>,
     Path((map_id, tile_path)): Path<(String, String)>,
+    headers: HeaderMap,
 ) -> Result<Response, StatusCode> {
     // Security: validate map_id and tile_path
-    if map_id.contains("..") || map_id.contains('/') {
+    if safe_path::segment(&map_id).is_err() {
         error!("Invalid map_id: {}", map_id);
         return Err(StatusCode::FORBIDDEN);
     }
-    if tile_path.contains("..") {
+    if safe_path::relative_path(&tile_path).is_err() {
         error!("Path traversal attempt in tile_path: {}", tile_path);
         return Err(StatusCode::FORBIDDEN);
     }
 
     let file_path = state.base_path.join("maps").join(&map_id).join("tiles").join(&tile_path);
 
-    // Ensure the path is within the expected directory
-    let expected_base = state.base_path.join("maps").join(&map_id).join("tiles");
-    if !file_path.starts_with(&expected_base) {
-        error!("Tile path outside expected directory: {:?}", file_path);
-        return Err(StatusCode::FORBIDDEN);
-    }
-
     // Determine content type from requested file extension
     let content_type = if tile_path.ends_with(".json") {
         "application/json"
@@ -209,15 +238,17 @@ pub async fn get_map_tile(
         "application/octet-stream"
     };
 
+    if let Some(tiles) = &state.tiles {
+        return serve_cached_tile(tiles, &map_id, &tile_path, file_path, content_type, &headers).await;
+    }
+
     // Check if requested file exists
     if file_path.exists() {
         // Serve the file directly
         serve_file_internal(file_path, content_type).await
     } else {
         // Try gzipped version (.gz extension)
-        let gz_path = file_path.with_extension(
-            format!("{}.gz", file_path.extension().and_then(|e| e.to_str()).unwrap_or(""))
-        );
+        let gz_path = gzipped_tile_path(&file_path);
 
         if gz_path.exists() {
             // Serve gzipped file with Content-Encoding header
@@ -236,24 +267,17 @@ pub async fn get_map_asset(
     Path((map_id, asset_path)): Path<(String, String)>,
 ) -> Result<Response, StatusCode> {
     // Security: validate map_id and asset_path
-    if map_id.contains("..") || map_id.contains('/') {
+    if safe_path::segment(&map_id).is_err() {
         error!("Invalid map_id: {}", map_id);
         return Err(StatusCode::FORBIDDEN);
     }
-    if asset_path.contains("..") {
+    if safe_path::relative_path(&asset_path).is_err() {
         error!("Path traversal attempt in asset_path: {}", asset_path);
         return Err(StatusCode::FORBIDDEN);
     }
 
     let file_path = state.base_path.join("maps").join(&map_id).join("assets").join(&asset_path);
 
-    // Ensure the path is within the expected directory
-    let expected_base = state.base_path.join("maps").join(&map_id).join("assets");
-    if !file_path.starts_with(&expected_base) {
-        error!("Asset path outside expected directory: {:?}", file_path);
-        return Err(StatusCode::FORBIDDEN);
-    }
-
     // Determine content type from extension
     let content_type = match file_path.extension().and_then(|e| e.to_str()) {
         Some("json") => "application/json",
@@ -292,6 +316,68 @@ async fn serve_file_internal(file_path: PathBuf, content_type: &str) -> Result<R
         .into_response())
 }
 
+/// Path of the gzipped variant BlueMap writes for a tile (`tile.png` -> `tile.png.gz`)
+fn gzipped_tile_path(file_path: &std::path::Path) -> PathBuf {
+    file_path.with_extension(format!(
+        "{}.gz",
+        file_path.extension().and_then(|e| e.to_str()).unwrap_or("")
+    ))
+}
+
+/// Serve a tile through the tile cache, answering If-None-Match with 304
+async fn serve_cached_tile(
+    tiles: &TileCache,
+    map_id: &str,
+    tile_path: &str,
+    file_path: PathBuf,
+    content_type: &str,
+    headers: &HeaderMap,
+) -> Result<Response, StatusCode> {
+    let key = format!("{}/{}", map_id, tile_path);
+    let gz_path = gzipped_tile_path(&file_path);
+    let tile = tiles
+        .get(&key, &[(file_path, false), (gz_path, true)])
+        .await
+        .map_err(|e| {
+            error!("Failed to read BlueMap tile {}: {}", key, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        // Unrendered areas have no tile; this is normal
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if let Some(if_none_match) = headers.get(header::IF_NONE_MATCH).and_then(|h| h.to_str().ok()) {
+        if etag_matches(if_none_match, &tile.etag) {
+            return Ok((
+                StatusCode::NOT_MODIFIED,
+                [
+                    (header::ETAG, tile.etag.as_str()),
+                    (header::ACCESS_CONTROL_ALLOW_ORIGIN, "*"),
+                ],
+            )
+                .into_response());
+        }
+    }
+
+    let mut response = (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, "public, max-age=300"),
+            (header::ETAG, tile.etag.as_str()),
+            (header::ACCESS_CONTROL_ALLOW_ORIGIN, "*"),
+        ],
+        tile.data,
+    )
+        .into_response();
+    if tile.gzipped {
+        // BlueMap's client expects this to transparently decompress
+        response
+            .headers_mut()
+            .insert(header::CONTENT_ENCODING, "gzip".parse().unwrap());
+    }
+    Ok(response)
+}
+
 /// Internal helper to serve gzipped tile files with proper encoding headers
 async fn serve_gzipped_tile(file_path: PathBuf, content_type: &str) -> Result<Response, StatusCode> {
     // Check if file exists
@@ -0,0 +1,229 @@
+use crate::api::admin::{require_role, AdminState, AppError};
+use crate::api::public::{AppError as PublicError, PublicState};
+use crate::database::{
+    self,
+    crashes::{CrashFilter, CrashReport, CrashReportSummary, CrashSignatureStats, NewCrashReport},
+};
+use crate::middleware::AdminToken;
+use crate::models::AdminRole;
+use crate::services::crashes::crash_signature;
+use crate::utils::text::truncate_bytes;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+/// Reports are truncated to this many bytes; the stack trace is at the top
+const MAX_REPORT_BYTES: usize = 512 * 1024;
+
+/// Body limit for crash uploads: a full-size report plus room for JSON escaping
+pub const MAX_CRASH_REQUEST_BYTES: usize = 1024 * 1024;
+
+/// Upper bound for the short metadata fields
+const MAX_FIELD_LEN: usize = 128;
+
+/// Body of POST /api/crashes
+#[derive(Debug, Deserialize)]
+pub struct SubmitCrashRequest {
+    pub modpack_version: String,
+    #[serde(default)]
+    pub minecraft_version: Option<String>,
+    pub os: String,
+    #[serde(default)]
+    pub java_version: Option<String>,
+    #[serde(default)]
+    pub launcher_version: Option<String>,
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+    /// Contents of the crash report (or the tail of latest.log)
+    pub report: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SubmitCrashResponse {
+    pub id: i64,
+    pub signature: String,
+}
+
+fn required_field(name: &str, value: String) -> Result<String, PublicError> {
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        return Err(PublicError::BadRequest(format!("{} is required", name)));
+    }
+    if value.len() > MAX_FIELD_LEN {
+        return Err(PublicError::BadRequest(format!("{} is too long", name)));
+    }
+    Ok(value)
+}
+
+pub(crate) fn optional_field(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .map(|v| truncate_bytes(&v, MAX_FIELD_LEN).to_string())
+}
+
+/// POST /api/crashes - Store a crash report uploaded by the launcher (opt-in on the client)
+pub async fn submit_crash_report(
+    State(state): State<PublicState>,
+    Json(payload): Json<SubmitCrashRequest>,
+) -> Result<(StatusCode, Json<SubmitCrashResponse>), PublicError> {
+    let modpack_version = required_field("modpack_version", payload.modpack_version)?;
+    let os = required_field("os", payload.os)?;
+
+    if payload.report.trim().is_empty() {
+        return Err(PublicError::BadRequest("report is required".to_string()));
+    }
+    let report = truncate_bytes(&payload.report, MAX_REPORT_BYTES).to_string();
+
+    let signature = crash_signature(&report);
+
+    let id = database::crashes::insert(
+        &state.db.conn,
+        NewCrashReport {
+            modpack_version,
+            minecraft_version: optional_field(payload.minecraft_version),
+            os,
+            java_version: optional_field(payload.java_version),
+            launcher_version: optional_field(payload.launcher_version),
+            exit_code: payload.exit_code,
+            signature: signature.signature.clone(),
+            summary: signature.summary,
+            report,
+        },
+    )
+    .await?;
+
+    tracing::info!("Stored crash report {} (signature {})", id, signature.signature);
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SubmitCrashResponse {
+            id,
+            signature: signature.signature,
+        }),
+    ))
+}
+
+/// Query parameters for GET /api/admin/crashes
+#[derive(Debug, Deserialize)]
+pub struct CrashListQuery {
+    #[serde(default = "default_page")]
+    pub page: usize,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    /// Only reports from this modpack version
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Only reports with this signature
+    #[serde(default)]
+    pub signature: Option<String>,
+    /// Only reports from this OS (`windows`, `macos`, `linux`)
+    #[serde(default)]
+    pub os: Option<String>,
+}
+
+fn default_page() -> usize { 1 }
+fn default_limit() -> usize { 50 }
+
+/// Paginated crash report response
+#[derive(Serialize)]
+pub struct PaginatedCrashResponse {
+    pub entries: Vec<CrashReportSummary>,
+    pub page: usize,
+    pub limit: usize,
+    pub total: usize,
+    pub total_pages: usize,
+}
+
+/// GET /api/admin/crashes - List crash reports, newest first
+pub async fn list_crash_reports(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Query(query): Query<CrashListQuery>,
+) -> Result<Json<PaginatedCrashResponse>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let limit = query.limit.clamp(1, 200);
+    let page = query.page.max(1); // Min page 1
+
+    let filter = CrashFilter {
+        modpack_version: query.version.filter(|s| !s.is_empty()),
+        signature: query.signature.filter(|s| !s.is_empty()),
+        os: query.os.filter(|s| !s.is_empty()),
+    };
+
+    let (entries, total) = database::crashes::list(&state.db.conn, filter, limit, (page - 1) * limit)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read crash reports: {}", e)))?;
+
+    let total_pages = total.div_ceil(limit);
+
+    Ok(Json(PaginatedCrashResponse {
+        entries,
+        page,
+        limit,
+        total,
+        total_pages,
+    }))
+}
+
+/// GET /api/admin/crashes/:id - Full crash report
+pub async fn get_crash_report(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(id): Path<i64>,
+) -> Result<Json<CrashReport>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let report = database::crashes::get(&state.db.conn, id)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read crash report: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Crash report {} not found", id)))?;
+
+    Ok(Json(report))
+}
+
+/// Query parameters for GET /api/admin/crashes/signatures
+#[derive(Debug, Deserialize)]
+pub struct CrashSignaturesQuery {
+    /// Only count reports from this modpack version
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default = "default_signature_limit")]
+    pub limit: usize,
+}
+
+fn default_signature_limit() -> usize { 20 }
+
+/// GET /api/admin/crashes/signatures - Most frequent crash signatures, optionally per release
+pub async fn get_crash_signatures(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Query(query): Query<CrashSignaturesQuery>,
+) -> Result<Json<Vec<CrashSignatureStats>>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let limit = query.limit.clamp(1, 100);
+    let version = query.version.filter(|s| !s.is_empty());
+
+    let stats = database::crashes::top_signatures(&state.db.conn, version, limit)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to aggregate crash reports: {}", e)))?;
+
+    Ok(Json(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_field() {
+        assert!(required_field("os", "  ".to_string()).is_err());
+        assert!(required_field("os", "x".repeat(MAX_FIELD_LEN + 1)).is_err());
+        assert!(matches!(required_field("os", " linux ".to_string()), Ok(os) if os == "linux"));
+    }
+}
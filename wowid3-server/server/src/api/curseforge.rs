@@ -0,0 +1,353 @@
+use crate::api::admin::{require_role, AdminState, AppError};
+use crate::api::drafts::{copy_dir_all, scan_directory_files};
+use crate::middleware::AdminToken;
+use crate::models::{AdminRole, DraftFile, DraftRelease};
+use crate::services::curseforge::{CurseForgeClient, CurseForgeFile, CurseForgePackManifest};
+use crate::services::tasks::TaskHandle;
+use crate::storage;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
+use tokio::fs;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct AddCurseForgeModRequest {
+    pub mod_id: u64,
+    /// Specific file id; defaults to the newest compatible Fabric file
+    #[serde(default)]
+    pub file_id: Option<u64>,
+    #[serde(default = "default_true")]
+    pub include_dependencies: bool,
+}
+
+fn default_true() -> bool { true }
+
+#[derive(Debug, Deserialize)]
+pub struct ImportCurseForgePackRequest {
+    /// Upload containing an extracted CurseForge modpack export (`manifest.json` + overrides)
+    pub upload_id: Uuid,
+}
+
+/// A jar placed in the draft from CurseForge
+#[derive(Debug, Serialize)]
+pub struct InstalledCurseForgeMod {
+    pub mod_id: u64,
+    pub file_id: u64,
+    pub display_name: String,
+    pub path: String,
+    pub sha256: String,
+}
+
+/// A file CurseForge won't serve to third parties
+#[derive(Debug, Serialize)]
+pub struct ManualCurseForgeMod {
+    pub mod_id: u64,
+    pub file_id: u64,
+    pub file_name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AddCurseForgeModResponse {
+    pub draft: DraftRelease,
+    pub installed: Vec<InstalledCurseForgeMod>,
+    /// Jars already present in the draft's mods folder
+    pub skipped: Vec<String>,
+    /// Files that must be downloaded by hand and uploaded to the draft
+    pub manual_downloads: Vec<ManualCurseForgeMod>,
+    /// Required dependency mod ids with no compatible file
+    pub unresolved_dependencies: Vec<u64>,
+}
+
+fn curseforge_client(state: &AdminState) -> Result<CurseForgeClient, AppError> {
+    let api_key = state.config.curseforge_api_key.as_deref().ok_or_else(|| {
+        AppError::BadRequest("CurseForge API key is not configured (CURSEFORGE_API_KEY)".to_string())
+    })?;
+    CurseForgeClient::new(api_key).map_err(AppError::Internal)
+}
+
+/// Download CurseForge files into the draft's mods folder
+///
+/// Files already in the draft are skipped and files without a download URL are
+/// reported for manual download. The draft manifest is not updated here.
+async fn install_files(
+    client: &CurseForgeClient,
+    draft: &DraftRelease,
+    draft_files_dir: &std::path::Path,
+    files: Vec<CurseForgeFile>,
+    task: Option<&TaskHandle>,
+) -> Result<(Vec<DraftFile>, Vec<InstalledCurseForgeMod>, Vec<String>, Vec<ManualCurseForgeMod>), AppError> {
+    let mut new_files = Vec::new();
+    let mut installed = Vec::new();
+    let mut skipped = Vec::new();
+    let mut manual_downloads = Vec::new();
+
+    if let Some(task) = task {
+        task.stage("Downloading mods", Some(files.len() as u64));
+    }
+
+    for (index, file) in files.into_iter().enumerate() {
+        if let Some(task) = task {
+            task.progress(index as u64 + 1, file.file_name.as_str());
+        }
+
+        if file.file_name.contains(['/', '\\']) || file.file_name.contains("..") {
+            return Err(AppError::BadRequest(format!(
+                "Refusing suspicious file name from CurseForge: {}",
+                file.file_name
+            )));
+        }
+
+        let relative = format!("mods/{}", file.file_name);
+        if draft.files.iter().any(|f| f.path == relative) || new_files.iter().any(|f: &DraftFile| f.path == relative) {
+            skipped.push(relative);
+            continue;
+        }
+
+        if file.download_url.is_none() {
+            manual_downloads.push(ManualCurseForgeMod {
+                mod_id: file.mod_id,
+                file_id: file.id,
+                file_name: file.file_name,
+            });
+            continue;
+        }
+
+        let sha256 = client
+            .download(&file, &draft_files_dir.join(&relative))
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to download {}: {}", file.file_name, e)))?;
+
+        new_files.push(DraftFile {
+            path: relative.clone(),
+            url: None, // URLs are generated when publishing
+            sha256: sha256.clone(),
+            size: file.file_length,
+        });
+        installed.push(InstalledCurseForgeMod {
+            mod_id: file.mod_id,
+            file_id: file.id,
+            display_name: file.display_name,
+            path: relative,
+            sha256,
+        });
+    }
+
+    Ok((new_files, installed, skipped, manual_downloads))
+}
+
+/// POST /api/admin/drafts/:id/mods/curseforge - Download a CurseForge mod (and its dependencies) into the draft
+pub async fn add_curseforge_mod(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<AddCurseForgeModRequest>,
+) -> Result<Json<AddCurseForgeModResponse>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let client = curseforge_client(&state)?;
+    let draft = storage::read_draft(state.config.storage_path(), id).await?;
+    if draft.minecraft_version.is_empty() {
+        return Err(AppError::BadRequest(
+            "Set the draft's Minecraft version before adding mods from CurseForge".to_string(),
+        ));
+    }
+
+    let root = match request.file_id {
+        Some(file_id) => client
+            .get_file(request.mod_id, file_id)
+            .await
+            .map_err(|e| AppError::NotFound(e.to_string()))?,
+        None => client
+            .compatible_files(request.mod_id, &draft.minecraft_version)
+            .await
+            .map_err(|e| AppError::NotFound(e.to_string()))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "CurseForge mod {} has no Fabric file for Minecraft {}",
+                    request.mod_id, draft.minecraft_version
+                ))
+            })?,
+    };
+
+    let (files, unresolved_dependencies) = if request.include_dependencies {
+        client
+            .resolve_with_dependencies(root, &draft.minecraft_version)
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to resolve dependencies: {}", e)))?
+    } else {
+        (vec![root], Vec::new())
+    };
+
+    let draft_files_dir = storage::get_draft_files_dir(state.config.storage_path(), id);
+    let (new_files, installed, skipped, manual_downloads) =
+        install_files(&client, &draft, &draft_files_dir, files, None).await?;
+
+    let draft = storage::add_files_to_draft(state.config.storage_path(), id, new_files, None).await?;
+
+    tracing::info!(
+        "Admin {} added {} CurseForge jar(s) to draft {} ({} skipped, {} manual)",
+        token.subject(),
+        installed.len(),
+        id,
+        skipped.len(),
+        manual_downloads.len()
+    );
+
+    Ok(Json(AddCurseForgeModResponse {
+        draft,
+        installed,
+        skipped,
+        manual_downloads,
+        unresolved_dependencies,
+    }))
+}
+
+/// POST /api/admin/drafts/:id/mods/curseforge/import - Import an uploaded CurseForge modpack export
+///
+/// Runs in the background; returns `202 Accepted` with a task id. The task
+/// result has the same shape as `add_curseforge_mod`'s response.
+pub async fn import_curseforge_pack(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<ImportCurseForgePackRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let client = curseforge_client(&state)?;
+    let draft = storage::read_draft(state.config.storage_path(), id).await?;
+
+    let upload_dir = state.config.uploads_path().join(request.upload_id.to_string());
+    let manifest_path = upload_dir.join("manifest.json");
+    if !manifest_path.exists() {
+        return Err(AppError::NotFound(format!(
+            "Upload {} does not contain a CurseForge manifest.json",
+            request.upload_id
+        )));
+    }
+
+    let manifest_data = fs::read_to_string(&manifest_path)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read manifest.json: {}", e)))?;
+    let manifest: CurseForgePackManifest = serde_json::from_str(&manifest_data)
+        .map_err(|e| AppError::BadRequest(format!("Invalid CurseForge manifest: {}", e)))?;
+    let overrides_dir = manifest
+        .overrides_dir(&upload_dir)
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    if !draft.minecraft_version.is_empty() && draft.minecraft_version != manifest.minecraft.version {
+        return Err(AppError::BadRequest(format!(
+            "Modpack targets Minecraft {} but the draft is for {}",
+            manifest.minecraft.version, draft.minecraft_version
+        )));
+    }
+
+    let task_state = state.clone();
+    let task_id = state.tasks.spawn("import_curseforge_pack", move |task| {
+        import_pack_files(task_state, id, overrides_dir, manifest, client, task)
+    });
+
+    tracing::info!(
+        "Admin {} started CurseForge import of upload {} into draft {}",
+        token.subject(),
+        request.upload_id,
+        id
+    );
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "message": "CurseForge import started",
+            "task_id": task_id
+        })),
+    ))
+}
+
+/// Background part of `import_curseforge_pack`
+async fn import_pack_files(
+    state: AdminState,
+    id: Uuid,
+    overrides_dir: PathBuf,
+    manifest: CurseForgePackManifest,
+    client: CurseForgeClient,
+    task: TaskHandle,
+) -> Result<serde_json::Value, AppError> {
+    let storage_path = state.config.storage_path();
+    let draft_files_dir = storage::get_draft_files_dir(storage_path, id);
+
+    // Fill in versions the draft doesn't have yet
+    let draft = storage::read_draft(storage_path, id).await?;
+    let minecraft_version = draft
+        .minecraft_version
+        .is_empty()
+        .then(|| manifest.minecraft.version.clone());
+    let fabric_loader = if draft.fabric_loader.is_empty() {
+        manifest.fabric_loader()
+    } else {
+        None
+    };
+    let draft = storage::update_draft(storage_path, id, None, minecraft_version, fabric_loader, None, None).await?;
+
+    // Overrides (configs, resource packs, ...) go to the draft root
+    let mut new_files = Vec::new();
+    if overrides_dir.is_dir() {
+        copy_dir_all(&overrides_dir, &draft_files_dir, Some(&task)).await?;
+        new_files.extend(scan_directory_files(&overrides_dir, Some(&task)).await?);
+    }
+
+    task.stage("Resolving CurseForge files", None);
+    let file_ids: Vec<u64> = manifest
+        .files
+        .iter()
+        .filter(|f| f.required)
+        .map(|f| f.file_id)
+        .collect();
+    let files = if file_ids.is_empty() {
+        Vec::new()
+    } else {
+        client
+            .get_files(&file_ids)
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to resolve CurseForge files: {}", e)))?
+    };
+
+    let unresolved_dependencies: Vec<u64> = manifest
+        .files
+        .iter()
+        .filter(|f| f.required && !files.iter().any(|file| file.id == f.file_id))
+        .map(|f| f.project_id)
+        .collect();
+
+    let (mod_files, installed, skipped, manual_downloads) =
+        install_files(&client, &draft, &draft_files_dir, files, Some(&task)).await?;
+    new_files.extend(mod_files);
+
+    let draft = storage::add_files_to_draft(storage_path, id, new_files, None).await?;
+
+    tracing::info!(
+        "Imported CurseForge pack {} into draft {}: {} installed, {} skipped, {} manual",
+        manifest.name.as_deref().unwrap_or("(unnamed)"),
+        id,
+        installed.len(),
+        skipped.len(),
+        manual_downloads.len()
+    );
+
+    let response = AddCurseForgeModResponse {
+        draft,
+        installed,
+        skipped,
+        manual_downloads,
+        unresolved_dependencies,
+    };
+    serde_json::to_value(response)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to serialize import result: {}", e)))
+}
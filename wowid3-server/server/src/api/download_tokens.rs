@@ -0,0 +1,56 @@
+use crate::api::public::{AppError, PublicState};
+use crate::database;
+use crate::utils::player::parse_player_uuid;
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+
+/// Body of POST /api/auth/token
+#[derive(Debug, Deserialize)]
+pub struct DownloadTokenRequest {
+    pub uuid: String,
+    /// The player's Minecraft access token, checked against the session server
+    pub access_token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DownloadTokenResponse {
+    pub token: String,
+    /// Unix seconds
+    pub expires_at: i64,
+    /// Whether this server refuses manifest and file downloads without a token
+    pub required: bool,
+}
+
+/// POST /api/auth/token - Short-lived download token for a whitelisted player
+///
+/// Present it as `Authorization: Bearer <token>` (or `?token=`) on /api/manifest and
+/// /files requests when DOWNLOAD_AUTH is enabled.
+pub async fn issue_download_token(
+    State(state): State<PublicState>,
+    Json(payload): Json<DownloadTokenRequest>,
+) -> Result<Json<DownloadTokenResponse>, AppError> {
+    let uuid = parse_player_uuid(&payload.uuid)?;
+
+    let profile = state
+        .profile_verifier
+        .verify(&payload.access_token)
+        .await?
+        .filter(|profile| profile.uuid == uuid)
+        .ok_or_else(|| AppError::Forbidden("Access token does not belong to this player".to_string()))?;
+
+    let listed = database::whitelist::get(&state.db.conn, profile.uuid.clone())
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read whitelist: {}", e)))?;
+    if listed.is_none() {
+        return Err(AppError::Forbidden(format!("{} is not on the whitelist", profile.name)));
+    }
+
+    let (token, expires_at) = state.download_tokens.issue(&profile)?;
+    tracing::debug!("Issued download token to {} ({})", profile.name, profile.uuid);
+
+    Ok(Json(DownloadTokenResponse {
+        token,
+        expires_at,
+        required: state.config.download_auth,
+    }))
+}
@@ -1,14 +1,22 @@
-use crate::api::admin::{AdminState, AppError};
+use crate::api::admin::{require_role, AdminState, AppError};
+use crate::cache::CacheEvent;
 use crate::middleware::AdminToken;
 use crate::models::{
-    AddFilesRequest, CreateDraftRequest, DraftFile, DraftRelease, GeneratedChangelog, Manifest,
-    ManifestFile, UpdateDraftRequest, UpdateFileRequest, VersionSuggestions,
+    AddFilesRequest, AdminRole, CreateDraftRequest, DraftFile, DraftRelease, GeneratedChangelog,
+    IgnorePatternOverrides, Manifest, ManifestFile, OptionalGroup, ReleaseDiff, UpdateDraftRequest, UpdateFileRequest,
+};
+use crate::services::diff::diff_release;
+use crate::services::tasks::TaskHandle;
+use crate::services::validation::{self, Severity, ValidationReport};
+use crate::services::{
+    analyze_files, check_dependencies, generate_changelog, scan_mods, suggest_next_version,
+    ChangeType,
 };
-use crate::services::{analyze_files, generate_changelog, suggest_next_version, ChangeType};
 use crate::storage;
 use crate::utils;
 use axum::{
     extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     Extension, Json,
 };
 use chrono;
@@ -24,9 +32,11 @@ use walkdir::WalkDir;
 /// POST /api/admin/drafts - Create a new draft
 pub async fn create_draft(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Json(request): Json<CreateDraftRequest>,
 ) -> Result<Json<DraftRelease>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     let draft = storage::create_draft(&state.config.storage_path(), request.version).await?;
 
     // If upload_id provided, add files from upload
@@ -34,15 +44,15 @@ pub async fn create_draft(
         let upload_dir = state.config.uploads_path().join(&upload_id);
         if upload_dir.exists() {
             let files =
-                scan_upload_files(&upload_dir, &state.config.base_url, &draft.id.to_string(), None)
+                scan_upload_files(&upload_dir, &state.settings.get().base_url, &draft.id.to_string(), None)
                     .await?;
             let updated_draft =
-                storage::add_files_to_draft(&state.config.storage_path(), draft.id, files).await?;
+                storage::add_files_to_draft(state.config.storage_path(), draft.id, files, None).await?;
 
             // Copy files to draft directory
             let draft_files_dir =
                 storage::get_draft_files_dir(&state.config.storage_path(), draft.id);
-            copy_dir_all(&upload_dir, &draft_files_dir).await?;
+            copy_dir_all(&upload_dir, &draft_files_dir, None).await?;
 
             return Ok(Json(updated_draft));
         }
@@ -54,8 +64,10 @@ pub async fn create_draft(
 /// GET /api/admin/drafts - List all drafts
 pub async fn list_drafts(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
 ) -> Result<Json<Vec<DraftRelease>>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
     let drafts = storage::list_drafts(&state.config.storage_path()).await?;
     Ok(Json(drafts))
 }
@@ -63,39 +75,75 @@ pub async fn list_drafts(
 /// GET /api/admin/drafts/:id - Get specific draft
 pub async fn get_draft(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<DraftRelease>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
     let draft = storage::read_draft(&state.config.storage_path(), id).await?;
     Ok(Json(draft))
 }
 
 /// PUT /api/admin/drafts/:id - Update draft metadata
+///
+/// Draft edits accept `If-Match: <revision>` and answer `409 Conflict` when the
+/// draft changed since the client loaded it.
 pub async fn update_draft(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(request): Json<UpdateDraftRequest>,
 ) -> Result<Json<DraftRelease>, AppError> {
-    let draft = storage::update_draft(
-        &state.config.storage_path(),
-        id,
-        request.version,
-        request.minecraft_version,
-        request.fabric_loader,
-        request.changelog,
-    )
-    .await?;
+    require_role(&token, AdminRole::ReleaseManager)?;
 
-    Ok(Json(draft))
+    if let Some(groups) = &request.optional_groups {
+        OptionalGroup::validate(groups).map_err(AppError::BadRequest)?;
+    }
+    let ignore_overrides = request
+        .ignore_overrides
+        .map(IgnorePatternOverrides::validate)
+        .transpose()
+        .map_err(AppError::BadRequest)?;
+
+    let mut edit = storage::drafts::edit_draft(state.config.storage_path(), id, expected_revision(&headers)?).await?;
+    let draft = &mut edit.draft;
+    if let Some(version) = request.version {
+        draft.version = version;
+    }
+    if let Some(minecraft_version) = request.minecraft_version {
+        draft.minecraft_version = minecraft_version;
+    }
+    if let Some(loader) = request.loader {
+        draft.loader = loader;
+    }
+    if let Some(fabric_loader) = request.fabric_loader {
+        draft.fabric_loader = fabric_loader;
+    }
+    if let Some(changelog) = request.changelog {
+        draft.changelog = changelog;
+    }
+    if let Some(channel) = request.channel {
+        draft.channel = channel;
+    }
+    if let Some(groups) = request.optional_groups {
+        draft.optional_groups = groups;
+    }
+    if let Some(overrides) = ignore_overrides {
+        draft.ignore_overrides = overrides;
+    }
+
+    Ok(Json(edit.commit().await?))
 }
 
 /// DELETE /api/admin/drafts/:id - Delete draft
 pub async fn delete_draft(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     storage::delete_draft(&state.config.storage_path(), id).await?;
 
     Ok(Json(json!({
@@ -105,17 +153,69 @@ pub async fn delete_draft(
 }
 
 /// POST /api/admin/drafts/:id/analyze - Analyze draft files and suggest versions
+///
+/// Runs in the background; the task result is a `VersionSuggestions` object.
 pub async fn analyze_draft(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(id): Path<Uuid>,
-) -> Result<Json<VersionSuggestions>, AppError> {
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
     let draft = storage::read_draft(&state.config.storage_path(), id).await?;
+
+    let task_state = state.clone();
+    let task_id = state.tasks.spawn("analyze_draft", move |task| {
+        analyze_draft_files(task_state, id, draft, task)
+    });
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "message": "Draft analysis started",
+            "task_id": task_id
+        })),
+    ))
+}
+
+/// Background part of `analyze_draft`
+async fn analyze_draft_files(
+    state: AdminState,
+    id: Uuid,
+    draft: DraftRelease,
+    task: TaskHandle,
+) -> Result<serde_json::Value, AppError> {
     let draft_files_dir = storage::get_draft_files_dir(&state.config.storage_path(), id);
 
-    let mut suggestions = analyze_files(&draft_files_dir)?;
+    // Reading JAR metadata is blocking file IO
+    task.stage("Reading mod metadata", None);
+    let mut suggestions = tokio::task::spawn_blocking(move || analyze_files(&draft_files_dir))
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Analysis task panicked: {}", e)))??;
+
+    // Check dependencies against what the draft will ship with, falling back to detected versions
+    task.stage("Checking dependencies", None);
+    let minecraft_version = Some(draft.minecraft_version.as_str())
+        .filter(|v| !v.is_empty())
+        .or(suggestions.minecraft_version.as_deref());
+    let fabric_loader = Some(draft.fabric_loader.as_str())
+        .filter(|v| !v.is_empty())
+        .or(suggestions.fabric_loader.as_deref());
+    let dependency_report = check_dependencies(&suggestions.detected_mods, minecraft_version, fabric_loader);
+    if dependency_report.has_problems() {
+        tracing::warn!(
+            "Draft {} has dependency problems: {} missing, {} version mismatches, {} conflicts, {} duplicates",
+            id,
+            dependency_report.missing_dependencies.len(),
+            dependency_report.version_mismatches.len(),
+            dependency_report.conflicts.len(),
+            dependency_report.duplicate_mods.len()
+        );
+    }
+    suggestions.dependency_report = dependency_report;
 
     // Suggest next version based on latest release
+    task.stage("Suggesting version", None);
     let versions = storage::manifest::list_versions(&state.config).await?;
     if let Some(latest_version) = versions.first() {
         // Determine change type based on file changes
@@ -128,16 +228,20 @@ pub async fn analyze_draft(
         suggestions.suggested_version = Some(suggest_next_version(latest_version, change_type));
     }
 
-    Ok(Json(suggestions))
+    serde_json::to_value(suggestions)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to serialize suggestions: {}", e)))
 }
 
 /// POST /api/admin/drafts/:id/files - Add files to draft
 pub async fn add_files(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(request): Json<AddFilesRequest>,
 ) -> Result<Json<DraftRelease>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     let upload_dir = state.config.uploads_path().join(&request.upload_id);
 
     if !upload_dir.exists() {
@@ -146,10 +250,10 @@ pub async fn add_files(
 
     // Scan files from upload
     let target_path = request.target_path.as_deref();
-    let files = scan_upload_files(&upload_dir, &state.config.base_url, &id.to_string(), target_path).await?;
+    let files = scan_upload_files(&upload_dir, &state.settings.get().base_url, &id.to_string(), target_path).await?;
 
     // Add to draft
-    let draft = storage::add_files_to_draft(&state.config.storage_path(), id, files).await?;
+    let draft = storage::add_files_to_draft(state.config.storage_path(), id, files, expected_revision(&headers)?).await?;
 
     // Copy files to draft directory
     let draft_files_dir = storage::get_draft_files_dir(&state.config.storage_path(), id);
@@ -172,7 +276,7 @@ pub async fn add_files(
         draft_files_dir
     };
 
-    copy_dir_all(&upload_dir, &dest_dir).await?;
+    copy_dir_all(&upload_dir, &dest_dir, None).await?;
 
     Ok(Json(draft))
 }
@@ -186,17 +290,21 @@ pub struct RecursiveParams {
 /// DELETE /api/admin/drafts/:id/files/*path - Remove file from draft
 pub async fn remove_file(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path((id, file_path)): Path<(Uuid, String)>,
     Query(params): Query<RecursiveParams>,
+    headers: HeaderMap,
 ) -> Result<Json<DraftRelease>, AppError> {
-    match storage::remove_file_from_draft(&state.config.storage_path(), id, &file_path, params.recursive).await {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let expected = expected_revision(&headers)?;
+    match storage::remove_file_from_draft(state.config.storage_path(), id, &file_path, params.recursive, expected).await {
         Ok(draft) => Ok(Json(draft)),
         Err(e) => {
             if e.to_string().contains("Directory is not empty") {
                 Err(AppError::BadRequest("Directory is not empty".to_string()))
             } else {
-                Err(AppError::Internal(e))
+                Err(AppError::from(e))
             }
         }
     }
@@ -205,50 +313,195 @@ pub async fn remove_file(
 /// PUT /api/admin/drafts/:id/files/*path - Update file metadata
 pub async fn update_file(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path((id, file_path)): Path<(Uuid, String)>,
+    headers: HeaderMap,
     Json(request): Json<UpdateFileRequest>,
 ) -> Result<Json<DraftRelease>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     let draft = storage::update_file_in_draft(
         &state.config.storage_path(),
         id,
         &file_path,
         request.sha256,
         request.url,
+        expected_revision(&headers)?,
     )
     .await?;
 
     Ok(Json(draft))
 }
 
-/// POST /api/admin/drafts/:id/generate-changelog - Generate changelog from file diff
+/// POST /api/admin/drafts/:id/generate-changelog - Generate changelog from mod version and file diff
 pub async fn generate_changelog_for_draft(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<GeneratedChangelog>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     let draft = storage::read_draft(&state.config.storage_path(), id).await?;
 
     // Get the latest release manifest for comparison
-    let previous_files = storage::manifest::read_latest_manifest(&state.config)
+    let previous = storage::manifest::read_latest_manifest(&state.config).await.ok();
+    let previous_dir = previous.as_ref().map(|m| state.config.release_path(&m.version));
+    let previous_files = previous.map(|m| m.files);
+
+    // Reading JAR metadata is blocking file IO
+    let draft_files_dir = storage::get_draft_files_dir(state.config.storage_path(), id);
+    let (current_mods, previous_mods) = tokio::task::spawn_blocking(move || {
+        let current_mods = scan_mods(&draft_files_dir);
+        let previous_mods = previous_dir.map(|dir| scan_mods(&dir)).unwrap_or_default();
+        (current_mods, previous_mods)
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Mod scan panicked: {}", e)))?;
+
+    let changelog = generate_changelog(
+        &draft.files,
+        previous_files.as_deref(),
+        &current_mods,
+        &previous_mods,
+    )?;
+
+    Ok(Json(changelog))
+}
+
+/// GET /api/admin/drafts/:id/diff/:version - Files and mods that differ between a release and the draft
+///
+/// Works against any published release, e.g. to preview a hotfix for players
+/// still on an older version.
+pub async fn diff_draft(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path((id, version)): Path<(Uuid, String)>,
+) -> Result<Json<ReleaseDiff>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let draft = storage::read_draft(state.config.storage_path(), id).await?;
+    let base = storage::manifest::read_manifest(&state.config, &version)
         .await
-        .ok()
-        .map(|m| m.files);
+        .map_err(|_| AppError::NotFound(format!("Release {} not found", version)))?;
 
-    let changelog =
-        generate_changelog(&draft.files, previous_files.as_ref().map(|v| v.as_slice()))?;
+    // Reading JAR metadata is blocking file IO
+    let draft_files_dir = storage::get_draft_files_dir(state.config.storage_path(), id);
+    let base_dir = state.config.release_path(&base.version);
+    let (draft_mods, base_mods) = tokio::task::spawn_blocking(move || (scan_mods(&draft_files_dir), scan_mods(&base_dir)))
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Mod scan panicked: {}", e)))?;
 
-    Ok(Json(changelog))
+    Ok(Json(diff_release(&draft, &base, &draft_mods, &base_mods)))
+}
+
+/// Check a draft on disk, including whether its version is already released
+async fn run_validation(state: &AdminState, id: Uuid, draft: &DraftRelease) -> Result<ValidationReport, AppError> {
+    let blacklist_patterns = utils::load_blacklist_patterns(&state.config)
+        .await
+        .map(|patterns| draft.ignore_overrides.apply(&patterns))
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to load blacklist: {}", e)))?;
+    let glob_set = utils::compile_patterns(&blacklist_patterns).map_err(|e| {
+        AppError::Internal(anyhow::anyhow!("Failed to compile blacklist patterns: {}", e))
+    })?;
+
+    let draft_files_dir = storage::get_draft_files_dir(state.config.storage_path(), id);
+    let size_budget = state.config.draft_size_budget();
+    let task_draft = draft.clone();
+
+    // Reading JAR metadata is blocking file IO
+    let mut report = tokio::task::spawn_blocking(move || {
+        let mods = scan_mods(&draft_files_dir);
+        validation::validate_draft(&task_draft, &draft_files_dir, &mods, &glob_set, size_budget)
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Validation task panicked: {}", e)))?;
+
+    if !draft.version.is_empty() && state.config.release_path(&draft.version).exists() {
+        report.push(
+            Severity::Error,
+            "release_exists",
+            format!("Release version {} already exists", draft.version),
+            None,
+        );
+    }
+
+    Ok(report)
+}
+
+/// POST /api/admin/drafts/:id/validate - Check a draft for common problems before publishing
+pub async fn validate_draft(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ValidationReport>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let draft = storage::read_draft(state.config.storage_path(), id).await?;
+    Ok(Json(run_validation(&state, id, &draft).await?))
+}
+
+/// Query parameters for POST /api/admin/drafts/:id/publish
+#[derive(Debug, Default, Deserialize)]
+pub struct PublishDraftQuery {
+    /// Refuse to publish when validation finds errors
+    #[serde(default)]
+    pub validate: bool,
+    /// Only validate; nothing is published
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 /// POST /api/admin/drafts/:id/publish - Publish draft as release
+///
+/// Validates the draft, then queues the publish as a job. Returns `202 Accepted`
+/// with a job id; see `/api/admin/jobs/:id` for the result and
+/// `/ws/admin/tasks/:id` (same id) for live progress.
+///
+/// `?validate=true` runs the full draft validation first and answers
+/// `422 Unprocessable Entity` with the report if it finds errors; `?dry_run=true`
+/// returns the report without publishing.
 pub async fn publish_draft(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let start = std::time::Instant::now();
+    Query(query): Query<PublishDraftQuery>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     let draft = storage::read_draft(&state.config.storage_path(), id).await?;
+    // Don't publish changes the admin hasn't seen
+    if let Some(expected) = expected_revision(&headers)?.filter(|expected| *expected != draft.revision) {
+        let conflict = storage::drafts::DraftConflict {
+            expected,
+            current: draft.revision,
+        };
+        return Err(AppError::Conflict(conflict.to_string()));
+    }
+
+    if query.validate || query.dry_run {
+        let report = run_validation(&state, id, &draft).await?;
+        if query.dry_run {
+            return Ok((
+                StatusCode::OK,
+                Json(json!({
+                    "message": "Dry run, nothing was published",
+                    "publishable": !report.has_errors(),
+                    "report": report
+                })),
+            ));
+        }
+        if report.has_errors() {
+            tracing::warn!("publish_draft refused: draft {} has {} validation error(s)", id, report.errors);
+            return Ok((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(json!({
+                    "error": format!("Draft has {} validation error(s)", report.errors),
+                    "report": report
+                })),
+            ));
+        }
+    }
 
     // Validate draft has required fields
     if draft.version.is_empty()
@@ -270,25 +523,131 @@ pub async fn publish_draft(
         return Err(AppError::BadRequest("Draft has no files".to_string()));
     }
 
-    // Create release directory
+    // Refuse to overwrite an existing release, unless this draft's own publish was interrupted
     let release_dir = state.config.release_path(&draft.version);
-    if release_dir.exists() {
+    if release_dir.exists() && !storage::publish::is_resumable(&state.config, id, &draft.version).await {
         let error_msg = format!("Release version {} already exists", draft.version);
         tracing::warn!("publish_draft failed: {}", error_msg);
         return Err(AppError::BadRequest(error_msg));
     }
 
-    fs::create_dir_all(&release_dir).await.map_err(|e| {
-        AppError::Internal(anyhow::anyhow!("Failed to create release directory: {}", e))
-    })?;
+    let version = draft.version.clone();
+    let job_state = state.clone();
+    let job_id = state
+        .jobs
+        .enqueue("publish_draft", token.subject(), move |task| {
+            publish_draft_files(job_state, id, draft, release_dir, task)
+        })
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to queue publish: {}", e)))?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "message": "Draft publish queued",
+            "version": version,
+            "job_id": job_id,
+            "task_id": job_id
+        })),
+    ))
+}
+
+/// Background part of `publish_draft`
+///
+/// The release is built in `staging/` and moved into `releases/` in one rename, so a
+/// crash never leaves a half-copied release behind. If a crash hits after that, running
+/// the publish again skips straight to the remaining steps.
+async fn publish_draft_files(
+    state: AdminState,
+    id: Uuid,
+    draft: DraftRelease,
+    release_dir: PathBuf,
+    task: TaskHandle,
+) -> Result<serde_json::Value, AppError> {
+    let start = std::time::Instant::now();
+
+    let manifest = if storage::publish::is_resumable(&state.config, id, &draft.version).await {
+        tracing::info!("Resuming interrupted publish of {} from draft {}", draft.version, id);
+        storage::manifest::read_manifest(&state.config, &draft.version).await?
+    } else {
+        // Another queued job may have created this version since the request was validated
+        if release_dir.exists() {
+            return Err(AppError::BadRequest(format!(
+                "Release version {} already exists",
+                draft.version
+            )));
+        }
+        storage::publish::begin_publish(&state.config, id, &draft.version).await?;
+        stage_draft_release(&state, id, &draft, &task).await?
+    };
+
+    // Share unchanged files with earlier releases
+    task.stage("Deduplicating files", None);
+    if let Err(e) = storage::files::deduplicate_release(&state.config, &manifest).await {
+        tracing::warn!("Failed to deduplicate release {}: {}", manifest.version, e);
+    }
+
+    // Players are only pointed at the release once its files are downloadable
+    task.stage("Uploading files", None);
+    state
+        .storage
+        .publish_release(&state.config, &manifest)
+        .await
+        .map_err(|e| {
+            AppError::Internal(anyhow::anyhow!(
+                "Failed to publish release files to {} storage: {}",
+                state.storage.name(),
+                e
+            ))
+        })?;
+
+    // Set as latest
+    storage::manifest::set_latest_manifest(&state.config, &draft.version).await?;
 
-    // Copy files from draft to release
+    // Invalidate cache after publishing
+    state
+        .cache
+        .publish(CacheEvent::ReleaseWritten { version: draft.version.clone() })
+        .await;
+    state.notifier.release_published(&manifest);
+
+    // Delete draft
+    storage::delete_draft(state.config.storage_path(), id).await?;
+    storage::publish::finish_publish(&state.config, &draft.version).await?;
+
+    let duration = start.elapsed();
+    tracing::info!(
+        "publish_draft completed in {:?} (version: {}, {} files)",
+        duration,
+        draft.version,
+        manifest.files.len()
+    );
+
+    Ok(json!({
+        "message": "Draft published successfully",
+        "version": draft.version,
+        "file_count": manifest.files.len()
+    }))
+}
+
+/// Copy, filter and checksum a draft's files in `staging/`, write the manifest, then move it into `releases/`
+async fn stage_draft_release(
+    state: &AdminState,
+    id: Uuid,
+    draft: &DraftRelease,
+    task: &TaskHandle,
+) -> Result<Manifest, AppError> {
+    let staging_dir = storage::publish::prepare_staging(&state.config, &draft.version).await?;
+
+    // Copy files from draft to the staged release
     let draft_files_dir = storage::get_draft_files_dir(&state.config.storage_path(), id);
-    copy_dir_all(&draft_files_dir, &release_dir).await?;
+    copy_dir_all(&draft_files_dir, &staging_dir, Some(task)).await?;
 
     // Load blacklist patterns to exclude files that should not be distributed
+    task.stage("Applying blacklist", None);
     let blacklist_patterns = utils::load_blacklist_patterns(&state.config)
         .await
+        .map(|patterns| draft.ignore_overrides.apply(&patterns))
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to load blacklist: {}", e)))?;
 
     let glob_set = utils::compile_patterns(&blacklist_patterns).map_err(|e| {
@@ -298,7 +657,7 @@ pub async fn publish_draft(
         ))
     })?;
 
-    let removed_blacklisted = remove_blacklisted_files(&release_dir, &glob_set).await?;
+    let removed_blacklisted = remove_blacklisted_files(&staging_dir, &glob_set).await?;
     if removed_blacklisted > 0 {
         tracing::warn!(
             "Removed {} blacklisted file(s) before publishing {}",
@@ -309,7 +668,7 @@ pub async fn publish_draft(
 
     // Regenerate checksums from the actual files on disk to ensure accuracy
     // This is critical because files may have been edited via the file browser
-    let verified_files = scan_directory_files(&release_dir).await?;
+    let verified_files = scan_directory_files(&staging_dir, Some(task)).await?;
 
     if verified_files.is_empty() {
         let error_msg = "All files were filtered out by the blacklist. Adjust your blacklist or add files before publishing.";
@@ -319,6 +678,7 @@ pub async fn publish_draft(
     // Convert DraftFile to ManifestFile with fresh checksums and release URLs
     // Filter out blacklisted files to prevent download failures
     let total_files = verified_files.len();
+    let settings = state.settings.get();
     let mut manifest_files: Vec<ManifestFile> = verified_files
         .iter()
         .filter(|f| !utils::is_blacklisted(&f.path, &glob_set))
@@ -326,10 +686,11 @@ pub async fn publish_draft(
             path: f.path.clone(),
             url: format!(
                 "{}/files/{}/{}",
-                state.config.base_url, draft.version, f.path
+                settings.base_url, draft.version, f.path
             ),
             sha256: f.sha256.clone(),
             size: f.size,
+            mirrors: Vec::new(),
         })
         .collect();
 
@@ -342,52 +703,36 @@ pub async fn publish_draft(
     }
 
     // Create manifest
-    let manifest = Manifest {
+    task.stage("Writing manifest", None);
+    let mut manifest = Manifest {
         version: draft.version.clone(),
         minecraft_version: draft.minecraft_version.clone(),
+        loader: draft.loader,
         fabric_loader: draft.fabric_loader.clone(),
         files: manifest_files,
         changelog: draft.changelog.clone(),
         ignore_patterns: blacklist_patterns,
+        channel: draft.channel,
+        optional_groups: draft.optional_groups.clone(),
+        ignore_overrides: draft.ignore_overrides.clone(),
     };
+    manifest.prune_optional_groups();
 
-    // Write manifest
-    storage::manifest::write_manifest(&state.config, &manifest).await?;
+    // Write manifest, then make the release visible
+    storage::manifest::write_manifest_to(&staging_dir, &manifest).await?;
+    storage::publish::commit_staging(&state.config, &draft.version).await?;
 
-    // Set as latest
-    storage::manifest::set_latest_manifest(&state.config, &draft.version).await?;
-
-    // Invalidate cache after publishing
-    state.cache.invalidate_manifest("latest").await;
-    state
-        .cache
-        .invalidate_manifest(&format!("version:{}", draft.version))
-        .await;
-
-    // Delete draft
-    storage::delete_draft(&state.config.storage_path(), id).await?;
-
-    let duration = start.elapsed();
-    tracing::info!(
-        "publish_draft completed in {:?} (version: {}, {} files)",
-        duration,
-        draft.version,
-        manifest.files.len()
-    );
-
-    Ok(Json(json!({
-        "message": "Draft published successfully",
-        "version": draft.version,
-        "file_count": manifest.files.len()
-    })))
+    Ok(manifest)
 }
 
 /// POST /api/admin/drafts/:id/duplicate - Duplicate a draft with all files
 pub async fn duplicate_draft(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<DraftRelease>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     let source_draft = storage::read_draft(&state.config.storage_path(), id).await?;
 
     // Create new draft with copied metadata
@@ -400,15 +745,18 @@ pub async fn duplicate_draft(
     let new_draft = storage::create_draft(&state.config.storage_path(), new_version).await?;
 
     // Copy metadata
-    let updated_draft = storage::update_draft(
+    storage::update_draft(
         &state.config.storage_path(),
         new_draft.id,
         Some(new_draft.version.clone()),
         Some(source_draft.minecraft_version.clone()),
         Some(source_draft.fabric_loader.clone()),
         Some(source_draft.changelog.clone()),
+        Some(source_draft.channel),
     )
     .await?;
+    let updated_draft =
+        storage::set_draft_loader(state.config.storage_path(), new_draft.id, source_draft.loader).await?;
 
     // Copy files if source draft has any
     if !source_draft.files.is_empty() {
@@ -417,10 +765,10 @@ pub async fn duplicate_draft(
             storage::get_draft_files_dir(&state.config.storage_path(), new_draft.id);
 
         // Copy all files from source to destination
-        copy_dir_all(&source_files_dir, &dest_files_dir).await?;
+        copy_dir_all(&source_files_dir, &dest_files_dir, None).await?;
 
         // Regenerate checksums from copied files instead of copying old checksums
-        let fresh_files = scan_directory_files(&dest_files_dir).await?;
+        let fresh_files = scan_directory_files(&dest_files_dir, None).await?;
 
         // Set files in draft with fresh checksums (replaces, not appends)
         let updated_draft =
@@ -485,10 +833,12 @@ pub struct MoveRequest {
 /// GET /api/admin/drafts/:id/browse?path=... - Browse directory contents
 pub async fn browse_directory(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(id): Path<Uuid>,
     Query(query): Query<BrowseQuery>,
 ) -> Result<Json<BrowseResponse>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
     let draft_files_dir = storage::get_draft_files_dir(&state.config.storage_path(), id);
 
     // Get target directory
@@ -577,10 +927,12 @@ pub async fn browse_directory(
 /// GET /api/admin/drafts/:id/read-file?path=... - Read text file contents
 pub async fn read_file_content(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(id): Path<Uuid>,
     Query(query): Query<ReadFileQuery>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
     let draft_files_dir = storage::get_draft_files_dir(&state.config.storage_path(), id);
     let file_path = draft_files_dir.join(&query.path);
 
@@ -617,10 +969,16 @@ pub async fn read_file_content(
 /// PUT /api/admin/drafts/:id/write-file - Write/update text file contents
 pub async fn write_file_content(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(request): Json<WriteFileRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    // Held until the file list is updated, so a concurrent edit can't slip in between
+    let mut edit = storage::drafts::edit_draft(state.config.storage_path(), id, expected_revision(&headers)?).await?;
+
     let draft_files_dir = storage::get_draft_files_dir(&state.config.storage_path(), id);
     let file_path = draft_files_dir.join(&request.path);
 
@@ -645,8 +1003,9 @@ pub async fn write_file_content(
         return Err(AppError::BadRequest("Invalid file path".to_string()));
     }
 
-    // Write file
-    fs::write(&file_path, request.content.as_bytes())
+    // Write file; a crash mid-write leaves the old contents rather than a truncated file,
+    // and a file that's still linked to a release's object is replaced, not edited in place
+    storage::drafts::write_atomic(&file_path, request.content.as_bytes())
         .await
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to write file: {}", e)))?;
 
@@ -664,33 +1023,32 @@ pub async fn write_file_content(
     };
 
     // Try to update existing file or add new one
-    let draft = storage::read_draft(&state.config.storage_path(), id).await?;
-    let mut files = draft.files;
-
-    if let Some(existing) = files.iter_mut().find(|f| f.path == request.path) {
+    if let Some(existing) = edit.draft.files.iter_mut().find(|f| f.path == request.path) {
         existing.sha256 = sha256.clone();
         existing.size = data.len() as u64;
     } else {
-        files.push(draft_file);
+        edit.draft.files.push(draft_file);
     }
-
-    storage::add_files_to_draft(&state.config.storage_path(), id, files).await?;
+    let draft = edit.commit().await?;
 
     Ok(Json(json!({
         "message": "File saved successfully",
         "path": request.path,
         "size": data.len(),
-        "sha256": sha256
+        "sha256": sha256,
+        "revision": draft.revision
     })))
 }
 
 /// POST /api/admin/drafts/:id/create-dir - Create new directory
 pub async fn create_directory(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(id): Path<Uuid>,
     Json(request): Json<CreateDirectoryRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
     let draft_files_dir = storage::get_draft_files_dir(&state.config.storage_path(), id);
     let new_dir = draft_files_dir.join(&request.path);
 
@@ -726,10 +1084,15 @@ pub async fn create_directory(
 /// POST /api/admin/drafts/:id/rename - Rename file or directory
 pub async fn rename_file(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(request): Json<RenameRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let mut edit = storage::drafts::edit_draft(state.config.storage_path(), id, expected_revision(&headers)?).await?;
+
     let draft_files_dir = storage::get_draft_files_dir(&state.config.storage_path(), id);
     let old_path = draft_files_dir.join(&request.old_path);
 
@@ -758,24 +1121,21 @@ pub async fn rename_file(
 
     // Update draft file list if it's a file
     if new_path.is_file() {
-        let draft = storage::read_draft(&state.config.storage_path(), id).await?;
-        let mut files = draft.files;
-
         let new_relative_path = new_path
             .strip_prefix(&draft_files_dir)
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_else(|_| request.new_name.clone());
 
-        if let Some(file) = files.iter_mut().find(|f| f.path == request.old_path) {
+        if let Some(file) = edit.draft.files.iter_mut().find(|f| f.path == request.old_path) {
             file.path = new_relative_path.clone();
         }
-
-        storage::add_files_to_draft(&state.config.storage_path(), id, files).await?;
+        let draft = edit.commit().await?;
 
         Ok(Json(json!({
             "message": "File renamed successfully",
             "old_path": request.old_path,
-            "new_path": new_relative_path
+            "new_path": new_relative_path,
+            "revision": draft.revision
         })))
     } else {
         Ok(Json(json!({
@@ -789,10 +1149,15 @@ pub async fn rename_file(
 /// POST /api/admin/drafts/:id/move - Move file to different directory
 pub async fn move_file(
     State(state): State<AdminState>,
-    Extension(_token): Extension<AdminToken>,
+    Extension(token): Extension<AdminToken>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(request): Json<MoveRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let mut edit = storage::drafts::edit_draft(state.config.storage_path(), id, expected_revision(&headers)?).await?;
+
     let draft_files_dir = storage::get_draft_files_dir(&state.config.storage_path(), id);
     let source_path = draft_files_dir.join(&request.source_path);
     let dest_path = draft_files_dir.join(&request.dest_path);
@@ -822,24 +1187,42 @@ pub async fn move_file(
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to move: {}", e)))?;
 
     // Update draft file list
-    let draft = storage::read_draft(&state.config.storage_path(), id).await?;
-    let mut files = draft.files;
-
-    if let Some(file) = files.iter_mut().find(|f| f.path == request.source_path) {
+    if let Some(file) = edit.draft.files.iter_mut().find(|f| f.path == request.source_path) {
         file.path = request.dest_path.clone();
     }
-
-    storage::add_files_to_draft(&state.config.storage_path(), id, files).await?;
+    let draft = edit.commit().await?;
 
     Ok(Json(json!({
         "message": "File moved successfully",
         "source_path": request.source_path,
-        "dest_path": request.dest_path
+        "dest_path": request.dest_path,
+        "revision": draft.revision
     })))
 }
 
 // Helper functions
 
+/// Revision an edit is based on, from `If-Match`; without the header the edit always applies
+fn expected_revision(headers: &HeaderMap) -> Result<Option<u64>, AppError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value
+        .to_str()
+        .map_err(|_| AppError::BadRequest("Invalid If-Match header".to_string()))?
+        .trim();
+    if value == "*" {
+        return Ok(None);
+    }
+    // Accept ETag-style quoting as well as a bare number
+    value
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .parse()
+        .map(Some)
+        .map_err(|_| AppError::BadRequest("If-Match must be a draft revision".to_string()))
+}
+
 async fn scan_upload_files(
     upload_dir: &PathBuf,
     base_url: &str,
@@ -896,43 +1279,61 @@ async fn scan_upload_files(
     Ok(files)
 }
 
-async fn copy_dir_all(src: &PathBuf, dst: &PathBuf) -> Result<(), AppError> {
+pub(crate) async fn copy_dir_all(src: &PathBuf, dst: &PathBuf, task: Option<&TaskHandle>) -> Result<(), AppError> {
     fs::create_dir_all(dst)
         .await
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create directory: {}", e)))?;
 
-    for entry in WalkDir::new(src).into_iter().filter_map(|e| e.ok()) {
+    let entries: Vec<_> = WalkDir::new(src)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .collect();
+
+    if let Some(task) = task {
+        task.stage("Copying files", Some(entries.len() as u64));
+    }
+
+    for (index, entry) in entries.iter().enumerate() {
         let path = entry.path();
-        if path.is_file() {
-            let relative = path
-                .strip_prefix(src)
-                .map_err(|e| AppError::Internal(anyhow::anyhow!("Path error: {}", e)))?;
-
-            let dest_path = dst.join(relative);
-            if let Some(parent) = dest_path.parent() {
-                fs::create_dir_all(parent).await.map_err(|e| {
-                    AppError::Internal(anyhow::anyhow!("Failed to create parent directory: {}", e))
-                })?;
-            }
+        let relative = path
+            .strip_prefix(src)
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Path error: {}", e)))?;
+
+        if let Some(task) = task {
+            task.progress(index as u64 + 1, relative.to_string_lossy());
+        }
 
-            fs::copy(path, &dest_path)
-                .await
-                .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to copy file: {}", e)))?;
+        let dest_path = dst.join(relative);
+        if let Some(parent) = dest_path.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| {
+                AppError::Internal(anyhow::anyhow!("Failed to create parent directory: {}", e))
+            })?;
         }
+
+        storage::files::copy_detached(path, &dest_path)
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to copy file: {}", e)))?;
     }
 
     Ok(())
 }
 
 /// Scan a directory and generate DraftFile entries with fresh SHA256 checksums
-async fn scan_directory_files(dir: &PathBuf) -> Result<Vec<DraftFile>, AppError> {
+pub(crate) async fn scan_directory_files(dir: &PathBuf, task: Option<&TaskHandle>) -> Result<Vec<DraftFile>, AppError> {
     let mut files = Vec::new();
 
-    for entry in WalkDir::new(dir)
+    let entries: Vec<_> = WalkDir::new(dir)
         .into_iter()
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
-    {
+        .collect();
+
+    if let Some(task) = task {
+        task.stage("Computing checksums", Some(entries.len() as u64));
+    }
+
+    for (index, entry) in entries.iter().enumerate() {
         let path = entry.path();
         let relative_path = path
             .strip_prefix(dir)
@@ -943,6 +1344,10 @@ async fn scan_directory_files(dir: &PathBuf) -> Result<Vec<DraftFile>, AppError>
             .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Invalid path encoding")))?;
         let relative_str = relative_str.replace('\\', "/");
 
+        if let Some(task) = task {
+            task.progress(index as u64 + 1, relative_str.as_str());
+        }
+
         // Skip manifest.json - it's generated, not part of the modpack files
         if relative_str == "manifest.json" {
             continue;
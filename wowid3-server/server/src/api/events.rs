@@ -0,0 +1,88 @@
+use crate::api::public::PublicState;
+use crate::cache::CacheEvent;
+use axum::{
+    extract::State,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures_util::stream::{self, Stream, StreamExt};
+use serde_json::json;
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Keeps idle connections open through proxies that drop silent streams
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(25);
+
+/// Launchers reconnect after this long if the stream drops
+const RETRY_AFTER: Duration = Duration::from_secs(10);
+
+/// The SSE event name and payload launchers see for a cache event
+///
+/// `release` means a modpack manifest may have changed, `launcher` that a new
+/// launcher build is available, `whitelist` that the allowed players changed,
+/// `resourcepacks` that the server resource pack changed.
+/// Blacklist changes don't alter manifests, so they are not forwarded.
+fn server_event(event: &CacheEvent) -> Option<(&'static str, serde_json::Value)> {
+    match event {
+        CacheEvent::ReleaseWritten { version } => Some(("release", json!({ "version": version }))),
+        CacheEvent::ReleaseDeleted { version } => {
+            Some(("release", json!({ "version": version, "deleted": true })))
+        }
+        CacheEvent::LauncherReleased { version } => Some(("launcher", json!({ "version": version }))),
+        // The game server's tracker refetches /api/tracker/whitelist
+        CacheEvent::WhitelistChanged => Some(("whitelist", json!({}))),
+        // The game server updates the resource-pack-sha1 it advertises
+        CacheEvent::ResourcePacksChanged => Some(("resourcepacks", json!({}))),
+        CacheEvent::BlacklistChanged => None,
+    }
+}
+
+/// GET /api/events - Server-Sent Events for release and launcher publishes
+///
+/// Launchers check for updates when notified instead of polling the manifest.
+/// A subscriber that falls behind gets a `release` event without a version,
+/// which means "re-check everything".
+pub async fn server_events(
+    State(state): State<PublicState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let receiver = state.cache.subscribe();
+
+    let hello = Event::default().event("connected").data("{}").retry(RETRY_AFTER);
+    let events = stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            let event = match receiver.recv().await {
+                Ok(event) => match server_event(&event) {
+                    Some((name, data)) => Event::default().event(name).data(data.to_string()),
+                    None => continue,
+                },
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::debug!("Event subscriber lagged by {} events", skipped);
+                    Event::default().event("release").data("{}")
+                }
+                Err(RecvError::Closed) => return None,
+            };
+            return Some((Ok(event), receiver));
+        }
+    });
+
+    Sse::new(stream::once(async move { Ok(hello) }).chain(events))
+        .keep_alive(KeepAlive::new().interval(KEEP_ALIVE_INTERVAL))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_event_mapping() {
+        let (name, data) = server_event(&CacheEvent::ReleaseWritten { version: "1.2.0".to_string() }).unwrap();
+        assert_eq!(name, "release");
+        assert_eq!(data, json!({ "version": "1.2.0" }));
+
+        let (name, data) = server_event(&CacheEvent::LauncherReleased { version: "2.0.0".to_string() }).unwrap();
+        assert_eq!(name, "launcher");
+        assert_eq!(data["version"], "2.0.0");
+
+        assert!(server_event(&CacheEvent::BlacklistChanged).is_none());
+    }
+}
@@ -0,0 +1,179 @@
+use crate::database::store::StatsStore;
+use crate::database::Database;
+use anyhow::Result;
+use axum::{extract::State, http::StatusCode, Json};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const SERVICE_NAME: &str = "wowid3-modpack-server";
+
+/// A check that hasn't answered by now counts as failed (e.g. a hung network mount)
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Clone)]
+pub struct HealthState {
+    pub storage_path: PathBuf,
+    pub db: Database,
+    /// Checked as well when player stats live outside the SQLite database
+    pub external_stats: Option<Arc<dyn StatsStore>>,
+    /// BlueMap web root, checked when BLUEMAP_HEALTH_CHECK is set
+    pub bluemap_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckResult {
+    /// "ok" or "fail"
+    pub status: &'static str,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl CheckResult {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadinessResponse {
+    /// "ok" when every check passed, "fail" otherwise
+    pub status: &'static str,
+    pub service: &'static str,
+    pub checks: BTreeMap<&'static str, CheckResult>,
+}
+
+/// GET /health, /health/live - The process is up and serving requests
+pub async fn health_live() -> Json<Value> {
+    Json(json!({
+        "status": "ok",
+        "service": SERVICE_NAME
+    }))
+}
+
+/// GET /health/ready - Whether the server's dependencies are usable
+///
+/// Responds 503 when any check fails, so load balancers stop routing to it.
+pub async fn health_ready(State(state): State<HealthState>) -> (StatusCode, Json<ReadinessResponse>) {
+    let response = readiness(&state).await;
+    let status = if response.status == "ok" {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(response))
+}
+
+/// Run every configured check concurrently
+pub async fn readiness(state: &HealthState) -> ReadinessResponse {
+    let (storage, database, postgres, bluemap) = tokio::join!(
+        run_check(check_storage(&state.storage_path)),
+        run_check(state.db.ping()),
+        async {
+            match &state.external_stats {
+                Some(stats) => Some(run_check(stats.ping()).await),
+                None => None,
+            }
+        },
+        async {
+            match &state.bluemap_path {
+                Some(path) => Some(run_check(check_bluemap(path)).await),
+                None => None,
+            }
+        },
+    );
+
+    let mut checks = BTreeMap::from([("storage", storage), ("database", database)]);
+    if let Some(postgres) = postgres {
+        checks.insert("postgres", postgres);
+    }
+    if let Some(bluemap) = bluemap {
+        checks.insert("bluemap", bluemap);
+    }
+
+    let healthy = checks.values().all(CheckResult::is_ok);
+    for (name, check) in checks.iter().filter(|(_, check)| !check.is_ok()) {
+        tracing::warn!("Readiness check {} failed: {}", name, check.error.as_deref().unwrap_or_default());
+    }
+
+    ReadinessResponse {
+        status: if healthy { "ok" } else { "fail" },
+        service: SERVICE_NAME,
+        checks,
+    }
+}
+
+async fn run_check(check: impl Future<Output = Result<()>>) -> CheckResult {
+    let started = Instant::now();
+    let error = match tokio::time::timeout(CHECK_TIMEOUT, check).await {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some(e.to_string()),
+        Err(_) => Some(format!("timed out after {}s", CHECK_TIMEOUT.as_secs())),
+    };
+    CheckResult {
+        status: if error.is_none() { "ok" } else { "fail" },
+        latency_ms: started.elapsed().as_millis() as u64,
+        error,
+    }
+}
+
+/// Write and remove a scratch file, as uploads and drafts need to
+async fn check_storage(storage_path: &std::path::Path) -> Result<()> {
+    let probe = storage_path.join(format!(".health-{}", uuid::Uuid::new_v4()));
+    tokio::fs::write(&probe, b"ok").await?;
+    tokio::fs::remove_file(&probe).await?;
+    Ok(())
+}
+
+/// BlueMap's global settings are the first file the web app loads
+async fn check_bluemap(base_path: &std::path::Path) -> Result<()> {
+    let settings = base_path.join("settings.json");
+    tokio::fs::metadata(&settings)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}: {}", settings.display(), e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_readiness_checks() {
+        let dir = tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db")).await.unwrap();
+        let mut state = HealthState {
+            storage_path: dir.path().to_path_buf(),
+            db,
+            external_stats: None,
+            bluemap_path: None,
+        };
+
+        let ready = readiness(&state).await;
+        assert_eq!(ready.status, "ok");
+        assert_eq!(ready.checks.keys().copied().collect::<Vec<_>>(), vec!["database", "storage"]);
+        // The probe file is cleaned up
+        let leftovers = std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter(|e| e.as_ref().unwrap().file_name().to_string_lossy().starts_with(".health-"))
+            .count();
+        assert_eq!(leftovers, 0);
+
+        state.storage_path = dir.path().join("missing");
+        state.bluemap_path = Some(dir.path().to_path_buf());
+        let ready = readiness(&state).await;
+        assert_eq!(ready.status, "fail");
+        assert!(!ready.checks["storage"].is_ok());
+        assert!(ready.checks["database"].is_ok());
+        assert!(ready.checks["bluemap"].error.as_deref().unwrap().contains("settings.json"));
+
+        std::fs::write(dir.path().join("settings.json"), "{}").unwrap();
+        assert!(readiness(&state).await.checks["bluemap"].is_ok());
+    }
+}
@@ -0,0 +1,40 @@
+use crate::api::admin::{require_role, AdminState, AppError};
+use crate::database::jobs::JobRecord;
+use crate::middleware::AdminToken;
+use crate::models::AdminRole;
+use crate::services::tasks::TaskStatus;
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Job record plus live progress while the job is still tracked in memory
+#[derive(Serialize)]
+pub struct JobResponse {
+    #[serde(flatten)]
+    pub job: JobRecord,
+    pub progress: Option<TaskStatus>,
+}
+
+/// GET /api/admin/jobs/:id - Status and result of a queued job
+pub async fn get_job(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<JobResponse>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let job = state
+        .jobs
+        .get(id)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read job: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Job {} not found", id)))?;
+
+    Ok(Json(JobResponse {
+        job,
+        progress: state.tasks.get(id),
+    }))
+}
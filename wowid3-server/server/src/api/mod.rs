@@ -1,5 +1,21 @@
 pub mod admin;
+pub mod admin_users;
+pub mod audit;
 pub mod bluemap;
+pub mod crashes;
+pub mod curseforge;
+pub mod download_tokens;
 pub mod drafts;
+pub mod events;
+pub mod health;
+pub mod jobs;
+pub mod modrinth;
 pub mod public;
+pub mod resourcepacks;
+pub mod screenshots;
+pub mod support;
+pub mod tasks;
+pub mod telemetry;
 pub mod tracker;
+pub mod usage;
+pub mod whitelist;
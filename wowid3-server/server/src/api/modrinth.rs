@@ -0,0 +1,192 @@
+use crate::api::admin::{require_role, AdminState, AppError};
+use crate::middleware::AdminToken;
+use crate::models::{AdminRole, DraftFile, DraftRelease};
+use crate::services::modrinth::{ModrinthClient, ModrinthSearchHit};
+use crate::storage;
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Drafts only ship Fabric modpacks
+const LOADER: &str = "fabric";
+
+#[derive(Debug, Deserialize)]
+pub struct ModrinthSearchQuery {
+    pub query: String,
+    #[serde(default = "default_search_limit")]
+    pub limit: usize,
+}
+
+fn default_search_limit() -> usize { 20 }
+
+#[derive(Debug, Deserialize)]
+pub struct AddModrinthModRequest {
+    /// Project id or slug
+    pub project: String,
+    /// Specific version id; defaults to the newest compatible version
+    #[serde(default)]
+    pub version_id: Option<String>,
+    #[serde(default = "default_true")]
+    pub include_dependencies: bool,
+}
+
+fn default_true() -> bool { true }
+
+/// A jar placed in the draft from Modrinth
+#[derive(Debug, Serialize)]
+pub struct InstalledModrinthMod {
+    pub project_id: String,
+    pub version_id: String,
+    pub version_number: String,
+    pub path: String,
+    pub sha256: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AddModrinthModResponse {
+    pub draft: DraftRelease,
+    pub installed: Vec<InstalledModrinthMod>,
+    /// Jars already present in the draft's mods folder
+    pub skipped: Vec<String>,
+    /// Required dependencies with no compatible version
+    pub unresolved_dependencies: Vec<String>,
+}
+
+fn draft_minecraft_version(draft: &DraftRelease) -> Result<&str, AppError> {
+    if draft.minecraft_version.is_empty() {
+        return Err(AppError::BadRequest(
+            "Set the draft's Minecraft version before adding mods from Modrinth".to_string(),
+        ));
+    }
+    Ok(&draft.minecraft_version)
+}
+
+fn modrinth_client() -> Result<ModrinthClient, AppError> {
+    ModrinthClient::new().map_err(AppError::Internal)
+}
+
+/// GET /api/admin/drafts/:id/mods/modrinth?query=... - Search Modrinth for mods compatible with the draft
+pub async fn search_modrinth_mods(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ModrinthSearchQuery>,
+) -> Result<Json<Vec<ModrinthSearchHit>>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let draft = storage::read_draft(state.config.storage_path(), id).await?;
+    let minecraft_version = draft_minecraft_version(&draft)?;
+
+    let hits = modrinth_client()?
+        .search(&query.query, minecraft_version, LOADER, query.limit.clamp(1, 100))
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Modrinth search failed: {}", e)))?;
+
+    Ok(Json(hits))
+}
+
+/// POST /api/admin/drafts/:id/mods/modrinth - Download a Modrinth mod (and its dependencies) into the draft
+pub async fn add_modrinth_mod(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<AddModrinthModRequest>,
+) -> Result<Json<AddModrinthModResponse>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let draft = storage::read_draft(state.config.storage_path(), id).await?;
+    let minecraft_version = draft_minecraft_version(&draft)?.to_string();
+    let client = modrinth_client()?;
+
+    let root = match &request.version_id {
+        Some(version_id) => client
+            .get_version(version_id)
+            .await
+            .map_err(|e| AppError::NotFound(e.to_string()))?,
+        None => client
+            .compatible_versions(&request.project, &minecraft_version, LOADER)
+            .await
+            .map_err(|e| AppError::NotFound(e.to_string()))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "{} has no {} version for Minecraft {}",
+                    request.project, LOADER, minecraft_version
+                ))
+            })?,
+    };
+
+    let (versions, unresolved_dependencies) = if request.include_dependencies {
+        client
+            .resolve_with_dependencies(root, &minecraft_version, LOADER)
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to resolve dependencies: {}", e)))?
+    } else {
+        (vec![root], Vec::new())
+    };
+
+    let draft_files_dir = storage::get_draft_files_dir(state.config.storage_path(), id);
+    let mut installed = Vec::new();
+    let mut skipped = Vec::new();
+    let mut new_files = Vec::new();
+
+    for version in versions {
+        let Some(file) = version.primary_file() else {
+            tracing::warn!("Modrinth version {} has no files", version.id);
+            continue;
+        };
+
+        if file.filename.contains(['/', '\\']) || file.filename.contains("..") {
+            return Err(AppError::BadRequest(format!(
+                "Refusing suspicious file name from Modrinth: {}",
+                file.filename
+            )));
+        }
+
+        let relative = format!("mods/{}", file.filename);
+        if draft.files.iter().any(|f| f.path == relative) {
+            skipped.push(relative);
+            continue;
+        }
+
+        let sha256 = client
+            .download(file, &draft_files_dir.join(&relative))
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to download {}: {}", file.filename, e)))?;
+
+        new_files.push(DraftFile {
+            path: relative.clone(),
+            url: None, // URLs are generated when publishing
+            sha256: sha256.clone(),
+            size: file.size,
+        });
+        installed.push(InstalledModrinthMod {
+            project_id: version.project_id.clone(),
+            version_id: version.id.clone(),
+            version_number: version.version_number.clone(),
+            path: relative,
+            sha256,
+        });
+    }
+
+    let draft = storage::add_files_to_draft(state.config.storage_path(), id, new_files, None).await?;
+
+    tracing::info!(
+        "Admin {} added {} Modrinth jar(s) to draft {} ({} skipped)",
+        token.subject(),
+        installed.len(),
+        id,
+        skipped.len()
+    );
+
+    Ok(Json(AddModrinthModResponse {
+        draft,
+        installed,
+        skipped,
+        unresolved_dependencies,
+    }))
+}
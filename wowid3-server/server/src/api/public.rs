@@ -1,21 +1,26 @@
-use crate::config::Config;
-use crate::models::{Manifest, manifest::{LauncherVersion, LauncherVersionsIndex}, TrackerState};
+use crate::cache::{latest_manifest_key, version_manifest_key};
+use crate::config::{Config, LiveSettings};
+use crate::middleware::usage::ServedReleaseFile;
+use crate::models::{Manifest, manifest::{LauncherVersion, LauncherVersionsIndex}, ReleaseChannel, TrackerState};
 use crate::storage;
 use crate::utils;
+use crate::utils::compression::PrecompressedBody;
+use crate::utils::http::{etag_matches, parse_byte_range, ByteRange};
+use crate::utils::safe_path::{self, PathRejection, ServedDir};
 use anyhow;
 use axum::{
-    body::Body,
-    extract::{Path, State},
-    http::{header, StatusCode},
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Redirect, Response},
     Json,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sha2::Digest;
 use std::sync::Arc;
 use tokio::fs;
 use tokio::sync::RwLock;
-use tokio::io::AsyncReadExt;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
 use tokio_util::io::ReaderStream;
 
 #[derive(Debug, Serialize, Clone)]
@@ -25,16 +30,28 @@ pub struct ResourcePackInfo {
     pub sha256: String,
 }
 
+use crate::services::minecraft_auth::ProfileVerifier;
+use crate::services::signing::{Signer, MANIFEST_SIGNATURE_HEADER};
 use crate::services::stats_processor::StatsProcessor;
-use crate::database::Database;
+use crate::database::{store::StatsStore, Database};
 
 #[derive(Clone)]
 pub struct PublicState {
     pub config: Arc<Config>,
+    /// Settings a reload can change (BASE_URL decides which file URLs get mirrors)
+    pub settings: LiveSettings,
     pub cache: crate::cache::CacheManager,
     pub tracker: Arc<RwLock<TrackerState>>,
     pub db: Database,
     pub stats_processor: Arc<StatsProcessor>,
+    /// Player stats, leaderboards and advancements (SQLite or Postgres)
+    pub stats: Arc<dyn StatsStore>,
+    pub storage: Arc<dyn storage::backend::StorageBackend>,
+    pub profile_verifier: ProfileVerifier,
+    /// Issues the tokens DOWNLOAD_AUTH requires on manifest and file routes
+    pub download_tokens: Arc<crate::services::download_tokens::DownloadTokens>,
+    /// Signs manifest responses so launchers can reject tampered ones
+    pub manifest_signer: Option<Arc<Signer>>,
 }
 
 /// Helper: Serve launcher file by platform and file type
@@ -69,11 +86,10 @@ async fn serve_launcher_file_by_type(
         })?;
 
     // Get file path
-    let file_path = state.config.launcher_version_path(&version.version).join(&file.filename);
-
-    if !file_path.exists() {
-        return Err(AppError::NotFound(format!("File not found: {}", file.filename)));
-    }
+    let file_path = ServedDir::new(state.config.launcher_version_path(&version.version), state.config.serve_symlinks)
+        .resolve(safe_path::segment(&file.filename)?)
+        .await
+        .map_err(|_| AppError::NotFound(format!("File not found: {}", file.filename)))?;
 
     // Stream file
     let file_handle = fs::File::open(&file_path)
@@ -168,24 +184,26 @@ pub async fn serve_launcher_file(
     Path(filename): Path<String>,
 ) -> Result<Response, AppError> {
     // Security: Only allow specific launcher filenames
-    let allowed_files = vec!["WOWID3Launcher.exe"];
+    let allowed_files = ["WOWID3Launcher.exe", "WOWID3Launcher.exe.sig"];
 
     if !allowed_files.contains(&filename.as_str()) {
         return Err(AppError::NotFound(format!("File {} not found", filename)));
     }
 
-    let launcher_dir = state.config.launcher_path();
-    let file_path = launcher_dir.join(&filename);
-
-    if !file_path.exists() {
-        return Err(AppError::NotFound(format!("File {} not found", filename)));
-    }
+    let file_path = ServedDir::new(state.config.launcher_path(), state.config.serve_symlinks)
+        .resolve(&filename)
+        .await
+        .map_err(|_| AppError::NotFound(format!("File {} not found", filename)))?;
 
     let file = fs::File::open(&file_path).await.map_err(|_| {
         AppError::NotFound(format!("Could not open file: {}", filename))
     })?;
 
-    let content_type = "application/vnd.microsoft.portable-executable";
+    let content_type = if filename.ends_with(".sig") {
+        "text/plain"
+    } else {
+        "application/vnd.microsoft.portable-executable"
+    };
 
     let stream = ReaderStream::new(file);
     let body = Body::from_stream(stream);
@@ -203,31 +221,33 @@ pub async fn serve_versioned_launcher_file(
     State(state): State<PublicState>,
     Path((version, filename)): Path<(String, String)>,
 ) -> Result<Response, AppError> {
-    // Security: Validate filename format and extension
-    let allowed_extensions = vec![".exe", ".AppImage"];
+    // Security: Validate filename format and extension (binaries and their detached signatures)
+    let allowed_extensions = [".exe", ".AppImage", ".exe.sig", ".AppImage.sig"];
     let has_allowed_ext = allowed_extensions.iter().any(|ext| filename.ends_with(ext));
 
     if !has_allowed_ext {
         return Err(AppError::NotFound(format!("File {} not found", filename)));
     }
 
-    // Validate filename doesn't contain path traversal attempts
-    if filename.contains("..") || filename.contains('/') || filename.contains('\\') {
-        return Err(AppError::NotFound("Invalid filename".to_string()));
-    }
-
-    let file_path = state.config.launcher_version_file_path(&version, &filename);
-
-    if !file_path.exists() {
-        return Err(AppError::NotFound(format!("File {} for version {} not found", filename, version)));
-    }
+    let dir = state.config.launcher_version_path(safe_path::segment(&version)?);
+    let file_path = ServedDir::new(dir, state.config.serve_symlinks)
+        .resolve(safe_path::segment(&filename)?)
+        .await
+        .map_err(|rejection| match rejection {
+            PathRejection::NotFound => {
+                AppError::NotFound(format!("File {} for version {} not found", filename, version))
+            }
+            other => other.into(),
+        })?;
 
     let file = fs::File::open(&file_path).await.map_err(|_| {
         AppError::NotFound(format!("Could not open file: {}", filename))
     })?;
 
     // Determine content type based on extension
-    let content_type = if filename.ends_with(".exe") {
+    let content_type = if filename.ends_with(".sig") {
+        "text/plain"
+    } else if filename.ends_with(".exe") {
         "application/vnd.microsoft.portable-executable"
     } else if filename.ends_with(".AppImage") {
         "application/x-executable"
@@ -269,43 +289,186 @@ pub async fn get_launcher_version(
     Ok(Json(version_manifest))
 }
 
-/// GET /api/manifest/latest
+/// Add the configured mirror URLs to every file (mirrors aren't stored with releases,
+/// so changing MIRROR_BASE_URLS applies to old versions too)
+fn with_mirrors(mut manifest: Manifest, config: &Config, base_url: &str) -> Manifest {
+    if !config.mirror_base_urls.is_empty() {
+        for file in &mut manifest.files {
+            file.mirrors = config.mirror_urls(base_url, &file.url);
+        }
+    }
+    manifest
+}
+
+/// Query parameters for the latest manifest
+#[derive(Debug, Deserialize)]
+pub struct LatestManifestQuery {
+    #[serde(default)]
+    pub channel: ReleaseChannel,
+}
+
+/// GET /api/manifest/latest?channel=beta - Newest manifest for a release channel (default stable)
 pub async fn get_latest_manifest(
     State(state): State<PublicState>,
-) -> Result<Json<Manifest>, AppError> {
+    Query(query): Query<LatestManifestQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let cache_key = latest_manifest_key(query.channel);
+
     // Try to get from cache first
-    if let Some(manifest) = state.cache.get_manifest("latest").await {
-        return Ok(Json((*manifest).clone()));
+    if let Some(body) = state.cache.get_manifest_body(&cache_key).await {
+        return Ok(body.response(&headers, "application/json"));
+    }
+    if let Some(manifest) = state.cache.get_manifest(&cache_key).await {
+        return manifest_response(&state, cache_key, (*manifest).clone(), &headers).await;
     }
 
     // Cache miss - read from disk
-    let manifest = storage::read_latest_manifest(&state.config).await?;
+    let manifest = storage::manifest::read_latest_channel_manifest(&state.config, query.channel).await?;
 
     // Store in cache
-    state.cache.put_manifest("latest".to_string(), manifest.clone()).await;
+    state.cache.put_manifest(cache_key.clone(), manifest.clone()).await;
 
-    Ok(Json(manifest))
+    manifest_response(&state, cache_key, manifest, &headers).await
 }
 
 /// GET /api/manifest/:version
 pub async fn get_manifest_by_version(
     State(state): State<PublicState>,
     Path(version): Path<String>,
-) -> Result<Json<Manifest>, AppError> {
-    let cache_key = format!("version:{}", version);
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let cache_key = version_manifest_key(&version);
 
     // Try to get from cache first
+    if let Some(body) = state.cache.get_manifest_body(&cache_key).await {
+        return Ok(body.response(&headers, "application/json"));
+    }
     if let Some(manifest) = state.cache.get_manifest(&cache_key).await {
-        return Ok(Json((*manifest).clone()));
+        return manifest_response(&state, cache_key, (*manifest).clone(), &headers).await;
     }
 
     // Cache miss - read from disk
     let manifest = storage::read_manifest(&state.config, &version).await?;
 
     // Store in cache
-    state.cache.put_manifest(cache_key, manifest.clone()).await;
+    state.cache.put_manifest(cache_key.clone(), manifest.clone()).await;
+
+    manifest_response(&state, cache_key, manifest, &headers).await
+}
+
+/// Serialize a manifest once, sign and precompress it (gzip and zstd) and cache the result
+///
+/// Every launcher polls the manifest, so compressing per request would cost more
+/// CPU than serving the (large) uncompressed JSON. The signature covers the exact
+/// JSON bytes, mirrors included, and travels in the `X-Manifest-Signature` header.
+async fn manifest_response(
+    state: &PublicState,
+    cache_key: String,
+    manifest: Manifest,
+    headers: &HeaderMap,
+) -> Result<Response, AppError> {
+    let json = serde_json::to_vec(&with_mirrors(manifest, &state.config, &state.settings.get().base_url))
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to serialize manifest: {}", e)))?;
+    let signature = state
+        .manifest_signer
+        .as_ref()
+        .map(|signer| signer.sign_digest(&sha2::Sha256::digest(&json)));
+    let body = tokio::task::spawn_blocking(move || PrecompressedBody::new(json))
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Compression task panicked: {}", e)))??;
+    let body = match signature {
+        Some(signature) => body.with_header(
+            MANIFEST_SIGNATURE_HEADER,
+            signature
+                .parse()
+                .map_err(|e| AppError::Internal(anyhow::anyhow!("Invalid manifest signature header: {}", e)))?,
+        ),
+        None => body,
+    };
+
+    let body = Arc::new(body);
+    state.cache.put_manifest_body(cache_key, Arc::clone(&body)).await;
+    Ok(body.response(headers, "application/json"))
+}
 
-    Ok(Json(manifest))
+/// Public release listing entry
+#[derive(Debug, Serialize, Clone)]
+pub struct PublicReleaseEntry {
+    pub version: String,
+    pub tags: Vec<String>,
+    pub latest: bool,
+}
+
+/// Query parameters for the public release listing
+#[derive(Debug, Deserialize)]
+pub struct ReleaseListQuery {
+    /// Only include releases carrying this tag
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Comma-separated tags to hide (e.g. "deprecated")
+    #[serde(default)]
+    pub exclude_tag: Option<String>,
+}
+
+/// GET /api/releases?tag=...&exclude_tag=... - List published modpack versions with tags
+pub async fn list_public_releases(
+    State(state): State<PublicState>,
+    Query(query): Query<ReleaseListQuery>,
+) -> Result<Json<Vec<PublicReleaseEntry>>, AppError> {
+    let mut versions = storage::manifest::list_versions(&state.config).await?;
+    let release_tags = storage::tags::load_release_tags(&state.config).await?;
+    let latest_version = storage::read_latest_manifest(&state.config)
+        .await
+        .ok()
+        .map(|m| m.version);
+
+    let required_tag = match query.tag.as_deref().filter(|t| !t.trim().is_empty()) {
+        Some(tag) => Some(
+            storage::tags::normalize_tag(tag).map_err(|e| AppError::BadRequest(e.to_string()))?,
+        ),
+        None => None,
+    };
+
+    let excluded_tags = query
+        .exclude_tag
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .filter(|t| !t.trim().is_empty())
+        .map(storage::tags::normalize_tag)
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    // Newest first
+    versions.sort_by(|a, b| match (semver::Version::parse(a), semver::Version::parse(b)) {
+        (Ok(va), Ok(vb)) => vb.cmp(&va),
+        _ => b.cmp(a),
+    });
+
+    let releases = versions
+        .into_iter()
+        .filter_map(|version| {
+            let tags = release_tags.get(&version).cloned().unwrap_or_default();
+
+            if let Some(required) = &required_tag {
+                if !tags.contains(required) {
+                    return None;
+                }
+            }
+            if tags.iter().any(|t| excluded_tags.contains(t)) {
+                return None;
+            }
+
+            Some(PublicReleaseEntry {
+                latest: latest_version.as_deref() == Some(version.as_str()),
+                version,
+                tags,
+            })
+        })
+        .collect();
+
+    Ok(Json(releases))
 }
 
 /// GET /api/assets/:filename
@@ -323,14 +486,10 @@ pub async fn serve_audio_file(
         return Err(AppError::NotFound(format!("Audio file {} not found", filename)));
     }
 
-    // Construct full file path
-    let assets_path = state.config.storage_path().join("assets");
-    let full_path = assets_path.join(&filename);
-
-    // Check if file exists
-    if !full_path.exists() {
-        return Err(AppError::NotFound(format!("Audio file {} not found", filename)));
-    }
+    let full_path = ServedDir::new(state.config.storage_path().join("assets"), state.config.serve_symlinks)
+        .resolve(&filename)
+        .await
+        .map_err(|_| AppError::NotFound(format!("Audio file {} not found", filename)))?;
 
     // Open and stream the file
     let file = fs::File::open(&full_path).await.map_err(|_| {
@@ -370,14 +529,10 @@ pub async fn serve_java_runtime(
         return Err(AppError::NotFound(format!("Java runtime {} not found", filename)));
     }
 
-    // Construct full file path
-    let java_path = state.config.storage_path().join("java");
-    let full_path = java_path.join(&filename);
-
-    // Check if file exists
-    if !full_path.exists() {
-        return Err(AppError::NotFound(format!("Java runtime {} not found", filename)));
-    }
+    let full_path = ServedDir::new(state.config.storage_path().join("java"), state.config.serve_symlinks)
+        .resolve(&filename)
+        .await
+        .map_err(|_| AppError::NotFound(format!("Java runtime {} not found", filename)))?;
 
     // Open and stream the file
     let file = fs::File::open(&full_path).await.map_err(|_| {
@@ -405,26 +560,46 @@ pub async fn serve_java_runtime(
 }
 
 /// GET /files/:version/*path
+///
+/// Supports single `Range` requests, `If-None-Match`/`If-Range` against the
+/// manifest sha256 (ETag), and long-lived caching for files the manifest knows.
+/// Small manifest files are kept in the hot-file cache and served from memory.
 pub async fn serve_file(
     State(state): State<PublicState>,
     Path((version, file_path)): Path<(String, String)>,
+    headers: HeaderMap,
 ) -> Result<Response, AppError> {
-    // Construct full file path
-    let release_path = state.config.release_path(&version);
-    let full_path = release_path.join(&file_path);
+    let mut response = release_file_response(&state, &version, &file_path, &headers).await?;
 
-    // Security: Ensure the file is within the release directory (prevent path traversal)
-    let canonical_release = fs::canonicalize(&release_path).await.map_err(|_| {
-        AppError::NotFound(format!("Release {} not found", version))
-    })?;
+    // Lets the usage middleware count downloads per file
+    response.extensions_mut().insert(ServedReleaseFile {
+        version,
+        path: file_path,
+    });
+    Ok(response)
+}
 
-    let canonical_file = fs::canonicalize(&full_path).await.map_err(|_| {
-        AppError::NotFound(format!("File {} not found", file_path))
-    })?;
+/// Cache policy for release files listed in a manifest
+///
+/// The URL names a version and path, not the content, and a version can be deleted and
+/// republished with different files, so caches must revalidate; the ETag makes that a 304.
+const RELEASE_FILE_CACHE_CONTROL: &str = "public, no-cache";
 
-    if !canonical_file.starts_with(&canonical_release) {
-        return Err(AppError::Forbidden("Path traversal attempt detected".to_string()));
-    }
+/// File contents, either from the hot-file cache or streamed from disk
+enum FileBody {
+    Memory(Bytes),
+    Disk(fs::File),
+}
+
+async fn release_file_response(
+    state: &PublicState,
+    version: &str,
+    file_path: &str,
+    headers: &HeaderMap,
+) -> Result<Response, AppError> {
+    // Reject traversal before the version or path reach the manifest lookup or cache
+    safe_path::segment(version)?;
+    safe_path::relative_path(file_path)?;
 
     // Check blacklist before serving
     let blacklist_patterns = utils::load_blacklist_patterns(&state.config)
@@ -434,31 +609,184 @@ pub async fn serve_file(
     let glob_set = utils::compile_patterns(&blacklist_patterns)
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to compile blacklist patterns: {}", e)))?;
 
-    if utils::is_blacklisted(&file_path, &glob_set) {
+    if utils::is_blacklisted(file_path, &glob_set) {
         tracing::debug!("Blocked access to blacklisted file: {}", file_path);
         return Err(AppError::Forbidden("File access denied".to_string()));
     }
 
-    // Open and stream the file
+    // Manifest files are downloaded from object storage when the backend has them
+    let known_file = release_file_entry(state, version, file_path).await;
+    if let Some((sha256, _)) = &known_file {
+        if let Some(url) = state.storage.file_url(sha256).await? {
+            return Ok(Redirect::temporary(&url).into_response());
+        }
+    }
+
+    // Only files listed in the manifest are cached, so a hit never needs the path checks below
+    let hot_sha256 = known_file
+        .as_ref()
+        .filter(|(_, size)| state.cache.is_hot_file_size(*size))
+        .map(|(sha256, _)| sha256.clone());
+    let cached = hot_sha256.as_deref().and_then(|sha256| state.cache.get_hot_file(sha256));
+
+    let (body, len, etag, cache_control) = match cached {
+        Some(bytes) => {
+            let len = bytes.len() as u64;
+            let sha256 = hot_sha256.as_deref().unwrap_or_default();
+            (FileBody::Memory(bytes), len, format!("\"{}\"", sha256), RELEASE_FILE_CACHE_CONTROL)
+        }
+        None => {
+            let (mut file, metadata) = open_release_file(state, version, file_path).await?;
+            let len = metadata.len();
+
+            // The manifest checksum makes a strong ETag. Files missing from the manifest
+            // fall back to a weak size/mtime tag.
+            let (etag, cache_control) = match &known_file {
+                Some((sha256, _)) => (format!("\"{}\"", sha256), RELEASE_FILE_CACHE_CONTROL),
+                None => {
+                    let modified = metadata
+                        .modified()
+                        .ok()
+                        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                        .map(|d| d.as_secs())
+                        .unwrap_or(0);
+                    (format!("W/\"{}-{}\"", len, modified), "public, max-age=300")
+                }
+            };
+
+            let body = match hot_sha256 {
+                Some(sha256) if state.cache.is_hot_file_size(len) => {
+                    let mut data = Vec::with_capacity(len as usize);
+                    file.read_to_end(&mut data).await.map_err(|e| {
+                        AppError::Internal(anyhow::anyhow!("Failed to read file: {}", e))
+                    })?;
+                    let bytes = Bytes::from(data);
+                    state.cache.put_hot_file(sha256, bytes.clone());
+                    FileBody::Memory(bytes)
+                }
+                _ => FileBody::Disk(file),
+            };
+            (body, len, etag, cache_control)
+        }
+    };
+
+    // Guess content type from the requested path (object store files have no extension)
+    let content_type = mime_guess::from_path(file_path)
+        .first_or_octet_stream()
+        .to_string();
+
+    let response = Response::builder()
+        .header(header::ETAG, &etag)
+        .header(header::CACHE_CONTROL, cache_control)
+        .header(header::ACCEPT_RANGES, "bytes");
+
+    // Conditional request: client already has this exact file
+    if let Some(if_none_match) = headers.get(header::IF_NONE_MATCH).and_then(|h| h.to_str().ok()) {
+        if etag_matches(if_none_match, &etag) {
+            return Ok(response
+                .status(StatusCode::NOT_MODIFIED)
+                .body(Body::empty())
+                .unwrap());
+        }
+    }
+
+    // Only honour Range when If-Range (if any) still matches the current file
+    let range_allowed = match headers.get(header::IF_RANGE).and_then(|h| h.to_str().ok()) {
+        Some(if_range) => known_file.is_some() && if_range.trim() == etag,
+        None => true,
+    };
+    let range = match headers.get(header::RANGE).and_then(|h| h.to_str().ok()) {
+        Some(range) if range_allowed => parse_byte_range(range, len),
+        _ => ByteRange::Full,
+    };
+
+    match range {
+        ByteRange::Full => {
+            let body = match body {
+                FileBody::Memory(bytes) => Body::from(bytes),
+                FileBody::Disk(file) => Body::from_stream(ReaderStream::new(file)),
+            };
+            Ok(response
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, content_type)
+                .header(header::CONTENT_LENGTH, len)
+                .body(body)
+                .unwrap())
+        }
+        ByteRange::Partial { start, end } => {
+            let part_len = end - start + 1;
+            let body = match body {
+                FileBody::Memory(bytes) => Body::from(bytes.slice(start as usize..=end as usize)),
+                FileBody::Disk(mut file) => {
+                    file.seek(std::io::SeekFrom::Start(start)).await.map_err(|e| {
+                        AppError::Internal(anyhow::anyhow!("Failed to seek file: {}", e))
+                    })?;
+                    Body::from_stream(ReaderStream::new(file.take(part_len)))
+                }
+            };
+
+            Ok(response
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header(header::CONTENT_TYPE, content_type)
+                .header(header::CONTENT_LENGTH, part_len)
+                .header(header::CONTENT_RANGE, format!("bytes {}-{}/{}", start, end, len))
+                .body(body)
+                .unwrap())
+        }
+        ByteRange::Unsatisfiable => Ok(response
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{}", len))
+            .body(Body::empty())
+            .unwrap()),
+    }
+}
+
+/// Open a file inside a release directory, rejecting paths that escape it
+async fn open_release_file(
+    state: &PublicState,
+    version: &str,
+    file_path: &str,
+) -> Result<(fs::File, std::fs::Metadata), AppError> {
+    // Release files may be symlinks into the object store
+    let canonical_file = ServedDir::new(state.config.release_path(safe_path::segment(version)?), state.config.serve_symlinks)
+        .allow_links_into(state.config.objects_path())
+        .resolve(file_path)
+        .await
+        .map_err(|rejection| match rejection {
+            PathRejection::NotFound => AppError::NotFound(format!("File {} not found", file_path)),
+            other => other.into(),
+        })?;
+
+    // Open the file
     let file = fs::File::open(&canonical_file).await.map_err(|_| {
         AppError::NotFound(format!("Could not open file: {}", file_path))
     })?;
 
-    // Guess content type from file extension
-    let content_type = mime_guess::from_path(&canonical_file)
-        .first_or_octet_stream()
-        .to_string();
+    let metadata = file.metadata().await.map_err(|e| {
+        AppError::Internal(anyhow::anyhow!("Failed to read file metadata: {}", e))
+    })?;
 
-    // Create streaming body
-    let stream = ReaderStream::new(file);
-    let body = Body::from_stream(stream);
+    Ok((file, metadata))
+}
 
-    // Build response with proper headers
-    Ok(Response::builder()
-        .status(StatusCode::OK)
-        .header(header::CONTENT_TYPE, content_type)
-        .body(body)
-        .unwrap())
+/// Look up a release file's sha256 and size from its (cached) manifest
+async fn release_file_entry(state: &PublicState, version: &str, file_path: &str) -> Option<(String, u64)> {
+    let cache_key = version_manifest_key(version);
+
+    let manifest = match state.cache.get_manifest(&cache_key).await {
+        Some(manifest) => manifest,
+        None => {
+            let manifest = storage::read_manifest(&state.config, version).await.ok()?;
+            state.cache.put_manifest(cache_key, manifest.clone()).await;
+            Arc::new(manifest)
+        }
+    };
+
+    manifest
+        .files
+        .iter()
+        .find(|f| f.path == file_path)
+        .map(|f| (f.sha256.clone(), f.size))
 }
 
 /// GET /api/resources - List all available resource packs
@@ -524,27 +852,13 @@ pub async fn serve_resource(
     State(state): State<PublicState>,
     Path(filename): Path<String>,
 ) -> Result<Response, AppError> {
-    // Security: Prevent directory traversal by ensuring filename is just a filename
-    if filename.contains('/') || filename.contains('\\') || filename.contains("..") {
-        return Err(AppError::Forbidden("Invalid filename".to_string()));
-    }
-
-    // Construct full file path
-    let resources_path = state.config.resources_path();
-    let full_path = resources_path.join(&filename);
-
-    // Security: Ensure the file is within the resources directory (prevent path traversal)
-    let canonical_resources = fs::canonicalize(&resources_path).await.map_err(|_| {
-        AppError::Internal(anyhow::anyhow!("Resources directory not found"))
-    })?;
-
-    let canonical_file = fs::canonicalize(&full_path).await.map_err(|_| {
-        AppError::NotFound(format!("Resource {} not found", filename))
-    })?;
-
-    if !canonical_file.starts_with(&canonical_resources) {
-        return Err(AppError::Forbidden("Path traversal attempt detected".to_string()));
-    }
+    let canonical_file = ServedDir::new(state.config.resources_path(), state.config.serve_symlinks)
+        .resolve(safe_path::segment(&filename)?)
+        .await
+        .map_err(|rejection| match rejection {
+            PathRejection::NotFound => AppError::NotFound(format!("Resource {} not found", filename)),
+            other => other.into(),
+        })?;
 
     // Open and stream the file
     let file = fs::File::open(&canonical_file).await.map_err(|_| {
@@ -602,6 +916,116 @@ pub async fn get_launcher_manifest_version(
     Ok(Json(launcher_version))
 }
 
+/// GET /api/mods/compatibility - Mods the launcher warns about when players add them
+pub async fn get_mod_compatibility(
+    State(state): State<PublicState>,
+) -> Result<Json<storage::compatibility::ModCompatibilityList>, AppError> {
+    let list = storage::compatibility::load_compatibility_list(&state.config)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to load mod compatibility list: {}", e)))?;
+
+    Ok(Json(list))
+}
+
+/// GET /api/cms-config - Branding, theme, polling intervals and feature flags for the launcher
+///
+/// Every launcher fetches this on startup, so it supports If-None-Match.
+pub async fn get_cms_config(
+    State(state): State<PublicState>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let cms = storage::cms::load_cms_config(&state.config)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to load CMS config: {}", e)))?;
+
+    cms_config_response(&cms, &headers)
+}
+
+/// The CMS config as JSON with an ETag, or `304 Not Modified` when `If-None-Match` matches it
+fn cms_config_response(cms: &storage::cms::CmsConfig, headers: &HeaderMap) -> Result<Response, AppError> {
+    let body = serde_json::to_vec(cms)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to serialize CMS config: {}", e)))?;
+    let etag = format!("\"{:x}\"", sha2::Sha256::digest(&body));
+
+    if let Some(if_none_match) = headers.get(header::IF_NONE_MATCH).and_then(|h| h.to_str().ok()) {
+        if etag_matches(if_none_match, &etag) {
+            return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+        }
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (header::ETAG, etag),
+            (header::CACHE_CONTROL, "no-cache".to_string()),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// GET /api/assets/playlist.json - Menu music the launcher caches and plays in order
+pub async fn get_audio_playlist(
+    State(state): State<PublicState>,
+) -> Result<Json<storage::playlist::AudioPlaylist>, AppError> {
+    let playlist = storage::playlist::load_playlist(&state.config)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to load audio playlist: {}", e)))?;
+
+    Ok(Json(playlist))
+}
+
+/// GET /api/assets-cms/:filename - Branding image or audio uploaded through the CMS
+///
+/// Assets can be replaced under the same name, so clients revalidate with If-None-Match.
+pub async fn serve_cms_asset(
+    State(state): State<PublicState>,
+    Path(filename): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    if storage::cms::validate_asset_filename(&filename).is_err() {
+        return Err(AppError::NotFound(format!("Asset {} not found", filename)));
+    }
+
+    let path = ServedDir::new(state.config.cms_assets_path(), state.config.serve_symlinks)
+        .resolve(&filename)
+        .await
+        .map_err(|_| AppError::NotFound(format!("Asset {} not found", filename)))?;
+    let metadata = fs::metadata(&path)
+        .await
+        .map_err(|_| AppError::NotFound(format!("Asset {} not found", filename)))?;
+
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let etag = format!("W/\"{:x}-{:x}\"", metadata.len(), modified);
+
+    if let Some(if_none_match) = headers.get(header::IF_NONE_MATCH).and_then(|h| h.to_str().ok()) {
+        if etag_matches(if_none_match, &etag) {
+            return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+        }
+    }
+
+    let file = fs::File::open(&path).await.map_err(|_| {
+        AppError::NotFound(format!("Could not open asset: {}", filename))
+    })?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, storage::cms::asset_content_type(&filename))
+        .header(header::CONTENT_LENGTH, metadata.len())
+        .header(header::ETAG, etag)
+        .header(header::CACHE_CONTROL, "public, max-age=3600, must-revalidate")
+        // SVGs can carry scripts; never run them in our origin
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .header(header::CONTENT_SECURITY_POLICY, "default-src 'none'; style-src 'unsafe-inline'; sandbox")
+        .body(Body::from_stream(ReaderStream::new(file)))
+        .unwrap())
+}
+
 /// Helper function to calculate SHA256 hash of a file
 async fn calculate_sha256(path: &std::path::Path) -> Result<String, anyhow::Error> {
     let mut file = fs::File::open(path).await?;
@@ -620,6 +1044,7 @@ async fn calculate_sha256(path: &std::path::Path) -> Result<String, anyhow::Erro
 }
 
 // Error handling
+#[derive(Debug)]
 pub enum AppError {
     Internal(anyhow::Error),
     NotFound(String),
@@ -633,6 +1058,21 @@ impl From<anyhow::Error> for AppError {
     }
 }
 
+impl From<crate::utils::player::InvalidPlayerUuid> for AppError {
+    fn from(err: crate::utils::player::InvalidPlayerUuid) -> Self {
+        AppError::BadRequest(err.to_string())
+    }
+}
+
+impl From<PathRejection> for AppError {
+    fn from(rejection: PathRejection) -> Self {
+        match rejection {
+            PathRejection::NotFound => AppError::NotFound(rejection.to_string()),
+            _ => AppError::Forbidden(rejection.to_string()),
+        }
+    }
+}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         let (status, message) = match self {
@@ -648,3 +1088,30 @@ impl IntoResponse for AppError {
         (status, Json(serde_json::json!({ "error": message }))).into_response()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cms_config_etag() {
+        let cms = storage::cms::CmsConfig::default();
+        let response = cms_config_response(&cms, &HeaderMap::new()).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "no-cache");
+        let etag = response.headers()[header::ETAG].clone();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag.clone());
+        let response = cms_config_response(&cms, &headers).unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], etag);
+
+        // Any change to the config changes the tag
+        let mut changed = cms.clone();
+        changed.features.insert("new_menu".to_string(), true);
+        let response = cms_config_response(&changed, &headers).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers()[header::ETAG], etag);
+    }
+}
@@ -0,0 +1,385 @@
+use crate::api::admin::{require_role, AdminState, AppError};
+use crate::api::public::{AppError as PublicError, PublicState};
+use crate::cache::CacheEvent;
+use crate::database::{self, resourcepacks::ResourcePack};
+use crate::middleware::AdminToken;
+use crate::models::AdminRole;
+use crate::utils::http::etag_matches;
+use crate::utils::safe_path::ServedDir;
+use axum::{
+    body::Body,
+    extract::{multipart::Multipart, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio_util::io::ReaderStream;
+
+/// Every resource pack is a zip archive
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// Query parameters for the public resource pack endpoints
+#[derive(Debug, Deserialize)]
+pub struct ResourcePackQuery {
+    /// Only consider packs targeting this Minecraft version
+    #[serde(default)]
+    pub minecraft_version: Option<String>,
+}
+
+/// One entry of `resourcepacks.json`
+#[derive(Debug, Serialize)]
+pub struct ResourcePackEntry {
+    pub version: String,
+    pub minecraft_version: String,
+    pub size: u64,
+    pub sha1: String,
+    pub sha256: String,
+    pub uploaded_at: i64,
+    pub url: String,
+}
+
+impl From<ResourcePack> for ResourcePackEntry {
+    fn from(pack: ResourcePack) -> Self {
+        ResourcePackEntry {
+            url: format!("/api/resourcepacks/{}", pack.version),
+            version: pack.version,
+            minecraft_version: pack.minecraft_version,
+            size: pack.size,
+            sha1: pack.sha1,
+            sha256: pack.sha256,
+            uploaded_at: pack.uploaded_at,
+        }
+    }
+}
+
+/// Body of GET /api/resourcepacks.json
+#[derive(Debug, Serialize)]
+pub struct ResourcePackIndex {
+    /// Newest version, also served at /api/resourcepacks/latest
+    pub latest: Option<String>,
+    /// Newest version first
+    pub packs: Vec<ResourcePackEntry>,
+}
+
+/// Versions become file names, so only accept strict semver
+fn parse_pack_version(version: &str) -> Result<String, AppError> {
+    semver::Version::parse(version.trim())
+        .map(|v| v.to_string())
+        .map_err(|_| AppError::BadRequest(format!("Invalid resource pack version (expected semver): {}", version)))
+}
+
+/// Minecraft versions look like `1.20.1` or `24w14a`
+fn validate_minecraft_version(version: &str) -> Result<(), AppError> {
+    let valid = !version.is_empty()
+        && version.len() <= 32
+        && version.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!("Invalid Minecraft version: {}", version)))
+    }
+}
+
+/// GET /api/admin/resourcepacks - All uploaded resource pack versions
+pub async fn list_resource_packs(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+) -> Result<Json<Vec<ResourcePack>>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let packs = database::resourcepacks::list(&state.db.conn)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to list resource packs: {}", e)))?;
+
+    Ok(Json(packs))
+}
+
+/// POST /api/admin/resourcepacks - Upload a new resource pack version
+///
+/// Multipart fields: `version` (semver), `minecraft_version` and `file` (the
+/// zip). Versions are immutable, so the sha1 advertised for one never changes;
+/// publishing a change means uploading a new version.
+pub async fn upload_resource_pack(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    mut multipart: Multipart,
+) -> Result<Json<ResourcePack>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let packs_dir = state.config.resourcepacks_path();
+    fs::create_dir_all(&packs_dir)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create resource packs directory: {}", e)))?;
+
+    // The file is streamed to a temporary name since fields may arrive in any order
+    let temp_path = packs_dir.join(format!(".upload-{}.part", uuid::Uuid::new_v4()));
+    let result = receive_resource_pack(&state, &token, &mut multipart, &temp_path).await;
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path).await;
+    }
+    let pack = result?;
+
+    tracing::info!(
+        "Uploaded resource pack {} for Minecraft {} ({} bytes, sha1: {})",
+        pack.version,
+        pack.minecraft_version,
+        pack.size,
+        pack.sha1
+    );
+
+    state.cache.publish(CacheEvent::ResourcePacksChanged).await;
+    Ok(Json(pack))
+}
+
+async fn receive_resource_pack(
+    state: &AdminState,
+    token: &AdminToken,
+    multipart: &mut Multipart,
+    temp_path: &std::path::Path,
+) -> Result<ResourcePack, AppError> {
+    let mut version = None;
+    let mut minecraft_version = None;
+    let mut file_info = None;
+
+    while let Some(mut field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Multipart error: {}", e)))?
+    {
+        match field.name().unwrap_or_default() {
+            "version" => {
+                let text = field
+                    .text()
+                    .await
+                    .map_err(|e| AppError::BadRequest(format!("Failed to read version: {}", e)))?;
+                version = Some(parse_pack_version(&text)?);
+            }
+            "minecraft_version" => {
+                let text = field
+                    .text()
+                    .await
+                    .map_err(|e| AppError::BadRequest(format!("Failed to read minecraft_version: {}", e)))?;
+                let text = text.trim().to_string();
+                validate_minecraft_version(&text)?;
+                minecraft_version = Some(text);
+            }
+            "file" => {
+                let filename = field.file_name().unwrap_or("resourcepack.zip").to_string();
+                let mut file = fs::File::create(temp_path)
+                    .await
+                    .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create file: {}", e)))?;
+
+                let mut sha1 = Sha1::new();
+                let mut sha256 = Sha256::new();
+                let mut size = 0u64;
+                let mut header = Vec::with_capacity(ZIP_MAGIC.len());
+
+                while let Some(chunk) = field
+                    .chunk()
+                    .await
+                    .map_err(|e| AppError::BadRequest(format!("Failed to read chunk: {}", e)))?
+                {
+                    if header.len() < ZIP_MAGIC.len() {
+                        let take = (ZIP_MAGIC.len() - header.len()).min(chunk.len());
+                        header.extend_from_slice(&chunk[..take]);
+                    }
+                    sha1.update(&chunk);
+                    sha256.update(&chunk);
+                    size += chunk.len() as u64;
+                    file.write_all(&chunk)
+                        .await
+                        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to write chunk: {}", e)))?;
+                }
+
+                file.flush()
+                    .await
+                    .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to flush file: {}", e)))?;
+
+                if header != ZIP_MAGIC {
+                    return Err(AppError::BadRequest("Resource pack must be a zip file".to_string()));
+                }
+
+                file_info = Some((
+                    filename,
+                    size,
+                    format!("{:x}", sha1.finalize()),
+                    format!("{:x}", sha256.finalize()),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    let version = version.ok_or_else(|| AppError::BadRequest("Missing version field".to_string()))?;
+    let minecraft_version =
+        minecraft_version.ok_or_else(|| AppError::BadRequest("Missing minecraft_version field".to_string()))?;
+    let (filename, size, sha1, sha256) =
+        file_info.ok_or_else(|| AppError::BadRequest("Missing file field".to_string()))?;
+
+    let pack = ResourcePack {
+        version: version.clone(),
+        minecraft_version,
+        filename,
+        size,
+        sha1,
+        sha256,
+        uploaded_by: token.claims.sub.clone(),
+        uploaded_at: chrono::Utc::now().timestamp(),
+    };
+
+    let inserted = database::resourcepacks::insert(&state.db.conn, pack.clone())
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to record resource pack: {}", e)))?;
+    if !inserted {
+        return Err(AppError::Conflict(format!("Resource pack {} already exists", version)));
+    }
+
+    let final_path = state.config.resourcepacks_path().join(format!("{}.zip", version));
+    if let Err(e) = fs::rename(temp_path, &final_path).await {
+        let _ = database::resourcepacks::remove(&state.db.conn, version).await;
+        return Err(AppError::Internal(anyhow::anyhow!("Failed to store resource pack: {}", e)));
+    }
+
+    Ok(pack)
+}
+
+/// DELETE /api/admin/resourcepacks/:version - Delete one resource pack version
+pub async fn delete_resource_pack(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(version): Path<String>,
+) -> Result<StatusCode, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let version = parse_pack_version(&version)?;
+    let removed = database::resourcepacks::remove(&state.db.conn, version.clone())
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to delete resource pack: {}", e)))?;
+    if removed.is_none() {
+        return Err(AppError::NotFound(format!("Resource pack {} not found", version)));
+    }
+
+    let path = state.config.resourcepacks_path().join(format!("{}.zip", version));
+    if let Err(e) = fs::remove_file(&path).await {
+        tracing::warn!("Failed to remove resource pack file {:?}: {}", path, e);
+    }
+
+    tracing::info!("Deleted resource pack {}", version);
+    state.cache.publish(CacheEvent::ResourcePacksChanged).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/resourcepacks.json - Index of resource pack versions with their sha1
+pub async fn get_resource_pack_index(
+    State(state): State<PublicState>,
+    Query(query): Query<ResourcePackQuery>,
+) -> Result<Json<ResourcePackIndex>, PublicError> {
+    let packs: Vec<ResourcePackEntry> = database::resourcepacks::list(&state.db.conn)
+        .await?
+        .into_iter()
+        .filter(|pack| {
+            query
+                .minecraft_version
+                .as_deref()
+                .is_none_or(|mc| pack.minecraft_version == mc)
+        })
+        .map(ResourcePackEntry::from)
+        .collect();
+
+    Ok(Json(ResourcePackIndex {
+        latest: packs.first().map(|pack| pack.version.clone()),
+        packs,
+    }))
+}
+
+/// GET /api/resourcepacks/latest - The newest resource pack at a stable URL
+///
+/// Point `resource-pack` in server.properties here. The matching sha1 is in
+/// the `X-Resource-Pack-Sha1` header, the ETag and `resourcepacks.json`.
+pub async fn serve_latest_resource_pack(
+    State(state): State<PublicState>,
+    Query(query): Query<ResourcePackQuery>,
+    headers: HeaderMap,
+) -> Result<Response, PublicError> {
+    let pack = database::resourcepacks::latest(&state.db.conn, query.minecraft_version.as_deref())
+        .await?
+        .ok_or_else(|| PublicError::NotFound("No resource pack available".to_string()))?;
+
+    send_resource_pack(&state, pack, &headers).await
+}
+
+/// GET /api/resourcepacks/:version - One specific resource pack version
+pub async fn serve_resource_pack(
+    State(state): State<PublicState>,
+    Path(version): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, PublicError> {
+    let pack = database::resourcepacks::get(&state.db.conn, version.clone())
+        .await?
+        .ok_or_else(|| PublicError::NotFound(format!("Resource pack {} not found", version)))?;
+
+    send_resource_pack(&state, pack, &headers).await
+}
+
+async fn send_resource_pack(
+    state: &PublicState,
+    pack: ResourcePack,
+    headers: &HeaderMap,
+) -> Result<Response, PublicError> {
+    let etag = format!("\"{}\"", pack.sha1);
+    if let Some(if_none_match) = headers.get(header::IF_NONE_MATCH).and_then(|h| h.to_str().ok()) {
+        if etag_matches(if_none_match, &etag) {
+            return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+        }
+    }
+
+    let path = ServedDir::new(state.config.resourcepacks_path(), state.config.serve_symlinks)
+        .resolve(&format!("{}.zip", pack.version))
+        .await
+        .map_err(|_| PublicError::NotFound(format!("Resource pack {} is missing on disk", pack.version)))?;
+    let file = fs::File::open(&path)
+        .await
+        .map_err(|_| PublicError::NotFound(format!("Resource pack {} is missing on disk", pack.version)))?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/zip")
+        .header(header::CONTENT_LENGTH, pack.size)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"resourcepack-{}.zip\"", pack.version),
+        )
+        .header(header::ETAG, etag)
+        // The latest URL changes content between versions
+        .header(header::CACHE_CONTROL, "no-cache")
+        .header("X-Resource-Pack-Version", pack.version)
+        .header("X-Resource-Pack-Sha1", pack.sha1)
+        .body(Body::from_stream(ReaderStream::new(file)))
+        .unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pack_version() {
+        assert_eq!(parse_pack_version(" 1.2.3 ").unwrap(), "1.2.3");
+        assert_eq!(parse_pack_version("2.0.0-beta.1").unwrap(), "2.0.0-beta.1");
+        assert!(parse_pack_version("1.2").is_err());
+        assert!(parse_pack_version("../1.0.0").is_err());
+    }
+
+    #[test]
+    fn test_validate_minecraft_version() {
+        assert!(validate_minecraft_version("1.20.1").is_ok());
+        assert!(validate_minecraft_version("24w14a").is_ok());
+        assert!(validate_minecraft_version("").is_err());
+        assert!(validate_minecraft_version("1.20/../x").is_err());
+    }
+}
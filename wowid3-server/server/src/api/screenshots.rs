@@ -0,0 +1,177 @@
+use crate::api::public::{AppError, PublicState};
+use crate::database::{self, screenshots::{NewScreenshot, ScreenshotRecord}};
+use crate::utils::player::parse_player_uuid;
+use crate::utils::safe_path::ServedDir;
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::Response,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::fs;
+
+/// Largest accepted screenshot (a 4K PNG is usually 5-15 MB); the upload route's body limit
+pub const MAX_SCREENSHOT_BYTES: usize = 32 * 1024 * 1024;
+
+const PNG_MAGIC: &[u8] = b"\x89PNG\r\n\x1a\n";
+const JPEG_MAGIC: &[u8] = &[0xFF, 0xD8, 0xFF];
+
+/// Query parameters for POST /api/screenshots/:uuid
+#[derive(Debug, Deserialize)]
+pub struct UploadScreenshotQuery {
+    /// File name on the player's machine
+    #[serde(default)]
+    pub filename: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScreenshotResponse {
+    #[serde(flatten)]
+    pub record: ScreenshotRecord,
+    pub url: String,
+}
+
+impl From<ScreenshotRecord> for ScreenshotResponse {
+    fn from(record: ScreenshotRecord) -> Self {
+        let url = format!("/api/screenshots/{}/{}", record.player_uuid, record.filename);
+        ScreenshotResponse { record, url }
+    }
+}
+
+/// File extension for supported image data, based on its magic bytes
+fn image_extension(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(PNG_MAGIC) {
+        Some("png")
+    } else if data.starts_with(JPEG_MAGIC) {
+        Some("jpg")
+    } else {
+        None
+    }
+}
+
+/// POST /api/screenshots/:uuid - Upload a PNG/JPEG screenshot (raw request body) for a player
+///
+/// The uploader proves they are that player with their Minecraft access token in
+/// `Authorization: Bearer`, checked against the Minecraft profile API.
+pub async fn upload_screenshot(
+    State(state): State<PublicState>,
+    Path(uuid): Path<String>,
+    Query(query): Query<UploadScreenshotQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<(StatusCode, Json<ScreenshotResponse>), AppError> {
+    let player_uuid = parse_player_uuid(&uuid)?;
+
+    let access_token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .ok_or_else(|| AppError::Forbidden("Screenshot uploads need the player's access token".to_string()))?;
+    state
+        .profile_verifier
+        .verify(access_token)
+        .await?
+        .filter(|profile| profile.uuid == player_uuid)
+        .ok_or_else(|| AppError::Forbidden("Access token does not belong to this player".to_string()))?;
+
+    let extension = image_extension(&body)
+        .ok_or_else(|| AppError::BadRequest("Screenshots must be PNG or JPEG images".to_string()))?;
+
+    let sha256 = format!("{:x}", Sha256::digest(&body));
+    let filename = format!("{}.{}", &sha256[..16], extension);
+
+    let player_dir = state.config.screenshots_path().join(&player_uuid);
+    fs::create_dir_all(&player_dir)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create screenshot directory: {}", e)))?;
+
+    let file_path = player_dir.join(&filename);
+    if !file_path.exists() {
+        fs::write(&file_path, &body)
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to save screenshot: {}", e)))?;
+    }
+
+    let original_name = query
+        .filename
+        .map(|name| name.trim().chars().take(255).collect::<String>())
+        .filter(|name| !name.is_empty());
+
+    let record = database::screenshots::insert(
+        &state.db.conn,
+        NewScreenshot {
+            player_uuid,
+            filename,
+            original_name,
+            size: body.len() as i64,
+            sha256,
+        },
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(record.into())))
+}
+
+/// GET /api/screenshots/:uuid - List a player's uploaded screenshots, newest first
+pub async fn list_player_screenshots(
+    State(state): State<PublicState>,
+    Path(uuid): Path<String>,
+) -> Result<Json<Vec<ScreenshotResponse>>, AppError> {
+    let player_uuid = parse_player_uuid(&uuid)?;
+
+    let records = database::screenshots::list_for_player(&state.db.conn, &player_uuid).await?;
+
+    Ok(Json(records.into_iter().map(ScreenshotResponse::from).collect()))
+}
+
+/// GET /api/screenshots/:uuid/:filename - Serve an uploaded screenshot
+pub async fn serve_screenshot(
+    State(state): State<PublicState>,
+    Path((uuid, filename)): Path<(String, String)>,
+) -> Result<Response, AppError> {
+    let player_uuid = parse_player_uuid(&uuid)?;
+
+    // Stored names are always <hex>.<ext>
+    let valid_name = filename
+        .split_once('.')
+        .map(|(stem, ext)| {
+            !stem.is_empty() && stem.chars().all(|c| c.is_ascii_hexdigit()) && matches!(ext, "png" | "jpg")
+        })
+        .unwrap_or(false);
+    if !valid_name {
+        return Err(AppError::Forbidden("Invalid filename".to_string()));
+    }
+
+    let file_path = ServedDir::new(state.config.screenshots_path(), state.config.serve_symlinks)
+        .resolve(&format!("{}/{}", player_uuid, filename))
+        .await
+        .map_err(|_| AppError::NotFound(format!("Screenshot {} not found", filename)))?;
+    let data = fs::read(&file_path)
+        .await
+        .map_err(|_| AppError::NotFound(format!("Screenshot {} not found", filename)))?;
+
+    let content_type = if filename.ends_with(".png") { "image/png" } else { "image/jpeg" };
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        // Content-addressed, so it never changes
+        .header(header::CACHE_CONTROL, "public, max-age=31536000, immutable")
+        .body(Body::from(data))
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to build response: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_extension() {
+        assert_eq!(image_extension(b"\x89PNG\r\n\x1a\n...."), Some("png"));
+        assert_eq!(image_extension(&[0xFF, 0xD8, 0xFF, 0xE0]), Some("jpg"));
+        assert_eq!(image_extension(b"GIF89a"), None);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::api::admin::{require_role, AdminState, AppError};
+use crate::api::crashes::optional_field;
+use crate::api::public::{AppError as PublicError, PublicState};
+use crate::database::{
+    self,
+    support::{NewSupportReport, SupportReport, SupportReportSummary},
+};
+use crate::middleware::AdminToken;
+use crate::models::AdminRole;
+use crate::utils::text::truncate_bytes;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// System reports are a few KB; anything far bigger isn't one
+const MAX_REPORT_BYTES: usize = 64 * 1024;
+
+/// Body limit for support uploads: the report plus the player's note
+pub const MAX_SUPPORT_REQUEST_BYTES: usize = MAX_REPORT_BYTES + 16 * 1024;
+
+/// Upper bound for the player's description of the problem
+const MAX_NOTE_LEN: usize = 1000;
+
+/// Body of POST /api/support/reports
+#[derive(Debug, Deserialize)]
+pub struct SubmitSupportReportRequest {
+    /// System report gathered by the launcher (OS, CPU/RAM, GPUs, Java, versions)
+    pub report: Value,
+    /// What the player said went wrong
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SubmitSupportReportResponse {
+    pub id: i64,
+}
+
+/// String at a JSON pointer in the report, if present
+fn report_field(report: &Value, pointer: &str) -> Option<String> {
+    optional_field(report.pointer(pointer).and_then(Value::as_str).map(str::to_string))
+}
+
+/// POST /api/support/reports - Store a system report so support can look it up by id
+pub async fn submit_support_report(
+    State(state): State<PublicState>,
+    Json(payload): Json<SubmitSupportReportRequest>,
+) -> Result<(StatusCode, Json<SubmitSupportReportResponse>), PublicError> {
+    if !payload.report.is_object() {
+        return Err(PublicError::BadRequest("report must be a JSON object".to_string()));
+    }
+
+    let os = report_field(&payload.report, "/os/family")
+        .ok_or_else(|| PublicError::BadRequest("report.os.family is required".to_string()))?;
+
+    let note = payload
+        .note
+        .map(|note| truncate_bytes(note.trim(), MAX_NOTE_LEN).to_string())
+        .filter(|note| !note.is_empty());
+
+    let id = database::support::insert(
+        &state.db.conn,
+        NewSupportReport {
+            os,
+            launcher_version: report_field(&payload.report, "/launcher_version"),
+            modpack_version: report_field(&payload.report, "/modpack_version"),
+            note,
+            report: payload.report,
+        },
+    )
+    .await?;
+
+    tracing::info!("Stored support report {}", id);
+
+    Ok((StatusCode::CREATED, Json(SubmitSupportReportResponse { id })))
+}
+
+/// Query parameters for GET /api/admin/support/reports
+#[derive(Debug, Deserialize)]
+pub struct SupportListQuery {
+    #[serde(default = "default_page")]
+    pub page: usize,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+fn default_page() -> usize { 1 }
+fn default_limit() -> usize { 50 }
+
+/// Paginated support report response
+#[derive(Serialize)]
+pub struct PaginatedSupportResponse {
+    pub entries: Vec<SupportReportSummary>,
+    pub page: usize,
+    pub limit: usize,
+    pub total: usize,
+    pub total_pages: usize,
+}
+
+/// GET /api/admin/support/reports - List support reports, newest first
+pub async fn list_support_reports(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Query(query): Query<SupportListQuery>,
+) -> Result<Json<PaginatedSupportResponse>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let limit = query.limit.clamp(1, 200);
+    let page = query.page.max(1); // Min page 1
+
+    let (entries, total) = database::support::list(&state.db.conn, limit, (page - 1) * limit)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read support reports: {}", e)))?;
+
+    let total_pages = total.div_ceil(limit);
+
+    Ok(Json(PaginatedSupportResponse {
+        entries,
+        page,
+        limit,
+        total,
+        total_pages,
+    }))
+}
+
+/// GET /api/admin/support/reports/:id - Full support report (the id players give in Discord)
+pub async fn get_support_report(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(id): Path<i64>,
+) -> Result<Json<SupportReport>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let report = database::support::get(&state.db.conn, id)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read support report: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("Support report {} not found", id)))?;
+
+    Ok(Json(report))
+}
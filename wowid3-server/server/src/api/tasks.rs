@@ -0,0 +1,114 @@
+use crate::api::admin::{require_role, AdminState, AppError};
+use crate::database;
+use crate::middleware::AdminToken;
+use crate::models::AdminRole;
+use crate::services::jwt::TokenType;
+use crate::services::tasks::TaskStatus;
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+    },
+    http::{header, HeaderMap},
+    response::Response,
+    Extension, Json,
+};
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+/// GET /api/admin/tasks/:id - Current status of a background task
+pub async fn get_task(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TaskStatus>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    state
+        .tasks
+        .get(id)
+        .map(Json)
+        .ok_or_else(|| AppError::NotFound(format!("Task {} not found", id)))
+}
+
+/// Query parameters for the task WebSocket
+#[derive(Debug, Deserialize)]
+pub struct TaskSocketQuery {
+    /// Access token; browsers can't set headers on WebSocket requests
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+/// GET /ws/admin/tasks/:id - Stream task status updates over a WebSocket
+///
+/// Sends the current status immediately, then every update as JSON text frames,
+/// and closes once the task completes or fails. Authenticates with either a
+/// Bearer header or a `?token=` query parameter.
+pub async fn task_socket(
+    ws: WebSocketUpgrade,
+    State(state): State<AdminState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<TaskSocketQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .map(|t| t.to_string())
+        .or(query.token)
+        .ok_or_else(|| AppError::Unauthorized("Missing token".to_string()))?;
+
+    let mut claims = state
+        .jwt
+        .verify(&token, TokenType::Access)
+        .map_err(|_| AppError::Unauthorized("Invalid or expired token".to_string()))?;
+
+    if database::auth::is_token_revoked(&state.db.conn, &claims.jti).await? {
+        return Err(AppError::Unauthorized("Token has been revoked".to_string()));
+    }
+
+    // Outside auth_middleware, so resolve the account's current role the same way it does
+    claims.role = database::admin_users::current_role(&state.db.conn, &claims.sub).await?;
+    require_role(&AdminToken { token, claims }, AdminRole::Viewer)?;
+
+    let (snapshot, updates) = state
+        .tasks
+        .subscribe(id)
+        .ok_or_else(|| AppError::NotFound(format!("Task {} not found", id)))?;
+
+    Ok(ws.on_upgrade(move |socket| stream_task(socket, snapshot, updates)))
+}
+
+async fn stream_task(
+    mut socket: WebSocket,
+    snapshot: TaskStatus,
+    mut updates: tokio::sync::broadcast::Receiver<TaskStatus>,
+) {
+    let mut finished = snapshot.is_finished();
+    if send_status(&mut socket, &snapshot).await.is_err() {
+        return;
+    }
+
+    while !finished {
+        let status = match updates.recv().await {
+            Ok(status) => status,
+            // Updates are full snapshots, so skipping some is harmless
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => break,
+        };
+
+        finished = status.is_finished();
+        if send_status(&mut socket, &status).await.is_err() {
+            return;
+        }
+    }
+
+    let _ = socket.send(Message::Close(None)).await;
+}
+
+async fn send_status(socket: &mut WebSocket, status: &TaskStatus) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(status).unwrap_or_default();
+    socket.send(Message::Text(text)).await
+}
@@ -0,0 +1,118 @@
+use crate::api::admin::{require_role, AdminState, AppError};
+use crate::api::public::{AppError as PublicError, PublicState};
+use crate::database::{
+    self,
+    telemetry::{NewUpdateReport, ReleaseUpdateStats, ERROR_CATEGORIES},
+};
+use crate::middleware::usage::LAUNCHER_ID_HEADER;
+use crate::middleware::AdminToken;
+use crate::models::AdminRole;
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    Extension, Json,
+};
+use serde::Deserialize;
+
+/// Upper bound for the short metadata fields
+const MAX_FIELD_LEN: usize = 64;
+
+/// Reports claiming longer updates are treated as bogus
+const MAX_DURATION_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Body of POST /api/telemetry/update
+#[derive(Debug, Deserialize)]
+pub struct UpdateReportRequest {
+    pub version: String,
+    #[serde(default)]
+    pub from_version: Option<String>,
+    pub success: bool,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub bytes: u64,
+    #[serde(default)]
+    pub files: u64,
+    #[serde(default)]
+    pub error_category: Option<String>,
+    #[serde(default)]
+    pub launcher_version: Option<String>,
+    #[serde(default)]
+    pub os: Option<String>,
+}
+
+fn short_field(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty() && v.len() <= MAX_FIELD_LEN)
+}
+
+/// POST /api/telemetry/update - Record the outcome of a modpack update (opt-in on the client)
+pub async fn submit_update_report(
+    State(state): State<PublicState>,
+    headers: HeaderMap,
+    Json(payload): Json<UpdateReportRequest>,
+) -> Result<StatusCode, PublicError> {
+    let version = short_field(Some(payload.version))
+        .ok_or_else(|| PublicError::BadRequest("version is required".to_string()))?;
+    if payload.duration_ms > MAX_DURATION_MS {
+        return Err(PublicError::BadRequest("duration_ms is out of range".to_string()));
+    }
+
+    // Successful reports carry no category; unknown categories are kept as "other"
+    let error_category = (!payload.success).then(|| {
+        payload
+            .error_category
+            .filter(|c| ERROR_CATEGORIES.contains(&c.as_str()))
+            .unwrap_or_else(|| "other".to_string())
+    });
+
+    let install_id = headers
+        .get(LAUNCHER_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+
+    database::telemetry::insert(
+        &state.db.conn,
+        NewUpdateReport {
+            version,
+            from_version: short_field(payload.from_version),
+            success: payload.success,
+            duration_ms: payload.duration_ms as i64,
+            bytes: payload.bytes.min(i64::MAX as u64) as i64,
+            files: payload.files.min(i64::MAX as u64) as i64,
+            error_category,
+            launcher_version: short_field(payload.launcher_version),
+            os: short_field(payload.os),
+            install_id: short_field(install_id),
+        },
+    )
+    .await?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Query parameters for GET /api/admin/telemetry/updates
+#[derive(Debug, Deserialize)]
+pub struct UpdateStatsQuery {
+    /// Only reports from the last N days
+    #[serde(default = "default_days")]
+    pub days: u32,
+}
+
+fn default_days() -> u32 { 30 }
+
+/// GET /api/admin/telemetry/updates - Update success rate, duration and failure causes per release
+pub async fn get_update_stats(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Query(query): Query<UpdateStatsQuery>,
+) -> Result<Json<Vec<ReleaseUpdateStats>>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let since = chrono::Utc::now().timestamp() - i64::from(query.days.clamp(1, 365)) * 86400;
+    let stats = database::telemetry::release_stats(&state.db.conn, since)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read update telemetry: {}", e)))?;
+
+    Ok(Json(stats))
+}
@@ -1,50 +1,78 @@
 use crate::api::public::{AppError, PublicState};
-use crate::models::tracker::{ChatMessage, UpdateStateRequest, ChatMessageRequest};
-use crate::models::stats::{StatEventBatch, PlayerStats};
+use crate::database::{
+    self,
+    advancements::{AdvancementCompletion, PlayerAdvancement},
+    sessions::{JoinedPlayer, PlayerSession, PlaytimeEntry},
+};
+use crate::models::tracker::{ChatMessage, UpdateStateRequest, ChatMessageRequest, OutgoingChatMessage, SendChatRequest};
+use crate::models::stats::{Leaderboard, LeaderboardMetric, LeaderboardWindow, StatEventBatch, PlayerStats};
+use crate::utils::player::parse_player_uuid;
 use axum::{
-    extract::{State, Path},
+    extract::{State, Path, Query},
     http::{HeaderMap, StatusCode, header},
     response::{IntoResponse, Response},
     Json,
 };
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-const TRACKER_SECRET_HEADER: &str = "x-tracker-secret";
 const MAX_CHAT_HISTORY: usize = 50;
+const WEEK_SECS: i64 = 7 * 86_400;
+/// Launcher messages kept for the tracker mod; the oldest are dropped first
+const MAX_OUTGOING_CHAT: usize = 100;
+/// Minecraft's own chat message limit
+const MAX_CHAT_MESSAGE_LEN: usize = 256;
+/// Longest accepted stat event idempotency key
+const MAX_EVENT_ID_LEN: usize = 128;
 
-/// Middleware-like helper to validate tracker secret
-/// Accepts secret in either x-tracker-secret header OR Authorization Bearer header
-fn validate_secret(headers: &HeaderMap, expected_secret: &str) -> Result<(), AppError> {
-    // Try x-tracker-secret header first (legacy)
-    if let Some(secret) = headers.get(TRACKER_SECRET_HEADER).and_then(|h| h.to_str().ok()) {
-        if secret == expected_secret {
-            return Ok(());
-        }
-    }
-
-    // Try Authorization Bearer header (new method)
-    if let Some(auth) = headers.get(header::AUTHORIZATION).and_then(|h| h.to_str().ok()) {
-        if auth.starts_with("Bearer ") {
-            let token = &auth[7..]; // Remove "Bearer " prefix
-            if token == expected_secret {
-                return Ok(());
-            }
-        }
+/// Normalize UUID format: add dashes if missing
+/// Minecraft UUIDs can be: "adca5752c67a4f0aae7444d9f369f6f8" (32 chars, no dashes)
+/// or: "adca5752-c67a-4f0a-ae74-44d9f369f6f8" (36 chars, with dashes)
+fn normalize_uuid(uuid: &str) -> String {
+    if uuid.len() == 32 && !uuid.contains('-') && uuid.is_ascii() {
+        // Add dashes: xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
+        format!(
+            "{}-{}-{}-{}-{}",
+            &uuid[0..8],
+            &uuid[8..12],
+            &uuid[12..16],
+            &uuid[16..20],
+            &uuid[20..32]
+        )
+    } else {
+        uuid.to_string()
     }
-
-    Err(AppError::Forbidden("Missing or invalid tracker secret".to_string()))
 }
 
 /// POST /api/tracker/update
 pub async fn update_tracker_state(
     State(state): State<PublicState>,
-    headers: HeaderMap,
     Json(payload): Json<UpdateStateRequest>,
 ) -> Result<StatusCode, AppError> {
-    validate_secret(&headers, &state.config.tracker_secret)?;
+    let player_uuids: Vec<String> = payload.players.iter().map(|p| p.uuid.clone()).collect();
 
     let mut tracker = state.tracker.write().await;
 
+    // Diff against the previous update to find joins and leaves
+    let previous: HashSet<&str> = tracker.online_players.iter().map(|p| p.uuid.as_str()).collect();
+    let current: HashSet<&str> = payload.players.iter().map(|p| p.uuid.as_str()).collect();
+    let joined: Vec<JoinedPlayer> = payload
+        .players
+        .iter()
+        .filter(|p| !previous.contains(p.uuid.as_str()))
+        .map(|p| JoinedPlayer {
+            uuid: p.uuid.clone(),
+            name: p.name.clone(),
+        })
+        .collect();
+    let left: Vec<String> = tracker
+        .online_players
+        .iter()
+        .filter(|p| !current.contains(p.uuid.as_str()))
+        .map(|p| p.uuid.clone())
+        .collect();
+
     // Update players and stats
     tracker.online_players = payload.players;
     tracker.tps = payload.tps;
@@ -56,6 +84,17 @@ pub async fn update_tracker_state(
         .duration_since(UNIX_EPOCH)
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Time error: {}", e)))?;
     tracker.last_updated = since_the_epoch.as_secs();
+    drop(tracker);
+
+    let now = since_the_epoch.as_secs() as i64;
+    if let Err(e) = database::sessions::record_changes(&state.db.conn, joined, left, now).await {
+        tracing::warn!("Failed to record player sessions: {}", e);
+    }
+
+    // Daily unique players and peak online for the admin dashboard
+    if let Err(e) = database::usage::record_online_players(&state.db.conn, player_uuids).await {
+        tracing::warn!("Failed to record player activity: {}", e);
+    }
 
     Ok(StatusCode::OK)
 }
@@ -63,11 +102,8 @@ pub async fn update_tracker_state(
 /// POST /api/tracker/chat
 pub async fn submit_chat_message(
     State(state): State<PublicState>,
-    headers: HeaderMap,
     Json(payload): Json<ChatMessageRequest>,
 ) -> Result<StatusCode, AppError> {
-    validate_secret(&headers, &state.config.tracker_secret)?;
-
     let mut tracker = state.tracker.write().await;
     
     let start = SystemTime::now();
@@ -91,6 +127,92 @@ pub async fn submit_chat_message(
     Ok(StatusCode::OK)
 }
 
+/// Trim a launcher chat message, dropping formatting codes so it can't
+/// impersonate server messages
+fn sanitize_chat_content(content: &str) -> Result<String, AppError> {
+    let mut sanitized = String::with_capacity(content.len());
+    let mut chars = content.trim().chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '§' => {
+                chars.next();
+            }
+            c if c.is_control() => {}
+            c => sanitized.push(c),
+        }
+    }
+    let sanitized = sanitized.trim().to_string();
+
+    if sanitized.is_empty() {
+        return Err(AppError::BadRequest("Message is empty".to_string()));
+    }
+    if sanitized.chars().count() > MAX_CHAT_MESSAGE_LEN {
+        return Err(AppError::BadRequest(format!(
+            "Message is longer than {} characters",
+            MAX_CHAT_MESSAGE_LEN
+        )));
+    }
+    Ok(sanitized)
+}
+
+/// POST /api/tracker/chat/send - Queue a chat message from a launcher
+///
+/// The sender proves who they are with their Minecraft access token; the
+/// message is broadcast in-game once the tracker mod picks it up.
+pub async fn send_chat_message(
+    State(state): State<PublicState>,
+    Json(payload): Json<SendChatRequest>,
+) -> Result<StatusCode, AppError> {
+    let uuid = parse_player_uuid(&payload.uuid)?;
+    let content = sanitize_chat_content(&payload.content)?;
+
+    let profile = state
+        .profile_verifier
+        .verify(&payload.access_token)
+        .await?
+        .filter(|profile| profile.uuid == uuid)
+        .ok_or_else(|| AppError::Forbidden("Access token does not belong to this player".to_string()))?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Time error: {}", e)))?
+        .as_secs();
+
+    let mut tracker = state.tracker.write().await;
+
+    tracker.outgoing_chat.push_back(OutgoingChatMessage {
+        uuid: profile.uuid,
+        sender: profile.name.clone(),
+        content: content.clone(),
+        timestamp,
+    });
+    while tracker.outgoing_chat.len() > MAX_OUTGOING_CHAT {
+        tracker.outgoing_chat.pop_front();
+    }
+
+    // Show it in the overlay right away
+    tracker.recent_chat.push_back(ChatMessage {
+        sender: profile.name,
+        content,
+        timestamp,
+    });
+    while tracker.recent_chat.len() > MAX_CHAT_HISTORY {
+        tracker.recent_chat.pop_front();
+    }
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// GET /api/tracker/chat/pending - Take the queued launcher messages
+///
+/// Polled by the tracker mod; each message is handed out once.
+pub async fn take_pending_chat(
+    State(state): State<PublicState>,
+) -> Result<Json<Vec<OutgoingChatMessage>>, AppError> {
+    let mut tracker = state.tracker.write().await;
+    Ok(Json(tracker.outgoing_chat.drain(..).collect()))
+}
+
 /// GET /api/tracker/status
 pub async fn get_tracker_status(
     State(state): State<PublicState>,
@@ -100,12 +222,24 @@ pub async fn get_tracker_status(
 }
 
 /// POST /api/tracker/stats-events
+///
+/// Events carrying an `event_id` are deduplicated, so a batch can be resent
+/// safely after a network error.
 pub async fn submit_stat_events(
     State(state): State<PublicState>,
-    headers: HeaderMap,
     Json(payload): Json<StatEventBatch>,
 ) -> Result<StatusCode, AppError> {
-    validate_secret(&headers, &state.config.tracker_secret)?;
+    if payload
+        .events
+        .iter()
+        .filter_map(|event| event.event_id.as_deref())
+        .any(|id| id.is_empty() || id.len() > MAX_EVENT_ID_LEN)
+    {
+        return Err(AppError::BadRequest(format!(
+            "Event ids must be 1-{} characters",
+            MAX_EVENT_ID_LEN
+        )));
+    }
 
     for event in payload.events {
         state.stats_processor.push_event(event).await;
@@ -125,22 +259,7 @@ pub async fn get_player_stats(
     // For this implementation, we'll assume the launcher handles auth and we trust it,
     // but basic protection is good.
 
-    // Normalize UUID format: add dashes if missing
-    // Minecraft UUIDs can be: "adca5752c67a4f0aae7444d9f369f6f8" (32 chars, no dashes)
-    // or: "adca5752-c67a-4f0a-ae74-44d9f369f6f8" (36 chars, with dashes)
-    let normalized_uuid = if uuid.len() == 32 && !uuid.contains('-') {
-        // Add dashes: xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
-        format!(
-            "{}-{}-{}-{}-{}",
-            &uuid[0..8],
-            &uuid[8..12],
-            &uuid[12..16],
-            &uuid[16..20],
-            &uuid[20..32]
-        )
-    } else {
-        uuid.clone()
-    };
+    let normalized_uuid = normalize_uuid(&uuid);
 
     // Check for If-None-Match header for caching
     let client_hash = headers
@@ -148,22 +267,8 @@ pub async fn get_player_stats(
         .and_then(|h| h.to_str().ok())
         .map(|s| s.trim_matches('"').to_string());
 
-    // Query DB with normalized UUID
-    let uuid_clone = normalized_uuid.clone();
-    let result = state.db.conn.call(move |conn| {
-        conn.query_row(
-            "SELECT stats_json, hash FROM player_stats WHERE uuid = ?1",
-            [&uuid_clone],
-            |row| {
-                let json: String = row.get(0)?;
-                let hash: String = row.get(1)?;
-                Ok((json, hash))
-            }
-        )
-    }).await;
-
-    match result {
-        Ok((json, hash)) => {
+    match state.stats.player_stats(&normalized_uuid).await? {
+        Some((json, hash)) => {
             // Check cache
             if let Some(client_hash) = client_hash {
                 if client_hash == hash {
@@ -183,7 +288,7 @@ pub async fn get_player_stats(
                 .body(axum::body::Body::from(json))
                 .unwrap())
         },
-        Err(tokio_rusqlite::Error::Error(rusqlite::Error::QueryReturnedNoRows)) => {
+        None => {
             // Return empty stats if not found
             let stats = PlayerStats {
                 uuid: normalized_uuid.clone(),
@@ -192,6 +297,191 @@ pub async fn get_player_stats(
             };
             Ok(Json(stats).into_response())
         },
-        Err(e) => Err(AppError::Internal(e.into())),
+    }
+}
+
+/// Query parameters for GET /api/stats/:uuid/sessions
+#[derive(Debug, Deserialize)]
+pub struct SessionsQuery {
+    #[serde(default = "default_sessions_limit")]
+    pub limit: u32,
+}
+
+fn default_sessions_limit() -> u32 { 20 }
+
+#[derive(Debug, Serialize)]
+pub struct PlayerSessionsResponse {
+    pub uuid: String,
+    /// Whether the tracker currently reports the player online
+    pub online: bool,
+    pub total_playtime_secs: i64,
+    pub playtime_last_7_days_secs: i64,
+    /// Most recent first
+    pub sessions: Vec<PlayerSession>,
+}
+
+/// GET /api/stats/:uuid/sessions - A player's recent sessions and playtime
+pub async fn get_player_sessions(
+    State(state): State<PublicState>,
+    Path(uuid): Path<String>,
+    Query(query): Query<SessionsQuery>,
+) -> Result<Json<PlayerSessionsResponse>, AppError> {
+    let uuid = normalize_uuid(&uuid);
+    let now = chrono::Utc::now().timestamp();
+    let conn = &state.db.conn;
+
+    let sessions = database::sessions::list_for_player(conn, uuid.clone(), query.limit.clamp(1, 200), now).await?;
+    let total_playtime_secs = database::sessions::playtime_since(conn, uuid.clone(), 0, now).await?;
+    let playtime_last_7_days_secs = database::sessions::playtime_since(conn, uuid.clone(), now - WEEK_SECS, now).await?;
+    let online = state
+        .tracker
+        .read()
+        .await
+        .online_players
+        .iter()
+        .any(|p| p.uuid == uuid);
+
+    Ok(Json(PlayerSessionsResponse {
+        uuid,
+        online,
+        total_playtime_secs,
+        playtime_last_7_days_secs,
+        sessions,
+    }))
+}
+
+/// Query parameters for GET /api/stats/leaderboard/playtime
+#[derive(Debug, Deserialize)]
+pub struct PlaytimeLeaderboardQuery {
+    /// Only count the last this many days (0 for all time)
+    #[serde(default = "default_leaderboard_days")]
+    pub days: u32,
+    #[serde(default = "default_leaderboard_limit")]
+    pub limit: u32,
+}
+
+fn default_leaderboard_days() -> u32 { 7 }
+fn default_leaderboard_limit() -> u32 { 10 }
+
+/// GET /api/stats/leaderboard/playtime - Players with the most playtime
+pub async fn get_playtime_leaderboard(
+    State(state): State<PublicState>,
+    Query(query): Query<PlaytimeLeaderboardQuery>,
+) -> Result<Json<Vec<PlaytimeEntry>>, AppError> {
+    let now = chrono::Utc::now().timestamp();
+    let since = match query.days {
+        0 => 0,
+        days => now - days.min(3650) as i64 * 86_400,
+    };
+
+    let entries = database::sessions::playtime_leaderboard(&state.db.conn, since, query.limit.clamp(1, 100), now).await?;
+    Ok(Json(entries))
+}
+
+/// Body of GET /api/stats/:uuid/advancements
+#[derive(Debug, Serialize)]
+pub struct PlayerAdvancementsResponse {
+    pub uuid: String,
+    /// Oldest first
+    pub advancements: Vec<PlayerAdvancement>,
+}
+
+/// GET /api/stats/:uuid/advancements - Advancements a player completed, with timestamps
+pub async fn get_player_advancements(
+    State(state): State<PublicState>,
+    Path(uuid): Path<String>,
+) -> Result<Json<PlayerAdvancementsResponse>, AppError> {
+    let uuid = normalize_uuid(&uuid);
+    let advancements = state.stats.player_advancements(&uuid).await?;
+    Ok(Json(PlayerAdvancementsResponse { uuid, advancements }))
+}
+
+/// Query parameters for GET /api/stats/advancements
+#[derive(Debug, Deserialize)]
+pub struct AdvancementCompletionsQuery {
+    /// Namespaced advancement id, e.g. `minecraft:nether/all_effects`
+    pub id: String,
+    #[serde(default = "default_leaderboard_limit")]
+    pub limit: u32,
+}
+
+/// Body of GET /api/stats/advancements
+#[derive(Debug, Serialize)]
+pub struct AdvancementCompletionsResponse {
+    pub advancement_id: String,
+    /// First to complete it first
+    pub completions: Vec<AdvancementCompletion>,
+}
+
+/// GET /api/stats/advancements?id= - Who completed an advancement, in order
+pub async fn get_advancement_completions(
+    State(state): State<PublicState>,
+    Query(query): Query<AdvancementCompletionsQuery>,
+) -> Result<Json<AdvancementCompletionsResponse>, AppError> {
+    let completions = state.stats.advancement_completions(&query.id, query.limit.clamp(1, 100)).await?;
+    Ok(Json(AdvancementCompletionsResponse {
+        advancement_id: query.id,
+        completions,
+    }))
+}
+
+/// Query parameters for GET /api/stats/leaderboards/:metric
+#[derive(Debug, Deserialize)]
+pub struct LeaderboardQuery {
+    #[serde(default)]
+    pub window: LeaderboardWindow,
+    #[serde(default = "default_leaderboard_limit")]
+    pub limit: u32,
+}
+
+/// GET /api/stats/leaderboards/:metric - Top players for a stat over 7d, 30d or all time
+pub async fn get_stat_leaderboard(
+    State(state): State<PublicState>,
+    Path(metric): Path<String>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Result<Json<Leaderboard>, AppError> {
+    let metric = LeaderboardMetric::parse(&metric).ok_or_else(|| {
+        AppError::NotFound(format!(
+            "Unknown leaderboard {}; expected one of {}",
+            metric,
+            LeaderboardMetric::ALL.map(|m| m.as_str()).join(", ")
+        ))
+    })?;
+
+    // Rollups are daily, so a window covers today plus the days before it
+    let today = database::stats::day_of(chrono::Utc::now().timestamp());
+    let since_day = query.window.days().map(|days| today - (days - 1));
+
+    let entries = state.stats.leaderboard(metric, since_day, query.limit.clamp(1, 100)).await?;
+    Ok(Json(Leaderboard {
+        metric,
+        window: query.window,
+        entries,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_chat_content() {
+        assert_eq!(sanitize_chat_content("  hello there \n").unwrap(), "hello there");
+        assert_eq!(sanitize_chat_content("§4[Server]§r hi").unwrap(), "[Server] hi");
+        assert!(sanitize_chat_content("§a").is_err());
+        assert!(sanitize_chat_content(&"a".repeat(MAX_CHAT_MESSAGE_LEN + 1)).is_err());
+        assert!(sanitize_chat_content(&"é".repeat(MAX_CHAT_MESSAGE_LEN)).is_ok());
+    }
+
+    #[test]
+    fn test_normalize_uuid() {
+        assert_eq!(
+            normalize_uuid("adca5752c67a4f0aae7444d9f369f6f8"),
+            "adca5752-c67a-4f0a-ae74-44d9f369f6f8"
+        );
+        assert_eq!(
+            normalize_uuid("adca5752-c67a-4f0a-ae74-44d9f369f6f8"),
+            "adca5752-c67a-4f0a-ae74-44d9f369f6f8"
+        );
     }
 }
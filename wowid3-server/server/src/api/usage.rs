@@ -0,0 +1,81 @@
+use crate::api::admin::{require_role, AdminState, AppError};
+use crate::database::{
+    self,
+    usage::{FileDownloads, UsageOverview},
+};
+use crate::middleware::AdminToken;
+use crate::models::AdminRole;
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+/// Query parameters for GET /api/admin/stats/overview
+#[derive(Debug, Deserialize)]
+pub struct OverviewQuery {
+    /// Length of the daily bandwidth and player series
+    #[serde(default = "default_days")]
+    pub days: u32,
+}
+
+fn default_days() -> u32 { 30 }
+
+#[derive(Debug, Serialize)]
+pub struct StatsOverviewResponse {
+    #[serde(flatten)]
+    pub usage: UsageOverview,
+    /// Players the tracker currently reports as online
+    pub online_now: usize,
+    /// Unix timestamp of the last tracker update (0 if the tracker never reported)
+    pub tracker_last_updated: u64,
+}
+
+/// GET /api/admin/stats/overview - Downloads per release, launcher installs, bandwidth and player activity
+pub async fn get_stats_overview(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Query(query): Query<OverviewQuery>,
+) -> Result<Json<StatsOverviewResponse>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let days = query.days.clamp(1, 365);
+    let usage = database::usage::overview(&state.db.conn, days)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read usage stats: {}", e)))?;
+
+    let tracker = state.tracker.read().await;
+
+    Ok(Json(StatsOverviewResponse {
+        usage,
+        online_now: tracker.online_players.len(),
+        tracker_last_updated: tracker.last_updated,
+    }))
+}
+
+/// Query parameters for GET /api/admin/stats/files
+#[derive(Debug, Deserialize)]
+pub struct FileStatsQuery {
+    /// Only files of this release
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default = "default_file_limit")]
+    pub limit: u32,
+}
+
+fn default_file_limit() -> u32 { 50 }
+
+/// GET /api/admin/stats/files - Most downloaded release files
+pub async fn get_file_stats(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Query(query): Query<FileStatsQuery>,
+) -> Result<Json<Vec<FileDownloads>>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let files = database::usage::file_downloads(&state.db.conn, query.version, query.limit.clamp(1, 1000))
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read file download stats: {}", e)))?;
+
+    Ok(Json(files))
+}
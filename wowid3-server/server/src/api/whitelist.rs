@@ -0,0 +1,157 @@
+use crate::api::admin::{require_role, AdminState, AppError};
+use crate::api::public::{AppError as PublicError, PublicState};
+use crate::cache::CacheEvent;
+use crate::database::{self, whitelist::WhitelistEntry};
+use crate::middleware::AdminToken;
+use crate::models::AdminRole;
+use crate::utils::http::etag_matches;
+use crate::utils::player::parse_player_uuid;
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Longest note kept with an entry
+const MAX_NOTE_LEN: usize = 256;
+
+/// Body of POST /api/admin/whitelist
+#[derive(Debug, Deserialize)]
+pub struct AddWhitelistRequest {
+    /// Minecraft UUID, with or without dashes
+    pub uuid: String,
+    pub name: String,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// One entry of the server's `whitelist.json`
+#[derive(Debug, Serialize)]
+pub struct MinecraftWhitelistEntry {
+    pub uuid: String,
+    pub name: String,
+}
+
+/// Minecraft names are 3-16 characters of letters, digits and underscores
+fn validate_player_name(name: &str) -> Result<(), AppError> {
+    let valid = (3..=16).contains(&name.len())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!("Invalid player name: {}", name)))
+    }
+}
+
+/// GET /api/admin/whitelist - Players allowed on the Minecraft server
+pub async fn list_whitelist(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+) -> Result<Json<Vec<WhitelistEntry>>, AppError> {
+    require_role(&token, AdminRole::Viewer)?;
+
+    let entries = database::whitelist::list(&state.db.conn)
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to read whitelist: {}", e)))?;
+
+    Ok(Json(entries))
+}
+
+/// POST /api/admin/whitelist - Add a player (or update their name and note)
+pub async fn add_to_whitelist(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Json(request): Json<AddWhitelistRequest>,
+) -> Result<Json<WhitelistEntry>, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let uuid = parse_player_uuid(&request.uuid)?;
+    let name = request.name.trim().to_string();
+    validate_player_name(&name)?;
+    let note = request
+        .note
+        .map(|note| note.trim().chars().take(MAX_NOTE_LEN).collect::<String>())
+        .filter(|note| !note.is_empty());
+
+    let entry = database::whitelist::upsert(&state.db.conn, uuid, name, note, token.claims.sub.clone())
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to update whitelist: {}", e)))?;
+
+    state.cache.publish(CacheEvent::WhitelistChanged).await;
+    Ok(Json(entry))
+}
+
+/// DELETE /api/admin/whitelist/:uuid - Remove a player
+pub async fn remove_from_whitelist(
+    State(state): State<AdminState>,
+    Extension(token): Extension<AdminToken>,
+    Path(uuid): Path<String>,
+) -> Result<StatusCode, AppError> {
+    require_role(&token, AdminRole::ReleaseManager)?;
+
+    let uuid = parse_player_uuid(&uuid)?;
+    let removed = database::whitelist::remove(&state.db.conn, uuid.clone())
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to update whitelist: {}", e)))?;
+    if !removed {
+        return Err(AppError::NotFound(format!("Player {} is not whitelisted", uuid)));
+    }
+
+    state.cache.publish(CacheEvent::WhitelistChanged).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/tracker/whitelist - The whitelist in `whitelist.json` format
+///
+/// Polled by the game server's tracker mod (which can also refetch on the
+/// `whitelist` event from /api/events). Supports If-None-Match. Tracker
+/// authentication is enforced by middleware.
+pub async fn get_tracker_whitelist(
+    State(state): State<PublicState>,
+    headers: HeaderMap,
+) -> Result<Response, PublicError> {
+    let entries: Vec<MinecraftWhitelistEntry> = database::whitelist::list(&state.db.conn)
+        .await?
+        .into_iter()
+        .map(|entry| MinecraftWhitelistEntry {
+            uuid: entry.uuid,
+            name: entry.name,
+        })
+        .collect();
+
+    let body = serde_json::to_vec_pretty(&entries)
+        .map_err(|e| PublicError::Internal(anyhow::anyhow!("Failed to serialize whitelist: {}", e)))?;
+    let etag = format!("\"{:x}\"", Sha256::digest(&body));
+
+    if let Some(if_none_match) = headers.get(header::IF_NONE_MATCH).and_then(|h| h.to_str().ok()) {
+        if etag_matches(if_none_match, &etag) {
+            return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+        }
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (header::ETAG, etag),
+            (header::CACHE_CONTROL, "no-cache".to_string()),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_player_name() {
+        assert!(validate_player_name("jeb_").is_ok());
+        assert!(validate_player_name("ab").is_err());
+        assert!(validate_player_name("has space").is_err());
+        assert!(validate_player_name("seventeen_chars_x").is_err());
+    }
+}
@@ -1,76 +1,356 @@
-use crate::models::Manifest;
+pub mod tiles;
+
+use crate::models::{Manifest, ModInfo, ReleaseChannel};
+use crate::utils::compression::PrecompressedBody;
+use axum::body::Bytes;
 use lru::LruCache;
 use serde::{Deserialize, Serialize};
 use std::num::NonZeroUsize;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, RwLock};
+
+/// Default manifest cache capacity and entry lifetime
+const MANIFEST_CACHE_SIZE: usize = 50;
+const MANIFEST_CACHE_TTL: Duration = Duration::from_secs(300);
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// Default hot-file cache budget and per-file limit
+const HOT_FILE_CACHE_BYTES: u64 = 64 * 1024 * 1024;
+const HOT_FILE_MAX_BYTES: u64 = 256 * 1024;
+
+/// Jars whose mod metadata is kept, keyed by SHA-256
+const JAR_METADATA_CACHE_SIZE: usize = 4096;
+
+/// Cache key for the latest manifest
+pub const LATEST_MANIFEST_KEY: &str = "latest";
+
+/// Cache key for the latest manifest of a release channel
+pub fn latest_manifest_key(channel: ReleaseChannel) -> String {
+    match channel {
+        ReleaseChannel::Stable => LATEST_MANIFEST_KEY.to_string(),
+        channel => format!("{}:{}", LATEST_MANIFEST_KEY, channel.as_str()),
+    }
+}
+
+/// Cache key for a specific release manifest
+pub fn version_manifest_key(version: &str) -> String {
+    format!("version:{}", version)
+}
+
+/// Invalidation events published by every mutating admin operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheEvent {
+    /// A release manifest was written (new release or rewrite)
+    ReleaseWritten { version: String },
+    /// A release was deleted from storage
+    ReleaseDeleted { version: String },
+    /// Blacklist patterns changed; every cached manifest is suspect
+    BlacklistChanged,
+    /// A launcher version (or a platform file of one) was published
+    LauncherReleased { version: String },
+    /// Players were added to or removed from the server whitelist
+    WhitelistChanged,
+    /// A server resource pack version was uploaded or deleted
+    ResourcePacksChanged,
+}
 
-const MANIFEST_CACHE_SIZE: usize = 50; // Cache up to 50 manifests
+impl CacheEvent {
+    /// Manifest keys affected by this event (`None` means drop everything)
+    fn manifest_keys(&self) -> Option<Vec<String>> {
+        match self {
+            CacheEvent::ReleaseWritten { version } | CacheEvent::ReleaseDeleted { version } => {
+                // Any channel's latest manifest may be a copy of this release, so drop them too
+                Some(vec![
+                    latest_manifest_key(ReleaseChannel::Stable),
+                    latest_manifest_key(ReleaseChannel::Beta),
+                    version_manifest_key(version),
+                ])
+            }
+            CacheEvent::BlacklistChanged => None,
+            // Launcher versions, the whitelist and resource packs aren't cached; only subscribers care
+            CacheEvent::LauncherReleased { .. }
+            | CacheEvent::WhitelistChanged
+            | CacheEvent::ResourcePacksChanged => Some(Vec::new()),
+        }
+    }
+}
 
 /// Cache statistics for monitoring
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheStats {
     pub manifest_cache_size: usize,
     pub manifest_cache_capacity: usize,
+    /// How long a manifest stays cached, in seconds (0 means until invalidated)
+    pub manifest_cache_ttl_secs: u64,
     pub manifest_hits: u64,
     pub manifest_misses: u64,
+    /// Misses caused by an entry outliving the TTL
+    pub manifest_expired: u64,
+    pub hot_file_count: usize,
+    pub hot_file_bytes: u64,
+    pub hot_file_capacity_bytes: u64,
+    pub hot_file_hits: u64,
+    pub hot_file_misses: u64,
+}
+
+/// Small release files kept in memory, keyed by sha256
+///
+/// Release files are content-addressed, so entries never go stale; eviction is
+/// purely by size, least recently used first.
+struct HotFiles {
+    files: LruCache<String, Bytes>,
+    bytes: u64,
+    capacity_bytes: u64,
+    max_file_bytes: u64,
+    hits: u64,
+    misses: u64,
+}
+
+/// A cached value and when it was stored
+struct Timed<T> {
+    value: Arc<T>,
+    cached_at: Instant,
+}
+
+impl<T> Timed<T> {
+    fn new(value: Arc<T>) -> Self {
+        Self { value, cached_at: Instant::now() }
+    }
+
+    fn is_expired(&self, ttl: Option<Duration>) -> bool {
+        ttl.is_some_and(|ttl| self.cached_at.elapsed() >= ttl)
+    }
 }
 
 /// Global cache manager for server-wide caching
 pub struct CacheManager {
-    manifests: Arc<RwLock<LruCache<String, Arc<Manifest>>>>,
+    manifests: Arc<RwLock<LruCache<String, Timed<Manifest>>>>,
+    /// Serialized, precompressed manifest responses under the same keys as `manifests`
+    manifest_bodies: Arc<RwLock<LruCache<String, Timed<PrecompressedBody>>>>,
+    /// Manifest entry lifetime; `None` keeps entries until invalidated or evicted
+    manifest_ttl: Arc<Mutex<Option<Duration>>>,
     manifest_hits: Arc<RwLock<u64>>,
     manifest_misses: Arc<RwLock<u64>>,
+    manifest_expired: Arc<RwLock<u64>>,
+    hot_files: Arc<Mutex<HotFiles>>,
+    /// Mod metadata read from jars, keyed by SHA-256 so it never goes stale
+    jar_metadata: Arc<Mutex<LruCache<String, ModInfo>>>,
+    events: broadcast::Sender<CacheEvent>,
 }
 
 impl CacheManager {
     /// Create a new cache manager with default sizes
     pub fn new() -> Self {
+        Self::with_hot_file_limits(HOT_FILE_CACHE_BYTES, HOT_FILE_MAX_BYTES)
+    }
+
+    /// Hold up to `capacity` manifests, each for at most `ttl` (`None` or zero disables expiry)
+    ///
+    /// Meant to be called right after construction, before the manager is cloned.
+    pub fn with_manifest_limits(mut self, capacity: usize, ttl: Option<Duration>) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        self.manifests = Arc::new(RwLock::new(LruCache::new(capacity)));
+        self.manifest_bodies = Arc::new(RwLock::new(LruCache::new(capacity)));
+        self.manifest_ttl = Arc::new(Mutex::new(ttl.filter(|ttl| !ttl.is_zero())));
+        self
+    }
+
+    /// Change the manifest limits of a running cache, keeping the most recently used entries that fit
+    pub async fn set_manifest_limits(&self, capacity: usize, ttl: Option<Duration>) {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        self.manifests.write().await.resize(capacity);
+        self.manifest_bodies.write().await.resize(capacity);
+        *self.manifest_ttl.lock().unwrap_or_else(|e| e.into_inner()) = ttl.filter(|ttl| !ttl.is_zero());
+    }
+
+    /// Change the hot-file limits of a running cache, evicting least recently used files to fit
+    pub fn set_hot_file_limits(&self, capacity_bytes: u64, max_file_bytes: u64) {
+        let mut hot = self.hot_files.lock().unwrap_or_else(|e| e.into_inner());
+        hot.capacity_bytes = capacity_bytes;
+        hot.max_file_bytes = max_file_bytes.min(capacity_bytes);
+        while hot.bytes > hot.capacity_bytes {
+            match hot.files.pop_lru() {
+                Some((_, evicted)) => hot.bytes -= evicted.len() as u64,
+                None => break,
+            }
+        }
+    }
+
+    fn manifest_ttl(&self) -> Option<Duration> {
+        *self.manifest_ttl.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Create a cache manager holding up to `capacity_bytes` of files no larger than
+    /// `max_file_bytes` in memory (0 disables the hot-file cache)
+    pub fn with_hot_file_limits(capacity_bytes: u64, max_file_bytes: u64) -> Self {
         Self {
             manifests: Arc::new(RwLock::new(LruCache::new(
                 NonZeroUsize::new(MANIFEST_CACHE_SIZE).unwrap(),
             ))),
+            manifest_bodies: Arc::new(RwLock::new(LruCache::new(
+                NonZeroUsize::new(MANIFEST_CACHE_SIZE).unwrap(),
+            ))),
+            manifest_ttl: Arc::new(Mutex::new(Some(MANIFEST_CACHE_TTL))),
             manifest_hits: Arc::new(RwLock::new(0)),
             manifest_misses: Arc::new(RwLock::new(0)),
+            manifest_expired: Arc::new(RwLock::new(0)),
+            hot_files: Arc::new(Mutex::new(HotFiles {
+                files: LruCache::unbounded(),
+                bytes: 0,
+                capacity_bytes,
+                max_file_bytes: max_file_bytes.min(capacity_bytes),
+                hits: 0,
+                misses: 0,
+            })),
+            jar_metadata: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(JAR_METADATA_CACHE_SIZE).unwrap(),
+            ))),
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
         }
     }
 
+    /// Publish an invalidation event
+    ///
+    /// Affected entries are evicted before this returns, so the next request after an
+    /// admin action always reads from disk. The event is then forwarded to subscribers.
+    pub async fn publish(&self, event: CacheEvent) {
+        match event.manifest_keys() {
+            Some(keys) => {
+                for key in &keys {
+                    self.invalidate_manifest(key).await;
+                }
+            }
+            None => self.clear_manifests().await,
+        }
+
+        tracing::info!("Cache invalidation event: {:?}", event);
+
+        // No subscribers is fine; the cache itself has already been updated
+        let _ = self.events.send(event);
+    }
+
+    /// Subscribe to invalidation events
+    pub fn subscribe(&self) -> broadcast::Receiver<CacheEvent> {
+        self.events.subscribe()
+    }
+
     /// Get manifest from cache
     pub async fn get_manifest(&self, key: &str) -> Option<Arc<Manifest>> {
         let mut cache = self.manifests.write().await;
-        if let Some(manifest) = cache.get(key) {
-            *self.manifest_hits.write().await += 1;
-            tracing::debug!("Manifest cache HIT for key: {}", key);
-            Some(Arc::clone(manifest))
-        } else {
-            *self.manifest_misses.write().await += 1;
-            tracing::debug!("Manifest cache MISS for key: {}", key);
-            None
+        match cache.get(key) {
+            Some(entry) if !entry.is_expired(self.manifest_ttl()) => {
+                *self.manifest_hits.write().await += 1;
+                tracing::debug!("Manifest cache HIT for key: {}", key);
+                Some(Arc::clone(&entry.value))
+            }
+            Some(_) => {
+                cache.pop(key);
+                *self.manifest_misses.write().await += 1;
+                *self.manifest_expired.write().await += 1;
+                tracing::debug!("Manifest cache entry EXPIRED for key: {}", key);
+                None
+            }
+            None => {
+                *self.manifest_misses.write().await += 1;
+                tracing::debug!("Manifest cache MISS for key: {}", key);
+                None
+            }
         }
     }
 
     /// Put manifest into cache
     pub async fn put_manifest(&self, key: String, manifest: Manifest) {
         let mut cache = self.manifests.write().await;
-        cache.put(key.clone(), Arc::new(manifest));
+        cache.put(key.clone(), Timed::new(Arc::new(manifest)));
         tracing::debug!("Cached manifest for key: {}", key);
     }
 
+    /// Get a precompressed manifest response body
+    pub async fn get_manifest_body(&self, key: &str) -> Option<Arc<PrecompressedBody>> {
+        let mut cache = self.manifest_bodies.write().await;
+        match cache.get(key) {
+            Some(entry) if !entry.is_expired(self.manifest_ttl()) => Some(Arc::clone(&entry.value)),
+            Some(_) => {
+                cache.pop(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Put a precompressed manifest response body into cache
+    pub async fn put_manifest_body(&self, key: String, body: Arc<PrecompressedBody>) {
+        self.manifest_bodies.write().await.put(key, Timed::new(body));
+    }
+
     /// Invalidate a specific manifest cache entry
     pub async fn invalidate_manifest(&self, key: &str) {
         let mut cache = self.manifests.write().await;
         cache.pop(key);
+        self.manifest_bodies.write().await.pop(key);
         tracing::debug!("Invalidated manifest cache for key: {}", key);
     }
 
+    /// Whether a file of this size is eligible for the hot-file cache
+    pub fn is_hot_file_size(&self, size: u64) -> bool {
+        let hot = self.hot_files.lock().unwrap_or_else(|e| e.into_inner());
+        size > 0 && size <= hot.max_file_bytes
+    }
+
+    /// Get a release file's contents by sha256
+    pub fn get_hot_file(&self, sha256: &str) -> Option<Bytes> {
+        let mut hot = self.hot_files.lock().unwrap_or_else(|e| e.into_inner());
+        match hot.files.get(sha256).cloned() {
+            Some(bytes) => {
+                hot.hits += 1;
+                Some(bytes)
+            }
+            None => {
+                hot.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Keep a release file in memory, evicting least recently used files to fit
+    pub fn put_hot_file(&self, sha256: String, bytes: Bytes) {
+        let mut hot = self.hot_files.lock().unwrap_or_else(|e| e.into_inner());
+        let size = bytes.len() as u64;
+        if size == 0 || size > hot.max_file_bytes {
+            return;
+        }
+        if let Some(previous) = hot.files.put(sha256, bytes) {
+            hot.bytes -= previous.len() as u64;
+        }
+        hot.bytes += size;
+        while hot.bytes > hot.capacity_bytes {
+            match hot.files.pop_lru() {
+                Some((_, evicted)) => hot.bytes -= evicted.len() as u64,
+                None => break,
+            }
+        }
+    }
+
     /// Clear all caches
     pub async fn clear_all(&self) {
         let mut manifest_cache = self.manifests.write().await;
         manifest_cache.clear();
+        self.manifest_bodies.write().await.clear();
+
+        {
+            let mut hot = self.hot_files.lock().unwrap_or_else(|e| e.into_inner());
+            hot.files.clear();
+            hot.bytes = 0;
+            hot.hits = 0;
+            hot.misses = 0;
+        }
+        self.jar_metadata.lock().unwrap_or_else(|e| e.into_inner()).clear();
 
         // Reset statistics
         *self.manifest_hits.write().await = 0;
         *self.manifest_misses.write().await = 0;
+        *self.manifest_expired.write().await = 0;
 
         tracing::info!("Cleared all caches");
     }
@@ -79,23 +359,52 @@ impl CacheManager {
     pub async fn clear_manifests(&self) {
         let mut cache = self.manifests.write().await;
         cache.clear();
+        self.manifest_bodies.write().await.clear();
         tracing::info!("Cleared manifest cache");
     }
 
-    /// Clear JAR metadata cache (no-op: JAR caching removed)
+    /// Mod metadata of the jar with this SHA-256, if it was read before
+    pub fn get_jar_metadata(&self, sha256: &str) -> Option<ModInfo> {
+        self.jar_metadata
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(sha256)
+            .cloned()
+    }
+
+    pub fn put_jar_metadata(&self, sha256: String, mod_info: ModInfo) {
+        self.jar_metadata
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .put(sha256, mod_info);
+    }
+
+    /// Clear JAR metadata cache
     pub async fn clear_jar_metadata(&self) {
-        tracing::info!("JAR metadata cache clearing requested (no-op: feature removed)");
+        self.jar_metadata.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        tracing::info!("Cleared JAR metadata cache");
     }
 
     /// Get cache statistics
     pub async fn get_stats(&self) -> CacheStats {
         let manifest_cache = self.manifests.read().await;
+        let manifest_hits = *self.manifest_hits.read().await;
+        let manifest_misses = *self.manifest_misses.read().await;
+        let manifest_expired = *self.manifest_expired.read().await;
+        let hot = self.hot_files.lock().unwrap_or_else(|e| e.into_inner());
 
         CacheStats {
             manifest_cache_size: manifest_cache.len(),
             manifest_cache_capacity: manifest_cache.cap().get(),
-            manifest_hits: *self.manifest_hits.read().await,
-            manifest_misses: *self.manifest_misses.read().await,
+            manifest_cache_ttl_secs: self.manifest_ttl().map_or(0, |ttl| ttl.as_secs()),
+            manifest_hits,
+            manifest_misses,
+            manifest_expired,
+            hot_file_count: hot.files.len(),
+            hot_file_bytes: hot.bytes,
+            hot_file_capacity_bytes: hot.capacity_bytes,
+            hot_file_hits: hot.hits,
+            hot_file_misses: hot.misses,
         }
     }
 }
@@ -110,8 +419,136 @@ impl Clone for CacheManager {
     fn clone(&self) -> Self {
         Self {
             manifests: Arc::clone(&self.manifests),
+            manifest_bodies: Arc::clone(&self.manifest_bodies),
+            manifest_ttl: Arc::clone(&self.manifest_ttl),
             manifest_hits: Arc::clone(&self.manifest_hits),
             manifest_misses: Arc::clone(&self.manifest_misses),
+            manifest_expired: Arc::clone(&self.manifest_expired),
+            hot_files: Arc::clone(&self.hot_files),
+            jar_metadata: Arc::clone(&self.jar_metadata),
+            events: self.events.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ModLoader;
+
+    fn manifest(version: &str) -> Manifest {
+        Manifest {
+            version: version.to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
+            fabric_loader: "0.15.0".to_string(),
+            files: vec![],
+            changelog: String::new(),
+            ignore_patterns: vec![],
+            channel: ReleaseChannel::Stable,
+            optional_groups: Vec::new(),
+            ignore_overrides: Default::default(),
         }
     }
+
+    #[tokio::test]
+    async fn test_release_written_evicts_latest_and_version() {
+        let cache = CacheManager::new();
+        cache.put_manifest(LATEST_MANIFEST_KEY.to_string(), manifest("1.0.0")).await;
+        cache.put_manifest(latest_manifest_key(ReleaseChannel::Beta), manifest("1.0.0")).await;
+        cache.put_manifest(version_manifest_key("1.0.0"), manifest("1.0.0")).await;
+        cache.put_manifest(version_manifest_key("0.9.0"), manifest("0.9.0")).await;
+
+        let mut events = cache.subscribe();
+        cache
+            .publish(CacheEvent::ReleaseWritten { version: "1.0.0".to_string() })
+            .await;
+
+        assert!(cache.get_manifest(LATEST_MANIFEST_KEY).await.is_none());
+        assert!(cache.get_manifest(&latest_manifest_key(ReleaseChannel::Beta)).await.is_none());
+        assert!(cache.get_manifest(&version_manifest_key("1.0.0")).await.is_none());
+        assert!(cache.get_manifest(&version_manifest_key("0.9.0")).await.is_some());
+        assert_eq!(
+            events.recv().await.unwrap(),
+            CacheEvent::ReleaseWritten { version: "1.0.0".to_string() }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_blacklist_change_clears_everything() {
+        let cache = CacheManager::new();
+        cache.put_manifest(LATEST_MANIFEST_KEY.to_string(), manifest("1.0.0")).await;
+        cache.put_manifest(version_manifest_key("0.9.0"), manifest("0.9.0")).await;
+
+        cache.publish(CacheEvent::BlacklistChanged).await;
+
+        assert_eq!(cache.get_stats().await.manifest_cache_size, 0);
+    }
+
+    #[tokio::test]
+    async fn test_manifest_limits_and_expiry() {
+        let cache = CacheManager::new().with_manifest_limits(2, Some(Duration::from_millis(50)));
+        cache.put_manifest(version_manifest_key("1.0.0"), manifest("1.0.0")).await;
+        cache.put_manifest(version_manifest_key("1.1.0"), manifest("1.1.0")).await;
+        cache.put_manifest(version_manifest_key("1.2.0"), manifest("1.2.0")).await;
+        assert!(cache.get_manifest(&version_manifest_key("1.0.0")).await.is_none());
+        assert!(cache.get_manifest(&version_manifest_key("1.2.0")).await.is_some());
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(cache.get_manifest(&version_manifest_key("1.2.0")).await.is_none());
+
+        let stats = cache.get_stats().await;
+        assert_eq!(stats.manifest_cache_capacity, 2);
+        assert_eq!(stats.manifest_cache_size, 1);
+        assert_eq!((stats.manifest_hits, stats.manifest_misses, stats.manifest_expired), (1, 2, 1));
+
+        let unbounded = CacheManager::new().with_manifest_limits(10, Some(Duration::ZERO));
+        assert_eq!(unbounded.get_stats().await.manifest_cache_ttl_secs, 0);
+
+        // Resizing a running cache affects every clone and keeps the newest entries
+        let shared = unbounded.clone();
+        for version in ["1.0.0", "1.1.0", "1.2.0"] {
+            unbounded.put_manifest(version_manifest_key(version), manifest(version)).await;
+        }
+        unbounded.set_manifest_limits(2, Some(Duration::from_secs(60))).await;
+        let stats = shared.get_stats().await;
+        assert_eq!((stats.manifest_cache_capacity, stats.manifest_cache_size), (2, 2));
+        assert_eq!(stats.manifest_cache_ttl_secs, 60);
+        assert!(shared.get_manifest(&version_manifest_key("1.0.0")).await.is_none());
+    }
+
+    #[test]
+    fn test_hot_files_evict_least_recently_used() {
+        let cache = CacheManager::with_hot_file_limits(10, 4);
+        cache.put_hot_file("a".to_string(), Bytes::from_static(b"aaaa"));
+        cache.put_hot_file("b".to_string(), Bytes::from_static(b"bbbb"));
+        assert!(cache.get_hot_file("a").is_some());
+
+        // Over budget: "b" is now least recently used
+        cache.put_hot_file("c".to_string(), Bytes::from_static(b"cccc"));
+        assert!(cache.get_hot_file("b").is_none());
+        assert_eq!(cache.get_hot_file("a").unwrap(), Bytes::from_static(b"aaaa"));
+        assert!(cache.get_hot_file("c").is_some());
+
+        // Too large for a single entry
+        cache.put_hot_file("d".to_string(), Bytes::from_static(b"ddddd"));
+        assert!(cache.get_hot_file("d").is_none());
+        assert!(!cache.is_hot_file_size(5));
+    }
+
+    #[tokio::test]
+    async fn test_hot_file_stats() {
+        let cache = CacheManager::with_hot_file_limits(100, 10);
+        cache.put_hot_file("a".to_string(), Bytes::from_static(b"abc"));
+        cache.get_hot_file("a");
+        cache.get_hot_file("missing");
+
+        let stats = cache.get_stats().await;
+        assert_eq!(stats.hot_file_count, 1);
+        assert_eq!(stats.hot_file_bytes, 3);
+        assert_eq!((stats.hot_file_hits, stats.hot_file_misses), (1, 1));
+
+        cache.clear_all().await;
+        assert_eq!(cache.get_stats().await.hot_file_bytes, 0);
+    }
 }
@@ -0,0 +1,467 @@
+//! Disk-backed cache for BlueMap tiles
+//!
+//! BlueMap's web output lives on the Minecraft server's filesystem, mounted
+//! into this host. Tiles are copied into a local LRU cache keyed by
+//! `map_id/tile_path` so map browsing doesn't hit the mount for every request,
+//! and keeps working while the mount (or BlueMap) is unavailable.
+
+use axum::body::Bytes;
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::fs;
+
+/// Metadata kept next to each cached tile (`<hash>.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TileMeta {
+    key: String,
+    /// The tile is stored gzipped (BlueMap's `.gz` variant)
+    gzipped: bool,
+    etag: String,
+    size: u64,
+    /// Source file modification time (ms since the epoch) and length, compared
+    /// on revalidation
+    source_modified_ms: u64,
+    source_len: u64,
+    /// When the source was last checked (ms since the epoch)
+    checked_at_ms: u64,
+}
+
+/// A tile served from the cache
+#[derive(Debug, Clone)]
+pub struct CachedTile {
+    pub data: Bytes,
+    pub gzipped: bool,
+    /// Strong ETag over the stored bytes
+    pub etag: String,
+}
+
+/// Tile cache statistics for monitoring
+#[derive(Debug, Clone, Serialize)]
+pub struct TileCacheStats {
+    pub tile_count: usize,
+    pub bytes: u64,
+    pub capacity_bytes: u64,
+    pub hits: u64,
+    pub revalidations: u64,
+    pub misses: u64,
+    pub stale_served: u64,
+}
+
+struct TileIndex {
+    entries: LruCache<String, TileMeta>,
+    bytes: u64,
+    hits: u64,
+    revalidations: u64,
+    misses: u64,
+    stale_served: u64,
+}
+
+pub struct TileCache {
+    dir: PathBuf,
+    capacity_bytes: u64,
+    ttl: Duration,
+    index: Mutex<TileIndex>,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn modified_ms(metadata: &std::fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// File name for a cache key; keys contain slashes and arbitrary tile paths
+fn file_stem(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
+
+impl TileCache {
+    /// Open the cache in `dir`, re-indexing tiles cached by a previous run
+    ///
+    /// Tiles are checked against their source again once `ttl` has passed;
+    /// least recently used tiles are evicted beyond `capacity_bytes`.
+    pub async fn open(dir: PathBuf, capacity_bytes: u64, ttl: Duration) -> anyhow::Result<Self> {
+        fs::create_dir_all(&dir).await?;
+
+        let mut metas = Vec::new();
+        let mut entries = fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            match fs::read(&path).await.map(|raw| serde_json::from_slice::<TileMeta>(&raw)) {
+                Ok(Ok(meta)) if path.with_extension("bin").exists() => metas.push(meta),
+                _ => {
+                    // Half-written or orphaned entry
+                    let _ = fs::remove_file(&path).await;
+                    let _ = fs::remove_file(path.with_extension("bin")).await;
+                }
+            }
+        }
+
+        // Oldest first, so the most recently checked tiles end up most recently used
+        metas.sort_by_key(|meta| meta.checked_at_ms);
+        let mut index = TileIndex {
+            entries: LruCache::unbounded(),
+            bytes: 0,
+            hits: 0,
+            revalidations: 0,
+            misses: 0,
+            stale_served: 0,
+        };
+        for meta in metas {
+            index.bytes += meta.size;
+            index.entries.put(meta.key.clone(), meta);
+        }
+
+        let cache = Self {
+            dir,
+            capacity_bytes,
+            ttl,
+            index: Mutex::new(index),
+        };
+        cache.evict().await;
+        Ok(cache)
+    }
+
+    fn data_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.bin", file_stem(key)))
+    }
+
+    fn meta_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", file_stem(key)))
+    }
+
+    /// Get a tile, loading it from the first existing source if needed
+    ///
+    /// `sources` are candidate files with whether each is gzipped (BlueMap
+    /// writes either `tile.png` or `tile.png.gz`). Within the TTL the cached
+    /// copy is served as-is; after it, the source's mtime and length decide
+    /// whether to reload. When no source can be read the cached copy is served
+    /// stale, so tiles survive the mount or BlueMap going away. `Ok(None)`
+    /// means the tile doesn't exist anywhere.
+    pub async fn get(&self, key: &str, sources: &[(PathBuf, bool)]) -> io::Result<Option<CachedTile>> {
+        let cached = self.lookup(key);
+
+        if let Some(meta) = &cached {
+            if now_ms().saturating_sub(meta.checked_at_ms) < self.ttl.as_millis() as u64 {
+                if let Some(tile) = self.read_cached(meta).await {
+                    self.index.lock().unwrap_or_else(|e| e.into_inner()).hits += 1;
+                    return Ok(Some(tile));
+                }
+            }
+        }
+
+        // Revalidate against (or load from) the source
+        let mut source = None;
+        for (path, gzipped) in sources {
+            if let Ok(metadata) = fs::metadata(path).await {
+                if metadata.is_file() {
+                    source = Some((path, *gzipped, metadata));
+                    break;
+                }
+            }
+        }
+
+        let Some((path, gzipped, metadata)) = source else {
+            if let Some(meta) = &cached {
+                if let Some(tile) = self.read_cached(meta).await {
+                    tracing::debug!("Serving stale BlueMap tile {}; source unavailable", key);
+                    self.index.lock().unwrap_or_else(|e| e.into_inner()).stale_served += 1;
+                    return Ok(Some(tile));
+                }
+            }
+            return Ok(None);
+        };
+
+        let source_modified_ms = modified_ms(&metadata);
+        if let Some(meta) = cached {
+            let unchanged = meta.gzipped == gzipped
+                && meta.source_modified_ms == source_modified_ms
+                && meta.source_len == metadata.len();
+            if unchanged {
+                if let Some(tile) = self.read_cached(&meta).await {
+                    let meta = TileMeta { checked_at_ms: now_ms(), ..meta };
+                    self.write_meta(&meta).await;
+                    let mut index = self.index.lock().unwrap_or_else(|e| e.into_inner());
+                    index.revalidations += 1;
+                    index.entries.put(meta.key.clone(), meta);
+                    return Ok(Some(tile));
+                }
+            }
+        }
+
+        let data = Bytes::from(fs::read(path).await?);
+        self.index.lock().unwrap_or_else(|e| e.into_inner()).misses += 1;
+
+        let meta = TileMeta {
+            key: key.to_string(),
+            gzipped,
+            etag: format!("\"{:x}\"", Sha256::digest(&data)),
+            size: data.len() as u64,
+            source_modified_ms,
+            source_len: metadata.len(),
+            checked_at_ms: now_ms(),
+        };
+        let tile = CachedTile {
+            data: data.clone(),
+            gzipped,
+            etag: meta.etag.clone(),
+        };
+
+        if data.len() as u64 <= self.capacity_bytes {
+            self.store(meta, &data).await;
+        }
+        Ok(Some(tile))
+    }
+
+    fn lookup(&self, key: &str) -> Option<TileMeta> {
+        self.index
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entries
+            .get(key)
+            .cloned()
+    }
+
+    async fn read_cached(&self, meta: &TileMeta) -> Option<CachedTile> {
+        match fs::read(self.data_path(&meta.key)).await {
+            Ok(data) => Some(CachedTile {
+                data: Bytes::from(data),
+                gzipped: meta.gzipped,
+                etag: meta.etag.clone(),
+            }),
+            Err(e) => {
+                tracing::warn!("Dropping unreadable cached BlueMap tile {}: {}", meta.key, e);
+                self.forget(&meta.key).await;
+                None
+            }
+        }
+    }
+
+    async fn write_meta(&self, meta: &TileMeta) {
+        let result = async {
+            let raw = serde_json::to_vec(meta).map_err(io::Error::other)?;
+            fs::write(self.meta_path(&meta.key), raw).await
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Failed to write BlueMap tile cache metadata for {}: {}", meta.key, e);
+        }
+    }
+
+    async fn store(&self, meta: TileMeta, data: &[u8]) {
+        if let Err(e) = fs::write(self.data_path(&meta.key), data).await {
+            tracing::warn!("Failed to cache BlueMap tile {}: {}", meta.key, e);
+            return;
+        }
+        self.write_meta(&meta).await;
+
+        {
+            let mut index = self.index.lock().unwrap_or_else(|e| e.into_inner());
+            index.bytes += meta.size;
+            if let Some(previous) = index.entries.put(meta.key.clone(), meta) {
+                index.bytes -= previous.size;
+            }
+        }
+        self.evict().await;
+    }
+
+    async fn forget(&self, key: &str) {
+        {
+            let mut index = self.index.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(meta) = index.entries.pop(key) {
+                index.bytes -= meta.size;
+            }
+        }
+        let _ = fs::remove_file(self.data_path(key)).await;
+        let _ = fs::remove_file(self.meta_path(key)).await;
+    }
+
+    /// Drop least recently used tiles until the cache fits its budget
+    async fn evict(&self) {
+        let evicted: Vec<String> = {
+            let mut index = self.index.lock().unwrap_or_else(|e| e.into_inner());
+            let mut evicted = Vec::new();
+            while index.bytes > self.capacity_bytes {
+                match index.entries.pop_lru() {
+                    Some((key, meta)) => {
+                        index.bytes -= meta.size;
+                        evicted.push(key);
+                    }
+                    None => break,
+                }
+            }
+            evicted
+        };
+
+        for key in evicted {
+            let _ = fs::remove_file(self.data_path(&key)).await;
+            let _ = fs::remove_file(self.meta_path(&key)).await;
+        }
+    }
+
+    pub fn stats(&self) -> TileCacheStats {
+        let index = self.index.lock().unwrap_or_else(|e| e.into_inner());
+        TileCacheStats {
+            tile_count: index.entries.len(),
+            bytes: index.bytes,
+            capacity_bytes: self.capacity_bytes,
+            hits: index.hits,
+            revalidations: index.revalidations,
+            misses: index.misses,
+            stale_served: index.stale_served,
+        }
+    }
+
+    /// Periodically pull low-zoom tiles into the cache
+    ///
+    /// BlueMap stores hires tiles under `tiles/0` and lowres levels under
+    /// `tiles/1..`, each coarser level holding far fewer tiles. Every level
+    /// from `min_lod` up is walked, so the zoomed-out view is always cached.
+    pub fn spawn_prefetch(self: Arc<Self>, base_path: PathBuf, interval: Duration, min_lod: u32) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let cache = Arc::clone(&self);
+                let base_path = base_path.clone();
+                let keys = tokio::task::spawn_blocking(move || prefetch_keys(&base_path, min_lod))
+                    .await
+                    .unwrap_or_default();
+
+                let mut loaded = 0usize;
+                for (key, source) in &keys {
+                    let gzipped = source.extension().and_then(|e| e.to_str()) == Some("gz");
+                    if let Ok(Some(_)) = cache.get(key, &[(source.clone(), gzipped)]).await {
+                        loaded += 1;
+                    }
+                }
+                tracing::debug!("Prefetched {} of {} low-zoom BlueMap tiles", loaded, keys.len());
+            }
+        });
+    }
+}
+
+/// Cache keys and source files of every tile at `min_lod` or coarser
+///
+/// Keys match the tile handler's: `map_id/tile_path`, with a `.gz` suffix
+/// stripped since the handler looks tiles up by their requested name.
+fn prefetch_keys(base_path: &Path, min_lod: u32) -> Vec<(String, PathBuf)> {
+    let Ok(maps) = std::fs::read_dir(base_path.join("maps")) else {
+        return Vec::new();
+    };
+
+    let mut keys = Vec::new();
+    for map in maps.flatten() {
+        let Some(map_id) = map.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        let tiles_dir = map.path().join("tiles");
+        let Ok(lods) = std::fs::read_dir(&tiles_dir) else {
+            continue;
+        };
+
+        for lod in lods.flatten() {
+            let is_low_zoom = lod
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<u32>().ok())
+                .is_some_and(|lod| lod >= min_lod);
+            if !is_low_zoom {
+                continue;
+            }
+
+            for entry in walkdir::WalkDir::new(lod.path()).into_iter().flatten() {
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let Ok(relative) = entry.path().strip_prefix(&tiles_dir) else {
+                    continue;
+                };
+                let relative = relative.to_string_lossy().replace('\\', "/");
+                let tile_path = relative.strip_suffix(".gz").unwrap_or(&relative);
+                keys.push((format!("{}/{}", map_id, tile_path), entry.path().to_path_buf()));
+            }
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_tile_cache_serves_stale_when_source_is_gone() {
+        let source_dir = tempdir().unwrap();
+        let cache_dir = tempdir().unwrap();
+        let source = source_dir.path().join("tile.png");
+        std::fs::write(&source, b"tile-v1").unwrap();
+
+        let cache = TileCache::open(cache_dir.path().to_path_buf(), 1024, Duration::ZERO)
+            .await
+            .unwrap();
+        let sources = [(source.clone(), false)];
+
+        let first = cache.get("world/1/x0/z0.png", &sources).await.unwrap().unwrap();
+        assert_eq!(&first.data[..], b"tile-v1");
+
+        // Unchanged source: revalidated without a reload
+        let second = cache.get("world/1/x0/z0.png", &sources).await.unwrap().unwrap();
+        assert_eq!(second.etag, first.etag);
+        assert_eq!(cache.stats().revalidations, 1);
+
+        // Source unavailable (mount down): keep serving the cached copy
+        std::fs::remove_file(&source).unwrap();
+        let stale = cache.get("world/1/x0/z0.png", &sources).await.unwrap().unwrap();
+        assert_eq!(&stale.data[..], b"tile-v1");
+        assert_eq!(cache.stats().stale_served, 1);
+
+        assert!(cache.get("world/1/x9/z9.png", &sources).await.unwrap().is_none());
+
+        // The index survives a restart
+        drop(cache);
+        let reopened = TileCache::open(cache_dir.path().to_path_buf(), 1024, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(reopened.stats().tile_count, 1);
+        assert!(reopened.get("world/1/x0/z0.png", &[]).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_tile_cache_evicts_least_recently_used() {
+        let source_dir = tempdir().unwrap();
+        let cache_dir = tempdir().unwrap();
+        let cache = TileCache::open(cache_dir.path().to_path_buf(), 10, Duration::from_secs(60))
+            .await
+            .unwrap();
+
+        for name in ["a", "b", "c"] {
+            let source = source_dir.path().join(name);
+            std::fs::write(&source, b"1234").unwrap();
+            cache.get(name, &[(source, false)]).await.unwrap();
+        }
+
+        let stats = cache.stats();
+        assert_eq!((stats.tile_count, stats.bytes), (2, 8));
+        assert!(cache.get("a", &[]).await.unwrap().is_none());
+        assert!(cache.get("c", &[]).await.unwrap().is_some());
+    }
+}
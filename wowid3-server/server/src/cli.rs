@@ -1,6 +1,7 @@
 use crate::config::Config;
-use crate::models::{Manifest, ManifestFile};
-use crate::storage::manifest::{read_manifest, set_latest_manifest, write_manifest};
+use crate::database::{store::PostgresStore, Database};
+use crate::models::{IgnorePatternOverrides, Manifest, ManifestFile, ModLoader, ReleaseChannel};
+use crate::storage::{self, manifest::{read_manifest, set_latest_manifest, write_manifest}};
 use crate::utils;
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
@@ -16,6 +17,15 @@ use walkdir::WalkDir;
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// Run as a service: write a PID file and shut down cleanly on SIGTERM.
+    /// The process stays in the foreground for its supervisor (systemd Type=simple)
+    #[arg(long)]
+    pub daemon: bool,
+
+    /// PID file to write (with --daemon, defaults to STORAGE_PATH/wowid3-server.pid)
+    #[arg(long, value_name = "PATH")]
+    pub pid_file: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -30,6 +40,22 @@ pub enum Commands {
         #[arg(long)]
         set_latest: bool,
     },
+
+    /// Move existing release files into the content-addressed object store
+    /// and remove objects no release references
+    DedupeStorage,
+
+    /// Upload every release to the configured storage backend (e.g. after
+    /// switching to S3) and remove objects no release references
+    SyncStorage,
+
+    /// Copy player stats and VPN peers from the local SQLite database into
+    /// Postgres (DATABASE_URL), keeping rows that already exist there
+    CopyToPostgres,
+
+    /// Print new Ed25519 key pairs for signing launcher uploads (LAUNCHER_SIGNING_KEY)
+    /// and manifests (MANIFEST_SIGNING_KEY), with the public keys to embed in launcher builds
+    GenerateSigningKey,
 }
 
 /// Run CLI command
@@ -41,6 +67,23 @@ pub async fn run_cli(cli: Cli, config: Config) -> Result<()> {
         }) => {
             regenerate_manifest(&config, &version, set_latest).await?;
         }
+        Some(Commands::DedupeStorage) => {
+            dedupe_storage(&config).await?;
+        }
+        Some(Commands::SyncStorage) => {
+            sync_storage(&config).await?;
+        }
+        Some(Commands::CopyToPostgres) => {
+            copy_to_postgres(&config).await?;
+        }
+        Some(Commands::GenerateSigningKey) => {
+            let (private_key, public_key) = crate::services::signing::generate_key_pair()?;
+            println!("LAUNCHER_SIGNING_KEY={}", private_key);
+            println!("WOWID3_LAUNCHER_PUBLIC_KEY={}", public_key);
+            let (private_key, public_key) = crate::services::signing::generate_key_pair()?;
+            println!("MANIFEST_SIGNING_KEY={}", private_key);
+            println!("WOWID3_MANIFEST_PUBLIC_KEY={}", public_key);
+        }
         None => {
             // No command provided, return to start server
             return Ok(());
@@ -49,6 +92,84 @@ pub async fn run_cli(cli: Cli, config: Config) -> Result<()> {
     Ok(())
 }
 
+/// Deduplicate every release into the object store, then garbage-collect objects
+async fn dedupe_storage(config: &Config) -> Result<()> {
+    let mut total = storage::files::DedupStats::default();
+
+    for version in storage::manifest::list_versions(config).await? {
+        let manifest = read_manifest(config, &version)
+            .await
+            .with_context(|| format!("Failed to read manifest for {}", version))?;
+        let stats = storage::files::deduplicate_release(config, &manifest).await?;
+
+        total.deduplicated += stats.deduplicated;
+        total.stored += stats.stored;
+        total.bytes_saved += stats.bytes_saved;
+    }
+
+    let (removed, freed) = storage::files::collect_garbage(config).await?;
+
+    tracing::info!(
+        "Storage deduplicated: {} shared files, {} objects, {} bytes saved, {} stale objects removed ({} bytes)",
+        total.deduplicated,
+        total.stored,
+        total.bytes_saved,
+        removed,
+        freed
+    );
+
+    Ok(())
+}
+
+/// Publish every release to the storage backend, then garbage-collect its objects
+async fn sync_storage(config: &Config) -> Result<()> {
+    let backend = storage::backend::from_config(config)?;
+    let versions = storage::manifest::list_versions(config).await?;
+
+    for version in &versions {
+        let manifest = read_manifest(config, version)
+            .await
+            .with_context(|| format!("Failed to read manifest for {}", version))?;
+        backend.publish_release(config, &manifest).await?;
+    }
+
+    let referenced = storage::files::referenced_objects(config).await?;
+    let removed = backend.collect_garbage(&referenced).await?;
+
+    tracing::info!(
+        "Synced {} release(s) to {} storage, {} stale objects removed",
+        versions.len(),
+        backend.name(),
+        removed
+    );
+
+    Ok(())
+}
+
+/// Copy stats and VPN peers into Postgres before switching DATABASE_BACKEND over
+async fn copy_to_postgres(config: &Config) -> Result<()> {
+    let url = config
+        .database_url
+        .as_deref()
+        .context("DATABASE_URL must be set to copy into Postgres")?;
+
+    let db = Database::new(config.storage_path().join("stats.db")).await?;
+    db.init_schema().await?;
+    let postgres = PostgresStore::connect(url, config.database_pool_size).await?;
+    let counts = postgres.import_from_sqlite(&db).await?;
+
+    tracing::info!(
+        "Copied {} player stats, {} event ids, {} rollups, {} advancements and {} VPN peers to Postgres",
+        counts.player_stats,
+        counts.stat_event_ids,
+        counts.stat_rollups,
+        counts.player_advancements,
+        counts.vpn_peers
+    );
+
+    Ok(())
+}
+
 /// Regenerate manifest for a release version by scanning files on disk
 async fn regenerate_manifest(config: &Config, version: &str, set_latest: bool) -> Result<()> {
     tracing::info!("Regenerating manifest for version {}", version);
@@ -65,21 +186,29 @@ async fn regenerate_manifest(config: &Config, version: &str, set_latest: bool) -
     }
 
     // Try to read existing manifest to preserve metadata
-    let (minecraft_version, fabric_loader, changelog) = match read_manifest(config, version).await {
+    let (minecraft_version, loader, fabric_loader, changelog, channel, optional_groups, ignore_overrides) = match read_manifest(config, version).await {
         Ok(existing_manifest) => {
             tracing::info!("Found existing manifest, preserving metadata");
             (
                 existing_manifest.minecraft_version,
+                existing_manifest.loader,
                 existing_manifest.fabric_loader,
                 existing_manifest.changelog,
+                existing_manifest.channel,
+                existing_manifest.optional_groups,
+                existing_manifest.ignore_overrides,
             )
         }
         Err(e) => {
             tracing::warn!("Could not read existing manifest: {}. Using defaults.", e);
             (
                 "1.21.4".to_string(),  // Default Minecraft version
+                ModLoader::Fabric,
                 "0.16.14".to_string(), // Default Fabric Loader version
                 String::new(),         // Empty changelog
+                ReleaseChannel::Stable,
+                Vec::new(),            // No optional groups
+                IgnorePatternOverrides::default(),
             )
         }
     };
@@ -87,7 +216,7 @@ async fn regenerate_manifest(config: &Config, version: &str, set_latest: bool) -
     tracing::info!("Scanning files in: {}", release_dir.display());
 
     // Load blacklist patterns to exclude player/local data
-    let blacklist_patterns = utils::load_blacklist_patterns(config).await?;
+    let blacklist_patterns = ignore_overrides.apply(&utils::load_blacklist_patterns(config).await?);
     let glob_set = utils::compile_patterns(&blacklist_patterns)?;
 
     let removed_blacklisted = remove_blacklisted_files(&release_dir, &glob_set).await?;
@@ -109,19 +238,29 @@ async fn regenerate_manifest(config: &Config, version: &str, set_latest: bool) -
     }
 
     // Create manifest with fresh checksums but preserved metadata
-    let manifest = Manifest {
+    let mut manifest = Manifest {
         version: version.to_string(),
         minecraft_version,
+        loader,
         fabric_loader,
         files,
         changelog,
         ignore_patterns: blacklist_patterns,
+        channel,
+        optional_groups,
+        ignore_overrides,
     };
+    manifest.prune_optional_groups();
 
     // Write manifest (with validation and atomic write)
     tracing::info!("Writing manifest to disk...");
     write_manifest(config, &manifest).await?;
 
+    // Fresh checksums may name objects the storage backend doesn't have yet
+    storage::backend::from_config(config)?
+        .publish_release(config, &manifest)
+        .await?;
+
     tracing::info!("✓ Manifest regenerated successfully!");
     tracing::info!("  Version: {}", version);
     tracing::info!("  Minecraft: {}", manifest.minecraft_version);
@@ -154,7 +293,9 @@ async fn scan_release_files(
     let mut file_count = 0;
     let mut filtered_count = 0;
 
+    // Release files may be symlinks into the object store
     for entry in WalkDir::new(dir)
+        .follow_links(true)
         .into_iter()
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
@@ -207,6 +348,7 @@ async fn scan_release_files(
             url: format!("{}/files/{}/{}", config.base_url, version, relative_str),
             sha256,
             size: data.len() as u64,
+            mirrors: Vec::new(),
         });
     }
 
@@ -223,7 +365,9 @@ async fn scan_release_files(
 async fn remove_blacklisted_files(dir: &PathBuf, glob_set: &GlobSet) -> Result<usize> {
     let mut removed = 0;
 
+    // Release files may be symlinks into the object store
     for entry in WalkDir::new(dir)
+        .follow_links(true)
         .into_iter()
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
@@ -1,5 +1,12 @@
+use crate::models::ReleaseChannel;
+use crate::utils::safe_path::SymlinkPolicy;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Environment variables the process started with, before `.env` was applied
+static STARTUP_ENV: OnceLock<HashMap<String, String>> = OnceLock::new();
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
@@ -21,8 +28,381 @@ pub struct Config {
     #[serde(default = "default_base_url")]
     pub base_url: String,
 
+    /// Base URLs of mirrors serving the same `/files` tree (comma separated in
+    /// MIRROR_BASE_URLS); added to every file in served manifests
+    #[serde(default)]
+    pub mirror_base_urls: Vec<String>,
+
     #[serde(default = "default_tracker_secret")]
     pub tracker_secret: String,
+
+    /// Earlier tracker secrets still accepted while the mod is switched over
+    /// (comma separated in TRACKER_PREVIOUS_SECRETS)
+    #[serde(default)]
+    pub tracker_previous_secrets: Vec<String>,
+
+    /// Only accept HMAC-signed tracker requests, not the plain shared secret
+    #[serde(default)]
+    pub tracker_require_signature: bool,
+
+    /// Allowed clock difference for signed tracker requests, in seconds
+    #[serde(default = "default_tracker_signature_max_skew_secs")]
+    pub tracker_signature_max_skew_secs: u64,
+
+    /// Secret for signing admin JWTs; a random per-process secret is used when unset
+    #[serde(default)]
+    pub jwt_secret: Option<String>,
+
+    /// Admin access token lifetime in seconds
+    #[serde(default = "default_jwt_expiry_secs")]
+    pub jwt_expiry_secs: i64,
+
+    /// Admin refresh token lifetime in seconds
+    #[serde(default = "default_jwt_refresh_expiry_secs")]
+    pub jwt_refresh_expiry_secs: i64,
+
+    /// Only serve manifests and release files to whitelisted players holding a download token
+    #[serde(default)]
+    pub download_auth: bool,
+
+    /// Secret for signing download tokens; derived from JWT_SECRET (or random per process) when unset
+    #[serde(default)]
+    pub download_token_secret: Option<String>,
+
+    /// Download token lifetime in seconds
+    #[serde(default = "default_download_token_ttl_secs")]
+    pub download_token_ttl_secs: i64,
+
+    /// Per-IP request limits per minute for each route group (0 disables)
+    #[serde(default = "default_rate_limit_login_per_minute")]
+    pub rate_limit_login_per_minute: u32,
+
+    #[serde(default = "default_rate_limit_tracker_per_minute")]
+    pub rate_limit_tracker_per_minute: u32,
+
+    #[serde(default = "default_rate_limit_downloads_per_minute")]
+    pub rate_limit_downloads_per_minute: u32,
+
+    #[serde(default = "default_rate_limit_crashes_per_minute")]
+    pub rate_limit_crashes_per_minute: u32,
+
+    #[serde(default = "default_rate_limit_support_per_minute")]
+    pub rate_limit_support_per_minute: u32,
+
+    #[serde(default = "default_rate_limit_screenshots_per_minute")]
+    pub rate_limit_screenshots_per_minute: u32,
+
+    #[serde(default = "default_rate_limit_telemetry_per_minute")]
+    pub rate_limit_telemetry_per_minute: u32,
+
+    #[serde(default = "default_rate_limit_download_tokens_per_minute")]
+    pub rate_limit_download_tokens_per_minute: u32,
+
+    /// Use X-Forwarded-For for client IPs (only enable behind a single reverse proxy that
+    /// appends the client address)
+    #[serde(default)]
+    pub trust_proxy_headers: bool,
+
+    /// CurseForge Core API key; CurseForge imports are disabled when unset
+    #[serde(default)]
+    pub curseforge_api_key: Option<String>,
+
+    /// Log output format (`text` or `json`)
+    #[serde(default)]
+    pub log_format: LogFormat,
+
+    /// How long shutdown waits for open connections (downloads, event streams), in seconds
+    #[serde(default = "default_shutdown_grace_secs")]
+    pub shutdown_grace_secs: u64,
+
+    /// Whether served files may be symlinks leading outside their directory (`contained` or `follow`)
+    #[serde(default)]
+    pub serve_symlinks: SymlinkPolicy,
+
+    /// Where player stats and VPN peers are stored (`sqlite` or `postgres`)
+    #[serde(default)]
+    pub database_backend: DatabaseBackendKind,
+
+    /// Postgres connection URL (required for the `postgres` backend)
+    #[serde(default)]
+    pub database_url: Option<String>,
+
+    /// Maximum Postgres connections per server
+    #[serde(default = "default_database_pool_size")]
+    pub database_pool_size: usize,
+
+    /// Where release files are served from (`filesystem` or `s3`)
+    #[serde(default)]
+    pub storage_backend: StorageBackendKind,
+
+    /// S3-compatible bucket for release files (required for the `s3` backend)
+    #[serde(default)]
+    pub s3_bucket: Option<String>,
+
+    #[serde(default = "default_s3_region")]
+    pub s3_region: String,
+
+    /// Custom endpoint for S3-compatible services (R2, MinIO, B2); AWS when unset
+    #[serde(default)]
+    pub s3_endpoint: Option<String>,
+
+    #[serde(default)]
+    pub s3_access_key: Option<String>,
+
+    #[serde(default)]
+    pub s3_secret_key: Option<String>,
+
+    /// Key prefix inside the bucket, e.g. `wowid3/`
+    #[serde(default)]
+    pub s3_prefix: String,
+
+    /// Address buckets as `endpoint/bucket` instead of `bucket.endpoint` (MinIO)
+    #[serde(default)]
+    pub s3_path_style: bool,
+
+    /// Public (CDN) base URL for the bucket; downloads use presigned URLs when unset
+    #[serde(default)]
+    pub s3_public_url: Option<String>,
+
+    #[serde(default = "default_s3_presign_expiry_secs")]
+    pub s3_presign_expiry_secs: u32,
+
+    /// Largest release draft validation accepts, in MB (0 disables the check)
+    #[serde(default)]
+    pub draft_size_budget_mb: u64,
+
+    /// Keep the newest N releases and prune older ones on a schedule (0 disables pruning).
+    /// Pinned releases and the latest stable and beta releases are always kept.
+    #[serde(default)]
+    pub release_retention_keep: usize,
+
+    /// Versions never pruned, besides releases tagged `pinned`
+    /// (comma separated in RELEASE_RETENTION_PINNED)
+    #[serde(default)]
+    pub release_retention_pinned: Vec<String>,
+
+    /// How often releases outside the retention policy are pruned, in seconds
+    #[serde(default = "default_release_retention_interval_secs")]
+    pub release_retention_interval_secs: u64,
+
+    /// Upload directories untouched for this many hours are deleted (0 keeps them forever)
+    #[serde(default = "default_upload_retention_hours")]
+    pub upload_retention_hours: u64,
+
+    /// How often abandoned uploads are cleaned up, in seconds
+    #[serde(default = "default_upload_gc_interval_secs")]
+    pub upload_gc_interval_secs: u64,
+
+    /// Number of manifests kept in memory
+    #[serde(default = "default_manifest_cache_size")]
+    pub manifest_cache_size: usize,
+
+    /// How long a cached manifest is served before being re-read from disk, in seconds
+    /// (0 keeps it until an admin change invalidates it)
+    #[serde(default = "default_manifest_cache_ttl_secs")]
+    pub manifest_cache_ttl_secs: u64,
+
+    /// Memory for caching small release files, in MB (0 disables the cache)
+    #[serde(default = "default_hot_file_cache_mb")]
+    pub hot_file_cache_mb: u64,
+
+    /// Largest file the hot-file cache holds, in KB
+    #[serde(default = "default_hot_file_max_kb")]
+    pub hot_file_max_kb: u64,
+
+    /// Disk space for cached BlueMap tiles, in MB (0 disables the tile cache)
+    #[serde(default = "default_bluemap_tile_cache_mb")]
+    pub bluemap_tile_cache_mb: u64,
+
+    /// How long a cached BlueMap tile is served before checking its source again, in seconds
+    #[serde(default = "default_bluemap_tile_ttl_secs")]
+    pub bluemap_tile_ttl_secs: u64,
+
+    /// How often low-zoom BlueMap tiles are prefetched into the cache, in seconds (0 disables)
+    #[serde(default)]
+    pub bluemap_prefetch_interval_secs: u64,
+
+    /// Finest lowres level prefetched (BlueMap's `tiles/<lod>`; higher is more zoomed out)
+    #[serde(default = "default_bluemap_prefetch_min_lod")]
+    pub bluemap_prefetch_min_lod: u32,
+
+    /// Fail the readiness check when the BlueMap mount is unreadable
+    #[serde(default)]
+    pub bluemap_health_check: bool,
+
+    /// How often VPN peer handshakes and transfer stats are read from wg0, in seconds
+    #[serde(default = "default_vpn_monitor_interval_secs")]
+    pub vpn_monitor_interval_secs: u64,
+
+    /// Revoke VPN peers with no handshake for this many days (0 disables)
+    #[serde(default = "default_vpn_revoke_after_days")]
+    pub vpn_revoke_after_days: u64,
+
+    /// Minecraft profile API used to verify launcher access tokens
+    #[serde(default = "default_minecraft_profile_url")]
+    pub minecraft_profile_url: String,
+
+    /// Base64 PKCS#8 Ed25519 key signing uploaded launcher binaries
+    /// (create one with `generate-signing-key`); uploads are unsigned when unset
+    #[serde(default)]
+    pub launcher_signing_key: Option<String>,
+
+    /// Base64 PKCS#8 Ed25519 key signing served manifests (a different key from
+    /// LAUNCHER_SIGNING_KEY); manifests are served unsigned when unset
+    #[serde(default)]
+    pub manifest_signing_key: Option<String>,
+
+    /// Discord webhooks announcing published releases and launcher versions
+    /// (comma separated in DISCORD_WEBHOOK_URLS)
+    #[serde(default)]
+    pub discord_webhook_urls: Vec<String>,
+}
+
+/// Release file storage backend
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackendKind {
+    #[default]
+    Filesystem,
+    S3,
+}
+
+/// Log line format
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    /// One JSON object per line, with the request id and other span fields
+    Json,
+}
+
+/// Backend for player stats and VPN peers
+///
+/// Admin data (users, audit log, jobs, sessions, ...) always stays in the local
+/// SQLite database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseBackendKind {
+    #[default]
+    Sqlite,
+    Postgres,
+}
+
+fn default_database_pool_size() -> usize {
+    16
+}
+
+fn default_s3_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_s3_presign_expiry_secs() -> u32 {
+    3600
+}
+
+fn default_rate_limit_login_per_minute() -> u32 {
+    10
+}
+
+fn default_rate_limit_tracker_per_minute() -> u32 {
+    120
+}
+
+fn default_rate_limit_downloads_per_minute() -> u32 {
+    6000 // Modpack installs fetch thousands of files in a burst
+}
+
+fn default_rate_limit_crashes_per_minute() -> u32 {
+    10
+}
+
+fn default_rate_limit_support_per_minute() -> u32 {
+    10
+}
+
+fn default_rate_limit_screenshots_per_minute() -> u32 {
+    30
+}
+
+fn default_rate_limit_telemetry_per_minute() -> u32 {
+    20
+}
+
+fn default_rate_limit_download_tokens_per_minute() -> u32 {
+    20
+}
+
+fn default_download_token_ttl_secs() -> i64 {
+    3600
+}
+
+fn default_release_retention_interval_secs() -> u64 {
+    24 * 3600
+}
+
+fn default_upload_retention_hours() -> u64 {
+    72
+}
+
+fn default_upload_gc_interval_secs() -> u64 {
+    3600
+}
+
+fn default_shutdown_grace_secs() -> u64 {
+    30
+}
+
+fn default_manifest_cache_size() -> usize {
+    50
+}
+
+fn default_manifest_cache_ttl_secs() -> u64 {
+    300
+}
+
+fn default_hot_file_cache_mb() -> u64 {
+    64
+}
+
+fn default_hot_file_max_kb() -> u64 {
+    256
+}
+
+fn default_bluemap_tile_cache_mb() -> u64 {
+    512
+}
+
+fn default_bluemap_tile_ttl_secs() -> u64 {
+    300 // Matches the max-age sent to browsers
+}
+
+fn default_bluemap_prefetch_min_lod() -> u32 {
+    2
+}
+
+fn default_vpn_monitor_interval_secs() -> u64 {
+    60
+}
+
+fn default_vpn_revoke_after_days() -> u64 {
+    30
+}
+
+fn default_minecraft_profile_url() -> String {
+    "https://api.minecraftservices.com/minecraft/profile".to_string()
+}
+
+fn default_jwt_expiry_secs() -> i64 {
+    3600 // 1 hour
+}
+
+fn default_jwt_refresh_expiry_secs() -> i64 {
+    7 * 24 * 3600 // 7 days
+}
+
+fn default_tracker_signature_max_skew_secs() -> u64 {
+    300
 }
 
 fn default_tracker_secret() -> String {
@@ -51,6 +431,7 @@ fn default_base_url() -> String {
 
 impl Config {
     pub fn from_env() -> anyhow::Result<Self> {
+        STARTUP_ENV.get_or_init(|| std::env::vars().collect());
         dotenvy::dotenv().ok(); // Load .env file if it exists
 
         let config = envy::from_env::<Config>()?;
@@ -64,9 +445,52 @@ impl Config {
             eprintln!("WARNING: Using default tracker secret. Set TRACKER_SECRET in .env");
         }
 
+        if config.jwt_secret.is_none() {
+            eprintln!("WARNING: JWT_SECRET not set. Admin sessions will not survive a restart");
+        }
+
         Ok(config)
     }
 
+    /// Read the configuration again the way a fresh start would
+    ///
+    /// Variables from the startup environment win; `.env` is re-read for everything
+    /// else, so edits to it take effect even though the process env still holds the old values.
+    pub fn reload() -> anyhow::Result<Self> {
+        let mut vars = STARTUP_ENV
+            .get()
+            .cloned()
+            .unwrap_or_else(|| std::env::vars().collect());
+        if let Ok(entries) = dotenvy::dotenv_iter() {
+            for entry in entries {
+                let (key, value) = entry?;
+                vars.entry(key).or_insert(value);
+            }
+        }
+        Ok(envy::from_iter(vars)?)
+    }
+
+    /// Mirror copies of a URL on this server; empty for URLs hosted elsewhere
+    ///
+    /// `base_url` is the live one from [`LiveSettings`], since a reload can change it.
+    pub fn mirror_urls(&self, base_url: &str, url: &str) -> Vec<String> {
+        let base = base_url.trim_end_matches('/');
+        let Some(rest) = url.strip_prefix(base).filter(|rest| rest.starts_with('/')) else {
+            return Vec::new();
+        };
+        self.mirror_base_urls
+            .iter()
+            .map(|mirror| mirror.trim().trim_end_matches('/'))
+            .filter(|mirror| !mirror.is_empty() && *mirror != base)
+            .map(|mirror| format!("{}{}", mirror, rest))
+            .collect()
+    }
+
+    /// Draft size budget in bytes, if one is configured
+    pub fn draft_size_budget(&self) -> Option<u64> {
+        (self.draft_size_budget_mb > 0).then(|| self.draft_size_budget_mb * 1024 * 1024)
+    }
+
     pub fn storage_path(&self) -> &PathBuf {
         &self.storage_path
     }
@@ -79,18 +503,96 @@ impl Config {
         self.storage_path.join("uploads")
     }
 
+    /// Chunked uploads that haven't been finalized yet
+    pub fn upload_sessions_path(&self) -> PathBuf {
+        self.storage_path.join("upload_sessions")
+    }
+
+    /// Content-addressed store backing release files
+    pub fn objects_path(&self) -> PathBuf {
+        self.storage_path.join("objects")
+    }
+
+    /// Releases being assembled; moved into `releases/` in one rename once complete
+    pub fn staging_path(&self) -> PathBuf {
+        self.storage_path.join("staging")
+    }
+
+    /// Journals of draft publishes that haven't finished (`<version>.json`)
+    pub fn publishing_path(&self) -> PathBuf {
+        self.storage_path.join("publishing")
+    }
+
+    /// Incomplete release directories moved aside at startup
+    pub fn quarantine_path(&self) -> PathBuf {
+        self.storage_path.join("quarantine")
+    }
+
     pub fn resources_path(&self) -> PathBuf {
         self.storage_path.join("resources")
     }
 
+    /// Versioned server resource packs, stored as `<version>.zip`
+    pub fn resourcepacks_path(&self) -> PathBuf {
+        self.storage_path.join("resourcepacks")
+    }
+
+    /// Local copies of BlueMap tiles from the mounted server filesystem
+    pub fn bluemap_tile_cache_path(&self) -> PathBuf {
+        self.storage_path.join("bluemap-tiles")
+    }
+
+    /// Player screenshots uploaded from the launcher, one directory per UUID
+    pub fn screenshots_path(&self) -> PathBuf {
+        self.storage_path.join("screenshots")
+    }
+
     pub fn blacklist_path(&self) -> PathBuf {
         self.storage_path.join("config-blacklist.txt")
     }
 
+    /// Earlier versions of the blacklist with who saved them (blacklist-history.json)
+    pub fn blacklist_history_path(&self) -> PathBuf {
+        self.storage_path.join("blacklist-history.json")
+    }
+
+    /// Mods the launcher warns about when players add them (mod-compatibility.json)
+    pub fn mod_compatibility_path(&self) -> PathBuf {
+        self.storage_path.join("mod-compatibility.json")
+    }
+
+    /// Launcher branding, theme, polling and feature flags (cms-config.json)
+    pub fn cms_config_path(&self) -> PathBuf {
+        self.storage_path.join("cms-config.json")
+    }
+
+    /// Branding images and audio uploaded through the CMS
+    pub fn cms_assets_path(&self) -> PathBuf {
+        self.storage_path.join("cms-assets")
+    }
+
+    /// Launcher menu music order (audio-playlist.json), tracks live in the CMS assets
+    pub fn audio_playlist_path(&self) -> PathBuf {
+        self.storage_path.join("audio-playlist.json")
+    }
+
+    /// Path to the release tag sidecar (release-tags.json)
+    pub fn release_tags_path(&self) -> PathBuf {
+        self.storage_path.join("release-tags.json")
+    }
+
     pub fn latest_manifest_path(&self) -> PathBuf {
         self.storage_path.join("latest.json")
     }
 
+    /// Latest manifest for a channel; stable keeps the original `latest.json`
+    pub fn latest_channel_manifest_path(&self, channel: ReleaseChannel) -> PathBuf {
+        match channel {
+            ReleaseChannel::Stable => self.latest_manifest_path(),
+            ReleaseChannel::Beta => self.storage_path.join("latest-beta.json"),
+        }
+    }
+
     pub fn release_path(&self, version: &str) -> PathBuf {
         self.releases_path().join(version)
     }
@@ -129,3 +631,110 @@ impl Config {
         self.launcher_version_path(version).join(filename)
     }
 }
+
+/// Settings a reload (SIGHUP) applies to the running server; everything else needs a restart
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeSettings {
+    pub base_url: String,
+    pub cors_origin: Option<String>,
+    pub admin_password: String,
+    pub manifest_cache_size: usize,
+    pub manifest_cache_ttl_secs: u64,
+    pub hot_file_cache_mb: u64,
+    pub hot_file_max_kb: u64,
+}
+
+impl RuntimeSettings {
+    /// Variables whose value differs in `other`
+    pub fn changes(&self, other: &RuntimeSettings) -> Vec<&'static str> {
+        [
+            ("BASE_URL", self.base_url != other.base_url),
+            ("CORS_ORIGIN", self.cors_origin != other.cors_origin),
+            ("ADMIN_PASSWORD", self.admin_password != other.admin_password),
+            ("MANIFEST_CACHE_SIZE", self.manifest_cache_size != other.manifest_cache_size),
+            ("MANIFEST_CACHE_TTL_SECS", self.manifest_cache_ttl_secs != other.manifest_cache_ttl_secs),
+            ("HOT_FILE_CACHE_MB", self.hot_file_cache_mb != other.hot_file_cache_mb),
+            ("HOT_FILE_MAX_KB", self.hot_file_max_kb != other.hot_file_max_kb),
+        ]
+        .into_iter()
+        .filter_map(|(name, changed)| changed.then_some(name))
+        .collect()
+    }
+}
+
+impl From<&Config> for RuntimeSettings {
+    fn from(config: &Config) -> Self {
+        Self {
+            base_url: config.base_url.clone(),
+            cors_origin: config.cors_origin.clone(),
+            admin_password: config.admin_password.clone(),
+            manifest_cache_size: config.manifest_cache_size,
+            manifest_cache_ttl_secs: config.manifest_cache_ttl_secs,
+            hot_file_cache_mb: config.hot_file_cache_mb,
+            hot_file_max_kb: config.hot_file_max_kb,
+        }
+    }
+}
+
+/// The current runtime settings, shared by handlers and swapped on reload
+#[derive(Debug, Clone)]
+pub struct LiveSettings(Arc<RwLock<Arc<RuntimeSettings>>>);
+
+impl LiveSettings {
+    pub fn new(config: &Config) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(RuntimeSettings::from(config)))))
+    }
+
+    /// Snapshot of the current settings; stays consistent even if a reload happens meanwhile
+    pub fn get(&self) -> Arc<RuntimeSettings> {
+        Arc::clone(&self.0.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Install new settings, returning the previous ones
+    pub fn replace(&self, settings: RuntimeSettings) -> Arc<RuntimeSettings> {
+        let mut current = self.0.write().unwrap_or_else(|e| e.into_inner());
+        std::mem::replace(&mut *current, Arc::new(settings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirror_urls() {
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "base_url": "https://origin.example",
+            "mirror_base_urls": ["https://na.example/", " ", "https://origin.example"]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            config.mirror_urls(&config.base_url, "https://origin.example/files/1.0.0/mods/a.jar"),
+            vec!["https://na.example/files/1.0.0/mods/a.jar"]
+        );
+        // Files hosted elsewhere (e.g. Modrinth CDN) have no mirror
+        assert!(config.mirror_urls(&config.base_url, "https://cdn.modrinth.com/a.jar").is_empty());
+        assert!(config.mirror_urls(&config.base_url, "https://origin.example.evil/a.jar").is_empty());
+    }
+
+    #[test]
+    fn test_live_settings_replace() {
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "base_url": "https://old.example"
+        }))
+        .unwrap();
+        let live = LiveSettings::new(&config);
+        let snapshot = live.get();
+
+        let mut next = RuntimeSettings::from(&config);
+        next.base_url = "https://new.example".to_string();
+        next.manifest_cache_size += 1;
+        let previous = live.replace(next);
+
+        assert_eq!(previous.changes(&live.get()), vec!["BASE_URL", "MANIFEST_CACHE_SIZE"]);
+        assert_eq!(live.clone().get().base_url, "https://new.example");
+        // Snapshots taken before the reload keep the old values
+        assert_eq!(snapshot.base_url, "https://old.example");
+    }
+}
@@ -0,0 +1,186 @@
+use anyhow::{Context, Result};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Startup step that failed; attached as error context and mapped to an exit code
+///
+/// Codes follow sysexits(3) so a supervisor can tell a broken configuration (don't
+/// bother restarting) from a failure that may clear up on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartupStage {
+    Config,
+    PidFile,
+    Storage,
+    Database,
+    Listen,
+}
+
+impl StartupStage {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StartupStage::Config => "config",
+            StartupStage::PidFile => "pid_file",
+            StartupStage::Storage => "storage",
+            StartupStage::Database => "database",
+            StartupStage::Listen => "listen",
+        }
+    }
+
+    pub fn exit_code(self) -> u8 {
+        match self {
+            StartupStage::Config => 78,   // EX_CONFIG
+            StartupStage::PidFile => 75,  // EX_TEMPFAIL: another instance is still running
+            StartupStage::Storage => 73,  // EX_CANTCREAT
+            StartupStage::Database => 74, // EX_IOERR
+            StartupStage::Listen => 69,   // EX_UNAVAILABLE: port taken or not permitted
+        }
+    }
+}
+
+impl fmt::Display for StartupStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StartupStage::Config => "Failed to load configuration",
+            StartupStage::PidFile => "Failed to take the PID file",
+            StartupStage::Storage => "Failed to prepare storage",
+            StartupStage::Database => "Failed to open the database",
+            StartupStage::Listen => "Failed to listen for connections",
+        })
+    }
+}
+
+/// The stage a startup error was tagged with, if any
+pub fn startup_stage(error: &anyhow::Error) -> Option<StartupStage> {
+    error.downcast_ref::<StartupStage>().copied()
+}
+
+/// PID file of the running server, removed again when dropped
+pub struct PidFile {
+    path: PathBuf,
+}
+
+impl PidFile {
+    /// Write our PID to `path`, refusing if it names another live process
+    ///
+    /// A file left behind by a crashed run is replaced. If we can't tell whether the
+    /// recorded process is still running, this fails rather than guess.
+    pub fn acquire(path: PathBuf) -> Result<Self> {
+        if let Some(pid) = read_pid(&path) {
+            let alive = pid != std::process::id()
+                && process_alive(pid).with_context(|| {
+                    format!("Cannot tell whether process {} from {} is running", pid, path.display())
+                })?;
+            if alive {
+                anyhow::bail!("{} belongs to running process {}", path.display(), pid);
+            }
+            warn!("Replacing stale PID file {} (process {} is gone)", path.display(), pid);
+        }
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).context("Failed to create PID file directory")?;
+        }
+        std::fs::write(&path, format!("{}\n", std::process::id()))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        // Leave it alone if a newer instance has taken it over
+        if read_pid(&self.path) == Some(std::process::id()) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+fn read_pid(path: &Path) -> Option<u32> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(target_os = "linux")]
+fn process_alive(pid: u32) -> Result<bool> {
+    Ok(Path::new("/proc").join(pid.to_string()).exists())
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_alive(pid: u32) -> Result<bool> {
+    let status = std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .context("Failed to run kill -0")?;
+    Ok(status.success())
+}
+
+#[cfg(windows)]
+fn process_alive(pid: u32) -> Result<bool> {
+    // tasklist prints one CSV row per match, or an INFO line when nothing matches
+    let output = std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+        .output()
+        .context("Failed to run tasklist")?;
+    if !output.status.success() {
+        anyhow::bail!("tasklist exited with {}", output.status);
+    }
+    let needle = format!("\"{}\"", pid);
+    Ok(String::from_utf8_lossy(&output.stdout).contains(&needle))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn process_alive(_pid: u32) -> Result<bool> {
+    anyhow::bail!("no way to check for a running process on this platform")
+}
+
+/// Resolves on Ctrl+C, or SIGTERM on Unix
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    info!("Shutdown requested; waiting for in-flight requests to finish");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pid_file_replaces_stale_and_cleans_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run/server.pid");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        // No process has PID u32::MAX
+        std::fs::write(&path, format!("{}\n", u32::MAX)).unwrap();
+
+        let pid_file = PidFile::acquire(path.clone()).unwrap();
+        assert_eq!(read_pid(&path), Some(std::process::id()));
+        drop(pid_file);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_startup_stage_survives_context() {
+        let error = anyhow::anyhow!("address in use").context(StartupStage::Listen);
+        let error = error.context("Server stopped");
+        assert_eq!(startup_stage(&error), Some(StartupStage::Listen));
+        assert_eq!(startup_stage(&anyhow::anyhow!("other")), None);
+    }
+}
@@ -0,0 +1,234 @@
+use tokio_rusqlite::Connection;
+use anyhow::Result;
+use rusqlite::OptionalExtension;
+
+use crate::models::{AdminRole, AdminUser};
+
+pub async fn init_schema(conn: &Connection) -> Result<()> {
+    conn.call(|conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS admin_users (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                username TEXT UNIQUE NOT NULL,
+                password_hash TEXT NOT NULL,
+                role TEXT NOT NULL,
+                disabled BOOLEAN NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_admin_users_username ON admin_users(username);"
+        )
+    }).await?;
+    Ok(())
+}
+
+/// Subject used for tokens issued via the shared admin password
+pub const ADMIN_SUBJECT: &str = "admin";
+
+/// An admin token whose account can no longer act
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum InactiveAccount {
+    #[error("Account is disabled")]
+    Disabled,
+    #[error("Unknown admin account")]
+    Unknown,
+}
+
+fn row_to_user(row: &rusqlite::Row) -> rusqlite::Result<AdminUser> {
+    let role: String = row.get(2)?;
+    Ok(AdminUser {
+        id: row.get(0)?,
+        username: row.get(1)?,
+        // Unknown roles from a newer schema degrade to read-only
+        role: AdminRole::parse(&role).unwrap_or_default(),
+        disabled: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+/// Number of admin accounts (the shared-password bootstrap login is only allowed at zero)
+pub async fn count_users(conn: &Connection) -> Result<i64> {
+    let count = conn.call(|conn| {
+        conn.query_row("SELECT COUNT(*) FROM admin_users", [], |row| row.get::<_, i64>(0))
+    }).await?;
+    Ok(count)
+}
+
+/// Number of enabled superadmins
+pub async fn count_active_superadmins(conn: &Connection) -> Result<i64> {
+    let count = conn.call(|conn| {
+        conn.query_row(
+            "SELECT COUNT(*) FROM admin_users WHERE role = ?1 AND disabled = 0",
+            [AdminRole::Superadmin.as_str()],
+            |row| row.get::<_, i64>(0),
+        )
+    }).await?;
+    Ok(count)
+}
+
+/// Resolve the current role for a token subject, failing with `InactiveAccount` if it has none
+///
+/// The shared-password `admin` subject is only honoured while no admin accounts exist.
+/// Checked on every admin request, so demotions and disabled accounts apply at once
+/// rather than when the access token expires.
+pub async fn current_role(conn: &Connection, subject: &str) -> Result<AdminRole> {
+    if let Some((user, _)) = get_user_with_hash(conn, subject).await? {
+        if user.disabled {
+            return Err(InactiveAccount::Disabled.into());
+        }
+        return Ok(user.role);
+    }
+
+    if subject == ADMIN_SUBJECT && count_users(conn).await? == 0 {
+        return Ok(AdminRole::Superadmin);
+    }
+
+    Err(InactiveAccount::Unknown.into())
+}
+
+pub async fn list_users(conn: &Connection) -> Result<Vec<AdminUser>> {
+    let users = conn.call(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, username, role, disabled, created_at FROM admin_users ORDER BY username"
+        )?;
+        let users = stmt
+            .query_map([], row_to_user)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok::<_, rusqlite::Error>(users)
+    }).await?;
+    Ok(users)
+}
+
+pub async fn get_user(conn: &Connection, id: i64) -> Result<Option<AdminUser>> {
+    let user = conn.call(move |conn| {
+        conn.query_row(
+            "SELECT id, username, role, disabled, created_at FROM admin_users WHERE id = ?1",
+            [id],
+            row_to_user,
+        )
+        .optional()
+    }).await?;
+    Ok(user)
+}
+
+/// Look up a user and their password hash for login
+pub async fn get_user_with_hash(conn: &Connection, username: &str) -> Result<Option<(AdminUser, String)>> {
+    let username = username.to_string();
+    let user = conn.call(move |conn| {
+        conn.query_row(
+            "SELECT id, username, role, disabled, created_at, password_hash FROM admin_users WHERE username = ?1",
+            [&username],
+            |row| Ok((row_to_user(row)?, row.get::<_, String>(5)?)),
+        )
+        .optional()
+    }).await?;
+    Ok(user)
+}
+
+pub async fn create_user(
+    conn: &Connection,
+    username: &str,
+    password_hash: &str,
+    role: AdminRole,
+) -> Result<AdminUser> {
+    let username = username.to_string();
+    let password_hash = password_hash.to_string();
+    let now = chrono::Utc::now().timestamp();
+
+    let id = conn.call(move |conn| {
+        conn.execute(
+            "INSERT INTO admin_users (username, password_hash, role, disabled, created_at) VALUES (?1, ?2, ?3, 0, ?4)",
+            rusqlite::params![username, password_hash, role.as_str(), now],
+        )?;
+        Ok::<_, rusqlite::Error>(conn.last_insert_rowid())
+    }).await?;
+
+    get_user(conn, id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Admin user {} vanished after insert", id))
+}
+
+/// Apply partial updates; `None` fields are left unchanged
+pub async fn update_user(
+    conn: &Connection,
+    id: i64,
+    password_hash: Option<String>,
+    role: Option<AdminRole>,
+    disabled: Option<bool>,
+) -> Result<Option<AdminUser>> {
+    conn.call(move |conn| {
+        let tx = conn.transaction()?;
+        if let Some(hash) = &password_hash {
+            tx.execute("UPDATE admin_users SET password_hash = ?1 WHERE id = ?2", rusqlite::params![hash, id])?;
+        }
+        if let Some(role) = role {
+            tx.execute("UPDATE admin_users SET role = ?1 WHERE id = ?2", rusqlite::params![role.as_str(), id])?;
+        }
+        if let Some(disabled) = disabled {
+            tx.execute("UPDATE admin_users SET disabled = ?1 WHERE id = ?2", rusqlite::params![disabled, id])?;
+        }
+        tx.commit()
+    }).await?;
+
+    get_user(conn, id).await
+}
+
+/// Delete a user; returns false if it did not exist
+pub async fn delete_user(conn: &Connection, id: i64) -> Result<bool> {
+    let deleted = conn.call(move |conn| {
+        conn.execute("DELETE FROM admin_users WHERE id = ?1", [id])
+    }).await?;
+    Ok(deleted > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_admin_user_crud() {
+        let temp_dir = tempdir().unwrap();
+        let conn = Connection::open(temp_dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        assert_eq!(count_users(&conn).await.unwrap(), 0);
+
+        let user = create_user(&conn, "alice", "hash", AdminRole::ReleaseManager).await.unwrap();
+        assert_eq!(user.role, AdminRole::ReleaseManager);
+        assert_eq!(count_active_superadmins(&conn).await.unwrap(), 0);
+
+        let updated = update_user(&conn, user.id, None, Some(AdminRole::Superadmin), None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.role, AdminRole::Superadmin);
+        assert_eq!(count_active_superadmins(&conn).await.unwrap(), 1);
+
+        let (found, hash) = get_user_with_hash(&conn, "alice").await.unwrap().unwrap();
+        assert_eq!(found.id, user.id);
+        assert_eq!(hash, "hash");
+
+        assert!(delete_user(&conn, user.id).await.unwrap());
+        assert!(get_user(&conn, user.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_current_role() {
+        let temp_dir = tempdir().unwrap();
+        let conn = Connection::open(temp_dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        let inactive = |err: anyhow::Error| err.downcast::<InactiveAccount>().unwrap();
+
+        // The bootstrap login only counts until the first account exists
+        assert_eq!(current_role(&conn, ADMIN_SUBJECT).await.unwrap(), AdminRole::Superadmin);
+        let user = create_user(&conn, "alice", "hash", AdminRole::Superadmin).await.unwrap();
+        assert_eq!(inactive(current_role(&conn, ADMIN_SUBJECT).await.unwrap_err()), InactiveAccount::Unknown);
+
+        update_user(&conn, user.id, None, Some(AdminRole::Viewer), None).await.unwrap();
+        assert_eq!(current_role(&conn, "alice").await.unwrap(), AdminRole::Viewer);
+
+        update_user(&conn, user.id, None, None, Some(true)).await.unwrap();
+        assert_eq!(inactive(current_role(&conn, "alice").await.unwrap_err()), InactiveAccount::Disabled);
+    }
+}
@@ -0,0 +1,138 @@
+use tokio_rusqlite::Connection;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// An advancement a player completed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerAdvancement {
+    /// Namespaced id, e.g. `minecraft:nether/all_effects`
+    pub advancement_id: String,
+    /// Display title, if the tracker sent one
+    pub title: Option<String>,
+    pub completed_at: i64,
+}
+
+/// A player who completed an advancement, in completion order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdvancementCompletion {
+    pub uuid: String,
+    /// Username sent with the completion
+    pub name: String,
+    pub completed_at: i64,
+}
+
+pub async fn init_schema(conn: &Connection) -> Result<()> {
+    conn.call(|conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS player_advancements (
+                uuid TEXT NOT NULL,
+                advancement_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                title TEXT,
+                completed_at INTEGER NOT NULL,
+                PRIMARY KEY (uuid, advancement_id)
+            );
+            CREATE INDEX IF NOT EXISTS idx_advancements_id ON player_advancements(advancement_id, completed_at);"
+        )
+    }).await?;
+    Ok(())
+}
+
+/// Record a completion; the first one is kept if the tracker reports it again
+///
+/// Runs inside the stats processor's transaction.
+pub fn record(
+    conn: &rusqlite::Connection,
+    uuid: &str,
+    name: &str,
+    advancement_id: &str,
+    title: Option<&str>,
+    completed_at: i64,
+) -> rusqlite::Result<bool> {
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO player_advancements (uuid, advancement_id, name, title, completed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        rusqlite::params![uuid, advancement_id, name, title, completed_at],
+    )?;
+    Ok(inserted > 0)
+}
+
+/// A player's completed advancements, oldest first
+pub async fn list_for_player(conn: &Connection, uuid: String) -> Result<Vec<PlayerAdvancement>> {
+    let advancements = conn.call(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT advancement_id, title, completed_at FROM player_advancements
+             WHERE uuid = ?1
+             ORDER BY completed_at, advancement_id",
+        )?;
+        let advancements = stmt
+            .query_map([&uuid], |row| {
+                Ok(PlayerAdvancement {
+                    advancement_id: row.get(0)?,
+                    title: row.get(1)?,
+                    completed_at: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok::<_, rusqlite::Error>(advancements)
+    }).await?;
+    Ok(advancements)
+}
+
+/// Players who completed an advancement, first to complete it first
+pub async fn completions(conn: &Connection, advancement_id: String, limit: u32) -> Result<Vec<AdvancementCompletion>> {
+    let completions = conn.call(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT uuid, name, completed_at FROM player_advancements
+             WHERE advancement_id = ?1
+             ORDER BY completed_at, uuid
+             LIMIT ?2",
+        )?;
+        let completions = stmt
+            .query_map(rusqlite::params![advancement_id, limit], |row| {
+                Ok(AdvancementCompletion {
+                    uuid: row.get(0)?,
+                    name: row.get(1)?,
+                    completed_at: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok::<_, rusqlite::Error>(completions)
+    }).await?;
+    Ok(completions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_first_completion_is_kept() {
+        let dir = tempdir().unwrap();
+        let conn = Connection::open(dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        let id = "minecraft:nether/all_effects";
+        conn.call(move |conn| {
+            assert!(record(conn, "b", "Bob", id, Some("How Did We Get Here?"), 200)?);
+            assert!(record(conn, "a", "Alex", id, None, 100)?);
+            assert!(!record(conn, "b", "Bob", id, None, 300)?);
+            record(conn, "b", "Bob", "minecraft:story/root", Some("Minecraft"), 50)
+        }).await.unwrap();
+
+        let bob = list_for_player(&conn, "b".to_string()).await.unwrap();
+        let ids: Vec<_> = bob.iter().map(|a| a.advancement_id.as_str()).collect();
+        assert_eq!(ids, vec!["minecraft:story/root", id]);
+        assert_eq!(bob[1].completed_at, 200);
+        assert_eq!(bob[1].title.as_deref(), Some("How Did We Get Here?"));
+
+        let first: Vec<_> = completions(&conn, id.to_string(), 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(first, vec!["Alex", "Bob"]);
+    }
+}
@@ -0,0 +1,233 @@
+use tokio_rusqlite::Connection;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// A single recorded admin mutation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub timestamp: i64,
+    /// Token subject (admin username)
+    pub actor: String,
+    pub role: String,
+    /// Token ID, so entries can be tied back to a specific session
+    pub token_id: String,
+    pub method: String,
+    /// Route pattern, e.g. `/api/admin/releases/:version`
+    pub route: String,
+    /// Concrete request path, e.g. `/api/admin/releases/1.2.0`
+    pub path: String,
+    pub query: Option<String>,
+    pub status: u16,
+    pub summary: String,
+}
+
+/// Entry to be inserted (id and timestamp are assigned on insert)
+#[derive(Debug, Clone)]
+pub struct NewAuditEntry {
+    pub actor: String,
+    pub role: String,
+    pub token_id: String,
+    pub method: String,
+    pub route: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub status: u16,
+    pub summary: String,
+}
+
+/// Filters for listing audit entries; all optional
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub actor: Option<String>,
+    pub method: Option<String>,
+    /// Matches route patterns or paths starting with this prefix
+    pub path_prefix: Option<String>,
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+    pub failed_only: bool,
+}
+
+pub async fn init_schema(conn: &Connection) -> Result<()> {
+    conn.call(|conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp INTEGER NOT NULL,
+                actor TEXT NOT NULL,
+                role TEXT NOT NULL,
+                token_id TEXT NOT NULL,
+                method TEXT NOT NULL,
+                route TEXT NOT NULL,
+                path TEXT NOT NULL,
+                query TEXT,
+                status INTEGER NOT NULL,
+                summary TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_audit_timestamp ON audit_log(timestamp);
+            CREATE INDEX IF NOT EXISTS idx_audit_actor ON audit_log(actor);"
+        )
+    }).await?;
+    Ok(())
+}
+
+pub async fn record(conn: &Connection, entry: NewAuditEntry) -> Result<()> {
+    let now = chrono::Utc::now().timestamp();
+
+    conn.call(move |conn| {
+        conn.execute(
+            "INSERT INTO audit_log (timestamp, actor, role, token_id, method, route, path, query, status, summary)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            rusqlite::params![
+                now,
+                entry.actor,
+                entry.role,
+                entry.token_id,
+                entry.method,
+                entry.route,
+                entry.path,
+                entry.query,
+                entry.status,
+                entry.summary,
+            ],
+        )
+    }).await?;
+    Ok(())
+}
+
+/// List entries (newest first) matching the filter, returning the page and total count
+pub async fn list(
+    conn: &Connection,
+    filter: AuditFilter,
+    limit: usize,
+    offset: usize,
+) -> Result<(Vec<AuditEntry>, usize)> {
+    let result = conn.call(move |conn| {
+        let mut clauses: Vec<&str> = Vec::new();
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(actor) = filter.actor {
+            clauses.push("actor = ?");
+            params.push(Box::new(actor));
+        }
+        if let Some(method) = filter.method {
+            clauses.push("method = ?");
+            params.push(Box::new(method.to_uppercase()));
+        }
+        if let Some(prefix) = filter.path_prefix {
+            clauses.push("(route LIKE ? ESCAPE '\\' OR path LIKE ? ESCAPE '\\')");
+            let pattern = format!(
+                "{}%",
+                prefix.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+            );
+            params.push(Box::new(pattern.clone()));
+            params.push(Box::new(pattern));
+        }
+        if let Some(since) = filter.since {
+            clauses.push("timestamp >= ?");
+            params.push(Box::new(since));
+        }
+        if let Some(until) = filter.until {
+            clauses.push("timestamp <= ?");
+            params.push(Box::new(until));
+        }
+        if filter.failed_only {
+            clauses.push("status >= 400");
+        }
+
+        let where_sql = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", clauses.join(" AND "))
+        };
+
+        let total: i64 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM audit_log{}", where_sql),
+            rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+            |row| row.get(0),
+        )?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, timestamp, actor, role, token_id, method, route, path, query, status, summary
+             FROM audit_log{} ORDER BY id DESC LIMIT {} OFFSET {}",
+            where_sql, limit, offset
+        ))?;
+
+        let entries = stmt
+            .query_map(
+                rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+                |row| {
+                    Ok(AuditEntry {
+                        id: row.get(0)?,
+                        timestamp: row.get(1)?,
+                        actor: row.get(2)?,
+                        role: row.get(3)?,
+                        token_id: row.get(4)?,
+                        method: row.get(5)?,
+                        route: row.get(6)?,
+                        path: row.get(7)?,
+                        query: row.get(8)?,
+                        status: row.get(9)?,
+                        summary: row.get(10)?,
+                    })
+                },
+            )?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok::<_, rusqlite::Error>((entries, total as usize))
+    }).await?;
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn entry(actor: &str, method: &str, path: &str, status: u16) -> NewAuditEntry {
+        NewAuditEntry {
+            actor: actor.to_string(),
+            role: "superadmin".to_string(),
+            token_id: "jti".to_string(),
+            method: method.to_string(),
+            route: path.to_string(),
+            path: path.to_string(),
+            query: None,
+            status,
+            summary: format!("{} {}", method, path),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_and_filter() {
+        let temp_dir = tempdir().unwrap();
+        let conn = Connection::open(temp_dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        record(&conn, entry("alice", "POST", "/api/admin/releases", 200)).await.unwrap();
+        record(&conn, entry("bob", "DELETE", "/api/admin/releases/1.0.0", 400)).await.unwrap();
+        record(&conn, entry("alice", "PUT", "/api/admin/blacklist", 200)).await.unwrap();
+
+        let (all, total) = list(&conn, AuditFilter::default(), 10, 0).await.unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(all[0].path, "/api/admin/blacklist"); // newest first
+
+        let filter = AuditFilter { actor: Some("alice".to_string()), ..Default::default() };
+        let (_, total) = list(&conn, filter, 10, 0).await.unwrap();
+        assert_eq!(total, 2);
+
+        let filter = AuditFilter {
+            path_prefix: Some("/api/admin/releases".to_string()),
+            failed_only: true,
+            ..Default::default()
+        };
+        let (failed, total) = list(&conn, filter, 10, 0).await.unwrap();
+        assert_eq!(total, 1);
+        assert_eq!(failed[0].actor, "bob");
+
+        let (page, total) = list(&conn, AuditFilter::default(), 1, 1).await.unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(page.len(), 1);
+    }
+}
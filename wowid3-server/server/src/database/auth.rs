@@ -0,0 +1,77 @@
+use tokio_rusqlite::Connection;
+use anyhow::Result;
+
+pub async fn init_schema(conn: &Connection) -> Result<()> {
+    conn.call(|conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS revoked_tokens (
+                jti TEXT PRIMARY KEY,
+                expires_at INTEGER NOT NULL,
+                revoked_at INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_revoked_expires ON revoked_tokens(expires_at);"
+        )
+    }).await?;
+    Ok(())
+}
+
+/// Add a token ID to the revocation list, pruning entries that have expired anyway
+///
+/// Returns false if it was already revoked. The insert is the check, so of two
+/// concurrent calls for the same token only one gets true.
+pub async fn revoke_token(conn: &Connection, jti: &str, expires_at: i64) -> Result<bool> {
+    let jti = jti.to_string();
+    let now = chrono::Utc::now().timestamp();
+
+    let inserted = conn.call(move |conn| {
+        let inserted = conn.execute(
+            "INSERT INTO revoked_tokens (jti, expires_at, revoked_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(jti) DO NOTHING",
+            rusqlite::params![jti, expires_at, now],
+        )?;
+        conn.execute(
+            "DELETE FROM revoked_tokens WHERE expires_at < ?1",
+            rusqlite::params![now],
+        )?;
+        Ok::<_, rusqlite::Error>(inserted > 0)
+    }).await?;
+    Ok(inserted)
+}
+
+/// Check whether a token ID has been revoked
+pub async fn is_token_revoked(conn: &Connection, jti: &str) -> Result<bool> {
+    let jti = jti.to_string();
+
+    let count = conn.call(move |conn| {
+        conn.query_row(
+            "SELECT COUNT(*) FROM revoked_tokens WHERE jti = ?1",
+            rusqlite::params![jti],
+            |row| row.get::<_, i64>(0),
+        )
+    }).await?;
+
+    Ok(count > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_revoke_token() {
+        let temp_dir = tempdir().unwrap();
+        let conn = Connection::open(temp_dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        let expires_at = chrono::Utc::now().timestamp() + 3600;
+        assert!(!is_token_revoked(&conn, "abc").await.unwrap());
+
+        assert!(revoke_token(&conn, "abc", expires_at).await.unwrap());
+        assert!(is_token_revoked(&conn, "abc").await.unwrap());
+        assert!(!is_token_revoked(&conn, "def").await.unwrap());
+
+        // A second revoke of the same token is refused
+        assert!(!revoke_token(&conn, "abc", expires_at).await.unwrap());
+    }
+}
@@ -0,0 +1,309 @@
+use tokio_rusqlite::Connection;
+use anyhow::Result;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+
+/// A crash report as listed in the admin panel (without the full report text)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReportSummary {
+    pub id: i64,
+    pub timestamp: i64,
+    pub modpack_version: String,
+    pub minecraft_version: Option<String>,
+    pub os: String,
+    pub java_version: Option<String>,
+    pub launcher_version: Option<String>,
+    pub exit_code: Option<i32>,
+    /// Stable hash of the exception and top stack frames
+    pub signature: String,
+    /// Exception line the signature was derived from
+    pub summary: String,
+}
+
+/// A full crash report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    #[serde(flatten)]
+    pub info: CrashReportSummary,
+    pub report: String,
+}
+
+/// Report to be inserted (id and timestamp are assigned on insert)
+#[derive(Debug, Clone)]
+pub struct NewCrashReport {
+    pub modpack_version: String,
+    pub minecraft_version: Option<String>,
+    pub os: String,
+    pub java_version: Option<String>,
+    pub launcher_version: Option<String>,
+    pub exit_code: Option<i32>,
+    pub signature: String,
+    pub summary: String,
+    pub report: String,
+}
+
+/// Filters for listing crash reports; all optional
+#[derive(Debug, Clone, Default)]
+pub struct CrashFilter {
+    pub modpack_version: Option<String>,
+    pub signature: Option<String>,
+    pub os: Option<String>,
+}
+
+/// How often a crash signature occurred
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashSignatureStats {
+    pub signature: String,
+    pub summary: String,
+    pub count: i64,
+    pub first_seen: i64,
+    pub last_seen: i64,
+    /// Distinct modpack versions the signature was reported on, newest report first
+    pub modpack_versions: Vec<String>,
+}
+
+pub async fn init_schema(conn: &Connection) -> Result<()> {
+    conn.call(|conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS crash_reports (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp INTEGER NOT NULL,
+                modpack_version TEXT NOT NULL,
+                minecraft_version TEXT,
+                os TEXT NOT NULL,
+                java_version TEXT,
+                launcher_version TEXT,
+                exit_code INTEGER,
+                signature TEXT NOT NULL,
+                summary TEXT NOT NULL,
+                report TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_crash_timestamp ON crash_reports(timestamp);
+            CREATE INDEX IF NOT EXISTS idx_crash_version_signature ON crash_reports(modpack_version, signature);
+            CREATE INDEX IF NOT EXISTS idx_crash_signature ON crash_reports(signature);"
+        )
+    }).await?;
+    Ok(())
+}
+
+pub async fn insert(conn: &Connection, report: NewCrashReport) -> Result<i64> {
+    let now = chrono::Utc::now().timestamp();
+
+    let id = conn.call(move |conn| {
+        conn.execute(
+            "INSERT INTO crash_reports (timestamp, modpack_version, minecraft_version, os, java_version,
+                launcher_version, exit_code, signature, summary, report)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            rusqlite::params![
+                now,
+                report.modpack_version,
+                report.minecraft_version,
+                report.os,
+                report.java_version,
+                report.launcher_version,
+                report.exit_code,
+                report.signature,
+                report.summary,
+                report.report,
+            ],
+        )?;
+        Ok::<_, rusqlite::Error>(conn.last_insert_rowid())
+    }).await?;
+    Ok(id)
+}
+
+const SUMMARY_COLUMNS: &str = "id, timestamp, modpack_version, minecraft_version, os, java_version,
+    launcher_version, exit_code, signature, summary";
+
+fn summary_from_row(row: &rusqlite::Row) -> rusqlite::Result<CrashReportSummary> {
+    Ok(CrashReportSummary {
+        id: row.get(0)?,
+        timestamp: row.get(1)?,
+        modpack_version: row.get(2)?,
+        minecraft_version: row.get(3)?,
+        os: row.get(4)?,
+        java_version: row.get(5)?,
+        launcher_version: row.get(6)?,
+        exit_code: row.get(7)?,
+        signature: row.get(8)?,
+        summary: row.get(9)?,
+    })
+}
+
+/// List reports (newest first) matching the filter, returning the page and total count
+pub async fn list(
+    conn: &Connection,
+    filter: CrashFilter,
+    limit: usize,
+    offset: usize,
+) -> Result<(Vec<CrashReportSummary>, usize)> {
+    let result = conn.call(move |conn| {
+        let mut clauses: Vec<&str> = Vec::new();
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(version) = filter.modpack_version {
+            clauses.push("modpack_version = ?");
+            params.push(Box::new(version));
+        }
+        if let Some(signature) = filter.signature {
+            clauses.push("signature = ?");
+            params.push(Box::new(signature));
+        }
+        if let Some(os) = filter.os {
+            clauses.push("os = ?");
+            params.push(Box::new(os));
+        }
+
+        let where_sql = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", clauses.join(" AND "))
+        };
+
+        let total: i64 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM crash_reports{}", where_sql),
+            rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+            |row| row.get(0),
+        )?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM crash_reports{} ORDER BY id DESC LIMIT {} OFFSET {}",
+            SUMMARY_COLUMNS, where_sql, limit, offset
+        ))?;
+
+        let reports = stmt
+            .query_map(
+                rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+                summary_from_row,
+            )?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok::<_, rusqlite::Error>((reports, total as usize))
+    }).await?;
+
+    Ok(result)
+}
+
+pub async fn get(conn: &Connection, id: i64) -> Result<Option<CrashReport>> {
+    let report = conn.call(move |conn| {
+        conn.query_row(
+            &format!("SELECT {}, report FROM crash_reports WHERE id = ?1", SUMMARY_COLUMNS),
+            [id],
+            |row| {
+                Ok(CrashReport {
+                    info: summary_from_row(row)?,
+                    report: row.get(10)?,
+                })
+            },
+        )
+        .optional()
+    }).await?;
+    Ok(report)
+}
+
+/// Most frequent crash signatures, optionally limited to one modpack version
+pub async fn top_signatures(
+    conn: &Connection,
+    modpack_version: Option<String>,
+    limit: usize,
+) -> Result<Vec<CrashSignatureStats>> {
+    let stats = conn.call(move |conn| {
+        let (where_sql, params): (&str, Vec<String>) = match modpack_version {
+            Some(version) => (" WHERE modpack_version = ?1", vec![version]),
+            None => ("", Vec::new()),
+        };
+
+        // The summary of the newest report stands in for the whole group
+        let mut stmt = conn.prepare(&format!(
+            "SELECT signature,
+                    (SELECT c2.summary FROM crash_reports c2 WHERE c2.signature = c.signature ORDER BY c2.id DESC LIMIT 1),
+                    COUNT(*), MIN(timestamp), MAX(timestamp)
+             FROM crash_reports c{}
+             GROUP BY signature
+             ORDER BY COUNT(*) DESC, MAX(timestamp) DESC
+             LIMIT {}",
+            where_sql, limit
+        ))?;
+
+        let mut stats = stmt
+            .query_map(rusqlite::params_from_iter(params.iter()), |row| {
+                Ok(CrashSignatureStats {
+                    signature: row.get(0)?,
+                    summary: row.get(1)?,
+                    count: row.get(2)?,
+                    first_seen: row.get(3)?,
+                    last_seen: row.get(4)?,
+                    modpack_versions: Vec::new(),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut versions_stmt = conn.prepare(
+            "SELECT modpack_version FROM crash_reports WHERE signature = ?1
+             GROUP BY modpack_version ORDER BY MAX(id) DESC",
+        )?;
+        for entry in &mut stats {
+            entry.modpack_versions = versions_stmt
+                .query_map([&entry.signature], |row| row.get(0))?
+                .collect::<rusqlite::Result<Vec<String>>>()?;
+        }
+
+        Ok::<_, rusqlite::Error>(stats)
+    }).await?;
+
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn report(version: &str, signature: &str) -> NewCrashReport {
+        NewCrashReport {
+            modpack_version: version.to_string(),
+            minecraft_version: Some("1.20.1".to_string()),
+            os: "windows".to_string(),
+            java_version: Some("21.0.9".to_string()),
+            launcher_version: Some("1.4.0".to_string()),
+            exit_code: Some(-1),
+            signature: signature.to_string(),
+            summary: format!("java.lang.RuntimeException: {}", signature),
+            report: "---- Minecraft Crash Report ----".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_insert_list_and_aggregate() {
+        let temp_dir = tempdir().unwrap();
+        let conn = Connection::open(temp_dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        insert(&conn, report("1.0.0", "aaa")).await.unwrap();
+        insert(&conn, report("1.1.0", "aaa")).await.unwrap();
+        insert(&conn, report("1.1.0", "aaa")).await.unwrap();
+        let id = insert(&conn, report("1.1.0", "bbb")).await.unwrap();
+
+        let (all, total) = list(&conn, CrashFilter::default(), 10, 0).await.unwrap();
+        assert_eq!(total, 4);
+        assert_eq!(all[0].id, id); // newest first
+
+        let filter = CrashFilter { modpack_version: Some("1.1.0".to_string()), ..Default::default() };
+        let (_, total) = list(&conn, filter, 10, 0).await.unwrap();
+        assert_eq!(total, 3);
+
+        let full = get(&conn, id).await.unwrap().unwrap();
+        assert_eq!(full.info.signature, "bbb");
+        assert!(full.report.contains("Crash Report"));
+        assert!(get(&conn, 9999).await.unwrap().is_none());
+
+        let top = top_signatures(&conn, None, 10).await.unwrap();
+        assert_eq!(top[0].signature, "aaa");
+        assert_eq!(top[0].count, 3);
+        assert_eq!(top[0].modpack_versions, vec!["1.1.0", "1.0.0"]);
+
+        let top = top_signatures(&conn, Some("1.0.0".to_string()), 10).await.unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].count, 1);
+    }
+}
@@ -0,0 +1,181 @@
+use tokio_rusqlite::Connection;
+use anyhow::Result;
+use serde::Serialize;
+
+/// Persisted record of a queued admin job
+#[derive(Debug, Clone, Serialize)]
+pub struct JobRecord {
+    pub id: String,
+    /// Operation name, e.g. `publish_draft`
+    pub kind: String,
+    /// `queued`, `running`, `completed` or `failed`
+    pub status: String,
+    /// Admin who enqueued the job
+    pub created_by: String,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub created_at: i64,
+    pub started_at: Option<i64>,
+    pub finished_at: Option<i64>,
+}
+
+pub async fn init_schema(conn: &Connection) -> Result<()> {
+    conn.call(|conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS jobs (
+                id TEXT PRIMARY KEY,
+                kind TEXT NOT NULL,
+                status TEXT NOT NULL,
+                created_by TEXT NOT NULL,
+                result TEXT,
+                error TEXT,
+                created_at INTEGER NOT NULL,
+                started_at INTEGER,
+                finished_at INTEGER
+            );
+            CREATE INDEX IF NOT EXISTS idx_jobs_status ON jobs(status);"
+        )
+    }).await?;
+    Ok(())
+}
+
+pub async fn insert_job(conn: &Connection, id: &str, kind: &str, created_by: &str) -> Result<()> {
+    let id = id.to_string();
+    let kind = kind.to_string();
+    let created_by = created_by.to_string();
+    let now = chrono::Utc::now().timestamp();
+
+    conn.call(move |conn| {
+        conn.execute(
+            "INSERT INTO jobs (id, kind, status, created_by, created_at) VALUES (?1, ?2, 'queued', ?3, ?4)",
+            rusqlite::params![id, kind, created_by, now],
+        )
+    }).await?;
+    Ok(())
+}
+
+pub async fn mark_running(conn: &Connection, id: &str) -> Result<()> {
+    let id = id.to_string();
+    let now = chrono::Utc::now().timestamp();
+
+    conn.call(move |conn| {
+        conn.execute(
+            "UPDATE jobs SET status = 'running', started_at = ?2 WHERE id = ?1",
+            rusqlite::params![id, now],
+        )
+    }).await?;
+    Ok(())
+}
+
+/// Record the final outcome of a job
+pub async fn mark_finished(
+    conn: &Connection,
+    id: &str,
+    result: std::result::Result<serde_json::Value, String>,
+) -> Result<()> {
+    let id = id.to_string();
+    let now = chrono::Utc::now().timestamp();
+    let (status, result, error) = match result {
+        Ok(value) => ("completed", Some(value.to_string()), None),
+        Err(e) => ("failed", None, Some(e)),
+    };
+
+    conn.call(move |conn| {
+        conn.execute(
+            "UPDATE jobs SET status = ?2, result = ?3, error = ?4, finished_at = ?5 WHERE id = ?1",
+            rusqlite::params![id, status, result, error, now],
+        )
+    }).await?;
+    Ok(())
+}
+
+/// Fail jobs left queued or running by a previous process; returns how many were updated
+pub async fn fail_interrupted(conn: &Connection) -> Result<usize> {
+    let now = chrono::Utc::now().timestamp();
+
+    let count = conn.call(move |conn| {
+        conn.execute(
+            "UPDATE jobs SET status = 'failed', error = 'Interrupted by server restart', finished_at = ?1
+             WHERE status IN ('queued', 'running')",
+            rusqlite::params![now],
+        )
+    }).await?;
+    Ok(count)
+}
+
+pub async fn get_job(conn: &Connection, id: &str) -> Result<Option<JobRecord>> {
+    let id = id.to_string();
+
+    let job = conn.call(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, kind, status, created_by, result, error, created_at, started_at, finished_at
+             FROM jobs WHERE id = ?1",
+        )?;
+        let mut rows = stmt.query(rusqlite::params![id])?;
+
+        match rows.next()? {
+            Some(row) => {
+                let result: Option<String> = row.get(4)?;
+                Ok::<_, rusqlite::Error>(Some(JobRecord {
+                    id: row.get(0)?,
+                    kind: row.get(1)?,
+                    status: row.get(2)?,
+                    created_by: row.get(3)?,
+                    result: result.and_then(|r| serde_json::from_str(&r).ok()),
+                    error: row.get(5)?,
+                    created_at: row.get(6)?,
+                    started_at: row.get(7)?,
+                    finished_at: row.get(8)?,
+                }))
+            }
+            None => Ok(None),
+        }
+    }).await?;
+
+    Ok(job)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_job_lifecycle() {
+        let temp_dir = tempdir().unwrap();
+        let conn = Connection::open(temp_dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        insert_job(&conn, "job-1", "publish_draft", "alice").await.unwrap();
+        assert_eq!(get_job(&conn, "job-1").await.unwrap().unwrap().status, "queued");
+
+        mark_running(&conn, "job-1").await.unwrap();
+        mark_finished(&conn, "job-1", Ok(serde_json::json!({ "version": "1.0.0" }))).await.unwrap();
+
+        let job = get_job(&conn, "job-1").await.unwrap().unwrap();
+        assert_eq!(job.status, "completed");
+        assert_eq!(job.result, Some(serde_json::json!({ "version": "1.0.0" })));
+        assert!(job.finished_at.is_some());
+
+        assert!(get_job(&conn, "missing").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fail_interrupted() {
+        let temp_dir = tempdir().unwrap();
+        let conn = Connection::open(temp_dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        insert_job(&conn, "queued", "create_release", "alice").await.unwrap();
+        insert_job(&conn, "running", "create_release", "alice").await.unwrap();
+        mark_running(&conn, "running").await.unwrap();
+        insert_job(&conn, "done", "create_release", "alice").await.unwrap();
+        mark_finished(&conn, "done", Err("boom".to_string())).await.unwrap();
+
+        assert_eq!(fail_interrupted(&conn).await.unwrap(), 2);
+        let job = get_job(&conn, "running").await.unwrap().unwrap();
+        assert_eq!(job.status, "failed");
+        assert_eq!(job.error.as_deref(), Some("Interrupted by server restart"));
+        assert_eq!(get_job(&conn, "done").await.unwrap().unwrap().error.as_deref(), Some("boom"));
+    }
+}
@@ -0,0 +1,190 @@
+use super::Database;
+use anyhow::{bail, Result};
+use tokio_rusqlite::Connection;
+
+/// A schema change applied once, in version order
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// Version of the schema created by the modules' `init_schema` functions
+///
+/// Those functions are frozen at this version: change an existing table by
+/// adding a migration below rather than editing its `CREATE TABLE`.
+pub const BASELINE_VERSION: u32 = 1;
+
+/// Migrations on top of the baseline, oldest first
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 2,
+        name: "player_stats_indexes",
+        sql: include_str!("migrations/0002_player_stats_indexes.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "vpn_peers_active_ip",
+        sql: include_str!("migrations/0003_vpn_peers_active_ip.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "support_reports",
+        sql: include_str!("migrations/0004_support_reports.sql"),
+    },
+];
+
+/// Latest schema version this build knows about
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map_or(BASELINE_VERSION, |m| m.version)
+}
+
+/// Schema version recorded in the database (0 if nothing was ever applied)
+pub async fn current_version(conn: &Connection) -> Result<u32> {
+    let version = conn.call(|conn| {
+        ensure_version_table(conn)?;
+        conn.query_row("SELECT COALESCE(MAX(version), 0) FROM schema_version", [], |row| row.get(0))
+    }).await?;
+    Ok(version)
+}
+
+fn ensure_version_table(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at INTEGER NOT NULL
+        );"
+    )
+}
+
+fn record_version(conn: &rusqlite::Connection, version: u32, name: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO schema_version (version, name, applied_at) VALUES (?1, ?2, ?3)",
+        rusqlite::params![version, name, chrono::Utc::now().timestamp()],
+    )?;
+    Ok(())
+}
+
+/// Bring the database up to the latest schema version
+pub async fn apply(db: &Database) -> Result<u32> {
+    migrate_to(db, latest_version()).await
+}
+
+/// Apply the baseline and migrations up to and including `target`
+///
+/// Databases created before migrations existed have no `schema_version` table;
+/// the baseline only uses `CREATE ... IF NOT EXISTS`, so it applies cleanly
+/// over them. Each migration runs in its own transaction together with its
+/// version row, so a failed one leaves the database at the previous version.
+pub(super) async fn migrate_to(db: &Database, target: u32) -> Result<u32> {
+    let mut version = current_version(&db.conn).await?;
+    if version > latest_version() {
+        bail!(
+            "Database schema is at version {} but this server only knows up to version {}; refusing to start",
+            version,
+            latest_version()
+        );
+    }
+
+    if version < BASELINE_VERSION && target >= BASELINE_VERSION {
+        db.init_baseline_schema().await?;
+        db.conn.call(|conn| record_version(conn, BASELINE_VERSION, "baseline")).await?;
+        tracing::info!("Applied database baseline schema (version {})", BASELINE_VERSION);
+        version = BASELINE_VERSION;
+    }
+
+    let from = version;
+    for migration in MIGRATIONS.iter().filter(|m| m.version > from && m.version <= target) {
+        let (number, name, sql) = (migration.version, migration.name, migration.sql);
+        db.conn.call(move |conn| {
+            let tx = conn.transaction()?;
+            tx.execute_batch(sql)?;
+            record_version(&tx, number, name)?;
+            tx.commit()
+        }).await.map_err(|e| anyhow::anyhow!("Migration {} ({}) failed: {}", number, name, e))?;
+        tracing::info!("Applied database migration {} ({})", number, name);
+        version = number;
+    }
+
+    Ok(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    /// Every table and index definition, for comparing schemas
+    async fn schema(db: &Database) -> Vec<(String, String)> {
+        db.conn.call(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT name, COALESCE(sql, '') FROM sqlite_master
+                 WHERE name NOT LIKE 'sqlite_%'
+                 ORDER BY name",
+            )?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok::<_, rusqlite::Error>(rows)
+        }).await.unwrap()
+    }
+
+    #[test]
+    fn test_migration_versions_are_sequential() {
+        for (expected, migration) in (BASELINE_VERSION + 1..).zip(MIGRATIONS) {
+            assert_eq!(migration.version, expected, "migration {} is out of order", migration.name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrations_apply_cleanly_from_each_prior_version() {
+        let dir = tempdir().unwrap();
+        let fresh = Database::new(dir.path().join("fresh.db")).await.unwrap();
+        assert_eq!(apply(&fresh).await.unwrap(), latest_version());
+        let expected = schema(&fresh).await;
+
+        for start in 0..latest_version() {
+            let db = Database::new(dir.path().join(format!("from-{}.db", start))).await.unwrap();
+            assert_eq!(migrate_to(&db, start).await.unwrap(), start);
+            assert_eq!(current_version(&db.conn).await.unwrap(), start);
+
+            assert_eq!(apply(&db).await.unwrap(), latest_version());
+            assert_eq!(schema(&db).await, expected, "schema differs when migrating from version {}", start);
+        }
+
+        // Running again is a no-op
+        assert_eq!(apply(&fresh).await.unwrap(), latest_version());
+        assert_eq!(schema(&fresh).await, expected);
+    }
+
+    #[tokio::test]
+    async fn test_database_from_before_migrations_is_adopted() {
+        let dir = tempdir().unwrap();
+        let db = Database::new(dir.path().join("legacy.db")).await.unwrap();
+        db.init_baseline_schema().await.unwrap();
+        db.conn.call(|conn| {
+            conn.execute(
+                "INSERT INTO player_stats (uuid, stats_json, hash, last_updated) VALUES ('a', '{}', 'h', 1)",
+                [],
+            )
+        }).await.unwrap();
+
+        assert_eq!(apply(&db).await.unwrap(), latest_version());
+        let kept: i64 = db.conn.call(|conn| {
+            conn.query_row("SELECT COUNT(*) FROM player_stats", [], |row| row.get(0))
+        }).await.unwrap();
+        assert_eq!(kept, 1);
+    }
+
+    #[tokio::test]
+    async fn test_newer_database_is_rejected() {
+        let dir = tempdir().unwrap();
+        let db = Database::new(dir.path().join("newer.db")).await.unwrap();
+        apply(&db).await.unwrap();
+        let future = latest_version() + 1;
+        db.conn.call(move |conn| record_version(conn, future, "from_the_future")).await.unwrap();
+
+        assert!(apply(&db).await.is_err());
+    }
+}
@@ -0,0 +1,6 @@
+-- player_stats is only ever looked up by uuid; the hash index just slowed down writes
+DROP INDEX IF EXISTS idx_hash;
+
+-- Give the remaining index a name that can't collide with other tables'
+DROP INDEX IF EXISTS idx_last_updated;
+CREATE INDEX IF NOT EXISTS idx_player_stats_last_updated ON player_stats(last_updated);
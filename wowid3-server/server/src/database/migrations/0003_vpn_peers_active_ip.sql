@@ -0,0 +1,2 @@
+-- IP allocation and peer listings only look at peers that haven't been revoked
+CREATE INDEX IF NOT EXISTS idx_vpn_active_ip ON vpn_peers(revoked, ip_address);
@@ -0,0 +1,11 @@
+-- System reports the launcher uploads when a player asks for support
+CREATE TABLE IF NOT EXISTS support_reports (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp INTEGER NOT NULL,
+    os TEXT NOT NULL,
+    launcher_version TEXT,
+    modpack_version TEXT,
+    note TEXT,
+    report TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_support_timestamp ON support_reports(timestamp);
@@ -2,7 +2,22 @@ use tokio_rusqlite::Connection;
 use anyhow::Result;
 use std::path::Path;
 
+pub mod admin_users;
+pub mod advancements;
+pub mod audit;
+pub mod auth;
+pub mod crashes;
+pub mod jobs;
+pub mod migrations;
+pub mod resourcepacks;
+pub mod screenshots;
+pub mod sessions;
 pub mod stats;
+pub mod store;
+pub mod support;
+pub mod telemetry;
+pub mod usage;
+pub mod whitelist;
 
 #[derive(Clone)]
 pub struct Database {
@@ -25,8 +40,34 @@ impl Database {
         Ok(Self { conn })
     }
 
+    /// Create or upgrade the schema to the latest migration
     pub async fn init_schema(&self) -> Result<()> {
+        let version = migrations::apply(self).await?;
+        tracing::info!("Database schema at version {}", version);
+        Ok(())
+    }
+
+    /// Check the connection still answers queries
+    pub async fn ping(&self) -> Result<()> {
+        self.conn.call(|conn| conn.query_row("SELECT 1", [], |row| row.get::<_, i64>(0))).await?;
+        Ok(())
+    }
+
+    /// Schema as of `migrations::BASELINE_VERSION`; later changes are migrations
+    async fn init_baseline_schema(&self) -> Result<()> {
         stats::init_schema(&self.conn).await?;
+        auth::init_schema(&self.conn).await?;
+        admin_users::init_schema(&self.conn).await?;
+        advancements::init_schema(&self.conn).await?;
+        audit::init_schema(&self.conn).await?;
+        crashes::init_schema(&self.conn).await?;
+        jobs::init_schema(&self.conn).await?;
+        resourcepacks::init_schema(&self.conn).await?;
+        screenshots::init_schema(&self.conn).await?;
+        sessions::init_schema(&self.conn).await?;
+        usage::init_schema(&self.conn).await?;
+        telemetry::init_schema(&self.conn).await?;
+        whitelist::init_schema(&self.conn).await?;
         self.init_vpn_schema().await?;
         Ok(())
     }
@@ -0,0 +1,178 @@
+use tokio_rusqlite::Connection;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// One uploaded version of the server resource pack
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourcePack {
+    /// Semantic version, unique
+    pub version: String,
+    pub minecraft_version: String,
+    pub filename: String,
+    pub size: u64,
+    /// Minecraft verifies server resource packs by SHA-1
+    pub sha1: String,
+    pub sha256: String,
+    pub uploaded_by: String,
+    pub uploaded_at: i64,
+}
+
+pub async fn init_schema(conn: &Connection) -> Result<()> {
+    conn.call(|conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS resource_packs (
+                version TEXT PRIMARY KEY,
+                minecraft_version TEXT NOT NULL,
+                filename TEXT NOT NULL,
+                size INTEGER NOT NULL,
+                sha1 TEXT NOT NULL,
+                sha256 TEXT NOT NULL,
+                uploaded_by TEXT NOT NULL,
+                uploaded_at INTEGER NOT NULL
+            );"
+        )
+    }).await?;
+    Ok(())
+}
+
+/// Returns false if the version already exists
+pub async fn insert(conn: &Connection, pack: ResourcePack) -> Result<bool> {
+    let inserted = conn.call(move |conn| {
+        conn.execute(
+            "INSERT OR IGNORE INTO resource_packs
+             (version, minecraft_version, filename, size, sha1, sha256, uploaded_by, uploaded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                pack.version,
+                pack.minecraft_version,
+                pack.filename,
+                pack.size as i64,
+                pack.sha1,
+                pack.sha256,
+                pack.uploaded_by,
+                pack.uploaded_at
+            ],
+        )
+    }).await?;
+    Ok(inserted > 0)
+}
+
+/// Returns the removed pack, if it existed
+pub async fn remove(conn: &Connection, version: String) -> Result<Option<ResourcePack>> {
+    let removed = conn.call(move |conn| {
+        let pack = conn
+            .query_row(
+                &format!("SELECT {} FROM resource_packs WHERE version = ?1", COLUMNS),
+                [&version],
+                pack_from_row,
+            )
+            .map(Some)
+            .or_else(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                e => Err(e),
+            })?;
+        conn.execute("DELETE FROM resource_packs WHERE version = ?1", [&version])?;
+        Ok::<_, rusqlite::Error>(pack)
+    }).await?;
+    Ok(removed)
+}
+
+/// All packs, newest version first
+pub async fn list(conn: &Connection) -> Result<Vec<ResourcePack>> {
+    let mut packs = conn.call(|conn| {
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM resource_packs", COLUMNS))?;
+        let packs = stmt
+            .query_map([], pack_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok::<_, rusqlite::Error>(packs)
+    }).await?;
+
+    // Versions are validated as semver on upload
+    packs.sort_by(|a, b| match (semver::Version::parse(&a.version), semver::Version::parse(&b.version)) {
+        (Ok(a), Ok(b)) => b.cmp(&a),
+        _ => b.version.cmp(&a.version),
+    });
+    Ok(packs)
+}
+
+/// Newest pack, optionally only among those for one Minecraft version
+pub async fn latest(conn: &Connection, minecraft_version: Option<&str>) -> Result<Option<ResourcePack>> {
+    Ok(list(conn)
+        .await?
+        .into_iter()
+        .find(|pack| minecraft_version.is_none_or(|mc| pack.minecraft_version == mc)))
+}
+
+pub async fn get(conn: &Connection, version: String) -> Result<Option<ResourcePack>> {
+    let pack = conn.call(move |conn| {
+        conn.query_row(
+            &format!("SELECT {} FROM resource_packs WHERE version = ?1", COLUMNS),
+            [&version],
+            pack_from_row,
+        )
+        .map(Some)
+        .or_else(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => Ok(None),
+            e => Err(e),
+        })
+    }).await?;
+    Ok(pack)
+}
+
+const COLUMNS: &str = "version, minecraft_version, filename, size, sha1, sha256, uploaded_by, uploaded_at";
+
+fn pack_from_row(row: &rusqlite::Row) -> rusqlite::Result<ResourcePack> {
+    Ok(ResourcePack {
+        version: row.get(0)?,
+        minecraft_version: row.get(1)?,
+        filename: row.get(2)?,
+        size: row.get::<_, i64>(3)? as u64,
+        sha1: row.get(4)?,
+        sha256: row.get(5)?,
+        uploaded_by: row.get(6)?,
+        uploaded_at: row.get(7)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn pack(version: &str, minecraft_version: &str) -> ResourcePack {
+        ResourcePack {
+            version: version.to_string(),
+            minecraft_version: minecraft_version.to_string(),
+            filename: "pack.zip".to_string(),
+            size: 10,
+            sha1: "a".repeat(40),
+            sha256: "b".repeat(64),
+            uploaded_by: "admin".to_string(),
+            uploaded_at: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resource_pack_versions() {
+        let dir = tempdir().unwrap();
+        let conn = Connection::open(dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        assert!(insert(&conn, pack("1.2.0", "1.20.1")).await.unwrap());
+        assert!(insert(&conn, pack("1.10.0", "1.20.1")).await.unwrap());
+        assert!(insert(&conn, pack("2.0.0", "1.21")).await.unwrap());
+        assert!(!insert(&conn, pack("1.2.0", "1.20.1")).await.unwrap());
+
+        // Semver order, not string order
+        let versions: Vec<_> = list(&conn).await.unwrap().into_iter().map(|p| p.version).collect();
+        assert_eq!(versions, vec!["2.0.0", "1.10.0", "1.2.0"]);
+
+        assert_eq!(latest(&conn, None).await.unwrap().unwrap().version, "2.0.0");
+        assert_eq!(latest(&conn, Some("1.20.1")).await.unwrap().unwrap().version, "1.10.0");
+        assert!(latest(&conn, Some("1.19")).await.unwrap().is_none());
+
+        assert_eq!(remove(&conn, "2.0.0".to_string()).await.unwrap().unwrap().minecraft_version, "1.21");
+        assert!(remove(&conn, "2.0.0".to_string()).await.unwrap().is_none());
+        assert!(get(&conn, "2.0.0".to_string()).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,143 @@
+use tokio_rusqlite::Connection;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// A screenshot uploaded from the launcher
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenshotRecord {
+    pub id: i64,
+    pub player_uuid: String,
+    /// Stored file name (`<sha256 prefix>.<ext>`)
+    pub filename: String,
+    /// File name on the player's machine, for display
+    pub original_name: Option<String>,
+    pub size: i64,
+    pub sha256: String,
+    pub uploaded_at: i64,
+}
+
+/// Screenshot to be inserted (id and upload time are assigned on insert)
+#[derive(Debug, Clone)]
+pub struct NewScreenshot {
+    pub player_uuid: String,
+    pub filename: String,
+    pub original_name: Option<String>,
+    pub size: i64,
+    pub sha256: String,
+}
+
+pub async fn init_schema(conn: &Connection) -> Result<()> {
+    conn.call(|conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS screenshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                player_uuid TEXT NOT NULL,
+                filename TEXT NOT NULL,
+                original_name TEXT,
+                size INTEGER NOT NULL,
+                sha256 TEXT NOT NULL,
+                uploaded_at INTEGER NOT NULL,
+                UNIQUE(player_uuid, sha256)
+            );
+            CREATE INDEX IF NOT EXISTS idx_screenshots_player ON screenshots(player_uuid, uploaded_at);"
+        )
+    }).await?;
+    Ok(())
+}
+
+/// Record an upload; uploading the same image twice returns the existing record
+pub async fn insert(conn: &Connection, screenshot: NewScreenshot) -> Result<ScreenshotRecord> {
+    let now = chrono::Utc::now().timestamp();
+
+    let record = conn.call(move |conn| {
+        conn.execute(
+            "INSERT OR IGNORE INTO screenshots (player_uuid, filename, original_name, size, sha256, uploaded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                screenshot.player_uuid,
+                screenshot.filename,
+                screenshot.original_name,
+                screenshot.size,
+                screenshot.sha256,
+                now,
+            ],
+        )?;
+
+        conn.query_row(
+            &format!("SELECT {} FROM screenshots WHERE player_uuid = ?1 AND sha256 = ?2", COLUMNS),
+            [&screenshot.player_uuid, &screenshot.sha256],
+            record_from_row,
+        )
+    }).await?;
+
+    Ok(record)
+}
+
+const COLUMNS: &str = "id, player_uuid, filename, original_name, size, sha256, uploaded_at";
+
+fn record_from_row(row: &rusqlite::Row) -> rusqlite::Result<ScreenshotRecord> {
+    Ok(ScreenshotRecord {
+        id: row.get(0)?,
+        player_uuid: row.get(1)?,
+        filename: row.get(2)?,
+        original_name: row.get(3)?,
+        size: row.get(4)?,
+        sha256: row.get(5)?,
+        uploaded_at: row.get(6)?,
+    })
+}
+
+/// A player's screenshots, newest first
+pub async fn list_for_player(conn: &Connection, player_uuid: &str) -> Result<Vec<ScreenshotRecord>> {
+    let player_uuid = player_uuid.to_string();
+
+    let records = conn.call(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM screenshots WHERE player_uuid = ?1 ORDER BY uploaded_at DESC, id DESC",
+            COLUMNS
+        ))?;
+
+        let records = stmt
+            .query_map([&player_uuid], record_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok::<_, rusqlite::Error>(records)
+    }).await?;
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn screenshot(uuid: &str, sha256: &str) -> NewScreenshot {
+        NewScreenshot {
+            player_uuid: uuid.to_string(),
+            filename: format!("{}.png", &sha256[..4]),
+            original_name: Some("2026-01-10_20.14.55.png".to_string()),
+            size: 1024,
+            sha256: sha256.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_insert_dedupes_and_lists_per_player() {
+        let temp_dir = tempdir().unwrap();
+        let conn = Connection::open(temp_dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        let first = insert(&conn, screenshot("player-a", "aaaa1111")).await.unwrap();
+        let again = insert(&conn, screenshot("player-a", "aaaa1111")).await.unwrap();
+        assert_eq!(first.id, again.id);
+
+        insert(&conn, screenshot("player-a", "bbbb2222")).await.unwrap();
+        insert(&conn, screenshot("player-b", "aaaa1111")).await.unwrap();
+
+        let records = list_for_player(&conn, "player-a").await.unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|r| r.player_uuid == "player-a"));
+        assert!(list_for_player(&conn, "player-c").await.unwrap().is_empty());
+    }
+}
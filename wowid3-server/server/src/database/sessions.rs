@@ -0,0 +1,217 @@
+use tokio_rusqlite::Connection;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// One stretch of a player being online, as seen by the tracker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSession {
+    pub id: i64,
+    pub uuid: String,
+    pub name: String,
+    pub joined_at: i64,
+    /// `None` while the player is still online
+    pub left_at: Option<i64>,
+    /// Seconds online; counts up to now for an open session
+    pub duration_secs: i64,
+}
+
+/// A player's summed playtime over some window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlaytimeEntry {
+    pub uuid: String,
+    /// Name from the player's most recent session
+    pub name: String,
+    pub playtime_secs: i64,
+    pub sessions: i64,
+}
+
+/// A player who appeared in a tracker update
+#[derive(Debug, Clone)]
+pub struct JoinedPlayer {
+    pub uuid: String,
+    pub name: String,
+}
+
+pub async fn init_schema(conn: &Connection) -> Result<()> {
+    conn.call(|conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS player_sessions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                uuid TEXT NOT NULL,
+                name TEXT NOT NULL,
+                joined_at INTEGER NOT NULL,
+                left_at INTEGER,
+                last_seen INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_sessions_uuid ON player_sessions(uuid, joined_at);
+            CREATE INDEX IF NOT EXISTS idx_sessions_open ON player_sessions(left_at) WHERE left_at IS NULL;"
+        )
+    }).await?;
+    Ok(())
+}
+
+/// Open sessions for players who joined, close them for players who left
+/// and mark the rest as seen
+///
+/// A player who already has an open session isn't given a second one, so a
+/// repeated join is harmless.
+pub async fn record_changes(
+    conn: &Connection,
+    joined: Vec<JoinedPlayer>,
+    left: Vec<String>,
+    now: i64,
+) -> Result<()> {
+    conn.call(move |conn| {
+        let tx = conn.transaction()?;
+        for uuid in &left {
+            tx.execute(
+                "UPDATE player_sessions SET left_at = ?1 WHERE uuid = ?2 AND left_at IS NULL",
+                rusqlite::params![now, uuid],
+            )?;
+        }
+        for player in &joined {
+            tx.execute(
+                "INSERT INTO player_sessions (uuid, name, joined_at, last_seen)
+                 SELECT ?1, ?2, ?3, ?3
+                 WHERE NOT EXISTS (SELECT 1 FROM player_sessions WHERE uuid = ?1 AND left_at IS NULL)",
+                rusqlite::params![player.uuid, player.name, now],
+            )?;
+        }
+        tx.execute("UPDATE player_sessions SET last_seen = ?1 WHERE left_at IS NULL", [now])?;
+        tx.commit()
+    }).await?;
+
+    Ok(())
+}
+
+/// Close every open session at the last tracker update that saw it
+///
+/// Used at startup: the in-memory tracker state the sessions were diffed
+/// against is gone, and downtime shouldn't count as playtime.
+pub async fn close_open_sessions(conn: &Connection) -> Result<usize> {
+    let closed = conn.call(|conn| {
+        conn.execute("UPDATE player_sessions SET left_at = last_seen WHERE left_at IS NULL", [])
+    }).await?;
+    Ok(closed)
+}
+
+/// A player's most recent sessions, newest first
+pub async fn list_for_player(conn: &Connection, uuid: String, limit: u32, now: i64) -> Result<Vec<PlayerSession>> {
+    let sessions = conn.call(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, uuid, name, joined_at, left_at, COALESCE(left_at, ?2) - joined_at
+             FROM player_sessions WHERE uuid = ?1
+             ORDER BY joined_at DESC, id DESC LIMIT ?3",
+        )?;
+        let sessions = stmt
+            .query_map(rusqlite::params![uuid, now, limit], |row| {
+                Ok(PlayerSession {
+                    id: row.get(0)?,
+                    uuid: row.get(1)?,
+                    name: row.get(2)?,
+                    joined_at: row.get(3)?,
+                    left_at: row.get(4)?,
+                    duration_secs: row.get::<_, i64>(5)?.max(0),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok::<_, rusqlite::Error>(sessions)
+    }).await?;
+    Ok(sessions)
+}
+
+/// Seconds a player was online since `since`; sessions that started earlier
+/// only count from `since`
+pub async fn playtime_since(conn: &Connection, uuid: String, since: i64, now: i64) -> Result<i64> {
+    let secs = conn.call(move |conn| {
+        conn.query_row(
+            "SELECT COALESCE(SUM(MAX(0, COALESCE(left_at, ?3) - MAX(joined_at, ?2))), 0)
+             FROM player_sessions WHERE uuid = ?1 AND COALESCE(left_at, ?3) > ?2",
+            rusqlite::params![uuid, since, now],
+            |row| row.get(0),
+        )
+    }).await?;
+    Ok(secs)
+}
+
+/// Players with the most playtime since `since`, most first
+pub async fn playtime_leaderboard(conn: &Connection, since: i64, limit: u32, now: i64) -> Result<Vec<PlaytimeEntry>> {
+    let entries = conn.call(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT s.uuid,
+                    (SELECT name FROM player_sessions WHERE uuid = s.uuid ORDER BY joined_at DESC, id DESC LIMIT 1),
+                    SUM(MAX(0, COALESCE(s.left_at, ?2) - MAX(s.joined_at, ?1))) AS playtime,
+                    COUNT(*)
+             FROM player_sessions s
+             WHERE COALESCE(s.left_at, ?2) > ?1
+             GROUP BY s.uuid
+             ORDER BY playtime DESC, s.uuid
+             LIMIT ?3",
+        )?;
+        let entries = stmt
+            .query_map(rusqlite::params![since, now, limit], |row| {
+                Ok(PlaytimeEntry {
+                    uuid: row.get(0)?,
+                    name: row.get(1)?,
+                    playtime_secs: row.get(2)?,
+                    sessions: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok::<_, rusqlite::Error>(entries)
+    }).await?;
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn player(uuid: &str, name: &str) -> JoinedPlayer {
+        JoinedPlayer {
+            uuid: uuid.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sessions_and_playtime() {
+        let dir = tempdir().unwrap();
+        let conn = Connection::open(dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        record_changes(&conn, vec![player("a", "alice"), player("b", "bob")], vec![], 1000).await.unwrap();
+        // A repeated join doesn't open a second session
+        record_changes(&conn, vec![player("a", "alice")], vec![], 1500).await.unwrap();
+        record_changes(&conn, vec![], vec!["a".to_string()], 4600).await.unwrap();
+        record_changes(&conn, vec![player("a", "alice2")], vec![], 5000).await.unwrap();
+
+        let sessions = list_for_player(&conn, "a".to_string(), 10, 6000).await.unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].name, "alice2");
+        assert_eq!(sessions[0].left_at, None);
+        assert_eq!(sessions[0].duration_secs, 1000);
+        assert_eq!(sessions[1].duration_secs, 3600);
+
+        assert_eq!(playtime_since(&conn, "a".to_string(), 0, 6000).await.unwrap(), 4600);
+        // Only the part of the first session after the window start counts
+        assert_eq!(playtime_since(&conn, "a".to_string(), 4000, 6000).await.unwrap(), 1600);
+        assert_eq!(playtime_since(&conn, "nobody".to_string(), 0, 6000).await.unwrap(), 0);
+
+        let leaderboard = playtime_leaderboard(&conn, 0, 10, 6000).await.unwrap();
+        assert_eq!(
+            leaderboard,
+            vec![
+                PlaytimeEntry { uuid: "b".to_string(), name: "bob".to_string(), playtime_secs: 5000, sessions: 1 },
+                PlaytimeEntry { uuid: "a".to_string(), name: "alice2".to_string(), playtime_secs: 4600, sessions: 2 },
+            ]
+        );
+
+        // Open sessions close at the last update that saw them
+        record_changes(&conn, vec![], vec![], 6500).await.unwrap();
+        assert_eq!(close_open_sessions(&conn).await.unwrap(), 2);
+        let sessions = list_for_player(&conn, "b".to_string(), 10, 9000).await.unwrap();
+        assert_eq!(sessions[0].left_at, Some(6500));
+    }
+}
@@ -1,6 +1,9 @@
+use crate::models::stats::{LeaderboardEntry, LeaderboardMetric, PlayerStats};
 use tokio_rusqlite::Connection;
 use anyhow::Result;
 
+const DAY_SECS: i64 = 86_400;
+
 pub async fn init_schema(conn: &Connection) -> Result<()> {
     conn.call(|conn| {
         conn.execute_batch(
@@ -11,8 +14,186 @@ pub async fn init_schema(conn: &Connection) -> Result<()> {
                 last_updated INTEGER NOT NULL
             );
             CREATE INDEX IF NOT EXISTS idx_hash ON player_stats(hash);
-            CREATE INDEX IF NOT EXISTS idx_last_updated ON player_stats(last_updated);"
-        )
+            CREATE INDEX IF NOT EXISTS idx_last_updated ON player_stats(last_updated);
+            CREATE TABLE IF NOT EXISTS stat_event_ids (
+                event_id TEXT PRIMARY KEY,
+                uuid TEXT NOT NULL,
+                received_at INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_stat_event_ids_received ON stat_event_ids(received_at);
+            CREATE TABLE IF NOT EXISTS stat_rollups (
+                uuid TEXT NOT NULL,
+                day INTEGER NOT NULL,
+                metric TEXT NOT NULL,
+                value REAL NOT NULL,
+                PRIMARY KEY (uuid, day, metric)
+            );
+            CREATE INDEX IF NOT EXISTS idx_stat_rollups_metric_day ON stat_rollups(metric, day);"
+        )?;
+        seed_rollups(conn)
     }).await?;
     Ok(())
 }
+
+/// Seed empty rollups from the all-time totals of players tracked before them
+///
+/// Totals land on day 0, so they only count toward all-time leaderboards.
+fn seed_rollups(conn: &mut rusqlite::Connection) -> rusqlite::Result<()> {
+    let has_rollups: bool = conn.query_row("SELECT EXISTS (SELECT 1 FROM stat_rollups)", [], |row| row.get(0))?;
+    if has_rollups {
+        return Ok(());
+    }
+
+    let tx = conn.transaction()?;
+    {
+        let mut stmt = tx.prepare("SELECT uuid, stats_json FROM player_stats")?;
+        let players = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        for (uuid, json) in players {
+            let Ok(stats) = serde_json::from_str::<PlayerStats>(&json) else {
+                continue;
+            };
+            for metric in LeaderboardMetric::ALL {
+                add_rollup(&tx, &uuid, 0, metric, metric.total(&stats))?;
+            }
+        }
+    }
+    tx.commit()
+}
+
+/// Unix day number of a timestamp
+pub fn day_of(timestamp: i64) -> i64 {
+    timestamp.div_euclid(DAY_SECS)
+}
+
+/// Add to a player's daily rollup of a leaderboard metric
+pub fn add_rollup(
+    conn: &rusqlite::Connection,
+    uuid: &str,
+    day: i64,
+    metric: LeaderboardMetric,
+    amount: f64,
+) -> rusqlite::Result<()> {
+    if amount == 0.0 {
+        return Ok(());
+    }
+    conn.execute(
+        "INSERT INTO stat_rollups (uuid, day, metric, value) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(uuid, day, metric) DO UPDATE SET value = value + excluded.value",
+        rusqlite::params![uuid, day, metric.as_str(), amount],
+    )?;
+    Ok(())
+}
+
+/// Top players for a metric over rollups from `since_day` on (all time when `None`)
+pub async fn leaderboard(
+    conn: &Connection,
+    metric: LeaderboardMetric,
+    since_day: Option<i64>,
+    limit: u32,
+) -> Result<Vec<LeaderboardEntry>> {
+    let entries = conn.call(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT r.uuid,
+                    (SELECT name FROM player_sessions WHERE uuid = r.uuid ORDER BY joined_at DESC, id DESC LIMIT 1),
+                    SUM(r.value) AS total
+             FROM stat_rollups r
+             WHERE r.metric = ?1 AND r.day >= ?2
+             GROUP BY r.uuid
+             HAVING total > 0
+             ORDER BY total DESC, r.uuid
+             LIMIT ?3",
+        )?;
+        let entries = stmt
+            .query_map(rusqlite::params![metric.as_str(), since_day.unwrap_or(i64::MIN), limit], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?, row.get::<_, f64>(2)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok::<_, rusqlite::Error>(entries)
+    }).await?;
+
+    Ok(entries
+        .into_iter()
+        .enumerate()
+        .map(|(i, (uuid, name, value))| LeaderboardEntry {
+            rank: i as u32 + 1,
+            uuid,
+            name,
+            value,
+        })
+        .collect())
+}
+
+/// Record that an event was applied; false if its id was already seen
+///
+/// Runs inside the transaction that applies the event, so a retried event is
+/// either counted with its id recorded or not at all.
+pub fn record_event_id(conn: &rusqlite::Connection, event_id: &str, uuid: &str, now: i64) -> rusqlite::Result<bool> {
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO stat_event_ids (event_id, uuid, received_at) VALUES (?1, ?2, ?3)",
+        rusqlite::params![event_id, uuid, now],
+    )?;
+    Ok(inserted > 0)
+}
+
+/// Forget event ids received before `cutoff`; retries never arrive that late
+pub async fn prune_event_ids(conn: &Connection, cutoff: i64) -> Result<usize> {
+    let removed = conn.call(move |conn| {
+        conn.execute("DELETE FROM stat_event_ids WHERE received_at < ?1", [cutoff])
+    }).await?;
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_leaderboard_windows() {
+        let dir = tempdir().unwrap();
+        let conn = Connection::open(dir.path().join("test.db")).await.unwrap();
+        crate::database::sessions::init_schema(&conn).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        let today = 20_000;
+        conn.call(move |conn| {
+            add_rollup(conn, "a", today, LeaderboardMetric::Deaths, 2.0)?;
+            add_rollup(conn, "a", today, LeaderboardMetric::Deaths, 1.0)?;
+            add_rollup(conn, "b", today - 10, LeaderboardMetric::Deaths, 5.0)?;
+            add_rollup(conn, "b", 0, LeaderboardMetric::Deaths, 100.0)?;
+            add_rollup(conn, "c", today, LeaderboardMetric::MobKills, 9.0)
+        }).await.unwrap();
+
+        let week = leaderboard(&conn, LeaderboardMetric::Deaths, Some(today - 6), 10).await.unwrap();
+        assert_eq!(week.len(), 1);
+        assert_eq!((week[0].rank, week[0].uuid.as_str(), week[0].value), (1, "a", 3.0));
+
+        let month = leaderboard(&conn, LeaderboardMetric::Deaths, Some(today - 29), 10).await.unwrap();
+        let order: Vec<_> = month.iter().map(|e| e.uuid.as_str()).collect();
+        assert_eq!(order, vec!["b", "a"]);
+
+        let all = leaderboard(&conn, LeaderboardMetric::Deaths, None, 1).await.unwrap();
+        assert_eq!((all[0].uuid.as_str(), all[0].value), ("b", 105.0));
+    }
+
+    #[tokio::test]
+    async fn test_event_ids_are_recorded_once() {
+        let dir = tempdir().unwrap();
+        let conn = Connection::open(dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        let (first, again) = conn.call(|conn| {
+            let first = record_event_id(conn, "event-1", "player", 100)?;
+            let again = record_event_id(conn, "event-1", "player", 200)?;
+            Ok::<_, rusqlite::Error>((first, again))
+        }).await.unwrap();
+        assert!(first);
+        assert!(!again);
+
+        assert_eq!(prune_event_ids(&conn, 150).await.unwrap(), 1);
+        let fresh = conn.call(|conn| record_event_id(conn, "event-1", "player", 300)).await.unwrap();
+        assert!(fresh);
+    }
+}
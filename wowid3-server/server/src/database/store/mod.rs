@@ -0,0 +1,290 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+use super::advancements::{AdvancementCompletion, PlayerAdvancement};
+use super::Database;
+use crate::config::{Config, DatabaseBackendKind};
+use crate::models::stats::{LeaderboardEntry, LeaderboardMetric, PlayerStatEvent, PlayerStats};
+use crate::vpn::monitor::PeerStats;
+
+pub mod postgres;
+pub mod sqlite;
+
+pub use postgres::PostgresStore;
+pub use sqlite::SqliteStore;
+
+/// Player stats, leaderboards and advancements
+///
+/// These are the tables that grow with player activity, so they can live in
+/// Postgres while admin data (users, audit log, jobs, ...) stays in the local
+/// SQLite database.
+#[async_trait]
+pub trait StatsStore: Send + Sync {
+    /// Fold one player's events into their stats in a single transaction
+    ///
+    /// Events whose id was already seen are skipped, so retried batches are only
+    /// counted once.
+    async fn apply_events(&self, uuid: &str, events: Vec<PlayerStatEvent>, now: i64) -> Result<()>;
+
+    /// Forget event ids received before `cutoff`; returns how many were removed
+    async fn prune_event_ids(&self, cutoff: i64) -> Result<usize>;
+
+    /// Stored stats JSON and its hash (used as the ETag)
+    async fn player_stats(&self, uuid: &str) -> Result<Option<(String, String)>>;
+
+    /// Top players for a metric over rollups from `since_day` on (all time when `None`)
+    async fn leaderboard(&self, metric: LeaderboardMetric, since_day: Option<i64>, limit: u32) -> Result<Vec<LeaderboardEntry>>;
+
+    /// A player's completed advancements, oldest first
+    async fn player_advancements(&self, uuid: &str) -> Result<Vec<PlayerAdvancement>>;
+
+    /// Players who completed an advancement, first to complete it first
+    async fn advancement_completions(&self, advancement_id: &str, limit: u32) -> Result<Vec<AdvancementCompletion>>;
+
+    /// Run a trivial query, for readiness checks
+    async fn ping(&self) -> Result<()>;
+}
+
+/// A registered VPN peer
+#[derive(Debug, Clone, PartialEq)]
+pub struct VpnPeerRecord {
+    pub uuid: String,
+    pub username: String,
+    pub public_key: String,
+    pub ip_address: String,
+    pub last_handshake: Option<i64>,
+    pub bytes_sent: i64,
+    pub bytes_received: i64,
+    pub registered_at: i64,
+}
+
+/// Sort order for peer listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerOrder {
+    Username,
+    /// Most recent handshake first, peers that never connected last
+    LastHandshake,
+}
+
+/// WireGuard peers registered through the launcher
+#[async_trait]
+pub trait VpnPeerStore: Send + Sync {
+    /// The player's peer, unless it was revoked
+    async fn active_peer(&self, uuid: &str) -> Result<Option<VpnPeerRecord>>;
+
+    /// Register a peer, reactivating a revoked row for the same player
+    async fn upsert_peer(&self, uuid: &str, username: &str, public_key: &str, ip_address: &str, now: i64) -> Result<()>;
+
+    /// Replace an active peer's key after the launcher generated a new one
+    async fn update_peer_key(&self, uuid: &str, username: &str, public_key: &str, now: i64) -> Result<()>;
+
+    /// Tunnel addresses held by active peers
+    async fn active_ips(&self) -> Result<Vec<String>>;
+
+    async fn list_active(&self, order: PeerOrder) -> Result<Vec<VpnPeerRecord>>;
+
+    /// Public key of a peer, revoked or not
+    async fn public_key(&self, uuid: &str) -> Result<Option<String>>;
+
+    async fn revoke(&self, uuid: &str, now: i64) -> Result<()>;
+
+    /// Store handshake and transfer stats on the matching active peers
+    ///
+    /// A peer without a new handshake keeps its previous one, so restarting the
+    /// interface doesn't make every peer look like it never connected.
+    async fn update_stats(&self, stats: Vec<PeerStats>) -> Result<usize>;
+
+    /// Active peers whose last handshake (or registration, if they never
+    /// connected) is older than `cutoff`, as (uuid, public key)
+    async fn stale_peers(&self, cutoff: i64) -> Result<Vec<(String, String)>>;
+}
+
+/// The stores selected in the config
+#[derive(Clone)]
+pub struct Stores {
+    pub stats: Arc<dyn StatsStore>,
+    pub vpn_peers: Arc<dyn VpnPeerStore>,
+    backend: DatabaseBackendKind,
+}
+
+impl Stores {
+    /// Whether the stores live outside the local SQLite database
+    pub fn is_external(&self) -> bool {
+        self.backend != DatabaseBackendKind::Sqlite
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self.backend {
+            DatabaseBackendKind::Sqlite => "sqlite",
+            DatabaseBackendKind::Postgres => "postgres",
+        }
+    }
+}
+
+/// Build the stores selected in the config
+///
+/// The SQLite stores share `db`; the Postgres ones connect and migrate their
+/// own schema first.
+pub async fn from_config(config: &Config, db: &Database) -> Result<Stores> {
+    match config.database_backend {
+        DatabaseBackendKind::Sqlite => {
+            let store = Arc::new(SqliteStore::new(db.clone()));
+            Ok(Stores {
+                stats: store.clone(),
+                vpn_peers: store,
+                backend: DatabaseBackendKind::Sqlite,
+            })
+        }
+        DatabaseBackendKind::Postgres => {
+            let url = config
+                .database_url
+                .as_deref()
+                .context("DATABASE_URL must be set for the postgres database backend")?;
+            let store = Arc::new(PostgresStore::connect(url, config.database_pool_size).await?);
+            Ok(Stores {
+                stats: store.clone(),
+                vpn_peers: store,
+                backend: DatabaseBackendKind::Postgres,
+            })
+        }
+    }
+}
+
+/// Stats JSON as stored, and the hash served as its ETag
+fn encode_stats(stats: &PlayerStats) -> serde_json::Result<(String, String)> {
+    let json = serde_json::to_string(stats)?;
+    let hash = format!("{:x}", Sha256::digest(json.as_bytes()));
+    Ok((json, hash))
+}
+
+/// Username for a player first seen in this batch
+fn batch_username(events: &[PlayerStatEvent]) -> String {
+    events.first().map(|e| e.username.clone()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::stats::StatEvent;
+    use tempfile::tempdir;
+
+    fn event(event_id: Option<&str>, event: StatEvent) -> PlayerStatEvent {
+        PlayerStatEvent {
+            event_id: event_id.map(str::to_string),
+            uuid: "player".to_string(),
+            username: "Steve".to_string(),
+            timestamp: 0,
+            event,
+        }
+    }
+
+    /// Behaviour every stats store must share
+    async fn check_stats_store(store: &dyn StatsStore) {
+        let now = 1_700_000_000;
+        let batch = vec![
+            event(Some("a"), StatEvent::PlayerDeath),
+            event(Some("b"), StatEvent::PlayerDeath),
+            event(
+                None,
+                StatEvent::AdvancementCompleted {
+                    advancement_id: "minecraft:story/root".to_string(),
+                    title: Some("Minecraft".to_string()),
+                },
+            ),
+        ];
+        store.apply_events("player", batch, now).await.unwrap();
+        // The tracker resends the batch after a network error
+        let retry = vec![
+            event(Some("a"), StatEvent::PlayerDeath),
+            event(
+                None,
+                StatEvent::AdvancementCompleted {
+                    advancement_id: "minecraft:story/root".to_string(),
+                    title: None,
+                },
+            ),
+        ];
+        store.apply_events("player", retry, now + 60).await.unwrap();
+
+        let (json, hash) = store.player_stats("player").await.unwrap().unwrap();
+        let stats: PlayerStats = serde_json::from_str(&json).unwrap();
+        assert_eq!((stats.deaths, stats.advancements_completed), (2, 1));
+        assert_eq!(hash.len(), 64);
+        assert!(store.player_stats("nobody").await.unwrap().is_none());
+
+        let today = super::super::stats::day_of(now);
+        let board = store.leaderboard(LeaderboardMetric::Deaths, Some(today), 10).await.unwrap();
+        assert_eq!((board[0].uuid.as_str(), board[0].value), ("player", 2.0));
+
+        let advancements = store.player_advancements("player").await.unwrap();
+        assert_eq!(advancements.len(), 1);
+        assert_eq!(advancements[0].completed_at, now);
+        let completions = store.advancement_completions("minecraft:story/root", 10).await.unwrap();
+        assert_eq!(completions[0].name, "Steve");
+
+        assert_eq!(store.prune_event_ids(now + 1).await.unwrap(), 2);
+        store.ping().await.unwrap();
+    }
+
+    /// Behaviour every VPN peer store must share
+    async fn check_vpn_peer_store(store: &dyn VpnPeerStore) {
+        store.upsert_peer("a", "alice", "a2V5MQ==", "10.8.0.2", 100).await.unwrap();
+        store.upsert_peer("b", "bob", "a2V5Mg==", "10.8.0.3", 100).await.unwrap();
+        assert_eq!(store.active_ips().await.unwrap(), vec!["10.8.0.2", "10.8.0.3"]);
+
+        store.update_peer_key("a", "alice", "a2V5Mw==", 200).await.unwrap();
+        let alice = store.active_peer("a").await.unwrap().unwrap();
+        assert_eq!((alice.public_key.as_str(), alice.registered_at), ("a2V5Mw==", 200));
+
+        let stats = vec![PeerStats {
+            public_key: "a2V5Mw==".to_string(),
+            last_handshake: Some(1_000),
+            bytes_sent: 10,
+            bytes_received: 20,
+        }];
+        assert_eq!(store.update_stats(stats).await.unwrap(), 1);
+        let listed = store.list_active(PeerOrder::LastHandshake).await.unwrap();
+        let order: Vec<_> = listed.iter().map(|p| p.uuid.as_str()).collect();
+        assert_eq!(order, vec!["a", "b"]);
+        assert_eq!(listed[0].bytes_received, 20);
+
+        let stale = store.stale_peers(500).await.unwrap();
+        assert_eq!(stale, vec![("b".to_string(), "a2V5Mg==".to_string())]);
+
+        store.revoke("b", 300).await.unwrap();
+        assert!(store.active_peer("b").await.unwrap().is_none());
+        assert_eq!(store.public_key("b").await.unwrap().as_deref(), Some("a2V5Mg=="));
+        assert_eq!(store.active_ips().await.unwrap(), vec!["10.8.0.2"]);
+
+        // Registering again reactivates the revoked row
+        store.upsert_peer("b", "bob", "a2V5NA==", "10.8.0.3", 400).await.unwrap();
+        let bob = store.active_peer("b").await.unwrap().unwrap();
+        assert_eq!((bob.public_key.as_str(), bob.last_handshake), ("a2V5NA==", None));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_store() {
+        let dir = tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db")).await.unwrap();
+        db.init_schema().await.unwrap();
+        let store = SqliteStore::new(db);
+
+        check_stats_store(&store).await;
+        check_vpn_peer_store(&store).await;
+    }
+
+    /// Runs against a scratch database when `TEST_POSTGRES_URL` is set
+    #[tokio::test]
+    async fn test_postgres_store() {
+        let Ok(url) = std::env::var("TEST_POSTGRES_URL") else {
+            return;
+        };
+        let store = PostgresStore::connect(&url, 2).await.unwrap();
+        store.truncate_all().await.unwrap();
+
+        check_stats_store(&store).await;
+        check_vpn_peer_store(&store).await;
+    }
+}
@@ -0,0 +1,541 @@
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use deadpool_postgres::{Manager, ManagerConfig, Pool, RecyclingMethod};
+use std::sync::Arc;
+use tokio_postgres_rustls::MakeRustlsConnect;
+
+use super::{batch_username, encode_stats, PeerOrder, StatsStore, VpnPeerRecord, VpnPeerStore};
+use crate::database::advancements::{AdvancementCompletion, PlayerAdvancement};
+use crate::database::migrations::Migration;
+use crate::database::{stats, Database};
+use crate::models::stats::{LeaderboardEntry, LeaderboardMetric, PlayerStatEvent, PlayerStats};
+use crate::vpn::monitor::PeerStats;
+
+/// Postgres schema migrations, oldest first
+///
+/// Versioned separately from the SQLite migrations; the Postgres schema starts
+/// out matching the SQLite tables as of SQLite version 3.
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    name: "init",
+    sql: include_str!("postgres/0001_init.sql"),
+}];
+
+const PEER_COLUMNS: &str =
+    "uuid, username, public_key, ip_address, last_handshake, bytes_sent, bytes_received, registered_at";
+
+/// Stores backed by a (possibly shared) Postgres database, for running replicas
+pub struct PostgresStore {
+    pool: Pool,
+}
+
+/// Rows copied by `PostgresStore::import_from_sqlite`
+#[derive(Debug, Default)]
+pub struct ImportCounts {
+    pub player_stats: u64,
+    pub stat_event_ids: u64,
+    pub stat_rollups: u64,
+    pub player_advancements: u64,
+    pub vpn_peers: u64,
+}
+
+fn tls_connector() -> Result<MakeRustlsConnect> {
+    let roots = rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .context("Failed to configure TLS for Postgres")?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok(MakeRustlsConnect::new(config))
+}
+
+fn peer_record(row: &tokio_postgres::Row) -> VpnPeerRecord {
+    VpnPeerRecord {
+        uuid: row.get(0),
+        username: row.get(1),
+        public_key: row.get(2),
+        ip_address: row.get(3),
+        last_handshake: row.get(4),
+        bytes_sent: row.get(5),
+        bytes_received: row.get(6),
+        registered_at: row.get(7),
+    }
+}
+
+impl PostgresStore {
+    /// Connect to `url` (a libpq-style URL; `sslmode=require` is honoured) and migrate the schema
+    pub async fn connect(url: &str, pool_size: usize) -> Result<Self> {
+        let pg_config: tokio_postgres::Config = url.parse().context("Invalid DATABASE_URL")?;
+        let manager = Manager::from_config(
+            pg_config,
+            tls_connector()?,
+            ManagerConfig {
+                recycling_method: RecyclingMethod::Fast,
+            },
+        );
+        let pool = Pool::builder(manager)
+            .max_size(pool_size.max(1))
+            .build()
+            .context("Failed to create Postgres pool")?;
+
+        let store = Self { pool };
+        let version = store.migrate().await?;
+        tracing::info!("Postgres schema at version {}", version);
+        Ok(store)
+    }
+
+    /// Apply pending migrations in one transaction
+    ///
+    /// Replicas starting at the same time wait on an advisory lock instead of
+    /// racing each other.
+    async fn migrate(&self) -> Result<u32> {
+        let mut client = self.pool.get().await?;
+        let tx = client.transaction().await?;
+        tx.batch_execute(
+            "SELECT pg_advisory_xact_lock(hashtext('wowid3_schema_version'));
+             CREATE TABLE IF NOT EXISTS schema_version (
+                version BIGINT PRIMARY KEY,
+                name TEXT NOT NULL,
+                applied_at BIGINT NOT NULL
+             );",
+        )
+        .await?;
+
+        let current: i64 = tx
+            .query_one("SELECT COALESCE(MAX(version), 0) FROM schema_version", &[])
+            .await?
+            .get(0);
+        let latest = MIGRATIONS.last().map_or(0, |m| m.version);
+        if current > latest as i64 {
+            bail!(
+                "Postgres schema is at version {} but this server only knows up to version {}; refusing to start",
+                current,
+                latest
+            );
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        for migration in MIGRATIONS.iter().filter(|m| m.version as i64 > current) {
+            tx.batch_execute(migration.sql)
+                .await
+                .with_context(|| format!("Postgres migration {} ({}) failed", migration.version, migration.name))?;
+            tx.execute(
+                "INSERT INTO schema_version (version, name, applied_at) VALUES ($1, $2, $3)",
+                &[&(migration.version as i64), &migration.name, &now],
+            )
+            .await?;
+            tracing::info!("Applied Postgres migration {} ({})", migration.version, migration.name);
+        }
+
+        tx.commit().await?;
+        Ok(latest.max(current as u32))
+    }
+
+    /// Copy stats and VPN peers from the SQLite database, keeping rows that already exist
+    pub async fn import_from_sqlite(&self, db: &Database) -> Result<ImportCounts> {
+        type StatsRow = (String, String, String, i64);
+        type RollupRow = (String, i64, String, f64);
+        type AdvancementRow = (String, String, String, Option<String>, i64);
+        type PeerRow = (VpnPeerRecord, bool, Option<i64>);
+
+        let (player_stats, event_ids, rollups, advancements, peers) = db.conn.call(|conn| {
+            let player_stats = conn
+                .prepare("SELECT uuid, stats_json, hash, last_updated FROM player_stats")?
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+                .collect::<Result<Vec<StatsRow>, _>>()?;
+            let event_ids = conn
+                .prepare("SELECT event_id, uuid, received_at FROM stat_event_ids")?
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+                .collect::<Result<Vec<(String, String, i64)>, _>>()?;
+            let rollups = conn
+                .prepare("SELECT uuid, day, metric, value FROM stat_rollups")?
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+                .collect::<Result<Vec<RollupRow>, _>>()?;
+            let advancements = conn
+                .prepare("SELECT uuid, advancement_id, name, title, completed_at FROM player_advancements")?
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)))?
+                .collect::<Result<Vec<AdvancementRow>, _>>()?;
+            let peers = conn
+                .prepare(&format!("SELECT {}, revoked, revoked_at FROM vpn_peers", PEER_COLUMNS))?
+                .query_map([], |row| {
+                    Ok((
+                        super::sqlite::peer_record(row)?,
+                        row.get::<_, Option<bool>>(8)?.unwrap_or(false),
+                        row.get(9)?,
+                    ))
+                })?
+                .collect::<Result<Vec<PeerRow>, _>>()?;
+            Ok::<_, rusqlite::Error>((player_stats, event_ids, rollups, advancements, peers))
+        }).await?;
+
+        let mut client = self.pool.get().await?;
+        let tx = client.transaction().await?;
+        let mut counts = ImportCounts::default();
+
+        for (uuid, json, hash, last_updated) in &player_stats {
+            let username = serde_json::from_str::<PlayerStats>(json).map(|s| s.username).unwrap_or_default();
+            counts.player_stats += tx
+                .execute(
+                    "INSERT INTO player_stats (uuid, username, stats_json, hash, last_updated)
+                     VALUES ($1, $2, $3, $4, $5) ON CONFLICT DO NOTHING",
+                    &[uuid, &username, json, hash, last_updated],
+                )
+                .await?;
+        }
+        for (event_id, uuid, received_at) in &event_ids {
+            counts.stat_event_ids += tx
+                .execute(
+                    "INSERT INTO stat_event_ids (event_id, uuid, received_at) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+                    &[event_id, uuid, received_at],
+                )
+                .await?;
+        }
+        for (uuid, day, metric, value) in &rollups {
+            counts.stat_rollups += tx
+                .execute(
+                    "INSERT INTO stat_rollups (uuid, day, metric, value) VALUES ($1, $2, $3, $4) ON CONFLICT DO NOTHING",
+                    &[uuid, day, metric, value],
+                )
+                .await?;
+        }
+        for (uuid, advancement_id, name, title, completed_at) in &advancements {
+            counts.player_advancements += tx
+                .execute(
+                    "INSERT INTO player_advancements (uuid, advancement_id, name, title, completed_at)
+                     VALUES ($1, $2, $3, $4, $5) ON CONFLICT DO NOTHING",
+                    &[uuid, advancement_id, name, title, completed_at],
+                )
+                .await?;
+        }
+        for (peer, revoked, revoked_at) in &peers {
+            counts.vpn_peers += tx
+                .execute(
+                    "INSERT INTO vpn_peers (uuid, username, public_key, ip_address, last_handshake,
+                                            bytes_sent, bytes_received, registered_at, revoked, revoked_at)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) ON CONFLICT DO NOTHING",
+                    &[
+                        &peer.uuid,
+                        &peer.username,
+                        &peer.public_key,
+                        &peer.ip_address,
+                        &peer.last_handshake,
+                        &peer.bytes_sent,
+                        &peer.bytes_received,
+                        &peer.registered_at,
+                        revoked,
+                        revoked_at,
+                    ],
+                )
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(counts)
+    }
+
+    /// Empty every table, for tests against a scratch database
+    #[cfg(test)]
+    pub async fn truncate_all(&self) -> Result<()> {
+        let client = self.pool.get().await?;
+        client
+            .batch_execute("TRUNCATE player_stats, stat_event_ids, stat_rollups, player_advancements, vpn_peers")
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StatsStore for PostgresStore {
+    async fn apply_events(&self, uuid: &str, events: Vec<PlayerStatEvent>, now: i64) -> Result<()> {
+        let mut client = self.pool.get().await?;
+        let tx = client.transaction().await?;
+
+        // Lock the row so replicas applying batches for the same player queue up
+        let existing = tx
+            .query_opt("SELECT stats_json FROM player_stats WHERE uuid = $1 FOR UPDATE", &[&uuid])
+            .await?;
+        let mut player = match existing {
+            Some(row) => serde_json::from_str(row.get::<_, &str>(0)).unwrap_or_default(),
+            None => PlayerStats::new_player(uuid, &batch_username(&events), now as u64),
+        };
+
+        // Skip events already counted
+        let mut fresh = Vec::with_capacity(events.len());
+        for event in events {
+            if let Some(event_id) = &event.event_id {
+                let inserted = tx
+                    .execute(
+                        "INSERT INTO stat_event_ids (event_id, uuid, received_at) VALUES ($1, $2, $3)
+                         ON CONFLICT DO NOTHING",
+                        &[event_id, &uuid, &now],
+                    )
+                    .await?;
+                if inserted == 0 {
+                    tracing::debug!("Skipping duplicate stat event {}", event_id);
+                    continue;
+                }
+            }
+            fresh.push(event);
+        }
+
+        let applied = player.apply_events(fresh);
+
+        // Completion time is when the server heard about it; repeats keep the first
+        for (advancement_id, title) in &applied.advancements {
+            let inserted = tx
+                .execute(
+                    "INSERT INTO player_advancements (uuid, advancement_id, name, title, completed_at)
+                     VALUES ($1, $2, $3, $4, $5) ON CONFLICT DO NOTHING",
+                    &[&uuid, advancement_id, &player.username, title, &now],
+                )
+                .await?;
+            if inserted > 0 {
+                player.advancements_completed += 1;
+            }
+        }
+
+        let today = stats::day_of(now);
+        for (metric, amount) in applied.rollups {
+            if amount == 0.0 {
+                continue;
+            }
+            tx.execute(
+                "INSERT INTO stat_rollups (uuid, day, metric, value) VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (uuid, day, metric) DO UPDATE SET value = stat_rollups.value + EXCLUDED.value",
+                &[&uuid, &today, &metric.as_str(), &amount],
+            )
+            .await?;
+        }
+
+        player.last_updated = now as u64;
+        let (json, hash) = encode_stats(&player)?;
+        tx.execute(
+            "INSERT INTO player_stats (uuid, username, stats_json, hash, last_updated)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (uuid) DO UPDATE SET
+                username = EXCLUDED.username,
+                stats_json = EXCLUDED.stats_json,
+                hash = EXCLUDED.hash,
+                last_updated = EXCLUDED.last_updated",
+            &[&uuid, &player.username, &json, &hash, &now],
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn prune_event_ids(&self, cutoff: i64) -> Result<usize> {
+        let client = self.pool.get().await?;
+        let removed = client
+            .execute("DELETE FROM stat_event_ids WHERE received_at < $1", &[&cutoff])
+            .await?;
+        Ok(removed as usize)
+    }
+
+    async fn player_stats(&self, uuid: &str) -> Result<Option<(String, String)>> {
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt("SELECT stats_json, hash FROM player_stats WHERE uuid = $1", &[&uuid])
+            .await?;
+        Ok(row.map(|row| (row.get(0), row.get(1))))
+    }
+
+    async fn leaderboard(&self, metric: LeaderboardMetric, since_day: Option<i64>, limit: u32) -> Result<Vec<LeaderboardEntry>> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT r.uuid, NULLIF(MAX(p.username), ''), SUM(r.value) AS total
+                 FROM stat_rollups r
+                 LEFT JOIN player_stats p ON p.uuid = r.uuid
+                 WHERE r.metric = $1 AND r.day >= $2
+                 GROUP BY r.uuid
+                 HAVING SUM(r.value) > 0
+                 ORDER BY total DESC, r.uuid
+                 LIMIT $3",
+                &[&metric.as_str(), &since_day.unwrap_or(i64::MIN), &(limit as i64)],
+            )
+            .await?;
+
+        Ok(rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| LeaderboardEntry {
+                rank: i as u32 + 1,
+                uuid: row.get(0),
+                name: row.get(1),
+                value: row.get(2),
+            })
+            .collect())
+    }
+
+    async fn player_advancements(&self, uuid: &str) -> Result<Vec<PlayerAdvancement>> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT advancement_id, title, completed_at FROM player_advancements
+                 WHERE uuid = $1
+                 ORDER BY completed_at, advancement_id",
+                &[&uuid],
+            )
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|row| PlayerAdvancement {
+                advancement_id: row.get(0),
+                title: row.get(1),
+                completed_at: row.get(2),
+            })
+            .collect())
+    }
+
+    async fn advancement_completions(&self, advancement_id: &str, limit: u32) -> Result<Vec<AdvancementCompletion>> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT uuid, name, completed_at FROM player_advancements
+                 WHERE advancement_id = $1
+                 ORDER BY completed_at, uuid
+                 LIMIT $2",
+                &[&advancement_id, &(limit as i64)],
+            )
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|row| AdvancementCompletion {
+                uuid: row.get(0),
+                name: row.get(1),
+                completed_at: row.get(2),
+            })
+            .collect())
+    }
+
+    async fn ping(&self) -> Result<()> {
+        let client = self.pool.get().await?;
+        client.query_one("SELECT 1", &[]).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl VpnPeerStore for PostgresStore {
+    async fn active_peer(&self, uuid: &str) -> Result<Option<VpnPeerRecord>> {
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt(
+                &format!("SELECT {} FROM vpn_peers WHERE uuid = $1 AND NOT revoked", PEER_COLUMNS),
+                &[&uuid],
+            )
+            .await?;
+        Ok(row.as_ref().map(peer_record))
+    }
+
+    async fn upsert_peer(&self, uuid: &str, username: &str, public_key: &str, ip_address: &str, now: i64) -> Result<()> {
+        let client = self.pool.get().await?;
+        // Revoked peers (e.g. auto-revoked for inactivity) keep their row; reactivate it
+        client
+            .execute(
+                "INSERT INTO vpn_peers (uuid, username, public_key, ip_address, registered_at)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (uuid) DO UPDATE SET
+                    username = EXCLUDED.username, public_key = EXCLUDED.public_key,
+                    ip_address = EXCLUDED.ip_address, registered_at = EXCLUDED.registered_at,
+                    last_handshake = NULL, bytes_sent = 0, bytes_received = 0,
+                    revoked = FALSE, revoked_at = NULL",
+                &[&uuid, &username, &public_key, &ip_address, &now],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn update_peer_key(&self, uuid: &str, username: &str, public_key: &str, now: i64) -> Result<()> {
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "UPDATE vpn_peers SET public_key = $1, username = $2, registered_at = $3 WHERE uuid = $4",
+                &[&public_key, &username, &now, &uuid],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn active_ips(&self) -> Result<Vec<String>> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query("SELECT ip_address FROM vpn_peers WHERE NOT revoked ORDER BY ip_address", &[])
+            .await?;
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+
+    async fn list_active(&self, order: PeerOrder) -> Result<Vec<VpnPeerRecord>> {
+        let order_by = match order {
+            PeerOrder::Username => "username ASC",
+            PeerOrder::LastHandshake => "last_handshake DESC NULLS LAST",
+        };
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                &format!("SELECT {} FROM vpn_peers WHERE NOT revoked ORDER BY {}", PEER_COLUMNS, order_by),
+                &[],
+            )
+            .await?;
+        Ok(rows.iter().map(peer_record).collect())
+    }
+
+    async fn public_key(&self, uuid: &str) -> Result<Option<String>> {
+        let client = self.pool.get().await?;
+        let row = client
+            .query_opt("SELECT public_key FROM vpn_peers WHERE uuid = $1", &[&uuid])
+            .await?;
+        Ok(row.map(|row| row.get(0)))
+    }
+
+    async fn revoke(&self, uuid: &str, now: i64) -> Result<()> {
+        let client = self.pool.get().await?;
+        client
+            .execute(
+                "UPDATE vpn_peers SET revoked = TRUE, revoked_at = $1 WHERE uuid = $2",
+                &[&now, &uuid],
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn update_stats(&self, stats: Vec<PeerStats>) -> Result<usize> {
+        let mut client = self.pool.get().await?;
+        let tx = client.transaction().await?;
+        let stmt = tx
+            .prepare(
+                "UPDATE vpn_peers
+                 SET last_handshake = COALESCE($1, last_handshake), bytes_sent = $2, bytes_received = $3
+                 WHERE public_key = $4 AND NOT revoked",
+            )
+            .await?;
+        let mut updated = 0;
+        for peer in &stats {
+            updated += tx
+                .execute(
+                    &stmt,
+                    &[&peer.last_handshake, &peer.bytes_sent, &peer.bytes_received, &peer.public_key],
+                )
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(updated as usize)
+    }
+
+    async fn stale_peers(&self, cutoff: i64) -> Result<Vec<(String, String)>> {
+        let client = self.pool.get().await?;
+        let rows = client
+            .query(
+                "SELECT uuid, public_key FROM vpn_peers
+                 WHERE NOT revoked AND COALESCE(last_handshake, registered_at) < $1
+                 ORDER BY uuid",
+                &[&cutoff],
+            )
+            .await?;
+        Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+}
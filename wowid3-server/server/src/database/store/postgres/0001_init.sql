@@ -0,0 +1,50 @@
+CREATE TABLE IF NOT EXISTS player_stats (
+    uuid TEXT PRIMARY KEY,
+    -- Copied out of stats_json so leaderboards can show names without parsing it
+    username TEXT NOT NULL DEFAULT '',
+    stats_json TEXT NOT NULL,
+    hash TEXT NOT NULL,
+    last_updated BIGINT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_player_stats_last_updated ON player_stats(last_updated);
+
+CREATE TABLE IF NOT EXISTS stat_event_ids (
+    event_id TEXT PRIMARY KEY,
+    uuid TEXT NOT NULL,
+    received_at BIGINT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_stat_event_ids_received ON stat_event_ids(received_at);
+
+CREATE TABLE IF NOT EXISTS stat_rollups (
+    uuid TEXT NOT NULL,
+    day BIGINT NOT NULL,
+    metric TEXT NOT NULL,
+    value DOUBLE PRECISION NOT NULL,
+    PRIMARY KEY (uuid, day, metric)
+);
+CREATE INDEX IF NOT EXISTS idx_stat_rollups_metric_day ON stat_rollups(metric, day);
+
+CREATE TABLE IF NOT EXISTS player_advancements (
+    uuid TEXT NOT NULL,
+    advancement_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    title TEXT,
+    completed_at BIGINT NOT NULL,
+    PRIMARY KEY (uuid, advancement_id)
+);
+CREATE INDEX IF NOT EXISTS idx_advancements_id ON player_advancements(advancement_id, completed_at);
+
+CREATE TABLE IF NOT EXISTS vpn_peers (
+    uuid TEXT PRIMARY KEY,
+    username TEXT NOT NULL,
+    public_key TEXT UNIQUE NOT NULL,
+    ip_address TEXT NOT NULL,
+    registered_at BIGINT NOT NULL,
+    last_handshake BIGINT,
+    bytes_sent BIGINT NOT NULL DEFAULT 0,
+    bytes_received BIGINT NOT NULL DEFAULT 0,
+    revoked BOOLEAN NOT NULL DEFAULT FALSE,
+    revoked_at BIGINT
+);
+CREATE INDEX IF NOT EXISTS idx_vpn_username ON vpn_peers(username);
+CREATE INDEX IF NOT EXISTS idx_vpn_active_ip ON vpn_peers(revoked, ip_address);
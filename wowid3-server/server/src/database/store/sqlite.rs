@@ -0,0 +1,280 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::{batch_username, encode_stats, PeerOrder, StatsStore, VpnPeerRecord, VpnPeerStore};
+use crate::database::advancements::{self, AdvancementCompletion, PlayerAdvancement};
+use crate::database::{stats, Database};
+use crate::models::stats::{LeaderboardEntry, LeaderboardMetric, PlayerStatEvent, PlayerStats};
+use crate::vpn::monitor::PeerStats;
+
+const PEER_COLUMNS: &str =
+    "uuid, username, public_key, ip_address, last_handshake, bytes_sent, bytes_received, registered_at";
+
+/// Stores backed by the server's local SQLite database
+pub struct SqliteStore {
+    db: Database,
+}
+
+impl SqliteStore {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+}
+
+pub(super) fn peer_record(row: &rusqlite::Row) -> rusqlite::Result<VpnPeerRecord> {
+    Ok(VpnPeerRecord {
+        uuid: row.get(0)?,
+        username: row.get(1)?,
+        public_key: row.get(2)?,
+        ip_address: row.get(3)?,
+        last_handshake: row.get(4)?,
+        bytes_sent: row.get::<_, Option<i64>>(5)?.unwrap_or(0),
+        bytes_received: row.get::<_, Option<i64>>(6)?.unwrap_or(0),
+        registered_at: row.get(7)?,
+    })
+}
+
+#[async_trait]
+impl StatsStore for SqliteStore {
+    async fn apply_events(&self, uuid: &str, events: Vec<PlayerStatEvent>, now: i64) -> Result<()> {
+        let uuid = uuid.to_string();
+        let default_username = batch_username(&events);
+
+        self.db.conn.call(move |conn| {
+            let tx = conn.transaction()?;
+
+            let existing = tx.query_row(
+                "SELECT stats_json FROM player_stats WHERE uuid = ?1",
+                [&uuid],
+                |row| row.get::<_, String>(0),
+            );
+            let mut player = match existing {
+                Ok(json) => serde_json::from_str(&json).unwrap_or_default(),
+                Err(rusqlite::Error::QueryReturnedNoRows) => {
+                    PlayerStats::new_player(&uuid, &default_username, now as u64)
+                }
+                Err(e) => return Err(e),
+            };
+
+            // Skip events already counted
+            let mut fresh = Vec::with_capacity(events.len());
+            for event in events {
+                if let Some(event_id) = &event.event_id {
+                    if !stats::record_event_id(&tx, event_id, &uuid, now)? {
+                        tracing::debug!("Skipping duplicate stat event {}", event_id);
+                        continue;
+                    }
+                }
+                fresh.push(event);
+            }
+
+            let applied = player.apply_events(fresh);
+
+            // Completion time is when the server heard about it; repeats keep the first
+            for (advancement_id, title) in &applied.advancements {
+                if advancements::record(&tx, &uuid, &player.username, advancement_id, title.as_deref(), now)? {
+                    player.advancements_completed += 1;
+                }
+            }
+
+            // Leaderboards read daily rollups instead of every player's stats
+            let today = stats::day_of(now);
+            for (metric, amount) in applied.rollups {
+                stats::add_rollup(&tx, &uuid, today, metric, amount)?;
+            }
+
+            player.last_updated = now as u64;
+            let (json, hash) = encode_stats(&player).map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
+            tx.execute(
+                "INSERT INTO player_stats (uuid, stats_json, hash, last_updated)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(uuid) DO UPDATE SET
+                    stats_json = excluded.stats_json,
+                    hash = excluded.hash,
+                    last_updated = excluded.last_updated",
+                rusqlite::params![uuid, json, hash, now],
+            )?;
+
+            tx.commit()
+        }).await?;
+        Ok(())
+    }
+
+    async fn prune_event_ids(&self, cutoff: i64) -> Result<usize> {
+        stats::prune_event_ids(&self.db.conn, cutoff).await
+    }
+
+    async fn player_stats(&self, uuid: &str) -> Result<Option<(String, String)>> {
+        let uuid = uuid.to_string();
+        let row = self.db.conn.call(move |conn| {
+            let row = conn.query_row(
+                "SELECT stats_json, hash FROM player_stats WHERE uuid = ?1",
+                [&uuid],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            );
+            match row {
+                Ok(row) => Ok(Some(row)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(e),
+            }
+        }).await?;
+        Ok(row)
+    }
+
+    async fn leaderboard(&self, metric: LeaderboardMetric, since_day: Option<i64>, limit: u32) -> Result<Vec<LeaderboardEntry>> {
+        stats::leaderboard(&self.db.conn, metric, since_day, limit).await
+    }
+
+    async fn player_advancements(&self, uuid: &str) -> Result<Vec<PlayerAdvancement>> {
+        advancements::list_for_player(&self.db.conn, uuid.to_string()).await
+    }
+
+    async fn advancement_completions(&self, advancement_id: &str, limit: u32) -> Result<Vec<AdvancementCompletion>> {
+        advancements::completions(&self.db.conn, advancement_id.to_string(), limit).await
+    }
+
+    async fn ping(&self) -> Result<()> {
+        self.db.ping().await
+    }
+}
+
+#[async_trait]
+impl VpnPeerStore for SqliteStore {
+    async fn active_peer(&self, uuid: &str) -> Result<Option<VpnPeerRecord>> {
+        let uuid = uuid.to_string();
+        let peer = self.db.conn.call(move |conn| {
+            let row = conn.query_row(
+                &format!("SELECT {} FROM vpn_peers WHERE uuid = ?1 AND revoked = 0", PEER_COLUMNS),
+                [&uuid],
+                peer_record,
+            );
+            match row {
+                Ok(peer) => Ok(Some(peer)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(e),
+            }
+        }).await?;
+        Ok(peer)
+    }
+
+    async fn upsert_peer(&self, uuid: &str, username: &str, public_key: &str, ip_address: &str, now: i64) -> Result<()> {
+        let params = (uuid.to_string(), username.to_string(), public_key.to_string(), ip_address.to_string(), now);
+        self.db.conn.call(move |conn| {
+            // Revoked peers (e.g. auto-revoked for inactivity) keep their row; reactivate it
+            conn.execute(
+                "INSERT INTO vpn_peers (uuid, username, public_key, ip_address, registered_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(uuid) DO UPDATE SET
+                    username = excluded.username, public_key = excluded.public_key,
+                    ip_address = excluded.ip_address, registered_at = excluded.registered_at,
+                    last_handshake = NULL, bytes_sent = 0, bytes_received = 0,
+                    revoked = 0, revoked_at = NULL",
+                params,
+            )
+        }).await?;
+        Ok(())
+    }
+
+    async fn update_peer_key(&self, uuid: &str, username: &str, public_key: &str, now: i64) -> Result<()> {
+        let params = (public_key.to_string(), username.to_string(), now, uuid.to_string());
+        self.db.conn.call(move |conn| {
+            conn.execute(
+                "UPDATE vpn_peers SET public_key = ?1, username = ?2, registered_at = ?3 WHERE uuid = ?4",
+                params,
+            )
+        }).await?;
+        Ok(())
+    }
+
+    async fn active_ips(&self) -> Result<Vec<String>> {
+        let ips = self.db.conn.call(|conn| {
+            let mut stmt = conn.prepare("SELECT ip_address FROM vpn_peers WHERE revoked = 0 ORDER BY ip_address")?;
+            let ips = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<Result<Vec<String>, _>>()?;
+            Ok::<_, rusqlite::Error>(ips)
+        }).await?;
+        Ok(ips)
+    }
+
+    async fn list_active(&self, order: PeerOrder) -> Result<Vec<VpnPeerRecord>> {
+        let order_by = match order {
+            PeerOrder::Username => "username ASC",
+            PeerOrder::LastHandshake => "last_handshake DESC NULLS LAST",
+        };
+        let peers = self.db.conn.call(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {} FROM vpn_peers WHERE revoked = 0 ORDER BY {}",
+                PEER_COLUMNS, order_by
+            ))?;
+            let peers = stmt
+                .query_map([], peer_record)?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok::<_, rusqlite::Error>(peers)
+        }).await?;
+        Ok(peers)
+    }
+
+    async fn public_key(&self, uuid: &str) -> Result<Option<String>> {
+        let uuid = uuid.to_string();
+        let key = self.db.conn.call(move |conn| {
+            match conn.query_row("SELECT public_key FROM vpn_peers WHERE uuid = ?1", [&uuid], |row| row.get(0)) {
+                Ok(key) => Ok(Some(key)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(e),
+            }
+        }).await?;
+        Ok(key)
+    }
+
+    async fn revoke(&self, uuid: &str, now: i64) -> Result<()> {
+        let uuid = uuid.to_string();
+        self.db.conn.call(move |conn| {
+            conn.execute(
+                "UPDATE vpn_peers SET revoked = 1, revoked_at = ?1 WHERE uuid = ?2",
+                rusqlite::params![now, &uuid],
+            )
+        }).await?;
+        Ok(())
+    }
+
+    async fn update_stats(&self, stats: Vec<PeerStats>) -> Result<usize> {
+        let updated = self.db.conn.call(move |conn| {
+            let tx = conn.transaction()?;
+            let mut updated = 0;
+            {
+                let mut stmt = tx.prepare(
+                    "UPDATE vpn_peers
+                     SET last_handshake = COALESCE(?1, last_handshake), bytes_sent = ?2, bytes_received = ?3
+                     WHERE public_key = ?4 AND revoked = 0",
+                )?;
+                for peer in &stats {
+                    updated += stmt.execute(rusqlite::params![
+                        peer.last_handshake,
+                        peer.bytes_sent,
+                        peer.bytes_received,
+                        peer.public_key
+                    ])?;
+                }
+            }
+            tx.commit()?;
+            Ok::<_, rusqlite::Error>(updated)
+        }).await?;
+        Ok(updated)
+    }
+
+    async fn stale_peers(&self, cutoff: i64) -> Result<Vec<(String, String)>> {
+        let peers = self.db.conn.call(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT uuid, public_key FROM vpn_peers
+                 WHERE revoked = 0 AND COALESCE(last_handshake, registered_at) < ?1
+                 ORDER BY uuid",
+            )?;
+            let peers = stmt
+                .query_map([cutoff], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<Vec<(String, String)>, _>>()?;
+            Ok::<_, rusqlite::Error>(peers)
+        }).await?;
+        Ok(peers)
+    }
+}
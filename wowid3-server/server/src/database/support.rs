@@ -0,0 +1,144 @@
+use tokio_rusqlite::Connection;
+use anyhow::Result;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A support report as listed in the admin panel (without the full report)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupportReportSummary {
+    pub id: i64,
+    pub timestamp: i64,
+    pub os: String,
+    pub launcher_version: Option<String>,
+    pub modpack_version: Option<String>,
+    /// What the player said went wrong
+    pub note: Option<String>,
+}
+
+/// A full support report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupportReport {
+    #[serde(flatten)]
+    pub info: SupportReportSummary,
+    /// System report as gathered by the launcher
+    pub report: Value,
+}
+
+/// Report to be inserted (id and timestamp are assigned on insert)
+#[derive(Debug, Clone)]
+pub struct NewSupportReport {
+    pub os: String,
+    pub launcher_version: Option<String>,
+    pub modpack_version: Option<String>,
+    pub note: Option<String>,
+    pub report: Value,
+}
+
+pub async fn insert(conn: &Connection, report: NewSupportReport) -> Result<i64> {
+    let now = chrono::Utc::now().timestamp();
+    let json = serde_json::to_string(&report.report)?;
+
+    let id = conn.call(move |conn| {
+        conn.execute(
+            "INSERT INTO support_reports (timestamp, os, launcher_version, modpack_version, note, report)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                now,
+                report.os,
+                report.launcher_version,
+                report.modpack_version,
+                report.note,
+                json,
+            ],
+        )?;
+        Ok::<_, rusqlite::Error>(conn.last_insert_rowid())
+    }).await?;
+    Ok(id)
+}
+
+const SUMMARY_COLUMNS: &str = "id, timestamp, os, launcher_version, modpack_version, note";
+
+fn summary_from_row(row: &rusqlite::Row) -> rusqlite::Result<SupportReportSummary> {
+    Ok(SupportReportSummary {
+        id: row.get(0)?,
+        timestamp: row.get(1)?,
+        os: row.get(2)?,
+        launcher_version: row.get(3)?,
+        modpack_version: row.get(4)?,
+        note: row.get(5)?,
+    })
+}
+
+/// List reports (newest first), returning the page and total count
+pub async fn list(conn: &Connection, limit: usize, offset: usize) -> Result<(Vec<SupportReportSummary>, usize)> {
+    let result = conn.call(move |conn| {
+        let total: i64 = conn.query_row("SELECT COUNT(*) FROM support_reports", [], |row| row.get(0))?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM support_reports ORDER BY id DESC LIMIT {} OFFSET {}",
+            SUMMARY_COLUMNS, limit, offset
+        ))?;
+        let reports = stmt
+            .query_map([], summary_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok::<_, rusqlite::Error>((reports, total as usize))
+    }).await?;
+
+    Ok(result)
+}
+
+pub async fn get(conn: &Connection, id: i64) -> Result<Option<SupportReport>> {
+    let row = conn.call(move |conn| {
+        conn.query_row(
+            &format!("SELECT {}, report FROM support_reports WHERE id = ?1", SUMMARY_COLUMNS),
+            [id],
+            |row| Ok((summary_from_row(row)?, row.get::<_, String>(6)?)),
+        )
+        .optional()
+    }).await?;
+
+    match row {
+        Some((info, report)) => Ok(Some(SupportReport {
+            info,
+            report: serde_json::from_str(&report)?,
+        })),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_insert_list_and_get() {
+        let temp_dir = tempdir().unwrap();
+        let db = Database::new(temp_dir.path().join("test.db")).await.unwrap();
+        db.init_schema().await.unwrap();
+
+        let report = |note: Option<&str>| NewSupportReport {
+            os: "windows".to_string(),
+            launcher_version: Some("1.4.0".to_string()),
+            modpack_version: Some("1.1.0".to_string()),
+            note: note.map(str::to_string),
+            report: json!({ "gpus": [{ "name": "NVIDIA GeForce RTX 3060" }] }),
+        };
+
+        insert(&db.conn, report(None)).await.unwrap();
+        let id = insert(&db.conn, report(Some("Crashes when joining"))).await.unwrap();
+
+        let (entries, total) = list(&db.conn, 10, 0).await.unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(entries[0].id, id); // newest first
+        assert_eq!(entries[0].note.as_deref(), Some("Crashes when joining"));
+
+        let full = get(&db.conn, id).await.unwrap().unwrap();
+        assert_eq!(full.report["gpus"][0]["name"], "NVIDIA GeForce RTX 3060");
+        assert!(get(&db.conn, 9999).await.unwrap().is_none());
+    }
+}
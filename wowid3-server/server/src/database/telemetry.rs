@@ -0,0 +1,244 @@
+use tokio_rusqlite::Connection;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Failure categories reported by the launcher; anything else is stored as `other`
+pub const ERROR_CATEGORIES: &[&str] = &[
+    "network",
+    "timeout",
+    "server",
+    "checksum",
+    "disk_space",
+    "filesystem",
+    "other",
+];
+
+/// Outcome of one modpack install or update, as reported by the launcher
+#[derive(Debug, Clone)]
+pub struct NewUpdateReport {
+    pub version: String,
+    pub from_version: Option<String>,
+    pub success: bool,
+    pub duration_ms: i64,
+    pub bytes: i64,
+    pub files: i64,
+    pub error_category: Option<String>,
+    pub launcher_version: Option<String>,
+    pub os: Option<String>,
+    pub install_id: Option<String>,
+}
+
+/// Update outcomes aggregated for one release
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReleaseUpdateStats {
+    pub version: String,
+    pub reports: i64,
+    pub successes: i64,
+    pub failures: i64,
+    /// Median and 95th percentile duration of successful updates
+    pub median_duration_ms: Option<i64>,
+    pub p95_duration_ms: Option<i64>,
+    /// Overall throughput of successful updates (total bytes / total time)
+    pub avg_bytes_per_sec: Option<i64>,
+    pub failures_by_category: BTreeMap<String, i64>,
+}
+
+/// One report's outcome, grouped by release in `release_stats`
+struct Outcome {
+    success: bool,
+    duration_ms: i64,
+    bytes: i64,
+    category: Option<String>,
+}
+
+pub async fn init_schema(conn: &Connection) -> Result<()> {
+    conn.call(|conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS update_reports (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp INTEGER NOT NULL,
+                version TEXT NOT NULL,
+                from_version TEXT,
+                success BOOLEAN NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                bytes INTEGER NOT NULL,
+                files INTEGER NOT NULL,
+                error_category TEXT,
+                launcher_version TEXT,
+                os TEXT,
+                install_id TEXT
+            );
+            CREATE INDEX IF NOT EXISTS idx_update_reports_version ON update_reports(version, timestamp);
+            CREATE INDEX IF NOT EXISTS idx_update_reports_timestamp ON update_reports(timestamp);"
+        )
+    }).await?;
+    Ok(())
+}
+
+pub async fn insert(conn: &Connection, report: NewUpdateReport) -> Result<i64> {
+    let now = chrono::Utc::now().timestamp();
+
+    let id = conn.call(move |conn| {
+        conn.execute(
+            "INSERT INTO update_reports
+                (timestamp, version, from_version, success, duration_ms, bytes, files,
+                 error_category, launcher_version, os, install_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            rusqlite::params![
+                now,
+                report.version,
+                report.from_version,
+                report.success,
+                report.duration_ms,
+                report.bytes,
+                report.files,
+                report.error_category,
+                report.launcher_version,
+                report.os,
+                report.install_id,
+            ],
+        )?;
+        Ok::<_, rusqlite::Error>(conn.last_insert_rowid())
+    }).await?;
+
+    Ok(id)
+}
+
+/// Per-release update statistics for reports received since `since`, versions in descending order
+pub async fn release_stats(conn: &Connection, since: i64) -> Result<Vec<ReleaseUpdateStats>> {
+    let rows = conn.call(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT version, success, duration_ms, bytes, error_category
+             FROM update_reports
+             WHERE timestamp >= ?1",
+        )?;
+        let rows = stmt
+            .query_map([since], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    Outcome {
+                        success: row.get(1)?,
+                        duration_ms: row.get(2)?,
+                        bytes: row.get(3)?,
+                        category: row.get(4)?,
+                    },
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok::<_, rusqlite::Error>(rows)
+    }).await?;
+
+    // Percentiles aren't available in SQLite, so aggregate here
+    let mut by_version: BTreeMap<String, Vec<Outcome>> = BTreeMap::new();
+    for (version, outcome) in rows {
+        by_version.entry(version).or_default().push(outcome);
+    }
+
+    let mut stats: Vec<ReleaseUpdateStats> = by_version
+        .into_iter()
+        .map(|(version, reports)| {
+            let mut durations: Vec<i64> = reports
+                .iter()
+                .filter(|r| r.success)
+                .map(|r| r.duration_ms)
+                .collect();
+            durations.sort_unstable();
+
+            let (total_bytes, total_ms) = reports
+                .iter()
+                .filter(|r| r.success)
+                .fold((0i64, 0i64), |(b, d), r| (b + r.bytes, d + r.duration_ms));
+
+            let mut failures_by_category = BTreeMap::new();
+            for report in reports.iter().filter(|r| !r.success) {
+                let category = report.category.clone().unwrap_or_else(|| "other".to_string());
+                *failures_by_category.entry(category).or_insert(0) += 1;
+            }
+
+            ReleaseUpdateStats {
+                version,
+                reports: reports.len() as i64,
+                successes: durations.len() as i64,
+                failures: (reports.len() - durations.len()) as i64,
+                median_duration_ms: percentile(&durations, 50),
+                p95_duration_ms: percentile(&durations, 95),
+                avg_bytes_per_sec: (total_ms > 0).then(|| total_bytes * 1000 / total_ms),
+                failures_by_category,
+            }
+        })
+        .collect();
+
+    stats.reverse();
+    Ok(stats)
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[i64], pct: usize) -> Option<i64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    Some(sorted[rank - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn report(version: &str, success: bool, duration_ms: i64, bytes: i64, category: Option<&str>) -> NewUpdateReport {
+        NewUpdateReport {
+            version: version.to_string(),
+            from_version: Some("1.0.0".to_string()),
+            success,
+            duration_ms,
+            bytes,
+            files: 10,
+            error_category: category.map(|c| c.to_string()),
+            launcher_version: Some("1.4.0".to_string()),
+            os: Some("windows".to_string()),
+            install_id: None,
+        }
+    }
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&[], 50), None);
+        assert_eq!(percentile(&[7], 95), Some(7));
+        let values: Vec<i64> = (1..=20).collect();
+        assert_eq!(percentile(&values, 50), Some(10));
+        assert_eq!(percentile(&values, 95), Some(19));
+    }
+
+    #[tokio::test]
+    async fn test_release_stats_aggregate_per_version() {
+        let temp_dir = tempdir().unwrap();
+        let conn = Connection::open(temp_dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        insert(&conn, report("1.1.0", true, 1000, 4000, None)).await.unwrap();
+        insert(&conn, report("1.1.0", true, 3000, 4000, None)).await.unwrap();
+        insert(&conn, report("1.1.0", false, 500, 100, Some("checksum"))).await.unwrap();
+        insert(&conn, report("1.1.0", false, 500, 0, None)).await.unwrap();
+        insert(&conn, report("1.0.0", false, 500, 0, Some("network"))).await.unwrap();
+
+        let stats = release_stats(&conn, 0).await.unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].version, "1.1.0");
+        assert_eq!(stats[0].reports, 4);
+        assert_eq!(stats[0].successes, 2);
+        assert_eq!(stats[0].failures, 2);
+        assert_eq!(stats[0].median_duration_ms, Some(1000));
+        assert_eq!(stats[0].avg_bytes_per_sec, Some(2000));
+        assert_eq!(stats[0].failures_by_category.get("checksum"), Some(&1));
+        assert_eq!(stats[0].failures_by_category.get("other"), Some(&1));
+
+        assert_eq!(stats[1].successes, 0);
+        assert_eq!(stats[1].median_duration_ms, None);
+        assert_eq!(stats[1].avg_bytes_per_sec, None);
+
+        let future = chrono::Utc::now().timestamp() + 60;
+        assert!(release_stats(&conn, future).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,464 @@
+use tokio_rusqlite::Connection;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Counters collected by the usage middleware between two flushes
+///
+/// Days are UTC `YYYY-MM-DD` strings so rows can be grouped without date math in SQL.
+#[derive(Debug, Clone, Default)]
+pub struct UsageBatch {
+    /// (day, release version) -> (file requests, bytes)
+    pub downloads: HashMap<(String, String), (i64, i64)>,
+    /// day -> (requests, bytes) across all public routes
+    pub bandwidth: HashMap<String, (i64, i64)>,
+    /// launcher install id -> (last seen, launcher version)
+    pub installs: HashMap<String, (i64, Option<String>)>,
+    /// (release version, launcher install id) pairs that downloaded release files
+    pub release_installs: HashSet<(String, String)>,
+    /// (release version, file path) -> (full downloads, bytes)
+    pub files: HashMap<(String, String), (i64, i64)>,
+}
+
+impl UsageBatch {
+    pub fn is_empty(&self) -> bool {
+        self.downloads.is_empty()
+            && self.bandwidth.is_empty()
+            && self.installs.is_empty()
+            && self.release_installs.is_empty()
+            && self.files.is_empty()
+    }
+}
+
+/// Download totals for one release
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReleaseDownloads {
+    pub version: String,
+    /// Distinct launcher installs that downloaded files of this release
+    pub installs: i64,
+    pub file_requests: i64,
+    pub bytes: i64,
+}
+
+/// Download totals for one release file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FileDownloads {
+    pub version: String,
+    pub path: String,
+    pub downloads: i64,
+    pub bytes: i64,
+    pub last_downloaded: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InstallCounts {
+    pub total: i64,
+    pub active_last_7_days: i64,
+    pub active_last_30_days: i64,
+    /// Installs first seen within the requested period
+    pub new_in_period: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DailyBandwidth {
+    pub day: String,
+    pub requests: i64,
+    pub bytes: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DailyPlayers {
+    pub day: String,
+    pub unique_players: i64,
+    pub peak_online: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageOverview {
+    pub releases: Vec<ReleaseDownloads>,
+    pub installs: InstallCounts,
+    pub bandwidth: Vec<DailyBandwidth>,
+    pub players: Vec<DailyPlayers>,
+}
+
+pub async fn init_schema(conn: &Connection) -> Result<()> {
+    conn.call(|conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS release_downloads (
+                version TEXT NOT NULL,
+                day TEXT NOT NULL,
+                file_requests INTEGER NOT NULL DEFAULT 0,
+                bytes INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (version, day)
+            );
+
+            CREATE TABLE IF NOT EXISTS release_installs (
+                version TEXT NOT NULL,
+                install_id TEXT NOT NULL,
+                first_seen INTEGER NOT NULL,
+                PRIMARY KEY (version, install_id)
+            );
+
+            CREATE TABLE IF NOT EXISTS launcher_installs (
+                install_id TEXT PRIMARY KEY,
+                first_seen INTEGER NOT NULL,
+                last_seen INTEGER NOT NULL,
+                launcher_version TEXT
+            );
+            CREATE INDEX IF NOT EXISTS idx_launcher_installs_last_seen ON launcher_installs(last_seen);
+
+            CREATE TABLE IF NOT EXISTS bandwidth_daily (
+                day TEXT PRIMARY KEY,
+                requests INTEGER NOT NULL DEFAULT 0,
+                bytes INTEGER NOT NULL DEFAULT 0
+            );
+
+            CREATE TABLE IF NOT EXISTS player_activity (
+                day TEXT NOT NULL,
+                player_uuid TEXT NOT NULL,
+                PRIMARY KEY (day, player_uuid)
+            );
+
+            CREATE TABLE IF NOT EXISTS release_file_downloads (
+                version TEXT NOT NULL,
+                path TEXT NOT NULL,
+                downloads INTEGER NOT NULL DEFAULT 0,
+                bytes INTEGER NOT NULL DEFAULT 0,
+                last_downloaded INTEGER NOT NULL,
+                PRIMARY KEY (version, path)
+            );
+            CREATE INDEX IF NOT EXISTS idx_release_file_downloads_downloads ON release_file_downloads(downloads);
+
+            CREATE TABLE IF NOT EXISTS player_peaks (
+                day TEXT PRIMARY KEY,
+                peak_online INTEGER NOT NULL
+            );"
+        )
+    }).await?;
+    Ok(())
+}
+
+/// UTC day key used by all usage tables
+pub fn day_key(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .unwrap_or_default()
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// Add a batch of middleware counters in a single transaction
+pub async fn record_batch(conn: &Connection, batch: UsageBatch) -> Result<()> {
+    if batch.is_empty() {
+        return Ok(());
+    }
+    let now = chrono::Utc::now().timestamp();
+
+    conn.call(move |conn| {
+        let tx = conn.transaction()?;
+
+        for ((day, version), (requests, bytes)) in &batch.downloads {
+            tx.execute(
+                "INSERT INTO release_downloads (version, day, file_requests, bytes) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(version, day) DO UPDATE SET
+                    file_requests = file_requests + excluded.file_requests,
+                    bytes = bytes + excluded.bytes",
+                rusqlite::params![version, day, requests, bytes],
+            )?;
+        }
+
+        for (day, (requests, bytes)) in &batch.bandwidth {
+            tx.execute(
+                "INSERT INTO bandwidth_daily (day, requests, bytes) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(day) DO UPDATE SET
+                    requests = requests + excluded.requests,
+                    bytes = bytes + excluded.bytes",
+                rusqlite::params![day, requests, bytes],
+            )?;
+        }
+
+        for (install_id, (last_seen, launcher_version)) in &batch.installs {
+            tx.execute(
+                "INSERT INTO launcher_installs (install_id, first_seen, last_seen, launcher_version)
+                 VALUES (?1, ?2, ?2, ?3)
+                 ON CONFLICT(install_id) DO UPDATE SET
+                    last_seen = MAX(last_seen, excluded.last_seen),
+                    launcher_version = COALESCE(excluded.launcher_version, launcher_version)",
+                rusqlite::params![install_id, last_seen, launcher_version],
+            )?;
+        }
+
+        for ((version, path), (downloads, bytes)) in &batch.files {
+            tx.execute(
+                "INSERT INTO release_file_downloads (version, path, downloads, bytes, last_downloaded)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(version, path) DO UPDATE SET
+                    downloads = downloads + excluded.downloads,
+                    bytes = bytes + excluded.bytes,
+                    last_downloaded = excluded.last_downloaded",
+                rusqlite::params![version, path, downloads, bytes, now],
+            )?;
+        }
+
+        for (version, install_id) in &batch.release_installs {
+            tx.execute(
+                "INSERT OR IGNORE INTO release_installs (version, install_id, first_seen) VALUES (?1, ?2, ?3)",
+                rusqlite::params![version, install_id, now],
+            )?;
+        }
+
+        tx.commit()
+    }).await?;
+
+    Ok(())
+}
+
+/// Record the players the tracker currently reports as online
+pub async fn record_online_players(conn: &Connection, player_uuids: Vec<String>) -> Result<()> {
+    let day = day_key(chrono::Utc::now().timestamp());
+    let online = player_uuids.len() as i64;
+
+    conn.call(move |conn| {
+        let tx = conn.transaction()?;
+        for uuid in &player_uuids {
+            tx.execute(
+                "INSERT OR IGNORE INTO player_activity (day, player_uuid) VALUES (?1, ?2)",
+                [&day, uuid],
+            )?;
+        }
+        tx.execute(
+            "INSERT INTO player_peaks (day, peak_online) VALUES (?1, ?2)
+             ON CONFLICT(day) DO UPDATE SET peak_online = MAX(peak_online, excluded.peak_online)",
+            rusqlite::params![day, online],
+        )?;
+        tx.commit()
+    }).await?;
+
+    Ok(())
+}
+
+/// Most downloaded release files, optionally limited to one release
+pub async fn file_downloads(conn: &Connection, version: Option<String>, limit: u32) -> Result<Vec<FileDownloads>> {
+    let files = conn.call(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT version, path, downloads, bytes, last_downloaded
+             FROM release_file_downloads
+             WHERE ?1 IS NULL OR version = ?1
+             ORDER BY downloads DESC, version DESC, path
+             LIMIT ?2",
+        )?;
+        let files = stmt
+            .query_map(rusqlite::params![version, limit], |row| {
+                Ok(FileDownloads {
+                    version: row.get(0)?,
+                    path: row.get(1)?,
+                    downloads: row.get(2)?,
+                    bytes: row.get(3)?,
+                    last_downloaded: row.get(4)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok::<_, rusqlite::Error>(files)
+    }).await?;
+
+    Ok(files)
+}
+
+/// Aggregate usage for the admin dashboard; daily series cover the last `days` days
+pub async fn overview(conn: &Connection, days: u32) -> Result<UsageOverview> {
+    let now = chrono::Utc::now().timestamp();
+    let since = now - i64::from(days.max(1)) * 86400;
+    let since_day = day_key(since);
+
+    let overview = conn.call(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT d.version,
+                    (SELECT COUNT(*) FROM release_installs i WHERE i.version = d.version),
+                    SUM(d.file_requests), SUM(d.bytes)
+             FROM release_downloads d
+             GROUP BY d.version
+             ORDER BY d.version DESC",
+        )?;
+        let releases = stmt
+            .query_map([], |row| {
+                Ok(ReleaseDownloads {
+                    version: row.get(0)?,
+                    installs: row.get(1)?,
+                    file_requests: row.get(2)?,
+                    bytes: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let installs = conn.query_row(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(last_seen >= ?1), 0),
+                    COALESCE(SUM(last_seen >= ?2), 0),
+                    COALESCE(SUM(first_seen >= ?3), 0)
+             FROM launcher_installs",
+            rusqlite::params![now - 7 * 86400, now - 30 * 86400, since],
+            |row| {
+                Ok(InstallCounts {
+                    total: row.get(0)?,
+                    active_last_7_days: row.get(1)?,
+                    active_last_30_days: row.get(2)?,
+                    new_in_period: row.get(3)?,
+                })
+            },
+        )?;
+
+        let mut stmt = conn.prepare(
+            "SELECT day, requests, bytes FROM bandwidth_daily WHERE day > ?1 ORDER BY day",
+        )?;
+        let bandwidth = stmt
+            .query_map([&since_day], |row| {
+                Ok(DailyBandwidth {
+                    day: row.get(0)?,
+                    requests: row.get(1)?,
+                    bytes: row.get(2)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT p.day,
+                    (SELECT COUNT(*) FROM player_activity a WHERE a.day = p.day),
+                    p.peak_online
+             FROM player_peaks p
+             WHERE p.day > ?1
+             ORDER BY p.day",
+        )?;
+        let players = stmt
+            .query_map([&since_day], |row| {
+                Ok(DailyPlayers {
+                    day: row.get(0)?,
+                    unique_players: row.get(1)?,
+                    peak_online: row.get(2)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok::<_, rusqlite::Error>(UsageOverview {
+            releases,
+            installs,
+            bandwidth,
+            players,
+        })
+    }).await?;
+
+    Ok(overview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    async fn test_conn() -> (tempfile::TempDir, Connection) {
+        let temp_dir = tempdir().unwrap();
+        let conn = Connection::open(temp_dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+        (temp_dir, conn)
+    }
+
+    fn today() -> String {
+        day_key(chrono::Utc::now().timestamp())
+    }
+
+    #[test]
+    fn test_day_key() {
+        assert_eq!(day_key(0), "1970-01-01");
+        assert_eq!(day_key(1_767_225_599), "2025-12-31");
+    }
+
+    #[tokio::test]
+    async fn test_batches_accumulate() {
+        let (_dir, conn) = test_conn().await;
+        let now = chrono::Utc::now().timestamp();
+
+        for _ in 0..2 {
+            let mut batch = UsageBatch::default();
+            batch.downloads.insert((today(), "1.2.0".to_string()), (10, 1000));
+            batch.bandwidth.insert(today(), (12, 1500));
+            batch.installs.insert("install-a".to_string(), (now, Some("1.4.0".to_string())));
+            batch.release_installs.insert(("1.2.0".to_string(), "install-a".to_string()));
+            record_batch(&conn, batch).await.unwrap();
+        }
+
+        let mut batch = UsageBatch::default();
+        batch.installs.insert("install-b".to_string(), (now, None));
+        batch.release_installs.insert(("1.2.0".to_string(), "install-b".to_string()));
+        record_batch(&conn, batch).await.unwrap();
+
+        let overview = overview(&conn, 30).await.unwrap();
+        assert_eq!(
+            overview.releases,
+            vec![ReleaseDownloads {
+                version: "1.2.0".to_string(),
+                installs: 2,
+                file_requests: 20,
+                bytes: 2000,
+            }]
+        );
+        assert_eq!(overview.installs.total, 2);
+        assert_eq!(overview.installs.active_last_7_days, 2);
+        assert_eq!(overview.installs.new_in_period, 2);
+        assert_eq!(
+            overview.bandwidth,
+            vec![DailyBandwidth { day: today(), requests: 24, bytes: 3000 }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_downloads_ranked() {
+        let (_dir, conn) = test_conn().await;
+
+        for _ in 0..2 {
+            let mut batch = UsageBatch::default();
+            batch.files.insert(("1.2.0".to_string(), "config/a.toml".to_string()), (3, 30));
+            batch.files.insert(("1.2.0".to_string(), "mods/b.jar".to_string()), (1, 1000));
+            batch.files.insert(("1.1.0".to_string(), "mods/b.jar".to_string()), (5, 5000));
+            record_batch(&conn, batch).await.unwrap();
+        }
+
+        let all = file_downloads(&conn, None, 10).await.unwrap();
+        let ranked: Vec<_> = all.iter().map(|f| (f.version.as_str(), f.path.as_str(), f.downloads)).collect();
+        assert_eq!(
+            ranked,
+            vec![("1.1.0", "mods/b.jar", 10), ("1.2.0", "config/a.toml", 6), ("1.2.0", "mods/b.jar", 2)]
+        );
+
+        let release = file_downloads(&conn, Some("1.2.0".to_string()), 1).await.unwrap();
+        assert_eq!(release.len(), 1);
+        assert_eq!(release[0].path, "config/a.toml");
+        assert_eq!(release[0].bytes, 60);
+    }
+
+    #[tokio::test]
+    async fn test_stale_installs_are_not_active() {
+        let (_dir, conn) = test_conn().await;
+        let old = chrono::Utc::now().timestamp() - 60 * 86400;
+
+        let mut batch = UsageBatch::default();
+        batch.installs.insert("old".to_string(), (old, None));
+        record_batch(&conn, batch).await.unwrap();
+
+        let installs = overview(&conn, 30).await.unwrap().installs;
+        assert_eq!(installs.total, 1);
+        assert_eq!(installs.active_last_30_days, 0);
+        assert_eq!(installs.new_in_period, 0);
+    }
+
+    #[tokio::test]
+    async fn test_online_players_track_unique_and_peak() {
+        let (_dir, conn) = test_conn().await;
+
+        record_online_players(&conn, vec!["a".into(), "b".into(), "c".into()]).await.unwrap();
+        record_online_players(&conn, vec!["a".into(), "d".into()]).await.unwrap();
+
+        let players = overview(&conn, 7).await.unwrap().players;
+        assert_eq!(
+            players,
+            vec![DailyPlayers { day: today(), unique_players: 4, peak_online: 3 }]
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use tokio_rusqlite::Connection;
+use anyhow::Result;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+
+/// A player allowed on the Minecraft server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WhitelistEntry {
+    /// Hyphenated Minecraft UUID
+    pub uuid: String,
+    pub name: String,
+    pub note: Option<String>,
+    /// Admin who added the player
+    pub added_by: String,
+    pub added_at: i64,
+}
+
+pub async fn init_schema(conn: &Connection) -> Result<()> {
+    conn.call(|conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS whitelist (
+                uuid TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                note TEXT,
+                added_by TEXT NOT NULL,
+                added_at INTEGER NOT NULL
+            );"
+        )
+    }).await?;
+    Ok(())
+}
+
+/// Add a player, or update the name and note of one already listed
+pub async fn upsert(
+    conn: &Connection,
+    uuid: String,
+    name: String,
+    note: Option<String>,
+    added_by: String,
+) -> Result<WhitelistEntry> {
+    let now = chrono::Utc::now().timestamp();
+
+    let entry = conn.call(move |conn| {
+        conn.execute(
+            "INSERT INTO whitelist (uuid, name, note, added_by, added_at) VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(uuid) DO UPDATE SET name = excluded.name, note = excluded.note",
+            rusqlite::params![uuid, name, note, added_by, now],
+        )?;
+
+        conn.query_row(
+            &format!("SELECT {} FROM whitelist WHERE uuid = ?1", COLUMNS),
+            [&uuid],
+            entry_from_row,
+        )
+    }).await?;
+
+    Ok(entry)
+}
+
+/// Returns false if the player wasn't listed
+pub async fn remove(conn: &Connection, uuid: String) -> Result<bool> {
+    let removed = conn.call(move |conn| {
+        conn.execute("DELETE FROM whitelist WHERE uuid = ?1", [&uuid])
+    }).await?;
+    Ok(removed > 0)
+}
+
+/// The entry for one player, if listed
+pub async fn get(conn: &Connection, uuid: String) -> Result<Option<WhitelistEntry>> {
+    let entry = conn.call(move |conn| {
+        conn.query_row(
+            &format!("SELECT {} FROM whitelist WHERE uuid = ?1", COLUMNS),
+            [&uuid],
+            entry_from_row,
+        )
+        .optional()
+    }).await?;
+    Ok(entry)
+}
+
+/// All listed players, by name
+pub async fn list(conn: &Connection) -> Result<Vec<WhitelistEntry>> {
+    let entries = conn.call(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM whitelist ORDER BY name COLLATE NOCASE, uuid",
+            COLUMNS
+        ))?;
+        let entries = stmt
+            .query_map([], entry_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok::<_, rusqlite::Error>(entries)
+    }).await?;
+    Ok(entries)
+}
+
+const COLUMNS: &str = "uuid, name, note, added_by, added_at";
+
+fn entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<WhitelistEntry> {
+    Ok(WhitelistEntry {
+        uuid: row.get(0)?,
+        name: row.get(1)?,
+        note: row.get(2)?,
+        added_by: row.get(3)?,
+        added_at: row.get(4)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_whitelist_round_trip() {
+        let dir = tempdir().unwrap();
+        let conn = Connection::open(dir.path().join("test.db")).await.unwrap();
+        init_schema(&conn).await.unwrap();
+
+        let uuid = "069a79f4-44e9-4726-a5be-fca90e38aaf5".to_string();
+        upsert(&conn, uuid.clone(), "Notch".to_string(), None, "admin".to_string()).await.unwrap();
+        upsert(&conn, "853c80ef-3c37-49fd-aa49-938b674adae6".to_string(), "jeb_".to_string(), None, "admin".to_string())
+            .await
+            .unwrap();
+
+        // Re-adding updates the name and note but keeps who added the player
+        let entry = upsert(&conn, uuid.clone(), "Notch2".to_string(), Some("renamed".to_string()), "other".to_string())
+            .await
+            .unwrap();
+        assert_eq!(entry.name, "Notch2");
+        assert_eq!(entry.added_by, "admin");
+
+        let names: Vec<_> = list(&conn).await.unwrap().into_iter().map(|e| e.name).collect();
+        assert_eq!(names, vec!["jeb_", "Notch2"]);
+
+        assert_eq!(get(&conn, uuid.clone()).await.unwrap().unwrap().name, "Notch2");
+        assert!(remove(&conn, uuid.clone()).await.unwrap());
+        assert!(get(&conn, uuid.clone()).await.unwrap().is_none());
+        assert!(!remove(&conn, uuid).await.unwrap());
+        assert_eq!(list(&conn).await.unwrap().len(), 1);
+    }
+}
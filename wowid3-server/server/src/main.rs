@@ -2,6 +2,7 @@ mod api;
 mod cache;
 mod cli;
 mod config;
+mod daemon;
 mod database;
 mod middleware;
 mod models;
@@ -12,173 +13,489 @@ mod utils;
 mod vpn;
 
 use api::admin::{
-    clear_cache, clear_jar_cache, clear_manifest_cache, copy_release_to_draft, create_release,
-    delete_release, delete_resource, get_blacklist, get_cache_stats, list_releases, login,
-    update_blacklist, upload_files, upload_resource, upload_launcher_release,
+    add_release_tags, clear_cache, clear_jar_cache, clear_manifest_cache, copy_release_to_draft,
+    create_release, delete_release, delete_resource, get_blacklist, get_cache_stats,
+    get_release_tags, list_releases, login, logout, promote_release, refresh_token, remove_release_tag, update_blacklist, preview_blacklist, get_blacklist_history, search_releases, preview_retention, upload_files, upload_resource, upload_launcher_release,
     upload_launcher_version_file, delete_launcher_version, create_launcher_release,
-    list_launcher_releases, AdminState as AdminApiState,
+    list_launcher_releases, list_uploads, create_upload_session, get_upload_session, upload_session_chunk,
+    finalize_upload_session, delete_upload_session, get_compatibility_list, update_compatibility_list,
+    get_cms_config_admin, update_cms_config, upload_cms_assets, list_cms_assets, delete_cms_asset,
+    get_audio_playlist_admin, update_audio_playlist,
+    AdminState as AdminApiState,
 };
+use api::admin_users::{create_admin_user, delete_admin_user, list_admin_users, update_admin_user};
+use api::audit::list_audit_log;
 use api::bluemap::{
     get_global_settings, get_live_markers, get_live_players, get_map_asset, get_map_settings,
-    get_map_textures, get_map_textures_gz, get_map_tile, serve_webapp_file, BlueMapState,
+    get_map_textures, get_map_textures_gz, get_map_tile, get_tile_cache_stats, serve_webapp_file, BlueMapState,
+    BLUEMAP_BASE_PATH,
 };
+use api::crashes::{get_crash_report, get_crash_signatures, list_crash_reports, submit_crash_report, MAX_CRASH_REQUEST_BYTES};
+use api::support::{get_support_report, list_support_reports, submit_support_report, MAX_SUPPORT_REQUEST_BYTES};
+use api::curseforge::{add_curseforge_mod, import_curseforge_pack};
+use api::download_tokens::issue_download_token;
 use api::drafts::{
     add_files, analyze_draft, browse_directory, create_directory, create_draft, delete_draft,
-    duplicate_draft, generate_changelog_for_draft, get_draft, list_drafts, move_file,
+    diff_draft, duplicate_draft, generate_changelog_for_draft, get_draft, list_drafts, move_file,
     publish_draft, read_file_content, remove_file, rename_file, update_draft, update_file,
-    write_file_content,
+    validate_draft, write_file_content,
 };
+use api::events::server_events;
+use api::health::{health_live, health_ready, HealthState};
+use api::jobs::get_job;
+use api::modrinth::{add_modrinth_mod, search_modrinth_mods};
 use api::public::{
-    get_latest_manifest, get_manifest_by_version, list_resources, serve_audio_file, serve_file,
+    get_latest_manifest, get_manifest_by_version, list_public_releases, list_resources, serve_audio_file, serve_file,
     serve_java_runtime, serve_resource, serve_launcher_file,
     serve_versioned_launcher_file, get_launcher_versions, get_launcher_version,
     get_latest_launcher_redirect, get_launcher_installer, get_launcher_installer_platform,
     get_launcher_executable, get_launcher_executable_platform,
-    get_launcher_manifest_latest, get_launcher_manifest_version, PublicState,
+    get_launcher_manifest_latest, get_launcher_manifest_version, get_mod_compatibility, get_cms_config, serve_cms_asset, get_audio_playlist, PublicState,
 };
-use api::tracker::{get_tracker_status, submit_chat_message, update_tracker_state, submit_stat_events, get_player_stats};
+use api::resourcepacks::{
+    delete_resource_pack, get_resource_pack_index, list_resource_packs, serve_latest_resource_pack,
+    serve_resource_pack, upload_resource_pack,
+};
+use api::screenshots::{list_player_screenshots, serve_screenshot, upload_screenshot, MAX_SCREENSHOT_BYTES};
+use api::tasks::{get_task, task_socket};
+use api::telemetry::{get_update_stats, submit_update_report};
+use api::tracker::{get_tracker_status, submit_chat_message, send_chat_message, take_pending_chat, update_tracker_state, submit_stat_events, get_player_stats, get_player_sessions, get_playtime_leaderboard, get_stat_leaderboard, get_player_advancements, get_advancement_completions};
+use api::usage::{get_file_stats, get_stats_overview};
+use api::whitelist::{add_to_whitelist, get_tracker_whitelist, list_whitelist, remove_from_whitelist};
 use axum::{
     extract::DefaultBodyLimit,
     middleware as axum_middleware,
-    response::Json,
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
     Router,
 };
 use clap::Parser;
 use cli::Cli;
-use config::Config;
+use config::{Config, LiveSettings, LogFormat};
+use daemon::{PidFile, StartupStage};
+use anyhow::Context;
 use database::Database;
-use middleware::auth::auth_middleware;
+use middleware::audit::audit_middleware;
+use middleware::auth::{auth_middleware, AuthState};
+use middleware::download_auth::{download_auth_middleware, DownloadAuth};
+use middleware::rate_limit::{rate_limit_middleware, RateLimiter};
+use middleware::request_id::{request_span, sanitize_request_id, REQUEST_ID_HEADER};
+use middleware::tracker_auth::{tracker_auth_middleware, TrackerAuth};
+use middleware::usage::{usage_middleware, UsageCounters};
 use models::tracker::TrackerState;
+use services::download_tokens::DownloadTokens;
+use services::jwt::JwtManager;
+use services::jobs::JobQueue;
+use services::minecraft_auth::ProfileVerifier;
+use services::notifications::Notifier;
+use services::signing::Signer;
 use services::stats_processor::StatsProcessor;
-use serde_json::json;
+use services::tasks::TaskRegistry;
+use std::future::IntoFuture;
+use std::net::SocketAddr;
+use std::process::ExitCode;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tower_http::cors::CorsLayer;
-use tracing::info;
+use tower_http::{
+    compression::CompressionLayer,
+    cors::{AllowOrigin, CorsLayer},
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::TraceLayer,
+};
+use tracing::{error, info, warn};
 
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "info".into()),
-        )
-        .init();
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            // One structured line a supervisor or log pipeline can act on
+            let stage = daemon::startup_stage(&e);
+            let exit_code = stage.map_or(1, StartupStage::exit_code);
+            error!(
+                stage = stage.map(StartupStage::as_str),
+                exit_code,
+                error = format!("{:#}", e),
+                "wowid3-server exited with an error"
+            );
+            ExitCode::from(exit_code)
+        }
+    }
+}
 
-    // Parse CLI arguments
-    let cli = Cli::parse();
+fn init_tracing(format: LogFormat) {
+    let subscriber = tracing_subscriber::fmt().with_env_filter(
+        tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()),
+    );
+    match format {
+        LogFormat::Text => subscriber.init(),
+        LogFormat::Json => subscriber.json().with_current_span(true).with_span_list(false).init(),
+    }
+}
 
+async fn run(cli: Cli) -> anyhow::Result<()> {
     // Load configuration
-    let config = Config::from_env()?;
+    let config = match Config::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            // LOG_FORMAT may be what failed to parse, so only honour an exact "json"
+            let json = std::env::var("LOG_FORMAT").is_ok_and(|format| format.eq_ignore_ascii_case("json"));
+            init_tracing(if json { LogFormat::Json } else { LogFormat::Text });
+            return Err(e.context(StartupStage::Config));
+        }
+    };
+
+    // Initialize tracing
+    init_tracing(config.log_format);
 
     // Check if a CLI command was provided
     if cli.command.is_some() {
         // Run CLI command and exit
-        cli::run_cli(cli, config).await?;
-        return Ok(());
+        return cli::run_cli(cli, config).await;
     }
 
+    // Refuse to start next to a running instance; removed again on exit
+    let pid_path = cli
+        .pid_file
+        .or_else(|| cli.daemon.then(|| config.storage_path().join("wowid3-server.pid")));
+    let _pid_file = pid_path
+        .map(PidFile::acquire)
+        .transpose()
+        .context(StartupStage::PidFile)?;
+
     // No CLI command, start the web server
     info!("Loaded configuration");
     info!("Storage path: {:?}", config.storage_path());
     info!("API listening on {}:{}", config.api_host, config.api_port);
 
     // Create storage directories
-    tokio::fs::create_dir_all(config.releases_path()).await?;
-    tokio::fs::create_dir_all(config.uploads_path()).await?;
-    tokio::fs::create_dir_all(config.resources_path()).await?;
-    tokio::fs::create_dir_all(config.launcher_path()).await?;
-    tokio::fs::create_dir_all(config.storage_path().join("drafts")).await?;
-    tokio::fs::create_dir_all(config.storage_path().join("assets")).await?;
+    for dir in [
+        config.releases_path(),
+        config.uploads_path(),
+        config.resources_path(),
+        config.cms_assets_path(),
+        config.resourcepacks_path(),
+        config.launcher_path(),
+        config.storage_path().join("drafts"),
+        config.storage_path().join("assets"),
+    ] {
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to create {}", dir.display()))
+            .context(StartupStage::Storage)?;
+    }
     info!("Storage directories initialized");
 
+    // Tidy up after a crash before anything can publish again
+    let recovery = storage::publish::recover(&config).await.context(StartupStage::Storage)?;
+    if recovery.staging_cleared > 0 || recovery.temp_files_removed > 0 {
+        info!(
+            "Removed {} half-built releases and {} temp files left by the previous run",
+            recovery.staging_cleared, recovery.temp_files_removed
+        );
+    }
+    for version in &recovery.quarantined {
+        warn!("Release {} has no manifest; moved it to {}", version, config.quarantine_path().display());
+    }
+    for journal in &recovery.unfinished {
+        warn!(
+            "Publishing {} from draft {} was interrupted; publish the draft again to finish",
+            journal.version, journal.draft_id
+        );
+    }
+
     // Initialize database connection pool
     let db_path = config.storage_path().join("stats.db");
-    let db = Database::new(&db_path).await?;
-    db.init_schema().await?;
+    let db = Database::new(&db_path).await.context(StartupStage::Database)?;
+    db.init_schema().await.context(StartupStage::Database)?;
     info!("Database initialized at {:?}", db_path);
 
+    // Sessions left open by the previous run can't be diffed against the empty
+    // tracker state; players still online get a new one on the next update
+    let closed = database::sessions::close_open_sessions(&db.conn).await?;
+    if closed > 0 {
+        info!("Closed {} player sessions left open by the previous run", closed);
+    }
+
     let config_arc = Arc::new(config.clone());
 
+    // Base URL, admin password, CORS origin and cache limits; SIGHUP re-reads them
+    let settings = LiveSettings::new(&config);
+
     // Initialize cache manager
-    let cache_manager = cache::CacheManager::new();
-    info!("Cache manager initialized");
+    let cache_manager = cache::CacheManager::with_hot_file_limits(
+        config.hot_file_cache_mb * 1024 * 1024,
+        config.hot_file_max_kb * 1024,
+    )
+    .with_manifest_limits(
+        config.manifest_cache_size,
+        Some(std::time::Duration::from_secs(config.manifest_cache_ttl_secs)),
+    );
+    info!(
+        "Cache manager initialized ({} manifests, {}s TTL)",
+        config.manifest_cache_size, config.manifest_cache_ttl_secs
+    );
+
+    #[cfg(unix)]
+    services::reload::ConfigReloader::new(settings.clone(), cache_manager.clone()).spawn_on_sighup()?;
 
     // Initialize tracker state
     let tracker_state = Arc::new(RwLock::new(TrackerState::default()));
     info!("Tracker state initialized");
 
+    // Player stats and VPN peers (local SQLite or shared Postgres)
+    let stores = database::store::from_config(&config, &db).await.context(StartupStage::Database)?;
+    info!("Storing player stats and VPN peers in {}", stores.name());
+
     // Initialize stats processor
-    let stats_processor = Arc::new(StatsProcessor::new(db.clone()));
+    let stats_processor = Arc::new(StatsProcessor::new(stores.stats.clone()));
     info!("Stats processor initialized");
 
+    // Release file storage (local disk or object storage)
+    let storage_backend = storage::backend::from_config(&config)?;
+    info!("Serving release files from {} storage", storage_backend.name());
+
+    // Download, bandwidth and install counters for the admin dashboard
+    let usage_counters = UsageCounters::new();
+    usage_counters.spawn_flush_task(db.clone());
+
     // Create shared state for public API
+    let manifest_signer =
+        Signer::from_setting(config.manifest_signing_key.as_deref(), "MANIFEST_SIGNING_KEY")?;
+    match &manifest_signer {
+        Some(signer) => info!("Signing manifests (public key {})", signer.public_key()),
+        None => warn!("MANIFEST_SIGNING_KEY not set; manifests will be served unsigned"),
+    }
+    let download_tokens = Arc::new(DownloadTokens::from_config(&config));
+    if config.download_auth {
+        info!("DOWNLOAD_AUTH enabled; manifests and release files require a download token");
+    }
     let public_state = PublicState {
         config: config_arc.clone(),
+        settings: settings.clone(),
         cache: cache_manager.clone(),
         tracker: tracker_state.clone(),
         db: db.clone(),
         stats_processor: stats_processor.clone(),
+        stats: stores.stats.clone(),
+        storage: storage_backend.clone(),
+        profile_verifier: ProfileVerifier::new(config.minecraft_profile_url.clone()),
+        download_tokens: download_tokens.clone(),
+        manifest_signer: manifest_signer.map(Arc::new),
     };
 
     // Create shared state for admin API
-    let admin_password = std::env::var("ADMIN_PASSWORD").unwrap_or_else(|_| "changeme".to_string());
+    let jwt_manager = Arc::new(JwtManager::from_config(&config));
+    let task_registry = TaskRegistry::new();
+    let job_queue = JobQueue::start(db.clone(), task_registry.clone()).await?;
+    let launcher_signer =
+        Signer::from_setting(config.launcher_signing_key.as_deref(), "LAUNCHER_SIGNING_KEY")?.map(Arc::new);
+    match &launcher_signer {
+        Some(signer) => info!("Signing launcher uploads (public key {})", signer.public_key()),
+        None => warn!("LAUNCHER_SIGNING_KEY not set; launcher uploads will be unsigned"),
+    }
+    if config.release_retention_keep > 0 {
+        services::retention::ReleasePruner::new(
+            config_arc.clone(),
+            cache_manager.clone(),
+            storage_backend.clone(),
+            std::time::Duration::from_secs(config.release_retention_interval_secs.max(60)),
+        )
+        .spawn();
+        info!("Release retention enabled: keeping the newest {} releases", config.release_retention_keep);
+    }
+    if config.upload_retention_hours > 0 {
+        storage::uploads::UploadJanitor::new(
+            config_arc.clone(),
+            std::time::Duration::from_secs(config.upload_gc_interval_secs.max(60)),
+        )
+        .spawn();
+    }
+
     let admin_state = AdminApiState {
         config: config_arc.clone(),
-        admin_password: Arc::new(admin_password),
+        settings: settings.clone(),
         cache: cache_manager.clone(),
+        jwt: jwt_manager.clone(),
+        db: db.clone(),
+        tasks: task_registry,
+        jobs: job_queue,
+        storage: storage_backend,
+        tracker: tracker_state.clone(),
+        notifier: Notifier::from_config(&config, settings.clone()),
+        signer: launcher_signer,
+    };
+
+    // Shared state for the admin auth middleware
+    let auth_state = AuthState {
+        jwt: jwt_manager,
+        db: db.clone(),
     };
 
     // Create shared state for BlueMap API
-    let bluemap_state = Arc::new(BlueMapState::new());
+    let tile_cache = if config.bluemap_tile_cache_mb > 0 {
+        let tiles = Arc::new(
+            cache::tiles::TileCache::open(
+                config.bluemap_tile_cache_path(),
+                config.bluemap_tile_cache_mb * 1024 * 1024,
+                std::time::Duration::from_secs(config.bluemap_tile_ttl_secs),
+            )
+            .await?,
+        );
+        if config.bluemap_prefetch_interval_secs > 0 {
+            tiles.clone().spawn_prefetch(
+                BLUEMAP_BASE_PATH.into(),
+                std::time::Duration::from_secs(config.bluemap_prefetch_interval_secs),
+                config.bluemap_prefetch_min_lod,
+            );
+        }
+        Some(tiles)
+    } else {
+        None
+    };
+    let bluemap_state = Arc::new(BlueMapState::new(tile_cache));
     info!("BlueMap state initialized");
 
     // Create shared state for VPN API
-    let ip_allocator = Arc::new(vpn::IpAllocator::new(db.conn.clone()));
+    let ip_allocator = Arc::new(vpn::IpAllocator::new(stores.vpn_peers.clone()));
     let vpn_state = vpn::api::VpnState {
-        db: db.clone(),
+        peers: stores.vpn_peers.clone(),
         ip_allocator,
+        revoke_after_days: config.vpn_revoke_after_days,
     };
+    vpn::VpnMonitor::new(
+        stores.vpn_peers.clone(),
+        std::time::Duration::from_secs(config.vpn_monitor_interval_secs.max(1)),
+        config.vpn_revoke_after_days,
+    )
+    .spawn();
     info!("VPN state initialized");
 
-    // Build CORS layer
-    let cors = if let Some(origin) = &config.cors_origin {
-        CorsLayer::permissive() // Dev mode
-            .allow_origin(origin.parse::<http::HeaderValue>().unwrap())
-    } else {
-        CorsLayer::permissive() // Production
+    // Readiness checks for the load balancer and deploy script
+    let health_state = HealthState {
+        storage_path: config.storage_path.clone(),
+        db: db.clone(),
+        external_stats: stores.is_external().then(|| stores.stats.clone()),
+        bluemap_path: config.bluemap_health_check.then(|| BLUEMAP_BASE_PATH.into()),
     };
 
+    // Build CORS layer; CORS_ORIGIN restricts it to one origin and follows reloads
+    let cors = CorsLayer::permissive().allow_origin(AllowOrigin::predicate({
+        let settings = settings.clone();
+        move |origin, _| match &settings.get().cors_origin {
+            Some(allowed) => origin.as_bytes() == allowed.as_bytes(),
+            None => true,
+        }
+    }));
+
+    // Per-IP rate limits for unauthenticated route groups
+    let rate_limited = |router: Router, name: &'static str, per_minute: u32| {
+        if per_minute == 0 {
+            return router;
+        }
+        let limiter = RateLimiter::per_minute(name, per_minute, config.trust_proxy_headers);
+        router.layer(axum_middleware::from_fn_with_state(limiter, rate_limit_middleware))
+    };
+
+    // Manifests and release files of a private modpack need a download token
+    let download_auth = axum_middleware::from_fn_with_state(
+        DownloadAuth {
+            tokens: download_tokens,
+            db: db.clone(),
+            required: config.download_auth,
+        },
+        download_auth_middleware,
+    );
+
     // Build public API router
-    let public_routes = Router::new()
-        .route("/api/manifest/latest", get(get_latest_manifest))
-        .route("/api/manifest/:version", get(get_manifest_by_version))
-        // Launcher endpoints
-        .route("/api/launcher/latest", get(get_latest_launcher_redirect))
+    let download_routes = Router::new()
         .route("/api/launcher/latest/installer", get(get_launcher_installer))
         .route("/api/launcher/latest/installer/:platform", get(get_launcher_installer_platform))
         .route("/api/launcher/latest/executable", get(get_launcher_executable))
         .route("/api/launcher/latest/executable/:platform", get(get_launcher_executable_platform))
-        .route("/api/launcher/manifest/latest", get(get_launcher_manifest_latest))
-        .route("/api/launcher/manifest/:version", get(get_launcher_manifest_version))
-        .route("/api/launcher/versions", get(get_launcher_versions))
-        .route("/api/launcher/:version", get(get_launcher_version))
+        .route("/api/assets/playlist.json", get(get_audio_playlist))
         .route("/api/assets/:filename", get(serve_audio_file))
         .route("/api/java/:filename", get(serve_java_runtime))
-        .route("/api/resources", get(list_resources))
         .route("/api/resources/:filename", get(serve_resource))
-        .route("/files/:version/*path", get(serve_file))
+        .route("/api/assets-cms/:filename", get(serve_cms_asset))
+        .route("/api/resourcepacks/latest", get(serve_latest_resource_pack))
+        .route("/api/resourcepacks/:version", get(serve_resource_pack))
+        .route("/files/:version/*path", get(serve_file).route_layer(download_auth.clone()))
         .route("/files/launcher/:filename", get(serve_launcher_file))
         .route("/files/launcher/versions/:version/:filename", get(serve_versioned_launcher_file))
-        // Tracker routes
+        .with_state(public_state.clone());
+
+    // Routes called by the tracker mod; launchers use chat/send and status
+    let tracker_routes = Router::new()
         .route("/api/tracker/update", post(update_tracker_state))
         .route("/api/tracker/chat", post(submit_chat_message))
-        .route("/api/tracker/status", get(get_tracker_status))
+        .route("/api/tracker/chat/pending", get(take_pending_chat))
         .route("/api/tracker/stats-events", post(submit_stat_events))
+        .route("/api/tracker/whitelist", get(get_tracker_whitelist))
+        .layer(axum_middleware::from_fn_with_state(
+            TrackerAuth::from_config(&config),
+            tracker_auth_middleware,
+        ))
+        .route("/api/tracker/chat/send", post(send_chat_message))
+        .route("/api/tracker/status", get(get_tracker_status))
+        .with_state(public_state.clone());
+
+    let crash_routes = Router::new()
+        .route("/api/crashes", post(submit_crash_report))
+        .layer(DefaultBodyLimit::max(MAX_CRASH_REQUEST_BYTES))
+        .with_state(public_state.clone());
+
+    let support_routes = Router::new()
+        .route("/api/support/reports", post(submit_support_report))
+        .layer(DefaultBodyLimit::max(MAX_SUPPORT_REQUEST_BYTES))
+        .with_state(public_state.clone());
+
+    let telemetry_routes = Router::new()
+        .route("/api/telemetry/update", post(submit_update_report))
+        .with_state(public_state.clone());
+
+    let download_token_routes = Router::new()
+        .route("/api/auth/token", post(issue_download_token))
+        .with_state(public_state.clone());
+
+    let screenshot_upload_routes = Router::new()
+        .route("/api/screenshots/:uuid", post(upload_screenshot))
+        .layer(DefaultBodyLimit::max(MAX_SCREENSHOT_BYTES))
+        .with_state(public_state.clone());
+
+    let public_routes = Router::new()
+        .route("/api/manifest/latest", get(get_latest_manifest).route_layer(download_auth.clone()))
+        .route("/api/manifest/:version", get(get_manifest_by_version).route_layer(download_auth))
+        .route("/api/releases", get(list_public_releases))
+        .route("/api/events", get(server_events))
+        // Launcher endpoints
+        .route("/api/launcher/latest", get(get_latest_launcher_redirect))
+        .route("/api/launcher/manifest/latest", get(get_launcher_manifest_latest))
+        .route("/api/launcher/manifest/:version", get(get_launcher_manifest_version))
+        .route("/api/launcher/versions", get(get_launcher_versions))
+        .route("/api/mods/compatibility", get(get_mod_compatibility))
+        .route("/api/cms-config", get(get_cms_config))
+        .route("/api/launcher/:version", get(get_launcher_version))
+        .route("/api/resources", get(list_resources))
+        .route("/api/resourcepacks.json", get(get_resource_pack_index))
+        .route("/api/stats/leaderboard/playtime", get(get_playtime_leaderboard))
+        .route("/api/stats/leaderboards/:metric", get(get_stat_leaderboard))
         .route("/api/stats/:uuid", get(get_player_stats))
-        .with_state(public_state);
+        .route("/api/stats/:uuid/sessions", get(get_player_sessions))
+        .route("/api/stats/:uuid/advancements", get(get_player_advancements))
+        .route("/api/stats/advancements", get(get_advancement_completions))
+        .route("/api/screenshots/:uuid", get(list_player_screenshots))
+        .route("/api/screenshots/:uuid/:filename", get(serve_screenshot))
+        // JSON responses only; release files and other downloads are served as-is
+        .layer(CompressionLayer::new())
+        .with_state(public_state)
+        .merge(rate_limited(download_routes, "downloads", config.rate_limit_downloads_per_minute))
+        .merge(rate_limited(tracker_routes, "tracker", config.rate_limit_tracker_per_minute))
+        .merge(rate_limited(crash_routes, "crashes", config.rate_limit_crashes_per_minute))
+        .merge(rate_limited(support_routes, "support", config.rate_limit_support_per_minute))
+        .merge(rate_limited(screenshot_upload_routes, "screenshots", config.rate_limit_screenshots_per_minute))
+        .merge(rate_limited(telemetry_routes, "telemetry", config.rate_limit_telemetry_per_minute))
+        .merge(rate_limited(download_token_routes, "download-tokens", config.rate_limit_download_tokens_per_minute))
+        .layer(axum_middleware::from_fn_with_state(usage_counters, usage_middleware));
 
     // Build BlueMap maps router (shared by both paths)
     let bluemap_maps_routes = Router::new()
@@ -198,31 +515,79 @@ async fn main() -> anyhow::Result<()> {
         .route("/api/bluemap/webapp/*path", get(serve_webapp_file))
         .nest("/api/bluemap", bluemap_maps_routes.clone())
         .nest("/api/bluemap/webapp", bluemap_maps_routes)
-        .with_state(bluemap_state);
+        .layer(CompressionLayer::new())
+        .with_state(bluemap_state.clone());
 
     // Admin login route (no auth required)
-    let admin_login = Router::new()
-        .route("/api/admin/login", post(login))
+    let admin_login = rate_limited(
+        Router::new()
+            .route("/api/admin/login", post(login))
+            .route("/api/admin/refresh", post(refresh_token))
+            .with_state(admin_state.clone()),
+        "login",
+        config.rate_limit_login_per_minute,
+    );
+
+    // Task progress sockets authenticate themselves (browsers can't send auth headers)
+    let admin_sockets = Router::new()
+        .route("/ws/admin/tasks/:id", get(task_socket))
         .with_state(admin_state.clone());
 
     // Build admin API router (with auth middleware)
     let admin_routes = Router::new()
+        .route("/api/admin/logout", post(logout))
+        .route("/api/admin/audit", get(list_audit_log))
+        .route("/api/admin/stats/overview", get(get_stats_overview))
+        .route("/api/admin/stats/files", get(get_file_stats))
+        .route("/api/admin/telemetry/updates", get(get_update_stats))
+        .route("/api/admin/crashes", get(list_crash_reports))
+        .route("/api/admin/crashes/signatures", get(get_crash_signatures))
+        .route("/api/admin/crashes/:id", get(get_crash_report))
+        .route("/api/admin/support/reports", get(list_support_reports))
+        .route("/api/admin/support/reports/:id", get(get_support_report))
+        .route("/api/admin/tasks/:id", get(get_task))
+        .route("/api/admin/jobs/:id", get(get_job))
+        .route("/api/admin/users", get(list_admin_users).post(create_admin_user))
+        .route("/api/admin/users/:id", put(update_admin_user).delete(delete_admin_user))
         .route("/api/admin/upload", post(upload_files))
+        .route("/api/admin/uploads", get(list_uploads).post(create_upload_session))
+        .route(
+            "/api/admin/uploads/:id",
+            get(get_upload_session).patch(upload_session_chunk).delete(delete_upload_session),
+        )
+        .route("/api/admin/uploads/:id/finalize", post(finalize_upload_session))
         .route("/api/admin/launcher", post(upload_launcher_release))
         .route("/api/admin/launcher/releases", post(create_launcher_release).get(list_launcher_releases))
         .route("/api/admin/launcher/version", post(upload_launcher_version_file))
         .route("/api/admin/launcher/:version", delete(delete_launcher_version))
         .route("/api/admin/resources", post(upload_resource))
         .route("/api/admin/resources/:filename", delete(delete_resource))
+        .route("/api/admin/resourcepacks", get(list_resource_packs).post(upload_resource_pack))
+        .route("/api/admin/resourcepacks/:version", delete(delete_resource_pack))
         .route("/api/admin/releases", post(create_release).get(list_releases))
         .route("/api/admin/releases/:version/copy-to-draft", post(copy_release_to_draft))
         .route("/api/admin/releases/:version", delete(delete_release))
+        .route("/api/admin/releases/:version/promote", post(promote_release))
+        .route("/api/admin/releases/:version/tags", get(get_release_tags).post(add_release_tags))
+        .route("/api/admin/releases/:version/tags/:tag", delete(remove_release_tag))
         .route("/api/admin/blacklist", get(get_blacklist).put(update_blacklist))
+        .route("/api/admin/blacklist/preview", post(preview_blacklist))
+        .route("/api/admin/blacklist/history", get(get_blacklist_history))
+        .route("/api/admin/search", get(search_releases))
+        .route("/api/admin/retention", get(preview_retention))
+        .route("/api/admin/mods/compatibility", get(get_compatibility_list).put(update_compatibility_list))
+        .route("/api/admin/cms-config", get(get_cms_config_admin).put(update_cms_config))
+        .route("/api/admin/assets", get(list_cms_assets).post(upload_cms_assets))
+        .route("/api/admin/assets/playlist", get(get_audio_playlist_admin).put(update_audio_playlist))
+        .route("/api/admin/assets/:filename", delete(delete_cms_asset))
+        .route("/api/admin/whitelist", get(list_whitelist).post(add_to_whitelist))
+        .route("/api/admin/whitelist/:uuid", delete(remove_from_whitelist))
         // Cache management routes
         .route("/api/admin/cache/stats", get(get_cache_stats))
         .route("/api/admin/cache/clear", post(clear_cache))
         .route("/api/admin/cache/clear/manifests", post(clear_manifest_cache))
         .route("/api/admin/cache/clear/jar", post(clear_jar_cache))
+        .route("/api/admin/bluemap/cache", get(get_tile_cache_stats).with_state(bluemap_state.clone()))
         // Draft management routes
         .route("/api/admin/drafts", post(create_draft).get(list_drafts))
         .route("/api/admin/drafts/:id", get(get_draft).put(update_draft).delete(delete_draft))
@@ -230,8 +595,13 @@ async fn main() -> anyhow::Result<()> {
         .route("/api/admin/drafts/:id/files", post(add_files))
         .route("/api/admin/drafts/:id/files/*path", delete(remove_file).put(update_file))
         .route("/api/admin/drafts/:id/generate-changelog", post(generate_changelog_for_draft))
+        .route("/api/admin/drafts/:id/validate", post(validate_draft))
+        .route("/api/admin/drafts/:id/diff/:version", get(diff_draft))
         .route("/api/admin/drafts/:id/publish", post(publish_draft))
         .route("/api/admin/drafts/:id/duplicate", post(duplicate_draft))
+        .route("/api/admin/drafts/:id/mods/modrinth", get(search_modrinth_mods).post(add_modrinth_mod))
+        .route("/api/admin/drafts/:id/mods/curseforge", post(add_curseforge_mod))
+        .route("/api/admin/drafts/:id/mods/curseforge/import", post(import_curseforge_pack))
         // File browser routes
         .route("/api/admin/drafts/:id/browse", get(browse_directory))
         .route("/api/admin/drafts/:id/read-file", get(read_file_content))
@@ -239,20 +609,31 @@ async fn main() -> anyhow::Result<()> {
         .route("/api/admin/drafts/:id/create-dir", post(create_directory))
         .route("/api/admin/drafts/:id/rename", post(rename_file))
         .route("/api/admin/drafts/:id/move", post(move_file))
-        .layer(axum_middleware::from_fn(auth_middleware))
+        // Audit runs inside auth so the authenticated token is available
+        .layer(axum_middleware::from_fn_with_state(auth_state.db.clone(), audit_middleware))
+        .layer(axum_middleware::from_fn_with_state(auth_state.clone(), auth_middleware))
+        .layer(CompressionLayer::new())
         .with_state(admin_state);
 
     // Build main router
     let app = Router::new()
-        .route("/health", get(health_check))
+        .route("/health", get(health_live))
+        .route("/health/live", get(health_live))
+        .route("/health/ready", get(health_ready).with_state(health_state))
         .merge(public_routes)
         .merge(bluemap_routes)
         .merge(admin_login)
+        .merge(admin_sockets)
         .merge(admin_routes)
         .merge(vpn::api::vpn_public_routes(vpn_state.clone()))
-        .merge(vpn::api::vpn_admin_routes(vpn_state))
+        .merge(vpn::api::vpn_admin_routes(vpn_state, auth_state))
         .layer(DefaultBodyLimit::max(20 * 1024 * 1024 * 1024)) // 20GB limit
-        .layer(cors);
+        .layer(cors)
+        // Outermost, so every log line of a request (CORS included) carries its id
+        .layer(TraceLayer::new_for_http().make_span_with(request_span))
+        .layer(PropagateRequestIdLayer::new(REQUEST_ID_HEADER))
+        .layer(SetRequestIdLayer::new(REQUEST_ID_HEADER, MakeRequestUuid))
+        .layer(axum_middleware::from_fn(sanitize_request_id));
 
     // Start TCP test server on port 25567
     let tcp_test_server = tcp_test_server::TcpTestServer::new(25567);
@@ -265,17 +646,32 @@ async fn main() -> anyhow::Result<()> {
 
     // Start HTTP server
     let addr = format!("{}:{}", config.api_host, config.api_port);
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("Failed to bind {}", addr))
+        .context(StartupStage::Listen)?;
     info!("HTTP server running on {}", addr);
 
-    axum::serve(listener, app).await?;
+    // Connection info provides client IPs for rate limiting. On SIGTERM or Ctrl+C new
+    // connections are refused and open ones get a grace period to finish.
+    let shutdown = Arc::new(tokio::sync::Notify::new());
+    let server = axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown({
+            let shutdown = shutdown.clone();
+            async move {
+                daemon::shutdown_signal().await;
+                shutdown.notify_one();
+            }
+        });
+    let grace = std::time::Duration::from_secs(config.shutdown_grace_secs);
+    tokio::select! {
+        result = server.into_future() => result?,
+        _ = async {
+            shutdown.notified().await;
+            tokio::time::sleep(grace).await;
+        } => warn!("Connections still open after {}s; stopping anyway", grace.as_secs()),
+    }
+    info!("Server stopped");
 
     Ok(())
 }
-
-async fn health_check() -> Json<serde_json::Value> {
-    Json(json!({
-        "status": "ok",
-        "service": "wowid3-modpack-server"
-    }))
-}
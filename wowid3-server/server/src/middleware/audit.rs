@@ -0,0 +1,58 @@
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+
+use super::AdminToken;
+use crate::database::{self, Database};
+
+/// Middleware that records every admin mutation (non-GET request) in the audit log
+///
+/// Must run inside `auth_middleware` so the `AdminToken` extension is available.
+/// Request bodies are not stored (uploads can be gigabytes); the route, path and
+/// query identify what was changed. Audit failures are logged but never fail the request.
+pub async fn audit_middleware(
+    State(db): State<Database>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    if matches!(method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return next.run(request).await;
+    }
+
+    let token = request.extensions().get::<AdminToken>().cloned();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string());
+    let path = request.uri().path().to_string();
+    let query = request.uri().query().map(|q| q.to_string());
+
+    let response = next.run(request).await;
+
+    let Some(token) = token else {
+        return response;
+    };
+
+    let status = response.status();
+    let entry = database::audit::NewAuditEntry {
+        actor: token.subject().to_string(),
+        role: token.role().as_str().to_string(),
+        token_id: token.claims.jti.clone(),
+        method: method.to_string(),
+        route: route.unwrap_or_else(|| path.clone()),
+        summary: format!("{} {} -> {}", method, path, status.as_u16()),
+        path,
+        query,
+        status: status.as_u16(),
+    };
+
+    if let Err(e) = database::audit::record(&db.conn, entry).await {
+        tracing::warn!("Failed to write audit log entry: {}", e);
+    }
+
+    response
+}
@@ -1,18 +1,28 @@
 use axum::{
-    extract::Request,
+    extract::{Request, State},
     http::{header, StatusCode},
     middleware::Next,
     response::Response,
 };
 use std::sync::Arc;
 
-#[allow(dead_code)]
-pub struct AdminState {
-    pub admin_password: Arc<String>,
+use crate::database::{self, Database};
+use crate::models::AdminRole;
+use crate::services::jwt::{Claims, JwtManager, TokenType};
+
+/// State required to validate admin tokens
+#[derive(Clone)]
+pub struct AuthState {
+    pub jwt: Arc<JwtManager>,
+    pub db: Database,
 }
 
-/// Middleware to validate Bearer token authentication
+/// Middleware to validate Bearer JWT authentication
+///
+/// Rejects tokens with a bad signature, past expiry, wrong type, or a revoked `jti`.
+/// The role in the token is replaced with the account's current role.
 pub async fn auth_middleware(
+    State(auth): State<AuthState>,
     mut request: Request,
     next: Next,
 ) -> Result<Response, (StatusCode, String)> {
@@ -22,18 +32,62 @@ pub async fn auth_middleware(
         .and_then(|h| h.to_str().ok())
         .map(|s| s.to_string());
 
-    match auth_header {
-        Some(auth) if auth.starts_with("Bearer ") => {
-            let token = auth[7..].to_string(); // Remove "Bearer " prefix
-            request.extensions_mut().insert(AdminToken(token));
-            Ok(next.run(request).await)
+    let token = match auth_header {
+        Some(auth) if auth.starts_with("Bearer ") => auth[7..].to_string(), // Remove "Bearer " prefix
+        _ => {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                "Missing or invalid Authorization header".to_string(),
+            ))
         }
-        _ => Err((
-            StatusCode::UNAUTHORIZED,
-            "Missing or invalid Authorization header".to_string(),
-        )),
+    };
+
+    let mut claims = auth
+        .jwt
+        .verify(&token, TokenType::Access)
+        .map_err(|_| (StatusCode::UNAUTHORIZED, "Invalid or expired token".to_string()))?;
+
+    let revoked = database::auth::is_token_revoked(&auth.db.conn, &claims.jti)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to check token revocation: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+        })?;
+
+    if revoked {
+        return Err((StatusCode::UNAUTHORIZED, "Token has been revoked".to_string()));
     }
+
+    claims.role = database::admin_users::current_role(&auth.db.conn, &claims.sub)
+        .await
+        .map_err(|e| match e.downcast::<database::admin_users::InactiveAccount>() {
+            Ok(inactive) => (StatusCode::UNAUTHORIZED, inactive.to_string()),
+            Err(e) => {
+                tracing::error!("Failed to resolve admin role: {}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+            }
+        })?;
+
+    request.extensions_mut().insert(AdminToken { token, claims });
+    Ok(next.run(request).await)
 }
 
+/// Authenticated admin token, inserted into request extensions by `auth_middleware`
 #[derive(Clone)]
-pub struct AdminToken(#[allow(dead_code)] pub String);
+pub struct AdminToken {
+    #[allow(dead_code)]
+    pub token: String,
+    pub claims: Claims,
+}
+
+impl AdminToken {
+    /// Role granted by this token
+    pub fn role(&self) -> AdminRole {
+        self.claims.role
+    }
+
+    /// Admin identity (username) that owns this token
+    pub fn subject(&self) -> &str {
+        &self.claims.sub
+    }
+}
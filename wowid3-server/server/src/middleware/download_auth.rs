@@ -0,0 +1,89 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use percent_encoding::percent_decode_str;
+use std::sync::Arc;
+
+use crate::database::{self, Database};
+use crate::services::download_tokens::DownloadTokens;
+
+/// State for checking download tokens on manifest and release file routes
+#[derive(Clone)]
+pub struct DownloadAuth {
+    pub tokens: Arc<DownloadTokens>,
+    /// Whitelist, re-checked on every download so removed players lose access at once
+    pub db: Database,
+    /// DOWNLOAD_AUTH; when off every request passes
+    pub required: bool,
+}
+
+/// Token from `Authorization: Bearer`, or the `token` query parameter for clients
+/// that can't set headers
+fn presented_token(headers: &HeaderMap, query: Option<&str>) -> Option<String> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .map(str::to_string);
+    bearer.or_else(|| {
+        query?
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+            .map(|token| percent_decode_str(token).decode_utf8_lossy().into_owned())
+    })
+}
+
+/// Middleware restricting modpack downloads to players holding a download token
+///
+/// Tokens come from `POST /api/auth/token`, which only issues them to whitelisted players;
+/// the holder must still be on the whitelist when the token is redeemed.
+pub async fn download_auth_middleware(
+    State(auth): State<DownloadAuth>,
+    request: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, String)> {
+    if !auth.required {
+        return Ok(next.run(request).await);
+    }
+
+    let token = presented_token(request.headers(), request.uri().query()).ok_or((
+        StatusCode::UNAUTHORIZED,
+        "A download token is required; request one from /api/auth/token".to_string(),
+    ))?;
+    let claims = auth
+        .tokens
+        .verify(&token)
+        .map_err(|_| (StatusCode::UNAUTHORIZED, "Invalid or expired download token".to_string()))?;
+
+    let listed = database::whitelist::get(&auth.db.conn, claims.sub)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to read whitelist: {}", e)))?;
+    if listed.is_none() {
+        return Err((StatusCode::FORBIDDEN, format!("{} is no longer on the whitelist", claims.name)));
+    }
+
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presented_token_sources() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(presented_token(&headers, None), None);
+        assert_eq!(
+            presented_token(&headers, Some("channel=beta&token=abc.def%2Dg")),
+            Some("abc.def-g".to_string())
+        );
+        assert_eq!(presented_token(&headers, Some("channel=beta")), None);
+
+        // The header wins over the query string
+        headers.insert(header::AUTHORIZATION, "Bearer xyz".parse().unwrap());
+        assert_eq!(presented_token(&headers, Some("token=abc")), Some("xyz".to_string()));
+    }
+}
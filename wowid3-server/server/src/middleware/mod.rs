@@ -1,3 +1,9 @@
+pub mod audit;
 pub mod auth;
+pub mod download_auth;
+pub mod rate_limit;
+pub mod request_id;
+pub mod tracker_auth;
+pub mod usage;
 
 pub use auth::AdminToken;
@@ -0,0 +1,162 @@
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Buckets idle this long (and therefore full) are dropped during pruning
+const IDLE_BUCKET_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Prune idle buckets once the map grows past this many entries
+const PRUNE_THRESHOLD: usize = 10_000;
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token-bucket rate limiter keyed by client IP
+///
+/// Each route group gets its own limiter; a client may burst up to `capacity`
+/// requests and then gets `capacity` per minute.
+#[derive(Clone)]
+pub struct RateLimiter {
+    name: &'static str,
+    capacity: f64,
+    refill_per_sec: f64,
+    trust_proxy_headers: bool,
+    buckets: Arc<Mutex<HashMap<IpAddr, Bucket>>>,
+}
+
+impl RateLimiter {
+    pub fn per_minute(name: &'static str, requests: u32, trust_proxy_headers: bool) -> Self {
+        let capacity = requests.max(1) as f64;
+        Self {
+            name,
+            capacity,
+            refill_per_sec: capacity / 60.0,
+            trust_proxy_headers,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Take a token for `ip`, or return how long until one is available
+    fn check_at(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+
+        if buckets.len() > PRUNE_THRESHOLD {
+            buckets.retain(|_, b| now.duration_since(b.last_refill) < IDLE_BUCKET_TTL);
+        }
+
+        let bucket = buckets.entry(ip).or_insert(Bucket {
+            tokens: self.capacity,
+            last_refill: now,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let wait = (1.0 - bucket.tokens) / self.refill_per_sec;
+            Err(Duration::from_secs_f64(wait))
+        }
+    }
+
+    /// Resolve the client IP, honouring `X-Forwarded-For` only behind a trusted proxy
+    ///
+    /// Proxies append the address they saw, so only the rightmost entry comes from our
+    /// proxy; anything to its left was sent by the client and could be made up.
+    fn client_ip(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> Option<IpAddr> {
+        if self.trust_proxy_headers {
+            let forwarded = headers
+                .get("x-forwarded-for")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|h| h.rsplit(',').next())
+                .and_then(|ip| ip.trim().parse().ok());
+            if forwarded.is_some() {
+                return forwarded;
+            }
+        }
+        peer
+    }
+}
+
+/// Middleware rejecting clients that exceed their bucket with `429 Too Many Requests`
+pub async fn rate_limit_middleware(
+    State(limiter): State<RateLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|info| info.0.ip());
+
+    let Some(ip) = limiter.client_ip(request.headers(), peer) else {
+        // No connection info (e.g. in tests); nothing to key on
+        return next.run(request).await;
+    };
+
+    match limiter.check_at(ip, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            tracing::warn!("Rate limit ({}) exceeded by {}", limiter.name, ip);
+            let retry_secs = retry_after.as_secs().max(1);
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_secs.to_string())],
+                "Too many requests",
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_limits_and_refills() {
+        let limiter = RateLimiter::per_minute("test", 60, false);
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let other: IpAddr = "10.0.0.2".parse().unwrap();
+        let start = Instant::now();
+
+        for _ in 0..60 {
+            assert!(limiter.check_at(ip, start).is_ok());
+        }
+        let wait = limiter.check_at(ip, start).unwrap_err();
+        assert!(wait <= Duration::from_secs(1));
+
+        // Other clients have their own bucket
+        assert!(limiter.check_at(other, start).is_ok());
+
+        // One token per second at 60/min
+        assert!(limiter.check_at(ip, start + Duration::from_secs(1)).is_ok());
+        assert!(limiter.check_at(ip, start + Duration::from_secs(1)).is_err());
+    }
+
+    #[test]
+    fn test_forwarded_ip_requires_trust() {
+        let mut headers = HeaderMap::new();
+        // The client sent the first entry itself; the proxy appended the second
+        headers.insert("x-forwarded-for", "203.0.113.7, 198.51.100.4".parse().unwrap());
+        let peer: IpAddr = "127.0.0.1".parse().unwrap();
+
+        let trusted = RateLimiter::per_minute("test", 10, true);
+        assert_eq!(trusted.client_ip(&headers, Some(peer)), Some("198.51.100.4".parse().unwrap()));
+
+        let untrusted = RateLimiter::per_minute("test", 10, false);
+        assert_eq!(untrusted.client_ip(&headers, Some(peer)), Some(peer));
+    }
+}
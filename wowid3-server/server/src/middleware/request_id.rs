@@ -0,0 +1,63 @@
+use axum::http::{HeaderName, Request};
+use tracing::Span;
+
+/// Header carrying the request id, set on every request and echoed on every response
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest request id accepted from a proxy in front of the server
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+/// Whether an incoming request id is safe to log and echo back
+pub fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_' || b == b'.')
+}
+
+/// Drop a request id header that isn't safe to log, so a fresh one is assigned
+///
+/// An id set by a proxy in front of the server (e.g. nginx's `$request_id`) is
+/// kept, so its access log and ours line up.
+pub async fn sanitize_request_id(
+    mut request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let invalid = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .is_some_and(|id| !id.to_str().is_ok_and(is_valid_request_id));
+    if invalid {
+        request.headers_mut().remove(&REQUEST_ID_HEADER);
+    }
+    next.run(request).await
+}
+
+/// Span wrapping each request, so every log line inside it carries the request id
+pub fn request_span<B>(request: &Request<B>) -> Span {
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|id| id.to_str().ok())
+        .unwrap_or("-");
+
+    tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_id_validation() {
+        assert!(is_valid_request_id("0f8e2c1a-3b7d-4e59-9a61-2d4c8b7e5f30"));
+        assert!(is_valid_request_id("a1b2c3d4e5f6"));
+        assert!(!is_valid_request_id(""));
+        assert!(!is_valid_request_id("line\nbreak"));
+        assert!(!is_valid_request_id(&"a".repeat(MAX_REQUEST_ID_LEN + 1)));
+    }
+}
@@ -0,0 +1,281 @@
+use crate::config::Config;
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const TRACKER_SECRET_HEADER: &str = "x-tracker-secret";
+const TIMESTAMP_HEADER: &str = "x-tracker-timestamp";
+const SIGNATURE_HEADER: &str = "x-tracker-signature";
+
+/// Largest tracker request body buffered for signature checks (stat event batches)
+const MAX_SIGNED_BODY_BYTES: usize = 8 * 1024 * 1024;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Authentication for requests from the game server's tracker mod
+///
+/// Two schemes are accepted:
+/// - the shared secret in `X-Tracker-Secret` or `Authorization: Bearer`
+/// - an HMAC-SHA256 signature in `X-Tracker-Signature: sha256=<hex>` over
+///   `"{timestamp}\n{METHOD}\n{path?query}\n" + body`, with the Unix time in
+///   `X-Tracker-Timestamp`. The secret never leaves the game server, and each
+///   signature is only accepted once within the allowed clock skew.
+///
+/// The current secret and any previous ones are accepted, so the secret can
+/// be rotated on the server first and on the mod afterwards.
+#[derive(Clone)]
+pub struct TrackerAuth {
+    secrets: Arc<Vec<String>>,
+    require_signature: bool,
+    max_skew_secs: u64,
+    /// Recently accepted signatures and when they stop being replayable
+    seen: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum AuthFailure {
+    Missing,
+    Invalid,
+    Expired,
+    Replayed,
+}
+
+impl AuthFailure {
+    fn message(&self) -> &'static str {
+        match self {
+            AuthFailure::Missing => "Missing tracker credentials",
+            AuthFailure::Invalid => "Missing or invalid tracker secret",
+            AuthFailure::Expired => "Tracker request timestamp is outside the allowed window",
+            AuthFailure::Replayed => "Tracker request signature was already used",
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl TrackerAuth {
+    pub fn new(secrets: Vec<String>, require_signature: bool, max_skew_secs: u64) -> Self {
+        Self {
+            secrets: Arc::new(secrets.into_iter().filter(|s| !s.is_empty()).collect()),
+            require_signature,
+            max_skew_secs,
+            seen: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        let mut secrets = vec![config.tracker_secret.clone()];
+        secrets.extend(config.tracker_previous_secrets.iter().map(|s| s.trim().to_string()));
+        Self::new(secrets, config.tracker_require_signature, config.tracker_signature_max_skew_secs)
+    }
+
+    /// Check a plain shared secret from the request headers
+    fn check_secret(&self, headers: &HeaderMap) -> Result<(), AuthFailure> {
+        let presented = headers
+            .get(TRACKER_SECRET_HEADER)
+            .and_then(|h| h.to_str().ok())
+            .or_else(|| {
+                headers
+                    .get(header::AUTHORIZATION)
+                    .and_then(|h| h.to_str().ok())
+                    .and_then(|auth| auth.strip_prefix("Bearer "))
+            })
+            .ok_or(AuthFailure::Missing)?;
+
+        let matches = self.secrets.iter().any(|secret| {
+            // Compare MACs rather than the strings to keep the comparison constant-time
+            let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+            mac.update(b"tracker-secret");
+            let expected = mac.finalize().into_bytes();
+            let mut mac = HmacSha256::new_from_slice(presented.as_bytes()).expect("HMAC accepts any key length");
+            mac.update(b"tracker-secret");
+            mac.verify_slice(&expected).is_ok()
+        });
+        if matches {
+            Ok(())
+        } else {
+            Err(AuthFailure::Invalid)
+        }
+    }
+
+    /// Check an HMAC signature over the request at time `now`
+    fn check_signature(
+        &self,
+        timestamp: &str,
+        signature: &str,
+        method: &str,
+        path: &str,
+        body: &[u8],
+        now: u64,
+    ) -> Result<(), AuthFailure> {
+        let sent_at: u64 = timestamp.trim().parse().map_err(|_| AuthFailure::Invalid)?;
+        if sent_at.abs_diff(now) > self.max_skew_secs {
+            return Err(AuthFailure::Expired);
+        }
+
+        let signature = signature.trim();
+        let hex = signature.strip_prefix("sha256=").unwrap_or(signature);
+        let signature_bytes = decode_hex(hex).ok_or(AuthFailure::Invalid)?;
+
+        let valid = self.secrets.iter().any(|secret| {
+            let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+            mac.update(format!("{}\n{}\n{}\n", sent_at, method, path).as_bytes());
+            mac.update(body);
+            mac.verify_slice(&signature_bytes).is_ok()
+        });
+        if !valid {
+            return Err(AuthFailure::Invalid);
+        }
+
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        seen.retain(|_, expires| *expires >= now);
+        if seen.insert(hex.to_ascii_lowercase(), sent_at + self.max_skew_secs).is_some() {
+            return Err(AuthFailure::Replayed);
+        }
+        Ok(())
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn reject(failure: AuthFailure) -> Response {
+    tracing::warn!("Rejected tracker request: {}", failure.message());
+    (
+        StatusCode::FORBIDDEN,
+        Json(serde_json::json!({ "error": failure.message() })),
+    )
+        .into_response()
+}
+
+/// Middleware authenticating the tracker mod's routes
+pub async fn tracker_auth_middleware(
+    State(auth): State<TrackerAuth>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let headers = request.headers();
+    let signed = headers.get(SIGNATURE_HEADER).and_then(|h| h.to_str().ok()).map(str::to_string);
+    let timestamp = headers.get(TIMESTAMP_HEADER).and_then(|h| h.to_str().ok()).map(str::to_string);
+
+    let (Some(signature), Some(timestamp)) = (signed, timestamp) else {
+        if auth.require_signature {
+            return reject(AuthFailure::Missing);
+        }
+        return match auth.check_secret(request.headers()) {
+            Ok(()) => next.run(request).await,
+            Err(failure) => reject(failure),
+        };
+    };
+
+    // The body is part of the signature, so buffer it and hand a copy on
+    let (parts, body) = request.into_parts();
+    let body = match to_bytes(body, MAX_SIGNED_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(_) => return (StatusCode::PAYLOAD_TOO_LARGE, "Request body too large").into_response(),
+    };
+    let path = parts
+        .uri
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or_else(|| parts.uri.path());
+
+    if let Err(failure) =
+        auth.check_signature(&timestamp, &signature, parts.method.as_str(), path, &body, unix_now())
+    {
+        return reject(failure);
+    }
+
+    next.run(Request::from_parts(parts, Body::from(body))).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign(secret: &str, timestamp: u64, method: &str, path: &str, body: &[u8]) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("{}\n{}\n{}\n", timestamp, method, path).as_bytes());
+        mac.update(body);
+        let bytes = mac.finalize().into_bytes();
+        format!("sha256={}", bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>())
+    }
+
+    #[test]
+    fn test_shared_secret_accepts_current_and_previous() {
+        let auth = TrackerAuth::new(vec!["new".to_string(), "old".to_string()], false, 300);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(TRACKER_SECRET_HEADER, "old".parse().unwrap());
+        assert_eq!(auth.check_secret(&headers), Ok(()));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, "Bearer new".parse().unwrap());
+        assert_eq!(auth.check_secret(&headers), Ok(()));
+
+        headers.insert(header::AUTHORIZATION, "Bearer wrong".parse().unwrap());
+        assert_eq!(auth.check_secret(&headers), Err(AuthFailure::Invalid));
+        assert_eq!(auth.check_secret(&HeaderMap::new()), Err(AuthFailure::Missing));
+    }
+
+    #[test]
+    fn test_signature_checks_body_time_and_replay() {
+        let auth = TrackerAuth::new(vec!["current".to_string(), "previous".to_string()], true, 300);
+        let body = br#"{"players":[]}"#;
+        let now = 1_700_000_000;
+
+        let signature = sign("previous", now, "POST", "/api/tracker/update", body);
+        assert_eq!(
+            auth.check_signature(&now.to_string(), &signature, "POST", "/api/tracker/update", body, now + 10),
+            Ok(())
+        );
+        assert_eq!(
+            auth.check_signature(&now.to_string(), &signature, "POST", "/api/tracker/update", body, now + 20),
+            Err(AuthFailure::Replayed)
+        );
+
+        // Tampered body, wrong path, unknown key
+        let signature = sign("current", now, "POST", "/api/tracker/update", body);
+        assert_eq!(
+            auth.check_signature(&now.to_string(), &signature, "POST", "/api/tracker/update", b"{}", now),
+            Err(AuthFailure::Invalid)
+        );
+        assert_eq!(
+            auth.check_signature(&now.to_string(), &signature, "POST", "/api/tracker/chat", body, now),
+            Err(AuthFailure::Invalid)
+        );
+        let forged = sign("guess", now, "POST", "/api/tracker/update", body);
+        assert_eq!(
+            auth.check_signature(&now.to_string(), &forged, "POST", "/api/tracker/update", body, now),
+            Err(AuthFailure::Invalid)
+        );
+
+        // Outside the clock skew window
+        assert_eq!(
+            auth.check_signature(&now.to_string(), &signature, "POST", "/api/tracker/update", body, now + 301),
+            Err(AuthFailure::Expired)
+        );
+    }
+}
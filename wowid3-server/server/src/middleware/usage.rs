@@ -0,0 +1,260 @@
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::database::{self, usage::UsageBatch, Database};
+
+/// Anonymous per-install id sent by the launcher
+pub const LAUNCHER_ID_HEADER: &str = "x-launcher-id";
+/// Launcher version sent alongside the install id
+pub const LAUNCHER_VERSION_HEADER: &str = "x-launcher-version";
+
+/// How often counters are written to the database
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Route serving release files; its `:version` segment attributes downloads to a release
+const RELEASE_FILES_ROUTE: &str = "/files/:version/*path";
+
+/// Response extension set by `serve_file` naming the release file it served
+///
+/// Carries the decoded path, which the request URI alone doesn't give us.
+#[derive(Debug, Clone)]
+pub struct ServedReleaseFile {
+    pub version: String,
+    pub path: String,
+}
+
+/// In-memory usage counters shared by the middleware and the flush task
+///
+/// A modpack install is thousands of file requests, so counters are aggregated here
+/// and written in one transaction per `FLUSH_INTERVAL` instead of once per request.
+#[derive(Clone, Default)]
+pub struct UsageCounters {
+    pending: Arc<Mutex<UsageBatch>>,
+}
+
+impl UsageCounters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&self, request: RequestUsage, file: Option<ServedReleaseFile>, bytes: i64) {
+        let now = chrono::Utc::now().timestamp();
+        let day = database::usage::day_key(now);
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+
+        let bandwidth = pending.bandwidth.entry(day.clone()).or_default();
+        bandwidth.0 += 1;
+        bandwidth.1 += bytes;
+
+        if let Some(version) = &request.release {
+            let downloads = pending.downloads.entry((day, version.clone())).or_default();
+            downloads.0 += 1;
+            downloads.1 += bytes;
+        }
+
+        if let Some(file) = file {
+            let downloads = pending.files.entry((file.version, file.path)).or_default();
+            downloads.0 += 1;
+            downloads.1 += bytes;
+        }
+
+        if let Some(install_id) = request.install_id {
+            if let Some(version) = request.release {
+                pending.release_installs.insert((version, install_id.clone()));
+            }
+            pending.installs.insert(install_id, (now, request.launcher_version));
+        }
+    }
+
+    fn take(&self) -> UsageBatch {
+        std::mem::take(&mut *self.pending.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Write pending counters; on failure they are merged back for the next attempt
+    pub async fn flush(&self, db: &Database) {
+        let batch = self.take();
+        if batch.is_empty() {
+            return;
+        }
+        if let Err(e) = database::usage::record_batch(&db.conn, batch.clone()).await {
+            tracing::warn!("Failed to write usage counters: {}", e);
+            self.restore(batch);
+        }
+    }
+
+    fn restore(&self, batch: UsageBatch) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        for (key, (requests, bytes)) in batch.downloads {
+            let entry = pending.downloads.entry(key).or_default();
+            entry.0 += requests;
+            entry.1 += bytes;
+        }
+        for (key, (requests, bytes)) in batch.bandwidth {
+            let entry = pending.bandwidth.entry(key).or_default();
+            entry.0 += requests;
+            entry.1 += bytes;
+        }
+        for (install_id, seen) in batch.installs {
+            pending.installs.entry(install_id).or_insert(seen);
+        }
+        for (key, (downloads, bytes)) in batch.files {
+            let entry = pending.files.entry(key).or_default();
+            entry.0 += downloads;
+            entry.1 += bytes;
+        }
+        pending.release_installs.extend(batch.release_installs);
+    }
+
+    /// Flush counters periodically for the lifetime of the server
+    pub fn spawn_flush_task(&self, db: Database) {
+        let counters = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                counters.flush(&db).await;
+            }
+        });
+    }
+}
+
+/// What a request contributes to the counters, captured before the handler consumes it
+struct RequestUsage {
+    release: Option<String>,
+    install_id: Option<String>,
+    launcher_version: Option<String>,
+}
+
+/// Middleware that counts requests, bytes served, release downloads and launcher installs
+///
+/// Bytes are taken from the response `Content-Length`, so redirects to object storage
+/// count as requests but not bandwidth. Only successful responses are counted.
+/// Per-file downloads count full responses and redirects, not ranges or revalidations.
+pub async fn usage_middleware(
+    State(counters): State<UsageCounters>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let release = request
+        .extensions()
+        .get::<MatchedPath>()
+        .filter(|route| route.as_str() == RELEASE_FILES_ROUTE)
+        .and_then(|_| release_version(request.uri().path()));
+
+    let usage = RequestUsage {
+        release,
+        install_id: header_value(request.headers(), LAUNCHER_ID_HEADER).filter(|id| valid_install_id(id)),
+        launcher_version: header_value(request.headers(), LAUNCHER_VERSION_HEADER)
+            .filter(|v| v.len() <= 32),
+    };
+
+    let response = next.run(request).await;
+
+    let status = response.status();
+    if status.is_success() || status.is_redirection() {
+        let bytes = header_value(response.headers(), header::CONTENT_LENGTH.as_str())
+            .and_then(|len| len.parse::<i64>().ok())
+            .unwrap_or(0);
+        let full_download =
+            status == StatusCode::OK || (status.is_redirection() && status != StatusCode::NOT_MODIFIED);
+        let file = response
+            .extensions()
+            .get::<ServedReleaseFile>()
+            .filter(|_| full_download)
+            .cloned();
+        counters.record(usage, file, bytes);
+    }
+
+    response
+}
+
+fn header_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// `/files/<version>/<path>` -> `<version>`
+fn release_version(path: &str) -> Option<String> {
+    path.strip_prefix("/files/")?
+        .split('/')
+        .next()
+        .filter(|v| !v.is_empty())
+        .map(|v| v.to_string())
+}
+
+/// Install ids are launcher-generated UUIDs; anything else is ignored rather than stored
+fn valid_install_id(id: &str) -> bool {
+    id.len() <= 64 && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(release: Option<&str>, install_id: Option<&str>) -> RequestUsage {
+        RequestUsage {
+            release: release.map(|v| v.to_string()),
+            install_id: install_id.map(|v| v.to_string()),
+            launcher_version: None,
+        }
+    }
+
+    #[test]
+    fn test_release_version() {
+        assert_eq!(release_version("/files/1.2.0/mods/a.jar").as_deref(), Some("1.2.0"));
+        assert_eq!(release_version("/files//mods/a.jar"), None);
+        assert_eq!(release_version("/api/manifest/latest"), None);
+    }
+
+    #[test]
+    fn test_valid_install_id() {
+        assert!(valid_install_id("0b6f4c1e-8a0d-4c57-9a55-3d2f1b6a7e90"));
+        assert!(!valid_install_id("'; DROP TABLE launcher_installs; --"));
+        assert!(!valid_install_id(&"a".repeat(65)));
+    }
+
+    #[test]
+    fn test_counters_aggregate_until_taken() {
+        let counters = UsageCounters::new();
+        let served = ServedReleaseFile {
+            version: "1.2.0".to_string(),
+            path: "mods/a b.jar".to_string(),
+        };
+        counters.record(usage(Some("1.2.0"), Some("install-a")), Some(served.clone()), 100);
+        counters.record(usage(Some("1.2.0"), Some("install-a")), Some(served), 50);
+        counters.record(usage(None, Some("install-b")), None, 10);
+
+        let batch = counters.take();
+        let day = database::usage::day_key(chrono::Utc::now().timestamp());
+        assert_eq!(batch.downloads[&(day.clone(), "1.2.0".to_string())], (2, 150));
+        assert_eq!(batch.bandwidth[&day], (3, 160));
+        assert_eq!(batch.installs.len(), 2);
+        assert_eq!(batch.release_installs.len(), 1);
+        assert_eq!(batch.files[&("1.2.0".to_string(), "mods/a b.jar".to_string())], (2, 150));
+
+        assert!(counters.take().is_empty());
+    }
+
+    #[test]
+    fn test_restore_merges_with_new_counts() {
+        let counters = UsageCounters::new();
+        counters.record(usage(None, None), None, 100);
+        let failed = counters.take();
+
+        counters.record(usage(None, None), None, 10);
+        counters.restore(failed);
+
+        let day = database::usage::day_key(chrono::Utc::now().timestamp());
+        assert_eq!(counters.take().bandwidth[&day], (2, 110));
+    }
+}
@@ -1,16 +1,103 @@
+use super::manifest::ReleaseChannel;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LoginRequest {
+    /// Admin account name; omitted for the shared-password bootstrap login
+    #[serde(default)]
+    pub username: Option<String>,
     pub password: String,
 }
 
+/// Admin roles, ordered from least to most privileged
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AdminRole {
+    /// Read-only access to releases, drafts and stats
+    #[default]
+    Viewer,
+    /// Can create, edit, publish and delete releases and drafts
+    ReleaseManager,
+    /// Full access, including admin account management
+    Superadmin,
+}
+
+impl AdminRole {
+    /// Whether this role grants at least the privileges of `required`
+    pub fn allows(self, required: AdminRole) -> bool {
+        self >= required
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AdminRole::Viewer => "viewer",
+            AdminRole::ReleaseManager => "release-manager",
+            AdminRole::Superadmin => "superadmin",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "viewer" => Some(AdminRole::Viewer),
+            "release-manager" => Some(AdminRole::ReleaseManager),
+            "superadmin" => Some(AdminRole::Superadmin),
+            _ => None,
+        }
+    }
+}
+
+/// Admin account (never includes the password hash)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminUser {
+    pub id: i64,
+    pub username: String,
+    pub role: AdminRole,
+    pub disabled: bool,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateAdminUserRequest {
+    pub username: String,
+    pub password: String,
+    pub role: AdminRole,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateAdminUserRequest {
+    #[serde(default)]
+    pub password: Option<String>,
+    #[serde(default)]
+    pub role: Option<AdminRole>,
+    #[serde(default)]
+    pub disabled: Option<bool>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LoginResponse {
+    /// Short-lived access token (JWT)
     pub token: String,
+    pub role: AdminRole,
+    pub refresh_token: String,
+    /// Access token lifetime in seconds
+    pub expires_in: i64,
     pub message: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LogoutRequest {
+    /// Refresh token to revoke alongside the access token
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UploadResponse {
     pub upload_id: String,
@@ -20,6 +107,46 @@ pub struct UploadResponse {
     pub message: String,
 }
 
+/// Result of uploading a CMS asset
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AssetUploadResponse {
+    pub filename: String,
+    pub size: u64,
+    pub content_type: String,
+    /// Public URL path the launcher loads the asset from
+    pub url: String,
+}
+
+/// Request to start a chunked upload
+#[derive(Debug, Deserialize)]
+pub struct CreateUploadSessionRequest {
+    /// Path of the file inside the upload (zips are extracted on finalize)
+    pub file_name: String,
+    /// Total size in bytes
+    pub size: u64,
+    /// Expected checksum, verified on finalize when given
+    #[serde(default)]
+    pub sha256: Option<String>,
+    /// Add the file to an existing upload instead of starting a new one
+    #[serde(default)]
+    pub upload_id: Option<Uuid>,
+}
+
+/// A chunked upload in progress, persisted in `session.json` next to the received bytes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadSession {
+    pub session_id: Uuid,
+    pub upload_id: Uuid,
+    pub file_name: String,
+    pub size: u64,
+    #[serde(default)]
+    pub sha256: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Bytes received so far; derived from the data file, never trusted from disk
+    #[serde(default)]
+    pub offset: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BlacklistResponse {
     pub patterns: Vec<String>,
@@ -30,13 +157,50 @@ pub struct UpdateBlacklistRequest {
     pub patterns: Vec<String>,
 }
 
+/// Query for `GET /api/admin/retention`
+#[derive(Debug, Deserialize)]
+pub struct RetentionQuery {
+    /// Releases to keep instead of the configured RELEASE_RETENTION_KEEP
+    #[serde(default)]
+    pub keep: Option<usize>,
+}
+
+/// Query for `GET /api/admin/search`
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+}
+
+/// Proposed patterns to try against the latest release, or a draft when `draft_id` is set
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlacklistPreviewRequest {
+    pub patterns: Vec<String>,
+    #[serde(default)]
+    pub draft_id: Option<uuid::Uuid>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ReleaseInfo {
     pub version: String,
     pub minecraft_version: String,
+    #[serde(default)]
+    pub channel: ReleaseChannel,
     pub created_at: String,
     pub file_count: usize,
     pub size_bytes: u64,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateReleaseTagsRequest {
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReleaseTagsResponse {
+    pub version: String,
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
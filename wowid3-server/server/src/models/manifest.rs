@@ -7,6 +7,146 @@ pub struct ManifestFile {
     pub url: String,
     pub sha256: String,
     pub size: u64,
+    /// Same file on mirror servers; filled in from config when the manifest is served
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mirrors: Vec<String>,
+}
+
+/// Release channel; beta releases only reach players who opt in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+impl ReleaseChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReleaseChannel::Stable => "stable",
+            ReleaseChannel::Beta => "beta",
+        }
+    }
+}
+
+/// Mod loader the pack runs on; the launcher installs it before the pack's files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModLoader {
+    #[default]
+    Fabric,
+    Quilt,
+    NeoForge,
+}
+
+impl ModLoader {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ModLoader::Fabric => "Fabric",
+            ModLoader::Quilt => "Quilt",
+            ModLoader::NeoForge => "NeoForge",
+        }
+    }
+}
+
+/// Files players can turn off in the launcher (e.g. shaders, extra biomes)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OptionalGroup {
+    /// Stable id the launcher stores selections under
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Whether players who never chose get this group
+    #[serde(default)]
+    pub default_enabled: bool,
+    /// Manifest paths belonging to the group
+    pub files: Vec<String>,
+}
+
+impl OptionalGroup {
+    /// Reject groups the launcher couldn't store selections for
+    pub fn validate(groups: &[OptionalGroup]) -> Result<(), String> {
+        let mut seen = std::collections::HashSet::new();
+        for group in groups {
+            let valid_id = !group.id.is_empty()
+                && group
+                    .id
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+            if !valid_id {
+                return Err(format!(
+                    "Invalid optional group id '{}' (use lowercase letters, digits, '-' and '_')",
+                    group.id
+                ));
+            }
+            if !seen.insert(group.id.as_str()) {
+                return Err(format!("Duplicate optional group id '{}'", group.id));
+            }
+            if group.name.trim().is_empty() {
+                return Err(format!("Optional group '{}' needs a name", group.id));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A release's exceptions to the global blacklist
+///
+/// `add` patterns are ignored on top of the blacklist. `remove` lifts blacklist
+/// patterns for this release only, e.g. to ship a forced config reset; the launcher
+/// overwrites matching files without merging and still never deletes unlisted ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IgnorePatternOverrides {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove: Vec<String>,
+}
+
+impl IgnorePatternOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.remove.is_empty()
+    }
+
+    /// Trim every pattern and reject empty or uncompilable ones
+    pub fn validate(self) -> Result<Self, String> {
+        let normalize = |patterns: Vec<String>| -> Result<Vec<String>, String> {
+            let mut normalized: Vec<String> = Vec::with_capacity(patterns.len());
+            for pattern in patterns {
+                let pattern = pattern.trim().to_string();
+                if pattern.is_empty() {
+                    return Err("Ignore pattern overrides cannot be empty".to_string());
+                }
+                globset::Glob::new(&pattern)
+                    .map_err(|e| format!("Invalid glob pattern '{}': {}", pattern, e.kind()))?;
+                if !normalized.contains(&pattern) {
+                    normalized.push(pattern);
+                }
+            }
+            Ok(normalized)
+        };
+        Ok(Self {
+            add: normalize(self.add)?,
+            remove: normalize(self.remove)?,
+        })
+    }
+
+    /// The global blacklist with this release's exceptions applied
+    pub fn apply(&self, global: &[String]) -> Vec<String> {
+        let mut patterns: Vec<String> = global
+            .iter()
+            .filter(|pattern| !self.remove.contains(pattern))
+            .cloned()
+            .collect();
+        for pattern in &self.add {
+            if !patterns.contains(pattern) {
+                patterns.push(pattern.clone());
+            }
+        }
+        patterns
+    }
 }
 
 /// Complete manifest matching launcher format
@@ -14,11 +154,22 @@ pub struct ManifestFile {
 pub struct Manifest {
     pub version: String,
     pub minecraft_version: String,
+    /// Manifests written before other loaders were supported are Fabric
+    #[serde(default)]
+    pub loader: ModLoader,
+    /// Version of `loader` (the name predates Quilt and NeoForge support)
     pub fabric_loader: String,
     pub files: Vec<ManifestFile>,
     pub changelog: String,
     #[serde(default)]
     pub ignore_patterns: Vec<String>,
+    #[serde(default)]
+    pub channel: ReleaseChannel,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub optional_groups: Vec<OptionalGroup>,
+    /// Exceptions to the blacklist, already applied to `ignore_patterns`
+    #[serde(default, skip_serializing_if = "IgnorePatternOverrides::is_empty")]
+    pub ignore_overrides: IgnorePatternOverrides,
 }
 
 impl Manifest {
@@ -33,13 +184,32 @@ impl Manifest {
         Self {
             version,
             minecraft_version,
+            loader: ModLoader::default(),
             fabric_loader,
             files: Vec::new(),
             changelog,
             ignore_patterns,
+            channel: ReleaseChannel::default(),
+            optional_groups: Vec::new(),
+            ignore_overrides: IgnorePatternOverrides::default(),
         }
     }
 
+    /// Drop optional group entries for files that aren't in the release, and groups left empty
+    pub fn prune_optional_groups(&mut self) {
+        let paths: std::collections::HashSet<&str> = self.files.iter().map(|f| f.path.as_str()).collect();
+        for group in &mut self.optional_groups {
+            group.files.retain(|path| {
+                let known = paths.contains(path.as_str());
+                if !known {
+                    tracing::warn!("Optional group '{}' lists missing file {}", group.id, path);
+                }
+                known
+            });
+        }
+        self.optional_groups.retain(|group| !group.files.is_empty());
+    }
+
     #[allow(dead_code)]
     pub fn add_file(&mut self, file: ManifestFile) {
         self.files.push(file);
@@ -60,6 +230,9 @@ pub struct LauncherManifest {
     pub size: u64,
     pub changelog: String,
     pub mandatory: bool,
+    /// Base64 Ed25519 signature over the SHA-256 digest (unset when uploaded without a signing key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
 }
 
 /// Platform-specific launcher file
@@ -72,6 +245,9 @@ pub struct LauncherFile {
     pub url: String,
     pub sha256: String,
     pub size: u64,
+    /// Base64 Ed25519 signature over the SHA-256 digest, also published as `<filename>.sig`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
 }
 
 /// Multi-platform launcher version (new format)
@@ -107,3 +283,82 @@ impl LauncherVersion {
         self.files.iter().map(|f| f.platform.clone()).collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(id: &str, files: &[&str]) -> OptionalGroup {
+        OptionalGroup {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            default_enabled: false,
+            files: files.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_validate_optional_groups() {
+        assert!(OptionalGroup::validate(&[group("shaders", &[]), group("extra-biomes", &[])]).is_ok());
+        assert!(OptionalGroup::validate(&[group("Shaders", &[])]).is_err());
+        assert!(OptionalGroup::validate(&[group("shaders", &[]), group("shaders", &[])]).is_err());
+        assert!(OptionalGroup::validate(&[group("", &[])]).is_err());
+    }
+
+    #[test]
+    fn test_manifest_loader_defaults_to_fabric() {
+        let json = r#"{"version": "1.0.0", "minecraft_version": "1.21.1", "fabric_loader": "0.16.5", "files": [], "changelog": ""}"#;
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+        assert_eq!(manifest.loader, ModLoader::Fabric);
+
+        let json = json.replace(r#""files""#, r#""loader": "neoforge", "files""#);
+        let manifest: Manifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(manifest.loader, ModLoader::NeoForge);
+    }
+
+    #[test]
+    fn test_prune_optional_groups() {
+        let mut manifest = Manifest::new(
+            "1.0.0".to_string(),
+            "1.20.1".to_string(),
+            "0.15.0".to_string(),
+            String::new(),
+            Vec::new(),
+        );
+        manifest.add_file(ManifestFile {
+            path: "mods/iris.jar".to_string(),
+            url: String::new(),
+            sha256: String::new(),
+            size: 1,
+            mirrors: Vec::new(),
+        });
+        manifest.optional_groups = vec![
+            group("shaders", &["mods/iris.jar", "mods/removed.jar"]),
+            group("gone", &["mods/old.jar"]),
+        ];
+
+        manifest.prune_optional_groups();
+        assert_eq!(manifest.optional_groups, vec![group("shaders", &["mods/iris.jar"])]);
+    }
+
+    #[test]
+    fn test_ignore_pattern_overrides() {
+        let overrides = IgnorePatternOverrides {
+            add: vec![" journeymap/** ".to_string(), "logs/**".to_string()],
+            remove: vec!["config/sodium-options.json".to_string()],
+        }
+        .validate()
+        .unwrap();
+        assert_eq!(overrides.add, vec!["journeymap/**", "logs/**"]);
+
+        let global = vec!["logs/**".to_string(), "config/sodium-options.json".to_string()];
+        assert_eq!(overrides.apply(&global), vec!["logs/**", "journeymap/**"]);
+
+        let invalid = IgnorePatternOverrides {
+            add: vec!["config/[abc".to_string()],
+            remove: Vec::new(),
+        };
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -5,13 +5,15 @@ pub mod tracker;
 pub mod stats;
 
 pub use admin::{
-    AdminError, BlacklistResponse, DeleteReleaseResponse, LoginRequest, LoginResponse,
-    ReleaseInfo, UpdateBlacklistRequest, UploadResponse,
+    AdminError, AdminRole, AdminUser, AssetUploadResponse, BlacklistPreviewRequest, BlacklistResponse, CreateAdminUserRequest,
+    DeleteReleaseResponse, LoginRequest, LoginResponse, LogoutRequest, RefreshTokenRequest,
+    ReleaseInfo, ReleaseTagsResponse, RetentionQuery, SearchQuery, UpdateAdminUserRequest, UpdateBlacklistRequest,
+    UpdateReleaseTagsRequest, UploadResponse, CreateUploadSessionRequest, UploadSession,
 };
-pub use manifest::{Manifest, ManifestFile};
+pub use manifest::{IgnorePatternOverrides, Manifest, ManifestFile, ModLoader, OptionalGroup, ReleaseChannel};
 pub use release::{
-    AddFilesRequest, CreateDraftRequest, CreateReleaseRequest, DraftFile, DraftRelease,
-    GeneratedChangelog, ModInfo, UpdateDraftRequest, UpdateFileRequest,
-    VersionSuggestions,
+    AddFilesRequest, ChangedFile, CreateDraftRequest, CreateReleaseRequest, DependencyIssue,
+    DependencyReport, DiffFile, DiffMod, DraftFile, DraftRelease, DuplicateMod, GeneratedChangelog,
+    ModInfo, ReleaseDiff, UpdateDraftRequest, UpdateFileRequest, VersionSuggestions,
 };
 pub use tracker::TrackerState;
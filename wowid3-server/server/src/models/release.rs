@@ -1,5 +1,7 @@
+use super::manifest::{IgnorePatternOverrides, ModLoader, OptionalGroup, ReleaseChannel};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 /// Request to create a new release
@@ -7,9 +9,18 @@ use uuid::Uuid;
 pub struct CreateReleaseRequest {
     pub version: String,
     pub minecraft_version: String,
+    #[serde(default)]
+    pub loader: ModLoader,
     pub fabric_loader: String,
     pub changelog: String,
     pub upload_id: String, // References temp upload directory
+    #[serde(default)]
+    pub channel: ReleaseChannel,
+    #[serde(default)]
+    pub optional_groups: Vec<OptionalGroup>,
+    /// Exceptions to the global blacklist for this release
+    #[serde(default)]
+    pub ignore_overrides: IgnorePatternOverrides,
 }
 
 /// Draft release metadata
@@ -18,9 +29,23 @@ pub struct DraftRelease {
     pub id: Uuid,
     pub version: String,
     pub minecraft_version: String,
+    #[serde(default)]
+    pub loader: ModLoader,
     pub fabric_loader: String,
     pub changelog: String,
     pub files: Vec<DraftFile>,
+    /// Channel the release is published to
+    #[serde(default)]
+    pub channel: ReleaseChannel,
+    /// Files players can opt out of, copied into the manifest on publish
+    #[serde(default)]
+    pub optional_groups: Vec<OptionalGroup>,
+    /// Exceptions to the global blacklist, copied into the manifest on publish
+    #[serde(default)]
+    pub ignore_overrides: IgnorePatternOverrides,
+    /// Incremented on every change; clients send it back in `If-Match` to detect concurrent edits
+    #[serde(default)]
+    pub revision: u64,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -46,8 +71,12 @@ pub struct CreateDraftRequest {
 pub struct UpdateDraftRequest {
     pub version: Option<String>,
     pub minecraft_version: Option<String>,
+    pub loader: Option<ModLoader>,
     pub fabric_loader: Option<String>,
     pub changelog: Option<String>,
+    pub channel: Option<ReleaseChannel>,
+    pub optional_groups: Option<Vec<OptionalGroup>>,
+    pub ignore_overrides: Option<IgnorePatternOverrides>,
 }
 
 /// Request to add files to draft
@@ -71,6 +100,8 @@ pub struct VersionSuggestions {
     pub fabric_loader: Option<String>,
     pub suggested_version: Option<String>,
     pub detected_mods: Vec<ModInfo>,
+    #[serde(default)]
+    pub dependency_report: DependencyReport,
 }
 
 /// Detected mod information
@@ -81,6 +112,115 @@ pub struct ModInfo {
     pub version: String,
     pub minecraft_version: Option<String>,
     pub fabric_loader: Option<String>,
+    /// Jar path relative to the draft root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// `depends` from fabric.mod.json: mod id -> version predicates (any may match)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub depends: BTreeMap<String, Vec<String>>,
+    /// `breaks` from fabric.mod.json: mod id -> version predicates
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub breaks: BTreeMap<String, Vec<String>>,
+    /// Other mod ids this jar satisfies (`provides` and jar-in-jar mods) -> version
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub provides: BTreeMap<String, String>,
+}
+
+/// Problems in a draft's mod dependency graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DependencyReport {
+    /// Required dependencies that no jar provides
+    pub missing_dependencies: Vec<DependencyIssue>,
+    /// Dependencies present in a version outside the required range
+    pub version_mismatches: Vec<DependencyIssue>,
+    /// Mods present in a version another mod declares it `breaks`
+    pub conflicts: Vec<DependencyIssue>,
+    /// Mod ids shipped by more than one jar
+    pub duplicate_mods: Vec<DuplicateMod>,
+}
+
+impl DependencyReport {
+    /// True if any of these would likely crash the game on startup
+    pub fn has_problems(&self) -> bool {
+        !self.missing_dependencies.is_empty()
+            || !self.version_mismatches.is_empty()
+            || !self.conflicts.is_empty()
+            || !self.duplicate_mods.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyIssue {
+    pub mod_id: String,
+    pub mod_name: String,
+    pub file: Option<String>,
+    pub dependency: String,
+    /// Version predicates as written in fabric.mod.json
+    pub requirement: Vec<String>,
+    /// Version found in the draft, if any
+    pub found: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateMod {
+    pub mod_id: String,
+    pub files: Vec<String>,
+}
+
+/// Mod identity shown next to a file in a release diff
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffMod {
+    pub mod_id: String,
+    pub name: String,
+    pub version: String,
+}
+
+/// A file present on only one side of a release diff
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffFile {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+    #[serde(rename = "mod", default, skip_serializing_if = "Option::is_none")]
+    pub mod_info: Option<DiffMod>,
+}
+
+/// A file that differs between the release and the draft
+///
+/// Mod jars are matched by mod id, so an update that renames the jar shows up
+/// here with `previous_path` set rather than as a removal plus an addition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangedFile {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_path: Option<String>,
+    pub size: u64,
+    pub previous_size: u64,
+    pub sha256: String,
+    pub previous_sha256: String,
+    #[serde(rename = "mod", default, skip_serializing_if = "Option::is_none")]
+    pub mod_info: Option<DiffMod>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_mod: Option<DiffMod>,
+}
+
+/// What players on `base_version` would receive when updating to the draft
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseDiff {
+    pub base_version: String,
+    pub draft_version: String,
+    pub previous_minecraft_version: String,
+    pub minecraft_version: String,
+    pub previous_fabric_loader: String,
+    pub fabric_loader: String,
+    pub added: Vec<DiffFile>,
+    pub removed: Vec<DiffFile>,
+    pub changed: Vec<ChangedFile>,
+    pub unchanged: usize,
+    /// Bytes an updating player downloads (added and changed files)
+    pub download_size: u64,
+    /// Total size of the draft minus total size of the release
+    pub size_delta: i64,
 }
 
 /// Changelog generation response
@@ -37,6 +37,13 @@ pub struct PlayerStats {
     #[serde(default)]
     pub playtime_seconds: u64,
 
+    /// Blocks travelled on foot, swimming, riding or flying
+    #[serde(default)]
+    pub distance_traveled: f64,
+
+    #[serde(default)]
+    pub advancements_completed: u64,
+
     // Aggregate totals for quick access
     #[serde(default)]
     pub total_blocks_broken: u64,
@@ -57,6 +64,101 @@ pub struct PlayerStats {
     pub last_updated: u64,
 }
 
+/// Side effects of applying a batch that live outside `PlayerStats`
+#[derive(Debug, Default)]
+pub struct AppliedEvents {
+    /// Amounts to add to today's leaderboard rollups
+    pub rollups: HashMap<LeaderboardMetric, f64>,
+    /// Completed advancements as (id, title); the store counts the ones it hadn't recorded yet
+    pub advancements: Vec<(String, Option<String>)>,
+}
+
+impl PlayerStats {
+    /// Stats for a player seen for the first time
+    pub fn new_player(uuid: &str, username: &str, now: u64) -> Self {
+        Self {
+            uuid: uuid.to_string(),
+            username: username.to_string(),
+            first_seen: now,
+            ..Default::default()
+        }
+    }
+
+    /// Fold events into the stats; duplicates must already be filtered out
+    pub fn apply_events(&mut self, events: Vec<PlayerStatEvent>) -> AppliedEvents {
+        let mut applied = AppliedEvents::default();
+        for event in events {
+            // Update username if changed
+            if !event.username.is_empty() {
+                self.username = event.username;
+            }
+
+            if let Some((metric, amount)) = LeaderboardMetric::for_event(&event.event) {
+                *applied.rollups.entry(metric).or_insert(0.0) += amount;
+            }
+
+            match event.event {
+                StatEvent::BlockBroken { item_id } => {
+                    *self.blocks_broken.entry(item_id).or_insert(0) += 1;
+                    self.total_blocks_broken += 1;
+                }
+                StatEvent::BlockPlaced { item_id } => {
+                    *self.blocks_placed.entry(item_id).or_insert(0) += 1;
+                    self.total_blocks_placed += 1;
+                }
+                StatEvent::MobKilled { entity_id } => {
+                    *self.mobs_killed.entry(entity_id).or_insert(0) += 1;
+                    self.total_mobs_killed += 1;
+                }
+                StatEvent::MobTamed { entity_id } => {
+                    *self.mobs_tamed.entry(entity_id).or_insert(0) += 1;
+                    self.total_mobs_tamed += 1;
+                }
+                StatEvent::OreMined { block_id } => {
+                    *self.ores_mined.entry(block_id).or_insert(0) += 1;
+                    self.total_ores_mined += 1;
+                }
+                StatEvent::ItemGathered { item_id, count } => {
+                    *self.items_gathered.entry(item_id).or_insert(0) += count;
+                }
+                StatEvent::FoodEaten { item_id, count } => {
+                    *self.food_eaten.entry(item_id).or_insert(0) += count;
+                    self.total_food_eaten += count;
+                }
+                StatEvent::DamageDealt { amount } => {
+                    self.damage_dealt += amount;
+                }
+                StatEvent::DamageTaken { amount } => {
+                    self.damage_taken += amount;
+                }
+                StatEvent::PlayerDeath => {
+                    self.deaths += 1;
+                }
+                StatEvent::DimensionVisited { dimension_id } => {
+                    if !self.dimensions_visited.contains(&dimension_id) {
+                        self.dimensions_visited.push(dimension_id);
+                    }
+                }
+                StatEvent::BiomeVisited { biome_id } => {
+                    if !self.biomes_visited.contains(&biome_id) {
+                        self.biomes_visited.push(biome_id);
+                    }
+                }
+                StatEvent::Playtime { seconds } => {
+                    self.playtime_seconds += seconds;
+                }
+                StatEvent::DistanceTraveled { blocks } => {
+                    self.distance_traveled += blocks;
+                }
+                StatEvent::AdvancementCompleted { advancement_id, title } => {
+                    applied.advancements.push((advancement_id, title));
+                }
+            }
+        }
+        applied
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum StatEvent {
@@ -73,6 +175,107 @@ pub enum StatEvent {
     DimensionVisited { dimension_id: String },
     BiomeVisited { biome_id: String },
     Playtime { seconds: u64 },
+    DistanceTraveled { blocks: f64 },
+    AdvancementCompleted {
+        advancement_id: String,
+        /// Display title, e.g. "How Did We Get Here?"
+        #[serde(default)]
+        title: Option<String>,
+    },
+}
+
+/// Stats with a leaderboard, rolled up per player and day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardMetric {
+    Deaths,
+    MobKills,
+    BlocksMined,
+    DistanceTraveled,
+}
+
+impl LeaderboardMetric {
+    pub const ALL: [LeaderboardMetric; 4] = [
+        LeaderboardMetric::Deaths,
+        LeaderboardMetric::MobKills,
+        LeaderboardMetric::BlocksMined,
+        LeaderboardMetric::DistanceTraveled,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LeaderboardMetric::Deaths => "deaths",
+            LeaderboardMetric::MobKills => "mob_kills",
+            LeaderboardMetric::BlocksMined => "blocks_mined",
+            LeaderboardMetric::DistanceTraveled => "distance_traveled",
+        }
+    }
+
+    pub fn parse(metric: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.as_str() == metric)
+    }
+
+    /// The metric an event counts toward, and by how much
+    pub fn for_event(event: &StatEvent) -> Option<(Self, f64)> {
+        match event {
+            StatEvent::PlayerDeath => Some((LeaderboardMetric::Deaths, 1.0)),
+            StatEvent::MobKilled { .. } => Some((LeaderboardMetric::MobKills, 1.0)),
+            StatEvent::BlockBroken { .. } => Some((LeaderboardMetric::BlocksMined, 1.0)),
+            StatEvent::DistanceTraveled { blocks } => Some((LeaderboardMetric::DistanceTraveled, *blocks)),
+            _ => None,
+        }
+    }
+
+    /// All-time value from a player's totals (used to seed the rollups)
+    pub fn total(self, stats: &PlayerStats) -> f64 {
+        match self {
+            LeaderboardMetric::Deaths => stats.deaths as f64,
+            LeaderboardMetric::MobKills => stats.total_mobs_killed as f64,
+            LeaderboardMetric::BlocksMined => stats.total_blocks_broken as f64,
+            LeaderboardMetric::DistanceTraveled => stats.distance_traveled,
+        }
+    }
+}
+
+/// Time range a leaderboard covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LeaderboardWindow {
+    #[serde(rename = "7d")]
+    #[default]
+    Week,
+    #[serde(rename = "30d")]
+    Month,
+    #[serde(rename = "all")]
+    All,
+}
+
+impl LeaderboardWindow {
+    /// Number of days covered, including today; `None` for all time
+    pub fn days(self) -> Option<i64> {
+        match self {
+            LeaderboardWindow::Week => Some(7),
+            LeaderboardWindow::Month => Some(30),
+            LeaderboardWindow::All => None,
+        }
+    }
+}
+
+/// One ranked player on a leaderboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub rank: u32,
+    pub uuid: String,
+    /// Most recent name seen on the server
+    pub name: Option<String>,
+    pub value: f64,
+}
+
+/// Body of GET /api/stats/leaderboards/:metric
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub metric: LeaderboardMetric,
+    pub window: LeaderboardWindow,
+    pub entries: Vec<LeaderboardEntry>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,6 +285,10 @@ pub struct StatEventBatch {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerStatEvent {
+    /// Unique per event (e.g. a UUID); resent events with the same id are
+    /// only counted once, so the tracker can retry failed batches
+    #[serde(default)]
+    pub event_id: Option<String>,
     pub uuid: String,
     pub username: String,
     pub timestamp: u64,
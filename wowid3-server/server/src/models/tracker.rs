@@ -17,6 +17,16 @@ pub struct ChatMessage {
     pub timestamp: u64, // Unix timestamp in seconds
 }
 
+/// A launcher chat message for the tracker mod to broadcast in-game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutgoingChatMessage {
+    /// Verified Minecraft UUID of the sender
+    pub uuid: String,
+    pub sender: String,
+    pub content: String,
+    pub timestamp: u64, // Unix timestamp in seconds
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrackerState {
     pub online_players: Vec<PlayerExt>,
@@ -24,6 +34,9 @@ pub struct TrackerState {
     pub tps: Option<f32>,
     pub mspt: Option<f32>,
     pub last_updated: u64, // Unix timestamp
+    /// Messages sent from launchers, waiting for the tracker mod to broadcast them
+    #[serde(skip)]
+    pub outgoing_chat: VecDeque<OutgoingChatMessage>,
 }
 
 impl Default for TrackerState {
@@ -34,6 +47,7 @@ impl Default for TrackerState {
             tps: None,
             mspt: None,
             last_updated: 0,
+            outgoing_chat: VecDeque::new(),
         }
     }
 }
@@ -52,3 +66,13 @@ pub struct ChatMessageRequest {
     pub content: String,
 }
 
+
+/// Body of POST /api/tracker/chat/send
+#[derive(Debug, Deserialize)]
+pub struct SendChatRequest {
+    /// Minecraft UUID the launcher is signed in as
+    pub uuid: String,
+    /// Minecraft access token proving ownership of `uuid`
+    pub access_token: String,
+    pub content: String,
+}
@@ -1,43 +1,32 @@
-use crate::models::{ModInfo, VersionSuggestions};
+use crate::models::{DependencyIssue, DependencyReport, DuplicateMod, ModInfo, VersionSuggestions};
 use anyhow::{Context, Result};
 use regex::Regex;
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
-use std::io::Read;
+use std::io::{Cursor, Read, Seek};
 use std::path::Path;
 use walkdir::WalkDir;
 use zip::ZipArchive;
 
+/// How deep to follow jar-in-jar (`jars` in fabric.mod.json)
+const MAX_NESTED_JAR_DEPTH: usize = 3;
+
 /// Analyze uploaded files and suggest versions
 pub fn analyze_files(files_dir: &Path) -> Result<VersionSuggestions> {
-    let mut detected_mods = Vec::new();
+    let detected_mods = scan_mods(files_dir);
     let mut minecraft_versions = HashMap::new();
     let mut fabric_versions = HashMap::new();
 
-    // Walk through all JAR files in mods directory
-    let mods_dir = files_dir.join("mods");
-    if mods_dir.exists() {
-        for entry in WalkDir::new(&mods_dir)
-            .max_depth(1)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("jar") {
-                if let Ok(mod_info) = read_jar_metadata(path) {
-                    // Track Minecraft versions
-                    if let Some(ref mc_ver) = mod_info.minecraft_version {
-                        *minecraft_versions.entry(mc_ver.clone()).or_insert(0) += 1;
-                    }
-
-                    // Track Fabric versions
-                    if let Some(ref fl_ver) = mod_info.fabric_loader {
-                        *fabric_versions.entry(fl_ver.clone()).or_insert(0) += 1;
-                    }
+    for mod_info in &detected_mods {
+        // Track Minecraft versions
+        if let Some(ref mc_ver) = mod_info.minecraft_version {
+            *minecraft_versions.entry(mc_ver.clone()).or_insert(0) += 1;
+        }
 
-                    detected_mods.push(mod_info);
-                }
-            }
+        // Track Fabric versions
+        if let Some(ref fl_ver) = mod_info.fabric_loader {
+            *fabric_versions.entry(fl_ver.clone()).or_insert(0) += 1;
         }
     }
 
@@ -55,16 +44,53 @@ pub fn analyze_files(files_dir: &Path) -> Result<VersionSuggestions> {
     // Suggest semantic version based on latest
     let suggested_version = None; // Will be set by API endpoint based on latest release
 
+    // Checked against the draft's Minecraft/loader versions by the API endpoint
+    let dependency_report = DependencyReport::default();
+
     Ok(VersionSuggestions {
         minecraft_version,
         fabric_loader,
         suggested_version,
         detected_mods,
+        dependency_report,
     })
 }
 
+/// Read metadata for every JAR in `files_dir/mods`
+///
+/// `file` is set to the jar's path relative to `files_dir`.
+pub fn scan_mods(files_dir: &Path) -> Vec<ModInfo> {
+    let mut mods = Vec::new();
+
+    let mods_dir = files_dir.join("mods");
+    if !mods_dir.exists() {
+        return mods;
+    }
+
+    for entry in WalkDir::new(&mods_dir)
+        .max_depth(1)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) == Some("jar") {
+            if let Ok(mut mod_info) = read_jar_metadata(path) {
+                mod_info.file = path
+                    .strip_prefix(files_dir)
+                    .ok()
+                    .map(|p| p.to_string_lossy().replace('\\', "/"));
+                mods.push(mod_info);
+            }
+        }
+    }
+
+    mods.sort_by(|a, b| a.file.cmp(&b.file));
+    mods
+}
+
 /// Read metadata from a JAR file
-fn read_jar_metadata(jar_path: &Path) -> Result<ModInfo> {
+pub fn read_jar_metadata(jar_path: &Path) -> Result<ModInfo> {
     let file = File::open(jar_path)
         .context("Failed to open JAR file")?;
 
@@ -72,7 +98,7 @@ fn read_jar_metadata(jar_path: &Path) -> Result<ModInfo> {
         .context("Failed to read JAR archive")?;
 
     // Try to read fabric.mod.json first (Fabric mods)
-    if let Ok(fabric_metadata) = read_fabric_metadata(&mut archive) {
+    if let Ok(fabric_metadata) = read_fabric_metadata(&mut archive, 0) {
         return Ok(fabric_metadata);
     }
 
@@ -81,16 +107,18 @@ fn read_jar_metadata(jar_path: &Path) -> Result<ModInfo> {
 }
 
 /// Read Fabric mod metadata from fabric.mod.json
-fn read_fabric_metadata(archive: &mut ZipArchive<File>) -> Result<ModInfo> {
-    let mut file = archive.by_name("fabric.mod.json")
-        .context("fabric.mod.json not found")?;
+fn read_fabric_metadata<R: Read + Seek>(archive: &mut ZipArchive<R>, depth: usize) -> Result<ModInfo> {
+    let json: serde_json::Value = {
+        let mut file = archive.by_name("fabric.mod.json")
+            .context("fabric.mod.json not found")?;
 
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)
-        .context("Failed to read fabric.mod.json")?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)
+            .context("Failed to read fabric.mod.json")?;
 
-    let json: serde_json::Value = serde_json::from_str(&contents)
-        .context("Failed to parse fabric.mod.json")?;
+        serde_json::from_str(&contents)
+            .context("Failed to parse fabric.mod.json")?
+    };
 
     let mod_id = json["id"].as_str().unwrap_or("unknown").to_string();
     let name = json["name"].as_str().unwrap_or(&mod_id).to_string();
@@ -106,15 +134,83 @@ fn read_fabric_metadata(archive: &mut ZipArchive<File>) -> Result<ModInfo> {
         .as_str()
         .map(|s| clean_version_range(s));
 
+    let mut provides: BTreeMap<String, String> = json["provides"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|id| id.as_str())
+        .map(|id| (id.to_string(), version.clone()))
+        .collect();
+
+    // Jar-in-jar mods satisfy dependencies just like top-level jars
+    if depth < MAX_NESTED_JAR_DEPTH {
+        let nested_paths: Vec<String> = json["jars"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|jar| jar["file"].as_str())
+            .map(|path| path.to_string())
+            .collect();
+
+        for path in nested_paths {
+            match read_nested_jar(archive, &path, depth + 1) {
+                Ok(nested) => {
+                    provides.entry(nested.mod_id).or_insert(nested.version);
+                    for (id, version) in nested.provides {
+                        provides.entry(id).or_insert(version);
+                    }
+                }
+                Err(e) => tracing::debug!("Skipping nested jar {} in {}: {}", path, mod_id, e),
+            }
+        }
+    }
+
     Ok(ModInfo {
         mod_id,
         name,
         version,
         minecraft_version,
         fabric_loader,
+        file: None,
+        depends: read_predicates(&json["depends"]),
+        breaks: read_predicates(&json["breaks"]),
+        provides,
     })
 }
 
+/// Read fabric.mod.json from a jar bundled inside another jar
+fn read_nested_jar<R: Read + Seek>(archive: &mut ZipArchive<R>, path: &str, depth: usize) -> Result<ModInfo> {
+    let mut data = Vec::new();
+    archive
+        .by_name(path)
+        .context("Nested jar not found")?
+        .read_to_end(&mut data)
+        .context("Failed to read nested jar")?;
+
+    let mut nested = ZipArchive::new(Cursor::new(data)).context("Failed to open nested jar")?;
+    read_fabric_metadata(&mut nested, depth)
+}
+
+/// Parse a `depends`/`breaks` object; values are a predicate string or an array of alternatives
+fn read_predicates(value: &serde_json::Value) -> BTreeMap<String, Vec<String>> {
+    value
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(id, predicates)| {
+            let predicates = match predicates {
+                serde_json::Value::String(p) => vec![p.clone()],
+                serde_json::Value::Array(list) => list
+                    .iter()
+                    .filter_map(|p| p.as_str().map(|p| p.to_string()))
+                    .collect(),
+                _ => vec!["*".to_string()],
+            };
+            (id.clone(), predicates)
+        })
+        .collect()
+}
+
 /// Extract mod info from filename as fallback
 fn extract_from_filename(path: &Path) -> Result<ModInfo> {
     let filename = path.file_stem()
@@ -138,6 +234,10 @@ fn extract_from_filename(path: &Path) -> Result<ModInfo> {
         version,
         minecraft_version: None,
         fabric_loader: None,
+        file: None,
+        depends: BTreeMap::new(),
+        breaks: BTreeMap::new(),
+        provides: BTreeMap::new(),
     })
 }
 
@@ -155,6 +255,193 @@ fn clean_version_range(version: &str) -> String {
         .to_string()
 }
 
+/// Check the dependency graph of detected mods
+///
+/// `minecraft_version` and `fabric_loader` are what the pack runs on; when
+/// unknown, dependencies on them are not checked.
+pub fn check_dependencies(
+    mods: &[ModInfo],
+    minecraft_version: Option<&str>,
+    fabric_loader: Option<&str>,
+) -> DependencyReport {
+    let mut report = DependencyReport::default();
+
+    // Everything installed, by mod id -> version
+    let mut available: HashMap<&str, &str> = HashMap::new();
+    for m in mods {
+        available.insert(&m.mod_id, &m.version);
+    }
+    for m in mods {
+        for (id, version) in &m.provides {
+            available.entry(id.as_str()).or_insert(version.as_str());
+        }
+    }
+    if let Some(mc) = minecraft_version {
+        available.insert("minecraft", mc);
+    }
+    if let Some(loader) = fabric_loader {
+        available.insert("fabricloader", loader);
+        available.insert("fabric-loader", loader);
+    }
+
+    for m in mods {
+        let issue = |dependency: &str, requirement: &[String], found: Option<&str>| DependencyIssue {
+            mod_id: m.mod_id.clone(),
+            mod_name: m.name.clone(),
+            file: m.file.clone(),
+            dependency: dependency.to_string(),
+            requirement: requirement.to_vec(),
+            found: found.map(|v| v.to_string()),
+        };
+
+        for (dependency, requirement) in &m.depends {
+            // The JVM is outside the modpack; skip the environment when unknown
+            if dependency == "java"
+                || (dependency == "minecraft" && minecraft_version.is_none())
+                || (is_loader_id(dependency) && fabric_loader.is_none())
+            {
+                continue;
+            }
+
+            match available.get(dependency.as_str()) {
+                None => report.missing_dependencies.push(issue(dependency, requirement, None)),
+                Some(found) if !requirement.iter().any(|p| version_satisfies(found, p)) => {
+                    report.version_mismatches.push(issue(dependency, requirement, Some(*found)))
+                }
+                Some(_) => {}
+            }
+        }
+
+        for (dependency, requirement) in &m.breaks {
+            if let Some(found) = available.get(dependency.as_str()) {
+                if requirement.iter().any(|p| version_satisfies(found, p)) {
+                    report.conflicts.push(issue(dependency, requirement, Some(*found)));
+                }
+            }
+        }
+    }
+
+    // Only top-level jars count; Fabric picks one copy of bundled libraries itself
+    let mut by_id: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for m in mods {
+        if let Some(file) = &m.file {
+            by_id.entry(&m.mod_id).or_default().push(file.clone());
+        }
+    }
+    report.duplicate_mods = by_id
+        .into_iter()
+        .filter(|(_, files)| files.len() > 1)
+        .map(|(mod_id, mut files)| {
+            files.sort();
+            DuplicateMod {
+                mod_id: mod_id.to_string(),
+                files,
+            }
+        })
+        .collect();
+
+    report
+}
+
+pub fn is_loader_id(id: &str) -> bool {
+    id == "fabricloader" || id == "fabric-loader"
+}
+
+/// A `major.minor.patch...[-pre]` version; build metadata (`+...`) is ignored
+struct SemVersion {
+    parts: Vec<u64>,
+    pre: Option<String>,
+}
+
+fn parse_version(version: &str) -> Option<SemVersion> {
+    let core = version.split('+').next()?.trim();
+    let (core, pre) = match core.split_once('-') {
+        Some((core, pre)) => (core, Some(pre.to_string())),
+        None => (core, None),
+    };
+    let parts = core
+        .split('.')
+        .map(|p| p.parse().ok())
+        .collect::<Option<Vec<u64>>>()?;
+
+    Some(SemVersion { parts, pre })
+}
+
+fn compare_versions(a: &SemVersion, b: &SemVersion) -> Ordering {
+    let len = a.parts.len().max(b.parts.len());
+    for i in 0..len {
+        let ord = a.parts.get(i).unwrap_or(&0).cmp(b.parts.get(i).unwrap_or(&0));
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+
+    // A pre-release sorts before its release
+    match (&a.pre, &b.pre) {
+        (None, None) => Ordering::Equal,
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(a), Some(b)) => a.cmp(b),
+    }
+}
+
+/// Check a version against a Fabric version predicate (e.g. `>=0.5 <0.6`, `~1.20.1`, `1.20.x`)
+///
+/// Space-separated terms must all match. Versions that aren't semantic
+/// (snapshots, "unknown") are assumed to match rather than reported.
+pub fn version_satisfies(version: &str, predicate: &str) -> bool {
+    let Some(version) = parse_version(version) else {
+        return true;
+    };
+    predicate.split_whitespace().all(|term| term_satisfied(&version, term))
+}
+
+fn term_satisfied(version: &SemVersion, term: &str) -> bool {
+    if term == "*" {
+        return true;
+    }
+
+    let (op, target) = [">=", "<=", ">", "<", "=", "~", "^"]
+        .iter()
+        .find_map(|op| term.strip_prefix(op).map(|rest| (*op, rest)))
+        .unwrap_or(("", term));
+
+    // Wildcards ("1.20.x") match on the leading components
+    let target_core = target.split(['-', '+']).next().unwrap_or(target);
+    if target_core.split('.').any(|p| matches!(p, "x" | "X" | "*")) {
+        let Some(prefix) = target_core
+            .split('.')
+            .take_while(|p| !matches!(*p, "x" | "X" | "*"))
+            .map(|p| p.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()
+        else {
+            return true;
+        };
+        return prefix
+            .iter()
+            .enumerate()
+            .all(|(i, p)| version.parts.get(i).unwrap_or(&0) == p);
+    }
+
+    let Some(target) = parse_version(target) else {
+        return true;
+    };
+    let ord = compare_versions(version, &target);
+    let same_leading = |n: usize| {
+        (0..n).all(|i| version.parts.get(i).unwrap_or(&0) == target.parts.get(i).unwrap_or(&0))
+    };
+
+    match op {
+        ">=" => ord != Ordering::Less,
+        "<=" => ord != Ordering::Greater,
+        ">" => ord == Ordering::Greater,
+        "<" => ord == Ordering::Less,
+        "~" => ord != Ordering::Less && same_leading(2),
+        "^" => ord != Ordering::Less && same_leading(1),
+        _ => ord == Ordering::Equal,
+    }
+}
+
 /// Suggest next semantic version based on change type
 pub fn suggest_next_version(current: &str, change_type: ChangeType) -> String {
     let parts: Vec<&str> = current.split('.').collect();
@@ -181,3 +468,64 @@ pub enum ChangeType {
     Minor,
     Patch,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_support::mod_info;
+
+    #[test]
+    fn test_version_satisfies() {
+        assert!(version_satisfies("0.5.11+mc1.20.1", ">=0.5.8"));
+        assert!(!version_satisfies("0.5.11", ">=0.5 <0.5.10"));
+        assert!(version_satisfies("1.20.1", "1.20.x"));
+        assert!(!version_satisfies("1.21", "1.20.x"));
+        assert!(version_satisfies("1.20.4", "~1.20.1"));
+        assert!(!version_satisfies("1.21.0", "~1.20.1"));
+        assert!(version_satisfies("2.5.0", "^2.1"));
+        assert!(!version_satisfies("1.0.0-beta.1", ">=1.0.0"));
+        assert!(version_satisfies("1.20.1", "*"));
+        assert!(version_satisfies("23w13a", ">=1.20"));
+    }
+
+    #[test]
+    fn test_check_dependencies() {
+        let mut sodium = mod_info("sodium", "0.5.11", "mods/sodium.jar");
+        sodium.depends.insert("minecraft".to_string(), vec!["~1.20.1".to_string()]);
+        sodium.depends.insert("fabric-api".to_string(), vec!["*".to_string()]);
+        sodium.depends.insert("java".to_string(), vec![">=17".to_string()]);
+        sodium.breaks.insert("optifabric".to_string(), vec!["*".to_string()]);
+
+        let mut iris = mod_info("iris", "1.7.0", "mods/iris.jar");
+        iris.depends.insert("sodium".to_string(), vec![">=0.6".to_string()]);
+        iris.depends.insert("fabric-resource-loader-v0".to_string(), vec!["*".to_string()]);
+        iris.provides.insert("fabric-resource-loader-v0".to_string(), "0.11.0".to_string());
+
+        let optifabric = mod_info("optifabric", "1.14.0", "mods/optifabric.jar");
+        let duplicate = mod_info("iris", "1.6.0", "mods/iris-old.jar");
+
+        let report = check_dependencies(
+            &[sodium, iris, optifabric, duplicate],
+            Some("1.20.1"),
+            Some("0.15.7"),
+        );
+
+        assert_eq!(report.missing_dependencies.len(), 1);
+        assert_eq!(report.missing_dependencies[0].dependency, "fabric-api");
+        assert_eq!(report.version_mismatches.len(), 1);
+        assert_eq!(report.version_mismatches[0].found.as_deref(), Some("0.5.11"));
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.duplicate_mods.len(), 1);
+        assert_eq!(report.duplicate_mods[0].files, vec!["mods/iris-old.jar", "mods/iris.jar"]);
+        assert!(report.has_problems());
+    }
+
+    #[test]
+    fn test_environment_skipped_when_unknown() {
+        let mut m = mod_info("a", "1.0.0", "mods/a.jar");
+        m.depends.insert("minecraft".to_string(), vec!["1.19.2".to_string()]);
+        m.depends.insert("fabricloader".to_string(), vec![">=0.14".to_string()]);
+
+        assert!(!check_dependencies(&[m], None, None).has_problems());
+    }
+}
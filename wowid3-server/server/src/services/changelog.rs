@@ -1,57 +1,104 @@
-use crate::models::{DraftFile, GeneratedChangelog, ManifestFile};
+use crate::models::{DraftFile, GeneratedChangelog, ManifestFile, ModInfo};
 use anyhow::Result;
 use regex::Regex;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-/// Generate changelog by comparing two file lists
+/// Generate changelog by comparing two releases
+///
+/// Jars with mod metadata (see `analyzer::scan_mods`) are compared by mod id and
+/// version; all other files are compared by path and checksum.
 pub fn generate_changelog(
     current_files: &[DraftFile],
     previous_files: Option<&[ManifestFile]>,
+    current_mods: &[ModInfo],
+    previous_mods: &[ModInfo],
 ) -> Result<GeneratedChangelog> {
     let mut added = Vec::new();
     let mut changed = Vec::new();
     let mut removed = Vec::new();
 
+    let current_mod_paths: HashSet<&str> = current_mods.iter().filter_map(|m| m.file.as_deref()).collect();
+    let previous_mod_paths: HashSet<&str> = previous_mods.iter().filter_map(|m| m.file.as_deref()).collect();
+
     if let Some(prev_files) = previous_files {
-        // Create lookup maps
-        let prev_map: HashMap<&str, &ManifestFile> = prev_files
+        // Mods, by id
+        let current_sha: HashMap<&str, &str> = current_files
             .iter()
-            .map(|f| (f.path.as_str(), f))
+            .map(|f| (f.path.as_str(), f.sha256.as_str()))
+            .collect();
+        let previous_sha: HashMap<&str, &str> = prev_files
+            .iter()
+            .map(|f| (f.path.as_str(), f.sha256.as_str()))
             .collect();
 
-        let _current_map: HashMap<&str, &DraftFile> = current_files
+        let current_by_id: BTreeMap<&str, &ModInfo> = current_mods.iter().map(|m| (m.mod_id.as_str(), m)).collect();
+        let previous_by_id: BTreeMap<&str, &ModInfo> = previous_mods.iter().map(|m| (m.mod_id.as_str(), m)).collect();
+
+        for (mod_id, current) in &current_by_id {
+            match previous_by_id.get(mod_id) {
+                None => added.push(format!("Added {}", current.name)),
+                Some(previous) if previous.version != current.version => changed.push(format!(
+                    "Updated {} {} → {}",
+                    current.name, previous.version, current.version
+                )),
+                Some(previous) => {
+                    // Same version but a different jar (e.g. a rebuilt file)
+                    fn sha<'a>(file: &Option<String>, shas: &HashMap<&str, &'a str>) -> Option<&'a str> {
+                        file.as_deref().and_then(|f| shas.get(f).copied())
+                    }
+                    if sha(&current.file, &current_sha) != sha(&previous.file, &previous_sha) {
+                        changed.push(format!("Updated {}", current.name));
+                    }
+                }
+            }
+        }
+
+        for (mod_id, previous) in &previous_by_id {
+            if !current_by_id.contains_key(mod_id) {
+                removed.push(format!("Removed {}", previous.name));
+            }
+        }
+
+        // Everything else, by path
+        let prev_map: HashMap<&str, &ManifestFile> = prev_files
             .iter()
+            .filter(|f| !previous_mod_paths.contains(f.path.as_str()))
             .map(|f| (f.path.as_str(), f))
             .collect();
 
-        // Find added and changed files
-        for file in current_files {
+        for file in current_files.iter().filter(|f| !current_mod_paths.contains(f.path.as_str())) {
             if let Some(prev_file) = prev_map.get(file.path.as_str()) {
                 // File exists in both - check if changed
                 if file.sha256 != prev_file.sha256 {
-                    changed.push(describe_file_change(file, Some(prev_file)));
+                    changed.push(format!("Updated {}", describe_file_change(file, Some(prev_file))));
                 }
             } else {
                 // File is new
-                added.push(describe_file(&file.path));
+                added.push(format!("Added {}", describe_file(&file.path)));
             }
         }
 
-        // Find removed files
         let current_paths: HashSet<&str> = current_files.iter().map(|f| f.path.as_str()).collect();
 
-        for prev_file in prev_files {
+        for prev_file in prev_map.values() {
             if !current_paths.contains(prev_file.path.as_str()) {
-                removed.push(describe_file(&prev_file.path));
+                removed.push(format!("Removed {}", describe_file(&prev_file.path)));
             }
         }
     } else {
         // No previous version - all files are new
-        for file in current_files {
-            added.push(describe_file(&file.path));
+        for m in current_mods {
+            added.push(format!("Added {}", m.name));
+        }
+        for file in current_files.iter().filter(|f| !current_mod_paths.contains(f.path.as_str())) {
+            added.push(format!("Added {}", describe_file(&file.path)));
         }
     }
 
+    added.sort();
+    changed.sort();
+    removed.sort();
+
     // Generate markdown
     let markdown = format_changelog(&added, &changed, &removed);
 
@@ -98,8 +145,9 @@ fn describe_file_change(current: &DraftFile, previous: Option<&ManifestFile>) ->
                 return format!("{} ({} → {})", name, prev_ver, curr_ver);
             }
 
+            // The entry already reads "Updated ...", so the name alone is enough
             if let Some(name) = extract_mod_name(current_filename) {
-                return format!("{} (updated)", name);
+                return name;
             }
         }
     }
@@ -118,11 +166,11 @@ fn extract_mod_name(filename: &str) -> Option<String> {
     if let Some(captures) = version_regex.captures(name) {
         if let Some(m) = captures.get(0) {
             let cleaned = name[..m.start()].to_string();
-            return Some(cleaned.replace('-', " ").replace('_', " "));
+            return Some(cleaned.replace(['-', '_'], " "));
         }
     }
 
-    Some(name.replace('-', " ").replace('_', " "))
+    Some(name.replace(['-', '_'], " "))
 }
 
 /// Extract version from filename
@@ -169,3 +217,75 @@ fn format_changelog(added: &[String], changed: &[String], removed: &[String]) ->
 
     sections.join("\n")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_support::mod_info;
+
+    fn draft_file(path: &str, sha256: &str) -> DraftFile {
+        DraftFile {
+            path: path.to_string(),
+            url: None,
+            sha256: sha256.to_string(),
+            size: 1,
+        }
+    }
+
+    fn manifest_file(path: &str, sha256: &str) -> ManifestFile {
+        ManifestFile {
+            path: path.to_string(),
+            url: String::new(),
+            sha256: sha256.to_string(),
+            size: 1,
+            mirrors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_mod_version_diff() {
+        let previous_files = vec![
+            manifest_file("mods/sodium-0.5.8.jar", "a"),
+            manifest_file("mods/hydrogen.jar", "b"),
+            manifest_file("config/sodium.json", "c"),
+            manifest_file("mods/custom-tweaks.jar", "g"),
+        ];
+        let previous_mods = vec![
+            mod_info("Sodium", "0.5.8", "mods/sodium-0.5.8.jar"),
+            mod_info("Hydrogen", "0.3", "mods/hydrogen.jar"),
+        ];
+        let current_files = vec![
+            draft_file("mods/sodium-0.5.11.jar", "d"),
+            draft_file("mods/lithium.jar", "e"),
+            draft_file("config/sodium.json", "f"),
+            draft_file("mods/custom-tweaks.jar", "h"),
+        ];
+        let current_mods = vec![
+            mod_info("Sodium", "0.5.11", "mods/sodium-0.5.11.jar"),
+            mod_info("Lithium", "0.11.2", "mods/lithium.jar"),
+        ];
+
+        let changelog =
+            generate_changelog(&current_files, Some(&previous_files), &current_mods, &previous_mods).unwrap();
+
+        assert_eq!(changelog.added, vec!["Added Lithium"]);
+        assert_eq!(
+            changelog.changed,
+            vec!["Updated Sodium 0.5.8 → 0.5.11", "Updated config/sodium.json", "Updated custom tweaks"]
+        );
+        assert_eq!(changelog.removed, vec!["Removed Hydrogen"]);
+        assert!(changelog.markdown.contains("## Changed"));
+    }
+
+    #[test]
+    fn test_unchanged_mod_is_not_listed() {
+        let previous_files = vec![manifest_file("mods/lithium.jar", "e")];
+        let previous_mods = vec![mod_info("Lithium", "0.11.2", "mods/lithium.jar")];
+        let current_files = vec![draft_file("mods/lithium.jar", "e")];
+
+        let changelog =
+            generate_changelog(&current_files, Some(&previous_files), &previous_mods, &previous_mods).unwrap();
+
+        assert_eq!(changelog.markdown, "No changes detected.");
+    }
+}
@@ -0,0 +1,150 @@
+use sha2::{Digest, Sha256};
+
+/// Stack frames that go into a crash signature
+const SIGNATURE_FRAMES: usize = 5;
+
+const MAX_SUMMARY_LEN: usize = 200;
+
+/// Grouping key for a crash report
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrashSignature {
+    /// Short hex hash; equal for crashes with the same cause
+    pub signature: String,
+    /// Exception line (or description) for display
+    pub summary: String,
+}
+
+/// Exception class at the start of a line, e.g. `java.lang.NullPointerException: ...`
+fn exception_class(line: &str) -> Option<&str> {
+    let line = line.trim().trim_start_matches("Caused by: ");
+    let class = line
+        .split(|c: char| c == ':' || c.is_whitespace())
+        .next()?;
+    let is_throwable = class.ends_with("Exception") || class.ends_with("Error") || class.ends_with("Throwable");
+    (is_throwable && class.contains('.')).then_some(class)
+}
+
+/// `at a.b.C.method(C.java:12) ~[mod.jar:?] {re:mixin}` -> `a.b.C.method`
+fn normalize_frame(line: &str) -> Option<String> {
+    let frame = line.trim().strip_prefix("at ")?;
+    let frame = frame.split('(').next()?.trim();
+    // Module prefixes like `java.base/` vary between JVMs
+    let frame = frame.rsplit('/').next().unwrap_or(frame);
+    (!frame.is_empty()).then(|| frame.to_string())
+}
+
+/// Derive a signature from a Minecraft crash report (or a log excerpt)
+///
+/// Uses the report description, the exception class (messages often contain
+/// coordinates or ids) and the top stack frames without line numbers, so the
+/// same bug maps to the same signature across modpack versions and machines.
+pub fn crash_signature(report: &str) -> CrashSignature {
+    let lines: Vec<&str> = report.lines().collect();
+
+    let description = lines
+        .iter()
+        .find_map(|l| l.trim().strip_prefix("Description:"))
+        .map(|d| d.trim())
+        .unwrap_or("");
+
+    let exception_index = lines.iter().position(|l| exception_class(l).is_some());
+
+    let (class, exception_line, frames) = match exception_index {
+        Some(index) => {
+            let frames: Vec<String> = lines[index + 1..]
+                .iter()
+                .take_while(|l| l.trim().starts_with("at ") || l.trim().is_empty())
+                .filter_map(|l| normalize_frame(l))
+                .take(SIGNATURE_FRAMES)
+                .collect();
+            (
+                exception_class(lines[index]).unwrap_or(""),
+                lines[index].trim(),
+                frames,
+            )
+        }
+        None => ("", "", Vec::new()),
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(description.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(class.as_bytes());
+    for frame in &frames {
+        hasher.update(b"\n");
+        hasher.update(frame.as_bytes());
+    }
+    let signature = format!("{:x}", hasher.finalize())[..16].to_string();
+
+    let summary = match (exception_line.is_empty(), description.is_empty()) {
+        (false, _) => exception_line,
+        (true, false) => description,
+        (true, true) => "Unknown crash",
+    };
+    let summary = if summary.chars().count() > MAX_SUMMARY_LEN {
+        format!("{}…", summary.chars().take(MAX_SUMMARY_LEN).collect::<String>())
+    } else {
+        summary.to_string()
+    };
+
+    CrashSignature { signature, summary }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = "---- Minecraft Crash Report ----
+// Who set us up the TNT?
+
+Time: 2026-01-10 20:14:55
+Description: Ticking entity
+
+java.lang.NullPointerException: Cannot invoke \"net.minecraft.class_1297.method_5667()\" because \"entity\" is null
+\tat net.minecraft.class_3218.method_18762(class_3218.java:752) ~[client-intermediary.jar:?]
+\tat com.example.mod.EntityHook.onTick(EntityHook.java:41) ~[examplemod-1.2.jar:?] {re:mixin}
+\tat java.base/java.lang.Thread.run(Thread.java:1583) [?:?]
+
+A detailed walkthrough of the error, its code path and all known details is as follows:
+";
+
+    #[test]
+    fn test_signature_ignores_line_numbers_and_messages() {
+        let a = crash_signature(REPORT);
+        let b = crash_signature(
+            &REPORT
+                .replace("752", "760")
+                .replace("because \\\"entity\\\" is null", "at 12, 64, -3")
+                .replace("Time: 2026-01-10 20:14:55", "Time: 2026-02-01 08:00:00"),
+        );
+
+        assert_eq!(a.signature, b.signature);
+        assert_eq!(a.signature.len(), 16);
+        assert!(a.summary.starts_with("java.lang.NullPointerException"));
+    }
+
+    #[test]
+    fn test_signature_differs_by_stack() {
+        let a = crash_signature(REPORT);
+        let b = crash_signature(&REPORT.replace("EntityHook.onTick", "EntityHook.onRemove"));
+        assert_ne!(a.signature, b.signature);
+    }
+
+    #[test]
+    fn test_normalize_frame() {
+        assert_eq!(
+            normalize_frame("\tat java.base/java.lang.Thread.run(Thread.java:1583) [?:?]").as_deref(),
+            Some("java.lang.Thread.run")
+        );
+        assert_eq!(normalize_frame("not a frame"), None);
+    }
+
+    #[test]
+    fn test_signature_without_exception() {
+        let sig = crash_signature("Description: Rendering overlay\n\nsomething went wrong");
+        assert_eq!(sig.summary, "Rendering overlay");
+
+        let sig = crash_signature("");
+        assert_eq!(sig.summary, "Unknown crash");
+    }
+}
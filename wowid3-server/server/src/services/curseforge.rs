@@ -0,0 +1,371 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::path::{Component, Path, PathBuf};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+use super::dependencies::DependencyQueue;
+
+const CURSEFORGE_API: &str = "https://api.curseforge.com/v1";
+
+/// CurseForge `ModLoaderType` for Fabric
+const FABRIC_LOADER_TYPE: u32 = 4;
+
+/// CurseForge `FileRelationType` for required dependencies
+const REQUIRED_DEPENDENCY: u32 = 3;
+
+/// CurseForge `HashAlgo` for SHA-1
+const HASH_ALGO_SHA1: u32 = 1;
+
+#[derive(Debug, Deserialize)]
+struct DataResponse<T> {
+    data: T,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeHash {
+    pub value: String,
+    pub algo: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeDependency {
+    pub mod_id: u64,
+    pub relation_type: u32,
+}
+
+/// A file (one released jar) of a CurseForge project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeFile {
+    pub id: u64,
+    pub mod_id: u64,
+    pub display_name: String,
+    pub file_name: String,
+    pub file_length: u64,
+    #[serde(default)]
+    pub hashes: Vec<CurseForgeHash>,
+    /// Null when the author has disabled third-party distribution
+    pub download_url: Option<String>,
+    #[serde(default)]
+    pub dependencies: Vec<CurseForgeDependency>,
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+}
+
+impl CurseForgeFile {
+    pub fn sha1(&self) -> Option<&str> {
+        self.hashes
+            .iter()
+            .find(|h| h.algo == HASH_ALGO_SHA1)
+            .map(|h| h.value.as_str())
+    }
+}
+
+/// `manifest.json` from a CurseForge modpack export
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgePackManifest {
+    pub minecraft: CurseForgePackMinecraft,
+    pub name: Option<String>,
+    pub files: Vec<CurseForgePackFile>,
+    #[serde(default = "default_overrides")]
+    pub overrides: String,
+}
+
+fn default_overrides() -> String {
+    "overrides".to_string()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgePackMinecraft {
+    pub version: String,
+    #[serde(default)]
+    pub mod_loaders: Vec<CurseForgePackLoader>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurseForgePackLoader {
+    /// e.g. `fabric-0.15.7`
+    pub id: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurseForgePackFile {
+    #[serde(rename = "projectID")]
+    pub project_id: u64,
+    #[serde(rename = "fileID")]
+    pub file_id: u64,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+impl CurseForgePackManifest {
+    /// Fabric loader version from the primary `fabric-x.y.z` loader entry
+    pub fn fabric_loader(&self) -> Option<String> {
+        let mut loaders = self.minecraft.mod_loaders.iter().collect::<Vec<_>>();
+        loaders.sort_by_key(|l| !l.primary);
+        loaders
+            .into_iter()
+            .find_map(|l| l.id.strip_prefix("fabric-").map(|v| v.to_string()))
+    }
+
+    /// The overrides folder inside the extracted export at `pack_dir`
+    ///
+    /// `overrides` comes from the uploaded manifest, so only a single plain folder name
+    /// is accepted; absolute paths, `..` and nested paths could reach outside the upload.
+    pub fn overrides_dir(&self, pack_dir: &Path) -> Result<PathBuf> {
+        let mut components = Path::new(&self.overrides).components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(name)), None) => Ok(pack_dir.join(name)),
+            _ => anyhow::bail!("Invalid overrides folder {:?} in manifest.json", self.overrides),
+        }
+    }
+}
+
+/// Minimal CurseForge Core API client
+#[derive(Clone)]
+pub struct CurseForgeClient {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: String,
+}
+
+impl CurseForgeClient {
+    pub fn new(api_key: &str) -> Result<Self> {
+        Self::with_base_url(CURSEFORGE_API, api_key)
+    }
+
+    pub fn with_base_url(base_url: &str, api_key: &str) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(60))
+            .build()
+            .context("Failed to build CurseForge HTTP client")?;
+
+        Ok(Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+        })
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str, query: &[(&str, String)]) -> Result<T> {
+        let response: DataResponse<T> = self
+            .http
+            .get(format!("{}{}", self.base_url, path))
+            .header("x-api-key", &self.api_key)
+            .query(query)
+            .send()
+            .await
+            .with_context(|| format!("CurseForge request {} failed", path))?
+            .error_for_status()
+            .with_context(|| format!("CurseForge request {} returned an error", path))?
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse CurseForge response for {}", path))?;
+
+        Ok(response.data)
+    }
+
+    /// Fabric files of a mod for a Minecraft version, newest first
+    pub async fn compatible_files(&self, mod_id: u64, minecraft_version: &str) -> Result<Vec<CurseForgeFile>> {
+        self.get(
+            &format!("/mods/{}/files", mod_id),
+            &[
+                ("gameVersion", minecraft_version.to_string()),
+                ("modLoaderType", FABRIC_LOADER_TYPE.to_string()),
+            ],
+        )
+        .await
+    }
+
+    pub async fn get_file(&self, mod_id: u64, file_id: u64) -> Result<CurseForgeFile> {
+        self.get(&format!("/mods/{}/files/{}", mod_id, file_id), &[]).await
+    }
+
+    /// Fetch many files in one request (used for modpack imports)
+    pub async fn get_files(&self, file_ids: &[u64]) -> Result<Vec<CurseForgeFile>> {
+        let response: DataResponse<Vec<CurseForgeFile>> = self
+            .http
+            .post(format!("{}/mods/files", self.base_url))
+            .header("x-api-key", &self.api_key)
+            .json(&serde_json::json!({ "fileIds": file_ids }))
+            .send()
+            .await
+            .context("CurseForge batch file request failed")?
+            .error_for_status()
+            .context("CurseForge batch file request returned an error")?
+            .json()
+            .await
+            .context("Failed to parse CurseForge batch file response")?;
+
+        Ok(response.data)
+    }
+
+    /// Resolve required dependencies of `root` (transitively)
+    ///
+    /// Returns the files to install and the mod ids with no compatible file.
+    pub async fn resolve_with_dependencies(
+        &self,
+        root: CurseForgeFile,
+        minecraft_version: &str,
+    ) -> Result<(Vec<CurseForgeFile>, Vec<u64>)> {
+        let mut resolved = Vec::new();
+        let mut unresolved = Vec::new();
+        let mut queue = DependencyQueue::new(root, |file: &CurseForgeFile| file.mod_id);
+
+        while let Some(file) = queue.next()? {
+            for dep in file.dependencies.iter().filter(|d| d.relation_type == REQUIRED_DEPENDENCY) {
+                if queue.visited(&dep.mod_id) {
+                    continue;
+                }
+                match self.compatible_files(dep.mod_id, minecraft_version).await?.into_iter().next() {
+                    Some(dep_file) => queue.push(dep_file),
+                    None => unresolved.push(dep.mod_id),
+                }
+            }
+
+            resolved.push(file);
+        }
+
+        Ok((resolved, unresolved))
+    }
+
+    /// Download a file to `dest`, verifying CurseForge's sha1; returns the sha256
+    pub async fn download(&self, file: &CurseForgeFile, dest: &Path) -> Result<String> {
+        let url = file.download_url.as_deref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} does not allow third-party downloads; add it manually",
+                file.file_name
+            )
+        })?;
+
+        let mut response = self
+            .http
+            .get(url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to download {}", file.file_name))?
+            .error_for_status()
+            .with_context(|| format!("Download of {} failed", file.file_name))?;
+
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let temp = dest.with_extension("download");
+        let mut out = fs::File::create(&temp)
+            .await
+            .with_context(|| format!("Failed to create {}", temp.display()))?;
+        let mut sha256 = Sha256::new();
+        let mut sha1 = Sha1::new();
+
+        while let Some(chunk) = response.chunk().await.context("Download interrupted")? {
+            sha256.update(&chunk);
+            sha1.update(&chunk);
+            out.write_all(&chunk).await?;
+        }
+        out.flush().await?;
+        drop(out);
+
+        if let Some(expected) = file.sha1() {
+            let actual = format!("{:x}", sha1.finalize());
+            if !actual.eq_ignore_ascii_case(expected) {
+                let _ = fs::remove_file(&temp).await;
+                anyhow::bail!("Checksum mismatch for {}", file.file_name);
+            }
+        }
+
+        fs::rename(&temp, dest)
+            .await
+            .with_context(|| format!("Failed to move {} into place", file.file_name))?;
+
+        Ok(format!("{:x}", sha256.finalize()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pack_manifest() {
+        let manifest: CurseForgePackManifest = serde_json::from_str(
+            r#"{
+                "minecraft": {
+                    "version": "1.20.1",
+                    "modLoaders": [
+                        { "id": "forge-47.2.0", "primary": false },
+                        { "id": "fabric-0.15.7", "primary": true }
+                    ]
+                },
+                "manifestType": "minecraftModpack",
+                "name": "Pack",
+                "version": "1.0.0",
+                "files": [
+                    { "projectID": 394468, "fileID": 4715408, "required": true },
+                    { "projectID": 306612, "fileID": 4642902 }
+                ],
+                "overrides": "overrides"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.minecraft.version, "1.20.1");
+        assert_eq!(manifest.fabric_loader().as_deref(), Some("0.15.7"));
+        assert_eq!(manifest.files.len(), 2);
+        assert!(manifest.files[1].required);
+    }
+
+    #[test]
+    fn test_overrides_dir_stays_in_pack() {
+        let manifest = |overrides: &str| CurseForgePackManifest {
+            minecraft: CurseForgePackMinecraft {
+                version: "1.20.1".to_string(),
+                mod_loaders: Vec::new(),
+            },
+            name: None,
+            files: Vec::new(),
+            overrides: overrides.to_string(),
+        };
+        let pack_dir = Path::new("/srv/uploads/pack");
+
+        assert_eq!(
+            manifest("overrides").overrides_dir(pack_dir).unwrap(),
+            pack_dir.join("overrides")
+        );
+        for malicious in ["/etc", "../../..", "..", "overrides/../../etc", "a/b", ".", ""] {
+            assert!(
+                manifest(malicious).overrides_dir(pack_dir).is_err(),
+                "{:?} was accepted",
+                malicious
+            );
+        }
+    }
+
+    #[test]
+    fn test_file_sha1() {
+        let file: CurseForgeFile = serde_json::from_str(
+            r#"{
+                "id": 1, "modId": 2, "displayName": "Mod", "fileName": "mod.jar",
+                "fileLength": 10, "downloadUrl": null,
+                "hashes": [{ "value": "md5value", "algo": 2 }, { "value": "sha1value", "algo": 1 }]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(file.sha1(), Some("sha1value"));
+        assert!(file.download_url.is_none());
+    }
+}
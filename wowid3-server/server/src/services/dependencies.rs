@@ -0,0 +1,76 @@
+use anyhow::Result;
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Upper bound on dependency resolution, in case of cycles or huge trees
+pub const MAX_RESOLVED_MODS: usize = 64;
+
+/// Breadth-first walk over a mod's required dependencies, shared by the Modrinth
+/// and CurseForge installers
+///
+/// Each project is handed out once, keyed by `key`; the walk fails once more than
+/// [`MAX_RESOLVED_MODS`] have been handed out.
+pub struct DependencyQueue<T, K> {
+    queue: VecDeque<T>,
+    seen: HashSet<K>,
+    key: fn(&T) -> K,
+    handed_out: usize,
+}
+
+impl<T, K: Eq + Hash> DependencyQueue<T, K> {
+    pub fn new(root: T, key: fn(&T) -> K) -> Self {
+        Self {
+            queue: VecDeque::from([root]),
+            seen: HashSet::new(),
+            key,
+            handed_out: 0,
+        }
+    }
+
+    /// The next project whose dependencies to look up, skipping ones already visited
+    pub fn next(&mut self) -> Result<Option<T>> {
+        while let Some(item) = self.queue.pop_front() {
+            if !self.seen.insert((self.key)(&item)) {
+                continue;
+            }
+            if self.handed_out >= MAX_RESOLVED_MODS {
+                anyhow::bail!("Too many dependencies (limit {})", MAX_RESOLVED_MODS);
+            }
+            self.handed_out += 1;
+            return Ok(Some(item));
+        }
+        Ok(None)
+    }
+
+    /// Whether a project was already visited, so looking up its versions can be skipped
+    pub fn visited(&self, key: &K) -> bool {
+        self.seen.contains(key)
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.queue.push_back(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_projects_visited_once_and_limited() {
+        let mut queue = DependencyQueue::new(1u32, |id| *id);
+        assert_eq!(queue.next().unwrap(), Some(1));
+        queue.push(2);
+        queue.push(1);
+        assert!(queue.visited(&1));
+        assert_eq!(queue.next().unwrap(), Some(2));
+        assert_eq!(queue.next().unwrap(), None);
+
+        let mut queue = DependencyQueue::new(0usize, |id| *id);
+        for id in 1..=MAX_RESOLVED_MODS {
+            queue.next().unwrap();
+            queue.push(id);
+        }
+        assert!(queue.next().is_err());
+    }
+}
@@ -0,0 +1,245 @@
+use crate::models::{ChangedFile, DiffFile, DiffMod, DraftFile, DraftRelease, Manifest, ModInfo, ReleaseDiff};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+fn diff_mod(info: &ModInfo) -> DiffMod {
+    DiffMod {
+        mod_id: info.mod_id.clone(),
+        name: info.name.clone(),
+        version: info.version.clone(),
+    }
+}
+
+/// Compare a draft against a published release, file by file
+///
+/// Jars with mod metadata (see `analyzer::scan_mods`) are paired by mod id, so
+/// a version bump that renames the jar is one changed file. Everything else is
+/// paired by path and compared by checksum.
+pub fn diff_release(
+    draft: &DraftRelease,
+    base: &Manifest,
+    draft_mods: &[ModInfo],
+    base_mods: &[ModInfo],
+) -> ReleaseDiff {
+    let current: HashMap<&str, &DraftFile> = draft.files.iter().map(|f| (f.path.as_str(), f)).collect();
+    let previous: HashMap<&str, _> = base.files.iter().map(|f| (f.path.as_str(), f)).collect();
+
+    let current_mods: HashMap<&str, &ModInfo> = draft_mods
+        .iter()
+        .filter_map(|m| m.file.as_deref().filter(|f| current.contains_key(f)).map(|f| (f, m)))
+        .collect();
+    let previous_mods: HashMap<&str, &ModInfo> = base_mods
+        .iter()
+        .filter_map(|m| m.file.as_deref().filter(|f| previous.contains_key(f)).map(|f| (f, m)))
+        .collect();
+
+    // Pair jars by mod id; a jar shipping several ids is paired on the first match
+    let previous_by_id: BTreeMap<&str, &str> = previous_mods
+        .iter()
+        .map(|(path, m)| (m.mod_id.as_str(), *path))
+        .collect();
+    let mut pairs: Vec<(&str, &str)> = Vec::new();
+    let mut paired_current = HashSet::new();
+    let mut paired_previous = HashSet::new();
+    for (path, m) in &current_mods {
+        if let Some(previous_path) = previous_by_id.get(m.mod_id.as_str()) {
+            if !paired_current.contains(path) && !paired_previous.contains(previous_path) {
+                paired_current.insert(*path);
+                paired_previous.insert(*previous_path);
+                pairs.push((*path, *previous_path));
+            }
+        }
+    }
+
+    // Remaining files pair by path
+    for path in current.keys() {
+        if !paired_current.contains(path) && !paired_previous.contains(path) && previous.contains_key(path) {
+            paired_current.insert(*path);
+            paired_previous.insert(*path);
+            pairs.push((*path, *path));
+        }
+    }
+
+    let mut changed = Vec::new();
+    let mut unchanged = 0;
+    for (path, previous_path) in pairs {
+        let file = current[path];
+        let previous_file = previous[previous_path];
+        if path == previous_path && file.sha256 == previous_file.sha256 {
+            unchanged += 1;
+            continue;
+        }
+        changed.push(ChangedFile {
+            path: path.to_string(),
+            previous_path: (path != previous_path).then(|| previous_path.to_string()),
+            size: file.size,
+            previous_size: previous_file.size,
+            sha256: file.sha256.clone(),
+            previous_sha256: previous_file.sha256.clone(),
+            mod_info: current_mods.get(path).map(|m| diff_mod(m)),
+            previous_mod: previous_mods.get(previous_path).map(|m| diff_mod(m)),
+        });
+    }
+
+    let mut added: Vec<DiffFile> = draft
+        .files
+        .iter()
+        .filter(|f| !paired_current.contains(f.path.as_str()))
+        .map(|f| DiffFile {
+            path: f.path.clone(),
+            size: f.size,
+            sha256: f.sha256.clone(),
+            mod_info: current_mods.get(f.path.as_str()).map(|m| diff_mod(m)),
+        })
+        .collect();
+
+    let mut removed: Vec<DiffFile> = base
+        .files
+        .iter()
+        .filter(|f| !paired_previous.contains(f.path.as_str()))
+        .map(|f| DiffFile {
+            path: f.path.clone(),
+            size: f.size,
+            sha256: f.sha256.clone(),
+            mod_info: previous_mods.get(f.path.as_str()).map(|m| diff_mod(m)),
+        })
+        .collect();
+
+    added.sort_by(|a, b| a.path.cmp(&b.path));
+    removed.sort_by(|a, b| a.path.cmp(&b.path));
+    changed.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let download_size = added.iter().map(|f| f.size).sum::<u64>() + changed.iter().map(|f| f.size).sum::<u64>();
+    let draft_size: u64 = draft.files.iter().map(|f| f.size).sum();
+    let base_size: u64 = base.files.iter().map(|f| f.size).sum();
+
+    ReleaseDiff {
+        base_version: base.version.clone(),
+        draft_version: draft.version.clone(),
+        previous_minecraft_version: base.minecraft_version.clone(),
+        minecraft_version: draft.minecraft_version.clone(),
+        previous_fabric_loader: base.fabric_loader.clone(),
+        fabric_loader: draft.fabric_loader.clone(),
+        added,
+        removed,
+        changed,
+        unchanged,
+        download_size,
+        size_delta: draft_size as i64 - base_size as i64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_support::mod_info;
+    use crate::models::{ManifestFile, ModLoader, ReleaseChannel};
+
+    fn draft_file(path: &str, sha256: &str, size: u64) -> DraftFile {
+        DraftFile {
+            path: path.to_string(),
+            url: None,
+            sha256: sha256.to_string(),
+            size,
+        }
+    }
+
+    fn manifest_file(path: &str, sha256: &str, size: u64) -> ManifestFile {
+        ManifestFile {
+            path: path.to_string(),
+            url: String::new(),
+            sha256: sha256.to_string(),
+            size,
+            mirrors: Vec::new(),
+        }
+    }
+
+    fn draft(files: Vec<DraftFile>) -> DraftRelease {
+        DraftRelease {
+            id: uuid::Uuid::new_v4(),
+            version: "1.0.1".to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
+            fabric_loader: "0.15.0".to_string(),
+            changelog: String::new(),
+            files,
+            channel: ReleaseChannel::default(),
+            optional_groups: vec![],
+            ignore_overrides: Default::default(),
+            revision: 0,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn manifest(files: Vec<ManifestFile>) -> Manifest {
+        let mut manifest = Manifest::new(
+            "0.9.0".to_string(),
+            "1.20.1".to_string(),
+            "0.14.0".to_string(),
+            String::new(),
+            Vec::new(),
+        );
+        manifest.files = files;
+        manifest
+    }
+
+    #[test]
+    fn test_diff_pairs_mods_by_id() {
+        let base = manifest(vec![
+            manifest_file("mods/sodium-0.5.8.jar", "a", 100),
+            manifest_file("mods/hydrogen.jar", "b", 50),
+            manifest_file("config/sodium.json", "c", 10),
+            manifest_file("config/keep.toml", "k", 5),
+        ]);
+        let base_mods = vec![
+            mod_info("sodium", "0.5.8", "mods/sodium-0.5.8.jar"),
+            mod_info("hydrogen", "0.3", "mods/hydrogen.jar"),
+        ];
+        let draft = draft(vec![
+            draft_file("mods/sodium-0.5.11.jar", "d", 120),
+            draft_file("mods/lithium.jar", "e", 70),
+            draft_file("config/sodium.json", "f", 12),
+            draft_file("config/keep.toml", "k", 5),
+        ]);
+        let draft_mods = vec![
+            mod_info("sodium", "0.5.11", "mods/sodium-0.5.11.jar"),
+            mod_info("lithium", "0.11.2", "mods/lithium.jar"),
+        ];
+
+        let diff = diff_release(&draft, &base, &draft_mods, &base_mods);
+
+        assert_eq!(diff.base_version, "0.9.0");
+        assert_eq!(diff.previous_fabric_loader, "0.14.0");
+        assert_eq!(diff.unchanged, 1);
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].path, "mods/lithium.jar");
+        assert_eq!(diff.added[0].mod_info.as_ref().unwrap().mod_id, "lithium");
+
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].mod_info.as_ref().unwrap().mod_id, "hydrogen");
+
+        assert_eq!(diff.changed.len(), 2);
+        assert_eq!(diff.changed[0].path, "config/sodium.json");
+        assert_eq!(diff.changed[0].previous_path, None);
+        assert_eq!(diff.changed[1].path, "mods/sodium-0.5.11.jar");
+        assert_eq!(diff.changed[1].previous_path.as_deref(), Some("mods/sodium-0.5.8.jar"));
+        assert_eq!(diff.changed[1].previous_mod.as_ref().unwrap().version, "0.5.8");
+        assert_eq!(diff.changed[1].mod_info.as_ref().unwrap().version, "0.5.11");
+
+        assert_eq!(diff.download_size, 70 + 12 + 120);
+        assert_eq!(diff.size_delta, (120 + 70 + 12 + 5) - (100 + 50 + 10 + 5));
+    }
+
+    #[test]
+    fn test_identical_release_has_no_changes() {
+        let base = manifest(vec![manifest_file("mods/a.jar", "a", 1)]);
+        let draft = draft(vec![draft_file("mods/a.jar", "a", 1)]);
+        let mods = vec![mod_info("a", "1.0", "mods/a.jar")];
+
+        let diff = diff_release(&draft, &base, &mods, &mods);
+        assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty());
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.download_size, 0);
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::services::minecraft_auth::VerifiedProfile;
+
+/// Audience of download tokens, so no other token signed with the same key passes
+const DOWNLOAD_AUDIENCE: &str = "wowid3-downloads";
+
+/// Claims of a download token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadClaims {
+    /// Hyphenated Minecraft UUID of the player
+    pub sub: String,
+    pub name: String,
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Issues and validates HS256-signed download tokens for whitelisted players
+pub struct DownloadTokens {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    ttl_secs: i64,
+}
+
+impl DownloadTokens {
+    pub fn new(secret: &[u8], ttl_secs: i64) -> Self {
+        Self {
+            encoding: EncodingKey::from_secret(secret),
+            decoding: DecodingKey::from_secret(secret),
+            ttl_secs,
+        }
+    }
+
+    /// Build from config
+    ///
+    /// Without DOWNLOAD_TOKEN_SECRET the key is derived from JWT_SECRET, so tokens
+    /// survive restarts whenever admin sessions do; otherwise it is random per process.
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        let secret = match (&config.download_token_secret, &config.jwt_secret) {
+            (Some(secret), _) if !secret.is_empty() => secret.as_bytes().to_vec(),
+            (_, Some(jwt_secret)) if !jwt_secret.is_empty() => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(jwt_secret.as_bytes())
+                    .expect("HMAC accepts any key length");
+                mac.update(DOWNLOAD_AUDIENCE.as_bytes());
+                mac.finalize().into_bytes().to_vec()
+            }
+            _ => rand::thread_rng().gen::<[u8; 32]>().to_vec(),
+        };
+        Self::new(&secret, config.download_token_ttl_secs)
+    }
+
+    /// Sign a token for `profile`; returns it with its expiry (Unix seconds)
+    pub fn issue(&self, profile: &VerifiedProfile) -> Result<(String, i64)> {
+        let now = chrono::Utc::now().timestamp();
+        let claims = DownloadClaims {
+            sub: profile.uuid.clone(),
+            name: profile.name.clone(),
+            aud: DOWNLOAD_AUDIENCE.to_string(),
+            iat: now,
+            exp: now + self.ttl_secs,
+        };
+        let token = encode(&Header::new(Algorithm::HS256), &claims, &self.encoding)
+            .context("Failed to sign download token")?;
+        Ok((token, claims.exp))
+    }
+
+    /// Validate signature, audience and expiry
+    pub fn verify(&self, token: &str) -> Result<DownloadClaims> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.leeway = 0;
+        validation.set_audience(&[DOWNLOAD_AUDIENCE]);
+
+        let data = decode::<DownloadClaims>(token, &self.decoding, &validation)
+            .context("Invalid or expired download token")?;
+        Ok(data.claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::AdminRole;
+    use crate::services::jwt::JwtManager;
+
+    fn profile() -> VerifiedProfile {
+        VerifiedProfile {
+            uuid: "069a79f4-44e9-4726-a5be-fca90e38aaf5".to_string(),
+            name: "Notch".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_issue_and_verify() {
+        let tokens = DownloadTokens::new(b"test-secret", 60);
+        let (token, expires_at) = tokens.issue(&profile()).unwrap();
+
+        let claims = tokens.verify(&token).unwrap();
+        assert_eq!(claims.sub, profile().uuid);
+        assert_eq!(claims.exp, expires_at);
+
+        let expired = DownloadTokens::new(b"test-secret", -10);
+        assert!(tokens.verify(&expired.issue(&profile()).unwrap().0).is_err());
+        assert!(DownloadTokens::new(b"other-secret", 60).verify(&token).is_err());
+    }
+
+    #[test]
+    fn test_admin_tokens_are_not_download_tokens() {
+        let jwt = JwtManager::new(b"test-secret", 60, 120);
+        let pair = jwt.issue_pair("admin", AdminRole::Superadmin).unwrap();
+        assert!(DownloadTokens::new(b"test-secret", 60).verify(&pair.access_token).is_err());
+    }
+}
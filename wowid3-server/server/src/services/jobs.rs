@@ -0,0 +1,145 @@
+use anyhow::Result;
+use std::future::Future;
+use std::pin::Pin;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::tasks::{TaskHandle, TaskRegistry};
+use crate::database::{self, jobs::JobRecord, Database};
+
+type JobFuture = Pin<Box<dyn Future<Output = Result<serde_json::Value, String>> + Send>>;
+type JobFn = Box<dyn FnOnce(TaskHandle) -> JobFuture + Send>;
+
+struct QueuedJob {
+    task: TaskHandle,
+    work: JobFn,
+}
+
+/// Serial queue for heavy admin operations (publishing, release creation)
+///
+/// Jobs run one at a time on a single worker so concurrent publishes can't race
+/// on the release directory or the latest manifest. Job state is persisted in
+/// SQLite so results outlive the in-memory task registry; live progress is
+/// reported through the task with the same id.
+#[derive(Clone)]
+pub struct JobQueue {
+    db: Database,
+    tasks: TaskRegistry,
+    sender: mpsc::UnboundedSender<QueuedJob>,
+}
+
+impl JobQueue {
+    /// Start the worker, failing any jobs a previous process left unfinished
+    pub async fn start(db: Database, tasks: TaskRegistry) -> Result<Self> {
+        let interrupted = database::jobs::fail_interrupted(&db.conn).await?;
+        if interrupted > 0 {
+            tracing::warn!("Marked {} interrupted job(s) as failed", interrupted);
+        }
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run_worker(db.clone(), receiver));
+
+        Ok(Self { db, tasks, sender })
+    }
+
+    /// Queue `work` and return its job id (also usable as a task id for progress)
+    pub async fn enqueue<F, Fut, E>(&self, kind: &str, created_by: &str, work: F) -> Result<Uuid>
+    where
+        F: FnOnce(TaskHandle) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<serde_json::Value, E>> + Send + 'static,
+        E: std::fmt::Display + Send + 'static,
+    {
+        let task = self.tasks.create(kind);
+        let id = task.id();
+
+        database::jobs::insert_job(&self.db.conn, &id.to_string(), kind, created_by).await?;
+
+        let work: JobFn = Box::new(move |task| {
+            Box::pin(async move { work(task).await.map_err(|e| e.to_string()) })
+        });
+
+        if self.sender.send(QueuedJob { task: task.clone(), work }).is_err() {
+            let error = "Job worker is not running".to_string();
+            database::jobs::mark_finished(&self.db.conn, &id.to_string(), Err(error.clone())).await?;
+            task.finish(Err(error));
+            anyhow::bail!("Job worker is not running");
+        }
+
+        tracing::info!("Queued {} job {} for {}", kind, id, created_by);
+        Ok(id)
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<Option<JobRecord>> {
+        database::jobs::get_job(&self.db.conn, &id.to_string()).await
+    }
+}
+
+async fn run_worker(db: Database, mut receiver: mpsc::UnboundedReceiver<QueuedJob>) {
+    while let Some(job) = receiver.recv().await {
+        let id = job.task.id().to_string();
+
+        if let Err(e) = database::jobs::mark_running(&db.conn, &id).await {
+            tracing::error!("Failed to mark job {} running: {}", id, e);
+        }
+        job.task.stage("Starting", None);
+
+        // Run on its own task so a panicking job doesn't take the worker down
+        let result = match tokio::spawn((job.work)(job.task.clone())).await {
+            Ok(result) => result,
+            Err(e) => Err(format!("Job panicked: {}", e)),
+        };
+
+        if let Err(e) = database::jobs::mark_finished(&db.conn, &id, result.clone()).await {
+            tracing::error!("Failed to record result of job {}: {}", id, e);
+        }
+        job.task.finish(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_jobs_run_in_order_and_persist() {
+        let temp_dir = tempdir().unwrap();
+        let db = Database::new(temp_dir.path().join("test.db")).await.unwrap();
+        db.init_schema().await.unwrap();
+
+        let tasks = TaskRegistry::new();
+        let queue = JobQueue::start(db, tasks.clone()).await.unwrap();
+
+        let first = queue
+            .enqueue("test", "alice", |task| async move {
+                task.stage("Working", Some(1));
+                Ok::<_, String>(serde_json::json!({ "n": 1 }))
+            })
+            .await
+            .unwrap();
+        let second = queue
+            .enqueue("test", "alice", |_task| async move {
+                Err::<serde_json::Value, _>("boom".to_string())
+            })
+            .await
+            .unwrap();
+
+        let (snapshot, mut updates) = tasks.subscribe(second).unwrap();
+        if !snapshot.is_finished() {
+            while let Ok(status) = updates.recv().await {
+                if status.is_finished() {
+                    break;
+                }
+            }
+        }
+
+        let first = queue.get(first).await.unwrap().unwrap();
+        assert_eq!(first.status, "completed");
+        assert_eq!(first.result, Some(serde_json::json!({ "n": 1 })));
+        assert_eq!(first.created_by, "alice");
+
+        let second = queue.get(second).await.unwrap().unwrap();
+        assert_eq!(second.status, "failed");
+        assert_eq!(second.error.as_deref(), Some("boom"));
+    }
+}
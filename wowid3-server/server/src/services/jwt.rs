@@ -0,0 +1,135 @@
+use anyhow::{Context, Result};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::models::AdminRole;
+
+/// Distinguishes short-lived access tokens from refresh tokens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenType {
+    Access,
+    Refresh,
+}
+
+/// JWT claims for admin tokens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    /// Subject (admin identity)
+    pub sub: String,
+    /// Unique token ID, used for revocation
+    pub jti: String,
+    pub iat: i64,
+    pub exp: i64,
+    pub typ: TokenType,
+    /// Role at issue time; tokens without one are treated as read-only
+    #[serde(default)]
+    pub role: AdminRole,
+}
+
+/// Access + refresh token pair returned on login/refresh
+#[derive(Debug, Clone)]
+pub struct TokenPair {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_in: i64,
+}
+
+/// Issues and validates HS256-signed admin tokens
+pub struct JwtManager {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    access_ttl_secs: i64,
+    refresh_ttl_secs: i64,
+}
+
+impl JwtManager {
+    pub fn new(secret: &[u8], access_ttl_secs: i64, refresh_ttl_secs: i64) -> Self {
+        Self {
+            encoding: EncodingKey::from_secret(secret),
+            decoding: DecodingKey::from_secret(secret),
+            access_ttl_secs,
+            refresh_ttl_secs,
+        }
+    }
+
+    /// Build from config, generating a random secret when none is configured
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        let secret = match &config.jwt_secret {
+            Some(secret) if !secret.is_empty() => secret.as_bytes().to_vec(),
+            _ => rand::thread_rng().gen::<[u8; 32]>().to_vec(),
+        };
+        Self::new(&secret, config.jwt_expiry_secs, config.jwt_refresh_expiry_secs)
+    }
+
+    fn issue(&self, subject: &str, role: AdminRole, typ: TokenType, ttl_secs: i64) -> Result<String> {
+        let now = chrono::Utc::now().timestamp();
+        let claims = Claims {
+            sub: subject.to_string(),
+            jti: uuid::Uuid::new_v4().to_string(),
+            iat: now,
+            exp: now + ttl_secs,
+            typ,
+            role,
+        };
+
+        encode(&Header::new(Algorithm::HS256), &claims, &self.encoding)
+            .context("Failed to sign token")
+    }
+
+    /// Issue a fresh access/refresh token pair
+    pub fn issue_pair(&self, subject: &str, role: AdminRole) -> Result<TokenPair> {
+        Ok(TokenPair {
+            access_token: self.issue(subject, role, TokenType::Access, self.access_ttl_secs)?,
+            refresh_token: self.issue(subject, role, TokenType::Refresh, self.refresh_ttl_secs)?,
+            expires_in: self.access_ttl_secs,
+        })
+    }
+
+    /// Validate signature, expiry and token type
+    pub fn verify(&self, token: &str, expected: TokenType) -> Result<Claims> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.leeway = 0;
+
+        let data = decode::<Claims>(token, &self.decoding, &validation)
+            .context("Invalid or expired token")?;
+
+        if data.claims.typ != expected {
+            anyhow::bail!("Wrong token type");
+        }
+
+        Ok(data.claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_and_verify_pair() {
+        let jwt = JwtManager::new(b"test-secret", 60, 120);
+        let pair = jwt.issue_pair("admin", AdminRole::Superadmin).unwrap();
+
+        let access = jwt.verify(&pair.access_token, TokenType::Access).unwrap();
+        assert_eq!(access.sub, "admin");
+        assert_eq!(access.role, AdminRole::Superadmin);
+        assert_eq!(pair.expires_in, 60);
+
+        // Tokens can't be used in place of each other
+        assert!(jwt.verify(&pair.refresh_token, TokenType::Access).is_err());
+        assert!(jwt.verify(&pair.access_token, TokenType::Refresh).is_err());
+    }
+
+    #[test]
+    fn test_rejects_expired_and_foreign_tokens() {
+        let jwt = JwtManager::new(b"test-secret", -10, -10);
+        let pair = jwt.issue_pair("admin", AdminRole::Superadmin).unwrap();
+        assert!(jwt.verify(&pair.access_token, TokenType::Access).is_err());
+
+        let other = JwtManager::new(b"other-secret", 60, 60);
+        let foreign = other.issue_pair("admin", AdminRole::Viewer).unwrap();
+        assert!(jwt.verify(&foreign.access_token, TokenType::Access).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// How long a verified access token is trusted before asking Mojang again
+const VERIFIED_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Bound on cached tokens; expired entries are pruned first
+const MAX_CACHED_TOKENS: usize = 1024;
+
+/// The Minecraft profile an access token belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifiedProfile {
+    /// Hyphenated Minecraft UUID
+    pub uuid: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileResponse {
+    /// Undashed UUID
+    id: String,
+    name: String,
+}
+
+/// Checks that a launcher's Minecraft access token (signed by Microsoft)
+/// belongs to the player it claims to be, via the Minecraft profile API
+#[derive(Clone)]
+pub struct ProfileVerifier {
+    client: reqwest::Client,
+    profile_url: String,
+    /// sha256(token) -> profile and when it was verified
+    cache: Arc<Mutex<HashMap<String, (VerifiedProfile, Instant)>>>,
+}
+
+impl ProfileVerifier {
+    pub fn new(profile_url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            profile_url: profile_url.into(),
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Resolve the profile of `access_token`; `Ok(None)` if Mojang rejects it
+    pub async fn verify(&self, access_token: &str) -> Result<Option<VerifiedProfile>> {
+        let key = format!("{:x}", Sha256::digest(access_token.as_bytes()));
+
+        if let Some((profile, verified_at)) = self.cache.lock().await.get(&key) {
+            if verified_at.elapsed() < VERIFIED_TTL {
+                return Ok(Some(profile.clone()));
+            }
+        }
+
+        let response = self
+            .client
+            .get(&self.profile_url)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .context("Failed to reach the Minecraft profile API")?;
+
+        if matches!(
+            response.status(),
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN | reqwest::StatusCode::NOT_FOUND
+        ) {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("Minecraft profile API returned {}", response.status());
+        }
+
+        let body: ProfileResponse = response.json().await.context("Invalid Minecraft profile response")?;
+        let uuid = uuid::Uuid::parse_str(&body.id)
+            .context("Invalid UUID in Minecraft profile response")?
+            .hyphenated()
+            .to_string();
+        let profile = VerifiedProfile { uuid, name: body.name };
+
+        let mut cache = self.cache.lock().await;
+        if cache.len() >= MAX_CACHED_TOKENS {
+            cache.retain(|_, (_, verified_at)| verified_at.elapsed() < VERIFIED_TTL);
+            if cache.len() >= MAX_CACHED_TOKENS {
+                cache.clear();
+            }
+        }
+        cache.insert(key, (profile.clone(), Instant::now()));
+
+        Ok(Some(profile))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cached_profile_skips_request() {
+        // Unroutable URL: only the cache can answer
+        let verifier = ProfileVerifier::new("http://127.0.0.1:9/minecraft/profile");
+        let profile = VerifiedProfile {
+            uuid: "069a79f4-44e9-4726-a5be-fca90e38aaf5".to_string(),
+            name: "Notch".to_string(),
+        };
+        let key = format!("{:x}", Sha256::digest(b"token"));
+        verifier.cache.lock().await.insert(key, (profile.clone(), Instant::now()));
+
+        assert_eq!(verifier.verify("token").await.unwrap(), Some(profile));
+        assert!(verifier.verify("other-token").await.is_err());
+    }
+}
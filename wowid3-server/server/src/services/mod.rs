@@ -1,6 +1,25 @@
 pub mod analyzer;
 pub mod changelog;
+pub mod crashes;
+pub mod curseforge;
+pub mod dependencies;
+pub mod diff;
+pub mod download_tokens;
+pub mod jobs;
+pub mod jwt;
+pub mod minecraft_auth;
+pub mod modrinth;
+pub mod notifications;
+pub mod password;
+pub mod reload;
+pub mod retention;
+pub mod search;
+pub mod signing;
 pub mod stats_processor;
+pub mod tasks;
+#[cfg(test)]
+pub mod test_support;
+pub mod validation;
 
 pub use analyzer::*;
 pub use changelog::*;
@@ -0,0 +1,327 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use std::path::Path;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+use super::dependencies::DependencyQueue;
+
+const MODRINTH_API: &str = "https://api.modrinth.com/v2";
+
+/// Modrinth asks API clients to identify themselves
+const USER_AGENT: &str = concat!("frostdev-ops/wowid3-modpack-server/", env!("CARGO_PKG_VERSION"));
+
+/// Search result entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModrinthSearchHit {
+    pub project_id: String,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    #[serde(default)]
+    pub downloads: u64,
+    #[serde(default)]
+    pub icon_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    hits: Vec<ModrinthSearchHit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModrinthHashes {
+    pub sha1: String,
+    pub sha512: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModrinthFile {
+    pub url: String,
+    pub filename: String,
+    pub hashes: ModrinthHashes,
+    #[serde(default)]
+    pub primary: bool,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModrinthDependency {
+    pub version_id: Option<String>,
+    pub project_id: Option<String>,
+    /// `required`, `optional`, `incompatible` or `embedded`
+    pub dependency_type: String,
+}
+
+/// A specific release of a Modrinth project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModrinthVersion {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+    pub version_number: String,
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+    #[serde(default)]
+    pub loaders: Vec<String>,
+    pub files: Vec<ModrinthFile>,
+    #[serde(default)]
+    pub dependencies: Vec<ModrinthDependency>,
+}
+
+impl ModrinthVersion {
+    /// The primary jar, or the first file when none is flagged primary
+    pub fn primary_file(&self) -> Option<&ModrinthFile> {
+        self.files.iter().find(|f| f.primary).or_else(|| self.files.first())
+    }
+}
+
+/// Minimal Modrinth v2 API client
+#[derive(Clone)]
+pub struct ModrinthClient {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+impl ModrinthClient {
+    pub fn new() -> Result<Self> {
+        Self::with_base_url(MODRINTH_API)
+    }
+
+    pub fn with_base_url(base_url: &str) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(std::time::Duration::from_secs(60))
+            .build()
+            .context("Failed to build Modrinth HTTP client")?;
+
+        Ok(Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Search mods compatible with a Minecraft version and loader
+    pub async fn search(
+        &self,
+        query: &str,
+        minecraft_version: &str,
+        loader: &str,
+        limit: usize,
+    ) -> Result<Vec<ModrinthSearchHit>> {
+        let facets = serde_json::json!([
+            [format!("categories:{}", loader)],
+            [format!("versions:{}", minecraft_version)],
+            ["project_type:mod"],
+        ])
+        .to_string();
+
+        let response: SearchResponse = self
+            .http
+            .get(format!("{}/search", self.base_url))
+            .query(&[
+                ("query", query),
+                ("facets", &facets),
+                ("limit", &limit.to_string()),
+            ])
+            .send()
+            .await
+            .context("Modrinth search request failed")?
+            .error_for_status()
+            .context("Modrinth search returned an error")?
+            .json()
+            .await
+            .context("Failed to parse Modrinth search response")?;
+
+        Ok(response.hits)
+    }
+
+    /// Versions of a project (id or slug) compatible with the given game version and loader, newest first
+    pub async fn compatible_versions(
+        &self,
+        project: &str,
+        minecraft_version: &str,
+        loader: &str,
+    ) -> Result<Vec<ModrinthVersion>> {
+        let loaders = serde_json::json!([loader]).to_string();
+        let game_versions = serde_json::json!([minecraft_version]).to_string();
+
+        self.http
+            .get(format!("{}/project/{}/version", self.base_url, project))
+            .query(&[("loaders", loaders), ("game_versions", game_versions)])
+            .send()
+            .await
+            .context("Modrinth version request failed")?
+            .error_for_status()
+            .with_context(|| format!("Modrinth project {} not found", project))?
+            .json()
+            .await
+            .context("Failed to parse Modrinth versions")
+    }
+
+    pub async fn get_version(&self, version_id: &str) -> Result<ModrinthVersion> {
+        self.http
+            .get(format!("{}/version/{}", self.base_url, version_id))
+            .send()
+            .await
+            .context("Modrinth version request failed")?
+            .error_for_status()
+            .with_context(|| format!("Modrinth version {} not found", version_id))?
+            .json()
+            .await
+            .context("Failed to parse Modrinth version")
+    }
+
+    /// Resolve a version plus its required dependencies (transitively)
+    ///
+    /// Dependencies pinned to a version id use that version; others use the newest
+    /// version compatible with the game version and loader. Dependencies with no
+    /// compatible version are returned in the second list.
+    pub async fn resolve_with_dependencies(
+        &self,
+        root: ModrinthVersion,
+        minecraft_version: &str,
+        loader: &str,
+    ) -> Result<(Vec<ModrinthVersion>, Vec<String>)> {
+        let mut resolved = Vec::new();
+        let mut unresolved = Vec::new();
+        let mut queue = DependencyQueue::new(root, |version: &ModrinthVersion| version.project_id.clone());
+
+        while let Some(version) = queue.next()? {
+            for dep in version.dependencies.iter().filter(|d| d.dependency_type == "required") {
+                if dep.project_id.as_ref().is_some_and(|p| queue.visited(p)) {
+                    continue;
+                }
+
+                let dep_version = match (&dep.version_id, &dep.project_id) {
+                    (Some(version_id), _) => Some(self.get_version(version_id).await?),
+                    (None, Some(project_id)) => self
+                        .compatible_versions(project_id, minecraft_version, loader)
+                        .await?
+                        .into_iter()
+                        .next(),
+                    (None, None) => None,
+                };
+
+                match dep_version {
+                    Some(v) => queue.push(v),
+                    None => unresolved.push(
+                        dep.project_id.clone().unwrap_or_else(|| "unknown".to_string()),
+                    ),
+                }
+            }
+
+            resolved.push(version);
+        }
+
+        Ok((resolved, unresolved))
+    }
+
+    /// Download a file to `dest`, verifying Modrinth's sha512; returns the sha256
+    pub async fn download(&self, file: &ModrinthFile, dest: &Path) -> Result<String> {
+        let mut response = self
+            .http
+            .get(&file.url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to download {}", file.filename))?
+            .error_for_status()
+            .with_context(|| format!("Download of {} failed", file.filename))?;
+
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let temp = dest.with_extension("download");
+        let mut out = fs::File::create(&temp)
+            .await
+            .with_context(|| format!("Failed to create {}", temp.display()))?;
+        let mut sha256 = Sha256::new();
+        let mut sha512 = Sha512::new();
+
+        while let Some(chunk) = response.chunk().await.context("Download interrupted")? {
+            sha256.update(&chunk);
+            sha512.update(&chunk);
+            out.write_all(&chunk).await?;
+        }
+        out.flush().await?;
+        drop(out);
+
+        let actual = format!("{:x}", sha512.finalize());
+        if !actual.eq_ignore_ascii_case(&file.hashes.sha512) {
+            let _ = fs::remove_file(&temp).await;
+            anyhow::bail!("Checksum mismatch for {}", file.filename);
+        }
+
+        fs::rename(&temp, dest)
+            .await
+            .with_context(|| format!("Failed to move {} into place", file.filename))?;
+
+        Ok(format!("{:x}", sha256.finalize()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(id: &str, project: &str, deps: Vec<ModrinthDependency>) -> ModrinthVersion {
+        ModrinthVersion {
+            id: id.to_string(),
+            project_id: project.to_string(),
+            name: id.to_string(),
+            version_number: "1.0.0".to_string(),
+            game_versions: vec!["1.20.1".to_string()],
+            loaders: vec!["fabric".to_string()],
+            files: Vec::new(),
+            dependencies: deps,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_skips_optional_and_seen_dependencies() {
+        let client = ModrinthClient::with_base_url("http://127.0.0.1:9").unwrap();
+        let root = version(
+            "v1",
+            "root",
+            vec![
+                ModrinthDependency {
+                    version_id: None,
+                    project_id: Some("optional-lib".to_string()),
+                    dependency_type: "optional".to_string(),
+                },
+                ModrinthDependency {
+                    version_id: None,
+                    project_id: Some("root".to_string()),
+                    dependency_type: "required".to_string(),
+                },
+            ],
+        );
+
+        // No network calls are needed: the only required dependency is the root itself
+        let (resolved, unresolved) = client
+            .resolve_with_dependencies(root, "1.20.1", "fabric")
+            .await
+            .unwrap();
+        assert_eq!(resolved.len(), 1);
+        assert!(unresolved.is_empty());
+    }
+
+    #[test]
+    fn test_primary_file_fallback() {
+        let mut v = version("v1", "p", Vec::new());
+        let file = |name: &str, primary| ModrinthFile {
+            url: String::new(),
+            filename: name.to_string(),
+            hashes: ModrinthHashes { sha1: String::new(), sha512: String::new() },
+            primary,
+            size: 0,
+        };
+        v.files = vec![file("sources.jar", false), file("mod.jar", true)];
+        assert_eq!(v.primary_file().unwrap().filename, "mod.jar");
+
+        v.files = vec![file("only.jar", false)];
+        assert_eq!(v.primary_file().unwrap().filename, "only.jar");
+    }
+}
@@ -0,0 +1,221 @@
+use crate::config::{Config, LiveSettings};
+use crate::models::manifest::{LauncherVersion, Manifest, ReleaseChannel};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Discord caps embed descriptions at 4096 characters
+const MAX_CHANGELOG_CHARS: usize = 3500;
+
+const COLOR_STABLE: u32 = 0x2ecc71;
+const COLOR_BETA: u32 = 0xf1c40f;
+const COLOR_LAUNCHER: u32 = 0x3498db;
+
+/// Posts release announcements to the configured Discord webhooks
+///
+/// Delivery happens in the background and failures are only logged; a broken
+/// webhook never fails a publish.
+#[derive(Clone)]
+pub struct Notifier {
+    client: reqwest::Client,
+    webhook_urls: Arc<Vec<String>>,
+    /// Links in announcements follow BASE_URL across reloads
+    settings: LiveSettings,
+}
+
+impl Notifier {
+    pub fn from_config(config: &Config, settings: LiveSettings) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            webhook_urls: Arc::new(
+                config
+                    .discord_webhook_urls
+                    .iter()
+                    .map(|url| url.trim().to_string())
+                    .filter(|url| !url.is_empty())
+                    .collect(),
+            ),
+            settings,
+        }
+    }
+
+    /// Announce a newly published modpack release
+    pub fn release_published(&self, manifest: &Manifest) {
+        self.send(release_embed(manifest, &self.base_url()));
+    }
+
+    /// Announce a newly published launcher version
+    pub fn launcher_published(&self, version: &LauncherVersion) {
+        self.send(launcher_embed(version, &self.base_url()));
+    }
+
+    fn base_url(&self) -> String {
+        self.settings.get().base_url.trim_end_matches('/').to_string()
+    }
+
+    fn send(&self, embed: Value) {
+        if self.webhook_urls.is_empty() {
+            return;
+        }
+
+        let payload = json!({ "embeds": [embed] });
+        for url in self.webhook_urls.iter() {
+            let url = url.clone();
+            let client = self.client.clone();
+            let payload = payload.clone();
+            tokio::spawn(async move {
+                match client.post(&url).json(&payload).send().await {
+                    Ok(response) if response.status().is_success() => {}
+                    Ok(response) => tracing::warn!("Discord webhook returned {}", response.status()),
+                    Err(e) => tracing::warn!("Failed to deliver Discord webhook: {}", e),
+                }
+            });
+        }
+    }
+}
+
+/// Embed for a modpack release: version, channel, changelog and download size
+pub fn release_embed(manifest: &Manifest, base_url: &str) -> Value {
+    let total_size: u64 = manifest.files.iter().map(|f| f.size).sum();
+    let (title, color) = match manifest.channel {
+        ReleaseChannel::Stable => (format!("Modpack {} released", manifest.version), COLOR_STABLE),
+        ReleaseChannel::Beta => (format!("Modpack {} (beta) released", manifest.version), COLOR_BETA),
+    };
+
+    json!({
+        "title": title,
+        "url": format!("{}/api/manifest/{}", base_url, manifest.version),
+        "description": changelog_text(&manifest.changelog),
+        "color": color,
+        "fields": [
+            { "name": "Minecraft", "value": manifest.minecraft_version, "inline": true },
+            { "name": manifest.loader.name(), "value": manifest.fabric_loader, "inline": true },
+            { "name": "Download", "value": format!("{} files, {}", manifest.files.len(), format_size(total_size)), "inline": true },
+        ],
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// Embed for a launcher version: changelog and a download size per platform
+pub fn launcher_embed(version: &LauncherVersion, base_url: &str) -> Value {
+    let mut fields: Vec<Value> = version
+        .files
+        .iter()
+        .map(|file| {
+            let name = match &file.file_type {
+                Some(file_type) => format!("{} ({})", file.platform, file_type),
+                None => file.platform.clone(),
+            };
+            json!({ "name": name, "value": format_size(file.size), "inline": true })
+        })
+        .collect();
+    if version.mandatory {
+        fields.push(json!({ "name": "Required", "value": "Older launchers must update", "inline": false }));
+    }
+
+    json!({
+        "title": format!("Launcher {} released", version.version),
+        "url": format!("{}/api/launcher/{}", base_url, version.version),
+        "description": changelog_text(&version.changelog),
+        "color": COLOR_LAUNCHER,
+        "fields": fields,
+        "timestamp": version.released_at,
+    })
+}
+
+fn changelog_text(changelog: &str) -> String {
+    let changelog = changelog.trim();
+    if changelog.is_empty() {
+        return "No changelog provided.".to_string();
+    }
+    if changelog.chars().count() <= MAX_CHANGELOG_CHARS {
+        return changelog.to_string();
+    }
+    let truncated: String = changelog.chars().take(MAX_CHANGELOG_CHARS).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::manifest::{LauncherFile, ManifestFile, ModLoader};
+
+    #[test]
+    fn test_release_embed() {
+        let manifest = Manifest {
+            version: "1.4.0".to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
+            fabric_loader: "0.15.11".to_string(),
+            files: vec![ManifestFile {
+                path: "mods/a.jar".to_string(),
+                url: "https://example.com/files/1.4.0/mods/a.jar".to_string(),
+                sha256: "abc".to_string(),
+                size: 3 * 1024 * 1024,
+                mirrors: Vec::new(),
+            }],
+            changelog: "- Added things\n".to_string(),
+            ignore_patterns: Vec::new(),
+            channel: ReleaseChannel::Beta,
+            optional_groups: Vec::new(),
+            ignore_overrides: Default::default(),
+        };
+
+        let embed = release_embed(&manifest, "https://example.com");
+        assert_eq!(embed["title"], "Modpack 1.4.0 (beta) released");
+        assert_eq!(embed["description"], "- Added things");
+        assert_eq!(embed["color"], COLOR_BETA);
+        assert_eq!(embed["fields"][2]["value"], "1 files, 3.0 MB");
+    }
+
+    #[test]
+    fn test_launcher_embed() {
+        let version = LauncherVersion {
+            version: "2.1.0".to_string(),
+            files: vec![LauncherFile {
+                platform: "windows".to_string(),
+                file_type: Some("installer".to_string()),
+                filename: "setup.exe".to_string(),
+                url: "https://example.com/files/launcher/2.1.0/setup.exe".to_string(),
+                sha256: "abc".to_string(),
+                size: 512,
+                signature: None,
+            }],
+            changelog: String::new(),
+            mandatory: true,
+            released_at: "2024-01-01T00:00:00+00:00".to_string(),
+        };
+
+        let embed = launcher_embed(&version, "https://example.com");
+        assert_eq!(embed["description"], "No changelog provided.");
+        assert_eq!(embed["fields"][0]["name"], "windows (installer)");
+        assert_eq!(embed["fields"][0]["value"], "512 B");
+        assert_eq!(embed["fields"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_changelog_truncated() {
+        let long = "x".repeat(MAX_CHANGELOG_CHARS + 10);
+        let text = changelog_text(&long);
+        assert_eq!(text.chars().count(), MAX_CHANGELOG_CHARS + 1);
+        assert!(text.ends_with('…'));
+    }
+}
@@ -0,0 +1,41 @@
+use anyhow::{anyhow, Result};
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+
+const MIN_PASSWORD_LEN: usize = 10;
+
+/// Hash a password into a PHC string (argon2id, random salt)
+pub fn hash_password(password: &str) -> Result<String> {
+    if password.len() < MIN_PASSWORD_LEN {
+        anyhow::bail!("Password must be at least {} characters", MIN_PASSWORD_LEN);
+    }
+
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| anyhow!("Failed to hash password: {}", e))
+}
+
+/// Verify a password against a stored PHC string
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    match PasswordHash::new(hash) {
+        Ok(parsed) => Argon2::default()
+            .verify_password(password.as_bytes(), &parsed)
+            .is_ok(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_and_verify() {
+        let hash = hash_password("correct horse battery").unwrap();
+        assert!(verify_password("correct horse battery", &hash));
+        assert!(!verify_password("wrong password!", &hash));
+        assert!(hash_password("short").is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use crate::cache::CacheManager;
+use crate::config::{Config, LiveSettings, RuntimeSettings};
+use anyhow::Result;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Re-reads the configuration and applies the runtime settings to the running server
+///
+/// Uploads and other requests in flight keep the settings they started with; only
+/// requests arriving after the reload see the new values.
+#[derive(Clone)]
+pub struct ConfigReloader {
+    settings: LiveSettings,
+    cache: CacheManager,
+}
+
+impl ConfigReloader {
+    pub fn new(settings: LiveSettings, cache: CacheManager) -> Self {
+        Self { settings, cache }
+    }
+
+    /// Reload and apply; returns the variables that changed
+    pub async fn reload(&self) -> Result<Vec<&'static str>> {
+        let config = Config::reload()?;
+        Ok(self.apply(RuntimeSettings::from(&config)).await)
+    }
+
+    async fn apply(&self, next: RuntimeSettings) -> Vec<&'static str> {
+        let previous = self.settings.replace(next.clone());
+        let changed = previous.changes(&next);
+
+        if previous.manifest_cache_size != next.manifest_cache_size
+            || previous.manifest_cache_ttl_secs != next.manifest_cache_ttl_secs
+        {
+            self.cache
+                .set_manifest_limits(
+                    next.manifest_cache_size,
+                    Some(Duration::from_secs(next.manifest_cache_ttl_secs)),
+                )
+                .await;
+        }
+        if previous.base_url != next.base_url {
+            // Cached manifest bodies carry mirror URLs worked out from the old base URL
+            self.cache.clear_manifests().await;
+        }
+        if previous.hot_file_cache_mb != next.hot_file_cache_mb || previous.hot_file_max_kb != next.hot_file_max_kb {
+            self.cache
+                .set_hot_file_limits(next.hot_file_cache_mb * 1024 * 1024, next.hot_file_max_kb * 1024);
+        }
+        changed
+    }
+
+    /// Reload whenever the process receives SIGHUP
+    #[cfg(unix)]
+    pub fn spawn_on_sighup(self) -> Result<()> {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut hangups = signal(SignalKind::hangup())?;
+        tokio::spawn(async move {
+            while hangups.recv().await.is_some() {
+                match self.reload().await {
+                    Ok(changed) if changed.is_empty() => info!("Configuration reloaded; nothing changed"),
+                    Ok(changed) => info!(changed = ?changed, "Configuration reloaded"),
+                    // Keep serving with the settings already in place
+                    Err(e) => warn!("Configuration reload failed, keeping current settings: {:#}", e),
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::compression::PrecompressedBody;
+    use axum::body::Bytes;
+
+    #[tokio::test]
+    async fn test_apply_resizes_caches() {
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "hot_file_cache_mb": 1,
+            "hot_file_max_kb": 512
+        }))
+        .unwrap();
+        let cache = CacheManager::with_hot_file_limits(1024 * 1024, 512 * 1024);
+        cache.put_hot_file("a".to_string(), Bytes::from(vec![0u8; 300 * 1024]));
+        let reloader = ConfigReloader::new(LiveSettings::new(&config), cache.clone());
+
+        let mut next = RuntimeSettings::from(&config);
+        next.hot_file_max_kb = 256;
+        next.manifest_cache_size = 3;
+        assert_eq!(reloader.apply(next).await, vec!["MANIFEST_CACHE_SIZE", "HOT_FILE_MAX_KB"]);
+
+        let stats = cache.get_stats().await;
+        assert_eq!(stats.manifest_cache_capacity, 3);
+        assert!(!cache.is_hot_file_size(300 * 1024));
+        // Files already cached stay until evicted
+        assert_eq!(stats.hot_file_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_base_url_reload_moves_mirrors() {
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "base_url": "https://old.example",
+            "mirror_base_urls": ["https://na.example"]
+        }))
+        .unwrap();
+        let cache = CacheManager::new();
+        let body = PrecompressedBody::new(b"{}".to_vec()).unwrap();
+        cache.put_manifest_body("latest".to_string(), std::sync::Arc::new(body)).await;
+        let settings = LiveSettings::new(&config);
+        let reloader = ConfigReloader::new(settings.clone(), cache.clone());
+
+        let mut next = RuntimeSettings::from(&config);
+        next.base_url = "https://new.example".to_string();
+        assert_eq!(reloader.apply(next).await, vec!["BASE_URL"]);
+
+        let base_url = settings.get().base_url.clone();
+        assert_eq!(
+            config.mirror_urls(&base_url, "https://new.example/files/1.0.0/a.jar"),
+            vec!["https://na.example/files/1.0.0/a.jar"]
+        );
+        assert!(config.mirror_urls(&base_url, "https://old.example/files/1.0.0/a.jar").is_empty());
+        // Manifests cached under the old base URL are dropped
+        assert!(cache.get_manifest_body("latest").await.is_none());
+    }
+}
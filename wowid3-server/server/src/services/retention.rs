@@ -0,0 +1,302 @@
+use crate::cache::{CacheEvent, CacheManager};
+use crate::config::Config;
+use crate::models::{Manifest, ReleaseChannel};
+use crate::storage::{self, backend::StorageBackend};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Releases tagged with this are never pruned
+pub const PINNED_TAG: &str = "pinned";
+
+/// Why a release survives pruning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeepReason {
+    /// Among the newest `keep_latest` releases
+    Recent,
+    /// Tagged `pinned` or listed in RELEASE_RETENTION_PINNED
+    Pinned,
+    /// Currently served as the latest stable or beta release
+    Latest,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KeptRelease {
+    pub version: String,
+    pub reason: KeepReason,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PrunedRelease {
+    pub version: String,
+    /// Size of the release's files
+    pub bytes: u64,
+}
+
+/// What a retention pass keeps and deletes
+#[derive(Debug, Clone, Serialize)]
+pub struct RetentionPlan {
+    pub keep_latest: usize,
+    /// Newest first
+    pub kept: Vec<KeptRelease>,
+    /// Newest first
+    pub pruned: Vec<PrunedRelease>,
+    /// Bytes freed from the object store: files no kept release shares
+    pub reclaimable_bytes: u64,
+}
+
+/// Decide which releases to prune, given their manifests and the protected versions
+///
+/// `keep_latest` of 0 keeps everything. Files are content-addressed, so only files
+/// that no kept release also ships count towards `reclaimable_bytes`.
+pub fn plan(
+    manifests: &[Manifest],
+    keep_latest: usize,
+    pinned: &HashSet<String>,
+    latest: &HashSet<String>,
+) -> RetentionPlan {
+    let mut ordered: Vec<&Manifest> = manifests.iter().collect();
+    ordered.sort_by(|a, b| match (semver::Version::parse(&a.version), semver::Version::parse(&b.version)) {
+        (Ok(va), Ok(vb)) => vb.cmp(&va),
+        _ => b.version.cmp(&a.version),
+    });
+
+    let mut kept = Vec::new();
+    let mut pruned = Vec::new();
+    for (index, manifest) in ordered.into_iter().enumerate() {
+        let reason = if latest.contains(&manifest.version) {
+            Some(KeepReason::Latest)
+        } else if pinned.contains(&manifest.version) {
+            Some(KeepReason::Pinned)
+        } else if keep_latest == 0 || index < keep_latest {
+            Some(KeepReason::Recent)
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => kept.push((manifest, reason)),
+            None => pruned.push(manifest),
+        }
+    }
+
+    let kept_hashes: HashSet<&str> = kept
+        .iter()
+        .flat_map(|(manifest, _)| manifest.files.iter().map(|f| f.sha256.as_str()))
+        .collect();
+    let freed: HashMap<&str, u64> = pruned
+        .iter()
+        .flat_map(|manifest| manifest.files.iter())
+        .filter(|f| !kept_hashes.contains(f.sha256.as_str()))
+        .map(|f| (f.sha256.as_str(), f.size))
+        .collect();
+
+    RetentionPlan {
+        keep_latest,
+        kept: kept
+            .into_iter()
+            .map(|(manifest, reason)| KeptRelease {
+                version: manifest.version.clone(),
+                reason,
+            })
+            .collect(),
+        pruned: pruned
+            .into_iter()
+            .map(|manifest| PrunedRelease {
+                version: manifest.version.clone(),
+                bytes: manifest.files.iter().map(|f| f.size).sum(),
+            })
+            .collect(),
+        reclaimable_bytes: freed.values().sum(),
+    }
+}
+
+/// Plan a retention pass over the releases on disk
+pub async fn build_plan(config: &Config, keep_latest: usize) -> Result<RetentionPlan> {
+    let mut manifests = Vec::new();
+    for version in storage::manifest::list_versions(config).await? {
+        match storage::manifest::read_manifest(config, &version).await {
+            Ok(manifest) => manifests.push(manifest),
+            // Never prune a release we can't read; it may just be mid-publish
+            Err(e) => warn!("Skipping release {} in retention plan: {}", version, e),
+        }
+    }
+
+    let mut pinned: HashSet<String> = config
+        .release_retention_pinned
+        .iter()
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty())
+        .collect();
+    let tags = storage::tags::load_release_tags(config)
+        .await
+        .context("Failed to load release tags")?;
+    pinned.extend(
+        tags.into_iter()
+            .filter(|(_, tags)| tags.iter().any(|tag| tag == PINNED_TAG))
+            .map(|(version, _)| version),
+    );
+
+    let mut latest = HashSet::new();
+    for channel in [ReleaseChannel::Stable, ReleaseChannel::Beta] {
+        if let Ok(manifest) = storage::manifest::read_latest_channel_manifest(config, channel).await {
+            latest.insert(manifest.version);
+        }
+    }
+
+    Ok(plan(&manifests, keep_latest, &pinned, &latest))
+}
+
+/// Delete a release's directory and tags and evict it from the cache
+///
+/// Objects it used stay until `collect_garbage` runs.
+pub async fn delete_release(config: &Config, cache: &CacheManager, version: &str) -> Result<()> {
+    tokio::fs::remove_dir_all(config.release_path(version))
+        .await
+        .context("Failed to delete release")?;
+
+    cache
+        .publish(CacheEvent::ReleaseDeleted { version: version.to_string() })
+        .await;
+
+    // Drop any tags that referenced the deleted release
+    if let Err(e) = storage::tags::clear_release_tags(config, version).await {
+        warn!("Failed to clear tags for deleted release {}: {}", version, e);
+    }
+    Ok(())
+}
+
+/// Free objects no remaining release references, locally and in the storage backend
+pub async fn collect_garbage(config: &Config, backend: &dyn StorageBackend) {
+    if let Err(e) = storage::files::collect_garbage(config).await {
+        warn!("Failed to collect unreferenced objects: {}", e);
+    }
+    match storage::files::referenced_objects(config).await {
+        Ok(referenced) => {
+            if let Err(e) = backend.collect_garbage(&referenced).await {
+                warn!("Failed to collect unreferenced {} objects: {}", backend.name(), e);
+            }
+        }
+        Err(e) => warn!("Failed to list referenced objects: {}", e),
+    }
+}
+
+/// Periodically deletes releases outside the retention policy
+pub struct ReleasePruner {
+    config: Arc<Config>,
+    cache: CacheManager,
+    backend: Arc<dyn StorageBackend>,
+    interval: Duration,
+}
+
+impl ReleasePruner {
+    pub fn new(config: Arc<Config>, cache: CacheManager, backend: Arc<dyn StorageBackend>, interval: Duration) -> Self {
+        Self {
+            config,
+            cache,
+            backend,
+            interval,
+        }
+    }
+
+    pub fn spawn(self) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_once().await {
+                    warn!("Release retention pass failed: {:#}", e);
+                }
+            }
+        });
+    }
+
+    async fn run_once(&self) -> Result<()> {
+        let plan = build_plan(&self.config, self.config.release_retention_keep).await?;
+        if plan.pruned.is_empty() {
+            return Ok(());
+        }
+
+        for release in &plan.pruned {
+            match delete_release(&self.config, &self.cache, &release.version).await {
+                Ok(()) => info!("Pruned release {} under the retention policy", release.version),
+                Err(e) => warn!("Failed to prune release {}: {:#}", release.version, e),
+            }
+        }
+        collect_garbage(&self.config, self.backend.as_ref()).await;
+        info!(
+            "Release retention pass pruned {} releases, freeing about {} MB",
+            plan.pruned.len(),
+            plan.reclaimable_bytes / 1024 / 1024
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(version: &str, files: &[(&str, u64)]) -> Manifest {
+        serde_json::from_value(serde_json::json!({
+            "version": version,
+            "minecraft_version": "1.20.1",
+            "fabric_loader": "0.15.0",
+            "files": files
+                .iter()
+                .map(|(sha256, size)| serde_json::json!({
+                    "path": format!("mods/{}.jar", sha256),
+                    "url": format!("https://example.com/{}", sha256),
+                    "sha256": sha256,
+                    "size": size,
+                }))
+                .collect::<Vec<_>>(),
+            "changelog": ""
+        }))
+        .unwrap()
+    }
+
+    fn versions(set: &[&str]) -> HashSet<String> {
+        set.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_plan_keeps_latest_pinned_and_channel_heads() {
+        let manifests = vec![
+            manifest("1.0.0", &[("a", 100), ("b", 10)]),
+            manifest("1.1.0", &[("a", 100), ("c", 20)]),
+            manifest("1.2.0", &[("a", 100), ("d", 30)]),
+            manifest("1.10.0", &[("e", 40)]),
+            manifest("1.3.0", &[("a", 100)]),
+        ];
+
+        let plan = plan(&manifests, 2, &versions(&["1.1.0"]), &versions(&["1.2.0"]));
+
+        let kept: Vec<(&str, KeepReason)> = plan.kept.iter().map(|k| (k.version.as_str(), k.reason)).collect();
+        assert_eq!(
+            kept,
+            vec![
+                ("1.10.0", KeepReason::Recent),
+                ("1.3.0", KeepReason::Recent),
+                ("1.2.0", KeepReason::Latest),
+                ("1.1.0", KeepReason::Pinned),
+            ]
+        );
+        assert_eq!(plan.pruned.len(), 1);
+        assert_eq!(plan.pruned[0].version, "1.0.0");
+        assert_eq!(plan.pruned[0].bytes, 110);
+        // `a` is still shipped by kept releases; only `b` is freed
+        assert_eq!(plan.reclaimable_bytes, 10);
+    }
+
+    #[test]
+    fn test_zero_keeps_everything() {
+        let manifests = vec![manifest("1.0.0", &[("a", 1)]), manifest("1.1.0", &[("b", 1)])];
+        let plan = plan(&manifests, 0, &HashSet::new(), &HashSet::new());
+        assert!(plan.pruned.is_empty());
+        assert_eq!(plan.reclaimable_bytes, 0);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::models::ModInfo;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Shorter queries match nearly every path
+pub const MIN_QUERY_LEN: usize = 2;
+/// Changelog lines reported per release or draft
+const MAX_CHANGELOG_LINES: usize = 5;
+
+/// What part of a release a match was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchField {
+    Path,
+    ModId,
+    Changelog,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchMatch {
+    pub field: MatchField,
+    /// Matching path, `mod_id version`, or changelog line
+    pub value: String,
+    /// Jar a mod id match came from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+}
+
+/// A release or draft with at least one match
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub version: String,
+    /// Set for drafts; releases are identified by `version`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft_id: Option<Uuid>,
+    pub matches: Vec<SearchMatch>,
+}
+
+impl SearchHit {
+    /// Whether the release ships the file or mod (not just mentions it in the changelog)
+    fn contains_file(&self) -> bool {
+        self.matches.iter().any(|m| m.field != MatchField::Changelog)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResults {
+    pub query: String,
+    /// Oldest release shipping a matching file or mod
+    pub introduced_in: Option<String>,
+    /// Newest first
+    pub releases: Vec<SearchHit>,
+    pub drafts: Vec<SearchHit>,
+}
+
+/// Case-insensitive substring matches of `query` in one release or draft
+///
+/// Mods match on id, display name or any id they provide (e.g. jar-in-jar libraries).
+pub fn find_matches<'a>(
+    query: &str,
+    paths: impl IntoIterator<Item = &'a str>,
+    mods: &[ModInfo],
+    changelog: &str,
+) -> Vec<SearchMatch> {
+    let query = query.to_lowercase();
+    let contains = |text: &str| text.to_lowercase().contains(&query);
+    let mut matches = Vec::new();
+
+    for path in paths.into_iter().filter(|path| contains(path)) {
+        matches.push(SearchMatch {
+            field: MatchField::Path,
+            value: path.to_string(),
+            file: None,
+        });
+    }
+
+    for mod_info in mods {
+        let matched = contains(&mod_info.mod_id)
+            || contains(&mod_info.name)
+            || mod_info.provides.keys().any(|id| contains(id));
+        if matched {
+            matches.push(SearchMatch {
+                field: MatchField::ModId,
+                value: format!("{} {}", mod_info.mod_id, mod_info.version),
+                file: mod_info.file.clone(),
+            });
+        }
+    }
+
+    let lines = changelog.lines().map(str::trim).filter(|line| contains(line));
+    for line in lines.take(MAX_CHANGELOG_LINES) {
+        matches.push(SearchMatch {
+            field: MatchField::Changelog,
+            value: line.to_string(),
+            file: None,
+        });
+    }
+
+    matches
+}
+
+/// Order release hits newest first and work out where the file first appeared
+pub fn collect_results(query: String, mut releases: Vec<SearchHit>, drafts: Vec<SearchHit>) -> SearchResults {
+    releases.sort_by(|a, b| compare_versions(&b.version, &a.version));
+    let introduced_in = releases
+        .iter()
+        .rev()
+        .find(|hit| hit.contains_file())
+        .map(|hit| hit.version.clone());
+
+    SearchResults {
+        query,
+        introduced_in,
+        releases,
+        drafts,
+    }
+}
+
+/// Semantic version order, falling back to string order like the release list
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    match (semver::Version::parse(a), semver::Version::parse(b)) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_support::mod_info;
+
+    fn hit(version: &str, matches: Vec<SearchMatch>) -> SearchHit {
+        SearchHit {
+            version: version.to_string(),
+            draft_id: None,
+            matches,
+        }
+    }
+
+    #[test]
+    fn test_find_matches() {
+        let mods = vec![mod_info("createaddition", "1.0.0", "mods/createaddition-1.2.jar")];
+        let matches = find_matches(
+            "CreateAddition",
+            ["mods/createaddition-1.2.jar", "mods/sodium.jar"],
+            &mods,
+            "Added Create Crafts & Additions\n- Updated createaddition to 1.2\n",
+        );
+
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches[0].field, MatchField::Path);
+        assert_eq!(matches[1].value, "createaddition 1.0.0");
+        assert_eq!(matches[1].file.as_deref(), Some("mods/createaddition-1.2.jar"));
+        assert_eq!(matches[2].value, "- Updated createaddition to 1.2");
+    }
+
+    #[test]
+    fn test_introduced_in_ignores_changelog_mentions() {
+        let path_match = || SearchMatch {
+            field: MatchField::Path,
+            value: "mods/createaddition.jar".to_string(),
+            file: None,
+        };
+        let changelog_match = SearchMatch {
+            field: MatchField::Changelog,
+            value: "Planned: createaddition".to_string(),
+            file: None,
+        };
+
+        let results = collect_results(
+            "createaddition".to_string(),
+            vec![
+                hit("1.10.0", vec![path_match()]),
+                hit("1.2.0", vec![changelog_match]),
+                hit("1.9.0", vec![path_match()]),
+            ],
+            vec![],
+        );
+
+        let versions: Vec<&str> = results.releases.iter().map(|h| h.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.10.0", "1.9.0", "1.2.0"]);
+        assert_eq!(results.introduced_in.as_deref(), Some("1.9.0"));
+    }
+}
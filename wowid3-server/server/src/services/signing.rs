@@ -0,0 +1,87 @@
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+
+/// Response header carrying the signature of a manifest body
+pub const MANIFEST_SIGNATURE_HEADER: &str = "x-manifest-signature";
+
+/// Signs launcher binaries and modpack manifests so the launcher can verify them
+///
+/// Signatures cover the SHA-256 digest rather than the bytes, so uploads are
+/// signed while they stream to disk. The launcher checks the digest first, then
+/// the signature over it. Launcher uploads and manifests use separate keys.
+pub struct Signer {
+    key: Ed25519KeyPair,
+}
+
+impl Signer {
+    /// The signer for a base64 key setting (`name` is the env var, for errors), if one is configured
+    pub fn from_setting(key: Option<&str>, name: &str) -> Result<Option<Self>> {
+        match key.map(str::trim) {
+            Some(key) if !key.is_empty() => {
+                let der = BASE64
+                    .decode(key)
+                    .with_context(|| format!("{} is not valid base64", name))?;
+                Ok(Some(Self::from_pkcs8(&der)?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Load a PKCS#8 Ed25519 key (as written by `generate-signing-key` or
+    /// `openssl genpkey -algorithm ed25519 -outform DER`)
+    pub fn from_pkcs8(der: &[u8]) -> Result<Self> {
+        let key = Ed25519KeyPair::from_pkcs8_maybe_unchecked(der)
+            .map_err(|e| anyhow::anyhow!("Invalid Ed25519 signing key: {}", e))?;
+        Ok(Self { key })
+    }
+
+    /// Base64 public key, to embed in launcher builds
+    pub fn public_key(&self) -> String {
+        BASE64.encode(self.key.public_key().as_ref())
+    }
+
+    /// Base64 signature over a file's SHA-256 digest
+    pub fn sign_digest(&self, sha256: &[u8]) -> String {
+        BASE64.encode(self.key.sign(sha256).as_ref())
+    }
+}
+
+/// A new key pair as base64 (PKCS#8 private key, raw public key)
+pub fn generate_key_pair() -> Result<(String, String)> {
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+        .map_err(|_| anyhow::anyhow!("Failed to generate signing key"))?;
+    let signer = Signer::from_pkcs8(pkcs8.as_ref())?;
+    Ok((BASE64.encode(pkcs8.as_ref()), signer.public_key()))
+}
+
+/// Name of the detached signature published next to a launcher file
+pub fn signature_file_name(filename: &str) -> String {
+    format!("{}.sig", filename)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{UnparsedPublicKey, ED25519};
+    use sha2::{Digest, Sha256};
+
+    #[test]
+    fn test_signature_verifies_with_public_key() {
+        let (private_key, public_key) = generate_key_pair().unwrap();
+        let signer = Signer::from_setting(Some(&private_key), "TEST_SIGNING_KEY").unwrap().unwrap();
+        assert_eq!(signer.public_key(), public_key);
+
+        let digest = Sha256::digest(b"launcher binary");
+        let signature = BASE64.decode(signer.sign_digest(&digest)).unwrap();
+        let verifier = UnparsedPublicKey::new(&ED25519, BASE64.decode(&public_key).unwrap());
+        assert!(verifier.verify(&digest, &signature).is_ok());
+
+        let tampered = Sha256::digest(b"launcher binary with malware");
+        assert!(verifier.verify(&tampered, &signature).is_err());
+
+        assert!(Signer::from_setting(Some("  "), "TEST_SIGNING_KEY").unwrap().is_none());
+        assert!(Signer::from_setting(Some("not base64!"), "TEST_SIGNING_KEY").is_err());
+    }
+}
@@ -1,24 +1,25 @@
-use crate::models::stats::{PlayerStats, PlayerStatEvent, StatEvent};
-use crate::database::Database;
+use crate::models::stats::PlayerStatEvent;
+use crate::database::store::StatsStore;
 use tokio::sync::mpsc::{self, Sender, Receiver};
 use std::collections::HashMap;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use sha2::{Sha256, Digest};
-use tokio_rusqlite::Connection;
+use std::sync::Arc;
+use std::time::Duration;
 
 const BATCH_SIZE: usize = 100;
 const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+/// How long event ids are remembered for deduplicating retried events
+const EVENT_ID_RETENTION_SECS: i64 = 7 * 86_400;
 
 pub struct StatsProcessor {
     tx: Sender<PlayerStatEvent>,
 }
 
 impl StatsProcessor {
-    pub fn new(db: Database) -> Self {
+    pub fn new(store: Arc<dyn StatsStore>) -> Self {
         let (tx, rx) = mpsc::channel(10000);
         
         tokio::spawn(async move {
-            Self::process_events(rx, db).await;
+            Self::process_events(rx, store).await;
         });
 
         Self { tx }
@@ -30,7 +31,7 @@ impl StatsProcessor {
         }
     }
 
-    async fn process_events(mut rx: Receiver<PlayerStatEvent>, db: Database) {
+    async fn process_events(mut rx: Receiver<PlayerStatEvent>, store: Arc<dyn StatsStore>) {
         let mut buffer: HashMap<String, Vec<PlayerStatEvent>> = HashMap::new();
 
         loop {
@@ -42,135 +43,67 @@ impl StatsProcessor {
 
                     let total_events: usize = buffer.values().map(|v| v.len()).sum();
                     if total_events >= BATCH_SIZE {
-                        Self::flush_buffer(&mut buffer, &db).await;
+                        Self::flush_buffer(&mut buffer, store.as_ref()).await;
                     }
                 }
                 _ = tokio::time::sleep(FLUSH_INTERVAL) => {
                     if !buffer.is_empty() {
-                        Self::flush_buffer(&mut buffer, &db).await;
+                        Self::flush_buffer(&mut buffer, store.as_ref()).await;
                     }
                 }
             }
         }
     }
 
-    async fn flush_buffer(buffer: &mut HashMap<String, Vec<PlayerStatEvent>>, db: &Database) {
+    async fn flush_buffer(buffer: &mut HashMap<String, Vec<PlayerStatEvent>>, store: &dyn StatsStore) {
+        let now = chrono::Utc::now().timestamp();
         for (uuid, events) in buffer.drain() {
-            if let Err(e) = Self::update_player_stats(uuid, events, &db.conn).await {
+            if let Err(e) = store.apply_events(&uuid, events, now).await {
                 tracing::error!("Failed to update stats: {}", e);
             }
         }
+
+        let cutoff = now - EVENT_ID_RETENTION_SECS;
+        if let Err(e) = store.prune_event_ids(cutoff).await {
+            tracing::warn!("Failed to prune stat event ids: {}", e);
+        }
     }
+}
 
-    async fn update_player_stats(uuid: String, events: Vec<PlayerStatEvent>, conn: &Connection) -> anyhow::Result<()> {
-        let uuid_clone = uuid.clone();
-        let default_username = events.first().map(|e| e.username.clone()).unwrap_or_default();
-        let events_clone = events.clone();
-        
-        conn.call(move |conn| -> Result<(), rusqlite::Error> {
-            let tx = conn.transaction()?;
-            
-            // 1. Fetch existing stats
-            let mut stats: PlayerStats = match tx.query_row(
-                "SELECT stats_json FROM player_stats WHERE uuid = ?1",
-                [&uuid_clone],
-                |row| {
-                    let json: String = row.get(0)?;
-                    Ok(serde_json::from_str(&json).unwrap_or_default())
-                }
-            ) {
-                Ok(stats) => stats,
-                Err(rusqlite::Error::QueryReturnedNoRows) => PlayerStats {
-                    uuid: uuid_clone.clone(),
-                    username: default_username.clone(),
-                    first_seen: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
-                    ..Default::default()
-                },
-                Err(e) => return Err(e),
-            };
-
-            // 2. Apply updates
-            for event in events_clone {
-                // Update username if changed
-                if !event.username.is_empty() {
-                    stats.username = event.username;
-                }
-                
-                match event.event {
-                    StatEvent::BlockBroken { item_id } => {
-                        *stats.blocks_broken.entry(item_id).or_insert(0) += 1;
-                        stats.total_blocks_broken += 1;
-                    }
-                    StatEvent::BlockPlaced { item_id } => {
-                        *stats.blocks_placed.entry(item_id).or_insert(0) += 1;
-                        stats.total_blocks_placed += 1;
-                    }
-                    StatEvent::MobKilled { entity_id } => {
-                        *stats.mobs_killed.entry(entity_id).or_insert(0) += 1;
-                        stats.total_mobs_killed += 1;
-                    }
-                    StatEvent::MobTamed { entity_id } => {
-                        *stats.mobs_tamed.entry(entity_id).or_insert(0) += 1;
-                        stats.total_mobs_tamed += 1;
-                    }
-                    StatEvent::OreMined { block_id } => {
-                        *stats.ores_mined.entry(block_id).or_insert(0) += 1;
-                        stats.total_ores_mined += 1;
-                    }
-                    StatEvent::ItemGathered { item_id, count } => {
-                        *stats.items_gathered.entry(item_id).or_insert(0) += count;
-                    }
-                    StatEvent::FoodEaten { item_id, count } => {
-                        *stats.food_eaten.entry(item_id).or_insert(0) += count;
-                        stats.total_food_eaten += count;
-                    }
-                    StatEvent::DamageDealt { amount } => {
-                        stats.damage_dealt += amount;
-                    }
-                    StatEvent::DamageTaken { amount } => {
-                        stats.damage_taken += amount;
-                    }
-                    StatEvent::PlayerDeath => {
-                        stats.deaths += 1;
-                    }
-                    StatEvent::DimensionVisited { dimension_id } => {
-                        if !stats.dimensions_visited.contains(&dimension_id) {
-                            stats.dimensions_visited.push(dimension_id);
-                        }
-                    }
-                    StatEvent::BiomeVisited { biome_id } => {
-                        if !stats.biomes_visited.contains(&biome_id) {
-                            stats.biomes_visited.push(biome_id);
-                        }
-                    }
-                    StatEvent::Playtime { seconds } => {
-                        stats.playtime_seconds += seconds;
-                    }
-                }
-            }
 
-            // 3. Update timestamp and hash
-            stats.last_updated = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-            
-            let json = serde_json::to_string(&stats).unwrap();
-            let hash = format!("{:x}", Sha256::digest(json.as_bytes()));
-
-            // 4. Save back to DB
-            tx.execute(
-                "INSERT INTO player_stats (uuid, stats_json, hash, last_updated) 
-                 VALUES (?1, ?2, ?3, ?4)
-                 ON CONFLICT(uuid) DO UPDATE SET 
-                    stats_json = excluded.stats_json,
-                    hash = excluded.hash,
-                    last_updated = excluded.last_updated",
-                (uuid_clone, json, hash, stats.last_updated),
-            )?;
-
-            tx.commit()?;
-            Ok(())
-        }).await?;
-
-        Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{store::SqliteStore, Database};
+    use crate::models::stats::{PlayerStats, StatEvent};
+    use tempfile::tempdir;
+
+    fn death(event_id: Option<&str>) -> PlayerStatEvent {
+        PlayerStatEvent {
+            event_id: event_id.map(str::to_string),
+            uuid: "player".to_string(),
+            username: "Steve".to_string(),
+            timestamp: 0,
+            event: StatEvent::PlayerDeath,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retried_events_are_counted_once() {
+        let dir = tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db")).await.unwrap();
+        db.init_schema().await.unwrap();
+        let store = SqliteStore::new(db);
+
+        let mut buffer = HashMap::new();
+        buffer.insert("player".to_string(), vec![death(Some("a")), death(Some("b")), death(None)]);
+        StatsProcessor::flush_buffer(&mut buffer, &store).await;
+        // The tracker resends the whole batch after a network error
+        buffer.insert("player".to_string(), vec![death(Some("a")), death(Some("b")), death(Some("a"))]);
+        StatsProcessor::flush_buffer(&mut buffer, &store).await;
+
+        let (json, _) = store.player_stats("player").await.unwrap().unwrap();
+        let stats: PlayerStats = serde_json::from_str(&json).unwrap();
+        assert_eq!(stats.deaths, 3);
     }
 }
-
@@ -0,0 +1,257 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// How long finished tasks stay queryable before being pruned
+const FINISHED_TASK_TTL_SECS: i64 = 60 * 60;
+
+/// Buffered updates per task; slow subscribers skip ahead to the latest snapshot
+const UPDATE_CHANNEL_CAPACITY: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskState {
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Snapshot of a background task, sent to pollers and WebSocket subscribers
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskStatus {
+    pub id: Uuid,
+    /// Operation name, e.g. `publish_draft`
+    pub kind: String,
+    pub state: TaskState,
+    /// Current step, e.g. "Copying files"
+    pub stage: String,
+    /// Items processed within the current stage
+    pub current: u64,
+    /// Total items in the current stage, when known
+    pub total: Option<u64>,
+    /// Detail for the latest item (usually a file path)
+    pub message: Option<String>,
+    /// Handler response body, set on completion
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub started_at: i64,
+    pub updated_at: i64,
+}
+
+impl TaskStatus {
+    pub fn is_finished(&self) -> bool {
+        self.state != TaskState::Running
+    }
+}
+
+struct TaskEntry {
+    status: TaskStatus,
+    updates: broadcast::Sender<TaskStatus>,
+}
+
+/// In-memory registry of long-running admin operations
+///
+/// Tasks do not survive a restart; clients that lose their task should re-check
+/// the resulting release or draft instead.
+#[derive(Clone, Default)]
+pub struct TaskRegistry {
+    tasks: Arc<Mutex<HashMap<Uuid, TaskEntry>>>,
+}
+
+impl TaskRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `work` in the background, returning the task id immediately
+    ///
+    /// The closure receives a `TaskHandle` for reporting progress; its `Ok` value
+    /// becomes the task result and its `Err` the task error.
+    pub fn spawn<F, Fut, E>(&self, kind: &str, work: F) -> Uuid
+    where
+        F: FnOnce(TaskHandle) -> Fut,
+        Fut: Future<Output = Result<serde_json::Value, E>> + Send + 'static,
+        E: std::fmt::Display + Send + 'static,
+    {
+        let handle = self.create(kind);
+        let id = handle.id;
+        let fut = work(handle.clone());
+
+        tokio::spawn(async move {
+            let result = fut.await.map_err(|e| e.to_string());
+            handle.finish(result);
+        });
+
+        id
+    }
+
+    /// Register a task without running anything; the caller reports progress and
+    /// must eventually call `TaskHandle::finish`
+    pub fn create(&self, kind: &str) -> TaskHandle {
+        let now = chrono::Utc::now().timestamp();
+        let id = Uuid::new_v4();
+        let (updates, _) = broadcast::channel(UPDATE_CHANNEL_CAPACITY);
+
+        let status = TaskStatus {
+            id,
+            kind: kind.to_string(),
+            state: TaskState::Running,
+            stage: "Queued".to_string(),
+            current: 0,
+            total: None,
+            message: None,
+            result: None,
+            error: None,
+            started_at: now,
+            updated_at: now,
+        };
+
+        let mut tasks = self.tasks.lock().unwrap();
+        tasks.retain(|_, entry| {
+            !entry.status.is_finished() || now - entry.status.updated_at < FINISHED_TASK_TTL_SECS
+        });
+        tasks.insert(id, TaskEntry { status, updates });
+
+        TaskHandle {
+            id,
+            kind: kind.to_string(),
+            registry: self.clone(),
+        }
+    }
+
+    /// Current snapshot of a task
+    pub fn get(&self, id: Uuid) -> Option<TaskStatus> {
+        self.tasks.lock().unwrap().get(&id).map(|e| e.status.clone())
+    }
+
+    /// Current snapshot plus a receiver for subsequent updates
+    pub fn subscribe(&self, id: Uuid) -> Option<(TaskStatus, broadcast::Receiver<TaskStatus>)> {
+        self.tasks
+            .lock()
+            .unwrap()
+            .get(&id)
+            .map(|e| (e.status.clone(), e.updates.subscribe()))
+    }
+
+    fn update(&self, id: Uuid, apply: impl FnOnce(&mut TaskStatus)) {
+        let mut tasks = self.tasks.lock().unwrap();
+        if let Some(entry) = tasks.get_mut(&id) {
+            apply(&mut entry.status);
+            entry.status.updated_at = chrono::Utc::now().timestamp();
+            // No subscribers is fine
+            let _ = entry.updates.send(entry.status.clone());
+        }
+    }
+}
+
+/// Progress reporter handed to a running task
+#[derive(Clone)]
+pub struct TaskHandle {
+    id: Uuid,
+    kind: String,
+    registry: TaskRegistry,
+}
+
+impl TaskHandle {
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Start a new stage, resetting the item counter
+    pub fn stage(&self, stage: &str, total: Option<u64>) {
+        self.registry.update(self.id, |s| {
+            s.stage = stage.to_string();
+            s.current = 0;
+            s.total = total;
+            s.message = None;
+        });
+    }
+
+    /// Report progress within the current stage
+    pub fn progress(&self, current: u64, message: impl Into<String>) {
+        let message = message.into();
+        self.registry.update(self.id, |s| {
+            s.current = current;
+            s.message = Some(message);
+        });
+    }
+
+    /// Mark the task completed with a result, or failed with an error
+    pub fn finish(&self, result: Result<serde_json::Value, String>) {
+        if let Err(e) = &result {
+            tracing::warn!("Task {} ({}) failed: {}", self.id, self.kind, e);
+        }
+
+        self.registry.update(self.id, |s| match result {
+            Ok(value) => {
+                s.state = TaskState::Completed;
+                s.stage = "Completed".to_string();
+                s.result = Some(value);
+            }
+            Err(e) => {
+                s.state = TaskState::Failed;
+                s.stage = "Failed".to_string();
+                s.error = Some(e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_task_reports_progress_and_result() {
+        let registry = TaskRegistry::new();
+        let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+
+        let id = registry.spawn("test", |task| async move {
+            task.stage("Working", Some(2));
+            task.progress(1, "a.jar");
+            release_rx.await.ok();
+            task.progress(2, "b.jar");
+            Ok::<_, String>(serde_json::json!({ "done": true }))
+        });
+
+        let (_, mut updates) = registry.subscribe(id).unwrap();
+        release_tx.send(()).unwrap();
+
+        let mut last = None;
+        while let Ok(status) = updates.recv().await {
+            let finished = status.is_finished();
+            last = Some(status);
+            if finished {
+                break;
+            }
+        }
+
+        let last = last.unwrap();
+        assert_eq!(last.state, TaskState::Completed);
+        assert_eq!(last.current, 2);
+        assert_eq!(last.result, Some(serde_json::json!({ "done": true })));
+        assert_eq!(registry.get(id).unwrap().state, TaskState::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_task_failure_is_recorded() {
+        let registry = TaskRegistry::new();
+        let id = registry.spawn("test", |_task| async move {
+            Err::<serde_json::Value, _>("boom")
+        });
+
+        for _ in 0..100 {
+            if registry.get(id).unwrap().is_finished() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+
+        let status = registry.get(id).unwrap();
+        assert_eq!(status.state, TaskState::Failed);
+        assert_eq!(status.error.as_deref(), Some("boom"));
+    }
+}
@@ -0,0 +1,19 @@
+//! Fixtures shared by the service tests
+
+use crate::models::ModInfo;
+use std::collections::BTreeMap;
+
+/// A mod in `file`, named after its id, with no dependency metadata
+pub fn mod_info(id: &str, version: &str, file: &str) -> ModInfo {
+    ModInfo {
+        mod_id: id.to_string(),
+        name: id.to_string(),
+        version: version.to_string(),
+        minecraft_version: None,
+        fabric_loader: None,
+        file: Some(file.to_string()),
+        depends: BTreeMap::new(),
+        breaks: BTreeMap::new(),
+        provides: BTreeMap::new(),
+    }
+}
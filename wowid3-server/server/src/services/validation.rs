@@ -0,0 +1,300 @@
+use crate::models::{DraftRelease, ModInfo};
+use crate::services::analyzer::{check_dependencies, is_loader_id};
+use globset::GlobSet;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Players would get a broken release; `publish?validate=true` refuses these
+    Error,
+    /// Worth a look, but the release is still installable
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    /// Stable identifier for the kind of problem (`zero_byte_file`, `duplicate_mod`, ...)
+    pub code: String,
+    pub message: String,
+    /// Path relative to the draft root, when the issue is about one file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+}
+
+/// Result of checking a draft before publish
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+    pub errors: usize,
+    pub warnings: usize,
+    /// Files that would be published (blacklisted files excluded)
+    pub file_count: usize,
+    pub total_size: u64,
+    /// Configured size budget in bytes, if any
+    pub size_budget: Option<u64>,
+}
+
+impl ValidationReport {
+    pub fn has_errors(&self) -> bool {
+        self.errors > 0
+    }
+
+    pub fn push(&mut self, severity: Severity, code: &str, message: String, file: Option<String>) {
+        match severity {
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+        }
+        self.issues.push(ValidationIssue {
+            severity,
+            code: code.to_string(),
+            message,
+            file,
+        });
+    }
+}
+
+/// Archives that are never valid when empty; other empty files (configs) only warn
+fn is_archive(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    lower.ends_with(".jar") || lower.ends_with(".zip")
+}
+
+/// Check a draft's files on disk for problems that commonly break releases
+///
+/// Blocking: walks the draft directory. `mods` is the draft's `scan_mods` output.
+pub fn validate_draft(
+    draft: &DraftRelease,
+    files_dir: &Path,
+    mods: &[ModInfo],
+    blacklist: &GlobSet,
+    size_budget: Option<u64>,
+) -> ValidationReport {
+    let mut report = ValidationReport {
+        size_budget,
+        ..Default::default()
+    };
+
+    for (field, value) in [
+        ("version", &draft.version),
+        ("minecraft_version", &draft.minecraft_version),
+        ("fabric_loader", &draft.fabric_loader),
+    ] {
+        if value.is_empty() {
+            report.push(Severity::Error, "missing_field", format!("{} is not set", field), None);
+        }
+    }
+
+    let mut blacklisted = 0;
+    for entry in WalkDir::new(files_dir)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let Ok(relative) = entry.path().strip_prefix(files_dir) else {
+            continue;
+        };
+        let path = relative.to_string_lossy().replace('\\', "/");
+        if path == "manifest.json" {
+            continue;
+        }
+
+        if crate::utils::is_blacklisted(&path, blacklist) {
+            blacklisted += 1;
+            report.push(
+                Severity::Warning,
+                "blacklisted_file",
+                format!("{} matches the blacklist and will not be published", path),
+                Some(path),
+            );
+            continue;
+        }
+
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        if size == 0 {
+            let severity = if is_archive(&path) { Severity::Error } else { Severity::Warning };
+            report.push(severity, "zero_byte_file", format!("{} is empty", path), Some(path));
+        }
+
+        report.file_count += 1;
+        report.total_size += size;
+    }
+
+    if report.file_count == 0 {
+        let message = if blacklisted > 0 {
+            "Every file in the draft is blacklisted".to_string()
+        } else {
+            "Draft has no files".to_string()
+        };
+        report.push(Severity::Error, "no_files", message, None);
+    }
+
+    if let Some(budget) = size_budget {
+        if report.total_size > budget {
+            report.push(
+                Severity::Error,
+                "size_budget_exceeded",
+                format!(
+                    "Release is {} MB, over the {} MB budget",
+                    report.total_size / 1024 / 1024,
+                    budget / 1024 / 1024
+                ),
+                None,
+            );
+        }
+    }
+
+    let dependencies = check_dependencies(
+        mods,
+        Some(draft.minecraft_version.as_str()).filter(|v| !v.is_empty()),
+        Some(draft.fabric_loader.as_str()).filter(|v| !v.is_empty()),
+    );
+
+    for duplicate in &dependencies.duplicate_mods {
+        report.push(
+            Severity::Error,
+            "duplicate_mod",
+            format!("Mod id {} is shipped by {}", duplicate.mod_id, duplicate.files.join(", ")),
+            duplicate.files.first().cloned(),
+        );
+    }
+
+    for issue in &dependencies.version_mismatches {
+        let (code, target) = if is_loader_id(&issue.dependency) {
+            ("incompatible_loader", format!("Fabric Loader {}", draft.fabric_loader))
+        } else if issue.dependency == "minecraft" {
+            ("incompatible_minecraft", format!("Minecraft {}", draft.minecraft_version))
+        } else {
+            (
+                "dependency_version_mismatch",
+                format!("{} {}", issue.dependency, issue.found.as_deref().unwrap_or("?")),
+            )
+        };
+        report.push(
+            Severity::Error,
+            code,
+            format!("{} requires {} {}, draft has {}", issue.mod_name, issue.dependency, issue.requirement.join(" || "), target),
+            issue.file.clone(),
+        );
+    }
+
+    for issue in &dependencies.missing_dependencies {
+        report.push(
+            Severity::Error,
+            "missing_dependency",
+            format!("{} requires {}, which no mod provides", issue.mod_name, issue.dependency),
+            issue.file.clone(),
+        );
+    }
+
+    for issue in &dependencies.conflicts {
+        report.push(
+            Severity::Error,
+            "mod_conflict",
+            format!(
+                "{} is incompatible with {} {}",
+                issue.mod_name,
+                issue.dependency,
+                issue.found.as_deref().unwrap_or("")
+            ),
+            issue.file.clone(),
+        );
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_support::mod_info;
+    use crate::models::{ModLoader, ReleaseChannel};
+
+    fn draft() -> DraftRelease {
+        DraftRelease {
+            id: uuid::Uuid::new_v4(),
+            version: "1.2.0".to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
+            fabric_loader: "0.15.0".to_string(),
+            changelog: String::new(),
+            files: vec![],
+            channel: ReleaseChannel::default(),
+            optional_groups: vec![],
+            ignore_overrides: Default::default(),
+            revision: 0,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn codes(report: &ValidationReport) -> Vec<&str> {
+        report.issues.iter().map(|i| i.code.as_str()).collect()
+    }
+
+    #[test]
+    fn test_clean_draft_has_no_issues() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("mods")).unwrap();
+        std::fs::write(dir.path().join("mods/a.jar"), b"jar").unwrap();
+
+        let report = validate_draft(&draft(), dir.path(), &[], &GlobSet::empty(), Some(1024));
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+        assert_eq!(report.file_count, 1);
+        assert_eq!(report.total_size, 3);
+    }
+
+    #[test]
+    fn test_file_problems() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("mods")).unwrap();
+        std::fs::create_dir_all(dir.path().join("config")).unwrap();
+        std::fs::write(dir.path().join("mods/empty.jar"), b"").unwrap();
+        std::fs::write(dir.path().join("config/empty.toml"), b"").unwrap();
+        std::fs::write(dir.path().join("config/secret.json"), b"{}").unwrap();
+        std::fs::write(dir.path().join("mods/big.jar"), vec![0u8; 2048]).unwrap();
+
+        let blacklist = crate::utils::compile_patterns(&["config/secret.json".to_string()]).unwrap();
+        let report = validate_draft(&draft(), dir.path(), &[], &blacklist, Some(1024));
+
+        let mut found = codes(&report);
+        found.sort();
+        assert_eq!(
+            found,
+            vec!["blacklisted_file", "size_budget_exceeded", "zero_byte_file", "zero_byte_file"]
+        );
+        assert_eq!(report.errors, 2); // empty jar, budget
+        assert_eq!(report.warnings, 2); // empty config, blacklisted file
+        assert_eq!(report.file_count, 3);
+    }
+
+    #[test]
+    fn test_mod_problems() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("readme.txt"), b"hi").unwrap();
+
+        let mut sodium = mod_info("sodium", "1.0.0", "mods/sodium.jar");
+        sodium.depends.insert("fabricloader".to_string(), vec![">=0.16".to_string()]);
+        let mods = vec![sodium, mod_info("iris", "1.0.0", "mods/iris.jar"), mod_info("iris", "1.0.0", "mods/iris-old.jar")];
+
+        let report = validate_draft(&draft(), dir.path(), &mods, &GlobSet::empty(), None);
+        let mut found = codes(&report);
+        found.sort();
+        assert_eq!(found, vec!["duplicate_mod", "incompatible_loader"]);
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn test_empty_draft() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut draft = draft();
+        draft.fabric_loader.clear();
+
+        let report = validate_draft(&draft, dir.path(), &[], &GlobSet::empty(), None);
+        assert_eq!(codes(&report), vec!["missing_field", "no_files"]);
+    }
+}
@@ -0,0 +1,288 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use s3::creds::Credentials;
+use s3::{Bucket, Region};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::fs;
+
+use crate::config::{Config, StorageBackendKind};
+use crate::models::Manifest;
+
+/// Where published release files are served from
+///
+/// Uploads, drafts and manifests always live on local disk, so the admin workflow is
+/// the same for every backend; a backend only decides how players download files.
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Make the files of a release that was just written to disk downloadable
+    async fn publish_release(&self, config: &Config, manifest: &Manifest) -> Result<()>;
+
+    /// URL to redirect a download of the file with this checksum to; `None` serves it from disk
+    async fn file_url(&self, sha256: &str) -> Result<Option<String>>;
+
+    /// Delete stored files no release references; returns how many were removed
+    async fn collect_garbage(&self, referenced: &HashSet<String>) -> Result<usize>;
+}
+
+/// Build the backend selected in the config
+pub fn from_config(config: &Config) -> Result<Arc<dyn StorageBackend>> {
+    match config.storage_backend {
+        StorageBackendKind::Filesystem => Ok(Arc::new(FilesystemBackend)),
+        StorageBackendKind::S3 => Ok(Arc::new(S3Backend::from_config(config)?)),
+    }
+}
+
+/// Serve release files straight from the release directories
+pub struct FilesystemBackend;
+
+#[async_trait]
+impl StorageBackend for FilesystemBackend {
+    fn name(&self) -> &'static str {
+        "filesystem"
+    }
+
+    async fn publish_release(&self, _config: &Config, _manifest: &Manifest) -> Result<()> {
+        Ok(())
+    }
+
+    async fn file_url(&self, _sha256: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    async fn collect_garbage(&self, _referenced: &HashSet<String>) -> Result<usize> {
+        // The local object store is cleaned by files::collect_garbage
+        Ok(0)
+    }
+}
+
+/// Mirror release files into an S3-compatible bucket and redirect downloads there
+///
+/// Objects are keyed by checksum (`<prefix>objects/<aa>/<sha256>`) like the local
+/// object store, so files shared between releases are uploaded once and CDN caches
+/// never serve stale content.
+pub struct S3Backend {
+    bucket: Box<Bucket>,
+    prefix: String,
+    public_url: Option<String>,
+    presign_expiry_secs: u32,
+}
+
+impl S3Backend {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let bucket_name = config
+            .s3_bucket
+            .as_deref()
+            .context("S3_BUCKET must be set for the s3 storage backend")?;
+
+        let region = match &config.s3_endpoint {
+            Some(endpoint) => Region::Custom {
+                region: config.s3_region.clone(),
+                endpoint: endpoint.trim_end_matches('/').to_string(),
+            },
+            None => config.s3_region.parse().context("Invalid S3_REGION")?,
+        };
+
+        let credentials = Credentials::new(
+            config.s3_access_key.as_deref(),
+            config.s3_secret_key.as_deref(),
+            None,
+            None,
+            None,
+        )
+        .context("Invalid S3 credentials")?;
+
+        let mut bucket = Bucket::new(bucket_name, region, credentials).context("Failed to configure S3 bucket")?;
+        if config.s3_path_style {
+            bucket = bucket.with_path_style();
+        }
+
+        Ok(Self {
+            bucket,
+            prefix: normalize_prefix(&config.s3_prefix),
+            public_url: config
+                .s3_public_url
+                .as_ref()
+                .map(|url| url.trim_end_matches('/').to_string()),
+            presign_expiry_secs: config.s3_presign_expiry_secs,
+        })
+    }
+
+    fn object_key(&self, sha256: &str) -> String {
+        let shard = sha256.get(..2).unwrap_or("00");
+        format!("{}objects/{}/{}", self.prefix, shard, sha256)
+    }
+
+    async fn object_exists(&self, key: &str) -> bool {
+        matches!(self.bucket.head_object(key).await, Ok((_, 200)))
+    }
+}
+
+#[async_trait]
+impl StorageBackend for S3Backend {
+    fn name(&self) -> &'static str {
+        "s3"
+    }
+
+    async fn publish_release(&self, config: &Config, manifest: &Manifest) -> Result<()> {
+        let release_dir = config.release_path(&manifest.version);
+        let mut seen = HashSet::new();
+        let mut uploaded = 0;
+
+        for file in &manifest.files {
+            if !seen.insert(file.sha256.as_str()) {
+                continue;
+            }
+            let key = self.object_key(&file.sha256);
+            if self.object_exists(&key).await {
+                continue;
+            }
+
+            let data = fs::read(release_dir.join(&file.path))
+                .await
+                .with_context(|| format!("Failed to read {} for upload", file.path))?;
+            let content_type = mime_guess::from_path(&file.path)
+                .first_or_octet_stream()
+                .to_string();
+
+            let response = self
+                .bucket
+                .put_object_with_content_type(&key, &data, &content_type)
+                .await
+                .with_context(|| format!("Failed to upload {}", file.path))?;
+            if response.status_code() != 200 {
+                anyhow::bail!(
+                    "Failed to upload {}: bucket returned HTTP {}",
+                    file.path,
+                    response.status_code()
+                );
+            }
+            uploaded += 1;
+        }
+
+        tracing::info!(
+            "Published release {} to S3: {} new objects, {} already stored",
+            manifest.version,
+            uploaded,
+            seen.len() - uploaded
+        );
+        Ok(())
+    }
+
+    async fn file_url(&self, sha256: &str) -> Result<Option<String>> {
+        let key = self.object_key(sha256);
+        if let Some(public_url) = &self.public_url {
+            return Ok(Some(format!("{}/{}", public_url, key)));
+        }
+
+        let url = self
+            .bucket
+            .presign_get(&key, self.presign_expiry_secs, None)
+            .await
+            .context("Failed to presign download URL")?;
+        Ok(Some(url))
+    }
+
+    async fn collect_garbage(&self, referenced: &HashSet<String>) -> Result<usize> {
+        let objects_prefix = format!("{}objects/", self.prefix);
+        let pages = self
+            .bucket
+            .list(objects_prefix, None)
+            .await
+            .context("Failed to list S3 objects")?;
+
+        let mut removed = 0;
+        for object in pages.iter().flat_map(|page| &page.contents) {
+            let sha256 = object.key.rsplit('/').next().unwrap_or_default();
+            if referenced.contains(sha256) {
+                continue;
+            }
+            self.bucket
+                .delete_object(&object.key)
+                .await
+                .with_context(|| format!("Failed to delete S3 object {}", object.key))?;
+            removed += 1;
+        }
+
+        if removed > 0 {
+            tracing::info!("Removed {} unreferenced S3 object(s)", removed);
+        }
+        Ok(removed)
+    }
+}
+
+/// `""` stays empty, anything else gets exactly one trailing slash
+fn normalize_prefix(prefix: &str) -> String {
+    let prefix = prefix.trim().trim_matches('/');
+    if prefix.is_empty() {
+        String::new()
+    } else {
+        format!("{}/", prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn s3_config(extra: serde_json::Value) -> Config {
+        let mut value = serde_json::json!({
+            "storage_backend": "s3",
+            "s3_bucket": "releases",
+            "s3_endpoint": "https://s3.example.com",
+            "s3_access_key": "access",
+            "s3_secret_key": "secret",
+            "s3_prefix": "/wowid3/",
+        });
+        value.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_normalize_prefix() {
+        assert_eq!(normalize_prefix(""), "");
+        assert_eq!(normalize_prefix("/"), "");
+        assert_eq!(normalize_prefix("wowid3"), "wowid3/");
+        assert_eq!(normalize_prefix("/a/b/"), "a/b/");
+    }
+
+    #[tokio::test]
+    async fn test_filesystem_backend_serves_from_disk() {
+        let backend = from_config(&serde_json::from_value(serde_json::json!({})).unwrap()).unwrap();
+        assert_eq!(backend.name(), "filesystem");
+        assert_eq!(backend.file_url(&"a".repeat(64)).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_s3_presigned_url() {
+        let backend = from_config(&s3_config(serde_json::json!({}))).unwrap();
+        assert_eq!(backend.name(), "s3");
+
+        let sha256 = format!("ab{}", "c".repeat(62));
+        let url = backend.file_url(&sha256).await.unwrap().unwrap();
+        assert!(url.contains(&format!("wowid3/objects/ab/{}", sha256)));
+        assert!(url.contains("X-Amz-Signature="));
+    }
+
+    #[tokio::test]
+    async fn test_s3_public_url_skips_presigning() {
+        let backend = from_config(&s3_config(serde_json::json!({
+            "s3_public_url": "https://cdn.example.com/"
+        })))
+        .unwrap();
+
+        let sha256 = format!("ab{}", "c".repeat(62));
+        assert_eq!(
+            backend.file_url(&sha256).await.unwrap(),
+            Some(format!("https://cdn.example.com/wowid3/objects/ab/{}", sha256))
+        );
+    }
+
+    #[test]
+    fn test_s3_requires_bucket() {
+        let config: Config = serde_json::from_value(serde_json::json!({ "storage_backend": "s3" })).unwrap();
+        assert!(from_config(&config).is_err());
+    }
+}
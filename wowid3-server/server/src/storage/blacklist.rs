@@ -0,0 +1,247 @@
+use crate::config::Config;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use globset::GlobBuilder;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+/// Revisions kept in blacklist-history.json; older ones are dropped
+const MAX_HISTORY: usize = 100;
+
+/// One saved version of the blacklist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlacklistRevision {
+    /// Increases by one with every save
+    pub revision: u64,
+    pub patterns: Vec<String>,
+    pub changed_by: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// A file the proposed patterns would keep out of a release
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExcludedFile {
+    pub path: String,
+    pub size: u64,
+    /// First pattern that matches the file
+    pub pattern: String,
+}
+
+/// How many files a single pattern matches
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PatternMatches {
+    pub pattern: String,
+    pub files: usize,
+}
+
+/// Effect of a proposed blacklist on a set of release files
+#[derive(Debug, Clone, Serialize)]
+pub struct BlacklistPreview {
+    /// What the patterns were checked against (`release 1.2.0` or `draft <id>`)
+    pub source: String,
+    pub total_files: usize,
+    pub excluded_bytes: u64,
+    pub excluded: Vec<ExcludedFile>,
+    /// Per-pattern counts in the order given, so an overly broad wildcard stands out
+    pub pattern_matches: Vec<PatternMatches>,
+}
+
+/// Trim patterns and reject empty, duplicate, uncompilable or match-everything ones
+///
+/// Blank lines are dropped. Patterns are stored one per line, so a leading `#`
+/// would turn them into comments and is rejected too.
+pub fn validate_patterns(patterns: Vec<String>) -> Result<Vec<String>> {
+    let mut valid: Vec<String> = Vec::with_capacity(patterns.len());
+
+    for pattern in patterns {
+        let pattern = pattern.trim().to_string();
+        if pattern.is_empty() {
+            continue;
+        }
+        if pattern.starts_with('#') {
+            anyhow::bail!("Pattern '{}' starts with '#' and would be read as a comment", pattern);
+        }
+        if pattern.chars().all(|c| matches!(c, '*' | '?' | '/')) {
+            anyhow::bail!("Pattern '{}' would exclude every file", pattern);
+        }
+        GlobBuilder::new(&pattern)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Invalid glob pattern '{}': {}", pattern, e.kind()))?;
+        if valid.contains(&pattern) {
+            anyhow::bail!("Pattern '{}' is listed more than once", pattern);
+        }
+        valid.push(pattern);
+    }
+
+    Ok(valid)
+}
+
+/// Which of `files` (path, size) the patterns exclude
+pub fn preview(patterns: &[String], files: &[(String, u64)], source: String) -> Result<BlacklistPreview> {
+    let glob_set = crate::utils::compile_patterns(patterns)?;
+    let mut pattern_matches: Vec<PatternMatches> = patterns
+        .iter()
+        .map(|pattern| PatternMatches {
+            pattern: pattern.clone(),
+            files: 0,
+        })
+        .collect();
+    let mut excluded = Vec::new();
+
+    for (path, size) in files {
+        let matches = glob_set.matches(path);
+        let Some(&first) = matches.first() else {
+            continue;
+        };
+        for &index in &matches {
+            pattern_matches[index].files += 1;
+        }
+        excluded.push(ExcludedFile {
+            path: path.clone(),
+            size: *size,
+            pattern: patterns[first].clone(),
+        });
+    }
+
+    Ok(BlacklistPreview {
+        source,
+        total_files: files.len(),
+        excluded_bytes: excluded.iter().map(|f| f.size).sum(),
+        excluded,
+        pattern_matches,
+    })
+}
+
+/// Write the blacklist file the publisher and file server read
+pub async fn save_blacklist(config: &Config, patterns: &[String]) -> Result<()> {
+    let content = format!(
+        "# Blacklist patterns - files matching these patterns are not synced to clients\n# Glob patterns are supported (e.g., journeymap/**, *.txt)\n{}",
+        patterns.iter().map(|p| format!("{}\n", p)).collect::<String>()
+    );
+    write_atomic(&config.blacklist_path(), content.as_bytes()).await
+}
+
+/// Saved blacklist revisions, oldest first (empty if none were recorded yet)
+pub async fn load_history(config: &Config) -> Result<Vec<BlacklistRevision>> {
+    let path = config.blacklist_history_path();
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .await
+        .context("Failed to read blacklist history")?;
+
+    serde_json::from_str(&content).context("Failed to parse blacklist history")
+}
+
+/// Append a revision for `patterns`, returning it
+pub async fn record_revision(config: &Config, patterns: &[String], changed_by: &str) -> Result<BlacklistRevision> {
+    let mut history = load_history(config).await?;
+    let revision = BlacklistRevision {
+        revision: history.last().map_or(1, |last| last.revision + 1),
+        patterns: patterns.to_vec(),
+        changed_by: changed_by.to_string(),
+        changed_at: Utc::now(),
+    };
+    history.push(revision.clone());
+    if history.len() > MAX_HISTORY {
+        history.drain(..history.len() - MAX_HISTORY);
+    }
+
+    let json = serde_json::to_string_pretty(&history).context("Failed to serialize blacklist history")?;
+    write_atomic(&config.blacklist_history_path(), json.as_bytes()).await?;
+    Ok(revision)
+}
+
+async fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    let parent = path.parent().context("Invalid path")?;
+    fs::create_dir_all(parent)
+        .await
+        .context("Failed to create storage directory")?;
+
+    let temp_path = parent.join(format!(".tmp.blacklist.{}", uuid::Uuid::new_v4()));
+
+    let mut file = fs::File::create(&temp_path)
+        .await
+        .context("Failed to create temp file")?;
+
+    file.write_all(content)
+        .await
+        .context("Failed to write temp file")?;
+
+    file.sync_all().await.context("Failed to sync")?;
+    drop(file);
+
+    fs::rename(&temp_path, path)
+        .await
+        .context("Failed to rename temp file")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_validate_patterns() {
+        assert_eq!(
+            validate_patterns(patterns(&[" logs/** ", "", "*.txt"])).unwrap(),
+            patterns(&["logs/**", "*.txt"])
+        );
+        assert!(validate_patterns(patterns(&["config/[abc"])).is_err());
+        assert!(validate_patterns(patterns(&["**"])).is_err());
+        assert!(validate_patterns(patterns(&["*/*"])).is_err());
+        assert!(validate_patterns(patterns(&["#notes"])).is_err());
+        assert!(validate_patterns(patterns(&["logs/**", "logs/**"])).is_err());
+    }
+
+    #[test]
+    fn test_preview_attributes_files_to_patterns() {
+        let files = vec![
+            ("mods/sodium.jar".to_string(), 100),
+            ("config/sodium.json".to_string(), 10),
+            ("config/iris.properties".to_string(), 5),
+        ];
+        let preview = preview(&patterns(&["config/**", "*sodium*"]), &files, "release 1.0.0".to_string()).unwrap();
+
+        assert_eq!(preview.total_files, 3);
+        assert_eq!(preview.excluded.len(), 3);
+        assert_eq!(preview.excluded_bytes, 115);
+        assert_eq!(preview.excluded[0].pattern, "*sodium*");
+        assert_eq!(preview.excluded[1].pattern, "config/**");
+        assert_eq!(
+            preview.pattern_matches,
+            vec![
+                PatternMatches { pattern: "config/**".to_string(), files: 2 },
+                PatternMatches { pattern: "*sodium*".to_string(), files: 2 },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_numbers_revisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "storage_path": dir.path()
+        }))
+        .unwrap();
+
+        record_revision(&config, &patterns(&["logs/**"]), "alice").await.unwrap();
+        let second = record_revision(&config, &patterns(&["logs/**", "*.txt"]), "bob").await.unwrap();
+        assert_eq!(second.revision, 2);
+
+        let history = load_history(&config).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].changed_by, "alice");
+        assert_eq!(history[1].patterns, patterns(&["logs/**", "*.txt"]));
+    }
+}
@@ -0,0 +1,463 @@
+use crate::config::Config;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+/// Themes the launcher ships with
+const LAUNCHER_THEMES: &[&str] = &["christmas", "dark", "light"];
+
+/// Polling faster than this would hammer the server from every running launcher
+const MIN_POLL_INTERVAL_MS: u64 = 1_000;
+const MAX_POLL_INTERVAL_MS: u64 = 24 * 60 * 60 * 1000;
+const MAX_RETRY_ATTEMPTS: u32 = 10;
+
+/// Branding images and launcher audio; nothing else is served from the asset store
+const ASSET_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "svg", "ico", "mp3", "ogg", "wav"];
+const MAX_ASSET_NAME_LEN: usize = 128;
+/// Large enough for a menu music track
+pub const MAX_ASSET_BYTES: usize = 50 * 1024 * 1024;
+
+/// Names shown in the launcher
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Branding {
+    pub server_name: String,
+    pub tagline: Option<String>,
+    pub discord_url: Option<String>,
+    pub website_url: Option<String>,
+}
+
+impl Default for Branding {
+    fn default() -> Self {
+        Self {
+            server_name: "WOWID3".to_string(),
+            tagline: None,
+            discord_url: None,
+            website_url: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// Theme for players who haven't picked one
+    pub default_theme: String,
+    /// `#rrggbb` override for the theme's accent color
+    pub accent_color: Option<String>,
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            default_theme: "christmas".to_string(),
+            accent_color: None,
+        }
+    }
+}
+
+/// How often the launcher polls the server (milliseconds)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PollingIntervals {
+    pub server_status_ms: u64,
+    pub tracker_status_ms: u64,
+    /// Modpack update checks while the event stream is disconnected
+    pub update_check_ms: u64,
+}
+
+impl Default for PollingIntervals {
+    fn default() -> Self {
+        Self {
+            server_status_ms: 30_000,
+            tracker_status_ms: 5_000,
+            update_check_ms: 300_000,
+        }
+    }
+}
+
+/// Backoff the launcher uses when a poll fails
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryConfig {
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay_ms: 1_000,
+            max_delay_ms: 30_000,
+        }
+    }
+}
+
+/// Launcher settings the server controls, so they can change without a launcher release
+///
+/// Served publicly, so nothing secret belongs here. Stored configs are read leniently
+/// (unknown keys are dropped); admin updates go through [`parse_update`], which rejects them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CmsConfig {
+    pub branding: Branding,
+    pub theme: ThemeConfig,
+    pub polling: PollingIntervals,
+    pub retry: RetryConfig,
+    /// Feature flags by name; flags the launcher doesn't know are ignored
+    pub features: BTreeMap<String, bool>,
+}
+
+/// A file in the CMS asset store (branding images, audio)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CmsAsset {
+    pub filename: String,
+    pub size: u64,
+    pub content_type: String,
+    /// Unix timestamp of the last upload
+    pub modified: i64,
+}
+
+/// Trim optional strings, turning blank ones into `None`
+fn optional(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+fn validate_url(name: &str, url: &Option<String>) -> Result<()> {
+    if let Some(url) = url {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            anyhow::bail!("{} must be an http(s) URL", name);
+        }
+    }
+    Ok(())
+}
+
+/// Parse and validate an admin update
+///
+/// Unknown keys are rejected so a typo'd field fails the update instead of silently
+/// falling back to a default.
+pub fn parse_update(value: serde_json::Value) -> Result<CmsConfig> {
+    let cms: CmsConfig = serde_json::from_value(value.clone()).context("Invalid CMS config")?;
+    let known = serde_json::to_value(&cms).context("Failed to serialize CMS config")?;
+    if let Some(field) = unknown_field(&value, &known, "") {
+        anyhow::bail!("Unknown field {}", field);
+    }
+    validate_config(cms)
+}
+
+/// First key in `given` that didn't survive the round trip through `CmsConfig`
+fn unknown_field(given: &serde_json::Value, known: &serde_json::Value, prefix: &str) -> Option<String> {
+    let (serde_json::Value::Object(given), serde_json::Value::Object(known)) = (given, known) else {
+        return None;
+    };
+    given.iter().find_map(|(key, value)| {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match known.get(key) {
+            Some(known) => unknown_field(value, known, &path),
+            None => Some(path),
+        }
+    })
+}
+
+/// Check the config is something the launcher can safely apply, normalizing strings
+pub fn validate_config(config: CmsConfig) -> Result<CmsConfig> {
+    let branding = Branding {
+        server_name: config.branding.server_name.trim().to_string(),
+        tagline: optional(config.branding.tagline),
+        discord_url: optional(config.branding.discord_url),
+        website_url: optional(config.branding.website_url),
+    };
+    if branding.server_name.is_empty() {
+        anyhow::bail!("branding.server_name cannot be empty");
+    }
+    validate_url("branding.discord_url", &branding.discord_url)?;
+    validate_url("branding.website_url", &branding.website_url)?;
+
+    let theme = ThemeConfig {
+        default_theme: config.theme.default_theme.trim().to_lowercase(),
+        accent_color: optional(config.theme.accent_color).map(|c| c.to_lowercase()),
+    };
+    if !LAUNCHER_THEMES.contains(&theme.default_theme.as_str()) {
+        anyhow::bail!("theme.default_theme must be one of: {}", LAUNCHER_THEMES.join(", "));
+    }
+    if let Some(color) = &theme.accent_color {
+        let hex = color.strip_prefix('#').unwrap_or_default();
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            anyhow::bail!("theme.accent_color must be a #rrggbb color");
+        }
+    }
+
+    let polling = config.polling;
+    for (name, value) in [
+        ("polling.server_status_ms", polling.server_status_ms),
+        ("polling.tracker_status_ms", polling.tracker_status_ms),
+        ("polling.update_check_ms", polling.update_check_ms),
+    ] {
+        if !(MIN_POLL_INTERVAL_MS..=MAX_POLL_INTERVAL_MS).contains(&value) {
+            anyhow::bail!(
+                "{} must be between {} and {}",
+                name,
+                MIN_POLL_INTERVAL_MS,
+                MAX_POLL_INTERVAL_MS
+            );
+        }
+    }
+
+    let retry = config.retry;
+    if retry.max_attempts > MAX_RETRY_ATTEMPTS {
+        anyhow::bail!("retry.max_attempts cannot exceed {}", MAX_RETRY_ATTEMPTS);
+    }
+    if retry.base_delay_ms == 0 || retry.base_delay_ms > retry.max_delay_ms {
+        anyhow::bail!("retry.base_delay_ms must be positive and at most retry.max_delay_ms");
+    }
+
+    let mut features = BTreeMap::new();
+    for (name, enabled) in config.features {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            anyhow::bail!("Feature flag names cannot be empty");
+        }
+        features.insert(name, enabled);
+    }
+
+    Ok(CmsConfig {
+        branding,
+        theme,
+        polling,
+        retry,
+        features,
+    })
+}
+
+/// Load the CMS config (defaults if none was saved yet)
+pub async fn load_cms_config(config: &Config) -> Result<CmsConfig> {
+    let path = config.cms_config_path();
+
+    if !path.exists() {
+        return Ok(CmsConfig::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .await
+        .context("Failed to read CMS config")?;
+
+    serde_json::from_str(&content).context("Failed to parse CMS config")
+}
+
+/// Save the CMS config
+pub async fn save_cms_config(config: &Config, cms: &CmsConfig) -> Result<()> {
+    let path = config.cms_config_path();
+
+    let parent = path.parent().context("Invalid path")?;
+    fs::create_dir_all(parent)
+        .await
+        .context("Failed to create storage directory")?;
+
+    let json = serde_json::to_string_pretty(cms).context("Failed to serialize CMS config")?;
+
+    // Atomic write
+    let temp_path = parent.join(format!(".tmp.cms.{}", uuid::Uuid::new_v4()));
+
+    let mut file = fs::File::create(&temp_path)
+        .await
+        .context("Failed to create temp file")?;
+
+    file.write_all(json.as_bytes())
+        .await
+        .context("Failed to write temp file")?;
+
+    file.sync_all().await.context("Failed to sync")?;
+    drop(file);
+
+    fs::rename(&temp_path, &path)
+        .await
+        .context("Failed to rename temp file")?;
+
+    Ok(())
+}
+
+/// Check an asset filename is a plain file name with an allowed extension
+pub fn validate_asset_filename(filename: &str) -> Result<()> {
+    if filename.is_empty() || filename.len() > MAX_ASSET_NAME_LEN {
+        anyhow::bail!("Asset filename must be 1-{} characters", MAX_ASSET_NAME_LEN);
+    }
+    if filename.starts_with('.')
+        || !filename
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+    {
+        anyhow::bail!("Asset filename may only contain letters, digits, '.', '-' and '_'");
+    }
+    let extension = filename
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    if !ASSET_EXTENSIONS.contains(&extension.as_str()) {
+        anyhow::bail!("Unsupported asset type (allowed: {})", ASSET_EXTENSIONS.join(", "));
+    }
+    Ok(())
+}
+
+/// Content type served for an asset
+pub fn asset_content_type(filename: &str) -> String {
+    mime_guess::from_path(filename).first_or_octet_stream().to_string()
+}
+
+fn asset_info(filename: String, metadata: &std::fs::Metadata) -> CmsAsset {
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+
+    CmsAsset {
+        content_type: asset_content_type(&filename),
+        size: metadata.len(),
+        modified,
+        filename,
+    }
+}
+
+/// Store an asset, replacing any existing file with the same name
+pub async fn save_asset(config: &Config, filename: &str, data: &[u8]) -> Result<CmsAsset> {
+    validate_asset_filename(filename)?;
+    if data.len() > MAX_ASSET_BYTES {
+        anyhow::bail!("Asset exceeds {} MB", MAX_ASSET_BYTES / 1024 / 1024);
+    }
+
+    let dir = config.cms_assets_path();
+    fs::create_dir_all(&dir)
+        .await
+        .context("Failed to create CMS assets directory")?;
+
+    // Atomic write
+    let temp_path = dir.join(format!(".tmp.asset.{}", uuid::Uuid::new_v4()));
+    let mut file = fs::File::create(&temp_path)
+        .await
+        .context("Failed to create temp file")?;
+    file.write_all(data).await.context("Failed to write temp file")?;
+    file.sync_all().await.context("Failed to sync")?;
+    drop(file);
+
+    let path = dir.join(filename);
+    fs::rename(&temp_path, &path)
+        .await
+        .context("Failed to rename temp file")?;
+
+    let metadata = fs::metadata(&path).await.context("Failed to read asset metadata")?;
+    Ok(asset_info(filename.to_string(), &metadata))
+}
+
+/// All stored assets, sorted by filename
+pub async fn list_assets(config: &Config) -> Result<Vec<CmsAsset>> {
+    let dir = config.cms_assets_path();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut assets = Vec::new();
+    let mut entries = fs::read_dir(&dir)
+        .await
+        .context("Failed to read CMS assets directory")?;
+    while let Some(entry) = entries.next_entry().await? {
+        let filename = entry.file_name().to_string_lossy().into_owned();
+        let metadata = entry.metadata().await?;
+        // Skips in-progress temp files too
+        if !metadata.is_file() || validate_asset_filename(&filename).is_err() {
+            continue;
+        }
+        assets.push(asset_info(filename, &metadata));
+    }
+
+    assets.sort_by(|a, b| a.filename.cmp(&b.filename));
+    Ok(assets)
+}
+
+/// Delete an asset, returning false if it didn't exist
+pub async fn delete_asset(config: &Config, filename: &str) -> Result<bool> {
+    validate_asset_filename(filename)?;
+
+    match fs::remove_file(config.cms_assets_path().join(filename)).await {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e).context("Failed to delete asset"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_config() {
+        let defaults = validate_config(CmsConfig::default()).unwrap();
+        assert_eq!(defaults, CmsConfig::default());
+
+        // Partial configs fill in defaults
+        let partial: CmsConfig = serde_json::from_str(r#"{"polling": {"tracker_status_ms": 10000}}"#).unwrap();
+        assert_eq!(partial.polling.tracker_status_ms, 10_000);
+        assert_eq!(partial.polling.update_check_ms, 300_000);
+
+        let mut config = CmsConfig::default();
+        config.theme.accent_color = Some(" #FF8800 ".to_string());
+        config.branding.discord_url = Some(" ".to_string());
+        let config = validate_config(config).unwrap();
+        assert_eq!(config.theme.accent_color.as_deref(), Some("#ff8800"));
+        assert_eq!(config.branding.discord_url, None);
+
+        let typo = parse_update(serde_json::json!({"polling": {"update_check": 60000}}));
+        assert_eq!(typo.unwrap_err().to_string(), "Unknown field polling.update_check");
+        assert!(parse_update(serde_json::json!({"branding": {"server_name": "X"}, "themes": {}})).is_err());
+        // Feature flags are free-form
+        let flags = parse_update(serde_json::json!({"features": {"new_menu": true}})).unwrap();
+        assert_eq!(flags.features.get("new_menu"), Some(&true));
+
+        let mut config = CmsConfig::default();
+        config.polling.server_status_ms = 10;
+        assert!(validate_config(config).is_err());
+
+        let mut config = CmsConfig::default();
+        config.theme.default_theme = "neon".to_string();
+        assert!(validate_config(config).is_err());
+
+        let mut config = CmsConfig::default();
+        config.retry.base_delay_ms = 60_000;
+        assert!(validate_config(config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_load_ignores_unknown_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "storage_path": dir.path()
+        }))
+        .unwrap();
+        fs::create_dir_all(config.cms_config_path().parent().unwrap()).await.unwrap();
+        // Written by a newer server, or edited by hand
+        fs::write(
+            config.cms_config_path(),
+            r#"{"branding": {"server_name": "Test", "logo": "x.png"}, "announcements": []}"#,
+        )
+        .await
+        .unwrap();
+
+        let cms = load_cms_config(&config).await.unwrap();
+        assert_eq!(cms.branding.server_name, "Test");
+    }
+
+    #[test]
+    fn test_validate_asset_filename() {
+        assert!(validate_asset_filename("logo.png").is_ok());
+        assert!(validate_asset_filename("Menu_Theme-2.OGG").is_ok());
+        assert!(validate_asset_filename("../secrets.png").is_err());
+        assert!(validate_asset_filename(".hidden.png").is_err());
+        assert!(validate_asset_filename("page.html").is_err());
+        assert!(validate_asset_filename("noext").is_err());
+        assert_eq!(asset_content_type("logo.svg"), "image/svg+xml");
+    }
+}
@@ -0,0 +1,141 @@
+use crate::config::Config;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+/// Longest mod id Fabric accepts
+const MAX_MOD_ID_LENGTH: usize = 64;
+
+/// A mod known to break the pack when a player adds it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncompatibleMod {
+    /// Fabric mod id (`id` in fabric.mod.json)
+    pub mod_id: String,
+    /// Shown to the player in the launcher's pre-launch warning
+    pub reason: String,
+}
+
+/// Compatibility list the launcher checks user-added mods against before launch
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModCompatibilityList {
+    #[serde(default)]
+    pub incompatible: Vec<IncompatibleMod>,
+}
+
+/// Normalize a mod id: trimmed, lowercase, limited to Fabric's [a-z0-9_-]
+pub fn normalize_mod_id(mod_id: &str) -> Result<String> {
+    let mod_id = mod_id.trim().to_lowercase();
+
+    if mod_id.is_empty() {
+        anyhow::bail!("Mod id cannot be empty");
+    }
+    if mod_id.len() > MAX_MOD_ID_LENGTH {
+        anyhow::bail!("Mod id '{}' exceeds {} characters", mod_id, MAX_MOD_ID_LENGTH);
+    }
+    if !mod_id
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    {
+        anyhow::bail!("Mod id '{}' contains invalid characters (allowed: a-z, 0-9, '-', '_')", mod_id);
+    }
+
+    Ok(mod_id)
+}
+
+/// Normalize every entry, rejecting duplicates and entries without a reason
+pub fn validate_list(list: ModCompatibilityList) -> Result<ModCompatibilityList> {
+    let mut incompatible: Vec<IncompatibleMod> = Vec::with_capacity(list.incompatible.len());
+
+    for entry in list.incompatible {
+        let mod_id = normalize_mod_id(&entry.mod_id)?;
+        let reason = entry.reason.trim().to_string();
+        if reason.is_empty() {
+            anyhow::bail!("A reason is required for '{}'", mod_id);
+        }
+        if incompatible.iter().any(|e| e.mod_id == mod_id) {
+            anyhow::bail!("'{}' is listed more than once", mod_id);
+        }
+        incompatible.push(IncompatibleMod { mod_id, reason });
+    }
+
+    incompatible.sort_by(|a, b| a.mod_id.cmp(&b.mod_id));
+    Ok(ModCompatibilityList { incompatible })
+}
+
+/// Load the compatibility list (empty if none was saved yet)
+pub async fn load_compatibility_list(config: &Config) -> Result<ModCompatibilityList> {
+    let path = config.mod_compatibility_path();
+
+    if !path.exists() {
+        return Ok(ModCompatibilityList::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .await
+        .context("Failed to read mod compatibility list")?;
+
+    serde_json::from_str(&content).context("Failed to parse mod compatibility list")
+}
+
+/// Save the compatibility list
+pub async fn save_compatibility_list(config: &Config, list: &ModCompatibilityList) -> Result<()> {
+    let path = config.mod_compatibility_path();
+
+    let parent = path.parent().context("Invalid path")?;
+    fs::create_dir_all(parent)
+        .await
+        .context("Failed to create storage directory")?;
+
+    let json = serde_json::to_string_pretty(list)
+        .context("Failed to serialize mod compatibility list")?;
+
+    // Atomic write
+    let temp_path = parent.join(format!(".tmp.compatibility.{}", uuid::Uuid::new_v4()));
+
+    let mut file = fs::File::create(&temp_path)
+        .await
+        .context("Failed to create temp file")?;
+
+    file.write_all(json.as_bytes())
+        .await
+        .context("Failed to write temp file")?;
+
+    file.sync_all().await.context("Failed to sync")?;
+    drop(file);
+
+    fs::rename(&temp_path, &path)
+        .await
+        .context("Failed to rename temp file")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(mod_id: &str, reason: &str) -> IncompatibleMod {
+        IncompatibleMod {
+            mod_id: mod_id.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_list() {
+        let list = validate_list(ModCompatibilityList {
+            incompatible: vec![entry(" OptiFabric ", "Breaks Sodium"), entry("bclib", " Crashes on world load ")],
+        })
+        .unwrap();
+        assert_eq!(list.incompatible, vec![entry("bclib", "Crashes on world load"), entry("optifabric", "Breaks Sodium")]);
+
+        let duplicate = ModCompatibilityList {
+            incompatible: vec![entry("optifabric", "a"), entry("OPTIFABRIC", "b")],
+        };
+        assert!(validate_list(duplicate).is_err());
+        assert!(validate_list(ModCompatibilityList { incompatible: vec![entry("optifabric", " ")] }).is_err());
+        assert!(normalize_mod_id("bad id").is_err());
+        assert!(normalize_mod_id("").is_err());
+    }
+}
@@ -1,11 +1,70 @@
-use crate::models::{DraftFile, DraftRelease};
+use crate::models::{DraftFile, DraftRelease, IgnorePatternOverrides, ModLoader, OptionalGroup, ReleaseChannel};
 use anyhow::{Context, Result};
 use chrono::Utc;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
 use uuid::Uuid;
 
+/// An edit was based on an older revision of the draft than the one on disk
+#[derive(Debug, thiserror::Error)]
+#[error("Draft was changed by someone else (revision {current}, edit based on {expected}); reload and try again")]
+pub struct DraftConflict {
+    pub expected: u64,
+    pub current: u64,
+}
+
+/// One lock per draft, so read-modify-write edits can't interleave
+fn draft_lock(id: Uuid) -> Arc<AsyncMutex<()>> {
+    static LOCKS: OnceLock<Mutex<HashMap<Uuid, Arc<AsyncMutex<()>>>>> = OnceLock::new();
+    let mut locks = LOCKS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    locks.entry(id).or_default().clone()
+}
+
+/// A draft loaded for editing; other edits of the same draft wait until it's committed or dropped
+pub struct DraftEdit {
+    pub draft: DraftRelease,
+    storage_path: PathBuf,
+    _lock: OwnedMutexGuard<()>,
+}
+
+impl DraftEdit {
+    /// Write the draft back as the next revision
+    pub async fn commit(mut self) -> Result<DraftRelease> {
+        self.draft.revision += 1;
+        self.draft.updated_at = Utc::now();
+        write_draft(&self.storage_path, &self.draft).await?;
+        Ok(self.draft)
+    }
+}
+
+/// Lock a draft and load it for editing
+///
+/// With `expected_revision` set (the client's `If-Match`), fails with `DraftConflict`
+/// if the draft changed since the client loaded it.
+pub async fn edit_draft(storage_path: &Path, id: Uuid, expected_revision: Option<u64>) -> Result<DraftEdit> {
+    let lock = draft_lock(id).lock_owned().await;
+    let draft = read_draft(storage_path, id).await?;
+    if let Some(expected) = expected_revision.filter(|expected| *expected != draft.revision) {
+        return Err(DraftConflict {
+            expected,
+            current: draft.revision,
+        }
+        .into());
+    }
+    Ok(DraftEdit {
+        draft,
+        storage_path: storage_path.to_path_buf(),
+        _lock: lock,
+    })
+}
+
 /// Create a new draft release
 pub async fn create_draft(storage_path: &Path, version: Option<String>) -> Result<DraftRelease> {
     let id = Uuid::new_v4();
@@ -19,9 +78,14 @@ pub async fn create_draft(storage_path: &Path, version: Option<String>) -> Resul
         id,
         version: version.unwrap_or_else(|| "0.0.0".to_string()),
         minecraft_version: String::new(),
+        loader: ModLoader::default(),
         fabric_loader: String::new(),
         changelog: String::new(),
         files: Vec::new(),
+        channel: ReleaseChannel::default(),
+        optional_groups: Vec::new(),
+        ignore_overrides: IgnorePatternOverrides::default(),
+        revision: 0,
         created_at: Utc::now(),
         updated_at: Utc::now(),
     };
@@ -64,7 +128,7 @@ pub async fn write_draft(storage_path: &Path, draft: &DraftRelease) -> Result<()
 }
 
 /// Atomic write with fsync: write to temp file, fsync, then rename
-async fn write_atomic(path: &PathBuf, content: &[u8]) -> Result<()> {
+pub async fn write_atomic(path: &PathBuf, content: &[u8]) -> Result<()> {
     let parent = path.parent().context("Invalid file path")?;
     let temp_path = parent.join(format!(".tmp.{}", uuid::Uuid::new_v4()));
 
@@ -101,8 +165,10 @@ pub async fn update_draft(
     minecraft_version: Option<String>,
     fabric_loader: Option<String>,
     changelog: Option<String>,
+    channel: Option<ReleaseChannel>,
 ) -> Result<DraftRelease> {
-    let mut draft = read_draft(storage_path, id).await?;
+    let mut edit = edit_draft(storage_path, id, None).await?;
+    let draft = &mut edit.draft;
 
     if let Some(v) = version {
         draft.version = v;
@@ -116,12 +182,11 @@ pub async fn update_draft(
     if let Some(cl) = changelog {
         draft.changelog = cl;
     }
+    if let Some(ch) = channel {
+        draft.channel = ch;
+    }
 
-    draft.updated_at = Utc::now();
-
-    write_draft(storage_path, &draft).await?;
-
-    Ok(draft)
+    edit.commit().await
 }
 
 /// List all draft releases
@@ -161,6 +226,8 @@ pub async fn list_drafts(storage_path: &Path) -> Result<Vec<DraftRelease>> {
 
 /// Delete a draft release
 pub async fn delete_draft(storage_path: &Path, id: Uuid) -> Result<()> {
+    // Let an edit in progress finish first
+    let _lock = draft_lock(id).lock_owned().await;
     let draft_dir = storage_path.join("drafts").join(id.to_string());
 
     if draft_dir.exists() {
@@ -177,21 +244,18 @@ pub async fn add_files_to_draft(
     storage_path: &Path,
     id: Uuid,
     files: Vec<DraftFile>,
+    expected_revision: Option<u64>,
 ) -> Result<DraftRelease> {
-    let mut draft = read_draft(storage_path, id).await?;
+    let mut edit = edit_draft(storage_path, id, expected_revision).await?;
 
     // Add files to draft, avoiding duplicates
     for file in files {
-        if !draft.files.iter().any(|f| f.path == file.path) {
-            draft.files.push(file);
+        if !edit.draft.files.iter().any(|f| f.path == file.path) {
+            edit.draft.files.push(file);
         }
     }
 
-    draft.updated_at = Utc::now();
-
-    write_draft(storage_path, &draft).await?;
-
-    Ok(draft)
+    edit.commit().await
 }
 
 /// Replace all files in a draft with new files
@@ -201,15 +265,47 @@ pub async fn set_draft_files(
     id: Uuid,
     files: Vec<DraftFile>,
 ) -> Result<DraftRelease> {
-    let mut draft = read_draft(storage_path, id).await?;
+    let mut edit = edit_draft(storage_path, id, None).await?;
 
     // Replace entire file list
-    draft.files = files;
-    draft.updated_at = Utc::now();
+    edit.draft.files = files;
 
-    write_draft(storage_path, &draft).await?;
+    edit.commit().await
+}
 
-    Ok(draft)
+/// Set the draft's optional file groups (replaces existing groups)
+pub async fn set_draft_optional_groups(
+    storage_path: &Path,
+    id: Uuid,
+    groups: Vec<OptionalGroup>,
+) -> Result<DraftRelease> {
+    let mut edit = edit_draft(storage_path, id, None).await?;
+
+    edit.draft.optional_groups = groups;
+
+    edit.commit().await
+}
+
+/// Set the draft's exceptions to the global blacklist (replaces existing ones)
+pub async fn set_draft_ignore_overrides(
+    storage_path: &Path,
+    id: Uuid,
+    overrides: IgnorePatternOverrides,
+) -> Result<DraftRelease> {
+    let mut edit = edit_draft(storage_path, id, None).await?;
+
+    edit.draft.ignore_overrides = overrides;
+
+    edit.commit().await
+}
+
+/// Set the mod loader the draft targets
+pub async fn set_draft_loader(storage_path: &Path, id: Uuid, loader: ModLoader) -> Result<DraftRelease> {
+    let mut edit = edit_draft(storage_path, id, None).await?;
+
+    edit.draft.loader = loader;
+
+    edit.commit().await
 }
 
 /// Remove a file from draft
@@ -218,6 +314,7 @@ pub async fn remove_file_from_draft(
     id: Uuid,
     file_path: &str,
     recursive: bool,
+    expected_revision: Option<u64>,
 ) -> Result<DraftRelease> {
     // Validation
     if file_path.trim().is_empty() || file_path == "/" || file_path == "." {
@@ -227,7 +324,7 @@ pub async fn remove_file_from_draft(
         return Err(anyhow::anyhow!("Invalid file path: directory traversal not allowed"));
     }
 
-    let mut draft = read_draft(storage_path, id).await?;
+    let mut edit = edit_draft(storage_path, id, expected_revision).await?;
 
     let draft_dir = storage_path.join("drafts").join(id.to_string());
     let file_full_path = draft_dir.join("files").join(file_path);
@@ -248,21 +345,17 @@ pub async fn remove_file_from_draft(
 
             // Remove all files in this directory from draft.files
             let prefix = format!("{}/", file_path.trim_end_matches('/'));
-            draft.files.retain(|f| !f.path.starts_with(&prefix) && f.path != file_path);
+            edit.draft.files.retain(|f| !f.path.starts_with(&prefix) && f.path != file_path);
         } else {
             fs::remove_file(&file_full_path).await?;
-            draft.files.retain(|f| f.path != file_path);
+            edit.draft.files.retain(|f| f.path != file_path);
         }
     } else {
         // File doesn't exist on disk, but might be in manifest
-        draft.files.retain(|f| f.path != file_path);
+        edit.draft.files.retain(|f| f.path != file_path);
     }
 
-    draft.updated_at = Utc::now();
-
-    write_draft(storage_path, &draft).await?;
-
-    Ok(draft)
+    edit.commit().await
 }
 
 /// Update file metadata in draft
@@ -272,10 +365,11 @@ pub async fn update_file_in_draft(
     file_path: &str,
     sha256: Option<String>,
     url: Option<String>,
+    expected_revision: Option<u64>,
 ) -> Result<DraftRelease> {
-    let mut draft = read_draft(storage_path, id).await?;
+    let mut edit = edit_draft(storage_path, id, expected_revision).await?;
 
-    if let Some(file) = draft.files.iter_mut().find(|f| f.path == file_path) {
+    if let Some(file) = edit.draft.files.iter_mut().find(|f| f.path == file_path) {
         if let Some(hash) = sha256 {
             file.sha256 = hash;
         }
@@ -284,14 +378,60 @@ pub async fn update_file_in_draft(
         }
     }
 
-    draft.updated_at = Utc::now();
-
-    write_draft(storage_path, &draft).await?;
-
-    Ok(draft)
+    edit.commit().await
 }
 
 /// Get draft files directory
 pub fn get_draft_files_dir(storage_path: &Path, id: Uuid) -> PathBuf {
     storage_path.join("drafts").join(id.to_string()).join("files")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str) -> DraftFile {
+        DraftFile {
+            path: path.to_string(),
+            url: None,
+            sha256: path.to_string(),
+            size: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_edits_keep_every_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let draft = create_draft(dir.path(), None).await.unwrap();
+
+        let edits = (0..8).map(|i| {
+            let storage_path = dir.path().to_path_buf();
+            tokio::spawn(async move {
+                add_files_to_draft(&storage_path, draft.id, vec![file(&format!("mods/{}.jar", i))], None).await
+            })
+        });
+        for edit in futures_util::future::join_all(edits).await {
+            edit.unwrap().unwrap();
+        }
+
+        let draft = read_draft(dir.path(), draft.id).await.unwrap();
+        assert_eq!(draft.files.len(), 8);
+        assert_eq!(draft.revision, 8);
+    }
+
+    #[tokio::test]
+    async fn test_stale_revision_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let draft = create_draft(dir.path(), None).await.unwrap();
+
+        add_files_to_draft(dir.path(), draft.id, vec![file("mods/a.jar")], Some(0)).await.unwrap();
+        let err = add_files_to_draft(dir.path(), draft.id, vec![file("mods/b.jar")], Some(0))
+            .await
+            .unwrap_err();
+        let conflict = err.downcast::<DraftConflict>().unwrap();
+        assert_eq!((conflict.expected, conflict.current), (0, 1));
+
+        let draft = read_draft(dir.path(), draft.id).await.unwrap();
+        assert_eq!(draft.files.len(), 1);
+    }
+}
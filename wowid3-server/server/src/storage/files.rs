@@ -1,9 +1,13 @@
 use anyhow::{Context, Result};
 use sha2::{Digest, Sha256};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use tokio::fs;
 use tokio::io::AsyncReadExt;
 
+use crate::config::Config;
+use crate::models::Manifest;
+
 /// Calculate SHA256 checksum of a file
 pub async fn calculate_checksum(file_path: &Path) -> Result<String> {
     let mut file = fs::File::open(file_path)
@@ -43,10 +47,22 @@ pub async fn copy_file(from: &Path, to: &Path) -> Result<()> {
         fs::create_dir_all(parent).await?;
     }
 
-    fs::copy(from, to)
-        .await
-        .context("Failed to copy file")?;
+    copy_detached(from, to).await
+}
 
+/// Copy `from` to a fresh file at `to`, replacing whatever was there
+///
+/// `fs::copy` writes into an existing destination in place; if that destination is
+/// a release file linked into the object store, every release sharing it would change.
+pub async fn copy_detached(from: &Path, to: &Path) -> Result<()> {
+    let temp = to.with_extension("copy-tmp");
+    let _ = fs::remove_file(&temp).await;
+    fs::copy(from, &temp)
+        .await
+        .with_context(|| format!("Failed to copy {} to {}", from.display(), to.display()))?;
+    fs::rename(&temp, to)
+        .await
+        .with_context(|| format!("Failed to replace {}", to.display()))?;
     Ok(())
 }
 
@@ -62,7 +78,6 @@ pub async fn delete_directory(path: &Path) -> Result<()> {
 }
 
 /// Walk a directory recursively and return all file paths relative to base
-#[allow(dead_code)]
 pub async fn walk_directory(base_path: &Path) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
 
@@ -107,9 +122,187 @@ pub fn matches_blacklist(path: &Path, blacklist_patterns: &[String]) -> bool {
     false
 }
 
+// --- Content-addressed object store ---
+//
+// Release files live once in `objects/<aa>/<sha256>`; each release directory holds
+// hardlinks (or symlinks, across filesystems) to those objects, so unchanged jars
+// cost no extra disk space from one release to the next.
+
+/// Space reclaimed by deduplicating a release
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DedupStats {
+    /// Files now backed by an existing object
+    pub deduplicated: usize,
+    /// Files that became new objects
+    pub stored: usize,
+    pub bytes_saved: u64,
+}
+
+/// Path of the object for a checksum, sharded by its first two hex characters
+pub fn object_path(objects_root: &Path, sha256: &str) -> PathBuf {
+    let shard = sha256.get(..2).unwrap_or("00");
+    objects_root.join(shard).join(sha256)
+}
+
+/// Atomically replace `dest` with a link to `object`
+///
+/// Tries a hardlink first, then a symlink, then falls back to a plain copy.
+pub async fn materialize_object(object: &Path, dest: &Path) -> Result<()> {
+    let temp = dest.with_extension("dedup-tmp");
+    let _ = fs::remove_file(&temp).await;
+
+    if fs::hard_link(object, &temp).await.is_err() {
+        #[cfg(unix)]
+        let linked = fs::symlink(object, &temp).await.is_ok();
+        #[cfg(not(unix))]
+        let linked = false;
+
+        if !linked {
+            fs::copy(object, &temp)
+                .await
+                .with_context(|| format!("Failed to materialize {}", dest.display()))?;
+        }
+    }
+
+    fs::rename(&temp, dest)
+        .await
+        .with_context(|| format!("Failed to replace {}", dest.display()))?;
+    Ok(())
+}
+
+/// Move every file of a release into the object store and link it back
+///
+/// Safe to re-run: files already linked to their object are left alone. Each file is
+/// hashed before it's linked, so a manifest checksum that doesn't match the bytes on
+/// disk can't make a release serve another file's contents.
+pub async fn deduplicate_release(config: &Config, manifest: &Manifest) -> Result<DedupStats> {
+    let objects_root = config.objects_path();
+    let release_dir = config.release_path(&manifest.version);
+    let mut stats = DedupStats::default();
+
+    for file in &manifest.files {
+        let path = release_dir.join(&file.path);
+        let meta = match fs::symlink_metadata(&path).await {
+            Ok(meta) if meta.is_file() => meta,
+            // Missing files and existing symlinks need no work
+            _ => continue,
+        };
+
+        let object = object_path(&objects_root, &file.sha256);
+        if object.exists() && same_file(&path, &object).await {
+            continue;
+        }
+
+        let actual = calculate_checksum(&path).await?;
+        if actual != file.sha256 {
+            tracing::warn!(
+                "Not deduplicating {} in release {}: manifest says {}, file hashes to {}",
+                file.path,
+                manifest.version,
+                file.sha256,
+                actual
+            );
+            continue;
+        }
+
+        if let Some(parent) = object.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        if object.exists() {
+            materialize_object(&object, &path).await?;
+            stats.deduplicated += 1;
+            stats.bytes_saved += meta.len();
+        } else {
+            // Adopt the release file as the object, copying only across filesystems
+            if fs::hard_link(&path, &object).await.is_err() {
+                fs::copy(&path, &object)
+                    .await
+                    .with_context(|| format!("Failed to store object for {}", file.path))?;
+                materialize_object(&object, &path).await?;
+            }
+            stats.stored += 1;
+        }
+    }
+
+    tracing::info!(
+        "Deduplicated release {}: {} shared, {} new objects, {} bytes saved",
+        manifest.version,
+        stats.deduplicated,
+        stats.stored,
+        stats.bytes_saved
+    );
+
+    Ok(stats)
+}
+
+/// Checksums of every file in every release manifest
+pub async fn referenced_objects(config: &Config) -> Result<HashSet<String>> {
+    let mut referenced = HashSet::new();
+    for version in super::manifest::list_versions(config).await? {
+        let manifest = super::manifest::read_manifest(config, &version)
+            .await
+            .with_context(|| format!("Failed to read manifest {} during garbage collection", version))?;
+        referenced.extend(manifest.files.into_iter().map(|f| f.sha256));
+    }
+    Ok(referenced)
+}
+
+/// Delete objects no longer referenced by any release manifest
+///
+/// Returns the number of objects removed and the bytes freed.
+pub async fn collect_garbage(config: &Config) -> Result<(usize, u64)> {
+    let objects_root = config.objects_path();
+    if !objects_root.exists() {
+        return Ok((0, 0));
+    }
+
+    let referenced = referenced_objects(config).await?;
+
+    let mut removed = 0;
+    let mut freed = 0;
+    for object in walk_directory(&objects_root).await? {
+        let Some(sha256) = object.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if referenced.contains(sha256) {
+            continue;
+        }
+
+        let path = objects_root.join(&object);
+        let size = fs::metadata(&path).await.map(|m| m.len()).unwrap_or(0);
+        fs::remove_file(&path)
+            .await
+            .with_context(|| format!("Failed to remove object {}", sha256))?;
+        removed += 1;
+        freed += size;
+    }
+
+    if removed > 0 {
+        tracing::info!("Removed {} unreferenced object(s), freed {} bytes", removed, freed);
+    }
+
+    Ok((removed, freed))
+}
+
+#[cfg(unix)]
+async fn same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::metadata(a).await, fs::metadata(b).await) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+async fn same_file(_a: &Path, _b: &Path) -> bool {
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::ModLoader;
     use std::path::PathBuf;
 
     #[test]
@@ -125,4 +318,123 @@ mod tests {
         assert!(matches_blacklist(&PathBuf::from("journeymap/config.json"), &patterns));
         assert!(!matches_blacklist(&PathBuf::from("config/mod.toml"), &patterns));
     }
+
+    fn manifest_for(version: &str, files: &[(&str, &str)]) -> Manifest {
+        Manifest {
+            version: version.to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
+            fabric_loader: "0.15.0".to_string(),
+            files: files
+                .iter()
+                .map(|(path, sha256)| crate::models::ManifestFile {
+                    path: path.to_string(),
+                    url: format!("http://localhost/files/{}/{}", version, path),
+                    sha256: sha256.to_string(),
+                    size: 0,
+                    mirrors: Vec::new(),
+                })
+                .collect(),
+            changelog: String::new(),
+            ignore_patterns: Vec::new(),
+            channel: crate::models::ReleaseChannel::Stable,
+            optional_groups: Vec::new(),
+            ignore_overrides: Default::default(),
+        }
+    }
+
+    fn sha256_of(content: &str) -> String {
+        format!("{:x}", Sha256::digest(content.as_bytes()))
+    }
+
+    async fn write_release(config: &Config, manifest: &Manifest, contents: &[&str]) {
+        for (file, content) in manifest.files.iter().zip(contents) {
+            let path = config.release_path(&manifest.version).join(&file.path);
+            fs::create_dir_all(path.parent().unwrap()).await.unwrap();
+            fs::write(&path, content).await.unwrap();
+        }
+        super::super::manifest::write_manifest(config, manifest).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_deduplicate_and_collect_garbage() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "storage_path": temp_dir.path()
+        }))
+        .unwrap();
+
+        let shared = sha256_of("shared");
+        let only_v2 = sha256_of("new");
+
+        let v1 = manifest_for("1.0.0", &[("mods/shared.jar", &shared)]);
+        write_release(&config, &v1, &["shared"]).await;
+        let stats = deduplicate_release(&config, &v1).await.unwrap();
+        assert_eq!(stats.stored, 1);
+
+        let v2 = manifest_for("1.0.1", &[("mods/shared.jar", &shared), ("mods/new.jar", &only_v2)]);
+        write_release(&config, &v2, &["shared", "new"]).await;
+        let stats = deduplicate_release(&config, &v2).await.unwrap();
+        assert_eq!(stats.deduplicated, 1);
+        assert_eq!(stats.stored, 1);
+        assert_eq!(stats.bytes_saved, "shared".len() as u64);
+
+        // Re-running is a no-op
+        let stats = deduplicate_release(&config, &v2).await.unwrap();
+        assert_eq!(stats.deduplicated + stats.stored, 0);
+
+        let served = fs::read_to_string(config.release_path("1.0.1").join("mods/shared.jar"))
+            .await
+            .unwrap();
+        assert_eq!(served, "shared");
+
+        // Nothing is unreferenced while both releases exist
+        assert_eq!(collect_garbage(&config).await.unwrap().0, 0);
+
+        fs::remove_dir_all(config.release_path("1.0.1")).await.unwrap();
+        let (removed, _) = collect_garbage(&config).await.unwrap();
+        assert_eq!(removed, 1);
+        assert!(object_path(&config.objects_path(), &shared).exists());
+        assert!(!object_path(&config.objects_path(), &only_v2).exists());
+    }
+
+    #[tokio::test]
+    async fn test_deduplicate_skips_checksum_mismatch() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "storage_path": temp_dir.path()
+        }))
+        .unwrap();
+
+        let v1 = manifest_for("1.0.0", &[("mods/real.jar", &sha256_of("real"))]);
+        write_release(&config, &v1, &["real"]).await;
+        deduplicate_release(&config, &v1).await.unwrap();
+
+        // A manifest claiming the same checksum for different bytes must not be linked to it
+        let v2 = manifest_for("1.0.1", &[("mods/fake.jar", &sha256_of("real"))]);
+        write_release(&config, &v2, &["fake"]).await;
+        let stats = deduplicate_release(&config, &v2).await.unwrap();
+        assert_eq!(stats.deduplicated + stats.stored, 0);
+
+        let path = config.release_path("1.0.1").join("mods/fake.jar");
+        assert_eq!(fs::read_to_string(&path).await.unwrap(), "fake");
+        let object = object_path(&config.objects_path(), &sha256_of("real"));
+        assert_eq!(fs::read_to_string(&object).await.unwrap(), "real");
+    }
+
+    #[tokio::test]
+    async fn test_copy_detached_leaves_links_alone() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let object = temp_dir.path().join("object");
+        let linked = temp_dir.path().join("linked.jar");
+        let source = temp_dir.path().join("source.jar");
+        fs::write(&object, "shared").await.unwrap();
+        fs::hard_link(&object, &linked).await.unwrap();
+        fs::write(&source, "replacement").await.unwrap();
+
+        copy_detached(&source, &linked).await.unwrap();
+
+        assert_eq!(fs::read_to_string(&linked).await.unwrap(), "replacement");
+        assert_eq!(fs::read_to_string(&object).await.unwrap(), "shared");
+    }
 }
@@ -1,8 +1,8 @@
 use crate::config::Config;
-use crate::models::Manifest;
+use crate::models::{Manifest, ReleaseChannel};
 use anyhow::{Context, Result};
 use tokio::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Read manifest for a specific version
 pub async fn read_manifest(config: &Config, version: &str) -> Result<Manifest> {
@@ -43,6 +43,50 @@ pub async fn read_latest_manifest(config: &Config) -> Result<Manifest> {
     Ok(manifest)
 }
 
+/// Read the newest manifest a channel's players should get
+///
+/// Beta players get the newer of the latest beta and the latest stable release,
+/// so they are never held back behind stable.
+pub async fn read_latest_channel_manifest(config: &Config, channel: ReleaseChannel) -> Result<Manifest> {
+    if channel == ReleaseChannel::Stable {
+        return read_latest_manifest(config).await;
+    }
+
+    let stable = read_latest_manifest(config).await.ok();
+    let channel_manifest = read_pointer(&config.latest_channel_manifest_path(channel)).await?;
+
+    match (stable, channel_manifest) {
+        (Some(stable), Some(beta)) if is_newer(&beta.version, &stable.version) => Ok(beta),
+        (Some(stable), _) => Ok(stable),
+        (None, Some(beta)) => Ok(beta),
+        (None, None) => anyhow::bail!("No latest manifest found. Create a release first."),
+    }
+}
+
+/// Read a latest-manifest copy, if it exists
+async fn read_pointer(path: &PathBuf) -> Result<Option<Manifest>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let manifest: Manifest = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    validate_manifest(&manifest)?;
+
+    Ok(Some(manifest))
+}
+
+/// Compare release versions, semver-aware with a string fallback
+fn is_newer(candidate: &str, current: &str) -> bool {
+    match (semver::Version::parse(candidate), semver::Version::parse(current)) {
+        (Ok(a), Ok(b)) => a > b,
+        _ => candidate > current,
+    }
+}
+
 /// Validate manifest integrity
 fn validate_manifest(manifest: &Manifest) -> Result<()> {
     // Check that manifest has a reasonable number of files
@@ -80,17 +124,20 @@ fn validate_manifest(manifest: &Manifest) -> Result<()> {
 
 /// Write manifest to disk using atomic write (temp file + rename)
 pub async fn write_manifest(config: &Config, manifest: &Manifest) -> Result<()> {
-    let manifest_path = config.manifest_path(&manifest.version);
+    write_manifest_to(&config.release_path(&manifest.version), manifest).await
+}
+
+/// Write manifest into a release directory, e.g. one still being staged
+pub async fn write_manifest_to(release_dir: &Path, manifest: &Manifest) -> Result<()> {
+    let manifest_path = release_dir.join("manifest.json");
 
     // Validate manifest before writing
     validate_manifest(manifest)?;
 
     // Create release directory if it doesn't exist
-    if let Some(parent) = manifest_path.parent() {
-        fs::create_dir_all(parent)
-            .await
-            .context("Failed to create release directory")?;
-    }
+    fs::create_dir_all(release_dir)
+        .await
+        .context("Failed to create release directory")?;
 
     // Serialize to pretty JSON
     let json = serde_json::to_string_pretty(manifest)
@@ -134,14 +181,16 @@ async fn write_atomic(path: &PathBuf, content: String) -> Result<()> {
     Ok(())
 }
 
-/// Update the latest.json symlink/file to point to a specific version
+/// Update the latest manifest of the release's channel to point to a specific version
+///
+/// Stable releases update `latest.json`; beta releases only update the beta channel.
 pub async fn set_latest_manifest(config: &Config, version: &str) -> Result<()> {
     let manifest = read_manifest(config, version).await?;
 
     // Validate manifest before setting as latest
     validate_manifest(&manifest)?;
 
-    let latest_path = config.latest_manifest_path();
+    let latest_path = config.latest_channel_manifest_path(manifest.channel);
 
     // Serialize to pretty JSON
     let json = serde_json::to_string_pretty(&manifest)
@@ -150,10 +199,33 @@ pub async fn set_latest_manifest(config: &Config, version: &str) -> Result<()> {
     // Atomic write to prevent partial writes
     write_atomic(&latest_path, json).await?;
 
-    tracing::info!("Set latest manifest to version {}", version);
+    tracing::info!("Set latest {} manifest to version {}", manifest.channel.as_str(), version);
     Ok(())
 }
 
+/// Move a beta release to the stable channel
+///
+/// The release becomes the latest stable release unless a newer stable one exists.
+pub async fn promote_release(config: &Config, version: &str) -> Result<Manifest> {
+    let mut manifest = read_manifest(config, version).await?;
+    if manifest.channel == ReleaseChannel::Stable {
+        anyhow::bail!("Release {} is already stable", version);
+    }
+
+    manifest.channel = ReleaseChannel::Stable;
+    write_manifest(config, &manifest).await?;
+
+    let newer_stable_exists = read_latest_manifest(config)
+        .await
+        .map(|latest| is_newer(&latest.version, version))
+        .unwrap_or(false);
+    if !newer_stable_exists {
+        set_latest_manifest(config, version).await?;
+    }
+
+    Ok(manifest)
+}
+
 /// List all available versions
 pub async fn list_versions(config: &Config) -> Result<Vec<String>> {
     let releases_path = config.releases_path();
@@ -178,3 +250,67 @@ pub async fn list_versions(config: &Config) -> Result<Vec<String>> {
     versions.sort();
     Ok(versions)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ManifestFile, ModLoader};
+
+    fn manifest(version: &str, channel: ReleaseChannel) -> Manifest {
+        Manifest {
+            version: version.to_string(),
+            minecraft_version: "1.20.1".to_string(),
+            loader: ModLoader::Fabric,
+            fabric_loader: "0.15.0".to_string(),
+            files: vec![ManifestFile {
+                path: "mods/a.jar".to_string(),
+                url: format!("http://localhost/files/{}/mods/a.jar", version),
+                sha256: "a".repeat(64),
+                size: 1,
+                mirrors: Vec::new(),
+            }],
+            changelog: String::new(),
+            ignore_patterns: Vec::new(),
+            channel,
+            optional_groups: Vec::new(),
+            ignore_overrides: Default::default(),
+        }
+    }
+
+    async fn publish(config: &Config, version: &str, channel: ReleaseChannel) {
+        write_manifest(config, &manifest(version, channel)).await.unwrap();
+        set_latest_manifest(config, version).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_channels_and_promotion() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "storage_path": temp_dir.path()
+        }))
+        .unwrap();
+
+        publish(&config, "1.0.0", ReleaseChannel::Stable).await;
+        publish(&config, "1.1.0", ReleaseChannel::Beta).await;
+
+        // Beta releases don't reach stable players
+        assert_eq!(read_latest_manifest(&config).await.unwrap().version, "1.0.0");
+        assert_eq!(
+            read_latest_channel_manifest(&config, ReleaseChannel::Beta).await.unwrap().version,
+            "1.1.0"
+        );
+
+        promote_release(&config, "1.1.0").await.unwrap();
+        let stable = read_latest_manifest(&config).await.unwrap();
+        assert_eq!(stable.version, "1.1.0");
+        assert_eq!(stable.channel, ReleaseChannel::Stable);
+        assert!(promote_release(&config, "1.1.0").await.is_err());
+
+        // A newer stable release supersedes an older beta for beta players
+        publish(&config, "1.2.0", ReleaseChannel::Stable).await;
+        assert_eq!(
+            read_latest_channel_manifest(&config, ReleaseChannel::Beta).await.unwrap().version,
+            "1.2.0"
+        );
+    }
+}
@@ -1,11 +1,19 @@
+pub mod backend;
+pub mod blacklist;
+pub mod compatibility;
+pub mod cms;
+pub mod playlist;
+pub mod publish;
 pub mod drafts;
 pub mod files;
 pub mod manifest;
 pub mod launcher;
+pub mod tags;
+pub mod uploads;
 
 use anyhow::Result;
-use crate::models::DraftRelease;
-use std::path::PathBuf;
+use crate::models::{DraftRelease, ReleaseChannel};
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 // --- Drafts Wrappers ---
@@ -23,8 +31,9 @@ pub async fn update_draft(
     minecraft_version: Option<String>,
     fabric_loader: Option<String>,
     changelog: Option<String>,
+    channel: Option<ReleaseChannel>,
 ) -> Result<DraftRelease> {
-    drafts::update_draft(storage_path, id, version, minecraft_version, fabric_loader, changelog).await
+    drafts::update_draft(storage_path, id, version, minecraft_version, fabric_loader, changelog, channel).await
 }
 
 /// Read a draft release by ID
@@ -56,13 +65,41 @@ pub async fn set_draft_files(
     drafts::set_draft_files(storage_path, id, files).await
 }
 
+/// Set draft optional groups (replaces existing groups)
+pub async fn set_draft_optional_groups(
+    storage_path: &Path,
+    id: Uuid,
+    groups: Vec<crate::models::OptionalGroup>,
+) -> Result<DraftRelease> {
+    drafts::set_draft_optional_groups(storage_path, id, groups).await
+}
+
+/// Set draft exceptions to the global blacklist (replaces existing ones)
+pub async fn set_draft_ignore_overrides(
+    storage_path: &Path,
+    id: Uuid,
+    overrides: crate::models::IgnorePatternOverrides,
+) -> Result<DraftRelease> {
+    drafts::set_draft_ignore_overrides(storage_path, id, overrides).await
+}
+
+/// Set the mod loader a draft targets
+pub async fn set_draft_loader(
+    storage_path: &Path,
+    id: Uuid,
+    loader: crate::models::ModLoader,
+) -> Result<DraftRelease> {
+    drafts::set_draft_loader(storage_path, id, loader).await
+}
+
 /// Add files to draft (appends/updates)
 pub async fn add_files_to_draft(
     storage_path: &PathBuf,
     id: Uuid,
     files: Vec<crate::models::DraftFile>,
+    expected_revision: Option<u64>,
 ) -> Result<DraftRelease> {
-    drafts::add_files_to_draft(storage_path, id, files).await
+    drafts::add_files_to_draft(storage_path, id, files, expected_revision).await
 }
 
 /// Remove file from draft
@@ -71,8 +108,9 @@ pub async fn remove_file_from_draft(
     id: Uuid,
     file_path: &str,
     recursive: bool,
+    expected_revision: Option<u64>,
 ) -> Result<DraftRelease> {
-    drafts::remove_file_from_draft(storage_path, id, file_path, recursive).await
+    drafts::remove_file_from_draft(storage_path, id, file_path, recursive, expected_revision).await
 }
 
 /// Update file metadata in draft
@@ -82,8 +120,9 @@ pub async fn update_file_in_draft(
     file_path: &str,
     sha256: Option<String>,
     url: Option<String>,
+    expected_revision: Option<u64>,
 ) -> Result<DraftRelease> {
-    drafts::update_file_in_draft(storage_path, id, file_path, sha256, url).await
+    drafts::update_file_in_draft(storage_path, id, file_path, sha256, url, expected_revision).await
 }
 
 // --- Manifest Wrappers ---
@@ -0,0 +1,161 @@
+use crate::config::Config;
+use crate::storage::cms;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+/// Asset types the launcher can play
+const AUDIO_EXTENSIONS: &[&str] = &["mp3", "ogg", "wav"];
+const MAX_TITLE_LEN: usize = 100;
+
+/// A track in the launcher's menu music playlist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlaylistTrack {
+    /// CMS asset the track is served from
+    pub filename: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Filled in by the server when the playlist is saved
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub sha256: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+/// Ordered menu music, served as /api/assets/playlist.json
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudioPlaylist {
+    #[serde(default)]
+    pub tracks: Vec<PlaylistTrack>,
+}
+
+fn is_audio(filename: &str) -> bool {
+    filename
+        .rsplit_once('.')
+        .map(|(_, ext)| AUDIO_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Hash and size of a stored asset
+async fn hash_asset(config: &Config, filename: &str) -> Result<(String, u64)> {
+    let data = fs::read(config.cms_assets_path().join(filename))
+        .await
+        .with_context(|| format!("Asset {} not found", filename))?;
+    Ok((format!("{:x}", Sha256::digest(&data)), data.len() as u64))
+}
+
+/// Validate the requested track order and fill in each track's URL, hash and size
+pub async fn build_playlist(config: &Config, tracks: Vec<PlaylistTrack>) -> Result<AudioPlaylist> {
+    let mut built: Vec<PlaylistTrack> = Vec::with_capacity(tracks.len());
+
+    for track in tracks {
+        cms::validate_asset_filename(&track.filename)?;
+        if !is_audio(&track.filename) {
+            anyhow::bail!("{} is not an audio file ({})", track.filename, AUDIO_EXTENSIONS.join(", "));
+        }
+        if built.iter().any(|t| t.filename == track.filename) {
+            anyhow::bail!("{} is listed more than once", track.filename);
+        }
+
+        let title = track.title.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+        if title.as_ref().is_some_and(|t| t.chars().count() > MAX_TITLE_LEN) {
+            anyhow::bail!("Title for {} exceeds {} characters", track.filename, MAX_TITLE_LEN);
+        }
+
+        let (sha256, size) = hash_asset(config, &track.filename).await?;
+        built.push(PlaylistTrack {
+            url: format!("/api/assets-cms/{}", track.filename),
+            filename: track.filename,
+            title,
+            sha256,
+            size,
+        });
+    }
+
+    Ok(AudioPlaylist { tracks: built })
+}
+
+/// Load the playlist (empty if none was saved yet)
+pub async fn load_playlist(config: &Config) -> Result<AudioPlaylist> {
+    let path = config.audio_playlist_path();
+
+    if !path.exists() {
+        return Ok(AudioPlaylist::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .await
+        .context("Failed to read audio playlist")?;
+
+    serde_json::from_str(&content).context("Failed to parse audio playlist")
+}
+
+/// Save the playlist
+pub async fn save_playlist(config: &Config, playlist: &AudioPlaylist) -> Result<()> {
+    let path = config.audio_playlist_path();
+
+    let parent = path.parent().context("Invalid path")?;
+    fs::create_dir_all(parent)
+        .await
+        .context("Failed to create storage directory")?;
+
+    let json = serde_json::to_string_pretty(playlist).context("Failed to serialize audio playlist")?;
+
+    // Atomic write
+    let temp_path = parent.join(format!(".tmp.playlist.{}", uuid::Uuid::new_v4()));
+
+    let mut file = fs::File::create(&temp_path)
+        .await
+        .context("Failed to create temp file")?;
+
+    file.write_all(json.as_bytes())
+        .await
+        .context("Failed to write temp file")?;
+
+    file.sync_all().await.context("Failed to sync")?;
+    drop(file);
+
+    fs::rename(&temp_path, &path)
+        .await
+        .context("Failed to rename temp file")?;
+
+    Ok(())
+}
+
+/// Keep the playlist in step with a re-uploaded or deleted asset
+///
+/// Re-uploads get a fresh hash so launchers download the new file; deleted assets drop out.
+pub async fn sync_asset(config: &Config, filename: &str) -> Result<()> {
+    let mut playlist = load_playlist(config).await?;
+    let Some(index) = playlist.tracks.iter().position(|t| t.filename == filename) else {
+        return Ok(());
+    };
+
+    if config.cms_assets_path().join(filename).exists() {
+        let (sha256, size) = hash_asset(config, filename).await?;
+        let track = &mut playlist.tracks[index];
+        track.sha256 = sha256;
+        track.size = size;
+    } else {
+        playlist.tracks.remove(index);
+    }
+
+    save_playlist(config, &playlist).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_audio() {
+        assert!(is_audio("theme.mp3"));
+        assert!(is_audio("Theme.OGG"));
+        assert!(!is_audio("logo.png"));
+        assert!(!is_audio("mp3"));
+    }
+}
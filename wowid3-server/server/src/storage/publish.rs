@@ -0,0 +1,256 @@
+use crate::config::Config;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use uuid::Uuid;
+
+/// A draft publish that committed or may commit a release; removed once publishing finished
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishJournal {
+    pub draft_id: Uuid,
+    pub version: String,
+    pub started_at: DateTime<Utc>,
+}
+
+/// What the startup recovery pass cleaned up
+#[derive(Debug, Default)]
+pub struct RecoveryReport {
+    /// Release directories without a manifest, moved to `quarantine/`
+    pub quarantined: Vec<String>,
+    /// Half-built releases removed from `staging/`
+    pub staging_cleared: usize,
+    /// Leftover temp files from interrupted atomic writes
+    pub temp_files_removed: usize,
+    /// Releases committed by a publish that didn't finish; publishing the draft again finishes it
+    pub unfinished: Vec<PublishJournal>,
+}
+
+/// Fresh staging directory to assemble a release in
+pub async fn prepare_staging(config: &Config, version: &str) -> Result<PathBuf> {
+    let dir = config.staging_path().join(version);
+    if dir.exists() {
+        // Left over from an earlier attempt that didn't complete
+        fs::remove_dir_all(&dir)
+            .await
+            .context("Failed to clear old staging directory")?;
+    }
+    fs::create_dir_all(&dir)
+        .await
+        .context("Failed to create staging directory")?;
+    Ok(dir)
+}
+
+/// Move a staged release into `releases/`; it appears complete, manifest included, or not at all
+pub async fn commit_staging(config: &Config, version: &str) -> Result<()> {
+    let release_dir = config.release_path(version);
+    if release_dir.exists() {
+        anyhow::bail!("Release version {} already exists", version);
+    }
+    fs::create_dir_all(config.releases_path())
+        .await
+        .context("Failed to create releases directory")?;
+    fs::rename(config.staging_path().join(version), &release_dir)
+        .await
+        .context("Failed to move staged release into place")?;
+    Ok(())
+}
+
+fn journal_path(config: &Config, version: &str) -> PathBuf {
+    config.publishing_path().join(format!("{}.json", version))
+}
+
+/// Record that `draft_id` is being published as `version`
+pub async fn begin_publish(config: &Config, draft_id: Uuid, version: &str) -> Result<()> {
+    let journal = PublishJournal {
+        draft_id,
+        version: version.to_string(),
+        started_at: Utc::now(),
+    };
+    let path = journal_path(config, version);
+    fs::create_dir_all(config.publishing_path())
+        .await
+        .context("Failed to create publishing directory")?;
+    let temp_path = config.publishing_path().join(format!(".tmp.{}", Uuid::new_v4()));
+    fs::write(&temp_path, serde_json::to_vec_pretty(&journal)?)
+        .await
+        .context("Failed to write publish journal")?;
+    fs::rename(&temp_path, &path)
+        .await
+        .context("Failed to write publish journal")?;
+    Ok(())
+}
+
+/// Publishing finished; forget the journal
+pub async fn finish_publish(config: &Config, version: &str) -> Result<()> {
+    match fs::remove_file(journal_path(config, version)).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e).context("Failed to remove publish journal"),
+        _ => Ok(()),
+    }
+}
+
+async fn read_journal(path: &Path) -> Option<PublishJournal> {
+    let raw = fs::read(path).await.ok()?;
+    serde_json::from_slice(&raw).ok()
+}
+
+/// Whether `version` exists because an earlier publish of `draft_id` was interrupted
+pub async fn is_resumable(config: &Config, draft_id: Uuid, version: &str) -> bool {
+    config.manifest_path(version).exists()
+        && read_journal(&journal_path(config, version))
+            .await
+            .is_some_and(|journal| journal.draft_id == draft_id)
+}
+
+/// Clean up after a crash; run at startup before any job can publish
+///
+/// Clears `staging/`, moves release directories without a manifest to `quarantine/`,
+/// removes temp files left by interrupted atomic writes, and settles publish journals.
+pub async fn recover(config: &Config) -> Result<RecoveryReport> {
+    let mut report = RecoveryReport::default();
+
+    for dir in subdirs(&config.staging_path()).await? {
+        fs::remove_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to clear {}", dir.display()))?;
+        report.staging_cleared += 1;
+    }
+
+    let stamp = Utc::now().format("%Y%m%d%H%M%S");
+    for dir in subdirs(&config.releases_path()).await? {
+        report.temp_files_removed += remove_temp_files(&dir, false).await;
+        if dir.join("manifest.json").exists() {
+            continue;
+        }
+        let version = dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        fs::create_dir_all(config.quarantine_path())
+            .await
+            .context("Failed to create quarantine directory")?;
+        fs::rename(&dir, config.quarantine_path().join(format!("{}-{}", version, stamp)))
+            .await
+            .with_context(|| format!("Failed to quarantine {}", dir.display()))?;
+        report.quarantined.push(version);
+    }
+
+    for dir in subdirs(&config.storage_path().join("drafts")).await? {
+        report.temp_files_removed += remove_temp_files(&dir, true).await;
+    }
+    report.temp_files_removed += remove_temp_files(&config.publishing_path(), false).await;
+
+    if let Ok(mut entries) = fs::read_dir(config.publishing_path()).await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let Some(journal) = read_journal(&entry.path()).await else {
+                continue;
+            };
+            let committed = config.manifest_path(&journal.version).exists();
+            let draft_left = config
+                .storage_path()
+                .join("drafts")
+                .join(journal.draft_id.to_string())
+                .exists();
+            // The draft is deleted last, so a committed release without its draft finished publishing
+            if committed && draft_left {
+                report.unfinished.push(journal);
+            } else {
+                let _ = fs::remove_file(entry.path()).await;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+async fn subdirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    let Ok(mut entries) = fs::read_dir(dir).await else {
+        return Ok(dirs);
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.file_type().await?.is_dir() {
+            dirs.push(entry.path());
+        }
+    }
+    Ok(dirs)
+}
+
+/// Remove `.tmp.<uuid>` files the atomic writers leave behind when interrupted
+async fn remove_temp_files(dir: &Path, recursive: bool) -> usize {
+    let dir = dir.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let depth = if recursive { usize::MAX } else { 1 };
+        walkdir::WalkDir::new(&dir)
+            .max_depth(depth)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file() && is_temp_file(&e.file_name().to_string_lossy()))
+            .filter(|e| std::fs::remove_file(e.path()).is_ok())
+            .count()
+    })
+    .await
+    .unwrap_or(0)
+}
+
+fn is_temp_file(name: &str) -> bool {
+    name.strip_prefix(".tmp.")
+        .and_then(|rest| rest.rsplit('.').next())
+        .is_some_and(|id| Uuid::parse_str(id).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_recover_quarantines_incomplete_releases() {
+        let dir = tempfile::tempdir().unwrap();
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "storage_path": dir.path()
+        }))
+        .unwrap();
+
+        // A complete release, a half-copied one and a half-staged one
+        std::fs::create_dir_all(config.release_path("1.0.0")).unwrap();
+        std::fs::write(config.manifest_path("1.0.0"), b"{}").unwrap();
+        std::fs::write(config.release_path("1.0.0").join(format!(".tmp.{}", Uuid::new_v4())), b"").unwrap();
+        std::fs::create_dir_all(config.release_path("1.1.0").join("mods")).unwrap();
+        std::fs::create_dir_all(config.staging_path().join("1.2.0/mods")).unwrap();
+
+        // 1.0.0 was committed by a draft that still exists; 1.2.0 never committed
+        let draft_id = Uuid::new_v4();
+        std::fs::create_dir_all(config.storage_path().join("drafts").join(draft_id.to_string())).unwrap();
+        begin_publish(&config, draft_id, "1.0.0").await.unwrap();
+        begin_publish(&config, Uuid::new_v4(), "1.2.0").await.unwrap();
+
+        let report = recover(&config).await.unwrap();
+        assert_eq!(report.quarantined, vec!["1.1.0"]);
+        assert_eq!(report.staging_cleared, 1);
+        assert_eq!(report.temp_files_removed, 1);
+        assert_eq!(report.unfinished.len(), 1);
+        assert_eq!(report.unfinished[0].version, "1.0.0");
+
+        assert!(!config.release_path("1.1.0").exists());
+        assert_eq!(std::fs::read_dir(config.quarantine_path()).unwrap().count(), 1);
+        assert!(is_resumable(&config, draft_id, "1.0.0").await);
+        assert!(!is_resumable(&config, Uuid::new_v4(), "1.0.0").await);
+        assert!(!config.publishing_path().join("1.2.0.json").exists());
+    }
+
+    #[tokio::test]
+    async fn test_commit_staging_refuses_existing_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "storage_path": dir.path()
+        }))
+        .unwrap();
+
+        let staging = prepare_staging(&config, "1.0.0").await.unwrap();
+        std::fs::write(staging.join("manifest.json"), b"{}").unwrap();
+        commit_staging(&config, "1.0.0").await.unwrap();
+        assert!(config.manifest_path("1.0.0").exists());
+        assert!(!staging.exists());
+
+        prepare_staging(&config, "1.0.0").await.unwrap();
+        assert!(commit_staging(&config, "1.0.0").await.is_err());
+    }
+}
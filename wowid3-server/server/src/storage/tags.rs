@@ -0,0 +1,186 @@
+use crate::config::Config;
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// Maximum length of a single release tag
+const MAX_TAG_LENGTH: usize = 32;
+
+/// Release tags keyed by version (stored as a sidecar so manifests stay untouched)
+pub type ReleaseTags = BTreeMap<String, Vec<String>>;
+
+/// Held across every read-modify-write of the sidecar so concurrent edits don't drop each other's tags
+static TAGS_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Normalize a tag: trimmed, lowercase, limited to [a-z0-9._-]
+pub fn normalize_tag(tag: &str) -> Result<String> {
+    let tag = tag.trim().to_lowercase();
+
+    if tag.is_empty() {
+        anyhow::bail!("Tag cannot be empty");
+    }
+    if tag.len() > MAX_TAG_LENGTH {
+        anyhow::bail!("Tag '{}' exceeds {} characters", tag, MAX_TAG_LENGTH);
+    }
+    if !tag
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    {
+        anyhow::bail!("Tag '{}' contains invalid characters (allowed: a-z, 0-9, '-', '_', '.')", tag);
+    }
+
+    Ok(tag)
+}
+
+/// Load all release tags (empty map if the sidecar doesn't exist yet)
+pub async fn load_release_tags(config: &Config) -> Result<ReleaseTags> {
+    let tags_path = config.release_tags_path();
+
+    if !tags_path.exists() {
+        return Ok(ReleaseTags::new());
+    }
+
+    let content = fs::read_to_string(&tags_path)
+        .await
+        .context("Failed to read release tags")?;
+
+    let tags: ReleaseTags = serde_json::from_str(&content)
+        .context("Failed to parse release tags")?;
+
+    Ok(tags)
+}
+
+/// Save all release tags
+pub async fn save_release_tags(config: &Config, tags: &ReleaseTags) -> Result<()> {
+    let tags_path = config.release_tags_path();
+
+    let parent = tags_path.parent().context("Invalid path")?;
+    fs::create_dir_all(parent)
+        .await
+        .context("Failed to create storage directory")?;
+
+    let json = serde_json::to_string_pretty(tags)
+        .context("Failed to serialize release tags")?;
+
+    // Atomic write
+    let temp_path = parent.join(format!(".tmp.tags.{}", uuid::Uuid::new_v4()));
+
+    let mut file = fs::File::create(&temp_path)
+        .await
+        .context("Failed to create temp file")?;
+
+    file.write_all(json.as_bytes())
+        .await
+        .context("Failed to write temp file")?;
+
+    file.sync_all().await.context("Failed to sync")?;
+    drop(file);
+
+    fs::rename(&temp_path, &tags_path)
+        .await
+        .context("Failed to rename temp file")?;
+
+    Ok(())
+}
+
+/// Get tags for a single release
+pub async fn get_release_tags(config: &Config, version: &str) -> Result<Vec<String>> {
+    let tags = load_release_tags(config).await?;
+    Ok(tags.get(version).cloned().unwrap_or_default())
+}
+
+/// Add tags to a release, returning the resulting tag list
+pub async fn add_release_tags(config: &Config, version: &str, new_tags: &[String]) -> Result<Vec<String>> {
+    let normalized = new_tags
+        .iter()
+        .map(|t| normalize_tag(t))
+        .collect::<Result<Vec<_>>>()?;
+
+    let _guard = TAGS_LOCK.lock().await;
+    let mut tags = load_release_tags(config).await?;
+    let entry = tags.entry(version.to_string()).or_default();
+
+    for tag in normalized {
+        if !entry.contains(&tag) {
+            entry.push(tag);
+        }
+    }
+    entry.sort();
+    let result = entry.clone();
+
+    save_release_tags(config, &tags).await?;
+
+    Ok(result)
+}
+
+/// Remove a tag from a release, returning the resulting tag list
+pub async fn remove_release_tag(config: &Config, version: &str, tag: &str) -> Result<Vec<String>> {
+    let tag = normalize_tag(tag)?;
+
+    let _guard = TAGS_LOCK.lock().await;
+    let mut tags = load_release_tags(config).await?;
+    let result = match tags.get_mut(version) {
+        Some(entry) => {
+            entry.retain(|t| t != &tag);
+            let result = entry.clone();
+            if result.is_empty() {
+                tags.remove(version);
+            }
+            result
+        }
+        None => Vec::new(),
+    };
+
+    save_release_tags(config, &tags).await?;
+
+    Ok(result)
+}
+
+/// Drop all tags for a release (used when a release is deleted)
+pub async fn clear_release_tags(config: &Config, version: &str) -> Result<()> {
+    let _guard = TAGS_LOCK.lock().await;
+    let mut tags = load_release_tags(config).await?;
+
+    if tags.remove(version).is_some() {
+        save_release_tags(config, &tags).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_tag() {
+        assert_eq!(normalize_tag("  Stable ").unwrap(), "stable");
+        assert_eq!(normalize_tag("event-build").unwrap(), "event-build");
+        assert!(normalize_tag("").is_err());
+        assert!(normalize_tag("bad tag").is_err());
+        assert!(normalize_tag(&"x".repeat(MAX_TAG_LENGTH + 1)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_adds_keep_every_tag() {
+        let dir = tempfile::tempdir().unwrap();
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "storage_path": dir.path()
+        }))
+        .unwrap();
+
+        let adds = (0..8).map(|i| {
+            let config = config.clone();
+            tokio::spawn(async move { add_release_tags(&config, "1.0.0", &[format!("tag-{}", i)]).await })
+        });
+        for add in adds.collect::<Vec<_>>() {
+            add.await.unwrap().unwrap();
+        }
+
+        assert_eq!(get_release_tags(&config, "1.0.0").await.unwrap().len(), 8);
+        remove_release_tag(&config, "1.0.0", "tag-3").await.unwrap();
+        assert_eq!(get_release_tags(&config, "1.0.0").await.unwrap().len(), 7);
+    }
+}
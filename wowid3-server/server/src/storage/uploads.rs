@@ -0,0 +1,244 @@
+use crate::config::Config;
+use crate::models::UploadSession;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tracing::{info, warn};
+use walkdir::WalkDir;
+
+/// A directory under `uploads/`, holding the files of one upload
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadDirInfo {
+    pub upload_id: String,
+    pub files: usize,
+    pub bytes: u64,
+    /// Newest modification time of the directory or anything in it
+    pub modified_at: DateTime<Utc>,
+    /// A chunked upload session is still writing into this upload
+    pub in_session: bool,
+}
+
+/// A chunked upload that hasn't been finalized
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadSessionInfo {
+    pub session_id: uuid::Uuid,
+    pub upload_id: uuid::Uuid,
+    pub file_name: String,
+    pub size: u64,
+    /// Bytes received so far
+    pub received: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadsOverview {
+    /// Oldest first
+    pub uploads: Vec<UploadDirInfo>,
+    pub sessions: Vec<UploadSessionInfo>,
+    /// Bytes in `uploads/` and unfinished sessions
+    pub total_bytes: u64,
+    /// Uploads older than this are deleted automatically (0 keeps them)
+    pub retention_hours: u64,
+}
+
+/// Files, bytes and newest mtime under `dir`
+fn dir_usage(dir: &Path) -> (usize, u64, SystemTime) {
+    let mut files = 0;
+    let mut bytes = 0;
+    let mut newest = SystemTime::UNIX_EPOCH;
+    for entry in WalkDir::new(dir).follow_links(false).into_iter().filter_map(|e| e.ok()) {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if let Ok(modified) = metadata.modified() {
+            newest = newest.max(modified);
+        }
+        if metadata.is_file() {
+            files += 1;
+            bytes += metadata.len();
+        }
+    }
+    (files, bytes, newest)
+}
+
+/// Unfinished chunked upload sessions; unreadable ones are skipped
+pub async fn list_sessions(config: &Config) -> Vec<UploadSessionInfo> {
+    let mut sessions = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir(config.upload_sessions_path()).await else {
+        return sessions;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let Ok(raw) = tokio::fs::read(entry.path().join("session.json")).await else {
+            continue;
+        };
+        let Ok(session) = serde_json::from_slice::<UploadSession>(&raw) else {
+            continue;
+        };
+        let received = tokio::fs::metadata(entry.path().join("data"))
+            .await
+            .map_or(0, |m| m.len());
+        sessions.push(UploadSessionInfo {
+            session_id: session.session_id,
+            upload_id: session.upload_id,
+            file_name: session.file_name,
+            size: session.size,
+            received,
+            created_at: session.created_at,
+        });
+    }
+    sessions.sort_by_key(|s| s.created_at);
+    sessions
+}
+
+/// Every upload directory and unfinished session, with sizes
+pub async fn overview(config: &Config) -> Result<UploadsOverview> {
+    let sessions = list_sessions(config).await;
+    let in_session: HashSet<String> = sessions.iter().map(|s| s.upload_id.to_string()).collect();
+
+    let root = config.uploads_path();
+    let mut uploads = tokio::task::spawn_blocking(move || scan_upload_dirs(&root, &in_session))
+        .await
+        .context("Upload scan panicked")??;
+    uploads.sort_by_key(|u| u.modified_at);
+
+    let total_bytes = uploads.iter().map(|u| u.bytes).sum::<u64>() + sessions.iter().map(|s| s.received).sum::<u64>();
+    Ok(UploadsOverview {
+        uploads,
+        sessions,
+        total_bytes,
+        retention_hours: config.upload_retention_hours,
+    })
+}
+
+fn scan_upload_dirs(root: &Path, in_session: &HashSet<String>) -> Result<Vec<UploadDirInfo>> {
+    let mut uploads = Vec::new();
+    if !root.exists() {
+        return Ok(uploads);
+    }
+    for entry in std::fs::read_dir(root).context("Failed to read uploads directory")? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let upload_id = entry.file_name().to_string_lossy().to_string();
+        let (files, bytes, newest) = dir_usage(&entry.path());
+        uploads.push(UploadDirInfo {
+            in_session: in_session.contains(&upload_id),
+            upload_id,
+            files,
+            bytes,
+            modified_at: newest.into(),
+        });
+    }
+    Ok(uploads)
+}
+
+/// Delete upload directories untouched for longer than `max_age`
+///
+/// Uploads a live chunked session still writes into are kept. Drafts and releases
+/// copy their files out of `uploads/`, so nothing else depends on them. Returns the
+/// number of directories removed and the bytes freed.
+pub async fn collect_stale_uploads(config: &Config, max_age: Duration) -> Result<(usize, u64)> {
+    let overview = overview(config).await?;
+    let cutoff: DateTime<Utc> = (SystemTime::now() - max_age).into();
+
+    let mut removed = 0;
+    let mut freed = 0;
+    for upload in overview.uploads.iter().filter(|u| !u.in_session && u.modified_at < cutoff) {
+        let path = config.uploads_path().join(&upload.upload_id);
+        match tokio::fs::remove_dir_all(&path).await {
+            Ok(()) => {
+                removed += 1;
+                freed += upload.bytes;
+            }
+            Err(e) => warn!("Failed to remove stale upload {}: {}", upload.upload_id, e),
+        }
+    }
+    Ok((removed, freed))
+}
+
+/// Periodically deletes abandoned upload directories
+pub struct UploadJanitor {
+    config: Arc<Config>,
+    interval: Duration,
+}
+
+impl UploadJanitor {
+    pub fn new(config: Arc<Config>, interval: Duration) -> Self {
+        Self { config, interval }
+    }
+
+    pub fn spawn(self) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            loop {
+                interval.tick().await;
+                let max_age = Duration::from_secs(self.config.upload_retention_hours * 3600);
+                match collect_stale_uploads(&self.config, max_age).await {
+                    Ok((0, _)) => {}
+                    Ok((removed, freed)) => {
+                        info!("Removed {} stale uploads, freeing {} MB", removed, freed / 1024 / 1024)
+                    }
+                    Err(e) => warn!("Upload cleanup failed: {:#}", e),
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_collect_stale_uploads_spares_recent_and_live_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "storage_path": dir.path()
+        }))
+        .unwrap();
+
+        let uploads = config.uploads_path();
+        let old = std::time::SystemTime::now() - Duration::from_secs(10 * 24 * 3600);
+        for id in ["abandoned", "resuming"] {
+            std::fs::create_dir_all(uploads.join(id)).unwrap();
+            let file = uploads.join(id).join("mod.jar");
+            std::fs::write(&file, b"12345").unwrap();
+            std::fs::File::options().write(true).open(&file).unwrap().set_modified(old).unwrap();
+            std::fs::File::open(uploads.join(id)).unwrap().set_modified(old).unwrap();
+        }
+        std::fs::create_dir_all(uploads.join("fresh")).unwrap();
+        std::fs::write(uploads.join("fresh/mod.jar"), b"123").unwrap();
+
+        // A chunked session still writing into the old "resuming" upload keeps it alive
+        let session = UploadSession {
+            session_id: uuid::Uuid::new_v4(),
+            upload_id: uuid::Uuid::new_v4(),
+            file_name: "pack.zip".to_string(),
+            size: 10,
+            sha256: None,
+            created_at: Utc::now(),
+            offset: 0,
+        };
+        std::fs::rename(uploads.join("resuming"), uploads.join(session.upload_id.to_string())).unwrap();
+        let session_dir = config.upload_sessions_path().join(session.session_id.to_string());
+        std::fs::create_dir_all(&session_dir).unwrap();
+        std::fs::write(session_dir.join("session.json"), serde_json::to_vec(&session).unwrap()).unwrap();
+        std::fs::write(session_dir.join("data"), b"1234").unwrap();
+
+        let overview = overview(&config).await.unwrap();
+        assert_eq!(overview.uploads.len(), 3);
+        assert_eq!(overview.sessions[0].received, 4);
+        assert_eq!(overview.total_bytes, 5 + 5 + 3 + 4);
+
+        let (removed, freed) = collect_stale_uploads(&config, Duration::from_secs(3 * 24 * 3600)).await.unwrap();
+        assert_eq!((removed, freed), (1, 5));
+        assert!(!uploads.join("abandoned").exists());
+        assert!(uploads.join(session.upload_id.to_string()).exists());
+        assert!(uploads.join("fresh").exists());
+    }
+}
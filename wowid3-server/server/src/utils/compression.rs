@@ -0,0 +1,184 @@
+use anyhow::{Context, Result};
+use axum::{
+    body::{Body, Bytes},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::Response,
+};
+use std::io::{Read, Write};
+
+/// zstd level for precompressed bodies; compressed once, served many times
+const ZSTD_LEVEL: i32 = 19;
+
+/// Content encodings we can serve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Zstd,
+    Gzip,
+    Identity,
+}
+
+impl Encoding {
+    fn token(self) -> &'static str {
+        match self {
+            Encoding::Zstd => "zstd",
+            Encoding::Gzip => "gzip",
+            Encoding::Identity => "identity",
+        }
+    }
+}
+
+/// Quality the client assigned to an encoding token (0.0 when not acceptable)
+fn quality(accept_encoding: &str, token: &str) -> f32 {
+    let mut wildcard = None;
+    for entry in accept_encoding.split(',') {
+        let mut parts = entry.split(';');
+        let name = parts.next().unwrap_or("").trim();
+        let q = parts
+            .find_map(|p| p.trim().strip_prefix("q=").and_then(|q| q.trim().parse::<f32>().ok()))
+            .unwrap_or(1.0);
+        if name.eq_ignore_ascii_case(token) {
+            return q;
+        }
+        if name == "*" {
+            wildcard = Some(q);
+        }
+    }
+    wildcard.unwrap_or(0.0)
+}
+
+/// Pick the best encoding the client accepts from `available`, preferring zstd
+pub fn negotiate(headers: &HeaderMap, available: &[Encoding]) -> Encoding {
+    let Some(accept) = headers.get(header::ACCEPT_ENCODING).and_then(|v| v.to_str().ok()) else {
+        return Encoding::Identity;
+    };
+
+    let mut best = (Encoding::Identity, 0.0);
+    for encoding in [Encoding::Zstd, Encoding::Gzip] {
+        if !available.contains(&encoding) {
+            continue;
+        }
+        let q = quality(accept, encoding.token());
+        if q > best.1 {
+            best = (encoding, q);
+        }
+    }
+    best.0
+}
+
+pub fn compress_gzip(data: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder.write_all(data).context("Failed to gzip data")?;
+    encoder.finish().context("Failed to gzip data")
+}
+
+pub fn gunzip(data: &[u8]) -> Result<Vec<u8>> {
+    let mut decoded = Vec::new();
+    flate2::read::GzDecoder::new(data)
+        .read_to_end(&mut decoded)
+        .context("Failed to decompress gzip data")?;
+    Ok(decoded)
+}
+
+pub fn compress_zstd(data: &[u8]) -> Result<Vec<u8>> {
+    zstd::bulk::compress(data, ZSTD_LEVEL).context("Failed to zstd-compress data")
+}
+
+/// A response body compressed ahead of time in every encoding we serve
+#[derive(Debug, Clone)]
+pub struct PrecompressedBody {
+    pub identity: Bytes,
+    pub gzip: Bytes,
+    pub zstd: Bytes,
+    /// Extra headers sent with every encoding (e.g. a signature of the identity bytes)
+    pub headers: HeaderMap,
+}
+
+impl PrecompressedBody {
+    /// Compress `data` (CPU heavy for large bodies; call from a blocking task)
+    pub fn new(data: Vec<u8>) -> Result<Self> {
+        Ok(Self {
+            gzip: Bytes::from(compress_gzip(&data)?),
+            zstd: Bytes::from(compress_zstd(&data)?),
+            identity: Bytes::from(data),
+            headers: HeaderMap::new(),
+        })
+    }
+
+    pub fn with_header(mut self, name: &'static str, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Build a 200 response in the encoding the client prefers
+    pub fn response(&self, headers: &HeaderMap, content_type: &'static str) -> Response {
+        let encoding = negotiate(headers, &[Encoding::Zstd, Encoding::Gzip]);
+        let body = match encoding {
+            Encoding::Zstd => self.zstd.clone(),
+            Encoding::Gzip => self.gzip.clone(),
+            Encoding::Identity => self.identity.clone(),
+        };
+        let mut response = encoded_response(body, encoding, content_type);
+        response.headers_mut().extend(self.headers.clone());
+        response
+    }
+}
+
+/// 200 response with `Content-Encoding` (unless identity) and `Vary: Accept-Encoding`
+pub fn encoded_response(body: Bytes, encoding: Encoding, content_type: &str) -> Response {
+    let mut response = Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::VARY, HeaderValue::from_static("accept-encoding"))
+        .header(header::CONTENT_LENGTH, body.len());
+    if encoding != Encoding::Identity {
+        response = response.header(header::CONTENT_ENCODING, encoding.token());
+    }
+    response.body(Body::from(body)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accept(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_ENCODING, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let all = [Encoding::Zstd, Encoding::Gzip];
+        assert_eq!(negotiate(&HeaderMap::new(), &all), Encoding::Identity);
+        assert_eq!(negotiate(&accept("gzip, deflate, br, zstd"), &all), Encoding::Zstd);
+        assert_eq!(negotiate(&accept("gzip, deflate"), &all), Encoding::Gzip);
+        assert_eq!(negotiate(&accept("zstd;q=0.5, gzip"), &all), Encoding::Gzip);
+        assert_eq!(negotiate(&accept("gzip;q=0, identity"), &all), Encoding::Identity);
+        assert_eq!(negotiate(&accept("*"), &all), Encoding::Zstd);
+        assert_eq!(negotiate(&accept("zstd"), &[Encoding::Gzip]), Encoding::Identity);
+    }
+
+    #[test]
+    fn test_precompressed_round_trip() {
+        let data = br#"{"files":[]}"#.repeat(100);
+        let body = PrecompressedBody::new(data.clone()).unwrap();
+
+        assert!(body.gzip.len() < data.len());
+        assert_eq!(gunzip(&body.gzip).unwrap(), data);
+        assert_eq!(zstd::bulk::decompress(&body.zstd, data.len()).unwrap(), data);
+
+        let response = body.response(&accept("gzip"), "application/json");
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+        assert_eq!(response.headers()[header::VARY], "accept-encoding");
+
+        let response = body.response(&HeaderMap::new(), "application/json");
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+        assert_eq!(response.headers()[header::CONTENT_LENGTH], data.len().to_string().as_str());
+
+        let body = body.with_header("x-manifest-signature", HeaderValue::from_static("c2ln"));
+        for encoding in ["zstd", "identity"] {
+            let response = body.response(&accept(encoding), "application/json");
+            assert_eq!(response.headers()["x-manifest-signature"], "c2ln");
+        }
+    }
+}
@@ -0,0 +1,94 @@
+/// Result of evaluating a `Range` header against a file length
+#[derive(Debug, PartialEq, Eq)]
+pub enum ByteRange {
+    /// No usable range; serve the whole file
+    Full,
+    /// Serve bytes `start..=end`
+    Partial { start: u64, end: u64 },
+    /// Range can't be satisfied (respond 416)
+    Unsatisfiable,
+}
+
+/// Parse a single-range `Range: bytes=...` header
+///
+/// Multi-range and malformed headers fall back to `Full`, which RFC 9110 allows.
+pub fn parse_byte_range(header: &str, len: u64) -> ByteRange {
+    let Some(spec) = header.trim().strip_prefix("bytes=") else {
+        return ByteRange::Full;
+    };
+    if spec.contains(',') {
+        return ByteRange::Full;
+    }
+
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return ByteRange::Full;
+    };
+    let (start, end) = (start.trim(), end.trim());
+
+    if start.is_empty() {
+        // Suffix range: last N bytes
+        let Ok(suffix) = end.parse::<u64>() else {
+            return ByteRange::Full;
+        };
+        if suffix == 0 || len == 0 {
+            return ByteRange::Unsatisfiable;
+        }
+        return ByteRange::Partial {
+            start: len.saturating_sub(suffix),
+            end: len - 1,
+        };
+    }
+
+    let Ok(start) = start.parse::<u64>() else {
+        return ByteRange::Full;
+    };
+    if start >= len {
+        return ByteRange::Unsatisfiable;
+    }
+
+    let end = if end.is_empty() {
+        len - 1
+    } else {
+        match end.parse::<u64>() {
+            Ok(end) if end >= start => end.min(len - 1),
+            _ => return ByteRange::Full,
+        }
+    };
+
+    ByteRange::Partial { start, end }
+}
+
+/// Check an `If-None-Match` header against an entity tag (weak comparison)
+pub fn etag_matches(header: &str, etag: &str) -> bool {
+    let strip = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = strip(etag);
+
+    header
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || strip(candidate) == etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_byte_range() {
+        assert_eq!(parse_byte_range("bytes=0-99", 1000), ByteRange::Partial { start: 0, end: 99 });
+        assert_eq!(parse_byte_range("bytes=500-", 1000), ByteRange::Partial { start: 500, end: 999 });
+        assert_eq!(parse_byte_range("bytes=-100", 1000), ByteRange::Partial { start: 900, end: 999 });
+        assert_eq!(parse_byte_range("bytes=900-5000", 1000), ByteRange::Partial { start: 900, end: 999 });
+        assert_eq!(parse_byte_range("bytes=1000-", 1000), ByteRange::Unsatisfiable);
+        assert_eq!(parse_byte_range("bytes=0-1,5-9", 1000), ByteRange::Full);
+        assert_eq!(parse_byte_range("items=0-1", 1000), ByteRange::Full);
+        assert_eq!(parse_byte_range("bytes=9-1", 1000), ByteRange::Full);
+    }
+
+    #[test]
+    fn test_etag_matches() {
+        assert!(etag_matches("\"abc\"", "\"abc\""));
+        assert!(etag_matches("W/\"abc\", \"def\"", "\"abc\""));
+        assert!(etag_matches("*", "\"abc\""));
+        assert!(!etag_matches("\"def\"", "\"abc\""));
+    }
+}
@@ -1,4 +1,9 @@
+pub mod compression;
+pub mod http;
 pub mod platform;
+pub mod player;
+pub mod safe_path;
+pub mod text;
 
 use crate::config::Config;
 use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
@@ -0,0 +1,32 @@
+/// A player UUID that doesn't parse; handlers turn it into a 400
+#[derive(Debug)]
+pub struct InvalidPlayerUuid(pub String);
+
+impl std::fmt::Display for InvalidPlayerUuid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid player UUID: {}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidPlayerUuid {}
+
+/// Normalize a Minecraft UUID (with or without dashes) to the hyphenated form
+pub fn parse_player_uuid(uuid: &str) -> Result<String, InvalidPlayerUuid> {
+    uuid::Uuid::parse_str(uuid.trim())
+        .map(|u| u.hyphenated().to_string())
+        .map_err(|_| InvalidPlayerUuid(uuid.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_player_uuid_normalizes() {
+        let dashed = "adca5752-c67a-4f0a-ae74-44d9f369f6f8";
+        assert_eq!(parse_player_uuid("adca5752c67a4f0aae7444d9f369f6f8").unwrap(), dashed);
+        assert_eq!(parse_player_uuid(dashed).unwrap(), dashed);
+        assert!(parse_player_uuid("../etc").is_err());
+        assert!(parse_player_uuid("not-a-uuid").is_err());
+    }
+}
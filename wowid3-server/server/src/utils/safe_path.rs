@@ -0,0 +1,226 @@
+//! Resolving request paths to files under a served directory
+//!
+//! Every handler that maps a URL path onto the filesystem goes through here, so the
+//! traversal and symlink rules live in one place:
+//!
+//! - each segment is percent-decoded (repeatedly, to catch double encoding) before it
+//!   is checked, and `..`, `.`, empty segments, backslashes, NUL and drive prefixes are
+//!   rejected; the path used on disk is built from the segments as received
+//! - the resolved file must stay inside the served directory; symlinks leading out of
+//!   it are refused unless the policy is [`SymlinkPolicy::Follow`] or they point into a
+//!   directory allowed with [`ServedDir::allow_links_into`]
+
+use percent_encoding::percent_decode_str;
+use serde::Deserialize;
+use std::path::{Component, Path, PathBuf};
+use tokio::fs;
+
+/// Rounds of percent-decoding applied before a segment is checked
+const MAX_DECODE_ROUNDS: usize = 3;
+
+/// What to do with symlinks that resolve outside the served directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymlinkPolicy {
+    /// Refuse them, except links into explicitly allowed directories (the object store)
+    #[default]
+    Contained,
+    /// Follow them anywhere; only for storage whose links are all trusted
+    Follow,
+}
+
+/// Why a request path was not resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum PathRejection {
+    #[error("Empty path")]
+    Empty,
+    #[error("Path traversal attempt detected")]
+    Traversal,
+    #[error("Invalid path segment")]
+    InvalidSegment,
+    #[error("Symlink points outside the served directory")]
+    SymlinkEscape,
+    #[error("File not found")]
+    NotFound,
+}
+
+/// Check one segment, percent-decoded until it stops changing
+fn check_segment(segment: &str) -> Result<(), PathRejection> {
+    let mut decoded = segment.to_string();
+    for _ in 0..MAX_DECODE_ROUNDS {
+        let next = percent_decode_str(&decoded).decode_utf8_lossy().into_owned();
+        if next == decoded {
+            break;
+        }
+        decoded = next;
+    }
+
+    if decoded.is_empty() || decoded == "." {
+        return Err(PathRejection::InvalidSegment);
+    }
+    if decoded == ".." || decoded.contains('/') {
+        return Err(PathRejection::Traversal);
+    }
+    if decoded.contains(['\\', '\0']) {
+        return Err(PathRejection::InvalidSegment);
+    }
+    // Catches drive prefixes (`C:`) and roots on platforms that have them
+    let mut components = Path::new(segment).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => Ok(()),
+        _ => Err(PathRejection::Traversal),
+    }
+}
+
+/// Validate a `/`-separated path relative to a served directory
+pub fn relative_path(raw: &str) -> Result<PathBuf, PathRejection> {
+    if raw.is_empty() {
+        return Err(PathRejection::Empty);
+    }
+    if raw.starts_with('/') {
+        return Err(PathRejection::Traversal);
+    }
+    let mut path = PathBuf::new();
+    for segment in raw.split('/') {
+        check_segment(segment)?;
+        path.push(segment);
+    }
+    Ok(path)
+}
+
+/// Validate a single segment such as a version or file name
+pub fn segment(raw: &str) -> Result<&str, PathRejection> {
+    if raw.is_empty() {
+        return Err(PathRejection::Empty);
+    }
+    if raw.contains('/') {
+        return Err(PathRejection::Traversal);
+    }
+    check_segment(raw)?;
+    Ok(raw)
+}
+
+/// A directory files are served from
+#[derive(Debug, Clone)]
+pub struct ServedDir {
+    root: PathBuf,
+    policy: SymlinkPolicy,
+    link_targets: Vec<PathBuf>,
+}
+
+impl ServedDir {
+    pub fn new(root: impl Into<PathBuf>, policy: SymlinkPolicy) -> Self {
+        Self {
+            root: root.into(),
+            policy,
+            link_targets: Vec::new(),
+        }
+    }
+
+    /// Also accept symlinks into `dir`, as long as the link itself sits inside the root
+    pub fn allow_links_into(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.link_targets.push(dir.into());
+        self
+    }
+
+    /// Canonical path of the existing file `raw` names under this directory
+    pub async fn resolve(&self, raw: &str) -> Result<PathBuf, PathRejection> {
+        let relative = relative_path(raw)?;
+        let root = fs::canonicalize(&self.root)
+            .await
+            .map_err(|_| PathRejection::NotFound)?;
+        let full = root.join(&relative);
+        let resolved = fs::canonicalize(&full)
+            .await
+            .map_err(|_| PathRejection::NotFound)?;
+
+        if resolved.starts_with(&root) || self.policy == SymlinkPolicy::Follow {
+            return Ok(resolved);
+        }
+
+        // A link to an allowed directory counts only if no directory above it leads out
+        let link_inside = match full.parent() {
+            Some(parent) => fs::canonicalize(parent)
+                .await
+                .is_ok_and(|parent| parent.starts_with(&root)),
+            None => false,
+        };
+        if link_inside {
+            for dir in &self.link_targets {
+                if fs::canonicalize(dir)
+                    .await
+                    .is_ok_and(|dir| resolved.starts_with(dir))
+                {
+                    return Ok(resolved);
+                }
+            }
+        }
+        Err(PathRejection::SymlinkEscape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_path_rejects_traversal() {
+        assert_eq!(relative_path("mods/a.jar").unwrap(), PathBuf::from("mods/a.jar"));
+        assert_eq!(relative_path("mods/100%25 fun.jar").unwrap(), PathBuf::from("mods/100%25 fun.jar"));
+
+        for raw in [
+            "../secret",
+            "mods/../../secret",
+            "/etc/passwd",
+            "%2e%2e/secret",
+            "%252e%252e/secret",
+            "mods%2f..%2fsecret",
+            "..%2fsecret",
+        ] {
+            assert_eq!(relative_path(raw), Err(PathRejection::Traversal), "{}", raw);
+        }
+        for raw in ["mods//a.jar", "./a.jar", "mods\\..\\a.jar", "a%00.jar", "mods/"] {
+            assert_eq!(relative_path(raw), Err(PathRejection::InvalidSegment), "{}", raw);
+        }
+        assert_eq!(relative_path(""), Err(PathRejection::Empty));
+
+        assert_eq!(segment("1.0.0"), Ok("1.0.0"));
+        assert_eq!(segment("1.0.0/mods"), Err(PathRejection::Traversal));
+        assert_eq!(segment(".."), Err(PathRejection::Traversal));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_resolve_symlink_policy() {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("releases/1.0.0");
+        let objects = dir.path().join("objects");
+        let outside = dir.path().join("outside");
+        for d in [&root, &objects, &outside] {
+            std::fs::create_dir_all(d).unwrap();
+        }
+        std::fs::write(root.join("a.jar"), b"a").unwrap();
+        std::fs::write(objects.join("abc"), b"object").unwrap();
+        std::fs::write(outside.join("secret"), b"secret").unwrap();
+        symlink(objects.join("abc"), root.join("linked.jar")).unwrap();
+        symlink(outside.join("secret"), root.join("escape.jar")).unwrap();
+        // A directory link out of the root, holding a link into the object store
+        symlink(&outside, root.join("sneaky")).unwrap();
+        symlink(objects.join("abc"), outside.join("object.jar")).unwrap();
+
+        let served = ServedDir::new(&root, SymlinkPolicy::Contained).allow_links_into(&objects);
+        assert!(served.resolve("a.jar").await.unwrap().ends_with("a.jar"));
+        assert!(served.resolve("linked.jar").await.unwrap().ends_with("objects/abc"));
+        assert_eq!(served.resolve("escape.jar").await, Err(PathRejection::SymlinkEscape));
+        assert_eq!(served.resolve("sneaky/object.jar").await, Err(PathRejection::SymlinkEscape));
+        assert_eq!(served.resolve("sneaky/secret").await, Err(PathRejection::SymlinkEscape));
+        assert_eq!(served.resolve("missing.jar").await, Err(PathRejection::NotFound));
+        assert_eq!(served.resolve("../../outside/secret").await, Err(PathRejection::Traversal));
+
+        let following = ServedDir::new(&root, SymlinkPolicy::Follow);
+        assert!(following.resolve("escape.jar").await.is_ok());
+        assert_eq!(following.resolve("../a.jar").await, Err(PathRejection::Traversal));
+    }
+}
@@ -0,0 +1,24 @@
+/// Cut a string to at most `max` bytes on a char boundary
+pub fn truncate_bytes(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_bytes_respects_char_boundaries() {
+        assert_eq!(truncate_bytes("hello", 10), "hello");
+        assert_eq!(truncate_bytes("hello", 3), "hel");
+        // 'é' is two bytes; cutting inside it backs off
+        assert_eq!(truncate_bytes("héllo", 2), "h");
+    }
+}
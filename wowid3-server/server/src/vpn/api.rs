@@ -1,17 +1,26 @@
 use axum::{
     extract::{Path, State, Json},
     http::StatusCode,
-    Router, routing::{get, post, delete},
+    Extension, Router, routing::{get, post, delete},
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use crate::database::Database;
-use super::{manager::WireGuardManager, provisioner::IpAllocator};
+use crate::api::admin::require_role;
+use crate::database::store::{PeerOrder, VpnPeerRecord, VpnPeerStore};
+use crate::middleware::AdminToken;
+use crate::models::AdminRole;
+use super::{
+    manager::WireGuardManager,
+    monitor::ONLINE_WINDOW_SECS,
+    provisioner::{IpAllocator, SERVER_IP},
+};
 
 #[derive(Clone)]
 pub struct VpnState {
-    pub db: Database,
+    pub peers: Arc<dyn VpnPeerStore>,
     pub ip_allocator: Arc<IpAllocator>,
+    /// Idle peers are revoked after this many days (0 disables)
+    pub revoke_after_days: u64,
 }
 
 #[derive(Deserialize)]
@@ -26,6 +35,8 @@ pub struct RegisterRequest {
 pub struct RegisterResponse {
     pub success: bool,
     pub assigned_ip: String,
+    /// Address players connect to through the tunnel
+    pub server_ip: String,
     pub server_public_key: String,
     pub endpoint: String,
 }
@@ -41,44 +52,31 @@ pub async fn register_peer(
     }
 
     // Check if peer already exists by UUID
-    let existing = state.db.conn.call({
-        let uuid = req.minecraft_uuid.clone();
-        move |conn| {
-            conn.query_row(
-                "SELECT ip_address, public_key FROM vpn_peers WHERE uuid = ?1 AND revoked = 0",
-                [&uuid],
-                |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
-            )
-        }
-    }).await;
+    let existing = state.peers
+        .active_peer(&req.minecraft_uuid)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
+    let now = chrono::Utc::now().timestamp();
 
     let assigned_ip = match existing {
-        Ok((ip, old_key)) => {
+        Some(peer) => {
             // Peer exists, check if public key changed
-            if old_key != req.public_key {
+            if peer.public_key != req.public_key {
                 // Remove old public key from WireGuard
-                if let Err(e) = WireGuardManager::remove_peer(&old_key) {
+                if let Err(e) = WireGuardManager::remove_peer(&peer.public_key) {
                     eprintln!("Warning: Failed to remove old WireGuard peer: {}", e);
                 }
 
                 // Update public key in database
-                state.db.conn.call({
-                    let uuid = req.minecraft_uuid.clone();
-                    let username = req.minecraft_username.clone();
-                    let public_key = req.public_key.clone();
-                    let now = chrono::Utc::now().timestamp();
-                    move |conn| {
-                        conn.execute(
-                            "UPDATE vpn_peers SET public_key = ?1, username = ?2, registered_at = ?3 WHERE uuid = ?4",
-                            rusqlite::params![&public_key, &username, &now, &uuid]
-                        )
-                    }
-                }).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
+                state.peers
+                    .update_peer_key(&req.minecraft_uuid, &req.minecraft_username, &req.public_key, now)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
             }
 
-            ip
+            peer.ip_address
         }
-        Err(_) => {
+        None => {
             // New peer, allocate IP
             let ip = state.ip_allocator
                 .next_available_ip()
@@ -86,20 +84,10 @@ pub async fn register_peer(
                 .map_err(|e| (StatusCode::SERVICE_UNAVAILABLE, e.to_string()))?;
 
             // Insert into database
-            state.db.conn.call({
-                let uuid = req.minecraft_uuid.clone();
-                let username = req.minecraft_username.clone();
-                let public_key = req.public_key.clone();
-                let ip_clone = ip.clone();
-                let now = chrono::Utc::now().timestamp();
-                move |conn| {
-                    conn.execute(
-                        "INSERT INTO vpn_peers (uuid, username, public_key, ip_address, registered_at)
-                         VALUES (?1, ?2, ?3, ?4, ?5)",
-                        rusqlite::params![&uuid, &username, &public_key, &ip_clone, &now]
-                    )
-                }
-            }).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
+            state.peers
+                .upsert_peer(&req.minecraft_uuid, &req.minecraft_username, &req.public_key, &ip, now)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
 
             ip
         }
@@ -118,22 +106,75 @@ pub async fn register_peer(
         Json(RegisterResponse {
             success: true,
             assigned_ip,
+            server_ip: SERVER_IP.to_string(),
             server_public_key: server_pubkey,
             endpoint: "wowid-launcher.frostdev.io:51820".to_string(),
         }),
     ))
 }
 
+/// Whether a peer's tunnel is in use, from its last handshake
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerLiveness {
+    Online,
+    Idle,
+    NeverConnected,
+}
+
 #[derive(Serialize)]
 pub struct PeerInfo {
     pub uuid: String,
     pub username: String,
     pub ip_address: String,
     pub online: bool,
+    pub liveness: PeerLiveness,
     pub last_handshake: Option<i64>,
     pub bytes_sent: i64,
     pub bytes_received: i64,
     pub registered_at: i64,
+    /// When the monitor will revoke the peer if it stays idle
+    pub revokes_at: Option<i64>,
+}
+
+fn peer_info(peer: VpnPeerRecord, now: i64, revoke_after_days: u64) -> PeerInfo {
+    let last_handshake = peer.last_handshake;
+    let registered_at = peer.registered_at;
+
+    let liveness = match last_handshake {
+        Some(ts) if now - ts < ONLINE_WINDOW_SECS => PeerLiveness::Online,
+        Some(_) => PeerLiveness::Idle,
+        None => PeerLiveness::NeverConnected,
+    };
+    let revokes_at = (revoke_after_days > 0)
+        .then(|| last_handshake.unwrap_or(registered_at) + revoke_after_days as i64 * 86_400);
+
+    PeerInfo {
+        uuid: peer.uuid,
+        username: peer.username,
+        ip_address: peer.ip_address,
+        online: liveness == PeerLiveness::Online,
+        liveness,
+        last_handshake,
+        bytes_sent: peer.bytes_sent,
+        bytes_received: peer.bytes_received,
+        registered_at,
+        revokes_at,
+    }
+}
+
+/// Active peers with liveness, in the given order
+async fn active_peer_infos(state: &VpnState, order: PeerOrder) -> Result<Vec<PeerInfo>, (StatusCode, String)> {
+    let peers = state.peers
+        .list_active(order)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
+
+    let now = chrono::Utc::now().timestamp();
+    Ok(peers
+        .into_iter()
+        .map(|peer| peer_info(peer, now, state.revoke_after_days))
+        .collect())
 }
 
 #[derive(Serialize)]
@@ -145,47 +186,18 @@ pub struct VpnStats {
     pub peers: Vec<PeerInfo>,
 }
 
-/// List all non-revoked VPN peers (admin only)
+/// [`require_role`] with this module's error type
+fn require_admin_role(token: &AdminToken, required: AdminRole) -> Result<(), (StatusCode, String)> {
+    require_role(token, required).map_err(|e| (StatusCode::FORBIDDEN, e.to_string()))
+}
+
+/// List all non-revoked VPN peers with liveness (admin only)
 pub async fn list_peers(
     State(state): State<VpnState>,
+    Extension(token): Extension<AdminToken>,
 ) -> Result<(StatusCode, Json<Vec<PeerInfo>>), (StatusCode, String)> {
-    // Query all non-revoked peers from database
-    let peers = state.db.conn.call(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT uuid, username, ip_address, last_handshake, bytes_sent, bytes_received, registered_at
-             FROM vpn_peers
-             WHERE revoked = 0
-             ORDER BY username ASC"
-        )?;
-
-        let peer_iter = stmt.query_map([], |row| {
-            let last_handshake: Option<i64> = row.get(3).ok();
-            let now = chrono::Utc::now().timestamp();
-
-            // Peer is online if last handshake was within last 3 minutes
-            let online = last_handshake
-                .map(|ts| now - ts < 180)
-                .unwrap_or(false);
-
-            Ok(PeerInfo {
-                uuid: row.get(0)?,
-                username: row.get(1)?,
-                ip_address: row.get(2)?,
-                online,
-                last_handshake,
-                bytes_sent: row.get(4).unwrap_or(0),
-                bytes_received: row.get(5).unwrap_or(0),
-                registered_at: row.get(6)?,
-            })
-        })?;
-
-        let mut peers = Vec::new();
-        for peer_result in peer_iter {
-            peers.push(peer_result?);
-        }
-
-        Ok::<Vec<PeerInfo>, rusqlite::Error>(peers)
-    }).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
+    require_admin_role(&token, AdminRole::Viewer)?;
+    let peers = active_peer_infos(&state, PeerOrder::Username).await?;
 
     Ok((StatusCode::OK, Json(peers)))
 }
@@ -193,44 +205,11 @@ pub async fn list_peers(
 /// Get VPN statistics (admin only)
 pub async fn get_vpn_stats(
     State(state): State<VpnState>,
+    Extension(token): Extension<AdminToken>,
 ) -> Result<(StatusCode, Json<VpnStats>), (StatusCode, String)> {
+    require_admin_role(&token, AdminRole::Viewer)?;
     // Query all non-revoked peers from database with stats
-    let peers = state.db.conn.call(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT uuid, username, ip_address, last_handshake, bytes_sent, bytes_received, registered_at
-             FROM vpn_peers
-             WHERE revoked = 0
-             ORDER BY last_handshake DESC NULLS LAST"
-        )?;
-
-        let peer_iter = stmt.query_map([], |row| {
-            let last_handshake: Option<i64> = row.get(3).ok();
-            let now = chrono::Utc::now().timestamp();
-
-            // Peer is online if last handshake was within last 3 minutes
-            let online = last_handshake
-                .map(|ts| now - ts < 180)
-                .unwrap_or(false);
-
-            Ok(PeerInfo {
-                uuid: row.get(0)?,
-                username: row.get(1)?,
-                ip_address: row.get(2)?,
-                online,
-                last_handshake,
-                bytes_sent: row.get(4).unwrap_or(0),
-                bytes_received: row.get(5).unwrap_or(0),
-                registered_at: row.get(6)?,
-            })
-        })?;
-
-        let mut peers = Vec::new();
-        for peer_result in peer_iter {
-            peers.push(peer_result?);
-        }
-
-        Ok::<Vec<PeerInfo>, rusqlite::Error>(peers)
-    }).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
+    let peers = active_peer_infos(&state, PeerOrder::LastHandshake).await?;
 
     // Calculate aggregate stats
     let total_peers = peers.len();
@@ -253,22 +232,18 @@ pub async fn get_vpn_stats(
 /// Revoke a VPN peer's access (admin only)
 pub async fn revoke_peer(
     State(state): State<VpnState>,
+    Extension(token): Extension<AdminToken>,
     Path(uuid): Path<String>,
 ) -> Result<StatusCode, (StatusCode, String)> {
+    require_admin_role(&token, AdminRole::ReleaseManager)?;
     // Get peer's public key from database
-    let public_key = state.db.conn.call({
-        let uuid = uuid.clone();
-        move |conn| {
-            conn.query_row(
-                "SELECT public_key FROM vpn_peers WHERE uuid = ?1",
-                [&uuid],
-                |row| row.get::<_, String>(0)
-            )
-        }
-    }).await;
+    let public_key = state.peers
+        .public_key(&uuid)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
 
     // Remove peer from WireGuard if found
-    if let Ok(key) = public_key {
+    if let Some(key) = public_key {
         if let Err(e) = WireGuardManager::remove_peer(&key) {
             eprintln!("Warning: Failed to remove WireGuard peer {}: {}", uuid, e);
             // Continue anyway to mark as revoked in database
@@ -276,15 +251,10 @@ pub async fn revoke_peer(
     }
 
     // Mark as revoked in database
-    state.db.conn.call({
-        let now = chrono::Utc::now().timestamp();
-        move |conn| {
-            conn.execute(
-                "UPDATE vpn_peers SET revoked = 1, revoked_at = ?1 WHERE uuid = ?2",
-                rusqlite::params![now, &uuid]
-            )
-        }
-    }).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
+    state.peers
+        .revoke(&uuid, chrono::Utc::now().timestamp())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -295,14 +265,16 @@ pub fn vpn_public_routes(state: VpnState) -> Router {
         .with_state(state)
 }
 
-pub fn vpn_admin_routes(state: VpnState) -> Router {
+pub fn vpn_admin_routes(state: VpnState, auth: crate::middleware::auth::AuthState) -> Router {
     use axum::middleware as axum_middleware;
-    use crate::middleware::auth::auth_middleware;
+    use crate::middleware::{audit::audit_middleware, auth::auth_middleware};
 
+    let db = auth.db.clone();
     Router::new()
         .route("/api/admin/vpn/peers", get(list_peers))
         .route("/api/admin/vpn/stats", get(get_vpn_stats))
         .route("/api/admin/vpn/peers/:uuid", delete(revoke_peer))
-        .layer(axum_middleware::from_fn(auth_middleware))
+        .layer(axum_middleware::from_fn_with_state(db, audit_middleware))
+        .layer(axum_middleware::from_fn_with_state(auth, auth_middleware))
         .with_state(state)
 }
@@ -30,6 +30,20 @@ impl WireGuardManager {
         Ok(())
     }
 
+    /// Raw `wg show wg0 dump` output (see `monitor::parse_dump`)
+    pub fn dump() -> Result<String> {
+        let output = Command::new("sudo")
+            .args(["wg", "show", "wg0", "dump"])
+            .output()?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow::anyhow!("Failed to read WireGuard peer stats: {}", error));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
     pub fn get_server_public_key() -> Result<String> {
         // Try reading from file first
         if let Ok(key) = std::fs::read_to_string("/etc/wireguard/server_public.key") {
@@ -3,4 +3,5 @@ pub mod provisioner;
 pub mod monitor;
 pub mod api;
 
+pub use monitor::VpnMonitor;
 pub use provisioner::IpAllocator;
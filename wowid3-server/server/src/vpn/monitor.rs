@@ -1 +1,156 @@
-// Placeholder for monitor module - will be implemented in future tasks
+use crate::database::store::VpnPeerStore;
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use super::manager::WireGuardManager;
+
+/// A peer counts as online if it completed a handshake this recently
+/// (WireGuard re-handshakes every 2 minutes while traffic flows)
+pub const ONLINE_WINDOW_SECS: i64 = 180;
+
+/// One peer line of `wg show <iface> dump`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerStats {
+    pub public_key: String,
+    /// Unix seconds; `None` if the peer never completed a handshake
+    pub last_handshake: Option<i64>,
+    /// Bytes the server sent to the peer since the interface came up
+    pub bytes_sent: i64,
+    /// Bytes the server received from the peer since the interface came up
+    pub bytes_received: i64,
+}
+
+/// Parse `wg show <iface> dump` output
+///
+/// The first line describes the interface; each following line is a peer:
+/// public-key, preshared-key, endpoint, allowed-ips, latest-handshake,
+/// transfer-rx, transfer-tx, persistent-keepalive (tab separated).
+pub fn parse_dump(dump: &str) -> Vec<PeerStats> {
+    dump.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 8 {
+                return None;
+            }
+            let handshake: i64 = fields[4].parse().ok()?;
+            Some(PeerStats {
+                public_key: fields[0].to_string(),
+                last_handshake: (handshake > 0).then_some(handshake),
+                bytes_received: fields[5].parse().ok()?,
+                bytes_sent: fields[6].parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Periodically syncs peer stats from wg0 and revokes long-idle peers
+pub struct VpnMonitor {
+    peers: Arc<dyn VpnPeerStore>,
+    interval: Duration,
+    /// Revoke peers idle for this many days (0 disables)
+    revoke_after_days: u64,
+}
+
+impl VpnMonitor {
+    pub fn new(peers: Arc<dyn VpnPeerStore>, interval: Duration, revoke_after_days: u64) -> Self {
+        Self {
+            peers,
+            interval,
+            revoke_after_days,
+        }
+    }
+
+    pub fn spawn(self) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_once().await {
+                    warn!("VPN monitor pass failed: {:#}", e);
+                }
+            }
+        });
+    }
+
+    async fn run_once(&self) -> Result<()> {
+        let dump = tokio::task::spawn_blocking(WireGuardManager::dump).await??;
+        self.peers.update_stats(parse_dump(&dump)).await?;
+
+        if self.revoke_after_days == 0 {
+            return Ok(());
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let cutoff = now - self.revoke_after_days as i64 * 86_400;
+        for (uuid, public_key) in self.peers.stale_peers(cutoff).await? {
+            let result = tokio::task::spawn_blocking(move || WireGuardManager::remove_peer(&public_key)).await?;
+            if let Err(e) = result {
+                // Still revoke; registering again reactivates the peer
+                warn!("Failed to remove idle WireGuard peer {}: {}", uuid, e);
+            }
+            self.peers.revoke(&uuid, now).await?;
+            info!("Revoked VPN peer {} after {} days without a handshake", uuid, self.revoke_after_days);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{store::SqliteStore, Database};
+    use tempfile::tempdir;
+
+    const DUMP: &str = "cHJpdmF0ZQ==\tc2VydmVy\t51820\toff\n\
+        cGVlcjE=\t(none)\t203.0.113.5:51000\t10.8.0.2/32\t1700000000\t1024\t4096\t25\n\
+        cGVlcjI=\t(none)\t(none)\t10.8.0.3/32\t0\t0\t0\toff\n";
+
+    #[test]
+    fn test_parse_dump() {
+        let peers = parse_dump(DUMP);
+        assert_eq!(peers.len(), 2);
+        assert_eq!(
+            peers[0],
+            PeerStats {
+                public_key: "cGVlcjE=".to_string(),
+                last_handshake: Some(1_700_000_000),
+                bytes_sent: 4096,
+                bytes_received: 1024,
+            }
+        );
+        assert_eq!(peers[1].last_handshake, None);
+    }
+
+    #[tokio::test]
+    async fn test_stats_update_and_stale_peers() {
+        let dir = tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db")).await.unwrap();
+        db.init_schema().await.unwrap();
+        db.conn
+            .call(|conn| {
+                conn.execute_batch(
+                    "INSERT INTO vpn_peers (uuid, username, public_key, ip_address, registered_at, last_handshake)
+                     VALUES ('a', 'alice', 'cGVlcjE=', '10.8.0.2', 100, 200),
+                            ('b', 'bob', 'cGVlcjI=', '10.8.0.3', 100, 1600000000),
+                            ('c', 'carol', 'cGVlcjM=', '10.8.0.4', 1600000000, NULL)",
+                )
+            })
+            .await
+            .unwrap();
+
+        let store = SqliteStore::new(db);
+        assert_eq!(store.update_stats(parse_dump(DUMP)).await.unwrap(), 2);
+
+        // alice got a fresh handshake; bob's is kept despite the 0 in the dump
+        let stale = store.stale_peers(1_650_000_000).await.unwrap();
+        let stale: Vec<_> = stale.into_iter().map(|(uuid, _)| uuid).collect();
+        assert_eq!(stale, vec!["b".to_string(), "c".to_string()]);
+
+        store.revoke("b", 1_700_000_000).await.unwrap();
+        let stale = store.stale_peers(1_650_000_000).await.unwrap();
+        assert_eq!(stale.len(), 1);
+    }
+}
@@ -1,29 +1,22 @@
+use crate::database::store::VpnPeerStore;
 use anyhow::Result;
-use tokio_rusqlite::Connection;
+use std::sync::Arc;
+
+/// Tunnel address of the game server itself (wg0); peers get .2 and up
+pub const SERVER_IP: &str = "10.8.0.1";
 
 pub struct IpAllocator {
-    conn: Connection,
+    peers: Arc<dyn VpnPeerStore>,
 }
 
 impl IpAllocator {
-    pub fn new(conn: Connection) -> Self {
-        Self { conn }
+    pub fn new(peers: Arc<dyn VpnPeerStore>) -> Self {
+        Self { peers }
     }
 
     pub async fn next_available_ip(&self) -> Result<String> {
         // Find next available IP in range 10.8.0.2 - 10.8.0.254
-        let assigned_ips = self.conn.call(|conn| {
-            let mut stmt = conn.prepare(
-                "SELECT ip_address FROM vpn_peers WHERE ip_address LIKE '10.8.0.%' AND revoked = 0 ORDER BY ip_address"
-            )?;
-
-            let ips = stmt.query_map([], |row| {
-                row.get::<_, String>(0)
-            })?
-            .collect::<Result<Vec<String>, _>>()?;
-
-            Ok::<Vec<String>, rusqlite::Error>(ips)
-        }).await?;
+        let assigned_ips = self.peers.active_ips().await?;
 
         // Find first unassigned IP
         for i in 2..=254 {
@@ -40,11 +33,24 @@ impl IpAllocator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::database::{store::SqliteStore, Database};
+    use tempfile::tempdir;
 
     #[tokio::test]
     async fn test_next_available_ip_starts_at_2() {
-        // Real test would use in-memory SQLite
-        // For now, just verify module compiles
-        assert_eq!(1, 1);
+        let dir = tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db")).await.unwrap();
+        db.init_schema().await.unwrap();
+        let store = Arc::new(SqliteStore::new(db));
+        let allocator = IpAllocator::new(store.clone());
+        assert_eq!(allocator.next_available_ip().await.unwrap(), "10.8.0.2");
+
+        store.upsert_peer("a", "alice", "a2V5MQ==", "10.8.0.2", 100).await.unwrap();
+        store.upsert_peer("b", "bob", "a2V5Mg==", "10.8.0.3", 100).await.unwrap();
+        assert_eq!(allocator.next_available_ip().await.unwrap(), "10.8.0.4");
+
+        // Revoked peers give their address back
+        store.revoke("a", 200).await.unwrap();
+        assert_eq!(allocator.next_available_ip().await.unwrap(), "10.8.0.2");
     }
 }
@@ -6,8 +6,22 @@ import type { LoginRequest, LoginResponse } from './types';
 /**
  * Login with admin password
  */
-export async function login(password: string): Promise<string> {
-  const request: LoginRequest = { password };
+export async function login(password: string, username?: string): Promise<string> {
+  const request: LoginRequest = username ? { username, password } : { password };
   const response = await api.post<LoginResponse>('/admin/login', request);
+  localStorage.setItem('refresh_token', response.data.refresh_token);
   return response.data.token;
 }
+
+/**
+ * Logout and revoke the current tokens on the server
+ */
+export async function logout(): Promise<void> {
+  const refreshToken = localStorage.getItem('refresh_token');
+  try {
+    await api.post('/admin/logout', { refresh_token: refreshToken });
+  } finally {
+    localStorage.removeItem('auth_token');
+    localStorage.removeItem('refresh_token');
+  }
+}
@@ -1,5 +1,5 @@
 import axios, { AxiosInstance, AxiosError, InternalAxiosRequestConfig } from 'axios';
-import type { ApiError } from './types';
+import type { ApiError, LoginResponse } from './types';
 
 // Create axios instance with base configuration
 const api: AxiosInstance = axios.create({
@@ -30,13 +30,47 @@ api.interceptors.request.use(
   }
 );
 
+// Exchange the stored refresh token for a new access token (rotates the refresh token)
+let refreshPromise: Promise<string | null> | null = null;
+function refreshAccessToken(): Promise<string | null> {
+  const refreshToken = localStorage.getItem('refresh_token');
+  if (!refreshToken) return Promise.resolve(null);
+
+  if (!refreshPromise) {
+    refreshPromise = axios
+      .post<LoginResponse>('/api/admin/refresh', { refresh_token: refreshToken })
+      .then((response) => {
+        localStorage.setItem('auth_token', response.data.token);
+        localStorage.setItem('refresh_token', response.data.refresh_token);
+        return response.data.token;
+      })
+      .catch(() => null)
+      .finally(() => {
+        refreshPromise = null;
+      });
+  }
+  return refreshPromise;
+}
+
 // Response interceptor - Handle response errors
 api.interceptors.response.use(
   (response) => response,
-  (error: AxiosError<ApiError>) => {
-    // Handle 401 Unauthorized - clear token and redirect to login
+  async (error: AxiosError<ApiError>) => {
+    const original = error.config as (InternalAxiosRequestConfig & { _retried?: boolean }) | undefined;
+
+    // Handle 401 Unauthorized - try a token refresh once, otherwise clear tokens and redirect to login
     if (error.response?.status === 401) {
+      const isAuthCall = original?.url?.startsWith('/admin/login') || original?.url?.startsWith('/admin/refresh');
+      if (original && !original._retried && !isAuthCall) {
+        original._retried = true;
+        const token = await refreshAccessToken();
+        if (token) {
+          original.headers.Authorization = `Bearer ${token}`;
+          return api(original);
+        }
+      }
       localStorage.removeItem('auth_token');
+      localStorage.removeItem('refresh_token');
       window.location.href = '/';
     }
 
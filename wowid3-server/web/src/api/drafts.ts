@@ -1,6 +1,7 @@
 // Typed API endpoints for draft operations
 
 import api from './client';
+import { waitForTask } from './tasks';
 import type {
   DraftRelease,
   CreateDraftRequest,
@@ -10,6 +11,10 @@ import type {
   VersionSuggestions,
   GeneratedChangelog,
   MessageResponse,
+  TaskAcceptedResponse,
+  TaskStatus,
+  ValidationReport,
+  ReleaseDiff,
 } from './types';
 
 const BASE_PATH = '/admin/drafts';
@@ -41,13 +46,15 @@ export async function createDraft(
 }
 
 /**
- * Update draft metadata
+ * Update draft metadata; with `revision`, fails with 409 if someone else changed the draft since
  */
 export async function updateDraft(
   id: string,
-  request: UpdateDraftRequest
+  request: UpdateDraftRequest,
+  revision?: number
 ): Promise<DraftRelease> {
-  const response = await api.put<DraftRelease>(`${BASE_PATH}/${id}`, request);
+  const headers = revision === undefined ? {} : { 'If-Match': String(revision) };
+  const response = await api.put<DraftRelease>(`${BASE_PATH}/${id}`, request, { headers });
   return response.data;
 }
 
@@ -60,11 +67,14 @@ export async function deleteDraft(id: string): Promise<MessageResponse> {
 }
 
 /**
- * Analyze draft files and get version suggestions
+ * Analyze draft files and get version suggestions (runs as a background task)
  */
-export async function analyzeDraft(id: string): Promise<VersionSuggestions> {
-  const response = await api.post<VersionSuggestions>(`${BASE_PATH}/${id}/analyze`, {});
-  return response.data;
+export async function analyzeDraft(
+  id: string,
+  onProgress?: (status: TaskStatus<VersionSuggestions>) => void
+): Promise<VersionSuggestions> {
+  const response = await api.post<TaskAcceptedResponse>(`${BASE_PATH}/${id}/analyze`, {});
+  return waitForTask<VersionSuggestions>(response.data, onProgress);
 }
 
 /**
@@ -116,13 +126,32 @@ export async function updateFileInDraft(
 }
 
 /**
- * Publish draft as a release
+ * Check a draft for problems (empty files, duplicate mods, loader mismatches, ...) before publishing
+ */
+export async function validateDraft(id: string): Promise<ValidationReport> {
+  const response = await api.post<ValidationReport>(`${BASE_PATH}/${id}/validate`, {});
+  return response.data;
+}
+
+/**
+ * Compare a draft with any published release
  */
-export async function publishDraft(id: string): Promise<MessageResponse> {
-  const response = await api.post<MessageResponse>(`${BASE_PATH}/${id}/publish`, {});
+export async function diffDraft(id: string, version: string): Promise<ReleaseDiff> {
+  const response = await api.get<ReleaseDiff>(`${BASE_PATH}/${id}/diff/${encodeURIComponent(version)}`);
   return response.data;
 }
 
+/**
+ * Publish draft as a release (runs as a background task)
+ */
+export async function publishDraft(
+  id: string,
+  onProgress?: (status: TaskStatus<MessageResponse>) => void
+): Promise<MessageResponse> {
+  const response = await api.post<TaskAcceptedResponse>(`${BASE_PATH}/${id}/publish`, {});
+  return waitForTask<MessageResponse>(response.data, onProgress);
+}
+
 /**
  * Duplicate a draft
  */
@@ -15,3 +15,6 @@ export * from './drafts';
 export * from './releases';
 export * from './uploads';
 export * from './files';
+export * from './tasks';
+export * from './whitelist';
+export * from './resourcepacks';
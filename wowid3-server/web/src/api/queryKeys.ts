@@ -18,6 +18,14 @@ export const queryKeys = {
     list: () => [...queryKeys.releases.lists()] as const,
     details: () => [...queryKeys.releases.all, 'detail'] as const,
     detail: (version: string) => [...queryKeys.releases.details(), version] as const,
+    search: (query: string) => [...queryKeys.releases.all, 'search', query] as const,
+    retention: (keep?: number) => [...queryKeys.releases.all, 'retention', keep ?? 'configured'] as const,
+  },
+
+  // Uploads
+  uploads: {
+    all: ['uploads'] as const,
+    list: () => [...queryKeys.uploads.all, 'list'] as const,
   },
 
   // File browser
@@ -33,6 +41,7 @@ export const queryKeys = {
   blacklist: {
     all: ['blacklist'] as const,
     list: () => [...queryKeys.blacklist.all, 'list'] as const,
+    history: () => [...queryKeys.blacklist.all, 'history'] as const,
   },
 
   // Version suggestions
@@ -1,6 +1,7 @@
 // Typed API endpoints for release operations
 
 import api from './client';
+import { waitForTask } from './tasks';
 import type {
   Release,
   ReleasesListResponse,
@@ -9,6 +10,13 @@ import type {
   MessageResponse,
   BlacklistResponse,
   UpdateBlacklistRequest,
+  BlacklistPreviewRequest,
+  BlacklistPreview,
+  BlacklistRevision,
+  SearchResults,
+  RetentionPlan,
+  TaskAcceptedResponse,
+  TaskStatus,
 } from './types';
 
 const BASE_PATH = '/admin/releases';
@@ -22,13 +30,14 @@ export async function listReleases(): Promise<Release[]> {
 }
 
 /**
- * Create a new release
+ * Create a new release (runs as a background task)
  */
 export async function createRelease(
-  request: CreateReleaseRequest
+  request: CreateReleaseRequest,
+  onProgress?: (status: TaskStatus<MessageResponse>) => void
 ): Promise<MessageResponse> {
-  const response = await api.post<MessageResponse>(BASE_PATH, request);
-  return response.data;
+  const response = await api.post<TaskAcceptedResponse>(BASE_PATH, request);
+  return waitForTask<MessageResponse>(response.data, onProgress);
 }
 
 /**
@@ -39,6 +48,14 @@ export async function deleteRelease(version: string): Promise<MessageResponse> {
   return response.data;
 }
 
+/**
+ * Promote a beta release to the stable channel
+ */
+export async function promoteRelease(version: string): Promise<MessageResponse> {
+  const response = await api.post<MessageResponse>(`${BASE_PATH}/${version}/promote`);
+  return response.data;
+}
+
 /**
  * Copy a release to draft for editing
  */
@@ -67,3 +84,36 @@ export async function updateBlacklist(patterns: string[]): Promise<MessageRespon
   const response = await api.put<MessageResponse>('/admin/blacklist', request);
   return response.data;
 }
+
+/**
+ * Show which files proposed blacklist patterns would exclude (nothing is saved)
+ */
+export async function previewBlacklist(patterns: string[], draftId?: string): Promise<BlacklistPreview> {
+  const request: BlacklistPreviewRequest = { patterns, draft_id: draftId };
+  const response = await api.post<BlacklistPreview>('/admin/blacklist/preview', request);
+  return response.data;
+}
+
+/**
+ * Get saved blacklist revisions, newest first
+ */
+export async function getBlacklistHistory(): Promise<BlacklistRevision[]> {
+  const response = await api.get<BlacklistRevision[]>('/admin/blacklist/history');
+  return response.data;
+}
+
+/**
+ * Preview which releases the retention policy would prune (optionally with a different keep count)
+ */
+export async function previewRetention(keep?: number): Promise<RetentionPlan> {
+  const response = await api.get<RetentionPlan>('/admin/retention', { params: keep === undefined ? {} : { keep } });
+  return response.data;
+}
+
+/**
+ * Find releases and drafts containing a file path, mod id or changelog text
+ */
+export async function searchReleases(query: string): Promise<SearchResults> {
+  const response = await api.get<SearchResults>('/admin/search', { params: { q: query } });
+  return response.data;
+}
@@ -0,0 +1,48 @@
+// Typed API endpoints for versioned server resource packs
+
+import api from './client';
+import type { ResourcePack } from './types';
+
+const BASE_PATH = '/admin/resourcepacks';
+
+/**
+ * List uploaded resource pack versions, newest first
+ */
+export async function listResourcePacks(): Promise<ResourcePack[]> {
+  const response = await api.get<ResourcePack[]>(BASE_PATH);
+  return response.data;
+}
+
+/**
+ * Upload a new resource pack version (versions can't be overwritten)
+ */
+export async function uploadResourcePack(
+  file: File,
+  version: string,
+  minecraftVersion: string,
+  onProgress?: (progress: number) => void
+): Promise<ResourcePack> {
+  const formData = new FormData();
+  formData.append('version', version);
+  formData.append('minecraft_version', minecraftVersion);
+  formData.append('file', file);
+
+  const response = await api.post<ResourcePack>(BASE_PATH, formData, {
+    headers: {
+      'Content-Type': 'multipart/form-data',
+    },
+    onUploadProgress: (progressEvent) => {
+      if (onProgress && progressEvent.total) {
+        onProgress(Math.round((progressEvent.loaded * 100) / progressEvent.total));
+      }
+    },
+  });
+  return response.data;
+}
+
+/**
+ * Delete a resource pack version
+ */
+export async function deleteResourcePack(version: string): Promise<void> {
+  await api.delete(`${BASE_PATH}/${version}`);
+}
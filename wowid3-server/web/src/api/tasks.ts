@@ -0,0 +1,84 @@
+// Background task tracking for long-running admin operations
+
+import api from './client';
+import type { TaskAcceptedResponse, TaskStatus } from './types';
+
+const POLL_INTERVAL_MS = 1000;
+
+/**
+ * Get the current status of a background task
+ */
+export async function getTask<T = unknown>(id: string): Promise<TaskStatus<T>> {
+  const response = await api.get<TaskStatus<T>>(`/admin/tasks/${id}`);
+  return response.data;
+}
+
+function settle<T>(status: TaskStatus<T>): T {
+  if (status.state === 'failed') {
+    throw new Error(status.error || `${status.kind} failed`);
+  }
+  return status.result as T;
+}
+
+/**
+ * Poll a task until it finishes (used when the WebSocket is unavailable)
+ */
+async function pollTask<T>(
+  id: string,
+  onProgress?: (status: TaskStatus<T>) => void
+): Promise<T> {
+  for (;;) {
+    const status = await getTask<T>(id);
+    onProgress?.(status);
+    if (status.state !== 'running') {
+      return settle(status);
+    }
+    await new Promise((resolve) => setTimeout(resolve, POLL_INTERVAL_MS));
+  }
+}
+
+/**
+ * Wait for a background task to finish, streaming progress over a WebSocket
+ * and falling back to polling if the socket can't be opened.
+ */
+export function waitForTask<T = unknown>(
+  accepted: TaskAcceptedResponse,
+  onProgress?: (status: TaskStatus<T>) => void
+): Promise<T> {
+  const id = accepted.task_id;
+  const token = localStorage.getItem('auth_token') ?? '';
+  const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
+  const url = `${protocol}//${window.location.host}/ws/admin/tasks/${id}?token=${encodeURIComponent(token)}`;
+
+  return new Promise<T>((resolve, reject) => {
+    let last: TaskStatus<T> | null = null;
+    let socket: WebSocket;
+
+    try {
+      socket = new WebSocket(url);
+    } catch {
+      pollTask(id, onProgress).then(resolve, reject);
+      return;
+    }
+
+    socket.onmessage = (event) => {
+      last = JSON.parse(event.data) as TaskStatus<T>;
+      onProgress?.(last);
+      if (last.state !== 'running') {
+        socket.close();
+        try {
+          resolve(settle(last));
+        } catch (err) {
+          reject(err);
+        }
+      }
+    };
+
+    // Socket dropped before the task finished: keep waiting via polling
+    socket.onclose = () => {
+      if (!last || last.state === 'running') {
+        pollTask(id, onProgress).then(resolve, reject);
+      }
+    };
+  });
+}
@@ -2,17 +2,33 @@
 
 // ========== Draft Types ==========
 
+export type ReleaseChannel = 'stable' | 'beta';
+
+// Loader the pack runs on; `fabric_loader` holds its version whichever loader it is
+export type ModLoader = 'fabric' | 'quilt' | 'neoforge';
+
 export interface DraftRelease {
   id: string;
   version: string;
   minecraft_version: string;
+  loader?: ModLoader;
   fabric_loader: string;
   changelog: string;
   files: DraftFile[];
+  channel: ReleaseChannel;
+  ignore_overrides?: IgnorePatternOverrides;
+  revision?: number; // Bumped on every change; sent back as If-Match to detect concurrent edits
   created_at: string;
   updated_at: string;
 }
 
+// A release's exceptions to the global blacklist: `add` ignores more files,
+// `remove` lifts blacklist patterns so the launcher force-resets those files
+export interface IgnorePatternOverrides {
+  add?: string[];
+  remove?: string[];
+}
+
 export interface DraftFile {
   path: string;
   url?: string;
@@ -28,8 +44,11 @@ export interface CreateDraftRequest {
 export interface UpdateDraftRequest {
   version?: string;
   minecraft_version?: string;
+  loader?: ModLoader;
   fabric_loader?: string;
   changelog?: string;
+  channel?: ReleaseChannel;
+  ignore_overrides?: IgnorePatternOverrides;
 }
 
 export interface AddFilesRequest {
@@ -56,6 +75,61 @@ export interface ModInfo {
   fabric_loader?: string;
 }
 
+export interface ValidationIssue {
+  severity: 'error' | 'warning';
+  code: string;
+  message: string;
+  file?: string;
+}
+
+export interface ValidationReport {
+  issues: ValidationIssue[];
+  errors: number;
+  warnings: number;
+  file_count: number;
+  total_size: number;
+  size_budget: number | null;
+}
+
+export interface DiffMod {
+  mod_id: string;
+  name: string;
+  version: string;
+}
+
+export interface DiffFile {
+  path: string;
+  size: number;
+  sha256: string;
+  mod?: DiffMod;
+}
+
+export interface ChangedFile {
+  path: string;
+  previous_path?: string;
+  size: number;
+  previous_size: number;
+  sha256: string;
+  previous_sha256: string;
+  mod?: DiffMod;
+  previous_mod?: DiffMod;
+}
+
+export interface ReleaseDiff {
+  base_version: string;
+  draft_version: string;
+  previous_minecraft_version: string;
+  minecraft_version: string;
+  previous_fabric_loader: string;
+  fabric_loader: string;
+  added: DiffFile[];
+  removed: DiffFile[];
+  changed: ChangedFile[];
+  unchanged: number;
+  download_size: number;
+  size_delta: number;
+}
+
 export interface GeneratedChangelog {
   markdown: string;
   added: string[];
@@ -68,6 +142,7 @@ export interface GeneratedChangelog {
 export interface Release {
   version: string;
   minecraft_version: string;
+  channel: ReleaseChannel;
   created_at: string;
   file_count: number;
   size_bytes: number;
@@ -83,6 +158,7 @@ export interface CreateReleaseRequest {
   minecraft_version: string;
   fabric_loader: string;
   changelog: string;
+  channel?: ReleaseChannel;
 }
 
 export interface CopyReleaseToDraftResponse {
@@ -100,6 +176,32 @@ export interface UploadResponse {
   message: string;
 }
 
+export interface UploadSession {
+  session_id: string;
+  upload_id: string;
+  file_name: string;
+  size: number;
+  sha256?: string | null;
+  created_at: string;
+  offset: number;
+}
+
+// An upload directory on the server (GET /admin/uploads)
+export interface UploadDirInfo {
+  upload_id: string;
+  files: number;
+  bytes: number;
+  modified_at: string; // Newest change anywhere in the upload
+  in_session: boolean; // A chunked upload still writes into it
+}
+
+export interface UploadsOverview {
+  uploads: UploadDirInfo[]; // Oldest first
+  sessions: (Omit<UploadSession, 'sha256' | 'offset'> & { received: number })[];
+  total_bytes: number;
+  retention_hours: number; // 0 keeps uploads forever
+}
+
 export interface UploadProgress {
   loaded: number;
   total: number;
@@ -177,14 +279,84 @@ export interface UpdateBlacklistRequest {
   patterns: string[];
 }
 
+export interface BlacklistPreviewRequest {
+  patterns: string[];
+  /** Check a draft instead of the latest release */
+  draft_id?: string;
+}
+
+export interface BlacklistExcludedFile {
+  path: string;
+  size: number;
+  /** First pattern matching the file */
+  pattern: string;
+}
+
+export interface BlacklistPatternMatches {
+  pattern: string;
+  files: number;
+}
+
+export interface BlacklistPreview {
+  source: string;
+  total_files: number;
+  excluded_bytes: number;
+  excluded: BlacklistExcludedFile[];
+  pattern_matches: BlacklistPatternMatches[];
+}
+
+export interface BlacklistRevision {
+  revision: number;
+  patterns: string[];
+  changed_by: string;
+  changed_at: string;
+}
+
+export type SearchMatchField = 'path' | 'mod_id' | 'changelog';
+
+export interface SearchMatch {
+  field: SearchMatchField;
+  value: string;
+  file?: string; // Jar a mod id match came from
+}
+
+export interface SearchHit {
+  version: string;
+  draft_id?: string; // Set for drafts
+  matches: SearchMatch[];
+}
+
+export interface SearchResults {
+  query: string;
+  introduced_in: string | null; // Oldest release shipping a matching file or mod
+  releases: SearchHit[]; // Newest first
+  drafts: SearchHit[];
+}
+
+export type RetentionKeepReason = 'recent' | 'pinned' | 'latest';
+
+// Dry run of the release retention policy (GET /admin/retention)
+export interface RetentionPlan {
+  keep_latest: number; // 0 keeps everything
+  kept: { version: string; reason: RetentionKeepReason }[];
+  pruned: { version: string; bytes: number }[];
+  reclaimable_bytes: number; // Files no kept release shares
+}
+
 // ========== Authentication Types ==========
 
 export interface LoginRequest {
+  username?: string;
   password: string;
 }
 
+export type AdminRole = 'viewer' | 'release-manager' | 'superadmin';
+
 export interface LoginResponse {
   token: string;
+  role: AdminRole;
+  refresh_token: string;
+  expires_in: number;
 }
 
 // ========== Error Response Types ==========
@@ -237,3 +409,56 @@ export interface UploadLauncherVersionResponse {
   platform: string;
   platforms: string[];
 }
+
+// ========== Background Task Types ==========
+
+export type TaskState = 'running' | 'completed' | 'failed';
+
+export interface TaskStatus<T = unknown> {
+  id: string;
+  kind: string;
+  state: TaskState;
+  stage: string;
+  current: number;
+  total: number | null;
+  message: string | null;
+  result: T | null;
+  error: string | null;
+  started_at: number;
+  updated_at: number;
+}
+
+export interface TaskAcceptedResponse {
+  message: string;
+  task_id: string;
+  version?: string;
+}
+
+// ========== Whitelist Types ==========
+
+export interface WhitelistEntry {
+  uuid: string;
+  name: string;
+  note: string | null;
+  added_by: string;
+  added_at: number;
+}
+
+export interface AddWhitelistRequest {
+  uuid: string;
+  name: string;
+  note?: string;
+}
+
+// ========== Resource Pack Types ==========
+
+export interface ResourcePack {
+  version: string;
+  minecraft_version: string;
+  filename: string;
+  size: number;
+  sha1: string;
+  sha256: string;
+  uploaded_by: string;
+  uploaded_at: number;
+}
@@ -1,7 +1,7 @@
 // Typed API endpoints for file upload operations
 
 import api, { createFormData } from './client';
-import type { UploadResponse } from './types';
+import type { UploadResponse, UploadSession, UploadsOverview } from './types';
 
 /**
  * Upload files to the server with progress tracking
@@ -26,3 +26,70 @@ export async function uploadFiles(
 
   return response.data;
 }
+
+/** Chunk size for resumable uploads */
+const CHUNK_SIZE = 8 * 1024 * 1024;
+
+/**
+ * Upload a large file in chunks, resuming after dropped connections
+ *
+ * Pass `uploadId` to add the file to an earlier upload. Pass a `sessionId`
+ * from a previous attempt to continue where it stopped.
+ */
+export async function uploadFileResumable(
+  file: File,
+  options: {
+    uploadId?: string;
+    sessionId?: string;
+    onSession?: (session: UploadSession) => void;
+    onProgress?: (progress: number) => void;
+  } = {}
+): Promise<UploadResponse[]> {
+  let session: UploadSession;
+  if (options.sessionId) {
+    session = (await api.get<UploadSession>(`/admin/uploads/${options.sessionId}`)).data;
+  } else {
+    session = (
+      await api.post<UploadSession>('/admin/uploads', {
+        file_name: file.name,
+        size: file.size,
+        upload_id: options.uploadId,
+      })
+    ).data;
+  }
+  options.onSession?.(session);
+
+  let offset = session.offset;
+  let retries = 0;
+  while (offset < file.size) {
+    const chunk = file.slice(offset, offset + CHUNK_SIZE);
+    try {
+      const response = await api.patch<UploadSession>(`/admin/uploads/${session.session_id}`, chunk, {
+        headers: {
+          'Content-Type': 'application/octet-stream',
+          'Upload-Offset': String(offset),
+        },
+      });
+      offset = response.data.offset;
+      retries = 0;
+    } catch (error) {
+      if (++retries > 3) throw error;
+      // Ask the server how much actually arrived before retrying
+      offset = (await api.get<UploadSession>(`/admin/uploads/${session.session_id}`)).data.offset;
+    }
+    if (options.onProgress && file.size > 0) {
+      options.onProgress(Math.round((offset * 100) / file.size));
+    }
+  }
+
+  const response = await api.post<UploadResponse[]>(`/admin/uploads/${session.session_id}/finalize`);
+  return response.data;
+}
+
+/**
+ * List upload directories and unfinished chunked uploads with their sizes
+ */
+export async function listUploads(): Promise<UploadsOverview> {
+  const response = await api.get<UploadsOverview>('/admin/uploads');
+  return response.data;
+}
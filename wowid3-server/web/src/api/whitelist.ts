@@ -0,0 +1,29 @@
+// Typed API endpoints for the Minecraft server whitelist
+
+import api from './client';
+import type { WhitelistEntry, AddWhitelistRequest } from './types';
+
+const BASE_PATH = '/admin/whitelist';
+
+/**
+ * List whitelisted players
+ */
+export async function listWhitelist(): Promise<WhitelistEntry[]> {
+  const response = await api.get<WhitelistEntry[]>(BASE_PATH);
+  return response.data;
+}
+
+/**
+ * Whitelist a player (re-adding updates their name and note)
+ */
+export async function addToWhitelist(data: AddWhitelistRequest): Promise<WhitelistEntry> {
+  const response = await api.post<WhitelistEntry>(BASE_PATH, data);
+  return response.data;
+}
+
+/**
+ * Remove a player from the whitelist
+ */
+export async function removeFromWhitelist(uuid: string): Promise<void> {
+  await api.delete(`${BASE_PATH}/${uuid}`);
+}
@@ -0,0 +1,84 @@
+import { useEffect, useState } from 'react';
+import { Search } from 'lucide-react';
+import { Card } from '@/components/ui/card';
+import { Input } from '@/components/ui/input';
+import { Badge } from '@/components/ui/badge';
+import { useReleaseSearchQuery } from '../../hooks/queries';
+import type { SearchHit, SearchMatchField } from '../../api/types';
+
+const FIELD_LABELS: Record<SearchMatchField, string> = {
+  path: 'File',
+  mod_id: 'Mod',
+  changelog: 'Changelog',
+};
+
+function HitRow({ hit, label }: { hit: SearchHit; label: string }) {
+  return (
+    <div className="border-b border-border py-2 last:border-0">
+      <div className="font-medium">{label}</div>
+      <ul className="mt-1 space-y-1 text-sm">
+        {hit.matches.map((match, index) => (
+          <li key={index} className="flex items-start gap-2">
+            <Badge variant="outline">{FIELD_LABELS[match.field]}</Badge>
+            <span className="font-mono break-all">
+              {match.value}
+              {match.file && <span className="text-muted-foreground"> ({match.file})</span>}
+            </span>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
+
+/**
+ * Search file paths, mod ids and changelogs across every release and draft
+ */
+export default function ContentSearch() {
+  const [input, setInput] = useState('');
+  const [query, setQuery] = useState('');
+
+  // Each search scans every release, so wait for typing to pause
+  useEffect(() => {
+    const timer = setTimeout(() => setQuery(input), 400);
+    return () => clearTimeout(timer);
+  }, [input]);
+
+  const searchQuery = useReleaseSearchQuery(query);
+  const results = searchQuery.data;
+
+  return (
+    <Card className="p-4 mb-6">
+      <div className="flex items-center gap-2">
+        <Search className="w-4 h-4 text-muted-foreground" />
+        <Input
+          value={input}
+          onChange={(e) => setInput(e.target.value)}
+          placeholder="Which releases contain a file, mod id or changelog text? (e.g. createaddition)"
+        />
+      </div>
+
+      {searchQuery.isFetching && <p className="mt-3 text-sm text-muted-foreground">Searching...</p>}
+      {searchQuery.error && <p className="mt-3 text-sm text-destructive">{searchQuery.error.message}</p>}
+
+      {results && !searchQuery.isFetching && (
+        <div className="mt-4 space-y-3">
+          {results.introduced_in && (
+            <p className="text-sm">
+              First shipped in <span className="font-semibold">{results.introduced_in}</span>
+            </p>
+          )}
+          {results.releases.length === 0 && results.drafts.length === 0 && (
+            <p className="text-sm text-muted-foreground">No release or draft matches "{results.query}".</p>
+          )}
+          {results.releases.map((hit) => (
+            <HitRow key={hit.version} hit={hit} label={`Release ${hit.version}`} />
+          ))}
+          {results.drafts.map((hit) => (
+            <HitRow key={hit.draft_id} hit={hit} label={`Draft ${hit.version || hit.draft_id}`} />
+          ))}
+        </div>
+      )}
+    </Card>
+  );
+}
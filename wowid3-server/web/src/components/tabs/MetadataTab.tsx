@@ -1,7 +1,13 @@
 import React, { useState, useCallback, memo } from 'react';
 import { useDrafts } from '../../hooks/useDrafts';
-import { Sparkles, Check, Package } from 'lucide-react';
-import type { DraftRelease, VersionSuggestions } from '../../types/releases';
+import { Sparkles, Check, Package, AlertTriangle } from 'lucide-react';
+import type { DraftRelease, ModLoader, VersionSuggestions } from '../../types/releases';
+
+const LOADER_NAMES: Record<ModLoader, string> = {
+  fabric: 'Fabric',
+  quilt: 'Quilt',
+  neoforge: 'NeoForge',
+};
 
 interface MetadataTabProps {
   draft: DraftRelease;
@@ -16,7 +22,9 @@ function MetadataTab({ draft, onUpdate }: MetadataTabProps) {
   const [formData, setFormData] = useState({
     version: draft.version,
     minecraft_version: draft.minecraft_version,
+    loader: draft.loader ?? 'fabric',
     fabric_loader: draft.fabric_loader,
+    channel: draft.channel ?? 'stable',
   });
 
   // Performance: Memoize callbacks to prevent re-renders
@@ -82,6 +90,39 @@ function MetadataTab({ draft, onUpdate }: MetadataTabProps) {
           {/* Suggestions */}
           {suggestions && (
             <div className="space-y-3 mt-4">
+              {/* Dependency problems */}
+              {(() => {
+                const report = suggestions.dependency_report;
+                const issues = [
+                  ...report.missing_dependencies.map(
+                    (i) => `${i.mod_name} requires ${i.dependency} ${i.requirement.join(' || ')}, which is missing`
+                  ),
+                  ...report.version_mismatches.map(
+                    (i) => `${i.mod_name} requires ${i.dependency} ${i.requirement.join(' || ')}, found ${i.found}`
+                  ),
+                  ...report.conflicts.map(
+                    (i) => `${i.mod_name} is incompatible with ${i.dependency} ${i.found}`
+                  ),
+                  ...report.duplicate_mods.map(
+                    (d) => `${d.mod_id} is included more than once: ${d.files.join(', ')}`
+                  ),
+                ];
+                if (issues.length === 0) return null;
+                return (
+                  <div className="bg-destructive/10 rounded-lg border border-destructive/30 p-4">
+                    <p className="font-medium mb-2 flex items-center gap-2 text-destructive">
+                      <AlertTriangle className="w-4 h-4" />
+                      Dependency Problems ({issues.length})
+                    </p>
+                    <ul className="text-sm space-y-1">
+                      {issues.map((issue) => (
+                        <li key={issue} className="text-foreground">• {issue}</li>
+                      ))}
+                    </ul>
+                  </div>
+                );
+              })()}
+
               {/* Detected mods */}
               {suggestions.detected_mods.length > 0 && (
                 <div className="bg-card rounded-lg border border-border p-4">
@@ -187,17 +228,33 @@ function MetadataTab({ draft, onUpdate }: MetadataTabProps) {
             </div>
           </div>
 
-          {/* Fabric Loader */}
+          {/* Mod loader */}
+          <div>
+            <label className="block text-sm font-medium mb-2">
+              Mod Loader
+            </label>
+            <select
+              value={formData.loader}
+              onChange={(e) => handleChange('loader', e.target.value)}
+              className="w-full px-4 py-2 border border-input bg-background rounded-lg focus:ring-2 focus:ring-ring focus:border-input transition-all"
+            >
+              <option value="fabric">Fabric</option>
+              <option value="quilt">Quilt</option>
+              <option value="neoforge">NeoForge</option>
+            </select>
+          </div>
+
+          {/* Loader version */}
           <div>
             <label className="block text-sm font-medium mb-2">
-              Fabric Loader Version *
+              {LOADER_NAMES[formData.loader]} Loader Version *
             </label>
             <div className="flex gap-2">
               <input
                 type="text"
                 value={formData.fabric_loader}
                 onChange={(e) => handleChange('fabric_loader', e.target.value)}
-                placeholder="e.g., 0.14.21"
+                placeholder={formData.loader === 'neoforge' ? 'e.g., 21.1.77' : 'e.g., 0.14.21'}
                 className="flex-1 px-4 py-2 border border-input bg-background rounded-lg focus:ring-2 focus:ring-ring focus:border-input transition-all"
               />
               {suggestions?.fabric_loader && formData.fabric_loader !== suggestions.fabric_loader && (
@@ -212,6 +269,24 @@ function MetadataTab({ draft, onUpdate }: MetadataTabProps) {
             </div>
           </div>
 
+          {/* Release channel */}
+          <div>
+            <label className="block text-sm font-medium mb-2">
+              Release Channel
+            </label>
+            <select
+              value={formData.channel}
+              onChange={(e) => handleChange('channel', e.target.value)}
+              className="w-full px-4 py-2 border border-input bg-background rounded-lg focus:ring-2 focus:ring-ring focus:border-input transition-all"
+            >
+              <option value="stable">Stable - all players</option>
+              <option value="beta">Beta - opt-in testers only</option>
+            </select>
+            <p className="text-xs text-muted-foreground mt-1">
+              Beta releases can be promoted to stable from the releases list once tested.
+            </p>
+          </div>
+
           {/* Info note */}
           <div className="bg-muted/30 border border-border rounded-lg p-4 text-sm">
             <p className="font-medium mb-1">💡 Tip</p>
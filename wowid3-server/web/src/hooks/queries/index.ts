@@ -4,6 +4,7 @@
 export * from './useDraftsQuery';
 export * from './useReleasesQuery';
 export * from './useFileBrowserQuery';
+export * from './useUploadsQuery';
 
 // Mutation hooks
 export * from './useAuthMutations';
@@ -11,8 +11,10 @@ import {
   deleteRelease,
   copyReleaseToDraft,
   updateBlacklist,
+  previewBlacklist,
 } from '../../api/releases';
 import type {
+  BlacklistPreview,
   Release,
   CreateReleaseRequest,
   CopyReleaseToDraftResponse,
@@ -127,7 +129,20 @@ export function useUpdateBlacklistMutation(): UseMutationResult<
       }
     },
     onSettled: () => {
-      queryClient.invalidateQueries({ queryKey: queryKeys.blacklist.list() });
+      queryClient.invalidateQueries({ queryKey: queryKeys.blacklist.all });
     },
   });
 }
+
+/**
+ * Mutation hook to preview proposed blacklist patterns against the latest release or a draft
+ */
+export function usePreviewBlacklistMutation(): UseMutationResult<
+  BlacklistPreview,
+  Error,
+  { patterns: string[]; draftId?: string }
+> {
+  return useMutation({
+    mutationFn: ({ patterns, draftId }) => previewBlacklist(patterns, draftId),
+  });
+}
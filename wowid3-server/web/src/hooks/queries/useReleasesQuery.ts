@@ -2,8 +2,8 @@
 
 import { useQuery, UseQueryResult } from '@tanstack/react-query';
 import { queryKeys } from '../../api/queryKeys';
-import { listReleases, getBlacklist } from '../../api/releases';
-import type { Release } from '../../api/types';
+import { listReleases, getBlacklist, getBlacklistHistory, previewRetention, searchReleases } from '../../api/releases';
+import type { BlacklistRevision, Release, RetentionPlan, SearchResults } from '../../api/types';
 
 /**
  * Query hook to list all releases
@@ -26,3 +26,35 @@ export function useBlacklistQuery(): UseQueryResult<string[], Error> {
     queryFn: getBlacklist,
   });
 }
+
+/**
+ * Query hook to get saved blacklist revisions
+ */
+export function useBlacklistHistoryQuery(): UseQueryResult<BlacklistRevision[], Error> {
+  return useQuery({
+    queryKey: queryKeys.blacklist.history(),
+    queryFn: getBlacklistHistory,
+  });
+}
+
+/**
+ * Query hook to search release and draft contents; idle until the query has 2+ characters
+ */
+export function useReleaseSearchQuery(query: string): UseQueryResult<SearchResults, Error> {
+  const trimmed = query.trim();
+  return useQuery({
+    queryKey: queryKeys.releases.search(trimmed),
+    queryFn: () => searchReleases(trimmed),
+    enabled: trimmed.length >= 2,
+  });
+}
+
+/**
+ * Query hook for a retention dry run; `keep` overrides the server's configured count
+ */
+export function useRetentionPreviewQuery(keep?: number): UseQueryResult<RetentionPlan, Error> {
+  return useQuery({
+    queryKey: queryKeys.releases.retention(keep),
+    queryFn: () => previewRetention(keep),
+  });
+}
@@ -0,0 +1,16 @@
+// React Query hook for server-side upload storage
+
+import { useQuery, UseQueryResult } from '@tanstack/react-query';
+import { queryKeys } from '../../api/queryKeys';
+import { listUploads } from '../../api/uploads';
+import type { UploadsOverview } from '../../api/types';
+
+/**
+ * Query hook for upload directories and unfinished chunked uploads
+ */
+export function useUploadsQuery(): UseQueryResult<UploadsOverview, Error> {
+  return useQuery({
+    queryKey: queryKeys.uploads.list(),
+    queryFn: listUploads,
+  });
+}
@@ -1,9 +1,12 @@
 import { useState } from 'react';
 import api from '../api/client';
+import { waitForTask } from '../api/tasks';
+import type { TaskAcceptedResponse } from '../api/types';
 
 export interface Release {
   version: string;
   minecraft_version: string;
+  channel: 'stable' | 'beta';
   created_at: string;
   file_count: number;
   size_bytes: number;
@@ -71,14 +74,14 @@ export const useAdmin = () => {
     setLoading(true);
     setError(null);
     try {
-      const response = await api.post('/admin/releases', {
+      const response = await api.post<TaskAcceptedResponse>('/admin/releases', {
         upload_id: uploadId,
         version,
         minecraft_version: minecraftVersion,
         fabric_loader: fabricLoader,
         changelog,
       });
-      return response.data;
+      return await waitForTask(response.data);
     } catch (err: any) {
       const message = err.response?.data?.error || 'Release creation failed';
       setError(message);
@@ -118,6 +121,21 @@ export const useAdmin = () => {
     }
   };
 
+  const promoteRelease = async (version: string) => {
+    setLoading(true);
+    setError(null);
+    try {
+      const response = await api.post(`/admin/releases/${version}/promote`);
+      return response.data;
+    } catch (err: any) {
+      const message = err.response?.data?.error || 'Failed to promote release';
+      setError(message);
+      throw err;
+    } finally {
+      setLoading(false);
+    }
+  };
+
   const getBlacklist = async (): Promise<string[]> => {
     setLoading(true);
     setError(null);
@@ -222,6 +240,7 @@ export const useAdmin = () => {
     createRelease,
     listReleases,
     deleteRelease,
+    promoteRelease,
     copyReleaseToDraft,
     getBlacklist,
     updateBlacklist,
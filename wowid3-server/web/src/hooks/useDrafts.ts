@@ -9,6 +9,8 @@ import type {
   VersionSuggestions,
   GeneratedChangelog,
 } from '../types/releases';
+import { waitForTask } from '../api/tasks';
+import type { TaskAcceptedResponse } from '../api/types';
 
 const API_BASE = '/api/admin';
 
@@ -97,16 +99,18 @@ export function useDrafts() {
     }
   }, []);
 
-  const updateDraft = useCallback(async (id: string, request: UpdateDraftRequest) => {
+  const updateDraft = useCallback(async (id: string, request: UpdateDraftRequest, revision?: number) => {
     setError(null);
     try {
+      // The server answers 409 if someone else saved the draft after `revision`
+      const ifMatch = revision === undefined ? {} : { 'If-Match': String(revision) };
       const response = await axios.put<DraftRelease>(`${API_BASE}/drafts/${id}`, request, {
-        headers: getAuthHeaders(),
+        headers: { ...getAuthHeaders(), ...ifMatch },
       });
       setCurrentDraft(response.data);
       return response.data;
     } catch (err: any) {
-      setError(err.response?.data?.message || 'Failed to update draft');
+      setError(err.response?.data?.error || err.response?.data?.message || 'Failed to update draft');
       return null;
     }
   }, []);
@@ -135,14 +139,14 @@ export function useDrafts() {
     setLoading(true);
     setError(null);
     try {
-      const response = await axios.post<VersionSuggestions>(
+      const response = await axios.post<TaskAcceptedResponse>(
         `${API_BASE}/drafts/${id}/analyze`,
         {},
         {
           headers: getAuthHeaders(),
         }
       );
-      return response.data;
+      return await waitForTask<VersionSuggestions>(response.data);
     } catch (err: any) {
       setError(err.response?.data?.message || 'Failed to analyze draft');
       return null;
@@ -229,13 +233,14 @@ export function useDrafts() {
     setLoading(true);
     setError(null);
     try {
-      await axios.post(
+      const response = await axios.post<TaskAcceptedResponse>(
         `${API_BASE}/drafts/${id}/publish`,
         {},
         {
           headers: getAuthHeaders(),
         }
       );
+      await waitForTask(response.data);
       setDrafts((prev) => prev.filter((d) => d.id !== id));
       setCurrentDraft(null);
       return true;
@@ -16,7 +16,7 @@ import ReviewTab from '../components/tabs/ReviewTab';
 export default function ReleaseEditor() {
   const { id } = useParams<{ id: string }>();
   const navigate = useNavigate();
-  const { getDraft, updateDraft, loading } = useDrafts();
+  const { getDraft, updateDraft, loading, error: saveError } = useDrafts();
   const {
     currentDraft,
     editorState,
@@ -50,12 +50,23 @@ export default function ReleaseEditor() {
 
     setCurrentDraft(updatedDraft);
     scheduleAutoSave(async () => {
-      await updateDraft(id, {
-        version: updatedDraft.version,
-        minecraft_version: updatedDraft.minecraft_version,
-        fabric_loader: updatedDraft.fabric_loader,
-        changelog: updatedDraft.changelog,
-      });
+      const saved = await updateDraft(
+        id,
+        {
+          version: updatedDraft.version,
+          minecraft_version: updatedDraft.minecraft_version,
+          loader: updatedDraft.loader,
+          fabric_loader: updatedDraft.fabric_loader,
+          changelog: updatedDraft.changelog,
+          channel: updatedDraft.channel,
+        },
+        useReleaseStore.getState().currentDraft?.revision
+      );
+      // Keep edits typed during the save, but base the next save on the new revision
+      const latest = useReleaseStore.getState().currentDraft;
+      if (saved && latest) {
+        setCurrentDraft({ ...latest, revision: saved.revision });
+      }
     });
   };
 
@@ -143,6 +154,13 @@ export default function ReleaseEditor() {
                 </div>
               ) : null}
 
+              {saveError && (
+                <div className="flex items-center gap-2 px-3 py-1.5 bg-destructive/20 text-destructive rounded-lg">
+                  <AlertCircle className="w-4 h-4" />
+                  <span className="text-sm font-medium">{saveError}</span>
+                </div>
+              )}
+
               {hasErrors && (
                 <div className="flex items-center gap-2 px-3 py-1.5 bg-destructive/20 text-destructive rounded-lg">
                   <AlertCircle className="w-4 h-4" />
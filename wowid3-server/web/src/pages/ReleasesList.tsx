@@ -4,7 +4,7 @@ import { useVirtualizer } from '@tanstack/react-virtual';
 import { motion, AnimatePresence } from 'framer-motion';
 import { useDrafts } from '../hooks/useDrafts';
 import { useAdmin, Release } from '../hooks/useAdmin';
-import { Plus, Edit, Trash2, Package, Clock, FileText, Sparkles, Search, CheckCircle2 } from 'lucide-react';
+import { Plus, Edit, Trash2, Package, Clock, FileText, Sparkles, Search, CheckCircle2, ArrowUpCircle } from 'lucide-react';
 import { formatDistanceToNow } from 'date-fns';
 import { useDebounce } from '../hooks/useDebounce';
 import { PageTransition, containerVariants, statsCardVariants } from '@/components/PageTransition';
@@ -115,11 +115,13 @@ DraftCard.displayName = 'DraftCard';
 const ReleaseCard = memo(({
   release,
   index,
-  onDelete
+  onDelete,
+  onPromote
 }: {
   release: Release;
   index: number;
   onDelete: (version: string) => void;
+  onPromote: (version: string) => void;
 }) => {
   return (
     <motion.div
@@ -141,6 +143,9 @@ const ReleaseCard = memo(({
               <Badge variant="success" className="shadow-sm bg-green-500/10 text-green-500 border-green-500/20">
                 PUBLISHED
               </Badge>
+              {release.channel === 'beta' && (
+                <Badge variant="warning" className="shadow-sm">BETA</Badge>
+              )}
             </div>
 
             {/* Metadata */}
@@ -166,6 +171,16 @@ const ReleaseCard = memo(({
 
           {/* Action Buttons */}
           <div className="flex gap-2">
+            {release.channel === 'beta' && (
+              <Button
+                onClick={() => onPromote(release.version)}
+                variant="ghost"
+                size="icon"
+                title="Promote to stable"
+              >
+                <ArrowUpCircle className="w-5 h-5" />
+              </Button>
+            )}
             <Button
               onClick={() => onDelete(release.version)}
               variant="ghost"
@@ -187,7 +202,7 @@ ReleaseCard.displayName = 'ReleaseCard';
 function ReleasesList() {
   const navigate = useNavigate();
   const { drafts, listDrafts, createDraft, deleteDraft, loading: draftsLoading } = useDrafts();
-  const { listReleases, deleteRelease, loading: releasesLoading } = useAdmin();
+  const { listReleases, deleteRelease, promoteRelease, loading: releasesLoading } = useAdmin();
   const [releases, setReleases] = useState<Release[]>([]);
   const [filter, setFilter] = useState<'all' | 'drafts' | 'published'>('all');
   const [isCreating, setIsCreating] = useState(false);
@@ -237,6 +252,14 @@ function ReleasesList() {
     }
   }, [deleteRelease, listReleases]);
 
+  const handlePromoteRelease = useCallback(async (version: string) => {
+    if (confirm(`Promote beta release ${version} to stable?\n\nAll players will receive it on their next update check.`)) {
+      await promoteRelease(version);
+      const newReleases = await listReleases();
+      setReleases(newReleases || []);
+    }
+  }, [promoteRelease, listReleases]);
+
   // Performance: Combine and filter drafts and releases with useMemo
   type ListItem = { type: 'draft'; data: any } | { type: 'release'; data: Release };
 
@@ -437,6 +460,7 @@ function ReleasesList() {
                         release={item.data}
                         index={virtualItem.index}
                         onDelete={handleDeleteRelease}
+                        onPromote={handlePromoteRelease}
                       />
                     )}
                   </div>
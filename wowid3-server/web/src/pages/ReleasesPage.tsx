@@ -1,5 +1,6 @@
 import { Suspense } from 'react';
 import ReleasesList from '@/components/releases/ReleaseList';
+import ContentSearch from '@/components/releases/ContentSearch';
 import { LoadingSpinner } from '@/components/ui/loading-spinner';
 import { PageTransition } from '@/components/PageTransition';
 
@@ -13,6 +14,7 @@ export default function ReleasesPage() {
   return (
     <PageTransition>
       <div className="p-6">
+        <ContentSearch />
         <Suspense fallback={<LoadingFallback />}>
           <ReleasesList />
         </Suspense>
@@ -1,16 +1,35 @@
 import { useState, useEffect } from 'react';
 import { Card } from '@/components/ui/card';
 import { Button } from '@/components/ui/button';
-import { useBlacklistQuery, useUpdateBlacklistMutation } from '@/hooks/queries';
-import { Plus, Trash2, CheckCircle, AlertCircle } from 'lucide-react';
+import {
+  useBlacklistQuery,
+  useBlacklistHistoryQuery,
+  useDraftsQuery,
+  usePreviewBlacklistMutation,
+  useRetentionPreviewQuery,
+  useUpdateBlacklistMutation,
+  useUploadsQuery,
+} from '@/hooks/queries';
+import { Plus, Trash2, CheckCircle, AlertCircle, Eye, RotateCcw } from 'lucide-react';
 import { PageTransition } from '@/components/PageTransition';
 
+function formatBytes(bytes: number): string {
+  return `${(bytes / 1024 / 1024).toFixed(2)} MB`;
+}
+
 export default function SettingsPage() {
   const blacklistQuery = useBlacklistQuery();
+  const historyQuery = useBlacklistHistoryQuery();
+  const draftsQuery = useDraftsQuery();
   const updateBlacklistMutation = useUpdateBlacklistMutation();
+  const previewMutation = usePreviewBlacklistMutation();
+  const [retentionKeep, setRetentionKeep] = useState('');
+  const retentionQuery = useRetentionPreviewQuery(retentionKeep === '' ? undefined : Number(retentionKeep));
+  const uploadsQuery = useUploadsQuery();
 
   const [blacklistPatterns, setBlacklistPatterns] = useState<string[]>([]);
   const [newPattern, setNewPattern] = useState('');
+  const [previewDraftId, setPreviewDraftId] = useState('');
   const [message, setMessage] = useState<{ type: 'success' | 'error'; text: string } | null>(null);
 
   // Sync blacklist patterns with query data
@@ -33,6 +52,17 @@ export default function SettingsPage() {
     setMessage(null);
   };
 
+  const handlePreview = () => {
+    previewMutation.mutate(
+      { patterns: blacklistPatterns, draftId: previewDraftId || undefined },
+      {
+        onError: (error: any) => {
+          setMessage({ type: 'error', text: error.message || 'Failed to preview blacklist' });
+        },
+      }
+    );
+  };
+
   const handleSave = () => {
     updateBlacklistMutation.mutate(blacklistPatterns, {
       onSuccess: () => {
@@ -134,6 +164,15 @@ export default function SettingsPage() {
           >
             {updateBlacklistMutation.isPending ? 'Saving...' : 'Save Changes'}
           </Button>
+          <Button
+            onClick={handlePreview}
+            disabled={previewMutation.isPending}
+            variant="outline"
+            className="flex gap-2"
+          >
+            <Eye className="w-4 h-4" />
+            {previewMutation.isPending ? 'Checking...' : 'Preview'}
+          </Button>
           <Button
             onClick={() => {
               if (blacklistQuery.data) {
@@ -146,6 +185,167 @@ export default function SettingsPage() {
             Reset
           </Button>
         </div>
+
+        {/* Preview */}
+        <div className="mt-6 space-y-3">
+          <label className="block text-sm font-medium">Preview Against</label>
+          <select
+            value={previewDraftId}
+            onChange={(e) => setPreviewDraftId(e.target.value)}
+            className="w-full px-3 py-2 border rounded-lg bg-background focus:outline-none focus:ring-2 focus:ring-primary"
+          >
+            <option value="">Latest release</option>
+            {(draftsQuery.data || []).map((draft) => (
+              <option key={draft.id} value={draft.id}>
+                Draft {draft.version || draft.id}
+              </option>
+            ))}
+          </select>
+
+          {previewMutation.data && (
+            <div className="p-4 bg-accent rounded-lg space-y-3">
+              <p className="text-sm">
+                <span className="font-semibold">{previewMutation.data.excluded.length}</span> of{' '}
+                {previewMutation.data.total_files} files in {previewMutation.data.source} would be excluded (
+                {formatBytes(previewMutation.data.excluded_bytes)})
+              </p>
+              <div className="space-y-1">
+                {previewMutation.data.pattern_matches.map((match) => (
+                  <div key={match.pattern} className="flex justify-between text-xs">
+                    <code className="font-mono">{match.pattern}</code>
+                    <span className={match.files === 0 ? 'text-muted-foreground' : ''}>{match.files} files</span>
+                  </div>
+                ))}
+              </div>
+              {previewMutation.data.excluded.length > 0 && (
+                <div className="max-h-64 overflow-y-auto space-y-1 border-t pt-2">
+                  {previewMutation.data.excluded.map((file) => (
+                    <div key={file.path} className="flex justify-between gap-3 text-xs">
+                      <code className="font-mono truncate">{file.path}</code>
+                      <span className="text-muted-foreground flex-shrink-0">{file.pattern}</span>
+                    </div>
+                  ))}
+                </div>
+              )}
+            </div>
+          )}
+        </div>
+      </Card>
+
+      {/* History */}
+      <Card className="p-6">
+        <h3 className="font-semibold mb-3">History</h3>
+        {!historyQuery.data || historyQuery.data.length === 0 ? (
+          <p className="text-muted-foreground text-sm italic">No saved revisions yet</p>
+        ) : (
+          <div className="space-y-2">
+            {historyQuery.data.map((revision) => (
+              <div key={revision.revision} className="flex items-center justify-between p-3 bg-accent rounded-lg">
+                <div className="text-sm">
+                  <p className="font-medium">
+                    Revision {revision.revision} &middot; {revision.patterns.length} patterns
+                  </p>
+                  <p className="text-xs text-muted-foreground">
+                    {revision.changed_by}, {new Date(revision.changed_at).toLocaleString()}
+                  </p>
+                </div>
+                <Button
+                  onClick={() => {
+                    setBlacklistPatterns(revision.patterns);
+                    setMessage(null);
+                  }}
+                  variant="ghost"
+                  size="sm"
+                  className="flex gap-2"
+                >
+                  <RotateCcw className="w-4 h-4" />
+                  Load
+                </Button>
+              </div>
+            ))}
+          </div>
+        )}
+      </Card>
+
+      {/* Release Retention */}
+      <Card className="p-6">
+        <h3 className="font-semibold mb-1">Release Retention</h3>
+        <p className="text-sm text-muted-foreground mb-4">
+          Releases outside the retention policy are pruned automatically. Pinned releases (tag{' '}
+          <code className="bg-accent px-1 rounded">pinned</code>) and the latest stable and beta releases are always kept.
+        </p>
+        <div className="flex items-center gap-2 mb-4">
+          <label className="text-sm font-medium">Keep newest</label>
+          <input
+            type="number"
+            min={0}
+            value={retentionKeep}
+            onChange={(e) => setRetentionKeep(e.target.value)}
+            placeholder={retentionQuery.data ? String(retentionQuery.data.keep_latest) : ''}
+            className="w-24 px-3 py-1 border rounded-lg bg-background focus:outline-none focus:ring-2 focus:ring-primary"
+          />
+          <span className="text-sm text-muted-foreground">releases (dry run; leave empty for the server setting)</span>
+        </div>
+        {retentionQuery.error && <p className="text-sm text-destructive">{retentionQuery.error.message}</p>}
+        {retentionQuery.data && (
+          retentionQuery.data.keep_latest === 0 ? (
+            <p className="text-sm text-muted-foreground italic">Retention is off; every release is kept</p>
+          ) : (
+            <div className="p-4 bg-accent rounded-lg space-y-2">
+              <p className="text-sm">
+                <span className="font-semibold">{retentionQuery.data.pruned.length}</span> releases would be pruned,
+                reclaiming {formatBytes(retentionQuery.data.reclaimable_bytes)}
+              </p>
+              {retentionQuery.data.pruned.map((release) => (
+                <div key={release.version} className="flex justify-between text-xs">
+                  <code className="font-mono">{release.version}</code>
+                  <span className="text-muted-foreground">{formatBytes(release.bytes)}</span>
+                </div>
+              ))}
+              <p className="text-xs text-muted-foreground border-t pt-2">
+                Kept: {retentionQuery.data.kept.map((release) => `${release.version} (${release.reason})`).join(', ')}
+              </p>
+            </div>
+          )
+        )}
+      </Card>
+
+      {/* Uploads */}
+      <Card className="p-6">
+        <h3 className="font-semibold mb-1">Uploads</h3>
+        <p className="text-sm text-muted-foreground mb-4">
+          {uploadsQuery.data && uploadsQuery.data.retention_hours > 0
+            ? `Uploads untouched for ${uploadsQuery.data.retention_hours} hours are deleted automatically.`
+            : 'Uploads are kept until a release is created from them.'}
+        </p>
+        {uploadsQuery.error && <p className="text-sm text-destructive">{uploadsQuery.error.message}</p>}
+        {uploadsQuery.data && (
+          <div className="p-4 bg-accent rounded-lg space-y-2">
+            <p className="text-sm">
+              <span className="font-semibold">{uploadsQuery.data.uploads.length}</span> uploads and{' '}
+              <span className="font-semibold">{uploadsQuery.data.sessions.length}</span> unfinished chunked uploads,{' '}
+              {formatBytes(uploadsQuery.data.total_bytes)} in total
+            </p>
+            {uploadsQuery.data.uploads.map((upload) => (
+              <div key={upload.upload_id} className="flex justify-between text-xs">
+                <code className="font-mono">{upload.upload_id}</code>
+                <span className="text-muted-foreground">
+                  {upload.files} files, {formatBytes(upload.bytes)}, {new Date(upload.modified_at).toLocaleString()}
+                  {upload.in_session && ' (in progress)'}
+                </span>
+              </div>
+            ))}
+            {uploadsQuery.data.sessions.map((session) => (
+              <div key={session.session_id} className="flex justify-between text-xs">
+                <code className="font-mono">{session.file_name}</code>
+                <span className="text-muted-foreground">
+                  {formatBytes(session.received)} of {formatBytes(session.size)}, started{' '}
+                  {new Date(session.created_at).toLocaleString()}
+                </span>
+              </div>
+            ))}
+          </div>
+        )}
       </Card>
 
       {/* Help Section */}
@@ -19,6 +19,7 @@ export const useAuthStore = create<AuthState>((set) => ({
 
   clearToken: () => {
     localStorage.removeItem('auth_token');
+    localStorage.removeItem('refresh_token');
     set({ token: null, isAuthenticated: false });
   },
 
@@ -4,6 +4,7 @@ export interface Release {
   version: string;
   minecraft_version: string;
   fabric_loader?: string;
+  channel?: 'stable' | 'beta';
   created_at: string;
   file_count: number;
   size_bytes: number;
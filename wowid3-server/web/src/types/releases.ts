@@ -1,16 +1,32 @@
 // Type definitions for release wizard
 
+// Loader the pack runs on; `fabric_loader` holds its version whichever loader it is
+export type ModLoader = 'fabric' | 'quilt' | 'neoforge';
+
 export interface DraftRelease {
   id: string;
   version: string;
   minecraft_version: string;
+  loader?: ModLoader;
   fabric_loader: string;
   changelog: string;
   files: DraftFile[];
+  channel: 'stable' | 'beta';
+  optional_groups?: OptionalGroup[];
+  revision?: number; // Bumped on every change; sent back as If-Match to detect concurrent edits
   created_at: string;
   updated_at: string;
 }
 
+// Mods players can switch on or off in the launcher
+export interface OptionalGroup {
+  id: string;
+  name: string;
+  description?: string;
+  default_enabled?: boolean;
+  files: string[];
+}
+
 export interface DraftFile {
   path: string;
   url?: string;
@@ -23,6 +39,7 @@ export interface VersionSuggestions {
   fabric_loader?: string;
   suggested_version?: string;
   detected_mods: ModInfo[];
+  dependency_report: DependencyReport;
 }
 
 export interface ModInfo {
@@ -31,6 +48,31 @@ export interface ModInfo {
   version: string;
   minecraft_version?: string;
   fabric_loader?: string;
+  file?: string;
+  depends?: Record<string, string[]>;
+  breaks?: Record<string, string[]>;
+  provides?: Record<string, string>;
+}
+
+export interface DependencyReport {
+  missing_dependencies: DependencyIssue[];
+  version_mismatches: DependencyIssue[];
+  conflicts: DependencyIssue[];
+  duplicate_mods: DuplicateMod[];
+}
+
+export interface DependencyIssue {
+  mod_id: string;
+  mod_name: string;
+  file?: string;
+  dependency: string;
+  requirement: string[];
+  found?: string;
+}
+
+export interface DuplicateMod {
+  mod_id: string;
+  files: string[];
 }
 
 export interface GeneratedChangelog {
@@ -48,8 +90,11 @@ export interface CreateDraftRequest {
 export interface UpdateDraftRequest {
   version?: string;
   minecraft_version?: string;
+  loader?: ModLoader;
   fabric_loader?: string;
   changelog?: string;
+  channel?: 'stable' | 'beta';
+  optional_groups?: OptionalGroup[];
 }
 
 export interface AddFilesRequest {